@@ -5,7 +5,7 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion, BenchmarkId};
 use ecs::backends::{EcsBackendType, DynamicWorld};
 use ecs::traits::{EcsWorld, ComponentAccess};
-use ecs::{Transform, Sprite, Collider, Rigidbody2D};
+use ecs::{Transform, CustomWorld, EntityOrdered};
 
 // Benchmark configuration
 const ENTITY_COUNTS: &[usize] = &[100, 1000, 10000];
@@ -256,6 +256,42 @@ fn bench_memory_usage(c: &mut Criterion) {
     group.finish();
 }
 
+/// Cost of the entity-id ordered views over plain HashMap iteration, and of lookups
+fn bench_ordered_iteration(c: &mut Criterion) {
+    let mut group = c.benchmark_group("ordered_iteration");
+
+    for &entity_count in ENTITY_COUNTS {
+        let mut world = CustomWorld::new();
+        for _ in 0..entity_count {
+            let entity = world.spawn();
+            world.transforms.insert(entity, Transform::default());
+        }
+
+        group.bench_with_input(BenchmarkId::new("hashmap_iter", entity_count), &world, |b, world| {
+            b.iter(|| {
+                for (entity, transform) in world.transforms.iter() {
+                    black_box((entity, transform));
+                }
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("iter_sorted", entity_count), &world, |b, world| {
+            b.iter(|| {
+                for (entity, transform) in world.transforms.iter_sorted() {
+                    black_box((entity, transform));
+                }
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("lookup", entity_count), &world, |b, world| {
+            b.iter(|| {
+                for entity in 0..entity_count as u32 {
+                    black_box(world.transforms.get(&entity));
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_entity_spawn,
@@ -264,7 +300,8 @@ criterion_group!(
     bench_world_clear,
     bench_hierarchy_operations,
     bench_mixed_operations,
-    bench_memory_usage
+    bench_memory_usage,
+    bench_ordered_iteration
 );
 
 criterion_main!(benches);
\ No newline at end of file
@@ -38,7 +38,7 @@ fn main() {
         sprite.texture_id = "player_sprite".to_string();
         sprite.width = 40.0;
         sprite.height = 40.0;
-        sprite.color = [0.2, 0.6, 1.0, 1.0]; // สีฟ้า
+        sprite.color = ecs::Color::rgba(0.2, 0.6, 1.0, 1.0); // สีฟ้า
         println!("   Texture: {}", sprite.texture_id);
         println!("   Size: {}x{}", sprite.width, sprite.height);
         println!("   Color: {:?}\n", sprite.color);
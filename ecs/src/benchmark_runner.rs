@@ -3,7 +3,7 @@
 //! Provides runtime benchmarking and comparison tools for different ECS backends.
 
 use std::time::{Duration, Instant};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use serde::{Serialize, Deserialize};
 
 use crate::backends::{EcsBackendType, DynamicWorld};
@@ -17,6 +17,9 @@ pub struct BenchmarkResult {
     pub duration: Duration,
     pub operations_per_second: f64,
     pub memory_usage_estimate: Option<usize>,
+    /// Time spent per system (physics, scripts, ...) for scenario benchmarks
+    #[serde(default)]
+    pub system_breakdown: BTreeMap<String, Duration>,
 }
 
 /// Collection of benchmark results
@@ -91,6 +94,9 @@ impl BenchmarkSuite {
                     result.operations_per_second,
                     result.duration.as_secs_f64() * 1000.0
                 ));
+                for (system, duration) in &result.system_breakdown {
+                    report.push_str(&format!("     {}: {:.2}ms\n", system, duration.as_secs_f64() * 1000.0));
+                }
             }
             report.push_str("\n");
         }
@@ -105,6 +111,31 @@ impl BenchmarkSuite {
         Ok(())
     }
     
+    /// Change in total duration per test and backend against a baseline run
+    /// (e.g. the previous CI run's JSON); positive percentages are slower
+    pub fn compare(&self, baseline: &BenchmarkSuite) -> String {
+        let mut report = String::new();
+        for result in &self.results {
+            let previous = baseline.results.iter()
+                .find(|b| b.test_name == result.test_name && b.backend == result.backend);
+            match previous {
+                Some(previous) if previous.duration > Duration::ZERO => {
+                    let change = (result.duration.as_secs_f64() / previous.duration.as_secs_f64() - 1.0) * 100.0;
+                    report.push_str(&format!(
+                        "{} ({:?}): {:.2}ms -> {:.2}ms ({:+.1}%)\n",
+                        result.test_name,
+                        result.backend,
+                        previous.duration.as_secs_f64() * 1000.0,
+                        result.duration.as_secs_f64() * 1000.0,
+                        change
+                    ));
+                }
+                _ => report.push_str(&format!("{} ({:?}): new\n", result.test_name, result.backend)),
+            }
+        }
+        report
+    }
+
     /// Load results from JSON file
     pub fn load_from_file(path: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let json = std::fs::read_to_string(path)?;
@@ -203,6 +234,7 @@ impl BenchmarkRunner {
             duration,
             operations_per_second: ops_per_second,
             memory_usage_estimate: None,
+            system_breakdown: BTreeMap::new(),
         })
     }
     
@@ -239,6 +271,7 @@ impl BenchmarkRunner {
             duration,
             operations_per_second: ops_per_second,
             memory_usage_estimate: None,
+            system_breakdown: BTreeMap::new(),
         })
     }
     
@@ -272,6 +305,7 @@ impl BenchmarkRunner {
             duration,
             operations_per_second: ops_per_second,
             memory_usage_estimate: None,
+            system_breakdown: BTreeMap::new(),
         })
     }
     
@@ -311,6 +345,7 @@ impl BenchmarkRunner {
             duration,
             operations_per_second: ops_per_second,
             memory_usage_estimate: None,
+            system_breakdown: BTreeMap::new(),
         })
     }
     
@@ -338,6 +373,7 @@ impl BenchmarkRunner {
             duration,
             operations_per_second: ops_per_second,
             memory_usage_estimate: None,
+            system_breakdown: BTreeMap::new(),
         })
     }
     
@@ -400,6 +436,7 @@ mod tests {
             duration: Duration::from_millis(100),
             operations_per_second: 1000.0,
             memory_usage_estimate: None,
+            system_breakdown: BTreeMap::new(),
         };
         
         suite.add_result(result);
@@ -409,4 +446,23 @@ mod tests {
         assert!(report.contains("ECS Backend Benchmark Report"));
         assert!(report.contains("Custom"));
     }
+
+    #[test]
+    fn test_benchmark_suite_compare() {
+        let result = |millis| BenchmarkResult {
+            backend: EcsBackendType::Custom,
+            test_name: "platformer_1k".to_string(),
+            duration: Duration::from_millis(millis),
+            operations_per_second: 1.0,
+            memory_usage_estimate: None,
+            system_breakdown: BTreeMap::new(),
+        };
+        let mut baseline = BenchmarkSuite::new();
+        baseline.add_result(result(100));
+        let mut current = BenchmarkSuite::new();
+        current.add_result(result(110));
+
+        assert!(current.compare(&baseline).contains("+10.0%"));
+        assert!(current.compare(&BenchmarkSuite::new()).contains("new"));
+    }
 }
\ No newline at end of file
@@ -6,7 +6,7 @@
 /// - GetComponent<T>() - ดึงข้อมูล Component
 /// - HasComponent<T>() - ตรวจสอบว่ามี Component หรือไม่
 
-use crate::{Entity, Transform, Sprite, Collider, Collider3D, Mesh, Camera, Script, ScriptLifecycleState, EntityTag, LdtkMap, TilemapCollider, LdtkIntGridCollider, Model3D};
+use crate::{Entity, Transform, Sprite, Collider, Collider3D, PolygonCollider, Mesh, Camera, Script, ScriptLifecycleState, EntityTag, LdtkMap, TilemapCollider, LdtkIntGridCollider, Model3D, SpriteMaterial, EditorIcon, TriggerZone, ColorFade, SaveState};
 use std::collections::HashMap;
 
 /// Component Type Enum สำหรับระบุประเภท Component
@@ -14,8 +14,10 @@ use std::collections::HashMap;
 pub enum ComponentType {
     Transform,
     Sprite,
+    SpriteMaterial,
     SpriteSheet,
     BoxCollider,
+    PolygonCollider,
     Rigidbody,
     Mesh,
     Camera,
@@ -27,6 +29,10 @@ pub enum ComponentType {
     LdtkIntGridCollider,
     Collider3D,
     Model3D,
+    EditorIcon,
+    TriggerZone,
+    ColorFade,
+    SaveState,
 }
 
 impl ComponentType {
@@ -35,8 +41,10 @@ impl ComponentType {
         vec![
             ComponentType::Transform,
             ComponentType::Sprite,
+            ComponentType::SpriteMaterial,
             ComponentType::SpriteSheet,
             ComponentType::BoxCollider,
+            ComponentType::PolygonCollider,
             ComponentType::Rigidbody,
             ComponentType::Mesh,
             ComponentType::Camera,
@@ -48,6 +56,10 @@ impl ComponentType {
             ComponentType::LdtkIntGridCollider,
             ComponentType::Collider3D,
             ComponentType::Model3D,
+            ComponentType::EditorIcon,
+            ComponentType::TriggerZone,
+            ComponentType::ColorFade,
+            ComponentType::SaveState,
         ]
     }
 
@@ -56,8 +68,10 @@ impl ComponentType {
         match self {
             ComponentType::Transform => "Transform",
             ComponentType::Sprite => "Sprite Renderer",
+            ComponentType::SpriteMaterial => "Sprite Material",
             ComponentType::SpriteSheet => "Sprite Sheet",
             ComponentType::BoxCollider => "Box Collider",
+            ComponentType::PolygonCollider => "Polygon Collider 2D",
             ComponentType::Rigidbody => "Rigidbody 2D",
             ComponentType::Mesh => "Mesh Renderer",
             ComponentType::Camera => "Camera",
@@ -69,6 +83,10 @@ impl ComponentType {
             ComponentType::LdtkIntGridCollider => "LDTK IntGrid Collider",
             ComponentType::Collider3D => "Collider 3D",
             ComponentType::Model3D => "Model 3D (XSG)",
+            ComponentType::EditorIcon => "Editor Icon",
+            ComponentType::TriggerZone => "Trigger Zone",
+            ComponentType::ColorFade => "Color Fade",
+            ComponentType::SaveState => "Save State",
         }
     }
 
@@ -120,17 +138,23 @@ impl ComponentManager for crate::CustomWorld {
                     asset_id: None,
                     width: 1.0,  // Base size
                     height: 1.0,
-                    color: [1.0, 1.0, 1.0, 1.0],
+                    color: crate::Color::WHITE,
                     billboard: false,
                     flip_x: false,
                     flip_y: false,
                     sprite_rect: None,
+                    rect_rotated: false,
+                    frame_name: None,
                     pixels_per_unit: 100.0,  // Unity standard
+                    pivot: None,
                     sorting_layer: "Default".to_string(),
                     order_in_layer: 0,
                     rendering_layer_mask: 1,
                 });
             }
+            ComponentType::SpriteMaterial => {
+                self.sprite_materials.insert(entity, SpriteMaterial::default());
+            }
             ComponentType::SpriteSheet => {
                 self.sprite_sheets.insert(entity, crate::SpriteSheet::new(
                     "assets/default.png",
@@ -180,12 +204,27 @@ impl ComponentManager for crate::CustomWorld {
             ComponentType::LdtkIntGridCollider => {
                 self.ldtk_intgrid_colliders.insert(entity, LdtkIntGridCollider::default());
             }
+            ComponentType::PolygonCollider => {
+                self.polygon_colliders.insert(entity, PolygonCollider::default());
+            }
             ComponentType::Collider3D => {
                 self.colliders_3d.insert(entity, Collider3D::default());
             }
             ComponentType::Model3D => {
                 self.model_3ds.insert(entity, Model3D::default());
             }
+            ComponentType::EditorIcon => {
+                self.editor_icons.insert(entity, EditorIcon::default());
+            }
+            ComponentType::TriggerZone => {
+                self.trigger_zones.insert(entity, TriggerZone::default());
+            }
+            ComponentType::ColorFade => {
+                self.color_fades.insert(entity, ColorFade::default());
+            }
+            ComponentType::SaveState => {
+                self.save_states.insert(entity, SaveState::default());
+            }
         }
 
         Ok(())
@@ -211,6 +250,9 @@ impl ComponentManager for crate::CustomWorld {
             ComponentType::Sprite => {
                 self.sprites.remove(&entity);
             }
+            ComponentType::SpriteMaterial => {
+                self.sprite_materials.remove(&entity);
+            }
             ComponentType::SpriteSheet => {
                 self.sprite_sheets.remove(&entity);
             }
@@ -245,12 +287,27 @@ impl ComponentManager for crate::CustomWorld {
             ComponentType::LdtkIntGridCollider => {
                 self.ldtk_intgrid_colliders.remove(&entity);
             }
+            ComponentType::PolygonCollider => {
+                self.polygon_colliders.remove(&entity);
+            }
             ComponentType::Collider3D => {
                 self.colliders_3d.remove(&entity);
             }
             ComponentType::Model3D => {
                 self.model_3ds.remove(&entity);
             }
+            ComponentType::EditorIcon => {
+                self.editor_icons.remove(&entity);
+            }
+            ComponentType::TriggerZone => {
+                self.trigger_zones.remove(&entity);
+            }
+            ComponentType::ColorFade => {
+                self.color_fades.remove(&entity);
+            }
+            ComponentType::SaveState => {
+                self.save_states.remove(&entity);
+            }
         }
 
         Ok(())
@@ -260,6 +317,7 @@ impl ComponentManager for crate::CustomWorld {
         match component_type {
             ComponentType::Transform => self.transforms.contains_key(&entity),
             ComponentType::Sprite => self.sprites.contains_key(&entity),
+            ComponentType::SpriteMaterial => self.sprite_materials.contains_key(&entity),
             ComponentType::SpriteSheet => self.sprite_sheets.contains_key(&entity),
             ComponentType::BoxCollider => self.colliders.contains_key(&entity),
             ComponentType::Rigidbody => self.rigidbodies.contains_key(&entity) || self.velocities.contains_key(&entity),
@@ -271,8 +329,13 @@ impl ComponentManager for crate::CustomWorld {
             ComponentType::LdtkMap => self.ldtk_maps.contains_key(&entity),
             ComponentType::TilemapCollider => self.tilemap_colliders.contains_key(&entity),
             ComponentType::LdtkIntGridCollider => self.ldtk_intgrid_colliders.contains_key(&entity),
+            ComponentType::PolygonCollider => self.polygon_colliders.contains_key(&entity),
             ComponentType::Collider3D => self.colliders_3d.contains_key(&entity),
             ComponentType::Model3D => self.model_3ds.contains_key(&entity),
+            ComponentType::EditorIcon => self.editor_icons.contains_key(&entity),
+            ComponentType::TriggerZone => self.trigger_zones.contains_key(&entity),
+            ComponentType::ColorFade => self.color_fades.contains_key(&entity),
+            ComponentType::SaveState => self.save_states.contains_key(&entity),
         }
     }
 
@@ -299,6 +362,7 @@ impl ComponentManager for crate::CustomWorld {
 #[cfg(all(test, not(feature = "hecs")))]
 mod tests {
     use super::*;
+    use crate::World;
 
     #[test]
     fn test_add_sprite_component() {
@@ -0,0 +1,156 @@
+use crate::Color;
+use serde::{Deserialize, Serialize};
+
+/// What happens to the entity when a ColorFade reaches its target
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FadeCompleteAction {
+    #[default]
+    None,
+    /// Set the entity inactive (it can be re-enabled and faded again)
+    Deactivate,
+    /// Destroy the entity, running its script's OnDestroy first
+    Destroy,
+}
+
+impl FadeCompleteAction {
+    pub const ALL: [FadeCompleteAction; 3] =
+        [FadeCompleteAction::None, FadeCompleteAction::Deactivate, FadeCompleteAction::Destroy];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FadeCompleteAction::None => "None",
+            FadeCompleteAction::Deactivate => "Deactivate",
+            FadeCompleteAction::Destroy => "Destroy",
+        }
+    }
+}
+
+/// Color Fade component: tweens a tint from `from` to `to` over `duration` seconds
+/// (after `delay`), then runs `on_complete`. The tint multiplies the sprite's own color
+/// when it is drawn, so `Sprite.color` and `set_sprite_color` keep working under a fade
+/// and a flash material still adds on top of the result.
+///
+/// The fade starts when play mode starts (or the component is added); `restart` plays
+/// it again.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ColorFade {
+    #[serde(default = "default_from")]
+    pub from: Color,
+    /// Target tint; the default fades alpha out
+    #[serde(default = "default_to")]
+    pub to: Color,
+    #[serde(default = "default_duration")]
+    pub duration: f32,
+    #[serde(default)]
+    pub delay: f32,
+    /// Easing name ("linear", "ease_out_quad", ...)
+    #[serde(default = "default_easing")]
+    pub easing: String,
+    #[serde(default)]
+    pub on_complete: FadeCompleteAction,
+    /// Seconds since the fade started (delay included)
+    #[serde(skip)]
+    pub elapsed: f32,
+    /// Tint applied this frame; None (untinted) until the fade system or the
+    /// inspector's preview sets it
+    #[serde(skip)]
+    pub tint: Option<Color>,
+}
+
+fn default_from() -> Color {
+    Color::WHITE
+}
+
+fn default_to() -> Color {
+    Color::WHITE.with_alpha(0.0)
+}
+
+fn default_duration() -> f32 {
+    1.0
+}
+
+fn default_easing() -> String {
+    "linear".to_string()
+}
+
+impl Default for ColorFade {
+    fn default() -> Self {
+        Self {
+            from: default_from(),
+            to: default_to(),
+            duration: default_duration(),
+            delay: 0.0,
+            easing: default_easing(),
+            on_complete: FadeCompleteAction::None,
+            elapsed: 0.0,
+            tint: None,
+        }
+    }
+}
+
+impl ColorFade {
+    /// Tint at eased progress `t` (0 = from, 1 = to)
+    pub fn color_at(&self, t: f32) -> Color {
+        self.from.lerp(self.to, t)
+    }
+
+    /// Whether the fade has reached its target
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.delay + self.duration
+    }
+
+    /// Linear progress in [0, 1] (0 during the delay)
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return if self.elapsed >= self.delay { 1.0 } else { 0.0 };
+        }
+        ((self.elapsed - self.delay) / self.duration).clamp(0.0, 1.0)
+    }
+
+    /// Play the fade again from the start
+    pub fn restart(&mut self) {
+        self.elapsed = 0.0;
+        self.tint = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_waits_for_the_delay() {
+        let mut fade = ColorFade { duration: 2.0, delay: 1.0, ..Default::default() };
+        fade.elapsed = 0.5;
+        assert_eq!(fade.progress(), 0.0);
+        fade.elapsed = 2.0;
+        assert_eq!(fade.progress(), 0.5);
+        assert!(!fade.is_finished());
+        fade.elapsed = 3.0;
+        assert_eq!(fade.progress(), 1.0);
+        assert!(fade.is_finished());
+
+        // Without a duration it jumps to the target once the delay is over
+        let mut instant = ColorFade { duration: 0.0, delay: 1.0, ..Default::default() };
+        assert_eq!(instant.progress(), 0.0);
+        instant.elapsed = 1.0;
+        assert_eq!(instant.progress(), 1.0);
+    }
+
+    #[test]
+    fn test_color_at_and_restart() {
+        let mut fade = ColorFade { to: Color::rgba(1.0, 0.0, 0.0, 0.5), ..Default::default() };
+        assert_eq!(fade.color_at(0.0), Color::WHITE);
+        assert_eq!(fade.color_at(1.0), Color::rgba(1.0, 0.0, 0.0, 0.5));
+        assert_eq!(fade.color_at(0.5), Color::rgba(1.0, 0.5, 0.5, 0.75));
+        // The default fades alpha out
+        assert_eq!(ColorFade::default().color_at(1.0), Color::WHITE.with_alpha(0.0));
+
+        fade.elapsed = 5.0;
+        fade.tint = Some(fade.color_at(1.0));
+        fade.restart();
+        assert_eq!((fade.elapsed, fade.tint), (0.0, None));
+        assert!(!fade.is_finished());
+    }
+}
@@ -0,0 +1,72 @@
+use crate::Color;
+use serde::{Deserialize, Serialize};
+
+/// Symbol an EditorIcon shows in the scene view
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EditorIconKind {
+    #[default]
+    Marker,
+    Spawn,
+    Flag,
+    Star,
+    Waypoint,
+    Trigger,
+}
+
+impl EditorIconKind {
+    pub const ALL: [EditorIconKind; 6] = [
+        EditorIconKind::Marker,
+        EditorIconKind::Spawn,
+        EditorIconKind::Flag,
+        EditorIconKind::Star,
+        EditorIconKind::Waypoint,
+        EditorIconKind::Trigger,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EditorIconKind::Marker => "Marker",
+            EditorIconKind::Spawn => "Spawn Point",
+            EditorIconKind::Flag => "Flag",
+            EditorIconKind::Star => "Star",
+            EditorIconKind::Waypoint => "Waypoint",
+            EditorIconKind::Trigger => "Trigger",
+        }
+    }
+}
+
+/// Editor Icon component: a billboard icon drawn for the entity in the editor's scene
+/// view only, so entities with nothing to render (spawn points, waypoints) can be seen
+/// and clicked. Never drawn in the Game view or in builds.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EditorIcon {
+    #[serde(default)]
+    pub icon: EditorIconKind,
+    #[serde(default = "default_tint")]
+    pub tint: Color,
+}
+
+fn default_tint() -> Color {
+    Color::WHITE
+}
+
+impl Default for EditorIcon {
+    fn default() -> Self {
+        Self { icon: EditorIconKind::Marker, tint: default_tint() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_kind_is_listed_once() {
+        let labels: std::collections::HashSet<_> = EditorIconKind::ALL.iter().map(|kind| kind.label()).collect();
+        assert_eq!(labels.len(), EditorIconKind::ALL.len());
+        assert_eq!(EditorIconKind::ALL[0], EditorIconKind::default());
+        // Saved by snake_case name
+        assert_eq!(serde_json::to_string(&EditorIconKind::Waypoint).unwrap(), "\"waypoint\"");
+    }
+}
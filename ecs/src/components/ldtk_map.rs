@@ -140,6 +140,17 @@ pub struct TilesetDef {
     pub tile_grid_size: i32,
     pub spacing: i32,
     pub padding: i32,
+    /// Per-tile custom data typed in LDtk's tileset editor
+    #[serde(default)]
+    pub custom_data: Vec<TileCustomData>,
+}
+
+/// Custom data attached to one tile of a tileset
+#[derive(Clone, Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TileCustomData {
+    pub tile_id: i32,
+    pub data: String,
 }
 
 /// Layer Instance (The actual layer data in a level)
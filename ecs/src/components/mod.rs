@@ -10,14 +10,22 @@ pub mod map;
 pub mod grid;
 pub mod world_ui;
 pub mod collider_3d;
+pub mod polygon_collider;
+pub mod render_texture;
+pub mod sprite_material;
+pub mod editor_icon;
+pub mod trigger_zone;
+pub mod color_fade;
+pub mod save_state;
+pub mod sprite_uv;
 
 // Re-export all components
-pub use sprite_sheet::{SpriteSheet, SpriteFrame, AnimatedSprite, AnimationMode};
-pub use tilemap::{TileSet, Tilemap, Tile, TileData, TilemapChunk, TilemapRenderer, TilemapRenderMode, MaskInteraction};
+pub use sprite_sheet::{SpriteSheet, SpriteFrame, AnimatedSprite, AnimationMode, AnimationClip, AnimationKeyframe};
+pub use tilemap::{TileSet, Tilemap, Tile, TileData, TileProperty, TileAnimation, TileAnimationFrame, TilemapChunk, TilemapRenderer, TilemapRenderMode, MaskInteraction, TILEMAP_CHUNK_SIZE};
 pub use tilemap_collider::{TilemapCollider, TilemapColliderMode, LdtkIntGridCollider};
 pub use ldtk_map::{
     LdtkJson, LdtkMap, LdtkDefs, LdtkLevel,
-    LayerDef, EntityDef, FieldDef, TilesetDef,
+    LayerDef, EntityDef, FieldDef, TilesetDef, TileCustomData,
     LayerInstance, TileInstance, EntityInstance, FieldInstance
 };
 pub use map::{Map, MapType};
@@ -25,6 +33,39 @@ pub use grid::{Grid, GridLayout, HexagonOrientation, CellSwizzle, GridPlane};
 pub use world_ui::{WorldUI, WorldUIType, QuestMarkerType};
 
 pub use collider_3d::{Collider3D, ColliderShape3D};
+pub use polygon_collider::PolygonCollider;
+pub use sprite_material::{SpriteMaterial, SpriteShader};
+pub use editor_icon::{EditorIcon, EditorIconKind};
+pub use trigger_zone::{TriggerZone, TriggerZoneShape};
+pub use color_fade::{ColorFade, FadeCompleteAction};
+pub use save_state::{SaveState, Guid};
+pub use sprite_uv::SpriteUv;
+pub use render_texture::{RenderTextureAsset, RenderTextureFormat, RENDER_TEXTURE_EXTENSION, RENDER_TEXTURE_PREFIX};
 
 pub mod ldtk_entity;
 pub use ldtk_entity::LdtkEntity;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::fmt::Debug;
+
+    /// `minimal` (the required fields only) loads as the default and the default survives
+    /// a save and load, so scenes saved before a field existed keep loading
+    fn assert_defaults_round_trip<T: Default + PartialEq + Debug + Serialize + DeserializeOwned>(minimal: &str) {
+        let loaded: T = serde_json::from_str(minimal).unwrap();
+        assert_eq!(loaded, T::default(), "{} from {}", std::any::type_name::<T>(), minimal);
+        let saved = serde_json::to_string(&T::default()).unwrap();
+        assert_eq!(serde_json::from_str::<T>(&saved).unwrap(), T::default(), "{}", saved);
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        assert_defaults_round_trip::<ColorFade>("{}");
+        assert_defaults_round_trip::<TriggerZone>("{}");
+        assert_defaults_round_trip::<EditorIcon>("{}");
+        assert_defaults_round_trip::<SaveState>("{}");
+        assert_defaults_round_trip::<RenderTextureAsset>(r#"{ "width": 256, "height": 256 }"#);
+    }
+}
@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+
+/// Polygon Collider component (Unity's PolygonCollider2D)
+///
+/// The outline may be concave; physics tests it as a set of convex parts.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PolygonCollider {
+    /// Outline in local space (world point = position + point * scale), y up
+    pub points: Vec<[f32; 2]>,
+    /// Trigger colliders report overlaps but don't block movement
+    #[serde(default)]
+    pub is_trigger: bool,
+}
+
+impl Default for PolygonCollider {
+    fn default() -> Self {
+        // Same footprint as a default box collider
+        Self {
+            points: vec![[-0.5, -0.5], [0.5, -0.5], [0.5, 0.5], [-0.5, 0.5]],
+            is_trigger: false,
+        }
+    }
+}
+
+impl PolygonCollider {
+    pub fn new(points: Vec<[f32; 2]>) -> Self {
+        Self { points, is_trigger: false }
+    }
+
+    /// Convert a sprite outline (pixels from the sprite rect's top-left, y down) into
+    /// local units around the sprite's pivot, matching how the sprite is rendered.
+    pub fn from_sprite_outline(outline: &[[f32; 2]], sprite_size: [u32; 2], pivot: [f32; 2], pixels_per_unit: f32) -> Self {
+        let ppu = if pixels_per_unit > 0.0 { pixels_per_unit } else { 100.0 };
+        let pivot_x = pivot[0] * sprite_size[0] as f32;
+        let pivot_y = (1.0 - pivot[1]) * sprite_size[1] as f32;
+        Self::new(
+            outline
+                .iter()
+                .map(|p| [(p[0] - pivot_x) / ppu, (pivot_y - p[1]) / ppu])
+                .collect(),
+        )
+    }
+
+    /// Outline in world space for an entity at `position` with `scale`
+    pub fn world_points(&self, position: [f32; 2], scale: [f32; 2]) -> Vec<[f32; 2]> {
+        self.points
+            .iter()
+            .map(|p| [position[0] + p[0] * scale[0], position[1] + p[1] * scale[1]])
+            .collect()
+    }
+
+    /// Split the outline into convex polygons (counter-clockwise, local space).
+    /// Ear clipping, then adjacent triangles are merged while the result stays convex.
+    pub fn convex_parts(&self) -> Vec<Vec<[f32; 2]>> {
+        let mut ring = self.points.clone();
+        ring.dedup();
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        if ring.len() < 3 || signed_area(&ring).abs() < f32::EPSILON {
+            return Vec::new();
+        }
+        if signed_area(&ring) < 0.0 {
+            ring.reverse();
+        }
+        if is_convex(&ring) {
+            return vec![ring];
+        }
+
+        let mut parts: Vec<Vec<usize>> = triangulate(&ring).into_iter().map(|t| t.to_vec()).collect();
+
+        // Hertel-Mehlhorn style merge across shared diagonals
+        let mut merged = true;
+        while merged {
+            merged = false;
+            'search: for a in 0..parts.len() {
+                for b in (a + 1)..parts.len() {
+                    if let Some(candidate) = merge_parts(&parts[a], &parts[b]) {
+                        let points: Vec<[f32; 2]> = candidate.iter().map(|&i| ring[i]).collect();
+                        if is_convex(&points) {
+                            parts[a] = candidate;
+                            parts.remove(b);
+                            merged = true;
+                            break 'search;
+                        }
+                    }
+                }
+            }
+        }
+
+        parts
+            .into_iter()
+            .map(|part| part.into_iter().map(|i| ring[i]).collect())
+            .collect()
+    }
+}
+
+/// Twice the signed area; positive for counter-clockwise rings
+fn signed_area(points: &[[f32; 2]]) -> f32 {
+    let n = points.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (points[i], points[(i + 1) % n]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum()
+}
+
+fn cross(o: [f32; 2], a: [f32; 2], b: [f32; 2]) -> f32 {
+    (a[0] - o[0]) * (b[1] - o[1]) - (a[1] - o[1]) * (b[0] - o[0])
+}
+
+/// Convex with counter-clockwise winding (collinear points allowed)
+fn is_convex(points: &[[f32; 2]]) -> bool {
+    let n = points.len();
+    (0..n).all(|i| cross(points[i], points[(i + 1) % n], points[(i + 2) % n]) >= -1e-6)
+}
+
+fn point_in_triangle(p: [f32; 2], a: [f32; 2], b: [f32; 2], c: [f32; 2]) -> bool {
+    cross(a, b, p) >= 0.0 && cross(b, c, p) >= 0.0 && cross(c, a, p) >= 0.0
+}
+
+/// Ear clipping on a counter-clockwise ring; returns index triangles
+fn triangulate(ring: &[[f32; 2]]) -> Vec<[usize; 3]> {
+    let mut remaining: Vec<usize> = (0..ring.len()).collect();
+    let mut triangles = Vec::with_capacity(ring.len().saturating_sub(2));
+
+    while remaining.len() > 3 {
+        let n = remaining.len();
+        let ear = (0..n).find(|&i| {
+            let (prev, current, next) = (remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]);
+            let (a, b, c) = (ring[prev], ring[current], ring[next]);
+            if cross(a, b, c) <= 0.0 {
+                return false;
+            }
+            remaining
+                .iter()
+                .filter(|&&j| j != prev && j != current && j != next)
+                .all(|&j| ring[j] == a || ring[j] == b || ring[j] == c || !point_in_triangle(ring[j], a, b, c))
+        });
+
+        // Self-intersecting outlines have no ear; clip the flattest corner so we still terminate
+        let i = ear.unwrap_or(0);
+        triangles.push([remaining[(i + n - 1) % n], remaining[i], remaining[(i + 1) % n]]);
+        remaining.remove(i);
+    }
+    if remaining.len() == 3 {
+        triangles.push([remaining[0], remaining[1], remaining[2]]);
+    }
+    triangles
+}
+
+/// Join two index polygons that share an edge (in opposite directions)
+fn merge_parts(a: &[usize], b: &[usize]) -> Option<Vec<usize>> {
+    let (na, nb) = (a.len(), b.len());
+    for i in 0..na {
+        let (u, v) = (a[i], a[(i + 1) % na]);
+        for j in 0..nb {
+            if b[j] == v && b[(j + 1) % nb] == u {
+                // a: ... u | v ...  walk b from u around to v, skipping the shared edge
+                let mut merged = Vec::with_capacity(na + nb - 2);
+                for k in 0..na {
+                    merged.push(a[(i + 1 + k) % na]);
+                }
+                // merged now ends at u; insert b's vertices between u and v
+                for k in 2..nb {
+                    merged.push(b[(j + k) % nb]);
+                }
+                return Some(merged);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_sprite_outline_uses_pivot() {
+        // 100x100 px sprite, bottom-center pivot, 100 ppu
+        let outline = [[0.0, 0.0], [100.0, 0.0], [100.0, 100.0], [0.0, 100.0]];
+        let collider = PolygonCollider::from_sprite_outline(&outline, [100, 100], [0.5, 0.0], 100.0);
+        assert_eq!(collider.points, vec![[-0.5, 1.0], [0.5, 1.0], [0.5, 0.0], [-0.5, 0.0]]);
+    }
+
+    #[test]
+    fn test_concave_outline_splits_into_convex_parts() {
+        // L shape, clockwise on purpose
+        let collider = PolygonCollider::new(vec![
+            [0.0, 0.0], [0.0, 2.0], [1.0, 2.0], [1.0, 1.0], [2.0, 1.0], [2.0, 0.0],
+        ]);
+        let parts = collider.convex_parts();
+        assert!(parts.len() >= 2);
+        let total: f32 = parts.iter().map(|p| {
+            assert!(is_convex(p));
+            signed_area(p) / 2.0
+        }).sum();
+        assert!((total - 3.0).abs() < 1e-5, "area {}", total);
+
+        assert_eq!(PolygonCollider::default().convex_parts().len(), 1);
+    }
+}
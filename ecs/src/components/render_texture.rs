@@ -0,0 +1,113 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// File extension of render texture assets (`minimap.rendertexture`)
+pub const RENDER_TEXTURE_EXTENSION: &str = "rendertexture";
+
+/// Texture ID prefix under which render textures are sampled by Sprites and UIImages
+pub const RENDER_TEXTURE_PREFIX: &str = "rendertexture://";
+
+/// How a render texture's pixels are interpreted when sampled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RenderTextureFormat {
+    /// Color data, decoded from sRGB when sampled; matches the main view
+    #[default]
+    Srgb,
+    /// Stored values are read back as-is (e.g. data for effects)
+    Linear,
+}
+
+/// Render texture asset: an offscreen target a Camera can render into.
+/// The asset's name is its file stem.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RenderTextureAsset {
+    pub width: u32,
+    pub height: u32,
+    #[serde(default)]
+    pub format: RenderTextureFormat,
+    /// Give the texture its own depth buffer, kept between frames. Without it, a
+    /// depth buffer shared by all render textures of the same size is used.
+    #[serde(default = "default_depth_buffer")]
+    pub depth_buffer: bool,
+}
+
+fn default_depth_buffer() -> bool {
+    true
+}
+
+impl Default for RenderTextureAsset {
+    fn default() -> Self {
+        Self::new(256, 256)
+    }
+}
+
+impl RenderTextureAsset {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            format: RenderTextureFormat::Srgb,
+            depth_buffer: true,
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read render texture '{}': {}", path.display(), e))?;
+        let asset: Self = serde_json::from_str(&contents)
+            .map_err(|e| format!("Failed to parse render texture '{}': {}", path.display(), e))?;
+        if asset.width == 0 || asset.height == 0 {
+            return Err(format!("Render texture '{}' has a zero size", path.display()));
+        }
+        Ok(asset)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize render texture: {}", e))?;
+        fs::write(path, json).map_err(|e| format!("Failed to write render texture '{}': {}", path.display(), e))
+    }
+
+    /// Texture ID that samples the render texture called `name`
+    pub fn texture_id(name: &str) -> String {
+        format!("{}{}", RENDER_TEXTURE_PREFIX, name)
+    }
+
+    /// Render texture name referenced by a texture ID, if it is one
+    pub fn name_from_texture_id(texture_id: &str) -> Option<&str> {
+        texture_id.strip_prefix(RENDER_TEXTURE_PREFIX).filter(|name| !name.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_texture_id_round_trip() {
+        let id = RenderTextureAsset::texture_id("minimap");
+        assert_eq!(id, "rendertexture://minimap");
+        assert_eq!(RenderTextureAsset::name_from_texture_id(&id), Some("minimap"));
+        assert_eq!(RenderTextureAsset::name_from_texture_id("sprites/hero.png"), None);
+        assert_eq!(RenderTextureAsset::name_from_texture_id(RENDER_TEXTURE_PREFIX), None);
+    }
+
+    #[test]
+    fn test_save_load_and_zero_size() {
+        let dir = std::env::temp_dir().join(format!("render_texture_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("minimap.rendertexture");
+
+        let asset = RenderTextureAsset { format: RenderTextureFormat::Linear, depth_buffer: false, ..RenderTextureAsset::new(320, 180) };
+        asset.save(&path).unwrap();
+        assert_eq!(RenderTextureAsset::load(&path).unwrap(), asset);
+
+        fs::write(&path, r#"{ "width": 0, "height": 180 }"#).unwrap();
+        assert!(RenderTextureAsset::load(&path).unwrap_err().contains("zero size"));
+        assert!(RenderTextureAsset::load(dir.join("missing.rendertexture")).is_err());
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,81 @@
+use serde::{Deserialize, Serialize};
+
+/// Save State component: marks an entity as dynamic game state. Save games
+/// (`World::capture_dynamic_state`) store the parts selected here for every such
+/// entity, on top of the authored scene they were saved in. The entity is found again
+/// by its Guid, which it gets the first time its scene is saved.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SaveState {
+    /// Position, rotation and scale
+    #[serde(default = "default_true")]
+    pub transform: bool,
+    /// Active flag (an opened chest that hid its lid, a collected key)
+    #[serde(default = "default_true")]
+    pub active: bool,
+    /// Sprite texture, frame, color and flips
+    #[serde(default = "default_true")]
+    pub sprite: bool,
+    /// Velocity and Rigidbody 2D settings
+    #[serde(default = "default_true")]
+    pub physics: bool,
+    /// Animated sprite clip and frame
+    #[serde(default = "default_true")]
+    pub animation: bool,
+    /// Script enabled flag and parameters (Entity parameters keep their authored value)
+    #[serde(default = "default_true")]
+    pub script: bool,
+    /// Components defined from Lua (Component.define)
+    #[serde(default = "default_true")]
+    pub custom_components: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for SaveState {
+    fn default() -> Self {
+        Self {
+            transform: true,
+            active: true,
+            sprite: true,
+            physics: true,
+            animation: true,
+            script: true,
+            custom_components: true,
+        }
+    }
+}
+
+/// Stable id of an entity across scene loads (entity ids change whenever a scene is
+/// merged into the world). Save games match saved entities to the scene's by it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Guid(pub String);
+
+impl Guid {
+    pub fn new() -> Self {
+        Guid(uuid::Uuid::new_v4().to_string())
+    }
+}
+
+impl Default for Guid {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_guids_are_unique_and_save_as_strings() {
+        let guid = Guid::new();
+        assert_ne!(guid, Guid::new());
+        assert_ne!(Guid::default(), Guid::default());
+        let json = serde_json::to_string(&guid).unwrap();
+        assert_eq!(json, format!("\"{}\"", guid.0));
+        assert_eq!(serde_json::from_str::<Guid>(&json).unwrap(), guid);
+    }
+}
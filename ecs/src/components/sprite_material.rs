@@ -0,0 +1,154 @@
+use crate::Color;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Built-in sprite shader variants a SpriteMaterial can select
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpriteShader {
+    /// Plain textured, tinted sprite (what sprites without a material use)
+    Default,
+    /// Blend towards `flash_color` by `flash_amount` (hit flash)
+    Flash,
+    /// `thickness` pixel outline in `outline_color` around opaque pixels
+    Outline,
+    /// Pixels whose noise value is below `threshold` are cut away, with an `edge_color` rim
+    Dissolve,
+}
+
+impl SpriteShader {
+    pub const ALL: [SpriteShader; 4] = [SpriteShader::Default, SpriteShader::Flash, SpriteShader::Outline, SpriteShader::Dissolve];
+
+    /// Variant by name; None for unknown names
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "default" | "" => Some(SpriteShader::Default),
+            "flash" => Some(SpriteShader::Flash),
+            "outline" => Some(SpriteShader::Outline),
+            "dissolve" => Some(SpriteShader::Dissolve),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpriteShader::Default => "default",
+            SpriteShader::Flash => "flash",
+            SpriteShader::Outline => "outline",
+            SpriteShader::Dissolve => "dissolve",
+        }
+    }
+
+    /// Float parameters with their defaults, in uniform slot order (at most 4)
+    pub fn float_params(&self) -> &'static [(&'static str, f32)] {
+        match self {
+            SpriteShader::Default => &[],
+            SpriteShader::Flash => &[("flash_amount", 0.0)],
+            SpriteShader::Outline => &[("thickness", 1.0)],
+            SpriteShader::Dissolve => &[("threshold", 0.0), ("edge_width", 0.05), ("noise_scale", 16.0)],
+        }
+    }
+
+    /// The color parameter and its default, if the variant has one
+    pub fn color_param(&self) -> Option<(&'static str, Color)> {
+        match self {
+            SpriteShader::Default => None,
+            SpriteShader::Flash => Some(("flash_color", Color::WHITE)),
+            SpriteShader::Outline => Some(("outline_color", Color::WHITE)),
+            SpriteShader::Dissolve => Some(("edge_color", Color::rgba(1.0, 0.5, 0.1, 1.0))),
+        }
+    }
+}
+
+/// Sprite Material component: renders the entity's sprite with a built-in shader
+/// variant instead of the default one.
+///
+/// The variant is stored by name so scenes saved with a newer engine still load; an
+/// unknown name renders with the default shader. Parameters not set here use the
+/// variant's defaults.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpriteMaterial {
+    /// Shader variant name ("flash", "outline", "dissolve")
+    pub shader: String,
+    #[serde(default)]
+    pub floats: BTreeMap<String, f32>,
+    #[serde(default)]
+    pub colors: BTreeMap<String, Color>,
+}
+
+impl Default for SpriteMaterial {
+    fn default() -> Self {
+        Self::new(SpriteShader::Flash)
+    }
+}
+
+impl SpriteMaterial {
+    pub fn new(shader: SpriteShader) -> Self {
+        Self {
+            shader: shader.name().to_string(),
+            floats: BTreeMap::new(),
+            colors: BTreeMap::new(),
+        }
+    }
+
+    /// Variant to render with (Default when the name is unknown)
+    pub fn variant(&self) -> SpriteShader {
+        SpriteShader::from_name(&self.shader).unwrap_or(SpriteShader::Default)
+    }
+
+    /// Float parameter, falling back to the variant's default (0 for unknown names)
+    pub fn float(&self, name: &str) -> f32 {
+        self.floats.get(name).copied().unwrap_or_else(|| {
+            self.variant().float_params().iter().find(|(n, _)| *n == name).map_or(0.0, |(_, v)| *v)
+        })
+    }
+
+    pub fn set_float(&mut self, name: &str, value: f32) {
+        self.floats.insert(name.to_string(), value);
+    }
+
+    /// Color parameter, falling back to the variant's default (white for unknown names)
+    pub fn color(&self, name: &str) -> Color {
+        self.colors.get(name).copied().unwrap_or_else(|| {
+            self.variant().color_param().filter(|(n, _)| *n == name).map_or(Color::WHITE, |(_, c)| c)
+        })
+    }
+
+    pub fn set_color(&mut self, name: &str, color: Color) {
+        self.colors.insert(name.to_string(), color);
+    }
+
+    /// Parameters packed for the GPU: the variant's float slots and its color
+    pub fn packed(&self) -> ([f32; 4], Color) {
+        let variant = self.variant();
+        let mut params = [0.0; 4];
+        for (slot, (name, _)) in params.iter_mut().zip(variant.float_params()) {
+            *slot = self.float(name);
+        }
+        let color = variant.color_param().map_or(Color::WHITE, |(name, _)| self.color(name));
+        (params, color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unknown_variant_falls_back_to_default() {
+        let material: SpriteMaterial = serde_json::from_str(r#"{ "shader": "hologram" }"#).unwrap();
+        assert_eq!(material.variant(), SpriteShader::Default);
+        assert_eq!(material.packed(), ([0.0; 4], Color::WHITE));
+    }
+
+    #[test]
+    fn test_params_pack_in_slot_order() {
+        let mut material = SpriteMaterial::new(SpriteShader::Dissolve);
+        material.set_float("threshold", 0.4);
+        material.set_color("edge_color", Color::rgba(0.0, 1.0, 0.0, 1.0));
+
+        let json = serde_json::to_string(&material).unwrap();
+        let loaded: SpriteMaterial = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, material);
+        assert_eq!(loaded.packed(), ([0.4, 0.05, 16.0, 0.0], Color::rgba(0.0, 1.0, 0.0, 1.0)));
+    }
+}
@@ -15,6 +15,21 @@ pub struct SpriteFrame {
     pub height: u32,
     /// Optional frame name/identifier
     pub name: Option<String>,
+    /// Stored turned 90° clockwise (width and height stay the frame's as displayed)
+    #[serde(default)]
+    pub rotated: bool,
+}
+
+impl SpriteFrame {
+    /// Rect as [x, y, width, height], the layout of Sprite::sprite_rect
+    pub fn rect(&self) -> [u32; 4] {
+        [self.x, self.y, self.width, self.height]
+    }
+
+    /// Texture coordinates of this frame in a `sheet_size` sheet, with a sprite's flips
+    pub fn uv(&self, sheet_size: [f32; 2], flip_x: bool, flip_y: bool) -> crate::SpriteUv {
+        crate::SpriteUv::from_rect(self.rect(), sheet_size, flip_x, flip_y, self.rotated)
+    }
 }
 
 /// Sprite sheet component for managing sprite atlas data
@@ -76,6 +91,7 @@ impl SpriteSheet {
                     width: frame_width,
                     height: frame_height,
                     name: Some(format!("frame_{}_{}", row, col)),
+                    rotated: false,
                 });
             }
         }
@@ -155,6 +171,7 @@ impl SpriteSheet {
                 width: sprite_def.width,
                 height: sprite_def.height,
                 name: Some(sprite_def.name),
+                rotated: sprite_def.rotated,
             };
             sprite_sheet.add_frame(frame);
         }
@@ -181,6 +198,8 @@ struct SpriteDefinition {
     y: u32,
     width: u32,
     height: u32,
+    #[serde(default)]
+    rotated: bool,
 }
 
 #[cfg(test)]
@@ -227,6 +246,64 @@ mod tests {
         assert_eq!(knight_frame.height, 32);
     }
 
+    #[test]
+    fn test_named_frame_follows_repacked_sheet() {
+        let mut world = crate::CustomWorld::new();
+        let sheet_owner = world.spawn();
+        let mut sheet = SpriteSheet::new("hero.png", "hero.png", 128, 32);
+        sheet.add_frame(SpriteFrame { x: 0, y: 0, width: 32, height: 32, name: Some("run_01".to_string()), rotated: false });
+        world.sprite_sheets.insert(sheet_owner, sheet);
+
+        // A sprite on another entity resolves through the sheet of its texture
+        let entity = world.spawn();
+        let sprite = crate::Sprite {
+            texture_id: "hero.png".to_string(),
+            sprite_rect: Some([0, 0, 32, 32]),
+            frame_name: Some("run_01".to_string()),
+            ..Default::default()
+        };
+        world.sprites.insert(entity, sprite.clone());
+        assert_eq!(world.sprite_rect_for(entity, &sprite), Some([0, 0, 32, 32]));
+
+        // Re-packed: the frame moved, the stale sprite_rect is ignored
+        world.sprite_sheets.get_mut(&sheet_owner).unwrap().frames[0].x = 64;
+        assert_eq!(world.sprite_rect_for(entity, &sprite), Some([64, 0, 32, 32]));
+
+        // Unknown frame names fall back to sprite_rect
+        let missing = crate::Sprite { frame_name: Some("jump".to_string()), ..sprite };
+        assert_eq!(world.sprite_rect_for(entity, &missing), Some([0, 0, 32, 32]));
+    }
+
+    #[test]
+    fn test_shared_texture_resolves_to_the_lowest_sheet() {
+        let mut world = crate::CustomWorld::new();
+        let sheet = |x: u32| {
+            let mut sheet = SpriteSheet::new("hero.png", "sprites/hero.png", 128, 32);
+            sheet.add_frame(SpriteFrame { x, y: 0, width: 32, height: 32, name: Some("idle".to_string()), rotated: false });
+            sheet
+        };
+        let owners: Vec<_> = (0..4).map(|_| world.spawn()).collect();
+        for (i, owner) in owners.iter().enumerate().rev() {
+            world.sprite_sheets.insert(*owner, sheet(32 * i as u32));
+        }
+        let entity = world.spawn();
+        let sprite = crate::Sprite { texture_id: "sprites/hero.png".to_string(), frame_name: Some("idle".to_string()), ..Default::default() };
+        world.sprites.insert(entity, sprite.clone());
+
+        // Same answer scanning and indexed, whatever the HashMap order
+        assert_eq!(world.sprite_rect_for(entity, &sprite), Some([0, 0, 32, 32]));
+        world.index_sprite_sheets();
+        assert_eq!(world.sprite_sheet_index.get("hero.png"), Some(&owners[0]));
+        assert_eq!(world.sprite_rect_for(entity, &sprite), Some([0, 0, 32, 32]));
+
+        // A stale index entry isn't trusted: the sheet moved to another texture
+        world.sprite_sheets.get_mut(&owners[0]).unwrap().texture_path = "other.png".to_string();
+        world.sprite_sheets.get_mut(&owners[0]).unwrap().texture_id = "other.png".to_string();
+        assert_eq!(world.sprite_rect_for(entity, &sprite), Some([32, 0, 32, 32]));
+        world.despawn(owners[1]);
+        assert_eq!(world.sprite_rect_for(entity, &sprite), Some([64, 0, 32, 32]));
+    }
+
     #[test]
     fn test_from_sprite_file_handles_missing_file() {
         let result = SpriteSheet::from_sprite_file(Path::new("nonexistent.sprite"));
@@ -255,7 +332,7 @@ mod tests {
 }
 
 /// Animation loop mode
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum AnimationMode {
     /// Play once and stop
     Once,
@@ -265,6 +342,61 @@ pub enum AnimationMode {
     PingPong,
 }
 
+fn default_animation_mode() -> AnimationMode {
+    AnimationMode::Loop
+}
+
+fn default_keyframe_duration() -> f32 {
+    0.1
+}
+
+fn default_speed() -> f32 {
+    1.0
+}
+
+/// One frame of an animation clip
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnimationKeyframe {
+    /// Frame index in the sprite sheet
+    pub frame: usize,
+    /// How long the frame is shown (seconds)
+    #[serde(default = "default_keyframe_duration")]
+    pub duration: f32,
+    /// Event names sent to the script's on_animation_event when the frame starts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<String>,
+}
+
+impl AnimationKeyframe {
+    pub fn new(frame: usize, duration: f32) -> Self {
+        Self { frame, duration, events: Vec::new() }
+    }
+}
+
+/// Named animation clip: an ordered list of sprite sheet frames
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AnimationClip {
+    pub name: String,
+    pub frames: Vec<AnimationKeyframe>,
+    #[serde(default = "default_animation_mode")]
+    pub mode: AnimationMode,
+}
+
+impl AnimationClip {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            frames: Vec::new(),
+            mode: AnimationMode::Loop,
+        }
+    }
+
+    /// Total length of one pass through the clip (seconds)
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|keyframe| keyframe.duration).sum()
+    }
+}
+
 /// Animated sprite component for frame-based animations
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AnimatedSprite {
@@ -286,6 +418,19 @@ pub struct AnimatedSprite {
     /// Animation direction (1 = forward, -1 = backward for ping-pong)
     #[serde(skip)]
     pub direction: i32,
+    /// Clips authored in the Animation window
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub clips: Vec<AnimationClip>,
+    /// Name of the playing clip; when set, the clip's frames, durations and mode
+    /// replace `frame_sequence`, `frame_duration` and `mode`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clip: Option<String>,
+    /// Playback speed multiplier
+    #[serde(default = "default_speed")]
+    pub speed: f32,
+    /// Clip events reached since the last `take_events`
+    #[serde(skip)]
+    pub pending_events: Vec<String>,
 }
 
 impl Default for AnimatedSprite {
@@ -299,6 +444,10 @@ impl Default for AnimatedSprite {
             mode: AnimationMode::Loop,
             playing: true,
             direction: 1,
+            clips: Vec::new(),
+            clip: None,
+            speed: 1.0,
+            pending_events: Vec::new(),
         }
     }
 }
@@ -315,11 +464,16 @@ impl AnimatedSprite {
 
     /// Update the animation
     pub fn update(&mut self, delta_time: f32, total_frames: usize) {
+        if let Some(clip_index) = self.active_clip_index() {
+            self.update_clip(clip_index, delta_time);
+            return;
+        }
+
         if !self.playing || total_frames == 0 {
             return;
         }
 
-        self.elapsed_time += delta_time;
+        self.elapsed_time += delta_time * self.speed;
 
         if self.elapsed_time >= self.frame_duration {
             self.elapsed_time -= self.frame_duration;
@@ -330,36 +484,76 @@ impl AnimatedSprite {
                 self.frame_sequence.len()
             };
 
-            match self.mode {
-                AnimationMode::Once => {
-                    if self.current_frame < frame_count - 1 {
-                        self.current_frame += 1;
-                    } else {
-                        self.playing = false;
-                    }
-                }
-                AnimationMode::Loop => {
-                    self.current_frame = (self.current_frame + 1) % frame_count;
+            self.advance(frame_count, self.mode);
+        }
+    }
+
+    /// Step to the next frame for the loop mode; stops playback at the end of a `Once` animation
+    fn advance(&mut self, frame_count: usize, mode: AnimationMode) {
+        match mode {
+            AnimationMode::Once => {
+                if self.current_frame < frame_count - 1 {
+                    self.current_frame += 1;
+                } else {
+                    self.playing = false;
                 }
-                AnimationMode::PingPong => {
-                    let next_frame = self.current_frame as i32 + self.direction;
-                    
-                    if next_frame >= frame_count as i32 {
-                        self.direction = -1;
-                        self.current_frame = frame_count.saturating_sub(2);
-                    } else if next_frame < 0 {
-                        self.direction = 1;
-                        self.current_frame = 1.min(frame_count - 1);
-                    } else {
-                        self.current_frame = next_frame as usize;
-                    }
+            }
+            AnimationMode::Loop => {
+                self.current_frame = (self.current_frame + 1) % frame_count;
+            }
+            AnimationMode::PingPong => {
+                let next_frame = self.current_frame as i32 + self.direction;
+                
+                if next_frame >= frame_count as i32 {
+                    self.direction = -1;
+                    self.current_frame = frame_count.saturating_sub(2);
+                } else if next_frame < 0 {
+                    self.direction = 1;
+                    self.current_frame = 1.min(frame_count - 1);
+                } else {
+                    self.current_frame = next_frame as usize;
                 }
             }
         }
     }
 
+    /// Advance the active clip using per-keyframe durations, collecting keyframe events
+    fn update_clip(&mut self, clip_index: usize, delta_time: f32) {
+        if !self.playing || self.clips[clip_index].frames.is_empty() {
+            return;
+        }
+
+        self.elapsed_time += delta_time * self.speed;
+
+        // Moved out while stepping so `advance` can borrow self mutably
+        let clips = std::mem::take(&mut self.clips);
+        let clip = &clips[clip_index];
+        let frame_count = clip.frames.len();
+
+        // Several short keyframes can pass in one update; bounded so zero durations can't spin
+        for _ in 0..frame_count * 4 {
+            let index = self.current_frame.min(frame_count - 1);
+            let duration = clip.frames[index].duration.max(0.001);
+            if self.elapsed_time < duration {
+                break;
+            }
+            self.elapsed_time -= duration;
+
+            self.advance(frame_count, clip.mode);
+            if !self.playing {
+                self.elapsed_time = 0.0;
+                break;
+            }
+            self.pending_events.extend(clip.frames[self.current_frame].events.iter().cloned());
+        }
+        self.clips = clips;
+    }
+
     /// Get the actual frame index to render
     pub fn get_frame_index(&self) -> usize {
+        if let Some(clip) = self.active_clip() {
+            return clip.frames.get(self.current_frame).map(|keyframe| keyframe.frame).unwrap_or(0);
+        }
         if self.frame_sequence.is_empty() {
             self.current_frame
         } else {
@@ -367,6 +561,41 @@ impl AnimatedSprite {
         }
     }
 
+    fn active_clip_index(&self) -> Option<usize> {
+        let name = self.clip.as_ref()?;
+        self.clips.iter().position(|clip| clip.name == *name)
+    }
+
+    /// The clip driving playback, if `clip` names an existing clip
+    pub fn active_clip(&self) -> Option<&AnimationClip> {
+        self.active_clip_index().map(|index| &self.clips[index])
+    }
+
+    /// Find a clip by name
+    pub fn find_clip(&self, name: &str) -> Option<&AnimationClip> {
+        self.clips.iter().find(|clip| clip.name == name)
+    }
+
+    /// Switch to a clip and play it from the first keyframe; returns false if no clip has that name
+    pub fn play_clip(&mut self, name: &str) -> bool {
+        let Some(clip) = self.find_clip(name) else {
+            return false;
+        };
+        let first_events = clip.frames.first().map(|keyframe| keyframe.events.clone()).unwrap_or_default();
+        self.clip = Some(name.to_string());
+        self.current_frame = 0;
+        self.elapsed_time = 0.0;
+        self.direction = 1;
+        self.playing = true;
+        self.pending_events.extend(first_events);
+        true
+    }
+
+    /// Take the clip events reached since the last call
+    pub fn take_events(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending_events)
+    }
+
     /// Play the animation
     pub fn play(&mut self) {
         self.playing = true;
@@ -391,3 +620,37 @@ impl AnimatedSprite {
         self.current_frame = 0;
     }
 }
+
+#[cfg(test)]
+mod animation_tests {
+    use super::*;
+
+    #[test]
+    fn test_clip_uses_keyframe_durations_and_events() {
+        let mut clip = AnimationClip::new("attack");
+        clip.mode = AnimationMode::Once;
+        clip.frames = vec![AnimationKeyframe::new(4, 0.1), AnimationKeyframe::new(5, 0.3), AnimationKeyframe::new(6, 0.1)];
+        clip.frames[1].events.push("hit".to_string());
+
+        let mut sprite = AnimatedSprite::default();
+        sprite.clips.push(clip);
+        assert!(sprite.play_clip("attack"));
+        assert!(!sprite.play_clip("missing"));
+        assert_eq!(sprite.get_frame_index(), 4);
+
+        sprite.update(0.15, 10);
+        assert_eq!(sprite.get_frame_index(), 5);
+        assert_eq!(sprite.take_events(), vec!["hit".to_string()]);
+
+        // The long keyframe holds for its own duration
+        sprite.update(0.2, 10);
+        assert_eq!(sprite.get_frame_index(), 5);
+        sprite.update(0.2, 10);
+        assert_eq!(sprite.get_frame_index(), 6);
+
+        sprite.update(1.0, 10);
+        assert!(!sprite.playing);
+        assert_eq!(sprite.get_frame_index(), 6);
+        assert!(sprite.take_events().is_empty());
+    }
+}
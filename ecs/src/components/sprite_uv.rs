@@ -0,0 +1,99 @@
+//! Texture coordinates of a sprite quad, shared by every sprite renderer
+
+/// Maps a point of a sprite's quad to the texture: the sprite rect (or sheet frame)
+/// mirrored by the sprite's flips and turned back for frames packed rotated.
+///
+/// `uv = orient(local) * scale + offset`, with `local` (0, 0) at the quad's top-left and
+/// (1, 1) at its bottom-right as displayed, and `orient(local)` = `(1 - local.y, local.x)`
+/// for rotated frames (the identity otherwise). A flipped axis has a negative scale and
+/// its offset at the far edge, so flips always mirror inside the rect.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpriteUv {
+    pub offset: [f32; 2],
+    pub scale: [f32; 2],
+    /// The frame is stored turned 90° clockwise in the texture (TexturePacker rotation)
+    pub rotated: bool,
+}
+
+impl SpriteUv {
+    /// The whole texture
+    pub fn whole(flip_x: bool, flip_y: bool) -> Self {
+        Self::normalized([0.0, 0.0, 1.0, 1.0], flip_x, flip_y, false)
+    }
+
+    /// `rect` ([x, y, width, height] in pixels, the layout of Sprite::sprite_rect) of a
+    /// `texture_size` texture. Width and height are the frame's as displayed; a rotated
+    /// frame covers `height` x `width` pixels of the texture from (x, y).
+    pub fn from_rect(rect: [u32; 4], texture_size: [f32; 2], flip_x: bool, flip_y: bool, rotated: bool) -> Self {
+        let [x, y, width, height] = rect.map(|value| value as f32);
+        let (width, height) = if rotated { (height, width) } else { (width, height) };
+        let [texture_width, texture_height] = texture_size.map(|size| size.max(1.0));
+        let region = [x / texture_width, y / texture_height, width / texture_width, height / texture_height];
+        Self::normalized(region, flip_x, flip_y, rotated)
+    }
+
+    /// `region` ([u, v, width, height] in UV space) with the flips of the frame as
+    /// displayed: turned frames run the displayed x axis along the texture's v axis
+    fn normalized(region: [f32; 4], flip_x: bool, flip_y: bool, rotated: bool) -> Self {
+        let (flip_u, flip_v) = if rotated { (flip_y, flip_x) } else { (flip_x, flip_y) };
+        let axis = |min: f32, size: f32, flip: bool| if flip { (min + size, -size) } else { (min, size) };
+        let (u, width) = axis(region[0], region[2], flip_u);
+        let (v, height) = axis(region[1], region[3], flip_v);
+        Self { offset: [u, v], scale: [width, height], rotated }
+    }
+
+    /// Texture coordinates of the quad point `local`
+    pub fn at(&self, local: [f32; 2]) -> [f32; 2] {
+        let [x, y] = if self.rotated { [1.0 - local[1], local[0]] } else { local };
+        [self.offset[0] + x * self.scale[0], self.offset[1] + y * self.scale[1]]
+    }
+
+    /// Texture coordinates of the quad's top-left, top-right, bottom-right and
+    /// bottom-left corners
+    pub fn corners(&self) -> [[f32; 2]; 4] {
+        [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]].map(|corner| self.at(corner))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_corners(uv: SpriteUv, expected: [[f32; 2]; 4]) {
+        for (corner, expected) in uv.corners().iter().zip(expected) {
+            assert!(
+                (corner[0] - expected[0]).abs() < 1e-6 && (corner[1] - expected[1]).abs() < 1e-6,
+                "{:?} != {:?}", uv.corners(), expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_flips_mirror_inside_the_frame() {
+        // 30x40 frame at (10, 20) of a 100x100 atlas: u 0.1..0.4, v 0.2..0.6
+        let uv = |flip_x, flip_y| SpriteUv::from_rect([10, 20, 30, 40], [100.0, 100.0], flip_x, flip_y, false);
+        assert_corners(uv(false, false), [[0.1, 0.2], [0.4, 0.2], [0.4, 0.6], [0.1, 0.6]]);
+        assert_corners(uv(true, false), [[0.4, 0.2], [0.1, 0.2], [0.1, 0.6], [0.4, 0.6]]);
+        assert_corners(uv(false, true), [[0.1, 0.6], [0.4, 0.6], [0.4, 0.2], [0.1, 0.2]]);
+        assert_corners(uv(true, true), [[0.4, 0.6], [0.1, 0.6], [0.1, 0.2], [0.4, 0.2]]);
+    }
+
+    #[test]
+    fn test_rotated_frames_turn_back_and_flip_as_displayed() {
+        // The same frame packed turned clockwise covers 40x30 pixels: u 0.1..0.5, v 0.2..0.5.
+        // Its displayed top-left pixel is stored at the region's top-right.
+        let uv = |flip_x, flip_y| SpriteUv::from_rect([10, 20, 30, 40], [100.0, 100.0], flip_x, flip_y, true);
+        assert_corners(uv(false, false), [[0.5, 0.2], [0.5, 0.5], [0.1, 0.5], [0.1, 0.2]]);
+        // Flipped horizontally the top-left shows the frame's top-right, stored bottom-right
+        assert_corners(uv(true, false), [[0.5, 0.5], [0.5, 0.2], [0.1, 0.2], [0.1, 0.5]]);
+        // Flipped vertically it shows the frame's bottom-left, stored top-left
+        assert_corners(uv(false, true), [[0.1, 0.2], [0.1, 0.5], [0.5, 0.5], [0.5, 0.2]]);
+        assert_corners(uv(true, true), [[0.1, 0.5], [0.1, 0.2], [0.5, 0.2], [0.5, 0.5]]);
+    }
+
+    #[test]
+    fn test_whole_texture() {
+        assert_corners(SpriteUv::whole(false, false), [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]]);
+        assert_corners(SpriteUv::whole(true, true), [[1.0, 1.0], [0.0, 1.0], [0.0, 0.0], [1.0, 0.0]]);
+    }
+}
@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// Size (in tiles) of the chunks used to track tilemap edits
+pub const TILEMAP_CHUNK_SIZE: u32 = 16;
+
 /// Represents a single tile in a tileset
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TileData {
@@ -16,7 +19,91 @@ pub struct TileData {
     pub height: u32,
     /// Custom properties for this tile
     #[serde(default)]
-    pub properties: HashMap<String, String>,
+    pub properties: HashMap<String, TileProperty>,
+}
+
+/// Value of a custom tile property (damage = 2, friction = 0.2, is_ladder = true)
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TileProperty {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl TileProperty {
+    /// Typed value of a property written as text ("true", "3", "0.5", anything else)
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim();
+        if let Ok(value) = text.parse::<bool>() {
+            Self::Bool(value)
+        } else if let Ok(value) = text.parse::<i64>() {
+            Self::Int(value)
+        } else if let Ok(value) = text.parse::<f64>() {
+            Self::Float(value)
+        } else {
+            Self::String(text.to_string())
+        }
+    }
+
+    /// Properties from LDtk tile custom data: a JSON object of scalars, or
+    /// "key = value" / "key: value" lines
+    pub fn parse_custom_data(data: &str) -> HashMap<String, TileProperty> {
+        if let Ok(serde_json::Value::Object(object)) = serde_json::from_str::<serde_json::Value>(data) {
+            return object
+                .into_iter()
+                .filter_map(|(key, value)| {
+                    let property = match value {
+                        serde_json::Value::Bool(b) => Self::Bool(b),
+                        serde_json::Value::Number(n) => n.as_i64().map(Self::Int).or(n.as_f64().map(Self::Float))?,
+                        serde_json::Value::String(s) => Self::String(s),
+                        _ => return None,
+                    };
+                    Some((key, property))
+                })
+                .collect();
+        }
+        data.lines()
+            .filter_map(|line| line.split_once('=').or_else(|| line.split_once(':')))
+            .map(|(key, value)| (key.trim().to_string(), Self::parse(value)))
+            .filter(|(key, _)| !key.is_empty())
+            .collect()
+    }
+}
+
+/// One frame of an animated tile
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TileAnimationFrame {
+    /// Tile shown during this frame
+    pub tile_id: u32,
+    /// How long the frame shows (seconds)
+    pub duration: f32,
+}
+
+/// Frames an animated tile loops through (water, torches)
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct TileAnimation {
+    pub frames: Vec<TileAnimationFrame>,
+}
+
+impl TileAnimation {
+    /// Tile shown `time` seconds into the shared tile clock. Every tile with this
+    /// animation shows the same frame at the same time, whichever chunk it is in.
+    pub fn tile_at(&self, time: f32) -> Option<u32> {
+        let total: f32 = self.frames.iter().map(|frame| frame.duration.max(0.0)).sum();
+        if total <= 0.0 {
+            return self.frames.first().map(|frame| frame.tile_id);
+        }
+        let mut remaining = time.rem_euclid(total);
+        for frame in &self.frames {
+            if remaining < frame.duration {
+                return Some(frame.tile_id);
+            }
+            remaining -= frame.duration.max(0.0);
+        }
+        self.frames.last().map(|frame| frame.tile_id)
+    }
 }
 
 /// Helper for deserializing TileSet with path normalization
@@ -35,6 +122,8 @@ struct TileSetRaw {
     margin: u32,
     #[serde(default)]
     tiles: HashMap<u32, TileData>,
+    #[serde(default)]
+    animations: HashMap<u32, TileAnimation>,
 }
 
 /// Tileset component containing tile data and texture information
@@ -61,6 +150,9 @@ pub struct TileSet {
     /// Individual tile data (for tiles with custom properties)
     #[serde(default)]
     pub tiles: HashMap<u32, TileData>,
+    /// Animated tiles: tile id -> frames it cycles through
+    #[serde(default)]
+    pub animations: HashMap<u32, TileAnimation>,
 }
 
 impl From<TileSetRaw> for TileSet {
@@ -76,6 +168,7 @@ impl From<TileSetRaw> for TileSet {
             spacing: raw.spacing,
             margin: raw.margin,
             tiles: raw.tiles,
+            animations: raw.animations,
         }
     }
 }
@@ -132,6 +225,7 @@ impl TileSet {
             spacing: 0,
             margin: 0,
             tiles: HashMap::new(),
+            animations: HashMap::new(),
         }
     }
 
@@ -154,6 +248,30 @@ impl TileSet {
     pub fn get_tile_data(&self, tile_id: u32) -> Option<&TileData> {
         self.tiles.get(&tile_id)
     }
+
+    /// Custom properties of a tile, if it has any
+    pub fn tile_properties(&self, tile_id: u32) -> Option<&HashMap<String, TileProperty>> {
+        self.tiles.get(&tile_id).map(|data| &data.properties).filter(|properties| !properties.is_empty())
+    }
+
+    /// Replace a tile's custom properties (creating its TileData if needed)
+    pub fn set_tile_properties(&mut self, tile_id: u32, properties: HashMap<String, TileProperty>) {
+        let (x, y) = self.get_tile_coords(tile_id).unwrap_or((0, 0));
+        let (width, height) = (self.tile_width, self.tile_height);
+        self.tiles
+            .entry(tile_id)
+            .or_insert_with(|| TileData { id: tile_id, x, y, width, height, properties: HashMap::new() })
+            .properties = properties;
+    }
+
+    pub fn is_animated(&self, tile_id: u32) -> bool {
+        self.animations.get(&tile_id).is_some_and(|animation| !animation.frames.is_empty())
+    }
+
+    /// Tile to draw for `tile_id` at `time` on the shared tile clock (itself if not animated)
+    pub fn animated_tile_id(&self, tile_id: u32, time: f32) -> u32 {
+        self.animations.get(&tile_id).and_then(|animation| animation.tile_at(time)).unwrap_or(tile_id)
+    }
 }
 
 /// Represents a single tile instance in a tilemap
@@ -241,6 +359,9 @@ pub struct Tilemap {
     /// Parallax scroll factor (for background layers)
     #[serde(default = "default_parallax_factor")]
     pub parallax_factor: (f32, f32),
+    /// Edit count per chunk (TILEMAP_CHUNK_SIZE tiles square); renderers rebuild chunks whose count changed
+    #[serde(skip)]
+    pub chunk_revisions: HashMap<(u32, u32), u64>,
 }
 
 fn default_animation_frame_rate() -> u32 { 1 }
@@ -280,6 +401,7 @@ impl Tilemap {
             visible: true,
             opacity: 1.0,
             parallax_factor: (1.0, 1.0),
+            chunk_revisions: HashMap::new(),
         }
     }
 
@@ -309,12 +431,34 @@ impl Tilemap {
         let index = (y * self.width + x) as usize;
         if let Some(t) = self.tiles.get_mut(index) {
             *t = tile;
+            *self.chunk_revisions.entry(Self::chunk_of(x, y)).or_default() += 1;
             true
         } else {
             false
         }
     }
 
+    /// Custom properties of the tile at a position (None for empty tiles or tiles without any)
+    pub fn get_tile_properties<'a>(&self, tileset: &'a TileSet, x: u32, y: u32) -> Option<&'a HashMap<String, TileProperty>> {
+        let tile = self.get_tile(x, y).filter(|tile| !tile.is_empty())?;
+        tileset.tile_properties(tile.tile_id)
+    }
+
+    /// Chunk containing a tile
+    pub fn chunk_of(x: u32, y: u32) -> (u32, u32) {
+        (x / TILEMAP_CHUNK_SIZE, y / TILEMAP_CHUNK_SIZE)
+    }
+
+    /// Number of edits made to a chunk through `set_tile`
+    pub fn chunk_revision(&self, chunk: (u32, u32)) -> u64 {
+        self.chunk_revisions.get(&chunk).copied().unwrap_or(0)
+    }
+
+    /// Number of chunks along each axis
+    pub fn chunk_count(&self) -> (u32, u32) {
+        (self.width.div_ceil(TILEMAP_CHUNK_SIZE), self.height.div_ceil(TILEMAP_CHUNK_SIZE))
+    }
+
     /// Set a tile ID at the specified position
     pub fn set_tile_id(&mut self, x: u32, y: u32, tile_id: u32) -> bool {
         self.set_tile(x, y, Tile::new(tile_id))
@@ -325,6 +469,12 @@ impl Tilemap {
         for tile in &mut self.tiles {
             *tile = Tile::default();
         }
+        let (chunks_x, chunks_y) = self.chunk_count();
+        for chunk_y in 0..chunks_y {
+            for chunk_x in 0..chunks_x {
+                *self.chunk_revisions.entry((chunk_x, chunk_y)).or_default() += 1;
+            }
+        }
     }
 
     /// Get the world position of a tile (in pixels, assuming tile size)
@@ -444,6 +594,20 @@ mod tests {
         assert_eq!(tile.tile_id, 99);
     }
 
+    #[test]
+    fn test_set_tile_bumps_chunk_revision() {
+        let mut tilemap = Tilemap::new("test_layer", "tileset_1", 40, 20);
+        assert_eq!(tilemap.chunk_count(), (3, 2));
+
+        tilemap.set_tile_id(17, 3, 5);
+        assert_eq!(tilemap.chunk_revision((1, 0)), 1);
+        assert_eq!(tilemap.chunk_revision((0, 0)), 0);
+
+        // Out of bounds writes don't touch any chunk
+        tilemap.set_tile_id(40, 3, 5);
+        assert_eq!(tilemap.chunk_revisions.len(), 1);
+    }
+
     #[test]
     fn test_tilemap_clear() {
         let mut tilemap = Tilemap::new("test_layer", "tileset_1", 3, 3);
@@ -528,6 +692,44 @@ mod tests {
         assert_eq!(tileset.get_tile_coords(4), Some((1, 19))); // 1 + 16 + 2
     }
 
+    #[test]
+    fn test_tile_animation_follows_shared_clock() {
+        let mut tileset = TileSet::new("water", "water.png", "water", 16, 16, 4, 16);
+        tileset.animations.insert(1, TileAnimation {
+            frames: vec![
+                TileAnimationFrame { tile_id: 1, duration: 0.5 },
+                TileAnimationFrame { tile_id: 2, duration: 0.25 },
+            ],
+        });
+
+        assert!(tileset.is_animated(1));
+        assert!(!tileset.is_animated(2));
+        assert_eq!(tileset.animated_tile_id(1, 0.0), 1);
+        assert_eq!(tileset.animated_tile_id(1, 0.6), 2);
+        // Loops every 0.75s
+        assert_eq!(tileset.animated_tile_id(1, 0.8), 1);
+        assert_eq!(tileset.animated_tile_id(3, 0.6), 3);
+    }
+
+    #[test]
+    fn test_tile_properties() {
+        let mut tileset = TileSet::new("terrain", "terrain.png", "terrain", 16, 16, 4, 16);
+        tileset.set_tile_properties(5, TileProperty::parse_custom_data("damage = 2\nfriction: 0.5\nis_ladder=true"));
+        tileset.set_tile_properties(6, TileProperty::parse_custom_data(r#"{"name": "spikes", "damage": 3}"#));
+
+        let mut tilemap = Tilemap::new("layer", "terrain", 4, 4);
+        tilemap.set_tile_id(1, 2, 5);
+        tilemap.set_tile_id(2, 2, 6);
+
+        let properties = tilemap.get_tile_properties(&tileset, 1, 2).unwrap();
+        assert_eq!(properties["damage"], TileProperty::Int(2));
+        assert_eq!(properties["friction"], TileProperty::Float(0.5));
+        assert_eq!(properties["is_ladder"], TileProperty::Bool(true));
+        let properties = tilemap.get_tile_properties(&tileset, 2, 2).unwrap();
+        assert_eq!(properties["name"], TileProperty::String("spikes".to_string()));
+        assert!(tilemap.get_tile_properties(&tileset, 0, 0).is_none());
+    }
+
     #[test]
     fn test_tilemap_renderer_default() {
         let renderer = TilemapRenderer::default();
@@ -0,0 +1,112 @@
+use crate::EntityTag;
+use serde::{Deserialize, Serialize};
+
+/// Area of a TriggerZone, centered on the entity (plus the zone's offset)
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TriggerZoneShape {
+    Box { width: f32, height: f32 },
+    Circle { radius: f32 },
+}
+
+impl Default for TriggerZoneShape {
+    fn default() -> Self {
+        TriggerZoneShape::Box { width: 2.0, height: 2.0 }
+    }
+}
+
+/// Trigger Zone component: an area that reports colliders entering and leaving it,
+/// without a collider of its own and without pushing anything. Evaluated after every
+/// physics step with the backend's overlap queries.
+///
+/// Enter/exit call `on_enter(entity)` / `on_exit(entity)` in the zone's own script and,
+/// when `event` is set, emit that event on the global event bus (`Events.on`) with
+/// `{ zone, entity, phase }`. A one-shot zone fires on the first enter only and then
+/// stays off (`fired`) until the scene is reloaded.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TriggerZone {
+    #[serde(default)]
+    pub shape: TriggerZoneShape,
+    #[serde(default)]
+    pub offset: [f32; 2],
+    /// Only entities with this tag count (any entity when None)
+    #[serde(default)]
+    pub tag: Option<EntityTag>,
+    /// Only entities on these layers count
+    #[serde(default = "default_layer_mask")]
+    pub layer_mask: u32,
+    #[serde(default)]
+    pub one_shot: bool,
+    /// Set when a one-shot zone has fired
+    #[serde(default)]
+    pub fired: bool,
+    #[serde(default)]
+    pub on_enter: Option<String>,
+    #[serde(default)]
+    pub on_exit: Option<String>,
+    /// Global event emitted on enter and exit
+    #[serde(default)]
+    pub event: Option<String>,
+}
+
+fn default_layer_mask() -> u32 {
+    u32::MAX
+}
+
+impl Default for TriggerZone {
+    fn default() -> Self {
+        Self {
+            shape: TriggerZoneShape::default(),
+            offset: [0.0, 0.0],
+            tag: None,
+            layer_mask: default_layer_mask(),
+            one_shot: false,
+            fired: false,
+            on_enter: None,
+            on_exit: None,
+            event: None,
+        }
+    }
+}
+
+impl TriggerZone {
+    /// Whether the zone still reports enters and exits
+    pub fn is_armed(&self) -> bool {
+        !(self.one_shot && self.fired)
+    }
+
+    /// Whether an entity with `tag` passes the zone's tag filter
+    pub fn accepts_tag(&self, tag: Option<&EntityTag>) -> bool {
+        match &self.tag {
+            Some(filter) => tag == Some(filter),
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_filter() {
+        assert!(TriggerZone::default().accepts_tag(None));
+        assert!(TriggerZone::default().accepts_tag(Some(&EntityTag::Item)));
+
+        let zone = TriggerZone { tag: Some(EntityTag::Player), ..Default::default() };
+        assert!(zone.accepts_tag(Some(&EntityTag::Player)));
+        assert!(!zone.accepts_tag(Some(&EntityTag::Item)));
+        assert!(!zone.accepts_tag(None));
+    }
+
+    #[test]
+    fn test_one_shot_zone_disarms_once_fired() {
+        let mut zone = TriggerZone { one_shot: true, ..Default::default() };
+        assert!(zone.is_armed());
+        zone.fired = true;
+        assert!(!zone.is_armed());
+        // `fired` means nothing to a zone that fires every time
+        zone.one_shot = false;
+        assert!(zone.is_armed());
+    }
+}
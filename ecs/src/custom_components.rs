@@ -0,0 +1,391 @@
+//! Custom components: gameplay data ("Health", "Inventory") whose schema is declared
+//! from Lua instead of Rust.
+//!
+//! `Component.define("Health", { max = "float", current = "float", regen = "bool" })`
+//! puts a `ComponentSchema` in the `ComponentRegistry`. Instances are JSON objects kept
+//! on `CustomWorld::custom_components` by component name, then entity, so they are
+//! saved with the scene like any other component and finding every entity with one is
+//! a single map lookup. Schemas can change between sessions: `conform` gives stored
+//! instances defaults for added fields and drops removed ones, with a warning, so old
+//! scenes keep loading.
+
+use crate::{CustomEntity, CustomWorld};
+use serde_json::{json, Map, Value as Json};
+use std::collections::{BTreeMap, HashMap};
+
+/// Type of a custom component field, named in `Component.define` ("float", "bool", ...)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldType {
+    Float,
+    Int,
+    Bool,
+    String,
+    /// { x, y }
+    Vector2,
+    /// { r, g, b, a }, 0-1
+    Color,
+}
+
+impl FieldType {
+    pub const ALL: [FieldType; 6] = [
+        FieldType::Float,
+        FieldType::Int,
+        FieldType::Bool,
+        FieldType::String,
+        FieldType::Vector2,
+        FieldType::Color,
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().as_str() {
+            "float" | "number" => Some(FieldType::Float),
+            "int" | "integer" => Some(FieldType::Int),
+            "bool" | "boolean" => Some(FieldType::Bool),
+            "string" => Some(FieldType::String),
+            "vector2" | "vec2" => Some(FieldType::Vector2),
+            "color" => Some(FieldType::Color),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            FieldType::Float => "float",
+            FieldType::Int => "int",
+            FieldType::Bool => "bool",
+            FieldType::String => "string",
+            FieldType::Vector2 => "vector2",
+            FieldType::Color => "color",
+        }
+    }
+
+    /// Value of a field nobody set
+    pub fn default_value(&self) -> Json {
+        match self {
+            FieldType::Float => json!(0.0),
+            FieldType::Int => json!(0),
+            FieldType::Bool => json!(false),
+            FieldType::String => json!(""),
+            FieldType::Vector2 => json!({ "x": 0.0, "y": 0.0 }),
+            FieldType::Color => json!({ "r": 1.0, "g": 1.0, "b": 1.0, "a": 1.0 }),
+        }
+    }
+
+    /// `value` as this type (a whole float is an int, an int is a float, a color may
+    /// leave out alpha), or None if it isn't one
+    pub fn coerce(&self, value: &Json) -> Option<Json> {
+        let number = |value: Option<&Json>| value.and_then(Json::as_f64);
+        match self {
+            FieldType::Float => value.as_f64().map(|n| json!(n)),
+            FieldType::Int => match value.as_i64() {
+                Some(i) => Some(json!(i)),
+                None => value.as_f64().filter(|n| n.fract() == 0.0).map(|n| json!(n as i64)),
+            },
+            FieldType::Bool => value.as_bool().map(Json::Bool),
+            FieldType::String => value.as_str().map(|s| json!(s)),
+            FieldType::Vector2 => {
+                let x = number(value.get("x"))?;
+                let y = number(value.get("y"))?;
+                Some(json!({ "x": x, "y": y }))
+            }
+            FieldType::Color => {
+                let r = number(value.get("r"))?;
+                let g = number(value.get("g"))?;
+                let b = number(value.get("b"))?;
+                let a = match value.get("a") {
+                    Some(a) => a.as_f64()?,
+                    None => 1.0,
+                };
+                Some(json!({ "r": r, "g": g, "b": b, "a": a }))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldSchema {
+    pub name: String,
+    pub field_type: FieldType,
+    pub default: Json,
+}
+
+impl FieldSchema {
+    /// A field with the type's default, or `default` when it has that type
+    pub fn new(name: &str, field_type: FieldType, default: Option<&Json>) -> Result<Self, String> {
+        let default = match default {
+            Some(value) => field_type.coerce(value).ok_or_else(|| {
+                format!("default of '{}' must be a {}, got {}", name, field_type.name(), value)
+            })?,
+            None => field_type.default_value(),
+        };
+        Ok(Self { name: name.to_string(), field_type, default })
+    }
+}
+
+/// Fields of a custom component, sorted by name
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComponentSchema {
+    pub name: String,
+    pub fields: Vec<FieldSchema>,
+}
+
+impl ComponentSchema {
+    pub fn new(name: &str, mut fields: Vec<FieldSchema>) -> Self {
+        fields.sort_by(|a, b| a.name.cmp(&b.name));
+        fields.dedup_by(|a, b| a.name == b.name);
+        Self { name: name.to_string(), fields }
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldSchema> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// A new instance: every field at its default
+    pub fn default_value(&self) -> Json {
+        Json::Object(self.fields.iter().map(|field| (field.name.clone(), field.default.clone())).collect())
+    }
+
+    /// `base` (an instance) with the fields of `values` set. Fails on a field the
+    /// schema doesn't have or a value of the wrong type, leaving nothing half-applied.
+    pub fn apply(&self, base: &Json, values: &Json) -> Result<Json, String> {
+        let Json::Object(values) = values else {
+            return Err(format!("{} values must be a table of fields", self.name));
+        };
+        let mut result = base.clone();
+        for (name, value) in values {
+            let field = self.field(name)
+                .ok_or_else(|| format!("{} has no field '{}'", self.name, name))?;
+            let value = field.field_type.coerce(value).ok_or_else(|| {
+                format!("{}.{} must be a {}, got {}", self.name, name, field.field_type.name(), value)
+            })?;
+            if let Json::Object(fields) = &mut result {
+                fields.insert(name.clone(), value);
+            }
+        }
+        Ok(result)
+    }
+
+    /// Bring a stored instance up to this schema: fields added since get their
+    /// default, fields removed since are dropped, values of a changed type are reset.
+    /// Returns what was changed, for a warning.
+    pub fn conform(&self, value: &mut Json) -> Vec<String> {
+        let mut changes = Vec::new();
+        let mut stored = match value.take() {
+            Json::Object(fields) => fields,
+            other => {
+                if !other.is_null() {
+                    changes.push(format!("{} was not a table of fields; reset to defaults", self.name));
+                }
+                Map::new()
+            }
+        };
+
+        let mut fields = Map::new();
+        for field in &self.fields {
+            let conformed = match stored.remove(&field.name) {
+                Some(old) => field.field_type.coerce(&old).unwrap_or_else(|| {
+                    changes.push(format!(
+                        "{}.{} is now a {}; {} reset to the default",
+                        self.name, field.name, field.field_type.name(), old
+                    ));
+                    field.default.clone()
+                }),
+                None => {
+                    changes.push(format!("{}.{} added with its default", self.name, field.name));
+                    field.default.clone()
+                }
+            };
+            fields.insert(field.name.clone(), conformed);
+        }
+        for name in stored.keys() {
+            changes.push(format!("{}.{} is no longer defined; dropped", self.name, name));
+        }
+        *value = Json::Object(fields);
+        changes
+    }
+}
+
+/// Custom component schemas by name
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ComponentRegistry {
+    schemas: BTreeMap<String, ComponentSchema>,
+}
+
+impl ComponentRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace a schema. Returns the one it replaced.
+    pub fn define(&mut self, schema: ComponentSchema) -> Option<ComponentSchema> {
+        self.schemas.insert(schema.name.clone(), schema)
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ComponentSchema> {
+        self.schemas.get(name)
+    }
+
+    /// Schemas sorted by name
+    pub fn schemas(&self) -> impl Iterator<Item = &ComponentSchema> {
+        self.schemas.values()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.schemas.clear();
+    }
+
+    /// Conform every custom component instance in `world` that has a schema here (see
+    /// `ComponentSchema::conform`). Instances of components without one are kept as they
+    /// are: a script may define it later. Returns one warning per distinct change, with
+    /// the number of entities it touched.
+    pub fn conform_world(&self, world: &mut CustomWorld) -> Vec<String> {
+        let mut changes: BTreeMap<String, usize> = BTreeMap::new();
+        for (name, instances) in &mut world.custom_components {
+            let Some(schema) = self.schemas.get(name) else {
+                continue;
+            };
+            for value in instances.values_mut() {
+                for change in schema.conform(value) {
+                    *changes.entry(change).or_default() += 1;
+                }
+            }
+        }
+        changes.into_iter()
+            .map(|(change, count)| match count {
+                1 => change,
+                n => format!("{} ({} entities)", change, n),
+            })
+            .collect()
+    }
+}
+
+impl CustomWorld {
+    pub fn custom_component(&self, entity: CustomEntity, name: &str) -> Option<&Json> {
+        self.custom_components.get(name)?.get(&entity)
+    }
+
+    pub fn custom_component_mut(&mut self, entity: CustomEntity, name: &str) -> Option<&mut Json> {
+        self.custom_components.get_mut(name)?.get_mut(&entity)
+    }
+
+    pub fn set_custom_component(&mut self, entity: CustomEntity, name: &str, value: Json) {
+        self.custom_components.entry(name.to_string()).or_default().insert(entity, value);
+    }
+
+    pub fn remove_custom_component(&mut self, entity: CustomEntity, name: &str) -> Option<Json> {
+        let instances = self.custom_components.get_mut(name)?;
+        let removed = instances.remove(&entity);
+        if instances.is_empty() {
+            self.custom_components.remove(name);
+        }
+        removed
+    }
+
+    pub fn has_custom_component(&self, entity: CustomEntity, name: &str) -> bool {
+        self.custom_component(entity, name).is_some()
+    }
+
+    /// Entities with an instance of component `name`, sorted
+    pub fn entities_with_custom_component(&self, name: &str) -> Vec<CustomEntity> {
+        let mut entities: Vec<CustomEntity> = self.custom_components.get(name)
+            .map(|instances| instances.keys().copied().collect())
+            .unwrap_or_default();
+        entities.sort_unstable();
+        entities
+    }
+
+    /// Names of the custom components on `entity`, sorted
+    pub fn custom_component_names(&self, entity: CustomEntity) -> Vec<String> {
+        let mut names: Vec<String> = self.custom_components.iter()
+            .filter(|(_, instances)| instances.contains_key(&entity))
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Every custom component of `entity`, by name (save games)
+    pub fn custom_components_of(&self, entity: CustomEntity) -> BTreeMap<String, Json> {
+        self.custom_components.iter()
+            .filter_map(|(name, instances)| Some((name.clone(), instances.get(&entity)?.clone())))
+            .collect()
+    }
+
+    pub(crate) fn remove_custom_components(&mut self, entity: CustomEntity) {
+        self.custom_components.retain(|_, instances| {
+            instances.remove(&entity);
+            !instances.is_empty()
+        });
+    }
+}
+
+/// Scene file form: component name -> (entity, instance) pairs
+pub(crate) type SavedCustomComponents = BTreeMap<String, Vec<(CustomEntity, Json)>>;
+
+pub(crate) fn to_saved(components: &HashMap<String, HashMap<CustomEntity, Json>>) -> SavedCustomComponents {
+    components.iter()
+        .map(|(name, instances)| {
+            (name.clone(), crate::ordered::sorted_entries(instances))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health() -> ComponentSchema {
+        ComponentSchema::new("Health", vec![
+            FieldSchema::new("max", FieldType::Float, Some(&json!(100))).unwrap(),
+            FieldSchema::new("current", FieldType::Float, None).unwrap(),
+            FieldSchema::new("regen", FieldType::Bool, None).unwrap(),
+        ])
+    }
+
+    #[test]
+    fn test_apply_checks_fields_and_types() {
+        let schema = health();
+        let base = schema.default_value();
+        assert_eq!(base, json!({ "current": 0.0, "max": 100.0, "regen": false }));
+
+        let hurt = schema.apply(&base, &json!({ "current": 40 })).unwrap();
+        assert_eq!(hurt["current"], json!(40.0));
+        assert_eq!(hurt["max"], json!(100.0));
+        assert!(schema.apply(&base, &json!({ "armor": 5 })).unwrap_err().contains("no field 'armor'"));
+        assert!(schema.apply(&base, &json!({ "regen": "yes" })).unwrap_err().contains("must be a bool"));
+        assert!(FieldSchema::new("count", FieldType::Int, Some(&json!(1.5))).is_err());
+    }
+
+    #[test]
+    fn test_schema_changes_keep_old_scenes_loading() {
+        let mut world = CustomWorld::new();
+        for _ in 0..2 {
+            let entity = world.spawn();
+            world.set_custom_component(entity, "Health", json!({ "max": 50.0, "current": 10.0, "shield": 3 }));
+        }
+        let saved = world.save_to_json().unwrap();
+        let mut world = CustomWorld::new();
+        world.load_from_json(&saved).unwrap();
+        assert_eq!(world.entities_with_custom_component("Health"), [0, 1]);
+
+        // "shield" was removed from the definition, "regen" added
+        let mut registry = ComponentRegistry::new();
+        registry.define(health());
+        let warnings = registry.conform_world(&mut world);
+        assert_eq!(warnings, [
+            "Health.regen added with its default (2 entities)",
+            "Health.shield is no longer defined; dropped (2 entities)",
+        ]);
+        assert_eq!(world.custom_component(1, "Health"), Some(&json!({ "current": 10.0, "max": 50.0, "regen": false })));
+        assert!(registry.conform_world(&mut world).is_empty());
+
+        world.despawn(0);
+        assert_eq!(world.entities_with_custom_component("Health"), [1]);
+        assert_eq!(world.remove_custom_component(1, "Health").map(|v| v["max"].clone()), Some(json!(50.0)));
+        assert!(world.custom_components.is_empty());
+    }
+}
@@ -0,0 +1,242 @@
+//! Dynamic game state: what a save game stores on top of the authored scene.
+//!
+//! The scene file is the level as authored; a snapshot holds only the entities marked
+//! with a `SaveState` (the parts it selects), keyed by their `Guid`, plus the Guids of
+//! the ones destroyed since the scene loaded. Applying it to a freshly loaded copy of
+//! the scene finds each entity by Guid, so it still works after the scene was edited:
+//! entities the save knows but the scene no longer has are reported and skipped, and
+//! entities added to the scene since are left as authored.
+
+use crate::{
+    AnimatedSprite, CustomEntity, CustomWorld, Guid, Rigidbody2D, SaveState, ScriptParameter, Sprite, Transform,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// Saved state of one SaveState entity (None for the parts it doesn't save)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EntitySnapshot {
+    pub guid: Guid,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<Transform>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprite: Option<Sprite>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub velocity: Option<(f32, f32)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rigidbody: Option<Rigidbody2D>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub animated_sprite: Option<AnimatedSprite>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_enabled: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_parameters: Option<HashMap<String, ScriptParameter>>,
+    /// Custom components by name; ones the entity has but the save doesn't are removed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_components: Option<BTreeMap<String, serde_json::Value>>,
+}
+
+/// Snapshot made by `World::capture_dynamic_state`
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DynamicState {
+    #[serde(default)]
+    pub entities: Vec<EntitySnapshot>,
+    /// SaveState entities destroyed before the save (despawned again on load)
+    #[serde(default)]
+    pub destroyed: Vec<Guid>,
+}
+
+impl CustomWorld {
+    /// Give every SaveState entity without a Guid a new one. Returns how many got one.
+    /// Call before saving a scene so its entities can be found again by save games.
+    pub fn assign_guids(&mut self) -> usize {
+        let missing: Vec<CustomEntity> = self.save_states.keys()
+            .filter(|entity| !self.guids.contains_key(entity))
+            .copied()
+            .collect();
+        for &entity in &missing {
+            self.guids.insert(entity, Guid::new());
+        }
+        missing.len()
+    }
+
+    /// Snapshot the parts of every SaveState entity its component selects. Entities
+    /// spawned at runtime get a Guid here, but their scene doesn't have it: they are
+    /// reported as missing when the save is loaded.
+    pub fn capture_dynamic_state(&mut self) -> DynamicState {
+        self.assign_guids();
+        let mut entities: Vec<(&CustomEntity, &SaveState)> = self.save_states.iter().collect();
+        entities.sort_unstable_by_key(|(entity, _)| **entity);
+
+        let snapshots = entities.into_iter().map(|(entity, save)| {
+            let script = self.scripts.get(entity).filter(|_| save.script);
+            EntitySnapshot {
+                guid: self.guids[entity].clone(),
+                transform: self.transforms.get(entity).filter(|_| save.transform).cloned(),
+                active: self.active.get(entity).filter(|_| save.active).copied(),
+                sprite: self.sprites.get(entity).filter(|_| save.sprite).cloned(),
+                velocity: self.velocities.get(entity).filter(|_| save.physics).copied(),
+                rigidbody: self.rigidbodies.get(entity).filter(|_| save.physics).cloned(),
+                animated_sprite: self.animated_sprites.get(entity).filter(|_| save.animation).cloned(),
+                script_enabled: script.map(|script| script.enabled),
+                // Entity ids differ in the next session
+                script_parameters: script.map(|script| {
+                    script.parameters.iter()
+                        .filter(|(_, value)| !matches!(value, ScriptParameter::Entity(_)))
+                        .map(|(name, value)| (name.clone(), value.clone()))
+                        .collect()
+                }),
+                custom_components: save.custom_components.then(|| self.custom_components_of(*entity)),
+            }
+        }).collect();
+
+        DynamicState { entities: snapshots, destroyed: self.destroyed_guids.clone() }
+    }
+
+    /// Put a snapshot back on the entities with the same Guids (despawning the ones it
+    /// saw destroyed). Returns the Guids of saved entities this world doesn't have.
+    pub fn apply_dynamic_state(&mut self, state: &DynamicState) -> Vec<Guid> {
+        let by_guid: HashMap<Guid, CustomEntity> = self.guids.iter().map(|(entity, guid)| (guid.clone(), *entity)).collect();
+
+        for guid in &state.destroyed {
+            if let Some(&entity) = by_guid.get(guid) {
+                self.despawn(entity);
+            }
+        }
+
+        let mut missing = Vec::new();
+        for snapshot in &state.entities {
+            let Some(&entity) = by_guid.get(&snapshot.guid) else {
+                missing.push(snapshot.guid.clone());
+                continue;
+            };
+            // Despawned above along with a destroyed parent
+            if !self.guids.contains_key(&entity) {
+                continue;
+            }
+            if let Some(transform) = &snapshot.transform {
+                self.transforms.insert(entity, transform.clone());
+            }
+            if let Some(active) = snapshot.active {
+                self.active.insert(entity, active);
+            }
+            if let Some(sprite) = &snapshot.sprite {
+                self.sprites.insert(entity, sprite.clone());
+            }
+            if let Some(velocity) = snapshot.velocity {
+                self.velocities.insert(entity, velocity);
+            }
+            if let Some(rigidbody) = &snapshot.rigidbody {
+                self.rigidbodies.insert(entity, rigidbody.clone());
+            }
+            if let Some(animated_sprite) = &snapshot.animated_sprite {
+                self.animated_sprites.insert(entity, animated_sprite.clone());
+            }
+            if let Some(script) = self.scripts.get_mut(&entity) {
+                if let Some(enabled) = snapshot.script_enabled {
+                    script.enabled = enabled;
+                }
+                if let Some(parameters) = &snapshot.script_parameters {
+                    script.parameters.extend(parameters.iter().map(|(name, value)| (name.clone(), value.clone())));
+                }
+            }
+            if let Some(components) = &snapshot.custom_components {
+                for name in self.custom_component_names(entity) {
+                    if !components.contains_key(&name) {
+                        self.remove_custom_component(entity, &name);
+                    }
+                }
+                for (name, value) in components {
+                    self.set_custom_component(entity, name, value.clone());
+                }
+            }
+        }
+        missing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Script;
+
+    fn level() -> CustomWorld {
+        let mut world = CustomWorld::new();
+        for name in ["Player", "Chest", "Coin", "Wall"] {
+            let entity = world.spawn();
+            world.transforms.insert(entity, Transform::default());
+            world.names.insert(entity, name.to_string());
+            if name != "Wall" {
+                world.save_states.insert(entity, SaveState::default());
+            }
+        }
+        world.scripts.insert(1, Script {
+            script_name: "chest".to_string(),
+            enabled: true,
+            parameters: HashMap::from([
+                ("opened".to_string(), ScriptParameter::Bool(false)),
+                ("target".to_string(), ScriptParameter::Entity(Some(0))),
+            ]),
+            lifecycle_state: Default::default(),
+        });
+        world.assign_guids();
+        world
+    }
+
+    #[test]
+    fn test_capture_and_apply_by_guid() {
+        let authored = level().save_to_json().unwrap();
+        let mut world = CustomWorld::new();
+        world.load_from_json(&authored).unwrap();
+
+        // Play: move the player, open the chest, pick up the coin
+        world.transforms.get_mut(&0).unwrap().position = [5.0, 6.0, 0.0];
+        let chest = world.scripts.get_mut(&1).unwrap();
+        chest.parameters.insert("opened".to_string(), ScriptParameter::Bool(true));
+        chest.parameters.insert("target".to_string(), ScriptParameter::Entity(None));
+        world.despawn(2);
+        let state: DynamicState = serde_json::from_str(&serde_json::to_string(&world.capture_dynamic_state()).unwrap()).unwrap();
+        assert_eq!(state.entities.len(), 2, "the player and the chest (the wall isn't saved)");
+        assert_eq!(state.destroyed.len(), 1);
+
+        // The scene was edited since: the chest is gone and a door was added
+        let mut edited = CustomWorld::new();
+        edited.load_from_json(&authored).unwrap();
+        edited.despawn(1);
+        let door = edited.spawn();
+        edited.save_states.insert(door, SaveState::default());
+        edited.assign_guids();
+        let mut world = CustomWorld::new();
+        world.load_from_json(&edited.save_to_json().unwrap()).unwrap();
+        // Entity ids differ from the ones saved (an additive load)
+        let mut merged = CustomWorld::new();
+        merged.spawn();
+        let ids = merged.merge(world);
+        let &[player, coin, _wall, door] = ids.as_slice() else { panic!("{:?}", ids) };
+
+        let missing = merged.apply_dynamic_state(&state);
+        assert_eq!(missing.len(), 1, "the chest is no longer in the scene");
+        assert_eq!(merged.transforms[&player].position, [5.0, 6.0, 0.0]);
+        assert!(!merged.transforms.contains_key(&coin), "the collected coin stays collected");
+        assert!(merged.save_states.contains_key(&door), "new authored entities are kept");
+        assert_eq!(merged.destroyed_guids.len(), 1, "saving again still removes the coin");
+    }
+
+    #[test]
+    fn test_script_parameters_except_entities() {
+        let mut world = level();
+        let chest = world.scripts.get_mut(&1).unwrap();
+        chest.parameters.insert("opened".to_string(), ScriptParameter::Bool(true));
+        chest.parameters.insert("target".to_string(), ScriptParameter::Entity(None));
+        let state = world.capture_dynamic_state();
+
+        let mut fresh = level();
+        fresh.guids = world.guids.clone();
+        assert!(fresh.apply_dynamic_state(&state).is_empty());
+        let parameters = &fresh.scripts[&1].parameters;
+        assert!(matches!(parameters["opened"], ScriptParameter::Bool(true)));
+        assert!(matches!(parameters["target"], ScriptParameter::Entity(Some(0))));
+    }
+}
@@ -0,0 +1,530 @@
+//! World-space transforms through the parent chain.
+//!
+//! `Transform` is always local (relative to the parent). `GlobalTransform` is the
+//! propagated world matrix, filled in once per frame by the engine's transform system;
+//! renderers, physics and cameras read it through `world_transform`. Queries made
+//! mid-frame (scripts, reparenting) walk the chain instead so they never see a stale
+//! parent.
+
+use crate::{CustomEntity, CustomWorld, GlobalTransform, ScriptParameter, Transform};
+use glam::{EulerRot, Mat4, Quat, Vec3};
+use std::collections::HashMap;
+
+/// Calls `$apply!` with every per-entity component map except the hierarchy
+/// (parents/children) and the computed global_transforms
+macro_rules! component_maps {
+    ($apply:ident) => {
+        $apply!(
+            transforms, velocities, rigidbodies, sprites, colliders, colliders_3d, polygon_colliders,
+            meshes, cameras, tags, scripts, active, layers, names, sprite_sheets, animated_sprites,
+            tilemaps, tilesets, tilemap_renderers, maps, grids, world_uis, ldtk_maps, tilemap_colliders,
+            ldtk_intgrid_colliders, model_3ds, ldtk_entities, sprite_materials,
+            editor_icons, trigger_zones, color_fades, save_states, guids,
+        )
+    };
+}
+
+/// Next free numbered name, Unity style: "Enemy" -> "Enemy (1)", "Enemy (1)" -> "Enemy (2)"
+pub fn numbered_name(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    let (base, mut number) = match name.strip_suffix(')').and_then(|rest| rest.rsplit_once(" (")) {
+        Some((base, n)) if n.parse::<u32>().is_ok() => (base, n.parse::<u32>().unwrap_or(0) + 1),
+        _ => (name, 1),
+    };
+    loop {
+        let candidate = format!("{} ({})", base, number);
+        if !taken(&candidate) {
+            return candidate;
+        }
+        number += 1;
+    }
+}
+
+impl Transform {
+    /// Local matrix: translation * rotation (XYZ euler, degrees) * scale
+    pub fn matrix(&self) -> Mat4 {
+        let rotation = Quat::from_euler(
+            EulerRot::XYZ,
+            self.rotation[0].to_radians(),
+            self.rotation[1].to_radians(),
+            self.rotation[2].to_radians(),
+        );
+        Mat4::from_scale_rotation_translation(Vec3::from(self.scale), rotation, Vec3::from(self.position))
+    }
+
+    /// Transform with the translation, rotation and scale of `matrix` (shear is dropped)
+    pub fn from_matrix(matrix: Mat4) -> Self {
+        let (scale, rotation, translation) = matrix.to_scale_rotation_translation();
+        let (x, y, z) = rotation.to_euler(EulerRot::XYZ);
+        Self {
+            position: translation.to_array(),
+            rotation: [x.to_degrees(), y.to_degrees(), z.to_degrees()],
+            scale: scale.to_array(),
+        }
+    }
+}
+
+impl GlobalTransform {
+    pub fn from_matrix(matrix: Mat4) -> Self {
+        Self { matrix: matrix.to_cols_array(), local: None }
+    }
+
+    pub fn to_mat4(&self) -> Mat4 {
+        Mat4::from_cols_array(&self.matrix)
+    }
+
+    /// World-space position
+    pub fn position(&self) -> [f32; 3] {
+        [self.matrix[12], self.matrix[13], self.matrix[14]]
+    }
+}
+
+impl CustomWorld {
+    /// World matrix of an entity, computed from the parent chain now (entities without a
+    /// Transform count as identity)
+    pub fn world_matrix(&self, entity: CustomEntity) -> Mat4 {
+        let mut matrix = Mat4::IDENTITY;
+        let mut current = Some(entity);
+        let mut depth = 0;
+        while let Some(e) = current {
+            if let Some(transform) = self.transforms.get(&e) {
+                matrix = transform.matrix() * matrix;
+            }
+            current = self.parents.get(&e).copied();
+            depth += 1;
+            if depth > self.parents.len() {
+                break; // Cycle guard
+            }
+        }
+        matrix
+    }
+
+    /// World matrix of the entity's parent (identity for roots)
+    pub fn parent_world_matrix(&self, entity: CustomEntity) -> Mat4 {
+        self.get_parent(entity).map_or(Mat4::IDENTITY, |parent| self.world_matrix(parent))
+    }
+
+    /// World-space transform for drawing and collision: roots return their local
+    /// transform as is, children the propagated GlobalTransform (or the parent chain
+    /// when propagation hasn't reached them yet)
+    pub fn world_transform(&self, entity: CustomEntity) -> Option<Transform> {
+        let local = self.transforms.get(&entity)?;
+        if !self.parents.contains_key(&entity) {
+            return Some(local.clone());
+        }
+        let matrix = match self.global_transforms.get(&entity) {
+            Some(global) if global.local.as_ref() == Some(local) => global.to_mat4(),
+            _ => self.world_matrix(entity),
+        };
+        Some(Transform::from_matrix(matrix))
+    }
+
+    /// World-space position, computed from the parent chain now
+    pub fn world_position(&self, entity: CustomEntity) -> Option<[f32; 3]> {
+        self.transforms.get(&entity)?;
+        Some(self.world_matrix(entity).w_axis.truncate().to_array())
+    }
+
+    /// Move an entity to a world-space position by back-solving its local position
+    pub fn set_world_position(&mut self, entity: CustomEntity, position: [f32; 3]) {
+        let local = self.parent_world_matrix(entity).inverse().transform_point3(Vec3::from(position));
+        if let Some(transform) = self.transforms.get_mut(&entity) {
+            transform.position = local.to_array();
+        }
+    }
+
+    /// Reparent an entity (None: make it a root). Unless `keep_local`, its local
+    /// transform is rewritten so it stays where it is in the world.
+    /// Parenting an entity under itself or one of its descendants is ignored.
+    pub fn reparent(&mut self, child: CustomEntity, parent: Option<CustomEntity>, keep_local: bool) {
+        if self.check_parent(child, parent).is_err() {
+            return;
+        }
+
+        let world = self.world_matrix(child);
+        self.set_parent(child, parent);
+        // The cached world matrix belongs to the old parent
+        self.global_transforms.remove(&child);
+
+        if !keep_local && self.transforms.contains_key(&child) {
+            let parent_world = self.parent_world_matrix(child);
+            self.transforms.insert(child, Transform::from_matrix(parent_world.inverse() * world));
+        }
+    }
+}
+
+impl CustomWorld {
+    /// Entities without a parent in hierarchy order: the ones in `root_order` first,
+    /// then the rest by ID
+    pub fn roots(&self) -> Vec<CustomEntity> {
+        let order: HashMap<CustomEntity, usize> =
+            self.root_order.iter().enumerate().map(|(i, &entity)| (entity, i)).collect();
+        let mut roots: Vec<CustomEntity> =
+            self.entities().into_iter().filter(|e| !self.parents.contains_key(e)).collect();
+        roots.sort_by_key(|e| order.get(e).copied().unwrap_or(usize::MAX));
+        roots
+    }
+
+    /// Position of an entity among its siblings (the roots, for a root)
+    pub fn sibling_index(&self, entity: CustomEntity) -> Option<usize> {
+        match self.get_parent(entity) {
+            Some(parent) => self.get_children(parent).iter().position(|&e| e == entity),
+            None => self.roots().iter().position(|&e| e == entity),
+        }
+    }
+
+    /// Move an entity to `index` among its siblings (clamped to the end)
+    pub fn set_sibling_index(&mut self, entity: CustomEntity, index: usize) {
+        let siblings = self.siblings_mut(entity);
+        siblings.retain(|&e| e != entity);
+        siblings.insert(index.min(siblings.len()), entity);
+    }
+
+    /// Move an entity among its siblings to just before `sibling` (to the end for None
+    /// or an entity that isn't a sibling)
+    pub fn move_before(&mut self, entity: CustomEntity, sibling: Option<CustomEntity>) {
+        let siblings = self.siblings_mut(entity);
+        siblings.retain(|&e| e != entity);
+        let index = sibling
+            .and_then(|sibling| siblings.iter().position(|&e| e == sibling))
+            .unwrap_or(siblings.len());
+        siblings.insert(index, entity);
+    }
+
+    /// The ordered list holding an entity: its parent's children, or every root (written
+    /// out into `root_order`)
+    fn siblings_mut(&mut self, entity: CustomEntity) -> &mut Vec<CustomEntity> {
+        match self.get_parent(entity) {
+            Some(parent) => self.children.entry(parent).or_default(),
+            None => {
+                self.root_order = self.roots();
+                &mut self.root_order
+            }
+        }
+    }
+
+    /// The entity and all its descendants, parents before children, children in order
+    pub fn subtree(&self, entity: CustomEntity) -> Vec<CustomEntity> {
+        let mut result = Vec::new();
+        let mut stack = vec![entity];
+        while let Some(e) = stack.pop() {
+            if result.contains(&e) {
+                continue; // Cycle guard
+            }
+            result.push(e);
+            stack.extend(self.get_children(e).iter().rev());
+        }
+        result
+    }
+
+    /// Deep-copy an entity and its descendants under new IDs, next to the original (same
+    /// parent, same local transform). Entity script parameters pointing inside the copied
+    /// subtree point at the copies; ones pointing elsewhere are kept. Names are copied as
+    /// is. Returns (original, copy) pairs, the root first.
+    pub fn duplicate_subtree(&mut self, entity: CustomEntity) -> Vec<(CustomEntity, CustomEntity)> {
+        if !self.transforms.contains_key(&entity) && !self.active.contains_key(&entity) {
+            return Vec::new();
+        }
+        let originals = self.subtree(entity);
+        let id_map: HashMap<CustomEntity, CustomEntity> =
+            originals.iter().map(|&old| (old, self.spawn())).collect();
+
+        macro_rules! copy_components {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    for old in &originals {
+                        if let Some(component) = self.$field.get(old).cloned() {
+                            self.$field.insert(id_map[old], component);
+                        }
+                    }
+                )*
+            };
+        }
+        component_maps!(copy_components);
+        for instances in self.custom_components.values_mut() {
+            for old in &originals {
+                if let Some(component) = instances.get(old).cloned() {
+                    instances.insert(id_map[old], component);
+                }
+            }
+        }
+
+        for new in id_map.values() {
+            // A copy is another entity: it gets its own Guid when its scene is saved
+            self.guids.remove(new);
+            if let Some(script) = self.scripts.get_mut(new) {
+                // The copy is a fresh instance: Awake/Start run again for it
+                script.lifecycle_state = Default::default();
+                for value in script.parameters.values_mut() {
+                    if let ScriptParameter::Entity(Some(target)) = value {
+                        if let Some(&copy) = id_map.get(target) {
+                            *target = copy;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Children in their original order; the copy's root sits next to the original
+        for old in &originals {
+            for child in self.get_children(*old).to_vec() {
+                self.set_parent(id_map[&child], Some(id_map[old]));
+            }
+        }
+        self.set_parent(id_map[&entity], self.get_parent(entity));
+
+        originals.iter().map(|old| (*old, id_map[old])).collect()
+    }
+
+    /// `duplicate_subtree` with the copy's root renamed to the next free numbered name
+    /// among its siblings ("Enemy" -> "Enemy (1)"). Returns the new root.
+    pub fn duplicate(&mut self, entity: CustomEntity) -> Option<CustomEntity> {
+        let copy = self.duplicate_subtree(entity).first()?.1;
+        if let Some(name) = self.names.get(&entity) {
+            let siblings: Vec<CustomEntity> = match self.get_parent(copy) {
+                Some(parent) => self.get_children(parent).to_vec(),
+                None => self.entities().into_iter().filter(|e| !self.parents.contains_key(e)).collect(),
+            };
+            let name = numbered_name(name, |candidate| {
+                siblings.iter().any(|s| self.names.get(s).is_some_and(|n| n == candidate))
+            });
+            self.names.insert(copy, name);
+        }
+        Some(copy)
+    }
+
+    /// Put the originals' components back on copies made by `duplicate_subtree` (a copy
+    /// loses components its original doesn't have), remapping entity script parameters
+    /// the same way. The hierarchy is left alone. Used to reset pooled entities on reuse.
+    pub fn reset_copies(&mut self, pairs: &[(CustomEntity, CustomEntity)]) {
+        macro_rules! reset_components {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    for (original, copy) in pairs {
+                        match self.$field.get(original).cloned() {
+                            Some(component) => { self.$field.insert(*copy, component); }
+                            None => { self.$field.remove(copy); }
+                        }
+                    }
+                )*
+            };
+        }
+        component_maps!(reset_components);
+        for instances in self.custom_components.values_mut() {
+            for (original, copy) in pairs {
+                match instances.get(original).cloned() {
+                    Some(component) => { instances.insert(*copy, component); }
+                    None => { instances.remove(copy); }
+                }
+            }
+        }
+        for (_, copy) in pairs {
+            self.guids.remove(copy);
+        }
+
+        let id_map: HashMap<CustomEntity, CustomEntity> = pairs.iter().copied().collect();
+        for (_, copy) in pairs {
+            if let Some(script) = self.scripts.get_mut(copy) {
+                for value in script.parameters.values_mut() {
+                    if let ScriptParameter::Entity(Some(target)) = value {
+                        if let Some(&mapped) = id_map.get(target) {
+                            *target = mapped;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Remove an entity and its descendants, keeping their IDs and components in a world
+    /// of their own so `restore_subtree` can put them back exactly (undo of a create)
+    pub fn take_subtree(&mut self, entity: CustomEntity) -> CustomWorld {
+        let entities = self.subtree(entity);
+        let mut taken = CustomWorld::new();
+        self.set_parent(entity, None);
+
+        macro_rules! take_components {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    for e in &entities {
+                        if let Some(component) = self.$field.remove(e) {
+                            taken.$field.insert(*e, component);
+                        }
+                    }
+                )*
+            };
+        }
+        component_maps!(take_components);
+        for e in &entities {
+            for (name, component) in self.custom_components_of(*e) {
+                self.remove_custom_component(*e, &name);
+                taken.set_custom_component(*e, &name, component);
+            }
+        }
+        for e in &entities {
+            self.global_transforms.remove(e);
+            if let Some(parent) = self.parents.remove(e) {
+                taken.parents.insert(*e, parent);
+            }
+            if let Some(children) = self.children.remove(e) {
+                taken.children.insert(*e, children);
+            }
+        }
+        taken
+    }
+
+    /// Put back a subtree removed with `take_subtree`, its root under `parent`
+    pub fn restore_subtree(&mut self, mut subtree: CustomWorld, root: CustomEntity, parent: Option<CustomEntity>) {
+        macro_rules! restore_components {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    self.$field.extend(subtree.$field.drain());
+                )*
+            };
+        }
+        component_maps!(restore_components);
+        for (name, instances) in subtree.custom_components.drain() {
+            self.custom_components.entry(name).or_default().extend(instances);
+        }
+        self.parents.extend(subtree.parents.drain());
+        self.children.extend(subtree.children.drain());
+        self.set_parent(root, parent);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: [f32; 3], b: [f32; 3]) {
+        for i in 0..3 {
+            assert!((a[i] - b[i]).abs() < 1e-4, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_world_position_follows_parent_chain() {
+        let mut world = CustomWorld::new();
+        let parent = world.spawn();
+        let child = world.spawn();
+        let mut parent_transform = Transform::with_position(10.0, 0.0, 0.0);
+        parent_transform.rotation[2] = 90.0;
+        parent_transform.scale = [2.0, 2.0, 1.0];
+        world.transforms.insert(parent, parent_transform);
+        world.transforms.insert(child, Transform::with_position(1.0, 0.0, 0.0));
+        world.set_parent(child, Some(parent));
+
+        // Scaled by 2 and rotated a quarter turn: +X local is +Y world
+        assert_near(world.world_position(child).unwrap(), [10.0, 2.0, 0.0]);
+        assert_near(world.world_transform(child).unwrap().rotation, [0.0, 0.0, 90.0]);
+
+        world.set_world_position(child, [10.0, 4.0, 0.0]);
+        assert_near(world.transforms[&child].position, [2.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_reparent_keeps_world_position_unless_keep_local() {
+        let mut world = CustomWorld::new();
+        let parent = world.spawn();
+        let child = world.spawn();
+        world.transforms.insert(parent, Transform::with_position(5.0, 5.0, 0.0));
+        world.transforms.insert(child, Transform::with_position(1.0, 2.0, 0.0));
+
+        world.reparent(child, Some(parent), false);
+        assert_near(world.world_position(child).unwrap(), [1.0, 2.0, 0.0]);
+        assert_near(world.transforms[&child].position, [-4.0, -3.0, 0.0]);
+
+        world.reparent(child, None, true);
+        assert_near(world.world_position(child).unwrap(), [-4.0, -3.0, 0.0]);
+
+        // Cycles are refused
+        world.reparent(child, Some(parent), true);
+        world.reparent(parent, Some(child), false);
+        assert_eq!(world.get_parent(parent), None);
+    }
+
+    #[test]
+    fn test_duplicate_copies_subtree_and_remaps_internal_references() {
+        let mut world = CustomWorld::new();
+        let outside = world.spawn();
+        let enemy = world.spawn();
+        let weapon = world.spawn();
+        for e in [outside, enemy, weapon] {
+            world.transforms.insert(e, Transform::default());
+        }
+        world.names.insert(enemy, "Enemy".to_string());
+        world.names.insert(weapon, "Weapon".to_string());
+        world.set_parent(weapon, Some(enemy));
+
+        let parameters = HashMap::from([
+            ("weapon".to_string(), ScriptParameter::Entity(Some(weapon))),
+            ("target".to_string(), ScriptParameter::Entity(Some(outside))),
+        ]);
+        world.scripts.insert(enemy, crate::Script {
+            script_name: "enemy".to_string(),
+            enabled: true,
+            parameters,
+            lifecycle_state: Default::default(),
+        });
+
+        let copy = world.duplicate(enemy).unwrap();
+        assert_eq!(world.names[&copy], "Enemy (1)");
+        let copy_weapon = world.get_children(copy)[0];
+        assert_ne!(copy_weapon, weapon);
+        assert_eq!(world.names[&copy_weapon], "Weapon");
+        let params = &world.scripts[&copy].parameters;
+        assert_eq!(params["weapon"], ScriptParameter::Entity(Some(copy_weapon)));
+        assert_eq!(params["target"], ScriptParameter::Entity(Some(outside)));
+
+        let second = world.duplicate(enemy).unwrap();
+        assert_eq!(world.names[&second], "Enemy (2)");
+        let third = world.duplicate(copy).unwrap();
+        assert_eq!(world.names[&third], "Enemy (3)");
+    }
+
+    #[test]
+    fn test_take_and_restore_subtree_keeps_ids() {
+        let mut world = CustomWorld::new();
+        let parent = world.spawn();
+        let child = world.spawn();
+        world.transforms.insert(parent, Transform::default());
+        world.transforms.insert(child, Transform::with_position(1.0, 0.0, 0.0));
+        world.set_parent(child, Some(parent));
+
+        let taken = world.take_subtree(parent);
+        assert!(world.entities().is_empty());
+
+        world.restore_subtree(taken, parent, None);
+        assert_eq!(world.get_children(parent), &[child]);
+        assert_eq!(world.transforms[&child].position, [1.0, 0.0, 0.0]);
+    }
+    #[test]
+    fn test_sibling_order_is_kept_and_saved() {
+        let mut world = CustomWorld::new();
+        let entities: Vec<CustomEntity> = (0..5).map(|_| world.spawn()).collect();
+        let &[parent, a, b, c, other] = entities.as_slice() else { unreachable!() };
+        for &child in &[a, b, c] {
+            world.set_parent(child, Some(parent));
+        }
+
+        world.move_before(c, Some(a));
+        assert_eq!(world.get_children(parent), &[c, a, b]);
+        world.set_sibling_index(c, 99);
+        assert_eq!(world.get_children(parent), &[a, b, c]);
+        assert_eq!(world.sibling_index(b), Some(1));
+
+        // Roots without an explicit order go by ID
+        assert_eq!(world.roots(), [parent, other]);
+        world.move_before(other, Some(parent));
+        assert_eq!(world.roots(), [other, parent]);
+        world.set_parent(b, None);
+        assert_eq!(world.roots(), [other, parent, b]);
+        assert!(world.check_parent(parent, Some(c)).is_err());
+        assert!(world.check_parent(c, None).is_ok());
+
+        world.move_before(c, Some(a));
+        let mut loaded = CustomWorld::new();
+        loaded.load_from_json(&world.save_to_json().unwrap()).unwrap();
+        assert_eq!(loaded.get_children(parent), &[c, a]);
+        assert_eq!(loaded.roots(), [other, parent, b]);
+    }
+}
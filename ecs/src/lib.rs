@@ -7,12 +7,25 @@ pub mod components;
 pub mod loaders;
 pub mod backends;
 pub mod benchmark_runner;
+pub mod picking;
+pub mod hierarchy;
+pub mod sorting;
+pub mod pool;
+pub mod scene_settings;
+pub mod dynamic_state;
+pub mod custom_components;
+pub mod ordered;
 
 // Re-export สำหรับใช้งานง่าย
 pub use component_manager::{ComponentType, ComponentManager};
 pub use components::*;
 pub use backends::{EcsBackendType, DynamicWorld, BackendPerformanceInfo, PerformanceLevel};
 pub use benchmark_runner::{BenchmarkRunner, BenchmarkSuite, BenchmarkResult};
+pub use engine_core::Color;
+pub use scene_settings::SceneSettings;
+pub use dynamic_state::{DynamicState, EntitySnapshot};
+pub use custom_components::{ComponentRegistry, ComponentSchema, FieldSchema, FieldType};
+pub use ordered::EntityOrdered;
 
 // ----------------------------------------------------------------------------
 // Backend Selection
@@ -75,7 +88,7 @@ impl Prefab {
                 texture_id: "player".to_string(),
                 width: 1.0,  // Base size
                 height: 1.0,
-                color: [0.2, 0.6, 1.0, 1.0],
+                color: Color::rgba(0.2, 0.6, 1.0, 1.0),
                 billboard: true, // Player sprite faces camera (good for 3D mode)
                 ..Default::default()
             }),
@@ -101,7 +114,7 @@ impl Prefab {
                 texture_id: "item".to_string(),
                 width: 1.0,  // Base size
                 height: 1.0,
-                color: [1.0, 0.8, 0.2, 1.0],
+                color: Color::rgba(1.0, 0.8, 0.2, 1.0),
                 billboard: true, // Item sprite faces camera
                 ..Default::default()
             }),
@@ -182,7 +195,7 @@ impl Prefab {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Transform {
     pub position: [f32; 3],  // X, Y, Z
     #[serde(deserialize_with = "deserialize_rotation")]
@@ -270,6 +283,10 @@ where
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GlobalTransform {
     pub matrix: [f32; 16], // 4x4 matrix column-major
+    /// Local transform the matrix was computed from; propagation skips subtrees whose
+    /// local transforms (and parents) haven't changed since
+    #[serde(skip)]
+    pub local: Option<Transform>,
 }
 
 impl Default for GlobalTransform {
@@ -281,6 +298,7 @@ impl Default for GlobalTransform {
                 0.0, 0.0, 1.0, 0.0, // Col 3
                 0.0, 0.0, 0.0, 1.0, // Col 4
             ],
+            local: None,
         }
     }
 }
@@ -301,7 +319,7 @@ pub struct Sprite {
     pub width: f32,
     /// Original sprite height in pixels (use Transform.scale for sizing)
     pub height: f32,
-    pub color: [f32; 4], // RGBA
+    pub color: Color,
     #[serde(default)]
     pub billboard: bool, // If true, sprite always faces camera (3D mode only)
     /// Flip sprite horizontally
@@ -314,10 +332,22 @@ pub struct Sprite {
     /// If None, uses full texture. If Some, uses sub-region of texture.
     #[serde(default)]
     pub sprite_rect: Option<[u32; 4]>,
+    /// sprite_rect is stored turned 90° clockwise in the texture (TexturePacker-style
+    /// rotated packing): it covers height x width pixels from its x, y
+    #[serde(default)]
+    pub rect_rotated: bool,
+    /// Named sprite sheet frame to draw; resolved at draw time so re-packing the atlas
+    /// keeps the sprite valid. Falls back to sprite_rect when no sheet has the frame.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_name: Option<String>,
     /// Pixels Per Unit (Unity-style) - how many pixels equal 1 world unit
     /// Default is 100 (like Unity). Lower values = larger sprites in world.
     #[serde(default = "default_pixels_per_unit")]
     pub pixels_per_unit: f32,
+    /// Pivot override in [0, 1] ([0.5, 0] = bottom center), set from the sprite editor.
+    /// None uses the texture's import pivot.
+    #[serde(default)]
+    pub pivot: Option<[f32; 2]>,
     
     /// Sorting Layer (Unity-style) - Group sprites
     #[serde(default = "default_sorting_layer")]
@@ -351,12 +381,15 @@ impl Default for Sprite {
             asset_id: None,
             width: 1.0,  // Default 1x1 pixel
             height: 1.0,
-            color: [1.0, 1.0, 1.0, 1.0],
+            color: Color::rgba(1.0, 1.0, 1.0, 1.0),
             billboard: false,
             flip_x: false,
             flip_y: false,
             sprite_rect: None,
+            rect_rotated: false,
+            frame_name: None,
             pixels_per_unit: 100.0,  // Unity standard
+            pivot: None,
             sorting_layer: default_sorting_layer(),
             order_in_layer: 0,
             rendering_layer_mask: default_rendering_layer_mask(),
@@ -403,12 +436,36 @@ pub struct Collider {
     /// Legacy height (for backward compatibility)
     #[serde(default)]
     pub height: f32,
+    /// Collision shape (Box uses size, Circle uses size.x as diameter)
+    #[serde(default)]
+    pub shape: ColliderShape,
+    /// Trigger colliders report overlaps but don't block movement
+    #[serde(default)]
+    pub is_trigger: bool,
+    /// Mirror the offset on the axes the entity's sprite is flipped on
+    #[serde(default = "default_flip_with_sprite")]
+    pub flip_with_sprite: bool,
+}
+
+/// Shape of a 2D collider
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ColliderShape {
+    #[default]
+    Box,
+    /// Diameter = size.x
+    Circle,
+    /// Vertical capsule: width = size.x, total height = size.y
+    Capsule,
 }
 
 fn default_collider_size() -> [f32; 2] {
     [1.0, 1.0]
 }
 
+fn default_flip_with_sprite() -> bool {
+    true
+}
+
 impl Default for Collider {
     fn default() -> Self {
         Self {
@@ -416,6 +473,9 @@ impl Default for Collider {
             size: [1.0, 1.0],
             width: 0.0,
             height: 0.0,
+            shape: ColliderShape::Box,
+            is_trigger: false,
+            flip_with_sprite: true,
         }
     }
 }
@@ -428,6 +488,9 @@ impl Collider {
             size: [size_x, size_y],
             width: 0.0,
             height: 0.0,
+            shape: ColliderShape::Box,
+            is_trigger: false,
+            flip_with_sprite: true,
         }
     }
     
@@ -438,6 +501,9 @@ impl Collider {
             size: [size_x, size_y],
             width: 0.0,
             height: 0.0,
+            shape: ColliderShape::Box,
+            is_trigger: false,
+            flip_with_sprite: true,
         }
     }
     
@@ -451,10 +517,25 @@ impl Collider {
         self.size[1] * scale_y
     }
     
+    /// Get world-space radius for Circle/Capsule shapes (half of size.x * scale.x)
+    pub fn get_world_radius(&self, scale_x: f32) -> f32 {
+        self.get_world_width(scale_x).abs() / 2.0
+    }
+    
     /// Get world-space offset
     pub fn get_world_offset(&self, scale_x: f32, scale_y: f32) -> [f32; 2] {
         [self.offset[0] * scale_x, self.offset[1] * scale_y]
     }
+
+    /// Get world-space offset for an entity whose sprite is flipped by `flip` (x, y):
+    /// mirrored on the flipped axes when `flip_with_sprite` is set. Still unrotated.
+    pub fn get_flipped_offset(&self, scale_x: f32, scale_y: f32, flip: [bool; 2]) -> [f32; 2] {
+        let [x, y] = self.get_world_offset(scale_x, scale_y);
+        if !self.flip_with_sprite {
+            return [x, y];
+        }
+        [if flip[0] { -x } else { x }, if flip[1] { -y } else { y }]
+    }
     
     /// Migrate from legacy width/height to size
     pub fn migrate_from_legacy(&mut self, transform_scale: [f32; 3]) {
@@ -571,6 +652,7 @@ pub enum ScriptParameter {
     String(String),
     Bool(bool),
     Entity(Option<Entity>), // Unity-style GameObject reference
+    Curve(engine_core::animation_curve::AnimationCurve), // Easing curve (curve editor)
 }
 
 /// Camera component for view control (Unity-like)
@@ -598,13 +680,23 @@ pub struct Camera {
 
     // Clear flags
     pub clear_flags: CameraClearFlags,
-    pub background_color: [f32; 4], // RGBA
+    pub background_color: Color,
     
     // Pixels Per Unit (Unity 2D style) - how many pixels equal 1 world unit
     // Default is 100 (like Unity). Used for pixel-perfect rendering.
     // Set to 1.0 for 1:1 pixel mapping (1 world unit = 1 pixel)
     #[serde(default = "default_camera_pixels_per_unit")]
     pub pixels_per_unit: f32,
+
+    // Render texture to draw into instead of the screen (by name, see RenderTextureAsset)
+    #[serde(default)]
+    pub target_texture: Option<String>,
+
+    // Zoom limits for orthographic_size (Lua Camera.set_orthographic_size/zoom_to), None = unlimited
+    #[serde(default)]
+    pub min_orthographic_size: Option<f32>,
+    #[serde(default)]
+    pub max_orthographic_size: Option<f32>,
 }
 
 fn default_camera_pixels_per_unit() -> f32 {
@@ -636,13 +728,23 @@ impl Default for Camera {
             viewport_rect: [0.0, 0.0, 1.0, 1.0], // Full screen
             depth: 0,
             clear_flags: CameraClearFlags::SolidColor,
-            background_color: [0.15, 0.16, 0.18, 1.0], // Dark gray (Unity default)
+            background_color: Color::rgba(0.15, 0.16, 0.18, 1.0), // Dark gray (Unity default)
             pixels_per_unit: 100.0,  // Unity standard
+            target_texture: None,
+            min_orthographic_size: None,
+            max_orthographic_size: None,
         }
     }
 }
 
 impl Camera {
+    /// `size` limited to the zoom range (and kept positive)
+    pub fn clamp_orthographic_size(&self, size: f32) -> f32 {
+        let size = self.max_orthographic_size.map_or(size, |max| size.min(max));
+        let size = self.min_orthographic_size.map_or(size, |min| size.max(min));
+        size.max(0.01)
+    }
+
     /// Create a 2D orthographic camera (Unity 2D style)
     pub fn orthographic_2d() -> Self {
         Self {
@@ -708,6 +810,7 @@ pub struct CustomWorld {
     pub sprites: HashMap<CustomEntity, Sprite>,
     pub colliders: HashMap<CustomEntity, Collider>,
     pub colliders_3d: HashMap<CustomEntity, Collider3D>, // 3D colliders
+    pub polygon_colliders: HashMap<CustomEntity, PolygonCollider>, // 2D polygon colliders
     pub meshes: HashMap<CustomEntity, Mesh>,      // 3D meshes
     pub cameras: HashMap<CustomEntity, Camera>,   // Camera components
     pub tags: HashMap<CustomEntity, EntityTag>,
@@ -715,10 +818,13 @@ pub struct CustomWorld {
     pub active: HashMap<CustomEntity, bool>,      // Active state (Unity-like)
     pub layers: HashMap<CustomEntity, u8>,        // Layer (0-31, Unity has 32 layers)
     pub parents: HashMap<CustomEntity, CustomEntity>,   // Parent entity
-    pub children: HashMap<CustomEntity, Vec<CustomEntity>>, // Children entities
+    pub children: HashMap<CustomEntity, Vec<CustomEntity>>, // Children entities (in hierarchy order)
+    pub root_order: Vec<CustomEntity>, // Hierarchy order of the roots (unlisted roots follow by ID)
     pub names: HashMap<CustomEntity, String>,     // Entity names (for editor)
     // Sprite sheet and tilemap components
     pub sprite_sheets: HashMap<CustomEntity, SpriteSheet>,
+    // Computed: texture id/path -> entity of the sheet its sprites use (see index_sprite_sheets)
+    pub sprite_sheet_index: HashMap<String, CustomEntity>,
     pub animated_sprites: HashMap<CustomEntity, AnimatedSprite>,
     pub tilemaps: HashMap<CustomEntity, Tilemap>,
     pub tilesets: HashMap<CustomEntity, TileSet>,
@@ -737,6 +843,25 @@ pub struct CustomWorld {
     // 3D Model component (Static Props)
     pub model_3ds: HashMap<CustomEntity, Model3D>,
     pub ldtk_entities: HashMap<CustomEntity, LdtkEntity>,
+    // Sprite shader overrides (flash, outline, dissolve)
+    pub sprite_materials: HashMap<CustomEntity, SpriteMaterial>,
+    // Scene view icons for entities with nothing to render (editor only)
+    pub editor_icons: HashMap<CustomEntity, EditorIcon>,
+    // Areas reporting colliders entering/leaving them
+    pub trigger_zones: HashMap<CustomEntity, TriggerZone>,
+    // Sprite tint tweens (multiplied into Sprite.color when drawn)
+    pub color_fades: HashMap<CustomEntity, ColorFade>,
+    // Dynamic game state captured by save games, and the stable ids that find it again
+    pub save_states: HashMap<CustomEntity, SaveState>,
+    pub guids: HashMap<CustomEntity, Guid>,
+    // Guids of SaveState entities despawned since the scene loaded (not saved in the scene)
+    pub destroyed_guids: Vec<Guid>,
+    // Components defined from Lua (Component.define): name -> entity -> JSON instance
+    pub custom_components: HashMap<String, HashMap<CustomEntity, serde_json::Value>>,
+    // Scene-level: asset paths loaded before play starts
+    pub preload_assets: Vec<String>,
+    // Scene-level: background, ambient light, gravity, hidden sorting layers
+    pub scene_settings: SceneSettings,
 }
 
 impl CustomWorld {
@@ -766,6 +891,7 @@ impl CustomWorld {
                 siblings.retain(|&x| x != e);
             }
         }
+        self.root_order.retain(|&x| x != e);
 
         self.transforms.remove(&e);
         self.global_transforms.remove(&e);
@@ -774,6 +900,7 @@ impl CustomWorld {
         self.sprites.remove(&e);
         self.colliders.remove(&e);
         self.colliders_3d.remove(&e);
+        self.polygon_colliders.remove(&e);
         self.meshes.remove(&e);
         self.cameras.remove(&e);
         self.tags.remove(&e);
@@ -794,16 +921,27 @@ impl CustomWorld {
         self.ldtk_intgrid_colliders.remove(&e);
         self.model_3ds.remove(&e);
         self.ldtk_entities.remove(&e);
+        self.sprite_materials.remove(&e);
+        self.editor_icons.remove(&e);
+        self.trigger_zones.remove(&e);
+        self.color_fades.remove(&e);
+        self.remove_custom_components(e);
+        // A save game made from here on removes it from its scene again
+        if let (Some(guid), Some(_)) = (self.guids.remove(&e), self.save_states.remove(&e)) {
+            self.destroyed_guids.push(guid);
+        }
     }
 
     pub fn clear(&mut self) {
         self.transforms.clear();
         self.global_transforms.clear();
+        self.sprite_sheet_index.clear();
         self.velocities.clear();
         self.rigidbodies.clear();
         self.sprites.clear();
         self.colliders.clear();
         self.colliders_3d.clear();
+        self.polygon_colliders.clear();
         self.meshes.clear();
         self.cameras.clear();
         self.tags.clear();
@@ -812,6 +950,7 @@ impl CustomWorld {
         self.layers.clear();
         self.parents.clear();
         self.children.clear();
+        self.root_order.clear();
         self.names.clear();
         self.sprite_sheets.clear();
         self.animated_sprites.clear();
@@ -826,9 +965,112 @@ impl CustomWorld {
         self.ldtk_intgrid_colliders.clear();
         self.model_3ds.clear();
         self.ldtk_entities.clear();
+        self.sprite_materials.clear();
+        self.editor_icons.clear();
+        self.trigger_zones.clear();
+        self.color_fades.clear();
+        self.save_states.clear();
+        self.guids.clear();
+        self.destroyed_guids.clear();
+        self.custom_components.clear();
+        self.preload_assets.clear();
+        self.scene_settings = SceneSettings::default();
         self.next_entity = 0;
     }
 
+    /// Every entity in the world (those with a transform or an active flag), sorted
+    pub fn entities(&self) -> Vec<CustomEntity> {
+        let mut entities: Vec<CustomEntity> = self.transforms.keys().chain(self.active.keys()).copied().collect();
+        entities.sort_unstable();
+        entities.dedup();
+        entities
+    }
+
+    /// Move every entity of `other` into this world under a new ID (additive scene
+    /// loading). Parents and Entity script parameters pointing inside `other` are
+    /// remapped; ones pointing outside it are cleared. Returns the new entities in
+    /// the order of their old IDs.
+    pub fn merge(&mut self, mut other: CustomWorld) -> Vec<CustomEntity> {
+        // Merged roots keep their order, after the roots already here
+        if !other.root_order.is_empty() {
+            self.root_order = self.roots();
+        }
+        let old_entities = other.entities();
+        let id_map: HashMap<CustomEntity, CustomEntity> =
+            old_entities.iter().map(|&old| (old, self.spawn())).collect();
+
+        macro_rules! move_components {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    for (old, component) in other.$field.drain() {
+                        if let Some(&new) = id_map.get(&old) {
+                            self.$field.insert(new, component);
+                        }
+                    }
+                )*
+            };
+        }
+        move_components!(
+            transforms, global_transforms, velocities, rigidbodies, sprites, colliders, colliders_3d,
+            polygon_colliders, meshes, cameras, tags, scripts, active, layers, names, sprite_sheets,
+            animated_sprites, tilemaps, tilesets, tilemap_renderers, maps, grids, world_uis, ldtk_maps,
+            tilemap_colliders, ldtk_intgrid_colliders, model_3ds, ldtk_entities, sprite_materials,
+            editor_icons, trigger_zones, color_fades, save_states, guids,
+        );
+        for (name, instances) in other.custom_components.drain() {
+            let merged = self.custom_components.entry(name).or_default();
+            merged.extend(instances.into_iter().filter_map(|(old, value)| Some((*id_map.get(&old)?, value))));
+        }
+
+        for &new in id_map.values() {
+            if let Some(script) = self.scripts.get_mut(&new) {
+                for value in script.parameters.values_mut() {
+                    if let ScriptParameter::Entity(target) = value {
+                        *target = target.and_then(|old| id_map.get(&old).copied());
+                    }
+                }
+            }
+        }
+
+        // Children in their original order
+        for old in &old_entities {
+            for child in other.children.get(old).map(Vec::as_slice).unwrap_or(&[]) {
+                if let (Some(&child), Some(&parent)) = (id_map.get(child), id_map.get(old)) {
+                    self.set_parent(child, Some(parent));
+                }
+            }
+        }
+        self.root_order.extend(other.root_order.iter().filter_map(|old| id_map.get(old)));
+
+        for path in other.preload_assets {
+            if !self.preload_assets.contains(&path) {
+                self.preload_assets.push(path);
+            }
+        }
+
+        old_entities.iter().map(|old| id_map[old]).collect()
+    }
+
+    /// Load a scene file's JSON into this world next to what is already there (see `merge`)
+    pub fn merge_from_json(&mut self, json: &str) -> Result<Vec<CustomEntity>, serde_json::Error> {
+        let mut scene = CustomWorld::new();
+        scene.load_from_json(json)?;
+        Ok(self.merge(scene))
+    }
+
+    /// Err(InvalidHierarchy) when `parent` is `child` itself or one of its descendants
+    pub fn check_parent(&self, child: CustomEntity, parent: Option<CustomEntity>) -> Result<(), EcsError> {
+        let mut current = parent;
+        while let Some(ancestor) = current {
+            if ancestor == child {
+                return Err(EcsError::InvalidHierarchy);
+            }
+            current = self.get_parent(ancestor);
+        }
+        Ok(())
+    }
+
+    /// Set or clear the parent. The child goes last among its new siblings.
     pub fn set_parent(&mut self, child: CustomEntity, parent: Option<CustomEntity>) {
         // Remove from old parent
         if let Some(old_parent) = self.parents.remove(&child) {
@@ -852,15 +1094,134 @@ impl CustomWorld {
         self.parents.get(&entity).copied()
     }
 
+    /// Set the entity's own active flag (Unity's SetActive). Children keep their own
+    /// flags but are inactive in the hierarchy while this entity is inactive.
+    pub fn set_active(&mut self, entity: CustomEntity, active: bool) {
+        if let Some(flag) = self.active.get_mut(&entity) {
+            *flag = active;
+        }
+    }
+
+    /// The entity's own active flag (Unity's activeSelf)
+    pub fn is_active_self(&self, entity: CustomEntity) -> bool {
+        self.active.get(&entity).copied().unwrap_or(true)
+    }
+
+    /// False if the entity or any of its ancestors is inactive (Unity's activeInHierarchy).
+    /// Systems should use this rather than reading `active` directly.
+    pub fn is_active_in_hierarchy(&self, entity: CustomEntity) -> bool {
+        let mut current = Some(entity);
+        // Depth cap guards against a corrupt parent cycle
+        for _ in 0..=self.parents.len() {
+            let Some(e) = current else {
+                return true;
+            };
+            if !self.is_active_self(e) {
+                return false;
+            }
+            current = self.parents.get(&e).copied();
+        }
+        true
+    }
+
+    /// The entity's sprite flips (x, y); no sprite means no flip
+    pub fn sprite_flip(&self, entity: CustomEntity) -> [bool; 2] {
+        self.sprites.get(&entity).map_or([false, false], |sprite| [sprite.flip_x, sprite.flip_y])
+    }
+
+    /// Tileset a tilemap draws with: the one on the same entity, else the one whose
+    /// texture id the tilemap names (LDtk layers reference theirs by id)
+    pub fn tileset_for(&self, entity: CustomEntity) -> Option<&TileSet> {
+        self.tilesets.get(&entity).or_else(|| {
+            let tilemap = self.tilemaps.get(&entity)?;
+            self.tilesets.values().find(|tileset| tileset.texture_id == tilemap.tileset_id)
+        })
+    }
+
+    /// Sprite sheet a sprite's named frames come from: the one on the same entity, else
+    /// one for the sprite's texture (sheets are shared by texture like tilesets). When
+    /// several sheets share a texture the one on the lowest entity id wins.
+    pub fn sprite_sheet_for(&self, entity: CustomEntity) -> Option<&SpriteSheet> {
+        self.sprite_sheets.get(&entity).or_else(|| {
+            let texture = &self.sprites.get(&entity)?.texture_id;
+            let uses = |sheet: &SpriteSheet| sheet.texture_id == *texture || sheet.texture_path == *texture;
+            // Indexed once a frame; sheets added or retargeted since are found by a scan
+            if let Some(sheet) = self.sprite_sheet_index.get(texture).and_then(|owner| self.sprite_sheets.get(owner)) {
+                if uses(sheet) {
+                    return Some(sheet);
+                }
+            }
+            self.sprite_sheets.iter()
+                .filter(|(_, sheet)| uses(sheet))
+                .min_by_key(|(owner, _)| **owner)
+                .map(|(_, sheet)| sheet)
+        })
+    }
+
+    /// Rebuild sprite_sheet_index so sprite_sheet_for doesn't scan every sheet per sprite
+    /// drawn. Called once a frame, next to the global transforms.
+    pub fn index_sprite_sheets(&mut self) {
+        let mut owners: Vec<CustomEntity> = self.sprite_sheets.keys().copied().collect();
+        owners.sort_unstable();
+        let mut index = HashMap::new();
+        for owner in owners {
+            let sheet = &self.sprite_sheets[&owner];
+            index.entry(sheet.texture_id.clone()).or_insert(owner);
+            index.entry(sheet.texture_path.clone()).or_insert(owner);
+        }
+        self.sprite_sheet_index = index;
+    }
+
+    /// Texture rect a sprite draws: its named frame when the sheet has it, else sprite_rect
+    pub fn sprite_rect_for(&self, entity: CustomEntity, sprite: &Sprite) -> Option<[u32; 4]> {
+        sprite.frame_name.as_deref()
+            .and_then(|name| self.sprite_sheet_for(entity)?.get_frame_by_name(name))
+            .map(SpriteFrame::rect)
+            .or(sprite.sprite_rect)
+    }
+
+    /// Whether the rect a sprite draws (see sprite_rect_for) is packed rotated
+    pub fn sprite_rect_rotated_for(&self, entity: CustomEntity, sprite: &Sprite) -> bool {
+        match sprite.frame_name.as_deref().and_then(|name| self.sprite_sheet_for(entity)?.get_frame_by_name(name)) {
+            Some(frame) => frame.rotated,
+            None => sprite.rect_rotated,
+        }
+    }
+
+    /// Texture coordinates a sprite draws with from a `texture_size` texture: its frame or
+    /// sprite_rect (else the whole texture), flipped and turned as the sprite says
+    pub fn sprite_uv_for(&self, entity: CustomEntity, sprite: &Sprite, texture_size: [f32; 2]) -> SpriteUv {
+        match self.sprite_rect_for(entity, sprite) {
+            Some(rect) => {
+                let rotated = self.sprite_rect_rotated_for(entity, sprite);
+                SpriteUv::from_rect(rect, texture_size, sprite.flip_x, sprite.flip_y, rotated)
+            }
+            None => SpriteUv::whole(sprite.flip_x, sprite.flip_y),
+        }
+    }
+
+    /// Color a sprite draws with: its own color times its ColorFade's tint (once the
+    /// fade runs or is previewed; edit mode otherwise shows the sprite untinted), lit by
+    /// the scene's ambient color
+    pub fn sprite_draw_color(&self, entity: CustomEntity, sprite: &Sprite) -> Color {
+        let color = match self.color_fades.get(&entity).and_then(|fade| fade.tint) {
+            Some(tint) => sprite.color * tint,
+            None => sprite.color,
+        };
+        color * self.scene_settings.ambient_color
+    }
+
     pub fn save_to_json(&self) -> Result<String, serde_json::Error> {
         #[derive(Serialize)]
         struct SceneData {
+            scene_settings: SceneSettings,
             next_entity: CustomEntity,
             transforms: Vec<(CustomEntity, Transform)>,
             velocities: Vec<(CustomEntity, (f32, f32))>,
             sprites: Vec<(CustomEntity, Sprite)>,
             colliders: Vec<(CustomEntity, Collider)>,
             colliders_3d: Vec<(CustomEntity, Collider3D)>,
+            polygon_colliders: Vec<(CustomEntity, PolygonCollider)>,
             rigidbodies: Vec<(CustomEntity, Rigidbody2D)>,  // Added rigidbody serialization
             cameras: Vec<(CustomEntity, Camera)>,
             meshes: Vec<(CustomEntity, Mesh)>,
@@ -869,6 +1230,7 @@ impl CustomWorld {
             active: Vec<(CustomEntity, bool)>,
             layers: Vec<(CustomEntity, u8)>,
             parents: Vec<(CustomEntity, CustomEntity)>,
+            root_order: Vec<CustomEntity>,
             names: Vec<(CustomEntity, String)>,
             sprite_sheets: Vec<(CustomEntity, SpriteSheet)>,
             animated_sprites: Vec<(CustomEntity, AnimatedSprite)>,
@@ -880,34 +1242,60 @@ impl CustomWorld {
             world_uis: Vec<(CustomEntity, WorldUI)>,
             model_3ds: Vec<(CustomEntity, Model3D)>,
             ldtk_entities: Vec<(CustomEntity, LdtkEntity)>,
+            sprite_materials: Vec<(CustomEntity, SpriteMaterial)>,
+            editor_icons: Vec<(CustomEntity, EditorIcon)>,
+            trigger_zones: Vec<(CustomEntity, TriggerZone)>,
+            color_fades: Vec<(CustomEntity, ColorFade)>,
+            save_states: Vec<(CustomEntity, SaveState)>,
+            guids: Vec<(CustomEntity, Guid)>,
+            custom_components: custom_components::SavedCustomComponents,
+            preload_assets: Vec<String>,
         }
 
         let data = SceneData {
+            scene_settings: self.scene_settings.clone(),
             next_entity: self.next_entity,
-            transforms: self.transforms.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            velocities: self.velocities.iter().map(|(k, v)| (*k, *v)).collect(),
-            sprites: self.sprites.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            colliders: self.colliders.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            colliders_3d: self.colliders_3d.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            rigidbodies: self.rigidbodies.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            cameras: self.cameras.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            meshes: self.meshes.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            tags: self.tags.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            scripts: self.scripts.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            active: self.active.iter().map(|(k, v)| (*k, *v)).collect(),
-            layers: self.layers.iter().map(|(k, v)| (*k, *v)).collect(),
-            parents: self.parents.iter().map(|(k, v)| (*k, *v)).collect(),
-            names: self.names.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            sprite_sheets: self.sprite_sheets.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            animated_sprites: self.animated_sprites.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            tilemaps: self.tilemaps.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            tilesets: self.tilesets.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            tilemap_renderers: self.tilemap_renderers.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            grids: self.grids.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            maps: self.maps.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            world_uis: self.world_uis.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            model_3ds: self.model_3ds.iter().map(|(k, v)| (*k, v.clone())).collect(),
-            ldtk_entities: self.ldtk_entities.iter().map(|(k, v)| (*k, v.clone())).collect(),
+            transforms: ordered::sorted_entries(&self.transforms),
+            velocities: ordered::sorted_entries(&self.velocities),
+            sprites: ordered::sorted_entries(&self.sprites),
+            colliders: ordered::sorted_entries(&self.colliders),
+            colliders_3d: ordered::sorted_entries(&self.colliders_3d),
+            polygon_colliders: ordered::sorted_entries(&self.polygon_colliders),
+            rigidbodies: ordered::sorted_entries(&self.rigidbodies),
+            cameras: ordered::sorted_entries(&self.cameras),
+            meshes: ordered::sorted_entries(&self.meshes),
+            tags: ordered::sorted_entries(&self.tags),
+            scripts: ordered::sorted_entries(&self.scripts),
+            active: ordered::sorted_entries(&self.active),
+            layers: ordered::sorted_entries(&self.layers),
+            // In sibling order, so loading rebuilds the children lists as they are
+            parents: {
+                let mut parents: Vec<_> = self.children.iter().collect();
+                parents.sort_unstable_by_key(|(parent, _)| **parent);
+                parents.into_iter()
+                    .flat_map(|(parent, children)| children.iter().map(move |child| (*child, *parent)))
+                    .collect()
+            },
+            root_order: if self.root_order.is_empty() { Vec::new() } else { self.roots() },
+            names: ordered::sorted_entries(&self.names),
+            sprite_sheets: ordered::sorted_entries(&self.sprite_sheets),
+            animated_sprites: ordered::sorted_entries(&self.animated_sprites),
+            tilemaps: ordered::sorted_entries(&self.tilemaps),
+            tilesets: ordered::sorted_entries(&self.tilesets),
+            tilemap_renderers: ordered::sorted_entries(&self.tilemap_renderers),
+            grids: ordered::sorted_entries(&self.grids),
+            maps: ordered::sorted_entries(&self.maps),
+            world_uis: ordered::sorted_entries(&self.world_uis),
+            model_3ds: ordered::sorted_entries(&self.model_3ds),
+            ldtk_entities: ordered::sorted_entries(&self.ldtk_entities),
+            sprite_materials: ordered::sorted_entries(&self.sprite_materials),
+            editor_icons: ordered::sorted_entries(&self.editor_icons),
+            trigger_zones: ordered::sorted_entries(&self.trigger_zones),
+            color_fades: ordered::sorted_entries(&self.color_fades),
+            save_states: ordered::sorted_entries(&self.save_states),
+            guids: ordered::sorted_entries(&self.guids),
+            custom_components: custom_components::to_saved(&self.custom_components),
+            preload_assets: self.preload_assets.clone(),
         };
 
         serde_json::to_string_pretty(&data)
@@ -916,6 +1304,9 @@ impl CustomWorld {
     pub fn load_from_json(&mut self, json: &str) -> Result<(), serde_json::Error> {
         #[derive(Deserialize)]
         struct SceneData {
+            // Scenes saved before Scene Settings existed get the project defaults
+            #[serde(default)]
+            scene_settings: SceneSettings,
             #[serde(default)]
             next_entity: CustomEntity,
             #[serde(default)]
@@ -929,6 +1320,8 @@ impl CustomWorld {
             #[serde(default)]
             colliders_3d: Vec<(CustomEntity, Collider3D)>,
             #[serde(default)]
+            polygon_colliders: Vec<(CustomEntity, PolygonCollider)>,
+            #[serde(default)]
             rigidbodies: Vec<(CustomEntity, Rigidbody2D)>,  // Added rigidbody deserialization
             #[serde(default)]
             cameras: Vec<(CustomEntity, Camera)>,
@@ -945,6 +1338,8 @@ impl CustomWorld {
             #[serde(default)]
             parents: Vec<(CustomEntity, CustomEntity)>,
             #[serde(default)]
+            root_order: Vec<CustomEntity>,
+            #[serde(default)]
             names: Vec<(CustomEntity, String)>,
             #[serde(default)]
             sprite_sheets: Vec<(CustomEntity, SpriteSheet)>,
@@ -966,6 +1361,22 @@ impl CustomWorld {
             model_3ds: Vec<(CustomEntity, Model3D)>,
             #[serde(default)]
             ldtk_entities: Vec<(CustomEntity, LdtkEntity)>,
+            #[serde(default)]
+            sprite_materials: Vec<(CustomEntity, SpriteMaterial)>,
+            #[serde(default)]
+            editor_icons: Vec<(CustomEntity, EditorIcon)>,
+            #[serde(default)]
+            trigger_zones: Vec<(CustomEntity, TriggerZone)>,
+            #[serde(default)]
+            color_fades: Vec<(CustomEntity, ColorFade)>,
+            #[serde(default)]
+            save_states: Vec<(CustomEntity, SaveState)>,
+            #[serde(default)]
+            guids: Vec<(CustomEntity, Guid)>,
+            #[serde(default)]
+            custom_components: custom_components::SavedCustomComponents,
+            #[serde(default)]
+            preload_assets: Vec<String>,
         }
 
         let data: SceneData = serde_json::from_str(json)?;
@@ -999,6 +1410,9 @@ impl CustomWorld {
         for (entity, collider) in data.colliders_3d {
             self.colliders_3d.insert(entity, collider);
         }
+        for (entity, collider) in data.polygon_colliders {
+            self.polygon_colliders.insert(entity, collider);
+        }
         for (entity, rigidbody) in data.rigidbodies {
             self.rigidbodies.insert(entity, rigidbody);
         }
@@ -1053,12 +1467,36 @@ impl CustomWorld {
         for (entity, ldtk_entity) in data.ldtk_entities {
             self.ldtk_entities.insert(entity, ldtk_entity);
         }
+        for (entity, material) in data.sprite_materials {
+            self.sprite_materials.insert(entity, material);
+        }
+        for (entity, icon) in data.editor_icons {
+            self.editor_icons.insert(entity, icon);
+        }
+        for (entity, zone) in data.trigger_zones {
+            self.trigger_zones.insert(entity, zone);
+        }
+        for (entity, fade) in data.color_fades {
+            self.color_fades.insert(entity, fade);
+        }
+        for (entity, save_state) in data.save_states {
+            self.save_states.insert(entity, save_state);
+        }
+        for (entity, guid) in data.guids {
+            self.guids.insert(entity, guid);
+        }
+        for (name, instances) in data.custom_components {
+            self.custom_components.insert(name, instances.into_iter().collect());
+        }
+        self.preload_assets = data.preload_assets;
+        self.scene_settings = data.scene_settings;
         
         // Reconstruct hierarchy
         for (child, parent) in data.parents {
             self.parents.insert(child, parent);
             self.children.entry(parent).or_default().push(child);
         }
+        self.root_order = data.root_order;
 
         // Ensure all entities have active and layer (backward compatibility)
         for &entity in self.transforms.keys() {
@@ -1114,16 +1552,7 @@ impl EcsWorld for CustomWorld {
     
     fn set_parent(&mut self, child: Self::Entity, parent: Option<Self::Entity>) -> Result<(), Self::Error> {
         // Check for circular reference if setting a parent
-        if let Some(p) = parent {
-            // Check if parent is actually a descendant of child
-            let mut current = Some(p);
-            while let Some(ancestor) = current {
-                if ancestor == child {
-                    return Err(EcsError::InvalidHierarchy);
-                }
-                current = self.get_parent(ancestor);
-            }
-        }
+        self.check_parent(child, parent)?;
         
         CustomWorld::set_parent(self, child, parent);
         Ok(())
@@ -1173,6 +1602,12 @@ mod custom_world_impls {
     impl_component_access!(CustomWorld, LdtkIntGridCollider, ldtk_intgrid_colliders, CustomEntity);
     impl_component_access!(CustomWorld, Model3D, model_3ds, CustomEntity);
     impl_component_access!(CustomWorld, LdtkEntity, ldtk_entities, CustomEntity);
+    impl_component_access!(CustomWorld, SpriteMaterial, sprite_materials, CustomEntity);
+    impl_component_access!(CustomWorld, EditorIcon, editor_icons, CustomEntity);
+    impl_component_access!(CustomWorld, TriggerZone, trigger_zones, CustomEntity);
+    impl_component_access!(CustomWorld, ColorFade, color_fades, CustomEntity);
+    impl_component_access!(CustomWorld, SaveState, save_states, CustomEntity);
+    impl_component_access!(CustomWorld, Guid, guids, CustomEntity);
 }
 
 // Manual implementations for tuple and primitive types
@@ -1387,4 +1822,45 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn inactive_parent_deactivates_children() {
+        let mut world = CustomWorld::new();
+        let parent = world.spawn();
+        let child = world.spawn();
+        let grandchild = world.spawn();
+        world.set_parent(child, Some(parent));
+        world.set_parent(grandchild, Some(child));
+
+        world.set_active(parent, false);
+        assert!(!world.is_active_in_hierarchy(grandchild));
+        // Own flags are untouched
+        assert!(world.is_active_self(grandchild));
+
+        world.set_active(parent, true);
+        world.set_active(child, false);
+        assert!(world.is_active_in_hierarchy(parent));
+        assert!(!world.is_active_in_hierarchy(grandchild));
+    }
+
+    #[test]
+    fn saved_scene_lists_components_in_entity_order() {
+        let build = |order: &[usize]| {
+            let mut world = CustomWorld::new();
+            let entities: Vec<_> = (0..order.len()).map(|_| world.spawn()).collect();
+            for &index in order {
+                world.names.insert(entities[index], format!("Entity {}", index));
+                world.transforms.insert(entities[index], Transform::default());
+            }
+            world.save_to_json().unwrap()
+        };
+
+        let forward = build(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        let shuffled = build(&[5, 2, 7, 0, 3, 6, 1, 4]);
+        assert_eq!(forward, shuffled);
+
+        let saved: serde_json::Value = serde_json::from_str(&forward).unwrap();
+        let entities: Vec<u64> = saved["names"].as_array().unwrap().iter().map(|entry| entry[0].as_u64().unwrap()).collect();
+        assert!(entities.windows(2).all(|pair| pair[0] < pair[1]));
+    }
 }
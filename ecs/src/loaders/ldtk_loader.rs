@@ -191,7 +191,7 @@ impl LdtkLoader {
                                         let rows = (tileset_def.px_hei / tileset_def.tile_grid_size) as u32;
                                         let tile_count = columns * rows;
 
-                                        let tileset = TileSet::new(
+                                        let mut tileset = TileSet::new(
                                             format!("tileset_{}", uid),
                                             tileset_path_str,
                                             format!("tileset_{}", uid),
@@ -200,6 +200,9 @@ impl LdtkLoader {
                                             columns,
                                             tile_count,
                                         );
+                                        for custom in &tileset_def.custom_data {
+                                            tileset.set_tile_properties(custom.tile_id as u32, crate::TileProperty::parse_custom_data(&custom.data));
+                                        }
                                         let _ = ComponentAccess::<TileSet>::insert(world, entity, tileset);
                                     }
                                 }
@@ -409,15 +412,16 @@ impl LdtkLoader {
 
 /// Rectangle in grid coordinates
 #[derive(Debug, Clone)]
-struct Rectangle {
-    x: u32,
-    y: u32,
-    width: u32,
-    height: u32,
+pub struct Rectangle {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
 }
 
-/// Find rectangles in a 2D grid using greedy meshing algorithm
-fn find_rectangles(grid: &mut Vec<Vec<bool>>, width: u32, height: u32) -> Vec<Rectangle> {
+/// Find rectangles in a 2D grid using greedy meshing algorithm.
+/// Cells covered by a rectangle are cleared from `grid`.
+pub fn find_rectangles(grid: &mut Vec<Vec<bool>>, width: u32, height: u32) -> Vec<Rectangle> {
     let mut rectangles = Vec::new();
     
     for y in 0..height {
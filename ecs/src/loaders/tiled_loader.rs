@@ -1,5 +1,6 @@
-use crate::{World, Entity, Tilemap, TileSet, Tile, Transform};
-use tiled::{Loader, Map};
+use crate::{World, Entity, Tilemap, TileSet, Tile, Transform, TileProperty, TileAnimation, TileAnimationFrame};
+use tiled::{Loader, Map, PropertyValue};
+use std::collections::HashMap;
 use std::path::Path;
 use crate::traits::{EcsWorld, ComponentAccess};
 
@@ -95,7 +96,7 @@ impl TiledLoader {
         for (i, tileset) in map.tilesets().iter().enumerate() {
             let entity = world.spawn();
 
-            let tile_set = TileSet::new(
+            let mut tile_set = TileSet::new(
                 tileset.name.as_str(),
                 tileset.image.as_ref().map(|img| img.source.to_string_lossy().to_string()).unwrap_or_default(),
                 format!("tileset_{}_{}", i, tileset.name),
@@ -105,6 +106,32 @@ impl TiledLoader {
                 tileset.tilecount,
             );
 
+            // Per-tile custom properties and animations
+            for (tile_id, tile) in tileset.tiles() {
+                let properties: HashMap<String, TileProperty> = tile.properties.iter()
+                    .filter_map(|(name, value)| {
+                        let property = match value {
+                            PropertyValue::BoolValue(b) => TileProperty::Bool(*b),
+                            PropertyValue::IntValue(n) => TileProperty::Int(*n as i64),
+                            PropertyValue::FloatValue(f) => TileProperty::Float(*f as f64),
+                            PropertyValue::StringValue(s) | PropertyValue::FileValue(s) => TileProperty::String(s.clone()),
+                            _ => return None,
+                        };
+                        Some((name.clone(), property))
+                    })
+                    .collect();
+                if !properties.is_empty() {
+                    tile_set.set_tile_properties(tile_id, properties);
+                }
+
+                if let Some(frames) = &tile.animation {
+                    let frames = frames.iter()
+                        .map(|frame| TileAnimationFrame { tile_id: frame.tile_id, duration: frame.duration as f32 / 1000.0 })
+                        .collect();
+                    tile_set.animations.insert(tile_id, TileAnimation { frames });
+                }
+            }
+
             let _ = ComponentAccess::<TileSet>::insert(world, entity, tile_set);
             let _ = ComponentAccess::<String>::insert(world, entity, format!("TileSet: {}", tileset.name));
 
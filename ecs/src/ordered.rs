@@ -0,0 +1,54 @@
+//! Entity-id ordered views of the component stores.
+//!
+//! The stores stay HashMaps (lookups are the hot path), but HashMap iteration order
+//! changes from run to run. Anything a player can see or a scene file records — script
+//! update order, physics steps, draw order ties, saved JSON — iterates through these
+//! views instead, so it happens in entity-id order.
+
+use crate::CustomEntity;
+use std::collections::HashMap;
+
+/// Ordered iteration over a component store (`world.sprites.iter_sorted()`)
+pub trait EntityOrdered<T> {
+    /// Entities with the component, lowest id first
+    fn sorted_entities(&self) -> Vec<CustomEntity>;
+
+    /// Entries by entity id, lowest first
+    fn iter_sorted(&self) -> Vec<(CustomEntity, &T)>;
+}
+
+impl<T, S: std::hash::BuildHasher> EntityOrdered<T> for HashMap<CustomEntity, T, S> {
+    fn sorted_entities(&self) -> Vec<CustomEntity> {
+        let mut entities: Vec<CustomEntity> = self.keys().copied().collect();
+        entities.sort_unstable();
+        entities
+    }
+
+    fn iter_sorted(&self) -> Vec<(CustomEntity, &T)> {
+        let mut entries: Vec<(CustomEntity, &T)> = self.iter().map(|(entity, value)| (*entity, value)).collect();
+        entries.sort_unstable_by_key(|(entity, _)| *entity);
+        entries
+    }
+}
+
+/// Owned copies of a store's entries by entity id, for serialization: saved scenes list
+/// components in the same order every time, so version control diffs show real changes
+pub(crate) fn sorted_entries<T: Clone>(store: &HashMap<CustomEntity, T>) -> Vec<(CustomEntity, T)> {
+    store.iter_sorted().into_iter().map(|(entity, value)| (entity, value.clone())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_views_are_in_entity_order_whatever_the_insertion_order() {
+        let mut store = HashMap::new();
+        for entity in [42, 7, 1000, 3, 19] {
+            store.insert(entity, entity * 2);
+        }
+        assert_eq!(store.sorted_entities(), [3, 7, 19, 42, 1000]);
+        assert_eq!(store.iter_sorted(), [(3, &6), (7, &14), (19, &38), (42, &84), (1000, &2000)]);
+        assert_eq!(sorted_entries(&store).first(), Some(&(3, 6)));
+    }
+}
@@ -0,0 +1,290 @@
+//! Sprite picking: which sprite is under a screen point, as the renderer draws it.
+//!
+//! Shared by the editor scene view (click to select) and the runtime
+//! (`Input.pick_entity` / `Input.get_mouse_world_position` in Lua).
+
+use crate::sorting::{SortKey, SortingLayers};
+use crate::{Camera, CameraProjection, CustomEntity, CustomWorld, Sprite, Transform};
+use engine_core::project_settings::AspectPolicy;
+use glam::{EulerRot, Mat4, Quat, Vec2, Vec3};
+
+/// World matrix of a sprite's quad (unit square centered on the origin), or None when
+/// it has no area. Size comes from width/height and pixels_per_unit like the renderer;
+/// sprite_rect only picks the texture region, but an empty one is never drawn.
+/// `pivot` is the sprite's own pivot or its texture's import pivot.
+pub fn sprite_quad_matrix(sprite: &Sprite, transform: &Transform, pivot: [f32; 2]) -> Option<Mat4> {
+    if sprite.pixels_per_unit <= 0.0 || sprite.sprite_rect.is_some_and(|r| r[2] == 0 || r[3] == 0) {
+        return None;
+    }
+
+    let rot = if sprite.billboard {
+        Quat::from_rotation_z(transform.rotation[2].to_radians())
+    } else {
+        Quat::from_euler(
+            EulerRot::XYZ,
+            transform.rotation[0].to_radians(),
+            transform.rotation[1].to_radians(),
+            transform.rotation[2].to_radians(),
+        )
+    };
+    let scale = Vec3::new(
+        transform.scale[0] * sprite.width / sprite.pixels_per_unit,
+        transform.scale[1] * sprite.height / sprite.pixels_per_unit,
+        1.0,
+    );
+    if scale.x == 0.0 || scale.y == 0.0 {
+        return None;
+    }
+
+    // The entity position is the pivot; the quad is centered
+    let [pivot_x, pivot_y] = pivot;
+    let pivot_x = if sprite.flip_x { 1.0 - pivot_x } else { pivot_x };
+    let pivot_y = if sprite.flip_y { 1.0 - pivot_y } else { pivot_y };
+    let pos = Vec3::from(transform.position) + rot * Vec3::new((0.5 - pivot_x) * scale.x, (0.5 - pivot_y) * scale.y, 0.0);
+
+    Some(Mat4::from_scale_rotation_translation(scale, rot, pos))
+}
+
+/// Distance along the ray (in units of `dir`) where it crosses the quad, if it does
+pub fn ray_hits_quad(quad: &Mat4, origin: Vec3, dir: Vec3) -> Option<f32> {
+    let inverse = quad.inverse();
+    let local_origin = inverse.transform_point3(origin);
+    let local_dir = inverse.transform_vector3(dir);
+    if local_dir.z.abs() < f32::EPSILON {
+        return None; // Edge-on
+    }
+    let t = -local_origin.z / local_dir.z;
+    let hit = local_origin + local_dir * t;
+    (t >= 0.0 && hit.x.abs() <= 0.5 && hit.y.abs() <= 0.5).then_some(t)
+}
+
+/// Active sprites under the ray, topmost (drawn last) first.
+/// `texture_pivot` gives the import pivot for sprites without their own.
+pub fn pick_sprites(
+    world: &CustomWorld,
+    layers: &SortingLayers,
+    origin: Vec3,
+    dir: Vec3,
+    texture_pivot: impl Fn(&str) -> [f32; 2],
+) -> Vec<CustomEntity> {
+    let mut hits: Vec<SortKey> = world.sprites.iter()
+        .filter(|(entity, _)| world.is_active_in_hierarchy(**entity))
+        .filter_map(|(entity, sprite)| {
+            let transform = world.world_transform(*entity)?;
+            let pivot = sprite.pivot.unwrap_or_else(|| texture_pivot(&sprite.texture_id));
+            let quad = sprite_quad_matrix(sprite, &transform, pivot)?;
+            ray_hits_quad(&quad, origin, dir).map(|_| SortKey::sprite(layers, *entity, sprite, &transform))
+        })
+        .collect();
+
+    hits.sort_by(|a, b| b.cmp(a));
+    hits.into_iter().map(|key| key.entity).collect()
+}
+
+/// View and projection matrices for a game camera, as the Game View draws it
+pub fn camera_view_projection(camera: &Camera, transform: &Transform, aspect: f32) -> (Mat4, Mat4) {
+    let rot_rad = Vec3::new(
+        transform.rotation[0].to_radians(),
+        transform.rotation[1].to_radians(),
+        transform.rotation[2].to_radians(),
+    );
+    let cam_rotation = Quat::from_euler(EulerRot::YXZ, rot_rad.y, rot_rad.x, rot_rad.z);
+    let view = Mat4::from_rotation_translation(cam_rotation, Vec3::from(transform.position)).inverse();
+
+    let projection = match camera.projection {
+        CameraProjection::Orthographic => {
+            let height = camera.orthographic_size;
+            let width = height * aspect;
+            Mat4::orthographic_rh(-width, width, -height, height, camera.near_clip, camera.far_clip)
+        }
+        CameraProjection::Perspective => {
+            Mat4::perspective_rh(camera.fov.to_radians(), aspect, camera.near_clip, camera.far_clip)
+        }
+    };
+    (view, projection)
+}
+
+/// `camera` as the project's aspect policy frames it on `screen` (the game rect,
+/// already letterboxed by `AspectPolicy::game_rect`): FixedHorizontal grows or
+/// shrinks the visible height so the visible width stays the same
+pub fn framed_camera(camera: &Camera, policy: &AspectPolicy, screen: [f32; 4]) -> Camera {
+    let [_, _, width, height] = camera_pixel_rect(camera, screen);
+    let scale = if width > 0.0 && height > 0.0 { policy.vertical_scale(width / height) } else { 1.0 };
+    let mut framed = camera.clone();
+    if scale != 1.0 {
+        framed.orthographic_size *= scale;
+        framed.fov = (((camera.fov.to_radians() * 0.5).tan() * scale).atan() * 2.0).to_degrees();
+    }
+    framed
+}
+
+/// Active camera drawing to the screen with the lowest depth, with its world transform
+pub fn main_camera(world: &CustomWorld) -> Option<(CustomEntity, &Camera, Transform)> {
+    world.cameras.iter()
+        .filter(|(entity, camera)| camera.target_texture.is_none() && world.is_active_in_hierarchy(**entity))
+        .filter_map(|(entity, camera)| world.world_transform(*entity).map(|t| (*entity, camera, t)))
+        .min_by_key(|(entity, camera, _)| (camera.depth, *entity))
+}
+
+/// Pixel rect `[x, y, width, height]` (top-left origin) the camera draws into, given the
+/// game's area on screen in the same form. `viewport_rect` is normalized with a
+/// bottom-left origin, like Unity.
+pub fn camera_pixel_rect(camera: &Camera, screen: [f32; 4]) -> [f32; 4] {
+    let [vx, vy, vw, vh] = camera.viewport_rect;
+    let [sx, sy, sw, sh] = screen;
+    [sx + vx * sw, sy + (1.0 - vy - vh) * sh, vw * sw, vh * sh]
+}
+
+/// World-space ray (origin on the near plane, unit direction) through a screen point,
+/// or None outside the camera's viewport
+pub fn screen_ray(camera: &Camera, transform: &Transform, screen: [f32; 4], point: Vec2) -> Option<(Vec3, Vec3)> {
+    let [x, y, width, height] = camera_pixel_rect(camera, screen);
+    if width <= 0.0 || height <= 0.0 {
+        return None;
+    }
+    let u = (point.x - x) / width;
+    let v = (point.y - y) / height;
+    if !(0.0..=1.0).contains(&u) || !(0.0..=1.0).contains(&v) {
+        return None;
+    }
+
+    let (view, projection) = camera_view_projection(camera, transform, width / height);
+    let inverse = (projection * view).inverse();
+    let ndc = Vec2::new(u * 2.0 - 1.0, 1.0 - v * 2.0);
+    let near = inverse.project_point3(ndc.extend(0.0));
+    let far = inverse.project_point3(ndc.extend(1.0));
+    let dir = (far - near).try_normalize()?;
+    Some((near, dir))
+}
+
+/// Where a screen point lands on the plane z = `plane_z` (the mouse's world position
+/// in a 2D game), or None outside the viewport or when the ray runs parallel to it
+pub fn screen_to_plane(camera: &Camera, transform: &Transform, screen: [f32; 4], point: Vec2, plane_z: f32) -> Option<Vec3> {
+    let (origin, dir) = screen_ray(camera, transform, screen, point)?;
+    if dir.z.abs() < f32::EPSILON {
+        return None;
+    }
+    let t = (plane_z - origin.z) / dir.z;
+    (t >= 0.0).then(|| origin + dir * t)
+}
+
+/// Screen point (top-left origin, same space as `screen`) a world position projects to,
+/// and whether it is in front of the camera. Points behind a perspective camera are
+/// mirrored so they still land on the side of the screen they are on.
+pub fn world_to_screen(camera: &Camera, transform: &Transform, screen: [f32; 4], point: Vec3) -> Option<(Vec2, bool)> {
+    let [x, y, width, height] = camera_pixel_rect(camera, screen);
+    if width <= 0.0 || height <= 0.0 {
+        return None;
+    }
+    let (view, projection) = camera_view_projection(camera, transform, width / height);
+    let clip = projection * view * point.extend(1.0);
+    if clip.w.abs() < f32::EPSILON {
+        return None;
+    }
+    let in_front = clip.w > 0.0;
+    let mut ndc = clip.truncate().truncate() / clip.w;
+    if !in_front {
+        ndc = -ndc;
+    }
+    let u = (ndc.x + 1.0) * 0.5;
+    let v = (1.0 - ndc.y) * 0.5;
+    Some((Vec2::new(x + u * width, y + v * height), in_front))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sprite(width: f32, height: f32) -> Sprite {
+        Sprite { width, height, pixels_per_unit: 100.0, ..Default::default() }
+    }
+
+    fn at(x: f32, y: f32, z: f32) -> Transform {
+        Transform::with_position(x, y, z)
+    }
+
+    #[test]
+    fn test_fixed_horizontal_keeps_visible_width() {
+        use engine_core::project_settings::{AspectMode, RenderingSettings};
+        let rendering = RenderingSettings { aspect_mode: AspectMode::FixedHorizontal, ..Default::default() };
+        let camera = Camera { orthographic_size: 5.0, ..Default::default() };
+        let transform = at(0.0, 0.0, 10.0);
+
+        // 16:9 reference, shown on a 9:16 portrait screen
+        let screen = [0.0, 0.0, 900.0, 1600.0];
+        let framed = framed_camera(&camera, &rendering.aspect_policy(), screen);
+        let right = screen_to_plane(&framed, &transform, screen, Vec2::new(900.0, 800.0), 0.0).unwrap();
+        assert!((right.x - 5.0 * 16.0 / 9.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_pick_honors_pivot_scale_and_rotation() {
+        let centered = sprite_quad_matrix(&sprite(200.0, 100.0), &at(0.0, 0.0, 0.0), [0.5, 0.5]).unwrap();
+        let down = Vec3::NEG_Z;
+        assert!(ray_hits_quad(&centered, Vec3::new(0.9, 0.4, 10.0), down).is_some());
+        assert!(ray_hits_quad(&centered, Vec3::new(1.1, 0.0, 10.0), down).is_none());
+
+        // Bottom-center pivot: the quad sits above the entity position
+        let feet = sprite_quad_matrix(&sprite(200.0, 100.0), &at(0.0, 0.0, 0.0), [0.5, 0.0]).unwrap();
+        assert!(ray_hits_quad(&feet, Vec3::new(0.0, 0.9, 10.0), down).is_some());
+        assert!(ray_hits_quad(&feet, Vec3::new(0.0, -0.1, 10.0), down).is_none());
+
+        // Rotated 90°: the long side is vertical
+        let mut rotated = at(0.0, 0.0, 0.0);
+        rotated.rotation[2] = 90.0;
+        rotated.scale = [2.0, 1.0, 1.0];
+        let quad = sprite_quad_matrix(&sprite(100.0, 100.0), &rotated, [0.5, 0.5]).unwrap();
+        assert!(ray_hits_quad(&quad, Vec3::new(0.0, 0.9, 10.0), down).is_some());
+        assert!(ray_hits_quad(&quad, Vec3::new(0.9, 0.0, 10.0), down).is_none());
+    }
+
+    #[test]
+    fn test_pick_sorts_topmost_first_in_render_order() {
+        let mut world = CustomWorld::new();
+        let back = world.spawn();
+        let front = world.spawn();
+        let overlay = world.spawn();
+        for (entity, z) in [(back, 0.0), (front, 1.0), (overlay, -5.0)] {
+            world.transforms.insert(entity, at(0.0, 0.0, z));
+            world.sprites.insert(entity, sprite(100.0, 100.0));
+        }
+        world.sprites.get_mut(&overlay).unwrap().order_in_layer = 1;
+
+        let hits = pick_sprites(&world, &SortingLayers::default(), Vec3::new(0.0, 0.0, 10.0), Vec3::NEG_Z, |_| [0.5, 0.5]);
+        assert_eq!(hits, vec![overlay, front, back]);
+    }
+
+    #[test]
+    fn test_screen_ray_respects_viewport_rect() {
+        let mut camera = Camera { orthographic_size: 5.0, ..Default::default() };
+        let transform = at(0.0, 0.0, 10.0);
+
+        // Center of an 800x600 screen offset by (100, 50) is the camera position
+        let hit = screen_to_plane(&camera, &transform, [100.0, 50.0, 800.0, 600.0], Vec2::new(500.0, 350.0), 0.0).unwrap();
+        assert!(hit.truncate().length() < 1e-4);
+        // Top edge is orthographic_size up
+        let top = screen_to_plane(&camera, &transform, [100.0, 50.0, 800.0, 600.0], Vec2::new(500.0, 50.0), 0.0).unwrap();
+        assert!((top.y - 5.0).abs() < 1e-4);
+
+        // Right half of the screen, bottom-left origin: the left half misses
+        camera.viewport_rect = [0.5, 0.0, 0.5, 1.0];
+        assert!(screen_ray(&camera, &transform, [0.0, 0.0, 800.0, 600.0], Vec2::new(100.0, 300.0)).is_none());
+        let hit = screen_to_plane(&camera, &transform, [0.0, 0.0, 800.0, 600.0], Vec2::new(600.0, 300.0), 0.0).unwrap();
+        assert!(hit.truncate().length() < 1e-4);
+    }
+
+    #[test]
+    fn test_world_to_screen_inverts_screen_to_plane() {
+        let camera = Camera { orthographic_size: 5.0, ..Default::default() };
+        let transform = at(2.0, 1.0, 10.0);
+        let screen = [100.0, 50.0, 800.0, 600.0];
+
+        let (point, in_front) = world_to_screen(&camera, &transform, screen, Vec3::new(2.0, 6.0, 0.0)).unwrap();
+        assert!(in_front);
+        assert!((point - Vec2::new(500.0, 50.0)).length() < 1e-3);
+
+        let world = screen_to_plane(&camera, &transform, screen, Vec2::new(300.0, 400.0), 0.0).unwrap();
+        let (back, _) = world_to_screen(&camera, &transform, screen, world).unwrap();
+        assert!((back - Vec2::new(300.0, 400.0)).length() < 1e-3);
+    }
+}
@@ -0,0 +1,399 @@
+//! Entity pools: copies of a template entity made up front and switched on and off
+//! instead of spawned and despawned (bullets, hit effects, enemies in waves).
+//!
+//! An instance is a `duplicate_subtree` copy of the template, detached from the
+//! template's parent and kept inactive while it's free, so physics and rendering skip it
+//! like any other inactive entity. Spawning one puts the template's components back on
+//! it first, so whatever the last user did to it is gone.
+
+use crate::{CustomEntity, CustomWorld};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+/// What `spawn` does when every instance is in use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PoolPolicy {
+    /// Make another instance (up to `max_size`, if set)
+    #[default]
+    Grow,
+    /// Take back the instance spawned longest ago
+    ReuseOldest,
+}
+
+impl PoolPolicy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "grow" => Some(Self::Grow),
+            "reuse_oldest" => Some(Self::ReuseOldest),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolStats {
+    /// Instances made so far
+    pub size: usize,
+    /// Instances currently spawned
+    pub active: usize,
+    /// Most instances spawned at once
+    pub peak_active: usize,
+    pub spawns: u64,
+    /// Spawns that had to make a new instance
+    pub grown: u64,
+    /// Spawns that took back an instance still in use
+    pub reused: u64,
+    /// Spawns refused (Grow at max_size)
+    pub exhausted: u64,
+}
+
+/// A spawned instance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolSpawn {
+    /// Root of the instance
+    pub entity: CustomEntity,
+    /// Entities made for this spawn because the pool grew (their scripts need loading)
+    pub created: Vec<CustomEntity>,
+    /// The instance was taken from an earlier spawn (ReuseOldest)
+    pub reused: bool,
+}
+
+struct EntityPool {
+    template: CustomEntity,
+    policy: PoolPolicy,
+    /// 0: no limit
+    max_size: usize,
+    /// Instance root -> (template entity, instance entity) pairs, root first
+    instances: HashMap<CustomEntity, Vec<(CustomEntity, CustomEntity)>>,
+    free: Vec<CustomEntity>,
+    /// Spawned instances, oldest first
+    active: VecDeque<CustomEntity>,
+    stats: PoolStats,
+}
+
+fn exists(world: &CustomWorld, entity: CustomEntity) -> bool {
+    world.transforms.contains_key(&entity) || world.active.contains_key(&entity)
+}
+
+impl EntityPool {
+    /// Make one more (inactive) instance; returns every entity it's made of
+    fn grow(&mut self, world: &mut CustomWorld) -> Vec<CustomEntity> {
+        let pairs = world.duplicate_subtree(self.template);
+        let Some(&(_, root)) = pairs.first() else {
+            return Vec::new();
+        };
+        world.set_parent(root, None);
+        world.active.insert(root, false);
+        let created = pairs.iter().map(|(_, copy)| *copy).collect();
+        self.instances.insert(root, pairs);
+        self.free.push(root);
+        self.stats.size = self.instances.len();
+        created
+    }
+
+    /// Drop an instance that was despawned behind the pool's back
+    fn discard(&mut self, root: CustomEntity) {
+        self.instances.remove(&root);
+        self.free.retain(|e| *e != root);
+        self.active.retain(|e| *e != root);
+        self.stats.size = self.instances.len();
+        self.stats.active = self.active.len();
+    }
+
+    /// Next free instance that still exists
+    fn pop_free(&mut self, world: &CustomWorld, owners: &mut HashMap<CustomEntity, String>) -> Option<CustomEntity> {
+        while let Some(root) = self.free.pop() {
+            if exists(world, root) {
+                return Some(root);
+            }
+            self.discard(root);
+            owners.remove(&root);
+        }
+        None
+    }
+
+    /// Oldest spawned instance that still exists
+    fn pop_oldest(&mut self, world: &CustomWorld, owners: &mut HashMap<CustomEntity, String>) -> Option<CustomEntity> {
+        while let Some(root) = self.active.pop_front() {
+            if exists(world, root) {
+                return Some(root);
+            }
+            self.discard(root);
+            owners.remove(&root);
+        }
+        None
+    }
+}
+
+/// Named pools, as scripts create them with `Pool.create`
+#[derive(Default)]
+pub struct PoolManager {
+    pools: BTreeMap<String, EntityPool>,
+    /// Instance root -> pool name
+    owners: HashMap<CustomEntity, String>,
+}
+
+impl PoolManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make a pool of `size` instances of `template`. Returns the entities created.
+    pub fn create(
+        &mut self,
+        world: &mut CustomWorld,
+        name: &str,
+        template: CustomEntity,
+        size: usize,
+        policy: PoolPolicy,
+        max_size: usize,
+    ) -> Result<Vec<CustomEntity>, String> {
+        if self.pools.contains_key(name) {
+            return Err(format!("Pool '{}' already exists", name));
+        }
+        if !exists(world, template) {
+            return Err(format!("Pool '{}': template entity {} doesn't exist", name, template));
+        }
+        let mut pool = EntityPool {
+            template,
+            policy,
+            max_size,
+            instances: HashMap::new(),
+            free: Vec::new(),
+            active: VecDeque::new(),
+            stats: PoolStats::default(),
+        };
+        let size = if max_size > 0 { size.min(max_size) } else { size };
+        let mut created = Vec::new();
+        for _ in 0..size {
+            created.extend(pool.grow(world));
+        }
+        // Free instances are handed out in creation order
+        pool.free.reverse();
+        for root in pool.instances.keys() {
+            self.owners.insert(*root, name.to_string());
+        }
+        self.pools.insert(name.to_string(), pool);
+        Ok(created)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.pools.contains_key(name)
+    }
+
+    /// Entity the pool's instances are copies of
+    pub fn template(&self, name: &str) -> Option<CustomEntity> {
+        self.pools.get(name).map(|pool| pool.template)
+    }
+
+    /// Take an instance, reset it to the template and place it at `position` (x, y),
+    /// active. None when the pool is exhausted under Grow with a max_size.
+    pub fn spawn(&mut self, world: &mut CustomWorld, name: &str, position: [f32; 2]) -> Result<Option<PoolSpawn>, String> {
+        let pool = self.pools.get_mut(name).ok_or_else(|| format!("No pool named '{}'", name))?;
+        if !exists(world, pool.template) {
+            return Err(format!("Pool '{}': template entity {} no longer exists", name, pool.template));
+        }
+        let mut created = Vec::new();
+        let mut reused = false;
+
+        let root = match pool.pop_free(world, &mut self.owners) {
+            Some(root) => root,
+            None => match pool.policy {
+                PoolPolicy::Grow if pool.max_size == 0 || pool.instances.len() < pool.max_size => {
+                    created = pool.grow(world);
+                    let Some(root) = pool.pop_free(world, &mut self.owners) else {
+                        return Err(format!("Pool '{}': template entity {} no longer exists", name, pool.template));
+                    };
+                    self.owners.insert(root, name.to_string());
+                    pool.stats.grown += 1;
+                    root
+                }
+                PoolPolicy::Grow => {
+                    pool.stats.exhausted += 1;
+                    return Ok(None);
+                }
+                PoolPolicy::ReuseOldest => {
+                    let Some(root) = pool.pop_oldest(world, &mut self.owners) else {
+                        pool.stats.exhausted += 1;
+                        return Ok(None);
+                    };
+                    reused = true;
+                    pool.stats.reused += 1;
+                    root
+                }
+            },
+        };
+
+        // Children a script despawned stay gone
+        let pairs: Vec<(CustomEntity, CustomEntity)> =
+            pool.instances[&root].iter().copied().filter(|(_, copy)| exists(world, *copy)).collect();
+        world.reset_copies(&pairs);
+        world.set_parent(root, None);
+        if let Some(transform) = world.transforms.get_mut(&root) {
+            transform.position[0] = position[0];
+            transform.position[1] = position[1];
+        }
+        world.active.insert(root, true);
+
+        pool.active.push_back(root);
+        pool.stats.spawns += 1;
+        pool.stats.active = pool.active.len();
+        pool.stats.peak_active = pool.stats.peak_active.max(pool.stats.active);
+        Ok(Some(PoolSpawn { entity: root, created, reused }))
+    }
+
+    /// Deactivate a spawned instance and give it back to its pool. False if the entity
+    /// isn't a pooled instance or is already free.
+    pub fn release(&mut self, world: &mut CustomWorld, entity: CustomEntity) -> bool {
+        let Some(pool) = self.owners.get(&entity).and_then(|name| self.pools.get_mut(name)) else {
+            return false;
+        };
+        let Some(index) = pool.active.iter().position(|e| *e == entity) else {
+            return false;
+        };
+        pool.active.remove(index);
+        world.active.insert(entity, false);
+        pool.free.push(entity);
+        pool.stats.active = pool.active.len();
+        true
+    }
+
+    /// Name of the pool `entity` (an instance root) belongs to
+    pub fn pool_of(&self, entity: CustomEntity) -> Option<&str> {
+        self.owners.get(&entity).map(String::as_str)
+    }
+
+    pub fn is_spawned(&self, entity: CustomEntity) -> bool {
+        self.owners.get(&entity)
+            .and_then(|name| self.pools.get(name))
+            .is_some_and(|pool| pool.active.contains(&entity))
+    }
+
+    /// Entities went away (despawned, scene unloaded): forget the instances among them,
+    /// and drop pools whose template is gone
+    pub fn forget(&mut self, entities: &[CustomEntity]) {
+        for entity in entities {
+            if let Some(name) = self.owners.remove(entity) {
+                if let Some(pool) = self.pools.get_mut(&name) {
+                    pool.instances.remove(entity);
+                    pool.free.retain(|e| e != entity);
+                    pool.active.retain(|e| e != entity);
+                    pool.stats.size = pool.instances.len();
+                    pool.stats.active = pool.active.len();
+                }
+            }
+        }
+        let owners = &mut self.owners;
+        self.pools.retain(|_, pool| {
+            let keep = !entities.contains(&pool.template);
+            if !keep {
+                for root in pool.instances.keys() {
+                    owners.remove(root);
+                }
+            }
+            keep
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.pools.clear();
+        self.owners.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pools.is_empty()
+    }
+
+    /// Statistics of every pool, by name
+    pub fn stats(&self) -> Vec<(String, PoolStats)> {
+        self.pools.iter().map(|(name, pool)| (name.clone(), pool.stats)).collect()
+    }
+
+    pub fn pool_stats(&self, name: &str) -> Option<PoolStats> {
+        self.pools.get(name).map(|pool| pool.stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Sprite, Transform};
+
+    fn bullet(world: &mut CustomWorld) -> CustomEntity {
+        let entity = world.spawn();
+        world.transforms.insert(entity, Transform::with_position(0.0, 0.0, 0.0));
+        world.sprites.insert(entity, Sprite { width: 4.0, ..Default::default() });
+        world.names.insert(entity, "Bullet".to_string());
+        world.active.insert(entity, false);
+        entity
+    }
+
+    #[test]
+    fn test_spawn_resets_to_template_and_release_deactivates() {
+        let mut world = CustomWorld::new();
+        let template = bullet(&mut world);
+        let mut pools = PoolManager::new();
+        let created = pools.create(&mut world, "bullet", template, 2, PoolPolicy::Grow, 0).unwrap();
+        assert_eq!(created.len(), 2);
+        assert!(created.iter().all(|e| !world.is_active_in_hierarchy(*e)));
+
+        let first = pools.spawn(&mut world, "bullet", [3.0, 4.0]).unwrap().unwrap();
+        assert!(first.created.is_empty());
+        assert!(world.is_active_in_hierarchy(first.entity));
+        assert_eq!(world.transforms[&first.entity].position, [3.0, 4.0, 0.0]);
+
+        // Whatever happened to it while spawned is undone by the next spawn
+        world.sprites.get_mut(&first.entity).unwrap().width = 99.0;
+        world.velocities.insert(first.entity, (5.0, 5.0));
+        assert!(pools.release(&mut world, first.entity));
+        assert!(!pools.release(&mut world, first.entity));
+        assert!(!world.is_active_in_hierarchy(first.entity));
+
+        let again = pools.spawn(&mut world, "bullet", [0.0, 1.0]).unwrap().unwrap();
+        assert_eq!(again.entity, first.entity);
+        assert_eq!(world.sprites[&again.entity].width, 4.0);
+        assert!(!world.velocities.contains_key(&again.entity));
+        pools.spawn(&mut world, "bullet", [0.0, 2.0]).unwrap().unwrap();
+        assert_eq!(pools.pool_stats("bullet").unwrap().active, 2);
+    }
+
+    #[test]
+    fn test_exhausted_pool_grows_or_reuses_oldest() {
+        let mut world = CustomWorld::new();
+        let template = bullet(&mut world);
+        let mut pools = PoolManager::new();
+        pools.create(&mut world, "grow", template, 1, PoolPolicy::Grow, 2).unwrap();
+        pools.create(&mut world, "reuse", template, 2, PoolPolicy::ReuseOldest, 0).unwrap();
+
+        pools.spawn(&mut world, "grow", [0.0, 0.0]).unwrap().unwrap();
+        let grown = pools.spawn(&mut world, "grow", [0.0, 0.0]).unwrap().unwrap();
+        assert_eq!(grown.created.len(), 1);
+        assert!(pools.spawn(&mut world, "grow", [0.0, 0.0]).unwrap().is_none());
+        let stats = pools.pool_stats("grow").unwrap();
+        assert_eq!((stats.size, stats.grown, stats.exhausted), (2, 1, 1));
+
+        let oldest = pools.spawn(&mut world, "reuse", [0.0, 0.0]).unwrap().unwrap().entity;
+        pools.spawn(&mut world, "reuse", [0.0, 0.0]).unwrap().unwrap();
+        let reused = pools.spawn(&mut world, "reuse", [7.0, 0.0]).unwrap().unwrap();
+        assert!(reused.reused);
+        assert_eq!(reused.entity, oldest);
+        assert_eq!(world.transforms[&oldest].position[0], 7.0);
+        assert_eq!(pools.pool_stats("reuse").unwrap().size, 2);
+    }
+
+    #[test]
+    fn test_forget_drops_instances_and_orphaned_pools() {
+        let mut world = CustomWorld::new();
+        let template = bullet(&mut world);
+        let mut pools = PoolManager::new();
+        let created = pools.create(&mut world, "bullet", template, 3, PoolPolicy::Grow, 0).unwrap();
+
+        pools.forget(&created[..1]);
+        assert_eq!(pools.pool_stats("bullet").unwrap().size, 2);
+        assert!(pools.pool_of(created[0]).is_none());
+
+        pools.forget(&[template]);
+        assert!(pools.is_empty());
+        assert!(pools.pool_of(created[1]).is_none());
+        assert!(pools.spawn(&mut world, "bullet", [0.0, 0.0]).is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//! Scene Settings: a scene's own environment, saved at the top of the scene file
+//!
+//! Background, ambient light, gravity and which sorting layers draw. Values a scene
+//! leaves unset come from the project (settings.json), so scenes saved before Scene
+//! Settings existed load as before and get the blob the next time they are saved.
+
+use crate::Color;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SceneSettings {
+    /// Shown where no camera clears with its own color: cameras whose clear flags are
+    /// Skybox, and the game view when no camera draws. None uses the project's clear
+    /// color.
+    #[serde(default)]
+    pub background_color: Option<Color>,
+    /// Ambient light of the 2D scene: multiplies every sprite's color (white draws
+    /// sprites as authored)
+    #[serde(default = "default_ambient_color")]
+    pub ambient_color: Color,
+    /// Gravity for this scene, instead of Project Settings > Physics
+    #[serde(default)]
+    pub gravity: Option<f32>,
+    /// Sorting layers that don't draw in this scene, by name
+    #[serde(default)]
+    pub hidden_sorting_layers: Vec<String>,
+}
+
+fn default_ambient_color() -> Color {
+    Color::WHITE
+}
+
+impl Default for SceneSettings {
+    fn default() -> Self {
+        Self {
+            background_color: None,
+            ambient_color: default_ambient_color(),
+            gravity: None,
+            hidden_sorting_layers: Vec::new(),
+        }
+    }
+}
+
+impl SceneSettings {
+    /// The settings saved in a scene file's JSON (defaults for older scenes)
+    pub fn from_scene_json(json: &str) -> Result<Self, serde_json::Error> {
+        #[derive(Deserialize)]
+        struct SceneHeader {
+            #[serde(default)]
+            scene_settings: SceneSettings,
+        }
+        Ok(serde_json::from_str::<SceneHeader>(json)?.scene_settings)
+    }
+
+    /// Gravity the physics simulation uses: this scene's override or the project's
+    pub fn gravity_or(&self, project_gravity: f32) -> f32 {
+        self.gravity.unwrap_or(project_gravity)
+    }
+
+    /// Background color: this scene's or the project's clear color
+    pub fn background_or(&self, project_clear_color: Color) -> Color {
+        self.background_color.unwrap_or(project_clear_color)
+    }
+
+    pub fn is_layer_visible(&self, sorting_layer: &str) -> bool {
+        !self.hidden_sorting_layers.iter().any(|hidden| hidden == sorting_layer)
+    }
+
+    /// Show or hide a sorting layer
+    pub fn set_layer_visible(&mut self, sorting_layer: &str, visible: bool) {
+        self.hidden_sorting_layers.retain(|hidden| hidden != sorting_layer);
+        if !visible {
+            self.hidden_sorting_layers.push(sorting_layer.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_older_scenes_use_project_defaults() {
+        let settings = SceneSettings::from_scene_json(r#"{ "next_entity": 3, "transforms": [] }"#).unwrap();
+        assert_eq!(settings, SceneSettings::default());
+        assert_eq!(settings.gravity_or(150.0), 150.0);
+        assert_eq!(settings.background_or(Color::BLACK), Color::BLACK);
+        assert!(settings.is_layer_visible("Default"));
+
+        let settings = SceneSettings::from_scene_json(
+            r##"{ "scene_settings": { "background_color": "#000000", "gravity": 20.0, "hidden_sorting_layers": ["Foreground"] } }"##,
+        ).unwrap();
+        assert_eq!(settings.gravity_or(150.0), 20.0);
+        assert_eq!(settings.background_or(Color::WHITE), Color::BLACK);
+        assert_eq!(settings.ambient_color, Color::WHITE);
+        assert!(!settings.is_layer_visible("Foreground"));
+
+        let mut settings = settings;
+        settings.set_layer_visible("Foreground", true);
+        settings.set_layer_visible("Background", false);
+        assert_eq!(settings.hidden_sorting_layers, ["Background"]);
+    }
+}
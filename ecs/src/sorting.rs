@@ -0,0 +1,149 @@
+//! 2D draw order shared by the game renderer, the editor views and sprite picking.
+//!
+//! Sprites and tilemap layers sort together by (sorting layer index in the project's
+//! list, order in layer, z, entity id); the entity id keeps ties stable from frame to
+//! frame instead of depending on HashMap iteration order.
+
+use crate::{CustomEntity, Sprite, TilemapRenderer, Transform};
+use std::cmp::Ordering;
+use std::sync::Mutex;
+
+/// Layer every sprite starts on, and where unknown layer names are drawn
+pub const DEFAULT_SORTING_LAYER: &str = "Default";
+
+/// Unknown layer names already warned about (once per name per run)
+static WARNED_LAYERS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// The project's sorting layers, back to front (Project Settings > Rendering)
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortingLayers {
+    names: Vec<String>,
+}
+
+impl Default for SortingLayers {
+    fn default() -> Self {
+        Self { names: vec![DEFAULT_SORTING_LAYER.to_string()] }
+    }
+}
+
+impl SortingLayers {
+    /// Layers in draw order; an empty list means just Default
+    pub fn new(names: Vec<String>) -> Self {
+        if names.is_empty() {
+            Self::default()
+        } else {
+            Self { names }
+        }
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Draw index of a layer. Names missing from the list draw on Default (or the
+    /// first layer if the project removed Default), with a warning the first time.
+    pub fn index(&self, name: &str) -> usize {
+        if let Some(index) = self.names.iter().position(|n| n == name) {
+            return index;
+        }
+        let fallback = self.names.iter().position(|n| n == DEFAULT_SORTING_LAYER).unwrap_or(0);
+        let mut warned = WARNED_LAYERS.lock().unwrap_or_else(|e| e.into_inner());
+        if !warned.iter().any(|n| n == name) {
+            log::warn!("Unknown sorting layer '{}', drawing it on '{}'", name, self.names[fallback]);
+            warned.push(name.to_string());
+        }
+        fallback
+    }
+}
+
+/// Position of a sprite or tilemap in the 2D draw order; lower keys draw first
+#[derive(Debug, Clone, Copy)]
+pub struct SortKey {
+    pub layer: usize,
+    pub order_in_layer: i32,
+    pub z: f32,
+    pub entity: CustomEntity,
+}
+
+impl SortKey {
+    pub fn sprite(layers: &SortingLayers, entity: CustomEntity, sprite: &Sprite, transform: &Transform) -> Self {
+        Self {
+            layer: layers.index(&sprite.sorting_layer),
+            order_in_layer: sprite.order_in_layer,
+            z: transform.position[2],
+            entity,
+        }
+    }
+
+    /// Tilemaps without a TilemapRenderer draw on Default at order 0
+    pub fn tilemap(layers: &SortingLayers, entity: CustomEntity, renderer: Option<&TilemapRenderer>, z: f32) -> Self {
+        Self {
+            layer: renderer.map_or_else(|| layers.index(DEFAULT_SORTING_LAYER), |r| layers.index(&r.sorting_layer)),
+            order_in_layer: renderer.map_or(0, |r| r.order_in_layer),
+            z,
+            entity,
+        }
+    }
+}
+
+impl Ord for SortKey {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.layer.cmp(&other.layer)
+            .then(self.order_in_layer.cmp(&other.order_in_layer))
+            .then(self.z.total_cmp(&other.z))
+            .then(self.entity.cmp(&other.entity))
+    }
+}
+
+impl PartialOrd for SortKey {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SortKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SortKey {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sprite_on(layer: &str, order_in_layer: i32) -> Sprite {
+        Sprite { sorting_layer: layer.to_string(), order_in_layer, ..Default::default() }
+    }
+
+    #[test]
+    fn test_project_layer_order_beats_name_order() {
+        let layers = SortingLayers::new(vec!["Background".into(), "Default".into(), "Foreground".into()]);
+        let at = |z| Transform::with_position(0.0, 0.0, z);
+
+        // "Foreground" sorts before "Background" alphabetically but draws after it
+        let foreground = SortKey::sprite(&layers, 1, &sprite_on("Foreground", -10), &at(-5.0));
+        let background = SortKey::sprite(&layers, 2, &sprite_on("Background", 10), &at(5.0));
+        assert!(background < foreground);
+
+        // Unknown layers fall back to Default
+        assert_eq!(layers.index("Missing"), 1);
+        let unknown = SortKey::sprite(&layers, 3, &sprite_on("Missing", 0), &at(0.0));
+        assert!(background < unknown && unknown < foreground);
+
+        // Tilemap layers slot into the same order
+        let tiles = SortKey::tilemap(&layers, 4, Some(&TilemapRenderer::with_sorting("Foreground", -20)), 0.0);
+        assert!(unknown < tiles && tiles < foreground);
+    }
+
+    #[test]
+    fn test_ties_break_on_entity_id() {
+        let layers = SortingLayers::default();
+        let transform = Transform::default();
+        let sprite = sprite_on("Default", 0);
+        let a = SortKey::sprite(&layers, 7, &sprite, &transform);
+        let b = SortKey::sprite(&layers, 3, &sprite, &transform);
+        assert!(b < a);
+    }
+}
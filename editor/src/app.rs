@@ -1,15 +1,17 @@
 use anyhow::Result;
 use engine_core::EngineContext;
+use engine_core::frame_clock::{self, FrameClock, FramePace};
+use engine_core::project_settings::RenderingSettings;
 use script::ScriptEngine;
 #[cfg(feature = "rapier")]
 use physics::rapier_backend::RapierPhysicsWorld;
 #[cfg(not(feature = "rapier"))]
 use physics::PhysicsWorld;
 use render::{RenderModule, CameraBinding};
-use crate::ui::TransformTool;
 use crate::states::{AppState, LauncherState, EditorState};
 use engine::runtime;
 use engine::runtime::render_system::RenderCache;
+use engine::runtime::WindowController;
 use engine_core::assets::AssetLoader;
 use crate::theme::UnityTheme;
 use winit::{
@@ -17,8 +19,7 @@ use winit::{
     event_loop::{ControlFlow, EventLoop, ActiveEventLoop},
     window::Window,
 };
-use glam::{Vec3, Mat4, Quat, EulerRot};
-use ecs::{Camera, CameraProjection};
+use glam::Vec3;
 
 pub struct EditorApp {
     pub window: Window,
@@ -40,8 +41,11 @@ pub struct EditorApp {
     pub scene_camera_binding: CameraBinding,
     pub grid_renderer: render::GridRenderer,
     pub physics_accumulator: f32,
-    pub fixed_timestep: f32,
     pub render_cache: RenderCache,
+    /// Screen.set_vsync / Cursor.* from play mode scripts
+    pub window_controller: WindowController,
+    /// Real frame deltas and frame pacing (throttled while the window is in the background)
+    pub frame_clock: FrameClock,
 }
 
 impl EditorApp {
@@ -128,7 +132,9 @@ impl EditorApp {
         let physics = PhysicsWorld::new();
 
         // Initialize renderer with window
-        let renderer = pollster::block_on(RenderModule::new(&window))?;
+        let mut renderer = pollster::block_on(RenderModule::new(&window))?;
+        // Screenshots read the Game view texture back, not the editor window
+        renderer.capture_surface = false;
 
         // egui setup
         let egui_ctx = egui::Context::default();
@@ -178,6 +184,7 @@ impl EditorApp {
         );
 
         let render_cache = RenderCache::new();
+        let window_controller = WindowController::play_mode(&renderer);
 
         Ok(Self {
             window,
@@ -196,8 +203,9 @@ impl EditorApp {
             scene_camera_binding,
             grid_renderer,
             physics_accumulator: 0.0,
-            fixed_timestep: 1.0 / 60.0,
             render_cache,
+            window_controller,
+            frame_clock: FrameClock::new(&RenderingSettings::default()),
         })
     }
 
@@ -222,17 +230,27 @@ impl EditorApp {
                         },
                         ..
                     } => {
-                        // If in editor and scene is modified, show exit dialog
-                        if self.app_state == AppState::Editor && self.editor_state.scene_modified {
+                        // Escape first dismisses the command palette or a pending shortcut capture
+                        let editor_escape = self.app_state == AppState::Editor
+                            && matches!(event, WindowEvent::KeyboardInput { .. });
+                        if editor_escape && self.editor_state.is_playing && self.window_controller.release_cursor(&self.window) {
+                            // A game that locked or hid the cursor gives it back first
+                        } else if editor_escape && self.editor_state.command_palette.open {
+                            self.editor_state.command_palette.close();
+                        } else if editor_escape && self.editor_state.shortcut_preferences.is_capturing() {
+                            self.editor_state.shortcut_preferences.cancel_capture();
+                        } else if self.app_state == AppState::Editor && self.editor_state.scene_modified {
+                            // If in editor and scene is modified, show exit dialog
                             self.editor_state.show_exit_dialog = true;
                         } else {
+                            self.editor_state.end_session();
                             target.exit();
                         }
                     }
                     WindowEvent::KeyboardInput { event: key_event, .. } => {
                         self.handle_keyboard_input(key_event);
                     }
-                    WindowEvent::CursorMoved { position, .. } => {
+                    WindowEvent::CursorMoved { position, .. } if !self.window_controller.cursor_locked() => {
                         self.ctx.input.set_mouse_position(position.x as f32, position.y as f32);
                     }
                     WindowEvent::MouseInput { state, button, .. } => {
@@ -252,8 +270,16 @@ impl EditorApp {
                         }
                     }
                     WindowEvent::Resized(physical_size) => {
+                        self.frame_clock.set_minimized(physical_size.width == 0 || physical_size.height == 0);
                         self.renderer.resize(*physical_size);
                     }
+                    // Unfocused or hidden: redraw at the background frame rate
+                    WindowEvent::Focused(focused) => {
+                        self.frame_clock.set_focused(*focused);
+                    }
+                    WindowEvent::Occluded(occluded) => {
+                        self.frame_clock.set_minimized(*occluded);
+                    }
                     WindowEvent::ScaleFactorChanged { .. } => {
                        // Handled by Resized typically
                     }
@@ -263,18 +289,31 @@ impl EditorApp {
                     _ => {}
                 }
             },
+            // Locked cursor (play mode mouse-look): raw motion drives the mouse delta
+            Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta }, .. } if self.window_controller.cursor_locked() => {
+                self.ctx.input.add_mouse_motion(delta.0 as f32, delta.1 as f32);
+            }
             Event::AboutToWait => {
                 // Check if we should exit
                 if self.editor_state.should_exit {
+                    self.editor_state.end_session();
                     target.exit();
                 }
                 
-                // Always request redraw for continuous updates
-                self.window.request_redraw();
-                
-                // When playing, ensure continuous updates
-                if self.editor_state.is_playing {
-                    target.set_control_flow(ControlFlow::Poll);
+                // Frame pacing: the project's frame cap applies to play mode only, the
+                // background rate whenever the window is unfocused or minimized
+                self.frame_clock.configure(&self.editor_state.project_settings.rendering);
+                if !self.editor_state.is_playing {
+                    self.frame_clock.set_target_fps(0);
+                }
+                self.frame_clock.set_vsync(self.renderer.vsync());
+                match self.frame_clock.pace() {
+                    FramePace::Redraw => self.window.request_redraw(),
+                    FramePace::SleepUntil(due) => {
+                        frame_clock::sleep_until(due);
+                        self.window.request_redraw();
+                    }
+                    FramePace::WaitUntil(wake) => target.set_control_flow(ControlFlow::WaitUntil(wake)),
                 }
             }
             _ => {}
@@ -282,12 +321,6 @@ impl EditorApp {
     }
 
     fn handle_keyboard_input(&mut self, key_event: &KeyEvent) {
-        // Update modifiers for shortcut manager (from egui context)
-        if self.app_state == AppState::Editor {
-            let modifiers = self.egui_ctx.input(|i| i.modifiers);
-            self.editor_state.shortcut_manager.update_modifiers(modifiers);
-        }
-        
         // Update InputSystem
         if let winit::keyboard::PhysicalKey::Code(key_code) = key_event.physical_key {
             // Use the same key mapping as Player binary
@@ -313,8 +346,8 @@ impl EditorApp {
             }
         }
 
-        // Handle editor shortcuts (only when not playing)
-        if self.app_state == AppState::Editor && !self.editor_state.is_playing {
+        // Editor shortcuts go through the command registry (play-mode ones like Pause included)
+        if self.app_state == AppState::Editor {
             self.handle_editor_shortcuts(key_event);
         }
 
@@ -333,150 +366,29 @@ impl EditorApp {
     }
 
     fn handle_editor_shortcuts(&mut self, key_event: &KeyEvent) {
+        if key_event.state != ElementState::Pressed || key_event.repeat {
+            return;
+        }
         if let winit::keyboard::PhysicalKey::Code(key_code) = key_event.physical_key {
-            if key_event.state == ElementState::Pressed {
-                if let Some(shortcut) = self.editor_state.shortcut_manager.check_shortcut(key_code) {
-                    use crate::EditorShortcut;
-                    match shortcut {
-                        EditorShortcut::ViewTool => {
-                            self.editor_state.current_tool = TransformTool::View;
-                            self.editor_state.console.info("Tool: View (Q)".to_string());
-                        }
-                        EditorShortcut::MoveTool => {
-                            self.editor_state.current_tool = TransformTool::Move;
-                            self.editor_state.console.info("Tool: Move (W)".to_string());
-                        }
-                        EditorShortcut::RotateTool => {
-                            self.editor_state.current_tool = TransformTool::Rotate;
-                            self.editor_state.console.info("Tool: Rotate (E)".to_string());
-                        }
-                        EditorShortcut::ScaleTool => {
-                            self.editor_state.current_tool = TransformTool::Scale;
-                            self.editor_state.console.info("Tool: Scale (R)".to_string());
-                        }
-                        EditorShortcut::Delete => {
-                            if let Some(entity) = self.editor_state.selected_entity {
-                                self.editor_state.world.despawn(entity);
-                                self.editor_state.entity_names.remove(&entity);
-                                self.editor_state.selected_entity = None;
-                                self.editor_state.scene_modified = true;
-                                self.editor_state.console.info("Entity deleted".to_string());
-                            }
-                        }
-                        EditorShortcut::FrameSelected => {
-                            if let Some(entity) = self.editor_state.selected_entity {
-                                if let Some(transform) = self.editor_state.world.transforms.get(&entity) {
-                                    let pos = glam::Vec3::new(transform.x(), transform.y(), 0.0);
-                                    let size = if let Some(sprite) = self.editor_state.world.sprites.get(&entity) {
-                                        glam::Vec2::new(sprite.width, sprite.height)
-                                    } else {
-                                        glam::Vec2::new(50.0, 50.0)
-                                    };
-                                    let viewport = glam::Vec2::new(800.0, 600.0);
-                                    let size_scalar = size.length(); // Convert Vec2 to scalar
-                                    self.editor_state.scene_camera.frame_object(pos, size_scalar, viewport);
-                                    self.editor_state.console.info("Framed selected object (F)".to_string());
-                                }
-                            }
-                        }
-                        EditorShortcut::ToggleGrid => {
-                            self.editor_state.scene_grid.toggle();
-                            let status = if self.editor_state.scene_grid.enabled { "ON" } else { "OFF" };
-                            self.editor_state.console.info(format!("Grid: {}", status));
-                        }
-                        EditorShortcut::Duplicate => {
-                            if let Some(entity) = self.editor_state.selected_entity {
-                                if let Some(new_entity) = self.editor_state.clipboard.duplicate_entity(
-                                    entity,
-                                    &mut self.editor_state.world,
-                                    &mut self.editor_state.entity_names
-                                ) {
-                                    self.editor_state.selected_entity = Some(new_entity);
-                                    self.editor_state.scene_modified = true;
-                                    self.editor_state.console.info("Entity duplicated (Ctrl+D)".to_string());
-                                }
-                            }
-                        }
-                        EditorShortcut::Copy => {
-                            if let Some(entity) = self.editor_state.selected_entity {
-                                self.editor_state.clipboard.copy_entity(
-                                    entity,
-                                    &self.editor_state.world,
-                                    &self.editor_state.entity_names
-                                );
-                                self.editor_state.console.info("Entity copied (Ctrl+C)".to_string());
-                            }
-                        }
-                        EditorShortcut::Paste => {
-                            let new_entities = self.editor_state.clipboard.paste(
-                                &mut self.editor_state.world,
-                                &mut self.editor_state.entity_names,
-                                Some([10.0, 10.0, 0.0]) // Offset by 10 pixels
-                            );
-                            if let Some(&new_entity) = new_entities.first() {
-                                self.editor_state.selected_entity = Some(new_entity);
-                                self.editor_state.scene_modified = true;
-                                self.editor_state.console.info("Entity pasted (Ctrl+V)".to_string());
-                            }
-                        }
-                        EditorShortcut::Undo => {
-                            if self.editor_state.undo_stack.undo(
-                                &mut self.editor_state.world,
-                                &mut self.editor_state.entity_names
-                            ) {
-                                self.editor_state.scene_modified = true;
-                                if let Some(desc) = self.editor_state.undo_stack.undo_description() {
-                                    self.editor_state.console.info(format!("Undo: {} (Ctrl+Z)", desc));
-                                } else {
-                                    self.editor_state.console.info("Undo (Ctrl+Z)".to_string());
-                                }
-                            } else {
-                                self.editor_state.console.warning("Nothing to undo".to_string());
-                            }
-                        }
-                        EditorShortcut::Redo => {
-                            if self.editor_state.undo_stack.redo(
-                                &mut self.editor_state.world,
-                                &mut self.editor_state.entity_names
-                            ) {
-                                self.editor_state.scene_modified = true;
-                                if let Some(desc) = self.editor_state.undo_stack.redo_description() {
-                                    self.editor_state.console.info(format!("Redo: {} (Ctrl+Y)", desc));
-                                } else {
-                                    self.editor_state.console.info("Redo (Ctrl+Y)".to_string());
-                                }
-                            } else {
-                                self.editor_state.console.warning("Nothing to redo".to_string());
-                            }
-                        }
-                        EditorShortcut::SaveScene => {
-                            // Save scene (Ctrl+S)
-                            if let Some(ref path) = self.editor_state.current_scene_path.clone() {
-                                if let Err(e) = self.editor_state.save_scene(path) {
-                                    self.editor_state.console.error(format!("Failed to save: {}", e));
-                                } else {
-                                    self.editor_state.console.info("Scene saved (Ctrl+S)".to_string());
-                                    self.editor_state.autosave.reset(); // Reset auto-save timer
-                                }
-                            } else {
-                                self.editor_state.console.warning("No scene to save. Use File → Save Scene As...".to_string());
-                            }
-                        }
-                        EditorShortcut::Exit => {
-                            // Exit editor (Ctrl+Q)
-                            self.editor_state.show_exit_dialog = true;
-                        }
-                        _ => {
-                            // Other shortcuts not yet implemented
-                        }
-                    }
-                }
-            }
+            let modifiers = self.egui_ctx.input(|i| i.modifiers);
+            // Text fields own Delete/Ctrl+A/C/V/Z/Y while focused
+            let typing = self.egui_ctx.wants_keyboard_input();
+            let flying = self.editor_state.scene_view_mode == crate::ui::scene_view::SceneViewMode::Mode3D
+                && self.egui_ctx.input(|i| i.pointer.button_down(egui::PointerButton::Secondary));
+            crate::shortcuts_handler::handle_key_press(&mut self.editor_state, key_code, modifiers, typing, flying);
         }
     }
 
     fn render(&mut self, target: &ActiveEventLoop) {
-        let _dt = 1.0 / 60.0; // Fixed time step for now
+        // Real time since the last frame (play mode scales it by Time.time_scale)
+        let dt = self.frame_clock.tick();
+
+        // Frame boundary for the profiler overlay (no-op while it's hidden)
+        profiler::new_frame();
+        // Script timings for the Game view's performance HUD and the script budget warnings
+        let playing = self.editor_state.is_playing;
+        profiler::scripts::set_hud_visible(playing && self.editor_state.game_view_settings.show_performance_hud);
+        profiler::scripts::set_budget_tracking(playing && self.editor_state.project_settings.scripting.budget().is_some());
 
         // Don't clear input here - let PlayModeSystem handle it after scripts run
         self.ctx.input.update_gamepads();
@@ -489,18 +401,22 @@ impl EditorApp {
         
         self.egui_ctx.begin_frame(raw_input);
 
-        // Auto-save logic (only in editor mode)
+        // Session journal (crash recovery) and background autosave results
+        if self.app_state == AppState::Editor {
+            self.editor_state.update_session_journal();
+            self.editor_state.autosave.refresh_settings();
+            match self.editor_state.autosave.poll_autosave() {
+                Some(Ok(autosave_path)) => self.editor_state.console.info(format!("Auto-saved to {}", autosave_path.display())),
+                Some(Err(e)) => self.editor_state.console.error(format!("Auto-save failed: {}", e)),
+                None => {}
+            }
+        }
+
+        // Auto-save logic (only in editor mode, never while playing)
         if self.app_state == AppState::Editor && !self.editor_state.is_playing {
             if self.editor_state.autosave.should_save() && self.editor_state.scene_modified {
                 if let Some(scene_path) = &self.editor_state.current_scene_path {
-                    let autosave_path = self.editor_state.autosave.create_autosave_path(scene_path);
-                    if let Ok(json) = self.editor_state.world.save_to_json() {
-                        if std::fs::write(&autosave_path, json).is_ok() {
-                            self.editor_state.autosave.mark_saved();
-                            self.editor_state.console.info(format!("Auto-saved to {}", autosave_path.display()));
-                            let _ = self.editor_state.autosave.cleanup_old_autosaves(scene_path);
-                        }
-                    }
+                    self.editor_state.autosave.start_autosave(&self.editor_state.world, scene_path);
                 }
             }
         }
@@ -524,7 +440,8 @@ impl EditorApp {
                 );
             }
             AppState::Editor => {
-                self.render_editor_ui();
+                profiler::profile_scope!("editor.ui");
+                self.render_editor_ui(dt);
             }
         }
 
@@ -545,8 +462,13 @@ impl EditorApp {
         // This ensures child entities follow their parents
         if self.app_state == AppState::Editor {
              runtime::transform_system::update_global_transforms(&mut self.editor_state.world);
+             self.editor_state.world.index_sprite_sheets();
         }
 
+        // GPU timings of earlier frames arrive a few frames late; pick up whatever is ready
+        self.renderer.gpu_timer.collect(&self.renderer.device);
+        self.renderer.gpu_timer.begin_frame();
+
         // Render Game World to Offscreen Textures (for Editor Game View) before the main render pass
         if self.app_state == AppState::Editor {
             // Ensure Asset meshes are loaded (idempotent check)
@@ -562,8 +484,31 @@ impl EditorApp {
                      &*self.ctx.asset_loader,
                  );
             }
+
+            // Render textures: load the project's when it opens, and again whenever one
+            // is created or edited (re-created on the GPU if its size or format changed)
+            let reload_render_textures = crate::ui::texture_inspector::take_render_texture_reload(&self.egui_ctx);
+            if let Some(ref project_path) = self.editor_state.current_project_path {
+                if reload_render_textures || self.renderer.render_textures.source_dir() != Some(project_path.as_path()) {
+                    let errors = self.renderer.render_textures.load_directory(
+                        &self.renderer.device,
+                        &mut self.renderer.texture_manager,
+                        project_path,
+                    );
+                    for error in errors {
+                        self.editor_state.console.warning(error);
+                    }
+                }
+            }
+            self.editor_state.ui_manager.set_safe_area(self.editor_state.project_settings.rendering.safe_area.active_rect());
+            self.editor_state.ui_manager.sync_render_textures(
+                &mut self.egui_renderer,
+                &self.renderer.device,
+                &self.renderer.render_textures,
+            );
             
             // Render Scene View and Game View to their respective offscreen textures
+            profiler::profile_scope!("render.offscreen_views");
             self.render_offscreen_views();
         }
 
@@ -616,7 +561,7 @@ impl EditorApp {
                 })],
                 depth_stencil_attachment: None,
                 occlusion_query_set: None,
-                timestamp_writes: None,
+                timestamp_writes: self.renderer.gpu_timer.pass_timestamp_writes("render.ui"),
             });
 
             // SAFETY: egui_wgpu::Renderer::render requires 'static RenderPass due to internal
@@ -631,8 +576,13 @@ impl EditorApp {
             );
         }
 
-        self.renderer.queue.submit(std::iter::once(encoder.finish()));
-        output.present();
+        {
+            profiler::profile_scope!("render.submit");
+            self.renderer.gpu_timer.end_frame(&mut encoder);
+            self.renderer.queue.submit(std::iter::once(encoder.finish()));
+            self.renderer.gpu_timer.after_submit();
+            output.present();
+        }
 
         // Free textures
         for id in &full_output.textures_delta.free {
@@ -640,26 +590,16 @@ impl EditorApp {
         }
     }
 
-    fn render_editor_ui(&mut self) {
+    fn render_editor_ui(&mut self, dt: f32) {
         // Local request flags - Moved to EditorLogic::handle_editor_frame
         // We don't need them here anymore.
 
-        // Handle Q/W/E/R keyboard shortcuts for transform tools
-        self.egui_ctx.input(|i| {
-            if i.key_pressed(egui::Key::Q) {
-                self.editor_state.current_tool = TransformTool::View;
-                self.editor_state.console.info("Tool: View (Q)".to_string());
-            } else if i.key_pressed(egui::Key::W) {
-                self.editor_state.current_tool = TransformTool::Move;
-                self.editor_state.console.info("Tool: Move (W)".to_string());
-            } else if i.key_pressed(egui::Key::E) {
-                self.editor_state.current_tool = TransformTool::Rotate;
-                self.editor_state.console.info("Tool: Rotate (E)".to_string());
-            } else if i.key_pressed(egui::Key::R) {
-                self.editor_state.current_tool = TransformTool::Scale;
-                self.editor_state.console.info("Tool: Scale (R)".to_string());
-            }
-        });
+        // Commands queued by shortcuts, the command palette and menus since the last frame
+        crate::systems::editor_commands::EditorCommandSystem::run_queued(
+            &self.egui_ctx,
+            &mut self.editor_state,
+            &mut self.script_engine,
+        );
 
         // Initialize asset manager if not yet initialized
         if self.editor_state.asset_manager.is_none() {
@@ -745,8 +685,22 @@ impl EditorApp {
             self.editor_state.map_manager.clear_hot_reload_error();
         }
 
+        // Open scene file changed outside the editor
+        self.editor_state.poll_scene_file();
+
+        // Custom component definitions (scripts/components.lua) changed
+        self.editor_state.poll_component_definitions();
+
+        // Large textures stream in their full-size levels over the next frames
+        self.renderer.texture_manager.set_streaming(engine::texture_manager::texture_streaming(&self.editor_state.project_settings.rendering));
+        self.renderer.texture_manager.update_streaming(&self.renderer.device, &self.renderer.queue);
+
         // Main Editor Logic
+        if self.editor_state.is_playing {
+            self.window_controller.sync_script(&self.script_engine, &self.window, &self.renderer);
+        }
         let asset_loader = self.ctx.asset_loader.clone();
+        let fixed_timestep = self.editor_state.project_settings.fixed_timestep();
         crate::editor_logic::EditorLogic::handle_editor_frame(
             &self.egui_ctx,
             &mut self.app_state,
@@ -755,8 +709,8 @@ impl EditorApp {
             &mut self.script_engine,
             &mut self.physics,
             &mut self.physics_accumulator,
-            self.fixed_timestep,
-            1.0 / 60.0, // dt
+            fixed_timestep,
+            dt,
             &mut self.game_view_renderer,
             &self.renderer.device,
             &self.renderer.queue,
@@ -768,14 +722,63 @@ impl EditorApp {
             &mut self.render_cache,
         );
         
+        // Screen / Cursor changes from this frame's scripts (fullscreen, resolution and title
+        // only apply in builds); stopping play gives the editor its cursor and vsync back
+        if self.editor_state.is_playing {
+            self.window_controller.apply_commands(&self.script_engine, &self.window, &mut self.renderer);
+            for note in self.window_controller.take_notes() {
+                self.editor_state.console.info(note);
+            }
+        } else {
+            self.window_controller.restore(&self.window, &mut self.renderer);
+        }
+
+        // Screenshots and GIF clips of the Game view (View menu, F9, Screenshot.* from scripts)
+        self.update_screen_capture();
+
         // Clear input state if not in play mode (PlayModeSystem handles it when playing)
         if !self.editor_state.is_playing {
             self.ctx.input.begin_frame();
         }
     }
 
+    /// Hand this frame's capture requests over, record GIF frames only while playing, and
+    /// report saved files and failures in the console
+    fn update_screen_capture(&mut self) {
+        use crate::game_view_renderer::CaptureRequest;
+
+        let settings = &self.editor_state.project_settings.capture;
+        let capture = &mut self.renderer.screen_capture;
+        if let Some(project_path) = &self.editor_state.current_project_path {
+            capture.set_output_dir(project_path.clone());
+        }
+        capture.set_gif_settings(runtime::script_system::gif_settings(settings));
+        capture.set_gif_recording(self.editor_state.is_playing && settings.gif_enabled);
+        runtime::script_system::apply_capture_commands(&self.script_engine, capture, settings);
+        for request in crate::game_view_renderer::take_capture_requests(&self.egui_ctx) {
+            match request {
+                CaptureRequest::Screenshot => capture.request_screenshot(None, settings.crop_letterbox),
+                CaptureRequest::SaveGif => capture.save_gif(None),
+            }
+        }
+
+        capture.collect(&self.renderer.device);
+        for result in capture.take_results() {
+            match result {
+                Ok(path) => self.editor_state.console.info(format!("Saved {}", path.display())),
+                Err(e) => self.editor_state.console.error(e),
+            }
+        }
+    }
+
     fn render_offscreen_views(&mut self) {
-        // Render Scene View
+        let sorting_layers = ecs::sorting::SortingLayers::new(self.editor_state.project_settings.rendering.sorting_layers.clone());
+
+        // Cameras targeting render textures first, so both views show this frame's contents
+        runtime::render_system::render_camera_targets(&mut self.render_cache, &self.editor_state.world, &sorting_layers, &mut self.renderer);
+
+        // Render Scene View (its draw calls are left out of the performance HUD's count)
+        let game_draw_calls = profiler::gpu::get_stats();
         let width = self.scene_view_renderer.width;
         let height = self.scene_view_renderer.height;
         
@@ -814,7 +817,7 @@ impl EditorApp {
                         stencil_ops: None,
                     }),
                     occlusion_query_set: None,
-                    timestamp_writes: None,
+                    timestamp_writes: self.renderer.gpu_timer.pass_timestamp_writes("render.scene_view"),
                 });
                 
                 // Draw Grid (WGPU)
@@ -829,6 +832,7 @@ impl EditorApp {
                 runtime::render_system::render_game_world(
                     &mut self.render_cache,
                     &self.editor_state.world,
+                    &sorting_layers,
                     &self.renderer.tilemap_renderer,
                     &mut self.renderer.batch_renderer,
                     &mut self.renderer.mesh_renderer,
@@ -840,11 +844,13 @@ impl EditorApp {
                     winit::dpi::PhysicalSize::new(width, height),
                     &mut rpass,
                     projection * view, 
+                    Some(&mut self.renderer.gpu_timer),
                 );
             }
 
             self.renderer.queue.submit(std::iter::once(encoder.finish()));
         }
+        profiler::gpu::restore_counters(game_draw_calls);
         
         // Render Game View
         let game_width = self.game_view_renderer.width;
@@ -854,8 +860,9 @@ impl EditorApp {
              // Find Main Camera
              let mut cameras: Vec<_> = self.editor_state.world.cameras.iter()
                 .filter_map(|(entity, camera)| {
-                    if self.editor_state.world.active.get(entity).copied().unwrap_or(true) {
-                        self.editor_state.world.transforms.get(entity).map(|transform| (entity, camera, transform))
+                    // Cameras drawing into a render texture don't render to the view
+                    if camera.target_texture.is_none() && self.editor_state.world.is_active_in_hierarchy(*entity) {
+                        self.editor_state.world.world_transform(*entity).map(|transform| (entity, camera, transform))
                     } else {
                         None
                     }
@@ -870,38 +877,12 @@ impl EditorApp {
                     label: Some("Game View Encoder"),
                  });
                  
-                 // Calculate View Matrix
-                 let rot_rad = Vec3::new(
-                    transform.rotation[0].to_radians(),
-                    transform.rotation[1].to_radians(),
-                    transform.rotation[2].to_radians(),
-                 );
-                 let cam_rotation = Quat::from_euler(EulerRot::YXZ, rot_rad.y, rot_rad.x, rot_rad.z);
-                 let cam_translation = Vec3::from(transform.position);
-                 let view = Mat4::from_rotation_translation(cam_rotation, cam_translation).inverse();
-                 
-                 // Calculate Projection Matrix
-                 let aspect = game_width as f32 / game_height as f32;
-                 let projection = match camera.projection {
-                    CameraProjection::Orthographic => {
-                         let height = camera.orthographic_size;
-                         let width = height * aspect;
-                         Mat4::orthographic_rh(
-                            -width, width, 
-                            -height, height, 
-                            camera.near_clip, camera.far_clip
-                        )
-                    },
-                    CameraProjection::Perspective => {
-                        Mat4::perspective_rh(
-                            camera.fov.to_radians(), 
-                            aspect, 
-                            camera.near_clip, 
-                            camera.far_clip
-                        )
-                    }
-                 };
-                 
+                 // The world draws into the letterboxed part of the view (the Game tab paints the bars)
+                 let policy = self.editor_state.project_settings.rendering.aspect_policy();
+                 let [game_x, game_y, view_width, view_height] = policy.game_rect([0.0, 0.0, game_width as f32, game_height as f32]);
+                 let camera = ecs::picking::framed_camera(camera, &policy, [0.0, 0.0, view_width, view_height]);
+                 let (view, projection) = ecs::picking::camera_view_projection(&camera, transform, view_width / view_height.max(1.0));
+
                  // Update Camera Binding (Reusing scene_camera_binding is safe because of sequential submission)
                  self.scene_camera_binding.update(&self.renderer.queue, view, projection, Vec3::from(transform.position));
                  
@@ -912,12 +893,7 @@ impl EditorApp {
                             view: &self.game_view_renderer.view,
                             resolve_target: None,
                             ops: wgpu::Operations {
-                                load: wgpu::LoadOp::Clear(wgpu::Color {
-                                    r: camera.background_color[0] as f64,
-                                    g: camera.background_color[1] as f64,
-                                    b: camera.background_color[2] as f64,
-                                    a: camera.background_color[3] as f64,
-                                }),
+                                load: wgpu::LoadOp::Clear(runtime::render_system::camera_background(&camera, &self.editor_state.world.scene_settings)),
                                 store: wgpu::StoreOp::Store,
                             },
                             depth_slice: None,
@@ -931,13 +907,15 @@ impl EditorApp {
                             stencil_ops: None,
                         }),
                         occlusion_query_set: None,
-                        timestamp_writes: None,
+                        timestamp_writes: self.renderer.gpu_timer.pass_timestamp_writes("render.game_view"),
                     });
+                    rpass.set_viewport(game_x, game_y, view_width.max(1.0), view_height.max(1.0), 0.0, 1.0);
                     
                     // Render Game World
                     runtime::render_system::render_game_world(
                         &mut self.render_cache,
                         &self.editor_state.world,
+                        &sorting_layers,
                         &self.renderer.tilemap_renderer,
                         &mut self.renderer.batch_renderer,
                         &mut self.renderer.mesh_renderer,
@@ -949,10 +927,18 @@ impl EditorApp {
                         winit::dpi::PhysicalSize::new(game_width, game_height),
                         &mut rpass,
                         projection * view, 
+                        Some(&mut self.renderer.gpu_timer),
                     );
                  }
+
+                 // Screenshots and GIF frames of the world as the Game view shows it
+                 self.renderer.screen_capture.set_game_rect([game_x, game_y, view_width, view_height]);
+                 self.renderer.screen_capture.record(&self.renderer.device, &mut encoder, &self.game_view_renderer.texture);
                 
                  self.renderer.queue.submit(std::iter::once(encoder.finish()));
+                 self.renderer.screen_capture.after_submit();
+            } else {
+                self.renderer.screen_capture.cancel_screenshots("the Game view has no camera");
             }
         }
     }
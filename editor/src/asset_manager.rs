@@ -1,9 +1,17 @@
 /// Unity/Unreal-like Asset Manager
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::time::{Duration, Instant, SystemTime};
 use serde::{Serialize, Deserialize};
+use crate::thumbnails::ThumbnailCache;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// How often the current folder listing is re-read to pick up changed files
+const LISTING_REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Cap on recursive search results, so searching a huge project stays cheap
+const MAX_SEARCH_RESULTS: usize = 2000;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AssetType {
     Scene,
     Sprite,
@@ -12,6 +20,7 @@ pub enum AssetType {
     Prefab,
     Audio,
     Font,
+    RenderTexture,  // .rendertexture files
     Folder,
     Unknown,
 }
@@ -26,6 +35,7 @@ impl AssetType {
             "prefab" => Self::Prefab,
             "wav" | "mp3" | "ogg" => Self::Audio,
             "ttf" | "otf" => Self::Font,
+            ecs::RENDER_TEXTURE_EXTENSION => Self::RenderTexture,
             _ => Self::Unknown,
         }
     }
@@ -39,6 +49,7 @@ impl AssetType {
             Self::Prefab => "📦",
             Self::Audio => "🔊",
             Self::Font => "🔤",
+            Self::RenderTexture => "📺",
             Self::Folder => "📁",
             Self::Unknown => "📄",
         }
@@ -53,10 +64,39 @@ impl AssetType {
             Self::Prefab => [255, 200, 100],   // Yellow
             Self::Audio => [200, 100, 255],    // Purple
             Self::Font => [255, 100, 150],     // Pink
+            Self::RenderTexture => [100, 220, 220], // Cyan
             Self::Folder => [150, 150, 150],   // Gray
             Self::Unknown => [100, 100, 100],  // Dark gray
         }
     }
+    
+    /// Types offered in the asset browser's filter menu
+    pub const FILTERABLE: [AssetType; 8] = [
+        Self::Sprite,
+        Self::SpriteSheet,
+        Self::Scene,
+        Self::Prefab,
+        Self::Script,
+        Self::Audio,
+        Self::Font,
+        Self::RenderTexture,
+    ];
+    
+    /// Filter menu label, listing the extensions the type covers
+    pub fn filter_label(&self) -> &'static str {
+        match self {
+            Self::Scene => "Scenes (.json)",
+            Self::Sprite => "Textures (.png, .jpg, .bmp, .gif)",
+            Self::SpriteSheet => "Sprite Sheets (.sprite)",
+            Self::Script => "Scripts (.lua)",
+            Self::Prefab => "Prefabs (.prefab)",
+            Self::Audio => "Audio (.wav, .mp3, .ogg)",
+            Self::Font => "Fonts (.ttf, .otf)",
+            Self::RenderTexture => "Render Textures (.rendertexture)",
+            Self::Folder => "Folders",
+            Self::Unknown => "Other",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -125,12 +165,19 @@ pub struct AssetManager {
     /// Sort mode
     pub sort_mode: SortMode,
     
-    /// Search query
+    /// Search query. Words match file names; `.png` / `*.png` words filter by extension.
+    /// A non-empty query searches subfolders too.
     pub search_query: String,
     
+    /// Only show these asset types (empty = all)
+    pub type_filter: Vec<AssetType>,
+    
     /// Selected asset
     pub selected_asset: Option<PathBuf>,
     
+    /// Asset waiting for the user to confirm its deletion
+    pub pending_delete: Option<PathBuf>,
+    
     /// Asset metadata cache
     metadata_cache: HashMap<PathBuf, AssetMetadata>,
     
@@ -146,6 +193,45 @@ pub struct AssetManager {
     /// Navigation history
     history: Vec<PathBuf>,
     history_index: usize,
+    
+    /// Cached listing of the current folder / search
+    listing: Option<FolderListing>,
+    
+    /// Background-generated previews
+    thumbnails: ThumbnailCache,
+}
+
+/// A folder listing plus what it was built from, so it's only re-read when needed
+struct FolderListing {
+    key: ListingKey,
+    folder_modified: Option<SystemTime>,
+    refreshed_at: Instant,
+    assets: Vec<AssetMetadata>,
+}
+
+#[derive(PartialEq)]
+struct ListingKey {
+    path: PathBuf,
+    search_query: String,
+    type_filter: Vec<AssetType>,
+    show_hidden: bool,
+    sort_mode: SortMode,
+}
+
+/// Search query split into name words and extension filters (lowercase, without the dot)
+fn parse_search_query(query: &str) -> (Vec<String>, Vec<String>) {
+    let mut words = Vec::new();
+    let mut extensions = Vec::new();
+    for token in query.split_whitespace().map(|t| t.to_lowercase()) {
+        if let Some(ext) = token.strip_prefix("*.").or_else(|| token.strip_prefix('.')) {
+            if !ext.is_empty() {
+                extensions.push(ext.to_string());
+            }
+        } else {
+            words.push(token);
+        }
+    }
+    (words, extensions)
 }
 
 impl AssetManager {
@@ -160,55 +246,90 @@ impl AssetManager {
             sort_mode: SortMode::Name,
             search_query: String::new(),
             selected_asset: None,
+            pending_delete: None,
             metadata_cache: HashMap::new(),
             favorites: Vec::new(),
             show_hidden: false,
             thumbnail_size: 80.0,
             history: vec![start_path],
             history_index: 0,
+            type_filter: Vec::new(),
+            listing: None,
+            thumbnails: ThumbnailCache::new(project_path),
         }
     }
     
-    /// Get assets in current folder
+    /// Get assets in current folder (or matching the search, including subfolders).
+    /// The listing is cached and only re-read when the folder, query or filters change.
     pub fn get_assets(&mut self) -> Vec<AssetMetadata> {
-        let mut assets = Vec::new();
+        let key = ListingKey {
+            path: self.current_path.clone(),
+            search_query: self.search_query.clone(),
+            type_filter: self.type_filter.clone(),
+            show_hidden: self.show_hidden,
+            sort_mode: self.sort_mode,
+        };
+        let folder_modified = std::fs::metadata(&self.current_path).and_then(|m| m.modified()).ok();
         
-        if let Ok(entries) = std::fs::read_dir(&self.current_path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                
-                // Skip hidden files
-                if !self.show_hidden {
-                    if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                        if name.starts_with('.') || name.starts_with('~') {
-                            continue;
-                        }
-                    }
-                }
-                
-                // Get or create metadata
-                let metadata = self.metadata_cache.entry(path.clone())
-                    .or_insert_with(|| AssetMetadata::from_path(&path).unwrap_or_else(|_| {
-                        AssetMetadata {
-                            path: path.clone(),
-                            name: "Error".to_string(),
-                            asset_type: AssetType::Unknown,
-                            size: 0,
-                            modified: std::time::SystemTime::now(),
-                            is_favorite: false,
-                            labels: Vec::new(),
-                            thumbnail: None,
-                        }
-                    }));
-                
-                // Apply search filter
-                if !self.search_query.is_empty() {
-                    if !metadata.name.to_lowercase().contains(&self.search_query.to_lowercase()) {
-                        continue;
-                    }
-                }
-                
-                assets.push(metadata.clone());
+        let up_to_date = self.listing.as_ref().is_some_and(|listing| {
+            listing.key == key
+                && listing.folder_modified == folder_modified
+                && listing.refreshed_at.elapsed() < LISTING_REFRESH_INTERVAL
+        });
+        
+        if !up_to_date {
+            let assets = self.read_assets();
+            self.listing = Some(FolderListing {
+                key,
+                folder_modified,
+                refreshed_at: Instant::now(),
+                assets,
+            });
+        }
+        
+        self.listing.as_ref().map(|l| l.assets.clone()).unwrap_or_default()
+    }
+    
+    /// Read the current folder from disk, applying search and type filters
+    fn read_assets(&mut self) -> Vec<AssetMetadata> {
+        let (words, extensions) = parse_search_query(&self.search_query);
+        let recursive = !words.is_empty() || !extensions.is_empty();
+        
+        let max_depth = if recursive { usize::MAX } else { 1 };
+        let show_hidden = self.show_hidden;
+        let walker = walkdir::WalkDir::new(&self.current_path)
+            .min_depth(1)
+            .max_depth(max_depth)
+            .into_iter()
+            .filter_entry(move |entry| {
+                // Skip hidden files (and the folders below them, like .cache)
+                show_hidden || !entry.file_name().to_str().is_some_and(|n| n.starts_with('.') || n.starts_with('~'))
+            });
+        
+        let mut assets = Vec::new();
+        for entry in walker.flatten() {
+            let path = entry.path();
+            let mut metadata = AssetMetadata::from_path(path).unwrap_or_else(|_| AssetMetadata {
+                path: path.to_path_buf(),
+                name: "Error".to_string(),
+                asset_type: AssetType::Unknown,
+                size: 0,
+                modified: SystemTime::now(),
+                is_favorite: false,
+                labels: Vec::new(),
+                thumbnail: None,
+            });
+            metadata.is_favorite = self.favorites.contains(&metadata.path);
+            self.metadata_cache.insert(metadata.path.clone(), metadata.clone());
+            
+            if !self.matches_filters(&metadata, &words, &extensions, recursive) {
+                continue;
+            }
+            
+            assets.push(metadata);
+            if assets.len() >= MAX_SEARCH_RESULTS {
+                log::warn!("Asset search stopped at {} results", MAX_SEARCH_RESULTS);
+                break;
             }
         }
         
@@ -218,6 +339,42 @@ impl AssetManager {
         assets
     }
     
+    /// Apply the search words, extension filters and type filter to an asset
+    fn matches_filters(&self, asset: &AssetMetadata, words: &[String], extensions: &[String], recursive: bool) -> bool {
+        if asset.asset_type == AssetType::Folder {
+            // Folders are for navigation; when searching only show matching names
+            return !recursive || (extensions.is_empty() && Self::name_matches(&asset.name, words));
+        }
+        
+        if !self.type_filter.is_empty() && !self.type_filter.contains(&asset.asset_type) {
+            return false;
+        }
+        
+        if !extensions.is_empty() {
+            let ext = asset.path.extension().and_then(|e| e.to_str()).unwrap_or("").to_lowercase();
+            if !extensions.contains(&ext) {
+                return false;
+            }
+        }
+        
+        Self::name_matches(&asset.name, words)
+    }
+    
+    fn name_matches(name: &str, words: &[String]) -> bool {
+        let name = name.to_lowercase();
+        words.iter().all(|w| name.contains(w.as_str()))
+    }
+    
+    /// Thumbnail for an asset; queues background generation the first time it's asked for
+    pub fn thumbnail(&mut self, asset: &AssetMetadata) -> Option<(egui::TextureId, egui::Vec2)> {
+        self.thumbnails.get(asset)
+    }
+    
+    /// Upload thumbnails finished by the background thread. Call once per frame.
+    pub fn update_thumbnails(&mut self, ctx: &egui::Context) {
+        self.thumbnails.update(ctx);
+    }
+    
     /// Sort assets
     fn sort_assets(&self, assets: &mut Vec<AssetMetadata>) {
         // Folders first
@@ -283,6 +440,7 @@ impl AssetManager {
     pub fn navigate_to(&mut self, path: &Path) {
         if path.is_dir() {
             self.current_path = path.to_path_buf();
+            self.thumbnails.cancel_pending();
             
             // Add to history
             if self.history_index < self.history.len() - 1 {
@@ -305,6 +463,7 @@ impl AssetManager {
         if self.history_index > 0 {
             self.history_index -= 1;
             self.current_path = self.history[self.history_index].clone();
+            self.thumbnails.cancel_pending();
             true
         } else {
             false
@@ -316,6 +475,7 @@ impl AssetManager {
         if self.history_index < self.history.len() - 1 {
             self.history_index += 1;
             self.current_path = self.history[self.history_index].clone();
+            self.thumbnails.cancel_pending();
             true
         } else {
             false
@@ -326,6 +486,7 @@ impl AssetManager {
     pub fn toggle_favorite(&mut self, path: &Path) {
         if let Some(metadata) = self.metadata_cache.get_mut(path) {
             metadata.is_favorite = !metadata.is_favorite;
+            self.listing = None;
             
             if metadata.is_favorite {
                 if !self.favorites.contains(&path.to_path_buf()) {
@@ -337,6 +498,27 @@ impl AssetManager {
         }
     }
     
+    /// Delete an asset file (with its .meta) or a folder from disk
+    pub fn delete_asset(&mut self, path: &Path) -> std::io::Result<()> {
+        if path.is_dir() {
+            std::fs::remove_dir_all(path)?;
+        } else {
+            std::fs::remove_file(path)?;
+            let meta_path = engine::assets::metadata::AssetMetadata::get_meta_path(path);
+            if meta_path.exists() {
+                std::fs::remove_file(meta_path)?;
+            }
+        }
+        
+        if self.selected_asset.as_ref().is_some_and(|selected| selected.starts_with(path)) {
+            self.selected_asset = None;
+        }
+        self.favorites.retain(|p| !p.starts_with(path));
+        self.metadata_cache.retain(|p, _| !p.starts_with(path));
+        self.listing = None;
+        Ok(())
+    }
+    
     /// Check if asset is favorite
     pub fn is_favorite(&self, path: &Path) -> bool {
         self.favorites.contains(&path.to_path_buf())
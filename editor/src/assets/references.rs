@@ -0,0 +1,258 @@
+//! Asset reference integrity
+//!
+//! The asset files a scene's components point at: sprite and tileset textures,
+//! Model3D models, scripts and LDtk/Tiled maps. `AssetReferenceIndex` resolves them
+//! against the project the way the loaders do, keeps the ones that can't be found
+//! (Edit > Validate Assets, checked on scene load) and counts how many entities use
+//! each file (the asset browser's "used by" and delete warning).
+
+use ecs::{Entity, World};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Folders (relative to the project) searched for sprite and tileset textures,
+/// in the order the scene texture loader tries them
+pub const TEXTURE_SEARCH_DIRS: [&str; 5] = ["", "assets", "atlas", "tilemaps/atlas", "levels/atlas"];
+
+/// How often the asset browser's "used by" counts are rebuilt
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Which component field an asset reference comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReferenceKind {
+    /// `Sprite.texture_id`
+    Texture,
+    /// `TileSet.texture_path`
+    Tileset,
+    /// `Model3D.asset_id`
+    Model,
+    /// `Script.script_name` (`scripts/<name>.lua`)
+    Script,
+    /// `Map.file_path` (LDtk or Tiled)
+    Map,
+}
+
+impl ReferenceKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReferenceKind::Texture => "Sprite Texture",
+            ReferenceKind::Tileset => "Tileset Texture",
+            ReferenceKind::Model => "Model",
+            ReferenceKind::Script => "Script",
+            ReferenceKind::Map => "Map",
+        }
+    }
+
+    /// File dialog filter for picking a replacement
+    pub fn file_filter(&self) -> (&'static str, &'static [&'static str]) {
+        match self {
+            ReferenceKind::Texture | ReferenceKind::Tileset => ("Image", &["png", "jpg", "jpeg", "bmp", "tga"]),
+            ReferenceKind::Model => ("XSG Model", &["xsg"]),
+            ReferenceKind::Script => ("Lua Script", &["lua"]),
+            ReferenceKind::Map => ("Map", &["ldtk", "tmx"]),
+        }
+    }
+}
+
+/// One entity's reference to an asset file
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssetReference {
+    pub entity: Entity,
+    pub kind: ReferenceKind,
+    /// The value stored in the component
+    pub reference: String,
+}
+
+/// Every asset reference in the world, ordered by entity
+pub fn collect_references(world: &World) -> Vec<AssetReference> {
+    let mut references = Vec::new();
+    let mut add = |entity: Entity, kind: ReferenceKind, reference: &str| {
+        if !reference.is_empty() {
+            references.push(AssetReference { entity, kind, reference: reference.to_string() });
+        }
+    };
+
+    for (&entity, sprite) in &world.sprites {
+        // Render textures aren't files
+        if ecs::RenderTextureAsset::name_from_texture_id(&sprite.texture_id).is_none() {
+            add(entity, ReferenceKind::Texture, &sprite.texture_id);
+        }
+    }
+    for (&entity, tileset) in &world.tilesets {
+        add(entity, ReferenceKind::Tileset, &tileset.texture_path);
+    }
+    for (&entity, model) in &world.model_3ds {
+        add(entity, ReferenceKind::Model, &model.asset_id);
+    }
+    for (&entity, script) in &world.scripts {
+        add(entity, ReferenceKind::Script, &script.script_name);
+    }
+    for (&entity, map) in &world.maps {
+        add(entity, ReferenceKind::Map, &map.file_path);
+    }
+
+    references.sort_by_key(|r| (r.entity, r.kind as u8));
+    references
+}
+
+/// The file a reference points at, if it exists. Resolved the way the loaders do:
+/// textures through `TEXTURE_SEARCH_DIRS`, scripts as `scripts/<name>.lua`, everything
+/// else relative to the project (or its assets folder).
+pub fn resolve(project_path: &Path, kind: ReferenceKind, reference: &str) -> Option<PathBuf> {
+    let candidates: Vec<PathBuf> = match kind {
+        ReferenceKind::Texture | ReferenceKind::Tileset => {
+            TEXTURE_SEARCH_DIRS.iter().map(|dir| project_path.join(dir).join(reference)).collect()
+        }
+        ReferenceKind::Script => {
+            let file = format!("scripts/{}.lua", reference);
+            vec![project_path.join(&file), project_path.join("assets").join(&file)]
+        }
+        ReferenceKind::Model | ReferenceKind::Map => {
+            vec![project_path.join(reference), project_path.join("assets").join(reference)]
+        }
+    };
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+/// The value to store in a component so that it points at `file`
+pub fn reference_for_file(project_path: &Path, kind: ReferenceKind, file: &Path) -> String {
+    let project = canonical(project_path);
+    let file = canonical(file);
+    if kind == ReferenceKind::Script {
+        // Script names are paths under scripts/ without the extension
+        let scripts = [project.join("scripts"), project.join("assets").join("scripts")];
+        let name = scripts
+            .iter()
+            .find_map(|dir| file.strip_prefix(dir).ok())
+            .map(|relative| relative.with_extension(""))
+            .or_else(|| file.file_stem().map(std::path::PathBuf::from))
+            .unwrap_or_default();
+        return name.to_string_lossy().replace('\\', "/");
+    }
+    file.strip_prefix(&project)
+        .unwrap_or(&file)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Point `reference` at a new value. Returns false if the component is gone.
+pub fn retarget(world: &mut World, reference: &AssetReference, value: String) -> bool {
+    let entity = reference.entity;
+    match reference.kind {
+        ReferenceKind::Texture => world.sprites.get_mut(&entity).map(|sprite| sprite.texture_id = value),
+        ReferenceKind::Tileset => world.tilesets.get_mut(&entity).map(|tileset| {
+            tileset.texture_id = value.clone();
+            tileset.texture_path = value;
+        }),
+        ReferenceKind::Model => world.model_3ds.get_mut(&entity).map(|model| model.asset_id = value),
+        ReferenceKind::Script => world.scripts.get_mut(&entity).map(|script| script.script_name = value),
+        ReferenceKind::Map => world.maps.get_mut(&entity).map(|map| map.file_path = value),
+    }
+    .is_some()
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Broken references of the open scene and which entities use each asset file
+#[derive(Default)]
+pub struct AssetReferenceIndex {
+    /// References whose file could not be found, ordered by entity
+    pub broken: Vec<AssetReference>,
+    usage: HashMap<PathBuf, Vec<Entity>>,
+    last_refresh: Option<Instant>,
+}
+
+impl AssetReferenceIndex {
+    /// Check every reference in `world`; returns the number of broken ones
+    pub fn validate(&mut self, world: &World, project_path: &Path) -> usize {
+        self.broken.clear();
+        self.usage.clear();
+        for reference in collect_references(world) {
+            match resolve(project_path, reference.kind, &reference.reference) {
+                Some(path) => {
+                    let users = self.usage.entry(canonical(&path)).or_default();
+                    if !users.contains(&reference.entity) {
+                        users.push(reference.entity);
+                    }
+                }
+                None => self.broken.push(reference),
+            }
+        }
+        self.last_refresh = Some(Instant::now());
+        self.broken.len()
+    }
+
+    /// Re-validate if the last check is older than a couple of seconds
+    pub fn refresh(&mut self, world: &World, project_path: &Path) {
+        if self.last_refresh.is_none_or(|at| at.elapsed() >= REFRESH_INTERVAL) {
+            self.validate(world, project_path);
+        }
+    }
+
+    /// Entities whose components reference the file at `path`, or any file under it
+    /// for a folder
+    pub fn users(&self, path: &Path) -> Vec<Entity> {
+        let path = canonical(path);
+        let mut users: Vec<Entity> = self
+            .usage
+            .iter()
+            .filter(|(file, _)| file.starts_with(&path))
+            .flat_map(|(_, entities)| entities.iter().copied())
+            .collect();
+        users.sort_unstable();
+        users.dedup();
+        users
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_validate_finds_broken_and_used_assets() {
+        let dir = tempdir().unwrap();
+        let project = dir.path();
+        std::fs::create_dir_all(project.join("assets")).unwrap();
+        std::fs::create_dir_all(project.join("scripts")).unwrap();
+        std::fs::write(project.join("assets/player.png"), b"").unwrap();
+        std::fs::write(project.join("scripts/player.lua"), b"").unwrap();
+
+        let mut world = World::new();
+        let player = world.spawn();
+        world.sprites.insert(player, ecs::Sprite { texture_id: "player.png".to_string(), ..Default::default() });
+        world.scripts.insert(player, ecs::Script {
+            script_name: "player".to_string(),
+            enabled: true,
+            parameters: Default::default(),
+            lifecycle_state: Default::default(),
+        });
+        let enemy = world.spawn();
+        world.sprites.insert(enemy, ecs::Sprite { texture_id: "enemy.png".to_string(), ..Default::default() });
+
+        let mut index = AssetReferenceIndex::default();
+        assert_eq!(index.validate(&world, project), 1);
+        assert_eq!(index.broken[0].entity, enemy);
+        assert_eq!(index.broken[0].kind, ReferenceKind::Texture);
+        assert_eq!(index.users(&project.join("assets/player.png")), vec![player]);
+
+        let broken = index.broken[0].clone();
+        assert!(retarget(&mut world, &broken, "assets/player.png".to_string()));
+        assert_eq!(index.validate(&world, project), 0);
+        assert_eq!(index.users(&project.join("assets")).len(), 2);
+    }
+
+    #[test]
+    fn test_reference_for_file() {
+        let project = Path::new("/project");
+        let file = Path::new("/project/scripts/enemy_ai.lua");
+        assert_eq!(reference_for_file(project, ReferenceKind::Script, file), "enemy_ai");
+        let nested = Path::new("/project/scripts/ai/patrol.lua");
+        assert_eq!(reference_for_file(project, ReferenceKind::Script, nested), "ai/patrol");
+        assert_eq!(reference_for_file(project, ReferenceKind::Map, Path::new("/project/levels/a.ldtk")), "levels/a.ldtk");
+    }
+}
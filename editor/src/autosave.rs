@@ -1,22 +1,129 @@
 /// Auto-save system for the editor
-use std::time::{Duration, Instant};
-use std::path::PathBuf;
+///
+/// Writes the open scene to sibling `<scene>~<timestamp>.autosave` files on a
+/// background thread (never the scene file itself), and keeps a session journal
+/// so the next start after a crash can offer the newest autosave.
+use std::time::{Duration, Instant, SystemTime};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::fs;
+use serde::{Deserialize, Serialize};
+
+/// Extension of autosave files (kept off `.json` so they never show up as scenes)
+pub const AUTOSAVE_EXTENSION: &str = "autosave";
+
+/// How often settings files are checked for changes made in Project Settings
+const SETTINGS_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Autosave configuration stored in project settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoSaveSettings {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+
+    /// Seconds between autosaves of a modified scene
+    #[serde(default = "default_interval_seconds")]
+    pub interval_seconds: u64,
+
+    /// Autosave files kept per scene (oldest are pruned)
+    #[serde(default = "default_keep")]
+    pub keep: usize,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_interval_seconds() -> u64 {
+    120
+}
+
+fn default_keep() -> usize {
+    5
+}
+
+impl Default for AutoSaveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            interval_seconds: default_interval_seconds(),
+            keep: default_keep(),
+        }
+    }
+}
+
+impl AutoSaveSettings {
+    /// Load autosave settings from project directory
+    /// Returns default settings if file doesn't exist
+    pub fn load(project_path: &Path) -> Self {
+        let settings_path = Self::get_settings_path(project_path);
+
+        if !settings_path.exists() {
+            return Self::default();
+        }
+
+        match fs::read_to_string(&settings_path) {
+            Ok(json) => match serde_json::from_str(&json) {
+                Ok(settings) => settings,
+                Err(e) => {
+                    log::warn!("Failed to parse autosave settings: {}. Using defaults.", e);
+                    Self::default()
+                }
+            },
+            Err(e) => {
+                log::warn!("Failed to read autosave settings: {}. Using defaults.", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Save autosave settings to project directory
+    pub fn save(&self, project_path: &Path) -> Result<(), String> {
+        let settings_path = Self::get_settings_path(project_path);
+
+        if let Some(parent) = settings_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+        }
+
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize autosave settings: {}", e))?;
+
+        fs::write(&settings_path, json)
+            .map_err(|e| format!("Failed to write autosave settings: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Get the path to the autosave settings file
+    pub fn get_settings_path(project_path: &Path) -> PathBuf {
+        project_path.join(".kiro").join("settings").join("autosave.json")
+    }
+}
 
 pub struct AutoSave {
     /// Last time the scene was saved
     last_save: Instant,
-    
+
     /// Auto-save interval in seconds
     interval: Duration,
-    
+
     /// Whether auto-save is enabled
     enabled: bool,
-    
+
     /// Number of backup files to keep
     backup_count: usize,
-    
+
     /// Last auto-save path
     last_autosave_path: Option<PathBuf>,
+
+    /// Result of the background write in progress
+    pending: Option<Receiver<Result<PathBuf, String>>>,
+
+    /// Project settings file these values came from, re-read when it changes
+    settings_path: Option<PathBuf>,
+    settings_modified: Option<SystemTime>,
+    settings_checked: Instant,
 }
 
 impl AutoSave {
@@ -26,26 +133,30 @@ impl AutoSave {
             last_save: Instant::now(),
             interval: Duration::from_secs(interval_seconds),
             enabled: true,
-            backup_count: 5,
+            backup_count: default_keep(),
             last_autosave_path: None,
+            pending: None,
+            settings_path: None,
+            settings_modified: None,
+            settings_checked: Instant::now(),
         }
     }
-    
+
     /// Check if it's time to auto-save
     pub fn should_save(&self) -> bool {
-        self.enabled && self.last_save.elapsed() >= self.interval
+        self.enabled && self.pending.is_none() && self.last_save.elapsed() >= self.interval
     }
-    
+
     /// Mark that a save was performed
     pub fn mark_saved(&mut self) {
         self.last_save = Instant::now();
     }
-    
+
     /// Reset the timer (e.g., after manual save)
     pub fn reset(&mut self) {
         self.last_save = Instant::now();
     }
-    
+
     /// Get time until next auto-save
     pub fn time_until_next_save(&self) -> Duration {
         let elapsed = self.last_save.elapsed();
@@ -55,115 +166,307 @@ impl AutoSave {
             self.interval - elapsed
         }
     }
-    
+
     /// Get time since last save
     pub fn time_since_last_save(&self) -> Duration {
         self.last_save.elapsed()
     }
-    
+
     /// Enable/disable auto-save
     pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
     }
-    
+
     /// Check if auto-save is enabled
     pub fn is_enabled(&self) -> bool {
         self.enabled
     }
-    
+
     /// Set auto-save interval
     pub fn set_interval(&mut self, seconds: u64) {
         self.interval = Duration::from_secs(seconds);
     }
-    
+
     /// Get auto-save interval in seconds
     pub fn interval_seconds(&self) -> u64 {
         self.interval.as_secs()
     }
-    
+
+    /// Apply project autosave settings
+    pub fn apply_settings(&mut self, settings: &AutoSaveSettings) {
+        self.enabled = settings.enabled;
+        self.interval = Duration::from_secs(settings.interval_seconds.max(10));
+        self.backup_count = settings.keep.max(1);
+    }
+
+    /// Use the project's autosave settings (and follow later edits to them)
+    pub fn watch_settings(&mut self, project_path: &Path) {
+        self.settings_path = Some(AutoSaveSettings::get_settings_path(project_path));
+        self.settings_modified = None;
+        self.apply_settings(&AutoSaveSettings::load(project_path));
+        self.settings_modified = self.settings_path.as_deref().and_then(modified_time);
+        self.settings_checked = Instant::now();
+    }
+
+    /// Re-read the settings file if Project Settings changed it (checked every couple of seconds)
+    pub fn refresh_settings(&mut self) {
+        if self.settings_checked.elapsed() < SETTINGS_CHECK_INTERVAL {
+            return;
+        }
+        self.settings_checked = Instant::now();
+
+        let Some(settings_path) = &self.settings_path else {
+            return;
+        };
+        let modified = modified_time(settings_path);
+        if modified != self.settings_modified {
+            self.settings_modified = modified;
+            // .kiro/settings/autosave.json -> project root
+            if let Some(project_path) = settings_path.ancestors().nth(3) {
+                let settings = AutoSaveSettings::load(project_path);
+                self.apply_settings(&settings);
+            }
+        }
+    }
+
+    /// Write a snapshot of `world` next to `scene_path` on a background thread.
+    /// Poll the outcome with `poll_autosave`.
+    pub fn start_autosave(&mut self, world: &ecs::World, scene_path: &Path) {
+        let autosave_path = self.create_autosave_path(scene_path);
+        self.mark_saved();
+
+        // Never write over the scene itself
+        if autosave_path == scene_path {
+            return;
+        }
+
+        let snapshot = world.clone();
+        let scene_path = scene_path.to_path_buf();
+        let keep = self.backup_count;
+        let (sender, receiver) = channel();
+        self.pending = Some(receiver);
+
+        std::thread::spawn(move || {
+            let result = write_autosave(&snapshot, &autosave_path).map(|_| {
+                let _ = prune_autosaves(&scene_path, keep);
+                autosave_path
+            });
+            let _ = sender.send(result);
+        });
+    }
+
+    /// Outcome of the last `start_autosave`, once the background write has finished
+    pub fn poll_autosave(&mut self) -> Option<Result<PathBuf, String>> {
+        let receiver = self.pending.as_ref()?;
+        match receiver.try_recv() {
+            Ok(result) => {
+                self.pending = None;
+                Some(result)
+            }
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.pending = None;
+                Some(Err("Autosave thread stopped unexpectedly".to_string()))
+            }
+        }
+    }
+
+    /// Whether a background autosave is still being written
+    pub fn is_saving(&self) -> bool {
+        self.pending.is_some()
+    }
+
     /// Create auto-save file path
-    pub fn create_autosave_path(&mut self, scene_path: &PathBuf) -> PathBuf {
-        let parent = scene_path.parent().unwrap_or(scene_path.as_ref());
+    pub fn create_autosave_path(&mut self, scene_path: &Path) -> PathBuf {
+        let parent = scene_path.parent().unwrap_or(scene_path);
         let filename = scene_path.file_stem().unwrap_or_default();
-        let extension = scene_path.extension().unwrap_or_default();
-        
-        // Create autosave filename: scene_autosave_timestamp.json
+
+        // Create autosave filename: scene~timestamp.autosave
         let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
         let autosave_filename = format!(
-            "{}~autosave_{}.{}",
+            "{}~{}.{}",
             filename.to_string_lossy(),
             timestamp,
-            extension.to_string_lossy()
+            AUTOSAVE_EXTENSION
         );
-        
+
         let autosave_path = parent.join(autosave_filename);
         self.last_autosave_path = Some(autosave_path.clone());
         autosave_path
     }
-    
+
     /// Clean up old auto-save files
-    pub fn cleanup_old_autosaves(&self, scene_path: &PathBuf) -> std::io::Result<()> {
-        let parent = scene_path.parent().unwrap_or(scene_path.as_ref());
-        let filename = scene_path.file_stem().unwrap_or_default();
-        
-        // Find all autosave files for this scene
-        let mut autosave_files: Vec<PathBuf> = Vec::new();
-        
-        if let Ok(entries) = std::fs::read_dir(parent) {
-            for entry in entries.flatten() {
-                if let Some(name) = entry.file_name().to_str() {
-                    if name.starts_with(&format!("{}~autosave_", filename.to_string_lossy())) {
-                        autosave_files.push(entry.path());
-                    }
+    pub fn cleanup_old_autosaves(&self, scene_path: &Path) -> std::io::Result<()> {
+        prune_autosaves(scene_path, self.backup_count)
+    }
+
+    /// Get list of available auto-save files
+    pub fn get_autosave_files(&self, scene_path: &Path) -> Vec<PathBuf> {
+        autosave_files(scene_path)
+    }
+}
+
+impl Default for AutoSave {
+    fn default() -> Self {
+        Self::new(default_interval_seconds())
+    }
+}
+
+/// Autosave files for a scene, newest first
+pub fn autosave_files(scene_path: &Path) -> Vec<PathBuf> {
+    let parent = scene_path.parent().unwrap_or(scene_path);
+    let prefix = format!("{}~", scene_path.file_stem().unwrap_or_default().to_string_lossy());
+    let suffix = format!(".{}", AUTOSAVE_EXTENSION);
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    if let Ok(entries) = fs::read_dir(parent) {
+        for entry in entries.flatten() {
+            if let Some(name) = entry.file_name().to_str() {
+                if name.starts_with(&prefix) && name.ends_with(&suffix) {
+                    files.push(entry.path());
                 }
             }
         }
-        
-        // Sort by modification time (newest first)
-        autosave_files.sort_by(|a, b| {
-            let a_time = std::fs::metadata(a).and_then(|m| m.modified()).ok();
-            let b_time = std::fs::metadata(b).and_then(|m| m.modified()).ok();
-            b_time.cmp(&a_time)
-        });
-        
-        // Keep only the newest N files
-        for old_file in autosave_files.iter().skip(self.backup_count) {
-            let _ = std::fs::remove_file(old_file);
-        }
-        
-        Ok(())
     }
-    
-    /// Get list of available auto-save files
-    pub fn get_autosave_files(&self, scene_path: &PathBuf) -> Vec<PathBuf> {
-        let parent = scene_path.parent().unwrap_or(scene_path.as_ref());
-        let filename = scene_path.file_stem().unwrap_or_default();
-        
-        let mut autosave_files: Vec<PathBuf> = Vec::new();
-        
-        if let Ok(entries) = std::fs::read_dir(parent) {
-            for entry in entries.flatten() {
-                if let Some(name) = entry.file_name().to_str() {
-                    if name.starts_with(&format!("{}~autosave_", filename.to_string_lossy())) {
-                        autosave_files.push(entry.path());
-                    }
-                }
+
+    // Sort by modification time, then by the timestamp in the name (newest first)
+    files.sort_by(|a, b| modified_time(b).cmp(&modified_time(a)).then_with(|| b.cmp(a)));
+    files
+}
+
+/// Delete all but the newest `keep` autosaves of a scene
+pub fn prune_autosaves(scene_path: &Path, keep: usize) -> std::io::Result<()> {
+    for old_file in autosave_files(scene_path).iter().skip(keep) {
+        fs::remove_file(old_file)?;
+    }
+    Ok(())
+}
+
+/// Serialize and write through a temp file so a crash mid-write can't leave a torn autosave
+fn write_autosave(world: &ecs::World, autosave_path: &Path) -> Result<(), String> {
+    let json = world.save_to_json().map_err(|e| format!("Failed to serialize scene: {}", e))?;
+    let temp_path = autosave_path.with_extension(format!("{}.tmp", AUTOSAVE_EXTENSION));
+    fs::write(&temp_path, json).map_err(|e| format!("Failed to write autosave: {}", e))?;
+    fs::rename(&temp_path, autosave_path).map_err(|e| format!("Failed to write autosave: {}", e))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// What the editor was doing, rewritten whenever the open scene or its modified
+/// state changes. `clean_shutdown` is only set on a normal exit, so a journal
+/// without it at startup means the last session crashed.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SessionJournal {
+    pub scene_path: Option<PathBuf>,
+    pub scene_modified: bool,
+    pub clean_shutdown: bool,
+}
+
+impl SessionJournal {
+    /// Journal left by the previous session, if any
+    pub fn load(project_path: &Path) -> Option<Self> {
+        let json = fs::read_to_string(Self::get_journal_path(project_path)).ok()?;
+        match serde_json::from_str(&json) {
+            Ok(journal) => Some(journal),
+            Err(e) => {
+                log::warn!("Failed to parse session journal: {}", e);
+                None
             }
         }
-        
-        // Sort by modification time (newest first)
-        autosave_files.sort_by(|a, b| {
-            let a_time = std::fs::metadata(a).and_then(|m| m.modified()).ok();
-            let b_time = std::fs::metadata(b).and_then(|m| m.modified()).ok();
-            b_time.cmp(&a_time)
-        });
-        
-        autosave_files
+    }
+
+    pub fn save(&self, project_path: &Path) -> Result<(), String> {
+        let journal_path = Self::get_journal_path(project_path);
+
+        if let Some(parent) = journal_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create .kiro directory: {}", e))?;
+        }
+
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize session journal: {}", e))?;
+
+        fs::write(&journal_path, json)
+            .map_err(|e| format!("Failed to write session journal: {}", e))
+    }
+
+    /// Newest autosave worth offering: the session ended uncleanly and the
+    /// autosave is newer than the scene file
+    pub fn recoverable_autosave(&self) -> Option<PathBuf> {
+        if self.clean_shutdown {
+            return None;
+        }
+        let scene_path = self.scene_path.as_ref()?;
+        let newest = autosave_files(scene_path).into_iter().next()?;
+        let autosave_time = modified_time(&newest)?;
+        match modified_time(scene_path) {
+            Some(scene_time) if scene_time >= autosave_time => None,
+            _ => Some(newest),
+        }
+    }
+
+    fn get_journal_path(project_path: &Path) -> PathBuf {
+        project_path.join(".kiro").join("session.json")
     }
 }
 
-impl Default for AutoSave {
-    fn default() -> Self {
-        Self::new(300) // 5 minutes default
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn wait_for(autosave: &mut AutoSave) -> Result<PathBuf, String> {
+        loop {
+            if let Some(result) = autosave.poll_autosave() {
+                return result;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    #[test]
+    fn test_autosave_never_touches_scene_and_prunes() {
+        let temp_dir = TempDir::new().unwrap();
+        let scene_path = temp_dir.path().join("main.json");
+        fs::write(&scene_path, "original").unwrap();
+
+        let mut autosave = AutoSave::new(120);
+        autosave.apply_settings(&AutoSaveSettings { enabled: true, interval_seconds: 120, keep: 2 });
+
+        // Pre-existing older autosaves
+        for stamp in ["20200101_000000", "20200101_000001"] {
+            fs::write(temp_dir.path().join(format!("main~{}.autosave", stamp)), "{}").unwrap();
+        }
+
+        let world = ecs::World::new();
+        autosave.start_autosave(&world, &scene_path);
+        let written = wait_for(&mut autosave).unwrap();
+
+        assert_eq!(fs::read_to_string(&scene_path).unwrap(), "original");
+        assert!(written.extension().map_or(false, |e| e == AUTOSAVE_EXTENSION));
+        let files = autosave_files(&scene_path);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0], written);
+    }
+
+    #[test]
+    fn test_journal_offers_newer_autosave_after_crash() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        let scene_path = project_path.join("main.json");
+        fs::write(&scene_path, "{}").unwrap();
+        std::thread::sleep(Duration::from_millis(20));
+        let autosave_path = project_path.join("main~20990101_000000.autosave");
+        fs::write(&autosave_path, "{}").unwrap();
+
+        let journal = SessionJournal { scene_path: Some(scene_path.clone()), scene_modified: true, clean_shutdown: false };
+        journal.save(project_path).unwrap();
+        let loaded = SessionJournal::load(project_path).unwrap();
+        assert_eq!(loaded.recoverable_autosave(), Some(autosave_path));
+
+        let clean = SessionJournal { clean_shutdown: true, ..loaded };
+        assert_eq!(clean.recoverable_autosave(), None);
     }
 }
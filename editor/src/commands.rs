@@ -0,0 +1,434 @@
+//! Editor Command Registry
+//!
+//! Every editor action is a command with an id ("scene.save"), a name, a category and
+//! default shortcuts. Key presses (shortcuts_handler), the command palette and code all
+//! run commands by queueing their id with `CommandRegistry::invoke`; the queue is
+//! executed once per frame by `EditorCommandSystem`. Users can rebind shortcuts in
+//! Preferences > Shortcuts; only the differences from the defaults are saved.
+
+use crate::shortcuts::{KeyChord, ShortcutSettings};
+use crate::ui::dock_layout::WINDOW_PANELS;
+use std::collections::HashMap;
+use winit::keyboard::KeyCode;
+
+/// Prefix of the generated "create an instance of this prefab" commands
+pub const CREATE_PREFAB_PREFIX: &str = "create.prefab:";
+
+/// When a command's shortcuts are live
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandScope {
+    /// Editing the scene (not in play mode)
+    Edit,
+    /// Only during play mode
+    Play,
+    Always,
+}
+
+impl CommandScope {
+    pub fn is_active(self, playing: bool) -> bool {
+        match self {
+            CommandScope::Edit => !playing,
+            CommandScope::Play => playing,
+            CommandScope::Always => true,
+        }
+    }
+
+    /// Both scopes can be live at the same time, so one chord can't serve both
+    pub fn overlaps(self, other: CommandScope) -> bool {
+        self == other || self == CommandScope::Always || other == CommandScope::Always
+    }
+}
+
+/// An editor action that can be bound to keys and found in the command palette
+#[derive(Debug, Clone, PartialEq)]
+pub struct Command {
+    pub id: String,
+    pub name: String,
+    pub category: String,
+    pub default_shortcuts: Vec<KeyChord>,
+    pub scope: CommandScope,
+    /// Shortcut still fires while a text field has keyboard focus
+    pub while_typing: bool,
+}
+
+impl Command {
+    pub fn new(id: impl Into<String>, name: impl Into<String>, category: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            name: name.into(),
+            category: category.into(),
+            default_shortcuts: Vec::new(),
+            scope: CommandScope::Edit,
+            while_typing: true,
+        }
+    }
+
+    pub fn shortcut(mut self, chord: KeyChord) -> Self {
+        self.default_shortcuts.push(chord);
+        self
+    }
+
+    pub fn scope(mut self, scope: CommandScope) -> Self {
+        self.scope = scope;
+        self
+    }
+
+    /// Leave the key to a focused text field (Delete, Ctrl+C, ...)
+    pub fn not_while_typing(mut self) -> Self {
+        self.while_typing = false;
+        self
+    }
+}
+
+/// All editor commands, the user's shortcut overrides and the commands waiting to run
+pub struct CommandRegistry {
+    commands: Vec<Command>,
+    /// Rebound shortcuts by command id; kept for ids that aren't registered (yet)
+    overrides: HashMap<String, Vec<KeyChord>>,
+    queue: Vec<String>,
+}
+
+impl CommandRegistry {
+    /// Registry with the built-in commands and the user's saved shortcuts
+    pub fn new() -> Self {
+        let mut registry = Self::with_defaults();
+        registry.apply_settings(&ShortcutSettings::load().unwrap_or_default());
+        registry
+    }
+
+    /// Registry with the built-in commands and default shortcuts only
+    pub fn with_defaults() -> Self {
+        let mut registry = Self { commands: Vec::new(), overrides: HashMap::new(), queue: Vec::new() };
+        for command in default_commands() {
+            registry.register(command);
+        }
+        registry
+    }
+
+    /// Add a command, replacing one with the same id
+    pub fn register(&mut self, command: Command) {
+        match self.commands.iter_mut().find(|c| c.id == command.id) {
+            Some(existing) => *existing = command,
+            None => self.commands.push(command),
+        }
+    }
+
+    /// Replace every command whose id starts with `prefix` (generated commands such as
+    /// one per prefab). Does nothing when the ids are unchanged.
+    pub fn sync_group(&mut self, prefix: &str, commands: Vec<Command>) {
+        let current = self.commands.iter().filter(|c| c.id.starts_with(prefix)).map(|c| c.id.as_str());
+        if current.eq(commands.iter().map(|c| c.id.as_str())) {
+            return;
+        }
+        self.commands.retain(|c| !c.id.starts_with(prefix));
+        self.commands.extend(commands);
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Command> {
+        self.commands.iter().find(|c| c.id == id)
+    }
+
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    /// Queue a command to run at the start of the next editor frame
+    pub fn invoke(&mut self, id: impl Into<String>) {
+        self.queue.push(id.into());
+    }
+
+    pub fn take_queued(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.queue)
+    }
+
+    /// Current shortcuts of a command (the user's if rebound)
+    pub fn shortcuts(&self, id: &str) -> Vec<KeyChord> {
+        if let Some(chords) = self.overrides.get(id) {
+            return chords.clone();
+        }
+        self.get(id).map(|c| c.default_shortcuts.clone()).unwrap_or_default()
+    }
+
+    /// First shortcut as text ("Ctrl+S") for menus and messages
+    pub fn shortcut_label(&self, id: &str) -> Option<String> {
+        self.shortcuts(id).first().map(|chord| chord.to_string())
+    }
+
+    pub fn is_customized(&self, id: &str) -> bool {
+        self.overrides.contains_key(id)
+    }
+
+    /// Rebind a command; binding it back to its defaults drops the override
+    pub fn set_shortcuts(&mut self, id: &str, chords: Vec<KeyChord>) {
+        let is_default = self.get(id).is_some_and(|c| c.default_shortcuts == chords);
+        if is_default {
+            self.overrides.remove(id);
+        } else {
+            self.overrides.insert(id.to_string(), chords);
+        }
+    }
+
+    pub fn reset(&mut self, id: &str) {
+        self.overrides.remove(id);
+    }
+
+    pub fn reset_all(&mut self) {
+        self.overrides.clear();
+    }
+
+    /// Other commands already using `chord` while `id` could use it
+    pub fn conflicts(&self, id: &str, chord: KeyChord) -> Vec<&Command> {
+        let scope = self.get(id).map(|c| c.scope).unwrap_or(CommandScope::Always);
+        self.commands
+            .iter()
+            .filter(|c| c.id != id && c.scope.overlaps(scope))
+            .filter(|c| self.shortcuts(&c.id).contains(&chord))
+            .collect()
+    }
+
+    /// Command a key press runs, if any
+    pub fn resolve(&self, chord: KeyChord, playing: bool, typing: bool) -> Option<&Command> {
+        self.commands.iter().find(|c| {
+            c.scope.is_active(playing)
+                && (c.while_typing || !typing)
+                && self.shortcuts(&c.id).contains(&chord)
+        })
+    }
+
+    /// Commands matching a fuzzy query on "category name", best match first
+    pub fn search(&self, query: &str) -> Vec<&Command> {
+        let mut matches: Vec<(i32, &Command)> = self
+            .commands
+            .iter()
+            .filter_map(|c| {
+                let score = fuzzy_score(query, &c.name)
+                    .max(fuzzy_score(query, &format!("{} {}", c.category, c.name)).map(|s| s - 1))?;
+                Some((score, c))
+            })
+            .collect();
+        // Stable sort keeps registration order between equal scores
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        matches.into_iter().map(|(_, c)| c).collect()
+    }
+
+    pub fn apply_settings(&mut self, settings: &ShortcutSettings) {
+        self.overrides = settings
+            .bindings
+            .iter()
+            .map(|(id, chords)| (id.clone(), chords.iter().filter_map(|text| KeyChord::parse(text)).collect()))
+            .collect();
+    }
+
+    pub fn settings(&self) -> ShortcutSettings {
+        ShortcutSettings {
+            bindings: self
+                .overrides
+                .iter()
+                .map(|(id, chords)| (id.clone(), chords.iter().map(|chord| chord.to_string()).collect()))
+                .collect(),
+        }
+    }
+
+    /// Write the user's rebindings to .kiro/settings/shortcuts.json
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        self.settings().save()
+    }
+}
+
+impl Default for CommandRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Subsequence match score: None unless every query character appears in order.
+/// Matches at word starts and runs of consecutive characters score higher.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let query: Vec<char> = query.to_lowercase().chars().filter(|c| !c.is_whitespace()).collect();
+    if query.is_empty() {
+        return Some(0);
+    }
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+    for (i, &c) in text.iter().enumerate() {
+        if next == query.len() {
+            break;
+        }
+        if c != query[next] {
+            continue;
+        }
+        score += 1;
+        if i == 0 || !text[i - 1].is_alphanumeric() {
+            score += 3;
+        }
+        if previous == Some(i.wrapping_sub(1)) {
+            score += 2;
+        }
+        previous = Some(i);
+        next += 1;
+    }
+    if next < query.len() {
+        return None;
+    }
+    // Prefer shorter names among equal matches
+    Some(score * 4 - text.len() as i32 / 8)
+}
+
+/// Command id of a docked panel ("window.hierarchy")
+pub fn panel_command_id(tab: &crate::ui::EditorTab) -> String {
+    format!("window.{:?}", tab).to_lowercase()
+}
+
+/// The built-in commands. Default shortcuts match the editor's long-standing keys.
+fn default_commands() -> Vec<Command> {
+    let mut commands = vec![
+        // Tools
+        Command::new("tool.view", "View Tool", "Tools").shortcut(KeyChord::key(KeyCode::KeyQ)),
+        Command::new("tool.move", "Move Tool", "Tools").shortcut(KeyChord::key(KeyCode::KeyW)),
+        Command::new("tool.rotate", "Rotate Tool", "Tools").shortcut(KeyChord::key(KeyCode::KeyE)),
+        Command::new("tool.scale", "Scale Tool", "Tools").shortcut(KeyChord::key(KeyCode::KeyR)),
+
+        // File
+        Command::new("scene.save", "Save Scene", "File").shortcut(KeyChord::key(KeyCode::KeyS).ctrl()),
+        Command::new("scene.revert", "Revert Scene", "File"),
+        Command::new("app.exit", "Back to Launcher", "File").shortcut(KeyChord::key(KeyCode::KeyQ).ctrl()),
+
+        // Edit
+        Command::new("edit.undo", "Undo", "Edit")
+            .shortcut(KeyChord::key(KeyCode::KeyZ).ctrl())
+            .not_while_typing(),
+        Command::new("edit.redo", "Redo", "Edit")
+            .shortcut(KeyChord::key(KeyCode::KeyY).ctrl())
+            .shortcut(KeyChord::key(KeyCode::KeyZ).ctrl().shift())
+            .not_while_typing(),
+        Command::new("edit.copy", "Copy", "Edit").shortcut(KeyChord::key(KeyCode::KeyC).ctrl()).not_while_typing(),
+        Command::new("edit.cut", "Cut", "Edit").not_while_typing(),
+        Command::new("edit.paste", "Paste", "Edit").shortcut(KeyChord::key(KeyCode::KeyV).ctrl()).not_while_typing(),
+        Command::new("edit.duplicate", "Duplicate", "Edit")
+            .shortcut(KeyChord::key(KeyCode::KeyD).ctrl())
+            .not_while_typing(),
+        Command::new("edit.delete", "Delete", "Edit").shortcut(KeyChord::key(KeyCode::Delete)).not_while_typing(),
+        Command::new("edit.select_all", "Select All", "Edit")
+            .shortcut(KeyChord::key(KeyCode::KeyA).ctrl())
+            .not_while_typing(),
+        Command::new("edit.deselect_all", "Deselect All", "Edit")
+            .shortcut(KeyChord::key(KeyCode::KeyA).ctrl().shift())
+            .not_while_typing(),
+        Command::new("edit.command_palette", "Command Palette", "Edit")
+            .shortcut(KeyChord::key(KeyCode::KeyP).ctrl().shift()),
+        Command::new("edit.shortcuts", "Keyboard Shortcuts", "Edit").scope(CommandScope::Always),
+        Command::new("edit.project_settings", "Project Settings", "Edit"),
+        Command::new("edit.validate_assets", "Validate Assets", "Edit").scope(CommandScope::Always),
+
+        // View
+        Command::new("scene.frame_selected", "Frame Selected", "View").shortcut(KeyChord::key(KeyCode::KeyF)),
+        Command::new("view.toggle_grid", "Toggle Grid", "View").shortcut(KeyChord::key(KeyCode::KeyG)),
+        Command::new("view.toggle_gizmos", "Toggle Gizmos", "View").scope(CommandScope::Always),
+        Command::new("view.toggle_snapping", "Toggle Snapping", "View"),
+        Command::new("view.profiler", "Toggle Profiler Overlay", "View")
+            .shortcut(KeyChord::key(KeyCode::F3))
+            .scope(CommandScope::Always),
+
+        // Play
+        Command::new("play.pause", "Pause / Resume", "Play")
+            .shortcut(KeyChord::key(KeyCode::KeyP).ctrl().shift())
+            .scope(CommandScope::Play),
+        Command::new("play.step", "Step Frame", "Play")
+            .shortcut(KeyChord::key(KeyCode::F10))
+            .shortcut(KeyChord::key(KeyCode::KeyP).ctrl().alt())
+            .scope(CommandScope::Play),
+        Command::new("play.screenshot", "Capture Screenshot", "Play").scope(CommandScope::Always),
+        Command::new("play.save_gif", "Save GIF Clip", "Play")
+            .shortcut(KeyChord::key(KeyCode::F9))
+            .scope(CommandScope::Play),
+
+        // Tilemap
+        Command::new("tilemap.reload", "Reload Selected Map", "Tilemap"),
+        Command::new("tilemap.regenerate_colliders", "Regenerate Map Colliders", "Tilemap"),
+        Command::new("tilemap.toggle_layer", "Toggle Layer Visibility", "Tilemap"),
+
+        // GameObject (project prefabs are added by EditorCommandSystem)
+        Command::new("create.empty", "Create Empty", "GameObject"),
+        Command::new("create.player", "Create Player", "GameObject"),
+        Command::new("create.item", "Create Item", "GameObject"),
+    ];
+
+    // Window
+    for tab in WINDOW_PANELS {
+        commands.push(
+            Command::new(panel_command_id(&tab), format!("Open {}", tab.title()), "Window").scope(CommandScope::Always),
+        );
+    }
+    for (id, name) in [
+        ("window.tile_palette", "Open Tile Palette"),
+        ("window.memory", "Open Memory"),
+        ("window.globals", "Open Globals"),
+        ("window.script_debugger", "Open Script Debugger"),
+    ] {
+        commands.push(Command::new(id, name, "Window").scope(CommandScope::Always));
+    }
+
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_shortcuts_resolve_by_mode() {
+        let registry = CommandRegistry::with_defaults();
+        let ctrl_shift_p = KeyChord::key(KeyCode::KeyP).ctrl().shift();
+        assert_eq!(registry.resolve(ctrl_shift_p, false, false).map(|c| c.id.as_str()), Some("edit.command_palette"));
+        assert_eq!(registry.resolve(ctrl_shift_p, true, false).map(|c| c.id.as_str()), Some("play.pause"));
+
+        // Text fields keep Ctrl+C, but Ctrl+S still saves
+        assert!(registry.resolve(KeyChord::key(KeyCode::KeyC).ctrl(), false, true).is_none());
+        assert_eq!(
+            registry.resolve(KeyChord::key(KeyCode::KeyS).ctrl(), false, true).map(|c| c.id.as_str()),
+            Some("scene.save")
+        );
+    }
+
+    #[test]
+    fn test_defaults_have_no_conflicts() {
+        let registry = CommandRegistry::with_defaults();
+        for command in registry.commands() {
+            for chord in registry.shortcuts(&command.id) {
+                let conflicts: Vec<_> = registry.conflicts(&command.id, chord).iter().map(|c| c.id.clone()).collect();
+                assert!(conflicts.is_empty(), "{} ({}) conflicts with {:?}", command.id, chord, conflicts);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rebinding_round_trips_through_settings() {
+        let mut registry = CommandRegistry::with_defaults();
+        let ctrl_alt_s = KeyChord::key(KeyCode::KeyS).ctrl().alt();
+        registry.set_shortcuts("scene.save", vec![ctrl_alt_s]);
+        registry.set_shortcuts("tool.view", vec![KeyChord::key(KeyCode::KeyQ)]);
+        assert!(registry.is_customized("scene.save"));
+        assert!(!registry.is_customized("tool.view"));
+
+        // Ctrl+S is free now and Ctrl+Alt+S is taken
+        assert!(registry.resolve(KeyChord::key(KeyCode::KeyS).ctrl(), false, false).is_none());
+        assert_eq!(registry.conflicts("tool.move", ctrl_alt_s).len(), 1);
+
+        let mut reloaded = CommandRegistry::with_defaults();
+        reloaded.apply_settings(&registry.settings());
+        assert_eq!(reloaded.shortcuts("scene.save"), vec![ctrl_alt_s]);
+        assert_eq!(reloaded.shortcut_label("scene.save").as_deref(), Some("Ctrl+Alt+S"));
+    }
+
+    #[test]
+    fn test_fuzzy_search_ranks_word_starts_first() {
+        let registry = CommandRegistry::with_defaults();
+        let results = registry.search("svsc");
+        assert_eq!(results.first().map(|c| c.id.as_str()), Some("scene.save"));
+        assert_eq!(fuzzy_score("xyz", "Save Scene"), None);
+        assert_eq!(registry.search("").len(), registry.commands().len());
+    }
+}
@@ -1,9 +1,11 @@
-use std::collections::VecDeque;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
 use chrono::Local;
 use arboard::Clipboard;
+use ecs::Entity;
 
 /// Log level for console messages
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LogLevel {
     Info,
     Warning,
@@ -31,6 +33,41 @@ impl LogLevel {
     }
 }
 
+/// Where a console message came from
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LogSource {
+    /// Lua script location (`scripts/player.lua`, line 12)
+    Script { file: String, line: Option<u32> },
+    /// Rust module path of an engine/editor log record
+    Module(String),
+}
+
+impl LogSource {
+    /// Lua location in a script error message: `scripts/player.lua:12: attempt to ...`
+    /// (also the `[string "scripts/player.lua"]:12:` form)
+    pub fn parse_lua_location(message: &str) -> Option<Self> {
+        let ext = message.find(".lua")?;
+        let start = message[..ext]
+            .rfind(|c: char| c.is_whitespace() || c == '"' || c == '(' || c == '[')
+            .map_or(0, |i| i + 1);
+        let file = &message[start..ext + 4];
+        let rest = message[ext + 4..].trim_start_matches("\"]");
+        let line = rest.strip_prefix(':').and_then(|rest| {
+            let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().ok()
+        });
+        Some(LogSource::Script { file: file.to_string(), line })
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            LogSource::Script { file, line: Some(line) } => format!("{}:{}", file, line),
+            LogSource::Script { file, line: None } => file.clone(),
+            LogSource::Module(module) => module.clone(),
+        }
+    }
+}
+
 /// Console log message
 #[derive(Debug, Clone)]
 pub struct LogMessage {
@@ -38,6 +75,7 @@ pub struct LogMessage {
     pub message: String,
     pub timestamp: String,
     pub count: usize,
+    pub source: Option<LogSource>,
 }
 
 impl LogMessage {
@@ -47,8 +85,94 @@ impl LogMessage {
             message,
             timestamp: Local::now().format("%H:%M:%S").to_string(),
             count: 1,
+            source: None,
+        }
+    }
+
+    /// One line of text for the clipboard
+    pub fn to_text(&self) -> String {
+        let mut text = format!("{} {} {}", self.level.icon(), self.timestamp, self.message);
+        if self.count > 1 {
+            text.push_str(&format!(" ({})", self.count));
+        }
+        if let Some(source) = &self.source {
+            text.push_str(&format!(" [{}]", source.label()));
+        }
+        text
+    }
+
+    /// Entity id mentioned as "entity 12" in the message, if any
+    pub fn entity_hint(&self) -> Option<Entity> {
+        let lower = self.message.to_lowercase();
+        let index = lower.find("entity ")?;
+        let digits: String = lower[index + 7..].chars().take_while(|c| c.is_ascii_digit()).collect();
+        digits.parse().ok()
+    }
+}
+
+/// Click on a console entry with a script location
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpenSourceRequest {
+    pub file: String,
+    pub line: Option<u32>,
+    pub entity_hint: Option<Entity>,
+}
+
+/// Lua typed into the console, run at the next script sync point
+/// (`PlayModeSystem::run_console_code`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplRequest {
+    pub code: String,
+    /// Entity whose script state runs it; None for the editor's own state
+    pub target: Option<Entity>,
+}
+
+/// Log records captured by `ConsoleLogger`, drained into the console each frame
+static CAPTURED_LOGS: Mutex<Vec<(log::Level, String, String)>> = Mutex::new(Vec::new());
+
+/// Forwards to env_logger and captures warnings, errors and Lua output for the editor console
+pub struct ConsoleLogger {
+    inner: env_logger::Logger,
+}
+
+impl ConsoleLogger {
+    /// Install as the global logger (replaces `env_logger::init`)
+    pub fn init() {
+        let inner = env_logger::Builder::from_default_env().build();
+        // Info must pass the log macros so `[Lua]` prints can be captured
+        let max_level = inner.filter().max(log::LevelFilter::Info);
+        if log::set_boxed_logger(Box::new(ConsoleLogger { inner })).is_ok() {
+            log::set_max_level(max_level);
+        }
+    }
+
+    fn captures(record: &log::Record) -> bool {
+        record.level() <= log::Level::Warn
+            || record.args().as_str().map_or_else(|| record.args().to_string().starts_with("[Lua]"), |s| s.starts_with("[Lua]"))
+    }
+}
+
+impl log::Log for ConsoleLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        self.inner.enabled(metadata) || metadata.level() <= log::Level::Info
+    }
+
+    fn log(&self, record: &log::Record) {
+        self.inner.log(record);
+        if Self::captures(record) {
+            if let Ok(mut captured) = CAPTURED_LOGS.lock() {
+                captured.push((
+                    record.level(),
+                    record.args().to_string(),
+                    record.module_path().unwrap_or("").to_string(),
+                ));
+            }
         }
     }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
 }
 
 /// Console window for displaying logs
@@ -62,6 +186,16 @@ pub struct Console {
     collapse: bool,
     auto_scroll: bool,
     filter: String,
+    selected: Option<usize>,
+    open_request: Option<OpenSourceRequest>,
+    repl_input: String,
+    repl_history: Vec<String>,
+    /// Position while browsing the history with up/down
+    history_cursor: Option<usize>,
+    repl_target: Option<Entity>,
+    /// Scripted entities the REPL can target (only while playing)
+    repl_targets: Vec<(Entity, String)>,
+    repl_requests: Vec<ReplRequest>,
 }
 
 impl Console {
@@ -76,30 +210,231 @@ impl Console {
             collapse: false,
             auto_scroll: true,
             filter: String::new(),
+            selected: None,
+            open_request: None,
+            repl_input: String::new(),
+            repl_history: Vec::new(),
+            history_cursor: None,
+            repl_target: None,
+            repl_targets: Vec::new(),
+            repl_requests: Vec::new(),
         }
     }
 
     pub fn log(&mut self, level: LogLevel, message: impl Into<String>) {
         let message = message.into();
+        let source = LogSource::parse_lua_location(&message);
+        self.log_with_source(level, message, source);
+    }
+
+    /// Log with an explicit source location (Lua file/line or Rust module)
+    pub fn log_with_source(&mut self, level: LogLevel, message: impl Into<String>, source: Option<LogSource>) {
+        let message = message.into();
 
         // Check if we should collapse duplicate messages
         if self.collapse {
             if let Some(last) = self.messages.back_mut() {
-                if last.level == level && last.message == message {
+                if last.level == level && last.message == message && last.source == source {
                     last.count += 1;
                     return;
                 }
             }
         }
 
-        self.messages.push_back(LogMessage::new(level, message));
+        let mut entry = LogMessage::new(level, message);
+        entry.source = source;
+        self.messages.push_back(entry);
 
-        // Limit message count
+        // Limit message count (ring buffer)
         while self.messages.len() > self.max_messages {
             self.messages.pop_front();
+            self.selected = self.selected.and_then(|i| i.checked_sub(1));
+        }
+    }
+
+    /// Move records captured by `ConsoleLogger` into the console
+    pub fn drain_captured_logs(&mut self) {
+        let captured = match CAPTURED_LOGS.lock() {
+            Ok(mut captured) => std::mem::take(&mut *captured),
+            Err(_) => return,
+        };
+        for (level, message, module) in captured {
+            let level = match level {
+                log::Level::Error => LogLevel::Error,
+                log::Level::Warn => LogLevel::Warning,
+                log::Level::Info => LogLevel::Info,
+                log::Level::Debug | log::Level::Trace => LogLevel::Debug,
+            };
+            let source = LogSource::parse_lua_location(&message)
+                .or_else(|| (!module.is_empty()).then_some(LogSource::Module(module)));
+            self.log_with_source(level, message, source);
+        }
+    }
+
+    /// Maximum retained entries; older ones are dropped first
+    pub fn max_messages(&self) -> usize {
+        self.max_messages
+    }
+
+    pub fn set_max_messages(&mut self, max_messages: usize) {
+        self.max_messages = max_messages.max(1);
+        while self.messages.len() > self.max_messages {
+            self.messages.pop_front();
+        }
+        self.selected = None;
+    }
+
+    pub fn messages(&self) -> impl Iterator<Item = &LogMessage> {
+        self.messages.iter()
+    }
+
+    /// Script location the user clicked, for the editor to open (taken once)
+    pub fn take_open_request(&mut self) -> Option<OpenSourceRequest> {
+        self.open_request.take()
+    }
+
+    /// Entities whose script state the REPL can run in, with their names (empty outside
+    /// play mode, which leaves only the editor state)
+    pub fn set_repl_targets(&mut self, targets: Vec<(Entity, String)>) {
+        if self.repl_target.is_some_and(|target| !targets.iter().any(|(entity, _)| *entity == target)) {
+            self.repl_target = None;
+        }
+        self.repl_targets = targets;
+    }
+
+    /// Code submitted since the last call, oldest first
+    pub fn take_repl_requests(&mut self) -> Vec<ReplRequest> {
+        std::mem::take(&mut self.repl_requests)
+    }
+
+    /// Show what a submitted request printed and evaluated to
+    pub fn repl_output(&mut self, lines: Vec<script::ReplLine>) {
+        for line in lines {
+            match line {
+                script::ReplLine::Output(text) => self.log_with_source(LogLevel::Info, text, None),
+                script::ReplLine::Value(value) => self.log_with_source(LogLevel::Info, format!("< {}", value), None),
+                script::ReplLine::Error(error) => self.error(error),
+            }
+        }
+    }
+
+    fn submit_repl(&mut self) {
+        let code = self.repl_input.trim().to_string();
+        if code.is_empty() {
+            return;
+        }
+        if self.repl_history.last() != Some(&code) {
+            self.repl_history.push(code.clone());
+        }
+        self.history_cursor = None;
+        self.repl_input.clear();
+        self.log_with_source(LogLevel::Debug, format!("> {}", code.replace('\n', "\n  ")), None);
+        self.repl_requests.push(ReplRequest { code, target: self.repl_target });
+    }
+
+    /// Up (`older`) / down through submitted code; past the newest entry clears the input
+    fn step_history(&mut self, older: bool) {
+        let Some(last) = self.repl_history.len().checked_sub(1) else {
+            return;
+        };
+        self.history_cursor = match (self.history_cursor, older) {
+            (None, true) => Some(last),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) => (index < last).then_some(index + 1),
+        };
+        self.repl_input = self.history_cursor.map(|index| self.repl_history[index].clone()).unwrap_or_default();
+    }
+
+    fn render_repl(&mut self, ui: &mut egui::Ui) {
+        let input_id = ui.make_persistent_id("console_repl_input");
+        let mut submit = false;
+        if ui.memory(|m| m.has_focus(input_id)) {
+            // Enter runs, Shift+Enter is a new line; up/down browse history on single-line input
+            submit = ui.input_mut(|i| !i.modifiers.shift && i.consume_key(egui::Modifiers::NONE, egui::Key::Enter));
+            if !self.repl_input.contains('\n') {
+                if ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp)) {
+                    self.step_history(true);
+                } else if ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown)) {
+                    self.step_history(false);
+                }
+            }
+        }
+
+        ui.horizontal(|ui| {
+            if !self.repl_targets.is_empty() {
+                let selected = match self.repl_target {
+                    Some(target) => self.repl_targets.iter()
+                        .find(|(entity, _)| *entity == target)
+                        .map_or_else(|| format!("Entity {}", target), |(_, name)| name.clone()),
+                    None => "Editor".to_string(),
+                };
+                egui::ComboBox::from_id_salt("console_repl_target")
+                    .selected_text(selected)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.repl_target, None, "Editor");
+                        for (entity, name) in &self.repl_targets {
+                            ui.selectable_value(&mut self.repl_target, Some(*entity), name);
+                        }
+                    })
+                    .response
+                    .on_hover_text("Script state the code runs in: the editor's own, or an entity's (its globals)");
+            }
+            ui.label(egui::RichText::new("Lua ›").monospace().color(egui::Color32::GRAY));
+            let run_width = 50.0;
+            ui.add(
+                egui::TextEdit::multiline(&mut self.repl_input)
+                    .id(input_id)
+                    .code_editor()
+                    .desired_rows(1)
+                    .desired_width(ui.available_width() - run_width)
+                    .return_key(egui::KeyboardShortcut::new(egui::Modifiers::SHIFT, egui::Key::Enter))
+                    .hint_text("Lua (Enter to run, Shift+Enter for a new line, ↑/↓ for history)"),
+            );
+            if ui.button("▶ Run").clicked() {
+                submit = true;
+            }
+        });
+
+        if submit {
+            self.submit_repl();
         }
     }
 
+    /// Indices of the entries that pass the level/search filters, with their
+    /// display counts (duplicates merged when collapsing)
+    fn visible_entries(&self) -> Vec<(usize, usize)> {
+        let filter = self.filter.to_lowercase();
+        let mut visible: Vec<(usize, usize)> = Vec::new();
+        let mut groups: HashMap<(LogLevel, &str, Option<&LogSource>), usize> = HashMap::new();
+
+        for (index, msg) in self.messages.iter().enumerate() {
+            let should_show = match msg.level {
+                LogLevel::Info => self.show_info,
+                LogLevel::Warning => self.show_warning,
+                LogLevel::Error => self.show_error,
+                LogLevel::Debug => self.show_debug,
+            };
+            if !should_show {
+                continue;
+            }
+            if !filter.is_empty() && !msg.message.to_lowercase().contains(&filter) {
+                continue;
+            }
+
+            if self.collapse {
+                let key = (msg.level, msg.message.as_str(), msg.source.as_ref());
+                if let Some(&slot) = groups.get(&key) {
+                    visible[slot].1 += msg.count;
+                    continue;
+                }
+                groups.insert(key, visible.len());
+            }
+            visible.push((index, msg.count));
+        }
+        visible
+    }
+
     pub fn info(&mut self, message: impl Into<String>) {
         self.log(LogLevel::Info, message);
     }
@@ -119,9 +454,12 @@ impl Console {
 
     pub fn clear(&mut self) {
         self.messages.clear();
+        self.selected = None;
     }
 
     pub fn render(&mut self, ui: &mut egui::Ui) {
+        let visible = self.visible_entries();
+
         // Toolbar
         ui.horizontal(|ui| {
             if ui.button("🗑 Clear").clicked() {
@@ -130,36 +468,14 @@ impl Console {
 
             if ui.button("📋 Copy All").clicked() {
                 // Copy all visible messages
-                let mut all_text = String::new();
-                for msg in &self.messages {
-                    // Filter by level
-                    let should_show = match msg.level {
-                        LogLevel::Info => self.show_info,
-                        LogLevel::Warning => self.show_warning,
-                        LogLevel::Error => self.show_error,
-                        LogLevel::Debug => self.show_debug,
-                    };
-
-                    if !should_show {
-                        continue;
-                    }
-
-                    // Filter by search text
-                    if !self.filter.is_empty() {
-                        if !msg.message.to_lowercase().contains(&self.filter.to_lowercase()) {
-                            continue;
-                        }
-                    }
+                let all_text: String = visible.iter()
+                    .map(|&(index, count)| {
+                        let mut msg = self.messages[index].clone();
+                        msg.count = count;
+                        msg.to_text() + "\n"
+                    })
+                    .collect();
 
-                    // Add message to output
-                    let text = if msg.count > 1 {
-                        format!("{} {} {} ({})\n", msg.level.icon(), msg.timestamp, msg.message, msg.count)
-                    } else {
-                        format!("{} {} {}\n", msg.level.icon(), msg.timestamp, msg.message)
-                    };
-                    all_text.push_str(&text);
-                }
-                
                 if !all_text.is_empty() {
                     if let Ok(mut clipboard) = Clipboard::new() {
                         let _ = clipboard.set_text(all_text);
@@ -170,10 +486,10 @@ impl Console {
             ui.separator();
 
             // Filter toggles
-            let info_count = self.messages.iter().filter(|m| m.level == LogLevel::Info).count();
-            let warning_count = self.messages.iter().filter(|m| m.level == LogLevel::Warning).count();
-            let error_count = self.messages.iter().filter(|m| m.level == LogLevel::Error).count();
-            let debug_count = self.messages.iter().filter(|m| m.level == LogLevel::Debug).count();
+            let info_count = self.messages.iter().filter(|m| m.level == LogLevel::Info).map(|m| m.count).sum::<usize>();
+            let warning_count = self.messages.iter().filter(|m| m.level == LogLevel::Warning).map(|m| m.count).sum::<usize>();
+            let error_count = self.messages.iter().filter(|m| m.level == LogLevel::Error).map(|m| m.count).sum::<usize>();
+            let debug_count = self.messages.iter().filter(|m| m.level == LogLevel::Debug).map(|m| m.count).sum::<usize>();
 
             ui.toggle_value(&mut self.show_info, format!("ℹ️ Info ({})", info_count));
             ui.toggle_value(&mut self.show_warning, format!("⚠️ Warning ({})", warning_count));
@@ -185,6 +501,14 @@ impl Console {
             ui.checkbox(&mut self.collapse, "Collapse");
             ui.checkbox(&mut self.auto_scroll, "Auto Scroll");
 
+            let mut max_messages = self.max_messages;
+            if ui.add(egui::DragValue::new(&mut max_messages).range(100..=100_000).speed(10).prefix("Keep: "))
+                .on_hover_text("Maximum retained entries; the oldest are dropped first")
+                .changed()
+            {
+                self.set_max_messages(max_messages);
+            }
+
             ui.separator();
 
             // Search filter
@@ -194,54 +518,73 @@ impl Console {
 
         ui.separator();
 
+        // REPL input along the bottom, the message list fills the rest
+        egui::TopBottomPanel::bottom("console_repl")
+            .frame(egui::Frame::NONE)
+            .show_inside(ui, |ui| {
+                ui.add_space(4.0);
+                self.render_repl(ui);
+            });
+
+        let mut clicked: Option<usize> = None;
+        let mut copy_text: Option<String> = None;
+
         // Message list
         egui::ScrollArea::vertical()
             .auto_shrink([false, false])
             .stick_to_bottom(self.auto_scroll)
             .show(ui, |ui| {
-                for msg in &self.messages {
-                    // Filter by level
-                    let should_show = match msg.level {
-                        LogLevel::Info => self.show_info,
-                        LogLevel::Warning => self.show_warning,
-                        LogLevel::Error => self.show_error,
-                        LogLevel::Debug => self.show_debug,
-                    };
-
-                    if !should_show {
-                        continue;
-                    }
-
-                    // Filter by search text
-                    if !self.filter.is_empty() {
-                        if !msg.message.to_lowercase().contains(&self.filter.to_lowercase()) {
-                            continue;
-                        }
-                    }
+                for &(index, count) in &visible {
+                    let msg = &self.messages[index];
+                    let is_selected = self.selected == Some(index);
 
                     // Render message with selectable text and copy button
-                    ui.horizontal(|ui| {
+                    let row = ui.horizontal(|ui| {
                         ui.label(egui::RichText::new(msg.level.icon()).color(msg.level.color()));
                         ui.label(egui::RichText::new(&msg.timestamp).color(egui::Color32::GRAY));
 
-                        let text = if msg.count > 1 {
-                            format!("{} ({})", msg.message, msg.count)
+                        let text = if count > 1 {
+                            format!("{} ({})", msg.message, count)
                         } else {
                             msg.message.clone()
                         };
 
-                        // Use monospace label which is selectable
-                        ui.monospace(&text);
+                        let mut rich = egui::RichText::new(&text).monospace();
+                        if is_selected {
+                            rich = rich.background_color(ui.visuals().selection.bg_fill);
+                        }
+                        let label = ui.add(egui::Label::new(rich).sense(egui::Sense::click()));
+                        let label = match &msg.source {
+                            Some(LogSource::Script { .. }) => label
+                                .on_hover_text("Click to open the script and select its entity")
+                                .on_hover_cursor(egui::CursorIcon::PointingHand),
+                            _ => label,
+                        };
+                        if label.clicked() {
+                            clicked = Some(index);
+                        }
 
-                        // Small copy button at the end
+                        // Small copy button and source location at the end
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            if ui.small_button("📋").on_hover_text("Copy message").clicked() {
-                                if let Ok(mut clipboard) = Clipboard::new() {
-                                    let _ = clipboard.set_text(&msg.message);
-                                }
+                            if ui.small_button("📋").on_hover_text("Copy entry").clicked() {
+                                copy_text = Some(msg.to_text());
+                            }
+                            if let Some(source) = &msg.source {
+                                ui.label(egui::RichText::new(source.label()).small().color(egui::Color32::GRAY));
                             }
                         });
                     });
+
+                    row.response.context_menu(|ui| {
+                        if ui.button("Copy entry").clicked() {
+                            copy_text = Some(msg.to_text());
+                            ui.close();
+                        }
+                        if ui.button("Copy all").clicked() {
+                            copy_text = Some(visible.iter().map(|&(i, _)| self.messages[i].to_text() + "\n").collect());
+                            ui.close();
+                        }
+                    });
                 }
 
                 // Show empty message
@@ -251,6 +594,24 @@ impl Console {
                     });
                 }
             });
+
+        if let Some(index) = clicked {
+            self.selected = Some(index);
+            let msg = &self.messages[index];
+            if let Some(LogSource::Script { file, line }) = &msg.source {
+                self.open_request = Some(OpenSourceRequest {
+                    file: file.clone(),
+                    line: *line,
+                    entity_hint: msg.entity_hint(),
+                });
+            }
+        }
+
+        if let Some(text) = copy_text {
+            if let Ok(mut clipboard) = Clipboard::new() {
+                let _ = clipboard.set_text(text);
+            }
+        }
     }
 }
 
@@ -259,3 +620,69 @@ impl Default for Console {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lua_location() {
+        let source = LogSource::parse_lua_location(
+            "Script update error for entity 5: runtime error: scripts/player.lua:12: attempt to index a nil value",
+        );
+        assert_eq!(source, Some(LogSource::Script { file: "scripts/player.lua".to_string(), line: Some(12) }));
+
+        let source = LogSource::parse_lua_location("[string \"scripts/enemy.lua\"]:7: unexpected symbol");
+        assert_eq!(source, Some(LogSource::Script { file: "scripts/enemy.lua".to_string(), line: Some(7) }));
+
+        assert_eq!(LogSource::parse_lua_location("Scripts loaded successfully"), None);
+    }
+
+    #[test]
+    fn test_ring_buffer_and_collapse() {
+        let mut console = Console::new();
+        console.set_max_messages(3);
+        for i in 0..5 {
+            console.info(format!("message {}", i));
+        }
+        let kept: Vec<_> = console.messages().map(|m| m.message.clone()).collect();
+        assert_eq!(kept, vec!["message 2", "message 3", "message 4"]);
+
+        console.clear();
+        console.error("boom");
+        console.info("other");
+        console.error("boom");
+        console.collapse = true;
+        assert_eq!(console.visible_entries(), vec![(0, 2), (1, 1)]);
+    }
+
+    #[test]
+    fn test_repl_history_and_requests() {
+        let mut console = Console::new();
+        for code in ["x = 1", "x = 1", "print(x)"] {
+            console.repl_input = code.to_string();
+            console.submit_repl();
+        }
+        console.repl_input = "   ".to_string();
+        console.submit_repl();
+        assert_eq!(console.repl_history, vec!["x = 1", "print(x)"]);
+        assert_eq!(console.take_repl_requests().len(), 3);
+        assert!(console.take_repl_requests().is_empty());
+
+        console.step_history(true);
+        assert_eq!(console.repl_input, "print(x)");
+        console.step_history(true);
+        console.step_history(true);
+        assert_eq!(console.repl_input, "x = 1");
+        console.step_history(false);
+        assert_eq!(console.repl_input, "print(x)");
+        console.step_history(false);
+        assert_eq!(console.repl_input, "");
+
+        // A target that stopped running falls back to the editor state
+        console.set_repl_targets(vec![(4, "Player".to_string())]);
+        console.repl_target = Some(4);
+        console.set_repl_targets(Vec::new());
+        assert_eq!(console.repl_target, None);
+    }
+}
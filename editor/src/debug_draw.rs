@@ -1,40 +1,56 @@
 /// Debug Draw System - Unity/Unreal style debug visualization
 /// 
 /// Provides functions to draw debug lines, rays, boxes, etc. in the scene view
+/// (the queue is shared with the script engine and physics, see engine_core::debug_draw)
 /// Similar to Unity's Debug.DrawLine() and Gizmos, or Unreal's DrawDebugLine()
 
 use egui;
+use engine_core::debug_draw::{DebugCategory, DebugDrawHandle, DebugDraws, DebugShape};
+use engine_core::Color;
+use navigation::Navigation;
+use physics::PhysicsDebugDraw;
+use std::cell::RefCell;
+use std::rc::Rc;
 
-#[derive(Clone, Debug)]
-pub struct DebugLine {
-    pub start: [f32; 3],
-    pub end: [f32; 3],
-    pub color: egui::Color32,
-    pub duration: f32, // How long to show (0 = one frame)
-}
-
-#[derive(Clone, Debug)]
-pub struct DebugRay {
-    pub origin: [f32; 3],
-    pub direction: [f32; 3],
-    pub length: f32,
-    pub color: egui::Color32,
-    pub duration: f32,
-}
+/// Most blocked cells drawn by the navigation overlay (zoomed-out views of huge grids)
+const MAX_NAV_CELLS: usize = 20_000;
 
-#[derive(Clone, Debug)]
-pub struct DebugBox {
-    pub center: [f32; 3],
-    pub size: [f32; 3],
-    pub color: egui::Color32,
-    pub duration: f32,
-}
+/// Frames physics contacts stay up by default
+const DEFAULT_CONTACT_FRAMES: u32 = 10;
+/// Contact persistence slider bound
+pub const MAX_CONTACT_FRAMES: u32 = 120;
 
-/// Debug Draw Manager - stores and renders debug primitives
+/// Debug Draw Manager - renders the shared debug draw queue (scripts, physics, editor systems)
 pub struct DebugDrawManager {
-    lines: Vec<(DebugLine, f32)>, // (line, time_remaining)
-    rays: Vec<(DebugRay, f32)>,
-    boxes: Vec<(DebugBox, f32)>,
+    draws: DebugDrawHandle,
+    /// Navigation grid to overlay (set while View > Show Navigation Grid is on)
+    pub navigation: Option<Rc<RefCell<Navigation>>>,
+    /// View > Gizmos: Lua debug_draw_* shapes
+    pub show_script: bool,
+    /// View > Gizmos: physics draws (queued only while one of the options below is on)
+    pub show_physics: bool,
+    /// Physics contact points
+    pub physics_contacts: bool,
+    /// Physics broad-phase bounds
+    pub physics_broadphase: bool,
+    /// Collider outlines from Rapier's debug renderer
+    pub physics_colliders: bool,
+    /// Joint anchors from Rapier's debug renderer
+    pub physics_joints: bool,
+    /// Frames contact points (and normals) stay up after the step that found them
+    pub physics_contact_frames: u32,
+    /// Last frame's delta time, to turn physics_contact_frames into seconds
+    frame_dt: f32,
+    /// View > Gizmos: scene view icons on cameras
+    pub show_camera_icons: bool,
+    /// View > Gizmos: scene view icons on EditorIcon markers
+    pub show_marker_icons: bool,
+    /// View > Gizmos: names under every icon, not just selected ones
+    pub show_icon_labels: bool,
+    /// View > Gizmos: TriggerZone areas (and their drag handles on the selected entity)
+    pub show_trigger_zones: bool,
+    /// View > Gizmos > Handle Size (saved with the editor settings)
+    pub gizmos: crate::ui::scene_view::gizmo_metrics::GizmoPreferences,
 }
 
 impl Default for DebugDrawManager {
@@ -46,141 +62,250 @@ impl Default for DebugDrawManager {
 impl DebugDrawManager {
     pub fn new() -> Self {
         Self {
-            lines: Vec::new(),
-            rays: Vec::new(),
-            boxes: Vec::new(),
+            draws: DebugDraws::handle(),
+            navigation: None,
+            show_script: true,
+            show_physics: true,
+            physics_contacts: false,
+            physics_broadphase: false,
+            physics_colliders: false,
+            physics_joints: false,
+            physics_contact_frames: DEFAULT_CONTACT_FRAMES,
+            frame_dt: 1.0 / 60.0,
+            show_camera_icons: true,
+            show_marker_icons: true,
+            show_icon_labels: false,
+            show_trigger_zones: true,
+            gizmos: crate::ui::scene_view::gizmo_metrics::GizmoPreferences::load().unwrap_or_default(),
+        }
+    }
+
+    /// Shared queue for the script engine and other Rust systems
+    pub fn handle(&self) -> DebugDrawHandle {
+        Rc::clone(&self.draws)
+    }
+
+    /// Point a physics backend at the shared queue with the current Gizmos options
+    pub fn configure_physics(&self, physics_draw: &mut PhysicsDebugDraw) {
+        if physics_draw.handle.is_none() {
+            physics_draw.handle = Some(self.handle());
         }
+        physics_draw.draw_contacts = self.show_physics && self.physics_contacts;
+        physics_draw.draw_broadphase = self.show_physics && self.physics_broadphase;
+        physics_draw.draw_colliders = self.show_physics && self.physics_colliders;
+        physics_draw.draw_joints = self.show_physics && self.physics_joints;
+        physics_draw.contact_duration = frames_to_duration(self.physics_contact_frames, self.frame_dt);
     }
 
     /// Draw a line (Unity-style)
     pub fn draw_line(&mut self, start: [f32; 3], end: [f32; 3], color: egui::Color32, duration: f32) {
-        self.lines.push((DebugLine { start, end, color, duration }, duration));
+        self.draws.borrow_mut().draw_line(DebugCategory::Engine, start, end, to_color(color), duration);
     }
 
     /// Draw a ray (Unity-style)
     pub fn draw_ray(&mut self, origin: [f32; 3], direction: [f32; 3], length: f32, color: egui::Color32, duration: f32) {
-        self.rays.push((DebugRay { origin, direction, length, color, duration }, duration));
+        let end = [
+            origin[0] + direction[0] * length,
+            origin[1] + direction[1] * length,
+            origin[2] + direction[2] * length,
+        ];
+        self.draw_line(origin, end, color, duration);
     }
 
     /// Draw a box (Unity-style)
-    pub fn draw_box(&mut self, center: [f32; 3], size: [f32; 3], color: egui::Color32, duration: f32) {
-        self.boxes.push((DebugBox { center, size, color, duration }, duration));
+    pub fn draw_box(&mut self, center: [f32; 3], size: [f32; 2], color: egui::Color32, duration: f32) {
+        self.draws.borrow_mut().draw_box(DebugCategory::Engine, center, size, to_color(color), duration);
     }
 
     /// Update - remove expired debug draws
     pub fn update(&mut self, dt: f32) {
-        // Update lines
-        self.lines.retain_mut(|(_, time_remaining)| {
-            *time_remaining -= dt;
-            *time_remaining > 0.0
-        });
-
-        // Update rays
-        self.rays.retain_mut(|(_, time_remaining)| {
-            *time_remaining -= dt;
-            *time_remaining > 0.0
-        });
-
-        // Update boxes
-        self.boxes.retain_mut(|(_, time_remaining)| {
-            *time_remaining -= dt;
-            *time_remaining > 0.0
-        });
+        if dt > 0.0 {
+            self.frame_dt = dt;
+        }
+        self.draws.borrow_mut().update(dt);
     }
 
     /// Clear all debug draws
     pub fn clear(&mut self) {
-        self.lines.clear();
-        self.rays.clear();
-        self.boxes.clear();
+        self.draws.borrow_mut().clear();
     }
 
-    /// Render debug draws in scene view
+    fn is_visible(&self, category: DebugCategory) -> bool {
+        match category {
+            DebugCategory::Script => self.show_script,
+            DebugCategory::Physics => self.show_physics,
+            // Navigation draws follow View > Show Navigation Grid
+            DebugCategory::Navigation => self.navigation.is_some(),
+            DebugCategory::Engine => true,
+        }
+    }
+
+    /// Render debug draws through the view's own world-to-screen mapping (the one its
+    /// sprites use), so they sit exactly on what they describe. `pixels_per_unit` sizes
+    /// circles.
     pub fn render(
+        &self,
+        painter: &egui::Painter,
+        to_screen: impl Fn([f32; 3]) -> egui::Pos2,
+        pixels_per_unit: f32,
+    ) {
+        for draw in self.draws.borrow().draws().iter().filter(|draw| self.is_visible(draw.category)) {
+            let color = to_color32(draw.color);
+            let stroke = egui::Stroke::new(2.0, color);
+            match &draw.shape {
+                DebugShape::Line { start, end } => {
+                    painter.line_segment([to_screen(*start), to_screen(*end)], stroke);
+                }
+                DebugShape::Box { center, size } => {
+                    let min = [center[0] - size[0] / 2.0, center[1] - size[1] / 2.0, center[2]];
+                    let max = [center[0] + size[0] / 2.0, center[1] + size[1] / 2.0, center[2]];
+                    let rect = egui::Rect::from_two_pos(to_screen(min), to_screen(max));
+                    painter.rect_stroke(rect, 0.0, stroke, egui::StrokeKind::Middle);
+                }
+                DebugShape::Circle { center, radius } => {
+                    painter.circle_stroke(to_screen(*center), radius * pixels_per_unit, stroke);
+                }
+                DebugShape::Cross { center, size } => {
+                    let half = size / 2.0;
+                    let [x, y, z] = *center;
+                    painter.line_segment([to_screen([x - half, y, z]), to_screen([x + half, y, z])], stroke);
+                    painter.line_segment([to_screen([x, y - half, z]), to_screen([x, y + half, z])], stroke);
+                }
+                DebugShape::Text { position, text } => {
+                    painter.text(to_screen(*position), egui::Align2::CENTER_CENTER, text, egui::FontId::monospace(12.0), color);
+                }
+            }
+        }
+    }
+
+    /// Render debug draws over the Game view (`game_rect`, before letterboxing) through
+    /// the main camera, framed the way the game view renders it
+    pub fn render_game_view(
+        &self,
+        painter: &egui::Painter,
+        world: &ecs::World,
+        policy: &engine_core::project_settings::AspectPolicy,
+        game_rect: egui::Rect,
+    ) {
+        let Some((_, camera, transform)) = ecs::picking::main_camera(world) else {
+            return;
+        };
+        let screen = policy.game_rect([game_rect.min.x, game_rect.min.y, game_rect.width(), game_rect.height()]);
+        let camera = ecs::picking::framed_camera(camera, policy, [0.0, 0.0, screen[2], screen[3]]);
+        let project = |p: glam::Vec3| ecs::picking::world_to_screen(&camera, &transform, screen, p).map(|(point, _)| point);
+
+        // Circle radii: screen length of one world unit at the camera's focus
+        let focus = glam::Vec3::new(transform.position[0], transform.position[1], 0.0);
+        let pixels_per_unit = match (project(focus), project(focus + glam::Vec3::X)) {
+            (Some(a), Some(b)) => a.distance(b),
+            _ => return,
+        };
+        let painter = painter.with_clip_rect(game_rect);
+        self.render(&painter, |p| {
+            let point = project(glam::Vec3::from(p)).unwrap_or_default();
+            egui::pos2(point.x, point.y)
+        }, pixels_per_unit);
+    }
+
+    /// Render the navigation grid: blocked cells, grid bounds and the last requested path
+    pub fn render_navigation(
         &self,
         painter: &egui::Painter,
         camera_pos: [f32; 3],
         zoom: f32,
         viewport_rect: egui::Rect,
     ) {
-        // Render lines
-        for (line, _) in &self.lines {
-            let start_screen = world_to_screen(line.start, camera_pos, zoom, viewport_rect);
-            let end_screen = world_to_screen(line.end, camera_pos, zoom, viewport_rect);
-            
-            painter.line_segment(
-                [start_screen, end_screen],
-                egui::Stroke::new(2.0, line.color),
+        let Some(navigation) = &self.navigation else {
+            return;
+        };
+        let navigation = navigation.borrow();
+        let grid = navigation.grid();
+        let to_screen = |p: [f32; 2]| world_to_screen([p[0], p[1], 0.0], camera_pos, zoom, viewport_rect);
+
+        if grid.width() > 0 {
+            let bounds = grid.bounds();
+            let bounds_rect = egui::Rect::from_two_pos(to_screen([bounds[0], bounds[1]]), to_screen([bounds[2], bounds[3]]));
+            painter.rect_stroke(
+                bounds_rect,
+                0.0,
+                egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(80, 160, 255, 160)),
+                egui::StrokeKind::Inside,
             );
-        }
 
-        // Render rays
-        for (ray, _) in &self.rays {
-            let end = [
-                ray.origin[0] + ray.direction[0] * ray.length,
-                ray.origin[1] + ray.direction[1] * ray.length,
-                ray.origin[2] + ray.direction[2] * ray.length,
-            ];
-            
-            let start_screen = world_to_screen(ray.origin, camera_pos, zoom, viewport_rect);
-            let end_screen = world_to_screen(end, camera_pos, zoom, viewport_rect);
-            
-            painter.line_segment(
-                [start_screen, end_screen],
-                egui::Stroke::new(2.0, ray.color),
+            // Only the cells inside the viewport
+            let half_w = viewport_rect.width() / 2.0 / zoom;
+            let half_h = viewport_rect.height() / 2.0 / zoom;
+            let visible = grid.cells_in_rect(
+                [camera_pos[0] - half_w, camera_pos[1] - half_h],
+                [camera_pos[0] + half_w, camera_pos[1] + half_h],
             );
-            
-            // Draw arrow head
-            let arrow_size = 5.0;
-            let dir = egui::vec2(end_screen.x - start_screen.x, end_screen.y - start_screen.y).normalized();
-            let perp = egui::vec2(-dir.y, dir.x);
-            
-            let arrow_tip = end_screen;
-            let arrow_left = arrow_tip - dir * arrow_size + perp * arrow_size * 0.5;
-            let arrow_right = arrow_tip - dir * arrow_size - perp * arrow_size * 0.5;
-            
-            painter.line_segment([arrow_tip, arrow_left], egui::Stroke::new(2.0, ray.color));
-            painter.line_segment([arrow_tip, arrow_right], egui::Stroke::new(2.0, ray.color));
+            if let Some(((x0, y0), (x1, y1))) = visible {
+                let fill = egui::Color32::from_rgba_unmultiplied(255, 60, 60, 70);
+                let half = grid.cell_size() / 2.0;
+                let mut drawn = 0;
+                'cells: for y in y0..=y1 {
+                    for x in x0..=x1 {
+                        if grid.is_cell_walkable(x, y) {
+                            continue;
+                        }
+                        let center = grid.cell_center(x, y);
+                        let cell_rect = egui::Rect::from_two_pos(
+                            to_screen([center[0] - half, center[1] - half]),
+                            to_screen([center[0] + half, center[1] + half]),
+                        );
+                        painter.rect_filled(cell_rect, 0.0, fill);
+                        drawn += 1;
+                        if drawn >= MAX_NAV_CELLS {
+                            break 'cells;
+                        }
+                    }
+                }
+            }
         }
 
-        // Render boxes
-        for (debug_box, _) in &self.boxes {
-            let half_size = [
-                debug_box.size[0] / 2.0,
-                debug_box.size[1] / 2.0,
-                debug_box.size[2] / 2.0,
-            ];
-            
-            // Draw box corners
-            let corners = [
-                [debug_box.center[0] - half_size[0], debug_box.center[1] - half_size[1], debug_box.center[2]],
-                [debug_box.center[0] + half_size[0], debug_box.center[1] - half_size[1], debug_box.center[2]],
-                [debug_box.center[0] + half_size[0], debug_box.center[1] + half_size[1], debug_box.center[2]],
-                [debug_box.center[0] - half_size[0], debug_box.center[1] + half_size[1], debug_box.center[2]],
-            ];
-            
-            // Draw box edges
-            for i in 0..4 {
-                let start = corners[i];
-                let end = corners[(i + 1) % 4];
-                
-                let start_screen = world_to_screen(start, camera_pos, zoom, viewport_rect);
-                let end_screen = world_to_screen(end, camera_pos, zoom, viewport_rect);
-                
-                painter.line_segment(
-                    [start_screen, end_screen],
-                    egui::Stroke::new(2.0, debug_box.color),
-                );
+        if let Some(path) = navigation.last_path() {
+            let (color, target) = if path.waypoints.is_empty() {
+                (egui::Color32::from_rgb(255, 90, 90), path.to)
+            } else {
+                (egui::Color32::from_rgb(80, 255, 120), *path.waypoints.last().unwrap_or(&path.to))
+            };
+            let mut previous = to_screen(path.from);
+            if path.waypoints.is_empty() {
+                // No path: dashed straight line to the target
+                painter.add(egui::Shape::dashed_line(&[previous, to_screen(target)], egui::Stroke::new(1.5, color), 6.0, 4.0));
+            }
+            for &waypoint in &path.waypoints {
+                let next = to_screen(waypoint);
+                painter.line_segment([previous, next], egui::Stroke::new(2.0, color));
+                painter.circle_filled(next, 3.0, color);
+                previous = next;
             }
+            painter.circle_stroke(to_screen(path.from), 4.0, egui::Stroke::new(1.5, color));
         }
     }
 
     /// Get number of active debug draws
     pub fn count(&self) -> usize {
-        self.lines.len() + self.rays.len() + self.boxes.len()
+        self.draws.borrow().len()
     }
 }
 
+/// Seconds a draw queued this frame has to last to still be drawn `frames` frames in a
+/// row (update() drops draws once their time runs out), at `frame_dt` per frame
+pub fn frames_to_duration(frames: u32, frame_dt: f32) -> f32 {
+    (frames.max(1) as f32 - 0.5) * frame_dt
+}
+
+fn to_color(color: egui::Color32) -> Color {
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    Color::rgba(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0, a as f32 / 255.0)
+}
+
+fn to_color32(color: Color) -> egui::Color32 {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    egui::Color32::from_rgba_unmultiplied(channel(color.r), channel(color.g), channel(color.b), channel(color.a))
+}
+
 /// Convert world position to screen position
 fn world_to_screen(
     world_pos: [f32; 3],
@@ -231,3 +356,30 @@ impl DebugDrawManager {
         self.draw_ray(origin, direction, length, egui::Color32::RED, duration);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Frames a draw queued with `duration` is drawn for (queued after that frame's update)
+    fn frames_drawn(duration: f32, frame_dt: f32) -> u32 {
+        let mut draws = DebugDraws::default();
+        draws.draw_cross(DebugCategory::Physics, [0.0; 3], 1.0, Color::WHITE, duration);
+        let mut frames = 0;
+        while !draws.draws().is_empty() && frames < 1000 {
+            frames += 1;
+            draws.update(frame_dt);
+        }
+        frames
+    }
+
+    #[test]
+    fn test_contact_frames() {
+        for frame_dt in [1.0 / 30.0, 1.0 / 60.0, 1.0 / 144.0] {
+            for frames in [1, 2, 10, MAX_CONTACT_FRAMES] {
+                assert_eq!(frames_drawn(frames_to_duration(frames, frame_dt), frame_dt), frames);
+            }
+        }
+        assert_eq!(frames_to_duration(0, 1.0 / 60.0), frames_to_duration(1, 1.0 / 60.0));
+    }
+}
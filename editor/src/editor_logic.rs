@@ -1,10 +1,14 @@
 use engine_core::EngineContext;
 use engine_core::assets::AssetLoader;
-use crate::states::{AppState, EditorState};
+use crate::states::{AppState, EditorState, SelectionAction};
 use crate::ui::EditorUI;
 use script::ScriptEngine;
 use crate::systems::{play_mode::PlayModeSystem, menu_commands::MenuCommandSystem};
-use crate::ui::dialogs::ExitDialog;
+use crate::ui::dialogs::{ExitDialog, PlayModeChangesDialog, PreloadDialog, RecoveryDialog, SceneReloadDialog};
+use crate::ui::export_dialog::ExportGameDialog;
+use crate::systems::play_mode_changes::PlayModeChangeTracker;
+use crate::systems::component_edits::ComponentEditRecorder;
+use crate::systems::component_clipboard;
 use wgpu;
 use egui_wgpu;
 
@@ -38,11 +42,58 @@ impl EditorLogic {
         let mut new_scene_request = false;
         let mut play_request = false;
         let mut stop_request = false;
+        let mut pause_request = false;
+        let mut step_request = false;
         let mut edit_script_request: Option<String> = None;
 
         // Note: Q/W/E/R/F keyboard shortcuts are handled in app.rs (InputSystem/Shortcuts)
         // because we need better control over input priority and context
+
+        // Snapshot selected entities during play so inspector/gizmo edits can be kept after Stop
+        let play_edit_snapshots = if editor_state.is_playing {
+            PlayModeChangeTracker::snapshot(&editor_state.world, &editor_state.current_selection())
+        } else {
+            Vec::new()
+        };
+
+        // Snapshot the selection in edit mode so inspector/gizmo edits become undo steps
+        let edit_snapshot = if editor_state.is_playing {
+            None
+        } else {
+            Some(ComponentEditRecorder::snapshot(
+                &editor_state.world,
+                &editor_state.entity_names,
+                &editor_state.current_selection(),
+            ))
+        };
+
+        // Scene-level settings aren't covered by the edit recorder
+        let preload_before = editor_state.world.preload_assets.clone();
+
+        // Hierarchy context-menu action on the whole selection
+        let mut selection_action: Option<SelectionAction> = None;
+        let previous_primary = editor_state.selected_entity;
         
+        // Navigation overlay draws the script engine's grid (kept in sync by update_scripts while playing)
+        if editor_state.show_navigation {
+            if !editor_state.is_playing {
+                script_engine.update_navigation(&editor_state.world);
+            }
+            editor_state.debug_draw.navigation = Some(std::rc::Rc::clone(&script_engine.navigation));
+        } else {
+            editor_state.debug_draw.navigation = None;
+        }
+
+        // Engine/Lua log records captured since last frame
+        editor_state.console.drain_captured_logs();
+
+        // Console Lua runs with the frame's scripts while the game runs, here otherwise
+        if !editor_state.is_playing || editor_state.is_paused {
+            PlayModeSystem::run_console_code(editor_state, &_ctx.input, script_engine);
+        }
+        let console_targets = PlayModeSystem::console_targets(editor_state, script_engine);
+        editor_state.console.set_repl_targets(console_targets);
+
         // ---------------------------------------------------------
         // Render Editor UI
         // ---------------------------------------------------------
@@ -52,6 +103,9 @@ impl EditorLogic {
                 &mut editor_state.dock_state,
                 &mut editor_state.world,
                 &mut editor_state.selected_entity,
+                &mut editor_state.selection,
+                &mut selection_action,
+                &mut editor_state.hierarchy_search,
                 &mut editor_state.entity_names,
                 &mut save_request,
                 &mut save_as_request,
@@ -60,17 +114,22 @@ impl EditorLogic {
                 &mut new_scene_request,
                 &mut play_request,
                 &mut stop_request,
+                &mut pause_request,
+                &mut step_request,
+                &mut editor_state.replay,
                 &mut edit_script_request,
                 &editor_state.current_project_path,
                 &editor_state.current_scene_path,
                 &mut editor_state.scene_view_tab,
                 editor_state.is_playing,
+                editor_state.is_paused,
                 &mut editor_state.show_colliders,
                 &mut editor_state.show_velocities,
                 &mut editor_state.console,
                 &mut editor_state.bottom_panel_tab,
                 &mut editor_state.current_tool,
                 &mut editor_state.show_project_settings,
+                &mut editor_state.project_settings,
                 &mut editor_state.scene_camera,
                 &editor_state.scene_grid,
                 &mut editor_state.infinite_grid,
@@ -83,6 +142,8 @@ impl EditorLogic {
                 &editor_state.current_layout_name,
                 &mut editor_state.dragging_entity,
                 &mut editor_state.drag_axis,
+                &mut editor_state.handle_drag,
+                &mut editor_state.snap_settings,
                 &mut editor_state.scene_view_mode,
                 &mut editor_state.projection_mode,
                 &mut editor_state.transform_space,
@@ -97,8 +158,10 @@ impl EditorLogic {
                 &mut editor_state.sprite_editor_windows,
                 &mut editor_state.sprite_picker_state,
                 &mut editor_state.texture_inspector,
+                &mut editor_state.ui_inspector,
                 &mut editor_state.map_view_state,
                 &mut editor_state.show_debug_lines,
+                &mut editor_state.show_navigation,
                 &mut editor_state.debug_draw,
                 &mut editor_state.map_manager,
                 &mut editor_state.prefab_manager,
@@ -114,6 +177,10 @@ impl EditorLogic {
                 &mut editor_state.reload_mesh_assets_request,
                 asset_loader,
                 render_cache,
+                &mut editor_state.animation_window,
+                &mut editor_state.scene_modified,
+                &mut editor_state.asset_references,
+                &editor_state.component_registry,
             );
         } else {
              // Fallback to old layout
@@ -129,11 +196,15 @@ impl EditorLogic {
                 &mut new_scene_request,
                 &mut play_request,
                 &mut stop_request,
+                &mut pause_request,
+                &mut step_request,
+                &mut editor_state.replay,
                 &mut edit_script_request,
                 &editor_state.current_project_path,
                 &editor_state.current_scene_path,
                 &mut editor_state.scene_view_tab,
                 editor_state.is_playing,
+                editor_state.is_paused,
                 &mut editor_state.show_colliders,
                 &mut editor_state.show_velocities,
                 &mut editor_state.console,
@@ -153,6 +224,7 @@ impl EditorLogic {
                 &mut editor_state.open_sprite_editor_request,
                 &mut editor_state.sprite_picker_state,
                 &mut editor_state.show_debug_lines,
+                &mut editor_state.show_navigation,
                 scene_view_renderer,
                 egui_renderer,
                 device,
@@ -195,13 +267,78 @@ impl EditorLogic {
         // Exit Confirmation Dialog
         ExitDialog::render(egui_ctx, editor_state);
 
+        // Crash recovery (autosave newer than the scene after an unclean shutdown)
+        RecoveryDialog::render(egui_ctx, editor_state);
+
+        // Open scene changed on disk, or File > Revert Scene
+        SceneReloadDialog::render(egui_ctx, editor_state);
+
+        // Export Game Dialog
+        ExportGameDialog::render(egui_ctx, editor_state);
+
+        // Scene Preload Progress (shown while Play waits on assets)
+        PreloadDialog::render(egui_ctx, editor_state);
+
+        // Keep Play Mode Changes Dialog (shown after Stop)
+        PlayModeChangesDialog::render(egui_ctx, editor_state);
+
         // Sprite Picker Dialog
         EditorLogic::handle_sprite_picker(egui_ctx, editor_state);
 
+        // An asset dropped outside the scene view cancels the drag
+        if editor_state.drag_drop.is_dragging() && egui_ctx.input(|i| i.pointer.any_released()) {
+            editor_state.drag_drop.stop_drag();
+        }
+
+        // Commit component edits once the drag/text edit that made them has ended
+        if let Some(snapshot) = edit_snapshot {
+            let interacting = egui_ctx.input(|i| i.pointer.any_down()) || egui_ctx.wants_keyboard_input();
+            if editor_state.edit_recorder.record(
+                &mut editor_state.world,
+                &mut editor_state.entity_names,
+                snapshot,
+                interacting,
+                &mut editor_state.undo_stack,
+            ) {
+                editor_state.scene_modified = true;
+            }
+        }
+
+        if !editor_state.is_playing && editor_state.world.preload_assets != preload_before {
+            editor_state.scene_modified = true;
+        }
+
+        // Inspector component menu: Copy / Paste Component (after the edit recorder, so a
+        // paste is its own undo step)
+        if let Some(action) = component_clipboard::take_request(egui_ctx) {
+            editor_state.apply_component_menu_action(action);
+        }
+
+        // Console click on a Lua error
+        if let Some(request) = editor_state.console.take_open_request() {
+            editor_state.open_console_source(request);
+        }
+
+        // Reconcile the multi-selection with panels that only set `selected_entity`
+        editor_state.sync_selection(previous_primary);
+        if let Some(action) = selection_action {
+            editor_state.apply_selection_action(action);
+        }
+
+        // Record user edits made through the UI this frame (before Stop can restore the scene)
+        if editor_state.is_playing {
+            editor_state.play_changes.record_edits(&editor_state.world, &play_edit_snapshots);
+        }
+
         // ---------------------------------------------------------
         // Handle Logic & Systems
         // ---------------------------------------------------------
         
+        // Keep requesting Play while the scene's preload list is loading
+        if editor_state.waiting_for_preload {
+            play_request = true;
+        }
+
         // Handle Menu Commands (Save, Load, New, Play, etc.)
         MenuCommandSystem::handle_commands(
             editor_state,
@@ -214,7 +351,11 @@ impl EditorLogic {
             &mut new_scene_request,
             &mut play_request,
             &mut stop_request,
+            &mut pause_request,
+            &mut step_request,
             &mut edit_script_request,
+            &mut _ctx.input,
+            fixed_time_step,
             device,
             queue,
             render_texture_manager,
@@ -256,8 +397,44 @@ impl EditorLogic {
              }
         }
 
-        // Render standalone floating windows (only in non-docking mode)
-        EditorLogic::handle_floating_windows(egui_ctx, editor_state, dt);
+        // Textures whose import settings changed in the inspector: re-create the WGPU copies too
+        let reimported = editor_state.texture_manager.take_reimported();
+        if !reimported.is_empty() {
+            for texture_id in &reimported {
+                render_texture_manager.remove_texture(texture_id);
+            }
+            if let Some(project_path) = &editor_state.current_project_path {
+                EditorLogic::load_scene_textures(
+                    &editor_state.world,
+                    project_path,
+                    device,
+                    queue,
+                    render_texture_manager,
+                    asset_loader,
+                );
+            }
+        }
+
+        // Render standalone floating windows (sprite editors only in non-docking mode)
+        EditorLogic::handle_floating_windows(egui_ctx, editor_state, script_engine, dt);
+
+        // Edited Lua modules load again on their next require()
+        if editor_state.is_playing {
+            for module in script_engine.reload_changed_modules() {
+                editor_state.console.info(format!("Lua module '{}' changed; the next require() reloads it", module));
+            }
+        }
+
+        // Input.pick_entity reads window pixels; the game is wherever the Game tab drew it
+        if editor_state.is_playing {
+            if let Some(rect) = crate::ui::dock_layout::game_view_screen_rect(egui_ctx) {
+                let sorting_layers = ecs::sorting::SortingLayers::new(editor_state.project_settings.rendering.sorting_layers.clone());
+                let canvas_scales = editor_state.ui_manager.canvas_scales();
+                engine::runtime::script_system::sync_screen(script_engine, &editor_state.world, &sorting_layers, rect, editor_state.project_settings.rendering.aspect_policy(), canvas_scales, |texture_id| {
+                    editor_state.texture_manager.sprite_pivot(texture_id)
+                });
+            }
+        }
 
         // Handle Play Mode Logic (Physics, Scripts, Collisions)
         PlayModeSystem::update(
@@ -308,12 +485,15 @@ impl EditorLogic {
                                     asset_id: None,
                                     width: sprite_def.width as f32,
                                     height: sprite_def.height as f32,
-                                    color: [1.0, 1.0, 1.0, 1.0],
+                                    color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
                                     billboard: false,
                                     flip_x: false,
                                     flip_y: false,
                                     sprite_rect: Some([sprite_def.x, sprite_def.y, sprite_def.width, sprite_def.height]),
+                                    rect_rotated: sprite_def.rotated,
+                                    frame_name: Some(sprite_def.name.clone()),
                                     pixels_per_unit: 100.0,
+                                    pivot: Some(sprite_def.pivot),
                                     sorting_layer: "Default".to_string(),
                                     order_in_layer: 0,
                                     rendering_layer_mask: 1,
@@ -336,12 +516,15 @@ impl EditorLogic {
                         asset_id: None,
                         width: 1.0,
                         height: 1.0,
-                        color: [1.0, 1.0, 1.0, 1.0],
+                        color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
                         billboard: false,
                         flip_x: false,
                         flip_y: false,
                         pixels_per_unit: 100.0,
+                        pivot: None,
                         sprite_rect: None,
+                        rect_rotated: false,
+                        frame_name: None,
                         sorting_layer: "Default".to_string(),
                         order_in_layer: 0,
                         rendering_layer_mask: 1,
@@ -366,6 +549,7 @@ impl EditorLogic {
         asset_loader: &dyn engine_core::assets::AssetLoader,
     ) {
         use std::collections::HashSet;
+        use engine::texture_manager::TextureImportSettings;
 
         // Collect unique texture paths from all sprites and tilesets
         // We use the path string as the Texture ID for WGPU lookups
@@ -373,7 +557,8 @@ impl EditorLogic {
         
         // 1. Sprites
         for sprite in world.sprites.values() {
-            if !sprite.texture_id.is_empty() {
+            // Render textures aren't files; the RenderTextureManager registers them
+            if !sprite.texture_id.is_empty() && ecs::RenderTextureAsset::name_from_texture_id(&sprite.texture_id).is_none() {
                 texture_paths.insert(sprite.texture_id.clone());
             }
         }
@@ -389,7 +574,7 @@ impl EditorLogic {
         println!("DEBUG: Loading {} unique textures for WGPU. Project Path: {}", texture_paths.len(), project_path.display());
 
         // Potential subdirectories to search for textures
-        let search_dirs = ["", "assets", "atlas", "tilemaps/atlas", "levels/atlas"];
+        let search_dirs = crate::assets::references::TEXTURE_SEARCH_DIRS;
 
         // Load each texture into WGPU TextureManager
         for texture_id in texture_paths {
@@ -410,7 +595,8 @@ impl EditorLogic {
 
                 // Attempt load via AssetLoader
                 if let Ok(bytes) = pollster::block_on(asset_loader.load_binary(check_path_str)) {
-                     match texture_manager.load_texture_from_bytes(device, queue, &bytes, &texture_id) {
+                     let settings = TextureImportSettings::load_with(asset_loader, check_path_str);
+                     match texture_manager.load_texture_from_bytes_with_options(device, queue, &bytes, &texture_id, settings.gpu_options()) {
                         Ok(_) => {
                             println!("DEBUG: ✓ Loaded texture for WGPU: {} (found in {})", texture_id, dir);
                         }
@@ -429,7 +615,7 @@ impl EditorLogic {
         }
     }
 
-    fn handle_floating_windows(egui_ctx: &egui::Context, editor_state: &mut EditorState, dt: f32) {
+    fn handle_floating_windows(egui_ctx: &egui::Context, editor_state: &mut EditorState, script_engine: &mut ScriptEngine, dt: f32) {
         if !editor_state.use_docking {
             let mut reloaded_sprite_files = Vec::new();
             editor_state.sprite_editor_windows.retain_mut(|window| {
@@ -448,5 +634,58 @@ impl EditorLogic {
                 editor_state.update_entities_using_sprite_file(&sprite_file_path);
             }
         }
+
+        // The docking layout has an Animation tab; the legacy layout floats it
+        if !editor_state.use_docking && editor_state.animation_window.render(
+            egui_ctx,
+            &mut editor_state.world,
+            editor_state.selected_entity,
+            editor_state.current_project_path.as_ref(),
+            &mut editor_state.texture_manager,
+            dt,
+        ) {
+            editor_state.scene_modified = true;
+        }
+
+        if editor_state.tile_palette.render(
+            egui_ctx,
+            &mut editor_state.world,
+            editor_state.selected_entity,
+            &mut editor_state.texture_manager,
+        ) {
+            editor_state.scene_modified = true;
+        }
+
+        editor_state.memory_panel.render(egui_ctx, script_engine, &editor_state.entity_names);
+        editor_state.globals_panel.render(egui_ctx, script_engine, editor_state.is_playing);
+
+        if editor_state.asset_validation.render(
+            egui_ctx,
+            &mut editor_state.world,
+            editor_state.current_project_path.as_deref(),
+            &mut editor_state.asset_references,
+            &editor_state.entity_names,
+            &mut editor_state.selected_entity,
+        ) {
+            editor_state.scene_modified = true;
+            // Load the new textures and models
+            editor_state.reload_mesh_assets_request = true;
+        }
+
+        editor_state.command_palette.render(egui_ctx, &mut editor_state.commands, editor_state.is_playing);
+        editor_state.shortcut_preferences.render(egui_ctx, &mut editor_state.commands);
+
+        editor_state.script_debugger.sync_project(editor_state.current_project_path.as_deref(), script_engine);
+        editor_state.script_debugger.render(egui_ctx, script_engine, &editor_state.entity_names);
+
+        // A finished paint stroke is already applied; record it as one undo step
+        if let Some(stroke) = crate::tools::tile_painting::take_finished_stroke(egui_ctx) {
+            editor_state.undo_stack.execute(
+                Box::new(crate::systems::undo::PaintTilesCommand::new(stroke)),
+                &mut editor_state.world,
+                &mut editor_state.entity_names,
+            );
+            editor_state.scene_modified = true;
+        }
     }
 }
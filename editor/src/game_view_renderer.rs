@@ -2,6 +2,27 @@ use egui_wgpu::wgpu;
 use egui;
 use egui_wgpu;
 
+/// Screenshot / GIF requests from the menu and shortcuts, handled where the Game view
+/// texture is rendered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureRequest {
+    Screenshot,
+    SaveGif,
+}
+
+fn capture_requests_id() -> egui::Id {
+    egui::Id::new("game_view_capture_requests")
+}
+
+/// Save the next Game view frame as a PNG, or the recorded frames as a GIF
+pub fn request_capture(ctx: &egui::Context, request: CaptureRequest) {
+    ctx.data_mut(|d| d.get_temp_mut_or_default::<Vec<CaptureRequest>>(capture_requests_id()).push(request));
+}
+
+pub fn take_capture_requests(ctx: &egui::Context) -> Vec<CaptureRequest> {
+    ctx.data_mut(|d| d.remove_temp::<Vec<CaptureRequest>>(capture_requests_id())).unwrap_or_default()
+}
+
 pub struct GameViewRenderer {
     pub texture: wgpu::Texture,
     pub view: wgpu::TextureView,
@@ -33,7 +54,7 @@ impl GameViewRenderer {
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
         });
 
@@ -102,7 +123,7 @@ impl GameViewRenderer {
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: self.format,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
         });
 
@@ -4,7 +4,7 @@ use std::path::{Path, PathBuf};
 use std::sync::mpsc::{channel, Receiver};
 use std::time::Duration;
 
-/// Hot-reload watcher for LDtk files
+/// Hot-reload watcher for LDtk files (or any other extensions, see `with_extensions`)
 pub struct HotReloadWatcher {
     /// File watcher with debouncing
     _debouncer: Debouncer<notify::RecommendedWatcher, FileIdMap>,
@@ -17,9 +17,15 @@ pub struct HotReloadWatcher {
 }
 
 impl HotReloadWatcher {
-    /// Create a new hot-reload watcher
+    /// Create a new hot-reload watcher for .ldtk files
     pub fn new() -> Result<Self, String> {
+        Self::with_extensions(&["ldtk"])
+    }
+
+    /// Create a watcher that reports changed files with one of `extensions`
+    pub fn with_extensions(extensions: &[&str]) -> Result<Self, String> {
         let (tx, rx) = channel();
+        let extensions: Vec<String> = extensions.iter().map(|e| e.to_string()).collect();
         
         // Create debouncer with 500ms delay to handle rapid file changes
         let debouncer = new_debouncer(
@@ -29,7 +35,7 @@ impl HotReloadWatcher {
                 match result {
                     Ok(events) => {
                         for event in events {
-                            if let Some(path) = Self::extract_ldtk_path(&event.event) {
+                            if let Some(path) = Self::extract_path(&event.event, &extensions) {
                                 // Send the path through the channel
                                 let _ = tx.send(path);
                             }
@@ -51,14 +57,15 @@ impl HotReloadWatcher {
         })
     }
     
-    /// Extract .ldtk file path from event if applicable
-    fn extract_ldtk_path(event: &Event) -> Option<PathBuf> {
+    /// Extract the file path (with a watched extension) from event if applicable
+    fn extract_path(event: &Event, extensions: &[String]) -> Option<PathBuf> {
         // Check if this is a relevant event type
         match event.kind {
             EventKind::Create(_) | EventKind::Modify(_) => {
-                // Check if any path in the event is an .ldtk file
+                // Check if any path in the event has a watched extension
                 for path in &event.paths {
-                    if path.extension().and_then(|s| s.to_str()) == Some("ldtk") {
+                    let extension = path.extension().and_then(|s| s.to_str());
+                    if extension.is_some_and(|ext| extensions.iter().any(|e| e == ext)) {
                         return Some(path.clone());
                     }
                 }
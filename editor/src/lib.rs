@@ -1,6 +1,7 @@
 pub mod app;
 pub mod theme;
 pub mod shortcuts;
+pub mod commands;
 pub mod console;
 pub mod tools;
 pub mod systems;
@@ -19,6 +20,7 @@ pub mod toolbar;
 pub mod autosave;
 pub mod assets;
 pub mod asset_manager;
+pub mod thumbnails;
 pub mod drag_drop;
 pub mod shortcuts_handler;
 pub mod texture_import_settings;
@@ -27,28 +29,34 @@ pub mod map_manager;
 pub mod hot_reload;
 pub mod tilemap_error;
 pub mod tilemap_settings;
+pub mod scene_camera_poses;
+pub mod scene_diff;
+pub mod scene_watch;
 pub mod widget_editor;
 pub mod prefab;
+pub mod script_annotations;
 pub mod sprite_editor_window;
 pub mod ui;
 // Re-exports for convenience (matching old engine::editor interface)
 pub use console::Console;
 pub use ui::{EditorUI, TransformTool};
-pub use states::{AppState, LauncherState, EditorState, EditorAction};
-pub use shortcuts::EditorShortcut; // Wait, shortcuts is in root.
-pub use systems::camera::{SceneCamera, SceneProjectionMode};
+pub use states::{AppState, LauncherState, EditorState, EditorAction, SelectionAction};
+pub use shortcuts::KeyChord;
+pub use commands::{Command, CommandRegistry, CommandScope};
+pub use systems::camera::{SceneCamera, SceneProjectionMode, CameraPose};
 pub use ui::camera_settings::CameraStateDisplay;
 pub use grid::{SceneGrid, InfiniteGrid, CameraState};
 pub use theme::UnityTheme;
 pub use asset_manager::AssetManager;
 pub use drag_drop::{DragDropState, DraggedAsset};
-pub use systems::undo::{UndoStack, CreateEntityCommand, DeleteEntityCommand, BatchCommand};
+pub use systems::undo::{UndoStack, CreateEntityCommand, DeleteEntityCommand, DuplicateEntitiesCommand, SetParentCommand, BatchCommand};
 pub use tools::selection::{SelectionManager, SelectionMode};
 pub use systems::clipboard::{Clipboard, copy_selected, paste_from_clipboard, duplicate_selected};
 pub use debug_draw::DebugDrawManager;
 pub use map_manager::MapManager;
 pub use tilemap_error::TilemapError;
 pub use tilemap_settings::TilemapSettings;
+pub use scene_camera_poses::SceneCameraPoses;
 pub use widget_editor::PrefabEditor;
 pub use prefab::{Prefab, PrefabManager, PrefabEntity, PrefabMetadata};
 pub use sprite_editor_window::SpriteEditorWindow;
@@ -2,8 +2,15 @@ use anyhow::Result;
 use winit::event_loop::EventLoop;
 use editor::app::EditorApp;
 
+// Attributes allocations to profiler scopes (Memory window)
+#[cfg(feature = "enable_profiling")]
+#[global_allocator]
+static ALLOCATOR: profiler::memory::ScopedAllocator<std::alloc::System> =
+    profiler::memory::ScopedAllocator::new(std::alloc::System);
+
 fn main() -> Result<()> {
-    env_logger::init();
+    // env_logger output plus capture into the editor Console
+    editor::console::ConsoleLogger::init();
     println!("Starting Game Engine...");
     log::info!("=== Rust 2D Game Engine Starting ===");
     log::info!("Logging initialized");
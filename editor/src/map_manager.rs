@@ -582,7 +582,7 @@ impl MapManager {
                                     let tile_count = columns * rows;
                                     
                                     // Create TileSet component
-                                    let tileset = ecs::TileSet::new(
+                                    let mut tileset = ecs::TileSet::new(
                                         format!("tileset_{}", tileset_uid),
                                         tileset_path_str.clone(),
                                         format!("tileset_{}", tileset_uid),
@@ -592,6 +592,15 @@ impl MapManager {
                                         tile_count,
                                     );
                                     
+                                    // Per-tile custom data (damage, friction, is_ladder...)
+                                    if let Some(custom_data) = tileset_def["customData"].as_array() {
+                                        for custom in custom_data {
+                                            if let (Some(tile_id), Some(data)) = (custom["tileId"].as_u64(), custom["data"].as_str()) {
+                                                tileset.set_tile_properties(tile_id as u32, ecs::TileProperty::parse_custom_data(data));
+                                            }
+                                        }
+                                    }
+                                    
                                     world.tilesets.insert(entity, tileset);
                                 }
                                 break;
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::fs;
+
+use crate::systems::camera::CameraPose;
+
+/// Last scene view camera pose per scene file.
+/// Editor-only: kept in project settings, never written into the scene.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SceneCameraPoses {
+    /// Keyed by scene path relative to the project, with `/` separators
+    #[serde(default)]
+    pub poses: HashMap<String, CameraPose>,
+}
+
+impl SceneCameraPoses {
+    /// Load camera poses from project directory
+    /// Returns an empty set if the file doesn't exist
+    pub fn load(project_path: &Path) -> Self {
+        let poses_path = Self::get_poses_path(project_path);
+
+        if !poses_path.exists() {
+            return Self::default();
+        }
+
+        match fs::read_to_string(&poses_path) {
+            Ok(json) => match serde_json::from_str(&json) {
+                Ok(poses) => poses,
+                Err(e) => {
+                    log::warn!("Failed to parse scene camera poses: {}. Starting fresh.", e);
+                    Self::default()
+                }
+            },
+            Err(e) => {
+                log::warn!("Failed to read scene camera poses: {}. Starting fresh.", e);
+                Self::default()
+            }
+        }
+    }
+
+    /// Save camera poses to project directory
+    pub fn save(&self, project_path: &Path) -> Result<(), String> {
+        let poses_path = Self::get_poses_path(project_path);
+
+        if let Some(parent) = poses_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create settings directory: {}", e))?;
+        }
+
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize scene camera poses: {}", e))?;
+
+        fs::write(&poses_path, json)
+            .map_err(|e| format!("Failed to write scene camera poses: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Pose last used for a scene, if any
+    pub fn get(&self, project_path: &Path, scene_path: &Path) -> Option<CameraPose> {
+        self.poses.get(&Self::scene_key(project_path, scene_path)).copied()
+    }
+
+    /// Remember the pose for a scene
+    pub fn set(&mut self, project_path: &Path, scene_path: &Path, pose: CameraPose) {
+        self.poses.insert(Self::scene_key(project_path, scene_path), pose);
+    }
+
+    /// Project-relative key so the file survives moving the project folder
+    fn scene_key(project_path: &Path, scene_path: &Path) -> String {
+        let relative = scene_path.strip_prefix(project_path).unwrap_or(scene_path);
+        relative.to_string_lossy().replace('\\', "/")
+    }
+
+    /// Get the path to the camera poses file
+    fn get_poses_path(project_path: &Path) -> PathBuf {
+        project_path.join(".kiro").join("settings").join("scene_cameras.json")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+    use tempfile::TempDir;
+
+    fn pose(x: f32) -> CameraPose {
+        CameraPose { position: Vec3::new(x, 1.0, 2.0), zoom: 50.0, rotation: 30.0, pitch: -20.0, distance: 12.0 }
+    }
+
+    #[test]
+    fn test_save_and_load_per_scene() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+        let main = project_path.join("scenes").join("main.json");
+        let level = project_path.join("scenes").join("level.json");
+
+        let mut poses = SceneCameraPoses::default();
+        poses.set(project_path, &main, pose(1.0));
+        poses.set(project_path, &level, pose(2.0));
+        poses.save(project_path).unwrap();
+
+        let loaded = SceneCameraPoses::load(project_path);
+        assert_eq!(loaded.get(project_path, &main), Some(pose(1.0)));
+        assert_eq!(loaded.get(project_path, &level), Some(pose(2.0)));
+        assert!(loaded.poses.contains_key("scenes/main.json"));
+    }
+
+    #[test]
+    fn test_unknown_scene_has_no_pose() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path();
+
+        let poses = SceneCameraPoses::load(project_path);
+        assert_eq!(poses.get(project_path, &project_path.join("scenes/other.json")), None);
+    }
+}
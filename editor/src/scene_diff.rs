@@ -0,0 +1,263 @@
+/// Entity/component level diff between two versions of a scene
+///
+/// Used when the open scene file changes on disk (or on Revert Scene) to show what a
+/// reload would change. Both worlds are compared through their scene serialization, so
+/// any serialized component is covered; entities, components and fields are keyed in
+/// sorted maps so HashMap iteration order never shows up as a change.
+use ecs::{Entity, World};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Scene keys that aren't per-entity components
+const SCENE_KEYS: [&str; 2] = ["next_entity", "preload_assets"];
+
+/// Longest value shown for a field before it's shortened
+const MAX_VALUE_LEN: usize = 60;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldDiff {
+    /// Field path inside the component, e.g. `position[1]` or `parameters.speed.Float`
+    pub path: String,
+    /// Value in the editor (None: the field doesn't exist there)
+    pub mine: Option<String>,
+    /// Value on disk
+    pub disk: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComponentChange {
+    /// Only on disk
+    Added,
+    /// Only in the editor
+    Removed,
+    Changed(Vec<FieldDiff>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentDiff {
+    /// Scene key of the component (`transforms`, `sprites`, ...)
+    pub component: String,
+    pub change: ComponentChange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityChange {
+    Added,
+    Removed,
+    Changed,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityDiff {
+    pub entity: Entity,
+    pub name: String,
+    pub change: EntityChange,
+    pub components: Vec<ComponentDiff>,
+}
+
+/// What reloading the disk version would do to the editor's scene
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SceneDiff {
+    /// Sorted by entity id
+    pub entities: Vec<EntityDiff>,
+    /// Changes to scene-level data (the preload list)
+    pub scene_fields: Vec<FieldDiff>,
+}
+
+type SceneComponents = BTreeMap<Entity, BTreeMap<String, Value>>;
+
+impl SceneDiff {
+    pub fn between(mine: &World, disk: &World) -> Result<Self, serde_json::Error> {
+        let (mine_components, mine_scene) = split_scene(mine)?;
+        let (disk_components, disk_scene) = split_scene(disk)?;
+
+        let mut entities = Vec::new();
+        let ids: std::collections::BTreeSet<Entity> =
+            mine_components.keys().chain(disk_components.keys()).copied().collect();
+        for entity in ids {
+            let name = disk.names.get(&entity)
+                .or_else(|| mine.names.get(&entity))
+                .cloned()
+                .unwrap_or_else(|| format!("Entity {}", entity));
+            let empty = BTreeMap::new();
+            let (change, before, after) = match (mine_components.get(&entity), disk_components.get(&entity)) {
+                (Some(before), Some(after)) => (EntityChange::Changed, before, after),
+                (None, Some(after)) => (EntityChange::Added, &empty, after),
+                (Some(before), None) => (EntityChange::Removed, before, &empty),
+                (None, None) => continue,
+            };
+            let components = diff_components(before, after);
+            if !components.is_empty() {
+                entities.push(EntityDiff { entity, name, change, components });
+            }
+        }
+
+        Ok(Self { entities, scene_fields: diff_fields(&mine_scene, &disk_scene) })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty() && self.scene_fields.is_empty()
+    }
+
+    /// (added, removed, changed) entity counts
+    pub fn counts(&self) -> (usize, usize, usize) {
+        let count = |change| self.entities.iter().filter(|e| e.change == change).count();
+        (count(EntityChange::Added), count(EntityChange::Removed), count(EntityChange::Changed))
+    }
+}
+
+/// Per-entity components and the scene-level values of a world's serialized scene
+fn split_scene(world: &World) -> Result<(SceneComponents, Value), serde_json::Error> {
+    let scene: Value = serde_json::from_str(&world.save_to_json()?)?;
+    let mut components = SceneComponents::new();
+    let mut scene_values = serde_json::Map::new();
+    let Value::Object(scene) = scene else {
+        return Ok((components, Value::Null));
+    };
+
+    for (key, value) in scene {
+        if SCENE_KEYS.contains(&key.as_str()) {
+            // next_entity moves with every spawn; it isn't a change anyone made
+            if key != "next_entity" {
+                scene_values.insert(key, value);
+            }
+            continue;
+        }
+        let Value::Array(pairs) = value else {
+            continue;
+        };
+        for pair in pairs {
+            // Components are stored as [entity, value] pairs
+            if let Value::Array(mut pair) = pair {
+                if pair.len() == 2 {
+                    let value = pair.pop().unwrap_or(Value::Null);
+                    if let Some(entity) = pair[0].as_u64() {
+                        components.entry(entity as Entity).or_default().insert(key.clone(), value);
+                    }
+                }
+            }
+        }
+    }
+    Ok((components, Value::Object(scene_values)))
+}
+
+fn diff_components(before: &BTreeMap<String, Value>, after: &BTreeMap<String, Value>) -> Vec<ComponentDiff> {
+    let keys: std::collections::BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let change = match (before.get(key), after.get(key)) {
+                (Some(before), Some(after)) => {
+                    let fields = diff_fields(before, after);
+                    if fields.is_empty() {
+                        return None;
+                    }
+                    ComponentChange::Changed(fields)
+                }
+                (None, Some(_)) => ComponentChange::Added,
+                (Some(_), None) => ComponentChange::Removed,
+                (None, None) => return None,
+            };
+            Some(ComponentDiff { component: key.clone(), change })
+        })
+        .collect()
+}
+
+fn diff_fields(before: &Value, after: &Value) -> Vec<FieldDiff> {
+    let mut mine = BTreeMap::new();
+    let mut disk = BTreeMap::new();
+    flatten("", before, &mut mine);
+    flatten("", after, &mut disk);
+
+    let paths: std::collections::BTreeSet<&String> = mine.keys().chain(disk.keys()).collect();
+    paths.into_iter()
+        .filter(|path| mine.get(*path) != disk.get(*path))
+        .map(|path| FieldDiff {
+            path: path.clone(),
+            mine: mine.get(path).map(|v| shorten(v)),
+            disk: disk.get(path).map(|v| shorten(v)),
+        })
+        .collect()
+}
+
+/// Leaf values by path; object keys land in a sorted map whatever order they came in
+fn flatten(path: &str, value: &Value, out: &mut BTreeMap<String, String>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, value) in map {
+                let path = if path.is_empty() { key.clone() } else { format!("{}.{}", path, key) };
+                flatten(&path, value, out);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (i, item) in items.iter().enumerate() {
+                flatten(&format!("{}[{}]", path, i), item, out);
+            }
+        }
+        leaf => {
+            let path = if path.is_empty() { "value".to_string() } else { path.to_string() };
+            out.insert(path, leaf.to_string());
+        }
+    }
+}
+
+fn shorten(value: &str) -> String {
+    if value.chars().count() <= MAX_VALUE_LEN {
+        return value.to_string();
+    }
+    let short: String = value.chars().take(MAX_VALUE_LEN - 1).collect();
+    format!("{}…", short)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spawn(world: &mut World, name: &str, x: f32) -> Entity {
+        let entity = world.spawn();
+        world.transforms.insert(entity, ecs::Transform::with_position(x, 0.0, 0.0));
+        world.names.insert(entity, name.to_string());
+        entity
+    }
+
+    #[test]
+    fn test_identical_scenes_have_no_diff() {
+        let mut world = World::new();
+        for i in 0..20 {
+            spawn(&mut world, &format!("Entity {}", i), i as f32);
+        }
+        // Same content, different HashMap insertion order
+        let mut reloaded = World::new();
+        reloaded.load_from_json(&world.save_to_json().unwrap()).unwrap();
+        assert!(SceneDiff::between(&world, &reloaded).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_added_removed_and_changed_entities() {
+        let mut mine = World::new();
+        let player = spawn(&mut mine, "Player", 0.0);
+        let enemy = spawn(&mut mine, "Enemy", 5.0);
+
+        let mut disk = World::new();
+        disk.load_from_json(&mine.save_to_json().unwrap()).unwrap();
+        disk.despawn(enemy);
+        disk.transforms.get_mut(&player).unwrap().position[1] = 2.5;
+        disk.sprites.insert(player, ecs::Sprite::default());
+        let coin = spawn(&mut disk, "Coin", 3.0);
+
+        let diff = SceneDiff::between(&mine, &disk).unwrap();
+        assert_eq!(diff.counts(), (1, 1, 1));
+
+        let changed = diff.entities.iter().find(|e| e.entity == player).unwrap();
+        assert_eq!(changed.name, "Player");
+        let transform = changed.components.iter().find(|c| c.component == "transforms").unwrap();
+        assert_eq!(transform.change, ComponentChange::Changed(vec![FieldDiff {
+            path: "position[1]".to_string(),
+            mine: Some("0.0".to_string()),
+            disk: Some("2.5".to_string()),
+        }]));
+        assert!(changed.components.iter().any(|c| c.component == "sprites" && c.change == ComponentChange::Added));
+
+        assert_eq!(diff.entities.iter().find(|e| e.entity == coin).unwrap().change, EntityChange::Added);
+        assert_eq!(diff.entities.iter().find(|e| e.entity == enemy).unwrap().change, EntityChange::Removed);
+    }
+}
@@ -0,0 +1,108 @@
+/// External changes to the open scene file
+///
+/// The scene's directory is watched with the hot-reload watcher (editors and git often
+/// replace files rather than write them in place). A change only counts when the file's
+/// contents differ from what the editor last loaded or saved, so the editor's own saves
+/// never prompt.
+use crate::hot_reload::HotReloadWatcher;
+use crate::scene_diff::SceneDiff;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+#[derive(Default)]
+pub struct SceneFileWatch {
+    watcher: Option<HotReloadWatcher>,
+    /// Directory currently watched (the open scene's)
+    directory: Option<PathBuf>,
+    /// Hash of the scene file as the editor last read or wrote it
+    known_hash: Option<u64>,
+    /// The watcher couldn't be created; don't retry every scene switch
+    unavailable: bool,
+}
+
+fn content_hash(contents: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    a == b || matches!((a.canonicalize(), b.canonicalize()), (Ok(a), Ok(b)) if a == b)
+}
+
+impl SceneFileWatch {
+    /// The editor loaded or saved `path` with `contents`: watch it and remember them
+    pub fn track(&mut self, path: &Path, contents: &str) {
+        self.known_hash = Some(content_hash(contents));
+
+        let Some(directory) = path.parent().map(Path::to_path_buf) else {
+            return;
+        };
+        if self.directory.as_ref() == Some(&directory) || self.unavailable {
+            return;
+        }
+        if self.watcher.is_none() {
+            match HotReloadWatcher::with_extensions(&["json", "scene"]) {
+                Ok(watcher) => self.watcher = Some(watcher),
+                Err(e) => {
+                    log::warn!("Scene file changes won't be detected: {}", e);
+                    self.unavailable = true;
+                    return;
+                }
+            }
+        }
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        if let Some(previous) = self.directory.take() {
+            let _ = watcher.unwatch(&previous);
+        }
+        match watcher.watch_file(&directory) {
+            Ok(()) => self.directory = Some(directory),
+            Err(e) => log::warn!("{}", e),
+        }
+    }
+
+    /// The user kept their version over `contents` on disk: only newer changes prompt again
+    pub fn acknowledge(&mut self, contents: &str) {
+        self.known_hash = Some(content_hash(contents));
+    }
+
+    /// New contents of `path` if it changed on disk since it was last tracked
+    pub fn poll(&mut self, path: &Path) -> Option<String> {
+        let watcher = self.watcher.as_ref()?;
+        let changed = watcher.poll_changes().iter().any(|changed| same_file(changed, path));
+        if !changed {
+            return None;
+        }
+        // Deleted or mid-write: wait for the next event
+        let contents = std::fs::read_to_string(path).ok()?;
+        (self.known_hash != Some(content_hash(&contents))).then_some(contents)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SceneReloadReason {
+    /// The file changed outside the editor
+    ExternalChange,
+    /// File > Revert Scene
+    Revert,
+}
+
+/// Reload / Keep Mine / Show Diff offered for the open scene
+pub struct SceneReloadPrompt {
+    pub reason: SceneReloadReason,
+    pub path: PathBuf,
+    /// Scene file contents on disk
+    pub disk_json: String,
+    /// Computed on the first Show Diff
+    pub diff: Option<Result<SceneDiff, String>>,
+    pub show_diff: bool,
+}
+
+impl SceneReloadPrompt {
+    pub fn new(reason: SceneReloadReason, path: PathBuf, disk_json: String) -> Self {
+        Self { reason, path, disk_json, diff: None, show_diff: false }
+    }
+}
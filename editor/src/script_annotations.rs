@@ -0,0 +1,355 @@
+//! `--@param` annotations at the top of Lua scripts
+//!
+//! ```lua
+//! --@param speed Float min=0 max=500 default=200 tooltip="Movement speed in px/s"
+//! --@param target Entity
+//! ```
+//!
+//! They tell the inspector how to edit a script's parameters: sliders for ranged
+//! numbers, checkboxes, entity pickers, the value a missing parameter starts with and a
+//! tooltip. Malformed annotations are reported with their line number instead of being
+//! skipped silently.
+
+use ecs::ScriptParameter;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use ui::animation::{AnimationCurve, EasingFunction};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamKind {
+    Float,
+    Int,
+    Bool,
+    String,
+    Entity,
+    Curve,
+}
+
+impl ParamKind {
+    /// Type name of an annotation ("Float", "number", "Integer", ...)
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "float" | "number" => Some(ParamKind::Float),
+            "int" | "integer" => Some(ParamKind::Int),
+            "bool" | "boolean" => Some(ParamKind::Bool),
+            "string" => Some(ParamKind::String),
+            "entity" => Some(ParamKind::Entity),
+            "curve" => Some(ParamKind::Curve),
+            _ => None,
+        }
+    }
+
+    pub fn matches(&self, value: &ScriptParameter) -> bool {
+        matches!(
+            (self, value),
+            (ParamKind::Float, ScriptParameter::Float(_))
+                | (ParamKind::Int, ScriptParameter::Int(_))
+                | (ParamKind::Bool, ScriptParameter::Bool(_))
+                | (ParamKind::String, ScriptParameter::String(_))
+                | (ParamKind::Entity, ScriptParameter::Entity(_))
+                | (ParamKind::Curve, ScriptParameter::Curve(_))
+        )
+    }
+
+    fn is_numeric(&self) -> bool {
+        matches!(self, ParamKind::Float | ParamKind::Int)
+    }
+}
+
+/// One `--@param` line
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParamAnnotation {
+    pub name: String,
+    pub kind: ParamKind,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    pub default: Option<ScriptParameter>,
+    pub tooltip: Option<String>,
+    /// 1-based line in the script
+    pub line: usize,
+}
+
+impl ParamAnnotation {
+    /// Value a parameter missing from the scene starts with: the annotated default,
+    /// else zero / false / empty clamped into the range
+    pub fn default_value(&self) -> ScriptParameter {
+        if let Some(default) = &self.default {
+            return default.clone();
+        }
+        let low = self.min.unwrap_or(0.0).max(0.0).min(self.max.unwrap_or(f64::MAX));
+        match self.kind {
+            ParamKind::Float => ScriptParameter::Float(low as f32),
+            ParamKind::Int => ScriptParameter::Int(low as i32),
+            ParamKind::Bool => ScriptParameter::Bool(false),
+            ParamKind::String => ScriptParameter::String(String::new()),
+            ParamKind::Entity => ScriptParameter::Entity(None),
+            ParamKind::Curve => ScriptParameter::Curve(AnimationCurve::linear()),
+        }
+    }
+
+    /// A scene value as this parameter's type (Int and Float convert into each other),
+    /// None when it can't be kept
+    pub fn coerce(&self, value: &ScriptParameter) -> Option<ScriptParameter> {
+        match (self.kind, value) {
+            (ParamKind::Float, ScriptParameter::Int(value)) => Some(ScriptParameter::Float(*value as f32)),
+            (ParamKind::Int, ScriptParameter::Float(value)) => Some(ScriptParameter::Int(value.round() as i32)),
+            (kind, value) if kind.matches(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+
+    /// Both ends of the range, when the inspector should draw a slider
+    pub fn range(&self) -> Option<(f64, f64)> {
+        Some((self.min?, self.max?))
+    }
+}
+
+/// Malformed annotation
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnnotationError {
+    /// 1-based line in the script
+    pub line: usize,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScriptAnnotations {
+    /// In the order they appear in the script
+    pub params: Vec<ParamAnnotation>,
+    pub errors: Vec<AnnotationError>,
+}
+
+impl ScriptAnnotations {
+    pub fn get(&self, name: &str) -> Option<&ParamAnnotation> {
+        self.params.iter().find(|param| param.name == name)
+    }
+
+    /// Parse every `--@param` line of a script (`-- @param`, tabs and spaces around `=`
+    /// are fine)
+    pub fn parse(source: &str) -> Self {
+        let mut annotations = ScriptAnnotations::default();
+        for (index, line) in source.lines().enumerate() {
+            let line_number = index + 1;
+            let Some(comment) = line.trim_start().strip_prefix("--") else {
+                continue;
+            };
+            let Some(rest) = comment.trim_start().strip_prefix("@param") else {
+                continue;
+            };
+            if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+                continue; // "@parameters" and the like are someone else's
+            }
+            match parse_param(rest) {
+                Ok(mut param) if annotations.get(&param.name).is_none() => {
+                    param.line = line_number;
+                    annotations.params.push(param);
+                }
+                Ok(param) => annotations.errors.push(AnnotationError {
+                    line: line_number,
+                    message: format!("'{}' is already annotated", param.name),
+                }),
+                Err(message) => annotations.errors.push(AnnotationError { line: line_number, message }),
+            }
+        }
+        annotations
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Word(String),
+    Quoted(String),
+    Equals,
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '=' {
+            chars.next();
+            tokens.push(Token::Equals);
+        } else if c == '"' || c == '\'' {
+            chars.next();
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some(ch) if ch == c => break,
+                    Some('\\') => value.extend(chars.next()),
+                    Some(ch) => value.push(ch),
+                    None => return Err("unterminated quote".to_string()),
+                }
+            }
+            tokens.push(Token::Quoted(value));
+        } else {
+            let mut word = String::new();
+            while let Some(&ch) = chars.peek() {
+                if ch.is_whitespace() || ch == '=' || ch == '"' || ch == '\'' {
+                    break;
+                }
+                word.push(ch);
+                chars.next();
+            }
+            tokens.push(Token::Word(word));
+        }
+    }
+    Ok(tokens)
+}
+
+/// `name Type key=value ...` after `@param`
+fn parse_param(text: &str) -> Result<ParamAnnotation, String> {
+    let mut tokens = tokenize(text)?.into_iter();
+    let name = match tokens.next() {
+        Some(Token::Word(name)) if name.chars().all(|c| c.is_alphanumeric() || c == '_') => name,
+        Some(Token::Word(name)) => return Err(format!("'{}' is not a parameter name", name)),
+        _ => return Err("expected '@param <name> <Type>'".to_string()),
+    };
+    let kind = match tokens.next() {
+        Some(Token::Word(kind)) => ParamKind::from_name(&kind).ok_or_else(|| {
+            format!("unknown type '{}' (Float, Int, Bool, String, Entity or Curve)", kind)
+        })?,
+        _ => return Err(format!("missing type for '{}'", name)),
+    };
+
+    let mut param = ParamAnnotation { name, kind, min: None, max: None, default: None, tooltip: None, line: 0 };
+    while let Some(token) = tokens.next() {
+        let Token::Word(key) = token else {
+            return Err("expected key=value".to_string());
+        };
+        if tokens.next() != Some(Token::Equals) {
+            return Err(format!("expected '=' after '{}'", key));
+        }
+        let value = match tokens.next() {
+            Some(Token::Word(value)) | Some(Token::Quoted(value)) => value,
+            _ => return Err(format!("missing value for '{}'", key)),
+        };
+        match key.as_str() {
+            "min" | "max" => {
+                if !kind.is_numeric() {
+                    return Err(format!("'{}' only applies to Float and Int parameters", key));
+                }
+                let bound = value.parse::<f64>().map_err(|_| format!("{} '{}' is not a number", key, value))?;
+                if key == "min" {
+                    param.min = Some(bound);
+                } else {
+                    param.max = Some(bound);
+                }
+            }
+            "default" => param.default = Some(parse_default(kind, &value)?),
+            "tooltip" => param.tooltip = Some(value),
+            _ => return Err(format!("unknown key '{}' (min, max, default or tooltip)", key)),
+        }
+    }
+
+    if let (Some(min), Some(max)) = (param.min, param.max) {
+        if min > max {
+            return Err(format!("min {} is greater than max {}", min, max));
+        }
+    }
+    if let Some(default) = &param.default {
+        let value = match default {
+            ScriptParameter::Float(value) => Some(*value as f64),
+            ScriptParameter::Int(value) => Some(*value as f64),
+            _ => None,
+        };
+        if value.is_some_and(|value| param.min.is_some_and(|min| value < min) || param.max.is_some_and(|max| value > max)) {
+            return Err("default is outside min..max".to_string());
+        }
+    }
+    Ok(param)
+}
+
+fn parse_default(kind: ParamKind, value: &str) -> Result<ScriptParameter, String> {
+    let invalid = || format!("default '{}' is not a valid {:?}", value, kind);
+    match kind {
+        ParamKind::Float => value.parse().map(ScriptParameter::Float).map_err(|_| invalid()),
+        ParamKind::Int => value.parse().map(ScriptParameter::Int).map_err(|_| invalid()),
+        ParamKind::Bool => value.parse().map(ScriptParameter::Bool).map_err(|_| invalid()),
+        ParamKind::String => Ok(ScriptParameter::String(value.to_string())),
+        ParamKind::Curve => EasingFunction::from_name(value)
+            .map(|easing| ScriptParameter::Curve(easing.to_curve()))
+            .ok_or_else(invalid),
+        ParamKind::Entity => Err("Entity parameters can't have a default".to_string()),
+    }
+}
+
+/// Annotations of a script file with its modified time when parsed
+type CacheEntry = (Option<SystemTime>, Arc<ScriptAnnotations>);
+
+/// Parsed annotations of each script file, until the file changes
+static CACHE: Mutex<Option<HashMap<PathBuf, CacheEntry>>> = Mutex::new(None);
+
+/// Annotations of a script file, parsed again only when it was modified. Malformed
+/// annotations are logged to the console (as `display_path:line:`) each time it is parsed.
+pub fn load(path: &Path, display_path: &str) -> Arc<ScriptAnnotations> {
+    let modified = std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+    let Ok(mut cache) = CACHE.lock() else {
+        return Arc::default();
+    };
+    let cache = cache.get_or_insert_with(HashMap::new);
+    if let Some((cached_modified, annotations)) = cache.get(path) {
+        if *cached_modified == modified {
+            return Arc::clone(annotations);
+        }
+    }
+
+    let annotations = Arc::new(ScriptAnnotations::parse(&std::fs::read_to_string(path).unwrap_or_default()));
+    for error in &annotations.errors {
+        log::warn!("{}:{}: malformed @param annotation: {}", display_path, error.line, error.message);
+    }
+    cache.insert(path.to_path_buf(), (modified, Arc::clone(&annotations)));
+    annotations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tolerates_whitespace() {
+        let annotations = ScriptAnnotations::parse(concat!(
+            "--@param speed Float min=0 max=500 default=200 tooltip=\"Movement speed in px/s\"\n",
+            "  --   @param\tlives  int  min = 1   max=9\n",
+            "-- @param target Entity\n",
+            "--@parameters are described below\n",
+            "local speed = 200\n",
+        ));
+        assert!(annotations.errors.is_empty(), "{:?}", annotations.errors);
+        assert_eq!(annotations.params.len(), 3);
+
+        let speed = annotations.get("speed").unwrap();
+        assert_eq!(speed.range(), Some((0.0, 500.0)));
+        assert_eq!(speed.default_value(), ScriptParameter::Float(200.0));
+        assert_eq!(speed.tooltip.as_deref(), Some("Movement speed in px/s"));
+
+        let lives = annotations.get("lives").unwrap();
+        assert_eq!((lives.kind, lives.line), (ParamKind::Int, 2));
+        assert_eq!(lives.default_value(), ScriptParameter::Int(1), "missing defaults start inside the range");
+        assert_eq!(annotations.get("target").unwrap().default_value(), ScriptParameter::Entity(None));
+        assert_eq!(speed.coerce(&ScriptParameter::Int(3)), Some(ScriptParameter::Float(3.0)));
+        assert_eq!(speed.coerce(&ScriptParameter::Bool(true)), None);
+    }
+
+    #[test]
+    fn test_malformed_annotations_report_their_line() {
+        let annotations = ScriptAnnotations::parse(concat!(
+            "--@param speed Floot\n",
+            "--@param jump Float min=abc\n",
+            "--@param name String tooltip=\"unterminated\n",
+            "--@param flag Bool min=0\n",
+            "--@param ok Float max=1 min=2\n",
+            "--@param\n",
+            "--@param fine Bool default=true\n",
+            "--@param fine Int\n",
+        ));
+        let lines: Vec<usize> = annotations.errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines, [1, 2, 3, 4, 5, 6, 8]);
+        assert!(annotations.errors[0].message.contains("Floot"));
+        assert_eq!(annotations.params.len(), 1);
+        assert_eq!(annotations.params[0].default_value(), ScriptParameter::Bool(true));
+    }
+}
@@ -1,105 +1,149 @@
-/// Keyboard shortcuts system for Unity-like editor
+/// Keyboard shortcuts for the Unity-like editor: key chords and the user's rebindings.
+/// Which command a chord runs is decided by the command registry (commands.rs).
 use winit::keyboard::KeyCode;
 use egui::Modifiers;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum EditorShortcut {
-    // Transform tools
-    ViewTool,      // Q
-    MoveTool,      // W
-    RotateTool,    // E
-    ScaleTool,     // R
-    RectTool,      // T
-    
-    // Frame & Focus
-    FrameSelected, // F
-    
-    // File operations
-    NewScene,      // Ctrl+N
-    OpenScene,     // Ctrl+O
-    SaveScene,     // Ctrl+S
-    SaveSceneAs,   // Ctrl+Shift+S
-    Exit,          // Ctrl+Q
-    
-    // Edit operations
-    Undo,          // Ctrl+Z
-    Redo,          // Ctrl+Y or Ctrl+Shift+Z
-    Copy,          // Ctrl+C
-    Paste,         // Ctrl+V
-    Duplicate,     // Ctrl+D
-    Delete,        // Delete
-    SelectAll,     // Ctrl+A
-    DeselectAll,   // Ctrl+Shift+A
-    
-    // Play controls
-    Play,          // Ctrl+P
-    Pause,         // Ctrl+Shift+P
-    Step,          // Ctrl+Alt+P
-    
-    // View
-    ToggleGrid,    // G
-    ToggleGizmos,  // Ctrl+G
-}
+/// Keys a shortcut can use, with the name shown in the UI and stored in shortcuts.json
+const KEY_NAMES: &[(KeyCode, &str)] = &[
+    (KeyCode::KeyA, "A"), (KeyCode::KeyB, "B"), (KeyCode::KeyC, "C"), (KeyCode::KeyD, "D"),
+    (KeyCode::KeyE, "E"), (KeyCode::KeyF, "F"), (KeyCode::KeyG, "G"), (KeyCode::KeyH, "H"),
+    (KeyCode::KeyI, "I"), (KeyCode::KeyJ, "J"), (KeyCode::KeyK, "K"), (KeyCode::KeyL, "L"),
+    (KeyCode::KeyM, "M"), (KeyCode::KeyN, "N"), (KeyCode::KeyO, "O"), (KeyCode::KeyP, "P"),
+    (KeyCode::KeyQ, "Q"), (KeyCode::KeyR, "R"), (KeyCode::KeyS, "S"), (KeyCode::KeyT, "T"),
+    (KeyCode::KeyU, "U"), (KeyCode::KeyV, "V"), (KeyCode::KeyW, "W"), (KeyCode::KeyX, "X"),
+    (KeyCode::KeyY, "Y"), (KeyCode::KeyZ, "Z"),
+    (KeyCode::Digit0, "0"), (KeyCode::Digit1, "1"), (KeyCode::Digit2, "2"), (KeyCode::Digit3, "3"),
+    (KeyCode::Digit4, "4"), (KeyCode::Digit5, "5"), (KeyCode::Digit6, "6"), (KeyCode::Digit7, "7"),
+    (KeyCode::Digit8, "8"), (KeyCode::Digit9, "9"),
+    (KeyCode::F1, "F1"), (KeyCode::F2, "F2"), (KeyCode::F3, "F3"), (KeyCode::F4, "F4"),
+    (KeyCode::F5, "F5"), (KeyCode::F6, "F6"), (KeyCode::F7, "F7"), (KeyCode::F8, "F8"),
+    (KeyCode::F9, "F9"), (KeyCode::F10, "F10"), (KeyCode::F11, "F11"), (KeyCode::F12, "F12"),
+    (KeyCode::Numpad0, "Num0"), (KeyCode::Numpad1, "Num1"), (KeyCode::Numpad2, "Num2"),
+    (KeyCode::Numpad3, "Num3"), (KeyCode::Numpad4, "Num4"), (KeyCode::Numpad5, "Num5"),
+    (KeyCode::Numpad6, "Num6"), (KeyCode::Numpad7, "Num7"), (KeyCode::Numpad8, "Num8"),
+    (KeyCode::Numpad9, "Num9"), (KeyCode::NumpadAdd, "Num+"), (KeyCode::NumpadSubtract, "Num-"),
+    (KeyCode::NumpadMultiply, "Num*"), (KeyCode::NumpadDivide, "Num/"),
+    (KeyCode::ArrowUp, "Up"), (KeyCode::ArrowDown, "Down"), (KeyCode::ArrowLeft, "Left"),
+    (KeyCode::ArrowRight, "Right"), (KeyCode::Home, "Home"), (KeyCode::End, "End"),
+    (KeyCode::PageUp, "PageUp"), (KeyCode::PageDown, "PageDown"), (KeyCode::Insert, "Insert"),
+    (KeyCode::Delete, "Delete"), (KeyCode::Backspace, "Backspace"), (KeyCode::Enter, "Enter"),
+    (KeyCode::Tab, "Tab"), (KeyCode::Space, "Space"),
+    (KeyCode::Minus, "-"), (KeyCode::Equal, "="), (KeyCode::BracketLeft, "["),
+    (KeyCode::BracketRight, "]"), (KeyCode::Backslash, "\\"), (KeyCode::Semicolon, ";"),
+    (KeyCode::Quote, "'"), (KeyCode::Comma, ","), (KeyCode::Period, "."), (KeyCode::Slash, "/"),
+    (KeyCode::Backquote, "`"),
+];
 
-pub struct ShortcutManager {
-    modifiers: Modifiers,
+/// A key plus the modifiers that must be held with it, e.g. Ctrl+Shift+P
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
 }
 
-impl ShortcutManager {
-    pub fn new() -> Self {
-        Self {
-            modifiers: Modifiers::default(),
+impl KeyChord {
+    pub const fn key(key: KeyCode) -> Self {
+        Self { key, ctrl: false, shift: false, alt: false }
+    }
+
+    pub const fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    pub const fn shift(mut self) -> Self {
+        self.shift = true;
+        self
+    }
+
+    pub const fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// Chord for a key press with the modifiers egui saw; None for keys shortcuts can't use
+    /// (modifier keys on their own, Escape, media keys)
+    pub fn from_key_press(key: KeyCode, modifiers: Modifiers) -> Option<Self> {
+        key_name(key)?;
+        Some(Self { key, ctrl: modifiers.ctrl, shift: modifiers.shift, alt: modifiers.alt })
+    }
+
+    /// Parse the `Display` form ("Ctrl+Shift+P")
+    pub fn parse(text: &str) -> Option<Self> {
+        // The key itself may be "+" ("Num+"), so split off modifiers from the front only
+        let mut chord_text = text.trim();
+        let mut chord = Self::key(KeyCode::Escape);
+        loop {
+            if let Some(rest) = chord_text.strip_prefix("Ctrl+") {
+                chord.ctrl = true;
+                chord_text = rest;
+            } else if let Some(rest) = chord_text.strip_prefix("Shift+") {
+                chord.shift = true;
+                chord_text = rest;
+            } else if let Some(rest) = chord_text.strip_prefix("Alt+") {
+                chord.alt = true;
+                chord_text = rest;
+            } else {
+                break;
+            }
         }
+        chord.key = KEY_NAMES.iter().find(|(_, name)| *name == chord_text)?.0;
+        Some(chord)
     }
-    
-    pub fn update_modifiers(&mut self, modifiers: Modifiers) {
-        self.modifiers = modifiers;
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        write!(f, "{}", key_name(self.key).unwrap_or("?"))
     }
-    
-    pub fn check_shortcut(&self, key: KeyCode) -> Option<EditorShortcut> {
-        let ctrl = self.modifiers.ctrl;
-        let shift = self.modifiers.shift;
-        let alt = self.modifiers.alt;
-        
-        match (key, ctrl, shift, alt) {
-            // Transform tools (no modifiers)
-            (KeyCode::KeyQ, false, false, false) => Some(EditorShortcut::ViewTool),
-            (KeyCode::KeyW, false, false, false) => Some(EditorShortcut::MoveTool),
-            (KeyCode::KeyE, false, false, false) => Some(EditorShortcut::RotateTool),
-            (KeyCode::KeyR, false, false, false) => Some(EditorShortcut::ScaleTool),
-            (KeyCode::KeyT, false, false, false) => Some(EditorShortcut::RectTool),
-            
-            // Frame selected
-            (KeyCode::KeyF, false, false, false) => Some(EditorShortcut::FrameSelected),
-            
-            // File operations
-            (KeyCode::KeyN, true, false, false) => Some(EditorShortcut::NewScene),
-            (KeyCode::KeyO, true, false, false) => Some(EditorShortcut::OpenScene),
-            (KeyCode::KeyS, true, false, false) => Some(EditorShortcut::SaveScene),
-            (KeyCode::KeyS, true, true, false) => Some(EditorShortcut::SaveSceneAs),
-            (KeyCode::KeyQ, true, false, false) => Some(EditorShortcut::Exit),
-            
-            // Edit operations
-            (KeyCode::KeyZ, true, false, false) => Some(EditorShortcut::Undo),
-            (KeyCode::KeyY, true, false, false) => Some(EditorShortcut::Redo),
-            (KeyCode::KeyZ, true, true, false) => Some(EditorShortcut::Redo),
-            (KeyCode::KeyC, true, false, false) => Some(EditorShortcut::Copy),
-            (KeyCode::KeyV, true, false, false) => Some(EditorShortcut::Paste),
-            (KeyCode::KeyD, true, false, false) => Some(EditorShortcut::Duplicate),
-            (KeyCode::Delete, false, false, false) => Some(EditorShortcut::Delete),
-            (KeyCode::KeyA, true, false, false) => Some(EditorShortcut::SelectAll),
-            (KeyCode::KeyA, true, true, false) => Some(EditorShortcut::DeselectAll),
-            
-            // Play controls
-            (KeyCode::KeyP, true, false, false) => Some(EditorShortcut::Play),
-            (KeyCode::KeyP, true, true, false) => Some(EditorShortcut::Pause),
-            (KeyCode::KeyP, true, false, true) => Some(EditorShortcut::Step),
-            
-            // View
-            (KeyCode::KeyG, false, false, false) => Some(EditorShortcut::ToggleGrid),
-            (KeyCode::KeyG, true, false, false) => Some(EditorShortcut::ToggleGizmos),
-            
-            _ => None,
+}
+
+fn key_name(key: KeyCode) -> Option<&'static str> {
+    KEY_NAMES.iter().find(|(code, _)| *code == key).map(|(_, name)| *name)
+}
+
+/// Shortcuts the user rebound (command id -> chords, empty = unbound).
+/// Saved per user next to the snap settings, not in the project.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ShortcutSettings {
+    #[serde(default)]
+    pub bindings: BTreeMap<String, Vec<String>>,
+}
+
+impl ShortcutSettings {
+    /// Load from file
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = std::path::Path::new(".kiro/settings/shortcuts.json");
+        if path.exists() {
+            let contents = std::fs::read_to_string(path)?;
+            Ok(serde_json::from_str(&contents)?)
+        } else {
+            Ok(Self::default())
         }
     }
+
+    /// Save to file
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::path::Path::new(".kiro/settings");
+        std::fs::create_dir_all(dir)?;
+
+        let path = dir.join("shortcuts.json");
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
 }
@@ -25,11 +25,28 @@ pub enum EditorAction {
     DeleteEntity(ecs::Entity),
 }
 
+/// Actions on the whole selection, requested from the hierarchy context menu
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelectionAction {
+    Delete,
+    Duplicate,
+    Copy,
+    Paste,
+    ParentTo(ecs::Entity),
+    Unparent,
+    /// Under `parent` (None: the scene root), in front of `before` (None: last)
+    MoveTo { parent: Option<ecs::Entity>, before: Option<ecs::Entity> },
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct BuildParams {
     pub output_path: PathBuf,
     pub game_name: String,
     pub target_platform: String,
+    pub pack_format: engine::export::PackFormat,
+    pub window_width: u32,
+    pub window_height: u32,
+    pub fullscreen: bool,
     pub is_building: bool,
     pub build_output: String,
     pub build_error: Option<String>,
@@ -40,7 +57,11 @@ impl Default for BuildParams {
         Self {
             output_path: std::env::current_dir().unwrap_or_default().join("build"),
             game_name: "RustGame".to_string(),
-            target_platform: "windows".to_string(),
+            target_platform: std::env::consts::OS.to_string(),
+            pack_format: engine::export::PackFormat::Directory,
+            window_width: 1280,
+            window_height: 720,
+            fullscreen: false,
             is_building: false,
             build_output: String::new(),
             build_error: None,
@@ -79,16 +100,28 @@ pub struct EditorState {
     pub entity_names: HashMap<Entity, String>,
     pub current_scene_path: Option<PathBuf>,
     pub current_project_path: Option<PathBuf>,
+    pub project_settings: engine_core::project_settings::ProjectSettings,  // settings.json of the open project
     pub scene_modified: bool,
     pub show_save_required_dialog: bool,
     pub scene_view_tab: usize,
     pub is_playing: bool,
+    pub is_paused: bool,           // Play mode paused (simulation frozen, editor still live)
+    pub step_requested: bool,      // Advance exactly one fixed timestep while paused
+    pub play_frame: u64,           // Simulated frames since entering play mode
+    pub play_changes: super::systems::play_mode_changes::PlayModeChangeTracker,  // User edits made during play
+    pub pending_play_changes: Vec<super::systems::play_mode_changes::RuntimeChange>,  // Offered in the dialog after Stop
     pub play_world: Option<World>,
+    pub edit_scene_settings: Option<ecs::SceneSettings>,  // Scene Settings when Play started, restored on Stop
+    pub scene_preload: Option<engine::assets::server::PreloadTracker>,  // Scene preload list, held while playing
+    pub waiting_for_preload: bool,  // Play requested, waiting on scene_preload
+    pub replay: super::systems::replay::EditorReplay,  // Input replay recording / playback
+    pub asset_server: Option<(PathBuf, std::sync::Arc<engine::assets::server::AssetServer>)>,  // Per-project async loader
     pub keyboard_state: HashMap<String, bool>,
     pub input_system: input::InputSystem,
     pub show_colliders: bool,
     pub show_velocities: bool,
     pub show_debug_lines: bool,  // Show debug draw lines (raycasts, etc.)
+    pub show_navigation: bool,  // Show the navigation grid and last Nav.find_path result
     pub console: super::console::Console,
     pub bottom_panel_tab: usize,
     pub show_project_settings: bool,
@@ -107,14 +140,21 @@ pub struct EditorState {
     pub build_receiver: Option<Receiver<String>>, // Channel for build updates
     
     // NEW: Unity-like editor features
-    pub shortcut_manager: super::shortcuts::ShortcutManager,
+    pub commands: super::commands::CommandRegistry,  // Editor commands and their (rebindable) shortcuts
+    pub command_palette: super::ui::command_palette::CommandPalette,  // Ctrl+Shift+P command search
+    pub shortcut_preferences: super::ui::shortcut_preferences::ShortcutPreferences,  // Preferences > Shortcuts window
     pub scene_camera: super::SceneCamera,
     pub scene_grid: super::grid::SceneGrid,
     pub infinite_grid: super::grid::InfiniteGrid,  // Enhanced infinite grid for 3D mode
     pub camera_state_display: super::ui::camera_settings::CameraStateDisplay,  // Camera state display
     pub selected_entities: Vec<Entity>,  // Multi-selection support
-    pub hierarchy_search: String,        // Search filter
+    pub hierarchy_search: super::tools::hierarchy_search::HierarchySearch,  // Hierarchy search filter
     pub autosave: super::autosave::AutoSave,  // Auto-save system
+    pub session_project: Option<PathBuf>,     // Project the session journal is being kept for
+    pub session_journal: Option<super::autosave::SessionJournal>,  // Last journal written
+    pub pending_recovery: Option<(PathBuf, PathBuf)>,  // (scene, autosave) offered after a crash
+    pub scene_watch: super::scene_watch::SceneFileWatch,  // Open scene file changed outside the editor
+    pub scene_reload_prompt: Option<super::scene_watch::SceneReloadPrompt>,  // Reload / Keep Mine / Show Diff
     pub show_exit_dialog: bool,          // Exit confirmation dialog
     pub should_exit: bool,               // Flag to trigger actual exit
     pub asset_manager: Option<super::asset_manager::AssetManager>,  // Asset manager
@@ -128,19 +168,31 @@ pub struct EditorState {
     pub save_layout_name: String,        // Name for saving layout
     pub dragging_entity: Option<Entity>, // Entity being dragged
     pub drag_axis: Option<u8>,           // Drag axis: 0=X, 1=Y, 2=Both
+    pub handle_drag: Option<super::ui::scene_view::interaction::handles::HandleDrag>, // Collider/camera handle being dragged
     pub scene_view_mode: super::ui::scene_view::SceneViewMode, // 2D or 3D mode
     pub projection_mode: super::ui::scene_view::SceneProjectionMode, // Isometric or Perspective
     pub transform_space: super::ui::scene_view::TransformSpace, // Local or World space
     pub texture_manager: engine::texture_manager::TextureManager, // Texture manager for sprites
     pub undo_stack: super::UndoStack,  // Undo/Redo system
+    pub edit_recorder: super::systems::component_edits::ComponentEditRecorder,  // Inspector/gizmo edits -> undo steps
     pub selection: super::SelectionManager,  // Multi-selection system
     pub clipboard: super::Clipboard,  // Copy/Paste/Duplicate system
+    pub component_clipboard: super::systems::component_clipboard::ComponentClipboard,  // Inspector Copy/Paste Component
     pub snap_settings: super::tools::snapping::SnapSettings,  // Snap to Grid system
     pub sprite_editor_windows: Vec<super::SpriteEditorWindow>,  // Open sprite editor windows
     pub open_sprite_editor_request: Option<PathBuf>,  // Request to open sprite editor for a texture
     pub open_prefab_editor_request: Option<PathBuf>,  // Request to open prefab editor for a UI prefab
     pub sprite_picker_state: super::ui::sprite_picker::SpritePickerState,  // Sprite picker popup state
+    pub animation_window: super::ui::animation_window::AnimationWindow,  // Animation clip editor window
+    pub tile_palette: super::ui::tile_palette::TilePalette,  // Tile painting palette window
+    pub memory_panel: super::ui::memory_panel::MemoryPanel,  // Memory window and play-mode leak report
+    pub globals_panel: super::ui::globals_panel::GlobalsPanel,  // Lua Globals store while playing
+    pub asset_references: super::assets::references::AssetReferenceIndex,  // Broken asset references and "used by" counts
+    pub asset_validation: super::ui::asset_validation::AssetValidationWindow,  // Validate Assets report window
+    pub script_debugger: super::ui::script_debugger::ScriptDebugger,  // Lua breakpoints, call stack and variables
+    pub scene_manager: engine::runtime::SceneManager,  // Scenes loaded by scripts during play (Scene.load)
     pub texture_inspector: super::ui::texture_inspector::TextureInspector,  // Texture import settings inspector
+    pub ui_inspector: super::ui::ui_inspector::UIInspector,  // Selected UI element (Canvas hierarchy)
     pub map_view_state: super::ui::map_view::MapViewState,  // Map view panel state
     pub debug_draw: super::debug_draw::DebugDrawManager,  // Debug draw system (Unity/Unreal style)
     pub map_manager: super::map_manager::MapManager,  // Map manager for LDtk files
@@ -151,9 +203,12 @@ pub struct EditorState {
     pub performance_panel: super::ui::panels::performance_panel::PerformancePanel,  // Performance monitoring panel for tilemap management
     pub collider_settings_panel: super::ui::panels::collider_settings_panel::ColliderSettingsPanel,  // Collider configuration panel for tilemap colliders
     pub game_view_settings: engine::runtime::GameViewSettings,  // Game view resolution and display settings
+    pub script_budget: profiler::scripts::ScriptBudget,  // Script time budget warnings while playing
     pub prefab_editor: super::widget_editor::PrefabEditor,  // Visual UI prefab editor (Unity-style)
     pub ui_manager: engine::ui_manager::UIManager,  // New UI system manager
     pub reload_mesh_assets_request: bool,  // Flag to request reloading mesh assets
+    pub component_registry: ecs::ComponentRegistry,  // Custom component schemas (scripts/components.lua) for the inspector
+    pub component_definitions_modified: Option<Option<std::time::SystemTime>>,  // components.lua when last read (None = read again)
 }
 
 #[allow(dead_code)]
@@ -170,16 +225,28 @@ impl EditorState {
             entity_names: HashMap::new(),
             current_scene_path: None,
             current_project_path: None,
+            project_settings: engine_core::project_settings::ProjectSettings::default(),
             scene_modified: false,
             show_save_required_dialog: false,
             scene_view_tab: 0,
             is_playing: false,
+            is_paused: false,
+            step_requested: false,
+            play_frame: 0,
+            play_changes: super::systems::play_mode_changes::PlayModeChangeTracker::new(),
+            pending_play_changes: Vec::new(),
             play_world: None,
+            edit_scene_settings: None,
+            scene_preload: None,
+            waiting_for_preload: false,
+            replay: super::systems::replay::EditorReplay::default(),
+            asset_server: None,
             keyboard_state: HashMap::new(),
             input_system: input::InputSystem::new(),
             show_colliders: true,
             show_velocities: false,
             show_debug_lines: true,  // Show debug lines by default
+            show_navigation: false,
             console,
             bottom_panel_tab: 1,  // Default to Console tab to show logs
             show_project_settings: false,
@@ -198,14 +265,21 @@ impl EditorState {
             build_receiver: None,
             
             // NEW: Initialize Unity-like features
-            shortcut_manager: super::shortcuts::ShortcutManager::new(),
+            commands: super::commands::CommandRegistry::new(),
+            command_palette: super::ui::command_palette::CommandPalette::default(),
+            shortcut_preferences: super::ui::shortcut_preferences::ShortcutPreferences::default(),
             scene_camera: super::SceneCamera::new(),
             scene_grid: super::grid::SceneGrid::new(),
             infinite_grid: super::grid::InfiniteGrid::new(),
             camera_state_display: super::ui::camera_settings::CameraStateDisplay::new(),
             selected_entities: Vec::new(),
-            hierarchy_search: String::new(),
-            autosave: super::autosave::AutoSave::new(300), // 5 minutes
+            hierarchy_search: super::tools::hierarchy_search::HierarchySearch::new(),
+            autosave: super::autosave::AutoSave::new(120), // 2 minutes (project settings override)
+            session_project: None,
+            session_journal: None,
+            pending_recovery: None,
+            scene_watch: super::scene_watch::SceneFileWatch::default(),
+            scene_reload_prompt: None,
             show_exit_dialog: false,
             should_exit: false,
             asset_manager: None, // Initialized when project is opened
@@ -219,19 +293,31 @@ impl EditorState {
             save_layout_name: String::new(),
             dragging_entity: None,
             drag_axis: None,
+            handle_drag: None,
             scene_view_mode: super::ui::scene_view::SceneViewMode::Mode2D,
             projection_mode: super::ui::scene_view::SceneProjectionMode::Perspective, // Unity-style default
             transform_space: super::ui::scene_view::TransformSpace::Local,
             undo_stack: super::UndoStack::new(),
+            edit_recorder: super::systems::component_edits::ComponentEditRecorder::new(),
             selection: super::SelectionManager::new(),
             clipboard: super::Clipboard::new(),
+            component_clipboard: super::systems::component_clipboard::ComponentClipboard::new(),
             snap_settings: super::tools::snapping::SnapSettings::load().unwrap_or_default(),
             texture_manager: engine::texture_manager::TextureManager::new(),
             sprite_editor_windows: Vec::new(),
             open_sprite_editor_request: None,
             open_prefab_editor_request: None,
             sprite_picker_state: super::ui::sprite_picker::SpritePickerState::new(),
+            animation_window: super::ui::animation_window::AnimationWindow::default(),
+            tile_palette: super::ui::tile_palette::TilePalette::default(),
+            memory_panel: super::ui::memory_panel::MemoryPanel::default(),
+            globals_panel: super::ui::globals_panel::GlobalsPanel::default(),
+            asset_references: super::assets::references::AssetReferenceIndex::default(),
+            asset_validation: super::ui::asset_validation::AssetValidationWindow::default(),
+            script_debugger: super::ui::script_debugger::ScriptDebugger::default(),
+            scene_manager: engine::runtime::SceneManager::new(),
             texture_inspector: super::ui::texture_inspector::TextureInspector::default(),
+            ui_inspector: super::ui::ui_inspector::UIInspector::default(),
             map_view_state: super::ui::map_view::MapViewState::default(),
             debug_draw: super::debug_draw::DebugDrawManager::new(),
             map_manager: super::map_manager::MapManager::new(),
@@ -242,9 +328,12 @@ impl EditorState {
             performance_panel: super::ui::panels::performance_panel::PerformancePanel::new(),
             collider_settings_panel: super::ui::panels::collider_settings_panel::ColliderSettingsPanel::new(),
             game_view_settings: engine::runtime::GameViewSettings::default(),
+            script_budget: profiler::scripts::ScriptBudget::default(),
             prefab_editor: super::widget_editor::PrefabEditor::new(),
             ui_manager: engine::ui_manager::UIManager::new(),
             reload_mesh_assets_request: false,
+            component_registry: ecs::ComponentRegistry::new(),
+            component_definitions_modified: None,
         }
     }
 
@@ -255,6 +344,7 @@ impl EditorState {
     /// Set the current project path and update related components
     pub fn set_project_path(&mut self, path: PathBuf) {
         self.current_project_path = Some(path.clone());
+        self.project_settings = engine_core::project_settings::ProjectSettings::load(&path);
         
         // Set texture manager base path to project assets folder
         self.texture_manager.set_base_path(path.join("assets"));
@@ -266,9 +356,404 @@ impl EditorState {
         
         // Request asset reload when project changes
         self.reload_mesh_assets_request = true;
+        self.component_definitions_modified = None;
+
+        self.load_editor_layout();
+    }
+
+    /// Async asset server rooted at the current project (recreated when the project changes)
+    pub fn project_asset_server(&mut self) -> Option<std::sync::Arc<engine::assets::server::AssetServer>> {
+        let project_path = self.current_project_path.clone()?;
+        match &self.asset_server {
+            Some((path, server)) if *path == project_path => Some(server.clone()),
+            _ => {
+                let loader = std::sync::Arc::new(engine::assets::native_loader::NativeAssetLoader::new(&project_path));
+                let server = std::sync::Arc::new(engine::assets::server::AssetServer::new(loader));
+                self.asset_server = Some((project_path, server.clone()));
+                Some(server)
+            }
+        }
+    }
+
+    /// Start or poll the scene's preload list before entering play mode.
+    /// Returns true while Play has to keep waiting.
+    pub fn wait_for_scene_preload(&mut self) -> bool {
+        if !self.waiting_for_preload {
+            if self.world.preload_assets.is_empty() {
+                return false;
+            }
+            let Some(server) = self.project_asset_server() else {
+                return false;
+            };
+            self.scene_preload = Some(server.preload(&self.world.preload_assets));
+            self.waiting_for_preload = true;
+            self.console.info(format!("⏳ Preloading {} scene asset(s)...", self.world.preload_assets.len()));
+        }
+
+        let Some(preload) = &self.scene_preload else {
+            self.waiting_for_preload = false;
+            return false;
+        };
+        if !preload.is_done() {
+            return true;
+        }
+
+        for (path, error) in preload.failures() {
+            self.console.warning(format!("Preload failed for '{}': {}", path, error));
+        }
+        self.waiting_for_preload = false;
+        false
+    }
+
+    /// Abandon a pending preload (Play is not started)
+    pub fn cancel_scene_preload(&mut self) {
+        self.waiting_for_preload = false;
+        self.scene_preload = None;
+        // A cancelled Record / Play Replay shouldn't apply to the next Play
+        self.replay.record_request = false;
+        self.replay.pending_playback = None;
+    }
+
+    /// Toggle play-mode pause (no-op outside play mode)
+    pub fn toggle_pause(&mut self) {
+        if !self.is_playing {
+            return;
+        }
+        self.is_paused = !self.is_paused;
+        self.step_requested = false;
+        if self.is_paused {
+            self.console.info(format!("⏸ Paused at frame {}", self.play_frame));
+        } else {
+            self.console.info(format!("▶ Resumed at frame {}", self.play_frame));
+        }
+    }
+
+    /// Request a single fixed-timestep frame while paused
+    pub fn request_step(&mut self) {
+        if self.is_playing && self.is_paused {
+            self.step_requested = true;
+        }
+    }
+
+    // ========================================================================
+    // MULTI-SELECTION
+    // ========================================================================
+
+    /// All selected entities including the primary one, in a stable order
+    pub fn current_selection(&self) -> Vec<Entity> {
+        let mut entities = self.selection.get_selected();
+        if let Some(primary) = self.selected_entity {
+            if !entities.contains(&primary) {
+                entities.push(primary);
+            }
+        }
+        entities.sort();
+        entities
+    }
+
+    /// Replace the selection; the last entity becomes the primary selection
+    pub fn set_selection(&mut self, entities: &[Entity]) {
+        if entities.is_empty() {
+            self.selection.clear();
+            self.selected_entity = None;
+        } else {
+            self.selection.select_multiple(entities, super::SelectionMode::Replace);
+            self.selected_entity = entities.last().copied();
+        }
+    }
+
+    /// Keep `selection` and `selected_entity` consistent after UI code changed either one.
+    /// Panels that only know about `selected_entity` replace the selection with it.
+    pub fn sync_selection(&mut self, previous_primary: Option<Entity>) {
+        self.selection.retain_existing(&self.world);
+        if self.selected_entity.is_some_and(|e| !self.world.transforms.contains_key(&e)) {
+            self.selected_entity = None;
+        }
+
+        if self.selected_entity != previous_primary {
+            match self.selected_entity {
+                Some(entity) if !self.selection.is_selected(entity) => {
+                    self.selection.select(entity, super::SelectionMode::Replace);
+                }
+                None => self.selection.clear(),
+                _ => {}
+            }
+        } else if let Some(entity) = self.selected_entity {
+            if !self.selection.is_selected(entity) {
+                // Primary was toggled out of the selection (Ctrl+Click)
+                self.selected_entity = None;
+            }
+        }
+
+        if self.selected_entity.is_none() && self.selection.has_selection() {
+            self.selected_entity = self
+                .selection
+                .get_last()
+                .or_else(|| self.selection.get_first());
+        }
+    }
+
+    /// Select every entity in the scene
+    pub fn select_all_entities(&mut self) {
+        let mut entities: Vec<Entity> = self.world.transforms.keys().copied().collect();
+        entities.sort();
+        self.set_selection(&entities);
+    }
+
+    pub fn apply_selection_action(&mut self, action: SelectionAction) {
+        match action {
+            SelectionAction::Delete => self.delete_selection(),
+            SelectionAction::Duplicate => self.duplicate_selection(),
+            SelectionAction::Copy => self.copy_selection(),
+            SelectionAction::Paste => self.paste_clipboard(),
+            SelectionAction::ParentTo(parent) => self.parent_selection_to(parent),
+            SelectionAction::Unparent => self.unparent_selection(),
+            SelectionAction::MoveTo { parent, before } => self.move_selection_to(parent, before),
+        }
+    }
+
+    /// Delete the selection (and children) as one undo step
+    pub fn delete_selection(&mut self) {
+        let selected = self.current_selection();
+        if selected.is_empty() {
+            return;
+        }
+
+        let entities = super::tools::selection::with_descendants(&selected, &self.world);
+        let mut batch = super::BatchCommand::new(self.batch_description("Delete", &selected));
+        for &entity in &entities {
+            batch.add(Box::new(super::DeleteEntityCommand::new(entity, &self.world, &self.entity_names)));
+        }
+        self.undo_stack.execute(Box::new(batch), &mut self.world, &mut self.entity_names);
+
+        self.set_selection(&[]);
+        self.scene_modified = true;
+        self.console.info(format!("Deleted {} entities", entities.len()));
+    }
+
+    /// Duplicate the selection (and children) in place as one undo step
+    pub fn duplicate_selection(&mut self) {
+        let selected = self.current_selection();
+        if selected.is_empty() {
+            return;
+        }
+
+        // Copies keep every component and sit next to the originals in the hierarchy
+        let roots = super::tools::selection::top_level_selected(&selected, &self.world);
+        let description = self.batch_description("Duplicate", &roots);
+        let command = super::DuplicateEntitiesCommand::new(description, &roots, &mut self.world, &mut self.entity_names);
+        let new_roots = command.roots();
+        self.undo_stack.execute(Box::new(command), &mut self.world, &mut self.entity_names);
+        self.scene_modified = true;
+        self.set_selection(&new_roots);
+        self.console.info(format!("Duplicated {} entities (Ctrl+D)", new_roots.len()));
+    }
+
+    /// Copy the selection (and children) to the editor clipboard
+    pub fn copy_selection(&mut self) {
+        let selected = self.current_selection();
+        if selected.is_empty() {
+            return;
+        }
+
+        let entities = super::tools::selection::with_descendants(&selected, &self.world);
+        self.clipboard.copy_entities(&entities, &self.world, &self.entity_names);
+        self.console.info(format!("Copied {} entities (Ctrl+C)", entities.len()));
+    }
+
+    /// Paste the clipboard as one undo step and select the pasted roots
+    pub fn paste_clipboard(&mut self) {
+        let new_entities = self.clipboard.paste(
+            &mut self.world,
+            &mut self.entity_names,
+            Some([10.0, 10.0, 0.0]), // Offset by 10 pixels
+        );
+        if new_entities.is_empty() {
+            return;
+        }
+
+        let new_roots: Vec<Entity> = new_entities
+            .iter()
+            .copied()
+            .filter(|e| !self.world.parents.contains_key(e))
+            .collect();
+        let description = self.batch_description("Paste", &new_roots);
+        self.record_created_entities(description, &new_entities);
+        self.set_selection(&new_roots);
+        self.console.info(format!("Pasted {} entities (Ctrl+V)", new_roots.len()));
+    }
+
+    /// Copy the selection to the editor clipboard, then delete it as one undo step
+    pub fn cut_selection(&mut self) {
+        self.copy_selection();
+        self.delete_selection();
+    }
+
+    /// Run a Copy / Paste picked in an inspector component menu. Pastes go to every
+    /// selected entity as one undo step (in play mode they apply directly).
+    pub fn apply_component_menu_action(&mut self, action: super::systems::component_clipboard::ComponentMenuAction) {
+        use super::systems::component_clipboard::{paste_component, ComponentMenuAction, CopiedComponent};
+
+        match action {
+            ComponentMenuAction::Copy(component) => {
+                let Some(entity) = self.selected_entity else {
+                    return;
+                };
+                match CopiedComponent::capture(&self.world, &self.entity_names, entity, component) {
+                    Some(copied) => {
+                        self.component_clipboard.copy(copied);
+                        self.console.info(format!("Copied {}", component.label()));
+                    }
+                    None => self.console.warning(format!("Nothing to copy: the entity has no {}", component.label())),
+                }
+            }
+            ComponentMenuAction::Paste(component, mode) => {
+                let targets = self.current_selection();
+                let result = self.component_clipboard.contents().and_then(|copied| {
+                    paste_component(&copied, component, mode, &targets, &self.world, &self.entity_names)
+                });
+                match result {
+                    Ok(mut batch) => {
+                        let count = batch.len();
+                        if self.is_playing {
+                            super::systems::undo::Command::execute(&mut batch, &mut self.world, &mut self.entity_names);
+                        } else {
+                            self.undo_stack.execute(Box::new(batch), &mut self.world, &mut self.entity_names);
+                            self.scene_modified = true;
+                        }
+                        self.console.info(format!(
+                            "Pasted {} onto {} entit{}", component.label(), count, if count == 1 { "y" } else { "ies" }
+                        ));
+                    }
+                    Err(e) => self.console.error(format!("Paste Component failed: {}", e)),
+                }
+            }
+        }
+    }
+
+    /// Record a just-spawned entity (and its children) as one "Create" undo step and select it
+    pub fn record_spawned(&mut self, root: Entity) {
+        let entities = super::tools::selection::with_descendants(&[root], &self.world);
+        let description = self.batch_description("Create", &[root]);
+        self.record_created_entities(description, &entities);
+        self.set_selection(&[root]);
+    }
+
+    /// Instantiate a prefab file at the scene root and select it
+    pub fn instantiate_prefab_file(&mut self, path: &std::path::Path) -> Result<Entity, String> {
+        let root = super::prefab::Prefab::load(path)?.instantiate(&mut self.world, &mut self.entity_names, None)?;
+        self.record_spawned(root);
+        Ok(root)
+    }
+
+    /// Make `parent` the parent of every selected entity as one undo step
+    pub fn parent_selection_to(&mut self, parent: Entity) {
+        let selected = self.current_selection();
+        let roots = super::tools::selection::top_level_selected(&selected, &self.world);
+
+        let mut batch = super::BatchCommand::new(format!(
+            "Parent to {}",
+            self.entity_names.get(&parent).cloned().unwrap_or_else(|| format!("Entity {}", parent))
+        ));
+        for entity in roots {
+            let old_parent = self.world.get_parent(entity);
+            // Can't parent to itself, a descendant, or the current parent
+            if self.world.check_parent(entity, Some(parent)).is_err() || old_parent == Some(parent) {
+                continue;
+            }
+            batch.add(Box::new(super::SetParentCommand::new(entity, old_parent, Some(parent))));
+        }
+
+        if batch.is_empty() {
+            self.console.warning("Nothing to parent".to_string());
+            return;
+        }
+        let count = batch.len();
+        self.undo_stack.execute(Box::new(batch), &mut self.world, &mut self.entity_names);
+        self.scene_modified = true;
+        self.console.info(format!("Parented {} entities", count));
+    }
+
+    /// Move every selected entity to the scene root as one undo step
+    pub fn unparent_selection(&mut self) {
+        let mut batch = super::BatchCommand::new("Unparent");
+        for entity in self.current_selection() {
+            if let Some(old_parent) = self.world.get_parent(entity) {
+                batch.add(Box::new(super::SetParentCommand::new(entity, Some(old_parent), None)));
+            }
+        }
+
+        if batch.is_empty() {
+            return;
+        }
+        let count = batch.len();
+        self.undo_stack.execute(Box::new(batch), &mut self.world, &mut self.entity_names);
+        self.scene_modified = true;
+        self.console.info(format!("Unparented {} entities", count));
     }
 
-    /// Load editor layout from project folder
+    /// Move the selection under `parent` (None: the scene root) in front of `before` (None:
+    /// after the last child) as one undo step. Entities keep their world position and
+    /// their hierarchy order; ones that would end up under themselves are skipped.
+    pub fn move_selection_to(&mut self, parent: Option<Entity>, before: Option<Entity>) {
+        let selected = self.current_selection();
+        let mut roots = super::tools::selection::top_level_selected(&selected, &self.world);
+        let order: Vec<Entity> = self.world.roots().into_iter().flat_map(|root| self.world.subtree(root)).collect();
+        roots.sort_by_key(|entity| order.iter().position(|e| e == entity));
+
+        // Dropped next to a moved entity: go in front of the next one that stays
+        let before = before.and_then(|before| {
+            let siblings = match self.world.get_parent(before) {
+                Some(parent) => self.world.get_children(parent).to_vec(),
+                None => self.world.roots(),
+            };
+            siblings.into_iter().skip_while(|&e| e != before).find(|e| !roots.contains(e))
+        });
+
+        let mut batch = super::BatchCommand::new(self.batch_description("Move", &roots));
+        for entity in roots {
+            if self.world.check_parent(entity, parent).is_err() {
+                continue;
+            }
+            let old_parent = self.world.get_parent(entity);
+            batch.add(Box::new(super::SetParentCommand::new(entity, old_parent, parent).before(before)));
+        }
+
+        if batch.is_empty() {
+            self.console.warning("Can't move an entity under itself".to_string());
+            return;
+        }
+        let count = batch.len();
+        self.undo_stack.execute(Box::new(batch), &mut self.world, &mut self.entity_names);
+        self.scene_modified = true;
+        self.console.info(format!("Moved {} entities", count));
+    }
+
+    /// Push already-created entities onto the undo stack as a single step
+    fn record_created_entities(&mut self, description: String, entities: &[Entity]) {
+        let mut batch = super::BatchCommand::new(description);
+        for &entity in entities {
+            batch.add(Box::new(super::CreateEntityCommand::new(entity, &self.world, &self.entity_names)));
+        }
+        // Re-applying the captured data is a no-op for entities that already exist
+        self.undo_stack.execute(Box::new(batch), &mut self.world, &mut self.entity_names);
+        self.scene_modified = true;
+    }
+
+    /// "Delete Player" for a single entity, "Delete 3 Entities" otherwise
+    fn batch_description(&self, verb: &str, entities: &[Entity]) -> String {
+        match entities {
+            [entity] => format!(
+                "{} {}",
+                verb,
+                self.entity_names.get(entity).cloned().unwrap_or_else(|| format!("Entity {}", entity))
+            ),
+            _ => format!("{} {} Entities", verb, entities.len()),
+        }
+    }
+
+    /// Load editor layout from project folder: the arrangement of the last session,
+    /// else the layout set as default
     pub fn load_editor_layout(&mut self) {
         if let Some(ref project_path) = self.current_project_path {
             if let Some(layout_name) = super::ui::load_default_layout_name(project_path) {
@@ -276,6 +761,18 @@ impl EditorState {
                 self.current_layout_name = layout_name.clone();
                 self.console.info(format!("Loaded layout: {}", layout_name));
             }
+            if let Some(dock_state) = super::ui::load_session_layout(project_path) {
+                self.dock_state = dock_state;
+            }
+        }
+    }
+
+    /// Save the current panel arrangement so the next session starts with it
+    pub fn save_editor_layout(&self) {
+        if let Some(ref project_path) = self.current_project_path {
+            if let Err(e) = super::ui::save_session_layout(&self.dock_state, project_path) {
+                log::warn!("Failed to save editor layout: {}", e);
+            }
         }
     }
     /// Save current layout as default
@@ -300,6 +797,7 @@ impl EditorState {
                 self.console.info(format!("Deleted entity {:?}", entity));
             }
             EditorAction::NewScene => {
+                self.remember_scene_camera_pose();
                 self.world = ecs::World::new();
                 self.selected_entity = None;
                 self.entity_names.clear();
@@ -335,11 +833,15 @@ impl EditorState {
         for (entity, name) in &self.entity_names {
             self.world.names.insert(*entity, name.clone());
         }
+        // Save games find Save State entities by Guid
+        self.world.assign_guids();
         
         let json = self.world.save_to_json()?;
-        std::fs::write(path, json)?;
+        std::fs::write(path, &json)?;
+        self.scene_watch.track(path, &json);
         self.current_scene_path = Some(path.clone());
         self.scene_modified = false;
+        self.remember_scene_camera_pose();
         
         // Update last_opened_scene in project config
         if let Some(project_path) = &self.current_project_path {
@@ -356,7 +858,13 @@ impl EditorState {
     }
 
     pub fn load_scene(&mut self, path: &PathBuf, asset_loader: &dyn engine_core::assets::AssetLoader) -> Result<()> {
+        profiler::profile_scope!("ecs.load_scene");
         let path_str = path.to_str().ok_or_else(|| anyhow::anyhow!("Invalid path"))?;
+        // Reloading the same scene (e.g. after play mode) keeps the camera where it is
+        let switching_scene = self.current_scene_path.as_ref() != Some(path);
+        if switching_scene {
+            self.remember_scene_camera_pose();
+        }
         
         // Use block_on to execute the async load synchronously for now
         // This is safe on native because NativeAssetLoader uses blocking fs calls inside async wrapper (conceptually)
@@ -364,13 +872,51 @@ impl EditorState {
         let json = pollster::block_on(asset_loader.load_text(path_str))?;
         
         self.world.load_from_json(&json)?;
+        self.scene_watch.track(path, &json);
         self.current_scene_path = Some(path.clone());
         self.scene_modified = false;
         self.selected_entity = None;
         
 
 
-        // Rebuild entity_names from loaded entities
+        self.rebuild_entity_names();
+
+        // Custom components saved with an older definition (fields added or removed since)
+        for change in self.component_registry.conform_world(&mut self.world) {
+            self.console.warning(format!("{}: {}", path.display(), change));
+        }
+
+        // Update last_opened_scene in project config
+        if let Some(project_path) = &self.current_project_path {
+            if let Ok(pm) = ProjectManager::new() {
+                // Make path relative to project
+                if let Ok(relative_path) = path.strip_prefix(project_path) {
+                    let _ = pm.set_last_opened_scene(project_path, Some(relative_path.to_path_buf()));
+                }
+            }
+        }
+
+        if switching_scene {
+            self.restore_scene_camera_pose();
+        }
+
+        // Reloads after play mode have already been reported
+        if let Some(project_path) = &self.current_project_path {
+            let broken = self.asset_references.validate(&self.world, project_path);
+            if broken > 0 && switching_scene {
+                self.console.warning(format!(
+                    "⚠ {} broken asset reference(s) in this scene. See Edit > Validate Assets",
+                    broken
+                ));
+            }
+        }
+
+        log::info!("Scene loaded from {:?}", path);
+        Ok(())
+    }
+
+    /// Rebuild entity_names from loaded entities
+    pub fn rebuild_entity_names(&mut self) {
         self.entity_names.clear();
         for &entity in self.world.transforms.keys() {
             // Use name from world if available, otherwise generate one
@@ -384,21 +930,210 @@ impl EditorState {
             self.entity_names.insert(entity, name.clone());
             self.world.names.insert(entity, name);
         }
+    }
 
-        // Update last_opened_scene in project config
-        if let Some(project_path) = &self.current_project_path {
-            if let Ok(pm) = ProjectManager::new() {
-                // Make path relative to project
-                if let Ok(relative_path) = path.strip_prefix(project_path) {
-                    let _ = pm.set_last_opened_scene(project_path, Some(relative_path.to_path_buf()));
-                }
+    /// Keep the session journal current; on the first call for a project, check
+    /// whether the previous session crashed with a newer autosave to offer
+    pub fn update_session_journal(&mut self) {
+        let Some(project_path) = self.current_project_path.clone() else {
+            return;
+        };
+
+        if self.session_project.as_ref() != Some(&project_path) {
+            self.pending_recovery = super::autosave::SessionJournal::load(&project_path).and_then(|journal| {
+                let autosave_path = journal.recoverable_autosave()?;
+                Some((journal.scene_path?, autosave_path))
+            });
+            self.autosave.watch_settings(&project_path);
+            self.session_project = Some(project_path.clone());
+            self.session_journal = None;
+        }
+
+        let journal = super::autosave::SessionJournal {
+            scene_path: self.current_scene_path.clone(),
+            scene_modified: self.scene_modified,
+            clean_shutdown: false,
+        };
+        if self.session_journal.as_ref() != Some(&journal) {
+            if let Err(e) = journal.save(&project_path) {
+                log::warn!("{}", e);
             }
+            self.session_journal = Some(journal);
         }
+    }
 
-        log::info!("Scene loaded from {:?}", path);
+    /// Normal exit: remember the camera and panel layout and mark the session journal clean
+    pub fn end_session(&mut self) {
+        self.remember_scene_camera_pose();
+        self.save_editor_layout();
+
+        if let (Some(project_path), Some(journal)) = (&self.session_project, self.session_journal.as_mut()) {
+            journal.clean_shutdown = true;
+            if let Err(e) = journal.save(project_path) {
+                log::warn!("{}", e);
+            }
+        }
+    }
+
+    /// Load the autosave offered by the recovery dialog in place of its scene.
+    /// The scene file itself is left alone until the user saves.
+    pub fn recover_autosave(&mut self) -> Result<()> {
+        let Some((scene_path, autosave_path)) = self.pending_recovery.take() else {
+            return Ok(());
+        };
+        let json = std::fs::read_to_string(&autosave_path)?;
+
+        self.remember_scene_camera_pose();
+        self.world.load_from_json(&json)?;
+        if let Ok(disk_json) = std::fs::read_to_string(&scene_path) {
+            self.scene_watch.track(&scene_path, &disk_json);
+        }
+        self.current_scene_path = Some(scene_path);
+        self.restore_scene_camera_pose();
+        self.selected_entity = None;
+        self.rebuild_entity_names();
+        self.undo_stack.clear();
+        self.scene_modified = true;
+        Ok(())
+    }
+
+    /// Offer Reload / Keep Mine when the open scene file changed outside the editor
+    /// (not while playing: the change waits until Stop)
+    pub fn poll_scene_file(&mut self) {
+        if self.is_playing {
+            return;
+        }
+        let Some(path) = self.current_scene_path.clone() else {
+            return;
+        };
+        let Some(disk_json) = self.scene_watch.poll(&path) else {
+            return;
+        };
+        match self.scene_reload_prompt.as_mut() {
+            // Changed again while the dialog is open: offer the newest version
+            Some(prompt) if prompt.path == path => {
+                prompt.disk_json = disk_json;
+                prompt.diff = None;
+            }
+            _ => {
+                self.console.warning(format!("Scene file changed on disk: {}", path.display()));
+                self.scene_reload_prompt = Some(super::scene_watch::SceneReloadPrompt::new(
+                    super::scene_watch::SceneReloadReason::ExternalChange,
+                    path,
+                    disk_json,
+                ));
+            }
+        }
+    }
+
+    /// Read the project's custom component definitions again when scripts/components.lua
+    /// changed (or another project opened), so the inspector shows their current fields.
+    /// Instances in the scene are brought up to the new definitions. Not while playing:
+    /// the script engine has its own copy then.
+    pub fn poll_component_definitions(&mut self) {
+        if self.is_playing {
+            return;
+        }
+        let Some(project_path) = self.current_project_path.clone() else {
+            return;
+        };
+        let file = project_path
+            .join(script::modules::SCRIPTS_DIR)
+            .join(format!("{}.lua", script::components::DEFINITIONS_MODULE));
+        let modified = std::fs::metadata(&file).and_then(|metadata| metadata.modified()).ok();
+        if self.component_definitions_modified == Some(modified) {
+            return;
+        }
+        self.component_definitions_modified = Some(modified);
+
+        let loader = std::sync::Arc::new(engine::assets::native_loader::NativeAssetLoader::new(&project_path));
+        match script::components::load_definitions(loader, &project_path, &self.project_settings.scripting.lib_paths) {
+            Ok(registry) => self.component_registry = registry,
+            Err(e) => {
+                self.console.error(format!("Failed to load component definitions: {}", e));
+                return;
+            }
+        }
+        let changes = self.component_registry.conform_world(&mut self.world);
+        for change in &changes {
+            self.console.warning(change.clone());
+        }
+        if !changes.is_empty() {
+            self.scene_modified = true;
+        }
+    }
+
+    /// File > Revert Scene: offer the saved version of the open scene in place of the editor's
+    pub fn request_scene_revert(&mut self) {
+        if self.is_playing {
+            self.console.warning("Revert Scene: stop play mode first".to_string());
+            return;
+        }
+        let Some(path) = self.current_scene_path.clone() else {
+            self.console.warning("Revert Scene: the scene has never been saved".to_string());
+            return;
+        };
+        match std::fs::read_to_string(&path) {
+            Ok(disk_json) => {
+                self.scene_reload_prompt = Some(super::scene_watch::SceneReloadPrompt::new(
+                    super::scene_watch::SceneReloadReason::Revert,
+                    path,
+                    disk_json,
+                ));
+            }
+            Err(e) => self.console.error(format!("Revert Scene: failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    /// What reloading `disk_json` would change, entity by entity
+    pub fn diff_against_disk(&mut self, disk_json: &str) -> Result<super::scene_diff::SceneDiff> {
+        for (entity, name) in &self.entity_names {
+            self.world.names.insert(*entity, name.clone());
+        }
+        let mut disk = World::new();
+        disk.load_from_json(disk_json)?;
+        Ok(super::scene_diff::SceneDiff::between(&self.world, &disk)?)
+    }
+
+    /// Replace the scene with `disk_json` (the open scene's file contents). The camera
+    /// stays put and entities that still exist stay selected.
+    pub fn reload_scene_from_disk(&mut self, disk_json: &str) -> Result<()> {
+        self.world.load_from_json(disk_json)?;
+        if let Some(path) = self.current_scene_path.clone() {
+            self.scene_watch.track(&path, disk_json);
+        }
+
+        self.selected_entity = self.selected_entity.filter(|e| self.world.transforms.contains_key(e));
+        self.selected_entities.retain(|e| self.world.transforms.contains_key(e));
+        self.selection.retain_existing(&self.world);
+        self.rebuild_entity_names();
+        self.undo_stack.clear();
+        self.scene_modified = false;
         Ok(())
     }
 
+    /// Store the scene view camera pose for the current scene in the editor-only sidecar
+    pub fn remember_scene_camera_pose(&self) {
+        let (Some(project_path), Some(scene_path)) = (&self.current_project_path, &self.current_scene_path) else {
+            return;
+        };
+        let mut poses = super::scene_camera_poses::SceneCameraPoses::load(project_path);
+        poses.set(project_path, scene_path, self.scene_camera.pose());
+        if let Err(e) = poses.save(project_path) {
+            log::warn!("{}", e);
+        }
+    }
+
+    /// Put the scene view camera back where it was when the current scene was last open
+    fn restore_scene_camera_pose(&mut self) {
+        let (Some(project_path), Some(scene_path)) = (&self.current_project_path, &self.current_scene_path) else {
+            return;
+        };
+        if let Some(pose) = super::scene_camera_poses::SceneCameraPoses::load(project_path).get(project_path, scene_path) {
+            self.scene_camera.set_pose(pose);
+        }
+    }
+
     pub fn create_script_file(&self, script_name: &str) -> Result<PathBuf> {
         if let Some(scripts_folder) = self.get_scripts_folder() {
             std::fs::create_dir_all(&scripts_folder)?;
@@ -413,7 +1148,7 @@ r#"-- Script: {}
 -- is_key_pressed(key) - Check if a key is pressed
 -- set_velocity(vx, vy) - Set entity velocity
 -- get_tag(entity) - Get entity tag
--- destroy_entity(entity) - Destroy an entity
+-- destroy_entity(entity) - Destroy an entity (and its children) at the end of the frame
 
 -- Movement speed
 local speed = 200.0
@@ -479,9 +1214,18 @@ end
         }
     }
 
-    pub fn open_script_in_editor(&self, script_name: &str) -> Result<()> {
+    /// Open a script in the external editor, at `line` where the editor supports it
+    /// (VS Code `--goto`); otherwise with the platform's default handler
+    pub fn open_script_in_editor(&self, script_name: &str, line: Option<u32>) -> Result<()> {
         let script_path = self.create_script_file(script_name)?;
 
+        if let Some(line) = line {
+            let target = format!("{}:{}", script_path.display(), line);
+            if std::process::Command::new("code").arg("--goto").arg(&target).spawn().is_ok() {
+                return Ok(());
+            }
+        }
+
         #[cfg(target_os = "windows")]
         {
             std::process::Command::new("cmd")
@@ -506,6 +1250,32 @@ end
         Ok(())
     }
 
+    /// Console click on a Lua error: open the script at the line and select the owning entity
+    pub fn open_console_source(&mut self, request: super::console::OpenSourceRequest) {
+        let script_name = request.file
+            .trim_start_matches("scripts/")
+            .trim_end_matches(".lua")
+            .to_string();
+
+        if let Err(e) = self.open_script_in_editor(&script_name, request.line) {
+            self.console.error(format!("Failed to open script: {}", e));
+        }
+
+        let uses_script = |entity: &Entity| {
+            self.world.scripts.get(entity).is_some_and(|s| s.script_name == script_name)
+        };
+        let owner = request.entity_hint
+            .filter(|entity| uses_script(entity))
+            .or_else(|| {
+                let mut owners: Vec<Entity> = self.world.scripts.keys().copied().filter(|e| uses_script(e)).collect();
+                owners.sort();
+                owners.first().copied()
+            });
+        if let Some(entity) = owner {
+            self.selected_entity = Some(entity);
+        }
+    }
+
     /// Update all entities that use a specific sprite file when it changes
     pub fn update_entities_using_sprite_file(&mut self, sprite_file_path: &PathBuf) {
         // Load the updated sprite metadata
@@ -533,6 +1303,7 @@ end
                         width: sprite_def.width,
                         height: sprite_def.height,
                         name: Some(sprite_def.name.clone()),
+                        rotated: sprite_def.rotated,
                     });
                 }
                 
@@ -544,6 +1315,23 @@ end
             }
         }
 
+        // Pivots edited in the sprite editor, matched by frame name (re-packing moves rects),
+        // else by sprite rect. The rect fallback follows the named frame too.
+        for sprite in self.world.sprites.values_mut() {
+            if sprite.texture_id != metadata.texture_path {
+                continue;
+            }
+            let def = match &sprite.frame_name {
+                Some(name) => metadata.find_sprite(name),
+                None => sprite.sprite_rect.and_then(|rect| metadata.sprites.iter().find(|def| def.rect() == rect)),
+            };
+            if let Some(def) = def {
+                sprite.pivot = Some(def.pivot);
+                sprite.sprite_rect = Some(def.rect());
+                sprite.rect_rotated = def.rotated;
+            }
+        }
+
         if updated_count > 0 {
             self.console.info(format!("🔄 Hot-reloaded sprite file: {} entities updated", updated_count));
             log::info!("Hot-reloaded sprite file {:?}: {} entities updated", sprite_file_path, updated_count);
@@ -577,7 +1365,7 @@ impl GameState {
             texture_id: "player".to_string(),
             width: 1.0,  // Base size
             height: 1.0,
-            color: [0.2, 0.6, 1.0, 1.0],
+            color: ecs::Color::rgba(0.2, 0.6, 1.0, 1.0),
             billboard: true, // Player sprite faces camera
             ..Default::default()
         });
@@ -653,6 +1441,9 @@ mod tests {
                     y: 0,
                     width: 32,
                     height: 32,
+                    pivot: [0.5, 0.5],
+                    polygon: Vec::new(),
+                    rotated: false,
                 },
                 sprite_editor::SpriteDefinition {
                     name: "sprite_1".to_string(),
@@ -660,6 +1451,9 @@ mod tests {
                     y: 0,
                     width: 32,
                     height: 32,
+                    pivot: [0.5, 0.5],
+                    polygon: Vec::new(),
+                    rotated: false,
                 },
             ],
         };
@@ -682,6 +1476,7 @@ mod tests {
                     width: 32,
                     height: 32,
                     name: Some("old_sprite_0".to_string()),
+                    rotated: false,
                 },
             ],
         };
@@ -728,6 +1523,9 @@ mod tests {
                     y: 0,
                     width: 32,
                     height: 32,
+                    pivot: [0.5, 0.5],
+                    polygon: Vec::new(),
+                    rotated: false,
                 },
             ],
         };
@@ -750,6 +1548,7 @@ mod tests {
                     width: 32,
                     height: 32,
                     name: Some("original_sprite".to_string()),
+                    rotated: false,
                 },
             ],
         };
@@ -770,4 +1569,64 @@ mod tests {
         // Clean up
         fs::remove_file(&sprite_file_path).ok();
     }
+
+    #[test]
+    fn test_delete_selection_is_single_undo_step() {
+        let mut editor_state = EditorState::new();
+        let parent = editor_state.world.spawn();
+        let child = editor_state.world.spawn();
+        let other = editor_state.world.spawn();
+        for (entity, name) in [(parent, "Parent"), (child, "Child"), (other, "Other")] {
+            editor_state.world.transforms.insert(entity, ecs::Transform::default());
+            editor_state.entity_names.insert(entity, name.to_string());
+        }
+        editor_state.world.set_parent(child, Some(parent));
+
+        editor_state.set_selection(&[parent, other]);
+        editor_state.delete_selection();
+        assert!(editor_state.world.transforms.is_empty());
+        assert!(editor_state.selected_entity.is_none());
+
+        assert!(editor_state.undo_stack.undo(&mut editor_state.world, &mut editor_state.entity_names));
+        assert_eq!(editor_state.world.transforms.len(), 3);
+        assert_eq!(editor_state.world.get_parent(child), Some(parent));
+        assert_eq!(editor_state.world.get_children(parent), &[child]);
+        assert!(!editor_state.undo_stack.can_undo());
+    }
+
+    #[test]
+    fn test_move_selection_reorders_and_undoes_as_one_step() {
+        let mut editor_state = EditorState::new();
+        let entities: Vec<Entity> = (0..4).map(|_| editor_state.world.spawn()).collect();
+        let &[parent, a, b, c] = entities.as_slice() else { unreachable!() };
+        editor_state.world.transforms.insert(parent, ecs::Transform::with_position(10.0, 0.0, 0.0));
+        for &entity in &[a, b, c] {
+            editor_state.world.transforms.insert(entity, ecs::Transform::default());
+            editor_state.world.set_parent(entity, Some(parent));
+        }
+
+        // Dragging a and c in front of b keeps a first
+        editor_state.set_selection(&[c, a]);
+        editor_state.move_selection_to(Some(parent), Some(b));
+        assert_eq!(editor_state.world.get_children(parent), &[a, c, b]);
+        assert_eq!(editor_state.world.transforms[&a].position, [0.0, 0.0, 0.0], "reordering keeps local transforms");
+
+        // Out to the root, in front of the parent, where they stay in the world
+        editor_state.move_selection_to(None, Some(parent));
+        assert_eq!(editor_state.world.roots(), [a, c, parent]);
+        assert_eq!(editor_state.world.get_children(parent), &[b]);
+        assert_eq!(editor_state.world.world_position(c), Some([10.0, 0.0, 0.0]));
+
+        // Under their own child: refused
+        editor_state.world.set_parent(b, Some(a));
+        editor_state.set_selection(&[a]);
+        editor_state.move_selection_to(Some(b), None);
+        assert_eq!(editor_state.world.get_parent(b), Some(a));
+
+        editor_state.world.set_parent(b, Some(parent));
+        assert!(editor_state.undo_stack.undo(&mut editor_state.world, &mut editor_state.entity_names));
+        assert_eq!(editor_state.world.get_children(parent), &[a, c, b]);
+        assert!(editor_state.undo_stack.undo(&mut editor_state.world, &mut editor_state.entity_names));
+        assert_eq!(editor_state.world.get_children(parent), &[a, b, c]);
+    }
 }
@@ -133,6 +133,32 @@ pub struct CameraState {
     pub pitch: f32,
 }
 
+/// Where the camera is and how it looks at the scene, saved per scene
+/// (see `crate::scene_camera_poses`)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CameraPose {
+    /// 2D: center of the view. 3D: the point the camera looks at (orbit pivot).
+    pub position: Vec3,
+    pub zoom: f32,
+    pub rotation: f32,
+    pub pitch: f32,
+    pub distance: f32,
+}
+
+/// Smooth move between two poses, advanced by `SceneCamera::update`
+#[derive(Debug, Clone, Copy)]
+struct CameraTransition {
+    from: CameraPose,
+    to: CameraPose,
+    elapsed: f32,
+}
+
+/// Seconds a focus or preset-view transition takes
+const TRANSITION_DURATION: f32 = 0.25;
+
+/// Vertical field of view of the 3D perspective projection, in degrees
+const FIELD_OF_VIEW: f32 = 45.0;
+
 /// Projection mode for 3D view
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SceneProjectionMode {
@@ -189,6 +215,12 @@ pub struct SceneCamera {
     
     // Cursor tracking for zoom
     last_cursor_world_pos: Option<Vec3>,
+
+    /// Fly-through speed in units per second (RMB + WASD, scroll adjusts it)
+    pub fly_speed: f32,
+
+    // Focus / preset view in progress
+    transition: Option<CameraTransition>,
 }
 
 impl SceneCamera {
@@ -225,6 +257,8 @@ impl SceneCamera {
             zoom_interpolation_speed: 10.0,
             saved_3d_state: None,
             last_cursor_world_pos: None,
+            fly_speed: 10.0,
+            transition: None,
         }
     }
     
@@ -245,31 +279,18 @@ impl SceneCamera {
 
             // Check if we're in 3D mode (pitch != 0)
             if self.pitch.abs() > 0.1 {
-                // 3D mode: pan in camera space
-                // Better pan speed calculation: based on distance and zoom-like feel
-                let base_speed = 0.5; // Reduced base speed for better control
-                let distance_factor = (self.distance / 100.0).max(0.1); // Clamp minimum
-                let pan_speed = self.settings.pan_sensitivity * base_speed * distance_factor;
-
-                let yaw_rad = self.rotation.to_radians();
+                // 3D mode: pan in camera space, so the point under the cursor follows it.
+                // World units per pixel at the pivot's depth, against a 1000px reference
+                // viewport (as in get_projection_matrix)
+                let units_per_pixel = self.distance * 2.0 * (FIELD_OF_VIEW.to_radians() * 0.5).tan() / 1000.0;
+                let pan_speed = self.settings.pan_sensitivity * units_per_pixel;
+                let (_, right, up) = self.basis();
 
-                // Right vector (perpendicular to view direction on XZ plane)
-                let right_x = yaw_rad.sin();
-                let right_z = -yaw_rad.cos();
-
-                // Forward vector (for up/down panning in 3D)
-                // Use pitch to determine vertical movement
-                let pitch_rad = self.pitch.to_radians();
-                let forward_x = yaw_rad.cos() * pitch_rad.cos();
-                let forward_z = yaw_rad.sin() * pitch_rad.cos();
-
-                // Apply delta in camera space (Unity-style: drag right = camera moves right)
-                let world_delta_x = (delta.x * right_x + delta.y * forward_x) * pan_speed;
-                let world_delta_z = (delta.x * right_z + delta.y * forward_z) * pan_speed;
-
-                let world_delta = Vec3::new(world_delta_x, 0.0, world_delta_z);
+                // Drag right = scene moves right = camera moves left
+                let world_delta = (-right * delta.x + up * delta.y) * pan_speed;
 
                 // Update both position and pivot
+                self.transition = None;
                 self.position += world_delta;
                 self.pivot += world_delta;
                 self.target_position = self.position;
@@ -487,6 +508,8 @@ impl SceneCamera {
     
     /// Update camera state (call each frame for smooth interpolation)
     pub fn update(&mut self, delta_time: f32) {
+        self.advance_transition(delta_time);
+
         // Apply damping to smooth out movements
         self.apply_damping(delta_time);
         
@@ -643,6 +666,122 @@ impl SceneCamera {
         // Kept as separate method for clarity and future enhancements
     }
     
+    /// Current pose (for saving it with the scene)
+    pub fn pose(&self) -> CameraPose {
+        CameraPose {
+            position: self.position,
+            zoom: self.zoom,
+            rotation: self.rotation,
+            pitch: self.pitch,
+            distance: self.distance,
+        }
+    }
+
+    /// Jump to a pose (reopening a scene)
+    pub fn set_pose(&mut self, pose: CameraPose) {
+        self.transition = None;
+        self.apply_pose(pose);
+    }
+
+    /// Move to a pose smoothly over the next frames
+    pub fn transition_to(&mut self, pose: CameraPose) {
+        self.transition = Some(CameraTransition { from: self.pose(), to: pose, elapsed: 0.0 });
+    }
+
+    /// True while a focus or preset-view transition is running
+    pub fn is_transitioning(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    fn apply_pose(&mut self, pose: CameraPose) {
+        if !pose.position.is_finite() || !pose.zoom.is_finite() || !pose.distance.is_finite() {
+            return;
+        }
+        self.position = pose.position;
+        self.pivot = pose.position;
+        self.zoom = pose.zoom.clamp(self.min_zoom, self.max_zoom);
+        self.rotation = pose.rotation;
+        self.pitch = pose.pitch.clamp(self.min_pitch, self.max_pitch);
+        self.distance = pose.distance.max(0.1);
+        self.target_position = self.position;
+        self.target_rotation = self.rotation;
+        self.target_pitch = self.pitch;
+        self.target_zoom = self.zoom;
+    }
+
+    fn advance_transition(&mut self, delta_time: f32) {
+        let Some(mut transition) = self.transition else {
+            return;
+        };
+        // Direct control takes over from an unfinished transition
+        if self.is_controlling() {
+            self.transition = None;
+            return;
+        }
+        if delta_time.is_finite() && delta_time > 0.0 {
+            transition.elapsed += delta_time;
+        }
+        let t = (transition.elapsed / TRANSITION_DURATION).min(1.0);
+        // Ease out (cubic)
+        let eased = 1.0 - (1.0 - t).powi(3);
+        let (from, to) = (transition.from, transition.to);
+        // Turn the short way round
+        let rotation_delta = (to.rotation - from.rotation + 180.0).rem_euclid(360.0) - 180.0;
+        self.apply_pose(CameraPose {
+            position: from.position.lerp(to.position, eased),
+            zoom: from.zoom + (to.zoom - from.zoom) * eased,
+            rotation: (from.rotation + rotation_delta * eased).rem_euclid(360.0),
+            pitch: from.pitch + (to.pitch - from.pitch) * eased,
+            distance: from.distance + (to.distance - from.distance) * eased,
+        });
+        self.transition = if t < 1.0 { Some(transition) } else { None };
+    }
+
+    /// Offset from the look-at point to the eye in 3D
+    fn eye_offset(&self) -> Vec3 {
+        let yaw_rad = self.rotation.to_radians();
+        let pitch_rad = self.pitch.to_radians();
+        Vec3::new(
+            self.distance * yaw_rad.cos() * pitch_rad.cos(),
+            self.distance * pitch_rad.sin(),
+            self.distance * yaw_rad.sin() * pitch_rad.cos(),
+        )
+    }
+
+    /// Where the 3D camera is (it looks at `position` from here)
+    pub fn eye_position(&self) -> Vec3 {
+        self.position + self.eye_offset()
+    }
+
+    /// View direction and its right/up axes in 3D
+    pub fn basis(&self) -> (Vec3, Vec3, Vec3) {
+        let forward = (-self.eye_offset()).normalize_or(Vec3::NEG_Z);
+        let right = forward.cross(Vec3::Y).normalize_or(Vec3::X);
+        let up = right.cross(forward);
+        (forward, right, up)
+    }
+
+    /// Fly through the scene (RMB + WASD/QE): `direction` is x = right, y = world up,
+    /// z = forward, moved at `fly_speed` units per second
+    pub fn fly(&mut self, direction: Vec3, delta_time: f32) {
+        if !direction.is_finite() || !delta_time.is_finite() || direction.length_squared() < 1e-6 {
+            return;
+        }
+        let (forward, right, _) = self.basis();
+        let movement = (right * direction.x + Vec3::Y * direction.y + forward * direction.z) * self.fly_speed * delta_time;
+        self.transition = None;
+        self.position += movement;
+        self.pivot += movement;
+        self.target_position = self.position;
+    }
+
+    /// Scroll while flying: each step is 20% faster (up) or slower (down)
+    pub fn adjust_fly_speed(&mut self, steps: f32) {
+        if steps.is_finite() {
+            self.fly_speed = (self.fly_speed * 1.2f32.powf(steps)).clamp(0.1, 1000.0);
+        }
+    }
+
     /// Start rotation (right mouse button pressed)
     pub fn start_rotate(&mut self, mouse_pos: Vec2) {
         self.is_rotating = true;
@@ -658,12 +797,20 @@ impl SceneCamera {
             let pitch_delta = -delta.y * self.settings.rotation_sensitivity;
             
             // Update rotation and pitch immediately from current values
+            let eye = self.eye_position();
             self.rotation += yaw_delta;
             self.rotation = self.rotation.rem_euclid(360.0);
             
             // Vertical movement changes pitch
             self.pitch += pitch_delta;
             self.pitch = self.pitch.clamp(self.min_pitch, self.max_pitch);
+
+            // Free look turns the camera in place: the look-at point swings around the eye
+            if self.pitch.abs() > 0.1 {
+                self.position = eye - self.eye_offset();
+                self.pivot = self.position;
+                self.target_position = self.position;
+            }
             
             // Also update targets to match
             self.target_rotation = self.rotation;
@@ -689,16 +836,18 @@ impl SceneCamera {
         self.pivot = pivot_point;
         self.last_mouse_pos = mouse_pos;
         
-        // Calculate current distance from pivot
-        let offset = self.position - self.pivot;
-        let horizontal_distance = offset.length();
-        
-        // Store distance (considering pitch for 3D orbiting)
         if self.pitch.abs() > 0.1 {
-            // In 3D mode, calculate actual 3D distance
-            let pitch_rad = self.pitch.to_radians();
-            self.distance = horizontal_distance / pitch_rad.cos().max(0.01);
+            // In 3D mode, turn to face the pivot from where the camera is, so the view
+            // doesn't jump, then orbit at that distance
+            let offset = self.eye_position() - pivot_point;
+            if offset.length() > 0.01 {
+                self.distance = offset.length();
+                self.pitch = (offset.y / self.distance).asin().to_degrees().clamp(self.min_pitch, self.max_pitch);
+                self.rotation = offset.z.atan2(offset.x).to_degrees().rem_euclid(360.0);
+            }
+            self.position = pivot_point;
         } else {
+            let horizontal_distance = (self.position - self.pivot).length();
             // In 2D mode, use horizontal distance
             self.distance = horizontal_distance.max(10.0);  // Minimum distance
         }
@@ -785,69 +934,55 @@ impl SceneCamera {
     
     /// Focus on object (F key) - frames entity appropriately in viewport
     pub fn focus_on(&mut self, target_pos: Vec3, object_size: f32, viewport_size: Vec2) {
+        // Check if we're in 3D mode (pitch != 0)
+        if self.pitch.abs() > 0.1 {
+            self.frame_bounds(target_pos, Vec3::splat(object_size), viewport_size);
+            return;
+        }
+
         // Set pivot to target position
         self.pivot = target_pos;
+
+        // 2D mode: center on target
+        self.position = target_pos;
+        self.target_position = target_pos;
         
-        // Check if we're in 3D mode (pitch != 0)
-        if self.pitch.abs() > 0.1 {
-            // 3D mode: maintain current rotation/pitch, adjust distance
-            // For tilemap viewing in 3D, we want to be much closer
-            
-            // For 3D tilemap editing, we want to be very close regardless of object size
-            // This provides optimal view for level editing
-            let base_distance = if object_size < 1.0 {
-                // Small objects: very close
-                1.0 + (object_size * 0.5) // 1.0-1.5 units
-            } else if object_size < 3.0 {
-                // Medium objects: still close
-                1.5 + (object_size * 0.3) // 1.5-2.4 units
-            } else {
-                // Large objects: moderate distance but still close
-                2.0 + (object_size * 0.2) // 2.0-3.0 units for size 5.0
-            };
-            
-            // No padding factor - use base distance directly for closer view
-            self.distance = base_distance;
-            
-            // Ensure very close bounds for tilemap editing
-            self.distance = self.distance.clamp(0.8, 8.0);
-            
-            // Debug logging for focus issues
-            log::info!("3D Focus: object_size={:.3}, base_distance={:.3}, final_distance={:.3}", 
-                object_size, base_distance, self.distance);
+        // Adjust zoom to fit object
+        if object_size > 0.0 && viewport_size.x > 0.0 && viewport_size.y > 0.0 {
+            let viewport_min_dim = viewport_size.x.min(viewport_size.y);
             
-            // Update camera position based on new distance and current rotation/pitch
-            // In 3D mode, we orbit around the pivot point
-            let _yaw_rad = self.rotation.to_radians();
-            let _pitch_rad = self.pitch.to_radians();
+            // Calculate zoom needed to fit object within 60% of viewport
+            let desired_screen_size = viewport_min_dim * 0.6;
             
-            // Position camera relative to pivot point
-            // In 3D mode, self.position IS the target/pivot point that the camera looks at.
-            // So we just set it to the target position. The 'distance' parameter handles the backing up.
-            self.position = self.pivot;
-            self.target_position = self.position;
+            // zoom = screen_size / world_size
+            let target_zoom = desired_screen_size / object_size;
             
-        } else {
-            // 2D mode: center on target
-            self.position = target_pos;
-            self.target_position = target_pos;
-            
-            // Adjust zoom to fit object
-            if object_size > 0.0 && viewport_size.x > 0.0 && viewport_size.y > 0.0 {
-                let viewport_min_dim = viewport_size.x.min(viewport_size.y);
-                
-                // Calculate zoom needed to fit object within 60% of viewport
-                let desired_screen_size = viewport_min_dim * 0.6;
-                
-                // zoom = screen_size / world_size
-                let target_zoom = desired_screen_size / object_size;
-                
-                // Clamp zoom to reasonable limits
-                self.target_zoom = target_zoom.clamp(self.min_zoom, self.max_zoom);
-                self.zoom = self.target_zoom;
-            }
+            // Clamp zoom to reasonable limits
+            self.target_zoom = target_zoom.clamp(self.min_zoom, self.max_zoom);
+            self.zoom = self.target_zoom;
         }
     }
+
+    /// Frame a box (F key in 3D): glide to look at its center from far enough away that
+    /// it fills most of the view, keeping the viewing angle. In 2D it frames like `focus_on`.
+    pub fn frame_bounds(&mut self, center: Vec3, size: Vec3, viewport_size: Vec2) {
+        if !center.is_finite() || !size.is_finite() {
+            return;
+        }
+        if self.pitch.abs() <= 0.1 {
+            self.focus_on(center, size.x.max(size.y), viewport_size);
+            return;
+        }
+
+        // Distance at which the bounding sphere fits the vertical field of view, with margin
+        let radius = (size.abs().length() * 0.5).max(0.05);
+        let distance = radius / (FIELD_OF_VIEW.to_radians() * 0.5).sin() * 1.1;
+        self.transition_to(CameraPose {
+            position: center,
+            distance: distance.clamp(0.1, 100000.0),
+            ..self.pose()
+        });
+    }
     
     /// Check if user is actively controlling camera
     pub fn is_controlling(&self) -> bool {
@@ -862,18 +997,8 @@ impl SceneCamera {
             // Current position is the TARGET/PIVOT point in 3D mode
             let target = self.position;
             
-            let yaw_rad = self.rotation.to_radians();
-            let pitch_rad = self.pitch.to_radians();
-            
-            // Calculate eye position based on distance, pitch, and yaw
-            // Standard spherical coordinate conversion
-            let eye_offset = Vec3::new(
-                self.distance * yaw_rad.cos() * pitch_rad.cos(),
-                self.distance * pitch_rad.sin(),
-                self.distance * yaw_rad.sin() * pitch_rad.cos()
-            );
-            
-            let eye = target + eye_offset;
+            // Eye position from distance, pitch, and yaw (spherical coordinates)
+            let eye = target + self.eye_offset();
             
             // Up vector depends on pitch to handle going over the top
             let up = Vec3::Y;
@@ -947,7 +1072,7 @@ impl SceneCamera {
     pub fn get_projection_matrix(&self, aspect_ratio: f32) -> Mat4 {
         if self.pitch.abs() > 0.1 || self.projection_mode == SceneProjectionMode::Perspective {
             // Perspective projection
-            Mat4::perspective_rh(45.0f32.to_radians(), aspect_ratio, 0.1, 1000.0)
+            Mat4::perspective_rh(FIELD_OF_VIEW.to_radians(), aspect_ratio, 0.1, 1000.0)
         } else {
             // Orthographic projection
             // Width/height depend on zoom
@@ -975,45 +1100,38 @@ impl SceneCamera {
 
     /// Set camera to back view
     pub fn set_view_back(&mut self) {
-        self.rotation = 180.0;
-        self.pitch = 0.0;
+        self.transition_to(CameraPose { rotation: 180.0, pitch: 0.0, ..self.pose() });
     }
 
     /// Set camera to front view
     pub fn set_view_front(&mut self) {
-        self.rotation = 0.0;
-        self.pitch = 0.0;
+        self.transition_to(CameraPose { rotation: 0.0, pitch: 0.0, ..self.pose() });
     }
 
     /// Set camera to left view
     pub fn set_view_left(&mut self) {
-        self.rotation = -90.0;
-        self.pitch = 0.0;
+        self.transition_to(CameraPose { rotation: -90.0, pitch: 0.0, ..self.pose() });
     }
 
     /// Set camera to right view
     pub fn set_view_right(&mut self) {
-        self.rotation = 90.0;
-        self.pitch = 0.0;
+        self.transition_to(CameraPose { rotation: 90.0, pitch: 0.0, ..self.pose() });
     }
 
     /// Set camera to bottom view
     pub fn set_view_bottom(&mut self) {
-        self.rotation = 0.0;
-        self.pitch = -90.0;
+        self.transition_to(CameraPose { rotation: 0.0, pitch: -90.0, ..self.pose() });
     }
 
     /// Set camera to top view
     pub fn set_view_top(&mut self) {
-        self.rotation = 0.0;
-        self.pitch = 90.0;
+        self.transition_to(CameraPose { rotation: 0.0, pitch: 90.0, ..self.pose() });
     }
 
     /// Set camera to perspective view
     pub fn set_view_perspective(&mut self) {
         self.projection_mode = SceneProjectionMode::Perspective;
-        self.rotation = 45.0;
-        self.pitch = 30.0;
+        self.transition_to(CameraPose { rotation: 45.0, pitch: 30.0, ..self.pose() });
     }
 
     /// Get zoom sensitivity
@@ -1041,6 +1159,7 @@ impl SceneCamera {
         self.pivot = Vec3::ZERO;
         self.projection_mode = SceneProjectionMode::Isometric;
         self.velocity = CameraVelocity::default();
+        self.transition = None;
         self.target_position = Vec3::ZERO;
         self.target_rotation = 0.0;
         self.target_pitch = 0.0;
@@ -1074,6 +1193,7 @@ impl SceneCamera {
 
     /// Switch to 2D mode
     pub fn switch_to_2d(&mut self) {
+        self.transition = None;
         self.projection_mode = SceneProjectionMode::Isometric;
         self.rotation = 0.0;
         self.pitch = 0.0;
@@ -1081,6 +1201,7 @@ impl SceneCamera {
 
     /// Switch to 3D mode
     pub fn switch_to_3d(&mut self) {
+        self.transition = None;
         self.projection_mode = SceneProjectionMode::Perspective;
         self.rotation = 45.0;
         self.pitch = 30.0;
@@ -27,6 +27,8 @@ pub struct EntityClipboardData {
     pub transform: Option<ecs::Transform>,
     pub sprite: Option<ecs::Sprite>,
     pub collider: Option<ecs::Collider>,
+    #[serde(default)]
+    pub rigidbody: Option<ecs::Rigidbody2D>,
     pub camera: Option<ecs::Camera>,
     pub mesh: Option<ecs::Mesh>,
     pub velocity: Option<(f32, f32)>,
@@ -44,6 +46,7 @@ impl EntityClipboardData {
             transform: world.transforms.get(&entity).cloned(),
             sprite: world.sprites.get(&entity).cloned(),
             collider: world.colliders.get(&entity).cloned(),
+            rigidbody: world.rigidbodies.get(&entity).cloned(),
             camera: world.cameras.get(&entity).cloned(),
             mesh: world.meshes.get(&entity).cloned(),
             velocity: world.velocities.get(&entity).copied(),
@@ -83,6 +86,9 @@ impl EntityClipboardData {
         if let Some(collider) = &self.collider {
             world.colliders.insert(entity, collider.clone());
         }
+        if let Some(rigidbody) = &self.rigidbody {
+            world.rigidbodies.insert(entity, rigidbody.clone());
+        }
         if let Some(camera) = &self.camera {
             world.cameras.insert(entity, camera.clone());
         }
@@ -234,62 +240,27 @@ impl Clipboard {
     // DUPLICATE OPERATIONS
     // ========================================================================
     
-    /// Duplicate single entity
+    /// Duplicate single entity (and its children) in place
     pub fn duplicate_entity(
         &self,
         entity: Entity,
         world: &mut World,
         entity_names: &mut HashMap<Entity, String>,
     ) -> Option<Entity> {
-        let entity_data = EntityClipboardData::from_world(entity, world, entity_names);
-        
-        let data = ClipboardData {
-            entities: vec![entity_data],
-            hierarchy: Vec::new(),
-        };
-        
-        // No offset - duplicate at same position
-        let new_entities = self.paste_data(&data, world, entity_names, None, Some("(Copy)"));
-        new_entities.first().copied()
+        duplicate_subtree(world, entity_names, entity).first().copied()
     }
     
-    /// Duplicate multiple entities
+    /// Duplicate multiple entities (and their children) in place; returns every new entity
     pub fn duplicate_entities(
         &self,
         entities: &[Entity],
         world: &mut World,
         entity_names: &mut HashMap<Entity, String>,
     ) -> Vec<Entity> {
-        if entities.is_empty() {
-            return Vec::new();
-        }
-        
-        let mut entity_data = Vec::new();
-        let mut hierarchy = Vec::new();
-        let mut entity_to_index = HashMap::new();
-        
-        // Capture entity data
-        for (index, &entity) in entities.iter().enumerate() {
-            entity_data.push(EntityClipboardData::from_world(entity, world, entity_names));
-            entity_to_index.insert(entity, index);
-        }
-        
-        // Capture hierarchy
-        for (index, &entity) in entities.iter().enumerate() {
-            if let Some(&parent) = world.parents.get(&entity) {
-                if let Some(&parent_index) = entity_to_index.get(&parent) {
-                    hierarchy.push((index, parent_index));
-                }
-            }
-        }
-        
-        let data = ClipboardData {
-            entities: entity_data,
-            hierarchy,
-            
-        };
-        
-        self.paste_data(&data, world, entity_names, Some([10.0, 10.0, 0.0]), Some("(Copy)"))
+        crate::tools::selection::top_level_selected(entities, world)
+            .into_iter()
+            .flat_map(|root| duplicate_subtree(world, entity_names, root))
+            .collect()
     }
     
     // ========================================================================
@@ -362,6 +333,42 @@ pub fn copy_selected(
     }
 }
 
+/// Deep-copy an entity and its descendants next to the original with every component
+/// (see `World::duplicate_subtree`), naming the copy "Enemy (1)" among its siblings.
+/// Returns the new entities, the root first.
+pub fn duplicate_subtree(
+    world: &mut World,
+    entity_names: &mut HashMap<Entity, String>,
+    entity: Entity,
+) -> Vec<Entity> {
+    let pairs = world.duplicate_subtree(entity);
+    let Some(&(_, root)) = pairs.first() else {
+        return Vec::new();
+    };
+    for (original, copy) in &pairs {
+        if let Some(name) = entity_names.get(original).cloned() {
+            entity_names.insert(*copy, name);
+        }
+    }
+
+    let name = entity_names
+        .get(&entity)
+        .or_else(|| world.names.get(&entity))
+        .cloned()
+        .unwrap_or_else(|| format!("Entity {}", entity));
+    let siblings: Vec<Entity> = match world.get_parent(root) {
+        Some(parent) => world.get_children(parent).to_vec(),
+        None => world.entities().into_iter().filter(|e| world.get_parent(*e).is_none()).collect(),
+    };
+    let name = ecs::hierarchy::numbered_name(&name, |candidate| {
+        siblings.iter().any(|s| entity_names.get(s).is_some_and(|n| n == candidate))
+    });
+    entity_names.insert(root, name.clone());
+    world.names.insert(root, name);
+
+    pairs.into_iter().map(|(_, copy)| copy).collect()
+}
+
 /// Paste from clipboard
 pub fn paste_from_clipboard(
     clipboard: &Clipboard,
@@ -383,15 +390,7 @@ pub fn duplicate_selected(
         return Vec::new();
     }
     
-    if selected.len() == 1 {
-        if let Some(entity) = clipboard.duplicate_entity(selected[0], world, entity_names) {
-            vec![entity]
-        } else {
-            Vec::new()
-        }
-    } else {
-        clipboard.duplicate_entities(selected, world, entity_names)
-    }
+    clipboard.duplicate_entities(selected, world, entity_names)
 }
 
 // ============================================================================
@@ -437,15 +436,12 @@ pub fn handle_clipboard_shortcuts(
         // Ctrl+D: Duplicate
         if i.modifiers.ctrl && i.key_pressed(egui::Key::D) {
             if !selected.is_empty() {
-                let new_entities = duplicate_selected(clipboard, selected, world, entity_names);
+                let roots = crate::tools::selection::top_level_selected(selected, world);
+                let command = crate::systems::undo::DuplicateEntitiesCommand::new("Duplicate", &roots, world, entity_names);
+                let new_entities = command.roots();
                 
                 if !new_entities.is_empty() {
-                    // Create undo command
-                    let mut batch = crate::systems::undo::BatchCommand::new("Duplicate");
-                    for &entity in &new_entities {
-                        batch.add(Box::new(crate::systems::undo::CreateEntityCommand::new(entity, world, entity_names)));
-                    }
-                    undo_stack.execute(Box::new(batch), world, entity_names);
+                    undo_stack.execute(Box::new(command), world, entity_names);
                     
                     return ClipboardAction::Duplicated(new_entities);
                 }
@@ -0,0 +1,286 @@
+//! Component Clipboard
+//!
+//! Copy / Paste of single components from the inspector's component menu (⋮).
+//! - Copy serializes the component to the editor's clipboard and puts the same JSON
+//!   on the OS clipboard, so another editor instance can paste it
+//! - Paste Values overwrites the component on every selected entity that has one,
+//!   Paste As New adds it to the ones that don't; either way one undo step
+//! - Transforms paste either their local values or the copied world position
+
+use ecs::{World, Entity};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use super::undo::{BatchCommand, ComponentKind, ModifyComponentCommand};
+
+/// Marks editor component JSON on the OS clipboard
+const CLIPBOARD_FORMAT: &str = "component";
+
+fn menu_request_id() -> egui::Id {
+    egui::Id::new("component_menu_request")
+}
+
+/// What was picked in a component header's menu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentMenuAction {
+    /// Copy the component of the primary selected entity
+    Copy(ComponentKind),
+    /// Paste onto every selected entity
+    Paste(ComponentKind, PasteMode),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PasteMode {
+    /// Overwrite the component where it exists (a Transform's local values)
+    Values,
+    /// Add the component where it is missing
+    AsNew,
+    /// Move to the copied Transform's world position, keeping rotation and scale
+    WorldPosition,
+}
+
+/// Ask the editor to run a component menu action after the inspector has drawn
+pub fn request(ctx: &egui::Context, action: ComponentMenuAction) {
+    // Stored as an Option: egui's remove_temp needs a Default type
+    ctx.data_mut(|d| d.insert_temp(menu_request_id(), Some(action)));
+}
+
+/// The component menu action picked this frame, if any
+pub fn take_request(ctx: &egui::Context) -> Option<ComponentMenuAction> {
+    ctx.data_mut(|d| d.remove_temp::<Option<ComponentMenuAction>>(menu_request_id())).flatten()
+}
+
+/// A copied component, as stored on the clipboard
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CopiedComponent {
+    format: String,
+    pub component: ComponentKind,
+    pub value: serde_json::Value,
+    /// World position of a copied Transform (for Paste World Position)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub world_position: Option<[f32; 3]>,
+}
+
+impl CopiedComponent {
+    /// Serialize `component` of `entity` (None if the entity doesn't have it)
+    pub fn capture(world: &World, entity_names: &HashMap<Entity, String>, entity: Entity, component: ComponentKind) -> Option<Self> {
+        let value = component.capture(world, entity_names, entity)?;
+        let world_position = match component {
+            ComponentKind::Transform => world.world_position(entity),
+            _ => None,
+        };
+        Some(Self { format: CLIPBOARD_FORMAT.to_string(), component, value, world_position })
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+
+    /// Parse clipboard text. Ok(None) when it isn't component JSON at all (some other
+    /// text was copied since), an error when it is but doesn't fit its component type.
+    pub fn from_json(text: &str) -> Result<Option<Self>, String> {
+        let Ok(json) = serde_json::from_str::<serde_json::Value>(text) else {
+            return Ok(None);
+        };
+        if json.get("format").and_then(|f| f.as_str()) != Some(CLIPBOARD_FORMAT) {
+            return Ok(None);
+        }
+        let copied: Self = serde_json::from_value(json).map_err(|e| format!("Clipboard component is malformed: {}", e))?;
+        copied.component.validate(&copied.value).map_err(|e| format!("Clipboard component is {}", e))?;
+        Ok(Some(copied))
+    }
+}
+
+/// Component copied from the inspector
+#[derive(Default)]
+pub struct ComponentClipboard {
+    copied: Option<CopiedComponent>,
+}
+
+impl ComponentClipboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keep a copy, and share it through the OS clipboard when there is one
+    pub fn copy(&mut self, copied: CopiedComponent) {
+        if let Ok(mut clipboard) = arboard::Clipboard::new() {
+            let _ = clipboard.set_text(copied.to_json());
+        }
+        self.copied = Some(copied);
+    }
+
+    /// Component to paste: one copied in another editor (on the OS clipboard) wins
+    /// over this editor's copy
+    pub fn contents(&self) -> Result<CopiedComponent, String> {
+        let shared = arboard::Clipboard::new().ok().and_then(|mut clipboard| clipboard.get_text().ok());
+        if let Some(text) = shared {
+            if let Some(copied) = CopiedComponent::from_json(&text)? {
+                return Ok(copied);
+            }
+        }
+        self.copied.clone().ok_or_else(|| "Nothing to paste: copy a component first".to_string())
+    }
+}
+
+/// Undo step pasting `copied` as `component` onto `targets` (apply it by executing it)
+pub fn paste_component(
+    copied: &CopiedComponent,
+    component: ComponentKind,
+    mode: PasteMode,
+    targets: &[Entity],
+    world: &World,
+    entity_names: &HashMap<Entity, String>,
+) -> Result<BatchCommand, String> {
+    if copied.component != component {
+        return Err(format!("Clipboard holds a {}, not a {}", copied.component.label(), component.label()));
+    }
+    component.validate(&copied.value).map_err(|e| format!("Clipboard component is {}", e))?;
+
+    let description = match mode {
+        PasteMode::Values => format!("Paste {} Values", component.label()),
+        PasteMode::AsNew => format!("Paste {} As New", component.label()),
+        PasteMode::WorldPosition => "Paste World Position".to_string(),
+    };
+    let mut batch = BatchCommand::new(description);
+
+    for &entity in targets {
+        let before = component.capture(world, entity_names, entity);
+        let after = match mode {
+            PasteMode::Values if before.is_some() => copied.value.clone(),
+            PasteMode::AsNew if before.is_none() => copied.value.clone(),
+            PasteMode::WorldPosition => {
+                let Some(position) = copied.world_position.filter(|_| component == ComponentKind::Transform) else {
+                    return Err("Paste World Position needs a copied Transform".to_string());
+                };
+                let Some(mut transform) = world.transforms.get(&entity).cloned() else {
+                    continue;
+                };
+                let local = world.parent_world_matrix(entity).inverse().transform_point3(glam::Vec3::from(position));
+                transform.position = local.to_array();
+                serde_json::to_value(transform).map_err(|e| e.to_string())?
+            }
+            _ => continue,
+        };
+        if before.as_ref() == Some(&after) {
+            continue;
+        }
+        let name = entity_names.get(&entity).cloned().unwrap_or_else(|| format!("Entity {}", entity));
+        batch.add(Box::new(ModifyComponentCommand::new(entity, name, component, before, Some(after))));
+    }
+
+    if batch.is_empty() {
+        return Err(match mode {
+            PasteMode::AsNew => format!("Every selected entity already has a {}", component.label()),
+            PasteMode::WorldPosition => "The selection is already at that position".to_string(),
+            PasteMode::Values if targets.iter().any(|&e| component.capture(world, entity_names, e).is_some()) => {
+                format!("The selection already has these {} values", component.label())
+            }
+            PasteMode::Values => format!("No selected entity has a {}", component.label()),
+        });
+    }
+    Ok(batch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::undo::{Command, UndoStack};
+
+    fn world_with_entities(count: usize) -> (World, HashMap<Entity, String>, Vec<Entity>) {
+        let mut world = World::new();
+        let mut names = HashMap::new();
+        let entities = (0..count)
+            .map(|i| {
+                let entity = world.spawn();
+                world.transforms.insert(entity, ecs::Transform::default());
+                names.insert(entity, format!("Entity{}", i));
+                entity
+            })
+            .collect();
+        (world, names, entities)
+    }
+
+    #[test]
+    fn test_paste_values_onto_selection_is_one_undo_step() {
+        let (mut world, mut names, entities) = world_with_entities(3);
+        let (source, targets) = (entities[0], &entities[1..]);
+        world.rigidbodies.insert(source, ecs::Rigidbody2D { gravity_scale: 3.0, ..Default::default() });
+        for &target in targets {
+            world.rigidbodies.insert(target, ecs::Rigidbody2D::default());
+        }
+        let mut undo_stack = UndoStack::new();
+
+        let copied = CopiedComponent::capture(&world, &names, source, ComponentKind::Rigidbody2D).unwrap();
+        let batch = paste_component(&copied, ComponentKind::Rigidbody2D, PasteMode::Values, targets, &world, &names).unwrap();
+        undo_stack.execute(Box::new(batch), &mut world, &mut names);
+        assert!(targets.iter().all(|t| world.rigidbodies[t].gravity_scale == 3.0));
+        assert_eq!(undo_stack.get_history(), vec!["Paste Rigidbody 2D Values".to_string()]);
+
+        undo_stack.undo(&mut world, &mut names);
+        assert!(targets.iter().all(|t| world.rigidbodies[t].gravity_scale == 1.0));
+    }
+
+    #[test]
+    fn test_paste_as_new_adds_only_where_missing() {
+        let (mut world, mut names, entities) = world_with_entities(3);
+        world.sprites.insert(entities[0], ecs::Sprite { texture_id: "hero".to_string(), ..Default::default() });
+        world.sprites.insert(entities[1], ecs::Sprite::default());
+
+        let copied = CopiedComponent::capture(&world, &names, entities[0], ComponentKind::Sprite).unwrap();
+        let mut batch = paste_component(&copied, ComponentKind::Sprite, PasteMode::AsNew, &entities[1..], &world, &names).unwrap();
+        assert_eq!(batch.len(), 1);
+        batch.execute(&mut world, &mut names);
+        assert_eq!(world.sprites[&entities[1]].texture_id, "");
+        assert_eq!(world.sprites[&entities[2]].texture_id, "hero");
+
+        let error = paste_component(&copied, ComponentKind::Sprite, PasteMode::AsNew, &entities[1..], &world, &names);
+        assert_eq!(error.err().as_deref(), Some("Every selected entity already has a Sprite"));
+    }
+
+    #[test]
+    fn test_mismatched_or_invalid_clipboard_is_an_error() {
+        let (mut world, names, entities) = world_with_entities(2);
+        world.rigidbodies.insert(entities[0], ecs::Rigidbody2D::default());
+        world.colliders.insert(entities[1], ecs::Collider::default());
+
+        let copied = CopiedComponent::capture(&world, &names, entities[0], ComponentKind::Rigidbody2D).unwrap();
+        let error = paste_component(&copied, ComponentKind::Collider, PasteMode::Values, &entities[1..], &world, &names);
+        assert_eq!(error.err().as_deref(), Some("Clipboard holds a Rigidbody 2D, not a Collider"));
+
+        // Shared JSON from another editor is checked against its component type
+        let mut json: serde_json::Value = serde_json::from_str(&copied.to_json()).unwrap();
+        json["value"] = serde_json::json!({ "velocity": "fast" });
+        let error = CopiedComponent::from_json(&json.to_string()).unwrap_err();
+        assert!(error.starts_with("Clipboard component is not a valid Rigidbody 2D"), "{}", error);
+
+        // Any other text on the OS clipboard is ignored
+        assert_eq!(CopiedComponent::from_json("hello"), Ok(None));
+        assert_eq!(CopiedComponent::from_json(&copied.to_json()), Ok(Some(copied)));
+    }
+
+    #[test]
+    fn test_transform_values_only_and_world_position() {
+        let (mut world, mut names, entities) = world_with_entities(3);
+        let (source, parent, child) = (entities[0], entities[1], entities[2]);
+        world.transforms.get_mut(&source).unwrap().position = [10.0, 5.0, 0.0];
+        world.transforms.get_mut(&source).unwrap().scale = [2.0, 2.0, 1.0];
+        world.transforms.get_mut(&parent).unwrap().position = [4.0, 0.0, 0.0];
+        world.set_parent(child, Some(parent));
+
+        let copied = CopiedComponent::capture(&world, &names, source, ComponentKind::Transform).unwrap();
+
+        // Values only: the child takes the local values as they are
+        let mut batch = paste_component(&copied, ComponentKind::Transform, PasteMode::Values, &[child], &world, &names).unwrap();
+        batch.execute(&mut world, &mut names);
+        assert_eq!(world.transforms[&child].position, [10.0, 5.0, 0.0]);
+        assert_eq!(world.transforms[&child].scale, [2.0, 2.0, 1.0]);
+
+        // World position: lands on the source in the world, keeping its own scale
+        world.transforms.insert(child, ecs::Transform::default());
+        let mut batch = paste_component(&copied, ComponentKind::Transform, PasteMode::WorldPosition, &[child], &world, &names).unwrap();
+        batch.execute(&mut world, &mut names);
+        assert_eq!(world.transforms[&child].position, [6.0, 5.0, 0.0]);
+        assert_eq!(world.world_position(child), Some([10.0, 5.0, 0.0]));
+        assert_eq!(world.transforms[&child].scale, [1.0, 1.0, 1.0]);
+    }
+}
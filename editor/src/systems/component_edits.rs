@@ -0,0 +1,165 @@
+//! Component Edit Recorder
+//!
+//! Turns direct component edits made through the editor UI (inspector fields,
+//! gizmo drags, renames) into undoable ModifyComponentCommands.
+//! - Snapshots the selected entities before the UI runs, diffs afterwards
+//! - Holds edits open while a drag or text field is active, so a whole drag
+//!   becomes a single undo step committed on release/focus loss
+
+use ecs::{World, Entity};
+use std::collections::{BTreeMap, HashMap};
+use super::undo::{BatchCommand, Command, ComponentKind, ModifyComponentCommand, UndoStack};
+
+/// Serialized components of the selection at the start of a frame
+pub struct EditSnapshot {
+    values: Vec<(Entity, ComponentKind, Option<serde_json::Value>)>,
+}
+
+#[derive(Default)]
+pub struct ComponentEditRecorder {
+    /// State before the edit for every (entity, component) changed by the ongoing interaction
+    pending: BTreeMap<(Entity, ComponentKind), Option<serde_json::Value>>,
+}
+
+impl ComponentEditRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Capture the entities the UI may edit this frame
+    pub fn snapshot(world: &World, entity_names: &HashMap<Entity, String>, entities: &[Entity]) -> EditSnapshot {
+        let values = entities
+            .iter()
+            .flat_map(|&entity| {
+                ComponentKind::ALL
+                    .iter()
+                    .map(move |&kind| (entity, kind, kind.capture(world, entity_names, entity)))
+            })
+            .collect();
+        EditSnapshot { values }
+    }
+
+    /// Diff the snapshot against the world. Edits are committed to the undo stack
+    /// once `interacting` (pointer held / widget focused) is false.
+    pub fn record(
+        &mut self,
+        world: &mut World,
+        entity_names: &mut HashMap<Entity, String>,
+        snapshot: EditSnapshot,
+        interacting: bool,
+        undo_stack: &mut UndoStack,
+    ) -> bool {
+        for (entity, kind, before) in snapshot.values {
+            if kind.capture(world, entity_names, entity) != before {
+                // Keep the oldest before-state so the whole drag is one step
+                self.pending.entry((entity, kind)).or_insert(before);
+            }
+        }
+
+        if interacting {
+            false
+        } else {
+            self.commit(world, entity_names, undo_stack)
+        }
+    }
+
+    /// Push all pending edits as one undo step. Returns true if anything was recorded.
+    pub fn commit(
+        &mut self,
+        world: &mut World,
+        entity_names: &mut HashMap<Entity, String>,
+        undo_stack: &mut UndoStack,
+    ) -> bool {
+        let mut commands: Vec<Box<dyn Command>> = Vec::new();
+        for ((entity, kind), before) in std::mem::take(&mut self.pending) {
+            let after = kind.capture(world, entity_names, entity);
+            if after == before {
+                continue; // Dragged back to where it started
+            }
+            let name = entity_names
+                .get(&entity)
+                .cloned()
+                .unwrap_or_else(|| format!("Entity {}", entity));
+            commands.push(Box::new(ModifyComponentCommand::new(entity, name, kind, before, after)));
+        }
+
+        let command: Box<dyn Command> = match commands.len() {
+            0 => return false,
+            1 => commands.pop().unwrap(),
+            count => {
+                let mut batch = BatchCommand::new(format!("Edit {} Components", count));
+                for command in commands {
+                    batch.add(command);
+                }
+                Box::new(batch)
+            }
+        };
+
+        // The world already holds the after-state; executing re-applies it as a no-op
+        undo_stack.execute(command, world, entity_names);
+        true
+    }
+
+    /// Forget edits in progress (scene reload, play mode)
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    pub fn has_pending(&self) -> bool {
+        !self.pending.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world_with_entity() -> (World, HashMap<Entity, String>, Entity) {
+        let mut world = World::new();
+        let mut names = HashMap::new();
+        let entity = world.spawn();
+        world.transforms.insert(entity, ecs::Transform::default());
+        names.insert(entity, "Player".to_string());
+        (world, names, entity)
+    }
+
+    #[test]
+    fn test_drag_is_one_undo_step() {
+        let (mut world, mut names, entity) = world_with_entity();
+        let mut recorder = ComponentEditRecorder::new();
+        let mut undo_stack = UndoStack::new();
+
+        // Three frames of dragging, then release
+        for frame in 1..=3 {
+            let snapshot = ComponentEditRecorder::snapshot(&world, &names, &[entity]);
+            world.transforms.get_mut(&entity).unwrap().position[0] = frame as f32;
+            recorder.record(&mut world, &mut names, snapshot, true, &mut undo_stack);
+        }
+        assert!(!undo_stack.can_undo());
+
+        let snapshot = ComponentEditRecorder::snapshot(&world, &names, &[entity]);
+        assert!(recorder.record(&mut world, &mut names, snapshot, false, &mut undo_stack));
+        assert_eq!(undo_stack.get_history().len(), 1);
+
+        undo_stack.undo(&mut world, &mut names);
+        assert_eq!(world.transforms[&entity].position[0], 0.0);
+        undo_stack.redo(&mut world, &mut names);
+        assert_eq!(world.transforms[&entity].position[0], 3.0);
+    }
+
+    #[test]
+    fn test_commands_for_deleted_entities_are_pruned() {
+        let (mut world, mut names, entity) = world_with_entity();
+        let mut recorder = ComponentEditRecorder::new();
+        let mut undo_stack = UndoStack::new();
+
+        let snapshot = ComponentEditRecorder::snapshot(&world, &names, &[entity]);
+        names.insert(entity, "Hero".to_string());
+        recorder.record(&mut world, &mut names, snapshot, false, &mut undo_stack);
+        assert!(undo_stack.can_undo());
+
+        world.despawn(entity);
+        assert!(!undo_stack.undo(&mut world, &mut names));
+        assert!(undo_stack.get_history().is_empty());
+    }
+}
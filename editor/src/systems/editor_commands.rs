@@ -0,0 +1,270 @@
+//! Editor Command Execution
+//!
+//! Runs the commands queued on `EditorState::commands` (by shortcuts, the command palette,
+//! menus or code) and keeps the generated commands, one per project prefab, registered.
+
+use crate::commands::{panel_command_id, Command, CREATE_PREFAB_PREFIX};
+use crate::states::EditorState;
+use crate::ui::dock_layout::{self, WINDOW_PANELS};
+use crate::ui::TransformTool;
+use ecs::Prefab;
+use script::ScriptEngine;
+
+pub struct EditorCommandSystem;
+
+impl EditorCommandSystem {
+    /// Run every queued command, in order
+    pub fn run_queued(egui_ctx: &egui::Context, editor_state: &mut EditorState, script_engine: &mut ScriptEngine) {
+        Self::sync_prefab_commands(editor_state);
+        for id in editor_state.commands.take_queued() {
+            Self::execute(&id, egui_ctx, editor_state, script_engine);
+        }
+    }
+
+    /// One "Create <prefab>" command per prefab file in the project
+    fn sync_prefab_commands(editor_state: &mut EditorState) {
+        let Some(project_path) = editor_state.current_project_path.as_ref() else {
+            editor_state.commands.sync_group(CREATE_PREFAB_PREFIX, Vec::new());
+            return;
+        };
+        let commands = editor_state
+            .prefab_manager
+            .available_files
+            .iter()
+            .map(|path| {
+                let relative = path.strip_prefix(project_path).unwrap_or(path);
+                let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("Prefab");
+                Command::new(
+                    format!("{}{}", CREATE_PREFAB_PREFIX, relative.to_string_lossy().replace('\\', "/")),
+                    format!("Create {}", name),
+                    "GameObject",
+                )
+            })
+            .collect();
+        editor_state.commands.sync_group(CREATE_PREFAB_PREFIX, commands);
+    }
+
+    /// "Tool: Move (W)", with whatever the command is currently bound to
+    fn with_shortcut(editor_state: &EditorState, id: &str, message: &str) -> String {
+        match editor_state.commands.shortcut_label(id) {
+            Some(label) => format!("{} ({})", message, label),
+            None => message.to_string(),
+        }
+    }
+
+    fn set_tool(editor_state: &mut EditorState, id: &str, tool: TransformTool, name: &str) {
+        editor_state.current_tool = tool;
+        let message = Self::with_shortcut(editor_state, id, &format!("Tool: {}", name));
+        editor_state.console.info(message);
+    }
+
+    /// Run one command now; unknown ids are reported on the console
+    pub fn execute(id: &str, egui_ctx: &egui::Context, editor_state: &mut EditorState, script_engine: &mut ScriptEngine) {
+        if let Some(relative) = id.strip_prefix(CREATE_PREFAB_PREFIX) {
+            Self::create_prefab(relative, editor_state);
+            return;
+        }
+        if let Some(tab) = WINDOW_PANELS.into_iter().find(|tab| panel_command_id(tab) == id) {
+            if editor_state.use_docking {
+                dock_layout::open_tab(&mut editor_state.dock_state, tab);
+            } else if tab == crate::ui::EditorTab::Animation {
+                crate::ui::animation_window::open(egui_ctx);
+            } else {
+                editor_state.console.warning(format!("{} is only available in the docking layout", tab.title()));
+            }
+            return;
+        }
+
+        match id {
+            "tool.view" => Self::set_tool(editor_state, id, TransformTool::View, "View"),
+            "tool.move" => Self::set_tool(editor_state, id, TransformTool::Move, "Move"),
+            "tool.rotate" => Self::set_tool(editor_state, id, TransformTool::Rotate, "Rotate"),
+            "tool.scale" => Self::set_tool(editor_state, id, TransformTool::Scale, "Scale"),
+            "scene.save" => {
+                if let Some(ref path) = editor_state.current_scene_path.clone() {
+                    if let Err(e) = editor_state.save_scene(path) {
+                        editor_state.console.error(format!("Failed to save: {}", e));
+                    } else {
+                        let message = Self::with_shortcut(editor_state, id, "Scene saved");
+                        editor_state.console.info(message);
+                        editor_state.autosave.reset(); // Reset auto-save timer
+                    }
+                } else {
+                    editor_state.console.warning("No scene to save. Use File → Save Scene As...".to_string());
+                }
+            }
+            "scene.revert" => editor_state.request_scene_revert(),
+            "app.exit" => editor_state.show_exit_dialog = true,
+            "edit.undo" if editor_state.ui_inspector.is_active() => {
+                // UI elements live in the UI manager, not the world undo stack
+                match editor_state.ui_inspector.undo(&mut editor_state.ui_manager) {
+                    Some(desc) => {
+                        let message = Self::with_shortcut(editor_state, id, &format!("Undo: {}", desc));
+                        editor_state.console.info(message);
+                    }
+                    None => editor_state.console.warning("Nothing to undo".to_string()),
+                }
+            }
+            "edit.redo" if editor_state.ui_inspector.is_active() => {
+                match editor_state.ui_inspector.redo(&mut editor_state.ui_manager) {
+                    Some(desc) => {
+                        let message = Self::with_shortcut(editor_state, id, &format!("Redo: {}", desc));
+                        editor_state.console.info(message);
+                    }
+                    None => editor_state.console.warning("Nothing to redo".to_string()),
+                }
+            }
+            "edit.undo" => {
+                if editor_state.undo_stack.undo(&mut editor_state.world, &mut editor_state.entity_names) {
+                    editor_state.scene_modified = true;
+                    let desc = editor_state.undo_stack.undo_description().map(|d| format!("Undo: {}", d));
+                    let message = Self::with_shortcut(editor_state, id, desc.as_deref().unwrap_or("Undo"));
+                    editor_state.console.info(message);
+                } else {
+                    editor_state.console.warning("Nothing to undo".to_string());
+                }
+            }
+            "edit.redo" => {
+                if editor_state.undo_stack.redo(&mut editor_state.world, &mut editor_state.entity_names) {
+                    editor_state.scene_modified = true;
+                    let desc = editor_state.undo_stack.redo_description().map(|d| format!("Redo: {}", d));
+                    let message = Self::with_shortcut(editor_state, id, desc.as_deref().unwrap_or("Redo"));
+                    editor_state.console.info(message);
+                } else {
+                    editor_state.console.warning("Nothing to redo".to_string());
+                }
+            }
+            "edit.copy" => editor_state.copy_selection(),
+            "edit.cut" => editor_state.cut_selection(),
+            "edit.paste" => editor_state.paste_clipboard(),
+            "edit.duplicate" => editor_state.duplicate_selection(),
+            "edit.delete" => editor_state.delete_selection(),
+            "edit.select_all" => {
+                editor_state.select_all_entities();
+                let message = Self::with_shortcut(editor_state, id, "Selected all entities");
+                editor_state.console.info(message);
+            }
+            "edit.deselect_all" => editor_state.set_selection(&[]),
+            "edit.command_palette" => crate::ui::command_palette::open(egui_ctx),
+            "edit.shortcuts" => crate::ui::shortcut_preferences::open(egui_ctx),
+            "edit.project_settings" => editor_state.show_project_settings = true,
+            "edit.validate_assets" => crate::ui::asset_validation::open(egui_ctx),
+            "scene.frame_selected" => crate::ui::scene_view::request_frame_selected(egui_ctx),
+            "view.toggle_grid" => {
+                editor_state.scene_grid.toggle();
+                let status = if editor_state.scene_grid.enabled { "ON" } else { "OFF" };
+                editor_state.console.info(format!("Grid: {}", status));
+            }
+            "view.toggle_gizmos" => {
+                let show = !(editor_state.show_colliders || editor_state.show_velocities || editor_state.show_debug_lines);
+                editor_state.show_colliders = show;
+                editor_state.show_velocities = show;
+                editor_state.show_debug_lines = show;
+                editor_state.console.info(format!("Gizmos: {}", if show { "ON" } else { "OFF" }));
+            }
+            "view.toggle_snapping" => {
+                editor_state.snap_settings.enabled = !editor_state.snap_settings.enabled;
+                editor_state.console.info(format!(
+                    "Snapping: {}",
+                    if editor_state.snap_settings.enabled { "ON" } else { "OFF" }
+                ));
+                let _ = editor_state.snap_settings.save();
+            }
+            "view.profiler" => {
+                // While a hierarchy search is active F3 cycles its matches instead
+                if editor_state.is_playing || !editor_state.hierarchy_search.is_active() {
+                    crate::ui::profiler_overlay::toggle();
+                }
+            }
+            "play.pause" => {
+                editor_state.toggle_pause();
+                script_engine.set_paused(editor_state.is_paused);
+            }
+            "play.step" => editor_state.request_step(),
+            "play.screenshot" => crate::game_view_renderer::request_capture(egui_ctx, crate::game_view_renderer::CaptureRequest::Screenshot),
+            "play.save_gif" => crate::game_view_renderer::request_capture(egui_ctx, crate::game_view_renderer::CaptureRequest::SaveGif),
+            "tilemap.reload" => Self::reload_selected_map(editor_state),
+            "tilemap.regenerate_colliders" => Self::regenerate_map_colliders(editor_state),
+            "tilemap.toggle_layer" => Self::toggle_selected_layer(editor_state),
+            "create.empty" => {
+                let entity = Prefab::new("GameObject").spawn(&mut editor_state.world);
+                editor_state.entity_names.insert(entity, format!("GameObject {}", entity));
+                editor_state.record_spawned(entity);
+            }
+            "create.player" => {
+                let entity = Prefab::player().spawn(&mut editor_state.world);
+                editor_state.entity_names.insert(entity, "Player".to_string());
+                editor_state.record_spawned(entity);
+            }
+            "create.item" => {
+                let entity = Prefab::item().spawn(&mut editor_state.world);
+                editor_state.entity_names.insert(entity, format!("Item {}", entity));
+                editor_state.record_spawned(entity);
+            }
+            "window.tile_palette" => crate::ui::tile_palette::open(egui_ctx),
+            "window.memory" => crate::ui::memory_panel::open(egui_ctx),
+            "window.globals" => crate::ui::globals_panel::open(egui_ctx),
+            "window.script_debugger" => crate::ui::script_debugger::open(egui_ctx),
+            _ => editor_state.console.warning(format!("Unknown editor command: {}", id)),
+        }
+    }
+
+    fn create_prefab(relative: &str, editor_state: &mut EditorState) {
+        let Some(project_path) = editor_state.current_project_path.clone() else {
+            editor_state.console.warning("Open a project to create prefabs".to_string());
+            return;
+        };
+        match editor_state.instantiate_prefab_file(&project_path.join(relative)) {
+            Ok(_) => editor_state.console.info(format!("Instantiated prefab {}", relative)),
+            Err(e) => editor_state.console.error(format!("Failed to instantiate prefab {}: {}", relative, e)),
+        }
+    }
+
+    fn reload_selected_map(editor_state: &mut EditorState) {
+        let Some(selected_map) = editor_state.map_manager.selected_map.clone() else {
+            editor_state.console.warning("No map selected to reload");
+            return;
+        };
+        match editor_state.map_manager.reload_map(&selected_map, &mut editor_state.world) {
+            Ok(()) => {
+                editor_state.console.info(format!("Reloaded map: {:?}", selected_map.file_name().unwrap_or_default()));
+                editor_state.scene_modified = true;
+            }
+            Err(e) => editor_state.console.error(format!("Failed to reload map: {}", e.display_message())),
+        }
+    }
+
+    fn regenerate_map_colliders(editor_state: &mut EditorState) {
+        let Some(selected_map) = editor_state.map_manager.selected_map.clone() else {
+            editor_state.console.warning("No map selected to regenerate colliders");
+            return;
+        };
+        match editor_state.map_manager.regenerate_colliders(&selected_map, &mut editor_state.world) {
+            Ok(count) => {
+                editor_state.console.info(format!("Regenerated {} colliders", count));
+                editor_state.scene_modified = true;
+            }
+            Err(e) => editor_state.console.error(format!("Failed to regenerate colliders: {}", e.display_message())),
+        }
+    }
+
+    fn toggle_selected_layer(editor_state: &mut EditorState) {
+        let Some(selected_entity) = editor_state.selected_entity else {
+            editor_state.console.warning("No layer selected to toggle visibility");
+            return;
+        };
+        let is_layer = editor_state
+            .map_manager
+            .loaded_maps
+            .values()
+            .any(|map| map.layer_entities.iter().any(|l| l.entity == selected_entity));
+        if !is_layer {
+            editor_state.console.warning("Selected entity is not a tilemap layer");
+            return;
+        }
+        editor_state.map_manager.toggle_layer_visibility(selected_entity, &mut editor_state.world);
+        let visible = editor_state.world.active.get(&selected_entity).copied().unwrap_or(true);
+        editor_state.console.info(format!("Layer visibility: {}", if visible { "ON" } else { "OFF" }));
+        editor_state.scene_modified = true;
+    }
+}
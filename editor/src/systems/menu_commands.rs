@@ -1,4 +1,6 @@
 use crate::states::EditorState;
+use crate::systems::replay::{self, ReplaySession};
+use engine::replay::{ReplayFile, ReplayPlayer, ReplayRecorder};
 use ecs::World;
 use script::ScriptEngine;
 #[cfg(feature = "rapier")]
@@ -20,7 +22,11 @@ impl MenuCommandSystem {
         new_scene_request: &mut bool,
         play_request: &mut bool,
         stop_request: &mut bool,
+        pause_request: &mut bool,
+        step_request: &mut bool,
         edit_script_request: &mut Option<String>,
+        input: &mut input::InputSystem,
+        fixed_time_step: f32,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         texture_manager: &mut render::TextureManager,
@@ -65,6 +71,7 @@ impl MenuCommandSystem {
         
         // New Scene
         if *new_scene_request {
+            editor_state.remember_scene_camera_pose();
             editor_state.world = World::new();
             editor_state.entity_names.clear();
             editor_state.selected_entity = None;
@@ -125,12 +132,59 @@ impl MenuCommandSystem {
              }
         }
 
+        // Play Replay: load the recorded scene, then play it in playback mode
+        if std::mem::take(&mut editor_state.replay.open_request) && !editor_state.is_playing {
+            let mut dialog = rfd::FileDialog::new().add_filter("Replay", &[engine::replay::REPLAY_EXTENSION]);
+            if let Some(project_path) = &editor_state.current_project_path {
+                dialog = dialog.set_directory(replay::replays_dir(project_path));
+            }
+            if let Some(path) = dialog.pick_file() {
+                match ReplayFile::load(&path) {
+                    Ok(file) => {
+                        let scene_path = replay::resolve_scene_path(editor_state.current_project_path.as_deref(), &file.scene_path);
+                        if let Err(e) = editor_state.load_scene(&scene_path, asset_loader) {
+                            editor_state.console.error(format!("Failed to load replay scene {:?}: {}", scene_path, e));
+                        } else {
+                            editor_state.current_scene_path = Some(scene_path);
+                            editor_state.replay.record_request = false;
+                            editor_state.replay.pending_playback = Some(file);
+                            *play_request = true;
+                        }
+                    }
+                    Err(e) => editor_state.console.error(format!("Failed to load replay {:?}: {}", path, e)),
+                }
+            }
+        }
+
         // Play/Stop
         if *play_request {
-            if !editor_state.is_playing {
+            if !editor_state.is_playing && editor_state.wait_for_scene_preload() {
+                 // Play is re-requested each frame until the scene's preload list has loaded
+            } else if !editor_state.is_playing {
                  // Start playing
                  editor_state.is_playing = true;
+                 editor_state.is_paused = false;
+                 editor_state.step_requested = false;
+                 editor_state.play_frame = 0;
+                 script_engine.set_paused(false);
+                 // Time.time / frame_count / time_scale start over each session
+                 script_engine.reset_time();
+                 editor_state.play_changes.begin(&editor_state.world);
+                 // Scripts and Scene.load change these while playing; unsaved edits come back on Stop
+                 editor_state.edit_scene_settings = Some(editor_state.world.scene_settings.clone());
+                 editor_state.edit_recorder.clear();
+                 editor_state.pending_play_changes.clear();
                  editor_state.console.info("▶ Starting Play Mode...".to_string());
+                 // Baseline for the leak report shown when play mode stops
+                 editor_state.memory_panel.begin_play();
+                 editor_state.script_budget.reset();
+
+                 // Seed math.random before any script runs: replays reuse the recorded seed
+                 let seed = match &editor_state.replay.pending_playback {
+                     Some(file) => file.seed,
+                     None => engine_core::random::EngineRng::from_time().seed(),
+                 };
+                 script_engine.set_random_seed(seed);
 
                  // Process GLTF assets (same as scene loading)
                  if let Some(project_path) = &editor_state.current_project_path {
@@ -147,8 +201,54 @@ impl MenuCommandSystem {
                      );
                  }
 
-                 // Load scripts (same as Player binary)
-                 // Load scripts (same as Player binary)
+                 // Load persistent save data before scripts run Awake()
+                 if let Some(project_path) = &editor_state.current_project_path {
+                     let project_name = engine_core::project::ProjectManager::get_project_name(project_path);
+                     if let Err(e) = script_engine.open_save_data(&project_name) {
+                         editor_state.console.error(format!("Failed to load save data: {}", e));
+                     }
+                 }
+
+                 // Project settings are re-read so edits apply to this session
+                 if let Some(project_path) = &editor_state.current_project_path {
+                     editor_state.project_settings = engine_core::project_settings::ProjectSettings::load(project_path);
+                 }
+
+                 // Action mapping from the project's input.json (Project Settings > Input)
+                 input.set_ui_blocks_game_input(editor_state.project_settings.input.ui_blocks_game_input);
+                 if let Some(project_path) = &editor_state.current_project_path {
+                     let action_map = project_path.join(&editor_state.project_settings.input.action_map);
+                     let config = if action_map.exists() { input::InputConfig::load(&action_map) } else { Ok(input::InputConfig::default()) };
+                     match config {
+                         Ok(config) => {
+                             for (player, binding, actions) in config.find_conflicts() {
+                                 editor_state.console.warning(format!(
+                                     "Input: {} is bound to {} (player {})", binding, actions.join(", "), player + 1
+                                 ));
+                             }
+                             input.set_config(config);
+                         }
+                         Err(e) => editor_state.console.error(format!("Failed to load input config: {}", e)),
+                     }
+                 }
+
+                 // String tables from the project's localization/ folder
+                 if let Some(project_path) = &editor_state.current_project_path {
+                     match ui::Localization::load_for_project(project_path) {
+                         Ok(localization) => editor_state.ui_manager.set_localization(localization),
+                         Err(e) => editor_state.console.error(format!("Failed to load localization: {}", e)),
+                     }
+                     // UIText fonts are asset paths ("fonts/Title.ttf")
+                     editor_state.ui_manager.set_font_base_path(project_path.join("assets"));
+                 }
+
+                 // Load scripts (same as Player binary); require() reads modules from the project
+                 // so edits to them are picked up while playing
+                 script_engine.configure_modules(
+                     editor_state.current_project_path.as_deref(),
+                     &editor_state.project_settings.scripting.lib_paths,
+                 );
+                 script_engine.set_vec2_values(editor_state.project_settings.scripting.vec2_values);
                  if editor_state.current_project_path.is_some() {
                      if let Err(e) = engine::runtime::script_loader::load_all_scripts(&mut editor_state.world, script_engine) {
                          editor_state.console.error(format!("Failed to load scripts: {}", e));
@@ -162,38 +262,136 @@ impl MenuCommandSystem {
                                  editor_state.console.error(format!("Script start error for entity {:?}: {}", entity, e));
                              }
                          }
+                         editor_state.memory_panel.scripts_started(script_engine, &editor_state.entity_names);
                      }
                  }
+
+                 // Scene.load paths are project-relative, like the scene that is playing
+                 let scene_path = editor_state.current_scene_path.as_deref()
+                     .map(|path| replay::scene_path_for_replay(editor_state.current_project_path.as_deref(), path))
+                     .unwrap_or_default();
+                 editor_state.scene_manager.begin(&scene_path, &editor_state.world);
                  
-                 // Initialize physics
+                 // Lua debug_draw_* go straight to the scene view's queue
+                 script_engine.set_debug_draw(editor_state.debug_draw.handle());
+
+                 // Initialize physics (scripts' Physics queries read the layer matrix)
+                 script_engine.sync_physics_settings(&editor_state.project_settings.physics);
                  #[cfg(feature = "rapier")]
                  {
                      if let Some(rapier_world) = physics.downcast_mut::<RapierPhysicsWorld>() {
+                         // Fresh simulation: no bodies or touching pairs left from the last session
+                         *rapier_world = RapierPhysicsWorld::new();
+                         engine::runtime::physics_system::apply_rapier_physics_settings(rapier_world, &editor_state.project_settings.physics);
+                         engine::runtime::physics_system::apply_rapier_scene_gravity(rapier_world, &editor_state.project_settings.physics, &editor_state.world);
                          rapier_world.sync_from_ecs(&editor_state.world);
                          editor_state.console.info("Physics (Rapier) initialized".to_string());
                      }
                  }
                  #[cfg(not(feature = "rapier"))]
                  {
-                     if let Some(_simple_world) = physics.downcast_mut::<PhysicsWorld>() {
+                     if let Some(simple_world) = physics.downcast_mut::<PhysicsWorld>() {
+                         *simple_world = PhysicsWorld::new();
+                         engine::runtime::physics_system::apply_physics_settings(simple_world, &editor_state.project_settings.physics);
+                         engine::runtime::physics_system::apply_scene_gravity(simple_world, &editor_state.project_settings.physics, &editor_state.world);
                          editor_state.console.info("Physics (Simple) initialized".to_string());
                      }
                  }
+
+                 // Replay session starts with the first simulated frame
+                 editor_state.replay.clock = Default::default();
+                 if let Some(file) = editor_state.replay.pending_playback.take() {
+                     editor_state.console.info(format!("🎞 Playing replay ({} frames, seed {})", file.frame_count, file.seed));
+                     editor_state.replay.session = Some(ReplaySession::Playing(ReplayPlayer::start(file, input)));
+                 } else if std::mem::take(&mut editor_state.replay.record_request) {
+                     let scene_path = editor_state.current_scene_path.as_deref()
+                         .map(|path| replay::scene_path_for_replay(editor_state.current_project_path.as_deref(), path))
+                         .unwrap_or_default();
+                     editor_state.console.info(format!("⏺ Recording replay (seed {})", seed));
+                     editor_state.replay.session = Some(ReplaySession::Recording(ReplayRecorder::start(scene_path, seed, fixed_time_step, input)));
+                 }
             }
         }
 
         if *stop_request {
             if editor_state.is_playing {
                  editor_state.is_playing = false;
-                 editor_state.console.info("⏹ Stopping Play Mode...".to_string());
+                 // Release the preloaded assets (freed once nothing else holds a handle)
+                 editor_state.scene_preload = None;
+                 editor_state.is_paused = false;
+                 editor_state.step_requested = false;
+                 script_engine.set_paused(false);
+                 editor_state.console.info(format!("⏹ Stopping Play Mode (frame {})...", editor_state.play_frame));
+
+                 // Persist anything scripts changed but did not explicitly Save.save()
+                 if let Err(e) = script_engine.flush_save_data() {
+                     editor_state.console.error(format!("Failed to write save data: {}", e));
+                 }
+
+                 // Measure Lua states while this session's script data is still alive
+                 editor_state.memory_panel.stopping_play(script_engine, &editor_state.entity_names);
+
+                 // Pending timers/tweens belong to this play session only
+                 script_engine.clear_timers();
+                 script_engine.clear_pools();
+                 script_engine.clear_scene_preloads();
+                 // Globals survive scene loads, not the end of play mode
+                 script_engine.clear_global_state();
+                 // And its debug draws, however long they were meant to stay
+                 editor_state.debug_draw.clear();
+                 // So does a script stopped at a breakpoint
+                 script_engine.clear_debug_session();
+                 editor_state.script_debugger.paused_by_debugger = false;
+                 editor_state.scene_manager.reset();
+
+                 match editor_state.replay.session.take() {
+                     Some(ReplaySession::Recording(recorder)) => {
+                         let file = recorder.finish(input);
+                         match &editor_state.current_project_path {
+                             Some(project_path) => {
+                                 let path = replay::new_replay_path(project_path, &file.scene_path);
+                                 match file.save(&path) {
+                                     Ok(()) => editor_state.console.info(format!("⏺ Replay saved ({} frames): {:?}", file.frame_count, path)),
+                                     Err(e) => editor_state.console.error(format!("Failed to save replay: {}", e)),
+                                 }
+                             }
+                             None => editor_state.console.warning("Replay not saved: no project open".to_string()),
+                         }
+                     }
+                     Some(ReplaySession::Playing(player)) => player.finish(input),
+                     None => {}
+                 }
                  
+                 // Capture user edits from the play world before it is thrown away
+                 let runtime_changes = editor_state.play_changes.collect_changes(&editor_state.world, &editor_state.entity_names);
+                 editor_state.play_changes.clear();
+
                  // Reload scene to reset state
                  if let Some(path) = editor_state.current_scene_path.clone() {
                       if let Err(e) = editor_state.load_scene(&path, asset_loader) {
                            editor_state.console.error(format!("Failed to reload scene after stop: {}", e));
+                      } else if !runtime_changes.is_empty() {
+                           // Offer to copy the edits onto the restored scene
+                           editor_state.pending_play_changes = runtime_changes;
                       }
                  }
+                 if let Some(scene_settings) = editor_state.edit_scene_settings.take() {
+                      editor_state.world.scene_settings = scene_settings;
+                 }
+
+                 // Anything still grown after the scene is restored was held past the session
+                 editor_state.memory_panel.end_play(&mut editor_state.console);
             }
         }
+
+        if *pause_request {
+            editor_state.toggle_pause();
+            script_engine.set_paused(editor_state.is_paused);
+        }
+
+        // Step is only meaningful while paused; PlayModeSystem consumes the flag
+        if *step_request {
+            editor_state.request_step();
+        }
     }
 }
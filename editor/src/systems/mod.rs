@@ -2,5 +2,10 @@ pub mod camera;
 pub mod undo;
 pub mod clipboard;
 pub mod play_mode;
+pub mod play_mode_changes;
+pub mod component_edits;
+pub mod component_clipboard;
 pub mod menu_commands;
+pub mod editor_commands;
+pub mod replay;
 pub mod generators;
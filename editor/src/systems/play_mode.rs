@@ -1,6 +1,7 @@
 use engine_core::EngineContext;
-use physics::PhysicsWorld;
+use engine_core::frame_clock::fixed_steps;
 use crate::states::EditorState;
+use crate::systems::replay::ReplaySession;
 use script::ScriptEngine;
 #[cfg(feature = "rapier")]
 use physics::rapier_backend::RapierPhysicsWorld;
@@ -23,194 +24,510 @@ impl PlayModeSystem {
         if !editor_state.is_playing {
             return;
         }
+        profiler::profile_scope!("play_mode.update");
+        Self::check_script_budget(editor_state);
 
         // Update gamepads (but don't clear input yet - scripts need to read it first)
         ctx.input.update_gamepads();
-        
-        // Update debug draw system
-        editor_state.debug_draw.update(dt);
 
-        // Update ground states for Rapier (before running scripts)
-        #[cfg(feature = "rapier")]
-        {
-            if let Some(rapier_world) = physics.downcast_mut::<RapierPhysicsWorld>() {
-                let entities_with_rigidbodies: Vec<_> = editor_state.world.rigidbodies.keys().cloned().collect();
-                for entity in entities_with_rigidbodies {
-                    // Cast ray 0.15 units down from player center
-                    let is_grounded = rapier_world.raycast_ground(entity, &editor_state.world, 0.15);
-                    script_engine.set_ground_state(entity, is_grounded);
-                    
-                    // Debug draw raycast
-                    if let Some(transform) = editor_state.world.transforms.get(&entity) {
-                        let collider_half_height = if let Some(collider) = editor_state.world.colliders.get(&entity) {
-                            collider.get_world_height(transform.scale[1]) / 2.0
-                        } else {
-                            0.0
-                        };
-                        
-                        let ray_start = [
-                            transform.position[0],
-                            transform.position[1] - collider_half_height,
-                            transform.position[2],
-                        ];
-                        let ray_end = [
-                            transform.position[0],
-                            transform.position[1] - collider_half_height - 0.15,
-                            transform.position[2],
-                        ];
-                        
-                        // Green if grounded, Red if not
-                        if is_grounded {
-                            editor_state.debug_draw.draw_line_green(ray_start, ray_end, 0.0);
-                        } else {
-                            editor_state.debug_draw.draw_line_red(ray_start, ray_end, 0.0);
-                        }
+        Self::update_script_debugger(editor_state, ctx, script_engine);
+
+        // Paused: the editor keeps rendering and accepts camera/selection input,
+        // but scripts, physics and animations are frozen. A step request advances
+        // exactly one fixed timestep and leaves the physics accumulator untouched.
+        script_engine.set_paused(editor_state.is_paused);
+        let stepping = editor_state.is_paused && editor_state.step_requested;
+
+        // Recording / playing a replay: whole fixed-dt frames only
+        if let Some(session) = editor_state.replay.session.take() {
+            Self::update_replay(session, editor_state, ctx, script_engine, physics, physics_accumulator, fixed_time_step, dt, stepping);
+            return;
+        }
+
+        if editor_state.is_paused && !stepping {
+            ctx.input.begin_frame();
+            return;
+        }
+        // Scripts, physics and animations run on game time (Time.time_scale); the editor
+        // UI keeps the real frame time
+        let dt = script_engine.advance_time(if stepping { fixed_time_step } else { dt });
+
+        Self::run_frame(editor_state, ctx, script_engine, physics, physics_accumulator, fixed_time_step, dt, stepping);
+
+        if stepping {
+            editor_state.step_requested = false;
+            editor_state.console.info(format!("⏭ Step → frame {}", editor_state.play_frame));
+        }
+    }
+
+    /// Run the Lua typed into the console since last frame. Called at the script sync point:
+    /// right after update_scripts while the game runs, from the editor frame otherwise.
+    pub fn run_console_code(editor_state: &mut EditorState, input: &input::InputSystem, script_engine: &mut ScriptEngine) {
+        let requests = editor_state.console.take_repl_requests();
+        if requests.is_empty() {
+            return;
+        }
+        for request in requests {
+            let lines = script_engine.run_repl(&request.code, request.target, &mut editor_state.world, input);
+            editor_state.console.repl_output(lines);
+        }
+        // Nothing else applies what the code queued while the game isn't running
+        if !editor_state.is_playing || editor_state.is_paused {
+            script_engine.apply_pending_destroys(&mut editor_state.world);
+            for error in engine::runtime::script_system::apply_ui_commands(script_engine, &mut editor_state.ui_manager) {
+                editor_state.console.error(error.to_string());
+            }
+        }
+    }
+
+    /// Scripted entities the console can run code in, by name
+    pub fn console_targets(editor_state: &EditorState, script_engine: &ScriptEngine) -> Vec<(ecs::Entity, String)> {
+        if !editor_state.is_playing {
+            return Vec::new();
+        }
+        let mut targets: Vec<(ecs::Entity, String)> = editor_state.world.scripts.iter()
+            .filter(|(entity, _)| script_engine.has_entity_state(**entity))
+            .map(|(entity, script)| {
+                let name = editor_state.entity_names.get(entity).cloned().unwrap_or_else(|| format!("Entity {}", entity));
+                (*entity, format!("{} ({})", name, script.script_name))
+            })
+            .collect();
+        targets.sort();
+        targets
+    }
+
+    /// Warn once per script about scripts staying over the project's script time budget
+    /// (settings.json scripting section), using the previous frame's timings
+    fn check_script_budget(editor_state: &mut EditorState) {
+        let scripting = &editor_state.project_settings.scripting;
+        let Some(budget) = scripting.budget() else {
+            return;
+        };
+        editor_state.script_budget.budget = budget;
+        editor_state.script_budget.frames = scripting.budget_frames;
+        for timing in editor_state.script_budget.update(&profiler::scripts::last_frame()) {
+            let message = editor_state.script_budget.warning(&timing);
+            editor_state.console.warning(format!("⏱ {}", message));
+        }
+    }
+
+    /// Continue an Update released by a debugger command, then keep play mode paused
+    /// for as long as a script is stopped at a breakpoint
+    fn update_script_debugger(editor_state: &mut EditorState, ctx: &EngineContext, script_engine: &mut ScriptEngine) {
+        if let Some(entity) = script_engine.take_debug_resume() {
+            engine::runtime::script_system::resume_suspended_script(script_engine, &mut editor_state.world, &ctx.input, entity);
+        }
+
+        if let Some(hit) = script_engine.take_debug_hit() {
+            let name = editor_state.entity_names.get(&hit.entity).cloned().unwrap_or_else(|| format!("Entity {}", hit.entity));
+            if hit.suspended {
+                editor_state.console.info(format!("🐞 Stopped at {}:{} ({})", hit.file, hit.line, name));
+            } else {
+                editor_state.console.warning(format!(
+                    "🐞 Breakpoint {}:{} ({}) hit outside Update; only Update can stop, so execution continued",
+                    hit.file, hit.line, name
+                ));
+            }
+        }
+
+        let stopped = script_engine.debug_suspended_entity().is_some();
+        if stopped && !editor_state.is_paused {
+            editor_state.is_paused = true;
+            editor_state.step_requested = false;
+            editor_state.script_debugger.paused_by_debugger = true;
+        } else if !stopped && editor_state.script_debugger.paused_by_debugger {
+            editor_state.is_paused = false;
+            editor_state.script_debugger.paused_by_debugger = false;
+        }
+    }
+
+    /// Run the frames a replay session owes for this update. Each frame uses the
+    /// replay's fixed dt and exactly one physics step, independent of real frame time.
+    #[allow(clippy::too_many_arguments)]
+    fn update_replay(
+        mut session: ReplaySession,
+        editor_state: &mut EditorState,
+        ctx: &mut EngineContext,
+        script_engine: &mut ScriptEngine,
+        physics: &mut dyn std::any::Any,
+        physics_accumulator: &mut f32,
+        fixed_time_step: f32,
+        dt: f32,
+        stepping: bool,
+    ) {
+        let (fixed_dt, speed) = match &session {
+            ReplaySession::Playing(player) => (player.replay().fixed_dt, player.speed),
+            ReplaySession::Recording(_) => (fixed_time_step, 1.0),
+        };
+        // No begin_frame while paused: input arriving now belongs to the next frame
+        let frames = if stepping {
+            1
+        } else if editor_state.is_paused {
+            0
+        } else {
+            editor_state.replay.clock.advance(dt, fixed_dt, speed)
+        };
+
+        let mut finished = false;
+        for _ in 0..frames {
+            match &mut session {
+                ReplaySession::Recording(recorder) => recorder.begin_frame(&mut ctx.input),
+                ReplaySession::Playing(player) => player.begin_frame(&mut ctx.input),
+            }
+
+            // Time.time / Time.frame_count follow the replay's frames
+            script_engine.advance_time(fixed_dt);
+            Self::run_frame(editor_state, ctx, script_engine, physics, physics_accumulator, fixed_dt, fixed_dt, true);
+
+            match &mut session {
+                ReplaySession::Recording(recorder) => recorder.end_frame(&editor_state.world),
+                ReplaySession::Playing(player) => {
+                    if let Some(divergence) = player.end_frame(&editor_state.world) {
+                        editor_state.console.warning(format!(
+                            "⚠ Replay desync at frame {}: transform hash {:016x}, recorded {:016x}. \
+                             Check scripts for randomness outside math.random or frame-time dependent logic.",
+                            divergence.frame, divergence.actual, divergence.expected
+                        ));
+                    }
+                    if player.is_finished() {
+                        finished = true;
+                        break;
                     }
                 }
             }
         }
-        
+
+        if stepping {
+            editor_state.step_requested = false;
+            editor_state.console.info(format!("⏭ Step → frame {}", editor_state.play_frame));
+        }
+
+        match session {
+            ReplaySession::Playing(player) if finished => {
+                let diverged = player.divergence().is_some();
+                player.finish(&mut ctx.input);
+                editor_state.is_paused = true;
+                script_engine.set_paused(true);
+                editor_state.console.info(format!(
+                    "🎞 Replay finished at frame {}{} - paused",
+                    editor_state.play_frame,
+                    if diverged { " (desynced)" } else { "" }
+                ));
+            }
+            session => editor_state.replay.session = Some(session),
+        }
+    }
+
+    /// Simulate one frame: scripts, UI commands, physics, collision callbacks.
+    /// `single_step` runs exactly one physics step of `fixed_time_step` instead of
+    /// accumulating `dt`.
+    #[allow(clippy::too_many_arguments)]
+    fn run_frame(
+        editor_state: &mut EditorState,
+        ctx: &mut EngineContext,
+        script_engine: &mut ScriptEngine,
+        physics: &mut dyn std::any::Any,
+        physics_accumulator: &mut f32,
+        fixed_time_step: f32,
+        dt: f32,
+        single_step: bool,
+    ) {
+        // Update debug draw system (expire old draws, pass the Gizmos options to physics)
+        editor_state.debug_draw.update(dt);
+        #[cfg(feature = "rapier")]
+        if let Some(rapier_world) = physics.downcast_mut::<RapierPhysicsWorld>() {
+            editor_state.debug_draw.configure_physics(&mut rapier_world.debug_draw);
+        }
+        #[cfg(not(feature = "rapier"))]
+        if let Some(simple_world) = physics.downcast_mut::<PhysicsWorld>() {
+            editor_state.debug_draw.configure_physics(&mut simple_world.debug_draw);
+        }
+
         // Run scripts FIRST (before physics) so they can set velocities
         // Use the same script system as Player binary for consistency
+        engine::runtime::script_system::sync_ui(script_engine, &mut editor_state.ui_manager);
         engine::runtime::script_system::update_scripts(script_engine, &mut editor_state.world, &ctx.input, dt);
+        Self::run_console_code(editor_state, &ctx.input, script_engine);
+        engine::runtime::script_system::apply_input_commands(script_engine, &mut ctx.input);
+        #[cfg(feature = "rapier")]
+        if let Some(rapier_world) = physics.downcast_mut::<RapierPhysicsWorld>() {
+            engine::runtime::physics_system::apply_rapier_character_moves(rapier_world, script_engine, &mut editor_state.world);
+        }
+        #[cfg(not(feature = "rapier"))]
+        engine::runtime::physics_system::apply_character_moves(script_engine, &mut editor_state.world);
 
-        // Transfer debug lines from script engine to debug_draw manager
-        let script_debug_lines = script_engine.take_debug_lines();
-        for line in script_debug_lines {
-            // Convert script DebugLine to editor DebugLine
-            let color = egui::Color32::from_rgba_premultiplied(
-                (line.color[0] * 255.0) as u8,
-                (line.color[1] * 255.0) as u8,
-                (line.color[2] * 255.0) as u8,
-                (line.color[3] * 255.0) as u8,
-            );
-            editor_state.debug_draw.draw_line(line.start, line.end, color, line.duration);
+        // Engine modules, stage by stage (FixedUpdate at the physics timestep)
+        ctx.fixed_timestep = fixed_time_step;
+        ctx.update(dt);
+
+        // UI commands from Lua scripts, in order, laid out in one pass
+        for error in engine::runtime::script_system::apply_ui_commands(script_engine, &mut editor_state.ui_manager) {
+            editor_state.console.error(error.to_string());
+        }
+        for warning in editor_state.ui_manager.take_warnings() {
+            editor_state.console.warning(warning);
+        }
+
+        if single_step {
+            Self::step_physics(physics, fixed_time_step, &mut editor_state.world);
+        } else {
+            // Fixed timestep physics on the accumulated game time (capped against a spiral of death)
+            let steps = fixed_steps(physics_accumulator, dt, fixed_time_step, engine_core::MAX_FIXED_STEPS_PER_FRAME);
+            for _ in 0..steps {
+                Self::step_physics(physics, fixed_time_step, &mut editor_state.world);
+            }
         }
         
-        // Process UI commands from Lua scripts
-        let ui_commands = script_engine.take_ui_commands();
-        for command in ui_commands {
-            use script::UICommand;
-            match command {
-                UICommand::LoadPrefab { path } => {
-                    if let Err(e) = editor_state.ui_manager.load_prefab(&path) {
-                        editor_state.console.error(format!("Failed to load prefab '{}': {}", path, e));
+        // Collision enter/stay/exit from this frame's physics steps
+        for event in Self::take_collision_events(physics) {
+            for (entity, other) in [(event.entity, event.other), (event.other, event.entity)] {
+                if let Some(script) = editor_state.world.scripts.get(&entity).filter(|s| s.enabled) {
+                    let script_name = script.script_name.clone();
+                    if let Err(e) = script_engine.call_collision(entity, other, event.phase, &mut editor_state.world) {
+                        editor_state.console.error(format!("Collision error {}: {}", script_name, e));
                     }
                 }
-                UICommand::ActivatePrefab { path, instance_name } => {
-                    if let Err(e) = editor_state.ui_manager.activate_prefab(&path, &instance_name) {
-                        editor_state.console.error(format!("Failed to activate prefab '{}': {}", path, e));
-                    }
-                }
-                UICommand::DeactivatePrefab { instance_name } => {
-                    editor_state.ui_manager.deactivate_prefab(&instance_name);
-                }
-                UICommand::SetText { element_path, text } => {
-                    editor_state.ui_manager.set_ui_data(&element_path, text);
-                }
-                UICommand::SetImageFill { element_path, fill_amount } => {
-                    if let Some((instance, element)) = element_path.split_once('/') {
-                        if let Err(e) = editor_state.ui_manager.set_element_fill(instance, element, fill_amount) {
-                            editor_state.console.error(format!("Failed to set fill: {}", e));
-                        }
-                    }
+            }
+        }
+
+        // Trigger zone enter/exit (zone scripts and the event bus)
+        for event in Self::take_trigger_zone_events(physics) {
+            if let Err(e) = script_engine.call_trigger_zone(&event, &mut editor_state.world) {
+                editor_state.console.error(format!("Trigger zone error {}: {}", event.zone, e));
+            }
+        }
+
+        // Sprite animations (skips entities inactive in the hierarchy)
+        let animation_events = engine::runtime::animation_system::update_animated_sprites(&mut editor_state.world, dt);
+        for (entity, event) in animation_events {
+            if let Some(script) = editor_state.world.scripts.get(&entity).filter(|s| s.enabled) {
+                let script_name = script.script_name.clone();
+                if let Err(e) = script_engine.call_animation_event(entity, &event, &mut editor_state.world) {
+                    editor_state.console.error(format!("Animation event error {}: {}", script_name, e));
                 }
-                UICommand::SetColor { element_path, r, g, b, a } => {
-                    if let Some((instance, element)) = element_path.split_once('/') {
-                        if let Err(e) = editor_state.ui_manager.set_element_color(instance, element, r, g, b, a) {
-                            editor_state.console.error(format!("Failed to set color: {}", e));
-                        }
-                    }
+            }
+        }
+
+        // Sprite color fades (their on-complete may deactivate or destroy the entity)
+        let fade_completions = engine::runtime::animation_system::update_color_fades(&mut editor_state.world, dt);
+        engine::runtime::script_system::apply_fade_completions(script_engine, &mut editor_state.world, fade_completions);
+
+        // Scene switches requested by scripts this frame
+        Self::apply_scene_commands(editor_state, script_engine, physics);
+        // Sprites of the sheets scripts or a scene load added draw their named frames
+        editor_state.world.index_sprite_sheets();
+
+        // On-screen joystick/buttons follow the active UI, which also takes the clicks
+        // and typing aimed at it
+        editor_state.ui_manager.sync_virtual_controls(&mut ctx.input);
+        editor_state.ui_manager.sync_input_capture(&mut ctx.input);
+
+        // Clear per-frame input state AFTER scripts have run
+        ctx.input.begin_frame();
+
+        editor_state.play_frame += 1;
+    }
+
+    fn apply_scene_commands(editor_state: &mut EditorState, script_engine: &mut ScriptEngine, physics: &mut dyn std::any::Any) {
+        let transition = editor_state.scene_manager.process_commands(
+            script_engine,
+            &mut editor_state.world,
+            &mut editor_state.ui_manager,
+            &mut editor_state.texture_manager,
+        );
+        if transition.is_empty() {
+            return;
+        }
+
+        #[cfg(feature = "rapier")]
+        {
+            if let Some(rapier_world) = physics.downcast_mut::<RapierPhysicsWorld>() {
+                for &entity in &transition.destroyed {
+                    rapier_world.remove_entity(entity);
                 }
-                UICommand::ShowElement { element_path } => {
-                    if let Some((instance, element)) = element_path.split_once('/') {
-                        if let Err(e) = editor_state.ui_manager.show_element(instance, element) {
-                            editor_state.console.error(format!("Failed to show element: {}", e));
-                        }
-                    }
+                if transition.replaced {
+                    engine::runtime::physics_system::apply_rapier_scene_gravity(rapier_world, &editor_state.project_settings.physics, &editor_state.world);
                 }
-                UICommand::HideElement { element_path } => {
-                    if let Some((instance, element)) = element_path.split_once('/') {
-                        if let Err(e) = editor_state.ui_manager.hide_element(instance, element) {
-                            editor_state.console.error(format!("Failed to hide element: {}", e));
-                        }
-                    }
+            }
+        }
+        #[cfg(not(feature = "rapier"))]
+        {
+            if let Some(simple_world) = physics.downcast_mut::<PhysicsWorld>() {
+                if transition.replaced {
+                    engine::runtime::physics_system::apply_scene_gravity(simple_world, &editor_state.project_settings.physics, &editor_state.world);
                 }
             }
         }
 
-        // Accumulate frame time for fixed timestep physics
-        *physics_accumulator += dt;
-        
-        // Update physics with fixed timestep (may run multiple times per frame)
-        let mut physics_steps = 0;
-        while *physics_accumulator >= fixed_time_step {
-            #[cfg(feature = "rapier")]
-            {
-                if let Some(rapier_world) = physics.downcast_mut::<RapierPhysicsWorld>() {
-                    rapier_world.step(fixed_time_step, &mut editor_state.world);
-                }
+        if transition.replaced {
+            // Edits to the previous scene's entities can't be kept: track the new scene from here
+            editor_state.play_changes.begin(&editor_state.world);
+            if let Some(scene) = editor_state.scene_manager.active_scene() {
+                editor_state.console.info(format!("Loaded scene {}", scene));
             }
-            #[cfg(not(feature = "rapier"))]
-            {
-                if let Some(simple_world) = physics.downcast_mut::<PhysicsWorld>() {
-                    simple_world.step(fixed_time_step, &mut editor_state.world);
-                }
+        }
+        editor_state.rebuild_entity_names();
+        let primary = editor_state.selected_entity;
+        editor_state.sync_selection(primary);
+    }
+
+    fn take_collision_events(physics: &mut dyn std::any::Any) -> Vec<physics::CollisionEvent> {
+        #[cfg(feature = "rapier")]
+        {
+            if let Some(rapier_world) = physics.downcast_mut::<RapierPhysicsWorld>() {
+                return rapier_world.take_collision_events();
             }
-            
-            *physics_accumulator -= fixed_time_step;
-            physics_steps += 1;
-            
-            // Safety: prevent spiral of death (too many physics steps)
-            if physics_steps >= 5 {
-                *physics_accumulator = 0.0;
-                break;
+        }
+        #[cfg(not(feature = "rapier"))]
+        {
+            if let Some(simple_world) = physics.downcast_mut::<PhysicsWorld>() {
+                return simple_world.take_collision_events();
             }
         }
-        
-        // Check collisions and call collision callbacks (using simple fallback for now or Rapier events if implemented)
-        // Note: For Rapier, we should arguably use its EventQueue, but for now maintaining simple check compatibility
-        // This is O(N^2) and should be optimized or replaced by physics engine events
-        let entities_with_colliders: Vec<_> = editor_state.world.colliders.keys().cloned().collect();
-        for i in 0..entities_with_colliders.len() {
-            for j in (i + 1)..entities_with_colliders.len() {
-                let e1 = entities_with_colliders[i];
-                let e2 = entities_with_colliders[j];
-
-                let collision = {
-                        #[cfg(feature = "rapier")]
-                        {
-                            // TODO: Use Rapier contact events
-                            PhysicsWorld::check_collision(&editor_state.world, e1, e2) 
-                        }
-                        #[cfg(not(feature = "rapier"))]
-                        {
-                            PhysicsWorld::check_collision(&editor_state.world, e1, e2) 
-                        }
-                };
-
-                if collision {
-                    // Call on_collision for e1's script
-                    if let Some(script) = editor_state.world.scripts.get(&e1).filter(|s| s.enabled) {
-                        let script_name = script.script_name.clone();
-                        // Call collision (path ignored by engine, resolved via entity state)
-                        if let Err(e) = script_engine.call_collision(&std::path::Path::new(""), e1, e2, &mut editor_state.world) {
-                             editor_state.console.error(format!("Collision error {}: {}", script_name, e));
-                        }
-                    }
+        Vec::new()
+    }
 
-                    // Call on_collision for e2's script
-                    if let Some(script) = editor_state.world.scripts.get(&e2).filter(|s| s.enabled) {
-                        let script_name = script.script_name.clone();
-                        if let Err(e) = script_engine.call_collision(&std::path::Path::new(""), e2, e1, &mut editor_state.world) {
-                             editor_state.console.error(format!("Collision error {}: {}", script_name, e));
-                        }
-                    }
-                }
+    fn take_trigger_zone_events(physics: &mut dyn std::any::Any) -> Vec<physics::TriggerZoneEvent> {
+        #[cfg(feature = "rapier")]
+        {
+            if let Some(rapier_world) = physics.downcast_mut::<RapierPhysicsWorld>() {
+                return rapier_world.take_trigger_zone_events();
+            }
+        }
+        #[cfg(not(feature = "rapier"))]
+        {
+            if let Some(simple_world) = physics.downcast_mut::<PhysicsWorld>() {
+                return simple_world.take_trigger_zone_events();
             }
         }
+        Vec::new()
+    }
 
-        // Clear per-frame input state AFTER scripts have run
-        ctx.input.begin_frame();
+    fn step_physics(physics: &mut dyn std::any::Any, fixed_time_step: f32, world: &mut ecs::World) {
+        #[cfg(feature = "rapier")]
+        {
+            if let Some(rapier_world) = physics.downcast_mut::<RapierPhysicsWorld>() {
+                rapier_world.step(fixed_time_step, world);
+            }
+        }
+        #[cfg(not(feature = "rapier"))]
+        {
+            if let Some(simple_world) = physics.downcast_mut::<PhysicsWorld>() {
+                simple_world.step(fixed_time_step, world);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use engine::assets::native_loader::NativeAssetLoader;
+    use std::sync::Arc;
+
+    #[cfg(feature = "rapier")]
+    type Physics = RapierPhysicsWorld;
+    #[cfg(not(feature = "rapier"))]
+    type Physics = PhysicsWorld;
+
+    const FIXED_DT: f32 = 1.0 / 60.0;
+
+    /// A falling body, so every physics step shows in its position
+    fn falling_world() -> (ecs::World, ecs::Entity) {
+        let mut world = ecs::World::new();
+        let body = world.spawn();
+        world.transforms.insert(body, ecs::Transform::default());
+        world.rigidbodies.insert(body, ecs::Rigidbody2D::default());
+        world.colliders.insert(body, ecs::Collider::default());
+        (world, body)
+    }
+
+    struct PlaySession {
+        editor_state: EditorState,
+        ctx: EngineContext,
+        script_engine: ScriptEngine,
+        physics: Physics,
+        accumulator: f32,
+        loader: NativeAssetLoader,
+        body: ecs::Entity,
+    }
+
+    impl PlaySession {
+        fn start() -> Self {
+            let (world, body) = falling_world();
+            let mut editor_state = EditorState::new();
+            editor_state.world = world;
+            editor_state.is_playing = true;
+            let loader = Arc::new(NativeAssetLoader::new("."));
+            Self {
+                editor_state,
+                ctx: EngineContext::new(loader.clone()),
+                script_engine: ScriptEngine::new(loader).unwrap(),
+                physics: Physics::new(),
+                accumulator: 0.0,
+                loader: NativeAssetLoader::new("."),
+                body,
+            }
+        }
+
+        fn update(&mut self, dt: f32) {
+            PlayModeSystem::update(
+                &mut self.editor_state,
+                &mut self.ctx,
+                &mut self.script_engine,
+                &mut self.physics,
+                &mut self.accumulator,
+                FIXED_DT,
+                dt,
+                &self.loader,
+            );
+        }
+
+        fn position(&self) -> [f32; 3] {
+            self.editor_state.world.transforms[&self.body].position
+        }
+    }
+
+    #[test]
+    fn test_pause_keeps_the_fixed_step_accumulator() {
+        let mut session = PlaySession::start();
+        session.update(FIXED_DT * 1.5);
+        let (accumulator, frame, position) = (session.accumulator, session.editor_state.play_frame, session.position());
+        assert!(accumulator > 0.0);
+
+        session.editor_state.toggle_pause();
+        for _ in 0..3 {
+            session.update(0.5);
+        }
+        assert_eq!(session.accumulator, accumulator);
+        assert_eq!(session.editor_state.play_frame, frame);
+        assert_eq!(session.position(), position);
+
+        session.editor_state.toggle_pause();
+        assert!(!session.editor_state.is_paused);
+        assert_eq!(session.accumulator, accumulator);
+    }
+
+    #[test]
+    fn test_step_advances_exactly_one_fixed_tick() {
+        let mut session = PlaySession::start();
+        session.editor_state.toggle_pause();
+        session.editor_state.request_step();
+        // A long real frame still steps only once
+        session.update(0.5);
+
+        assert!(!session.editor_state.step_requested);
+        assert_eq!(session.editor_state.play_frame, 1);
+        assert_eq!(session.accumulator, 0.0);
+
+        let (mut expected, body) = falling_world();
+        let mut physics = Physics::new();
+        PlayModeSystem::step_physics(&mut physics, FIXED_DT, &mut expected);
+        assert_ne!(expected.transforms[&body].position, [0.0, 0.0, 0.0]);
+        assert_eq!(session.position(), expected.transforms[&body].position);
+
+        // No further frames until the next request
+        session.update(0.5);
+        assert_eq!(session.editor_state.play_frame, 1);
     }
 }
@@ -0,0 +1,388 @@
+//! Play Mode Changes
+//!
+//! Tracks component edits the user makes while the game is running, so they can be
+//! copied back onto the editor world after Stop restores the scene.
+//! Supports:
+//! - Transform, Sprite, Rigidbody2D, Collider and Script parameter edits
+//! - Entities created during play ("keep new entity")
+//! - Re-matching entities by name when IDs differ after the restore
+
+use ecs::{World, Entity};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use super::clipboard::EntityClipboardData;
+
+/// Component types whose runtime edits can be kept
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum TrackedComponent {
+    Transform,
+    Sprite,
+    Rigidbody2D,
+    Collider,
+    ScriptParameters,
+}
+
+impl TrackedComponent {
+    pub const ALL: [TrackedComponent; 5] = [
+        TrackedComponent::Transform,
+        TrackedComponent::Sprite,
+        TrackedComponent::Rigidbody2D,
+        TrackedComponent::Collider,
+        TrackedComponent::ScriptParameters,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TrackedComponent::Transform => "Transform",
+            TrackedComponent::Sprite => "Sprite",
+            TrackedComponent::Rigidbody2D => "Rigidbody 2D",
+            TrackedComponent::Collider => "Collider",
+            TrackedComponent::ScriptParameters => "Script Parameters",
+        }
+    }
+
+    /// Serialized form used for change detection (components don't implement PartialEq)
+    fn fingerprint(&self, world: &World, entity: Entity) -> Option<serde_json::Value> {
+        let value = match self {
+            TrackedComponent::Transform => serde_json::to_value(world.transforms.get(&entity)?),
+            TrackedComponent::Sprite => serde_json::to_value(world.sprites.get(&entity)?),
+            TrackedComponent::Rigidbody2D => serde_json::to_value(world.rigidbodies.get(&entity)?),
+            TrackedComponent::Collider => serde_json::to_value(world.colliders.get(&entity)?),
+            TrackedComponent::ScriptParameters => serde_json::to_value(&world.scripts.get(&entity)?.parameters),
+        };
+        value.ok()
+    }
+
+    fn capture(&self, world: &World, entity: Entity) -> Option<ComponentValue> {
+        Some(match self {
+            TrackedComponent::Transform => ComponentValue::Transform(world.transforms.get(&entity)?.clone()),
+            TrackedComponent::Sprite => ComponentValue::Sprite(world.sprites.get(&entity)?.clone()),
+            TrackedComponent::Rigidbody2D => ComponentValue::Rigidbody2D(world.rigidbodies.get(&entity)?.clone()),
+            TrackedComponent::Collider => ComponentValue::Collider(world.colliders.get(&entity)?.clone()),
+            TrackedComponent::ScriptParameters => {
+                ComponentValue::ScriptParameters(world.scripts.get(&entity)?.parameters.clone())
+            }
+        })
+    }
+}
+
+/// Runtime value of a tracked component, captured when play mode stops
+#[derive(Clone, Debug)]
+pub enum ComponentValue {
+    Transform(ecs::Transform),
+    Sprite(ecs::Sprite),
+    Rigidbody2D(ecs::Rigidbody2D),
+    Collider(ecs::Collider),
+    ScriptParameters(HashMap<String, ecs::ScriptParameter>),
+}
+
+impl ComponentValue {
+    pub fn component(&self) -> TrackedComponent {
+        match self {
+            ComponentValue::Transform(_) => TrackedComponent::Transform,
+            ComponentValue::Sprite(_) => TrackedComponent::Sprite,
+            ComponentValue::Rigidbody2D(_) => TrackedComponent::Rigidbody2D,
+            ComponentValue::Collider(_) => TrackedComponent::Collider,
+            ComponentValue::ScriptParameters(_) => TrackedComponent::ScriptParameters,
+        }
+    }
+
+    fn apply(&self, world: &mut World, entity: Entity) {
+        match self {
+            ComponentValue::Transform(t) => {
+                world.transforms.insert(entity, t.clone());
+            }
+            ComponentValue::Sprite(s) => {
+                world.sprites.insert(entity, s.clone());
+            }
+            ComponentValue::Rigidbody2D(rb) => {
+                world.rigidbodies.insert(entity, rb.clone());
+            }
+            ComponentValue::Collider(c) => {
+                world.colliders.insert(entity, c.clone());
+            }
+            ComponentValue::ScriptParameters(params) => {
+                // Only parameters are kept; the script itself must still exist in the scene
+                if let Some(script) = world.scripts.get_mut(&entity) {
+                    script.parameters = params.clone();
+                }
+            }
+        }
+    }
+}
+
+/// Tracked components of one entity at the start of a frame
+pub struct ComponentSnapshot {
+    entity: Entity,
+    values: Vec<(TrackedComponent, Option<serde_json::Value>)>,
+}
+
+/// Records which (entity, component) pairs the user edited during play
+#[derive(Default)]
+pub struct PlayModeChangeTracker {
+    /// Entities that existed when play started (anything else was created during play)
+    initial_entities: HashSet<Entity>,
+    edited: BTreeSet<(Entity, TrackedComponent)>,
+}
+
+impl PlayModeChangeTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking a new play session
+    pub fn begin(&mut self, world: &World) {
+        self.initial_entities = world.transforms.keys().copied().collect();
+        self.edited.clear();
+    }
+
+    pub fn clear(&mut self) {
+        self.initial_entities.clear();
+        self.edited.clear();
+    }
+
+    /// Snapshot entities before the editor UI runs. Scripts and physics don't run
+    /// during UI rendering, so any difference afterwards is a user edit.
+    pub fn snapshot(world: &World, entities: &[Entity]) -> Vec<ComponentSnapshot> {
+        entities
+            .iter()
+            .map(|&entity| ComponentSnapshot {
+                entity,
+                values: TrackedComponent::ALL
+                    .iter()
+                    .map(|c| (*c, c.fingerprint(world, entity)))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Compare snapshots against the world and mark edited components
+    pub fn record_edits(&mut self, world: &World, snapshots: &[ComponentSnapshot]) {
+        for snapshot in snapshots {
+            for (component, before) in &snapshot.values {
+                if component.fingerprint(world, snapshot.entity) != *before {
+                    self.edited.insert((snapshot.entity, *component));
+                }
+            }
+        }
+    }
+
+    /// Number of edited components
+    pub fn edit_count(&self) -> usize {
+        self.edited.len()
+    }
+
+    /// Capture the runtime values of all edits plus entities created during play.
+    /// Must be called on the play world, before the scene is restored.
+    pub fn collect_changes(&self, world: &World, entity_names: &HashMap<Entity, String>) -> Vec<RuntimeChange> {
+        let mut changes = Vec::new();
+
+        for &(entity, component) in &self.edited {
+            if !self.initial_entities.contains(&entity) {
+                continue; // Covered by the "keep new entity" option
+            }
+            if let Some(value) = component.capture(world, entity) {
+                changes.push(RuntimeChange {
+                    entity,
+                    entity_name: entity_name(world, entity_names, entity),
+                    kind: RuntimeChangeKind::Component(value),
+                    apply: true,
+                });
+            }
+        }
+
+        let mut new_entities: Vec<Entity> = world
+            .transforms
+            .keys()
+            .copied()
+            .filter(|e| !self.initial_entities.contains(e))
+            .collect();
+        new_entities.sort();
+
+        for entity in new_entities {
+            let parent = world.parents.get(&entity).copied();
+            changes.push(RuntimeChange {
+                entity,
+                entity_name: entity_name(world, entity_names, entity),
+                kind: RuntimeChangeKind::NewEntity {
+                    data: Box::new(EntityClipboardData::from_world(entity, world, entity_names)),
+                    parent: parent.map(|p| (p, entity_name(world, entity_names, p))),
+                },
+                // Spawned entities are usually bullets/effects; opt-in only
+                apply: false,
+            });
+        }
+
+        changes
+    }
+}
+
+pub enum RuntimeChangeKind {
+    Component(ComponentValue),
+    NewEntity {
+        data: Box<EntityClipboardData>,
+        /// Parent in the play world (entity, name)
+        parent: Option<(Entity, String)>,
+    },
+}
+
+/// One change offered in the "keep play mode changes" dialog
+pub struct RuntimeChange {
+    /// Entity ID in the play world
+    pub entity: Entity,
+    pub entity_name: String,
+    pub kind: RuntimeChangeKind,
+    /// Checkbox state in the dialog
+    pub apply: bool,
+}
+
+impl RuntimeChange {
+    pub fn description(&self) -> String {
+        match &self.kind {
+            RuntimeChangeKind::Component(value) => format!("{}: {}", self.entity_name, value.component().label()),
+            RuntimeChangeKind::NewEntity { .. } => format!("Keep new entity '{}'", self.entity_name),
+        }
+    }
+}
+
+fn entity_name(world: &World, entity_names: &HashMap<Entity, String>, entity: Entity) -> String {
+    entity_names
+        .get(&entity)
+        .or_else(|| world.names.get(&entity))
+        .cloned()
+        .unwrap_or_else(|| format!("Entity {}", entity))
+}
+
+/// Find the restored entity matching a play-world entity.
+/// Prefers the same ID when its name still matches, otherwise a unique name match.
+fn resolve_entity(world: &World, entity_names: &HashMap<Entity, String>, entity: Entity, name: &str) -> Option<Entity> {
+    if world.transforms.contains_key(&entity) && entity_name(world, entity_names, entity) == name {
+        return Some(entity);
+    }
+
+    let mut matches = world
+        .transforms
+        .keys()
+        .copied()
+        .filter(|&e| entity_name(world, entity_names, e) == name);
+    let first = matches.next()?;
+    if matches.next().is_some() {
+        return None; // Ambiguous
+    }
+    Some(first)
+}
+
+/// Apply the selected changes onto the restored editor world.
+/// Returns (applied, skipped) where skipped changes had no matching entity.
+pub fn apply_runtime_changes(
+    changes: &[RuntimeChange],
+    world: &mut World,
+    entity_names: &mut HashMap<Entity, String>,
+) -> (usize, usize) {
+    let mut applied = 0;
+    let mut skipped = 0;
+
+    // New entities first, so edits/parenting can refer to them
+    let mut created: HashMap<Entity, Entity> = HashMap::new();
+    for change in changes.iter().filter(|c| c.apply) {
+        if let RuntimeChangeKind::NewEntity { data, .. } = &change.kind {
+            let new_entity = data.create_in_world(world, entity_names, None);
+            created.insert(change.entity, new_entity);
+            applied += 1;
+        }
+    }
+
+    for change in changes.iter().filter(|c| c.apply) {
+        match &change.kind {
+            RuntimeChangeKind::NewEntity { parent: Some((parent, parent_name)), .. } => {
+                let new_entity = created[&change.entity];
+                let restored_parent = created
+                    .get(parent)
+                    .copied()
+                    .or_else(|| resolve_entity(world, entity_names, *parent, parent_name));
+                if let Some(restored_parent) = restored_parent {
+                    world.set_parent(new_entity, Some(restored_parent));
+                }
+            }
+            RuntimeChangeKind::NewEntity { parent: None, .. } => {}
+            RuntimeChangeKind::Component(value) => {
+                match resolve_entity(world, entity_names, change.entity, &change.entity_name) {
+                    Some(target) => {
+                        value.apply(world, target);
+                        applied += 1;
+                    }
+                    None => skipped += 1,
+                }
+            }
+        }
+    }
+
+    (applied, skipped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// World with one entity per name, spawned in order
+    fn world_with(names: &[&str]) -> (World, HashMap<Entity, String>) {
+        let mut world = World::new();
+        let mut entity_names = HashMap::new();
+        for name in names {
+            let entity = world.spawn();
+            world.transforms.insert(entity, ecs::Transform::default());
+            entity_names.insert(entity, name.to_string());
+        }
+        (world, entity_names)
+    }
+
+    /// Play session where the user moves the Player; returns its changes
+    fn moved_player() -> Vec<RuntimeChange> {
+        let (mut play_world, names) = world_with(&["Player", "Enemy"]);
+        let player = *names.iter().find(|(_, name)| *name == "Player").unwrap().0;
+        let mut tracker = PlayModeChangeTracker::new();
+        tracker.begin(&play_world);
+
+        let snapshots = PlayModeChangeTracker::snapshot(&play_world, &[player]);
+        play_world.transforms.get_mut(&player).unwrap().position = [4.0, 2.0, 0.0];
+        tracker.record_edits(&play_world, &snapshots);
+        assert_eq!(tracker.edit_count(), 1);
+
+        let changes = tracker.collect_changes(&play_world, &names);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].description(), "Player: Transform");
+        changes
+    }
+
+    #[test]
+    fn test_field_edit_is_kept_on_the_restored_entity() {
+        let changes = moved_player();
+        let (mut world, mut names) = world_with(&["Player", "Enemy"]);
+
+        assert_eq!(apply_runtime_changes(&changes, &mut world, &mut names), (1, 0));
+        assert_eq!(world.transforms[&changes[0].entity].position, [4.0, 2.0, 0.0]);
+    }
+
+    #[test]
+    fn test_respawned_entity_is_matched_by_name() {
+        let changes = moved_player();
+        // Restored with new IDs, and the Player's old ID now names the Enemy
+        let (mut world, mut names) = world_with(&["Enemy", "Player"]);
+        let player = *names.iter().find(|(_, name)| *name == "Player").unwrap().0;
+        assert_ne!(player, changes[0].entity);
+        assert_eq!(resolve_entity(&world, &names, changes[0].entity, "Player"), Some(player));
+
+        assert_eq!(apply_runtime_changes(&changes, &mut world, &mut names), (1, 0));
+        assert_eq!(world.transforms[&player].position, [4.0, 2.0, 0.0]);
+        assert_eq!(world.transforms.values().filter(|t| t.position == [4.0, 2.0, 0.0]).count(), 1);
+    }
+
+    #[test]
+    fn test_entity_gone_from_the_scene_is_skipped() {
+        let changes = moved_player();
+        let (mut world, mut names) = world_with(&["Enemy"]);
+
+        assert_eq!(resolve_entity(&world, &names, changes[0].entity, "Player"), None);
+        assert_eq!(apply_runtime_changes(&changes, &mut world, &mut names), (0, 1));
+        assert!(world.transforms.values().all(|t| t.position == [0.0, 0.0, 0.0]));
+    }
+}
@@ -0,0 +1,92 @@
+//! Editor Replays
+//!
+//! Record / Play Replay for play mode (see `engine::replay`). While a replay session
+//! is active, PlayModeSystem runs whole fixed-dt frames with exactly one physics
+//! step each, so the recording and its playback simulate the same frames.
+
+use engine::replay::{FixedFrameClock, ReplayFile, ReplayPlayer, ReplayRecorder, REPLAY_EXTENSION};
+use std::path::{Path, PathBuf};
+
+/// Playback speeds offered in the toolbar
+pub const PLAYBACK_SPEEDS: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+
+pub enum ReplaySession {
+    Recording(ReplayRecorder),
+    Playing(ReplayPlayer),
+}
+
+#[derive(Default)]
+pub struct EditorReplay {
+    pub session: Option<ReplaySession>,
+    pub clock: FixedFrameClock,
+    /// Record the next play session (set together with the play request)
+    pub record_request: bool,
+    /// Pick a .replay file and play it
+    pub open_request: bool,
+    /// Replay loaded from disk, started once play mode starts
+    pub pending_playback: Option<ReplayFile>,
+}
+
+impl EditorReplay {
+    pub fn is_recording(&self) -> bool {
+        matches!(self.session, Some(ReplaySession::Recording(_)))
+    }
+
+    pub fn is_playing_back(&self) -> bool {
+        matches!(self.session, Some(ReplaySession::Playing(_)))
+    }
+
+    /// Playback speed multiplier (1.0 while recording or idle)
+    pub fn speed(&self) -> f32 {
+        match &self.session {
+            Some(ReplaySession::Playing(player)) => player.speed,
+            _ => 1.0,
+        }
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        if let Some(ReplaySession::Playing(player)) = &mut self.session {
+            player.speed = speed;
+        }
+    }
+
+    /// (next frame, total frames) of the replay being played
+    pub fn playback_progress(&self) -> Option<(u64, u64)> {
+        match &self.session {
+            Some(ReplaySession::Playing(player)) => Some((player.frame(), player.replay().frame_count)),
+            _ => None,
+        }
+    }
+}
+
+/// Project folder replays are saved to
+pub fn replays_dir(project_path: &Path) -> PathBuf {
+    project_path.join("replays")
+}
+
+/// `<project>/replays/<scene>_<timestamp>.replay`
+pub fn new_replay_path(project_path: &Path, scene_path: &str) -> PathBuf {
+    let scene = Path::new(scene_path)
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("scene");
+    let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S");
+    replays_dir(project_path).join(format!("{}_{}.{}", scene, timestamp, REPLAY_EXTENSION))
+}
+
+/// Scene path as stored in a replay: project-relative with forward slashes when possible
+pub fn scene_path_for_replay(project_path: Option<&Path>, scene_path: &Path) -> String {
+    let relative = project_path
+        .and_then(|project| scene_path.strip_prefix(project).ok())
+        .unwrap_or(scene_path);
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// Resolve a replay's scene path against the project
+pub fn resolve_scene_path(project_path: Option<&Path>, scene_path: &str) -> PathBuf {
+    let path = PathBuf::from(scene_path);
+    match project_path {
+        Some(project) if path.is_relative() => project.join(path),
+        _ => path,
+    }
+}
@@ -3,15 +3,19 @@
 //! Command pattern implementation for editor actions.
 //! Supports unlimited undo/redo with memory management.
 
-use ecs::{World, Entity, Transform, Sprite, Collider, Camera, Mesh, EntityTag, Script};
+use ecs::{World, Entity, Transform, Sprite, Collider, Camera, Mesh, EntityTag, Script, Rigidbody2D};
 use std::collections::HashMap;
-use serde::{Serialize, Deserialize};
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize, de::DeserializeOwned};
 
 #[allow(dead_code)]
 
 /// Maximum number of undo steps to keep in memory
 const MAX_UNDO_STEPS: usize = 100;
 
+/// Component edits to the same field closer together than this become one undo step
+const MERGE_WINDOW: Duration = Duration::from_millis(500);
+
 /// Command trait for undo/redo operations
 pub trait Command: Send + Sync {
     /// Execute the command
@@ -36,6 +40,12 @@ pub trait Command: Send + Sync {
     /// Merge with another command (for optimization)
     fn merge(&mut self, _other: Box<dyn Command>) {}
 
+    /// False if the command can no longer do anything (e.g. its entity was deleted).
+    /// Invalid commands are pruned from the stack instead of being undone/redone.
+    fn is_valid(&self, _world: &World) -> bool {
+        true
+    }
+
     /// Helper for downcasting
     fn as_any(&self) -> &dyn std::any::Any;
     
@@ -93,6 +103,10 @@ impl UndoStack {
     
     /// Undo the last command
     pub fn undo(&mut self, world: &mut World, entity_names: &mut HashMap<Entity, String>) -> bool {
+        while self.can_undo() && !self.commands[self.current_index - 1].is_valid(world) {
+            self.current_index -= 1;
+            self.remove_at(self.current_index);
+        }
         if self.can_undo() {
             self.current_index -= 1;
             self.commands[self.current_index].undo(world, entity_names);
@@ -104,6 +118,9 @@ impl UndoStack {
     
     /// Redo the next command
     pub fn redo(&mut self, world: &mut World, entity_names: &mut HashMap<Entity, String>) -> bool {
+        while self.can_redo() && !self.commands[self.current_index].is_valid(world) {
+            self.remove_at(self.current_index);
+        }
         if self.can_redo() {
             self.commands[self.current_index].redo(world, entity_names);
             self.current_index += 1;
@@ -113,6 +130,16 @@ impl UndoStack {
         }
     }
     
+    /// Drop a pruned command, keeping the saved marker in place
+    fn remove_at(&mut self, index: usize) {
+        self.commands.remove(index);
+        if let Some(saved) = self.saved_index {
+            if saved > index {
+                self.saved_index = Some(saved - 1);
+            }
+        }
+    }
+    
     /// Check if undo is available
     pub fn can_undo(&self) -> bool {
         self.current_index > 0
@@ -187,6 +214,8 @@ pub struct EntityData {
     pub velocity: Option<(f32, f32)>,
     pub tag: Option<EntityTag>,
     pub script: Option<Script>,
+    #[serde(default)]
+    pub rigidbody: Option<Rigidbody2D>,
     pub active: bool,
     pub layer: u8,
     pub parent: Option<Entity>,
@@ -206,6 +235,7 @@ impl EntityData {
             velocity: world.velocities.get(&entity).copied(),
             tag: world.tags.get(&entity).cloned(),
             script: world.scripts.get(&entity).cloned(),
+            rigidbody: world.rigidbodies.get(&entity).cloned(),
             active: world.active.get(&entity).copied().unwrap_or(true),
             layer: world.layers.get(&entity).copied().unwrap_or(0),
             parent: world.parents.get(&entity).copied(),
@@ -241,12 +271,18 @@ impl EntityData {
         if let Some(script) = &self.script {
             world.scripts.insert(self.entity, script.clone());
         }
+        if let Some(rigidbody) = &self.rigidbody {
+            world.rigidbodies.insert(self.entity, rigidbody.clone());
+        }
         world.active.insert(self.entity, self.active);
         world.layers.insert(self.entity, self.layer);
         
         if let Some(parent) = self.parent {
             world.parents.insert(self.entity, parent);
-            world.children.entry(parent).or_default().push(self.entity);
+            let children = world.children.entry(parent).or_default();
+            if !children.contains(&self.entity) {
+                children.push(self.entity);
+            }
         }
     }
     
@@ -262,6 +298,7 @@ impl EntityData {
         world.velocities.remove(&self.entity);
         world.tags.remove(&self.entity);
         world.scripts.remove(&self.entity);
+        world.rigidbodies.remove(&self.entity);
         world.active.remove(&self.entity);
         world.layers.remove(&self.entity);
         
@@ -366,6 +403,84 @@ impl Command for DeleteEntityCommand {
     }
 }
 
+// ============================================================================
+// DUPLICATE ENTITIES COMMAND
+// ============================================================================
+
+/// Duplicate entity subtrees with all their components (Ctrl+D). Undo parks the
+/// copies in a side world, so redo brings them back under the same IDs.
+pub struct DuplicateEntitiesCommand {
+    description: String,
+    /// Copied roots and the parent each one sits under
+    roots: Vec<(Entity, Option<Entity>)>,
+    /// Undone copies and their names, in `roots` order
+    parked: Vec<(World, HashMap<Entity, String>)>,
+}
+
+impl DuplicateEntitiesCommand {
+    /// Duplicates the top-level `originals` right away; executing the command is a
+    /// no-op until it has been undone
+    pub fn new(
+        description: impl Into<String>,
+        originals: &[Entity],
+        world: &mut World,
+        entity_names: &mut HashMap<Entity, String>,
+    ) -> Self {
+        let mut roots = Vec::new();
+        for &original in originals {
+            if let Some(&root) = crate::systems::clipboard::duplicate_subtree(world, entity_names, original).first() {
+                roots.push((root, world.get_parent(root)));
+            }
+        }
+        Self { description: description.into(), roots, parked: Vec::new() }
+    }
+
+    /// The copied roots, in the order of the originals
+    pub fn roots(&self) -> Vec<Entity> {
+        self.roots.iter().map(|(root, _)| *root).collect()
+    }
+}
+
+impl Command for DuplicateEntitiesCommand {
+    fn execute(&mut self, world: &mut World, entity_names: &mut HashMap<Entity, String>) {
+        for (&(root, parent), (subtree, names)) in self.roots.iter().zip(self.parked.drain(..)) {
+            world.restore_subtree(subtree, root, parent);
+            entity_names.extend(names);
+        }
+    }
+
+    fn undo(&mut self, world: &mut World, entity_names: &mut HashMap<Entity, String>) {
+        self.parked = self
+            .roots
+            .iter()
+            .map(|&(root, _)| {
+                let names = world
+                    .subtree(root)
+                    .into_iter()
+                    .filter_map(|e| entity_names.remove(&e).map(|name| (e, name)))
+                    .collect();
+                (world.take_subtree(root), names)
+            })
+            .collect();
+    }
+
+    fn description(&self) -> String {
+        self.description.clone()
+    }
+
+    fn is_valid(&self, world: &World) -> bool {
+        !self.parked.is_empty() || self.roots.iter().any(|(root, _)| world.active.contains_key(root))
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+}
+
 // ============================================================================
 // MOVE ENTITY COMMAND
 // ============================================================================
@@ -572,6 +687,330 @@ impl Command for RenameEntityCommand {
     }
 }
 
+// ============================================================================
+// SET PARENT COMMAND
+// ============================================================================
+
+/// Reparent an entity, keeping it where it is in the world, and place it among its new
+/// siblings
+pub struct SetParentCommand {
+    entity: Entity,
+    old_parent: Option<Entity>,
+    new_parent: Option<Entity>,
+    /// Sibling to go in front of (None: after the last one)
+    before: Option<Entity>,
+    /// Position among the old siblings before execute, restored on undo
+    old_index: Option<usize>,
+    /// Local transform before execute, restored exactly on undo
+    old_transform: Option<Transform>,
+}
+
+impl SetParentCommand {
+    pub fn new(entity: Entity, old_parent: Option<Entity>, new_parent: Option<Entity>) -> Self {
+        Self {
+            entity,
+            old_parent,
+            new_parent,
+            before: None,
+            old_index: None,
+            old_transform: None,
+        }
+    }
+
+    /// Place the entity in front of `sibling` instead of last
+    pub fn before(mut self, sibling: Option<Entity>) -> Self {
+        self.before = sibling;
+        self
+    }
+}
+
+impl Command for SetParentCommand {
+    fn execute(&mut self, world: &mut World, _entity_names: &mut HashMap<Entity, String>) {
+        self.old_transform = world.transforms.get(&self.entity).cloned();
+        self.old_index = world.sibling_index(self.entity);
+        // Reordering among the same siblings leaves the transform alone
+        if self.new_parent != self.old_parent {
+            world.reparent(self.entity, self.new_parent, false);
+        }
+        world.move_before(self.entity, self.before);
+    }
+    
+    fn undo(&mut self, world: &mut World, _entity_names: &mut HashMap<Entity, String>) {
+        if self.new_parent != self.old_parent {
+            world.reparent(self.entity, self.old_parent, true);
+        }
+        if let Some(transform) = self.old_transform.clone() {
+            world.transforms.insert(self.entity, transform);
+        }
+        if let Some(index) = self.old_index {
+            world.set_sibling_index(self.entity, index);
+        }
+    }
+    
+    fn description(&self) -> String {
+        match self.new_parent {
+            _ if self.new_parent == self.old_parent => format!("Reorder Entity {}", self.entity),
+            Some(parent) => format!("Parent Entity {} to {}", self.entity, parent),
+            None => format!("Unparent Entity {}", self.entity),
+        }
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+}
+
+// ============================================================================
+// MODIFY COMPONENT COMMAND
+// ============================================================================
+
+/// Component (or entity property) whose value edits are undoable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ComponentKind {
+    Name,
+    Transform,
+    Sprite,
+    Collider,
+    Rigidbody2D,
+    Camera,
+    Script,
+    PolygonCollider,
+    TriggerZone,
+}
+
+impl ComponentKind {
+    pub const ALL: [ComponentKind; 9] = [
+        ComponentKind::Name,
+        ComponentKind::Transform,
+        ComponentKind::Sprite,
+        ComponentKind::Collider,
+        ComponentKind::Rigidbody2D,
+        ComponentKind::Camera,
+        ComponentKind::Script,
+        ComponentKind::PolygonCollider,
+        ComponentKind::TriggerZone,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ComponentKind::Name => "Name",
+            ComponentKind::Transform => "Transform",
+            ComponentKind::Sprite => "Sprite",
+            ComponentKind::Collider => "Collider",
+            ComponentKind::Rigidbody2D => "Rigidbody 2D",
+            ComponentKind::Camera => "Camera",
+            ComponentKind::Script => "Script",
+            ComponentKind::PolygonCollider => "Polygon Collider 2D",
+            ComponentKind::TriggerZone => "Trigger Zone",
+        }
+    }
+
+    /// Serialized state of the component (None = not present)
+    pub fn capture(&self, world: &World, entity_names: &HashMap<Entity, String>, entity: Entity) -> Option<serde_json::Value> {
+        let value = match self {
+            ComponentKind::Name => serde_json::to_value(entity_names.get(&entity)?),
+            ComponentKind::Transform => serde_json::to_value(world.transforms.get(&entity)?),
+            ComponentKind::Sprite => serde_json::to_value(world.sprites.get(&entity)?),
+            ComponentKind::Collider => serde_json::to_value(world.colliders.get(&entity)?),
+            ComponentKind::Rigidbody2D => serde_json::to_value(world.rigidbodies.get(&entity)?),
+            ComponentKind::Camera => serde_json::to_value(world.cameras.get(&entity)?),
+            ComponentKind::Script => serde_json::to_value(world.scripts.get(&entity)?),
+            ComponentKind::PolygonCollider => serde_json::to_value(world.polygon_colliders.get(&entity)?),
+            ComponentKind::TriggerZone => serde_json::to_value(world.trigger_zones.get(&entity)?),
+        };
+        value.ok()
+    }
+
+    /// Check that a serialized value is a valid state of this component
+    pub fn validate(&self, value: &serde_json::Value) -> Result<(), String> {
+        let result = match self {
+            ComponentKind::Name => serde_json::from_value::<String>(value.clone()).map(drop),
+            ComponentKind::Transform => serde_json::from_value::<Transform>(value.clone()).map(drop),
+            ComponentKind::Sprite => serde_json::from_value::<Sprite>(value.clone()).map(drop),
+            ComponentKind::Collider => serde_json::from_value::<Collider>(value.clone()).map(drop),
+            ComponentKind::Rigidbody2D => serde_json::from_value::<Rigidbody2D>(value.clone()).map(drop),
+            ComponentKind::Camera => serde_json::from_value::<Camera>(value.clone()).map(drop),
+            ComponentKind::Script => serde_json::from_value::<Script>(value.clone()).map(drop),
+            ComponentKind::PolygonCollider => serde_json::from_value::<ecs::PolygonCollider>(value.clone()).map(drop),
+            ComponentKind::TriggerZone => serde_json::from_value::<ecs::TriggerZone>(value.clone()).map(drop),
+        };
+        result.map_err(|e| format!("not a valid {}: {}", self.label(), e))
+    }
+
+    /// Put a captured state back (None removes the component)
+    pub fn restore(
+        &self,
+        world: &mut World,
+        entity_names: &mut HashMap<Entity, String>,
+        entity: Entity,
+        value: &Option<serde_json::Value>,
+    ) {
+        match self {
+            ComponentKind::Name => {
+                restore_map(entity_names, entity, value);
+                restore_map(&mut world.names, entity, value);
+            }
+            ComponentKind::Transform => restore_map(&mut world.transforms, entity, value),
+            ComponentKind::Sprite => restore_map(&mut world.sprites, entity, value),
+            ComponentKind::Collider => restore_map(&mut world.colliders, entity, value),
+            ComponentKind::Rigidbody2D => restore_map(&mut world.rigidbodies, entity, value),
+            ComponentKind::Camera => restore_map(&mut world.cameras, entity, value),
+            ComponentKind::Script => restore_map(&mut world.scripts, entity, value),
+            ComponentKind::PolygonCollider => restore_map(&mut world.polygon_colliders, entity, value),
+            ComponentKind::TriggerZone => restore_map(&mut world.trigger_zones, entity, value),
+        }
+    }
+}
+
+fn restore_map<T: DeserializeOwned>(map: &mut HashMap<Entity, T>, entity: Entity, value: &Option<serde_json::Value>) {
+    match value {
+        Some(value) => {
+            if let Ok(component) = serde_json::from_value(value.clone()) {
+                map.insert(entity, component);
+            }
+        }
+        None => {
+            map.remove(&entity);
+        }
+    }
+}
+
+/// Generic component edit, stored as serialized before/after snapshots
+pub struct ModifyComponentCommand {
+    entity: Entity,
+    entity_name: String,
+    component: ComponentKind,
+    before: Option<serde_json::Value>,
+    after: Option<serde_json::Value>,
+    last_edit: Instant,
+}
+
+impl ModifyComponentCommand {
+    pub fn new(
+        entity: Entity,
+        entity_name: String,
+        component: ComponentKind,
+        before: Option<serde_json::Value>,
+        after: Option<serde_json::Value>,
+    ) -> Self {
+        Self {
+            entity,
+            entity_name,
+            component,
+            before,
+            after,
+            last_edit: Instant::now(),
+        }
+    }
+}
+
+impl Command for ModifyComponentCommand {
+    fn execute(&mut self, world: &mut World, entity_names: &mut HashMap<Entity, String>) {
+        if self.is_valid(world) {
+            self.component.restore(world, entity_names, self.entity, &self.after);
+        }
+    }
+    
+    fn undo(&mut self, world: &mut World, entity_names: &mut HashMap<Entity, String>) {
+        if self.is_valid(world) {
+            self.component.restore(world, entity_names, self.entity, &self.before);
+        }
+    }
+    
+    fn description(&self) -> String {
+        format!("Edit {} of {}", self.component.label(), self.entity_name)
+    }
+
+    fn can_merge(&self, other: &dyn Command) -> bool {
+        if let Some(other) = other.as_any().downcast_ref::<ModifyComponentCommand>() {
+            return other.entity == self.entity
+                && other.component == self.component
+                && other.before == self.after
+                && other.last_edit.duration_since(self.last_edit) < MERGE_WINDOW;
+        }
+        false
+    }
+
+    fn merge(&mut self, other: Box<dyn Command>) {
+        if let Ok(other) = other.into_any().downcast::<ModifyComponentCommand>() {
+            // Keep the original before state, take the latest after state
+            self.after = other.after;
+            self.last_edit = other.last_edit;
+        }
+    }
+
+    fn is_valid(&self, world: &World) -> bool {
+        // Deleted entities lose both their active flag and transform
+        world.active.contains_key(&self.entity) || world.transforms.contains_key(&self.entity)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+}
+
+// ============================================================================
+// TILE PAINTING
+// ============================================================================
+
+/// One painted stroke on a tilemap (a whole brush drag, rectangle or fill).
+/// The tiles are already painted when it's recorded, so executing re-applies them as a no-op.
+pub struct PaintTilesCommand {
+    stroke: crate::tools::tile_painting::TileStroke,
+}
+
+impl PaintTilesCommand {
+    pub fn new(stroke: crate::tools::tile_painting::TileStroke) -> Self {
+        Self { stroke }
+    }
+
+    fn apply(&self, world: &mut World, undo: bool) {
+        if let Some(tilemap) = world.tilemaps.get_mut(&self.stroke.entity) {
+            crate::tools::tile_painting::restore_changes(tilemap, &self.stroke.changes, undo);
+        }
+        crate::tools::tile_painting::regenerate_chunk_colliders(world, self.stroke.entity, &self.stroke.affected_chunks());
+    }
+}
+
+impl Command for PaintTilesCommand {
+    fn execute(&mut self, world: &mut World, _entity_names: &mut HashMap<Entity, String>) {
+        self.apply(world, false);
+    }
+
+    fn undo(&mut self, world: &mut World, _entity_names: &mut HashMap<Entity, String>) {
+        self.apply(world, true);
+    }
+
+    fn description(&self) -> String {
+        let count = self.stroke.changes.len();
+        let verb = match self.stroke.tool {
+            crate::tools::tile_painting::TilePaintTool::Erase => "Erase",
+            _ => "Paint",
+        };
+        format!("{} {} Tile{}", verb, count, if count == 1 { "" } else { "s" })
+    }
+
+    fn is_valid(&self, world: &World) -> bool {
+        world.tilemaps.contains_key(&self.stroke.entity)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn std::any::Any> {
+        self
+    }
+}
+
 // ============================================================================
 // BATCH COMMAND (for multiple operations)
 // ============================================================================
@@ -592,6 +1031,14 @@ impl BatchCommand {
     pub fn add(&mut self, command: Box<dyn Command>) {
         self.commands.push(command);
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
 }
 
 impl Command for BatchCommand {
@@ -612,6 +1059,10 @@ impl Command for BatchCommand {
         self.description.clone()
     }
 
+    fn is_valid(&self, world: &World) -> bool {
+        self.commands.iter().any(|cmd| cmd.is_valid(world))
+    }
+
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
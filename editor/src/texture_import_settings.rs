@@ -129,7 +129,7 @@ pub struct TextureImportSettings {
     /// Pixels per unit (for Sprite2D type)
     pub pixels_per_unit: f32,
     
-    /// Generate mipmaps
+    /// Generate mipmaps (Bilinear/Trilinear only; Point filtering is never mipmapped)
     pub generate_mipmaps: bool,
     
     /// sRGB color space
@@ -138,6 +138,14 @@ pub struct TextureImportSettings {
     /// Alpha is transparency
     pub alpha_is_transparency: bool,
     
+    /// Color already multiplied by alpha in the source image
+    #[serde(default)]
+    pub premultiplied_alpha: bool,
+    
+    /// Default sprite pivot, (0,0) = bottom-left, (1,1) = top-right
+    #[serde(default = "default_pivot")]
+    pub pivot: [f32; 2],
+    
     /// Read/Write enabled (allows CPU access)
     pub read_write_enabled: bool,
     
@@ -158,15 +166,21 @@ pub struct TextureImportSettings {
     pub platform_overrides: std::collections::HashMap<String, PlatformOverride>,
 }
 
+fn default_pivot() -> [f32; 2] {
+    [0.5, 0.5]
+}
+
 impl Default for TextureImportSettings {
     fn default() -> Self {
         Self {
             texture_type: TextureType::Sprite2D,
             sprite_mode: SpriteMode::Single,
             pixels_per_unit: 100.0,
-            generate_mipmaps: false,
+            generate_mipmaps: true,
             srgb: true,
             alpha_is_transparency: true,
+            premultiplied_alpha: false,
+            pivot: default_pivot(),
             read_write_enabled: false,
             filter_mode: FilterMode::Point,
             wrap_mode: WrapMode::Clamp,
@@ -207,14 +221,9 @@ impl TextureImportSettings {
         Ok(())
     }
     
-    /// Get .meta file path for a texture
+    /// Get .meta file path for a texture (shared with the runtime loader)
     fn get_meta_path(texture_path: &Path) -> PathBuf {
-        let mut meta_path = texture_path.to_path_buf();
-        let current_ext = meta_path.extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("");
-        meta_path.set_extension(format!("{}.meta", current_ext));
-        meta_path
+        engine::texture_manager::TextureImportSettings::meta_path(texture_path)
     }
     
     /// Check if settings need to be applied (meta file is newer than texture)
@@ -0,0 +1,361 @@
+//! Asset Browser Thumbnails
+//!
+//! Previews are generated on a background thread and cached on disk under
+//! `<project>/.cache/thumbnails`, keyed by a hash of the file contents so an
+//! edited asset gets a fresh thumbnail. Images are downscaled, sprite sheets
+//! show their texture, and prefabs/scenes get a generated layout preview.
+//!
+//! The browser only requests thumbnails for visible items and a few finished
+//! thumbnails are uploaded per frame, so large folders stay responsive.
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::SystemTime;
+use crate::asset_manager::{AssetMetadata, AssetType};
+
+/// Thumbnail edge length in pixels
+pub const THUMBNAIL_SIZE: u32 = 128;
+
+/// Finished thumbnails uploaded to the GPU per frame
+const UPLOADS_PER_FRAME: usize = 8;
+
+/// What kind of preview to generate for a file
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PreviewKind {
+    Image,
+    SpriteSheet,
+    Prefab,
+    Scene,
+}
+
+impl PreviewKind {
+    fn for_asset(asset_type: &AssetType) -> Option<Self> {
+        match asset_type {
+            AssetType::Sprite => Some(Self::Image),
+            AssetType::SpriteSheet => Some(Self::SpriteSheet),
+            AssetType::Prefab => Some(Self::Prefab),
+            AssetType::Scene => Some(Self::Scene),
+            _ => None,
+        }
+    }
+}
+
+struct ThumbnailRequest {
+    path: PathBuf,
+    modified: SystemTime,
+    kind: PreviewKind,
+}
+
+struct ThumbnailResult {
+    path: PathBuf,
+    modified: SystemTime,
+    image: Option<image::RgbaImage>,
+}
+
+enum ThumbnailState {
+    Pending,
+    Ready(egui::TextureHandle),
+    /// No preview possible (unsupported format, parse error); the icon is shown instead
+    Failed,
+}
+
+/// State shared with the worker thread
+struct WorkerShared {
+    /// Newest requests are at the back and processed first, so whatever the
+    /// user scrolled to most recently shows up first
+    queue: Mutex<VecDeque<ThumbnailRequest>>,
+    wake: Condvar,
+    shutdown: AtomicBool,
+}
+
+pub struct ThumbnailCache {
+    entries: HashMap<PathBuf, (SystemTime, ThumbnailState)>,
+    shared: Arc<WorkerShared>,
+    results: Receiver<ThumbnailResult>,
+    worker: Option<std::thread::JoinHandle<()>>,
+}
+
+impl ThumbnailCache {
+    pub fn new(project_path: &Path) -> Self {
+        let shared = Arc::new(WorkerShared {
+            queue: Mutex::new(VecDeque::new()),
+            wake: Condvar::new(),
+            shutdown: AtomicBool::new(false),
+        });
+        let (sender, results) = mpsc::channel();
+
+        let worker_shared = shared.clone();
+        let project_root = project_path.to_path_buf();
+        let worker = std::thread::Builder::new()
+            .name("asset-thumbnails".to_string())
+            .spawn(move || run_worker(worker_shared, sender, project_root))
+            .map_err(|e| log::error!("Failed to start thumbnail thread: {}", e))
+            .ok();
+
+        Self {
+            entries: HashMap::new(),
+            shared,
+            results,
+            worker,
+        }
+    }
+
+    /// Thumbnail texture and its size for an asset, queueing generation if needed.
+    /// Returns None while pending or when the asset has no preview.
+    pub fn get(&mut self, asset: &AssetMetadata) -> Option<(egui::TextureId, egui::Vec2)> {
+        let kind = PreviewKind::for_asset(&asset.asset_type)?;
+
+        match self.entries.get(&asset.path) {
+            Some((modified, ThumbnailState::Ready(texture))) if *modified == asset.modified => {
+                return Some((texture.id(), texture.size_vec2()));
+            }
+            Some((modified, _)) if *modified == asset.modified => return None,
+            _ => {}
+        }
+
+        // New or changed on disk since the last thumbnail
+        self.entries.insert(asset.path.clone(), (asset.modified, ThumbnailState::Pending));
+        self.shared.queue.lock().unwrap().push_back(ThumbnailRequest {
+            path: asset.path.clone(),
+            modified: asset.modified,
+            kind,
+        });
+        self.shared.wake.notify_one();
+        None
+    }
+
+    /// Drop queued requests, e.g. after navigating to another folder
+    pub fn cancel_pending(&mut self) {
+        let cancelled: Vec<ThumbnailRequest> = self.shared.queue.lock().unwrap().drain(..).collect();
+        for request in cancelled {
+            if matches!(self.entries.get(&request.path), Some((_, ThumbnailState::Pending))) {
+                self.entries.remove(&request.path);
+            }
+        }
+    }
+
+    /// Upload finished thumbnails. Call once per frame.
+    pub fn update(&mut self, ctx: &egui::Context) {
+        let mut received = false;
+        for result in self.results.try_iter().take(UPLOADS_PER_FRAME) {
+            received = true;
+            // Ignore results for files that changed again while generating
+            if !matches!(self.entries.get(&result.path), Some((modified, _)) if *modified == result.modified) {
+                continue;
+            }
+            let state = match result.image {
+                Some(image) => {
+                    let size = [image.width() as usize, image.height() as usize];
+                    let color_image = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+                    let texture = ctx.load_texture(
+                        format!("asset_thumbnail_{}", result.path.display()),
+                        color_image,
+                        egui::TextureOptions::LINEAR,
+                    );
+                    ThumbnailState::Ready(texture)
+                }
+                None => ThumbnailState::Failed,
+            };
+            self.entries.insert(result.path, (result.modified, state));
+        }
+
+        // More may be waiting; keep repainting until the queue drains
+        if received || self.entries.values().any(|(_, state)| matches!(state, ThumbnailState::Pending)) {
+            ctx.request_repaint();
+        }
+    }
+}
+
+impl Drop for ThumbnailCache {
+    fn drop(&mut self) {
+        self.shared.shutdown.store(true, Ordering::Relaxed);
+        self.shared.wake.notify_all();
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn run_worker(shared: Arc<WorkerShared>, sender: Sender<ThumbnailResult>, project_root: PathBuf) {
+    let cache_dir = project_root.join(".cache").join("thumbnails");
+
+    loop {
+        let request = {
+            let mut queue = shared.queue.lock().unwrap();
+            loop {
+                if shared.shutdown.load(Ordering::Relaxed) {
+                    return;
+                }
+                if let Some(request) = queue.pop_back() {
+                    break request;
+                }
+                queue = shared.wake.wait(queue).unwrap();
+            }
+        };
+
+        let image = generate_thumbnail(&request, &cache_dir, &project_root);
+        let result = ThumbnailResult {
+            path: request.path,
+            modified: request.modified,
+            image,
+        };
+        if sender.send(result).is_err() {
+            return;
+        }
+    }
+}
+
+/// Load a cached thumbnail or generate (and cache) a new one
+fn generate_thumbnail(request: &ThumbnailRequest, cache_dir: &Path, project_root: &Path) -> Option<image::RgbaImage> {
+    let bytes = std::fs::read(&request.path).ok()?;
+
+    // Sprite sheets preview their texture, so its contents are part of the key
+    let sheet_texture = if request.kind == PreviewKind::SpriteSheet {
+        let metadata: sprite_editor::SpriteMetadata = serde_json::from_slice(&bytes).ok()?;
+        let texture_path = [project_root.join(&metadata.texture_path), PathBuf::from(&metadata.texture_path)]
+            .into_iter()
+            .find(|p| p.exists())?;
+        Some(std::fs::read(texture_path).ok()?)
+    } else {
+        None
+    };
+
+    let mut hash = content_hash(FNV_OFFSET, &bytes);
+    if let Some(texture) = &sheet_texture {
+        hash = content_hash(hash, texture);
+    }
+    let cache_file = cache_dir.join(format!("{:016x}_{}.png", hash, THUMBNAIL_SIZE));
+
+    if let Ok(cached) = image::open(&cache_file) {
+        return Some(cached.to_rgba8());
+    }
+
+    let thumbnail = match request.kind {
+        PreviewKind::Image => downscale(&bytes)?,
+        PreviewKind::SpriteSheet => downscale(sheet_texture.as_deref()?)?,
+        PreviewKind::Prefab => {
+            let prefab: crate::prefab::Prefab = serde_json::from_slice(&bytes).ok()?;
+            let mut world = ecs::World::new();
+            let mut names = HashMap::new();
+            prefab.instantiate(&mut world, &mut names, None).ok()?;
+            render_layout_preview(&world)
+        }
+        PreviewKind::Scene => {
+            let mut world = ecs::World::new();
+            world.load_from_json(std::str::from_utf8(&bytes).ok()?).ok()?;
+            if world.transforms.is_empty() {
+                return None; // Some other JSON file
+            }
+            render_layout_preview(&world)
+        }
+    };
+
+    if std::fs::create_dir_all(cache_dir).is_ok() {
+        if let Err(e) = thumbnail.save(&cache_file) {
+            log::warn!("Failed to cache thumbnail {:?}: {}", cache_file, e);
+        }
+    }
+
+    Some(thumbnail)
+}
+
+fn downscale(bytes: &[u8]) -> Option<image::RgbaImage> {
+    let image = image::load_from_memory(bytes).ok()?;
+    Some(image.thumbnail(THUMBNAIL_SIZE, THUMBNAIL_SIZE).to_rgba8())
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a; stable across runs, unlike std's hasher
+fn content_hash(seed: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(seed, |hash, &b| (hash ^ b as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// Top-down preview of a world: sprites as colored boxes, other entities as
+/// small gray markers, fitted into the thumbnail
+fn render_layout_preview(world: &ecs::World) -> image::RgbaImage {
+    let size = THUMBNAIL_SIZE;
+    let mut image = image::RgbaImage::from_pixel(size, size, image::Rgba([45, 45, 52, 255]));
+
+    // (center, half size, color) in world units
+    let boxes: Vec<(glam::Vec2, glam::Vec2, [u8; 4])> = world
+        .transforms
+        .iter()
+        .filter(|(entity, _)| !world.cameras.contains_key(entity))
+        .map(|(entity, transform)| {
+            let center = glam::Vec2::new(transform.position[0], transform.position[1]);
+            let scale = glam::Vec2::new(transform.scale[0].abs(), transform.scale[1].abs());
+            match world.sprites.get(entity) {
+                Some(sprite) => {
+                    let half = glam::Vec2::new(sprite.width, sprite.height) / sprite.pixels_per_unit.max(0.001) * scale / 2.0;
+                    let color = sprite.color.to_array().map(|c| (c.clamp(0.0, 1.0) * 255.0) as u8);
+                    (center, half, color)
+                }
+                None => (center, glam::Vec2::splat(0.25), [150, 150, 150, 255]),
+            }
+        })
+        .collect();
+
+    if boxes.is_empty() {
+        return image;
+    }
+
+    let min = boxes.iter().fold(glam::Vec2::splat(f32::MAX), |acc, (c, h, _)| acc.min(*c - *h));
+    let max = boxes.iter().fold(glam::Vec2::splat(f32::MIN), |acc, (c, h, _)| acc.max(*c + *h));
+    let extent = (max - min).max_element().max(0.001);
+    let padding = 8.0;
+    let pixels_per_unit = (size as f32 - padding * 2.0) / extent;
+    let origin = (min + max) / 2.0;
+
+    for (center, half, color) in boxes {
+        // Y-up world to Y-down image
+        let to_pixel = |p: glam::Vec2| {
+            let x = (p.x - origin.x) * pixels_per_unit + size as f32 / 2.0;
+            let y = (origin.y - p.y) * pixels_per_unit + size as f32 / 2.0;
+            (x, y)
+        };
+        let (x0, y0) = to_pixel(center + glam::Vec2::new(-half.x, half.y));
+        let (x1, y1) = to_pixel(center + glam::Vec2::new(half.x, -half.y));
+        let clamp = |v: f32| (v.round().max(0.0) as u32).min(size);
+        // At least one pixel so tiny entities stay visible
+        let (x0, y0) = (clamp(x0), clamp(y0));
+        let (x1, y1) = (clamp(x1).max(x0 + 1).min(size), clamp(y1).max(y0 + 1).min(size));
+
+        for y in y0..y1 {
+            for x in x0..x1 {
+                image.put_pixel(x, y, image::Rgba(color));
+            }
+        }
+    }
+
+    image
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_hash_changes_with_contents() {
+        let a = content_hash(FNV_OFFSET, b"sprite");
+        assert_eq!(a, content_hash(FNV_OFFSET, b"sprite"));
+        assert_ne!(a, content_hash(FNV_OFFSET, b"sprites"));
+    }
+
+    #[test]
+    fn test_layout_preview_draws_sprites() {
+        let mut world = ecs::World::new();
+        let entity = world.spawn();
+        world.transforms.insert(entity, ecs::Transform::default());
+        let mut sprite = ecs::Sprite::default();
+        sprite.color = ecs::Color::rgba(1.0, 0.0, 0.0, 1.0);
+        world.sprites.insert(entity, sprite);
+
+        let preview = render_layout_preview(&world);
+        let center = preview.get_pixel(THUMBNAIL_SIZE / 2, THUMBNAIL_SIZE / 2);
+        assert_eq!(center.0, [255, 0, 0, 255]);
+    }
+}
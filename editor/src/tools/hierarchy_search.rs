@@ -0,0 +1,261 @@
+//! Hierarchy Search
+//!
+//! Filters the hierarchy with a space-separated query. Every term must match:
+//! - `gob` - name contains "gob" (case-insensitive)
+//! - `t:Player` - entity tag
+//! - `c:Camera`, `c:Rigidbody2D` - entity has a component of that type
+//!
+//! Matched entities keep their ancestors visible so the tree keeps its context.
+
+use ecs::{ComponentManager, ComponentType, Entity, World};
+use std::collections::{HashMap, HashSet};
+
+/// One term of a hierarchy search query, lowercased
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchTerm {
+    /// Name substring
+    Name(String),
+    /// Tag prefix (`t:`)
+    Tag(String),
+    /// Component types whose name contains the term (`c:`)
+    Component(Vec<ComponentType>),
+}
+
+/// Parse a query into terms; empty `t:`/`c:` prefixes are ignored while typing
+pub fn parse_query(query: &str) -> Vec<SearchTerm> {
+    query
+        .split_whitespace()
+        .filter_map(|word| {
+            let lower = word.to_lowercase();
+            if let Some(tag) = lower.strip_prefix("t:") {
+                (!tag.is_empty()).then(|| SearchTerm::Tag(tag.to_string()))
+            } else if let Some(component) = lower.strip_prefix("c:") {
+                (!component.is_empty()).then(|| SearchTerm::Component(component_types_matching(component)))
+            } else {
+                Some(SearchTerm::Name(lower))
+            }
+        })
+        .collect()
+}
+
+/// Component types whose display or type name contains `term`, ignoring spaces
+/// ("rigidbody2d" matches "Rigidbody 2D", "collider" matches every collider)
+fn component_types_matching(term: &str) -> Vec<ComponentType> {
+    let normalize = |s: &str| s.to_lowercase().replace([' ', '_'], "");
+    let term = normalize(term);
+    ComponentType::all()
+        .into_iter()
+        .filter(|ty| normalize(ty.display_name()).contains(&term) || normalize(&format!("{:?}", ty)).contains(&term))
+        .collect()
+}
+
+/// Lowercased copy of an entity name, with the name it was built from
+struct IndexedName {
+    source: String,
+    lower: String,
+}
+
+/// Search state for the hierarchy panel
+#[derive(Default)]
+pub struct HierarchySearch {
+    /// Text in the search field
+    pub query: String,
+    parsed_query: String,
+    terms: Vec<SearchTerm>,
+    /// Lowercase names, rebuilt per entity only when its name changes
+    name_index: HashMap<Entity, IndexedName>,
+    /// Matches in hierarchy display order
+    matches: Vec<Entity>,
+    match_set: HashSet<Entity>,
+    /// Ancestors of matches, shown dimmed
+    context: HashSet<Entity>,
+    /// Entity the hierarchy should scroll to on the next frame
+    reveal: Option<Entity>,
+}
+
+impl HierarchySearch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the query has any terms
+    pub fn is_active(&self) -> bool {
+        !self.query.trim().is_empty()
+    }
+
+    /// Clear the query
+    pub fn clear(&mut self) {
+        self.query.clear();
+    }
+
+    /// Re-run the query against the world.
+    /// `display_order` is the hierarchy's depth-first order, so matches cycle top to bottom.
+    pub fn update(&mut self, world: &World, entity_names: &HashMap<Entity, String>, display_order: &[Entity]) {
+        if self.parsed_query != self.query {
+            self.terms = parse_query(&self.query);
+            self.parsed_query = self.query.clone();
+        }
+
+        self.matches.clear();
+        self.match_set.clear();
+        self.context.clear();
+        if self.terms.is_empty() {
+            return;
+        }
+
+        self.refresh_name_index(entity_names);
+
+        for &entity in display_order {
+            if self.entity_matches(world, entity) {
+                self.matches.push(entity);
+                self.match_set.insert(entity);
+            }
+        }
+
+        for &entity in &self.matches {
+            let mut current = entity;
+            while let Some(&parent) = world.parents.get(&current) {
+                if !self.context.insert(parent) {
+                    break;
+                }
+                current = parent;
+            }
+        }
+    }
+
+    /// Re-lowercase only names that changed since the last update (renames), and drop despawned entities
+    fn refresh_name_index(&mut self, entity_names: &HashMap<Entity, String>) {
+        self.name_index.retain(|entity, _| entity_names.contains_key(entity));
+        for (&entity, name) in entity_names {
+            match self.name_index.get_mut(&entity) {
+                Some(indexed) if indexed.source == *name => {}
+                Some(indexed) => {
+                    indexed.source.clone_from(name);
+                    indexed.lower = name.to_lowercase();
+                }
+                None => {
+                    self.name_index.insert(entity, IndexedName { source: name.clone(), lower: name.to_lowercase() });
+                }
+            }
+        }
+    }
+
+    fn entity_matches(&self, world: &World, entity: Entity) -> bool {
+        self.terms.iter().all(|term| match term {
+            SearchTerm::Name(text) => self
+                .name_index
+                .get(&entity)
+                .is_some_and(|indexed| indexed.lower.contains(text.as_str())),
+            SearchTerm::Tag(tag) => world
+                .tags
+                .get(&entity)
+                .is_some_and(|entity_tag| format!("{:?}", entity_tag).to_lowercase().starts_with(tag.as_str())),
+            SearchTerm::Component(types) => types.iter().any(|&ty| world.has_component(entity, ty)),
+        })
+    }
+
+    /// Matched entities in display order
+    pub fn matches(&self) -> &[Entity] {
+        &self.matches
+    }
+
+    pub fn is_match(&self, entity: Entity) -> bool {
+        self.match_set.contains(&entity)
+    }
+
+    /// Ancestor of a match that is shown only for context
+    pub fn is_context(&self, entity: Entity) -> bool {
+        self.context.contains(&entity)
+    }
+
+    /// Whether the entity's row is shown; everything is shown without a query
+    pub fn is_visible(&self, entity: Entity) -> bool {
+        !self.is_active() || self.is_match(entity) || self.is_context(entity)
+    }
+
+    /// Next (or previous) match after `current`, wrapping around.
+    /// Starts at the first (or last) match when `current` isn't a match.
+    pub fn step(&mut self, current: Option<Entity>, backwards: bool) -> Option<Entity> {
+        if self.matches.is_empty() {
+            return None;
+        }
+        let len = self.matches.len();
+        let index = match current.and_then(|entity| self.matches.iter().position(|&e| e == entity)) {
+            Some(index) if backwards => (index + len - 1) % len,
+            Some(index) => (index + 1) % len,
+            None if backwards => len - 1,
+            None => 0,
+        };
+        let entity = self.matches[index];
+        self.reveal = Some(entity);
+        Some(entity)
+    }
+
+    /// Whether the hierarchy should scroll to this entity; consumed once
+    pub fn take_reveal(&mut self, entity: Entity) -> bool {
+        if self.reveal == Some(entity) {
+            self.reveal = None;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scene() -> (World, HashMap<Entity, String>, Vec<Entity>) {
+        let mut world = World::new();
+        let mut names = HashMap::new();
+        let root = world.spawn();
+        names.insert(root, "Level".to_string());
+        let player = world.spawn();
+        names.insert(player, "Hero".to_string());
+        world.tags.insert(player, ecs::EntityTag::Player);
+        world.rigidbodies.insert(player, ecs::Rigidbody2D::default());
+        world.set_parent(player, Some(root));
+        let camera = world.spawn();
+        names.insert(camera, "Main Camera".to_string());
+        world.cameras.insert(camera, ecs::Camera::default());
+        (world, names, vec![root, player, camera])
+    }
+
+    #[test]
+    fn test_terms_combine_and_keep_ancestors() {
+        let (world, names, order) = scene();
+        let mut search = HierarchySearch::new();
+
+        search.query = "t:player c:Rigidbody2D".to_string();
+        search.update(&world, &names, &order);
+        assert_eq!(search.matches(), &[order[1]]);
+        assert!(search.is_context(order[0]));
+        assert!(!search.is_visible(order[2]));
+
+        search.query = "CAM c:camera".to_string();
+        search.update(&world, &names, &order);
+        assert_eq!(search.matches(), &[order[2]]);
+
+        search.query = "hero c:camera".to_string();
+        search.update(&world, &names, &order);
+        assert!(search.matches().is_empty());
+    }
+
+    #[test]
+    fn test_rename_invalidates_index_and_step_wraps() {
+        let (world, mut names, order) = scene();
+        let mut search = HierarchySearch::new();
+
+        search.query = "e".to_string();
+        search.update(&world, &names, &order);
+        assert_eq!(search.matches(), &[order[0], order[1], order[2]]);
+        assert_eq!(search.step(Some(order[2]), false), Some(order[0]));
+        assert_eq!(search.step(None, true), Some(order[2]));
+
+        names.insert(order[1], "Knight".to_string());
+        search.query = "knight".to_string();
+        search.update(&world, &names, &order);
+        assert_eq!(search.matches(), &[order[1]]);
+    }
+}
@@ -1,2 +1,4 @@
 pub mod snapping;
 pub mod selection;
+pub mod hierarchy_search;
+pub mod tile_painting;
@@ -192,6 +192,18 @@ impl SelectionManager {
         }
     }
     
+    /// Drop entities that no longer exist (deleted, undone, scene reloaded)
+    pub fn retain_existing(&mut self, world: &World) {
+        let before = self.selected.len();
+        self.selected.retain(|e| world.transforms.contains_key(e));
+        if self.last_selected.is_some_and(|e| !self.selected.contains(&e)) {
+            self.last_selected = None;
+        }
+        if self.selected.len() != before {
+            self.push_history();
+        }
+    }
+    
     // ========================================================================
     // BOX SELECTION
     // ========================================================================
@@ -590,3 +602,81 @@ pub fn scale_selected_by_factor(
         }
     }
 }
+
+/// Check whether `ancestor` is a parent, grandparent, ... of `entity`
+pub fn is_descendant_of(world: &World, entity: Entity, ancestor: Entity) -> bool {
+    let mut current = world.parents.get(&entity).copied();
+    while let Some(parent) = current {
+        if parent == ancestor {
+            return true;
+        }
+        current = world.parents.get(&parent).copied();
+    }
+    false
+}
+
+/// Selected entities without the ones whose ancestor is also selected.
+/// Moving/parenting a parent already carries its children along.
+pub fn top_level_selected(selected: &[Entity], world: &World) -> Vec<Entity> {
+    selected
+        .iter()
+        .copied()
+        .filter(|&entity| !selected.iter().any(|&other| other != entity && is_descendant_of(world, entity, other)))
+        .collect()
+}
+
+/// Selected entities plus all their descendants (parents before children, no duplicates)
+pub fn with_descendants(selected: &[Entity], world: &World) -> Vec<Entity> {
+    let mut result = Vec::new();
+    let mut seen = HashSet::new();
+    let mut stack: Vec<Entity> = top_level_selected(selected, world).into_iter().rev().collect();
+    
+    while let Some(entity) = stack.pop() {
+        if !seen.insert(entity) {
+            continue;
+        }
+        result.push(entity);
+        for &child in world.get_children(entity).iter().rev() {
+            stack.push(child);
+        }
+    }
+    
+    result
+}
+
+/// Apply the gizmo change of the primary entity to the rest of the selection.
+/// Position and rotation are applied as deltas, scale as a ratio.
+pub fn apply_gizmo_delta_to_selected(
+    selected: &[Entity],
+    primary: Entity,
+    before: &ecs::Transform,
+    after: &ecs::Transform,
+    world: &mut World,
+) {
+    let others: Vec<Entity> = top_level_selected(selected, world)
+        .into_iter()
+        .filter(|&e| e != primary && !is_descendant_of(world, e, primary))
+        .collect();
+    if others.is_empty() {
+        return;
+    }
+    
+    let delta = |a: [f32; 3], b: [f32; 3]| [b[0] - a[0], b[1] - a[1], b[2] - a[2]];
+    
+    if after.position != before.position {
+        move_selected_by_delta(&others, world, delta(before.position, after.position));
+    }
+    if after.rotation != before.rotation {
+        rotate_selected_by_delta(&others, world, delta(before.rotation, after.rotation));
+    }
+    if after.scale != before.scale {
+        // Avoid dividing by a zero scale on the primary entity
+        let ratio = |a: f32, b: f32| if a.abs() > f32::EPSILON { b / a } else { 1.0 };
+        let factor = [
+            ratio(before.scale[0], after.scale[0]),
+            ratio(before.scale[1], after.scale[1]),
+            ratio(before.scale[2], after.scale[2]),
+        ];
+        scale_selected_by_factor(&others, world, factor);
+    }
+}
@@ -8,6 +8,7 @@
 //! - Configurable grid sizes
 //! - Visual feedback
 //! - Toggle with Ctrl key
+//! - Vertex/edge snapping to nearby entities (hold V)
 
 use serde::{Serialize, Deserialize};
 
@@ -29,6 +30,9 @@ pub struct SnapSettings {
     
     // Grid sizes
     pub position_snap: f32,    // World units (e.g., 1.0)
+    /// Per-axis position grid; None uses `position_snap` on every axis
+    #[serde(default)]
+    pub position_snap_axes: Option<[f32; 3]>,
     pub rotation_snap: f32,    // Degrees (e.g., 15.0)
     pub scale_snap: f32,       // Scale increment (e.g., 0.1)
     
@@ -42,6 +46,20 @@ pub struct SnapSettings {
     pub snap_on_move: bool,    // Snap when moving
     pub snap_on_rotate: bool,  // Snap when rotating
     pub snap_on_scale: bool,   // Snap when scaling
+    /// Holding Ctrl flips `enabled` for the current drag
+    #[serde(default = "default_ctrl_toggles")]
+    pub ctrl_toggles: bool,
+    /// Screen distance (pixels) within which vertex snapping catches a corner/edge midpoint
+    #[serde(default = "default_vertex_snap_distance")]
+    pub vertex_snap_distance: f32,
+}
+
+fn default_ctrl_toggles() -> bool {
+    true
+}
+
+fn default_vertex_snap_distance() -> f32 {
+    12.0
 }
 
 impl Default for SnapSettings {
@@ -50,6 +68,7 @@ impl Default for SnapSettings {
             enabled: false,
             mode: SnapMode::Absolute,
             position_snap: 1.0, // 1 world unit = 1 LDtk cell (8x8 pixels)
+            position_snap_axes: None,
             rotation_snap: 15.0,
             scale_snap: 0.1,
             show_grid: true,
@@ -59,6 +78,8 @@ impl Default for SnapSettings {
             snap_on_move: true,
             snap_on_rotate: true,
             snap_on_scale: true,
+            ctrl_toggles: default_ctrl_toggles(),
+            vertex_snap_distance: default_vertex_snap_distance(),
         }
     }
 }
@@ -72,6 +93,14 @@ impl SnapSettings {
         }
     }
     
+    /// Position grid size along one axis (0 = X, 1 = Y, 2 = Z)
+    pub fn position_snap_axis(&self, axis: usize) -> f32 {
+        match self.position_snap_axes {
+            Some(axes) => axes[axis.min(2)],
+            None => self.position_snap,
+        }
+    }
+    
     /// Common presets
     pub fn preset_fine() -> Self {
         Self {
@@ -137,20 +166,35 @@ pub fn snap_value(value: f32, grid_size: f32, mode: SnapMode, original: f32) ->
         return value;
     }
     
+    // Work in f64 so multiples of e.g. 0.1 or 0.32 don't come out as 63.999997
+    let grid = grid_size as f64;
     match mode {
         SnapMode::Absolute => {
             // Snap to absolute grid positions
-            (value / grid_size).round() * grid_size
+            let snapped = (value as f64 / grid).round() * grid;
+            round_to_grid_precision(snapped, grid) as f32
         }
         SnapMode::Relative => {
             // Snap relative to original position
-            let delta = value - original;
-            let snapped_delta = (delta / grid_size).round() * grid_size;
-            original + snapped_delta
+            let delta = value as f64 - original as f64;
+            let snapped_delta = round_to_grid_precision((delta / grid).round() * grid, grid);
+            (original as f64 + snapped_delta) as f32
         }
     }
 }
 
+/// Round a grid multiple to as many decimals as the grid size has (up to 6)
+fn round_to_grid_precision(value: f64, grid: f64) -> f64 {
+    let decimals = (0..=6)
+        .find(|&d| {
+            let scaled = grid * 10f64.powi(d);
+            (scaled - scaled.round()).abs() < 1e-4
+        })
+        .unwrap_or(6);
+    let scale = 10f64.powi(decimals);
+    (value * scale).round() / scale
+}
+
 /// Snap position (3D vector)
 pub fn snap_position(
     position: [f32; 3],
@@ -164,9 +208,9 @@ pub fn snap_position(
     let orig = original.unwrap_or(position);
     
     [
-        snap_value(position[0], settings.position_snap, settings.mode, orig[0]),
-        snap_value(position[1], settings.position_snap, settings.mode, orig[1]),
-        snap_value(position[2], settings.position_snap, settings.mode, orig[2]),
+        snap_value(position[0], settings.position_snap_axis(0), settings.mode, orig[0]),
+        snap_value(position[1], settings.position_snap_axis(1), settings.mode, orig[1]),
+        snap_value(position[2], settings.position_snap_axis(2), settings.mode, orig[2]),
     ]
 }
 
@@ -221,8 +265,8 @@ pub fn snap_position_2d(
     let orig = original.unwrap_or(position);
     
     [
-        snap_value(position[0], settings.position_snap, settings.mode, orig[0]),
-        snap_value(position[1], settings.position_snap, settings.mode, orig[1]),
+        snap_value(position[0], settings.position_snap_axis(0), settings.mode, orig[0]),
+        snap_value(position[1], settings.position_snap_axis(1), settings.mode, orig[1]),
     ]
 }
 
@@ -254,6 +298,78 @@ pub fn snap_scale_single(
     snap_value(scale, settings.scale_snap, settings.mode, orig).max(0.01)
 }
 
+// ============================================================================
+// VERTEX / EDGE SNAPPING
+// ============================================================================
+
+/// Edges (start, end) drawn through a caught point
+type Guides = Vec<(glam::Vec2, glam::Vec2)>;
+
+/// A dragged entity caught on a nearby entity's corner or edge midpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct VertexSnap {
+    /// New transform position for the dragged entity
+    pub position: glam::Vec2,
+    /// The target corner/edge midpoint that was caught
+    pub point: glam::Vec2,
+    /// Target edges through `point`, drawn as the indicator line
+    pub guides: Guides,
+}
+
+/// Snap the corners of a dragged rectangle (bounds `center`/`size`, transform at
+/// `position`) to the nearest corner or edge midpoint among `targets` (center, size).
+/// Returns None when nothing is within `max_distance` world units.
+pub fn find_vertex_snap(
+    position: glam::Vec2,
+    center: glam::Vec2,
+    size: glam::Vec2,
+    targets: &[(glam::Vec2, glam::Vec2)],
+    max_distance: f32,
+) -> Option<VertexSnap> {
+    let signs = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+    let half = size.as_dvec2() * 0.5;
+    let corner_offsets = signs.map(|(x, y)| glam::DVec2::new(half.x * x, half.y * y));
+
+    let mut best: Option<(f64, glam::DVec2, glam::DVec2, Guides)> = None;
+    for &(target_center, target_size) in targets {
+        // Target corners counter-clockwise from bottom-left; edge i runs corner i -> i+1
+        let target_half = target_size.as_dvec2() * 0.5;
+        let corners = signs.map(|(x, y)| target_center.as_dvec2() + glam::DVec2::new(target_half.x * x, target_half.y * y));
+        let edge = |i: usize| (corners[i % 4].as_vec2(), corners[(i + 1) % 4].as_vec2());
+
+        let mut points: Vec<(glam::DVec2, Guides)> = Vec::with_capacity(8);
+        for i in 0..4 {
+            points.push((corners[i], vec![edge(i + 3), edge(i)]));
+            points.push(((corners[i] + corners[(i + 1) % 4]) * 0.5, vec![edge(i)]));
+        }
+
+        for (point, guides) in points {
+            for offset in corner_offsets {
+                let distance = (center.as_dvec2() + offset).distance(point);
+                if distance <= max_distance as f64 && best.as_ref().is_none_or(|b| distance < b.0) {
+                    best = Some((distance, point, offset, guides.clone()));
+                }
+            }
+        }
+    }
+
+    best.map(|(_, point, offset, guides)| {
+        // Bounds may sit off the transform (sprite pivot, collider offset)
+        let anchor = center.as_dvec2() - position.as_dvec2();
+        let new_position = point - offset - anchor;
+        VertexSnap {
+            position: glam::Vec2::new(clean_world_value(new_position.x), clean_world_value(new_position.y)),
+            point: point.as_vec2(),
+            guides,
+        }
+    })
+}
+
+/// Drop float noise below 1e-4 world units (so 64.0 doesn't end up as 63.999997)
+fn clean_world_value(value: f64) -> f32 {
+    ((value * 1e4).round() / 1e4) as f32
+}
+
 // ============================================================================
 // VISUAL FEEDBACK
 // ============================================================================
@@ -269,8 +385,9 @@ pub fn render_snap_grid(
         return;
     }
     
-    let grid_size = settings.position_snap;
-    if grid_size <= 0.0 {
+    let grid_x = settings.position_snap_axis(0);
+    let grid_y = settings.position_snap_axis(1);
+    if grid_x <= 0.0 || grid_y <= 0.0 {
         return;
     }
     
@@ -286,10 +403,10 @@ pub fn render_snap_grid(
     
     let cam_pos = scene_camera.position;
     
-    let min_x = ((cam_pos.x - world_half_width) / grid_size).floor() * grid_size;
-    let max_x = ((cam_pos.x + world_half_width) / grid_size).ceil() * grid_size;
-    let min_y = ((cam_pos.y - world_half_height) / grid_size).floor() * grid_size;
-    let max_y = ((cam_pos.y + world_half_height) / grid_size).ceil() * grid_size;
+    let min_x = ((cam_pos.x - world_half_width) / grid_x).floor() * grid_x;
+    let max_x = ((cam_pos.x + world_half_width) / grid_x).ceil() * grid_x;
+    let min_y = ((cam_pos.y - world_half_height) / grid_y).floor() * grid_y;
+    let max_y = ((cam_pos.y + world_half_height) / grid_y).ceil() * grid_y;
     
     let color = egui::Color32::from_rgba_premultiplied(
         (settings.grid_color[0] * 255.0) as u8,
@@ -312,7 +429,7 @@ pub fn render_snap_grid(
             egui::Stroke::new(1.0, color),
         );
         
-        x += grid_size;
+        x += grid_x;
     }
     
     // Draw horizontal lines
@@ -329,7 +446,7 @@ pub fn render_snap_grid(
             egui::Stroke::new(1.0, color),
         );
         
-        y += grid_size;
+        y += grid_y;
     }
     
     // Draw origin (thicker lines)
@@ -404,6 +521,31 @@ pub fn render_snap_indicator(
     );
 }
 
+/// Render the vertex snap indicator: the caught target edge(s) and the snap point
+pub fn render_vertex_snap_guide(
+    painter: &egui::Painter,
+    snap: &VertexSnap,
+    scene_camera: &crate::SceneCamera,
+    center: egui::Pos2,
+    settings: &SnapSettings,
+) {
+    let to_screen = |p: glam::Vec2| {
+        let screen = scene_camera.world_to_screen(glam::Vec3::new(p.x, p.y, 0.0));
+        egui::pos2(center.x + screen.x, center.y + screen.y)
+    };
+    let color = egui::Color32::from_rgba_unmultiplied(
+        (settings.snap_indicator_color[0] * 255.0) as u8,
+        (settings.snap_indicator_color[1] * 255.0) as u8,
+        (settings.snap_indicator_color[2] * 255.0) as u8,
+        (settings.snap_indicator_color[3] * 255.0) as u8,
+    );
+
+    for &(a, b) in &snap.guides {
+        painter.line_segment([to_screen(a), to_screen(b)], egui::Stroke::new(2.0, color));
+    }
+    painter.circle_stroke(to_screen(snap.point), 6.0, egui::Stroke::new(2.0, color));
+}
+
 // ============================================================================
 // SETTINGS UI
 // ============================================================================
@@ -441,11 +583,28 @@ pub fn render_snap_settings_ui(ui: &mut egui::Ui, settings: &mut SnapSettings) -
     
     ui.horizontal(|ui| {
         ui.label("Position:");
-        if ui.add(egui::DragValue::new(&mut settings.position_snap).speed(0.1).clamp_range(0.01..=100.0)).changed() {
-            changed = true;
+        match settings.position_snap_axes.as_mut() {
+            Some(axes) => {
+                for (axis, label) in axes.iter_mut().zip(["X", "Y", "Z"]) {
+                    if ui.add(egui::DragValue::new(axis).speed(0.1).range(0.01..=100.0).prefix(format!("{}: ", label))).changed() {
+                        changed = true;
+                    }
+                }
+            }
+            None => {
+                if ui.add(egui::DragValue::new(&mut settings.position_snap).speed(0.1).range(0.01..=100.0)).changed() {
+                    changed = true;
+                }
+            }
         }
     });
     
+    let mut per_axis = settings.position_snap_axes.is_some();
+    if ui.checkbox(&mut per_axis, "Per-axis position grid").changed() {
+        settings.position_snap_axes = per_axis.then_some([settings.position_snap; 3]);
+        changed = true;
+    }
+    
     ui.horizontal(|ui| {
         ui.label("Rotation:");
         if ui.add(egui::DragValue::new(&mut settings.rotation_snap).speed(1.0).clamp_range(1.0..=180.0).suffix("°")).changed() {
@@ -513,6 +672,19 @@ pub fn render_snap_settings_ui(ui: &mut egui::Ui, settings: &mut SnapSettings) -
     
     ui.add_space(8.0);
     
+    if ui.checkbox(&mut settings.ctrl_toggles, "Hold Ctrl to toggle snapping").changed() {
+        changed = true;
+    }
+    
+    ui.horizontal(|ui| {
+        ui.label("Vertex snap (V) distance:");
+        if ui.add(egui::DragValue::new(&mut settings.vertex_snap_distance).speed(0.5).range(2.0..=64.0).suffix(" px")).changed() {
+            changed = true;
+        }
+    });
+    
+    ui.add_space(8.0);
+    
     // Save/Load
     ui.horizontal(|ui| {
         if ui.button("Save Settings").clicked() {
@@ -560,9 +732,9 @@ pub fn is_snap_override(modifiers: &egui::Modifiers) -> bool {
     modifiers.shift
 }
 
-/// Check if snapping should be temporarily enabled
-pub fn is_snap_force(modifiers: &egui::Modifiers) -> bool {
-    // Hold Ctrl to temporarily enable snapping
+/// Check if snapping should be temporarily toggled
+pub fn is_snap_toggle(modifiers: &egui::Modifiers) -> bool {
+    // Hold Ctrl to temporarily flip snapping on/off
     modifiers.ctrl && !modifiers.shift
 }
 
@@ -570,8 +742,8 @@ pub fn is_snap_force(modifiers: &egui::Modifiers) -> bool {
 pub fn get_effective_snap_enabled(settings: &SnapSettings, modifiers: &egui::Modifiers) -> bool {
     if is_snap_override(modifiers) {
         false
-    } else if is_snap_force(modifiers) {
-        true
+    } else if settings.ctrl_toggles && is_snap_toggle(modifiers) {
+        !settings.enabled
     } else {
         settings.enabled
     }
@@ -629,3 +801,32 @@ pub fn get_grid_lines_near(position: glam::Vec2, grid_size: f32, range: f32) ->
     
     lines
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec2;
+
+    #[test]
+    fn test_snap_value_has_no_float_drift() {
+        assert_eq!(snap_value(63.99, 0.32, SnapMode::Absolute, 0.0), 64.0);
+        assert_eq!(snap_value(0.71, 0.1, SnapMode::Absolute, 0.0), 0.7);
+        assert_eq!(snap_value(2.33, 0.1, SnapMode::Relative, 2.0), 2.3);
+    }
+
+    #[test]
+    fn test_vertex_snap_catches_nearest_corner() {
+        // 2x2 box at (0, 0); dragged 1x1 box (transform offset by its collider) near its top-right corner
+        let targets = [(Vec2::ZERO, Vec2::splat(2.0))];
+        let position = Vec2::new(1.45, 1.55);
+        let center = position + Vec2::new(0.1, 0.0);
+
+        let snap = find_vertex_snap(position, center, Vec2::ONE, &targets, 0.2).unwrap();
+        assert_eq!(snap.point, Vec2::new(1.0, 1.0));
+        // Bottom-left corner of the dragged box lands exactly on the target corner
+        assert_eq!(snap.position, Vec2::new(1.4, 1.5));
+        assert_eq!(snap.guides.len(), 2);
+
+        assert!(find_vertex_snap(position, center, Vec2::ONE, &targets, 0.01).is_none());
+    }
+}
@@ -0,0 +1,412 @@
+//! Tile Painting
+//!
+//! Scene-view painting on the selected Tilemap layer with the Tile Palette's stamp:
+//! brush, rectangle, flood fill and eraser.
+//! - Tiles change live while dragging; the whole drag becomes one undo step
+//! - Tiles are 1x1 world units hanging down-right from the tilemap's position,
+//!   matching how the 2D scene view draws them
+//! - Merged box colliders are rebuilt only for the chunks a stroke touched
+
+use ecs::{Collider, Entity, Rigidbody2D, Tile, Tilemap, TilemapColliderMode, Transform, World, TILEMAP_CHUNK_SIZE};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Prefix of the collider entities generated for painted tilemaps
+pub const COLLIDER_NAME_PREFIX: &str = "TileCollider";
+
+/// Upper bound for one flood fill, so a click on a huge empty map stays responsive
+const MAX_FILL_CELLS: usize = 65_536;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TilePaintTool {
+    Brush,
+    Rectangle,
+    Fill,
+    Erase,
+}
+
+impl TilePaintTool {
+    pub const ALL: [TilePaintTool; 4] = [
+        TilePaintTool::Brush,
+        TilePaintTool::Rectangle,
+        TilePaintTool::Fill,
+        TilePaintTool::Erase,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TilePaintTool::Brush => "🖌 Brush",
+            TilePaintTool::Rectangle => "⬛ Rectangle",
+            TilePaintTool::Fill => "🪣 Fill",
+            TilePaintTool::Erase => "🧽 Erase",
+        }
+    }
+}
+
+/// Tiles placed by one click, row-major with the top row first
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileStamp {
+    pub width: u32,
+    pub height: u32,
+    pub tiles: Vec<u32>,
+}
+
+impl TileStamp {
+    pub fn single(tile_id: u32) -> Self {
+        Self { width: 1, height: 1, tiles: vec![tile_id] }
+    }
+
+    /// Tile at an offset from the stamp origin; the stamp repeats so fills tile it
+    pub fn tile_at(&self, dx: i64, dy: i64) -> u32 {
+        let x = dx.rem_euclid(self.width.max(1) as i64) as u32;
+        let y = dy.rem_euclid(self.height.max(1) as i64) as u32;
+        self.tiles.get((y * self.width + x) as usize).copied().unwrap_or(0)
+    }
+}
+
+/// The Tile Palette's current tool and stamp
+#[derive(Clone, Debug, PartialEq)]
+pub struct TileBrush {
+    pub tool: TilePaintTool,
+    pub stamp: TileStamp,
+}
+
+/// One cell written by a stroke
+#[derive(Clone, Debug)]
+pub struct TileChange {
+    pub x: u32,
+    pub y: u32,
+    pub before: Tile,
+    pub after: Tile,
+}
+
+/// Changes made to one tilemap by a single click or drag
+#[derive(Clone, Debug)]
+pub struct TileStroke {
+    pub entity: Entity,
+    pub tool: TilePaintTool,
+    pub changes: Vec<TileChange>,
+}
+
+impl TileStroke {
+    pub fn new(entity: Entity, tool: TilePaintTool) -> Self {
+        Self { entity, tool, changes: Vec::new() }
+    }
+
+    /// Add changes, keeping each cell's first before-state and latest after-state
+    pub fn record(&mut self, changes: Vec<TileChange>) {
+        for change in changes {
+            if let Some(existing) = self.changes.iter_mut().find(|c| c.x == change.x && c.y == change.y) {
+                existing.after = change.after;
+            } else {
+                self.changes.push(change);
+            }
+        }
+    }
+
+    pub fn affected_chunks(&self) -> Vec<(u32, u32)> {
+        let chunks: BTreeSet<(u32, u32)> = self.changes.iter().map(|c| Tilemap::chunk_of(c.x, c.y)).collect();
+        chunks.into_iter().collect()
+    }
+}
+
+// ============================================================================
+// Palette <-> scene view <-> editor hand-off (egui temp memory)
+// ============================================================================
+
+fn brush_id() -> egui::Id {
+    egui::Id::new("tile_paint_brush")
+}
+
+fn stroke_id() -> egui::Id {
+    egui::Id::new("tile_paint_stroke")
+}
+
+fn finished_stroke_id() -> egui::Id {
+    egui::Id::new("tile_paint_finished_stroke")
+}
+
+/// Set by the Tile Palette while a tool is active (None stops painting)
+pub fn set_brush(ctx: &egui::Context, brush: Option<TileBrush>) {
+    ctx.data_mut(|d| match brush {
+        Some(brush) => d.insert_temp(brush_id(), brush),
+        None => d.remove::<TileBrush>(brush_id()),
+    });
+}
+
+pub fn brush(ctx: &egui::Context) -> Option<TileBrush> {
+    ctx.data(|d| d.get_temp::<TileBrush>(brush_id()))
+}
+
+/// Stroke in progress (while the pointer is held)
+pub fn current_stroke(ctx: &egui::Context) -> Option<TileStroke> {
+    ctx.data(|d| d.get_temp::<TileStroke>(stroke_id()))
+}
+
+pub fn set_current_stroke(ctx: &egui::Context, stroke: TileStroke) {
+    ctx.data_mut(|d| d.insert_temp(stroke_id(), stroke));
+}
+
+/// Pointer released: hand the stroke to the editor to record as an undo step
+pub fn finish_stroke(ctx: &egui::Context) {
+    ctx.data_mut(|d| {
+        if let Some(stroke) = d.get_temp::<TileStroke>(stroke_id()) {
+            d.remove::<TileStroke>(stroke_id());
+            if !stroke.changes.is_empty() {
+                d.insert_temp(finished_stroke_id(), stroke);
+            }
+        }
+    });
+}
+
+pub fn take_finished_stroke(ctx: &egui::Context) -> Option<TileStroke> {
+    ctx.data_mut(|d| {
+        let stroke = d.get_temp::<TileStroke>(finished_stroke_id());
+        d.remove::<TileStroke>(finished_stroke_id());
+        stroke
+    })
+}
+
+// ============================================================================
+// Cells
+// ============================================================================
+
+/// Tile under a world point (may be outside the map)
+pub fn cell_at(tilemap_position: [f32; 3], point: glam::Vec2) -> (i64, i64) {
+    (
+        (point.x - tilemap_position[0]).floor() as i64,
+        (tilemap_position[1] - point.y).floor() as i64,
+    )
+}
+
+/// World position of a cell's top-left corner
+pub fn cell_top_left(tilemap_position: [f32; 3], cell: (i64, i64)) -> glam::Vec2 {
+    glam::Vec2::new(tilemap_position[0] + cell.0 as f32, tilemap_position[1] - cell.1 as f32)
+}
+
+/// Cells the stamp covers with its top-left at `cell`
+pub fn brush_cells(stamp: &TileStamp, cell: (i64, i64)) -> Vec<((i64, i64), u32)> {
+    (0..stamp.height as i64)
+        .flat_map(|dy| (0..stamp.width as i64).map(move |dx| (dx, dy)))
+        .map(|(dx, dy)| ((cell.0 + dx, cell.1 + dy), stamp.tile_at(dx, dy)))
+        .collect()
+}
+
+/// Rectangle between two corner cells, tiled with the stamp from its top-left
+pub fn rectangle_cells(stamp: &TileStamp, a: (i64, i64), b: (i64, i64)) -> Vec<((i64, i64), u32)> {
+    let (min_x, max_x) = (a.0.min(b.0), a.0.max(b.0));
+    let (min_y, max_y) = (a.1.min(b.1), a.1.max(b.1));
+    (min_y..=max_y)
+        .flat_map(|y| (min_x..=max_x).map(move |x| (x, y)))
+        .map(|(x, y)| ((x, y), stamp.tile_at(x - min_x, y - min_y)))
+        .collect()
+}
+
+/// 4-connected region of tiles matching the clicked one, bounded by the map
+pub fn fill_cells(tilemap: &Tilemap, stamp: &TileStamp, start: (i64, i64)) -> Vec<((i64, i64), u32)> {
+    let in_bounds = |(x, y): (i64, i64)| x >= 0 && y >= 0 && x < tilemap.width as i64 && y < tilemap.height as i64;
+    if !in_bounds(start) {
+        return Vec::new();
+    }
+    let target = tilemap.get_tile(start.0 as u32, start.1 as u32).map(|t| t.tile_id).unwrap_or(0);
+
+    let mut visited = HashSet::new();
+    let mut stack = vec![start];
+    let mut cells = Vec::new();
+    while let Some(cell) = stack.pop() {
+        if cells.len() >= MAX_FILL_CELLS {
+            break;
+        }
+        if !in_bounds(cell) || !visited.insert(cell) {
+            continue;
+        }
+        if tilemap.get_tile(cell.0 as u32, cell.1 as u32).map(|t| t.tile_id) != Some(target) {
+            continue;
+        }
+        cells.push((cell, stamp.tile_at(cell.0 - start.0, cell.1 - start.1)));
+        stack.extend([(cell.0 + 1, cell.1), (cell.0 - 1, cell.1), (cell.0, cell.1 + 1), (cell.0, cell.1 - 1)]);
+    }
+    cells
+}
+
+/// Cells the eraser clears: the stamp's footprint
+pub fn erase_cells(stamp: &TileStamp, cell: (i64, i64)) -> Vec<((i64, i64), u32)> {
+    brush_cells(stamp, cell).into_iter().map(|(cell, _)| (cell, 0)).collect()
+}
+
+/// Write tile ids; cells outside the map or already holding the tile are skipped
+pub fn apply_cells(tilemap: &mut Tilemap, cells: &[((i64, i64), u32)]) -> Vec<TileChange> {
+    let mut changes = Vec::new();
+    for &((x, y), tile_id) in cells {
+        if x < 0 || y < 0 {
+            continue;
+        }
+        let (x, y) = (x as u32, y as u32);
+        let Some(before) = tilemap.get_tile(x, y).cloned() else {
+            continue;
+        };
+        if before.tile_id == tile_id && !before.flip_h && !before.flip_v && !before.flip_d {
+            continue;
+        }
+        let after = Tile::new(tile_id);
+        tilemap.set_tile(x, y, after.clone());
+        changes.push(TileChange { x, y, before, after });
+    }
+    changes
+}
+
+/// Put a stroke's before (undo) or after (redo) tiles back
+pub fn restore_changes(tilemap: &mut Tilemap, changes: &[TileChange], undo: bool) {
+    for change in changes {
+        let tile = if undo { &change.before } else { &change.after };
+        tilemap.set_tile(change.x, change.y, tile.clone());
+    }
+}
+
+// ============================================================================
+// Colliders
+// ============================================================================
+
+fn chunk_collider_name(chunk: (u32, u32)) -> String {
+    format!("{}_{}_{}", COLLIDER_NAME_PREFIX, chunk.0, chunk.1)
+}
+
+/// Rebuild the box colliders of the given chunks of a tilemap with a TilemapCollider.
+/// Colliders are kinematic children of the tilemap; solid tiles in a chunk are merged
+/// into rectangles unless the collider uses Individual mode without compositing.
+pub fn regenerate_chunk_colliders(world: &mut World, entity: Entity, chunks: &[(u32, u32)]) -> usize {
+    let Some(settings) = world.tilemap_colliders.get(&entity).cloned() else {
+        return 0;
+    };
+    let Some(tilemap) = world.tilemaps.get(&entity) else {
+        return 0;
+    };
+    let position = world.transforms.get(&entity).map(|t| t.position).unwrap_or([0.0; 3]);
+
+    // Solid cells per chunk, computed before the world is mutated
+    let mut rectangles: BTreeMap<(u32, u32), Vec<ecs::loaders::ldtk_loader::Rectangle>> = BTreeMap::new();
+    for &chunk in chunks {
+        let origin = (chunk.0 * TILEMAP_CHUNK_SIZE, chunk.1 * TILEMAP_CHUNK_SIZE);
+        let width = TILEMAP_CHUNK_SIZE.min(tilemap.width.saturating_sub(origin.0));
+        let height = TILEMAP_CHUNK_SIZE.min(tilemap.height.saturating_sub(origin.1));
+        let mut grid = vec![vec![false; width as usize]; height as usize];
+        for (y, row) in grid.iter_mut().enumerate() {
+            for (x, solid) in row.iter_mut().enumerate() {
+                *solid = tilemap
+                    .get_tile(origin.0 + x as u32, origin.1 + y as u32)
+                    .is_some_and(|tile| settings.should_collide(tile.tile_id));
+            }
+        }
+
+        let chunk_rectangles = if settings.mode == TilemapColliderMode::None {
+            Vec::new()
+        } else if settings.mode == TilemapColliderMode::Individual && !settings.use_composite {
+            grid.iter().enumerate()
+                .flat_map(|(y, row)| row.iter().enumerate().filter(|(_, solid)| **solid).map(move |(x, _)| (x, y)))
+                .map(|(x, y)| ecs::loaders::ldtk_loader::Rectangle { x: x as u32, y: y as u32, width: 1, height: 1 })
+                .collect()
+        } else {
+            ecs::loaders::ldtk_loader::find_rectangles(&mut grid, width, height)
+        };
+        rectangles.insert(chunk, chunk_rectangles.into_iter()
+            .map(|r| ecs::loaders::ldtk_loader::Rectangle { x: r.x + origin.0, y: r.y + origin.1, ..r })
+            .collect());
+    }
+
+    // Drop the chunks' previous colliders
+    let names: HashSet<String> = chunks.iter().map(|&chunk| chunk_collider_name(chunk)).collect();
+    let stale: Vec<Entity> = world.get_children(entity).iter().copied()
+        .filter(|child| world.names.get(child).is_some_and(|name| names.contains(name)))
+        .collect();
+    for child in stale {
+        world.despawn(child);
+    }
+
+    let mut count = 0;
+    for (chunk, chunk_rectangles) in rectangles {
+        for rect in chunk_rectangles {
+            let collider_entity = world.spawn();
+            let center_x = position[0] + rect.x as f32 + rect.width as f32 / 2.0 + settings.offset[0];
+            let center_y = position[1] - rect.y as f32 - rect.height as f32 / 2.0 + settings.offset[1];
+            world.transforms.insert(collider_entity, Transform::with_position(center_x, center_y, position[2]));
+
+            let mut collider = Collider::new(rect.width as f32, rect.height as f32);
+            collider.is_trigger = settings.is_trigger;
+            world.colliders.insert(collider_entity, collider);
+            world.rigidbodies.insert(collider_entity, Rigidbody2D {
+                velocity: (0.0, 0.0),
+                gravity_scale: 0.0,
+                mass: 1.0,
+                is_kinematic: true,
+                freeze_rotation: true,
+                enable_ccd: false,
+            });
+            world.names.insert(collider_entity, chunk_collider_name(chunk));
+            world.set_parent(collider_entity, Some(entity));
+            count += 1;
+        }
+    }
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rectangle_and_fill_cells() {
+        let stamp = TileStamp { width: 2, height: 1, tiles: vec![3, 4] };
+        let cells = rectangle_cells(&stamp, (2, 1), (0, 0));
+        assert_eq!(cells.len(), 6);
+        assert!(cells.contains(&((0, 0), 3)) && cells.contains(&((1, 0), 4)) && cells.contains(&((2, 1), 3)));
+
+        // A wall splits the map; fill stays on the clicked side
+        let mut tilemap = Tilemap::new("layer", "tiles", 5, 3);
+        for y in 0..3 {
+            tilemap.set_tile_id(2, y, 9);
+        }
+        let cells = fill_cells(&tilemap, &TileStamp::single(1), (0, 0));
+        assert_eq!(cells.len(), 6);
+        assert!(cells.iter().all(|((x, _), tile)| *x < 2 && *tile == 1));
+        assert!(fill_cells(&tilemap, &TileStamp::single(1), (7, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_stroke_keeps_first_before_and_skips_unchanged() {
+        let mut tilemap = Tilemap::new("layer", "tiles", 4, 4);
+        let mut stroke = TileStroke::new(0, TilePaintTool::Brush);
+        stroke.record(apply_cells(&mut tilemap, &brush_cells(&TileStamp::single(5), (1, 1))));
+        stroke.record(apply_cells(&mut tilemap, &brush_cells(&TileStamp::single(5), (1, 1))));
+        stroke.record(apply_cells(&mut tilemap, &brush_cells(&TileStamp::single(6), (1, 1))));
+        stroke.record(apply_cells(&mut tilemap, &brush_cells(&TileStamp::single(6), (-1, 8))));
+
+        assert_eq!(stroke.changes.len(), 1);
+        assert_eq!(stroke.changes[0].before.tile_id, 0);
+        assert_eq!(stroke.changes[0].after.tile_id, 6);
+
+        restore_changes(&mut tilemap, &stroke.changes, true);
+        assert!(tilemap.get_tile(1, 1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_chunk_colliders_merge_and_replace() {
+        let mut world = World::new();
+        let entity = world.spawn();
+        world.transforms.insert(entity, Transform::with_position(0.0, 0.0, 0.0));
+        let mut tilemap = Tilemap::new("layer", "tiles", 20, 4);
+        for x in 0..20 {
+            tilemap.set_tile_id(x, 3, 1);
+        }
+        world.tilemaps.insert(entity, tilemap);
+        world.tilemap_colliders.insert(entity, ecs::TilemapCollider::default());
+
+        // The floor crosses two chunks: one merged box per chunk
+        assert_eq!(regenerate_chunk_colliders(&mut world, entity, &[(0, 0), (1, 0)]), 2);
+        assert_eq!(world.get_children(entity).len(), 2);
+
+        // Regenerating one chunk replaces only its own collider
+        assert_eq!(regenerate_chunk_colliders(&mut world, entity, &[(1, 0)]), 1);
+        assert_eq!(world.get_children(entity).len(), 2);
+        let widths: Vec<f32> = world.get_children(entity).iter().map(|c| world.colliders[c].size[0]).collect();
+        assert!(widths.contains(&16.0) && widths.contains(&4.0), "{:?}", widths);
+    }
+}
@@ -0,0 +1,582 @@
+//! Animation Window
+//!
+//! Edits the clips of the selected entity's AnimatedSprite: ordered sprite sheet frames
+//! with per-frame durations, loop mode and event markers, plus a playback preview.
+//! Clips are stored on the component, so they are saved with the scene.
+
+use ecs::{AnimatedSprite, AnimationClip, AnimationKeyframe, AnimationMode, Entity, SpriteSheet, World};
+use egui;
+use engine::texture_manager::TextureManager;
+use std::path::{Path, PathBuf};
+
+fn open_request_id() -> egui::Id {
+    egui::Id::new("animation_window_open_request")
+}
+
+/// Ask the editor to show the Animation window (from menus and the inspector)
+pub fn open(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(open_request_id(), true));
+}
+
+/// Animation window state
+pub struct AnimationWindow {
+    pub open: bool,
+    selected_clip: usize,
+    selected_keyframe: Option<usize>,
+    preview_time: f32,
+    preview_playing: bool,
+    preview_speed: f32,
+    new_event: String,
+    /// .sprite files under the project's assets folder (project-relative)
+    sprite_files: Option<Vec<String>>,
+}
+
+impl Default for AnimationWindow {
+    fn default() -> Self {
+        Self {
+            open: false,
+            selected_clip: 0,
+            selected_keyframe: None,
+            preview_time: 0.0,
+            preview_playing: false,
+            preview_speed: 1.0,
+            new_event: String::new(),
+            sprite_files: None,
+        }
+    }
+}
+
+/// Keyframe shown `time` seconds into a clip, following the clip's loop mode
+pub fn keyframe_at(clip: &AnimationClip, time: f32) -> Option<usize> {
+    if clip.frames.is_empty() {
+        return None;
+    }
+    let duration = clip.duration();
+    if duration <= 0.0 {
+        return Some(0);
+    }
+
+    let time = match clip.mode {
+        AnimationMode::Once => time.clamp(0.0, duration),
+        AnimationMode::Loop => time.rem_euclid(duration),
+        AnimationMode::PingPong => {
+            let t = time.rem_euclid(duration * 2.0);
+            if t > duration { duration * 2.0 - t } else { t }
+        }
+    };
+
+    let mut start = 0.0;
+    for (index, keyframe) in clip.frames.iter().enumerate() {
+        start += keyframe.duration;
+        if time < start {
+            return Some(index);
+        }
+    }
+    Some(clip.frames.len() - 1)
+}
+
+/// Start time of a keyframe within a clip
+fn keyframe_start(clip: &AnimationClip, index: usize) -> f32 {
+    clip.frames.iter().take(index).map(|keyframe| keyframe.duration).sum()
+}
+
+/// Paint one sprite sheet frame into `rect`, letterboxed to keep its aspect ratio
+pub fn paint_sheet_frame(
+    ui: &egui::Ui,
+    rect: egui::Rect,
+    sheet: &SpriteSheet,
+    frame_index: usize,
+    texture_manager: &mut TextureManager,
+) {
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, egui::Color32::from_gray(35));
+
+    let Some(frame) = sheet.get_frame(frame_index) else {
+        painter.text(rect.center(), egui::Align2::CENTER_CENTER, "No frame", egui::FontId::proportional(11.0), egui::Color32::GRAY);
+        return;
+    };
+    if frame.width == 0 || frame.height == 0 || sheet.sheet_width == 0 || sheet.sheet_height == 0 {
+        return;
+    }
+
+    let texture_path = Path::new(&sheet.texture_path);
+    let Some(texture_id) = texture_manager.load_texture(ui.ctx(), &sheet.texture_id, texture_path).map(|texture| texture.id()) else {
+        painter.text(rect.center(), egui::Align2::CENTER_CENTER, "Missing texture", egui::FontId::proportional(11.0), egui::Color32::GRAY);
+        return;
+    };
+
+    let fit = (rect.width() / frame.width as f32).min(rect.height() / frame.height as f32);
+    let image_rect = egui::Rect::from_center_size(
+        rect.center(),
+        egui::vec2(frame.width as f32 * fit, frame.height as f32 * fit),
+    );
+    let uv = egui::Rect::from_min_max(
+        egui::pos2(frame.x as f32 / sheet.sheet_width as f32, frame.y as f32 / sheet.sheet_height as f32),
+        egui::pos2(
+            (frame.x + frame.width) as f32 / sheet.sheet_width as f32,
+            (frame.y + frame.height) as f32 / sheet.sheet_height as f32,
+        ),
+    );
+    painter.image(texture_id, image_rect, uv, egui::Color32::WHITE);
+}
+
+fn frame_label(sheet: Option<&SpriteSheet>, frame_index: usize) -> String {
+    match sheet.and_then(|sheet| sheet.get_frame(frame_index)).and_then(|frame| frame.name.clone()) {
+        Some(name) => format!("{}: {}", frame_index, name),
+        None => format!("Frame {}", frame_index),
+    }
+}
+
+fn mode_label(mode: AnimationMode) -> &'static str {
+    match mode {
+        AnimationMode::Once => "Once",
+        AnimationMode::Loop => "Loop",
+        AnimationMode::PingPong => "Ping Pong",
+    }
+}
+
+impl AnimationWindow {
+    /// Render the window; returns true if the scene was modified
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        world: &mut World,
+        selected_entity: Option<Entity>,
+        project_path: Option<&PathBuf>,
+        texture_manager: &mut TextureManager,
+        dt: f32,
+    ) -> bool {
+        if self.take_open_request(ctx) {
+            self.open = true;
+        }
+        if !self.open {
+            return false;
+        }
+
+        let mut changed = false;
+        let mut open = self.open;
+        egui::Window::new("🎞 Animation")
+            .open(&mut open)
+            .default_size([640.0, 420.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                changed = self.render_contents(ui, world, selected_entity, project_path, texture_manager, dt);
+            });
+        self.open = open;
+        changed
+    }
+
+    /// True once per `open` call; the sprite sheet list is rescanned when it is shown again
+    pub fn take_open_request(&mut self, ctx: &egui::Context) -> bool {
+        let requested = ctx.data_mut(|d| d.remove_temp::<bool>(open_request_id())).unwrap_or(false);
+        if requested {
+            self.sprite_files = None;
+        }
+        requested
+    }
+
+    /// Render inside a dock tab; returns true if the scene was modified
+    pub fn render_panel(
+        &mut self,
+        ui: &mut egui::Ui,
+        world: &mut World,
+        selected_entity: Option<Entity>,
+        project_path: Option<&PathBuf>,
+        texture_manager: &mut TextureManager,
+        dt: f32,
+    ) -> bool {
+        egui::ScrollArea::both()
+            .id_salt("animation_panel")
+            .show(ui, |ui| self.render_contents(ui, world, selected_entity, project_path, texture_manager, dt))
+            .inner
+    }
+
+    fn render_contents(
+        &mut self,
+        ui: &mut egui::Ui,
+        world: &mut World,
+        selected_entity: Option<Entity>,
+        project_path: Option<&PathBuf>,
+        texture_manager: &mut TextureManager,
+        dt: f32,
+    ) -> bool {
+        let Some(entity) = selected_entity else {
+            ui.label("Select an entity to edit its animations.");
+            return false;
+        };
+
+        let mut changed = false;
+
+        if !world.animated_sprites.contains_key(&entity) {
+            ui.label("The selected entity has no Animated Sprite.");
+            if ui.button("➕ Add Animated Sprite").clicked() {
+                let sheet_id = world.sprite_sheets.get(&entity).map(|sheet| sheet.texture_path.clone()).unwrap_or_default();
+                let mut animated_sprite = AnimatedSprite::new(sheet_id, 0.1);
+                animated_sprite.playing = false;
+                world.animated_sprites.insert(entity, animated_sprite);
+                changed = true;
+            }
+            return changed;
+        }
+
+        changed |= self.render_sprite_sheet_picker(ui, world, entity, project_path);
+        ui.separator();
+
+        let sheet = world.sprite_sheets.get(&entity);
+        let Some(animated_sprite) = world.animated_sprites.get_mut(&entity) else {
+            return changed;
+        };
+
+        ui.horizontal_top(|ui| {
+            // ----- Clip list -----
+            ui.vertical(|ui| {
+                ui.set_width(150.0);
+                ui.strong("Clips");
+                egui::ScrollArea::vertical().id_salt("animation_clip_list").max_height(240.0).show(ui, |ui| {
+                    for (index, clip) in animated_sprite.clips.iter().enumerate() {
+                        let is_default = animated_sprite.clip.as_deref() == Some(clip.name.as_str());
+                        let label = if is_default { format!("▶ {}", clip.name) } else { clip.name.clone() };
+                        if ui.selectable_label(index == self.selected_clip, label).clicked() {
+                            self.selected_clip = index;
+                            self.selected_keyframe = None;
+                            self.preview_time = 0.0;
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    if ui.button("➕ Clip").on_hover_text("New clip using every sheet frame").clicked() {
+                        let mut name = format!("Clip {}", animated_sprite.clips.len() + 1);
+                        while animated_sprite.find_clip(&name).is_some() {
+                            name.push('_');
+                        }
+                        let mut clip = AnimationClip::new(name);
+                        let frame_count = sheet.map(|sheet| sheet.frames.len()).unwrap_or(1).max(1);
+                        clip.frames = (0..frame_count)
+                            .map(|frame| AnimationKeyframe::new(frame, animated_sprite.frame_duration))
+                            .collect();
+                        animated_sprite.clips.push(clip);
+                        self.selected_clip = animated_sprite.clips.len() - 1;
+                        self.selected_keyframe = None;
+                        changed = true;
+                    }
+                    let can_delete = self.selected_clip < animated_sprite.clips.len();
+                    if ui.add_enabled(can_delete, egui::Button::new("🗑")).on_hover_text("Delete clip").clicked() {
+                        let removed = animated_sprite.clips.remove(self.selected_clip);
+                        if animated_sprite.clip.as_deref() == Some(removed.name.as_str()) {
+                            animated_sprite.clip = None;
+                        }
+                        self.selected_clip = self.selected_clip.saturating_sub(1);
+                        self.selected_keyframe = None;
+                        changed = true;
+                    }
+                });
+            });
+
+            ui.separator();
+
+            // ----- Selected clip -----
+            ui.vertical(|ui| {
+                if self.selected_clip >= animated_sprite.clips.len() {
+                    ui.label("Add a clip to start animating.");
+                    return;
+                }
+                changed |= self.render_clip_properties(ui, animated_sprite);
+                ui.separator();
+                self.render_preview(ui, &animated_sprite.clips[self.selected_clip], sheet, texture_manager, dt);
+                ui.separator();
+                changed |= self.render_keyframes(ui, &mut animated_sprite.clips[self.selected_clip], sheet);
+            });
+        });
+
+        changed
+    }
+
+    fn render_sprite_sheet_picker(
+        &mut self,
+        ui: &mut egui::Ui,
+        world: &mut World,
+        entity: Entity,
+        project_path: Option<&PathBuf>,
+    ) -> bool {
+        let mut picked = None;
+
+        ui.horizontal(|ui| {
+            ui.label("Sprite Sheet");
+            let current = world.sprite_sheets.get(&entity)
+                .map(|sheet| format!("{} ({} frames)", sheet.texture_path, sheet.frames.len()))
+                .unwrap_or_else(|| "None".to_string());
+
+            let sprite_files = self.sprite_files.get_or_insert_with(|| {
+                let Some(project_path) = project_path else {
+                    return Vec::new();
+                };
+                let mut files: Vec<String> = crate::ui::sprite_picker::find_sprite_files(&project_path.join("assets"))
+                    .into_iter()
+                    .filter_map(|path| path.strip_prefix(project_path).ok().map(|p| p.to_string_lossy().replace('\\', "/")))
+                    .collect();
+                files.sort();
+                files
+            });
+
+            egui::ComboBox::from_id_salt("animation_sprite_sheet")
+                .selected_text(current)
+                .width(260.0)
+                .show_ui(ui, |ui| {
+                    if sprite_files.is_empty() {
+                        ui.label("No .sprite files in assets/");
+                    }
+                    for file in sprite_files.iter() {
+                        if ui.selectable_label(false, file).clicked() {
+                            picked = Some(file.clone());
+                        }
+                    }
+                });
+
+            if ui.small_button("🔄").on_hover_text("Rescan .sprite files").clicked() {
+                self.sprite_files = None;
+            }
+        });
+
+        let (Some(file), Some(project_path)) = (picked, project_path) else {
+            return false;
+        };
+        match SpriteSheet::from_sprite_file(project_path.join(&file)) {
+            Ok(sheet) => {
+                if let Some(animated_sprite) = world.animated_sprites.get_mut(&entity) {
+                    animated_sprite.sprite_sheet_id = sheet.texture_path.clone();
+                }
+                world.sprite_sheets.insert(entity, sheet);
+                true
+            }
+            Err(e) => {
+                log::error!("Failed to load sprite sheet {}: {}", file, e);
+                false
+            }
+        }
+    }
+
+    fn render_clip_properties(&mut self, ui: &mut egui::Ui, animated_sprite: &mut AnimatedSprite) -> bool {
+        let mut changed = false;
+        let old_name = animated_sprite.clips[self.selected_clip].name.clone();
+
+        egui::Grid::new("animation_clip_grid")
+            .num_columns(2)
+            .spacing([8.0, 6.0])
+            .show(ui, |ui| {
+                ui.label("Name");
+                let mut name = old_name.clone();
+                if ui.text_edit_singleline(&mut name).changed() {
+                    let taken = animated_sprite.clips.iter().enumerate()
+                        .any(|(index, clip)| index != self.selected_clip && clip.name == name);
+                    if !name.is_empty() && !taken {
+                        if animated_sprite.clip.as_deref() == Some(old_name.as_str()) {
+                            animated_sprite.clip = Some(name.clone());
+                        }
+                        animated_sprite.clips[self.selected_clip].name = name;
+                        changed = true;
+                    }
+                }
+                ui.end_row();
+
+                let clip = &mut animated_sprite.clips[self.selected_clip];
+                ui.label("Loop Mode");
+                egui::ComboBox::from_id_salt("animation_clip_mode")
+                    .selected_text(mode_label(clip.mode))
+                    .show_ui(ui, |ui| {
+                        for mode in [AnimationMode::Loop, AnimationMode::Once, AnimationMode::PingPong] {
+                            changed |= ui.selectable_value(&mut clip.mode, mode, mode_label(mode)).changed();
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Play On Start");
+                let clip_name = clip.name.clone();
+                let mut is_default = animated_sprite.clip.as_deref() == Some(clip_name.as_str());
+                if ui.checkbox(&mut is_default, "").on_hover_text("The component plays this clip").changed() {
+                    animated_sprite.clip = is_default.then_some(clip_name);
+                    animated_sprite.current_frame = 0;
+                    changed = true;
+                }
+                ui.end_row();
+            });
+
+        changed
+    }
+
+    fn render_preview(
+        &mut self,
+        ui: &mut egui::Ui,
+        clip: &AnimationClip,
+        sheet: Option<&SpriteSheet>,
+        texture_manager: &mut TextureManager,
+        dt: f32,
+    ) {
+        let duration = clip.duration();
+        if self.preview_playing && duration > 0.0 {
+            self.preview_time += dt * self.preview_speed;
+            match clip.mode {
+                AnimationMode::Once if self.preview_time >= duration => {
+                    self.preview_time = duration;
+                    self.preview_playing = false;
+                }
+                AnimationMode::Loop => self.preview_time = self.preview_time.rem_euclid(duration),
+                AnimationMode::PingPong => self.preview_time = self.preview_time.rem_euclid(duration * 2.0),
+                _ => {}
+            }
+            ui.ctx().request_repaint();
+        }
+
+        let current = keyframe_at(clip, self.preview_time);
+
+        ui.horizontal(|ui| {
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(128.0, 128.0), egui::Sense::hover());
+            match (sheet, current) {
+                (Some(sheet), Some(index)) => paint_sheet_frame(ui, rect, sheet, clip.frames[index].frame, texture_manager),
+                _ => {
+                    ui.painter().rect_filled(rect, 2.0, egui::Color32::from_gray(35));
+                }
+            }
+
+            ui.vertical(|ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("⏮").on_hover_text("Rewind").clicked() {
+                        self.preview_time = 0.0;
+                    }
+                    let play_label = if self.preview_playing { "⏸" } else { "▶" };
+                    if ui.button(play_label).clicked() {
+                        if !self.preview_playing && clip.mode == AnimationMode::Once && self.preview_time >= duration {
+                            self.preview_time = 0.0;
+                        }
+                        self.preview_playing = !self.preview_playing;
+                    }
+                    ui.label("Speed");
+                    ui.add(egui::DragValue::new(&mut self.preview_speed).speed(0.05).range(0.05..=8.0).suffix("x"));
+                });
+
+                let scrub_max = if clip.mode == AnimationMode::PingPong { duration * 2.0 } else { duration };
+                let mut time = self.preview_time.min(scrub_max);
+                if ui.add(egui::Slider::new(&mut time, 0.0..=scrub_max.max(0.001)).suffix("s")).changed() {
+                    self.preview_time = time;
+                    self.preview_playing = false;
+                }
+
+                if let Some(index) = current {
+                    ui.label(format!(
+                        "Keyframe {} / {}  •  {}",
+                        index + 1,
+                        clip.frames.len(),
+                        frame_label(sheet, clip.frames[index].frame)
+                    ));
+                }
+                ui.label(egui::RichText::new(format!("Length {:.2}s", duration)).small().color(egui::Color32::GRAY));
+            });
+        });
+    }
+
+    fn render_keyframes(&mut self, ui: &mut egui::Ui, clip: &mut AnimationClip, sheet: Option<&SpriteSheet>) -> bool {
+        let mut changed = false;
+        let current = keyframe_at(clip, self.preview_time);
+        let frame_count = sheet.map(|sheet| sheet.frames.len()).unwrap_or(0);
+
+        ui.strong("Keyframes");
+        let mut move_up = None;
+        let mut remove = None;
+
+        egui::ScrollArea::vertical().id_salt("animation_keyframes").max_height(180.0).show(ui, |ui| {
+            for (index, keyframe) in clip.frames.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    let marker = if current == Some(index) { "▶" } else { " " };
+                    let selected = self.selected_keyframe == Some(index);
+                    if ui.selectable_label(selected, format!("{} {}", marker, index)).clicked() {
+                        self.selected_keyframe = Some(index);
+                    }
+
+                    egui::ComboBox::from_id_salt(("animation_keyframe_frame", index))
+                        .selected_text(frame_label(sheet, keyframe.frame))
+                        .width(140.0)
+                        .show_ui(ui, |ui| {
+                            for frame in 0..frame_count {
+                                changed |= ui.selectable_value(&mut keyframe.frame, frame, frame_label(sheet, frame)).changed();
+                            }
+                        });
+
+                    changed |= ui.add(
+                        egui::DragValue::new(&mut keyframe.duration).speed(0.005).range(0.01..=10.0).suffix("s")
+                    ).changed();
+
+                    if !keyframe.events.is_empty() {
+                        ui.label(format!("🏷 {}", keyframe.events.len()))
+                            .on_hover_text(keyframe.events.join(", "));
+                    }
+
+                    if ui.add_enabled(index > 0, egui::Button::new("⬆").small()).clicked() {
+                        move_up = Some(index);
+                    }
+                    if ui.small_button("🗑").clicked() {
+                        remove = Some(index);
+                    }
+                });
+            }
+        });
+
+        if let Some(index) = move_up {
+            clip.frames.swap(index - 1, index);
+            changed = true;
+        }
+        if let Some(index) = remove {
+            clip.frames.remove(index);
+            self.selected_keyframe = None;
+            changed = true;
+        }
+
+        if ui.button("➕ Frame").on_hover_text("Append the next sheet frame").clicked() {
+            let (frame, duration) = clip.frames.last()
+                .map(|last| ((last.frame + 1) % frame_count.max(1), last.duration))
+                .unwrap_or((0, 0.1));
+            clip.frames.push(AnimationKeyframe::new(frame, duration));
+            changed = true;
+        }
+
+        // Event markers of the selected keyframe
+        if let Some(index) = self.selected_keyframe.filter(|&index| index < clip.frames.len()) {
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.strong(format!("Events on keyframe {}", index));
+                if ui.small_button("⏭").on_hover_text("Move the preview to this keyframe").clicked() {
+                    self.preview_time = keyframe_start(clip, index);
+                    self.preview_playing = false;
+                }
+            });
+
+            let keyframe = &mut clip.frames[index];
+            let mut remove_event = None;
+            ui.horizontal_wrapped(|ui| {
+                for (event_index, event) in keyframe.events.iter().enumerate() {
+                    if ui.button(format!("🏷 {} ✖", event)).on_hover_text("Remove event").clicked() {
+                        remove_event = Some(event_index);
+                    }
+                }
+            });
+            if let Some(event_index) = remove_event {
+                keyframe.events.remove(event_index);
+                changed = true;
+            }
+
+            ui.horizontal(|ui| {
+                let response = ui.add(egui::TextEdit::singleline(&mut self.new_event).hint_text("event name").desired_width(140.0));
+                let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if (ui.button("➕ Event").clicked() || submitted) && !self.new_event.trim().is_empty() {
+                    keyframe.events.push(self.new_event.trim().to_string());
+                    self.new_event.clear();
+                    changed = true;
+                }
+            });
+            ui.label(egui::RichText::new("💡 Scripts receive these in on_animation_event(name)")
+                .small()
+                .color(egui::Color32::from_rgb(150, 150, 150)));
+        }
+
+        changed
+    }
+}
@@ -0,0 +1,134 @@
+//! Validate Assets window (Edit menu)
+//!
+//! Lists every component reference in the open scene whose asset file can't be found
+//! (see assets::references), with the entity that owns it. "Fix..." picks a
+//! replacement file and points the reference at it.
+
+use crate::assets::references::{self, AssetReference, AssetReferenceIndex, ReferenceKind};
+use ecs::{Entity, World};
+use egui::{Color32, RichText};
+use std::collections::HashMap;
+use std::path::Path;
+
+fn open_request_id() -> egui::Id {
+    egui::Id::new("asset_validation_open_request")
+}
+
+/// Validate the open scene and show the report
+pub fn open(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(open_request_id(), true));
+}
+
+/// Where the replacement picker starts
+fn picker_directory(project_path: &Path, kind: ReferenceKind) -> std::path::PathBuf {
+    let preferred = match kind {
+        ReferenceKind::Script => project_path.join("scripts"),
+        _ => project_path.join("assets"),
+    };
+    if preferred.is_dir() { preferred } else { project_path.to_path_buf() }
+}
+
+#[derive(Default)]
+pub struct AssetValidationWindow {
+    pub open: bool,
+}
+
+impl AssetValidationWindow {
+    /// Returns true if a reference was retargeted (the scene changed and its assets
+    /// need loading again)
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        world: &mut World,
+        project_path: Option<&Path>,
+        index: &mut AssetReferenceIndex,
+        entity_names: &HashMap<Entity, String>,
+        selected_entity: &mut Option<Entity>,
+    ) -> bool {
+        if ctx.data_mut(|d| d.remove_temp::<bool>(open_request_id())).unwrap_or(false) {
+            self.open = true;
+            if let Some(project_path) = project_path {
+                index.validate(world, project_path);
+            }
+        }
+        if !self.open {
+            return false;
+        }
+
+        let mut changed = false;
+        let mut open = self.open;
+        egui::Window::new("🔗 Validate Assets")
+            .open(&mut open)
+            .default_size([560.0, 360.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                let Some(project_path) = project_path else {
+                    ui.label("Open a project to validate its assets");
+                    return;
+                };
+
+                ui.horizontal(|ui| {
+                    if index.broken.is_empty() {
+                        ui.colored_label(Color32::from_rgb(120, 200, 120), "✅ Every asset reference resolves");
+                    } else {
+                        ui.colored_label(
+                            Color32::from_rgb(230, 180, 80),
+                            format!("⚠ {} broken reference(s)", index.broken.len()),
+                        );
+                    }
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button("🔄 Re-check").clicked() {
+                            index.validate(world, project_path);
+                        }
+                    });
+                });
+                ui.separator();
+
+                let mut retarget: Option<(AssetReference, String)> = None;
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("asset_validation_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .spacing([12.0, 4.0])
+                        .show(ui, |ui| {
+                            for reference in &index.broken {
+                                let name = entity_names
+                                    .get(&reference.entity)
+                                    .cloned()
+                                    .unwrap_or_else(|| format!("Entity {}", reference.entity));
+                                if ui
+                                    .selectable_label(*selected_entity == Some(reference.entity), name)
+                                    .on_hover_text("Select entity")
+                                    .clicked()
+                                {
+                                    *selected_entity = Some(reference.entity);
+                                }
+                                ui.label(reference.kind.label());
+                                ui.label(RichText::new(&reference.reference).monospace().color(Color32::from_rgb(230, 90, 90)));
+                                if ui.button("Fix...").on_hover_text("Pick the file this should point at").clicked() {
+                                    let (filter_name, extensions) = reference.kind.file_filter();
+                                    if let Some(file) = rfd::FileDialog::new()
+                                        .add_filter(filter_name, extensions)
+                                        .set_directory(picker_directory(project_path, reference.kind))
+                                        .pick_file()
+                                    {
+                                        let value = references::reference_for_file(project_path, reference.kind, &file);
+                                        retarget = Some((reference.clone(), value));
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                if let Some((reference, value)) = retarget {
+                    if references::retarget(world, &reference, value) {
+                        changed = true;
+                    }
+                    index.validate(world, project_path);
+                }
+            });
+        self.open = open;
+        changed
+    }
+}
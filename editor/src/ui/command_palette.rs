@@ -0,0 +1,119 @@
+//! Command Palette
+//!
+//! Ctrl+Shift+P: type part of a command's name, pick it with the arrow keys and Enter
+//! (or a click) to run it. Lists the commands available in the current mode.
+
+use crate::commands::CommandRegistry;
+use egui::{Align2, Color32, Key, RichText};
+
+/// Rows shown at once
+const MAX_RESULTS: usize = 12;
+
+fn open_request_id() -> egui::Id {
+    egui::Id::new("command_palette_open_request")
+}
+
+/// Ask the editor to show the command palette
+pub fn open(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(open_request_id(), true));
+}
+
+#[derive(Default)]
+pub struct CommandPalette {
+    pub open: bool,
+    query: String,
+    selected: usize,
+    focus_query: bool,
+}
+
+impl CommandPalette {
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, commands: &mut CommandRegistry, playing: bool) {
+        if ctx.data_mut(|d| d.remove_temp::<bool>(open_request_id())).unwrap_or(false) {
+            self.open = true;
+            self.query.clear();
+            self.selected = 0;
+            self.focus_query = true;
+        }
+        if !self.open {
+            return;
+        }
+
+        let results: Vec<(String, String, String, Option<String>)> = commands
+            .search(&self.query)
+            .into_iter()
+            .filter(|c| c.scope.is_active(playing))
+            .take(MAX_RESULTS)
+            .map(|c| (c.id.clone(), c.name.clone(), c.category.clone(), commands.shortcut_label(&c.id)))
+            .collect();
+
+        let (up, down, enter) = ctx.input(|i| {
+            (i.key_pressed(Key::ArrowUp), i.key_pressed(Key::ArrowDown), i.key_pressed(Key::Enter))
+        });
+        if down && self.selected + 1 < results.len() {
+            self.selected += 1;
+        }
+        if up {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        self.selected = self.selected.min(results.len().saturating_sub(1));
+
+        let mut run: Option<String> = None;
+        let window = egui::Window::new("Command Palette")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .anchor(Align2::CENTER_TOP, [0.0, 80.0])
+            .fixed_size([440.0, 0.0])
+            .show(ctx, |ui| {
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.query)
+                        .hint_text("Type a command...")
+                        .desired_width(f32::INFINITY),
+                );
+                if self.focus_query {
+                    response.request_focus();
+                    self.focus_query = false;
+                }
+                if response.changed() {
+                    self.selected = 0;
+                }
+
+                ui.separator();
+                if results.is_empty() {
+                    ui.weak("No matching commands");
+                }
+                for (index, (id, name, category, shortcut)) in results.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        let label = ui.selectable_label(index == self.selected, name);
+                        ui.label(RichText::new(category).small().color(Color32::GRAY));
+                        if let Some(shortcut) = shortcut {
+                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                                ui.label(RichText::new(shortcut).monospace().color(Color32::GRAY));
+                            });
+                        }
+                        if label.clicked() {
+                            run = Some(id.clone());
+                        }
+                    });
+                }
+            });
+
+        if enter {
+            run = run.or_else(|| results.get(self.selected).map(|(id, ..)| id.clone()));
+        }
+        if let Some(id) = run {
+            commands.invoke(id);
+            self.open = false;
+            return;
+        }
+
+        // Clicking anywhere else dismisses it
+        if window.is_some_and(|w| w.response.clicked_elsewhere()) {
+            self.open = false;
+        }
+    }
+}
@@ -1,5 +1,9 @@
 pub mod exit_dialog;
 pub mod layout_dialog;
+pub mod play_mode_changes_dialog;
+pub mod preload_dialog;
+pub mod recovery_dialog;
+pub mod scene_reload_dialog;
 // prefab dialog logic was deeply coupled with prefab_manager creation loop, 
 // leaving it in editor_logic for now or standardizing it later is fine, 
 // but let's try to extract it if possible? 
@@ -8,3 +12,7 @@ pub mod layout_dialog;
 
 pub use exit_dialog::ExitDialog;
 pub use layout_dialog::LayoutDialog;
+pub use play_mode_changes_dialog::PlayModeChangesDialog;
+pub use preload_dialog::PreloadDialog;
+pub use recovery_dialog::RecoveryDialog;
+pub use scene_reload_dialog::SceneReloadDialog;
@@ -0,0 +1,85 @@
+use crate::states::EditorState;
+use crate::systems::play_mode_changes::{apply_runtime_changes, RuntimeChangeKind};
+
+pub struct PlayModeChangesDialog;
+
+impl PlayModeChangesDialog {
+    pub fn render(
+        egui_ctx: &egui::Context,
+        editor_state: &mut EditorState,
+    ) {
+        if editor_state.pending_play_changes.is_empty() {
+            return;
+        }
+
+        let mut apply = false;
+        let mut discard = false;
+
+        egui::Window::new("Keep Play Mode Changes?")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(360.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(egui_ctx, |ui| {
+                ui.label("These changes were made while playing. Select the ones to copy back into the scene.");
+                ui.add_space(6.0);
+
+                egui::ScrollArea::vertical()
+                    .id_salt("play_mode_changes_scroll")
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for change in editor_state.pending_play_changes.iter_mut() {
+                            let label = change.description();
+                            let label = match change.kind {
+                                RuntimeChangeKind::Component(_) => egui::RichText::new(label),
+                                RuntimeChangeKind::NewEntity { .. } => egui::RichText::new(label).italics(),
+                            };
+                            ui.checkbox(&mut change.apply, label);
+                        }
+                    });
+
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    if ui.small_button("Select All").clicked() {
+                        editor_state.pending_play_changes.iter_mut().for_each(|c| c.apply = true);
+                    }
+                    if ui.small_button("Select None").clicked() {
+                        editor_state.pending_play_changes.iter_mut().for_each(|c| c.apply = false);
+                    }
+                });
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    let any_selected = editor_state.pending_play_changes.iter().any(|c| c.apply);
+                    if ui.add_enabled(any_selected, egui::Button::new("Apply Selected")).clicked() {
+                        apply = true;
+                    }
+                    if ui.button("Discard").clicked() {
+                        discard = true;
+                    }
+                });
+            });
+
+        if apply {
+            let changes = std::mem::take(&mut editor_state.pending_play_changes);
+            let (applied, skipped) = apply_runtime_changes(
+                &changes,
+                &mut editor_state.world,
+                &mut editor_state.entity_names,
+            );
+            if applied > 0 {
+                editor_state.scene_modified = true;
+                editor_state.console.info(format!("Applied {} play mode change(s)", applied));
+            }
+            if skipped > 0 {
+                editor_state.console.warning(format!(
+                    "Skipped {} play mode change(s): entity not found in the restored scene",
+                    skipped
+                ));
+            }
+        } else if discard {
+            editor_state.pending_play_changes.clear();
+            editor_state.console.info("Discarded play mode changes".to_string());
+        }
+    }
+}
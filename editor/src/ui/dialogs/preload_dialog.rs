@@ -0,0 +1,46 @@
+use crate::states::EditorState;
+
+pub struct PreloadDialog;
+
+impl PreloadDialog {
+    pub fn render(
+        egui_ctx: &egui::Context,
+        editor_state: &mut EditorState,
+    ) {
+        if !editor_state.waiting_for_preload {
+            return;
+        }
+        let Some(preload) = &editor_state.scene_preload else {
+            return;
+        };
+
+        let mut cancel = false;
+
+        egui::Window::new("Loading Scene Assets")
+            .collapsible(false)
+            .resizable(false)
+            .default_width(320.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(egui_ctx, |ui| {
+                ui.label("Play mode starts once the scene's preload list has loaded.");
+                ui.add_space(6.0);
+                ui.add(
+                    egui::ProgressBar::new(preload.progress())
+                        .text(format!("{} / {}", preload.finished_count(), preload.len()))
+                        .animate(true),
+                );
+                ui.add_space(6.0);
+                if ui.button("Cancel").clicked() {
+                    cancel = true;
+                }
+            });
+
+        // Loader threads don't wake the UI; keep polling
+        egui_ctx.request_repaint();
+
+        if cancel {
+            editor_state.cancel_scene_preload();
+            editor_state.console.info("Play cancelled while preloading assets".to_string());
+        }
+    }
+}
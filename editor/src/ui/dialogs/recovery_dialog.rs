@@ -0,0 +1,46 @@
+use crate::states::EditorState;
+
+/// Offered on startup when the previous session crashed and left an autosave
+/// newer than its scene file
+pub struct RecoveryDialog;
+
+impl RecoveryDialog {
+    pub fn render(
+        egui_ctx: &egui::Context,
+        editor_state: &mut EditorState,
+    ) {
+        let Some((scene_path, autosave_path)) = editor_state.pending_recovery.clone() else {
+            return;
+        };
+
+        egui::Window::new("Recover Unsaved Changes")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(egui_ctx, |ui| {
+                ui.label("The editor did not shut down cleanly last time.");
+                ui.label(format!(
+                    "An autosave of {} is newer than the saved scene.",
+                    scene_path.file_name().unwrap_or_default().to_string_lossy()
+                ));
+                ui.label(egui::RichText::new(autosave_path.display().to_string()).small().color(egui::Color32::GRAY));
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("Restore Autosave").clicked() {
+                        match editor_state.recover_autosave() {
+                            Ok(()) => editor_state.console.info(format!(
+                                "Restored autosave {} (save to keep it)",
+                                autosave_path.display()
+                            )),
+                            Err(e) => editor_state.console.error(format!("Failed to restore autosave: {}", e)),
+                        }
+                    }
+
+                    if ui.button("Discard").clicked() {
+                        editor_state.pending_recovery = None;
+                    }
+                });
+            });
+    }
+}
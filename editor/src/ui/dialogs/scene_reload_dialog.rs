@@ -0,0 +1,214 @@
+use crate::scene_diff::{ComponentChange, EntityChange, SceneDiff};
+use crate::scene_watch::SceneReloadReason;
+use crate::states::EditorState;
+
+const ADDED_COLOR: egui::Color32 = egui::Color32::from_rgb(100, 200, 100);
+const REMOVED_COLOR: egui::Color32 = egui::Color32::from_rgb(220, 100, 100);
+const CHANGED_COLOR: egui::Color32 = egui::Color32::from_rgb(220, 180, 80);
+
+/// Fields listed per changed component before the rest are summarized
+const MAX_FIELDS_SHOWN: usize = 50;
+
+fn revert_request_id() -> egui::Id {
+    egui::Id::new("scene_revert_request")
+}
+
+/// File > Revert Scene (the menu bar can't reach the editor state)
+pub fn request_revert(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(revert_request_id(), true));
+}
+
+/// Offered when the open scene file changed outside the editor, and by File > Revert Scene
+pub struct SceneReloadDialog;
+
+impl SceneReloadDialog {
+    pub fn render(
+        egui_ctx: &egui::Context,
+        editor_state: &mut EditorState,
+    ) {
+        if egui_ctx.data_mut(|d| d.remove_temp::<bool>(revert_request_id())).unwrap_or(false) {
+            editor_state.request_scene_revert();
+        }
+        let Some(prompt) = editor_state.scene_reload_prompt.as_ref() else {
+            return;
+        };
+        let reason = prompt.reason;
+        let file_name = prompt.path.file_name().unwrap_or_default().to_string_lossy().to_string();
+
+        // The diff is computed once, the first time it's shown
+        if prompt.show_diff && prompt.diff.is_none() {
+            let disk_json = prompt.disk_json.clone();
+            let diff = editor_state.diff_against_disk(&disk_json).map_err(|e| e.to_string());
+            if let Some(prompt) = editor_state.scene_reload_prompt.as_mut() {
+                prompt.diff = Some(diff);
+            }
+        }
+
+        let mut reload = false;
+        let mut keep = false;
+        let mut toggle_diff = false;
+        let title = match reason {
+            SceneReloadReason::ExternalChange => "Scene Changed on Disk",
+            SceneReloadReason::Revert => "Revert Scene?",
+        };
+
+        egui::Window::new(title)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(420.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+            .show(egui_ctx, |ui| {
+                match reason {
+                    SceneReloadReason::ExternalChange => {
+                        ui.label(format!("{} was changed outside the editor.", file_name));
+                    }
+                    SceneReloadReason::Revert => {
+                        ui.label(format!("Go back to the saved version of {}?", file_name));
+                    }
+                }
+                if editor_state.scene_modified {
+                    ui.label(egui::RichText::new("Reloading discards your unsaved changes.").color(REMOVED_COLOR));
+                }
+                ui.add_space(10.0);
+
+                ui.horizontal(|ui| {
+                    let reload_label = match reason {
+                        SceneReloadReason::ExternalChange => "Reload",
+                        SceneReloadReason::Revert => "Revert",
+                    };
+                    if ui.button(reload_label).clicked() {
+                        reload = true;
+                    }
+                    let keep_label = match reason {
+                        SceneReloadReason::ExternalChange => "Keep Mine",
+                        SceneReloadReason::Revert => "Cancel",
+                    };
+                    if ui.button(keep_label).clicked() {
+                        keep = true;
+                    }
+                    let diff_label = if editor_state.scene_reload_prompt.as_ref().is_some_and(|p| p.show_diff) {
+                        "Hide Diff"
+                    } else {
+                        "Show Diff"
+                    };
+                    if ui.button(diff_label).clicked() {
+                        toggle_diff = true;
+                    }
+                });
+
+                let Some(prompt) = editor_state.scene_reload_prompt.as_ref() else {
+                    return;
+                };
+                if !prompt.show_diff {
+                    return;
+                }
+                ui.separator();
+                match &prompt.diff {
+                    Some(Ok(diff)) => render_diff(ui, diff),
+                    Some(Err(e)) => {
+                        ui.colored_label(REMOVED_COLOR, format!("Can't read the scene on disk: {}", e));
+                    }
+                    None => {}
+                }
+            });
+
+        if toggle_diff {
+            if let Some(prompt) = editor_state.scene_reload_prompt.as_mut() {
+                prompt.show_diff = !prompt.show_diff;
+            }
+        }
+
+        if reload {
+            let Some(prompt) = editor_state.scene_reload_prompt.take() else {
+                return;
+            };
+            match editor_state.reload_scene_from_disk(&prompt.disk_json) {
+                Ok(()) => editor_state.console.info(format!("Reloaded scene from disk: {}", file_name)),
+                Err(e) => editor_state.console.error(format!("Failed to reload scene: {}", e)),
+            }
+        } else if keep {
+            let Some(prompt) = editor_state.scene_reload_prompt.take() else {
+                return;
+            };
+            if reason == SceneReloadReason::ExternalChange {
+                // The editor's version now differs from the file: the next save overwrites it
+                editor_state.scene_watch.acknowledge(&prompt.disk_json);
+                editor_state.scene_modified = true;
+                editor_state.console.info(format!("Kept the editor's version of {}", file_name));
+            }
+        }
+    }
+}
+
+/// Entity by entity: what reloading the disk version would change
+fn render_diff(ui: &mut egui::Ui, diff: &SceneDiff) {
+    if diff.is_empty() {
+        ui.label("The saved scene matches the editor's.");
+        return;
+    }
+    let (added, removed, changed) = diff.counts();
+    ui.horizontal(|ui| {
+        ui.colored_label(ADDED_COLOR, format!("+{} added", added));
+        ui.colored_label(REMOVED_COLOR, format!("−{} removed", removed));
+        ui.colored_label(CHANGED_COLOR, format!("~{} changed", changed));
+    });
+    ui.label(egui::RichText::new("Values read: editor → disk").small().color(egui::Color32::GRAY));
+
+    egui::ScrollArea::vertical()
+        .id_salt("scene_reload_diff_scroll")
+        .max_height(320.0)
+        .show(ui, |ui| {
+            if !diff.scene_fields.is_empty() {
+                egui::CollapsingHeader::new(egui::RichText::new("~ Scene").color(CHANGED_COLOR))
+                    .id_salt("scene_diff_scene_fields")
+                    .show(ui, |ui| render_fields(ui, "scene_fields", &diff.scene_fields));
+            }
+
+            for entity in &diff.entities {
+                let (marker, color) = match entity.change {
+                    EntityChange::Added => ("+", ADDED_COLOR),
+                    EntityChange::Removed => ("−", REMOVED_COLOR),
+                    EntityChange::Changed => ("~", CHANGED_COLOR),
+                };
+                let header = format!("{} {} (#{})", marker, entity.name, entity.entity);
+                egui::CollapsingHeader::new(egui::RichText::new(header).color(color))
+                    .id_salt(("scene_diff_entity", entity.entity))
+                    .show(ui, |ui| {
+                        for component in &entity.components {
+                            match &component.change {
+                                ComponentChange::Added => {
+                                    ui.colored_label(ADDED_COLOR, format!("+ {}", component.component));
+                                }
+                                ComponentChange::Removed => {
+                                    ui.colored_label(REMOVED_COLOR, format!("− {}", component.component));
+                                }
+                                ComponentChange::Changed(fields) => {
+                                    ui.colored_label(CHANGED_COLOR, format!("~ {}", component.component));
+                                    ui.indent(("scene_diff_fields", entity.entity, &component.component), |ui| {
+                                        render_fields(ui, &format!("{}_{}", entity.entity, component.component), fields);
+                                    });
+                                }
+                            }
+                        }
+                    });
+            }
+        });
+}
+
+fn render_fields(ui: &mut egui::Ui, id: &str, fields: &[crate::scene_diff::FieldDiff]) {
+    egui::Grid::new(("scene_diff_grid", id))
+        .num_columns(2)
+        .spacing([10.0, 2.0])
+        .show(ui, |ui| {
+            for field in fields.iter().take(MAX_FIELDS_SHOWN) {
+                ui.label(egui::RichText::new(&field.path).monospace());
+                let mine = field.mine.as_deref().unwrap_or("—");
+                let disk = field.disk.as_deref().unwrap_or("—");
+                ui.label(format!("{} → {}", mine, disk));
+                ui.end_row();
+            }
+        });
+    if fields.len() > MAX_FIELDS_SHOWN {
+        ui.label(egui::RichText::new(format!("… and {} more", fields.len() - MAX_FIELDS_SHOWN)).small().color(egui::Color32::GRAY));
+    }
+}
@@ -5,7 +5,7 @@ use wgpu;
 use egui_wgpu;
 use std::collections::HashMap;
 use crate::{Console, SceneCamera, SceneGrid, AssetManager, DragDropState};
-use super::{TransformTool, inspector, scene_view, texture_inspector};
+use super::{TransformTool, inspector, scene_view, texture_inspector, ui_inspector};
 use super::panels::{hierarchy, asset_browser};
 use engine_core::assets::AssetLoader;
 
@@ -26,12 +26,61 @@ pub enum EditorTab {
     ColliderSettings,  // Collider configuration panel for tilemap colliders
     SpriteEditor(std::path::PathBuf),  // Sprite editor for a specific texture file
     PrefabEditor,  // Visual UI prefab editor (Unity-style)
+    Animation,  // Animation clip editor for the selected entity
+    Profiler,  // Frame timings and the most expensive profiler scopes
+    UIEvents,  // Game UI event log, hover inspection and registered callbacks
 }
 
+impl EditorTab {
+    /// Tab caption, also used by the Window menu
+    pub fn title(&self) -> String {
+        match self {
+            EditorTab::Hierarchy => "Hierarchy".to_string(),
+            EditorTab::Inspector => "Inspector".to_string(),
+            EditorTab::Scene => "Scene".to_string(),
+            EditorTab::Game => "Game".to_string(),
+            EditorTab::Console => "Console".to_string(),
+            EditorTab::Project => "Project".to_string(),
+            EditorTab::MapView => "🗺️ Maps".to_string(),
+            EditorTab::Prefabs => "📦 Prefabs".to_string(),
+            EditorTab::LayerProperties => "🎨 Layer Properties".to_string(),
+            EditorTab::LayerOrdering => "📑 Layer Ordering".to_string(),
+            EditorTab::Performance => "📊 Performance".to_string(),
+            EditorTab::ColliderSettings => "⚙️ Collider Settings".to_string(),
+            EditorTab::PrefabEditor => "🎨 Prefab Editor".to_string(),
+            EditorTab::Animation => "🎞 Animation".to_string(),
+            EditorTab::Profiler => "📈 Profiler".to_string(),
+            EditorTab::UIEvents => "🖱 UI Events".to_string(),
+            EditorTab::SpriteEditor(path) => {
+                let file_name = path.file_stem()
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("Sprite Editor");
+                format!("🖼️ {}", file_name)
+            }
+        }
+    }
+}
+
+/// Panels listed in the Window menu, which re-opens them after they are closed
+pub const WINDOW_PANELS: [EditorTab; 9] = [
+    EditorTab::Hierarchy,
+    EditorTab::Inspector,
+    EditorTab::Scene,
+    EditorTab::Game,
+    EditorTab::Console,
+    EditorTab::Project,
+    EditorTab::Animation,
+    EditorTab::Profiler,
+    EditorTab::UIEvents,
+];
+
 /// Context for tab rendering
 pub struct TabContext<'a> {
     pub world: &'a mut World,
     pub selected_entity: &'a mut Option<Entity>,
+    pub selection: &'a mut crate::SelectionManager,
+    pub selection_action: &'a mut Option<crate::SelectionAction>,
+    pub hierarchy_search: &'a mut crate::tools::hierarchy_search::HierarchySearch,
     pub entity_names: &'a mut HashMap<Entity, String>,
     pub edit_script_request: &'a mut Option<String>,
     pub project_path: &'a Option<std::path::PathBuf>,
@@ -41,6 +90,7 @@ pub struct TabContext<'a> {
     pub scene_view_tab: &'a mut usize,
     pub map_view_state: &'a mut super::map_view::MapViewState,
     pub is_playing: bool,
+    pub is_paused: bool,
     pub show_colliders: &'a mut bool,
     pub show_velocities: &'a mut bool,
     pub current_tool: &'a mut TransformTool,
@@ -51,10 +101,14 @@ pub struct TabContext<'a> {
     pub delta_time: f32,
     pub play_request: &'a mut bool,
     pub stop_request: &'a mut bool,
+    pub pause_request: &'a mut bool,
+    pub step_request: &'a mut bool,
     pub asset_manager: &'a mut Option<AssetManager>,
     pub drag_drop: &'a mut DragDropState,
     pub dragging_entity: &'a mut Option<Entity>,
     pub drag_axis: &'a mut Option<u8>,
+    pub handle_drag: &'a mut Option<scene_view::interaction::handles::HandleDrag>,
+    pub snap_settings: &'a mut crate::tools::snapping::SnapSettings,
     pub scene_view_mode: &'a mut scene_view::SceneViewMode,
     pub projection_mode: &'a mut scene_view::SceneProjectionMode,
     pub transform_space: &'a mut scene_view::TransformSpace,
@@ -64,6 +118,7 @@ pub struct TabContext<'a> {
     pub sprite_editor_windows: &'a mut Vec<crate::SpriteEditorWindow>,
     pub sprite_picker_state: &'a mut super::sprite_picker::SpritePickerState,
     pub texture_inspector: &'a mut texture_inspector::TextureInspector,
+    pub ui_inspector: &'a mut ui_inspector::UIInspector,
     pub show_debug_lines: &'a mut bool,
     pub debug_draw: &'a mut crate::debug_draw::DebugDrawManager,
     pub map_manager: &'a mut crate::map_manager::MapManager,
@@ -85,9 +140,37 @@ pub struct TabContext<'a> {
     pub scene_view_renderer: &'a mut crate::scene_view_renderer::SceneViewRenderer,
     pub asset_loader: &'a dyn AssetLoader,
     pub render_cache: &'a mut engine::runtime::render_system::RenderCache,
+    /// Project sorting layers (Project Settings > Rendering)
+    pub sorting_layers: &'a ecs::sorting::SortingLayers,
+    /// Project aspect policy (Project Settings > Rendering), for the Game tab's bars
+    pub aspect_policy: engine_core::project_settings::AspectPolicy,
+    /// Project gravity and clear color, shown under Scene Settings
+    pub scene_defaults: inspector::scene::ProjectSceneDefaults,
+    pub animation_window: &'a mut super::animation_window::AnimationWindow,
+    pub scene_modified: &'a mut bool,
+    /// Broken references and "used by" counts of the open scene
+    pub asset_references: &'a mut crate::assets::references::AssetReferenceIndex,
+    /// Custom component definitions (scripts/components.lua)
+    pub component_registry: &'a ecs::ComponentRegistry,
 }
 
 /// Render game view toolbar (resolution selector, etc.)
+fn game_view_rect_id() -> egui::Id {
+    egui::Id::new("game_view_rect")
+}
+
+fn set_game_view_rect(ctx: &egui::Context, rect: egui::Rect) {
+    ctx.data_mut(|d| d.insert_temp(game_view_rect_id(), rect));
+}
+
+/// Where the Game tab drew the game last frame, in window pixels (the coordinates of
+/// `InputSystem::mouse_position`) as `[x, y, width, height]`
+pub fn game_view_screen_rect(ctx: &egui::Context) -> Option<[f32; 4]> {
+    let rect = ctx.data(|d| d.get_temp::<egui::Rect>(game_view_rect_id()))?;
+    let scale = ctx.pixels_per_point();
+    Some([rect.min.x * scale, rect.min.y * scale, rect.width() * scale, rect.height() * scale])
+}
+
 fn render_game_view_toolbar(ui: &mut egui::Ui, settings: &mut engine::runtime::GameViewSettings) {
     use engine::runtime::GameViewResolution;
     
@@ -116,6 +199,7 @@ fn render_game_view_toolbar(ui: &mut egui::Ui, settings: &mut engine::runtime::G
                 ui.selectable_value(&mut settings.resolution, GameViewResolution::IPhone14, "iPhone 14 (1170x2532)");
                 ui.selectable_value(&mut settings.resolution, GameViewResolution::IPhone14Pro, "iPhone 14 Pro (1179x2556)");
                 ui.selectable_value(&mut settings.resolution, GameViewResolution::IPhoneSE, "iPhone SE (750x1334)");
+                ui.selectable_value(&mut settings.resolution, GameViewResolution::PhonePortrait, "Phone (390x844)");
                 ui.selectable_value(&mut settings.resolution, GameViewResolution::Pixel7, "Pixel 7 (1080x2400)");
                 ui.selectable_value(&mut settings.resolution, GameViewResolution::GalaxyS23, "Galaxy S23 (1080x2340)");
                 ui.separator();
@@ -148,6 +232,14 @@ fn render_game_view_toolbar(ui: &mut egui::Ui, settings: &mut engine::runtime::G
         
         // Show resolution info toggle
         ui.checkbox(&mut settings.show_resolution_info, "Info");
+
+        // Performance HUD toggle (play mode)
+        ui.checkbox(&mut settings.show_performance_hud, "Stats")
+            .on_hover_text("FPS, frame times, entities, draw calls and the slowest scripts while playing");
+
+        // Debug draws overlay (what View > Gizmos shows in the scene view)
+        ui.checkbox(&mut settings.show_debug_draws, "Gizmos")
+            .on_hover_text("Debug lines from scripts and physics, as enabled in View > Gizmos");
     });
 }
 
@@ -160,27 +252,7 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
     type Tab = EditorTab;
 
     fn title(&mut self, tab: &mut Self::Tab) -> egui::WidgetText {
-        match tab {
-            EditorTab::Hierarchy => "Hierarchy".into(),
-            EditorTab::Inspector => "Inspector".into(),
-            EditorTab::Scene => "Scene".into(),
-            EditorTab::Game => "Game".into(),
-            EditorTab::Console => "Console".into(),
-            EditorTab::Project => "Project".into(),
-            EditorTab::MapView => "🗺️ Maps".into(),
-            EditorTab::Prefabs => "📦 Prefabs".into(),
-            EditorTab::LayerProperties => "🎨 Layer Properties".into(),
-            EditorTab::LayerOrdering => "📑 Layer Ordering".into(),
-            EditorTab::Performance => "📊 Performance".into(),
-            EditorTab::ColliderSettings => "⚙️ Collider Settings".into(),
-            EditorTab::PrefabEditor => "🎨 Prefab Editor".into(),
-            EditorTab::SpriteEditor(path) => {
-                let file_name = path.file_stem()
-                    .and_then(|s| s.to_str())
-                    .unwrap_or("Sprite Editor");
-                format!("🖼️ {}", file_name).into()
-            }
-        }
+        tab.title().into()
     }
 
     fn ui(&mut self, ui: &mut egui::Ui, tab: &mut Self::Tab) {
@@ -194,6 +266,9 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                     self.context.world,
                     self.context.entity_names,
                     self.context.selected_entity,
+                    self.context.selection,
+                    self.context.selection_action,
+                    self.context.hierarchy_search,
                     self.context.load_file_request,
                     self.context.project_path,
                     self.context.current_scene_path,
@@ -201,6 +276,7 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                     get_scene_files,
                     &get_entity_icon,
                     Some(self.context.map_manager), // Pass map_manager to filter map entities
+                    Some((&*self.context.ui_manager, &mut *self.context.ui_inspector)),
                 ) {
                     // User requested to create prefab from entity
                     self.context.create_prefab_dialog.open(entity, self.context.entity_names);
@@ -209,49 +285,94 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                 // Clear texture inspector selection when entity selection changes
                 if previous_selected != *self.context.selected_entity {
                     self.context.texture_inspector.clear();
+                    if self.context.selected_entity.is_some() {
+                        self.context.ui_inspector.clear();
+                    }
                 }
             }
             EditorTab::Inspector => {
                 // Show entity inspector or texture inspector based on selection
-                if self.context.texture_inspector.selected_texture.is_some() {
+                if self.context.texture_inspector.is_active() {
                     // Show texture import settings
                     if let Some(action) = self.context.texture_inspector.render(ui) {
-                        match action {
-                            texture_inspector::TextureInspectorAction::Apply => {
-                                // Save settings to .meta file
-                                if let Some(ref path) = self.context.texture_inspector.selected_texture {
-                                    if let Err(e) = self.context.texture_inspector.settings.save(path) {
-                                        self.context.console.error(format!("Failed to save texture settings: {}", e));
-                                    } else {
-                                        self.context.console.info("Texture settings saved");
-                                        self.context.texture_inspector.has_changes = false;
+                        if let Some(path) = self.context.texture_inspector.selected_render_texture.clone() {
+                            match action {
+                                texture_inspector::TextureInspectorAction::Apply => {
+                                    match self.context.texture_inspector.render_texture.save(&path) {
+                                        Ok(()) => {
+                                            // The GPU texture is re-created on the next reload
+                                            texture_inspector::request_render_texture_reload(ui.ctx());
+                                            self.context.console.info("Render texture saved");
+                                            self.context.texture_inspector.has_changes = false;
+                                        }
+                                        Err(e) => self.context.console.error(e),
                                     }
                                 }
+                                texture_inspector::TextureInspectorAction::Revert => {
+                                    self.context.texture_inspector.set_render_texture(path);
+                                    self.context.console.info("Render texture settings reverted");
+                                }
                             }
-                            texture_inspector::TextureInspectorAction::Revert => {
-                                // Reload settings from .meta file
-                                if let Some(ref path) = self.context.texture_inspector.selected_texture {
-                                    self.context.texture_inspector.settings = 
-                                        crate::texture_import_settings::TextureImportSettings::load(path)
-                                            .unwrap_or_default();
-                                    self.context.texture_inspector.has_changes = false;
-                                    self.context.console.info("Texture settings reverted");
+                        } else {
+                            match action {
+                                texture_inspector::TextureInspectorAction::Apply => {
+                                    // Save settings to .meta file
+                                    if let Some(ref path) = self.context.texture_inspector.selected_texture {
+                                        if let Err(e) = self.context.texture_inspector.settings.save(path) {
+                                            self.context.console.error(format!("Failed to save texture settings: {}", e));
+                                        } else {
+                                            // Re-create textures already uploaded with the old settings
+                                            let reimported = self.context.texture_manager.reimport(path);
+                                            self.context.console.info(format!("Texture settings saved ({} texture(s) reimported)", reimported.len()));
+                                            self.context.texture_inspector.has_changes = false;
+                                        }
+                                    }
+                                }
+                                texture_inspector::TextureInspectorAction::Revert => {
+                                    // Reload settings from .meta file
+                                    if let Some(ref path) = self.context.texture_inspector.selected_texture {
+                                        self.context.texture_inspector.settings = 
+                                            crate::texture_import_settings::TextureImportSettings::load(path)
+                                                .unwrap_or_default();
+                                        self.context.texture_inspector.has_changes = false;
+                                        self.context.console.info("Texture settings reverted");
+                                    }
                                 }
                             }
                         }
                     }
+                } else if self.context.ui_inspector.is_active() {
+                    // Show the selected UI element (Canvas subtree of the hierarchy)
+                    if let Some(ui_inspector::UIInspectorAction::ApplyToPrefab { instance, paths }) =
+                        self.context.ui_inspector.render(ui, self.context.ui_manager)
+                    {
+                        match self.context.ui_manager.apply_instance_to_prefab(&instance, &paths) {
+                            Ok((path, written)) => {
+                                self.context.console.info(format!("Applied {} UI element(s) to {}", written, path));
+                                self.context.ui_inspector.mark_applied(&instance);
+                            }
+                            Err(e) => self.context.console.error(format!("Apply to prefab failed: {}", e)),
+                        }
+                    }
                 } else {
-                    // Show entity inspector
+                    // Show entity inspector (shared components for multi-selection)
+                    let mut multi_selection = self.context.selection.get_selected();
+                    multi_selection.sort();
                     inspector::render_inspector(
                         ui,
                         self.context.world,
                         self.context.selected_entity,
+                        &multi_selection,
                         self.context.entity_names,
                         self.context.edit_script_request,
                         self.context.project_path,
                         self.context.open_sprite_editor_request,
                         self.context.sprite_picker_state,
                         self.context.reload_mesh_assets_request,
+                        self.context.texture_manager,
+                        self.context.sorting_layers,
+                        self.context.scene_defaults,
+                        self.context.component_registry,
                     );
                 }
             }
@@ -264,8 +385,10 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                     ui,
                     self.context.world,
                     self.context.selected_entity,
+                    self.context.selection,
                     self.context.scene_view_tab,
                     self.context.is_playing,
+                    self.context.is_paused,
                     self.context.show_colliders,
                     self.context.show_velocities,
                     self.context.show_debug_lines,
@@ -277,8 +400,14 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                     self.context.camera_state_display,
                     self.context.play_request,
                     self.context.stop_request,
+                    self.context.pause_request,
+                    self.context.step_request,
                     self.context.dragging_entity,
                     self.context.drag_axis,
+                    self.context.handle_drag,
+                    self.context.snap_settings,
+                    self.context.entity_names,
+                    self.context.project_path.as_ref(),
                     self.context.scene_view_mode,
                     self.context.projection_mode,
                     self.context.transform_space,
@@ -292,6 +421,7 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                     self.context.queue,
                     self.context.asset_loader,
                     self.context.render_cache,
+                    self.context.sorting_layers,
                 );
                 
                 // Clear texture inspector selection when entity selection changes
@@ -313,9 +443,14 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                 // NOTE: We now use the offscreen texture rendered by WGPU in EditorApp::render
                 // Instead of software rendering via engine::runtime::render_game_view
 
-                // Get available size for the game view
-                let available_size = ui.available_size();
-                let (width, height) = (available_size.x as u32, available_size.y as u32);
+                // Fixed resolutions are letterboxed inside the tab
+                let available_rect = ui.available_rect_before_wrap();
+                let game_rect = self.context.game_view_settings.calculate_game_rect(available_rect);
+                if game_rect != available_rect {
+                    let [r, g, b, a] = self.context.game_view_settings.background_color;
+                    ui.painter().rect_filled(available_rect, 0.0, egui::Rgba::from_rgba_unmultiplied(r, g, b, a));
+                }
+                let (width, height) = (game_rect.width() as u32, game_rect.height() as u32);
                 
                 // Resize if needed
                 if width > 0 && height > 0 {
@@ -327,15 +462,28 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                     );
                 }
 
-                // Calculate display rect based on resolution settings (e.g. aspect ratio) if needed.
-                // For now, if "Free", fill the space. If fixed, center it.
-                // GameViewSettings handling logic needs to be respected here.
-                
                 let texture_id = self.context.game_view_renderer.texture_id;
                 
                 // Draw the texture
                 // We use uv (0,0) to (1,1)
-                ui.image(egui::load::SizedTexture::new(texture_id, available_size));
+                let image = egui::Image::new(egui::load::SizedTexture::new(texture_id, game_rect.size()));
+                ui.put(game_rect, image);
+                let inner_rect = engine::runtime::renderer::letterbox(ui, game_rect, &self.context.aspect_policy);
+                set_game_view_rect(ui.ctx(), game_rect);
+                if self.context.is_playing {
+                    // The game's UI canvases, as the player draws them over the game
+                    self.context.ui_manager.render(ui, self.context.world, inner_rect);
+                    if super::ui_events_panel::inspect_hover_enabled(ui.ctx()) {
+                        super::ui_events_panel::inspect_game_view(ui, self.context.ui_manager, inner_rect);
+                    }
+                }
+                if self.context.game_view_settings.show_debug_draws {
+                    self.context.debug_draw.render_game_view(ui.painter(), self.context.world, &self.context.aspect_policy, game_rect);
+                }
+                super::profiler_overlay::render(ui, game_rect);
+                if self.context.is_playing && self.context.game_view_settings.show_performance_hud {
+                    super::performance_hud::render(ui, game_rect, self.context.world, self.context.entity_names);
+                }
             }
             EditorTab::Console => {
                 // Render console with full functionality
@@ -392,12 +540,14 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
             }
             EditorTab::Project => {
                 if let Some(ref mut manager) = self.context.asset_manager {
+                    if let Some(project_path) = self.context.project_path {
+                        self.context.asset_references.refresh(self.context.world, project_path);
+                    }
                     if let Some(action) = asset_browser::AssetBrowser::render(
                         ui,
                         manager,
                         self.context.drag_drop,
-                        self.context.texture_manager,
-                        self.context.project_path.as_ref(),
+                        self.context.asset_references,
                     ) {
                         match action {
                             asset_browser::AssetBrowserAction::OpenSpriteEditor(path) => {
@@ -406,6 +556,11 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                             asset_browser::AssetBrowserAction::SelectTexture(path) => {
                                 self.context.texture_inspector.set_texture(path);
                             }
+                            asset_browser::AssetBrowserAction::SelectRenderTexture(path) => {
+                                self.context.texture_inspector.set_render_texture(path);
+                                // Newly created ones need loading too
+                                texture_inspector::request_render_texture_reload(ui.ctx());
+                            }
                             asset_browser::AssetBrowserAction::OpenUIPrefabEditor(path) => {
                                 *self.context.open_prefab_editor_request = Some(path);
                             }
@@ -433,6 +588,24 @@ impl<'a> TabViewer for EditorTabViewer<'a> {
                 // Render prefab editor
                 self.context.prefab_editor.render(ui);
             }
+            EditorTab::Animation => {
+                if self.context.animation_window.render_panel(
+                    ui,
+                    self.context.world,
+                    *self.context.selected_entity,
+                    self.context.project_path.as_ref(),
+                    self.context.texture_manager,
+                    self.context.dt,
+                ) {
+                    *self.context.scene_modified = true;
+                }
+            }
+            EditorTab::Profiler => {
+                super::profiler_overlay::render_panel(ui);
+            }
+            EditorTab::UIEvents => {
+                super::ui_events_panel::render_panel(ui, self.context.ui_manager, self.context.is_playing);
+            }
             EditorTab::SpriteEditor(texture_path) => {
                 // Find or create sprite editor window for this texture
                 let window_idx = self.context.sprite_editor_windows.iter()
@@ -550,6 +723,48 @@ pub fn create_wide_layout() -> DockState<EditorTab> {
     dock_state
 }
 
+/// True if the tab is docked or floating anywhere in the layout
+pub fn is_tab_open(dock_state: &DockState<EditorTab>, tab: &EditorTab) -> bool {
+    dock_state.find_tab(tab).is_some()
+}
+
+/// Bring a tab to the front, adding it to the focused panel if it was closed
+pub fn open_tab(dock_state: &mut DockState<EditorTab>, tab: EditorTab) {
+    match dock_state.find_tab(&tab) {
+        Some(path) => dock_state.set_active_tab(path),
+        None => dock_state.push_to_focused_leaf(tab),
+    }
+}
+
+/// Close a tab wherever it is
+pub fn close_tab(dock_state: &mut DockState<EditorTab>, tab: &EditorTab) {
+    if let Some(path) = dock_state.find_tab(tab) {
+        dock_state.remove_tab(path);
+    }
+}
+
+/// Save the arrangement the editor was closed with (splits, sizes, floating windows)
+pub fn save_session_layout(
+    dock_state: &DockState<EditorTab>,
+    project_path: &std::path::Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(dock_state)?;
+    std::fs::write(project_path.join(".editor_layout.json"), json)?;
+    Ok(())
+}
+
+/// Arrangement saved by `save_session_layout`, if any
+pub fn load_session_layout(project_path: &std::path::Path) -> Option<DockState<EditorTab>> {
+    let json = std::fs::read_to_string(project_path.join(".editor_layout.json")).ok()?;
+    match serde_json::from_str(&json) {
+        Ok(dock_state) => Some(dock_state),
+        Err(e) => {
+            log::warn!("Failed to parse saved editor layout: {}. Using the default layout.", e);
+            None
+        }
+    }
+}
+
 /// Save current layout name as default
 pub fn save_default_layout(layout_name: &str, project_path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
     let layout_path = project_path.join(".editor_layout.txt");
@@ -1,6 +1,7 @@
 use egui;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use crate::states::EditorState;
+use engine::export::{export_project, ExportOptions, PackFormat};
 use std::sync::mpsc;
 use std::thread;
 use std::process::Command;
@@ -13,6 +14,8 @@ impl ExportGameDialog {
         ctx: &egui::Context,
         editor_state: &mut EditorState,
     ) {
+        poll_build_messages(editor_state);
+
         if !editor_state.show_export_dialog {
             return;
         }
@@ -67,11 +70,27 @@ impl ExportGameDialog {
                     }
                 });
 
-                // Target Platform (Display only for now)
+                // Target Platform (exports for the platform the editor runs on)
                 ui.horizontal(|ui| {
                     ui.label("Target Platform:");
                     ui.label(&params.target_platform);
                 });
+
+                ui.add_enabled_ui(!params.is_building, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Assets:");
+                        ui.radio_value(&mut params.pack_format, PackFormat::Directory, "Folder (data/)");
+                        ui.radio_value(&mut params.pack_format, PackFormat::Archive, "Single archive (data.pak)");
+                    });
+
+                    ui.horizontal(|ui| {
+                        ui.label("Window:");
+                        ui.add(egui::DragValue::new(&mut params.window_width).range(320..=7680).suffix(" px"));
+                        ui.label("×");
+                        ui.add(egui::DragValue::new(&mut params.window_height).range(240..=4320).suffix(" px"));
+                        ui.checkbox(&mut params.fullscreen, "Fullscreen");
+                    });
+                });
                 
                 ui.separator();
                 
@@ -80,15 +99,21 @@ impl ExportGameDialog {
                         ui.label("Building...");
                         ui.spinner();
                     });
-                    
+                }
+
+                if !params.build_output.is_empty() {
                     ui.collapsing("Build Log", |ui| {
                         egui::ScrollArea::vertical()
+                            .id_salt("export_build_log")
+                            .max_height(200.0)
                             .stick_to_bottom(true)
                             .show(ui, |ui| {
                                 ui.label(&params.build_output);
                         });
                     });
-                } else {
+                }
+
+                if !params.is_building {
                     ui.horizontal(|ui| {
                         if ui.button("Build").clicked() {
                              should_start_build = true;
@@ -130,8 +155,21 @@ fn start_build_process(editor_state: &mut EditorState, project_path: PathBuf) {
     let (tx, rx) = mpsc::channel();
     editor_state.build_receiver = Some(rx);
     
-    let game_name = editor_state.build_params.game_name.clone();
-    let output_path = editor_state.build_params.output_path.clone();
+    let params = &editor_state.build_params;
+    let game_name = params.game_name.clone();
+    let output_path = params.output_path.clone();
+
+    let mut options = ExportOptions::new(project_path.clone(), output_path.clone(), game_name.clone());
+    options.pack_format = params.pack_format;
+    options.window.width = params.window_width;
+    options.window.height = params.window_height;
+    options.window.fullscreen = params.fullscreen;
+    if project_game_startup_scene(&project_path).is_none() {
+        // Export the scene being edited when the project has no game startup scene
+        if let Some(scene) = editor_state.current_scene_path.as_ref() {
+            options.startup_scene = Some(scene.strip_prefix(&project_path).unwrap_or(scene).to_path_buf());
+        }
+    }
     
     // We assume the editor is running from the engine root, so we use current_dir for building
     let engine_path = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
@@ -171,15 +209,7 @@ fn start_build_process(editor_state: &mut EditorState, project_path: PathBuf) {
             }
         }
         
-        // 2. Create output directory
-        if !output_path.exists() {
-            if let Err(e) = fs::create_dir_all(&output_path) {
-                 let _ = tx.send(format!("ERROR: Failed to create output directory: {}", e));
-                 return;
-            }
-        }
-        
-        // 3. Copy executable
+        // 2. Package the project next to the runtime
         let exe_name = if cfg!(target_os = "windows") { "player.exe" } else { "player" };
 
         // Find workspace root - try engine_path/../target/release first
@@ -192,74 +222,73 @@ fn start_build_process(editor_state: &mut EditorState, project_path: PathBuf) {
             engine_path.join("target/release")
         };
 
-        let target_exe = workspace_target.join(exe_name);
-        
-        let dest_exe = output_path.join(format!("{}{}", game_name, if cfg!(target_os = "windows") { ".exe" } else { "" }));
-        
-        let _ = tx.send(format!("Copying executable from {:?} to {:?}...", target_exe, dest_exe));
-        if let Err(e) = fs::copy(&target_exe, &dest_exe) {
-             let _ = tx.send(format!("ERROR: Failed to copy executable: {}", e));
-             return;
-        }
+        options.runtime_binary = Some(workspace_target.join(exe_name));
 
-        // 4. Copy assets folder (from project_path)
-        let assets_src = project_path.join("assets");
-        let assets_dest = output_path.join("assets");
-        if assets_src.exists() {
-            let _ = tx.send("Copying assets...".to_string());
-             if let Err(e) = copy_dir_recursive(&assets_src, &assets_dest) {
-                 let _ = tx.send(format!("ERROR: Failed to copy assets: {}", e));
-                 return;
-             }
-        } else {
-            let _ = tx.send("WARNING: No assets folder found in project path.".to_string());
+        let mut log = |line: String| {
+            let _ = tx.send(line);
+        };
+        match export_project(&options, &mut log) {
+            Ok(report) => {
+                let _ = tx.send(format!(
+                    "Exported {} file(s) ({} KB), executable: {:?}",
+                    report.file_count,
+                    report.total_bytes / 1024,
+                    report.executable
+                ));
+            }
+            Err(e) => {
+                let _ = tx.send(format!("ERROR: Export failed: {}", e));
+                return;
+            }
         }
 
-       // 5. Copy scenes folder (from project_path)
-        let scenes_src = project_path.join("scenes");
-        let scenes_dest = output_path.join("scenes");
-        if scenes_src.exists() {
-            let _ = tx.send("Copying scenes...".to_string());
-             if let Err(e) = copy_dir_recursive(&scenes_src, &scenes_dest) {
-                 let _ = tx.send(format!("ERROR: Failed to copy scenes: {}", e));
-                 return;
-             }
-        } else {
-            let _ = tx.send("WARNING: No scenes folder found in project path.".to_string());
-        }
-        
-       // 6. Copy scripts folder (from project_path)
-        let scripts_src = project_path.join("scripts");
-        let scripts_dest = output_path.join("scripts");
-        if scripts_src.exists() {
-            let _ = tx.send("Copying scripts...".to_string());
-             if let Err(e) = copy_dir_recursive(&scripts_src, &scripts_dest) {
-                 let _ = tx.send(format!("ERROR: Failed to copy scripts: {}", e));
-                 return;
-             }
-        }
-        
         let _ = tx.send("Build completed successfully!".to_string());
         let _ = tx.send("SUCCESS".to_string());
     });
 }
-// Helper function to copy directories
-fn copy_dir_recursive(src: &PathBuf, dst: &PathBuf) -> std::io::Result<()> {
-    if !dst.exists() {
-        fs::create_dir_all(dst)?;
-    }
-    
-    for entry in fs::read_dir(src)? {
-        let entry = entry?;
-        let ty = entry.file_type()?;
-        let path = entry.path();
-        let dest_path = dst.join(entry.file_name());
-        
-        if ty.is_dir() {
-            copy_dir_recursive(&path, &dest_path)?;
-        } else {
-            fs::copy(&path, &dest_path)?;
+/// The project's configured game startup scene (legacy `startup_scene` as fallback)
+fn project_game_startup_scene(project_path: &Path) -> Option<PathBuf> {
+    let json = fs::read_to_string(project_path.join("project.json")).ok()?;
+    let config: engine_core::project::ProjectConfig = serde_json::from_str(&json).ok()?;
+    config.game_startup_scene.or(config.startup_scene)
+}
+
+/// Drain progress lines from the build thread. "SUCCESS" and "ERROR: ..." end the build.
+fn poll_build_messages(editor_state: &mut EditorState) {
+    let Some(receiver) = &editor_state.build_receiver else {
+        return;
+    };
+
+    let mut finished = false;
+    loop {
+        match receiver.try_recv() {
+            Ok(message) => {
+                let params = &mut editor_state.build_params;
+                if message == "SUCCESS" {
+                    params.is_building = false;
+                    finished = true;
+                    editor_state.console.info(format!("✅ Exported {} to {:?}", params.game_name, params.output_path));
+                    continue;
+                }
+                if let Some(error) = message.strip_prefix("ERROR: ") {
+                    params.is_building = false;
+                    params.build_error = Some(error.to_string());
+                    finished = true;
+                    editor_state.console.error(format!("❌ {}", error));
+                }
+                params.build_output.push_str(&message);
+                params.build_output.push('\n');
+            }
+            Err(mpsc::TryRecvError::Empty) => break,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                editor_state.build_params.is_building = false;
+                finished = true;
+                break;
+            }
         }
     }
-    Ok(())
+
+    if finished {
+        editor_state.build_receiver = None;
+    }
 }
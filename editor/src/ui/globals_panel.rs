@@ -0,0 +1,107 @@
+//! Globals Window
+//!
+//! What scripts keep in the Globals store (score, inventory, settings...) during play
+//! mode: every key with its value, tables as collapsible trees, and how many scripts
+//! subscribe to it.
+
+use egui::{Color32, RichText};
+use script::ScriptEngine;
+use serde_json::Value;
+
+const KEY_COLOR: Color32 = Color32::from_rgb(150, 200, 255);
+
+fn open_request_id() -> egui::Id {
+    egui::Id::new("globals_panel_open_request")
+}
+
+/// Ask the editor to show the Globals window (View menu)
+pub fn open(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(open_request_id(), true));
+}
+
+#[derive(Default)]
+pub struct GlobalsPanel {
+    pub open: bool,
+}
+
+impl GlobalsPanel {
+    pub fn render(&mut self, ctx: &egui::Context, script_engine: &ScriptEngine, is_playing: bool) {
+        if ctx.data_mut(|d| d.remove_temp::<bool>(open_request_id())).unwrap_or(false) {
+            self.open = true;
+        }
+        if !self.open {
+            return;
+        }
+
+        let mut open = self.open;
+        egui::Window::new("🌐 Globals")
+            .open(&mut open)
+            .default_size([340.0, 380.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                let state = script_engine.global_state.borrow();
+                if state.values().is_empty() {
+                    let hint = if is_playing {
+                        "No values yet (Globals.set)."
+                    } else {
+                        "Enter play mode to see what scripts store with Globals.set."
+                    };
+                    ui.label(RichText::new(hint).small().color(Color32::GRAY));
+                    return;
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (key, value) in state.values() {
+                        let subscribers = state.subscriber_count(key);
+                        ui.horizontal(|ui| {
+                            render_value(ui, key, key, value);
+                            if subscribers > 0 {
+                                ui.label(RichText::new(format!("({} subscribed)", subscribers)).small().color(Color32::GRAY))
+                                    .on_hover_text("Scripts called Globals.subscribe for this key");
+                            }
+                        });
+                    }
+                });
+            });
+        self.open = open;
+        // Live values: keep repainting while playing
+        if is_playing {
+            ctx.request_repaint();
+        }
+    }
+}
+
+/// `label = value`, or a collapsible tree for tables; `id` is the value's path
+fn render_value(ui: &mut egui::Ui, id: &str, label: &str, value: &Value) {
+    let children: Vec<(String, &Value)> = match value {
+        Value::Object(fields) => fields.iter().map(|(key, value)| (key.clone(), value)).collect(),
+        Value::Array(items) => items.iter().enumerate().map(|(index, value)| (format!("[{}]", index + 1), value)).collect(),
+        _ => {
+            ui.label(RichText::new(label).color(KEY_COLOR));
+            ui.label(RichText::new(format_scalar(value)).monospace());
+            return;
+        }
+    };
+    let summary = match value {
+        Value::Array(_) => format!("{}  [{} items]", label, children.len()),
+        _ => format!("{}  {{{} fields}}", label, children.len()),
+    };
+    ui.vertical(|ui| {
+        egui::CollapsingHeader::new(RichText::new(summary).color(KEY_COLOR))
+            .id_salt(("globals_value", id))
+            .show(ui, |ui| {
+                for (child_label, child) in children {
+                    let child_id = format!("{}/{}", id, child_label);
+                    ui.horizontal(|ui| render_value(ui, &child_id, &child_label, child));
+                }
+            });
+    });
+}
+
+/// A value as Lua would print it
+fn format_scalar(value: &Value) -> String {
+    match value {
+        Value::Null => "nil".to_string(),
+        Value::String(s) => format!("{:?}", s),
+        other => other.to_string(),
+    }
+}
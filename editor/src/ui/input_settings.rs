@@ -0,0 +1,240 @@
+use input::{
+    ActionBinding, ActionKind, GamepadAxis, GamepadButton, InputAction, InputConfig, InputProfile, InputSource, Key,
+    MouseButton, INPUT_CONFIG_FILE,
+};
+
+/// Edits the project's input.json: per-player profiles, their actions and bindings.
+/// Conflicting bindings are listed as warnings rather than resolved here.
+pub fn render_input_settings_section(ui: &mut egui::Ui, project_path: &std::path::Path) {
+    let config_path = project_path.join(INPUT_CONFIG_FILE);
+
+    ui.add_space(5.0);
+    ui.label(egui::RichText::new(format!("File: {}", config_path.display())).small());
+
+    let mut config = match InputConfig::load_for_project(project_path) {
+        Ok(config) => config,
+        Err(e) => {
+            ui.colored_label(egui::Color32::RED, format!("Failed to read input config: {}", e));
+            return;
+        }
+    };
+
+    let mut changed = false;
+    let mut profile_to_remove: Option<usize> = None;
+    let profile_count = config.profiles.len();
+
+    for (player, profile) in config.profiles.iter_mut().enumerate() {
+        egui::CollapsingHeader::new(format!("Player {}: {}", player + 1, profile.name))
+            .id_salt(("input_profile", player))
+            .default_open(player == 0)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    changed |= ui.text_edit_singleline(&mut profile.name).changed();
+                    ui.label("Gamepad:");
+                    changed |= ui.add(egui::DragValue::new(&mut profile.gamepad).range(0..=3)).changed();
+                    if profile_count > 1 && ui.small_button("🗑").on_hover_text("Remove player").clicked() {
+                        profile_to_remove = Some(player);
+                    }
+                });
+                ui.add_space(5.0);
+                changed |= render_profile_actions(ui, player, profile);
+            });
+    }
+
+    if let Some(player) = profile_to_remove {
+        config.profiles.remove(player);
+        changed = true;
+    }
+
+    ui.add_space(5.0);
+    if ui.button("+ Add Player").clicked() {
+        let player = config.profiles.len();
+        let gamepad = player.min(3);
+        let mut profile = InputProfile::new(format!("Player {}", player + 1), gamepad);
+        // Start from player 1's actions so games only need one set of names
+        if let Some(first) = config.profiles.first() {
+            profile.actions = first.actions.clone();
+        }
+        config.profiles.push(profile);
+        changed = true;
+    }
+
+    let conflicts = config.find_conflicts();
+    if !conflicts.is_empty() {
+        ui.add_space(5.0);
+        for (player, binding, actions) in conflicts {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                format!("⚠ Player {}: {} is bound to {}", player + 1, binding, actions.join(", ")),
+            );
+        }
+    }
+
+    if changed {
+        if let Err(e) = config.save(&config_path) {
+            log::error!("Failed to write input config: {}", e);
+        }
+    }
+}
+
+fn render_profile_actions(ui: &mut egui::Ui, player: usize, profile: &mut InputProfile) -> bool {
+    let mut changed = false;
+    let mut action_to_remove: Option<usize> = None;
+
+    for (index, action) in profile.actions.iter_mut().enumerate() {
+        let builtin = action.name == input::actions::MOVE_X || action.name == input::actions::MOVE_Y;
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                if builtin {
+                    ui.label(egui::RichText::new(&action.name).strong())
+                        .on_hover_text("Built-in action used by get_movement_input");
+                } else {
+                    changed |= ui.add(egui::TextEdit::singleline(&mut action.name).desired_width(120.0)).changed();
+                }
+
+                egui::ComboBox::from_id_salt(("input_action_kind", player, index))
+                    .selected_text(format!("{:?}", action.kind))
+                    .width(80.0)
+                    .show_ui(ui, |ui| {
+                        for kind in [ActionKind::Button, ActionKind::Axis] {
+                            changed |= ui.selectable_value(&mut action.kind, kind, format!("{:?}", kind)).changed();
+                        }
+                    });
+
+                if !builtin && ui.small_button("🗑").on_hover_text("Remove action").clicked() {
+                    action_to_remove = Some(index);
+                }
+            });
+            changed |= render_action_bindings(ui, player, index, action);
+        });
+    }
+
+    if let Some(index) = action_to_remove {
+        profile.actions.remove(index);
+        changed = true;
+    }
+
+    if ui.button("+ Add Action").clicked() {
+        let mut name = "new_action".to_string();
+        let mut suffix = 1;
+        while profile.action(&name).is_some() {
+            suffix += 1;
+            name = format!("new_action_{}", suffix);
+        }
+        profile.actions.push(InputAction::new(name, ActionKind::Button, Vec::new()));
+        changed = true;
+    }
+
+    changed
+}
+
+fn render_action_bindings(ui: &mut egui::Ui, player: usize, action_index: usize, action: &mut InputAction) -> bool {
+    let mut changed = false;
+    let mut binding_to_remove: Option<usize> = None;
+
+    for (index, binding) in action.bindings.iter_mut().enumerate() {
+        let id = (player, action_index, index);
+        ui.horizontal(|ui| {
+            ui.add_space(16.0);
+
+            let mut device = device_name(binding.source);
+            egui::ComboBox::from_id_salt(("input_binding_device", id))
+                .selected_text(device)
+                .width(110.0)
+                .show_ui(ui, |ui| {
+                    for name in ["Key", "Mouse", "Gamepad Button", "Gamepad Axis"] {
+                        ui.selectable_value(&mut device, name, name);
+                    }
+                });
+            if device != device_name(binding.source) {
+                binding.source = default_source(device);
+                changed = true;
+            }
+
+            egui::ComboBox::from_id_salt(("input_binding_value", id))
+                .selected_text(source_value_name(binding.source))
+                .width(110.0)
+                .height(300.0)
+                .show_ui(ui, |ui| {
+                    changed |= source_value_picker(ui, &mut binding.source);
+                });
+
+            ui.label("Scale:");
+            changed |= ui.add(egui::DragValue::new(&mut binding.scale).speed(0.05).range(-1.0..=1.0)).changed();
+
+            if ui.small_button("🗑").on_hover_text("Remove binding").clicked() {
+                binding_to_remove = Some(index);
+            }
+        });
+    }
+
+    if let Some(index) = binding_to_remove {
+        action.bindings.remove(index);
+        changed = true;
+    }
+
+    ui.horizontal(|ui| {
+        ui.add_space(16.0);
+        if ui.small_button("+ Binding").clicked() {
+            action.bindings.push(ActionBinding::key(Key::Space));
+            changed = true;
+        }
+    });
+
+    changed
+}
+
+fn device_name(source: InputSource) -> &'static str {
+    match source {
+        InputSource::Key { .. } => "Key",
+        InputSource::Mouse { .. } => "Mouse",
+        InputSource::GamepadButton { .. } => "Gamepad Button",
+        InputSource::GamepadAxis { .. } => "Gamepad Axis",
+    }
+}
+
+fn default_source(device: &str) -> InputSource {
+    match device {
+        "Mouse" => InputSource::Mouse { button: MouseButton::Left },
+        "Gamepad Button" => InputSource::GamepadButton { button: GamepadButton::South },
+        "Gamepad Axis" => InputSource::GamepadAxis { axis: GamepadAxis::LeftStickX },
+        _ => InputSource::Key { key: Key::Space },
+    }
+}
+
+fn source_value_name(source: InputSource) -> String {
+    match source {
+        InputSource::Key { key } => format!("{:?}", key),
+        InputSource::Mouse { button } => format!("{:?}", button),
+        InputSource::GamepadButton { button } => format!("{:?}", button),
+        InputSource::GamepadAxis { axis } => format!("{:?}", axis),
+    }
+}
+
+fn source_value_picker(ui: &mut egui::Ui, source: &mut InputSource) -> bool {
+    let mut changed = false;
+    match source {
+        InputSource::Key { key } => {
+            for value in Key::ALL {
+                changed |= ui.selectable_value(key, *value, format!("{:?}", value)).changed();
+            }
+        }
+        InputSource::Mouse { button } => {
+            for value in MouseButton::ALL {
+                changed |= ui.selectable_value(button, *value, format!("{:?}", value)).changed();
+            }
+        }
+        InputSource::GamepadButton { button } => {
+            for value in GamepadButton::ALL {
+                changed |= ui.selectable_value(button, *value, format!("{:?}", value)).changed();
+            }
+        }
+        InputSource::GamepadAxis { axis } => {
+            for value in GamepadAxis::ALL {
+                changed |= ui.selectable_value(axis, *value, format!("{:?}", value)).changed();
+            }
+        }
+    }
+    changed
+}
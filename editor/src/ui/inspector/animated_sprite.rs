@@ -0,0 +1,109 @@
+use ecs::{World, Entity, AnimationMode};
+use egui;
+use engine::texture_manager::TextureManager;
+use super::utils::render_component_header;
+use crate::ui::animation_window;
+
+pub fn render_animated_sprite_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity, texture_manager: &mut TextureManager) {
+    // Animated Sprite Component (clips are authored in the Animation window)
+    if !world.animated_sprites.contains_key(&entity) {
+        return;
+    }
+    let mut remove_component = false;
+
+    let animated_id = ui.make_persistent_id("animated_sprite_component");
+    let is_open = egui::collapsing_header::CollapsingState::load_with_default_open(
+        ui.ctx(), animated_id, true
+    );
+
+    render_component_header(ui, "Animated Sprite", "🎞", false);
+
+    if is_open.is_open() {
+        let sheet = world.sprite_sheets.get(&entity);
+        if let Some(animated_sprite) = world.animated_sprites.get_mut(&entity) {
+            ui.indent("animated_sprite_indent", |ui| {
+                egui::Grid::new("animated_sprite_grid")
+                    .num_columns(2)
+                    .spacing([5.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label("Clip");
+                        let selected_text = animated_sprite.clip.clone().unwrap_or_else(|| "None (frame sequence)".to_string());
+                        let mut new_clip = None;
+                        egui::ComboBox::from_id_salt("animated_sprite_clip")
+                            .selected_text(selected_text)
+                            .show_ui(ui, |ui| {
+                                if ui.selectable_label(animated_sprite.clip.is_none(), "None (frame sequence)").clicked() {
+                                    new_clip = Some(None);
+                                }
+                                for clip in &animated_sprite.clips {
+                                    let selected = animated_sprite.clip.as_deref() == Some(clip.name.as_str());
+                                    if ui.selectable_label(selected, &clip.name).clicked() {
+                                        new_clip = Some(Some(clip.name.clone()));
+                                    }
+                                }
+                            });
+                        if let Some(clip) = new_clip {
+                            animated_sprite.clip = clip;
+                            animated_sprite.current_frame = 0;
+                            animated_sprite.elapsed_time = 0.0;
+                        }
+                        ui.end_row();
+
+                        ui.label("Playing");
+                        ui.checkbox(&mut animated_sprite.playing, "");
+                        ui.end_row();
+
+                        ui.label("Speed");
+                        ui.add(egui::DragValue::new(&mut animated_sprite.speed).speed(0.05).range(0.0..=8.0).suffix("x"));
+                        ui.end_row();
+
+                        if animated_sprite.clip.is_none() {
+                            ui.label("Frame Duration");
+                            ui.add(egui::DragValue::new(&mut animated_sprite.frame_duration).speed(0.005).range(0.01..=10.0).suffix("s"));
+                            ui.end_row();
+
+                            ui.label("Mode");
+                            egui::ComboBox::from_id_salt("animated_sprite_mode")
+                                .selected_text(format!("{:?}", animated_sprite.mode))
+                                .show_ui(ui, |ui| {
+                                    for mode in [AnimationMode::Loop, AnimationMode::Once, AnimationMode::PingPong] {
+                                        ui.selectable_value(&mut animated_sprite.mode, mode, format!("{:?}", mode));
+                                    }
+                                });
+                            ui.end_row();
+                        }
+                    });
+
+                // Live preview of the selected clip, independent of play mode
+                if let (Some(clip), Some(sheet)) = (animated_sprite.active_clip(), sheet) {
+                    let time_id = ui.make_persistent_id(("animated_sprite_preview_time", entity));
+                    let time = ui.data(|d| d.get_temp::<f32>(time_id)).unwrap_or(0.0) + ui.input(|i| i.stable_dt);
+                    let time = if clip.duration() > 0.0 { time.rem_euclid(clip.duration() * 2.0) } else { 0.0 };
+                    ui.data_mut(|d| d.insert_temp(time_id, time));
+
+                    if let Some(index) = animation_window::keyframe_at(clip, time) {
+                        ui.add_space(4.0);
+                        let (rect, _) = ui.allocate_exact_size(egui::vec2(64.0, 64.0), egui::Sense::hover());
+                        animation_window::paint_sheet_frame(ui, rect, sheet, clip.frames[index].frame, texture_manager);
+                        ui.ctx().request_repaint();
+                    }
+                }
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    if ui.button("🎞 Edit Animations").clicked() {
+                        animation_window::open(ui.ctx());
+                    }
+                    if ui.button("❌ Remove Component").clicked() {
+                        remove_component = true;
+                    }
+                });
+            });
+        }
+        ui.add_space(10.0);
+    }
+
+    if remove_component {
+        world.animated_sprites.remove(&entity);
+    }
+}
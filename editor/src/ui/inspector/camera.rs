@@ -1,6 +1,7 @@
 use ecs::{World, Entity, ComponentType, ComponentManager};
 use egui;
-use super::utils::render_component_header;
+use super::utils::{render_component_header_with_menu, color_edit};
+use crate::systems::undo::ComponentKind;
 
 pub fn render_camera_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
     // Camera Component (Unity-style)
@@ -13,7 +14,7 @@ pub fn render_camera_inspector(ui: &mut egui::Ui, world: &mut World, entity: Ent
             ui.ctx(), camera_id, true
         );
 
-        render_component_header(ui, "Camera", "📷", false);
+        render_component_header_with_menu(ui, "Camera", "📷", false, ComponentKind::Camera);
 
         if is_open.is_open() {
             if let Some(camera) = world.cameras.get_mut(&entity) {
@@ -40,6 +41,15 @@ pub fn render_camera_inspector(ui: &mut egui::Ui, world: &mut World, entity: Ent
                                     ui.label("Size");
                                     ui.add(egui::DragValue::new(&mut camera.orthographic_size).speed(0.1).clamp_range(0.1..=1000.0));
                                     ui.end_row();
+
+                                    let size = camera.orthographic_size;
+                                    ui.label("Min Size").on_hover_text("Smallest size scripts can zoom to");
+                                    zoom_limit(ui, &mut camera.min_orthographic_size, size);
+                                    ui.end_row();
+
+                                    ui.label("Max Size").on_hover_text("Largest size scripts can zoom to");
+                                    zoom_limit(ui, &mut camera.max_orthographic_size, size);
+                                    ui.end_row();
                                 }
                                 ecs::CameraProjection::Perspective => {
                                     ui.label("FOV");
@@ -54,7 +64,18 @@ pub fn render_camera_inspector(ui: &mut egui::Ui, world: &mut World, entity: Ent
                             ui.end_row();
 
                             ui.label("Background");
-                            ui.color_edit_button_rgba_unmultiplied(&mut camera.background_color);
+                            color_edit(ui, &mut camera.background_color);
+                            ui.end_row();
+
+                            ui.label("Target Texture");
+                            let mut target = camera.target_texture.clone().unwrap_or_default();
+                            if ui.add(egui::TextEdit::singleline(&mut target).hint_text("None (screen)"))
+                                .on_hover_text("Name of a render texture to draw into instead of the screen")
+                                .changed()
+                            {
+                                let target = target.trim();
+                                camera.target_texture = (!target.is_empty()).then(|| target.to_string());
+                            }
                             ui.end_row();
 
                             ui.label("Near Clip");
@@ -126,3 +147,21 @@ pub fn render_camera_inspector(ui: &mut egui::Ui, world: &mut World, entity: Ent
         let _ = world.remove_component(entity, ComponentType::Camera);
     }
 }
+
+/// Optional zoom limit: a checkbox, then the value (starting at the current size)
+fn zoom_limit(ui: &mut egui::Ui, limit: &mut Option<f32>, size: f32) {
+    ui.horizontal(|ui| {
+        let mut enabled = limit.is_some();
+        if ui.checkbox(&mut enabled, "").changed() {
+            *limit = enabled.then_some(size);
+        }
+        match limit {
+            Some(value) => {
+                ui.add(egui::DragValue::new(value).speed(0.1).range(0.1..=1000.0));
+            }
+            None => {
+                ui.label(egui::RichText::new("None").color(egui::Color32::GRAY));
+            }
+        }
+    });
+}
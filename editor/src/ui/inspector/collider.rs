@@ -1,6 +1,7 @@
-use ecs::{World, Entity, ComponentType, ComponentManager};
+use ecs::{World, Entity, ComponentType, ComponentManager, ColliderShape};
 use egui;
-use super::utils::render_component_header;
+use super::utils::render_component_header_with_menu;
+use crate::systems::undo::ComponentKind;
 
 pub fn render_collider_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
     // Collider Component (Unity-style)
@@ -13,7 +14,12 @@ pub fn render_collider_inspector(ui: &mut egui::Ui, world: &mut World, entity: E
             ui.ctx(), collider_id, true
         );
         
-        render_component_header(ui, "Box Collider 2D", "📦", false);
+        let title = match world.colliders.get(&entity).map(|c| c.shape) {
+            Some(ColliderShape::Circle) => "Circle Collider 2D",
+            Some(ColliderShape::Capsule) => "Capsule Collider 2D",
+            _ => "Box Collider 2D",
+        };
+        render_component_header_with_menu(ui, title, "📦", false, ComponentKind::Collider);
         
         if is_open.is_open() {
             // Migrate legacy colliders
@@ -36,6 +42,23 @@ pub fn render_collider_inspector(ui: &mut egui::Ui, world: &mut World, entity: E
                             }
                             ui.end_row();
                             
+                            // Shape
+                            ui.label("Shape");
+                            egui::ComboBox::from_id_salt("collider_shape")
+                                .selected_text(format!("{:?}", collider.shape))
+                                .show_ui(ui, |ui| {
+                                    for shape in [ColliderShape::Box, ColliderShape::Circle, ColliderShape::Capsule] {
+                                        ui.selectable_value(&mut collider.shape, shape, format!("{:?}", shape));
+                                    }
+                                });
+                            ui.end_row();
+                            
+                            // Trigger
+                            ui.label("Is Trigger");
+                            ui.checkbox(&mut collider.is_trigger, "")
+                                .on_hover_text("Report overlaps without blocking movement");
+                            ui.end_row();
+                            
                             // Offset
                             ui.label("Offset");
                             ui.label("X");
@@ -44,12 +67,20 @@ pub fn render_collider_inspector(ui: &mut egui::Ui, world: &mut World, entity: E
                             ui.add(egui::DragValue::new(&mut collider.offset[1]).speed(0.01).max_decimals(2));
                             ui.end_row();
                             
-                            // Size
-                            ui.label("Size");
+                            // Flip
+                            ui.label("Flip With Sprite");
+                            ui.checkbox(&mut collider.flip_with_sprite, "")
+                                .on_hover_text("Mirror the offset when the sprite is flipped");
+                            ui.end_row();
+                            
+                            // Size (Circle only uses X as the diameter)
+                            ui.label(if collider.shape == ColliderShape::Circle { "Diameter" } else { "Size" });
                             ui.label("X");
                             ui.add(egui::DragValue::new(&mut collider.size[0]).speed(0.01).max_decimals(2).clamp_range(0.01..=100.0));
-                            ui.label("Y");
-                            ui.add(egui::DragValue::new(&mut collider.size[1]).speed(0.01).max_decimals(2).clamp_range(0.01..=100.0));
+                            if collider.shape != ColliderShape::Circle {
+                                ui.label("Y");
+                                ui.add(egui::DragValue::new(&mut collider.size[1]).speed(0.01).max_decimals(2).range(0.01..=100.0));
+                            }
                             ui.end_row();
                         });
                     
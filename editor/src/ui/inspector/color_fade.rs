@@ -0,0 +1,125 @@
+use ecs::{World, Entity, ComponentType, ComponentManager, FadeCompleteAction};
+use egui;
+use ui::animation::EasingFunction;
+use super::utils::{render_component_header, color_edit};
+
+/// Scrub position of the edit-mode preview (None while not previewing)
+fn preview_id(entity: Entity) -> egui::Id {
+    egui::Id::new(("color_fade_preview", entity))
+}
+
+pub fn render_color_fade_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
+    // Color Fade Component (tint tween multiplied into the sprite color)
+    let has_fade = world.has_component(entity, ComponentType::ColorFade);
+    let mut remove_fade = false;
+
+    if has_fade {
+        let fade_id = ui.make_persistent_id("color_fade_component");
+        let is_open = egui::collapsing_header::CollapsingState::load_with_default_open(
+            ui.ctx(), fade_id, true
+        );
+
+        render_component_header(ui, "Color Fade", "🌗", false);
+
+        if is_open.is_open() {
+            if let Some(fade) = world.color_fades.get_mut(&entity) {
+                ui.indent("color_fade_indent", |ui| {
+                    egui::Grid::new("color_fade_grid")
+                        .num_columns(2)
+                        .spacing([10.0, 8.0])
+                        .show(ui, |ui| {
+                            ui.label("From");
+                            color_edit(ui, &mut fade.from).on_hover_text("Tint when the fade starts (multiplies the sprite color)");
+                            ui.end_row();
+
+                            ui.label("To");
+                            color_edit(ui, &mut fade.to).on_hover_text("Tint the fade ends on (alpha 0 fades the sprite out)");
+                            ui.end_row();
+
+                            ui.label("Duration");
+                            ui.add(egui::DragValue::new(&mut fade.duration).speed(0.05).range(0.0..=f32::MAX).suffix(" s"));
+                            ui.end_row();
+
+                            ui.label("Delay");
+                            ui.add(egui::DragValue::new(&mut fade.delay).speed(0.05).range(0.0..=f32::MAX).suffix(" s"));
+                            ui.end_row();
+
+                            ui.label("Easing");
+                            let selected = EasingFunction::from_name(&fade.easing).unwrap_or(EasingFunction::Linear);
+                            egui::ComboBox::from_id_salt("color_fade_easing")
+                                .selected_text(format!("{:?}", selected))
+                                .height(300.0)
+                                .show_ui(ui, |ui| {
+                                    for easing in EasingFunction::ALL {
+                                        if ui.selectable_label(easing == selected, format!("{:?}", easing)).clicked() {
+                                            fade.easing = easing_name(&easing);
+                                        }
+                                    }
+                                });
+                            ui.end_row();
+
+                            ui.label("On Complete");
+                            egui::ComboBox::from_id_salt("color_fade_on_complete")
+                                .selected_text(fade.on_complete.label())
+                                .show_ui(ui, |ui| {
+                                    for action in FadeCompleteAction::ALL {
+                                        ui.selectable_value(&mut fade.on_complete, action, action.label());
+                                    }
+                                });
+                            ui.end_row();
+
+                            // Scrub the fade in the Scene view without entering play mode
+                            ui.label("Preview");
+                            let mut preview = ui.ctx().data(|d| d.get_temp::<Option<f32>>(preview_id(entity))).flatten();
+                            let was_previewing = preview.is_some();
+                            ui.horizontal(|ui| {
+                                let mut previewing = was_previewing;
+                                ui.checkbox(&mut previewing, "")
+                                    .on_hover_text("Show the fade at the scrubbed time in the Scene view");
+                                let mut t = preview.unwrap_or(0.0);
+                                let slider = ui.add_enabled(previewing, egui::Slider::new(&mut t, 0.0..=1.0).show_value(false));
+                                ui.label(format!("{:.2} s", t * fade.duration));
+                                if slider.changed() || previewing != was_previewing {
+                                    preview = previewing.then_some(t);
+                                }
+                            });
+                            match preview {
+                                Some(t) => {
+                                    let easing = EasingFunction::from_name(&fade.easing).unwrap_or(EasingFunction::Linear);
+                                    fade.tint = Some(fade.color_at(easing.evaluate(t)));
+                                }
+                                // Leaving the preview shows the sprite untinted again
+                                None if was_previewing => fade.tint = None,
+                                None => {}
+                            }
+                            ui.ctx().data_mut(|d| d.insert_temp(preview_id(entity), preview));
+                            ui.end_row();
+                        });
+
+                    ui.add_space(5.0);
+                    if ui.button("❌ Remove Component").clicked() {
+                        remove_fade = true;
+                    }
+                });
+            }
+            ui.add_space(10.0);
+        }
+    }
+
+    if remove_fade {
+        ui.ctx().data_mut(|d| d.remove_temp::<Option<f32>>(preview_id(entity)));
+        let _ = world.remove_component(entity, ComponentType::ColorFade);
+    }
+}
+
+/// "EaseOutQuad" -> "ease_out_quad" (the spelling scripts use)
+fn easing_name(easing: &EasingFunction) -> String {
+    let mut name = String::new();
+    for (i, c) in format!("{:?}", easing).chars().enumerate() {
+        if c.is_ascii_uppercase() && i > 0 {
+            name.push('_');
+        }
+        name.push(c.to_ascii_lowercase());
+    }
+    name
+}
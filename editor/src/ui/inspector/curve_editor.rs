@@ -0,0 +1,257 @@
+use egui;
+use ui::animation::{AnimationCurve, CurveKey, EasingFunction};
+
+const KEY_RADIUS: f32 = 4.5;
+const HANDLE_RADIUS: f32 = 3.5;
+const PICK_RADIUS: f32 = 7.0;
+
+#[derive(Clone, Copy, PartialEq)]
+enum DragTarget {
+    Key(usize),
+    InHandle(usize),
+    OutHandle(usize),
+}
+
+/// Selection and drag of one curve editor, kept in egui's temp memory
+#[derive(Clone, Default)]
+struct CurveEditorState {
+    open: bool,
+    selected: Option<usize>,
+    drag: Option<DragTarget>,
+}
+
+/// Inspector field for a curve: a thumbnail that opens the curve editor below it.
+/// Returns true when the curve changed.
+pub fn curve_field(ui: &mut egui::Ui, id_salt: impl std::hash::Hash, curve: &mut AnimationCurve) -> bool {
+    let id = ui.make_persistent_id(id_salt);
+    let mut state: CurveEditorState = ui.data_mut(|data| data.get_temp(id).unwrap_or_default());
+    let mut changed = false;
+
+    ui.vertical(|ui| {
+        let (rect, response) = ui.allocate_exact_size(egui::vec2(96.0, 24.0), egui::Sense::click());
+        let frame = CurveFrame::new(rect, curve);
+        ui.painter().rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+        ui.painter().add(egui::Shape::line(frame.samples(curve, 48), egui::Stroke::new(1.5, ui.visuals().selection.stroke.color)));
+        if response.on_hover_text(if state.open { "Close curve editor" } else { "Edit curve" }).clicked() {
+            state.open = !state.open;
+        }
+        if state.open {
+            changed = curve_editor(ui, &mut state, curve);
+        }
+    });
+
+    ui.data_mut(|data| data.insert_temp(id, state));
+    changed
+}
+
+/// Maps curve space (time 0..1, value range of the curve) to a screen rect
+struct CurveFrame {
+    rect: egui::Rect,
+    min_value: f32,
+    max_value: f32,
+}
+
+impl CurveFrame {
+    /// Fits 0..1 and everything the curve reaches (overshoot included), with a margin
+    fn new(rect: egui::Rect, curve: &AnimationCurve) -> Self {
+        let values = (0..=64).map(|i| curve.evaluate(i as f32 / 64.0)).chain(curve.keys().iter().map(|key| key.value));
+        let (min_value, max_value) = values.fold((0.0f32, 1.0f32), |(min, max), v| (min.min(v), max.max(v)));
+        let margin = (max_value - min_value) * 0.1;
+        Self { rect, min_value: min_value - margin, max_value: max_value + margin }
+    }
+
+    fn to_screen(&self, time: f32, value: f32) -> egui::Pos2 {
+        let y = (value - self.min_value) / (self.max_value - self.min_value);
+        egui::pos2(
+            egui::lerp(self.rect.left()..=self.rect.right(), time),
+            egui::lerp(self.rect.bottom()..=self.rect.top(), y),
+        )
+    }
+
+    fn to_curve(&self, pos: egui::Pos2) -> (f32, f32) {
+        let time = (pos.x - self.rect.left()) / self.rect.width();
+        let y = (self.rect.bottom() - pos.y) / self.rect.height();
+        (time, self.min_value + y * (self.max_value - self.min_value))
+    }
+
+    fn samples(&self, curve: &AnimationCurve, count: usize) -> Vec<egui::Pos2> {
+        (0..=count)
+            .map(|i| {
+                let t = i as f32 / count as f32;
+                self.to_screen(t, curve.evaluate(t))
+            })
+            .collect()
+    }
+}
+
+/// Tangent handles of a key: a third of the way to the neighbouring keys, like Bezier
+/// control points (the end keys use their one neighbour on both sides)
+fn handle_positions(frame: &CurveFrame, curve: &AnimationCurve, index: usize) -> (egui::Pos2, egui::Pos2) {
+    let keys = curve.keys();
+    let key = keys[index];
+    let before = index.checked_sub(1).map(|i| key.time - keys[i].time);
+    let after = keys.get(index + 1).map(|next| next.time - key.time);
+    let span_in = before.or(after).unwrap_or(0.25).max(0.01) / 3.0;
+    let span_out = after.or(before).unwrap_or(0.25).max(0.01) / 3.0;
+    (
+        frame.to_screen(key.time - span_in, key.value - key.in_tangent * span_in),
+        frame.to_screen(key.time + span_out, key.value + key.out_tangent * span_out),
+    )
+}
+
+fn curve_editor(ui: &mut egui::Ui, state: &mut CurveEditorState, curve: &mut AnimationCurve) -> bool {
+    let mut changed = false;
+    state.selected = state.selected.filter(|&index| index < curve.keys().len());
+
+    ui.horizontal(|ui| {
+        egui::ComboBox::from_id_salt(ui.id().with("curve_preset"))
+            .selected_text("Preset")
+            .width(120.0)
+            .show_ui(ui, |ui| {
+                for easing in EasingFunction::ALL {
+                    if ui.selectable_label(false, format!("{:?}", easing)).clicked() {
+                        *curve = easing.to_curve();
+                        state.selected = None;
+                        changed = true;
+                    }
+                }
+            });
+        if ui.button("Auto Tangents").on_hover_text("Smooth tangents that don't overshoot between keys").clicked() {
+            curve.auto_tangents();
+            changed = true;
+        }
+    });
+
+    let width = ui.available_width().clamp(160.0, 320.0);
+    let (response, painter) = ui.allocate_painter(egui::vec2(width, 150.0), egui::Sense::click_and_drag());
+    let frame = CurveFrame::new(response.rect.shrink(6.0), curve);
+    let visuals = ui.visuals().clone();
+
+    // Pick what's under the pointer: the selected key's handles first, then keys
+    let pick = |pos: egui::Pos2, curve: &AnimationCurve, selected: Option<usize>| -> Option<DragTarget> {
+        if let Some(index) = selected {
+            let (in_handle, out_handle) = handle_positions(&frame, curve, index);
+            if pos.distance(out_handle) < PICK_RADIUS {
+                return Some(DragTarget::OutHandle(index));
+            }
+            if pos.distance(in_handle) < PICK_RADIUS {
+                return Some(DragTarget::InHandle(index));
+            }
+        }
+        curve.keys().iter()
+            .position(|key| pos.distance(frame.to_screen(key.time, key.value)) < PICK_RADIUS)
+            .map(DragTarget::Key)
+    };
+
+    if let Some(pos) = response.interact_pointer_pos() {
+        if response.drag_started() || response.clicked() {
+            state.drag = pick(pos, curve, state.selected);
+            state.selected = match state.drag {
+                Some(DragTarget::Key(index) | DragTarget::InHandle(index) | DragTarget::OutHandle(index)) => Some(index),
+                None => None,
+            };
+        }
+        if response.double_clicked() && state.drag.is_none() {
+            // New key on the curve, keeping its shape
+            let (time, _) = frame.to_curve(pos);
+            let time = time.clamp(0.0, 1.0);
+            let slope = (curve.evaluate(time + 1e-3) - curve.evaluate(time - 1e-3)) / 2e-3;
+            state.selected = curve.add_key(CurveKey::with_tangents(time, curve.evaluate(time), slope, slope));
+            changed = true;
+        }
+        if response.dragged() {
+            let (time, value) = frame.to_curve(pos);
+            // Alt breaks the tangent: only the dragged side changes
+            let broken = ui.input(|input| input.modifiers.alt);
+            match state.drag {
+                Some(DragTarget::Key(index)) => {
+                    let new_index = curve.move_key(index, time, value);
+                    state.drag = new_index.map(DragTarget::Key);
+                    state.selected = new_index;
+                    changed = true;
+                }
+                Some(DragTarget::OutHandle(index)) => {
+                    let key = curve.keys()[index];
+                    let slope = (value - key.value) / (time - key.time).max(1e-3);
+                    curve.set_tangents(index, if broken { key.in_tangent } else { slope }, slope);
+                    changed = true;
+                }
+                Some(DragTarget::InHandle(index)) => {
+                    let key = curve.keys()[index];
+                    let slope = (key.value - value) / (key.time - time).max(1e-3);
+                    curve.set_tangents(index, slope, if broken { key.out_tangent } else { slope });
+                    changed = true;
+                }
+                None => {}
+            }
+        }
+    }
+    if response.drag_stopped() {
+        state.drag = None;
+    }
+    if response.secondary_clicked() {
+        if let (Some(pos), true) = (response.interact_pointer_pos(), curve.keys().len() > 2) {
+            if let Some(DragTarget::Key(index)) = pick(pos, curve, None) {
+                curve.remove_key(index);
+                state.selected = None;
+                changed = true;
+            }
+        }
+    }
+
+    // Background, the 0 and 1 lines, the curve, then keys and handles on top
+    painter.rect_filled(response.rect, 3.0, visuals.extreme_bg_color);
+    let guide = egui::Stroke::new(1.0, visuals.widgets.noninteractive.bg_stroke.color);
+    for value in [0.0, 1.0] {
+        painter.line_segment([frame.to_screen(0.0, value), frame.to_screen(1.0, value)], guide);
+    }
+    for time in [0.0, 0.5, 1.0] {
+        painter.line_segment([frame.to_screen(time, frame.min_value), frame.to_screen(time, frame.max_value)], guide);
+    }
+    painter.add(egui::Shape::line(frame.samples(curve, 128), egui::Stroke::new(2.0, visuals.selection.stroke.color)));
+
+    if let Some(index) = state.selected {
+        let key = curve.keys()[index];
+        let center = frame.to_screen(key.time, key.value);
+        let (in_handle, out_handle) = handle_positions(&frame, curve, index);
+        for handle in [in_handle, out_handle] {
+            painter.line_segment([center, handle], egui::Stroke::new(1.0, visuals.text_color()));
+            painter.circle_filled(handle, HANDLE_RADIUS, visuals.text_color());
+        }
+    }
+    for (index, key) in curve.keys().iter().enumerate() {
+        let color = if state.selected == Some(index) { visuals.warn_fg_color } else { visuals.strong_text_color() };
+        painter.circle_filled(frame.to_screen(key.time, key.value), KEY_RADIUS, color);
+    }
+
+    // Exact numbers for the selected key
+    if let Some(index) = state.selected {
+        let mut key = curve.keys()[index];
+        let before = key;
+        ui.horizontal(|ui| {
+            ui.label("Time");
+            ui.add(egui::DragValue::new(&mut key.time).speed(0.005).range(0.0..=1.0));
+            ui.label("Value");
+            ui.add(egui::DragValue::new(&mut key.value).speed(0.01));
+        });
+        ui.horizontal(|ui| {
+            ui.label("In");
+            ui.add(egui::DragValue::new(&mut key.in_tangent).speed(0.05));
+            ui.label("Out");
+            ui.add(egui::DragValue::new(&mut key.out_tangent).speed(0.05));
+            if ui.add_enabled(curve.keys().len() > 2, egui::Button::new("🗑")).on_hover_text("Delete key").clicked() {
+                curve.remove_key(index);
+                state.selected = None;
+                changed = true;
+            }
+        });
+        if state.selected.is_some() && key != before {
+            curve.set_tangents(index, key.in_tangent, key.out_tangent);
+            state.selected = curve.move_key(index, key.time, key.value);
+            changed = true;
+        }
+    }
+    ui.label(egui::RichText::new("Double-click: add key · Right-click: delete · Alt+drag handle: break tangent").small().weak());
+
+    changed
+}
@@ -0,0 +1,131 @@
+use ecs::{ComponentRegistry, Entity, FieldType, World};
+use egui;
+use serde_json::{json, Value as Json};
+use super::utils::render_component_header;
+
+/// Custom components (Component.define in scripts/components.lua): one section per
+/// component on the entity, with a widget per field generated from its definition.
+/// Components without a definition show their stored fields read-only.
+pub fn render_custom_component_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity, registry: &ComponentRegistry) {
+    let mut remove = None;
+
+    for name in world.custom_component_names(entity) {
+        let schema = registry.get(&name);
+        let id = ui.make_persistent_id(("custom_component", &name));
+        let is_open = egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, true);
+
+        render_component_header(ui, &name, "🧩", false);
+
+        if is_open.is_open() {
+            let Some(value) = world.custom_component_mut(entity, &name) else {
+                continue;
+            };
+            ui.indent(("custom_component_indent", &name), |ui| {
+                match schema {
+                    Some(schema) => {
+                        // Fields added to the definition since this was saved
+                        schema.conform(value);
+                        egui::Grid::new(("custom_component_grid", &name))
+                            .num_columns(2)
+                            .spacing([10.0, 8.0])
+                            .show(ui, |ui| {
+                                for field in &schema.fields {
+                                    ui.label(&field.name).on_hover_text(field.field_type.name());
+                                    if let Some(field_value) = value.get_mut(&field.name) {
+                                        field_widget(ui, field.field_type, field_value);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    }
+                    None => {
+                        ui.label(egui::RichText::new(format!(
+                            "Not defined in scripts/{}.lua; kept as saved", script::components::DEFINITIONS_MODULE
+                        )).small().color(egui::Color32::GRAY));
+                        let text = serde_json::to_string_pretty(value).unwrap_or_default();
+                        ui.label(egui::RichText::new(text).monospace().small());
+                    }
+                }
+
+                ui.add_space(5.0);
+                if ui.button("❌ Remove Component").clicked() {
+                    remove = Some(name.clone());
+                }
+            });
+            ui.add_space(10.0);
+        }
+    }
+
+    if let Some(name) = remove {
+        world.remove_custom_component(entity, &name);
+    }
+}
+
+fn number(value: &Json, key: &str) -> f32 {
+    value.get(key).and_then(Json::as_f64).unwrap_or(0.0) as f32
+}
+
+fn field_widget(ui: &mut egui::Ui, field_type: FieldType, value: &mut Json) {
+    match field_type {
+        FieldType::Float => {
+            let mut number = value.as_f64().unwrap_or(0.0);
+            if ui.add(egui::DragValue::new(&mut number).speed(0.1)).changed() {
+                *value = json!(number);
+            }
+        }
+        FieldType::Int => {
+            let mut number = value.as_i64().unwrap_or(0);
+            if ui.add(egui::DragValue::new(&mut number)).changed() {
+                *value = json!(number);
+            }
+        }
+        FieldType::Bool => {
+            let mut flag = value.as_bool().unwrap_or(false);
+            if ui.checkbox(&mut flag, "").changed() {
+                *value = json!(flag);
+            }
+        }
+        FieldType::String => {
+            let mut text = value.as_str().unwrap_or_default().to_string();
+            if ui.text_edit_singleline(&mut text).changed() {
+                *value = json!(text);
+            }
+        }
+        FieldType::Vector2 => {
+            let (mut x, mut y) = (number(value, "x"), number(value, "y"));
+            let changed = ui.horizontal(|ui| {
+                ui.label("X");
+                let x_changed = ui.add(egui::DragValue::new(&mut x).speed(0.1)).changed();
+                ui.label("Y");
+                let y_changed = ui.add(egui::DragValue::new(&mut y).speed(0.1)).changed();
+                x_changed || y_changed
+            }).inner;
+            if changed {
+                *value = json!({ "x": x, "y": y });
+            }
+        }
+        FieldType::Color => {
+            let mut rgba = [number(value, "r"), number(value, "g"), number(value, "b"), number(value, "a")];
+            if ui.color_edit_button_rgba_unmultiplied(&mut rgba).changed() {
+                *value = json!({ "r": rgba[0], "g": rgba[1], "b": rgba[2], "a": rgba[3] });
+            }
+        }
+    }
+}
+
+/// "🧩 Custom" section of the Add Component menu: defined components the entity lacks
+pub fn render_add_custom_component_menu(ui: &mut egui::Ui, world: &mut World, entity: Entity, registry: &ComponentRegistry) {
+    let addable: Vec<_> = registry.schemas().filter(|schema| !world.has_custom_component(entity, &schema.name)).collect();
+    if addable.is_empty() {
+        return;
+    }
+    ui.label("🧩 Custom");
+    ui.separator();
+    for schema in addable {
+        if ui.button(&schema.name).clicked() {
+            world.set_custom_component(entity, &schema.name, schema.default_value());
+            ui.close();
+        }
+    }
+    ui.add_space(5.0);
+}
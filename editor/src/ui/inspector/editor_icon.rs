@@ -0,0 +1,56 @@
+use ecs::{World, Entity, ComponentType, ComponentManager, EditorIconKind};
+use egui;
+use super::utils::{render_component_header, color_edit};
+use crate::ui::scene_view::rendering::icons::glyph;
+
+pub fn render_editor_icon_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
+    // Editor Icon Component (scene view marker, never drawn in game)
+    let has_icon = world.has_component(entity, ComponentType::EditorIcon);
+    let mut remove_icon = false;
+
+    if has_icon {
+        let icon_id = ui.make_persistent_id("editor_icon_component");
+        let is_open = egui::collapsing_header::CollapsingState::load_with_default_open(
+            ui.ctx(), icon_id, true
+        );
+
+        render_component_header(ui, "Editor Icon", "📍", false);
+
+        if is_open.is_open() {
+            if let Some(icon) = world.editor_icons.get_mut(&entity) {
+                ui.indent("editor_icon_indent", |ui| {
+                    egui::Grid::new("editor_icon_grid")
+                        .num_columns(2)
+                        .spacing([10.0, 8.0])
+                        .show(ui, |ui| {
+                            ui.label("Icon");
+                            egui::ComboBox::from_id_salt("editor_icon_kind")
+                                .selected_text(format!("{} {}", glyph(icon.icon), icon.icon.label()))
+                                .show_ui(ui, |ui| {
+                                    for kind in EditorIconKind::ALL {
+                                        ui.selectable_value(&mut icon.icon, kind, format!("{} {}", glyph(kind), kind.label()));
+                                    }
+                                });
+                            ui.end_row();
+
+                            ui.label("Tint");
+                            color_edit(ui, &mut icon.tint);
+                            ui.end_row();
+                        });
+
+                    ui.label(egui::RichText::new("Scene view only, hidden in the Game view and builds").small().weak());
+
+                    ui.add_space(5.0);
+                    if ui.button("❌ Remove Component").clicked() {
+                        remove_icon = true;
+                    }
+                });
+            }
+            ui.add_space(10.0);
+        }
+    }
+
+    if remove_icon {
+        let _ = world.remove_component(entity, ComponentType::EditorIcon);
+    }
+}
@@ -1,32 +1,48 @@
 pub mod utils;
 pub mod transform;
 pub mod sprite;
+pub mod sprite_material;
 pub mod collider;
 pub mod collider_3d;
+pub mod polygon_collider;
+pub mod animated_sprite;
 pub mod rigidbody;
 pub mod mesh;
 pub mod camera;
 pub mod script;
+pub mod curve_editor;
 pub mod model_3d;
+pub mod editor_icon;
+pub mod trigger_zone;
+pub mod color_fade;
+pub mod save_state;
+pub mod custom_component;
+pub mod multi;
+pub mod scene;
 
 use ecs::{World, Entity, EntityTag, ComponentType, ComponentManager};
 use egui;
 use std::collections::HashMap;
 use arboard::Clipboard;
 
-pub use utils::parse_hex_color;
+pub use utils::{parse_hex_color, color_edit, sorting_layer_combo};
 
 /// Renders the Inspector panel showing entity properties and components
 pub fn render_inspector(
     ui: &mut egui::Ui,
     world: &mut World,
     selected_entity: &mut Option<Entity>,
+    multi_selection: &[Entity],
     entity_names: &mut HashMap<Entity, String>,
     edit_script_request: &mut Option<String>,
     project_path: &Option<std::path::PathBuf>,
     open_sprite_editor_request: &mut Option<std::path::PathBuf>,
     sprite_picker_state: &mut crate::ui::sprite_picker::SpritePickerState,
     reload_mesh_assets_request: &mut bool,
+    texture_manager: &mut engine::texture_manager::TextureManager,
+    sorting_layers: &ecs::sorting::SortingLayers,
+    scene_defaults: scene::ProjectSceneDefaults,
+    component_registry: &ecs::ComponentRegistry,
 ) {
     // Unity-style header
     ui.horizontal(|ui| {
@@ -58,6 +74,11 @@ pub fn render_inspector(
     });
     ui.separator();
 
+    if multi_selection.len() > 1 {
+        multi::render_multi_inspector(ui, world, multi_selection);
+        return;
+    }
+
     if let Some(entity) = *selected_entity {
         egui::ScrollArea::vertical().show(ui, |ui| {
             // ===== Unity-style Inspector Header (Gray bar) =====
@@ -164,14 +185,22 @@ pub fn render_inspector(
 
             // --- Components ---
             transform::render_transform_inspector(ui, world, entity);
-            sprite::render_sprite_inspector(ui, world, entity, sprite_picker_state, open_sprite_editor_request);
+            sprite::render_sprite_inspector(ui, world, entity, sprite_picker_state, open_sprite_editor_request, sorting_layers, texture_manager);
+            sprite_material::render_sprite_material_inspector(ui, world, entity);
+            color_fade::render_color_fade_inspector(ui, world, entity);
+            animated_sprite::render_animated_sprite_inspector(ui, world, entity, texture_manager);
             collider::render_collider_inspector(ui, world, entity);
+            polygon_collider::render_polygon_collider_inspector(ui, world, entity);
             collider_3d::render_collider_3d_inspector(ui, world, entity);
             rigidbody::render_rigidbody_inspector(ui, world, entity);
             mesh::render_mesh_inspector(ui, world, entity, project_path.as_deref(), reload_mesh_assets_request);
             camera::render_camera_inspector(ui, world, entity);
             script::render_script_inspector(ui, world, entity, project_path, edit_script_request);
             model_3d::render_model_3d_inspector(ui, world, entity, project_path.as_deref());
+            editor_icon::render_editor_icon_inspector(ui, world, entity);
+            trigger_zone::render_trigger_zone_inspector(ui, world, entity);
+            save_state::render_save_state_inspector(ui, world, entity);
+            custom_component::render_custom_component_inspector(ui, world, entity, component_registry);
 
             // ===== Add Component Button (Unity-style) =====
             ui.add_space(15.0);
@@ -205,11 +234,12 @@ pub fn render_inspector(
                                 }
                             };
 
-                            render_component_category(ui, "🎨 Rendering", &[ComponentType::Sprite, ComponentType::Mesh, ComponentType::Model3D]);
-                            render_component_category(ui, "⚙️ Physics", &[ComponentType::BoxCollider, ComponentType::Collider3D, ComponentType::Rigidbody, ComponentType::TilemapCollider, ComponentType::LdtkIntGridCollider]);
+                            render_component_category(ui, "🎨 Rendering", &[ComponentType::Sprite, ComponentType::SpriteMaterial, ComponentType::ColorFade, ComponentType::Mesh, ComponentType::Model3D]);
+                            render_component_category(ui, "⚙️ Physics", &[ComponentType::BoxCollider, ComponentType::PolygonCollider, ComponentType::Collider3D, ComponentType::Rigidbody, ComponentType::TilemapCollider, ComponentType::LdtkIntGridCollider, ComponentType::TriggerZone]);
                             render_component_category(ui, "🗺️ Tilemap", &[ComponentType::LdtkMap]);
-                            render_component_category(ui, "📜 Other", &[ComponentType::Camera, ComponentType::Script, ComponentType::Tag, ComponentType::Map]);
+                            render_component_category(ui, "📜 Other", &[ComponentType::Camera, ComponentType::Script, ComponentType::Tag, ComponentType::Map, ComponentType::EditorIcon, ComponentType::SaveState]);
                     }
+                    custom_component::render_add_custom_component_menu(ui, world, entity, component_registry);
                 });
             });
 
@@ -232,5 +262,7 @@ pub fn render_inspector(
             ui.add_space(50.0);
             ui.label(egui::RichText::new("Select an object to inspect").color(egui::Color32::GRAY));
         });
+        ui.add_space(30.0);
+        scene::render_scene_settings(ui, world, sorting_layers, scene_defaults);
     }
 }
@@ -0,0 +1,288 @@
+use ecs::{World, Entity};
+use egui;
+use egui::emath::Numeric;
+use super::utils::{render_component_header_with_menu, color_edit};
+use crate::systems::undo::ComponentKind;
+
+/// Shown instead of a value when the selected entities disagree
+const MIXED_VALUE: &str = "—";
+
+/// Vector field of a component edited as one row (Position, Offset, ...)
+type TransformField = fn(&mut ecs::Transform) -> &mut [f32; 3];
+type ColliderField = fn(&mut ecs::Collider) -> &mut [f32; 2];
+
+/// Inspector for a multi-selection: shows components shared by every selected entity.
+/// Fields with differing values show "—"; editing a field sets it on all entities.
+pub fn render_multi_inspector(ui: &mut egui::Ui, world: &mut World, entities: &[Entity]) {
+    egui::ScrollArea::vertical().show(ui, |ui| {
+        egui::Frame::NONE
+            .fill(egui::Color32::from_rgb(60, 60, 60))
+            .inner_margin(egui::Margin::same(5))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let active: Vec<bool> = entities
+                        .iter()
+                        .map(|e| world.active.get(e).copied().unwrap_or(true))
+                        .collect();
+                    if let Some(value) = mixed_checkbox(ui, &active, "") {
+                        for &entity in entities {
+                            world.active.insert(entity, value);
+                        }
+                    }
+                    ui.label("🎲");
+                    ui.strong(format!("{} objects selected", entities.len()));
+                });
+            });
+
+        ui.add_space(5.0);
+
+        if entities.iter().all(|e| world.transforms.contains_key(e)) {
+            render_transform_section(ui, world, entities);
+        }
+        if entities.iter().all(|e| world.sprites.contains_key(e)) {
+            render_sprite_section(ui, world, entities);
+        }
+        if entities.iter().all(|e| world.rigidbodies.contains_key(e)) {
+            render_rigidbody_section(ui, world, entities);
+        }
+        if entities.iter().all(|e| world.colliders.contains_key(e)) {
+            render_collider_section(ui, world, entities);
+        }
+
+        ui.add_space(10.0);
+        ui.label(
+            egui::RichText::new("Only components shared by all selected objects are shown")
+                .small()
+                .color(egui::Color32::GRAY),
+        );
+    });
+}
+
+fn render_transform_section(ui: &mut egui::Ui, world: &mut World, entities: &[Entity]) {
+    render_component_header_with_menu(ui, "Transform", "⚙️", true, ComponentKind::Transform);
+
+    egui::Grid::new("multi_transform_grid")
+        .num_columns(7)
+        .spacing([5.0, 8.0])
+        .show(ui, |ui| {
+            let rows: [(&str, f64, TransformField); 3] = [
+                ("Position", 0.1, |t| &mut t.position),
+                ("Rotation", 0.5, |t| &mut t.rotation),
+                ("Scale", 0.01, |t| &mut t.scale),
+            ];
+
+            for (label, speed, field) in rows {
+                ui.label(label);
+                for (axis, axis_label) in ["X", "Y", "Z"].iter().enumerate() {
+                    ui.label(*axis_label);
+                    let values: Vec<f32> = entities
+                        .iter()
+                        .filter_map(|e| world.transforms.get_mut(e).map(|t| field(t)[axis]))
+                        .collect();
+                    if let Some(value) = mixed_drag_value(ui, &values, speed) {
+                        for entity in entities {
+                            if let Some(transform) = world.transforms.get_mut(entity) {
+                                field(transform)[axis] = value;
+                            }
+                        }
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+    ui.add_space(5.0);
+}
+
+fn render_sprite_section(ui: &mut egui::Ui, world: &mut World, entities: &[Entity]) {
+    render_component_header_with_menu(ui, "Sprite Renderer", "🎨", true, ComponentKind::Sprite);
+
+    let sprites: Vec<ecs::Sprite> = entities.iter().filter_map(|e| world.sprites.get(e).cloned()).collect();
+
+    egui::Grid::new("multi_sprite_grid")
+        .num_columns(2)
+        .spacing([10.0, 8.0])
+        .show(ui, |ui| {
+            ui.label("Color");
+            let mut color = sprites[0].color;
+            let mixed = sprites.iter().any(|s| s.color != color);
+            ui.horizontal(|ui| {
+                if color_edit(ui, &mut color).changed() {
+                    for entity in entities {
+                        if let Some(sprite) = world.sprites.get_mut(entity) {
+                            sprite.color = color;
+                        }
+                    }
+                }
+                if mixed {
+                    ui.label(MIXED_VALUE);
+                }
+            });
+            ui.end_row();
+
+            ui.label("Flip");
+            ui.horizontal(|ui| {
+                let flip_x: Vec<bool> = sprites.iter().map(|s| s.flip_x).collect();
+                if let Some(value) = mixed_checkbox(ui, &flip_x, "X") {
+                    for entity in entities {
+                        if let Some(sprite) = world.sprites.get_mut(entity) {
+                            sprite.flip_x = value;
+                        }
+                    }
+                }
+                let flip_y: Vec<bool> = sprites.iter().map(|s| s.flip_y).collect();
+                if let Some(value) = mixed_checkbox(ui, &flip_y, "Y") {
+                    for entity in entities {
+                        if let Some(sprite) = world.sprites.get_mut(entity) {
+                            sprite.flip_y = value;
+                        }
+                    }
+                }
+            });
+            ui.end_row();
+
+            ui.label("Order in Layer");
+            let orders: Vec<i32> = sprites.iter().map(|s| s.order_in_layer).collect();
+            if let Some(value) = mixed_drag_value(ui, &orders, 1.0) {
+                for entity in entities {
+                    if let Some(sprite) = world.sprites.get_mut(entity) {
+                        sprite.order_in_layer = value;
+                    }
+                }
+            }
+            ui.end_row();
+        });
+
+    ui.add_space(5.0);
+}
+
+fn render_rigidbody_section(ui: &mut egui::Ui, world: &mut World, entities: &[Entity]) {
+    render_component_header_with_menu(ui, "Rigidbody 2D", "⚡", true, ComponentKind::Rigidbody2D);
+
+    let bodies: Vec<ecs::Rigidbody2D> = entities.iter().filter_map(|e| world.rigidbodies.get(e).cloned()).collect();
+
+    egui::Grid::new("multi_rigidbody_grid")
+        .num_columns(2)
+        .spacing([10.0, 8.0])
+        .show(ui, |ui| {
+            ui.label("Gravity Scale");
+            let values: Vec<f32> = bodies.iter().map(|rb| rb.gravity_scale).collect();
+            if let Some(value) = mixed_drag_value(ui, &values, 0.1) {
+                for entity in entities {
+                    if let Some(rb) = world.rigidbodies.get_mut(entity) {
+                        rb.gravity_scale = value;
+                    }
+                }
+            }
+            ui.end_row();
+
+            ui.label("Mass");
+            let values: Vec<f32> = bodies.iter().map(|rb| rb.mass).collect();
+            if let Some(value) = mixed_drag_value(ui, &values, 0.1) {
+                for entity in entities {
+                    if let Some(rb) = world.rigidbodies.get_mut(entity) {
+                        rb.mass = value.max(0.0);
+                    }
+                }
+            }
+            ui.end_row();
+
+            ui.label("Is Kinematic");
+            let values: Vec<bool> = bodies.iter().map(|rb| rb.is_kinematic).collect();
+            if let Some(value) = mixed_checkbox(ui, &values, "") {
+                for entity in entities {
+                    if let Some(rb) = world.rigidbodies.get_mut(entity) {
+                        rb.is_kinematic = value;
+                    }
+                }
+            }
+            ui.end_row();
+
+            ui.label("Freeze Rotation");
+            let values: Vec<bool> = bodies.iter().map(|rb| rb.freeze_rotation).collect();
+            if let Some(value) = mixed_checkbox(ui, &values, "") {
+                for entity in entities {
+                    if let Some(rb) = world.rigidbodies.get_mut(entity) {
+                        rb.freeze_rotation = value;
+                    }
+                }
+            }
+            ui.end_row();
+        });
+
+    ui.add_space(5.0);
+}
+
+fn render_collider_section(ui: &mut egui::Ui, world: &mut World, entities: &[Entity]) {
+    render_component_header_with_menu(ui, "Box Collider 2D", "📦", true, ComponentKind::Collider);
+
+    egui::Grid::new("multi_collider_grid")
+        .num_columns(5)
+        .spacing([5.0, 8.0])
+        .show(ui, |ui| {
+            let rows: [(&str, ColliderField); 2] = [
+                ("Offset", |c| &mut c.offset),
+                ("Size", |c| &mut c.size),
+            ];
+
+            for (label, field) in rows {
+                ui.label(label);
+                for (axis, axis_label) in ["X", "Y"].iter().enumerate() {
+                    ui.label(*axis_label);
+                    let values: Vec<f32> = entities
+                        .iter()
+                        .filter_map(|e| world.colliders.get_mut(e).map(|c| field(c)[axis]))
+                        .collect();
+                    if let Some(value) = mixed_drag_value(ui, &values, 0.01) {
+                        for entity in entities {
+                            if let Some(collider) = world.colliders.get_mut(entity) {
+                                field(collider)[axis] = value;
+                            }
+                        }
+                    }
+                }
+                ui.end_row();
+            }
+
+            ui.label("Is Trigger");
+            let values: Vec<bool> = entities
+                .iter()
+                .filter_map(|e| world.colliders.get(e).map(|c| c.is_trigger))
+                .collect();
+            if let Some(value) = mixed_checkbox(ui, &values, "") {
+                for entity in entities {
+                    if let Some(collider) = world.colliders.get_mut(entity) {
+                        collider.is_trigger = value;
+                    }
+                }
+            }
+            ui.end_row();
+        });
+
+    ui.add_space(5.0);
+}
+
+/// Drag value over several entities' values. Returns the new value when edited.
+fn mixed_drag_value<T: Numeric>(ui: &mut egui::Ui, values: &[T], speed: f64) -> Option<T> {
+    let first = *values.first()?;
+    let mixed = values.iter().any(|v| *v != first);
+
+    let mut value = first;
+    let mut drag = egui::DragValue::new(&mut value).speed(speed).max_decimals(2);
+    if mixed {
+        drag = drag.custom_formatter(|_, _| MIXED_VALUE.to_string());
+    }
+
+    ui.add(drag).changed().then_some(value)
+}
+
+/// Checkbox over several entities' flags; mixed values show as indeterminate.
+fn mixed_checkbox(ui: &mut egui::Ui, values: &[bool], label: &str) -> Option<bool> {
+    let first = *values.first()?;
+    let mixed = values.iter().any(|v| *v != first);
+
+    let mut value = first && !mixed;
+    ui.add(egui::Checkbox::new(&mut value, label).indeterminate(mixed))
+        .changed()
+        .then_some(value)
+}
@@ -0,0 +1,85 @@
+use ecs::{World, Entity, ComponentType, ComponentManager, PolygonCollider};
+use egui;
+use super::utils::render_component_header_with_menu;
+use crate::systems::undo::ComponentKind;
+
+pub fn render_polygon_collider_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
+    // Polygon Collider Component (generated in the Sprite Editor, or edited here)
+    let has_collider = world.has_component(entity, ComponentType::PolygonCollider);
+    let mut remove_collider = false;
+
+    if has_collider {
+        let collider_id = ui.make_persistent_id("polygon_collider_component");
+        let is_open = egui::collapsing_header::CollapsingState::load_with_default_open(
+            ui.ctx(), collider_id, true
+        );
+
+        render_component_header_with_menu(ui, "Polygon Collider 2D", "🔷", false, ComponentKind::PolygonCollider);
+
+        if is_open.is_open() {
+            if let Some(collider) = world.polygon_colliders.get_mut(&entity) {
+                ui.indent("polygon_collider_indent", |ui| {
+                    egui::Grid::new("polygon_collider_grid")
+                        .num_columns(2)
+                        .spacing([5.0, 8.0])
+                        .show(ui, |ui| {
+                            // Trigger
+                            ui.label("Is Trigger");
+                            ui.checkbox(&mut collider.is_trigger, "")
+                                .on_hover_text("Report overlaps without blocking movement");
+                            ui.end_row();
+
+                            ui.label("Points");
+                            ui.label(format!("{} ({} convex parts)", collider.points.len(), collider.convex_parts().len()));
+                            ui.end_row();
+                        });
+
+                    ui.collapsing("Vertices", |ui| {
+                        let mut remove_point = None;
+                        for (i, point) in collider.points.iter_mut().enumerate() {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{}", i));
+                                ui.label("X");
+                                ui.add(egui::DragValue::new(&mut point[0]).speed(0.01).max_decimals(3));
+                                ui.label("Y");
+                                ui.add(egui::DragValue::new(&mut point[1]).speed(0.01).max_decimals(3));
+                                if ui.small_button("🗑").on_hover_text("Remove vertex").clicked() {
+                                    remove_point = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove_point {
+                            if collider.points.len() > 3 {
+                                collider.points.remove(i);
+                            }
+                        }
+                        if ui.button("➕ Add Vertex").on_hover_text("Split the last edge").clicked() {
+                            if let (Some(&first), Some(&last)) = (collider.points.first(), collider.points.last()) {
+                                collider.points.push([(first[0] + last[0]) / 2.0, (first[1] + last[1]) / 2.0]);
+                            }
+                        }
+                    });
+
+                    ui.label(egui::RichText::new(
+                        "💡 Generate the shape from sprite alpha in the Sprite Editor (Physics Shape)"
+                    ).small().color(egui::Color32::from_rgb(150, 150, 150)));
+
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        if ui.button("⬜ Reset").on_hover_text("Reset to a unit square").clicked() {
+                            *collider = PolygonCollider { is_trigger: collider.is_trigger, ..PolygonCollider::default() };
+                        }
+                        if ui.button("❌ Remove Component").clicked() {
+                            remove_collider = true;
+                        }
+                    });
+                });
+            }
+            ui.add_space(10.0);
+        }
+    }
+
+    if remove_collider {
+        let _ = world.remove_component(entity, ComponentType::PolygonCollider);
+    }
+}
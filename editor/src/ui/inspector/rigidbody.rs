@@ -1,7 +1,8 @@
 use ecs::{World, Entity, ComponentType, ComponentManager};
 use egui;
 use arboard::Clipboard;
-use super::utils::render_component_header;
+use super::utils::render_component_header_with_menu;
+use crate::systems::undo::ComponentKind;
 
 pub fn render_rigidbody_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
     // Rigidbody 2D Component - Unity-style with full properties
@@ -14,7 +15,7 @@ pub fn render_rigidbody_inspector(ui: &mut egui::Ui, world: &mut World, entity:
             ui.ctx(), rigidbody_id, true
         );
         
-        render_component_header(ui, "Rigidbody 2D", "⚡", false);
+        render_component_header_with_menu(ui, "Rigidbody 2D", "⚡", false, ComponentKind::Rigidbody2D);
         
         if is_open.is_open() {
             // Ensure rigidbody exists (create if only legacy velocity exists)
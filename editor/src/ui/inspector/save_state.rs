@@ -0,0 +1,73 @@
+use ecs::{World, Entity, ComponentType, ComponentManager};
+use egui;
+use super::utils::render_component_header;
+
+pub fn render_save_state_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
+    // Save State Component (what save games store for this entity)
+    let has_save_state = world.has_component(entity, ComponentType::SaveState);
+    let mut remove_save_state = false;
+
+    if has_save_state {
+        let save_state_id = ui.make_persistent_id("save_state_component");
+        let is_open = egui::collapsing_header::CollapsingState::load_with_default_open(
+            ui.ctx(), save_state_id, true
+        );
+
+        render_component_header(ui, "Save State", "💾", false);
+
+        if is_open.is_open() {
+            let guid = world.guids.get(&entity).map(|guid| guid.0.clone());
+            if let Some(save_state) = world.save_states.get_mut(&entity) {
+                ui.indent("save_state_indent", |ui| {
+                    ui.label(egui::RichText::new("Stored by SaveGame.save, put back by SaveGame.load").small().color(egui::Color32::GRAY));
+                    egui::Grid::new("save_state_grid")
+                        .num_columns(2)
+                        .spacing([10.0, 8.0])
+                        .show(ui, |ui| {
+                            ui.label("Transform");
+                            ui.checkbox(&mut save_state.transform, "");
+                            ui.end_row();
+
+                            ui.label("Active");
+                            ui.checkbox(&mut save_state.active, "");
+                            ui.end_row();
+
+                            ui.label("Sprite");
+                            ui.checkbox(&mut save_state.sprite, "").on_hover_text("Texture, frame, color and flips");
+                            ui.end_row();
+
+                            ui.label("Physics");
+                            ui.checkbox(&mut save_state.physics, "").on_hover_text("Velocity and Rigidbody 2D");
+                            ui.end_row();
+
+                            ui.label("Animation");
+                            ui.checkbox(&mut save_state.animation, "");
+                            ui.end_row();
+
+                            ui.label("Script");
+                            ui.checkbox(&mut save_state.script, "")
+                                .on_hover_text("Enabled flag and parameters (Entity parameters keep their scene value)");
+                            ui.end_row();
+
+                            ui.label("GUID");
+                            match &guid {
+                                Some(guid) => { ui.label(egui::RichText::new(guid).monospace().small()); }
+                                None => { ui.label(egui::RichText::new("Assigned when the scene is saved").color(egui::Color32::GRAY)); }
+                            }
+                            ui.end_row();
+                        });
+
+                    ui.add_space(5.0);
+                    if ui.button("❌ Remove Component").clicked() {
+                        remove_save_state = true;
+                    }
+                });
+            }
+            ui.add_space(10.0);
+        }
+    }
+
+    if remove_save_state {
+        let _ = world.remove_component(entity, ComponentType::SaveState);
+    }
+}
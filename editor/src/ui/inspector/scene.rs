@@ -0,0 +1,122 @@
+use ecs::{World, Color};
+use egui;
+use super::utils::{render_component_header, color_edit};
+
+/// Project values a scene falls back to where it has no override (settings.json)
+#[derive(Clone, Copy)]
+pub struct ProjectSceneDefaults {
+    pub gravity: f32,
+    pub clear_color: Color,
+}
+
+/// Scene-level settings, shown in the inspector when nothing is selected
+pub fn render_scene_settings(
+    ui: &mut egui::Ui,
+    world: &mut World,
+    sorting_layers: &ecs::sorting::SortingLayers,
+    defaults: ProjectSceneDefaults,
+) {
+    render_component_header(ui, "Scene Settings", "🎬", true);
+    let settings = &mut world.scene_settings;
+
+    egui::Grid::new("scene_settings_grid")
+        .num_columns(2)
+        .spacing([10.0, 8.0])
+        .show(ui, |ui| {
+            // Overrides start from the project's value when they're switched on
+            ui.label("Background");
+            ui.horizontal(|ui| {
+                let mut overridden = settings.background_color.is_some();
+                if ui.checkbox(&mut overridden, "")
+                    .on_hover_text("Override the project's clear color (used where no camera clears with a solid color)")
+                    .changed()
+                {
+                    settings.background_color = overridden.then_some(defaults.clear_color);
+                }
+                match &mut settings.background_color {
+                    Some(color) => { color_edit(ui, color); }
+                    None => { ui.label(egui::RichText::new("Project").color(egui::Color32::GRAY)); }
+                }
+            });
+            ui.end_row();
+
+            ui.label("Ambient Light");
+            color_edit(ui, &mut settings.ambient_color).on_hover_text("Multiplies every sprite's color (white draws sprites as authored)");
+            ui.end_row();
+
+            ui.label("Gravity");
+            ui.horizontal(|ui| {
+                let mut overridden = settings.gravity.is_some();
+                if ui.checkbox(&mut overridden, "")
+                    .on_hover_text("Override Project Settings > Physics gravity in this scene")
+                    .changed()
+                {
+                    settings.gravity = overridden.then_some(defaults.gravity);
+                }
+                match &mut settings.gravity {
+                    Some(gravity) => { ui.add(egui::DragValue::new(gravity).speed(1.0).suffix(" px/s²")); }
+                    None => { ui.label(egui::RichText::new(format!("Project ({})", defaults.gravity)).color(egui::Color32::GRAY)); }
+                }
+            });
+            ui.end_row();
+        });
+
+    ui.add_space(5.0);
+    ui.label("Visible Sorting Layers");
+    for name in sorting_layers.names() {
+        let mut visible = settings.is_layer_visible(name);
+        if ui.checkbox(&mut visible, name).changed() {
+            settings.set_layer_visible(name, visible);
+        }
+    }
+    // Hidden layers that were since removed from the project
+    let stale: Vec<String> = settings.hidden_sorting_layers.iter()
+        .filter(|name| !sorting_layers.names().contains(*name))
+        .cloned()
+        .collect();
+    for name in stale {
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(format!("{} (missing)", name)).color(egui::Color32::from_rgb(255, 165, 0)));
+            if ui.small_button("✖").on_hover_text("Forget this hidden layer").clicked() {
+                settings.set_layer_visible(&name, true);
+            }
+        });
+    }
+    ui.add_space(10.0);
+
+    render_component_header(ui, "Scene Preload", "⏳", true);
+
+    ui.label(
+        egui::RichText::new("Loaded before play starts (paths relative to the project)")
+            .small()
+            .color(egui::Color32::GRAY),
+    );
+
+    let mut remove = None;
+    for (index, path) in world.preload_assets.iter().enumerate() {
+        ui.horizontal(|ui| {
+            if ui.small_button("✖").on_hover_text("Remove from preload list").clicked() {
+                remove = Some(index);
+            }
+            ui.label(path);
+        });
+    }
+    if let Some(index) = remove {
+        world.preload_assets.remove(index);
+    }
+
+    let id = ui.make_persistent_id("scene_preload_new_path");
+    let mut new_path = ui.data_mut(|d| d.get_temp::<String>(id)).unwrap_or_default();
+    ui.horizontal(|ui| {
+        let response = ui.add(egui::TextEdit::singleline(&mut new_path).hint_text("assets/level.png"));
+        let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+        if (ui.button("Add").clicked() || submitted) && !new_path.trim().is_empty() {
+            let path = new_path.trim().replace('\\', "/");
+            if !world.preload_assets.contains(&path) {
+                world.preload_assets.push(path);
+            }
+            new_path.clear();
+        }
+    });
+    ui.data_mut(|d| d.insert_temp(id, new_path));
+}
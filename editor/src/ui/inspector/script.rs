@@ -1,6 +1,8 @@
 use ecs::{World, Entity, ComponentType, ComponentManager, ScriptParameter};
 use egui;
-use super::utils::{render_component_header, parse_lua_script_parameters};
+use super::utils::{render_component_header_with_menu, parse_lua_script_parameters};
+use crate::systems::undo::ComponentKind;
+use crate::script_annotations;
 
 pub fn render_script_inspector(
     ui: &mut egui::Ui,
@@ -19,7 +21,7 @@ pub fn render_script_inspector(
             ui.ctx(), script_id, true
         );
         
-        render_component_header(ui, "Script", "📜", false);
+        render_component_header_with_menu(ui, "Script", "📜", false, ComponentKind::Script);
         
         if is_open.is_open() {
             ui.indent("script_indent", |ui| {
@@ -70,13 +72,42 @@ pub fn render_script_inspector(
                     let script_file = proj_path.join("scripts").join(format!("{}.lua", script.script_name));
                     if script_file.exists() {
                         let parsed_params = parse_lua_script_parameters(&script_file);
+                        let annotations = script_annotations::load(&script_file, &format!("scripts/{}.lua", script.script_name));
+
+                        // Annotated parameters missing from the scene start at their default
+                        for param in &annotations.params {
+                            let value = match script.parameters.get(&param.name).and_then(|value| param.coerce(value)) {
+                                Some(value) => value,
+                                None => match parsed_params.get(&param.name) {
+                                    Some(declared) if param.default.is_none() => {
+                                        param.coerce(declared).unwrap_or_else(|| param.default_value())
+                                    }
+                                    _ => param.default_value(),
+                                },
+                            };
+                            script.parameters.insert(param.name.clone(), value);
+                        }
 
                         // Merge parsed parameters with existing ones (keep user-modified values)
-                        for (key, default_value) in parsed_params {
-                            script.parameters.entry(key).or_insert(default_value);
+                        for (key, default_value) in &parsed_params {
+                            script.parameters.entry(key.clone()).or_insert_with(|| default_value.clone());
                         }
 
-                        // Display all parameters (Unity-style)
+                        // Saved in the scene but no longer declared by the script
+                        let mut stale: Vec<String> = script.parameters.keys()
+                            .filter(|key| annotations.get(key).is_none() && !parsed_params.contains_key(*key))
+                            .cloned()
+                            .collect();
+                        stale.sort();
+
+                        if !annotations.errors.is_empty() {
+                            ui.label(egui::RichText::new(format!(
+                                "⚠ {} malformed @param annotation(s), see the Console",
+                                annotations.errors.len()
+                            )).color(ui.visuals().warn_fg_color));
+                        }
+
+                        // Display all parameters (Unity-style): annotated ones in script order first
                         if !script.parameters.is_empty() {
                             ui.add_space(10.0);
                             ui.separator();
@@ -87,23 +118,58 @@ pub fn render_script_inspector(
                                 .num_columns(2)
                                 .spacing([10.0, 8.0])
                                 .show(ui, |ui| {
-                                    let param_keys: Vec<String> = script.parameters.keys().cloned().collect();
+                                    let mut param_keys: Vec<String> = annotations.params.iter().map(|param| param.name.clone()).collect();
+                                    let mut others: Vec<String> = script.parameters.keys()
+                                        .filter(|key| annotations.get(key).is_none())
+                                        .cloned()
+                                        .collect();
+                                    others.sort();
+                                    param_keys.extend(others);
+
                                     for key in param_keys {
+                                        let annotation = annotations.get(&key);
+                                        let tooltip = annotation.and_then(|param| param.tooltip.as_deref());
                                         if let Some(value) = script.parameters.get_mut(&key) {
-                                            ui.label(&key);
+                                            let label = if stale.contains(&key) {
+                                                ui.label(egui::RichText::new(format!("⚠ {}", key)).color(ui.visuals().warn_fg_color))
+                                                    .on_hover_text("Stale parameter: the script no longer declares it")
+                                            } else {
+                                                ui.label(&key)
+                                            };
+                                            if let Some(tooltip) = tooltip {
+                                                label.on_hover_text(tooltip);
+                                            }
 
-                                            match value {
+                                            let response = match value {
                                                 ScriptParameter::Float(f) => {
-                                                    ui.add(egui::DragValue::new(f).speed(0.1));
+                                                    Some(match annotation.and_then(|param| param.range()) {
+                                                        Some((min, max)) => ui.add(egui::Slider::new(f, min as f32..=max as f32)),
+                                                        None => {
+                                                            let min = annotation.and_then(|param| param.min).map_or(f32::NEG_INFINITY, |min| min as f32);
+                                                            let max = annotation.and_then(|param| param.max).map_or(f32::INFINITY, |max| max as f32);
+                                                            ui.add(egui::DragValue::new(f).speed(0.1).range(min..=max))
+                                                        }
+                                                    })
                                                 }
                                                 ScriptParameter::Int(i) => {
-                                                    ui.add(egui::DragValue::new(i).speed(1));
+                                                    Some(match annotation.and_then(|param| param.range()) {
+                                                        Some((min, max)) => ui.add(egui::Slider::new(i, min as i32..=max as i32)),
+                                                        None => {
+                                                            let min = annotation.and_then(|param| param.min).map_or(i32::MIN, |min| min as i32);
+                                                            let max = annotation.and_then(|param| param.max).map_or(i32::MAX, |max| max as i32);
+                                                            ui.add(egui::DragValue::new(i).speed(1).range(min..=max))
+                                                        }
+                                                    })
                                                 }
                                                 ScriptParameter::String(s) => {
-                                                    ui.text_edit_singleline(s);
+                                                    Some(ui.text_edit_singleline(s))
                                                 }
                                                 ScriptParameter::Bool(b) => {
-                                                    ui.checkbox(b, "");
+                                                    Some(ui.checkbox(b, ""))
+                                                }
+                                                ScriptParameter::Curve(curve) => {
+                                                    super::curve_editor::curve_field(ui, format!("curve_param_{}", key), curve);
+                                                    None
                                                 }
                                                 ScriptParameter::Entity(entity_opt) => {
                                                     // Entity dropdown (Unity-style GameObject reference)
@@ -117,7 +183,7 @@ pub fn render_script_inspector(
                                                         "None".to_string()
                                                     };
 
-                                                    egui::ComboBox::from_id_source(format!("entity_param_{}", key))
+                                                    let combo = egui::ComboBox::from_id_salt(format!("entity_param_{}", key))
                                                         .selected_text(current_text)
                                                         .show_ui(ui, |ui| {
                                                             // None option
@@ -141,12 +207,25 @@ pub fn render_script_inspector(
                                                                 }
                                                             }
                                                         });
+                                                    Some(combo.response)
                                                 }
+                                            };
+                                            if let (Some(response), Some(tooltip)) = (response, tooltip) {
+                                                response.on_hover_text(tooltip);
                                             }
                                             ui.end_row();
                                         }
                                     }
                                 });
+
+                            if !stale.is_empty() && ui.button(format!("🧹 Remove {} stale parameter(s)", stale.len()))
+                                .on_hover_text(stale.join(", "))
+                                .clicked()
+                            {
+                                for key in &stale {
+                                    script.parameters.remove(key);
+                                }
+                            }
                         }
                     }
                 }
@@ -1,6 +1,7 @@
 use ecs::{World, Entity, ComponentType, ComponentManager};
 use egui;
-use super::utils::render_component_header;
+use super::utils::{render_component_header, render_component_header_with_menu, color_edit, sorting_layer_combo};
+use crate::systems::undo::ComponentKind;
 
 pub fn render_sprite_inspector(
     ui: &mut egui::Ui,
@@ -8,6 +9,8 @@ pub fn render_sprite_inspector(
     entity: Entity,
     sprite_picker_state: &mut crate::ui::sprite_picker::SpritePickerState,
     open_sprite_editor_request: &mut Option<std::path::PathBuf>,
+    sorting_layers: &ecs::sorting::SortingLayers,
+    texture_manager: &mut engine::texture_manager::TextureManager,
 ) {
     // Sprite Component (Unity-style collapsible)
     let has_sprite = world.has_component(entity, ComponentType::Sprite);
@@ -19,9 +22,11 @@ pub fn render_sprite_inspector(
             ui.ctx(), sprite_id, true
         );
         
-        render_component_header(ui, "Sprite Renderer", "🎨", false);
+        render_component_header_with_menu(ui, "Sprite Renderer", "🎨", false, ComponentKind::Sprite);
         
         if is_open.is_open() {
+            // Named frames come from the entity's sheet or one shared by texture
+            let sheet = world.sprite_sheet_for(entity).cloned();
             if let Some(sprite) = world.sprites.get_mut(&entity) {
                 ui.indent("sprite_indent", |ui| {
                     egui::Grid::new("sprite_grid")
@@ -51,10 +56,16 @@ pub fn render_sprite_inspector(
                                 });
                             });
                             ui.end_row();
+
+                            if let Some(sheet) = &sheet {
+                                ui.label("Frame");
+                                render_frame_picker(ui, sprite, sheet, texture_manager);
+                                ui.end_row();
+                            }
                             
                             // Color tint
                             ui.label("Color");
-                            ui.color_edit_button_rgba_unmultiplied(&mut sprite.color);
+                            color_edit(ui, &mut sprite.color);
                             ui.end_row();
                             
                             // Flip options (Unity-style)
@@ -87,8 +98,7 @@ pub fn render_sprite_inspector(
 
                             // Unity-style Sorting
                             ui.label("Sorting Layer");
-                            ui.text_edit_singleline(&mut sprite.sorting_layer)
-                                .on_hover_text("Name of the sorting layer (e.g. Default, Background)");
+                            sorting_layer_combo(ui, "sprite_sorting_layer", &mut sprite.sorting_layer, sorting_layers);
                             ui.end_row();
 
                             ui.label("Order in Layer");
@@ -101,6 +111,14 @@ pub fn render_sprite_inspector(
                                 .on_hover_text("Bitmask for Camera culling (Default: 1)");
                             ui.end_row();
                         });
+
+                    if let Some(name) = &sprite.frame_name {
+                        if sheet.as_ref().and_then(|sheet| sheet.get_frame_by_name(name)).is_none() {
+                            ui.label(egui::RichText::new(format!("⚠ No sprite sheet has frame '{}', drawing Sprite Rect", name))
+                                .small()
+                                .color(egui::Color32::from_rgb(255, 165, 0)));
+                        }
+                    }
                     
                     ui.add_space(5.0);
                     
@@ -229,3 +247,37 @@ pub fn render_sprite_inspector(
         let _ = world.remove_component(entity, ComponentType::SpriteSheet);
     }
 }
+
+/// Frame dropdown with thumbnails; picking a frame references it by name so re-packing
+/// the sheet keeps the sprite valid
+fn render_frame_picker(
+    ui: &mut egui::Ui,
+    sprite: &mut ecs::Sprite,
+    sheet: &ecs::SpriteSheet,
+    texture_manager: &mut engine::texture_manager::TextureManager,
+) {
+    let selected = sprite.frame_name.clone().unwrap_or_else(|| "None (Sprite Rect)".to_string());
+    egui::ComboBox::from_id_salt("sprite_frame")
+        .selected_text(selected)
+        .width(150.0)
+        .height(300.0)
+        .show_ui(ui, |ui| {
+            if ui.selectable_label(sprite.frame_name.is_none(), "None (Sprite Rect)").clicked() {
+                sprite.frame_name = None;
+            }
+            for (index, frame) in sheet.frames.iter().enumerate() {
+                let Some(name) = &frame.name else { continue };
+                ui.horizontal(|ui| {
+                    let (rect, _) = ui.allocate_exact_size(egui::vec2(32.0, 32.0), egui::Sense::hover());
+                    crate::ui::animation_window::paint_sheet_frame(ui, rect, sheet, index, texture_manager);
+                    if ui.selectable_label(sprite.frame_name.as_ref() == Some(name), name).clicked() {
+                        sprite.frame_name = Some(name.clone());
+                        sprite.sprite_rect = Some(frame.rect());
+                        sprite.rect_rotated = frame.rotated;
+                        sprite.width = frame.width as f32;
+                        sprite.height = frame.height as f32;
+                    }
+                });
+            }
+        });
+}
@@ -0,0 +1,90 @@
+use ecs::{World, Entity, ComponentType, ComponentManager, SpriteShader};
+use egui;
+use super::utils::{render_component_header, color_edit};
+
+pub fn render_sprite_material_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
+    // Sprite Material Component (shader variant + parameters)
+    let has_material = world.has_component(entity, ComponentType::SpriteMaterial);
+    let mut remove_material = false;
+
+    if has_material {
+        let material_id = ui.make_persistent_id("sprite_material_component");
+        let is_open = egui::collapsing_header::CollapsingState::load_with_default_open(
+            ui.ctx(), material_id, true
+        );
+
+        render_component_header(ui, "Sprite Material", "✨", false);
+
+        if is_open.is_open() {
+            if let Some(material) = world.sprite_materials.get_mut(&entity) {
+                ui.indent("sprite_material_indent", |ui| {
+                    let known = SpriteShader::from_name(&material.shader).is_some();
+                    let variant = material.variant();
+
+                    egui::Grid::new("sprite_material_grid")
+                        .num_columns(2)
+                        .spacing([10.0, 8.0])
+                        .show(ui, |ui| {
+                            ui.label("Shader");
+                            let selected = if known {
+                                egui::RichText::new(variant.name())
+                            } else {
+                                egui::RichText::new(&material.shader).color(egui::Color32::from_rgb(255, 165, 0))
+                            };
+                            egui::ComboBox::from_id_salt("sprite_material_shader")
+                                .selected_text(selected)
+                                .show_ui(ui, |ui| {
+                                    for shader in SpriteShader::ALL {
+                                        if ui.selectable_label(known && variant == shader, shader.name()).clicked() {
+                                            material.shader = shader.name().to_string();
+                                        }
+                                    }
+                                });
+                            ui.end_row();
+
+                            // Parameters of the current variant (defaults until edited)
+                            let variant = material.variant();
+                            for (name, _) in variant.float_params() {
+                                ui.label(*name);
+                                let mut value = material.float(name);
+                                if ui.add(egui::DragValue::new(&mut value).speed(0.01)).changed() {
+                                    material.set_float(name, value);
+                                }
+                                ui.end_row();
+                            }
+                            if let Some((name, _)) = variant.color_param() {
+                                ui.label(name);
+                                let mut color = material.color(name);
+                                if color_edit(ui, &mut color).changed() {
+                                    material.set_color(name, color);
+                                }
+                                ui.end_row();
+                            }
+                        });
+
+                    if !known {
+                        ui.label(egui::RichText::new(
+                            "⚠ Unknown shader, drawn with the default sprite shader"
+                        ).small().color(egui::Color32::from_rgb(255, 165, 0)));
+                    }
+
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        if ui.button("⟲ Reset Parameters").clicked() {
+                            material.floats.clear();
+                            material.colors.clear();
+                        }
+                        if ui.button("❌ Remove Component").clicked() {
+                            remove_material = true;
+                        }
+                    });
+                });
+            }
+            ui.add_space(10.0);
+        }
+    }
+
+    if remove_material {
+        let _ = world.remove_component(entity, ComponentType::SpriteMaterial);
+    }
+}
@@ -1,11 +1,12 @@
 use ecs::{World, Entity};
 use egui;
 use arboard::Clipboard;
-use super::utils::render_component_header;
+use super::utils::render_component_header_with_menu;
+use crate::systems::undo::ComponentKind;
 
 pub fn render_transform_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
     if let Some(transform) = world.transforms.get_mut(&entity) {
-        render_component_header(ui, "Transform", "⚙️", true);
+        render_component_header_with_menu(ui, "Transform", "⚙️", true, ComponentKind::Transform);
 
         // Transform fields - Unity style (X Y Z in same row)
         egui::Grid::new("transform_grid")
@@ -0,0 +1,147 @@
+use ecs::{World, Entity, ComponentType, ComponentManager, EntityTag, TriggerZoneShape};
+use egui;
+use super::utils::render_component_header_with_menu;
+use crate::systems::undo::ComponentKind;
+
+pub fn render_trigger_zone_inspector(ui: &mut egui::Ui, world: &mut World, entity: Entity) {
+    // Trigger Zone Component (enter/exit area evaluated after each physics step)
+    let has_zone = world.has_component(entity, ComponentType::TriggerZone);
+    let mut remove_zone = false;
+
+    if has_zone {
+        let zone_id = ui.make_persistent_id("trigger_zone_component");
+        let is_open = egui::collapsing_header::CollapsingState::load_with_default_open(
+            ui.ctx(), zone_id, true
+        );
+
+        render_component_header_with_menu(ui, "Trigger Zone", "⚡", false, ComponentKind::TriggerZone);
+
+        if is_open.is_open() {
+            if let Some(zone) = world.trigger_zones.get_mut(&entity) {
+                ui.indent("trigger_zone_indent", |ui| {
+                    egui::Grid::new("trigger_zone_grid")
+                        .num_columns(2)
+                        .spacing([10.0, 8.0])
+                        .show(ui, |ui| {
+                            ui.label("Shape");
+                            let is_circle = matches!(zone.shape, TriggerZoneShape::Circle { .. });
+                            egui::ComboBox::from_id_salt("trigger_zone_shape")
+                                .selected_text(if is_circle { "Circle" } else { "Box" })
+                                .show_ui(ui, |ui| {
+                                    if ui.selectable_label(!is_circle, "Box").clicked() && is_circle {
+                                        if let TriggerZoneShape::Circle { radius } = zone.shape {
+                                            zone.shape = TriggerZoneShape::Box { width: radius * 2.0, height: radius * 2.0 };
+                                        }
+                                    }
+                                    if ui.selectable_label(is_circle, "Circle").clicked() && !is_circle {
+                                        if let TriggerZoneShape::Box { width, height } = zone.shape {
+                                            zone.shape = TriggerZoneShape::Circle { radius: width.max(height) / 2.0 };
+                                        }
+                                    }
+                                });
+                            ui.end_row();
+
+                            match &mut zone.shape {
+                                TriggerZoneShape::Box { width, height } => {
+                                    ui.label("Size");
+                                    ui.horizontal(|ui| {
+                                        ui.label("W");
+                                        ui.add(egui::DragValue::new(width).speed(0.05).range(0.01..=f32::MAX));
+                                        ui.label("H");
+                                        ui.add(egui::DragValue::new(height).speed(0.05).range(0.01..=f32::MAX));
+                                    });
+                                }
+                                TriggerZoneShape::Circle { radius } => {
+                                    ui.label("Radius");
+                                    ui.add(egui::DragValue::new(radius).speed(0.05).range(0.01..=f32::MAX));
+                                }
+                            }
+                            ui.end_row();
+
+                            ui.label("Offset");
+                            ui.horizontal(|ui| {
+                                ui.label("X");
+                                ui.add(egui::DragValue::new(&mut zone.offset[0]).speed(0.05));
+                                ui.label("Y");
+                                ui.add(egui::DragValue::new(&mut zone.offset[1]).speed(0.05));
+                            });
+                            ui.end_row();
+
+                            ui.label("Tag");
+                            let tag_label = |tag: &Option<EntityTag>| match tag {
+                                Some(EntityTag::Player) => "Player",
+                                Some(EntityTag::Item) => "Item",
+                                None => "Any",
+                            };
+                            egui::ComboBox::from_id_salt("trigger_zone_tag")
+                                .selected_text(tag_label(&zone.tag))
+                                .show_ui(ui, |ui| {
+                                    for tag in [None, Some(EntityTag::Player), Some(EntityTag::Item)] {
+                                        let label = tag_label(&tag);
+                                        ui.selectable_value(&mut zone.tag, tag, label);
+                                    }
+                                });
+                            ui.end_row();
+
+                            ui.label("Layer Mask");
+                            ui.horizontal(|ui| {
+                                ui.add(egui::DragValue::new(&mut zone.layer_mask).speed(1).hexadecimal(8, false, true))
+                                    .on_hover_text("Bitmask of the physics layers that count (FFFFFFFF: every layer)");
+                                if ui.small_button("All").clicked() {
+                                    zone.layer_mask = u32::MAX;
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label("One Shot");
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut zone.one_shot, "")
+                                    .on_hover_text("Fire on the first enter only, then turn off");
+                                if zone.one_shot && zone.fired {
+                                    ui.label(egui::RichText::new("✔ Fired").color(egui::Color32::from_rgb(100, 200, 100)));
+                                    if ui.small_button("Reset").on_hover_text("Arm the zone again").clicked() {
+                                        zone.fired = false;
+                                    }
+                                }
+                            });
+                            ui.end_row();
+
+                            ui.label("On Enter");
+                            optional_name_edit(ui, &mut zone.on_enter, "function(entity) in this entity's script");
+                            ui.end_row();
+
+                            ui.label("On Exit");
+                            optional_name_edit(ui, &mut zone.on_exit, "function(entity) in this entity's script");
+                            ui.end_row();
+
+                            ui.label("Event");
+                            optional_name_edit(ui, &mut zone.event, "Global event; Events.on(name, fn) gets { zone, entity, phase }");
+                            ui.end_row();
+                        });
+
+                    ui.add_space(5.0);
+                    if ui.button("❌ Remove Component").clicked() {
+                        remove_zone = true;
+                    }
+                });
+            }
+            ui.add_space(10.0);
+        }
+    }
+
+    if remove_zone {
+        let _ = world.remove_component(entity, ComponentType::TriggerZone);
+    }
+}
+
+/// Text field for an optional name (empty means None)
+fn optional_name_edit(ui: &mut egui::Ui, value: &mut Option<String>, hint: &str) {
+    let mut text = value.clone().unwrap_or_default();
+    if ui.add(egui::TextEdit::singleline(&mut text).hint_text("none").desired_width(140.0))
+        .on_hover_text(hint)
+        .changed()
+    {
+        let text = text.trim();
+        *value = (!text.is_empty()).then(|| text.to_string());
+    }
+}
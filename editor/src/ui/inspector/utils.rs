@@ -1,23 +1,61 @@
 use ecs::{World, Entity, ScriptParameter};
+use ui::animation::{AnimationCurve, EasingFunction};
 use egui;
 use std::collections::HashMap;
+use crate::systems::component_clipboard::{self, ComponentMenuAction, PasteMode};
+use crate::systems::undo::ComponentKind;
 
 /// Parse hex color string to egui Color32
 pub fn parse_hex_color(hex: &str) -> Result<egui::Color32, String> {
-    let hex = hex.trim_start_matches('#');
-    if hex.len() != 6 {
-        return Err("Invalid hex color format".to_string());
+    let [r, g, b, a] = ecs::Color::from_hex(hex)?.to_array().map(|c| (c * 255.0).round() as u8);
+    Ok(egui::Color32::from_rgba_unmultiplied(r, g, b, a))
+}
+
+/// Color picker button (unmultiplied RGBA) for a shared Color
+pub fn color_edit(ui: &mut egui::Ui, color: &mut ecs::Color) -> egui::Response {
+    let mut rgba = color.to_array();
+    let response = ui.color_edit_button_rgba_unmultiplied(&mut rgba);
+    if response.changed() {
+        *color = rgba.into();
     }
-    
-    let r = u8::from_str_radix(&hex[0..2], 16).map_err(|_| "Invalid red component")?;
-    let g = u8::from_str_radix(&hex[2..4], 16).map_err(|_| "Invalid green component")?;
-    let b = u8::from_str_radix(&hex[4..6], 16).map_err(|_| "Invalid blue component")?;
-    
-    Ok(egui::Color32::from_rgb(r, g, b))
+    response
+}
+
+/// Sorting layer dropdown fed from the project's list; a name missing from it is shown
+/// in orange (it draws on Default) until another layer is picked
+pub fn sorting_layer_combo(ui: &mut egui::Ui, id: &str, layer: &mut String, layers: &ecs::sorting::SortingLayers) -> egui::Response {
+    let known = layers.names().contains(layer);
+    let selected_text = if known {
+        egui::RichText::new(layer.as_str())
+    } else {
+        egui::RichText::new(format!("{} (missing)", layer)).color(egui::Color32::from_rgb(255, 165, 0))
+    };
+    let mut response = egui::ComboBox::from_id_salt(id)
+        .selected_text(selected_text)
+        .width(150.0)
+        .show_ui(ui, |ui| {
+            for name in layers.names() {
+                ui.selectable_value(layer, name.clone(), name);
+            }
+        })
+        .response;
+    if !known {
+        response = response.on_hover_text("Not in Project Settings > Rendering > Sorting Layers; drawn on Default");
+    }
+    response
 }
 
 /// Render Unity-style component header
 pub fn render_component_header(ui: &mut egui::Ui, name: &str, icon: &str, always_open: bool) {
+    component_header(ui, name, icon, always_open, None);
+}
+
+/// Component header whose ⋮ menu copies and pastes the component
+pub fn render_component_header_with_menu(ui: &mut egui::Ui, name: &str, icon: &str, always_open: bool, component: ComponentKind) {
+    component_header(ui, name, icon, always_open, Some(component));
+}
+
+fn component_header(ui: &mut egui::Ui, name: &str, icon: &str, always_open: bool, component: Option<ComponentKind>) {
     egui::Frame::none()
         .fill(egui::Color32::from_rgb(56, 56, 56))
         .inner_margin(egui::Margin::same(6))
@@ -29,9 +67,14 @@ pub fn render_component_header(ui: &mut egui::Ui, name: &str, icon: &str, always
                 ui.label(icon);
                 ui.strong(name);
 
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if ui.small_button("⋮").on_hover_text("Component Options").clicked() {
-                        // Component menu
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| match component {
+                    Some(component) => {
+                        ui.menu_button("⋮", |ui| component_menu(ui, component))
+                            .response
+                            .on_hover_text("Component Options");
+                    }
+                    None => {
+                        ui.small_button("⋮").on_hover_text("Component Options");
                     }
                 });
             });
@@ -39,6 +82,36 @@ pub fn render_component_header(ui: &mut egui::Ui, name: &str, icon: &str, always
     ui.add_space(8.0);
 }
 
+/// Copy / Paste entries of a component header's ⋮ menu; the picked one runs after the
+/// inspector has drawn, on every selected entity
+fn component_menu(ui: &mut egui::Ui, component: ComponentKind) {
+    let mut action = None;
+    if ui.button("📋 Copy Component").clicked() {
+        action = Some(ComponentMenuAction::Copy(component));
+    }
+    ui.separator();
+    if component == ComponentKind::Transform {
+        // Every entity has a Transform: paste its local values or move to where the copy was
+        if ui.button("📥 Paste Values Only").on_hover_text("Local position, rotation and scale").clicked() {
+            action = Some(ComponentMenuAction::Paste(component, PasteMode::Values));
+        }
+        if ui.button("🌐 Paste World Position").on_hover_text("Move to the copied world position").clicked() {
+            action = Some(ComponentMenuAction::Paste(component, PasteMode::WorldPosition));
+        }
+    } else {
+        if ui.button("📥 Paste Component Values").clicked() {
+            action = Some(ComponentMenuAction::Paste(component, PasteMode::Values));
+        }
+        if ui.button("➕ Paste Component As New").on_hover_text("Add to selected entities without one").clicked() {
+            action = Some(ComponentMenuAction::Paste(component, PasteMode::AsNew));
+        }
+    }
+    if let Some(action) = action {
+        component_clipboard::request(ui.ctx(), action);
+        ui.close();
+    }
+}
+
 /// Format entity debug information for copying to clipboard
 pub fn format_entity_debug_info(
     world: &World,
@@ -214,6 +287,10 @@ pub fn parse_lua_script_parameters(script_path: &std::path::Path) -> HashMap<Str
                     // Boolean value
                     let bool_value = value_part == "true";
                     Some(ScriptParameter::Bool(bool_value))
+                } else if let Some(curve) = parse_curve_declaration(value_part) {
+                    // Easing curve, edited in the curve editor
+                    // Pattern: local bounce = Curve.from_easing("EaseOutBack")
+                    Some(ScriptParameter::Curve(curve))
                 } else if value_part.trim_end_matches(',') == "nil" {
                     // Entity reference (Unity-style GameObject)
                     // Pattern: local playerTarget = nil
@@ -239,3 +316,13 @@ pub fn parse_lua_script_parameters(script_path: &std::path::Path) -> HashMap<Str
 
     parameters
 }
+
+/// `Curve.from_easing("EaseOutBack")` (that easing's shape) or `Curve.new(...)` (linear)
+fn parse_curve_declaration(value: &str) -> Option<AnimationCurve> {
+    let value = value.trim_end_matches(',');
+    if value.starts_with("Curve.new(") {
+        return Some(AnimationCurve::linear());
+    }
+    let name = value.strip_prefix("Curve.from_easing(")?.strip_suffix(')')?.trim().trim_matches(['"', '\'']);
+    EasingFunction::from_name(name).map(|easing| easing.to_curve())
+}
@@ -41,9 +41,6 @@ impl LauncherWindow {
                                 editor_state.console.info("👋 Welcome to Rust 2D Game Engine!");
                                 editor_state.console.debug("Debug logging enabled".to_string());
 
-                                // Load editor layout
-                                editor_state.load_editor_layout();
-
                                 // Try to load last opened scene first, then startup scene
                                 Self::load_initial_scene(editor_state, launcher_state, &folder, asset_loader);
                             }
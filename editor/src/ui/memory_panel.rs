@@ -0,0 +1,263 @@
+//! Memory Window
+//!
+//! Live bytes and allocation counts per profiler subsystem (with `enable_profiling`),
+//! GPU texture memory by category, per-entity Lua state memory, and the leak report
+//! from the last play session: scopes and Lua states whose live bytes grew between
+//! play-mode entry and exit.
+
+use crate::console::Console;
+use ecs::Entity;
+use egui::{Color32, RichText};
+use profiler::memory::{self, ScopeGrowth, ScopeMemory};
+use script::ScriptEngine;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap};
+
+/// Smaller growth is treated as noise in the leak report
+const LEAK_THRESHOLD_BYTES: i64 = 4 * 1024;
+/// Leak report lines written to the console
+const MAX_CONSOLE_LINES: usize = 10;
+
+fn open_request_id() -> egui::Id {
+    egui::Id::new("memory_panel_open_request")
+}
+
+/// Ask the editor to show the Memory window (View menu)
+pub fn open(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(open_request_id(), true));
+}
+
+/// "1.5 MB"-style size
+pub fn format_bytes(bytes: i64) -> String {
+    let magnitude = bytes.unsigned_abs() as f64;
+    let sign = if bytes < 0 { "-" } else { "" };
+    if magnitude >= 1024.0 * 1024.0 {
+        format!("{}{:.1} MB", sign, magnitude / (1024.0 * 1024.0))
+    } else if magnitude >= 1024.0 {
+        format!("{}{:.1} KB", sign, magnitude / 1024.0)
+    } else {
+        format!("{}{} B", sign, magnitude)
+    }
+}
+
+/// Lua state memory as scope-like entries, so the same growth check applies
+fn lua_scopes(script_engine: &ScriptEngine, entity_names: &HashMap<Entity, String>) -> Vec<ScopeMemory> {
+    script_engine
+        .lua_memory_usage()
+        .into_iter()
+        .map(|(entity, bytes)| ScopeMemory {
+            name: format!(
+                "lua.{}",
+                entity_names.get(&entity).cloned().unwrap_or_else(|| format!("Entity {}", entity))
+            ),
+            live_bytes: bytes as i64,
+            live_allocations: 0,
+            total_allocations: 0,
+        })
+        .collect()
+}
+
+/// Memory window state and the play-session baselines for the leak report
+#[derive(Default)]
+pub struct MemoryPanel {
+    pub open: bool,
+    /// Scope snapshot taken when play mode started
+    entry_scopes: Option<Vec<ScopeMemory>>,
+    /// Lua state sizes once scripts were loaded and started
+    entry_lua: Option<Vec<ScopeMemory>>,
+    /// Lua growth measured before the play session's states were dropped
+    exit_lua_growth: Vec<ScopeGrowth>,
+    /// Result of the last play session
+    pub last_report: Option<Vec<ScopeGrowth>>,
+}
+
+impl MemoryPanel {
+    /// Play mode is starting: remember live bytes per scope
+    pub fn begin_play(&mut self) {
+        self.entry_scopes = Some(memory::snapshot());
+        self.entry_lua = None;
+        self.exit_lua_growth.clear();
+    }
+
+    /// Scripts are loaded and started: remember each Lua state's size
+    pub fn scripts_started(&mut self, script_engine: &ScriptEngine, entity_names: &HashMap<Entity, String>) {
+        self.entry_lua = Some(lua_scopes(script_engine, entity_names));
+    }
+
+    /// Play mode is stopping (Lua states still alive): measure Lua growth
+    pub fn stopping_play(&mut self, script_engine: &ScriptEngine, entity_names: &HashMap<Entity, String>) {
+        if let Some(entry) = self.entry_lua.take() {
+            self.exit_lua_growth = memory::growth(&entry, &lua_scopes(script_engine, entity_names));
+        }
+    }
+
+    /// Play mode has ended and the scene is restored: compare with the entry snapshot
+    /// and log anything that grew
+    pub fn end_play(&mut self, console: &mut Console) {
+        let Some(entry) = self.entry_scopes.take() else {
+            return;
+        };
+        let mut report = memory::growth(&entry, &memory::snapshot());
+        report.append(&mut self.exit_lua_growth);
+        report.retain(|g| g.grown_bytes() >= LEAK_THRESHOLD_BYTES);
+        report.sort_by_key(|scope| Reverse(scope.grown_bytes()));
+
+        if !report.is_empty() {
+            console.warning(format!("🧠 Memory grew during play mode in {} scope(s):", report.len()));
+            for growth in report.iter().take(MAX_CONSOLE_LINES) {
+                console.warning(format!(
+                    "   {}: {} -> {} (+{})",
+                    growth.name,
+                    format_bytes(growth.before_bytes),
+                    format_bytes(growth.after_bytes),
+                    format_bytes(growth.grown_bytes())
+                ));
+            }
+        }
+        self.last_report = Some(report);
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, script_engine: &ScriptEngine, entity_names: &HashMap<Entity, String>) {
+        if ctx.data_mut(|d| d.remove_temp::<bool>(open_request_id())).unwrap_or(false) {
+            self.open = true;
+        }
+        if !self.open {
+            return;
+        }
+
+        let mut open = self.open;
+        egui::Window::new("🧠 Memory")
+            .open(&mut open)
+            .default_size([380.0, 460.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    self.render_subsystems(ui);
+                    ui.separator();
+                    render_textures(ui);
+                    ui.separator();
+                    render_lua_states(ui, &lua_scopes(script_engine, entity_names));
+                    ui.separator();
+                    self.render_report(ui);
+                });
+            });
+        self.open = open;
+        // Live numbers: keep repainting while visible
+        ctx.request_repaint();
+    }
+
+    fn render_subsystems(&self, ui: &mut egui::Ui) {
+        ui.label(RichText::new("Subsystems").strong());
+        if !memory::tracking_enabled() {
+            ui.label(
+                RichText::new("Allocation tracking is off. Build the editor with `--features enable_profiling`.")
+                    .small()
+                    .color(Color32::GRAY),
+            );
+            return;
+        }
+
+        let mut subsystems: BTreeMap<String, (i64, i64, Vec<ScopeMemory>)> = BTreeMap::new();
+        for scope in memory::snapshot() {
+            let entry = subsystems.entry(scope.subsystem().to_string()).or_default();
+            entry.0 += scope.live_bytes;
+            entry.1 += scope.live_allocations;
+            entry.2.push(scope);
+        }
+        let mut subsystems: Vec<_> = subsystems.into_iter().collect();
+        subsystems.sort_by_key(|(_, (bytes, _, _))| Reverse(*bytes));
+
+        for (subsystem, (bytes, allocations, mut scopes)) in subsystems {
+            let header = format!("{}  {}  ({} allocs)", subsystem, format_bytes(bytes), allocations);
+            egui::CollapsingHeader::new(header)
+                .id_salt(("memory_subsystem", &subsystem))
+                .show(ui, |ui| {
+                    scopes.sort_by_key(|scope| Reverse(scope.live_bytes));
+                    egui::Grid::new(("memory_scopes", &subsystem)).num_columns(3).striped(true).show(ui, |ui| {
+                        for scope in scopes {
+                            ui.label(&scope.name);
+                            ui.label(format_bytes(scope.live_bytes));
+                            ui.label(format!("{} / {} allocs", scope.live_allocations, scope.total_allocations));
+                            ui.end_row();
+                        }
+                    });
+                });
+        }
+    }
+
+    fn render_report(&self, ui: &mut egui::Ui) {
+        ui.label(RichText::new("Last play session").strong());
+        match &self.last_report {
+            None => {
+                ui.label(RichText::new("Enter and exit play mode to check for leaks.").small().color(Color32::GRAY));
+            }
+            Some(report) if report.is_empty() => {
+                ui.label(RichText::new("✅ No scope grew between entry and exit").color(Color32::from_rgb(120, 200, 120)));
+            }
+            Some(report) => {
+                egui::Grid::new("memory_leak_report").num_columns(2).striped(true).show(ui, |ui| {
+                    for growth in report {
+                        ui.label(RichText::new(&growth.name).color(Color32::from_rgb(255, 180, 80)));
+                        ui.label(format!("+{}", format_bytes(growth.grown_bytes())));
+                        ui.end_row();
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// GPU texture memory by category, and how full the streaming budget is
+fn render_textures(ui: &mut egui::Ui) {
+    let Some(textures) = profiler::textures::texture_memory() else {
+        ui.label(RichText::new("Textures").strong());
+        ui.label(RichText::new("No textures loaded yet.").small().color(Color32::GRAY));
+        return;
+    };
+    ui.label(RichText::new(format!("Textures  {}", format_bytes(textures.total_bytes() as i64))).strong());
+    egui::Grid::new("memory_textures").num_columns(3).striped(true).show(ui, |ui| {
+        for category in &textures.categories {
+            ui.label(&category.category);
+            ui.label(format_bytes(category.bytes as i64));
+            ui.label(format!("{} textures", category.textures));
+            ui.end_row();
+        }
+    });
+
+    if let Some(used) = textures.budget_used() {
+        let color = if used > 1.0 || textures.held_back > 0 { Color32::from_rgb(255, 180, 80) } else { Color32::GRAY };
+        ui.label(
+            RichText::new(format!("Budget: {} of {} ({:.0}%)", format_bytes(textures.total_bytes() as i64), format_bytes(textures.budget_bytes as i64), used * 100.0))
+                .small()
+                .color(color),
+        );
+    }
+    if textures.streaming > 0 {
+        ui.label(RichText::new(format!("{} streaming in", textures.streaming)).small().color(Color32::GRAY));
+    }
+    if textures.held_back > 0 {
+        ui.label(
+            RichText::new(format!("⚠ {} streamed textures held at preview size (budget reached)", textures.held_back))
+                .small()
+                .color(Color32::from_rgb(255, 180, 80)),
+        );
+    }
+}
+
+fn render_lua_states(ui: &mut egui::Ui, lua: &[ScopeMemory]) {
+    let total: i64 = lua.iter().map(|s| s.live_bytes).sum();
+    ui.label(RichText::new(format!("Lua states  {}", format_bytes(total))).strong());
+    if lua.is_empty() {
+        ui.label(RichText::new("No scripts loaded (enter play mode).").small().color(Color32::GRAY));
+        return;
+    }
+    let mut lua: Vec<&ScopeMemory> = lua.iter().collect();
+    lua.sort_by_key(|scope| Reverse(scope.live_bytes));
+    egui::Grid::new("memory_lua_states").num_columns(2).striped(true).show(ui, |ui| {
+        for state in lua {
+            ui.label(state.name.trim_start_matches("lua."));
+            ui.label(format_bytes(state.live_bytes));
+            ui.end_row();
+        }
+    });
+}
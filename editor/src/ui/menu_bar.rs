@@ -3,7 +3,7 @@ use egui;
 use std::collections::HashMap;
 use ecs::Entity;
 
-/// Render the top menu bar with File, Edit, View, GameObject menus
+/// Render the top menu bar with File, Edit, View, GameObject menus (and Window when docking)
 pub fn render_menu_bar(
     ui: &mut egui::Ui,
     world: &mut World,
@@ -15,17 +15,24 @@ pub fn render_menu_bar(
     load_file_request: &mut Option<std::path::PathBuf>,
     play_request: &mut bool,
     stop_request: &mut bool,
+    pause_request: &mut bool,
+    step_request: &mut bool,
+    replay: &mut crate::systems::replay::EditorReplay,
     show_project_settings: &mut bool,
     show_colliders: &mut bool,
     show_velocities: &mut bool,
     show_debug_lines: &mut bool,
+    show_navigation: &mut bool,
+    debug_draw: Option<&mut crate::debug_draw::DebugDrawManager>,
     project_path: &Option<std::path::PathBuf>,
     current_scene_path: &Option<std::path::PathBuf>,
     is_playing: bool,
+    is_paused: bool,
     show_exit_dialog: &mut bool,
     show_export_dialog: &mut bool,
     layout_request: &mut Option<String>,
     current_layout_name: &str,
+    dock_state: Option<&mut egui_dock::DockState<super::EditorTab>>,
     get_scene_files_fn: impl Fn(&std::path::Path) -> Vec<String>,
 ) {
     egui::menu::bar(ui, |ui| {
@@ -43,6 +50,13 @@ pub fn render_menu_bar(
                 *save_as_request = true;
                 ui.close_menu();
             }
+            if ui.add_enabled(current_scene_path.is_some() && !is_playing, egui::Button::new("↺ Revert Scene"))
+                .on_hover_text("Reload the scene from its saved file, showing what would change")
+                .clicked()
+            {
+                crate::ui::dialogs::scene_reload_dialog::request_revert(ui.ctx());
+                ui.close();
+            }
             ui.separator();
             if ui.button("Load Scene...").clicked() {
                 *load_request = true;
@@ -64,6 +78,19 @@ pub fn render_menu_bar(
                 *show_project_settings = true;
                 ui.close_menu();
             }
+            if ui.button("🔗 Validate Assets...").on_hover_text("Find references to missing asset files").clicked() {
+                crate::ui::asset_validation::open(ui.ctx());
+                ui.close();
+            }
+            ui.separator();
+            if ui.button("🔎 Command Palette...").on_hover_text("Ctrl+Shift+P").clicked() {
+                crate::ui::command_palette::open(ui.ctx());
+                ui.close();
+            }
+            if ui.button("⌨ Keyboard Shortcuts...").clicked() {
+                crate::ui::shortcut_preferences::open(ui.ctx());
+                ui.close();
+            }
         });
         ui.menu_button("View", |ui| {
             ui.label("🔧 Gizmos");
@@ -71,6 +98,90 @@ pub fn render_menu_bar(
             ui.checkbox(show_colliders, "Show Colliders");
             ui.checkbox(show_velocities, "Show Velocities");
             ui.checkbox(show_debug_lines, "Show Debug Lines");
+            ui.checkbox(show_navigation, "Show Navigation Grid")
+                .on_hover_text("Walkable grid used by Nav.find_path, and the last path found");
+            if let Some(debug_draw) = debug_draw {
+                ui.add_enabled_ui(*show_debug_lines, |ui| {
+                    ui.checkbox(&mut debug_draw.show_script, "Script Draws")
+                        .on_hover_text("debug_draw_* shapes and text from Lua");
+                    ui.checkbox(&mut debug_draw.show_physics, "Physics Draws");
+                    ui.add_enabled_ui(debug_draw.show_physics, |ui| {
+                        ui.indent("physics_draws", |ui| {
+                            ui.checkbox(&mut debug_draw.physics_contacts, "Contact Points");
+                            ui.add_enabled_ui(debug_draw.physics_contacts, |ui| {
+                                ui.horizontal(|ui| {
+                                    use crate::debug_draw::MAX_CONTACT_FRAMES;
+                                    ui.label("Persist");
+                                    ui.add(egui::Slider::new(&mut debug_draw.physics_contact_frames, 1..=MAX_CONTACT_FRAMES).suffix(" frames"))
+                                        .on_hover_text("How long contact points stay up after the step that found them");
+                                });
+                            });
+                            ui.checkbox(&mut debug_draw.physics_broadphase, "Broad-phase Bounds")
+                                .on_hover_text("AABB of every collider, as tested by the broad phase");
+                            #[cfg(feature = "rapier")]
+                            {
+                                ui.checkbox(&mut debug_draw.physics_colliders, "Collider Shapes")
+                                    .on_hover_text("Collider outlines as Rapier simulates them");
+                                ui.checkbox(&mut debug_draw.physics_joints, "Joints")
+                                    .on_hover_text("Joint anchors and how far apart they are");
+                            }
+                        });
+                    });
+                });
+                ui.checkbox(&mut debug_draw.show_camera_icons, "Camera Icons");
+                ui.checkbox(&mut debug_draw.show_marker_icons, "Marker Icons")
+                    .on_hover_text("Icons of entities with an Editor Icon component");
+                ui.checkbox(&mut debug_draw.show_icon_labels, "Show Labels")
+                    .on_hover_text("Entity names under every icon (selected ones always show theirs)");
+                ui.checkbox(&mut debug_draw.show_trigger_zones, "Trigger Zones")
+                    .on_hover_text("Areas of Trigger Zone components");
+                ui.horizontal(|ui| {
+                    use crate::ui::scene_view::gizmo_metrics::{MAX_HANDLE_SCALE, MIN_HANDLE_SCALE};
+                    ui.label("Handle Size");
+                    let slider = egui::Slider::new(&mut debug_draw.gizmos.handle_scale, MIN_HANDLE_SCALE..=MAX_HANDLE_SCALE)
+                        .custom_formatter(|value, _| format!("{:.0}%", value * 100.0));
+                    if ui.add(slider).on_hover_text("Transform gizmo and component handles, and how close the pointer must be to grab them").changed() {
+                        if let Err(e) = debug_draw.gizmos.save() {
+                            log::warn!("Failed to save gizmo preferences: {}", e);
+                        }
+                    }
+                });
+            }
+            ui.separator();
+            let mut show_profiler = profiler::is_enabled();
+            if ui.checkbox(&mut show_profiler, "Profiler Overlay").on_hover_text("F3").changed() {
+                profiler::set_enabled(show_profiler);
+            }
+            ui.separator();
+            if ui.button("📷 Capture Screenshot").on_hover_text("Save the Game view as a PNG under screenshots/").clicked() {
+                crate::game_view_renderer::request_capture(ui.ctx(), crate::game_view_renderer::CaptureRequest::Screenshot);
+                ui.close();
+            }
+            if ui.button("🎞 Save GIF Clip").on_hover_text("F9 in play mode: the last few seconds of the Game view").clicked() {
+                crate::game_view_renderer::request_capture(ui.ctx(), crate::game_view_renderer::CaptureRequest::SaveGif);
+                ui.close();
+            }
+            ui.separator();
+            if ui.button("🎞 Animation").clicked() {
+                crate::ui::animation_window::open(ui.ctx());
+                ui.close();
+            }
+            if ui.button("🧱 Tile Palette").clicked() {
+                crate::ui::tile_palette::open(ui.ctx());
+                ui.close();
+            }
+            if ui.button("🧠 Memory").clicked() {
+                crate::ui::memory_panel::open(ui.ctx());
+                ui.close();
+            }
+            if ui.button("🌐 Globals").clicked() {
+                crate::ui::globals_panel::open(ui.ctx());
+                ui.close();
+            }
+            if ui.button("🐞 Script Debugger").clicked() {
+                crate::ui::script_debugger::open(ui.ctx());
+                ui.close();
+            }
         });
         ui.menu_button("GameObject", |ui| {
             if ui.button("Create Empty").clicked() {
@@ -86,6 +197,25 @@ pub fn render_menu_bar(
                 entity_names.insert(entity, format!("Item {}", entity));
             }
         });
+        if let Some(dock_state) = dock_state {
+            ui.menu_button("Window", |ui| {
+                for tab in super::dock_layout::WINDOW_PANELS {
+                    let mut open = super::dock_layout::is_tab_open(dock_state, &tab);
+                    if ui.checkbox(&mut open, tab.title()).changed() {
+                        if open {
+                            super::dock_layout::open_tab(dock_state, tab);
+                        } else {
+                            super::dock_layout::close_tab(dock_state, &tab);
+                        }
+                    }
+                }
+                ui.separator();
+                if ui.button("Reset Layout").on_hover_text("Back to the default panel arrangement").clicked() {
+                    *layout_request = Some("load:default".to_string());
+                    ui.close();
+                }
+            });
+        }
 
         ui.separator();
 
@@ -136,10 +266,41 @@ pub fn render_menu_bar(
             if ui.button("▶ Play").clicked() {
                 *play_request = true;
             }
+            if ui.button("⏺ Record").on_hover_text("Play and record input to a .replay file").clicked() {
+                replay.record_request = true;
+                *play_request = true;
+            }
+            if ui.button("🎞 Play Replay...").clicked() {
+                replay.open_request = true;
+            }
         } else {
             if ui.button("⏹ Stop").clicked() {
                 *stop_request = true;
             }
+            let pause_label = if is_paused { "▶ Resume" } else { "⏸ Pause" };
+            if ui.button(pause_label).on_hover_text("Ctrl+Shift+P").clicked() {
+                *pause_request = true;
+            }
+            if ui.add_enabled(is_paused, egui::Button::new("⏭ Step")).on_hover_text("F10").clicked() {
+                *step_request = true;
+            }
+
+            if replay.is_recording() {
+                ui.label(egui::RichText::new("● REC").color(egui::Color32::from_rgb(230, 70, 70)).strong());
+            }
+            if let Some((frame, total)) = replay.playback_progress() {
+                ui.label(format!("🎞 {} / {}", frame, total));
+                let mut speed = replay.speed();
+                egui::ComboBox::from_id_salt("replay_speed")
+                    .width(60.0)
+                    .selected_text(format!("{}x", speed))
+                    .show_ui(ui, |ui| {
+                        for option in crate::systems::replay::PLAYBACK_SPEEDS {
+                            ui.selectable_value(&mut speed, option, format!("{}x", option));
+                        }
+                    });
+                replay.set_speed(speed);
+            }
         }
 
         // Push layout dropdown to the right
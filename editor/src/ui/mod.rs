@@ -3,11 +3,21 @@ pub mod resource_manager;
 pub mod menu_bar;
 pub mod inspector;
 pub mod texture_inspector;
+pub mod ui_inspector;
 pub mod scene_view;
 pub mod project_settings;
+pub mod input_settings;
 pub mod dock_layout;
 pub mod camera_settings;
 pub mod sprite_picker;
+pub mod animation_window;
+pub mod tile_palette;
+pub mod memory_panel;
+pub mod globals_panel;
+pub mod asset_validation;
+pub mod script_debugger;
+pub mod command_palette;
+pub mod shortcut_preferences;
 pub mod map_inspector;
 pub mod map_view;
 pub mod create_prefab_dialog;
@@ -16,6 +26,9 @@ pub mod dialogs;
 pub mod launcher_window;
 pub mod game_window;
 pub mod panels;
+pub mod profiler_overlay;
+pub mod ui_events_panel;
+pub mod performance_hud;
 
 // Re-exports
 use ecs::{World, Entity, EntityTag};
@@ -25,7 +38,8 @@ use crate::{Console, SceneCamera, SceneGrid};
 pub use dock_layout::{
     EditorTab, TabContext, EditorTabViewer, 
     create_default_layout,
-    get_dock_style, save_default_layout, load_default_layout_name, get_layout_by_name, load_custom_layouts, save_custom_layout_state, load_custom_layout_state
+    get_dock_style, save_default_layout, load_default_layout_name, get_layout_by_name, load_custom_layouts, save_custom_layout_state, load_custom_layout_state,
+    save_session_layout, load_session_layout
 };
 use panels::{hierarchy, bottom_panel};
 use engine_core::assets::AssetLoader;
@@ -54,11 +68,15 @@ impl EditorUI {
         new_scene_request: &mut bool,
         play_request: &mut bool,
         stop_request: &mut bool,
+        pause_request: &mut bool,
+        step_request: &mut bool,
+        replay: &mut crate::systems::replay::EditorReplay,
         edit_script_request: &mut Option<String>,
         project_path: &Option<std::path::PathBuf>,
         current_scene_path: &Option<std::path::PathBuf>,
         scene_view_tab: &mut usize,
         is_playing: bool,
+        is_paused: bool,
         show_colliders: &mut bool,
         show_velocities: &mut bool,
         console: &mut Console,
@@ -78,6 +96,7 @@ impl EditorUI {
         open_sprite_editor_request: &mut Option<std::path::PathBuf>,
         sprite_picker_state: &mut sprite_picker::SpritePickerState,
         show_debug_lines: &mut bool,
+        show_navigation: &mut bool,
         scene_view_renderer: &mut crate::scene_view_renderer::SceneViewRenderer,
         egui_renderer: &mut egui_wgpu::Renderer,
         device: &wgpu::Device,
@@ -100,17 +119,24 @@ impl EditorUI {
                 load_file_request,
                 play_request,
                 stop_request,
+                pause_request,
+                step_request,
+                replay,
                 show_project_settings,
                 show_colliders,
                 show_velocities,
                 show_debug_lines,
+                show_navigation,
+                None,
                 project_path,
                 current_scene_path,
                 is_playing,
+                is_paused,
                 show_exit_dialog,
                 show_export_dialog,
                 &mut dummy_layout_request,
                 "legacy", 
+                None,
                 Self::get_scene_files,
             );
         });
@@ -190,6 +216,9 @@ impl EditorUI {
         dock_state: &mut egui_dock::DockState<EditorTab>,
         world: &mut World,
         selected_entity: &mut Option<Entity>,
+        selection: &mut crate::SelectionManager,
+        selection_action: &mut Option<crate::SelectionAction>,
+        hierarchy_search: &mut crate::tools::hierarchy_search::HierarchySearch,
         entity_names: &mut HashMap<Entity, String>,
         save_request: &mut bool,
         save_as_request: &mut bool,
@@ -198,17 +227,22 @@ impl EditorUI {
         new_scene_request: &mut bool,
         play_request: &mut bool,
         stop_request: &mut bool,
+        pause_request: &mut bool,
+        step_request: &mut bool,
+        replay: &mut crate::systems::replay::EditorReplay,
         edit_script_request: &mut Option<String>,
         project_path: &Option<std::path::PathBuf>,
         current_scene_path: &Option<std::path::PathBuf>,
         scene_view_tab: &mut usize,
         is_playing: bool,
+        is_paused: bool,
         show_colliders: &mut bool,
         show_velocities: &mut bool,
         console: &mut Console,
         _bottom_panel_tab: &mut usize,
         current_tool: &mut TransformTool,
         show_project_settings: &mut bool,
+        project_settings: &mut engine_core::project_settings::ProjectSettings,
         scene_camera: &mut SceneCamera,
         scene_grid: &SceneGrid,
         infinite_grid: &mut crate::grid::InfiniteGrid,
@@ -222,6 +256,8 @@ impl EditorUI {
         current_layout_name: &str,
         dragging_entity: &mut Option<Entity>,
         drag_axis: &mut Option<u8>,
+        handle_drag: &mut Option<scene_view::interaction::handles::HandleDrag>,
+        snap_settings: &mut crate::tools::snapping::SnapSettings,
         scene_view_mode: &mut scene_view::SceneViewMode,
         projection_mode: &mut scene_view::SceneProjectionMode,
         transform_space: &mut scene_view::TransformSpace,
@@ -236,8 +272,10 @@ impl EditorUI {
         sprite_editor_windows: &mut Vec<crate::SpriteEditorWindow>,
         sprite_picker_state: &mut sprite_picker::SpritePickerState,
         texture_inspector: &mut texture_inspector::TextureInspector,
+        ui_inspector: &mut ui_inspector::UIInspector,
         map_view_state: &mut map_view::MapViewState,
         show_debug_lines: &mut bool,
+        show_navigation: &mut bool,
         debug_draw: &mut crate::debug_draw::DebugDrawManager,
         map_manager: &mut crate::map_manager::MapManager,
         prefab_manager: &mut crate::PrefabManager,
@@ -253,6 +291,10 @@ impl EditorUI {
         reload_mesh_assets_request: &mut bool,
         asset_loader: &dyn AssetLoader,
         render_cache: &mut engine::runtime::render_system::RenderCache,
+        animation_window: &mut animation_window::AnimationWindow,
+        scene_modified: &mut bool,
+        asset_references: &mut crate::assets::references::AssetReferenceIndex,
+        component_registry: &ecs::ComponentRegistry,
     ) {
         // Handle layout change request (will be processed by caller)
         // Layout changes are handled in main.rs to access EditorState
@@ -270,26 +312,37 @@ impl EditorUI {
                 load_file_request,
                 play_request,
                 stop_request,
+                pause_request,
+                step_request,
+                replay,
                 show_project_settings,
                 show_colliders,
                 show_velocities,
                 show_debug_lines,
+                show_navigation,
+                Some(&mut *debug_draw),
                 project_path,
                 current_scene_path,
                 is_playing,
+                is_paused,
                 show_exit_dialog,
                 show_export_dialog,
                 layout_request,
                 current_layout_name,
+                Some(&mut *dock_state),
                 Self::get_scene_files,
             );
         });
 
         // Main docking area
         egui::CentralPanel::default().show(ctx, |ui| {
+            let sorting_layers = ecs::sorting::SortingLayers::new(project_settings.rendering.sorting_layers.clone());
             let mut tab_context = TabContext {
                 world,
                 selected_entity,
+                selection,
+                selection_action,
+                hierarchy_search,
                 entity_names,
                 edit_script_request,
                 project_path,
@@ -299,6 +352,7 @@ impl EditorUI {
                 scene_view_tab,
                 map_view_state,
                 is_playing,
+                is_paused,
                 show_colliders,
                 show_velocities,
                 current_tool,
@@ -309,10 +363,14 @@ impl EditorUI {
                 delta_time: dt,
                 play_request,
                 stop_request,
+                pause_request,
+                step_request,
                 asset_manager,
                 drag_drop,
                 dragging_entity,
                 drag_axis,
+                handle_drag,
+                snap_settings,
                 scene_view_mode,
                 projection_mode,
                 transform_space,
@@ -322,6 +380,7 @@ impl EditorUI {
                 sprite_editor_windows,
                 sprite_picker_state,
                 texture_inspector,
+                ui_inspector,
                 show_debug_lines,
                 debug_draw,
                 map_manager,
@@ -343,6 +402,16 @@ impl EditorUI {
                 scene_view_renderer,
                 asset_loader,
                 render_cache,
+                sorting_layers: &sorting_layers,
+                aspect_policy: project_settings.rendering.aspect_policy(),
+                scene_defaults: inspector::scene::ProjectSceneDefaults {
+                    gravity: project_settings.physics.gravity,
+                    clear_color: project_settings.rendering.clear_color.into(),
+                },
+                animation_window,
+                scene_modified,
+                asset_references,
+                component_registry,
             };
 
             // Handle Layout Requests
@@ -373,6 +442,11 @@ impl EditorUI {
                 }
             }
     
+            // Animation.open (menus, inspector) brings up the docked Animation tab
+            if tab_context.animation_window.take_open_request(ctx) {
+                dock_layout::open_tab(dock_state, EditorTab::Animation);
+            }
+
             let mut tab_viewer = EditorTabViewer {
                 context: &mut tab_context,
             };
@@ -389,6 +463,7 @@ impl EditorUI {
         project_settings::render_project_settings(
             ctx,
             show_project_settings,
+            project_settings,
             project_path,
             Self::get_scene_files,
         );
@@ -2,6 +2,7 @@
 use egui::{Color32, Rect, Sense, Vec2};
 use crate::asset_manager::{AssetManager, AssetMetadata, AssetType, ViewMode, SortMode};
 use crate::{UnityTheme, DragDropState, DraggedAsset};
+use crate::assets::references::AssetReferenceIndex;
 use std::path::PathBuf;
 
 pub struct AssetBrowser;
@@ -12,12 +13,13 @@ impl AssetBrowser {
         ui: &mut egui::Ui,
         asset_manager: &mut AssetManager,
         drag_drop: &mut DragDropState,
-        texture_manager: &mut engine::texture_manager::TextureManager,
-        project_path: Option<&PathBuf>,
+        references: &AssetReferenceIndex,
     ) -> Option<AssetBrowserAction> {
         let mut action = None;
         let colors = UnityTheme::colors();
         
+        asset_manager.update_thumbnails(ui.ctx());
+        
         // Toolbar (above both columns)
         ui.horizontal(|ui| {
             // Navigation buttons
@@ -72,10 +74,39 @@ impl AssetBrowser {
         // Search bar
         ui.horizontal(|ui| {
             ui.label("🔍");
-            ui.text_edit_singleline(&mut asset_manager.search_query);
+            ui.add(
+                egui::TextEdit::singleline(&mut asset_manager.search_query)
+                    .hint_text("Search (name, .png)")
+                    .desired_width(200.0),
+            );
             if ui.button("✖").clicked() {
                 asset_manager.search_query.clear();
             }
+            
+            // Type filters
+            let filter_text = if asset_manager.type_filter.is_empty() {
+                "Filter".to_string()
+            } else {
+                format!("Filter ({})", asset_manager.type_filter.len())
+            };
+            ui.menu_button(filter_text, |ui| {
+                for asset_type in AssetType::FILTERABLE {
+                    let mut enabled = asset_manager.type_filter.contains(&asset_type);
+                    let label = format!("{} {}", asset_type.icon(), asset_type.filter_label());
+                    if ui.checkbox(&mut enabled, label).changed() {
+                        if enabled {
+                            asset_manager.type_filter.push(asset_type);
+                        } else {
+                            asset_manager.type_filter.retain(|t| *t != asset_type);
+                        }
+                    }
+                }
+                ui.separator();
+                if ui.button("Show All").clicked() {
+                    asset_manager.type_filter.clear();
+                    ui.close();
+                }
+            });
         });
         
         ui.separator();
@@ -90,9 +121,7 @@ impl AssetBrowser {
                 });
             
             // Right column: Asset list
-            egui::ScrollArea::vertical()
-                .id_source("asset_list")
-                .show(&mut columns[1], |ui| {
+            let ui = &mut columns[1];
             let assets = asset_manager.get_assets();
             
             if assets.is_empty() {
@@ -104,19 +133,35 @@ impl AssetBrowser {
             
             match asset_manager.view_mode {
                 ViewMode::Grid => {
-                    if let Some(a) = Self::render_grid_view(ui, asset_manager, &assets, colors, drag_drop, texture_manager, project_path) {
+                    if let Some(a) = Self::render_grid_view(ui, asset_manager, &assets, colors, drag_drop, references) {
                         action = Some(a);
                     }
                 }
                 ViewMode::List => {
-                    if let Some(a) = Self::render_list_view(ui, asset_manager, &assets, colors, drag_drop) {
+                    if let Some(a) = Self::render_list_view(ui, asset_manager, &assets, colors, drag_drop, references) {
                         action = Some(a);
                     }
                 }
             }
-                });
         });
         
+        Self::render_delete_confirmation(ui.ctx(), asset_manager, references);
+        
+        // Follow the pointer with the dragged asset
+        if let Some(asset) = drag_drop.get_dragged_asset() {
+            if let Some(pointer) = ui.ctx().pointer_latest_pos() {
+                egui::Area::new(egui::Id::new("asset_drag_preview"))
+                    .order(egui::Order::Tooltip)
+                    .fixed_pos(pointer + Vec2::new(12.0, 12.0))
+                    .interactable(false)
+                    .show(ui.ctx(), |ui| {
+                        egui::Frame::popup(ui.style()).show(ui, |ui| {
+                            ui.label(format!("{} {}", asset.asset_type.icon(), asset.name));
+                        });
+                    });
+            }
+        }
+        
         action
     }
     
@@ -184,7 +229,7 @@ impl AssetBrowser {
             if let Ok(entries) = std::fs::read_dir(path) {
                 let mut folders: Vec<_> = entries
                     .filter_map(|e| e.ok())
-                    .filter(|e| e.path().is_dir())
+                    .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
                     .collect();
                 
                 folders.sort_by(|a, b| {
@@ -207,8 +252,7 @@ impl AssetBrowser {
         assets: &[AssetMetadata],
         colors: crate::theme::UnityColors,
         drag_drop: &mut DragDropState,
-        texture_manager: &mut engine::texture_manager::TextureManager,
-        project_path: Option<&PathBuf>,
+        references: &AssetReferenceIndex,
     ) -> Option<AssetBrowserAction> {
         let mut action = None;
         let thumbnail_size = asset_manager.thumbnail_size;
@@ -216,16 +260,23 @@ impl AssetBrowser {
         let item_width = thumbnail_size + spacing;
         let available_width = ui.available_width();
         let columns = (available_width / item_width).floor().max(1.0) as usize;
+        let rows: Vec<&[AssetMetadata]> = assets.chunks(columns).collect();
+        let row_height = thumbnail_size + 30.0;
         
-        for row_assets in assets.chunks(columns) {
-            ui.horizontal(|ui| {
-                for asset in row_assets {
-                    if let Some(a) = Self::render_grid_item(ui, asset_manager, asset, thumbnail_size, colors, drag_drop, texture_manager, project_path) {
-                        action = Some(a);
-                    }
+        // Only rows in view are laid out, so folders with thousands of files stay fast
+        egui::ScrollArea::vertical()
+            .id_salt("asset_list")
+            .show_rows(ui, row_height, rows.len(), |ui, row_range| {
+                for row_assets in &rows[row_range] {
+                    ui.horizontal(|ui| {
+                        for asset in *row_assets {
+                            if let Some(a) = Self::render_grid_item(ui, asset_manager, asset, thumbnail_size, colors, drag_drop, references) {
+                                action = Some(a);
+                            }
+                        }
+                    });
                 }
             });
-        }
         
         action
     }
@@ -238,13 +289,12 @@ impl AssetBrowser {
         size: f32,
         colors: crate::theme::UnityColors,
         drag_drop: &mut DragDropState,
-        texture_manager: &mut engine::texture_manager::TextureManager,
-        project_path: Option<&PathBuf>,
+        references: &AssetReferenceIndex,
     ) -> Option<AssetBrowserAction> {
         let mut action = None;
         let (rect, response) = ui.allocate_exact_size(
             Vec2::new(size, size + 30.0),
-            Sense::click(),
+            Sense::click_and_drag(),
         );
         
         if ui.is_rect_visible(rect) {
@@ -273,38 +323,23 @@ impl AssetBrowser {
             let icon_bg = Color32::from_rgb(icon_color[0], icon_color[1], icon_color[2]);
             ui.painter().rect_filled(thumb_rect, 4.0, icon_bg);
             
-            // Try to load texture preview for sprites
+            // Thumbnail (generated in the background; the icon shows until it's ready)
             let mut show_icon = true;
-            if matches!(asset.asset_type, AssetType::Sprite | AssetType::SpriteSheet) {
-                if let Some(project_path) = project_path {
-                    // Calculate relative path from project root
-                    let relative_path = if let Ok(rel) = asset.path.strip_prefix(project_path) {
-                        rel
-                    } else {
-                        asset.path.as_path()
-                    };
-                    
-                    // Load texture
-                    let texture_id = format!("asset_preview_{}", asset.path.display());
-                    if let Some(texture) = texture_manager.load_texture(
-                        ui.ctx(),
-                        &texture_id,
-                        relative_path
-                    ) {
-                        // Draw texture preview
-                        let preview_rect = thumb_rect.shrink(4.0);
-                        ui.painter().image(
-                            texture.id(),
-                            preview_rect,
-                            egui::Rect::from_min_max(
-                                egui::pos2(0.0, 0.0),
-                                egui::pos2(1.0, 1.0)
-                            ),
-                            Color32::WHITE
-                        );
-                        show_icon = false;
-                    }
-                }
+            if let Some((texture_id, texture_size)) = asset_manager.thumbnail(asset) {
+                // Fit inside the thumbnail area, keeping the aspect ratio
+                let area = thumb_rect.shrink(4.0);
+                let scale = (area.width() / texture_size.x).min(area.height() / texture_size.y);
+                let preview_rect = Rect::from_center_size(area.center(), texture_size * scale);
+                ui.painter().image(
+                    texture_id,
+                    preview_rect,
+                    egui::Rect::from_min_max(
+                        egui::pos2(0.0, 0.0),
+                        egui::pos2(1.0, 1.0)
+                    ),
+                    Color32::WHITE
+                );
+                show_icon = false;
             }
             
             // Fallback: Icon text
@@ -369,16 +404,24 @@ impl AssetBrowser {
                     if matches!(asset.asset_type, AssetType::Sprite | AssetType::SpriteSheet) {
                         action = Some(AssetBrowserAction::SelectTexture(asset.path.clone()));
                     }
+                    if asset.asset_type == AssetType::RenderTexture {
+                        action = Some(AssetBrowserAction::SelectRenderTexture(asset.path.clone()));
+                    }
                 }
             }
             
             // Context menu
             response.context_menu(|ui| {
-                if let Some(a) = Self::render_context_menu(ui, asset_manager, asset) {
+                if let Some(a) = Self::render_context_menu(ui, asset_manager, asset, references) {
                     action = Some(a);
                 }
             });
             
+            let response = response.on_hover_ui(|ui| {
+                ui.label(&asset.name);
+                Self::used_by_label(ui, references, asset);
+            });
+            
             // Double click to open
             if response.double_clicked() {
                 if asset.asset_type == AssetType::Folder {
@@ -406,6 +449,7 @@ impl AssetBrowser {
         assets: &[AssetMetadata],
         _colors: crate::theme::UnityColors,
         drag_drop: &mut DragDropState,
+        references: &AssetReferenceIndex,
     ) -> Option<AssetBrowserAction> {
         let mut action = None;
         // Header
@@ -421,107 +465,117 @@ impl AssetBrowser {
         
         ui.separator();
         
-        // Items
-        for asset in assets {
-            let is_selected = asset_manager.selected_asset.as_ref() == Some(&asset.path);
-            let is_favorite = asset_manager.is_favorite(&asset.path);
+        // Items (only rows in view are laid out)
+        let row_height = ui.spacing().interact_size.y;
+        egui::ScrollArea::vertical()
+            .id_salt("asset_list")
+            .show_rows(ui, row_height, assets.len(), |ui, row_range| {
+                for asset in &assets[row_range] {
+                    let is_selected = asset_manager.selected_asset.as_ref() == Some(&asset.path);
+                    let is_favorite = asset_manager.is_favorite(&asset.path);
             
-            let response = ui.horizontal(|ui| {
-                // Icon
-                ui.label(asset.asset_type.icon());
+                    let response = ui.horizontal(|ui| {
+                        // Icon
+                        ui.label(asset.asset_type.icon());
                 
-                // Favorite
-                if is_favorite {
-                    ui.label("⭐");
-                } else {
-                    ui.label("  ");
-                }
+                        // Favorite
+                        if is_favorite {
+                            ui.label("⭐");
+                        } else {
+                            ui.label("  ");
+                        }
                 
-                // Name
-                let name_response = ui.selectable_label(is_selected, &asset.name);
+                        // Name
+                        let name_response = ui
+                            .add(egui::Button::selectable(is_selected, &asset.name).sense(Sense::click_and_drag()))
+                            .on_hover_ui(|ui| Self::used_by_label(ui, references, asset));
                 
-                ui.separator();
+                        ui.separator();
                 
-                // Type
-                ui.label(format!("{:?}", asset.asset_type));
+                        // Type
+                        ui.label(format!("{:?}", asset.asset_type));
                 
-                ui.separator();
+                        ui.separator();
                 
-                // Size
-                if asset.asset_type != AssetType::Folder {
-                    ui.label(AssetManager::format_size(asset.size));
-                } else {
-                    ui.label("-");
-                }
+                        // Size
+                        if asset.asset_type != AssetType::Folder {
+                            ui.label(AssetManager::format_size(asset.size));
+                        } else {
+                            ui.label("-");
+                        }
                 
-                ui.separator();
+                        ui.separator();
                 
-                // Modified
-                if let Ok(elapsed) = asset.modified.elapsed() {
-                    let secs = elapsed.as_secs();
-                    let time_str = if secs < 60 {
-                        format!("{}s ago", secs)
-                    } else if secs < 3600 {
-                        format!("{}m ago", secs / 60)
-                    } else if secs < 86400 {
-                        format!("{}h ago", secs / 3600)
-                    } else {
-                        format!("{}d ago", secs / 86400)
-                    };
-                    ui.label(time_str);
-                } else {
-                    ui.label("-");
-                }
+                        // Modified
+                        if let Ok(elapsed) = asset.modified.elapsed() {
+                            let secs = elapsed.as_secs();
+                            let time_str = if secs < 60 {
+                                format!("{}s ago", secs)
+                            } else if secs < 3600 {
+                                format!("{}m ago", secs / 60)
+                            } else if secs < 86400 {
+                                format!("{}h ago", secs / 3600)
+                            } else {
+                                format!("{}d ago", secs / 86400)
+                            };
+                            ui.label(time_str);
+                        } else {
+                            ui.label("-");
+                        }
                 
-                name_response
-            }).inner;
+                        name_response
+                    }).inner;
             
-            // Handle drag
-            if response.drag_started() && asset.asset_type != AssetType::Folder {
-                drag_drop.start_drag(DraggedAsset {
-                    path: asset.path.clone(),
-                    name: asset.name.clone(),
-                    asset_type: asset.asset_type.clone(),
-                });
-            }
+                    // Handle drag
+                    if response.drag_started() && asset.asset_type != AssetType::Folder {
+                        drag_drop.start_drag(DraggedAsset {
+                            path: asset.path.clone(),
+                            name: asset.name.clone(),
+                            asset_type: asset.asset_type.clone(),
+                        });
+                    }
             
-            // Handle click
-            if response.clicked() {
-                if asset.asset_type == AssetType::Folder {
-                    asset_manager.navigate_to(&asset.path);
-                } else {
-                    asset_manager.selected_asset = Some(asset.path.clone());
+                    // Handle click
+                    if response.clicked() {
+                        if asset.asset_type == AssetType::Folder {
+                            asset_manager.navigate_to(&asset.path);
+                        } else {
+                            asset_manager.selected_asset = Some(asset.path.clone());
                     
-                    // If it's a texture, trigger SelectTexture action to show import settings
-                    if matches!(asset.asset_type, AssetType::Sprite | AssetType::SpriteSheet) {
-                        action = Some(AssetBrowserAction::SelectTexture(asset.path.clone()));
+                            // If it's a texture, trigger SelectTexture action to show import settings
+                            if matches!(asset.asset_type, AssetType::Sprite | AssetType::SpriteSheet) {
+                                action = Some(AssetBrowserAction::SelectTexture(asset.path.clone()));
+                            }
+                            if asset.asset_type == AssetType::RenderTexture {
+                                action = Some(AssetBrowserAction::SelectRenderTexture(asset.path.clone()));
+                            }
+                        }
                     }
-                }
-            }
             
-            // Context menu
-            response.context_menu(|ui| {
-                if let Some(a) = Self::render_context_menu(ui, asset_manager, asset) {
-                    action = Some(a);
-                }
-            });
-            
-            // Double click
-            if response.double_clicked() {
-                if asset.asset_type == AssetType::Folder {
-                    asset_manager.navigate_to(&asset.path);
-                } else if let Some(ext) = asset.path.extension() {
-                    match ext.to_str() {
-                        Some("uiprefab") => {
-                            action = Some(AssetBrowserAction::OpenUIPrefabEditor(asset.path.clone()));
+                    // Context menu
+                    response.context_menu(|ui| {
+                        if let Some(a) = Self::render_context_menu(ui, asset_manager, asset, references) {
+                            action = Some(a);
                         }
-                        _ => {
-                            // TODO: Open other asset types
+                    });
+            
+                    // Double click
+                    if response.double_clicked() {
+                        if asset.asset_type == AssetType::Folder {
+                            asset_manager.navigate_to(&asset.path);
+                        } else if let Some(ext) = asset.path.extension() {
+                            match ext.to_str() {
+                                Some("uiprefab") => {
+                                    action = Some(AssetBrowserAction::OpenUIPrefabEditor(asset.path.clone()));
+                                }
+                                _ => {
+                                    // TODO: Open other asset types
+                                }
+                            }
                         }
                     }
                 }
-            }
-        }
+            });
         
         action
     }
@@ -531,10 +585,15 @@ impl AssetBrowser {
         ui: &mut egui::Ui,
         asset_manager: &mut AssetManager,
         asset: &AssetMetadata,
+        references: &AssetReferenceIndex,
     ) -> Option<AssetBrowserAction> {
         let mut action = None;
         
         ui.label(format!("📝 {}", asset.name));
+        let users = references.users(&asset.path).len();
+        if users > 0 {
+            ui.label(egui::RichText::new(Self::used_by_text(users)).small().weak());
+        }
         ui.separator();
         
         if asset.asset_type != AssetType::Folder {
@@ -564,6 +623,16 @@ impl AssetBrowser {
                 }
             }
             
+            ui.separator();
+        } else {
+            if ui.button("📺 New Render Texture").clicked() {
+                match Self::create_render_texture(&asset.path) {
+                    Ok(path) => action = Some(AssetBrowserAction::SelectRenderTexture(path)),
+                    Err(e) => log::error!("{}", e),
+                }
+                ui.close();
+            }
+            
             ui.separator();
         }
         
@@ -610,12 +679,91 @@ impl AssetBrowser {
             }
         
             if ui.button("🗑 Delete").clicked() {
-                // TODO: Delete with confirmation
+                asset_manager.pending_delete = Some(asset.path.clone());
                 ui.close_menu();
             }
         
         action
     }
+    
+    fn used_by_text(users: usize) -> String {
+        if users == 1 { "Used by 1 entity".to_string() } else { format!("Used by {} entities", users) }
+    }
+    
+    /// "Used by N entities" in the open scene, or "Not used in this scene"
+    fn used_by_label(ui: &mut egui::Ui, references: &AssetReferenceIndex, asset: &AssetMetadata) {
+        let users = references.users(&asset.path).len();
+        if users > 0 {
+            ui.label(Self::used_by_text(users));
+        } else if asset.asset_type != AssetType::Folder {
+            ui.label(egui::RichText::new("Not used in this scene").weak());
+        }
+    }
+    
+    /// Ask before deleting `pending_delete`, warning if the open scene still uses it
+    fn render_delete_confirmation(
+        ctx: &egui::Context,
+        asset_manager: &mut AssetManager,
+        references: &AssetReferenceIndex,
+    ) {
+        let Some(path) = asset_manager.pending_delete.clone() else {
+            return;
+        };
+        let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let users = references.users(&path).len();
+        
+        let mut confirmed = None;
+        egui::Window::new("🗑 Delete Asset")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!("Delete '{}'? This can't be undone.", name));
+                if users > 0 {
+                    ui.colored_label(
+                        Color32::from_rgb(230, 180, 80),
+                        format!(
+                            "⚠ {} in the open scene. Missing assets show as broken references (Edit > Validate Assets).",
+                            Self::used_by_text(users)
+                        ),
+                    );
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Delete").clicked() {
+                        confirmed = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        confirmed = Some(false);
+                    }
+                });
+            });
+        
+        let Some(confirmed) = confirmed else {
+            return;
+        };
+        asset_manager.pending_delete = None;
+        if confirmed {
+            match asset_manager.delete_asset(&path) {
+                Ok(()) => log::info!("Deleted asset {}", path.display()),
+                Err(e) => log::error!("Failed to delete {}: {}", path.display(), e),
+            }
+        }
+    }
+    
+    /// Write a default render texture (`NewRenderTexture.rendertexture`, numbered if
+    /// taken) into `folder`
+    fn create_render_texture(folder: &std::path::Path) -> Result<PathBuf, String> {
+        let path = (0..)
+            .map(|n| {
+                let name = if n == 0 { "NewRenderTexture".to_string() } else { format!("NewRenderTexture{}", n) };
+                folder.join(format!("{}.{}", name, ecs::RENDER_TEXTURE_EXTENSION))
+            })
+            .find(|path| !path.exists())
+            .unwrap();
+        ecs::RenderTextureAsset::default().save(&path)?;
+        Ok(path)
+    }
 }
 
 /// Actions that can be triggered from the asset browser
@@ -625,4 +773,5 @@ pub enum AssetBrowserAction {
     SelectTexture(PathBuf),  // Select texture to show import settings
     OpenUIPrefabEditor(PathBuf),  // Open UI Prefab Editor
     ConvertGltfToXsg(PathBuf),    // Convert GLTF/GLB to XSG
+    SelectRenderTexture(PathBuf),  // Select render texture to edit its size/format
 }
@@ -9,8 +9,7 @@ pub fn render_bottom_panel(
     asset_manager: &mut Option<AssetManager>,
     console: &mut Console,
     drag_drop: &mut DragDropState,
-    texture_manager: &mut engine::texture_manager::TextureManager,
-    project_path: Option<&std::path::PathBuf>,
+    references: &crate::assets::references::AssetReferenceIndex,
 ) -> Option<AssetBrowserAction> {
     let mut action = None;
     // Tab bar
@@ -25,7 +24,7 @@ pub fn render_bottom_panel(
         0 => {
             // Assets tab
             if let Some(ref mut manager) = asset_manager {
-                action = AssetBrowser::render(ui, manager, drag_drop, texture_manager, project_path);
+                action = AssetBrowser::render(ui, manager, drag_drop, references);
             } else {
                 ui.centered_and_justified(|ui| {
                     ui.label("No project open");
@@ -1,7 +1,8 @@
 use ecs::{World, Entity};
 use egui;
 use std::collections::HashMap;
-use crate::Console;
+use crate::{Console, SelectionAction, SelectionManager, SelectionMode};
+use crate::tools::hierarchy_search::HierarchySearch;
 
 /// Render the hierarchy panel (left panel) showing scene entities
 /// Returns Some(entity) if user requested to create prefab from entity
@@ -10,6 +11,9 @@ pub fn render_hierarchy(
     world: &mut World,
     entity_names: &mut HashMap<Entity, String>,
     selected_entity: &mut Option<Entity>,
+    selection: &mut SelectionManager,
+    selection_action: &mut Option<SelectionAction>,
+    hierarchy_search: &mut HierarchySearch,
     _load_file_request: &mut Option<std::path::PathBuf>,
     _project_path: &Option<std::path::PathBuf>,
     current_scene_path: &Option<std::path::PathBuf>,
@@ -22,6 +26,9 @@ pub fn render_hierarchy(
         world,
         entity_names,
         selected_entity,
+        selection,
+        selection_action,
+        hierarchy_search,
         _load_file_request,
         _project_path,
         current_scene_path,
@@ -29,6 +36,7 @@ pub fn render_hierarchy(
         _get_scene_files_fn,
         get_entity_icon_fn,
         None, // No map_manager filter
+        None, // No UI canvas
     )
 }
 
@@ -39,6 +47,9 @@ pub fn render_hierarchy_with_filter(
     world: &mut World,
     entity_names: &mut HashMap<Entity, String>,
     selected_entity: &mut Option<Entity>,
+    selection: &mut SelectionManager,
+    selection_action: &mut Option<SelectionAction>,
+    hierarchy_search: &mut HierarchySearch,
     _load_file_request: &mut Option<std::path::PathBuf>,
     _project_path: &Option<std::path::PathBuf>,
     current_scene_path: &Option<std::path::PathBuf>,
@@ -46,6 +57,7 @@ pub fn render_hierarchy_with_filter(
     _get_scene_files_fn: impl Fn(&std::path::Path) -> Vec<String>,
     get_entity_icon_fn: &impl Fn(&World, Entity) -> &'static str,
     map_manager: Option<&crate::map_manager::MapManager>,
+    ui_canvas: Option<(&engine::ui_manager::UIManager, &mut crate::ui::ui_inspector::UIInspector)>,
 ) -> Option<Entity> {
     // Unity-style header with title and icons
     ui.horizontal(|ui| {
@@ -98,12 +110,15 @@ pub fn render_hierarchy_with_filter(
                     asset_id: None,
                     width: 1.0,  // Base size, actual size determined by transform.scale
                     height: 1.0,
-                    color: [1.0, 1.0, 1.0, 1.0],
+                    color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
                     billboard: false, // Default sprite, not billboard
                     flip_x: false,
                     flip_y: false,
                     sprite_rect: None,
+                    rect_rotated: false,
+                    frame_name: None,
                     pixels_per_unit: 100.0,
+                    pivot: None,
                     sorting_layer: "Default".to_string(),
                     order_in_layer: 0,
                     rendering_layer_mask: 1,
@@ -202,20 +217,57 @@ pub fn render_hierarchy_with_filter(
             }
         });
         
-        // Search box (Unity style)
-        ui.add(
-            egui::TextEdit::singleline(&mut String::new())
-                .hint_text("🔍 Search")
-                .desired_width(ui.available_width())
-        );
+        // Search box (Unity style): name, t:Tag, c:Component
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            if hierarchy_search.is_active() && ui.small_button("✖").on_hover_text("Clear search").clicked() {
+                hierarchy_search.clear();
+            }
+            ui.add(
+                egui::TextEdit::singleline(&mut hierarchy_search.query)
+                    .hint_text("🔍 Search")
+                    .desired_width(ui.available_width())
+            ).on_hover_text("Name, t:Tag or c:Component - combine with spaces");
+        });
     });
+
+    // Collect roots (entities with no parent) in hierarchy order
+    let mut roots: Vec<Entity> = world.roots().into_iter()
+        .filter(|e| {
+            // Filter out entities the editor doesn't name
+            if !entity_names.contains_key(e) {
+                return false;
+            }
+            
+            // Filter out map entities if map_manager is provided
+            if let Some(manager) = map_manager {
+                if is_map_entity(*e, world, manager) {
+                    return false;
+                }
+            }
+            
+            true
+        })
+        .collect();
+
+    // Flattened display order for Shift+Click range selection and search cycling
+    let mut display_order = Vec::new();
+    for &root in &roots {
+        collect_display_order(root, world, map_manager, &mut display_order);
+    }
+
+    hierarchy_search.update(world, entity_names, &display_order);
+    if hierarchy_search.is_active() {
+        roots.retain(|&e| hierarchy_search.is_visible(e));
+        display_order.retain(|&e| hierarchy_search.is_visible(e));
+        render_search_results_bar(ui, hierarchy_search, selected_entity, selection);
+    }
     
     ui.separator();
 
     // Main hierarchy scroll area
     egui::ScrollArea::vertical().show(ui, |ui| {
-        // Track entity to delete (for right-click menu)
-        let mut entity_to_delete: Option<Entity> = None;
+        auto_scroll_while_dragging(ui);
+
         let mut entity_to_create_child: Option<Entity> = None;
         let mut entity_to_create_prefab: Option<Entity> = None;
 
@@ -230,35 +282,17 @@ pub fn render_hierarchy_with_filter(
         };
 
         // Unity-style scene header (collapsible but default open)
+        // Clicking it shows the Scene Settings in the inspector
         let scene_id = ui.make_persistent_id("scene_root");
+        let mut scene_root_clicked = false;
+        let scene_root_selected = selected_entity.is_none();
         egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), scene_id, true)
             .show_header(ui, |ui| {
-                ui.label(format!("🎬 {}", scene_name));
+                scene_root_clicked = ui.selectable_label(scene_root_selected, format!("🎬 {}", scene_name))
+                    .on_hover_text("Scene Settings")
+                    .clicked();
             })
             .body(|ui| {
-                // Collect roots (entities with no parent)
-                let mut roots: Vec<Entity> = entity_names.keys()
-                    .filter(|&e| {
-                        // Filter out entities with no parent
-                        if world.parents.get(e).is_some() {
-                            return false;
-                        }
-                        
-                        // Filter out map entities if map_manager is provided
-                        if let Some(manager) = map_manager {
-                            if is_map_entity(*e, world, manager) {
-                                return false;
-                            }
-                        }
-                        
-                        true
-                    })
-                    .cloned()
-                    .collect();
-
-                // Sort by ID for stability
-                roots.sort();
-
                 // Draw all root entities
                 for root in roots {
                     draw_entity_node(
@@ -267,7 +301,10 @@ pub fn render_hierarchy_with_filter(
                         world,
                         entity_names,
                         selected_entity,
-                        &mut entity_to_delete,
+                        selection,
+                        selection_action,
+                        hierarchy_search,
+                        &display_order,
                         &mut entity_to_create_child,
                         &mut entity_to_create_prefab,
                         get_entity_icon_fn,
@@ -276,6 +313,33 @@ pub fn render_hierarchy_with_filter(
                 }
             });
 
+        if scene_root_clicked {
+            *selected_entity = None;
+            selection.clear();
+        }
+
+        // Active UI prefab instances (selecting one shows the UI inspector)
+        if let Some((ui_manager, ui_inspector)) = ui_canvas {
+            if scene_root_clicked {
+                ui_inspector.clear();
+            }
+            let clicked = crate::ui::ui_inspector::render_canvas_hierarchy(ui, ui_manager, ui_inspector.selected.as_deref());
+            if let Some(path) = clicked {
+                ui_inspector.select(path);
+                *selected_entity = None;
+                selection.clear();
+            }
+        }
+
+        // Dropping below the last row moves the selection to the end of the scene root
+        let empty_space = ui.allocate_response(
+            egui::vec2(ui.available_width(), ui.available_height().max(40.0)),
+            egui::Sense::hover(),
+        );
+        if empty_space.dnd_release_payload::<Entity>().is_some() {
+            *selection_action = Some(SelectionAction::MoveTo { parent: None, before: None });
+        }
+
         // Handle creation
         if let Some(parent) = entity_to_create_child {
             let child = world.spawn();
@@ -285,21 +349,59 @@ pub fn render_hierarchy_with_filter(
             *selected_entity = Some(child);
         }
 
-        // Delete entity if requested
-        if let Some(entity) = entity_to_delete {
-            world.despawn(entity);
-            entity_names.remove(&entity);
-            if *selected_entity == Some(entity) {
-                *selected_entity = None;
-            }
-        }
-        
         // Return entity_to_create_prefab for handling by caller
         entity_to_create_prefab
     }).inner
 
 }
 
+/// Match count, F3/Shift+F3 cycling and "select all matches" for an active search
+fn render_search_results_bar(
+    ui: &mut egui::Ui,
+    search: &mut HierarchySearch,
+    selected_entity: &mut Option<Entity>,
+    selection: &mut SelectionManager,
+) {
+    let (next_key, previous_key) = ui.ctx().input(|i| {
+        let f3 = i.key_pressed(egui::Key::F3) && !i.modifiers.ctrl && !i.modifiers.alt;
+        (f3 && !i.modifiers.shift, f3 && i.modifiers.shift)
+    });
+
+    let mut step = None;
+    if next_key {
+        step = Some(false);
+    } else if previous_key {
+        step = Some(true);
+    }
+
+    ui.horizontal(|ui| {
+        let count = search.matches().len();
+        ui.label(egui::RichText::new(format!("{} match{}", count, if count == 1 { "" } else { "es" })).weak());
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.add_enabled_ui(count > 0, |ui| {
+                if ui.small_button("Select All").on_hover_text("Select all matches").clicked() {
+                    selection.select_multiple(search.matches(), SelectionMode::Replace);
+                    *selected_entity = search.matches().first().copied();
+                }
+                if ui.small_button("⏷").on_hover_text("Next match (F3)").clicked() {
+                    step = Some(false);
+                }
+                if ui.small_button("⏶").on_hover_text("Previous match (Shift+F3)").clicked() {
+                    step = Some(true);
+                }
+            });
+        });
+    });
+
+    if let Some(backwards) = step {
+        if let Some(entity) = search.step(*selected_entity, backwards) {
+            selection.select(entity, SelectionMode::Replace);
+            *selected_entity = Some(entity);
+            crate::ui::scene_view::request_frame_selected(ui.ctx());
+        }
+    }
+}
+
 /// Check if entity is a map-related entity that should be hidden from hierarchy
 fn is_map_entity(
     entity: Entity,
@@ -327,6 +429,24 @@ fn is_map_entity(
     false
 }
 
+/// Depth-first order of an entity and its visible descendants
+fn collect_display_order(
+    entity: Entity,
+    world: &World,
+    map_manager: Option<&crate::map_manager::MapManager>,
+    order: &mut Vec<Entity>,
+) {
+    order.push(entity);
+    for &child in world.get_children(entity) {
+        if let Some(manager) = map_manager {
+            if is_map_entity(child, world, manager) {
+                continue;
+            }
+        }
+        collect_display_order(child, world, map_manager, order);
+    }
+}
+
 /// Recursively draw entity node in hierarchy with children (Unity style)
 pub fn draw_entity_node(
     ui: &mut egui::Ui,
@@ -334,70 +454,53 @@ pub fn draw_entity_node(
     world: &World,
     entity_names: &HashMap<Entity, String>,
     selected_entity: &mut Option<Entity>,
-    entity_to_delete: &mut Option<Entity>,
+    selection: &mut SelectionManager,
+    selection_action: &mut Option<SelectionAction>,
+    search: &mut HierarchySearch,
+    display_order: &[Entity],
     entity_to_create_child: &mut Option<Entity>,
     entity_to_create_prefab: &mut Option<Entity>,
     get_entity_icon_fn: &impl Fn(&World, Entity) -> &'static str,
     map_manager: Option<&crate::map_manager::MapManager>,
 ) {
     let name = entity_names.get(&entity).cloned().unwrap_or(format!("Entity {}", entity));
-    let is_selected = *selected_entity == Some(entity);
+    let is_selected = *selected_entity == Some(entity) || selection.is_selected(entity);
     let icon = get_entity_icon_fn(world, entity);
-    let children = world.get_children(entity);
+    let children: Vec<Entity> = world.get_children(entity).iter()
+        .copied()
+        .filter(|&child| search.is_visible(child))
+        .collect();
     let has_children = !children.is_empty();
 
+    // Ancestors shown only because a descendant matched the search are dimmed
+    let mut text = egui::RichText::new(format!("{} {}", icon, name));
+    if search.is_active() && !search.is_match(entity) {
+        text = text.color(ui.visuals().weak_text_color());
+    }
+    let reveal = search.take_reveal(entity);
+
     let id = ui.make_persistent_id(entity);
 
     if has_children {
         // Unity-style parent node with arrow
-        egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false)
+        let mut state = egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false);
+        if search.is_active() && search.is_context(entity) {
+            // Expand so matches below are visible
+            state.set_open(true);
+        }
+        state
             .show_header(ui, |ui| {
-                let response = ui.selectable_label(is_selected, format!("{} {}", icon, name));
-                
-                if response.clicked() {
-                    *selected_entity = Some(entity);
+                let response = entity_label(ui, is_selected, text);
+                if reveal {
+                    response.scroll_to_me(Some(egui::Align::Center));
                 }
-
-                // Unity-style context menu
-                response.context_menu(|ui| {
-                    if ui.button("Create Empty Child").clicked() {
-                        *entity_to_create_child = Some(entity);
-                        ui.close_menu();
-                    }
-                    
-                    ui.separator();
-                    
-                    if ui.button("Copy").clicked() {
-                        // TODO: Implement copy
-                        ui.close_menu();
-                    }
-                    
-                    if ui.button("Paste").clicked() {
-                        // TODO: Implement paste
-                        ui.close_menu();
-                    }
-                    
-                    if ui.button("Duplicate").clicked() {
-                        // TODO: Implement duplicate
-                        ui.close_menu();
-                    }
-                    
-                    if ui.button("Rename").clicked() {
-                        // TODO: Implement rename
-                        ui.close_menu();
-                    }
-                    
-                    ui.separator();
-                    
-                    if ui.button("Delete").clicked() {
-                        *entity_to_delete = Some(entity);
-                        ui.close_menu();
-                    }
-                });
+                handle_entity_click(&response, entity, selected_entity, selection, display_order);
+                handle_entity_drag_drop(ui, &response, entity, world, selected_entity, selection, selection_action);
+                render_entity_context_menu(&response, entity, selected_entity, selection, selection_action, entity_to_create_child);
             })
             .body(|ui| {
                 // Draw children with proper indentation
-                for &child in children {
+                for child in children {
                     // Skip map entities
                     if let Some(manager) = map_manager {
                         if is_map_entity(child, world, manager) {
@@ -405,7 +508,7 @@ pub fn draw_entity_node(
                         }
                     }
                     
-                    draw_entity_node(ui, child, world, entity_names, selected_entity, entity_to_delete, entity_to_create_child, entity_to_create_prefab, get_entity_icon_fn, map_manager);
+                    draw_entity_node(ui, child, world, entity_names, selected_entity, selection, selection_action, search, display_order, entity_to_create_child, entity_to_create_prefab, get_entity_icon_fn, map_manager);
                 }
             });
     } else {
@@ -414,48 +517,212 @@ pub fn draw_entity_node(
             // Add spacing to align with parent nodes
             ui.add_space(18.0);
             
-            let response = ui.selectable_label(is_selected, format!("{} {}", icon, name));
-            
-            if response.clicked() {
+            let response = entity_label(ui, is_selected, text);
+            if reveal {
+                response.scroll_to_me(Some(egui::Align::Center));
+            }
+            handle_entity_click(&response, entity, selected_entity, selection, display_order);
+            handle_entity_drag_drop(ui, &response, entity, world, selected_entity, selection, selection_action);
+            render_entity_context_menu(&response, entity, selected_entity, selection, selection_action, entity_to_create_child);
+        });
+    }
+}
+
+/// Selectable entity row that can also be dragged onto another entity
+fn entity_label(ui: &mut egui::Ui, is_selected: bool, text: impl Into<egui::WidgetText>) -> egui::Response {
+    ui.add(egui::Button::selectable(is_selected, text).sense(egui::Sense::click_and_drag()))
+}
+
+/// Dragging selected entities onto another entity parents the whole selection to it;
+/// dropping on the top or bottom edge of a row moves it in front of or after that row.
+/// Drops that would put an entity under itself show a forbidden cursor and do nothing.
+fn handle_entity_drag_drop(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    entity: Entity,
+    world: &World,
+    selected_entity: &mut Option<Entity>,
+    selection: &mut SelectionManager,
+    selection_action: &mut Option<SelectionAction>,
+) {
+    if response.drag_started() && !selection.is_selected(entity) {
+        selection.select(entity, SelectionMode::Replace);
+        *selected_entity = Some(entity);
+    }
+    response.dnd_set_drag_payload(entity);
+
+    if response.dnd_hover_payload::<Entity>().is_none() {
+        return;
+    }
+    let Some(pointer) = ui.ctx().pointer_latest_pos() else {
+        return;
+    };
+
+    let rect = response.rect;
+    let edge = rect.height() * 0.25;
+    let parent = world.get_parent(entity);
+    let (action, new_parent) = if pointer.y < rect.top() + edge {
+        (SelectionAction::MoveTo { parent, before: Some(entity) }, parent)
+    } else if pointer.y > rect.bottom() - edge {
+        let siblings = match parent {
+            Some(parent) => world.get_children(parent).to_vec(),
+            None => world.roots(),
+        };
+        let next = siblings.into_iter().skip_while(|&e| e != entity).nth(1);
+        (SelectionAction::MoveTo { parent, before: next }, parent)
+    } else {
+        (SelectionAction::ParentTo(entity), Some(entity))
+    };
+
+    if selection.get_selected().iter().any(|&dragged| world.check_parent(dragged, new_parent).is_err()) {
+        ui.ctx().set_cursor_icon(egui::CursorIcon::NotAllowed);
+        return;
+    }
+
+    let stroke = egui::Stroke::new(1.5, ui.visuals().selection.stroke.color);
+    match action {
+        SelectionAction::ParentTo(_) => {
+            ui.painter().rect_stroke(rect, 2.0, stroke, egui::StrokeKind::Inside);
+        }
+        _ => {
+            let y = if pointer.y < rect.center().y { rect.top() } else { rect.bottom() };
+            ui.painter().hline(rect.x_range(), y, stroke);
+        }
+    }
+    if response.dnd_release_payload::<Entity>().is_some() {
+        *selection_action = Some(action);
+    }
+}
+
+/// Scroll the hierarchy while an entity is dragged near its top or bottom edge, faster
+/// the closer it gets
+fn auto_scroll_while_dragging(ui: &egui::Ui) {
+    const EDGE: f32 = 24.0;
+    const SPEED: f32 = 600.0; // Points per second at the edge
+
+    if !egui::DragAndDrop::has_payload_of_type::<Entity>(ui.ctx()) {
+        return;
+    }
+    let viewport = ui.clip_rect();
+    let Some(pointer) = ui.ctx().pointer_latest_pos().filter(|&p| viewport.expand2(egui::vec2(0.0, EDGE)).contains(p)) else {
+        return;
+    };
+
+    // Positive scrolls up
+    let direction = if pointer.y < viewport.top() + EDGE {
+        (viewport.top() + EDGE - pointer.y).min(EDGE) / EDGE
+    } else if pointer.y > viewport.bottom() - EDGE {
+        -(pointer.y - viewport.bottom() + EDGE).min(EDGE) / EDGE
+    } else {
+        return;
+    };
+    let dt = ui.input(|i| i.stable_dt).min(0.1);
+    ui.scroll_with_delta_animation(egui::vec2(0.0, direction * SPEED * dt), egui::style::ScrollAnimation::none());
+    ui.ctx().request_repaint();
+}
+
+/// Click = select, Ctrl+Click = toggle, Shift+Click = range
+fn handle_entity_click(
+    response: &egui::Response,
+    entity: Entity,
+    selected_entity: &mut Option<Entity>,
+    selection: &mut SelectionManager,
+    display_order: &[Entity],
+) {
+    if !response.clicked() {
+        return;
+    }
+
+    let modifiers = response.ctx.input(|i| i.modifiers);
+    match SelectionManager::get_selection_mode(&modifiers) {
+        SelectionMode::Range => {
+            selection.select_range(entity, display_order);
+            *selected_entity = Some(entity);
+        }
+        SelectionMode::Toggle => {
+            selection.select(entity, SelectionMode::Toggle);
+            if selection.is_selected(entity) {
                 *selected_entity = Some(entity);
+            } else if *selected_entity == Some(entity) {
+                // Primary selection moves to another selected entity
+                *selected_entity = selection.get_last().filter(|&e| selection.is_selected(e))
+                    .or_else(|| selection.get_first());
             }
+        }
+        mode => {
+            selection.select(entity, mode);
+            *selected_entity = Some(entity);
+        }
+    }
+}
 
-            // Unity-style context menu
-            response.context_menu(|ui| {
-                if ui.button("Create Empty Child").clicked() {
-                    *entity_to_create_child = Some(entity);
-                    ui.close_menu();
-                }
-                
-                ui.separator();
-                
-                if ui.button("Copy").clicked() {
-                    // TODO: Implement copy
-                    ui.close_menu();
-                }
-                
-                if ui.button("Paste").clicked() {
-                    // TODO: Implement paste
-                    ui.close_menu();
-                }
-                
-                if ui.button("Duplicate").clicked() {
-                    // TODO: Implement duplicate
-                    ui.close_menu();
-                }
-                
-                if ui.button("Rename").clicked() {
-                    // TODO: Implement rename
-                    ui.close_menu();
-                }
-                
-                ui.separator();
-                
-                if ui.button("Delete").clicked() {
-                    *entity_to_delete = Some(entity);
-                    ui.close_menu();
-                }
-            });
-        });
+/// Unity-style context menu; Copy/Paste/Duplicate/Delete act on the whole selection
+fn render_entity_context_menu(
+    response: &egui::Response,
+    entity: Entity,
+    selected_entity: &mut Option<Entity>,
+    selection: &mut SelectionManager,
+    selection_action: &mut Option<SelectionAction>,
+    entity_to_create_child: &mut Option<Entity>,
+) {
+    // Right-clicking outside the selection selects that entity first
+    if response.secondary_clicked() && !selection.is_selected(entity) && *selected_entity != Some(entity) {
+        selection.select(entity, SelectionMode::Replace);
+        *selected_entity = Some(entity);
     }
+
+    let selection_count = selection.count().max(1);
+
+    response.context_menu(|ui| {
+        if ui.button("Create Empty Child").clicked() {
+            *entity_to_create_child = Some(entity);
+            ui.close();
+        }
+        
+        ui.separator();
+        
+        if ui.button("Copy").clicked() {
+            *selection_action = Some(SelectionAction::Copy);
+            ui.close();
+        }
+        
+        if ui.button("Paste").clicked() {
+            *selection_action = Some(SelectionAction::Paste);
+            ui.close();
+        }
+        
+        let duplicate_label = if selection_count > 1 {
+            format!("Duplicate ({})", selection_count)
+        } else {
+            "Duplicate".to_string()
+        };
+        if ui.button(duplicate_label).clicked() {
+            *selection_action = Some(SelectionAction::Duplicate);
+            ui.close();
+        }
+        
+        if ui.button("Rename").clicked() {
+            // TODO: Implement rename
+            ui.close();
+        }
+        
+        ui.separator();
+        
+        if ui.button("Unparent").clicked() {
+            *selection_action = Some(SelectionAction::Unparent);
+            ui.close();
+        }
+        
+        ui.separator();
+        
+        let delete_label = if selection_count > 1 {
+            format!("Delete ({})", selection_count)
+        } else {
+            "Delete".to_string()
+        };
+        if ui.button(delete_label).clicked() {
+            *selection_action = Some(SelectionAction::Delete);
+            ui.close();
+        }
+    });
 }
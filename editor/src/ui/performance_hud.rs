@@ -0,0 +1,72 @@
+//! Performance HUD drawn over the top-right of the Game view while playing ("Stats"
+//! in the Game view toolbar).
+//!
+//! FPS, a frame-time graph, entity and draw call counts, and the slowest scripts of
+//! the last frame. Same numbers as Lua's `Profiler.get_frame_stats()`; script timings
+//! are only recorded while it is shown (see `profiler::scripts`).
+
+use ecs::{Entity, World};
+use egui::{Color32, RichText};
+use profiler::scripts::{self, FRAME_TIME_HISTORY, TOP_SCRIPTS};
+use std::collections::HashMap;
+
+const WIDTH: f32 = 280.0;
+
+pub fn render(ui: &mut egui::Ui, view_rect: egui::Rect, world: &World, entity_names: &HashMap<Entity, String>) {
+    let area = egui::Rect::from_min_size(
+        egui::pos2(view_rect.right() - WIDTH - 8.0, view_rect.top() + 8.0),
+        egui::vec2(WIDTH, (view_rect.height() - 16.0).max(0.0)),
+    );
+    let mut hud_ui = ui.new_child(egui::UiBuilder::new().max_rect(area));
+
+    let stats = scripts::frame_stats(TOP_SCRIPTS);
+    egui::Frame::NONE
+        .fill(Color32::from_black_alpha(200))
+        .corner_radius(4.0)
+        .inner_margin(8.0)
+        .show(&mut hud_ui, |ui| {
+            ui.set_width(WIDTH - 16.0);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(format!("{:.0} FPS", stats.fps)).strong().color(Color32::WHITE));
+                ui.label(
+                    RichText::new(format!("{:.2} ms", stats.frame_time.as_secs_f64() * 1000.0)).color(Color32::LIGHT_GRAY),
+                );
+            });
+
+            let frame_times: Vec<f32> = scripts::frame_times().iter().map(|t| t.as_secs_f32() * 1000.0).collect();
+            super::profiler_overlay::draw_frame_graph(ui, &frame_times, FRAME_TIME_HISTORY);
+
+            ui.horizontal(|ui| {
+                ui.label(RichText::new(format!("Entities: {}", world.transforms.len())).color(Color32::LIGHT_GRAY));
+                ui.label(RichText::new(format!("Draw calls: {}", stats.draw_calls)).color(Color32::LIGHT_GRAY));
+            });
+
+            ui.add_space(4.0);
+            if stats.scripts.is_empty() {
+                ui.label(RichText::new("No scripts ran last frame").small().color(Color32::GRAY));
+                return;
+            }
+            egui::Grid::new("performance_hud_scripts")
+                .num_columns(3)
+                .spacing([10.0, 2.0])
+                .show(ui, |ui| {
+                    ui.label(RichText::new("Entity").small().color(Color32::GRAY));
+                    ui.label(RichText::new("Script").small().color(Color32::GRAY));
+                    ui.label(RichText::new("ms").small().color(Color32::GRAY));
+                    ui.end_row();
+
+                    for timing in &stats.scripts {
+                        let name = entity_names.get(&timing.entity).cloned().unwrap_or_else(|| format!("Entity {}", timing.entity));
+                        ui.label(RichText::new(name).color(Color32::WHITE));
+                        ui.label(RichText::new(&timing.script).monospace().color(Color32::LIGHT_GRAY));
+                        ui.label(
+                            RichText::new(format!("{:.3}", timing.duration.as_secs_f64() * 1000.0)).color(Color32::WHITE),
+                        );
+                        ui.end_row();
+                    }
+                });
+        });
+
+    // Keep the numbers moving even when nothing else requests a repaint
+    ui.ctx().request_repaint();
+}
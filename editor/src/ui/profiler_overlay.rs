@@ -0,0 +1,292 @@
+//! Profiler overlay drawn over the Game view (toggle with F3), and the same readout
+//! in the Profiler dock tab.
+//!
+//! Shows FPS, a frame-time graph of the profiler history and the most expensive
+//! scopes averaged over the last second, with GPU pass times (timestamp queries) in
+//! their own column. Collection only runs while it is visible.
+
+use egui::{Color32, RichText};
+use profiler::frame::{self, ScopeStats, HISTORY_FRAMES};
+use profiler::gpu::GpuTimingSupport;
+use std::time::Duration;
+
+/// Frames averaged for FPS and the scope table (~1 second at 60 FPS)
+const AVERAGE_FRAMES: usize = 60;
+/// Rows shown in the scope table
+const MAX_ROWS: usize = 12;
+const TARGET_60_MS: f32 = 1000.0 / 60.0;
+const TARGET_30_MS: f32 = 1000.0 / 30.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SortColumn {
+    Name,
+    Calls,
+    SelfTime,
+    Total,
+    Gpu,
+}
+
+pub fn toggle() {
+    profiler::set_enabled(!profiler::is_enabled());
+}
+
+/// Draw the overlay in the top-left corner of `view_rect` if the profiler is enabled
+pub fn render(ui: &mut egui::Ui, view_rect: egui::Rect) {
+    if !profiler::is_enabled() {
+        return;
+    }
+
+    let area = egui::Rect::from_min_size(
+        view_rect.min + egui::vec2(8.0, 8.0),
+        egui::vec2(380.0, (view_rect.height() - 16.0).max(0.0)),
+    );
+    let mut overlay_ui = ui.new_child(egui::UiBuilder::new().max_rect(area));
+
+    egui::Frame::NONE
+        .fill(Color32::from_black_alpha(200))
+        .corner_radius(4.0)
+        .inner_margin(8.0)
+        .show(&mut overlay_ui, |ui| {
+            ui.set_width(area.width() - 16.0);
+            render_stats(ui);
+        });
+
+    // Keep the graph moving even when nothing else requests a repaint
+    ui.ctx().request_repaint();
+}
+
+/// Contents of the Profiler dock tab: the same readout as the overlay, with a switch
+/// since collection only runs while the profiler is enabled
+pub fn render_panel(ui: &mut egui::Ui) {
+    let mut enabled = profiler::is_enabled();
+    if ui.checkbox(&mut enabled, "Record").on_hover_text("Also shows the readout over the Game view (F3)").changed() {
+        profiler::set_enabled(enabled);
+    }
+    if !enabled {
+        return;
+    }
+
+    egui::Frame::NONE
+        .fill(Color32::from_black_alpha(200))
+        .corner_radius(4.0)
+        .inner_margin(8.0)
+        .show(ui, |ui| {
+            egui::ScrollArea::vertical().id_salt("profiler_panel").show(ui, render_stats);
+        });
+    ui.ctx().request_repaint();
+}
+
+/// FPS, GPU summary, frame graph and the sortable scope table
+fn render_stats(ui: &mut egui::Ui) {
+    let frame_times: Vec<f32> = frame::with_history(|history| {
+        history.iter().map(|f| f.duration.as_secs_f32() * 1000.0).collect()
+    });
+    let mut stats = frame::scope_stats(AVERAGE_FRAMES);
+    let gpu = profiler::gpu::average_timings(AVERAGE_FRAMES);
+    let gpu_passes = gpu.as_ref().map(|(_, passes)| passes.as_slice()).unwrap_or(&[]);
+    // GPU-only passes still get a row
+    for pass in gpu_passes {
+        if !stats.iter().any(|stat| stat.name == pass.name) {
+            stats.push(ScopeStats { name: pass.name.clone(), calls: 0.0, total: Duration::ZERO, self_time: Duration::ZERO });
+        }
+    }
+    let gpu_time_of = |name: &str| gpu_passes.iter().find(|pass| pass.name == name).map(|pass| pass.duration);
+
+    let sort_id = ui.make_persistent_id("profiler_overlay_sort");
+    let (mut sort, mut descending) = ui
+        .data_mut(|d| d.get_temp::<(SortColumn, bool)>(sort_id))
+        .unwrap_or((SortColumn::Total, true));
+
+    if frame_times.is_empty() {
+        ui.label(RichText::new("Profiler: collecting…").color(Color32::WHITE));
+        return;
+    }
+
+    let recent = &frame_times[frame_times.len().saturating_sub(AVERAGE_FRAMES)..];
+    let average = recent.iter().sum::<f32>() / recent.len() as f32;
+    let worst = recent.iter().cloned().fold(0.0, f32::max);
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(format!("{:.0} FPS", 1000.0 / average.max(0.001))).strong().color(Color32::WHITE));
+        ui.label(RichText::new(format!("{:.2} ms avg, {:.2} ms max", average, worst)).color(Color32::LIGHT_GRAY));
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            ui.label(RichText::new("F3").small().color(Color32::GRAY));
+        });
+    });
+
+    draw_gpu_summary(ui, gpu.as_ref().map(|(total, _)| *total));
+    draw_frame_graph(ui, &frame_times, HISTORY_FRAMES);
+    ui.add_space(6.0);
+
+    sort_stats(&mut stats, sort, descending, &gpu_time_of);
+    egui::Grid::new("profiler_overlay_scopes")
+        .num_columns(5)
+        .striped(true)
+        .spacing([12.0, 2.0])
+        .show(ui, |ui| {
+            for (column, label) in [
+                (SortColumn::Name, "Scope"),
+                (SortColumn::Calls, "Calls"),
+                (SortColumn::SelfTime, "Self ms"),
+                (SortColumn::Total, "Total ms"),
+                (SortColumn::Gpu, "GPU ms"),
+            ] {
+                let arrow = match (sort == column, descending) {
+                    (true, true) => " ⏷",
+                    (true, false) => " ⏶",
+                    _ => "",
+                };
+                if ui.selectable_label(sort == column, format!("{}{}", label, arrow)).clicked() {
+                    if sort == column {
+                        descending = !descending;
+                    } else {
+                        sort = column;
+                        descending = column != SortColumn::Name;
+                    }
+                }
+            }
+            ui.end_row();
+
+            for stat in stats.iter().take(MAX_ROWS) {
+                ui.label(RichText::new(&stat.name).monospace().color(Color32::WHITE));
+                ui.label(RichText::new(format!("{:.1}", stat.calls)).color(Color32::LIGHT_GRAY));
+                ui.label(RichText::new(format!("{:.3}", stat.self_time.as_secs_f64() * 1000.0)).color(Color32::LIGHT_GRAY));
+                ui.label(RichText::new(format!("{:.3}", stat.total.as_secs_f64() * 1000.0)).color(Color32::WHITE));
+                let gpu_ms = gpu_time_of(&stat.name)
+                    .map(|duration| format!("{:.3}", duration.as_secs_f64() * 1000.0))
+                    .unwrap_or_else(|| "–".to_string());
+                ui.label(RichText::new(gpu_ms).color(Color32::from_rgb(150, 190, 255)));
+                ui.end_row();
+            }
+        });
+
+    ui.data_mut(|d| d.insert_temp(sort_id, (sort, descending)));
+    draw_pools(ui);
+}
+
+/// Entity pools created by scripts (`Pool.create`), while any exist
+fn draw_pools(ui: &mut egui::Ui) {
+    let pools = profiler::pools::snapshot();
+    if pools.is_empty() {
+        return;
+    }
+    ui.add_space(6.0);
+    ui.label(RichText::new("Pools").strong().color(Color32::WHITE));
+    egui::Grid::new("profiler_overlay_pools")
+        .num_columns(5)
+        .striped(true)
+        .spacing([12.0, 2.0])
+        .show(ui, |ui| {
+            for label in ["Pool", "Active", "Size", "Peak", "Spawns"] {
+                ui.label(RichText::new(label).color(Color32::GRAY));
+            }
+            ui.end_row();
+
+            for pool in &pools {
+                ui.label(RichText::new(&pool.name).monospace().color(Color32::WHITE));
+                ui.label(RichText::new(pool.active.to_string()).color(Color32::WHITE));
+                ui.label(RichText::new(pool.size.to_string()).color(Color32::LIGHT_GRAY));
+                ui.label(RichText::new(pool.peak_active.to_string()).color(Color32::LIGHT_GRAY));
+                let spawns = ui.label(RichText::new(pool.spawns.to_string()).color(Color32::LIGHT_GRAY));
+                if pool.grown + pool.reused + pool.exhausted > 0 {
+                    spawns.on_hover_text(format!(
+                        "{} grew the pool, {} reused a spawned instance, {} refused",
+                        pool.grown, pool.reused, pool.exhausted
+                    ));
+                }
+                ui.end_row();
+            }
+        });
+}
+
+/// GPU frame time, plus a warning when the GPU misses the 60 FPS budget while the
+/// main thread's own work fits in it
+fn draw_gpu_summary(ui: &mut egui::Ui, gpu_time: Option<Duration>) {
+    let text = match (profiler::gpu::timing_support(), gpu_time) {
+        (GpuTimingSupport::Unsupported, _) => "GPU: unsupported".to_string(),
+        (_, None) => "GPU: waiting for timings…".to_string(),
+        (_, Some(time)) => format!("GPU: {:.2} ms", time.as_secs_f64() * 1000.0),
+    };
+    ui.horizontal(|ui| {
+        ui.label(RichText::new(text).color(Color32::from_rgb(150, 190, 255)));
+
+        let Some(gpu_time) = gpu_time else {
+            return;
+        };
+        let budget = Duration::from_secs_f32(TARGET_60_MS / 1000.0);
+        if profiler::gpu::is_gpu_bound(gpu_time, cpu_busy_time(), budget) {
+            ui.label(RichText::new("⚠ GPU bound").strong().color(Color32::from_rgb(230, 80, 70)))
+                .on_hover_text("The GPU takes longer than the frame budget while the CPU's work fits in it");
+        }
+    });
+}
+
+/// Average main-thread work per frame: the top-level scopes, without the time spent
+/// waiting for the next frame or the GPU
+fn cpu_busy_time() -> Duration {
+    frame::with_history(|history| {
+        let recent: Vec<_> = history.iter().rev().take(AVERAGE_FRAMES).collect();
+        if recent.is_empty() {
+            return Duration::ZERO;
+        }
+        let busy: Duration = recent
+            .iter()
+            .flat_map(|f| f.scopes.iter().filter(|scope| scope.depth == 0 && scope.name != "render.submit"))
+            .map(|scope| scope.duration)
+            .sum();
+        busy / recent.len() as u32
+    })
+}
+
+fn sort_stats(stats: &mut [ScopeStats], column: SortColumn, descending: bool, gpu_time_of: &dyn Fn(&str) -> Option<Duration>) {
+    stats.sort_by(|a, b| {
+        let ordering = match column {
+            SortColumn::Name => a.name.cmp(&b.name),
+            SortColumn::Calls => a.calls.total_cmp(&b.calls),
+            SortColumn::SelfTime => a.self_time.cmp(&b.self_time),
+            SortColumn::Total => a.total.cmp(&b.total),
+            SortColumn::Gpu => gpu_time_of(&a.name).cmp(&gpu_time_of(&b.name)),
+        };
+        if descending { ordering.reverse() } else { ordering }
+    });
+}
+
+/// One bar per frame (room for `capacity` frames), scaled so the 30 FPS line is always visible
+pub(crate) fn draw_frame_graph(ui: &mut egui::Ui, frame_times: &[f32], capacity: usize) {
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(ui.available_width(), 60.0), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 2.0, Color32::from_black_alpha(120));
+
+    let max_ms = frame_times.iter().cloned().fold(TARGET_30_MS * 1.2, f32::max);
+    let y_for = |ms: f32| rect.bottom() - (ms / max_ms).min(1.0) * rect.height();
+    let bar_width = rect.width() / capacity as f32;
+    // Newest frame on the right edge
+    let first_x = rect.right() - bar_width * frame_times.len() as f32;
+
+    for (i, &ms) in frame_times.iter().enumerate() {
+        let color = if ms <= TARGET_60_MS + 0.5 {
+            Color32::from_rgb(90, 200, 90)
+        } else if ms <= TARGET_30_MS + 0.5 {
+            Color32::from_rgb(230, 190, 60)
+        } else {
+            Color32::from_rgb(230, 80, 70)
+        };
+        let x = first_x + i as f32 * bar_width;
+        painter.rect_filled(
+            egui::Rect::from_min_max(egui::pos2(x, y_for(ms)), egui::pos2(x + bar_width.max(1.0), rect.bottom())),
+            0.0,
+            color,
+        );
+    }
+
+    for (ms, label) in [(TARGET_60_MS, "60"), (TARGET_30_MS, "30")] {
+        let y = y_for(ms);
+        painter.hline(rect.x_range(), y, egui::Stroke::new(1.0, Color32::from_white_alpha(60)));
+        painter.text(
+            egui::pos2(rect.left() + 2.0, y - 1.0),
+            egui::Align2::LEFT_BOTTOM,
+            label,
+            egui::FontId::monospace(9.0),
+            Color32::from_white_alpha(140),
+        );
+    }
+}
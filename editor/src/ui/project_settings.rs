@@ -1,16 +1,20 @@
 use egui;
+use engine_core::project_settings::{ApplicationSettings, AspectMode, CaptureSettings, PhysicsSettings, ProjectSettings, RenderingSettings, ScriptingSettings, MAX_COLLISION_LAYERS};
 use std::path::PathBuf;
 
-/// Renders the Project Settings window with General and Play Mode sections.
+/// Renders the Project Settings window: General and Play Mode (project.json), Application,
+/// Physics, Rendering, Scripting and Input (settings.json), Saved Data and Autosave.
 ///
 /// # Parameters
 /// - `ctx`: The egui context for rendering
 /// - `show_project_settings`: Boolean flag to control window visibility
+/// - `settings`: The open project's settings.json, edited in place
 /// - `project_path`: Optional path to the currently open project
 /// - `get_scene_files_fn`: Closure function to retrieve scene files from a path
 pub fn render_project_settings(
     ctx: &egui::Context,
     show_project_settings: &mut bool,
+    settings: &mut ProjectSettings,
     project_path: &Option<PathBuf>,
     get_scene_files_fn: impl Fn(&std::path::Path) -> Vec<String>,
 ) {
@@ -24,6 +28,9 @@ pub fn render_project_settings(
                 if let Some(path) = project_path {
                     use engine_core::project::ProjectManager;
 
+                    // settings.json sections edit the editor's copy, saved when anything changes
+                    let before = settings.clone();
+
                     ui.heading("Project Configuration");
                     ui.separator();
 
@@ -48,7 +55,7 @@ pub fn render_project_settings(
                     // Play Mode Section
                     ui.collapsing("🎮 Play Mode", |ui| {
                         ui.add_space(5.0);
-                        ui.label(egui::RichText::new("Configure startup scenes:").strong());
+                        ui.label(egui::RichText::new("Configure the editor startup scene (the game's is under Application):").strong());
                         ui.add_space(10.0);
 
                         // Editor Startup Scene
@@ -103,62 +110,69 @@ pub fn render_project_settings(
                             }
                         }
 
-                        ui.add_space(15.0);
+                        ui.add_space(10.0);
+                    });
 
-                        // Game Startup Scene
-                        ui.label(egui::RichText::new("Game Startup Scene").strong());
-                        ui.label("Scene to load when running exported game");
-                        ui.add_space(5.0);
+                    ui.add_space(10.0);
 
-                        let mut current_game_scene = String::new();
-                        if let Ok(pm) = ProjectManager::new() {
-                            if let Ok(Some(scene)) = pm.get_game_startup_scene(path) {
-                                current_game_scene = scene.to_string_lossy().to_string();
-                            }
-                        }
+                    // Application Section (settings.json)
+                    ui.collapsing("🪟 Application", |ui| {
+                        render_application_section(ui, &mut settings.application, &get_scene_files_fn(path));
+                    });
 
-                        let mut new_game_scene = current_game_scene.clone();
+                    // Physics Section (settings.json)
+                    ui.collapsing("⚛ Physics", |ui| {
+                        render_physics_section(ui, &mut settings.physics);
+                    });
 
-                        // Get all .scene files in project
-                        let scene_files = get_scene_files_fn(path);
+                    // Rendering Section (settings.json)
+                    ui.collapsing("🖥 Rendering", |ui| {
+                        render_rendering_section(ui, &mut settings.rendering);
+                    });
 
-                        // Dropdown to select scene
-                        let selected_text = if new_game_scene.is_empty() {
-                            "(None)".to_string()
-                        } else {
-                            new_game_scene.clone()
-                        };
+                    // Scripting Section (settings.json)
+                    ui.collapsing("📜 Scripting", |ui| {
+                        render_scripting_section(ui, &mut settings.scripting, path);
+                    });
 
-                        egui::ComboBox::from_label("")
-                            .selected_text(&selected_text)
-                            .width(400.0)
-                            .show_ui(ui, |ui| {
-                                // None option
-                                if ui.selectable_value(&mut new_game_scene, String::new(), "(None)").clicked() {
-                                    new_game_scene.clear();
-                                }
+                    // Capture Section (settings.json)
+                    ui.collapsing("📷 Capture", |ui| {
+                        render_capture_section(ui, &mut settings.capture);
+                    });
 
-                                ui.separator();
+                    // Saved Data Section (Save API / PlayerPrefs)
+                    ui.collapsing("💾 Saved Data", |ui| {
+                        render_saved_data_section(ui, path);
+                    });
 
-                                // All .scene files
-                                for scene_file in scene_files {
-                                    ui.selectable_value(&mut new_game_scene, scene_file.clone(), &scene_file);
-                                }
-                            });
+                    // Input Section (action mapping in input.json)
+                    ui.collapsing("🎮 Input", |ui| {
+                        ui.horizontal(|ui| {
+                            ui.label("Action Map File:");
+                            ui.text_edit_singleline(&mut settings.input.action_map);
+                        });
+                        ui.checkbox(&mut settings.input.ui_blocks_game_input, "UI Blocks Game Input")
+                            .on_hover_text("Clicks on interactable UI and typing into UI text fields don't reach gameplay input");
+                        ui.add_space(5.0);
+                        super::input_settings::render_input_settings_section(ui, path);
+                    });
+
+                    // Autosave Section (.kiro/settings/autosave.json)
+                    ui.collapsing("⏱ Autosave", |ui| {
+                        render_autosave_section(ui, path);
+                    });
 
-                        if new_game_scene != current_game_scene {
+                    if *settings != before {
+                        if let Err(e) = settings.save(path) {
+                            log::warn!("Failed to save project settings: {}", e);
+                        }
+                        // Export and older tools read the game startup scene from project.json
+                        if settings.application.startup_scene != before.application.startup_scene {
                             if let Ok(pm) = ProjectManager::new() {
-                                let scene_path = if new_game_scene.is_empty() {
-                                    None
-                                } else {
-                                    Some(std::path::PathBuf::from(&new_game_scene))
-                                };
-                                let _ = pm.set_game_startup_scene(path, scene_path);
+                                let _ = pm.set_game_startup_scene(path, settings.application.startup_scene.as_ref().map(PathBuf::from));
                             }
                         }
-
-                        ui.add_space(10.0);
-                    });
+                    }
 
                 } else {
                     ui.label("No project open.");
@@ -172,3 +186,433 @@ pub fn render_project_settings(
             });
     }
 }
+
+/// Startup scene and player window (settings.json)
+fn render_application_section(ui: &mut egui::Ui, application: &mut ApplicationSettings, scene_files: &[String]) {
+    ui.add_space(5.0);
+    egui::Grid::new("project_settings_application").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+        ui.label("Startup Scene:").on_hover_text("Scene the game starts in (player and export)");
+        let selected_text = application.startup_scene.clone().unwrap_or_else(|| "(None)".to_string());
+        egui::ComboBox::from_id_salt("project_settings_startup_scene")
+            .selected_text(selected_text)
+            .width(300.0)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut application.startup_scene, None, "(None)");
+                ui.separator();
+                for scene_file in scene_files {
+                    ui.selectable_value(&mut application.startup_scene, Some(scene_file.clone()), scene_file);
+                }
+            });
+        ui.end_row();
+
+        ui.label("Window Title:");
+        ui.text_edit_singleline(&mut application.window_title);
+        ui.end_row();
+
+        ui.label("Window Size:");
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut application.window_width).range(320..=7680).suffix(" px"));
+            ui.label("×");
+            ui.add(egui::DragValue::new(&mut application.window_height).range(240..=4320).suffix(" px"));
+        });
+        ui.end_row();
+
+        ui.label("Resizable:");
+        ui.checkbox(&mut application.resizable, "");
+        ui.end_row();
+    });
+    ui.add_space(5.0);
+}
+
+/// Gravity, timestep, world bounds and the collision layer matrix (settings.json)
+fn render_physics_section(ui: &mut egui::Ui, physics: &mut PhysicsSettings) {
+    ui.add_space(5.0);
+    egui::Grid::new("project_settings_physics").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+        ui.label("Gravity:").on_hover_text("Downward acceleration; applies from the next play session");
+        ui.add(egui::DragValue::new(&mut physics.gravity).speed(1.0).suffix(" u/s²"));
+        ui.end_row();
+
+        ui.label("Fixed Timestep:");
+        let mut hz = 1.0 / physics.fixed_timestep.max(0.001);
+        if ui.add(egui::DragValue::new(&mut hz).range(10.0..=240.0).speed(1.0).suffix(" Hz")).changed() {
+            physics.fixed_timestep = 1.0 / hz;
+        }
+        ui.end_row();
+
+        ui.label("World Bounds:").on_hover_text("Non-kinematic bodies are kept inside (simple physics backend)");
+        ui.checkbox(&mut physics.world_bounds.enabled, "Enabled");
+        ui.end_row();
+
+        if physics.world_bounds.enabled {
+            ui.label("  Min / Max:");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut physics.world_bounds.min[0]).prefix("x "));
+                ui.add(egui::DragValue::new(&mut physics.world_bounds.min[1]).prefix("y "));
+                ui.label("/");
+                ui.add(egui::DragValue::new(&mut physics.world_bounds.max[0]).prefix("x "));
+                ui.add(egui::DragValue::new(&mut physics.world_bounds.max[1]).prefix("y "));
+            });
+            ui.end_row();
+        }
+    });
+
+    ui.add_space(8.0);
+    ui.label(egui::RichText::new("Collision Layers").strong());
+    let mut remove_layer = None;
+    for (index, name) in physics.layers.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}", index));
+            ui.text_edit_singleline(name);
+            if index > 0 && ui.small_button("🗑").on_hover_text("Remove layer").clicked() {
+                remove_layer = Some(index);
+            }
+        });
+    }
+    if let Some(index) = remove_layer {
+        physics.layers.remove(index);
+        physics.layer_matrix.truncate(physics.layers.len());
+    }
+    if physics.layers.len() < MAX_COLLISION_LAYERS && ui.button("➕ Add Layer").clicked() {
+        physics.layers.push(format!("Layer {}", physics.layers.len()));
+    }
+
+    // Lower-triangle matrix: row layer collides with column layer
+    if physics.layers.len() > 1 {
+        ui.add_space(5.0);
+        let names = physics.layers.clone();
+        egui::Grid::new("project_settings_layer_matrix").spacing([4.0, 2.0]).show(ui, |ui| {
+            ui.label("");
+            for name in &names {
+                ui.label(egui::RichText::new(name).small());
+            }
+            ui.end_row();
+            for (a, name) in names.iter().enumerate() {
+                ui.label(egui::RichText::new(name).small());
+                for (b, other) in names.iter().enumerate().take(a + 1) {
+                    let mut collide = physics.layers_collide(a, b);
+                    if ui.checkbox(&mut collide, "").on_hover_text(format!("{} ↔ {}", name, other)).changed() {
+                        physics.set_layers_collide(a, b, collide);
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    }
+    ui.add_space(5.0);
+}
+
+/// Clear color, vsync, frame pacing, reference resolution, safe area, texture streaming and sorting layers (settings.json)
+fn render_rendering_section(ui: &mut egui::Ui, rendering: &mut RenderingSettings) {
+    ui.add_space(5.0);
+    egui::Grid::new("project_settings_rendering").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+        ui.label("Clear Color:");
+        ui.color_edit_button_rgba_unmultiplied(&mut rendering.clear_color);
+        ui.end_row();
+
+        ui.label("VSync:");
+        ui.checkbox(&mut rendering.vsync, "");
+        ui.end_row();
+
+        ui.label("Target FPS:").on_hover_text("0 = unlimited");
+        ui.add(egui::DragValue::new(&mut rendering.target_fps).range(0..=480));
+        ui.end_row();
+
+        ui.label("Background FPS:").on_hover_text("Frame rate while the window is unfocused or minimized (0 = no throttling)");
+        ui.add(egui::DragValue::new(&mut rendering.background_fps).range(0..=120));
+        ui.end_row();
+
+        ui.label("Max Frame Delta:")
+            .on_hover_text("Longest frame the game sees; slower frames slow the game down instead of skipping ahead");
+        ui.add(egui::DragValue::new(&mut rendering.max_delta).speed(0.005).range(0.01..=1.0).suffix(" s"));
+        ui.end_row();
+
+        ui.label("Reference Resolution:");
+        ui.horizontal(|ui| {
+            ui.add(egui::DragValue::new(&mut rendering.reference_resolution[0]).range(320..=7680).suffix(" px"));
+            ui.label("×");
+            ui.add(egui::DragValue::new(&mut rendering.reference_resolution[1]).range(240..=4320).suffix(" px"));
+        });
+        ui.end_row();
+
+        ui.label("Aspect Mode:")
+            .on_hover_text("How the game fits windows whose aspect differs from the reference resolution");
+        egui::ComboBox::from_id_salt("project_settings_aspect_mode")
+            .selected_text(rendering.aspect_mode.label())
+            .show_ui(ui, |ui| {
+                for mode in AspectMode::ALL {
+                    ui.selectable_value(&mut rendering.aspect_mode, mode, mode.label());
+                }
+            });
+        ui.end_row();
+
+        if rendering.aspect_mode == AspectMode::FixedAspect {
+            ui.label("Letterbox Color:");
+            ui.color_edit_button_rgba_unmultiplied(&mut rendering.letterbox_color);
+            ui.end_row();
+        }
+
+        ui.label("Simulate Safe Area:")
+            .on_hover_text("Lay out UI elements with a Safe Area component as if the device had notches/rounded corners");
+        ui.checkbox(&mut rendering.safe_area.simulate, "");
+        ui.end_row();
+
+        if rendering.safe_area.simulate {
+            // Normalized, bottom-left origin like camera viewport rects
+            let [x, y, width, height] = &mut rendering.safe_area.rect;
+            ui.label("Safe Area Position:");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(x).range(0.0..=1.0).speed(0.005).prefix("x: "));
+                ui.add(egui::DragValue::new(y).range(0.0..=1.0).speed(0.005).prefix("y: "));
+            });
+            ui.end_row();
+
+            ui.label("Safe Area Size:");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(width).range(0.0..=1.0).speed(0.005).prefix("w: "));
+                ui.add(egui::DragValue::new(height).range(0.0..=1.0).speed(0.005).prefix("h: "));
+            });
+            ui.end_row();
+        }
+
+        ui.label("Texture Streaming Size:")
+            .on_hover_text("Textures with a longer side show a small preview first and stream in full size (0 = off)");
+        ui.add(egui::DragValue::new(&mut rendering.texture_streaming_size).range(0..=16384).speed(64.0).suffix(" px"));
+        ui.end_row();
+
+        ui.label("Texture Budget:")
+            .on_hover_text("Past this much texture memory, streamed textures stay at their preview size (0 = unlimited)");
+        ui.add(egui::DragValue::new(&mut rendering.texture_budget_mb).range(0..=16384).speed(16.0).suffix(" MB"));
+        ui.end_row();
+    });
+
+    // Top of the list draws first; Default can be moved but not renamed or removed
+    ui.add_space(8.0);
+    ui.label(egui::RichText::new("Sorting Layers").strong())
+        .on_hover_text("Sprites and tilemap layers draw in this order, then by Order in Layer");
+    let mut remove_layer = None;
+    let mut move_up = None;
+    let layer_count = rendering.sorting_layers.len();
+    for (index, name) in rendering.sorting_layers.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}", index));
+            if name == ecs::sorting::DEFAULT_SORTING_LAYER {
+                ui.label(name.as_str());
+            } else {
+                ui.text_edit_singleline(name);
+                if ui.small_button("🗑").on_hover_text("Remove layer").clicked() {
+                    remove_layer = Some(index);
+                }
+            }
+            if index > 0 && ui.small_button("⬆").on_hover_text("Draw earlier").clicked() {
+                move_up = Some(index);
+            }
+            if index + 1 < layer_count && ui.small_button("⬇").on_hover_text("Draw later").clicked() {
+                move_up = Some(index + 1);
+            }
+        });
+    }
+    if let Some(index) = remove_layer {
+        rendering.sorting_layers.remove(index);
+    }
+    if let Some(index) = move_up {
+        rendering.sorting_layers.swap(index - 1, index);
+    }
+    if ui.button("➕ Add Sorting Layer").clicked() {
+        rendering.sorting_layers.push(format!("Layer {}", rendering.sorting_layers.len()));
+    }
+    ui.add_space(5.0);
+}
+
+/// Autosave interval/retention; the editor picks up changes within a few seconds.
+/// Script time budget warnings (settings.json)
+fn render_scripting_section(ui: &mut egui::Ui, scripting: &mut ScriptingSettings, project_path: &std::path::Path) {
+    ui.add_space(5.0);
+    egui::Grid::new("project_settings_scripting").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+        ui.label("Script Budget:")
+            .on_hover_text("Warn when one entity's script takes longer than this per frame; 0 = off");
+        ui.add(egui::DragValue::new(&mut scripting.budget_ms).range(0.0..=100.0).speed(0.1).suffix(" ms"));
+        ui.end_row();
+
+        ui.label("Warn After:").on_hover_text("Consecutive frames over budget before the warning");
+        ui.add(egui::DragValue::new(&mut scripting.budget_frames).range(1..=3600).suffix(" frames"));
+        ui.end_row();
+
+        ui.label("Vec2 Values:")
+            .on_hover_text("get_position / get_velocity return native Vec2 values; off keeps the {x, y, z} tables older scripts use");
+        ui.checkbox(&mut scripting.vec2_values, "");
+        ui.end_row();
+    });
+
+    // require("a.b") looks in scripts/a/b.lua first, then in each of these folders
+    ui.add_space(8.0);
+    ui.label(egui::RichText::new("Library Paths").strong())
+        .on_hover_text("Project folders require() searches after scripts/");
+    let mut remove_path = None;
+    for (index, path) in scripting.lib_paths.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(path).hint_text("lib").desired_width(200.0));
+            if ui.small_button("🗑").on_hover_text("Remove path").clicked() {
+                remove_path = Some(index);
+            }
+        });
+    }
+    if let Some(index) = remove_path {
+        scripting.lib_paths.remove(index);
+    }
+    if ui.button("➕ Add Library Path").clicked() {
+        scripting.lib_paths.push("lib".to_string());
+    }
+
+    ui.add_space(8.0);
+    if ui.button("📝 Write Autocomplete Stubs")
+        .on_hover_text(format!("Write the engine Lua library (require(\"engine.vec2\") ...) to {}/ for Lua language servers", LUA_STUBS_DIR))
+        .clicked()
+    {
+        match write_lua_stubs(project_path) {
+            Ok(count) => log::info!("Wrote {} Lua stub files to {}", count, project_path.join(LUA_STUBS_DIR).display()),
+            Err(e) => log::warn!("Failed to write Lua stubs: {}", e),
+        }
+    }
+}
+
+/// Screenshot cropping and the GIF buffer (settings.json)
+fn render_capture_section(ui: &mut egui::Ui, capture: &mut CaptureSettings) {
+    ui.add_space(5.0);
+    egui::Grid::new("project_settings_capture").num_columns(2).spacing([10.0, 6.0]).show(ui, |ui| {
+        ui.label("Crop Letterbox:").on_hover_text("Screenshots keep only the game area, without the bars");
+        ui.checkbox(&mut capture.crop_letterbox, "");
+        ui.end_row();
+
+        ui.label("Record GIF:").on_hover_text("Keep the last few seconds so F9 can save them as a GIF");
+        ui.checkbox(&mut capture.gif_enabled, "");
+        ui.end_row();
+
+        if capture.gif_enabled {
+            ui.label("GIF Length:");
+            ui.add(egui::DragValue::new(&mut capture.gif_seconds).range(1.0..=30.0).speed(0.1).suffix(" s"));
+            ui.end_row();
+
+            ui.label("GIF Frame Rate:");
+            ui.add(egui::DragValue::new(&mut capture.gif_fps).range(1..=30).suffix(" fps"));
+            ui.end_row();
+
+            ui.label("GIF Width:").on_hover_text("Frames are scaled down to this width; memory grows with its square");
+            ui.add(egui::DragValue::new(&mut capture.gif_width).range(64..=1280).suffix(" px"));
+            ui.end_row();
+        }
+    });
+}
+
+/// Hidden so exports skip it
+const LUA_STUBS_DIR: &str = ".lua_stubs";
+
+/// Engine library stubs, plus a .luarc.json pointing the language server at them when
+/// the project has none
+fn write_lua_stubs(project_path: &std::path::Path) -> std::io::Result<usize> {
+    let written = script::modules::write_library_stubs(&project_path.join(LUA_STUBS_DIR))?;
+    let luarc = project_path.join(".luarc.json");
+    if !luarc.exists() {
+        let config = serde_json::json!({
+            "runtime.version": "Lua 5.4",
+            "runtime.path": ["scripts/?.lua", "scripts/?/init.lua", "?.lua", "?/init.lua"],
+            "workspace.library": [LUA_STUBS_DIR],
+        });
+        std::fs::write(&luarc, serde_json::to_string_pretty(&config).unwrap_or_default())?;
+    }
+    Ok(written.len())
+}
+
+fn render_autosave_section(ui: &mut egui::Ui, project_path: &std::path::Path) {
+    use crate::autosave::AutoSaveSettings;
+
+    let mut settings = AutoSaveSettings::load(project_path);
+    let before = settings.clone();
+
+    ui.checkbox(&mut settings.enabled, "Autosave modified scenes (not while playing)");
+    ui.horizontal(|ui| {
+        ui.label("Interval:");
+        let mut minutes = settings.interval_seconds as f32 / 60.0;
+        if ui.add(egui::DragValue::new(&mut minutes).range(0.5..=60.0).speed(0.1).suffix(" min")).changed() {
+            settings.interval_seconds = (minutes * 60.0).round() as u64;
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("Keep last:");
+        ui.add(egui::DragValue::new(&mut settings.keep).range(1..=50).suffix(" autosaves"));
+    });
+    ui.label(egui::RichText::new("Written next to the scene as <scene>~<time>.autosave").small().color(egui::Color32::GRAY));
+
+    if settings != before {
+        if let Err(e) = settings.save(project_path) {
+            log::warn!("{}", e);
+        }
+    }
+}
+
+/// Shows the contents of the project's Save file with per-key and bulk delete.
+fn render_saved_data_section(ui: &mut egui::Ui, project_path: &std::path::Path) {
+    use engine_core::project::ProjectManager;
+    use engine_core::save_data::SaveData;
+
+    let project_name = ProjectManager::get_project_name(project_path);
+    let mut save_data = SaveData::for_project(&project_name);
+
+    ui.add_space(5.0);
+    if let Some(save_path) = save_data.path() {
+        ui.label(egui::RichText::new(format!("File: {}", save_path.display())).small());
+    }
+
+    if let Err(e) = save_data.load() {
+        ui.colored_label(egui::Color32::RED, format!("Failed to read save data: {}", e));
+        return;
+    }
+
+    if save_data.is_empty() {
+        ui.label("No saved data for this project.");
+        return;
+    }
+
+    let mut key_to_delete: Option<String> = None;
+    egui::ScrollArea::vertical()
+        .id_salt("saved_data_scroll")
+        .max_height(200.0)
+        .show(ui, |ui| {
+            egui::Grid::new("saved_data_grid")
+                .num_columns(4)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label(egui::RichText::new("Key").strong());
+                    ui.label(egui::RichText::new("Type").strong());
+                    ui.label(egui::RichText::new("Value").strong());
+                    ui.label("");
+                    ui.end_row();
+
+                    for (key, value) in save_data.iter() {
+                        ui.label(key);
+                        ui.label(value.type_name());
+                        ui.label(value.to_string());
+                        if ui.small_button("🗑").on_hover_text("Delete key").clicked() {
+                            key_to_delete = Some(key.clone());
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+    let mut changed = false;
+    if let Some(key) = key_to_delete {
+        changed = save_data.delete(&key);
+    }
+
+    ui.add_space(5.0);
+    if ui.button("Clear All Saved Data").clicked() {
+        save_data.clear();
+        changed = true;
+    }
+
+    if changed {
+        if let Err(e) = save_data.save() {
+            log::error!("Failed to write save data: {}", e);
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Gizmo Metrics
+//!
+//! Sizes of the transform gizmo and the component handles, shared by their rendering
+//! and their hit-tests so what is drawn is exactly what can be grabbed.
+//!
+//! Sizes are defined in physical pixels: a base size (for a 100% display) times the
+//! display's scale factor (egui's pixels_per_point) and the Handle Size preference.
+//! The painter and pointer work in egui points, so every size is converted back with
+//! pixels_per_point before use. Drags convert points to world units with the scene
+//! zoom alone, so the same drag moves an entity the same distance on any monitor.
+
+use egui;
+use serde::{Deserialize, Serialize};
+
+/// Transform gizmo axis length, physical pixels at 100%
+const GIZMO_LENGTH_PX: f32 = 80.0;
+/// Transform gizmo handle radius, physical pixels at 100%
+const HANDLE_RADIUS_PX: f32 = 10.0;
+/// How far around a move/scale handle (3D) or the gizmo center still grabs it
+const PICK_RADIUS_PX: f32 = 25.0;
+/// 2D move handles are picked a little tighter: their arrows sit closer together
+const PICK_RADIUS_2D_MOVE_PX: f32 = 18.0;
+/// Half width of the band around a rotation ring that grabs it (2D)
+const RING_PICK_WIDTH_PX: f32 = 30.0;
+/// Distance from a 3D rotation ring that still grabs it
+const RING_PICK_WIDTH_3D_PX: f32 = 10.0;
+/// Component handle (collider, camera, trigger zone) square size
+const COMPONENT_HANDLE_PX: f32 = 8.0;
+/// Extra pick distance around a component handle
+const COMPONENT_PICK_MARGIN_PX: f32 = 3.0;
+
+/// Handle Size preference bounds (1.0 = default)
+pub const MIN_HANDLE_SCALE: f32 = 0.5;
+pub const MAX_HANDLE_SCALE: f32 = 3.0;
+
+/// View > Gizmos > Handle Size, saved to .kiro/settings/gizmos.json
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GizmoPreferences {
+    /// Multiplier on every handle and pick size (accessibility)
+    #[serde(default = "default_handle_scale")]
+    pub handle_scale: f32,
+}
+
+fn default_handle_scale() -> f32 {
+    1.0
+}
+
+impl Default for GizmoPreferences {
+    fn default() -> Self {
+        Self { handle_scale: default_handle_scale() }
+    }
+}
+
+impl GizmoPreferences {
+    /// Load from file
+    pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
+        let path = std::path::Path::new(".kiro/settings/gizmos.json");
+        if path.exists() {
+            let contents = std::fs::read_to_string(path)?;
+            Ok(serde_json::from_str(&contents)?)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    /// Save to file
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::path::Path::new(".kiro/settings");
+        std::fs::create_dir_all(dir)?;
+
+        let path = dir.join("gizmos.json");
+        let contents = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+}
+
+/// Gizmo sizes for one frame of the scene view
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GizmoMetrics {
+    pixels_per_point: f32,
+    handle_scale: f32,
+}
+
+impl Default for GizmoMetrics {
+    fn default() -> Self {
+        Self::new(1.0, 1.0)
+    }
+}
+
+impl GizmoMetrics {
+    pub fn new(pixels_per_point: f32, handle_scale: f32) -> Self {
+        Self {
+            pixels_per_point: if pixels_per_point > 0.0 { pixels_per_point } else { 1.0 },
+            handle_scale: handle_scale.clamp(MIN_HANDLE_SCALE, MAX_HANDLE_SCALE),
+        }
+    }
+
+    pub fn from_ctx(ctx: &egui::Context, preferences: &GizmoPreferences) -> Self {
+        Self::new(ctx.pixels_per_point(), preferences.handle_scale)
+    }
+
+    pub fn pixels_per_point(&self) -> f32 {
+        self.pixels_per_point
+    }
+
+    /// Physical size of a base size
+    pub fn physical(&self, base_px: f32) -> f32 {
+        base_px * self.pixels_per_point * self.handle_scale
+    }
+
+    /// Physical pixels to egui points
+    pub fn to_points(&self, physical: f32) -> f32 {
+        physical / self.pixels_per_point
+    }
+
+    fn points(&self, base_px: f32) -> f32 {
+        self.to_points(self.physical(base_px))
+    }
+
+    /// Transform gizmo axis length (points)
+    pub fn gizmo_length(&self) -> f32 {
+        self.points(GIZMO_LENGTH_PX)
+    }
+
+    /// Transform gizmo handle radius (points)
+    pub fn handle_radius(&self) -> f32 {
+        self.points(HANDLE_RADIUS_PX)
+    }
+
+    /// Pick radius of the 3D move/scale handles and the 2D scale handles (points)
+    pub fn pick_radius(&self) -> f32 {
+        self.points(PICK_RADIUS_PX)
+    }
+
+    /// Pick radius of the 2D move handles (points)
+    pub fn move_pick_radius_2d(&self) -> f32 {
+        self.points(PICK_RADIUS_2D_MOVE_PX)
+    }
+
+    /// Radius of the 2D rotation ring (points)
+    pub fn rotate_ring_radius(&self) -> f32 {
+        self.gizmo_length() * 0.8
+    }
+
+    /// Half width of the band that grabs the 2D rotation ring (points)
+    pub fn ring_pick_width(&self) -> f32 {
+        self.points(RING_PICK_WIDTH_PX)
+    }
+
+    /// Distance from a 3D rotation ring that grabs it (points)
+    pub fn ring_pick_width_3d(&self) -> f32 {
+        self.points(RING_PICK_WIDTH_3D_PX)
+    }
+
+    /// Component handle square size (points)
+    pub fn component_handle_size(&self) -> f32 {
+        self.points(COMPONENT_HANDLE_PX)
+    }
+
+    /// Pick radius of a component handle (points)
+    pub fn component_pick_radius(&self) -> f32 {
+        self.component_handle_size() / 2.0 + self.points(COMPONENT_PICK_MARGIN_PX)
+    }
+
+    /// Ends of the 2D X and Y axis handles of a gizmo at `center` rotated by `rotation`
+    /// (radians, counter-clockwise; screen Y points down)
+    pub fn axis_ends_2d(&self, center: egui::Pos2, rotation: f32) -> (egui::Pos2, egui::Pos2) {
+        let length = self.gizmo_length();
+        let x_end = center + egui::vec2(rotation.cos(), -rotation.sin()) * length;
+        let y_end = center + egui::vec2(-rotation.sin(), -rotation.cos()) * length;
+        (x_end, y_end)
+    }
+}
+
+/// Whether `pointer` is within `radius` of a handle at `center`
+pub fn point_in_handle(center: egui::Pos2, radius: f32, pointer: egui::Pos2) -> bool {
+    center.distance(pointer) <= radius
+}
+
+/// Whether `pointer` is on a ring (within `half_width` of its circle) or inside it
+pub fn point_on_ring(center: egui::Pos2, radius: f32, half_width: f32, pointer: egui::Pos2) -> bool {
+    let distance = center.distance(pointer);
+    (distance - radius).abs() <= half_width || distance < radius
+}
+
+/// Screen drag (points, Y down) to a world-space move in the 2D view. Points are what
+/// `zoom` is measured in, so the display scale factor doesn't enter into it.
+pub fn drag_to_world(delta: egui::Vec2, zoom: f32) -> glam::Vec2 {
+    glam::Vec2::new(delta.x, -delta.y) / zoom.max(f32::EPSILON)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCALES: [f32; 3] = [1.0, 1.5, 2.0];
+
+    /// A pointer `physical` pixels to the right of `center`
+    fn pointer_at(metrics: &GizmoMetrics, center: egui::Pos2, physical: f32) -> egui::Pos2 {
+        center + egui::vec2(metrics.to_points(physical), 0.0)
+    }
+
+    #[test]
+    fn test_handle_hit_area_scales_with_the_display() {
+        let center = egui::pos2(400.0, 300.0);
+        for scale in SCALES {
+            let metrics = GizmoMetrics::new(scale, 1.0);
+            // Same size in points, so the handle covers more physical pixels on a denser display
+            assert_eq!(metrics.pick_radius(), PICK_RADIUS_PX);
+            let radius_px = PICK_RADIUS_PX * scale;
+            assert!(point_in_handle(center, metrics.pick_radius(), pointer_at(&metrics, center, radius_px - 0.5)));
+            assert!(!point_in_handle(center, metrics.pick_radius(), pointer_at(&metrics, center, radius_px + 0.5)));
+
+            let radius_px = metrics.physical(COMPONENT_HANDLE_PX) / 2.0 + COMPONENT_PICK_MARGIN_PX * scale;
+            assert!(point_in_handle(center, metrics.component_pick_radius(), pointer_at(&metrics, center, radius_px - 0.5)));
+            assert!(!point_in_handle(center, metrics.component_pick_radius(), pointer_at(&metrics, center, radius_px + 0.5)));
+        }
+    }
+
+    #[test]
+    fn test_ring_and_axis_handles_match_their_drawing() {
+        let center = egui::pos2(100.0, 100.0);
+        for scale in SCALES {
+            let metrics = GizmoMetrics::new(scale, 1.0);
+            let ring = metrics.rotate_ring_radius();
+            let width = metrics.ring_pick_width();
+            assert!(point_on_ring(center, ring, width, center + egui::vec2(0.0, ring + width - 0.1)));
+            assert!(!point_on_ring(center, ring, width, center + egui::vec2(0.0, ring + width + 0.1)));
+
+            // Rotated a quarter turn, X points up the screen and Y to the left
+            let (x_end, y_end) = metrics.axis_ends_2d(center, std::f32::consts::FRAC_PI_2);
+            assert!(point_in_handle(center + egui::vec2(0.0, -metrics.gizmo_length()), metrics.handle_radius(), x_end));
+            assert!(point_in_handle(center + egui::vec2(-metrics.gizmo_length(), 0.0), metrics.handle_radius(), y_end));
+        }
+    }
+
+    #[test]
+    fn test_handle_size_preference() {
+        let metrics = GizmoMetrics::new(1.5, 2.0);
+        assert_eq!(metrics.handle_radius(), HANDLE_RADIUS_PX * 2.0);
+        assert_eq!(metrics.physical(HANDLE_RADIUS_PX), HANDLE_RADIUS_PX * 3.0);
+        assert_eq!(GizmoMetrics::new(1.0, 100.0), GizmoMetrics::new(1.0, MAX_HANDLE_SCALE));
+        assert_eq!(GizmoMetrics::new(0.0, 1.0), GizmoMetrics::default());
+    }
+
+    #[test]
+    fn test_drag_moves_the_same_world_distance_at_any_scale() {
+        let zoom = 50.0;
+        for scale in SCALES {
+            let metrics = GizmoMetrics::new(scale, 1.0);
+            // A 10 pixel drag (on a 100% display) arrives as 10 points at any scale factor
+            let delta = egui::vec2(metrics.to_points(10.0 * scale), metrics.to_points(-10.0 * scale));
+            let moved = drag_to_world(delta, zoom);
+            assert!((moved - glam::Vec2::new(0.2, 0.2)).length() < 1e-6, "{} at {}x", moved, scale);
+        }
+    }
+}
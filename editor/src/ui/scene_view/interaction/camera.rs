@@ -7,34 +7,35 @@ use egui;
 use crate::SceneCamera;
 use super::super::types::*;
 
-/// Handle camera controls (pan, orbit, zoom)
+/// Handle camera controls (pan, orbit, zoom, fly). `gizmo_active` is true while a
+/// transform gizmo or handle drag is in progress: it keeps the left button.
 pub fn handle_camera_controls(
     response: &egui::Response,
     scene_camera: &mut SceneCamera,
     rect: egui::Rect,
     scene_view_mode: &SceneViewMode,
-    selected_entity: &Option<Entity>,
-    world: &World,
+    gizmo_active: bool,
+    delta_time: f32,
 ) {
+    // A gizmo drag owns the pointer until it ends
+    if gizmo_active {
+        scene_camera.stop_orbit();
+        scene_camera.stop_rotate();
+        scene_camera.stop_pan();
+        return;
+    }
+
     let is_alt_pressed = response.ctx.input(|i| i.modifiers.alt);
 
     // === UNITY-LIKE CAMERA CONTROLS ===
 
-    // Alt + Left Mouse Button - Orbit around pivot point (3D mode only)
+    // Alt + Left Mouse Button - Orbit around the current pivot (3D mode only).
+    // F moves the pivot to the selection.
     if *scene_view_mode == SceneViewMode::Mode3D && is_alt_pressed {
         if response.dragged_by(egui::PointerButton::Primary) {
             if let Some(mouse_pos) = response.interact_pointer_pos() {
                 if response.drag_started_by(egui::PointerButton::Primary) {
-                    // Get pivot point from selected entity or use camera position
-                    let pivot = if let Some(entity) = selected_entity {
-                        if let Some(transform) = world.transforms.get(entity) {
-                            glam::Vec3::new(transform.x(), transform.y(), 0.0)
-                        } else {
-                            scene_camera.position
-                        }
-                    } else {
-                        scene_camera.position
-                    };
+                    let pivot = scene_camera.position;
                     scene_camera.start_orbit(glam::Vec2::new(mouse_pos.x, mouse_pos.y), pivot);
                 } else {
                     scene_camera.update_orbit(glam::Vec2::new(mouse_pos.x, mouse_pos.y));
@@ -43,6 +44,8 @@ pub fn handle_camera_controls(
         } else {
             scene_camera.stop_orbit();
         }
+    } else {
+        scene_camera.stop_orbit();
     }
 
     // Right Mouse Button - Free look / Fly camera (3D mode)
@@ -82,9 +85,17 @@ pub fn handle_camera_controls(
         scene_camera.stop_pan();
     }
 
-    // Scroll Wheel - Zoom (Unity-like smooth zoom)
+    let is_right_mouse_down = response.ctx.input(|i| i.pointer.button_down(egui::PointerButton::Secondary));
+    let is_flying = *scene_view_mode == SceneViewMode::Mode3D && is_right_mouse_down;
+
+    // Scroll Wheel - Zoom (Unity-like smooth zoom), or fly speed while flying
     // Only zoom if mouse is hovering over the scene view
-    if response.hovered() {
+    if response.hovered() && is_flying {
+        let scroll_delta = response.ctx.input(|i| i.raw_scroll_delta.y);
+        if scroll_delta.abs() > 0.1 {
+            scene_camera.adjust_fly_speed(scroll_delta.signum());
+        }
+    } else if response.hovered() {
         let scroll_delta = response.ctx.input(|i| {
             if i.smooth_scroll_delta.y.abs() > 0.1 {
                 i.smooth_scroll_delta.y
@@ -122,98 +133,89 @@ pub fn handle_camera_controls(
     }
 
     // WASD / QE Fly Controls (3D mode)
-    // Works when:
-    // 1. Right mouse button is held (Unity-style fly mode)
-    let is_right_mouse_down = response.ctx.input(|i| i.pointer.button_down(egui::PointerButton::Secondary));
-    
-    // Only allow fly controls when Right Mouse Button is held (Unity Style)
-    if *scene_view_mode == SceneViewMode::Mode3D && is_right_mouse_down {
-        let fly_vector = response.ctx.input(|i| {
-            let base_speed = 0.5; // Base movement speed (units per frame approx)
-
-            // Speed modifiers
-            let speed_multiplier = if i.modifiers.shift {
-                4.0  // Fast mode (Shift)
-            } else if i.modifiers.ctrl {
-                0.1  // Slow mode (Ctrl)
-            } else {
-                1.0  // Normal speed
-            };
-
-            // Calculate adaptive speed based on distance from pivot (if we were strictly orbiting)
-            // But for Fly mode, we want constant speed usually, or logarithmic.
-            // Let's us a simple speed for now.
-            let final_speed = base_speed * speed_multiplier; // * (scene_camera.distance / 20.0).max(0.1);
-
-            let yaw_rad = scene_camera.rotation.to_radians();
-            let pitch_rad = scene_camera.pitch.to_radians();
-
-            // Calculate Camera Direction Vectors using Spherical Coordinates
-            // Looking direction (Forward)
-            // Note: Our coordinate system Y is UP.
-            
-            // Forward vector: Direction camera is looking (Target - Eye)
-            // Derived from View Matrix logic: Eye = Target + Offset
-            // Forward = -Offset direction
-            let forward = glam::Vec3::new(
-                -yaw_rad.cos() * pitch_rad.cos(), // X
-                -pitch_rad.sin(),                 // Y
-                -yaw_rad.sin() * pitch_rad.cos()  // Z
-            ).normalize_or_zero();
-            
-            // Right vector: Perpendicular to Forward and global Up (Y)
-            // Cross(Forward, Up) where Up=(0,1,0)
-            // Right = (sin(yaw), 0, -cos(yaw))
-            let right = glam::Vec3::new(
-                yaw_rad.sin(),
-                0.0,
-                -yaw_rad.cos()
-            ).normalize_or_zero();
-            
-            // Up vector: Global Up
-            let up = glam::Vec3::Y;
-
-            let mut movement = glam::Vec3::ZERO;
-
-            // Forward/Backward (W/S) - Moves along VIEW direction
+    // Only while Right Mouse Button is held (Unity style)
+    if is_flying {
+        let (direction, speed_multiplier) = response.ctx.input(|i| {
+            let mut direction = glam::Vec3::ZERO;
+            // Forward/Backward (W/S) - along the view direction
             if i.key_down(egui::Key::W) {
-                movement += forward;
+                direction.z += 1.0;
             }
             if i.key_down(egui::Key::S) {
-                movement -= forward;
+                direction.z -= 1.0;
             }
-
-            // Left/Right (A/D) - Moves along RIGHT vector
+            // Left/Right (A/D)
             if i.key_down(egui::Key::A) {
-                movement -= right;
+                direction.x -= 1.0;
             }
             if i.key_down(egui::Key::D) {
-                movement += right;
+                direction.x += 1.0;
             }
-            
-            // Up/Down (Q/E) - Moves along GLOBAL Y axis (Elevator)
-            // Unity uses Q=Down, E=Up
+            // Up/Down (Q/E) - along the global Y axis (Unity uses Q=Down, E=Up)
             if i.key_down(egui::Key::E) {
-                movement += up;
+                direction.y += 1.0;
             }
             if i.key_down(egui::Key::Q) {
-                movement -= up;
+                direction.y -= 1.0;
             }
 
-            movement * final_speed
+            // Speed modifiers: Shift = fast, Ctrl = slow
+            let speed_multiplier = if i.modifiers.shift {
+                4.0
+            } else if i.modifiers.ctrl {
+                0.1
+            } else {
+                1.0
+            };
+            (direction, speed_multiplier)
         });
 
-        if fly_vector.length_squared() > 0.0001 {
-            // Apply movement directly to camera pivot/position
-            scene_camera.position += fly_vector;
-            scene_camera.pivot += fly_vector; // Move pivot with camera to maintain focus distance
-            
-            // Trigger repaint
+        if direction.length_squared() > 0.0001 {
+            scene_camera.fly(direction.normalize() * speed_multiplier, delta_time);
+
+            // Keep moving while the keys are held
             response.ctx.request_repaint();
         }
     }
 }
 
+/// Center and size of the box around what an entity draws or collides with (sprite,
+/// mesh, colliders), for framing it with F in 3D. Entities with none of those get a unit
+/// box at their scale.
+pub fn entity_bounds(world: &World, entity: Entity) -> Option<(glam::Vec3, glam::Vec3)> {
+    let transform = world.transforms.get(&entity)?;
+    let position = glam::Vec3::from(transform.position);
+    let scale = glam::Vec3::from(transform.scale).abs();
+
+    let mut boxes: Vec<(glam::Vec3, glam::Vec3)> = Vec::new();
+    if let Some(sprite) = world.sprites.get(&entity) {
+        let ppu = if sprite.pixels_per_unit > 0.0 { sprite.pixels_per_unit } else { 100.0 };
+        boxes.push((position, glam::Vec3::new(sprite.width / ppu * scale.x, sprite.height / ppu * scale.y, 0.0)));
+    }
+    if world.meshes.contains_key(&entity) {
+        // Built-in meshes are unit sized
+        boxes.push((position, scale));
+    }
+    if let Some(collider) = world.colliders.get(&entity) {
+        let offset = glam::Vec3::new(collider.offset[0] * scale.x, collider.offset[1] * scale.y, 0.0);
+        let size = glam::Vec3::new(collider.get_world_width(scale.x).abs(), collider.get_world_height(scale.y).abs(), 0.0);
+        boxes.push((position + offset, size));
+    }
+    if let Some(collider) = world.colliders_3d.get(&entity) {
+        let offset = glam::Vec3::from(collider.offset) * scale;
+        boxes.push((position + offset, (glam::Vec3::from(collider.size) * scale).abs()));
+    }
+    if boxes.is_empty() {
+        return Some((position, scale));
+    }
+
+    let (min, max) = boxes.iter().fold(
+        (glam::Vec3::splat(f32::MAX), glam::Vec3::splat(f32::MIN)),
+        |(min, max), (center, size)| (min.min(*center - *size * 0.5), max.max(*center + *size * 0.5)),
+    );
+    Some(((min + max) * 0.5, max - min))
+}
+
 /// Handle clicks on gizmo axes to snap camera to preset views
 pub fn handle_gizmo_axis_clicks(
     ui: &mut egui::Ui,
@@ -254,37 +256,31 @@ pub fn handle_gizmo_axis_clicks(
     // X axis click (Right view)
     let x_response = ui.allocate_rect(x_rect, egui::Sense::click());
     if x_response.clicked() {
-        scene_camera.rotation = 90.0;  // Look from +X axis
-        scene_camera.pitch = 0.0;
+        scene_camera.set_view_right();  // Look from +X axis
     }
 
     // Y axis click (Top view)
     let y_response = ui.allocate_rect(y_rect, egui::Sense::click());
     if y_response.clicked() {
-        scene_camera.rotation = 0.0;
-        scene_camera.pitch = 90.0;  // Look from +Y axis (top)
+        scene_camera.set_view_top();  // Look from +Y axis (top)
     }
 
     // Z axis click (Front view)
     let z_response = ui.allocate_rect(z_rect, egui::Sense::click());
     if z_response.clicked() {
-        scene_camera.rotation = 0.0;   // Look from +Z axis
-        scene_camera.pitch = 0.0;
+        scene_camera.set_view_front();   // Look from +Z axis
     }
 
     // Also handle clicks on opposite directions (click axis label with Shift for opposite view)
     if ui.input(|i| i.modifiers.shift) {
         if x_response.clicked() {
-            scene_camera.rotation = -90.0; // Look from -X axis (left)
-            scene_camera.pitch = 0.0;
+            scene_camera.set_view_left(); // Look from -X axis (left)
         }
         if y_response.clicked() {
-            scene_camera.rotation = 0.0;
-            scene_camera.pitch = -90.0; // Look from -Y axis (bottom)
+            scene_camera.set_view_bottom(); // Look from -Y axis (bottom)
         }
         if z_response.clicked() {
-            scene_camera.rotation = 180.0; // Look from -Z axis (back)
-            scene_camera.pitch = 0.0;
+            scene_camera.set_view_back(); // Look from -Z axis (back)
         }
     }
 }
@@ -0,0 +1,469 @@
+//! Component Handles
+//!
+//! Drag handles for editing components directly in the 2D scene view:
+//! - Collider corner/edge handles resize, the center handle moves the offset
+//! - Camera edge handle changes the orthographic size (or FOV for perspective)
+//! - Trigger zone corner/edge handles resize the area, the center handle moves its offset
+//!
+//! Handles keep a constant pixel size at any zoom (sized by `GizmoMetrics`) and are
+//! picked before the transform gizmo and entity selection.
+
+use ecs::{World, Entity, ColliderShape, CameraProjection};
+use egui;
+use crate::SceneCamera;
+use crate::tools::snapping::{snap_value, SnapSettings};
+use super::super::gizmo_metrics::{self, GizmoMetrics};
+use super::super::rendering::gizmos::{
+    camera_plane_distance, camera_view_half_size, collider_bounds_2d, trigger_zone_bounds_2d, LocalScreenFrame,
+};
+
+/// Smallest collider extent a drag can produce (world units)
+const MIN_EXTENT: f32 = 0.01;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HandleKind {
+    /// Collider bounds handle; x/y pick the side per axis (-1, 0 or 1)
+    ColliderResize { x: i8, y: i8 },
+    /// Moves the collider offset
+    ColliderCenter,
+    /// Top edge of the camera view (orthographic size / FOV)
+    CameraSize,
+    /// Trigger zone bounds handle; x/y pick the side per axis (-1, 0 or 1)
+    ZoneResize { x: i8, y: i8 },
+    /// Moves the trigger zone offset
+    ZoneCenter,
+}
+
+impl HandleKind {
+    /// Handles that move an offset (they give way to the transform gizmo)
+    fn is_center(&self) -> bool {
+        matches!(self, HandleKind::ColliderCenter | HandleKind::ZoneCenter)
+    }
+
+    /// Handles drawn and dragged without the entity's rotation
+    fn is_axis_aligned(&self) -> bool {
+        matches!(self, HandleKind::CameraSize | HandleKind::ZoneCenter | HandleKind::ZoneResize { .. })
+    }
+}
+
+/// A handle placed on screen this frame
+#[derive(Clone, Copy, Debug)]
+pub struct Handle {
+    pub entity: Entity,
+    pub kind: HandleKind,
+    pub pos: egui::Pos2,
+}
+
+/// Handle being dragged, kept across frames
+#[derive(Clone, Copy, Debug)]
+pub struct HandleDrag {
+    pub entity: Entity,
+    pub kind: HandleKind,
+    /// Handle position minus pointer position when grabbed, so the handle doesn't jump
+    grab_offset: egui::Vec2,
+    /// Dragged value at the start (entity-local position or camera half height), for relative snapping
+    start: glam::Vec2,
+}
+
+/// Screen frame of an entity in the 2D view
+fn entity_frame(world: &World, entity: Entity, scene_camera: &SceneCamera, center: egui::Pos2) -> Option<LocalScreenFrame> {
+    let transform = world.transforms.get(&entity)?;
+    let screen = scene_camera.world_to_screen(glam::Vec3::new(transform.x(), transform.y(), 0.0));
+    Some(LocalScreenFrame {
+        origin: egui::pos2(center.x + screen.x, center.y + screen.y),
+        rotation: transform.rotation[2].to_radians(),
+        zoom: scene_camera.zoom,
+    })
+}
+
+/// Same frame without rotation (camera views and trigger zones are axis aligned)
+fn axis_aligned_frame(world: &World, entity: Entity, scene_camera: &SceneCamera, center: egui::Pos2) -> Option<LocalScreenFrame> {
+    entity_frame(world, entity, scene_camera, center).map(|frame| LocalScreenFrame { rotation: 0.0, ..frame })
+}
+
+/// Handles of the selected entity. Collider handles are only shown while colliders are
+/// visible, trigger zone handles while trigger zones are.
+pub fn collect_handles(
+    world: &World,
+    entity: Entity,
+    scene_camera: &SceneCamera,
+    center: egui::Pos2,
+    show_colliders: bool,
+    show_trigger_zones: bool,
+) -> Vec<Handle> {
+    let mut handles = Vec::new();
+
+    if let (Some(camera), Some(transform), Some(frame)) = (
+        world.cameras.get(&entity),
+        world.transforms.get(&entity),
+        axis_aligned_frame(world, entity, scene_camera, center),
+    ) {
+        let half = camera_view_half_size(camera, transform);
+        handles.push(Handle {
+            entity,
+            kind: HandleKind::CameraSize,
+            pos: frame.to_screen(glam::Vec2::new(0.0, half.y)),
+        });
+    }
+
+    if show_colliders {
+        if let (Some(collider), Some(transform), Some(frame)) = (
+            world.colliders.get(&entity),
+            world.transforms.get(&entity),
+            entity_frame(world, entity, scene_camera, center),
+        ) {
+            let scale = glam::Vec2::new(transform.scale[0], transform.scale[1]);
+            let (local_center, half) = collider_bounds_2d(collider, scale, world.sprite_flip(entity));
+            for x in -1i8..=1 {
+                for y in -1i8..=1 {
+                    let is_corner = x != 0 && y != 0;
+                    if (x == 0 && y == 0) || (is_corner && collider.shape != ColliderShape::Box) {
+                        continue;
+                    }
+                    let local = local_center + half * glam::Vec2::new(x as f32, y as f32);
+                    handles.push(Handle {
+                        entity,
+                        kind: HandleKind::ColliderResize { x, y },
+                        pos: frame.to_screen(local),
+                    });
+                }
+            }
+            handles.push(Handle {
+                entity,
+                kind: HandleKind::ColliderCenter,
+                pos: frame.to_screen(local_center),
+            });
+        }
+    }
+
+    if show_trigger_zones {
+        if let (Some(zone), Some(transform), Some(frame)) = (
+            world.trigger_zones.get(&entity),
+            world.transforms.get(&entity),
+            axis_aligned_frame(world, entity, scene_camera, center),
+        ) {
+            let is_circle = matches!(zone.shape, ecs::TriggerZoneShape::Circle { .. });
+            let (local_center, half) = trigger_zone_bounds_2d(zone, glam::Vec2::new(transform.scale[0], transform.scale[1]));
+            for x in -1i8..=1 {
+                for y in -1i8..=1 {
+                    if (x == 0 && y == 0) || (is_circle && x != 0 && y != 0) {
+                        continue;
+                    }
+                    let local = local_center + half * glam::Vec2::new(x as f32, y as f32);
+                    handles.push(Handle {
+                        entity,
+                        kind: HandleKind::ZoneResize { x, y },
+                        pos: frame.to_screen(local),
+                    });
+                }
+            }
+            handles.push(Handle {
+                entity,
+                kind: HandleKind::ZoneCenter,
+                pos: frame.to_screen(local_center),
+            });
+        }
+    }
+
+    handles
+}
+
+/// Closest handle under the pointer. The collider and zone center handles yield to the
+/// transform gizmo so the entity can still be moved when the offset is zero.
+pub fn pick_handle(handles: &[Handle], pointer: egui::Pos2, gizmo_hovered: bool, metrics: &GizmoMetrics) -> Option<Handle> {
+    let pick_radius = metrics.component_pick_radius();
+    handles
+        .iter()
+        .filter(|h| !(gizmo_hovered && h.kind.is_center()))
+        .filter(|h| gizmo_metrics::point_in_handle(h.pos, pick_radius, pointer))
+        .map(|h| (h, h.pos.distance(pointer)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(h, _)| *h)
+}
+
+/// Start dragging a handle grabbed at `pointer`
+pub fn begin_handle_drag(
+    handle: Handle,
+    pointer: egui::Pos2,
+    world: &World,
+    scene_camera: &SceneCamera,
+    center: egui::Pos2,
+) -> HandleDrag {
+    let start = match handle.kind {
+        HandleKind::CameraSize => {
+            let half = match (world.cameras.get(&handle.entity), world.transforms.get(&handle.entity)) {
+                (Some(camera), Some(transform)) => camera_view_half_size(camera, transform).y,
+                _ => 0.0,
+            };
+            glam::Vec2::new(0.0, half)
+        }
+        kind => {
+            let frame = if kind.is_axis_aligned() {
+                axis_aligned_frame(world, handle.entity, scene_camera, center)
+            } else {
+                entity_frame(world, handle.entity, scene_camera, center)
+            };
+            frame.map(|frame| frame.to_local(handle.pos)).unwrap_or(glam::Vec2::ZERO)
+        }
+    };
+
+    HandleDrag {
+        entity: handle.entity,
+        kind: handle.kind,
+        grab_offset: handle.pos - pointer,
+        start,
+    }
+}
+
+/// Apply a handle drag for the current pointer position.
+/// `snap` is Some when grid snapping is in effect for this drag.
+pub fn update_handle_drag(
+    drag: &HandleDrag,
+    pointer: egui::Pos2,
+    world: &mut World,
+    scene_camera: &SceneCamera,
+    center: egui::Pos2,
+    snap: Option<&SnapSettings>,
+) {
+    let target = pointer + drag.grab_offset;
+
+    match drag.kind {
+        HandleKind::CameraSize => {
+            let Some(frame) = axis_aligned_frame(world, drag.entity, scene_camera, center) else {
+                return;
+            };
+            let Some(transform) = world.transforms.get(&drag.entity).cloned() else {
+                return;
+            };
+            let Some(camera) = world.cameras.get_mut(&drag.entity) else {
+                return;
+            };
+
+            let half_height = frame.to_local(target).y.max(MIN_EXTENT);
+            match camera.projection {
+                CameraProjection::Orthographic => {
+                    camera.orthographic_size = match snap.filter(|s| s.snap_on_scale) {
+                        Some(s) => snap_value(half_height, s.position_snap_axis(1), s.mode, drag.start.y).max(MIN_EXTENT),
+                        None => half_height,
+                    };
+                }
+                CameraProjection::Perspective => {
+                    let distance = camera_plane_distance(&transform);
+                    let fov = (2.0 * (half_height / distance).atan()).to_degrees();
+                    let fov = match snap.filter(|s| s.snap_on_rotate) {
+                        Some(s) => {
+                            let start_fov = (2.0 * (drag.start.y / distance).atan()).to_degrees();
+                            snap_value(fov, s.rotation_snap, s.mode, start_fov)
+                        }
+                        None => fov,
+                    };
+                    camera.fov = fov.clamp(1.0, 179.0);
+                }
+            }
+        }
+        HandleKind::ColliderCenter | HandleKind::ColliderResize { .. } => {
+            let Some(frame) = entity_frame(world, drag.entity, scene_camera, center) else {
+                return;
+            };
+            let Some(scale) = world.transforms.get(&drag.entity).map(|t| glam::Vec2::new(t.scale[0], t.scale[1])) else {
+                return;
+            };
+            if scale.x.abs() < f32::EPSILON || scale.y.abs() < f32::EPSILON {
+                return;
+            }
+            let flip = world.sprite_flip(drag.entity);
+            let Some(collider) = world.colliders.get_mut(&drag.entity) else {
+                return;
+            };
+
+            let mut local = frame.to_local(target);
+            if let Some(s) = snap {
+                let enabled = match drag.kind {
+                    HandleKind::ColliderCenter => s.snap_on_move,
+                    _ => s.snap_on_scale,
+                };
+                if enabled {
+                    local.x = snap_value(local.x, s.position_snap_axis(0), s.mode, drag.start.x);
+                    local.y = snap_value(local.y, s.position_snap_axis(1), s.mode, drag.start.y);
+                }
+            }
+
+            let (local_center, half) = collider_bounds_2d(collider, scale, flip);
+            let (new_center, new_half) = match drag.kind {
+                HandleKind::ColliderResize { x, y } => resize_bounds(collider.shape, local_center, half, x, y, local),
+                _ => (local, half),
+            };
+
+            // Back from world units to the collider's scale-relative (unflipped) values
+            let mirror = |flipped: bool| if flipped && collider.flip_with_sprite { -1.0 } else { 1.0 };
+            collider.offset = [new_center.x / scale.x * mirror(flip[0]), new_center.y / scale.y * mirror(flip[1])];
+            match collider.shape {
+                ColliderShape::Circle => collider.size[0] = new_half.x * 2.0 / scale.x.abs(),
+                _ => collider.size = [new_half.x * 2.0 / scale.x.abs(), new_half.y * 2.0 / scale.y.abs()],
+            }
+        }
+        HandleKind::ZoneCenter | HandleKind::ZoneResize { .. } => {
+            let Some(frame) = axis_aligned_frame(world, drag.entity, scene_camera, center) else {
+                return;
+            };
+            let Some(scale) = world.transforms.get(&drag.entity).map(|t| glam::Vec2::new(t.scale[0], t.scale[1])) else {
+                return;
+            };
+            if scale.x.abs() < f32::EPSILON || scale.y.abs() < f32::EPSILON {
+                return;
+            }
+            let Some(zone) = world.trigger_zones.get_mut(&drag.entity) else {
+                return;
+            };
+
+            let mut local = frame.to_local(target);
+            if let Some(s) = snap {
+                let enabled = match drag.kind {
+                    HandleKind::ZoneCenter => s.snap_on_move,
+                    _ => s.snap_on_scale,
+                };
+                if enabled {
+                    local.x = snap_value(local.x, s.position_snap_axis(0), s.mode, drag.start.x);
+                    local.y = snap_value(local.y, s.position_snap_axis(1), s.mode, drag.start.y);
+                }
+            }
+
+            // Circles resize uniformly, like circle colliders
+            let shape = match zone.shape {
+                ecs::TriggerZoneShape::Circle { .. } => ColliderShape::Circle,
+                ecs::TriggerZoneShape::Box { .. } => ColliderShape::Box,
+            };
+            let (local_center, half) = trigger_zone_bounds_2d(zone, scale);
+            let (new_center, new_half) = match drag.kind {
+                HandleKind::ZoneResize { x, y } => resize_bounds(shape, local_center, half, x, y, local),
+                _ => (local, half),
+            };
+
+            zone.offset = [new_center.x / scale.x, new_center.y / scale.y];
+            zone.shape = match zone.shape {
+                ecs::TriggerZoneShape::Circle { .. } => ecs::TriggerZoneShape::Circle {
+                    radius: new_half.x / scale.x.abs().max(scale.y.abs()),
+                },
+                ecs::TriggerZoneShape::Box { .. } => ecs::TriggerZoneShape::Box {
+                    width: new_half.x * 2.0 / scale.x.abs(),
+                    height: new_half.y * 2.0 / scale.y.abs(),
+                },
+            };
+        }
+    }
+}
+
+/// Move the dragged sides of a collider's bounds to `target`, keeping the opposite sides in place.
+/// Circles resize uniformly from the dragged side.
+fn resize_bounds(
+    shape: ColliderShape,
+    center: glam::Vec2,
+    half: glam::Vec2,
+    side_x: i8,
+    side_y: i8,
+    target: glam::Vec2,
+) -> (glam::Vec2, glam::Vec2) {
+    let mut center = center;
+    let mut half = half;
+
+    for (axis, side) in [(0, side_x), (1, side_y)] {
+        if side == 0 {
+            continue;
+        }
+        let sign = side as f32;
+        let fixed = center[axis] - sign * half[axis];
+        // Don't let the dragged side cross over the fixed one
+        let moved = if sign > 0.0 {
+            target[axis].max(fixed + MIN_EXTENT)
+        } else {
+            target[axis].min(fixed - MIN_EXTENT)
+        };
+        center[axis] = (moved + fixed) / 2.0;
+        half[axis] = (moved - fixed).abs() / 2.0;
+
+        if shape == ColliderShape::Circle {
+            let other = 1 - axis;
+            half[other] = half[axis];
+        }
+    }
+
+    (center, half)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resize_keeps_opposite_edge() {
+        let (center, half) = resize_bounds(
+            ColliderShape::Box,
+            glam::Vec2::ZERO,
+            glam::Vec2::new(1.0, 1.0),
+            1,
+            0,
+            glam::Vec2::new(3.0, 10.0),
+        );
+        // Left edge stays at -1, right edge moves to 3, height unchanged
+        assert_eq!(center, glam::Vec2::new(1.0, 0.0));
+        assert_eq!(half, glam::Vec2::new(2.0, 1.0));
+    }
+
+    #[test]
+    fn test_resize_cannot_invert_bounds() {
+        let (center, half) = resize_bounds(
+            ColliderShape::Circle,
+            glam::Vec2::ZERO,
+            glam::Vec2::new(1.0, 1.0),
+            0,
+            1,
+            glam::Vec2::new(0.0, -5.0),
+        );
+        assert!((center.y - (-1.0 + MIN_EXTENT / 2.0)).abs() < 1e-5);
+        assert!((half.x - MIN_EXTENT / 2.0).abs() < 1e-5);
+        assert_eq!(half.x, half.y);
+    }
+
+    #[test]
+    fn test_pick_prefers_closest_handle() {
+        let metrics = GizmoMetrics::default();
+        let handles = [
+            Handle { entity: 1, kind: HandleKind::ColliderCenter, pos: egui::pos2(0.0, 0.0) },
+            Handle { entity: 1, kind: HandleKind::ColliderResize { x: 1, y: 0 }, pos: egui::pos2(4.0, 0.0) },
+        ];
+        let picked = pick_handle(&handles, egui::pos2(3.0, 0.0), false, &metrics).map(|h| h.kind);
+        assert_eq!(picked, Some(HandleKind::ColliderResize { x: 1, y: 0 }));
+
+        // The center handle gives way to the transform gizmo
+        assert!(pick_handle(&handles[..1], egui::pos2(0.0, 0.0), true, &metrics).is_none());
+        assert!(pick_handle(&handles, egui::pos2(50.0, 0.0), false, &metrics).is_none());
+    }
+
+    #[test]
+    fn test_zone_center_yields_to_gizmo() {
+        let metrics = GizmoMetrics::default();
+        let handles = [
+            Handle { entity: 1, kind: HandleKind::ZoneCenter, pos: egui::pos2(0.0, 0.0) },
+            Handle { entity: 1, kind: HandleKind::ZoneResize { x: 0, y: 1 }, pos: egui::pos2(0.0, 20.0) },
+        ];
+        assert!(pick_handle(&handles, egui::pos2(1.0, 0.0), true, &metrics).is_none());
+        assert_eq!(pick_handle(&handles, egui::pos2(1.0, 0.0), false, &metrics).map(|h| h.kind), Some(HandleKind::ZoneCenter));
+        assert_eq!(pick_handle(&handles, egui::pos2(0.0, 19.0), true, &metrics).map(|h| h.kind), Some(HandleKind::ZoneResize { x: 0, y: 1 }));
+    }
+
+    #[test]
+    fn test_pick_radius_follows_handle_size_at_any_scale() {
+        let handles = [Handle { entity: 1, kind: HandleKind::ColliderResize { x: 1, y: 0 }, pos: egui::pos2(100.0, 100.0) }];
+        for scale in [1.0, 1.5, 2.0] {
+            // 7 points (4 half size + 3 margin) around the handle, whatever the display scale
+            let metrics = GizmoMetrics::new(scale, 1.0);
+            assert!(pick_handle(&handles, egui::pos2(106.5, 100.0), false, &metrics).is_some());
+            assert!(pick_handle(&handles, egui::pos2(107.5, 100.0), false, &metrics).is_none());
+
+            // Twice the handle size preference doubles it
+            let metrics = GizmoMetrics::new(scale, 2.0);
+            assert!(pick_handle(&handles, egui::pos2(113.5, 100.0), false, &metrics).is_some());
+            assert!(pick_handle(&handles, egui::pos2(114.5, 100.0), false, &metrics).is_none());
+        }
+    }
+}
@@ -1,6 +1,9 @@
 //! Interaction Module
 //!
-//! User interaction handlers (camera, selection, transforms).
+//! User interaction handlers (camera, selection, sprite picking, transforms, component handles, tile painting).
 
 pub mod camera;
+pub mod handles;
+pub mod picking;
 pub mod transform;
+pub mod tile_paint;
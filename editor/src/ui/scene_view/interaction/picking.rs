@@ -0,0 +1,115 @@
+//! Sprite Picking
+//!
+//! Click selection of sprites with the same quad math and draw order as the game
+//! (ecs::picking): the topmost sprite under the cursor wins, and clicking again at
+//! the same spot cycles down through the overlapping ones.
+
+use ecs::{World, Entity};
+use egui;
+use glam::{Vec2, Vec3};
+use crate::SceneCamera;
+use super::super::types::SceneViewMode;
+use super::super::rendering::projection_3d;
+
+/// Clicks this close (screen pixels) to the previous one cycle instead of re-picking
+const CYCLE_DISTANCE: f32 = 4.0;
+
+/// Sprites under `pointer`, topmost first
+pub fn sprites_at(
+    world: &World,
+    sorting_layers: &ecs::sorting::SortingLayers,
+    scene_camera: &SceneCamera,
+    scene_view_mode: &SceneViewMode,
+    rect: egui::Rect,
+    pointer: egui::Pos2,
+    texture_manager: &engine::texture_manager::TextureManager,
+) -> Vec<Entity> {
+    let (origin, dir) = match scene_view_mode {
+        SceneViewMode::Mode2D => {
+            // Straight down the view axis from in front of everything
+            let offset = pointer - rect.center();
+            let point = scene_camera.screen_to_world(Vec2::new(offset.x, offset.y));
+            (Vec3::new(point.x, point.y, 1.0e4), Vec3::NEG_Z)
+        }
+        SceneViewMode::Mode3D => {
+            let local = pointer - rect.min;
+            let ray = projection_3d::screen_to_ray(Vec2::new(local.x, local.y), scene_camera, Vec2::new(rect.width(), rect.height()));
+            (ray.origin, ray.direction)
+        }
+    };
+    ecs::picking::pick_sprites(world, sorting_layers, origin, dir, |texture_id| texture_manager.sprite_pivot(texture_id))
+}
+
+/// Where the last click-cycle is: the entity it picked among how many under the cursor
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PickCycle {
+    pub entity: Entity,
+    /// 0 for the topmost
+    pub index: usize,
+    pub count: usize,
+}
+
+fn last_click_id() -> egui::Id {
+    egui::Id::new("scene_view_last_pick_click")
+}
+
+fn pick_cycle_id() -> egui::Id {
+    egui::Id::new("scene_view_pick_cycle")
+}
+
+/// Entity a click at `pointer` selects from `hits` (topmost first): the topmost, or the one
+/// below `selected` when the previous click landed on the same spot
+pub fn cycle_pick(ctx: &egui::Context, hits: &[Entity], selected: Option<Entity>, pointer: egui::Pos2) -> Option<Entity> {
+    let previous = ctx.data_mut(|d| {
+        let previous = d.get_temp::<egui::Pos2>(last_click_id());
+        d.insert_temp(last_click_id(), pointer);
+        previous
+    });
+    let repeated = previous.is_some_and(|p| p.distance(pointer) <= CYCLE_DISTANCE);
+
+    let below_selected = selected
+        .and_then(|selected| hits.iter().position(|&e| e == selected))
+        .map(|i| (i + 1) % hits.len());
+    let index = match below_selected {
+        Some(next) if repeated => next,
+        _ => 0,
+    };
+    let entity = hits.get(index).copied();
+    let cycle = entity.map(|entity| PickCycle { entity, index, count: hits.len() });
+    ctx.data_mut(|d| d.insert_temp(pick_cycle_id(), cycle));
+    entity
+}
+
+/// "2 of 5" while the selection is an entity picked among overlapping ones (until
+/// something else gets selected)
+pub fn pick_status(ctx: &egui::Context, selected: Option<Entity>) -> Option<String> {
+    let cycle = ctx.data(|d| d.get_temp::<Option<PickCycle>>(pick_cycle_id())).flatten()?;
+    (cycle.count > 1 && selected == Some(cycle.entity)).then(|| format!("{} of {}", cycle.index + 1, cycle.count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_clicks_cycle_with_status() {
+        let ctx = egui::Context::default();
+        let hits = [3, 1, 2];
+        let at = egui::pos2(100.0, 100.0);
+
+        let mut selected = cycle_pick(&ctx, &hits, None, at);
+        assert_eq!(selected, Some(3));
+        assert_eq!(pick_status(&ctx, selected).as_deref(), Some("1 of 3"));
+        selected = cycle_pick(&ctx, &hits, selected, at + egui::vec2(2.0, 0.0));
+        assert_eq!(pick_status(&ctx, selected).as_deref(), Some("2 of 3"));
+        selected = cycle_pick(&ctx, &hits, selected, at);
+        selected = cycle_pick(&ctx, &hits, selected, at);
+        assert_eq!(selected, Some(3), "wraps around to the topmost");
+
+        // A click elsewhere starts over from the topmost; a single hit shows no status
+        assert_eq!(cycle_pick(&ctx, &hits, Some(1), egui::pos2(10.0, 10.0)), Some(3));
+        selected = cycle_pick(&ctx, &[1], None, egui::pos2(50.0, 50.0));
+        assert_eq!(pick_status(&ctx, selected), None);
+        assert_eq!(pick_status(&ctx, Some(2)), None);
+    }
+}
@@ -0,0 +1,198 @@
+//! Tile Painting Interaction
+//!
+//! Paints the Tile Palette's stamp onto the selected tilemap in the 2D scene view
+//! and draws the ghost preview under the cursor.
+
+use crate::tools::tile_painting::{self, TileBrush, TilePaintTool, TileStroke};
+use crate::ui::tile_palette;
+use crate::SceneCamera;
+use ecs::{Entity, World};
+use egui;
+use engine::texture_manager::TextureManager;
+
+fn rect_anchor_id() -> egui::Id {
+    egui::Id::new("tile_paint_rect_anchor")
+}
+
+fn last_cell_id() -> egui::Id {
+    egui::Id::new("tile_paint_last_cell")
+}
+
+/// Cells on the line between two cells, so fast drags don't leave gaps
+fn line_cells(from: (i64, i64), to: (i64, i64)) -> Vec<(i64, i64)> {
+    let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).max(1);
+    let mut cells: Vec<(i64, i64)> = (1..=steps)
+        .map(|i| {
+            let t = i as f32 / steps as f32;
+            (
+                (from.0 as f32 + (to.0 - from.0) as f32 * t).round() as i64,
+                (from.1 as f32 + (to.1 - from.1) as f32 * t).round() as i64,
+            )
+        })
+        .collect();
+    cells.dedup();
+    cells
+}
+
+/// Paint with `brush` on `entity`'s tilemap: a press starts a stroke, release hands it
+/// to the editor as one undo step
+#[allow(clippy::too_many_arguments)]
+pub fn handle_tile_painting(
+    ui: &egui::Ui,
+    response: &egui::Response,
+    painter: &egui::Painter,
+    world: &mut World,
+    entity: Entity,
+    brush: &TileBrush,
+    scene_camera: &SceneCamera,
+    center: egui::Pos2,
+    texture_manager: &mut TextureManager,
+) {
+    let ctx = ui.ctx();
+    let position = world.transforms.get(&entity).map(|t| t.position).unwrap_or([0.0; 3]);
+    let to_world = |pos: egui::Pos2| {
+        scene_camera.screen_to_world(glam::Vec2::new(pos.x - center.x, pos.y - center.y)).truncate()
+    };
+    let hovered_cell = response.hover_pos().map(|pos| tile_painting::cell_at(position, to_world(pos)));
+    let (pressed, down, released, alt) = ui.input(|i| (
+        i.pointer.primary_pressed(),
+        i.pointer.primary_down(),
+        i.pointer.primary_released(),
+        i.modifiers.alt,
+    ));
+
+    // A stroke on another tilemap (selection changed mid-drag) is closed first
+    if tile_painting::current_stroke(ctx).is_some_and(|stroke| stroke.entity != entity) {
+        tile_painting::finish_stroke(ctx);
+    }
+
+    // Begin a stroke on press (Alt+drag stays camera panning)
+    if pressed && !alt && response.hovered() {
+        if let Some(cell) = hovered_cell {
+            let mut stroke = TileStroke::new(entity, brush.tool);
+            ctx.data_mut(|d| {
+                d.insert_temp(rect_anchor_id(), cell);
+                d.remove::<(i64, i64)>(last_cell_id());
+            });
+            if let Some(tilemap) = world.tilemaps.get_mut(&entity) {
+                match brush.tool {
+                    TilePaintTool::Fill => {
+                        let cells = tile_painting::fill_cells(tilemap, &brush.stamp, cell);
+                        stroke.record(tile_painting::apply_cells(tilemap, &cells));
+                    }
+                    TilePaintTool::Brush | TilePaintTool::Erase | TilePaintTool::Rectangle => {}
+                }
+            }
+            tile_painting::set_current_stroke(ctx, stroke);
+        }
+    }
+
+    let Some(mut stroke) = tile_painting::current_stroke(ctx) else {
+        draw_ghost(ctx, painter, world, entity, brush, hovered_cell, None, scene_camera, center, texture_manager);
+        return;
+    };
+    let anchor = ctx.data(|d| d.get_temp::<(i64, i64)>(rect_anchor_id()));
+
+    // Brush and eraser paint continuously along the drag
+    if down && matches!(brush.tool, TilePaintTool::Brush | TilePaintTool::Erase) {
+        if let (Some(cell), Some(tilemap)) = (hovered_cell, world.tilemaps.get_mut(&entity)) {
+            let last = ctx.data(|d| d.get_temp::<(i64, i64)>(last_cell_id()));
+            let path = match last {
+                Some(last) if last == cell => Vec::new(),
+                Some(last) => line_cells(last, cell),
+                None => vec![cell],
+            };
+            for cell in path {
+                let cells = if brush.tool == TilePaintTool::Erase {
+                    tile_painting::erase_cells(&brush.stamp, cell)
+                } else {
+                    tile_painting::brush_cells(&brush.stamp, cell)
+                };
+                stroke.record(tile_painting::apply_cells(tilemap, &cells));
+            }
+            ctx.data_mut(|d| d.insert_temp(last_cell_id(), cell));
+        }
+    }
+
+    if released || !down {
+        // Rectangles are committed on release
+        if brush.tool == TilePaintTool::Rectangle {
+            if let (Some(anchor), Some(cell), Some(tilemap)) = (anchor, hovered_cell, world.tilemaps.get_mut(&entity)) {
+                let cells = tile_painting::rectangle_cells(&brush.stamp, anchor, cell);
+                stroke.record(tile_painting::apply_cells(tilemap, &cells));
+            }
+        }
+        tile_painting::set_current_stroke(ctx, stroke);
+        tile_painting::finish_stroke(ctx);
+        ctx.data_mut(|d| {
+            d.remove::<(i64, i64)>(rect_anchor_id());
+            d.remove::<(i64, i64)>(last_cell_id());
+        });
+    } else {
+        tile_painting::set_current_stroke(ctx, stroke);
+        let rect_anchor = anchor.filter(|_| brush.tool == TilePaintTool::Rectangle);
+        draw_ghost(ctx, painter, world, entity, brush, hovered_cell, rect_anchor, scene_camera, center, texture_manager);
+    }
+}
+
+/// Translucent preview of what a click would paint
+#[allow(clippy::too_many_arguments)]
+fn draw_ghost(
+    ctx: &egui::Context,
+    painter: &egui::Painter,
+    world: &World,
+    entity: Entity,
+    brush: &TileBrush,
+    hovered_cell: Option<(i64, i64)>,
+    rect_anchor: Option<(i64, i64)>,
+    scene_camera: &SceneCamera,
+    center: egui::Pos2,
+    texture_manager: &mut TextureManager,
+) {
+    let Some(cell) = hovered_cell else {
+        return;
+    };
+    let Some(tilemap) = world.tilemaps.get(&entity) else {
+        return;
+    };
+    let position = world.transforms.get(&entity).map(|t| t.position).unwrap_or([0.0; 3]);
+
+    let cells = match (brush.tool, rect_anchor) {
+        (TilePaintTool::Rectangle, Some(anchor)) => tile_painting::rectangle_cells(&brush.stamp, anchor, cell),
+        (TilePaintTool::Erase, _) => tile_painting::erase_cells(&brush.stamp, cell),
+        (TilePaintTool::Fill, _) => vec![(cell, brush.stamp.tile_at(0, 0))],
+        _ => tile_painting::brush_cells(&brush.stamp, cell),
+    };
+
+    let tileset = world.tilesets.get(&entity);
+    let texture = tileset.and_then(|ts| tile_palette::tileset_texture(ctx, ts, texture_manager));
+    let outline = match brush.tool {
+        TilePaintTool::Erase => egui::Color32::from_rgb(255, 90, 90),
+        _ => egui::Color32::from_rgb(255, 200, 0),
+    };
+
+    for ((x, y), tile_id) in cells {
+        if x < 0 || y < 0 || x >= tilemap.width as i64 || y >= tilemap.height as i64 {
+            continue;
+        }
+        let top_left = tile_painting::cell_top_left(position, (x, y));
+        let screen = scene_camera.world_to_screen(glam::Vec3::new(top_left.x, top_left.y, 0.0));
+        let rect = egui::Rect::from_min_size(
+            egui::pos2(center.x + screen.x, center.y + screen.y),
+            egui::vec2(scene_camera.zoom, scene_camera.zoom),
+        );
+
+        let uv = match (tileset, texture.as_ref()) {
+            (Some(ts), Some(texture)) if brush.tool != TilePaintTool::Erase && tile_id != 0 => {
+                tile_palette::tile_uv(ts, texture.size(), tile_id).map(|uv| (texture.id(), uv))
+            }
+            _ => None,
+        };
+        if let Some((texture_id, uv)) = uv {
+            painter.image(texture_id, rect, uv, egui::Color32::from_white_alpha(150));
+        } else {
+            painter.rect_filled(rect, 0.0, outline.gamma_multiply(0.25));
+        }
+        painter.rect_stroke(rect, 0.0, egui::Stroke::new(1.0, outline), egui::StrokeKind::Inside);
+    }
+}
@@ -6,11 +6,20 @@ use ecs::{World, Entity};
 use egui;
 use crate::ui::TransformTool;
 use crate::SceneCamera;
+use crate::tools::snapping::{snap_value, SnapSettings};
 use super::super::types::*;
 use super::super::rendering::projection_3d;
+use super::super::gizmo_metrics::{self, GizmoMetrics};
 use glam;
 
+/// Drag start and unsnapped working copy of the dragged transform, kept in egui memory
+fn drag_transform_id(entity: Entity) -> egui::Id {
+    egui::Id::new(("gizmo_drag_transform", entity))
+}
+
 /// Handle transform gizmo interaction
+///
+/// `snap` is Some when grid snapping is in effect for this drag.
 pub fn handle_gizmo_interaction_stateful(
     response: &egui::Response,
     entity: Entity,
@@ -25,28 +34,33 @@ pub fn handle_gizmo_interaction_stateful(
     transform: &ecs::Transform,
     scene_view_mode: &SceneViewMode,
     viewport_rect: Option<egui::Rect>,
+    snap: Option<&SnapSettings>,
+    metrics: &GizmoMetrics,
 ) {
     if *current_tool == TransformTool::View {
         return;
     }
 
-    // Start dragging - determine which handle
-    // Start dragging - determine which handle
+    // Start dragging - determine which handle. Hit-test where the press began: by the
+    // time the drag threshold is crossed the pointer may have left a small handle
     if response.drag_started() {
-        if let Some(hover_pos) = response.hover_pos() {
+        let press_pos = response.ctx.input(|i| i.pointer.press_origin()).or(response.hover_pos());
+        if let Some(press_pos) = press_pos {
              if let Some(axis) = hit_test_gizmo(
                 screen_x,
                 screen_y,
-                hover_pos,
+                press_pos,
                 current_tool,
                 scene_camera,
                 scene_view_mode,
                 transform_space,
                 transform,
                 viewport_rect,
+                metrics,
             ) {
                 *dragging_entity = Some(entity);
                 *drag_axis = Some(axis);
+                response.ctx.data_mut(|d| d.insert_temp(drag_transform_id(entity), (transform.clone(), transform.clone())));
             }
         }
     }
@@ -55,7 +69,14 @@ pub fn handle_gizmo_interaction_stateful(
     if response.dragged() && *dragging_entity == Some(entity) {
         let delta = response.drag_delta();
 
-        if let Some(transform_mut) = world.transforms.get_mut(&entity) {
+        // Deltas accumulate on an unsnapped copy so small moves add up to a grid step;
+        // the entity gets the snapped result
+        let id = drag_transform_id(entity);
+        let (start, mut raw) = response.ctx.data(|d| d.get_temp::<(ecs::Transform, ecs::Transform)>(id))
+            .unwrap_or_else(|| (transform.clone(), transform.clone()));
+
+        {
+            let transform_mut = &mut raw;
             match scene_view_mode {
                 SceneViewMode::Mode3D => {
                     // --------------------------------------------------------
@@ -253,10 +274,8 @@ pub fn handle_gizmo_interaction_stateful(
                     // --------------------------------------------------------
                     // 2D MODE (Legacy logic)
                     // --------------------------------------------------------
-                    let move_scale = 1.0 / scene_camera.zoom;
                     // Convert screen delta to world space (invert Y)
-                    let screen_delta = glam::Vec2::new(delta.x, -delta.y);
-                    let world_delta = screen_delta * move_scale;
+                    let world_delta = gizmo_metrics::drag_to_world(delta, scene_camera.zoom);
 
                     match current_tool {
                         TransformTool::Move => {
@@ -359,7 +378,42 @@ pub fn handle_gizmo_interaction_stateful(
                 }
             }
         }
+
+        if let Some(transform_mut) = world.transforms.get_mut(&entity) {
+            *transform_mut = snap_transform(&raw, &start, current_tool, snap);
+        }
+        response.ctx.data_mut(|d| d.insert_temp(id, (start, raw)));
+    }
+}
+
+/// Snap the components the current tool changed since the drag started.
+/// Untouched axes keep their exact start value.
+fn snap_transform(
+    raw: &ecs::Transform,
+    start: &ecs::Transform,
+    current_tool: &TransformTool,
+    snap: Option<&SnapSettings>,
+) -> ecs::Transform {
+    let mut snapped = raw.clone();
+    let Some(s) = snap else {
+        return snapped;
+    };
+
+    for i in 0..3 {
+        match current_tool {
+            TransformTool::Move if s.snap_on_move && raw.position[i] != start.position[i] => {
+                snapped.position[i] = snap_value(raw.position[i], s.position_snap_axis(i), s.mode, start.position[i]);
+            }
+            TransformTool::Rotate if s.snap_on_rotate && raw.rotation[i] != start.rotation[i] => {
+                snapped.rotation[i] = snap_value(raw.rotation[i], s.rotation_snap, s.mode, start.rotation[i]);
+            }
+            TransformTool::Scale if s.snap_on_scale && raw.scale[i] != start.scale[i] => {
+                snapped.scale[i] = snap_value(raw.scale[i], s.scale_snap, s.mode, start.scale[i]).max(0.01);
+            }
+            _ => {}
+        }
     }
+    snapped
 }
 
 /// Check which gizmo axis is under the cursor
@@ -373,9 +427,8 @@ pub fn hit_test_gizmo(
     transform_space: &TransformSpace,
     transform: &ecs::Transform,
     viewport_rect: Option<egui::Rect>,
+    metrics: &GizmoMetrics,
 ) -> Option<u8> {
-    let gizmo_size = 80.0;
-    let handle_size = 10.0;
     let center = egui::pos2(screen_x, screen_y);
 
     match scene_view_mode {
@@ -420,7 +473,7 @@ pub fn hit_test_gizmo(
                         let dist_y = p_up.map_or(f32::MAX, |p| hover_pos.distance(p));
                         let dist_z = p_fwd.map_or(f32::MAX, |p| hover_pos.distance(p));
 
-                        let hit_radius = handle_size * 2.5;
+                        let hit_radius = metrics.pick_radius();
 
                         if dist_center < hit_radius {
                             Some(3) // All/Free
@@ -436,7 +489,7 @@ pub fn hit_test_gizmo(
                     }
                     TransformTool::Rotate => {
                         let radius_world = scale * 1.2;
-                        let hit_threshold = 10.0;
+                        let hit_threshold = metrics.ring_pick_width_3d();
                         
                         let get_ring_dist = |axis_u: glam::Vec3, axis_v: glam::Vec3| -> f32 {
                             let segments = 32;
@@ -477,7 +530,7 @@ pub fn hit_test_gizmo(
                         let dist_x = p_right.map_or(f32::MAX, |p| hover_pos.distance(p));
                         let dist_y = p_up.map_or(f32::MAX, |p| hover_pos.distance(p));
                         let dist_z = p_fwd.map_or(f32::MAX, |p| hover_pos.distance(p));
-                        let hit_radius = handle_size * 2.5;
+                        let hit_radius = metrics.pick_radius();
 
                         if dist_center < hit_radius {
                             Some(3u8) // Uniform
@@ -505,54 +558,33 @@ pub fn hit_test_gizmo(
 
             match current_tool {
                 TransformTool::Move => {
-                    let x_dir = glam::Vec2::new(rotation_rad.cos(), -rotation_rad.sin());
-                    let y_dir = glam::Vec2::new(-rotation_rad.sin(), -rotation_rad.cos());
-                    
-                    let x_handle = egui::pos2(screen_x + x_dir.x * gizmo_size, screen_y + x_dir.y * gizmo_size);
-                    let y_handle = egui::pos2(screen_x + y_dir.x * gizmo_size, screen_y + y_dir.y * gizmo_size);
-                    
-                    let dist_x = hover_pos.distance(x_handle);
-                    let dist_y = hover_pos.distance(y_handle);
-                    let dist_center = hover_pos.distance(center);
-                    
-                    if dist_center < handle_size * 1.8 {
+                    // Same handle positions as render_transform_gizmo
+                    let (x_handle, y_handle) = metrics.axis_ends_2d(center, rotation_rad);
+                    let radius = metrics.move_pick_radius_2d();
+
+                    if gizmo_metrics::point_in_handle(center, radius, hover_pos) {
                         Some(3)
-                    } else if dist_x < handle_size * 1.8 {
+                    } else if gizmo_metrics::point_in_handle(x_handle, radius, hover_pos) {
                         Some(0)
-                    } else if dist_y < handle_size * 1.8 {
+                    } else if gizmo_metrics::point_in_handle(y_handle, radius, hover_pos) {
                         Some(1)
                     } else {
                         None
                     }
                 }
                 TransformTool::Rotate => {
-                    let radius = gizmo_size * 0.8;
-                    let dist_from_center = hover_pos.distance(center);
-                    let dist_from_circle = (dist_from_center - radius).abs();
-                    
-                    if dist_from_circle < 30.0 || dist_from_center < radius {
-                        Some(0)
-                    } else {
-                        None
-                    }
+                    let radius = metrics.rotate_ring_radius();
+                    gizmo_metrics::point_on_ring(center, radius, metrics.ring_pick_width(), hover_pos).then_some(0)
                 }
                 TransformTool::Scale => {
-                    let axis_length = gizmo_size;
-                    let x_dir = glam::Vec2::new(rotation_rad.cos(), -rotation_rad.sin());
-                    let y_dir = glam::Vec2::new(-rotation_rad.sin(), -rotation_rad.cos());
-                    
-                    let x_handle = egui::pos2(screen_x + x_dir.x * axis_length, screen_y + x_dir.y * axis_length);
-                    let y_handle = egui::pos2(screen_x + y_dir.x * axis_length, screen_y + y_dir.y * axis_length);
-                    
-                    let dist_x = hover_pos.distance(x_handle);
-                    let dist_y = hover_pos.distance(y_handle);
-                    let dist_center = hover_pos.distance(center);
-                    
-                    if dist_center < handle_size * 2.5 {
+                    let (x_handle, y_handle) = metrics.axis_ends_2d(center, rotation_rad);
+                    let radius = metrics.pick_radius();
+
+                    if gizmo_metrics::point_in_handle(center, radius, hover_pos) {
                         Some(2) // Uniform
-                    } else if dist_x < handle_size * 2.5 {
+                    } else if gizmo_metrics::point_in_handle(x_handle, radius, hover_pos) {
                         Some(0)
-                    } else if dist_y < handle_size * 2.5 {
+                    } else if gizmo_metrics::point_in_handle(y_handle, radius, hover_pos) {
                         Some(1)
                     } else {
                         None
@@ -6,6 +6,7 @@
 //! - `types`: Type definitions and enums
 //! - `rendering`: All rendering functions (grid, entities, gizmos)
 //! - `interaction`: User interaction (camera, selection, transforms)
+//! - `gizmo_metrics`: Gizmo and handle sizes shared by rendering and hit-testing
 //! - `toolbar`: Toolbar UI
 //! - `shortcuts`: Keyboard shortcuts
 
@@ -13,6 +14,7 @@
 pub mod types;
 pub mod rendering;
 pub mod interaction;
+pub mod gizmo_metrics;
 pub mod toolbar;
 pub mod shortcuts;
 
@@ -22,7 +24,20 @@ pub use types::*;
 use ecs::{World, Entity};
 use egui;
 use crate::ui::TransformTool;
-use crate::{SceneCamera, SceneGrid, DragDropState};
+use crate::{SceneCamera, SceneGrid, DragDropState, SelectionManager, SelectionMode};
+
+fn frame_selected_request_id() -> egui::Id {
+    egui::Id::new("scene_view_frame_selected_request")
+}
+
+/// Ask the scene view to frame the selected entity on its next render, as if F was pressed
+pub fn request_frame_selected(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(frame_selected_request_id(), true));
+}
+
+fn take_frame_selected_request(ctx: &egui::Context) -> bool {
+    ctx.data_mut(|d| d.remove_temp::<bool>(frame_selected_request_id())).unwrap_or(false)
+}
 
 /// Main scene view render function
 /// 
@@ -32,8 +47,10 @@ pub fn render_scene_view(
     ui: &mut egui::Ui,
     world: &mut World,
     selected_entity: &mut Option<Entity>,
+    selection: &mut SelectionManager,
     _scene_view_tab: &mut usize,
     is_playing: bool,
+    is_paused: bool,
     show_colliders: &bool,
     show_velocities: &bool,
     show_debug_lines: &bool,
@@ -45,8 +62,14 @@ pub fn render_scene_view(
     camera_state_display: &crate::ui::camera_settings::CameraStateDisplay,
     play_request: &mut bool,
     stop_request: &mut bool,
+    pause_request: &mut bool,
+    step_request: &mut bool,
     dragging_entity: &mut Option<Entity>,
     drag_axis: &mut Option<u8>,
+    handle_drag: &mut Option<interaction::handles::HandleDrag>,
+    snap_settings: &mut crate::tools::snapping::SnapSettings,
+    entity_names: &mut std::collections::HashMap<Entity, String>,
+    project_path: Option<&std::path::PathBuf>,
     scene_view_mode: &mut SceneViewMode,
     projection_mode: &mut SceneProjectionMode,
     transform_space: &mut TransformSpace,
@@ -60,6 +83,7 @@ pub fn render_scene_view(
     queue: &wgpu::Queue,
     asset_loader: &dyn engine_core::assets::AssetLoader,
     render_cache: &mut engine::runtime::render_system::RenderCache,
+    sorting_layers: &ecs::sorting::SortingLayers,
 ) {
     // Sync camera projection mode with editor state
     scene_camera.projection_mode = *projection_mode;
@@ -75,10 +99,14 @@ pub fn render_scene_view(
         ui,
         current_tool,
         is_playing,
+        is_paused,
         play_request,
         stop_request,
+        pause_request,
+        step_request,
         scene_view_mode,
         transform_space,
+        snap_settings,
     );
 
     // Handle mode switching
@@ -95,22 +123,24 @@ pub fn render_scene_view(
         egui::Sense::click_and_drag(),
     );
     let rect = response.rect;
+    let gizmo_metrics = gizmo_metrics::GizmoMetrics::from_ctx(ui.ctx(), &debug_draw.gizmos);
 
     // Handle keyboard shortcuts
-    shortcuts::handle_keyboard_shortcuts(ui, current_tool, scene_camera, scene_view_mode);
+    shortcuts::handle_keyboard_shortcuts(ui, scene_camera, scene_view_mode);
     
-    // Check for F key press (focus on selected entity)
-    // Use ctx.input instead of ui.input to ensure we catch the key press even if UI doesn't have focus
-    let focus_pressed = ui.ctx().input(|i| i.key_pressed(egui::Key::F) && !i.modifiers.ctrl && !i.modifiers.shift && !i.modifiers.alt);
+    // Frame Selected (F, or whatever the command is bound to) and other panels
+    // (hierarchy search F3) ask for framing the selected entity
+    let focus_pressed = take_frame_selected_request(ui.ctx());
     
-    // Handle camera controls
+    // Handle camera controls (a gizmo or handle drag in progress keeps the pointer)
+    let gizmo_active = drag_axis.is_some() || dragging_entity.is_some() || handle_drag.is_some();
     interaction::camera::handle_camera_controls(
         &response,
         scene_camera,
         rect,
         scene_view_mode,
-        selected_entity,
-        world,
+        gizmo_active,
+        delta_time,
     );
 
     // Background - Unity-like colors
@@ -145,6 +175,10 @@ pub fn render_scene_view(
         }
     }
 
+    // Camera and marker icons (editor only), placed before the entities draw so clicks
+    // can pick them ahead of sprites
+    let icons = rendering::icons::collect_icons(world, debug_draw, scene_camera, scene_view_mode, rect);
+
     // Render entities based on mode
     let ctx = ui.ctx().clone();
     match scene_view_mode {
@@ -164,7 +198,26 @@ pub fn render_scene_view(
                 texture_manager,
                 &ctx,
                 rect,
+                sorting_layers,
             );
+
+            // Outline the rest of a multi-selection
+            for &entity in selection.get_selected_set() {
+                if Some(entity) != *selected_entity {
+                    rendering::view_2d::render_selection_outline(
+                        &painter,
+                        entity,
+                        world,
+                        scene_camera,
+                        center,
+                        rendering::view_2d::SELECTED_OUTLINE_COLOR,
+                        texture_manager,
+                        &ctx,
+                    );
+                }
+            }
+
+            rendering::icons::render_icons(&painter, &icons, world, |e| selection.is_selected(e) || *selected_entity == Some(e), debug_draw.show_icon_labels);
             
             // Render transform gizmo for selected entity
             if let Some(entity) = *selected_entity {
@@ -176,6 +229,7 @@ pub fn render_scene_view(
                     center,
                     current_tool,
                     transform_space,
+                    &gizmo_metrics,
                 );
             }
         }
@@ -201,6 +255,17 @@ pub fn render_scene_view(
                 egui_renderer,
                 device,
             );
+
+            rendering::icons::render_icons(&painter, &icons, world, |e| selection.is_selected(e) || *selected_entity == Some(e), debug_draw.show_icon_labels);
+        }
+    }
+
+    // Sprites are picked with the game's quad math and draw order (topmost wins over
+    // the rough per-entity bounds the renderers report); icons win over sprites
+    if let Some(pointer) = response.hover_pos() {
+        let hits = interaction::picking::sprites_at(world, sorting_layers, scene_camera, scene_view_mode, rect, pointer, texture_manager);
+        if let Some(topmost) = rendering::icons::icon_at(&icons, pointer).or(hits.first().copied()) {
+            hovered_entity = Some(topmost);
         }
     }
 
@@ -241,7 +306,12 @@ pub fn render_scene_view(
     }
 
     // Focus on selected entity (F key)
-    if focus_pressed {
+    if focus_pressed && *scene_view_mode == SceneViewMode::Mode3D {
+        // 3D: glide to frame the selection's sprite/mesh/collider bounds
+        if let Some((center, size)) = selected_entity.and_then(|entity| interaction::camera::entity_bounds(world, entity)) {
+            scene_camera.frame_bounds(center, size, glam::Vec2::new(rect.width(), rect.height()));
+        }
+    } else if focus_pressed {
         if let Some(entity) = *selected_entity {
             if let Some(transform) = world.transforms.get(&entity) {
                 // Calculate center position of the entity
@@ -289,15 +359,119 @@ pub fn render_scene_view(
     }
     
     // Handle entity selection
-    let is_camera_control = response.dragged_by(egui::PointerButton::Middle) || 
-                           response.dragged_by(egui::PointerButton::Secondary) ||
-                           (ui.input(|i| i.modifiers.alt) && response.dragged_by(egui::PointerButton::Primary));
+    let is_camera_control = !gizmo_active && (
+        response.dragged_by(egui::PointerButton::Middle) ||
+        response.dragged_by(egui::PointerButton::Secondary) ||
+        (ui.input(|i| i.modifiers.alt) && response.dragged_by(egui::PointerButton::Primary)));
+
+    // Tile painting (2D): the Tile Palette's tool takes the primary button on a selected tilemap
+    let tile_brush = crate::tools::tile_painting::brush(ui.ctx())
+        .filter(|_| *scene_view_mode == SceneViewMode::Mode2D && !is_playing);
+    let tile_paint_entity = selected_entity.filter(|e| world.tilemaps.contains_key(e));
+    let tile_paint_mode = tile_brush.is_some() && tile_paint_entity.is_some();
+    if let (Some(brush), Some(entity)) = (&tile_brush, tile_paint_entity) {
+        interaction::tile_paint::handle_tile_painting(
+            ui, &response, &painter, world, entity, brush, scene_camera, center, texture_manager,
+        );
+    }
     
-    if response.clicked() && !response.dragged() && !is_camera_control {
-        if let Some(entity) = hovered_entity {
-            *selected_entity = Some(entity);
-        } else {
-            *selected_entity = None;
+    // Component handles (collider bounds, camera size, trigger zones) on the selected entity, 2D only.
+    // They are picked before the transform gizmo and entity selection.
+    let mut hovered_handle = None;
+    if *scene_view_mode == SceneViewMode::Mode2D && !tile_paint_mode {
+        if let Some(entity) = *selected_entity {
+            let handles = interaction::handles::collect_handles(world, entity, scene_camera, center, *show_colliders, debug_draw.show_trigger_zones);
+            let pick = |pointer: egui::Pos2| {
+                let gizmo_hovered = world.transforms.get(&entity).and_then(|t| {
+                    let screen_pos = scene_camera.world_to_screen(glam::Vec3::new(t.x(), t.y(), 0.0));
+                    interaction::transform::hit_test_gizmo(
+                        center.x + screen_pos.x,
+                        center.y + screen_pos.y,
+                        pointer,
+                        current_tool,
+                        scene_camera,
+                        scene_view_mode,
+                        transform_space,
+                        t,
+                        Some(rect),
+                        &gizmo_metrics,
+                    )
+                }).is_some();
+                interaction::handles::pick_handle(&handles, pointer, gizmo_hovered, &gizmo_metrics)
+            };
+
+            hovered_handle = response.hover_pos().and_then(pick);
+
+            if response.drag_started_by(egui::PointerButton::Primary) && !is_camera_control {
+                // Pick where the press began, not where the drag threshold was crossed
+                let press_pos = ui.input(|i| i.pointer.press_origin()).or(response.interact_pointer_pos());
+                if let Some(press_pos) = press_pos {
+                    if let Some(handle) = pick(press_pos) {
+                        *handle_drag = Some(interaction::handles::begin_handle_drag(
+                            handle, press_pos, world, scene_camera, center,
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(drag) = *handle_drag {
+        if response.dragged_by(egui::PointerButton::Primary) {
+            if let Some(pointer) = response.interact_pointer_pos() {
+                let modifiers = ui.input(|i| i.modifiers);
+                let snap = crate::tools::snapping::get_effective_snap_enabled(snap_settings, &modifiers)
+                    .then_some(&*snap_settings);
+                interaction::handles::update_handle_drag(&drag, pointer, world, scene_camera, center, snap);
+            }
+        }
+    }
+
+    if response.clicked() && !response.dragged() && !is_camera_control && hovered_handle.is_none() && !tile_paint_mode {
+        let modifiers = ui.input(|i| i.modifiers);
+        // Icons first; clicking again on the same spot cycles through overlapping sprites
+        let clicked_entity = match response.interact_pointer_pos() {
+            Some(pointer) => {
+                let hits = interaction::picking::sprites_at(world, sorting_layers, scene_camera, scene_view_mode, rect, pointer, texture_manager);
+                if let Some(entity) = rendering::icons::icon_at(&icons, pointer) {
+                    Some(entity)
+                } else if hits.is_empty() {
+                    hovered_entity
+                } else {
+                    interaction::picking::cycle_pick(ui.ctx(), &hits, *selected_entity, pointer)
+                }
+            }
+            None => hovered_entity,
+        };
+        match (clicked_entity, SelectionManager::get_selection_mode(&modifiers)) {
+            (Some(entity), SelectionMode::Toggle) => {
+                // Ctrl+Click adds/removes; keep the current primary in the set first
+                if let Some(primary) = *selected_entity {
+                    selection.select(primary, SelectionMode::Add);
+                }
+                selection.select(entity, SelectionMode::Toggle);
+                if selection.is_selected(entity) {
+                    *selected_entity = Some(entity);
+                } else if *selected_entity == Some(entity) {
+                    *selected_entity = selection.get_first();
+                }
+            }
+            (Some(entity), SelectionMode::Range) => {
+                if let Some(primary) = *selected_entity {
+                    selection.select(primary, SelectionMode::Add);
+                }
+                selection.select(entity, SelectionMode::Add);
+                *selected_entity = Some(entity);
+            }
+            (Some(entity), _) => {
+                selection.select(entity, SelectionMode::Replace);
+                *selected_entity = Some(entity);
+            }
+            (None, SelectionMode::Replace) => {
+                selection.clear();
+                *selected_entity = None;
+            }
+            (None, _) => {}
         }
     }
 
@@ -342,6 +516,7 @@ pub fn render_scene_view(
                     transform_space,
                     &transform_copy,
                     Some(rect),
+                    &gizmo_metrics,
                 )
             } else {
                 None
@@ -360,9 +535,13 @@ pub fn render_scene_view(
                 &transform_copy,
                 Some(rect),
                 highlight_axis,
+                &gizmo_metrics,
             );
             
-            if !is_camera_control {
+            if !is_camera_control && handle_drag.is_none() && !tile_paint_mode {
+                let modifiers = ui.input(|i| i.modifiers);
+                let snap = crate::tools::snapping::get_effective_snap_enabled(snap_settings, &modifiers)
+                    .then_some(&*snap_settings);
                 interaction::transform::handle_gizmo_interaction_stateful(
                     &response,
                     sel_entity,
@@ -377,27 +556,142 @@ pub fn render_scene_view(
                     &transform_copy,
                     scene_view_mode,
                     Some(rect),
+                    snap,
+                    &gizmo_metrics,
                 );
+
+                // Vertex snapping (2D): hold V while moving to catch nearby corners/edge midpoints
+                if *dragging_entity == Some(sel_entity)
+                    && *current_tool == TransformTool::Move
+                    && *scene_view_mode == SceneViewMode::Mode2D
+                    && ui.input(|i| i.key_down(egui::Key::V))
+                {
+                    if let Some(vertex_snap) = snap_to_nearby_entity(world, sel_entity, selection, scene_camera, snap_settings) {
+                        if let Some(t) = world.transforms.get_mut(&sel_entity) {
+                            t.position[0] = vertex_snap.position.x;
+                            t.position[1] = vertex_snap.position.y;
+                        }
+                        crate::tools::snapping::render_vertex_snap_guide(&painter, &vertex_snap, scene_camera, center, snap_settings);
+                    }
+                }
+
+                // Carry the rest of the selection along with the gizmo
+                if *dragging_entity == Some(sel_entity) && selection.count() > 1 {
+                    if let Some(after) = world.transforms.get(&sel_entity).cloned() {
+                        let selected = selection.get_selected();
+                        crate::tools::selection::apply_gizmo_delta_to_selected(
+                            &selected,
+                            sel_entity,
+                            &transform_copy,
+                            &after,
+                            world,
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    // Draw component handles on top of the gizmo
+    if *scene_view_mode == SceneViewMode::Mode2D {
+        if let Some(entity) = *selected_entity {
+            let handles = interaction::handles::collect_handles(world, entity, scene_camera, center, *show_colliders, debug_draw.show_trigger_zones);
+            rendering::gizmos::render_component_handles(
+                &painter,
+                &handles,
+                world,
+                hovered_handle.map(|h| h.kind),
+                handle_drag.map(|d| d.kind),
+                &gizmo_metrics,
+            );
+        }
+    }
+
+    // Marquee selection (2D): drag on empty space
+    if *scene_view_mode == SceneViewMode::Mode2D && !is_camera_control && !tile_paint_mode {
+        if response.drag_started_by(egui::PointerButton::Primary)
+            && dragging_entity.is_none()
+            && handle_drag.is_none()
+            && hovered_entity.is_none()
+        {
+            if let Some(pos) = response.interact_pointer_pos() {
+                let modifiers = ui.input(|i| i.modifiers);
+                let mode = match SelectionManager::get_selection_mode(&modifiers) {
+                    SelectionMode::Range => SelectionMode::Add,
+                    mode => mode,
+                };
+                selection.start_box_selection(pos, mode);
+            }
+        }
+
+        if selection.get_box_selection().is_some() {
+            if response.dragged_by(egui::PointerButton::Primary) {
+                if let Some(pos) = response.interact_pointer_pos() {
+                    selection.update_box_selection(pos);
+                }
+            }
+
+            if response.drag_stopped() {
+                let mode = selection.get_box_selection().map(|b| b.mode);
+                if let Some(primary) = *selected_entity {
+                    if mode != Some(SelectionMode::Replace) {
+                        selection.select(primary, SelectionMode::Add);
+                    }
+                }
+                let picked = selection.finish_box_selection(world, scene_camera, center);
+                if picked.is_empty() && mode == Some(SelectionMode::Replace) {
+                    selection.clear();
+                }
+                *selected_entity = match selected_entity.filter(|&e| selection.is_selected(e)) {
+                    Some(primary) => Some(primary),
+                    None => picked.iter().copied().find(|&e| selection.is_selected(e)),
+                };
+            } else {
+                selection.render_box_selection(&painter);
             }
         }
+    } else {
+        selection.cancel_box_selection();
+    }
+
+    // Which of the overlapping entities under the last click is selected ("2 of 5")
+    if let Some(status) = interaction::picking::pick_status(ui.ctx(), *selected_entity) {
+        let text = format!("{} (click again to cycle)", status);
+        let galley = painter.layout_no_wrap(text, egui::FontId::proportional(12.0), egui::Color32::from_gray(220));
+        let bar = egui::Rect::from_min_size(
+            egui::pos2(rect.min.x + 6.0, rect.max.y - galley.size().y - 10.0),
+            galley.size() + egui::vec2(12.0, 6.0),
+        );
+        painter.rect_filled(bar, 3.0, egui::Color32::from_black_alpha(160));
+        painter.galley(bar.min + egui::vec2(6.0, 3.0), galley, egui::Color32::from_gray(220));
     }
 
     // Clear drag state when not dragging
     if !response.dragged() {
         *dragging_entity = None;
         *drag_axis = None;
+        *handle_drag = None;
     }
     
     // Handle drag-drop from asset browser
     if drag_drop.is_dragging() {
+        // The drag belongs to the asset browser widget, so track the raw pointer
+        // instead of this view's response
+        let pointer_in_view = ui.ctx().pointer_latest_pos().filter(|pos| rect.contains(*pos));
+        
         // Update drop position
-        if let Some(hover_pos) = response.hover_pos() {
+        if let Some(hover_pos) = pointer_in_view {
             drag_drop.set_drop_position(hover_pos);
         }
         
         // Handle drop
-        if response.drag_stopped() {
-            if let Some(asset) = drag_drop.get_dragged_asset() {
+        if pointer_in_view.is_some() && ui.input(|i| i.pointer.any_released()) {
+            if let Some(asset) = drag_drop.get_dragged_asset().cloned() {
+                let drop_world_pos = drag_drop.drop_position.map(|screen_pos| {
+                    let relative = glam::Vec2::new(screen_pos.x - center.x, screen_pos.y - center.y);
+                    scene_camera.screen_to_world(relative).truncate()
+                });
+                
                 // Check if it's a sprite file
                 if asset.path.extension().and_then(|s| s.to_str()) == Some("sprite") {
                     // Load sprite metadata
@@ -434,16 +728,29 @@ pub fn render_scene_view(
                                     asset_id: None,
                                     width: first_sprite.width as f32,
                                     height: first_sprite.height as f32,
-                                    color: [1.0, 1.0, 1.0, 1.0],
+                                    color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
                                     billboard: false,
                                     flip_x: false,
                                     flip_y: false,
                                     sprite_rect: Some([first_sprite.x, first_sprite.y, first_sprite.width, first_sprite.height]),
+                                    rect_rotated: first_sprite.rotated,
+                                    frame_name: Some(first_sprite.name.clone()),
                                     pixels_per_unit: 100.0,
+                                    pivot: Some(first_sprite.pivot),
                                     sorting_layer: "Default".to_string(),
                                     order_in_layer: 0,
                                     rendering_layer_mask: 1,
                                 });
+
+                                // Physics shape traced in the sprite editor
+                                if !first_sprite.polygon.is_empty() {
+                                    world.polygon_colliders.insert(entity, ecs::PolygonCollider::from_sprite_outline(
+                                        &first_sprite.polygon,
+                                        [first_sprite.width, first_sprite.height],
+                                        first_sprite.pivot,
+                                        100.0,
+                                    ));
+                                }
                             }
                             
                             // Add SpriteSheet component
@@ -462,6 +769,7 @@ pub fn render_scene_view(
                                     width: sprite_def.width,
                                     height: sprite_def.height,
                                     name: Some(sprite_def.name.clone()),
+                                    rotated: sprite_def.rotated,
                                 });
                             }
                             
@@ -484,7 +792,35 @@ pub fn render_scene_view(
                     } else {
                         log::error!("Failed to load sprite metadata from {:?}", asset.path);
                     }
-                } 
+                }
+                // Texture: new sprite entity at the drop position
+                else if asset.asset_type == crate::asset_manager::AssetType::Sprite {
+                    if let Some(world_pos) = drop_world_pos {
+                        let entity = spawn_texture_sprite(world, &asset, project_path, world_pos);
+                        selection.select(entity, SelectionMode::Replace);
+                        *selected_entity = Some(entity);
+                        log::info!("Created sprite entity from {}", asset.name);
+                    }
+                }
+                // Prefab: instantiate at the drop position
+                else if asset.asset_type == crate::asset_manager::AssetType::Prefab {
+                    if let Some(world_pos) = drop_world_pos {
+                        match crate::prefab::Prefab::load(&asset.path)
+                            .and_then(|prefab| prefab.instantiate(world, entity_names, None))
+                        {
+                            Ok(root) => {
+                                if let Some(transform) = world.transforms.get_mut(&root) {
+                                    transform.position[0] = world_pos.x;
+                                    transform.position[1] = world_pos.y;
+                                }
+                                selection.select(root, SelectionMode::Replace);
+                                *selected_entity = Some(root);
+                                log::info!("Instantiated prefab {}", asset.name);
+                            }
+                            Err(e) => log::error!("Failed to instantiate prefab {:?}: {}", asset.path, e),
+                        }
+                    }
+                }
                 // Handle XSG files
                 else if asset.path.extension().and_then(|s| s.to_str()) == Some("xsg") {
                     if let Ok(xsg) = engine::assets::xsg_importer::XsgImporter::load_from_asset(asset_loader, &asset.path.to_string_lossy()) {
@@ -618,3 +954,78 @@ pub fn render_scene_view(
         );
     }
 }
+
+/// Vertex snap for the dragged entity against unselected sprites/colliders,
+/// within `vertex_snap_distance` screen pixels
+fn snap_to_nearby_entity(
+    world: &World,
+    entity: Entity,
+    selection: &SelectionManager,
+    scene_camera: &SceneCamera,
+    snap_settings: &crate::tools::snapping::SnapSettings,
+) -> Option<crate::tools::snapping::VertexSnap> {
+    let transform = world.transforms.get(&entity)?;
+    let (center, size) = interaction::camera::entity_bounds(world, entity)?;
+
+    let targets: Vec<(glam::Vec2, glam::Vec2)> = world.transforms.keys()
+        .copied()
+        .filter(|&other| other != entity && !selection.is_selected(other))
+        .filter(|other| world.sprites.contains_key(other) || world.colliders.contains_key(other))
+        .filter_map(|other| interaction::camera::entity_bounds(world, other))
+        .map(|(c, s)| (c.truncate(), s.truncate()))
+        .collect();
+
+    crate::tools::snapping::find_vertex_snap(
+        glam::Vec2::new(transform.position[0], transform.position[1]),
+        center.truncate(),
+        size.truncate(),
+        &targets,
+        snap_settings.vertex_snap_distance / scene_camera.zoom.max(0.0001),
+    )
+}
+
+/// Create a sprite entity for a dropped texture file
+fn spawn_texture_sprite(
+    world: &mut World,
+    asset: &crate::DraggedAsset,
+    project_path: Option<&std::path::PathBuf>,
+    world_pos: glam::Vec2,
+) -> Entity {
+    // Sprites reference textures relative to the project root
+    let texture_id = project_path
+        .and_then(|root| asset.path.strip_prefix(root).ok())
+        .unwrap_or(&asset.path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    let (width, height) = image::image_dimensions(&asset.path)
+        .map(|(w, h)| (w as f32, h as f32))
+        .unwrap_or((100.0, 100.0));
+
+    let entity = world.spawn();
+    world.transforms.insert(entity, ecs::Transform {
+        position: [world_pos.x, world_pos.y, 0.0],
+        rotation: [0.0, 0.0, 0.0],
+        scale: [1.0, 1.0, 1.0],
+    });
+    let name = asset.path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| asset.name.clone());
+    world.names.insert(entity, name);
+    world.sprites.insert(entity, ecs::Sprite {
+        texture_id,
+        asset_id: None,
+        width,
+        height,
+        color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
+        billboard: false,
+        flip_x: false,
+        flip_y: false,
+        sprite_rect: None,
+        rect_rotated: false,
+        frame_name: None,
+        pixels_per_unit: 100.0,
+        pivot: None,
+        sorting_layer: "Default".to_string(),
+        order_in_layer: 0,
+        rendering_layer_mask: 1,
+    });
+    entity
+}
@@ -8,6 +8,8 @@ use crate::ui::TransformTool;
 use crate::SceneCamera;
 use super::super::types::*;
 use super::projection_3d;
+use super::super::interaction::handles::{Handle, HandleKind};
+use super::super::gizmo_metrics::GizmoMetrics;
 
 /// Render scene gizmo (XYZ axes in top-right corner)
 pub fn render_scene_gizmo_visual(
@@ -125,9 +127,9 @@ pub fn render_transform_gizmo(
     transform: &ecs::Transform,
     viewport_rect: Option<egui::Rect>,
     highlight_axis: Option<u8>,
+    metrics: &GizmoMetrics,
 ) {
-    let gizmo_size = 80.0;
-    let handle_size = 10.0;
+    let handle_size = metrics.handle_radius();
     
     // Choose rendering mode based on View Mode
     match scene_view_mode {
@@ -251,7 +253,7 @@ pub fn render_transform_gizmo(
                         draw_ring(right, up, col_z, "Z", (0.7, 0.7));
 
                         // Outer white circle (Screen space Billboarding)
-                        let radius_screen = gizmo_size * 0.8;
+                        let radius_screen = metrics.rotate_ring_radius();
                         painter.circle_stroke(p_origin, radius_screen, egui::Stroke::new(1.0, egui::Color32::from_rgba_premultiplied(255, 255, 255, 100)));
                     }
                     TransformTool::Scale => {
@@ -295,19 +297,15 @@ pub fn render_transform_gizmo(
             match current_tool {
                 TransformTool::View => {}
                 TransformTool::Move => {
+                    // Same handle positions as hit_test_gizmo
+                    let (x_end, y_end) = metrics.axis_ends_2d(egui::pos2(screen_x, screen_y), rotation_rad);
+
                     // X Axis (Red)
-                    let x_dir = (rotation_rad.cos(), rotation_rad.sin());
-                    // Invert Y for screen space (World Up = Screen Up/Negative)
-                    let x_end = egui::pos2(screen_x + x_dir.0 * gizmo_size, screen_y - x_dir.1 * gizmo_size);
                     painter.line_segment([egui::pos2(screen_x, screen_y), x_end], egui::Stroke::new(4.0, egui::Color32::from_rgb(255, 0, 0)));
                     painter.circle_filled(x_end, handle_size, egui::Color32::from_rgb(255, 0, 0));
                     painter.text(egui::pos2(x_end.x + 12.0, x_end.y), egui::Align2::LEFT_CENTER, "X", egui::FontId::proportional(14.0), egui::Color32::from_rgb(255, 0, 0));
 
                     // Y Axis (Green)
-                    let y_angle = rotation_rad + std::f32::consts::PI / 2.0; // +90 degrees for Y axis
-                    let y_dir = (y_angle.cos(), y_angle.sin());
-                    // Invert Y for screen space
-                    let y_end = egui::pos2(screen_x + y_dir.0 * gizmo_size, screen_y - y_dir.1 * gizmo_size);
                     painter.line_segment([egui::pos2(screen_x, screen_y), y_end], egui::Stroke::new(4.0, egui::Color32::from_rgb(0, 255, 0)));
                     painter.circle_filled(y_end, handle_size, egui::Color32::from_rgb(0, 255, 0));
                     painter.text(egui::pos2(y_end.x, y_end.y - 12.0), egui::Align2::CENTER_BOTTOM, "Y", egui::FontId::proportional(14.0), egui::Color32::from_rgb(0, 255, 0));
@@ -315,7 +313,7 @@ pub fn render_transform_gizmo(
                     painter.circle_filled(egui::pos2(screen_x, screen_y), handle_size * 1.2, egui::Color32::from_rgb(255, 255, 0));
                 }
                 TransformTool::Rotate => {
-                    let radius = gizmo_size * 0.8;
+                    let radius = metrics.rotate_ring_radius();
                     painter.circle_stroke(egui::pos2(screen_x, screen_y), radius, egui::Stroke::new(5.0, egui::Color32::from_rgb(0, 150, 255)));
                     painter.circle_filled(egui::pos2(screen_x, screen_y), 3.0, egui::Color32::from_rgb(0, 150, 255));
                     for i in 0..4 {
@@ -326,18 +324,13 @@ pub fn render_transform_gizmo(
                     }
                 }
                 TransformTool::Scale => {
+                    let (x_end, y_end) = metrics.axis_ends_2d(egui::pos2(screen_x, screen_y), rotation_rad);
+
                     // X Axis (Red)
-                    let x_dir = (rotation_rad.cos(), rotation_rad.sin());
-                    // Invert Y
-                    let x_end = egui::pos2(screen_x + x_dir.0 * gizmo_size, screen_y - x_dir.1 * gizmo_size);
                     painter.line_segment([egui::pos2(screen_x, screen_y), x_end], egui::Stroke::new(4.0, egui::Color32::from_rgb(255, 0, 0)));
                     painter.rect_filled(egui::Rect::from_center_size(x_end, egui::vec2(handle_size * 1.8, handle_size * 1.8)), 0.0, egui::Color32::from_rgb(255, 0, 0));
 
                     // Y Axis (Green)
-                    let y_angle = rotation_rad + std::f32::consts::PI / 2.0;
-                    let y_dir = (y_angle.cos(), y_angle.sin());
-                    // Invert Y
-                    let y_end = egui::pos2(screen_x + y_dir.0 * gizmo_size, screen_y - y_dir.1 * gizmo_size);
                     painter.line_segment([egui::pos2(screen_x, screen_y), y_end], egui::Stroke::new(4.0, egui::Color32::from_rgb(0, 255, 0)));
                     painter.rect_filled(egui::Rect::from_center_size(y_end, egui::vec2(handle_size * 1.8, handle_size * 1.8)), 0.0, egui::Color32::from_rgb(0, 255, 0));
                     
@@ -360,6 +353,24 @@ pub fn render_collider_gizmo(
     _is_selected: bool,
     is_2d_mode: bool,
 ) {
+    if let Some(polygon) = world.polygon_colliders.get(&entity) {
+        if is_2d_mode && polygon.points.len() >= 2 {
+            let (rotation_rad, scale) = world.transforms.get(&entity)
+                .map(|t| (t.rotation[2].to_radians(), glam::Vec2::new(t.scale[0], t.scale[1])))
+                .unwrap_or((0.0, glam::Vec2::ONE));
+            let frame = LocalScreenFrame {
+                origin: egui::pos2(screen_x, screen_y),
+                rotation: rotation_rad,
+                zoom: scene_camera.zoom,
+            };
+            let color = if polygon.is_trigger { TRIGGER_COLOR } else { COLLIDER_COLOR };
+            let points = polygon.points.iter()
+                .map(|p| frame.to_screen(glam::Vec2::new(p[0], p[1]) * scale))
+                .collect();
+            painter.add(egui::Shape::closed_line(points, egui::Stroke::new(1.5, color)));
+        }
+    }
+
     if let Some(collider) = world.colliders.get(&entity) {
         // Get entity transform for rotation and scale
         let (rotation_rad, scale) = world.transforms.get(&entity)
@@ -369,69 +380,43 @@ pub fn render_collider_gizmo(
             ))
             .unwrap_or((0.0, glam::Vec2::ONE));
         
-        // Apply transform.scale to collider size (Unity-like: size * scale)
-        let world_width = collider.get_world_width(scale.x);
-        let world_height = collider.get_world_height(scale.y);
-        let size = egui::vec2(
-            world_width * scene_camera.zoom,
-            world_height * scene_camera.zoom
-        );
-        
-        // Apply offset
-        let world_offset = collider.get_world_offset(scale.x, scale.y);
-        let offset_screen = egui::vec2(
-            world_offset[0] * scene_camera.zoom,
-            world_offset[1] * scene_camera.zoom
-        );
-        let screen_x = screen_x + offset_screen.x;
-        // Only flip Y in 3D mode because 3D projection usually flips Y
-        // But in our Y-Up 2D mode, +WorldY means -ScreenY (Up), so we subtract offset in both cases
-        let screen_y = if is_2d_mode {
-            screen_y - offset_screen.y  // Y-Up 2D: Subtract offset to go Up on screen
-        } else {
-            screen_y - offset_screen.y  // 3D mode: Subtract offset (already handled by projection?)
+        // Offset and size are applied in the entity's local (rotated) space, like the physics body,
+        // so a rotated box is drawn as the oriented box it collides as
+        let frame = LocalScreenFrame {
+            origin: egui::pos2(screen_x, screen_y),
+            rotation: rotation_rad,
+            zoom: scene_camera.zoom,
         };
+        let (local_center, half) = collider_bounds_2d(collider, scale, world.sprite_flip(entity));
+        let color = if collider.is_trigger { TRIGGER_COLOR } else { COLLIDER_COLOR };
+        let stroke = egui::Stroke::new(1.5, color);
         
-        if rotation_rad.abs() < 0.01 {
-            // No rotation - use simple rect
-            painter.rect_stroke(
-                egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), size),
-                0.0,
-                egui::Stroke::new(1.5, egui::Color32::from_rgb(0, 255, 100)),
-                egui::epaint::StrokeKind::Outside,
-            );
-        } else {
-            // Has rotation - draw as rotated polygon outline
-            let half_width = size.x / 2.0;
-            let half_height = size.y / 2.0;
-            
-            let cos_r = rotation_rad.cos();
-            let sin_r = rotation_rad.sin();
-            
-            let corners = [
-                egui::pos2(
-                    screen_x + (-half_width * cos_r - (-half_height) * sin_r),
-                    screen_y + (-half_width * sin_r + (-half_height) * cos_r),
-                ),
-                egui::pos2(
-                    screen_x + (half_width * cos_r - (-half_height) * sin_r),
-                    screen_y + (half_width * sin_r + (-half_height) * cos_r),
-                ),
-                egui::pos2(
-                    screen_x + (half_width * cos_r - half_height * sin_r),
-                    screen_y + (half_width * sin_r + half_height * cos_r),
-                ),
-                egui::pos2(
-                    screen_x + (-half_width * cos_r - half_height * sin_r),
-                    screen_y + (-half_width * sin_r + half_height * cos_r),
-                ),
-            ];
-            
-            // Draw rotated collider outline
-            painter.add(egui::Shape::closed_line(
-                corners.to_vec(),
-                egui::Stroke::new(1.5, egui::Color32::from_rgb(0, 255, 100)),
-            ));
+        match collider.shape {
+            ecs::ColliderShape::Box => {
+                let corners = [(-1.0, 1.0), (1.0, 1.0), (1.0, -1.0), (-1.0, -1.0)]
+                    .iter()
+                    .map(|&(x, y)| frame.to_screen(local_center + half * glam::Vec2::new(x, y)))
+                    .collect();
+                painter.add(egui::Shape::closed_line(corners, stroke));
+            }
+            ecs::ColliderShape::Circle => {
+                painter.circle_stroke(frame.to_screen(local_center), half.x * frame.zoom, stroke);
+            }
+            ecs::ColliderShape::Capsule => {
+                // Two half circles joined by straight sides
+                let radius = half.x;
+                let straight = (half.y - radius).max(0.0);
+                let segments = 16;
+                let mut points = Vec::with_capacity((segments + 1) * 2);
+                for (cap_y, start_angle) in [(straight, 0.0), (-straight, std::f32::consts::PI)] {
+                    for i in 0..=segments {
+                        let angle = start_angle + std::f32::consts::PI * i as f32 / segments as f32;
+                        let local = local_center + glam::Vec2::new(angle.cos() * radius, cap_y + angle.sin() * radius);
+                        points.push(frame.to_screen(local));
+                    }
+                }
+                painter.add(egui::Shape::closed_line(points, stroke));
+            }
         }
     } else if let Some(collider_3d) = world.colliders_3d.get(&entity) {
         // 3D Collider rendering
@@ -506,6 +491,159 @@ pub fn render_collider_gizmo(
     }
 }
 
+/// Collider outline color
+const COLLIDER_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 255, 100);
+/// Trigger colliders get a distinct color so they're easy to tell from solid ones
+const TRIGGER_COLOR: egui::Color32 = egui::Color32::from_rgb(80, 170, 255);
+/// Trigger Zone areas (not colliders: nothing bumps into them)
+const ZONE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 150, 40);
+/// One-shot zones that already fired
+const FIRED_ZONE_COLOR: egui::Color32 = egui::Color32::from_rgb(140, 140, 140);
+
+/// Maps between an entity's local 2D space (world units, Y-up, rotated with the
+/// entity) and screen space
+pub struct LocalScreenFrame {
+    /// Screen position of the entity origin
+    pub origin: egui::Pos2,
+    /// Entity Z rotation in radians, counter-clockwise in world space like the sprite
+    /// renderer and physics
+    pub rotation: f32,
+    /// Pixels per world unit
+    pub zoom: f32,
+}
+
+impl LocalScreenFrame {
+    pub fn to_screen(&self, local: glam::Vec2) -> egui::Pos2 {
+        let world = glam::Vec2::from_angle(self.rotation).rotate(local) * self.zoom;
+        egui::pos2(self.origin.x + world.x, self.origin.y - world.y)
+    }
+
+    pub fn to_local(&self, screen: egui::Pos2) -> glam::Vec2 {
+        let d = screen - self.origin;
+        let world = glam::Vec2::new(d.x, -d.y) / self.zoom;
+        glam::Vec2::from_angle(-self.rotation).rotate(world)
+    }
+}
+
+/// Collider center and half extents in the entity's local space (world units), the
+/// offset mirrored with the sprite's flips like the physics body. Circles report the
+/// radius on both axes.
+pub fn collider_bounds_2d(collider: &ecs::Collider, scale: glam::Vec2, flip: [bool; 2]) -> (glam::Vec2, glam::Vec2) {
+    let offset = collider.get_flipped_offset(scale.x, scale.y, flip);
+    let half = match collider.shape {
+        ecs::ColliderShape::Circle => glam::Vec2::splat(collider.get_world_radius(scale.x)),
+        _ => glam::Vec2::new(
+            collider.get_world_width(scale.x).abs() / 2.0,
+            collider.get_world_height(scale.y).abs() / 2.0,
+        ),
+    };
+    (glam::Vec2::from(offset), half)
+}
+
+/// Trigger zone center and half extents in the entity's local space (world units),
+/// axis aligned like the area physics tests. Circles report the radius on both axes.
+pub fn trigger_zone_bounds_2d(zone: &ecs::TriggerZone, scale: glam::Vec2) -> (glam::Vec2, glam::Vec2) {
+    let offset = glam::Vec2::new(zone.offset[0] * scale.x, zone.offset[1] * scale.y);
+    let half = match zone.shape {
+        ecs::TriggerZoneShape::Box { width, height } => glam::Vec2::new(width.abs() * scale.x.abs(), height.abs() * scale.y.abs()) / 2.0,
+        ecs::TriggerZoneShape::Circle { radius } => glam::Vec2::splat(radius.abs() * scale.x.abs().max(scale.y.abs())),
+    };
+    (offset, half)
+}
+
+/// Render a TriggerZone's area: a tinted outline with a light fill, grey with a check
+/// mark once a one-shot zone has fired
+pub fn render_trigger_zone_gizmo(
+    painter: &egui::Painter,
+    entity: Entity,
+    world: &World,
+    scene_camera: &SceneCamera,
+    center: egui::Pos2,
+    is_selected: bool,
+) {
+    let (Some(zone), Some(area)) = (world.trigger_zones.get(&entity), physics::trigger_zones::zone_area(world, entity)) else {
+        return;
+    };
+    let to_screen = |point: [f32; 2]| {
+        let screen = scene_camera.world_to_screen(glam::Vec3::new(point[0], point[1], 0.0));
+        center + egui::vec2(screen.x, screen.y)
+    };
+    let color = if zone.is_armed() { ZONE_COLOR } else { FIRED_ZONE_COLOR };
+    let fill = color.gamma_multiply(if is_selected { 0.18 } else { 0.08 });
+    let stroke = egui::Stroke::new(if is_selected { 2.0 } else { 1.5 }, color);
+
+    let label_pos = match area {
+        physics::TriggerZoneArea::Box { min, max } => {
+            let rect = egui::Rect::from_two_pos(to_screen(min), to_screen(max));
+            painter.rect(rect, 0.0, fill, stroke, egui::epaint::StrokeKind::Middle);
+            rect.left_top()
+        }
+        physics::TriggerZoneArea::Circle { center: circle_center, radius } => {
+            let screen_center = to_screen(circle_center);
+            let screen_radius = radius * scene_camera.zoom;
+            painter.circle(screen_center, screen_radius, fill, stroke);
+            screen_center + egui::vec2(-screen_radius, -screen_radius) * std::f32::consts::FRAC_1_SQRT_2
+        }
+    };
+
+    let mut label = String::from(if zone.is_armed() { "⚡" } else { "✔" });
+    if let Some(event) = zone.event.as_deref().filter(|event| is_selected && !event.is_empty()) {
+        label.push(' ');
+        label.push_str(event);
+    }
+    painter.text(label_pos + egui::vec2(3.0, 2.0), egui::Align2::LEFT_TOP, label, egui::FontId::proportional(12.0), color);
+}
+
+/// Render component drag handles (collider bounds, camera size) at a constant pixel size
+pub fn render_component_handles(
+    painter: &egui::Painter,
+    handles: &[Handle],
+    world: &World,
+    hovered: Option<HandleKind>,
+    active: Option<HandleKind>,
+    metrics: &GizmoMetrics,
+) {
+    let size = metrics.component_handle_size();
+    for handle in handles {
+        let highlighted = hovered == Some(handle.kind) || active == Some(handle.kind);
+        let base_color = match handle.kind {
+            HandleKind::CameraSize => egui::Color32::from_rgb(255, 220, 0),
+            HandleKind::ZoneCenter | HandleKind::ZoneResize { .. } => ZONE_COLOR,
+            _ if world.colliders.get(&handle.entity).is_some_and(|c| c.is_trigger) => TRIGGER_COLOR,
+            _ => COLLIDER_COLOR,
+        };
+        let fill = if highlighted { egui::Color32::WHITE } else { base_color };
+        let stroke = egui::Stroke::new(1.0, egui::Color32::BLACK);
+
+        match handle.kind {
+            HandleKind::ColliderCenter | HandleKind::ZoneCenter => {
+                painter.circle(handle.pos, size / 2.0, fill, stroke);
+            }
+            _ => {
+                let rect = egui::Rect::from_center_size(handle.pos, egui::vec2(size, size));
+                painter.rect(rect, 0.0, fill, stroke, egui::epaint::StrokeKind::Middle);
+            }
+        }
+
+        // Show the value being edited next to the camera handle
+        if handle.kind == HandleKind::CameraSize && highlighted {
+            if let Some(camera) = world.cameras.get(&handle.entity) {
+                let text = match camera.projection {
+                    ecs::CameraProjection::Orthographic => format!("Size {:.2}", camera.orthographic_size),
+                    ecs::CameraProjection::Perspective => format!("FOV {:.0}°", camera.fov),
+                };
+                painter.text(
+                    handle.pos + egui::vec2(size, -size),
+                    egui::Align2::LEFT_BOTTOM,
+                    text,
+                    egui::FontId::proportional(12.0),
+                    base_color,
+                );
+            }
+        }
+    }
+}
+
 /// Render velocity gizmo
 pub fn render_velocity_gizmo(
     painter: &egui::Painter,
@@ -1048,26 +1186,8 @@ pub fn render_camera_viewport_bounds(
     if let (Some(camera), Some(transform)) = (world.cameras.get(&camera_entity), world.transforms.get(&camera_entity)) {
         // Get camera position in world space
         let cam_world_pos = glam::Vec3::new(transform.x(), transform.y(), 0.0);
-        
-        // Calculate viewport size in world units based on orthographic_size
-        // orthographic_size is the half-height of the camera view
-        let viewport_height = camera.orthographic_size * 2.0;
-        
-        // Calculate aspect ratio from viewport_rect
-        // viewport_rect is [x, y, width, height] where width and height are normalized (0-1)
-        let viewport_width_normalized = camera.viewport_rect[2];
-        let viewport_height_normalized = camera.viewport_rect[3];
-        
-        // Calculate actual aspect ratio
-        // For a typical game window, we assume 16:9 as base, but scale by viewport rect
-        let base_aspect_ratio = 16.0 / 9.0;
-        let aspect_ratio = if viewport_height_normalized > 0.0 {
-            base_aspect_ratio * (viewport_width_normalized / viewport_height_normalized)
-        } else {
-            base_aspect_ratio
-        };
-        
-        let viewport_width = viewport_height * aspect_ratio;
+        let half_size = camera_view_half_size(camera, transform);
+        let aspect_ratio = camera_aspect_ratio(camera);
         
         // Convert camera world position to screen position
         let cam_screen_pos = scene_camera.world_to_screen(cam_world_pos);
@@ -1075,8 +1195,8 @@ pub fn render_camera_viewport_bounds(
         let cam_screen_y = center.y + cam_screen_pos.y;
         
         // Calculate viewport bounds in screen space
-        let half_width = (viewport_width / 2.0) * scene_camera.zoom;
-        let half_height = (viewport_height / 2.0) * scene_camera.zoom;
+        let half_width = half_size.x * scene_camera.zoom;
+        let half_height = half_size.y * scene_camera.zoom;
         
         // Draw viewport rectangle (yellow outline)
         let viewport_rect = egui::Rect::from_center_size(
@@ -1092,6 +1212,16 @@ pub fn render_camera_viewport_bounds(
             egui::epaint::StrokeKind::Outside,
         );
         
+        // Perspective cameras: frustum edges from the camera to the view rectangle on the Z=0 plane
+        if camera.projection == ecs::CameraProjection::Perspective {
+            for corner in [viewport_rect.left_top(), viewport_rect.right_top(), viewport_rect.left_bottom(), viewport_rect.right_bottom()] {
+                painter.line_segment(
+                    [egui::pos2(cam_screen_x, cam_screen_y), corner],
+                    egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(255, 220, 0, 120)),
+                );
+            }
+        }
+        
         // Draw corner markers (Unity style)
         let corner_size = 10.0;
         let corners = [
@@ -1132,6 +1262,40 @@ pub fn render_camera_viewport_bounds(
     }
 }
 
+/// Distance used for the perspective view rectangle when the camera sits on the Z=0 plane
+const DEFAULT_PERSPECTIVE_DISTANCE: f32 = 10.0;
+
+/// Aspect ratio of a camera's view, assuming a 16:9 window scaled by the viewport rect
+pub fn camera_aspect_ratio(camera: &ecs::Camera) -> f32 {
+    // viewport_rect is [x, y, width, height] where width and height are normalized (0-1)
+    let viewport_width_normalized = camera.viewport_rect[2];
+    let viewport_height_normalized = camera.viewport_rect[3];
+    let base_aspect_ratio = 16.0 / 9.0;
+    if viewport_height_normalized > 0.0 {
+        base_aspect_ratio * (viewport_width_normalized / viewport_height_normalized)
+    } else {
+        base_aspect_ratio
+    }
+}
+
+/// Distance from a perspective camera to the Z=0 plane its 2D view is measured on
+pub fn camera_plane_distance(transform: &ecs::Transform) -> f32 {
+    let distance = transform.position[2].abs();
+    if distance < 0.01 { DEFAULT_PERSPECTIVE_DISTANCE } else { distance }
+}
+
+/// Half width/height (world units) of the area a camera sees on the Z=0 plane.
+/// Orthographic: orthographic_size is the half height. Perspective: derived from the FOV.
+pub fn camera_view_half_size(camera: &ecs::Camera, transform: &ecs::Transform) -> glam::Vec2 {
+    let half_height = match camera.projection {
+        ecs::CameraProjection::Orthographic => camera.orthographic_size,
+        ecs::CameraProjection::Perspective => {
+            camera_plane_distance(transform) * (camera.fov.to_radians() / 2.0).tan()
+        }
+    };
+    glam::Vec2::new(half_height * camera_aspect_ratio(camera), half_height)
+}
+
 /// Render 3D selection box (wireframe) for selected entity
 pub fn render_selection_box_3d(
     painter: &egui::Painter,
@@ -0,0 +1,121 @@
+//! Entity Icons
+//!
+//! Billboard icons for entities that draw nothing themselves (cameras, EditorIcon
+//! markers), the same size in screen pixels at any zoom, with the entity name below
+//! when selected or when View > Gizmos > Show Labels is on. Only the scene view draws
+//! them, so they never show up in the Game view or in builds.
+
+use ecs::{EditorIconKind, Entity, World};
+use egui;
+use glam::{Vec2, Vec3};
+use crate::SceneCamera;
+use crate::debug_draw::DebugDrawManager;
+use super::super::types::SceneViewMode;
+use super::projection_3d;
+
+/// Icon width and height in screen pixels
+const ICON_SIZE: f32 = 22.0;
+
+const CAMERA_TINT: egui::Color32 = egui::Color32::from_rgb(255, 230, 90);
+
+/// An icon placed on screen for this frame
+#[derive(Clone, Debug)]
+pub struct EntityIcon {
+    pub entity: Entity,
+    pub rect: egui::Rect,
+    glyph: &'static str,
+    tint: egui::Color32,
+}
+
+/// Glyph drawn for an EditorIcon kind
+pub fn glyph(kind: EditorIconKind) -> &'static str {
+    match kind {
+        EditorIconKind::Marker => "📍",
+        EditorIconKind::Spawn => "🚩",
+        EditorIconKind::Flag => "⚑",
+        EditorIconKind::Star => "⭐",
+        EditorIconKind::Waypoint => "◆",
+        EditorIconKind::Trigger => "⚡",
+    }
+}
+
+/// Icons for the categories enabled in View > Gizmos, in entity order (later ones on top)
+pub fn collect_icons(
+    world: &World,
+    gizmos: &DebugDrawManager,
+    scene_camera: &SceneCamera,
+    scene_view_mode: &SceneViewMode,
+    rect: egui::Rect,
+) -> Vec<EntityIcon> {
+    let mut entities: Vec<Entity> = world.transforms.keys().copied().collect();
+    entities.sort_unstable();
+
+    entities.into_iter()
+        .filter_map(|entity| {
+            let (glyph, tint) = if let Some(icon) = world.editor_icons.get(&entity).filter(|_| gizmos.show_marker_icons) {
+                let [r, g, b, a] = icon.tint.to_array();
+                (glyph(icon.icon), egui::Color32::from_rgba_unmultiplied(
+                    (r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8, (a * 255.0) as u8,
+                ))
+            } else if gizmos.show_camera_icons && world.cameras.contains_key(&entity) {
+                ("📷", CAMERA_TINT)
+            } else {
+                return None;
+            };
+            let transform = world.world_transform(entity)?;
+            let position = icon_position(transform.position.into(), scene_camera, scene_view_mode, rect)?;
+            Some(EntityIcon {
+                entity,
+                rect: egui::Rect::from_center_size(position, egui::vec2(ICON_SIZE, ICON_SIZE)),
+                glyph,
+                tint,
+            })
+        })
+        .collect()
+}
+
+/// Screen position of a world point in the current view mode (None behind a 3D camera)
+fn icon_position(world_pos: Vec3, scene_camera: &SceneCamera, scene_view_mode: &SceneViewMode, rect: egui::Rect) -> Option<egui::Pos2> {
+    match scene_view_mode {
+        SceneViewMode::Mode2D => {
+            let screen = scene_camera.world_to_screen(Vec3::new(world_pos.x, world_pos.y, 0.0));
+            Some(rect.center() + egui::vec2(screen.x, screen.y))
+        }
+        SceneViewMode::Mode3D => {
+            projection_3d::world_to_screen(world_pos, scene_camera, Vec2::new(rect.width(), rect.height()))
+                .map(|screen| rect.min + egui::vec2(screen.x, screen.y))
+        }
+    }
+}
+
+/// Draw the icons, and the names of selected entities (or all, with `show_labels`)
+pub fn render_icons(
+    painter: &egui::Painter,
+    icons: &[EntityIcon],
+    world: &World,
+    is_selected: impl Fn(Entity) -> bool,
+    show_labels: bool,
+) {
+    for icon in icons {
+        let selected = is_selected(icon.entity);
+        let outline = if selected { egui::Color32::from_rgb(255, 200, 0) } else { icon.tint.gamma_multiply(0.6) };
+        painter.rect_filled(icon.rect, 4.0, egui::Color32::from_rgba_unmultiplied(30, 30, 36, 200));
+        painter.rect_stroke(icon.rect, 4.0, egui::Stroke::new(if selected { 2.0 } else { 1.0 }, outline), egui::epaint::StrokeKind::Outside);
+        painter.text(icon.rect.center(), egui::Align2::CENTER_CENTER, icon.glyph, egui::FontId::proportional(ICON_SIZE * 0.7), icon.tint);
+
+        if selected || show_labels {
+            if let Some(name) = world.names.get(&icon.entity) {
+                let anchor = icon.rect.center_bottom() + egui::vec2(0.0, 2.0);
+                let galley = painter.layout_no_wrap(name.clone(), egui::FontId::proportional(11.0), egui::Color32::WHITE);
+                let background = egui::Align2::CENTER_TOP.anchor_size(anchor, galley.size()).expand2(egui::vec2(3.0, 1.0));
+                painter.rect_filled(background, 2.0, egui::Color32::from_rgba_unmultiplied(0, 0, 0, 160));
+                painter.galley(background.min + egui::vec2(3.0, 1.0), galley, egui::Color32::WHITE);
+            }
+        }
+    }
+}
+
+/// Entity of the topmost icon under `pointer`
+pub fn icon_at(icons: &[EntityIcon], pointer: egui::Pos2) -> Option<Entity> {
+    icons.iter().rev().find(|icon| icon.rect.contains(pointer)).map(|icon| icon.entity)
+}
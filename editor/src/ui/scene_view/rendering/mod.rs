@@ -4,6 +4,7 @@
 
 pub mod grid;
 pub mod gizmos;
+pub mod icons;
 pub mod view_2d;
 pub mod view_3d;
 pub mod projection_3d;
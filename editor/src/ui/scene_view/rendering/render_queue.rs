@@ -176,7 +176,7 @@ impl Default for RenderQueue {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use glam::{Vec2, Vec3};
+    use glam::{Quat, Vec2, Vec3};
     
     #[test]
     fn test_render_queue_creation() {
@@ -209,7 +209,7 @@ mod tests {
     fn test_depth_sorting_grid_first() {
         let mut queue = RenderQueue::new();
         let mut camera = SceneCamera::new();
-        camera.position = Vec2::new(0.0, 0.0);
+        camera.position = Vec3::ZERO;
         camera.rotation = 0.0;
         camera.pitch = 0.0;
         
@@ -217,10 +217,13 @@ mod tests {
         queue.push(RenderObject::Sprite(SpriteRenderData {
             entity: 1,
             position: Vec3::new(0.0, 0.0, 10.0),
-            rotation: 0.0,
+            rotation: Quat::IDENTITY,
             scale: Vec2::new(1.0, 1.0),
             texture_id: "test".to_string(),
             sprite_rect: None,
+            rect_rotated: false,
+            flip_x: false,
+            flip_y: false,
             color: [1.0, 1.0, 1.0, 1.0],
             billboard: false,
             width: 32.0,
@@ -239,7 +242,7 @@ mod tests {
     fn test_depth_sorting_gizmos_last() {
         let mut queue = RenderQueue::new();
         let mut camera = SceneCamera::new();
-        camera.position = Vec2::new(0.0, 0.0);
+        camera.position = Vec3::ZERO;
         camera.rotation = 0.0;
         camera.pitch = 0.0;
         
@@ -253,10 +256,13 @@ mod tests {
         queue.push(RenderObject::Sprite(SpriteRenderData {
             entity: 2,
             position: Vec3::new(0.0, 0.0, 10.0),
-            rotation: 0.0,
+            rotation: Quat::IDENTITY,
             scale: Vec2::new(1.0, 1.0),
             texture_id: "test".to_string(),
             sprite_rect: None,
+            rect_rotated: false,
+            flip_x: false,
+            flip_y: false,
             color: [1.0, 1.0, 1.0, 1.0],
             billboard: false,
             width: 32.0,
@@ -274,7 +280,7 @@ mod tests {
     fn test_depth_sorting_sprites_by_z() {
         let mut queue = RenderQueue::new();
         let mut camera = SceneCamera::new();
-        camera.position = Vec2::new(0.0, 0.0);
+        camera.position = Vec3::ZERO;
         camera.rotation = 0.0;
         camera.pitch = 0.0;
         
@@ -282,10 +288,13 @@ mod tests {
         queue.push(RenderObject::Sprite(SpriteRenderData {
             entity: 1,
             position: Vec3::new(0.0, 0.0, 5.0), // Closer
-            rotation: 0.0,
+            rotation: Quat::IDENTITY,
             scale: Vec2::new(1.0, 1.0),
             texture_id: "test1".to_string(),
             sprite_rect: None,
+            rect_rotated: false,
+            flip_x: false,
+            flip_y: false,
             color: [1.0, 1.0, 1.0, 1.0],
             billboard: false,
             width: 32.0,
@@ -294,10 +303,13 @@ mod tests {
         queue.push(RenderObject::Sprite(SpriteRenderData {
             entity: 2,
             position: Vec3::new(0.0, 0.0, 15.0), // Farther
-            rotation: 0.0,
+            rotation: Quat::IDENTITY,
             scale: Vec2::new(1.0, 1.0),
             texture_id: "test2".to_string(),
             sprite_rect: None,
+            rect_rotated: false,
+            flip_x: false,
+            flip_y: false,
             color: [1.0, 1.0, 1.0, 1.0],
             billboard: false,
             width: 32.0,
@@ -325,7 +337,7 @@ mod tests {
     fn test_depth_sorting_tilemaps_by_z() {
         let mut queue = RenderQueue::new();
         let mut camera = SceneCamera::new();
-        camera.position = Vec2::new(0.0, 0.0);
+        camera.position = Vec3::ZERO;
         camera.rotation = 0.0;
         camera.pitch = 0.0;
         
@@ -370,7 +382,7 @@ mod tests {
     fn test_depth_sorting_mixed_objects() {
         let mut queue = RenderQueue::new();
         let mut camera = SceneCamera::new();
-        camera.position = Vec2::new(0.0, 0.0);
+        camera.position = Vec3::ZERO;
         camera.rotation = 0.0;
         camera.pitch = 0.0;
         
@@ -383,10 +395,13 @@ mod tests {
         queue.push(RenderObject::Sprite(SpriteRenderData {
             entity: 2,
             position: Vec3::new(0.0, 0.0, 10.0),
-            rotation: 0.0,
+            rotation: Quat::IDENTITY,
             scale: Vec2::new(1.0, 1.0),
             texture_id: "test".to_string(),
             sprite_rect: None,
+            rect_rotated: false,
+            flip_x: false,
+            flip_y: false,
             color: [1.0, 1.0, 1.0, 1.0],
             billboard: false,
             width: 32.0,
@@ -415,7 +430,7 @@ mod tests {
     fn test_calculate_sprite_depth() {
         let queue = RenderQueue::new();
         let mut camera = SceneCamera::new();
-        camera.position = Vec2::new(0.0, 0.0);
+        camera.position = Vec3::ZERO;
         camera.rotation = 0.0;
         camera.pitch = 0.0;
         
@@ -434,7 +449,7 @@ mod tests {
     fn test_depth_sorting_handles_nan() {
         let mut queue = RenderQueue::new();
         let mut camera = SceneCamera::new();
-        camera.position = Vec2::new(0.0, 0.0);
+        camera.position = Vec3::ZERO;
         camera.rotation = 0.0;
         camera.pitch = 0.0;
         
@@ -442,10 +457,13 @@ mod tests {
         queue.push(RenderObject::Sprite(SpriteRenderData {
             entity: 1,
             position: Vec3::new(f32::NAN, 0.0, 10.0),
-            rotation: 0.0,
+            rotation: Quat::IDENTITY,
             scale: Vec2::new(1.0, 1.0),
             texture_id: "test".to_string(),
             sprite_rect: None,
+            rect_rotated: false,
+            flip_x: false,
+            flip_y: false,
             color: [1.0, 1.0, 1.0, 1.0],
             billboard: false,
             width: 32.0,
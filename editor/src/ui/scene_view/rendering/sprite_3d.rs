@@ -33,6 +33,10 @@ pub struct SpriteRenderData {
     pub scale: Vec2,
     pub texture_id: String,
     pub sprite_rect: Option<[u32; 4]>,
+    /// sprite_rect is packed turned 90° clockwise
+    pub rect_rotated: bool,
+    pub flip_x: bool,
+    pub flip_y: bool,
     pub color: [f32; 4],
     pub billboard: bool,
     pub width: f32,
@@ -66,9 +70,9 @@ impl Sprite3DRenderer {
         let mut sprites = Vec::new();
         
         for (&entity, sprite) in world.sprites.iter() {
-            if let Some(transform) = world.transforms.get(&entity) {
+            if let Some(transform) = world.world_transform(entity) {
                 // Validate transform data
-                if !Self::is_valid_transform(transform) {
+                if !Self::is_valid_transform(&transform) {
                     eprintln!("Warning: Invalid transform for sprite entity {:?}, skipping", entity);
                     continue;
                 }
@@ -104,8 +108,11 @@ impl Sprite3DRenderer {
                     rotation,
                     scale,
                     texture_id: sprite.texture_id.clone(),
-                    sprite_rect: sprite.sprite_rect,
-                    color: sprite.color,
+                    sprite_rect: world.sprite_rect_for(entity, sprite),
+                    rect_rotated: world.sprite_rect_rotated_for(entity, sprite),
+                    flip_x: sprite.flip_x,
+                    flip_y: sprite.flip_y,
+                    color: sprite.color.to_array(),
                     billboard: sprite.billboard,
                     // Convert pixel dimensions to world units using PPU
                     // Default to 16.0 PPU if for some reason it's 0 to avoid division by zero
@@ -187,7 +194,7 @@ impl Sprite3DRenderer {
         }
         
         // Check color values are valid (0.0 to 1.0)
-        for &c in &sprite.color {
+        for c in sprite.color.to_array() {
             if !c.is_finite() || c < 0.0 || c > 1.0 {
                 return false;
             }
@@ -427,32 +434,18 @@ impl Sprite3DRenderer {
                 // Try to load and render the actual texture
                 let texture_path = std::path::Path::new(&sprite.texture_id);
                 if let Some(texture_handle) = texture_manager.load_texture(ctx, &sprite.texture_id, texture_path) {
-                    // Calculate UV coordinates if sprite_rect is specified
+                    // Calculate UV coordinates if sprite_rect is specified, flips mirrored inside it
                     let uv = if let Some(sprite_rect) = sprite.sprite_rect {
-                        // Get texture size
                         let tex_size = texture_handle.size_vec2();
-                        
-                        // Calculate UV coordinates
-                        let u_min = sprite_rect[0] as f32 / tex_size.x;
-                        let v_min = sprite_rect[1] as f32 / tex_size.y;
-                        let u_max = (sprite_rect[0] + sprite_rect[2]) as f32 / tex_size.x;
-                        let v_max = (sprite_rect[1] + sprite_rect[3]) as f32 / tex_size.y;
-                        
-                        egui::Rect::from_min_max(
-                            egui::pos2(u_min, v_min),
-                            egui::pos2(u_max, v_max),
-                        )
+                        ecs::SpriteUv::from_rect(sprite_rect, [tex_size.x, tex_size.y], sprite.flip_x, sprite.flip_y, sprite.rect_rotated)
                     } else {
                         // Use full texture
-                        egui::Rect::from_min_max(
-                            egui::pos2(0.0, 0.0),
-                            egui::pos2(1.0, 1.0),
-                        )
+                        ecs::SpriteUv::whole(sprite.flip_x, sprite.flip_y)
                     };
                     
                     // Render textured sprite
                     let mut mesh = egui::Mesh::with_texture(texture_handle.id());
-                    mesh.add_rect_with_uv(rect, uv, screen_sprite.color);
+                    engine::texture_manager::add_sprite_quad(&mut mesh, rect, &uv, screen_sprite.color);
                     
                     // Apply rotation if needed
                     if screen_sprite.rotation != 0.0 {
@@ -3,11 +3,12 @@
 //! Handles rendering of the scene in 2D mode (sprites, grid, gizmos).
 
 use ecs::{World, Entity, MeshType};
+use ecs::sorting::{SortKey, SortingLayers};
 use egui;
 use crate::SceneCamera;
-use engine::texture_manager::TextureManager;
+use engine::texture_manager::{pivot_rect, sprite_quad_shape, TextureManager};
 use super::super::types::*;
-use super::gizmos::{render_camera_gizmo, render_camera_viewport_bounds, render_collider_gizmo, render_velocity_gizmo};
+use super::gizmos::{render_camera_gizmo, render_camera_viewport_bounds, render_collider_gizmo, render_trigger_zone_gizmo, render_velocity_gizmo};
 
 /// Render a tilemap in the scene view
 fn render_tilemap_in_scene(
@@ -39,6 +40,12 @@ fn render_tilemap_in_scene(
     let tilemap_x = transform.position[0];
     let tilemap_y = transform.position[1];
 
+    // Animated tiles follow the editor clock, all in step
+    let tile_time = ctx.input(|i| i.time) as f32;
+    if tileset.is_some_and(|ts| !ts.animations.is_empty()) {
+        ctx.request_repaint();
+    }
+
     // Render each tile
     for y in 0..tilemap.height {
         for x in 0..tilemap.width {
@@ -72,7 +79,7 @@ fn render_tilemap_in_scene(
                 // Render with texture if available
                 if let (Some(texture), Some(ts)) = (texture_opt.as_ref(), tileset) {
                     // Get tile coordinates in tileset
-                    if let Some((src_x, src_y)) = ts.get_tile_coords(tile.tile_id) {
+                    if let Some((src_x, src_y)) = ts.get_tile_coords(ts.animated_tile_id(tile.tile_id, tile_time)) {
                         // Calculate UV coordinates
                         let tex_size = texture.size();
                         let tex_width = tex_size[0] as f32;
@@ -137,103 +144,95 @@ pub fn render_scene_2d(
     texture_manager: &mut TextureManager,
     ctx: &egui::Context,
     rect: egui::Rect,
+    sorting_layers: &SortingLayers,
 ) {
-    // In 2D, we just iterate through all entities. 
-    // Z-ordering is usually determined by entity order or a specific Z-index component (if we had one).
-    // For now, we'll just use the order in the HashMap (arbitrary) or sorted by ID.
-    // To be consistent, let's sort by ID for stability.
-    let mut entities: Vec<Entity> = world.transforms.keys().cloned().collect();
-    entities.sort();
-
-    // First, render tilemaps (background)
-    for entity in &entities {
-        if let Some(tilemap) = world.tilemaps.get(entity) {
-            if let Some(transform) = world.transforms.get(entity) {
-                render_tilemap_in_scene(
-                    painter,
-                    *entity,
-                    tilemap,
-                    transform,
-                    world,
-                    scene_camera,
-                    center,
-                    texture_manager,
-                    ctx,
-                );
-            }
-        }
-    }
-
-    // Then render camera viewport bounds
-    for entity in &entities {
-        if world.cameras.contains_key(entity) {
-            render_camera_viewport_bounds(
+    // Tilemaps and sprites in the game's draw order (ecs::sorting), so tile layers
+    // interleave with sprites as they do in play; everything else after, by id.
+    let mut entities: Vec<(Option<SortKey>, Entity)> = world.transforms.keys()
+        .map(|&entity| {
+            let key = if world.tilemaps.contains_key(&entity) {
+                let z = world.world_transform(entity).map_or(0.0, |t| t.position[2]);
+                Some(SortKey::tilemap(sorting_layers, entity, world.tilemap_renderers.get(&entity), z))
+            } else {
+                world.sprites.get(&entity).zip(world.world_transform(entity))
+                    .map(|(sprite, transform)| SortKey::sprite(sorting_layers, entity, sprite, &transform))
+            };
+            (key, entity)
+        })
+        .collect();
+    entities.sort_by(|a, b| match (&a.0, &b.0) {
+        (Some(a), Some(b)) => a.cmp(b),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => a.1.cmp(&b.1),
+    });
+
+    for &(_, entity) in &entities {
+        let Some(transform) = world.world_transform(entity) else {
+            continue;
+        };
+        if let Some(tilemap) = world.tilemaps.get(&entity) {
+            render_tilemap_in_scene(
                 painter,
-                *entity,
+                entity,
+                tilemap,
+                &transform,
                 world,
                 scene_camera,
                 center,
+                texture_manager,
+                ctx,
             );
         }
+        render_entity_2d(
+            painter,
+            entity,
+            &transform,
+            world,
+            scene_camera,
+            center,
+            selected_entity,
+            show_colliders,
+            show_velocities,
+            hovered_entity,
+            response,
+            texture_manager,
+            ctx,
+        );
     }
-    
-    // Then render all entities
-    for entity in entities {
-        if let Some(transform) = world.transforms.get(&entity) {
-            render_entity_2d(
+
+    // Camera viewport bounds over the scene
+    for &(_, entity) in &entities {
+        if world.cameras.contains_key(&entity) {
+            render_camera_viewport_bounds(
                 painter,
                 entity,
-                transform,
                 world,
                 scene_camera,
                 center,
-                selected_entity,
-                show_colliders,
-                show_velocities,
-                hovered_entity,
-                response,
-                texture_manager,
-                ctx,
             );
         }
     }
 
+    // Trigger zone areas (View > Gizmos > Trigger Zones)
+    if debug_draw.show_trigger_zones {
+        for &(_, entity) in &entities {
+            if world.trigger_zones.contains_key(&entity) {
+                render_trigger_zone_gizmo(painter, entity, world, scene_camera, center, *selected_entity == Some(entity));
+            }
+        }
+    }
+
     // Render selection outline on top
     if let Some(sel_entity) = *selected_entity {
-        if let Some(transform) = world.transforms.get(&sel_entity) {
+        if let Some(transform) = world.world_transform(sel_entity) {
             let world_pos = glam::Vec3::new(transform.x(), transform.y(), 0.0);
             let screen_pos = scene_camera.world_to_screen(world_pos);
             let screen_x = center.x + screen_pos.x;
             let screen_y = center.y + screen_pos.y;
 
             // Draw selection outline
-            if let Some(sprite) = world.sprites.get(&sel_entity) {
-                let scale = glam::Vec2::new(transform.scale[0], transform.scale[1]);
-                // Use sprite dimensions with pixels_per_unit (same as rendering)
-                let world_width = sprite.width / sprite.pixels_per_unit;
-                let world_height = sprite.height / sprite.pixels_per_unit;
-                let size = egui::vec2(
-                    world_width * scale.x * scene_camera.zoom,
-                    world_height * scale.y * scene_camera.zoom
-                );
-                painter.rect_stroke(
-                    egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), size + egui::vec2(4.0, 4.0)),
-                    2.0,
-                    egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 200, 0)),
-                    egui::epaint::StrokeKind::Outside,
-                );
-            } else if world.meshes.contains_key(&sel_entity) {
-                let scale = glam::Vec3::from(transform.scale);
-                let world_size = 2.0;
-                let base_size = world_size * scene_camera.zoom * scale.x.max(scale.y).max(scale.z);
-                let selection_size = base_size + 8.0;
-                painter.rect_stroke(
-                    egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), egui::vec2(selection_size, selection_size)),
-                    2.0,
-                    egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 200, 0)),
-                    egui::epaint::StrokeKind::Outside,
-                );
-            }
+            render_selection_outline(painter, sel_entity, world, scene_camera, center, ACTIVE_OUTLINE_COLOR, texture_manager, ctx);
 
             // Draw selected entity's collider gizmo on top
             if *show_colliders {
@@ -243,9 +242,183 @@ pub fn render_scene_2d(
     }
 
     // Render debug lines if enabled
+    let camera_pos = [scene_camera.position.x, scene_camera.position.y, scene_camera.position.z];
     if *show_debug_lines {
-        let camera_pos = [scene_camera.position.x, scene_camera.position.y, scene_camera.position.z];
-        debug_draw.render(painter, camera_pos, scene_camera.zoom, rect);
+        // Same mapping as the sprites, so physics outlines sit exactly on them
+        let to_screen = |p: [f32; 3]| {
+            let screen_pos = scene_camera.world_to_screen(glam::Vec3::from(p));
+            egui::pos2(center.x + screen_pos.x, center.y + screen_pos.y)
+        };
+        debug_draw.render(painter, to_screen, scene_camera.zoom);
+    }
+
+    // Navigation overlay (only set while View > Show Navigation Grid is on)
+    debug_draw.render_navigation(painter, camera_pos, scene_camera.zoom, rect);
+}
+
+/// Outline of the active selection
+pub const ACTIVE_OUTLINE_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 140, 0);
+/// Outline of the other entities of a multi-selection
+pub const SELECTED_OUTLINE_COLOR: egui::Color32 = egui::Color32::from_rgb(150, 95, 40);
+/// Outline width in screen pixels
+const OUTLINE_WIDTH: f32 = 2.0;
+
+/// Where a sprite's texture goes on screen, as render_entity_2d draws it
+struct SpriteQuad {
+    texture_id: String,
+    texture: egui::TextureId,
+    rect: egui::Rect,
+    /// Sprite rect or sheet frame, flips and rotated packing applied
+    uv: ecs::SpriteUv,
+    /// Sprite color (white for sheets without a Sprite)
+    color: egui::Color32,
+}
+
+impl SpriteQuad {
+    fn shape(&self, texture: egui::TextureId, rect: egui::Rect, color: egui::Color32) -> egui::Shape {
+        sprite_quad_shape(texture, rect, &self.uv, color)
+    }
+}
+
+/// The textured quad of an entity's animated sprite frame or sprite, if its texture is loaded
+fn sprite_quad(
+    entity: Entity,
+    transform: &ecs::Transform,
+    world: &World,
+    scene_camera: &SceneCamera,
+    center: egui::Pos2,
+    texture_manager: &mut TextureManager,
+    ctx: &egui::Context,
+) -> Option<SpriteQuad> {
+    let screen_pos = scene_camera.world_to_screen(glam::Vec3::new(transform.x(), transform.y(), 0.0));
+    let anchor = egui::pos2(center.x + screen_pos.x, center.y + screen_pos.y);
+    let sprite = world.sprites.get(&entity);
+    let color = sprite.map_or(egui::Color32::WHITE, |sprite| {
+        let tint = world.sprite_draw_color(entity, sprite);
+        egui::Color32::from_rgba_unmultiplied(
+            (tint.r * 255.0) as u8,
+            (tint.g * 255.0) as u8,
+            (tint.b * 255.0) as u8,
+            (tint.a * 255.0) as u8,
+        )
+    });
+
+    // Animated sprites draw their sheet's current frame, sized by its aspect ratio
+    if let Some(animated_sprite) = world.animated_sprites.get(&entity) {
+        let sprite_sheet = world.sprite_sheets.get(&entity)?;
+        let frame = sprite_sheet.get_frame(animated_sprite.get_frame_index())?;
+        let aspect_ratio = frame.width as f32 / frame.height as f32;
+        let size = egui::vec2(
+            transform.scale[0] * scene_camera.zoom * aspect_ratio,
+            transform.scale[1] * scene_camera.zoom,
+        );
+        let texture_path = std::path::Path::new(&sprite_sheet.texture_path);
+        let texture = texture_manager.load_texture(ctx, &sprite_sheet.texture_id, texture_path)?.id();
+        let (flip_x, flip_y) = sprite.map_or((false, false), |sprite| (sprite.flip_x, sprite.flip_y));
+        let uv = frame.uv([sprite_sheet.sheet_width as f32, sprite_sheet.sheet_height as f32], flip_x, flip_y);
+        return Some(SpriteQuad {
+            texture_id: sprite_sheet.texture_id.clone(),
+            texture,
+            rect: egui::Rect::from_center_size(anchor, size),
+            uv,
+            color,
+        });
+    }
+
+    let sprite = sprite.filter(|sprite| !sprite.texture_id.is_empty())?;
+    // Unity-style: sprite size in world units = pixels / pixels_per_unit
+    let size = egui::vec2(
+        sprite.width / sprite.pixels_per_unit * transform.scale[0] * scene_camera.zoom,
+        sprite.height / sprite.pixels_per_unit * transform.scale[1] * scene_camera.zoom,
+    );
+    let texture = texture_manager.load_texture(ctx, &sprite.texture_id, std::path::Path::new(&sprite.texture_id))?;
+    let (texture, tex_size) = (texture.id(), texture.size());
+
+    // The entity position is the sprite's pivot (sprite editor override, else the texture's import settings)
+    let pivot = sprite.pivot.unwrap_or_else(|| texture_manager.sprite_pivot(&sprite.texture_id));
+    let rect = pivot_rect(anchor, size, pivot, sprite.flip_x, sprite.flip_y);
+
+    // UV coordinates from sprite_rect (Unity-style), else the full texture
+    let uv = world.sprite_uv_for(entity, sprite, tex_size.map(|size| size as f32));
+
+    Some(SpriteQuad {
+        texture_id: sprite.texture_id.clone(),
+        texture,
+        rect,
+        uv,
+        color,
+    })
+}
+
+/// Outline around a selected sprite or mesh. Textured sprites get a silhouette pass:
+/// their texture's alpha drawn in `color` around them, then the sprite again on top,
+/// so the outline hugs the visible pixels of the sprite rect (and the selection shows
+/// even when other sprites cover it).
+#[allow(clippy::too_many_arguments)]
+pub fn render_selection_outline(
+    painter: &egui::Painter,
+    entity: Entity,
+    world: &World,
+    scene_camera: &SceneCamera,
+    center: egui::Pos2,
+    color: egui::Color32,
+    texture_manager: &mut TextureManager,
+    ctx: &egui::Context,
+) {
+    let Some(transform) = world.world_transform(entity) else {
+        return;
+    };
+    if let Some(quad) = sprite_quad(entity, &transform, world, scene_camera, center, texture_manager, ctx) {
+        if let Some(silhouette) = texture_manager.silhouette(ctx, &quad.texture_id).map(|texture| texture.id()) {
+            let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+            let directions = [
+                (1.0, 0.0), (-1.0, 0.0), (0.0, 1.0), (0.0, -1.0),
+                (diagonal, diagonal), (diagonal, -diagonal), (-diagonal, diagonal), (-diagonal, -diagonal),
+            ];
+            for (x, y) in directions {
+                let offset = egui::vec2(x, y) * OUTLINE_WIDTH;
+                painter.add(quad.shape(silhouette, quad.rect.translate(offset), color));
+            }
+            painter.add(quad.shape(quad.texture, quad.rect, quad.color));
+            return;
+        }
+    }
+
+    let world_pos = glam::Vec3::new(transform.x(), transform.y(), 0.0);
+    let screen_pos = scene_camera.world_to_screen(world_pos);
+    let screen_x = center.x + screen_pos.x;
+    let screen_y = center.y + screen_pos.y;
+
+    if let Some(sprite) = world.sprites.get(&entity) {
+        // Untextured (or unreadable) sprites: the quad is what's visible
+        let scale = glam::Vec2::new(transform.scale[0], transform.scale[1]);
+        // Use sprite dimensions with pixels_per_unit (same as rendering)
+        let world_width = sprite.width / sprite.pixels_per_unit;
+        let world_height = sprite.height / sprite.pixels_per_unit;
+        let size = egui::vec2(
+            world_width * scale.x * scene_camera.zoom,
+            world_height * scale.y * scene_camera.zoom
+        );
+        painter.rect_stroke(
+            egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), size),
+            0.0,
+            egui::Stroke::new(OUTLINE_WIDTH, color),
+            egui::epaint::StrokeKind::Outside,
+        );
+    } else if world.meshes.contains_key(&entity) {
+        let scale = glam::Vec3::from(transform.scale);
+        let world_size = 2.0;
+        let base_size = world_size * scene_camera.zoom * scale.x.max(scale.y).max(scale.z);
+        let selection_size = base_size + 8.0;
+        painter.rect_stroke(
+            egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), egui::vec2(selection_size, selection_size)),
+            2.0,
+            egui::Stroke::new(OUTLINE_WIDTH, color),
+            egui::epaint::StrokeKind::Outside,
+        );
+    } else {
+        // Empty objects: small marker so they still show as selected
+        painter.circle_stroke(egui::pos2(screen_x, screen_y), 6.0, egui::Stroke::new(OUTLINE_WIDTH, color));
     }
 }
 
@@ -258,6 +431,7 @@ pub fn render_transform_gizmo_2d(
     center: egui::Pos2,
     current_tool: &super::super::super::TransformTool,
     transform_space: &super::super::types::TransformSpace,
+    metrics: &super::super::gizmo_metrics::GizmoMetrics,
 ) {
     if let Some(transform) = world.transforms.get(&entity) {
         let world_pos = glam::Vec3::new(transform.x(), transform.y(), 0.0);
@@ -276,6 +450,7 @@ pub fn render_transform_gizmo_2d(
             transform,
             None, // No viewport_rect needed for 2D mode
             None,
+            metrics,
         );
     }
 }
@@ -350,38 +525,20 @@ fn render_entity_2d(
                 
                 // Get sprite color if exists, otherwise white
                 let color = if let Some(sprite) = world.sprites.get(&entity) {
+                    let tint = world.sprite_draw_color(entity, sprite);
                     egui::Color32::from_rgba_unmultiplied(
-                        (sprite.color[0] * 255.0) as u8,
-                        (sprite.color[1] * 255.0) as u8,
-                        (sprite.color[2] * 255.0) as u8,
-                        (sprite.color[3] * 255.0) as u8,
+                        (tint.r * 255.0) as u8,
+                        (tint.g * 255.0) as u8,
+                        (tint.b * 255.0) as u8,
+                        (tint.a * 255.0) as u8,
                     )
                 } else {
                     egui::Color32::WHITE
                 };
 
                 // Try to load and render texture
-                let texture_path = std::path::Path::new(&sprite_sheet.texture_path);
-                if let Some(texture) = texture_manager.load_texture(ctx, &sprite_sheet.texture_id, texture_path) {
-                    // Calculate UV coordinates for the frame
-                    let uv_min = egui::pos2(
-                        frame.x as f32 / sprite_sheet.sheet_width as f32,
-                        frame.y as f32 / sprite_sheet.sheet_height as f32,
-                    );
-                    let uv_max = egui::pos2(
-                        (frame.x + frame.width) as f32 / sprite_sheet.sheet_width as f32,
-                        (frame.y + frame.height) as f32 / sprite_sheet.sheet_height as f32,
-                    );
-                    
-                    let rect = egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), size);
-                    
-                    // Draw the texture with UV coordinates
-                    painter.image(
-                        texture.id(),
-                        rect,
-                        egui::Rect::from_min_max(uv_min, uv_max),
-                        color,
-                    );
+                if let Some(quad) = sprite_quad(entity, transform, world, scene_camera, center, texture_manager, ctx) {
+                    painter.add(quad.shape(quad.texture, quad.rect, color));
                 } else {
                     // Fallback: draw colored rectangle
                     painter.rect_filled(
@@ -402,54 +559,19 @@ fn render_entity_2d(
             world_width * scale.x * scene_camera.zoom,
             world_height * scale.y * scene_camera.zoom
         );
+        let tint = world.sprite_draw_color(entity, sprite);
         let color = egui::Color32::from_rgba_unmultiplied(
-            (sprite.color[0] * 255.0) as u8,
-            (sprite.color[1] * 255.0) as u8,
-            (sprite.color[2] * 255.0) as u8,
-            (sprite.color[3] * 255.0) as u8,
+            (tint.r * 255.0) as u8,
+            (tint.g * 255.0) as u8,
+            (tint.b * 255.0) as u8,
+            (tint.a * 255.0) as u8,
         );
 
         // Try to load and render texture
         if !sprite.texture_id.is_empty() {
-            let texture_path = std::path::Path::new(&sprite.texture_id);
-            if let Some(texture) = texture_manager.load_texture(ctx, &sprite.texture_id, texture_path) {
+            if let Some(quad) = sprite_quad(entity, transform, world, scene_camera, center, texture_manager, ctx) {
                 // Render texture with color tint and flipping
-                let mut mesh = egui::Mesh::with_texture(texture.id());
-
-                let rect = egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), size);
-
-                // Calculate UV coordinates based on sprite_rect (Unity-style)
-                let (u_min_base, u_max_base, v_min_base, v_max_base) = if let Some(sprite_rect) = sprite.sprite_rect {
-                    // Use sprite rect to calculate UV coordinates
-                    let tex_size = texture.size();
-                    let tex_width = tex_size[0] as f32;
-                    let tex_height = tex_size[1] as f32;
-                    
-                    let u_min = sprite_rect[0] as f32 / tex_width;
-                    let v_min = sprite_rect[1] as f32 / tex_height;
-                    let u_max = (sprite_rect[0] + sprite_rect[2]) as f32 / tex_width;
-                    let v_max = (sprite_rect[1] + sprite_rect[3]) as f32 / tex_height;
-                    
-                    (u_min, u_max, v_min, v_max)
-                } else {
-                    // Use full texture
-                    (0.0, 1.0, 0.0, 1.0)
-                };
-
-                // Apply flipping
-                let (u_min, u_max) = if sprite.flip_x { (u_max_base, u_min_base) } else { (u_min_base, u_max_base) };
-                let (v_min, v_max) = if sprite.flip_y { (v_max_base, v_min_base) } else { (v_min_base, v_max_base) };
-
-                mesh.add_rect_with_uv(
-                    rect,
-                    egui::Rect::from_min_max(
-                        egui::pos2(u_min, v_min),
-                        egui::pos2(u_max, v_max),
-                    ),
-                    color,
-                );
-
-                painter.add(egui::Shape::mesh(mesh));
+                painter.add(quad.shape(quad.texture, quad.rect, color));
             } else {
                 // Fallback to colored rectangle if texture load fails
                 let rotation_rad = transform.rotation[2].to_radians();
@@ -3,31 +3,23 @@
 //! Keyboard shortcut handlers for scene view.
 
 use egui;
-use crate::ui::TransformTool;
 use crate::SceneCamera;
 use super::types::*;
 
-/// Handle keyboard shortcuts for tools and camera views
+/// Handle keyboard shortcuts for camera views. Tool keys (Q/W/E/R) are editor
+/// commands, see commands.rs.
 pub fn handle_keyboard_shortcuts(
     ui: &mut egui::Ui,
-    current_tool: &mut TransformTool,
     scene_camera: &mut SceneCamera,
     scene_view_mode: &SceneViewMode,
 ) {
-    // Tool shortcuts (Unity-like: Q, W, E, R)
-    if ui.input(|i| i.key_pressed(egui::Key::Q)) {
-        *current_tool = TransformTool::View;
+    // No view changes while flying with RMB + WASD/QE
+    let flying = *scene_view_mode == SceneViewMode::Mode3D
+        && ui.input(|i| i.pointer.button_down(egui::PointerButton::Secondary));
+    if flying {
+        return;
     }
-    if ui.input(|i| i.key_pressed(egui::Key::W)) {
-        *current_tool = TransformTool::Move;
-    }
-    if ui.input(|i| i.key_pressed(egui::Key::E)) {
-        *current_tool = TransformTool::Rotate;
-    }
-    if ui.input(|i| i.key_pressed(egui::Key::R)) {
-        *current_tool = TransformTool::Scale;
-    }
-    
+
     // Camera view shortcuts (only in 3D mode)
     if *scene_view_mode == SceneViewMode::Mode3D {
         let ctrl_pressed = ui.input(|i| i.modifiers.ctrl);
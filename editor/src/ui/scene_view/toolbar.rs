@@ -1,6 +1,6 @@
 //! Toolbar
 //!
-//! Scene view toolbar UI (tools, mode switches, play/stop buttons).
+//! Scene view toolbar UI (tools, mode switches, play/pause/step/stop buttons).
 
 use egui;
 use crate::ui::TransformTool;
@@ -11,10 +11,14 @@ pub fn render_scene_toolbar(
     ui: &mut egui::Ui,
     current_tool: &mut TransformTool,
     is_playing: bool,
+    is_paused: bool,
     play_request: &mut bool,
     stop_request: &mut bool,
+    pause_request: &mut bool,
+    step_request: &mut bool,
     scene_view_mode: &mut SceneViewMode,
     transform_space: &mut TransformSpace,
+    snap_settings: &mut crate::tools::snapping::SnapSettings,
 ) {
     ui.horizontal(|ui| {
         // Transform tools
@@ -41,6 +45,21 @@ pub fn render_scene_toolbar(
         ui.selectable_value(transform_space, TransformSpace::Local, "Local");
         ui.selectable_value(transform_space, TransformSpace::World, "World");
         
+        ui.separator();
+        
+        // Snapping: quick toggle + settings popover
+        let mut changed = ui.toggle_value(&mut snap_settings.enabled, "🧲 Snap")
+            .on_hover_text("Ctrl+G (hold Ctrl to toggle while dragging, hold V for vertex snap)")
+            .changed();
+        ui.menu_button("⏷", |ui| {
+            changed |= crate::tools::snapping::render_snap_settings_ui(ui, snap_settings);
+        });
+        if changed {
+            if let Err(e) = snap_settings.save() {
+                log::warn!("Failed to save snap settings: {}", e);
+            }
+        }
+        
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             // Play/Stop buttons
             if !is_playing {
@@ -48,6 +67,14 @@ pub fn render_scene_toolbar(
                     *play_request = true;
                 }
             } else {
+                // Right-to-left layout: Step ends up rightmost, next to Pause
+                if ui.add_enabled(is_paused, egui::Button::new("⏭ Step")).on_hover_text("F10").clicked() {
+                    *step_request = true;
+                }
+                let pause_label = if is_paused { "▶ Resume" } else { "⏸ Pause" };
+                if ui.button(pause_label).on_hover_text("Ctrl+Shift+P").clicked() {
+                    *pause_request = true;
+                }
                 if ui.button("⏹ Stop").clicked() {
                     *stop_request = true;
                 }
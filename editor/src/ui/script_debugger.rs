@@ -0,0 +1,387 @@
+//! Script Debugger Window
+//!
+//! Breakpoints on Lua script lines (saved per project in `.editor_breakpoints.json`).
+//! When a script's Update stops at one, play mode pauses (the editor keeps rendering)
+//! and the window shows the call stack, the selected frame's locals and the script's
+//! globals, with Continue / Step Over / Step Into.
+
+use ecs::Entity;
+use egui::{Color32, RichText};
+use script::{Breakpoint, DebugBreak, DebugCommand, ScriptEngine};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Breakpoints file, relative to the project folder
+pub const BREAKPOINTS_FILE: &str = ".editor_breakpoints.json";
+
+const BREAK_LINE_COLOR: Color32 = Color32::from_rgb(90, 80, 20);
+const BREAKPOINT_COLOR: Color32 = Color32::from_rgb(220, 60, 60);
+
+fn open_request_id() -> egui::Id {
+    egui::Id::new("script_debugger_open_request")
+}
+
+/// Ask the editor to show the Script Debugger window (View menu)
+pub fn open(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(open_request_id(), true));
+}
+
+fn load_breakpoints(project_path: &Path) -> BTreeSet<Breakpoint> {
+    std::fs::read_to_string(project_path.join(BREAKPOINTS_FILE))
+        .ok()
+        .and_then(|json| serde_json::from_str::<Vec<Breakpoint>>(&json).ok())
+        .map(|breakpoints| breakpoints.into_iter().collect())
+        .unwrap_or_default()
+}
+
+fn save_breakpoints(project_path: &Path, breakpoints: &BTreeSet<Breakpoint>) -> Result<(), Box<dyn std::error::Error>> {
+    let json = serde_json::to_string_pretty(&breakpoints.iter().collect::<Vec<_>>())?;
+    std::fs::write(project_path.join(BREAKPOINTS_FILE), json)?;
+    Ok(())
+}
+
+/// `scripts/**/*.lua` below the project, as chunk names (`scripts/enemies/bat.lua`)
+fn find_scripts(project_path: &Path) -> Vec<String> {
+    fn visit(dir: &Path, project_path: &Path, out: &mut Vec<String>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                visit(&path, project_path, out);
+            } else if path.extension().is_some_and(|ext| ext == "lua") {
+                if let Ok(relative) = path.strip_prefix(project_path) {
+                    out.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+    }
+    let mut scripts = Vec::new();
+    visit(&project_path.join("scripts"), project_path, &mut scripts);
+    scripts.sort();
+    scripts
+}
+
+/// Script Debugger window state and the project's breakpoints
+#[derive(Default)]
+pub struct ScriptDebugger {
+    pub open: bool,
+    /// Play mode was paused by a breakpoint, and resumes when the break ends
+    pub paused_by_debugger: bool,
+    breakpoints: BTreeSet<Breakpoint>,
+    /// Project the breakpoints belong to
+    project_path: Option<PathBuf>,
+    scripts: Vec<String>,
+    /// Script in the source view and its lines
+    selected_script: Option<String>,
+    source: Vec<String>,
+    selected_frame: usize,
+    /// Line to scroll the source view to on the next frame
+    scroll_to_line: Option<u32>,
+    /// Break the view last followed (entity, file, line)
+    followed_break: Option<(Entity, String, u32)>,
+}
+
+impl ScriptDebugger {
+    /// Load the breakpoints of a newly opened project and hand them to the script engine
+    pub fn sync_project(&mut self, project_path: Option<&Path>, script_engine: &mut ScriptEngine) {
+        if self.project_path.as_deref() == project_path {
+            return;
+        }
+        self.project_path = project_path.map(Path::to_path_buf);
+        self.breakpoints = project_path.map(load_breakpoints).unwrap_or_default();
+        self.scripts = project_path.map(find_scripts).unwrap_or_default();
+        self.selected_script = None;
+        self.source.clear();
+        script_engine.set_breakpoints(self.breakpoints.iter().cloned());
+    }
+
+    fn toggle_breakpoint(&mut self, file: &str, line: u32, script_engine: &mut ScriptEngine) {
+        let breakpoint = Breakpoint { file: file.to_string(), line };
+        if !self.breakpoints.remove(&breakpoint) {
+            self.breakpoints.insert(breakpoint);
+        }
+        self.breakpoints_changed(script_engine);
+    }
+
+    fn breakpoints_changed(&mut self, script_engine: &mut ScriptEngine) {
+        script_engine.set_breakpoints(self.breakpoints.iter().cloned());
+        if let Some(project_path) = &self.project_path {
+            if let Err(e) = save_breakpoints(project_path, &self.breakpoints) {
+                log::error!("Failed to save breakpoints: {}", e);
+            }
+        }
+    }
+
+    fn select_script(&mut self, file: &str) {
+        if self.selected_script.as_deref() == Some(file) {
+            return;
+        }
+        self.selected_script = Some(file.to_string());
+        self.source = self
+            .project_path
+            .as_ref()
+            .and_then(|project_path| std::fs::read_to_string(project_path.join(file)).ok())
+            .map(|text| text.lines().map(str::to_string).collect())
+            .unwrap_or_default();
+    }
+
+    /// Show `file` at `line` in the source view
+    fn show_location(&mut self, file: &str, line: u32) {
+        self.select_script(file);
+        self.scroll_to_line = Some(line);
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, script_engine: &mut ScriptEngine, entity_names: &HashMap<Entity, String>) {
+        if ctx.data_mut(|d| d.remove_temp::<bool>(open_request_id())).unwrap_or(false) {
+            self.open = true;
+            if let Some(project_path) = &self.project_path {
+                self.scripts = find_scripts(project_path);
+            }
+        }
+
+        // Follow new breaks: show the window at the stopped line
+        let current = script_engine.debug_break();
+        let break_key = current.as_ref().map(|b| (b.entity, b.file.clone(), b.line));
+        if break_key.is_some() && break_key != self.followed_break {
+            if let Some(hit) = &current {
+                self.open = true;
+                self.selected_frame = 0;
+                self.show_location(&hit.file, hit.line);
+            }
+        }
+        self.followed_break = break_key;
+
+        if !self.open {
+            return;
+        }
+
+        let mut open = self.open;
+        egui::Window::new("🐞 Script Debugger")
+            .open(&mut open)
+            .default_size([720.0, 520.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                self.render_toolbar(ui, script_engine, current.as_ref(), entity_names);
+                ui.separator();
+                egui::SidePanel::right("script_debugger_inspect")
+                    .resizable(true)
+                    .default_width(300.0)
+                    .show_inside(ui, |ui| {
+                        egui::ScrollArea::vertical().id_salt("script_debugger_inspect_scroll").show(ui, |ui| {
+                            self.render_inspect(ui, script_engine, current.as_ref());
+                        });
+                    });
+                egui::CentralPanel::default().show_inside(ui, |ui| {
+                    self.render_source(ui, script_engine, current.as_ref());
+                });
+            });
+        self.open = open;
+    }
+
+    fn render_toolbar(
+        &mut self,
+        ui: &mut egui::Ui,
+        script_engine: &mut ScriptEngine,
+        current: Option<&DebugBreak>,
+        entity_names: &HashMap<Entity, String>,
+    ) {
+        let suspended = current.is_some_and(|b| b.suspended);
+        ui.horizontal(|ui| {
+            let mut command = None;
+            if ui.add_enabled(suspended, egui::Button::new("▶ Continue")).clicked() {
+                command = Some(DebugCommand::Continue);
+            }
+            if ui.add_enabled(suspended, egui::Button::new("⤼ Step Over")).clicked() {
+                command = Some(DebugCommand::StepOver);
+            }
+            if ui.add_enabled(suspended, egui::Button::new("⤵ Step Into")).clicked() {
+                command = Some(DebugCommand::StepInto);
+            }
+            if ui.add_enabled(suspended, egui::Button::new("⤴ Step Out")).clicked() {
+                command = Some(DebugCommand::StepOut);
+            }
+            if let Some(command) = command {
+                script_engine.debug_command(command);
+            }
+
+            ui.separator();
+            let status = match current {
+                Some(hit) => {
+                    let name = entity_names.get(&hit.entity).cloned().unwrap_or_else(|| format!("Entity {}", hit.entity));
+                    if hit.suspended {
+                        RichText::new(format!("⏸ Stopped at {}:{} ({})", hit.file, hit.line, name))
+                            .color(Color32::from_rgb(255, 200, 80))
+                    } else {
+                        RichText::new(format!("Passed {}:{} ({}), outside Update", hit.file, hit.line, name))
+                            .color(Color32::GRAY)
+                    }
+                }
+                None => RichText::new("Running").color(Color32::GRAY),
+            };
+            ui.label(status).on_hover_text(
+                "Only Update stops at breakpoints. Hits in Start, callbacks or timers are shown here but execution continues.",
+            );
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Script:");
+            let selected = self.selected_script.clone().unwrap_or_default();
+            let mut choice = None;
+            egui::ComboBox::from_id_salt("script_debugger_script")
+                .selected_text(if selected.is_empty() { "Select a script" } else { selected.as_str() })
+                .width(260.0)
+                .show_ui(ui, |ui| {
+                    for script in &self.scripts {
+                        if ui.selectable_label(*script == selected, script).clicked() {
+                            choice = Some(script.clone());
+                        }
+                    }
+                });
+            if let Some(script) = choice {
+                self.select_script(&script);
+            }
+            if ui.small_button("⟳").on_hover_text("Rescan scripts and reload the source").clicked() {
+                if let Some(project_path) = &self.project_path {
+                    self.scripts = find_scripts(project_path);
+                }
+                if let Some(script) = self.selected_script.take() {
+                    self.select_script(&script);
+                }
+            }
+        });
+    }
+
+    fn render_source(&mut self, ui: &mut egui::Ui, script_engine: &mut ScriptEngine, current: Option<&DebugBreak>) {
+        let Some(file) = self.selected_script.clone() else {
+            ui.label(RichText::new("Select a script, then click a line number to toggle a breakpoint.").color(Color32::GRAY));
+            return;
+        };
+        let stopped_line = current.filter(|b| b.file == file).map(|b| b.line);
+        let frame_line = current
+            .and_then(|b| b.frames.get(self.selected_frame))
+            .filter(|frame| frame.file == file && self.selected_frame > 0)
+            .map(|frame| frame.line);
+
+        let mut toggled = None;
+        let scroll_to_line = self.scroll_to_line.take();
+        egui::ScrollArea::both().id_salt("script_debugger_source").auto_shrink([false, false]).show(ui, |ui| {
+            ui.spacing_mut().item_spacing.y = 0.0;
+            for (index, text) in self.source.iter().enumerate() {
+                let line = index as u32 + 1;
+                let has_breakpoint = self.breakpoints.contains(&Breakpoint { file: file.clone(), line });
+                let row = ui.horizontal(|ui| {
+                    let marker = if has_breakpoint {
+                        RichText::new("●").color(BREAKPOINT_COLOR)
+                    } else {
+                        RichText::new("○").color(Color32::from_gray(70))
+                    };
+                    let gutter = ui
+                        .add(egui::Label::new(marker.monospace()).sense(egui::Sense::click()))
+                        .on_hover_text("Toggle breakpoint");
+                    let number = ui.add(
+                        egui::Label::new(RichText::new(format!("{:>4} ", line)).monospace().color(Color32::GRAY))
+                            .sense(egui::Sense::click()),
+                    );
+                    if gutter.clicked() || number.clicked() {
+                        toggled = Some(line);
+                    }
+
+                    let mut text = RichText::new(text).monospace();
+                    if Some(line) == stopped_line {
+                        text = text.background_color(BREAK_LINE_COLOR);
+                    } else if Some(line) == frame_line {
+                        text = text.background_color(Color32::from_rgb(40, 60, 90));
+                    }
+                    ui.label(text);
+                });
+                if scroll_to_line == Some(line) {
+                    row.response.scroll_to_me(Some(egui::Align::Center));
+                }
+            }
+        });
+
+        if let Some(line) = toggled {
+            self.toggle_breakpoint(&file, line, script_engine);
+        }
+    }
+
+    fn render_inspect(&mut self, ui: &mut egui::Ui, script_engine: &mut ScriptEngine, current: Option<&DebugBreak>) {
+        if let Some(hit) = current {
+            ui.label(RichText::new("Call Stack").strong());
+            let mut show = None;
+            for (index, frame) in hit.frames.iter().enumerate() {
+                let text = format!("{}  {}:{}", frame.function, frame.file, frame.line);
+                if ui.selectable_label(index == self.selected_frame, text).clicked() {
+                    self.selected_frame = index;
+                    show = Some((frame.file.clone(), frame.line));
+                }
+            }
+            if let Some((file, line)) = show {
+                if file != "[C]" {
+                    self.show_location(&file, line);
+                }
+            }
+
+            ui.separator();
+            ui.label(RichText::new("Locals").strong());
+            match hit.frames.get(self.selected_frame) {
+                Some(frame) if !frame.locals.is_empty() => render_variables(ui, "script_debugger_locals", &frame.locals),
+                _ => {
+                    ui.label(RichText::new("None").small().color(Color32::GRAY));
+                }
+            }
+
+            ui.separator();
+            ui.label(RichText::new("Globals").strong());
+            let globals = script_engine.debug_globals(hit.entity);
+            if globals.is_empty() {
+                ui.label(RichText::new("None").small().color(Color32::GRAY));
+            } else {
+                render_variables(ui, "script_debugger_globals", &globals);
+            }
+            ui.separator();
+        }
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("Breakpoints ({})", self.breakpoints.len())).strong());
+            if !self.breakpoints.is_empty() && ui.small_button("Clear all").clicked() {
+                self.breakpoints.clear();
+                self.breakpoints_changed(script_engine);
+            }
+        });
+        if self.breakpoints.is_empty() {
+            ui.label(RichText::new("Click a line number in the source view to add one.").small().color(Color32::GRAY));
+        }
+        let mut remove = None;
+        let mut show = None;
+        for breakpoint in &self.breakpoints {
+            ui.horizontal(|ui| {
+                if ui.small_button("✖").on_hover_text("Remove breakpoint").clicked() {
+                    remove = Some(breakpoint.clone());
+                }
+                if ui.link(format!("{}:{}", breakpoint.file, breakpoint.line)).clicked() {
+                    show = Some(breakpoint.clone());
+                }
+            });
+        }
+        if let Some(breakpoint) = show {
+            self.show_location(&breakpoint.file, breakpoint.line);
+        }
+        if let Some(breakpoint) = remove {
+            self.breakpoints.remove(&breakpoint);
+            self.breakpoints_changed(script_engine);
+        }
+    }
+}
+
+fn render_variables(ui: &mut egui::Ui, id: &str, variables: &[(String, String)]) {
+    egui::Grid::new(id).num_columns(2).striped(true).show(ui, |ui| {
+        for (name, value) in variables {
+            ui.label(RichText::new(name).monospace());
+            ui.add(egui::Label::new(RichText::new(value).monospace()).truncate()).on_hover_text(value);
+            ui.end_row();
+        }
+    });
+}
@@ -0,0 +1,202 @@
+//! Preferences > Shortcuts
+//!
+//! Every command with its current shortcuts. "+" waits for the next key press
+//! (routed here by shortcuts_handler) and adds it; a chord another command already
+//! uses asks before moving it. Changes are saved to .kiro/settings/shortcuts.json.
+
+use crate::commands::CommandRegistry;
+use crate::shortcuts::KeyChord;
+use egui::{Color32, RichText};
+
+fn open_request_id() -> egui::Id {
+    egui::Id::new("shortcut_preferences_open_request")
+}
+
+/// Ask the editor to show Preferences > Shortcuts
+pub fn open(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(open_request_id(), true));
+}
+
+/// A captured chord that other commands already use
+struct PendingConflict {
+    command: String,
+    chord: KeyChord,
+    others: Vec<(String, String)>,
+}
+
+#[derive(Default)]
+pub struct ShortcutPreferences {
+    pub open: bool,
+    filter: String,
+    /// Command waiting for its new shortcut
+    capturing: Option<String>,
+    conflict: Option<PendingConflict>,
+    error: Option<String>,
+}
+
+impl ShortcutPreferences {
+    pub fn is_capturing(&self) -> bool {
+        self.open && self.capturing.is_some()
+    }
+
+    /// Stop waiting for a key press (Escape)
+    pub fn cancel_capture(&mut self) {
+        self.capturing = None;
+    }
+
+    /// Key press while waiting for a shortcut
+    pub fn capture(&mut self, chord: KeyChord, commands: &mut CommandRegistry) {
+        let Some(id) = self.capturing.take() else {
+            return;
+        };
+        let others: Vec<(String, String)> = commands
+            .conflicts(&id, chord)
+            .into_iter()
+            .map(|c| (c.id.clone(), c.name.clone()))
+            .collect();
+        if others.is_empty() {
+            self.bind(&id, chord, commands);
+        } else {
+            self.conflict = Some(PendingConflict { command: id, chord, others });
+        }
+    }
+
+    fn bind(&mut self, id: &str, chord: KeyChord, commands: &mut CommandRegistry) {
+        let mut chords = commands.shortcuts(id);
+        if !chords.contains(&chord) {
+            chords.push(chord);
+        }
+        commands.set_shortcuts(id, chords);
+        self.save(commands);
+    }
+
+    fn save(&mut self, commands: &CommandRegistry) {
+        self.error = commands.save().err().map(|e| format!("Failed to save shortcuts: {}", e));
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, commands: &mut CommandRegistry) {
+        if ctx.data_mut(|d| d.remove_temp::<bool>(open_request_id())).unwrap_or(false) {
+            self.open = true;
+        }
+        if !self.open {
+            self.capturing = None;
+            self.conflict = None;
+            return;
+        }
+
+        let mut open = self.open;
+        egui::Window::new("⌨ Preferences > Shortcuts")
+            .open(&mut open)
+            .default_size([520.0, 520.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("🔍");
+                    ui.add(egui::TextEdit::singleline(&mut self.filter).hint_text("Filter commands"));
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.button("Reset All").clicked() {
+                            commands.reset_all();
+                            self.save(commands);
+                        }
+                    });
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(Color32::from_rgb(230, 90, 90), error);
+                }
+                self.render_conflict(ui, commands);
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    self.render_commands(ui, commands);
+                });
+            });
+        self.open = open;
+    }
+
+    fn render_conflict(&mut self, ui: &mut egui::Ui, commands: &mut CommandRegistry) {
+        let Some(conflict) = &self.conflict else {
+            return;
+        };
+        let names: Vec<&str> = conflict.others.iter().map(|(_, name)| name.as_str()).collect();
+        let mut resolved = None;
+        ui.group(|ui| {
+            ui.colored_label(
+                Color32::from_rgb(230, 180, 80),
+                format!("⚠ {} is already used by {}", conflict.chord, names.join(", ")),
+            );
+            ui.horizontal(|ui| {
+                if ui.button("Reassign").clicked() {
+                    resolved = Some(true);
+                }
+                if ui.button("Cancel").clicked() {
+                    resolved = Some(false);
+                }
+            });
+        });
+        let Some(reassign) = resolved else {
+            return;
+        };
+        let Some(conflict) = self.conflict.take() else {
+            return;
+        };
+        if reassign {
+            // Take the chord away from the other commands, then bind it
+            for (other, _) in &conflict.others {
+                let mut chords = commands.shortcuts(other);
+                chords.retain(|c| *c != conflict.chord);
+                commands.set_shortcuts(other, chords);
+            }
+            self.bind(&conflict.command, conflict.chord, commands);
+        }
+    }
+
+    fn render_commands(&mut self, ui: &mut egui::Ui, commands: &mut CommandRegistry) {
+        let filter = self.filter.trim().to_lowercase();
+        let rows: Vec<(String, String, String)> = commands
+            .commands()
+            .iter()
+            .filter(|c| filter.is_empty() || format!("{} {}", c.category, c.name).to_lowercase().contains(&filter))
+            .map(|c| (c.id.clone(), c.name.clone(), c.category.clone()))
+            .collect();
+
+        let mut category = String::new();
+        for (id, name, row_category) in rows {
+            if row_category != category {
+                ui.add_space(4.0);
+                ui.label(RichText::new(&row_category).strong());
+                category = row_category;
+            }
+            ui.horizontal(|ui| {
+                ui.add_sized([200.0, 18.0], egui::Label::new(&name).truncate());
+
+                let mut chords = commands.shortcuts(&id);
+                let mut removed = None;
+                for (index, chord) in chords.iter().enumerate() {
+                    if ui.button(RichText::new(format!("{} ✖", chord)).monospace()).on_hover_text("Remove").clicked() {
+                        removed = Some(index);
+                    }
+                }
+                if let Some(index) = removed {
+                    chords.remove(index);
+                    commands.set_shortcuts(&id, chords);
+                    self.save(commands);
+                }
+
+                if self.capturing.as_deref() == Some(id.as_str()) {
+                    ui.label(RichText::new("Press a key...").italics().color(Color32::from_rgb(120, 180, 255)));
+                    if ui.small_button("Cancel").clicked() {
+                        self.capturing = None;
+                    }
+                } else if ui.small_button("+").on_hover_text("Add a shortcut").clicked() {
+                    self.capturing = Some(id.clone());
+                    self.conflict = None;
+                }
+
+                if commands.is_customized(&id) && ui.small_button("↺").on_hover_text("Reset to default").clicked() {
+                    commands.reset(&id);
+                    self.save(commands);
+                }
+            });
+        }
+    }
+}
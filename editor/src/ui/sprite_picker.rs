@@ -243,7 +243,7 @@ pub fn render_sprite_picker(
 }
 
 /// Find all .sprite files recursively
-fn find_sprite_files(dir: &PathBuf) -> Vec<PathBuf> {
+pub(crate) fn find_sprite_files(dir: &PathBuf) -> Vec<PathBuf> {
     let mut files = Vec::new();
 
     if let Ok(entries) = std::fs::read_dir(dir) {
@@ -1,11 +1,27 @@
 //! Texture Inspector UI (Unity-style)
 //!
-//! Shows texture import settings when a texture is selected in Asset Browser.
+//! Shows texture import settings when a texture is selected in Asset Browser,
+//! or size/format settings when a render texture is.
 
 use crate::texture_import_settings::*;
+use ecs::{RenderTextureAsset, RenderTextureFormat};
 use egui;
 use std::path::PathBuf;
 
+fn render_texture_reload_id() -> egui::Id {
+    egui::Id::new("render_texture_reload_request")
+}
+
+/// Ask the editor to reload the project's render textures (after one was created or edited)
+pub fn request_render_texture_reload(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(render_texture_reload_id(), true));
+}
+
+/// Whether a render texture reload was requested since the last call
+pub fn take_render_texture_reload(ctx: &egui::Context) -> bool {
+    ctx.data_mut(|d| d.remove_temp::<bool>(render_texture_reload_id())).unwrap_or(false)
+}
+
 /// Texture Inspector state
 pub struct TextureInspector {
     /// Currently selected texture path
@@ -14,6 +30,10 @@ pub struct TextureInspector {
     pub settings: TextureImportSettings,
     /// Has unsaved changes
     pub has_changes: bool,
+    /// Currently selected render texture asset (instead of a texture)
+    pub selected_render_texture: Option<PathBuf>,
+    /// Edited render texture settings
+    pub render_texture: RenderTextureAsset,
 }
 
 impl Default for TextureInspector {
@@ -22,6 +42,8 @@ impl Default for TextureInspector {
             selected_texture: None,
             settings: TextureImportSettings::default(),
             has_changes: false,
+            selected_render_texture: None,
+            render_texture: RenderTextureAsset::default(),
         }
     }
 }
@@ -33,6 +55,20 @@ impl TextureInspector {
         self.settings = TextureImportSettings::load(&texture_path)
             .unwrap_or_default();
         self.has_changes = false;
+        self.selected_render_texture = None;
+    }
+    
+    /// Set the selected render texture and load its asset
+    pub fn set_render_texture(&mut self, path: PathBuf) {
+        self.render_texture = RenderTextureAsset::load(&path).unwrap_or_default();
+        self.selected_render_texture = Some(path);
+        self.selected_texture = None;
+        self.has_changes = false;
+    }
+    
+    /// Whether a texture or render texture is selected (and shown instead of the entity inspector)
+    pub fn is_active(&self) -> bool {
+        self.selected_texture.is_some() || self.selected_render_texture.is_some()
     }
     
     /// Clear selection
@@ -40,10 +76,15 @@ impl TextureInspector {
         self.selected_texture = None;
         self.settings = TextureImportSettings::default();
         self.has_changes = false;
+        self.selected_render_texture = None;
     }
     
     /// Render the inspector UI
     pub fn render(&mut self, ui: &mut egui::Ui) -> Option<TextureInspectorAction> {
+        if self.selected_render_texture.is_some() {
+            return self.render_render_texture(ui);
+        }
+        
         let mut action = None;
         
         if let Some(texture_path) = &self.selected_texture {
@@ -87,6 +128,12 @@ impl TextureInspector {
                         if changed { self.has_changes = true; }
                         ui.end_row();
                         
+                        ui.label("Pivot");
+                        if Self::pivot_editor(ui, &mut self.settings.pivot) {
+                            self.has_changes = true;
+                        }
+                        ui.end_row();
+                        
                         ui.label("Pixels Per Unit");
                         if ui.add(egui::DragValue::new(&mut self.settings.pixels_per_unit)
                             .speed(1.0)
@@ -162,10 +209,15 @@ impl TextureInspector {
                 if ui.checkbox(&mut self.settings.alpha_is_transparency, "Alpha Is Transparency").changed() {
                     self.has_changes = true;
                 }
+                if ui.checkbox(&mut self.settings.premultiplied_alpha, "Premultiplied Alpha").changed() {
+                    self.has_changes = true;
+                }
                 if ui.checkbox(&mut self.settings.read_write_enabled, "Read/Write Enabled").changed() {
                     self.has_changes = true;
                 }
-                if ui.checkbox(&mut self.settings.generate_mipmaps, "Generate Mipmaps").changed() {
+                if ui.checkbox(&mut self.settings.generate_mipmaps, "Generate Mipmaps")
+                    .on_hover_text("Smaller copies for zoomed-out views. Ignored with Point filtering, so pixel art stays crisp")
+                    .changed() {
                     self.has_changes = true;
                 }
             });
@@ -174,19 +226,7 @@ impl TextureInspector {
             ui.separator();
             
             // Action buttons
-            ui.horizontal(|ui| {
-                if ui.button("💾 Apply").clicked() && self.has_changes {
-                    action = Some(TextureInspectorAction::Apply);
-                }
-                
-                if ui.button("↺ Revert").clicked() && self.has_changes {
-                    action = Some(TextureInspectorAction::Revert);
-                }
-                
-                if self.has_changes {
-                    ui.label(egui::RichText::new("● Unsaved changes").color(egui::Color32::YELLOW));
-                }
-            });
+            action = self.action_buttons(ui);
             
         } else {
             ui.centered_and_justified(|ui| {
@@ -196,6 +236,121 @@ impl TextureInspector {
         
         action
     }
+    
+    /// Apply / Revert row shared by both kinds of selection
+    fn action_buttons(&self, ui: &mut egui::Ui) -> Option<TextureInspectorAction> {
+        let mut action = None;
+        ui.horizontal(|ui| {
+            if ui.button("💾 Apply").clicked() && self.has_changes {
+                action = Some(TextureInspectorAction::Apply);
+            }
+            
+            if ui.button("↺ Revert").clicked() && self.has_changes {
+                action = Some(TextureInspectorAction::Revert);
+            }
+            
+            if self.has_changes {
+                ui.label(egui::RichText::new("● Unsaved changes").color(egui::Color32::YELLOW));
+            }
+        });
+        action
+    }
+    
+    fn render_render_texture(&mut self, ui: &mut egui::Ui) -> Option<TextureInspectorAction> {
+        let Some(path) = &self.selected_render_texture else {
+            return None;
+        };
+        
+        ui.heading("Render Texture");
+        ui.separator();
+        if let Some(name) = path.file_stem() {
+            ui.label(format!("📺 {}", name.to_string_lossy()));
+            ui.label(
+                egui::RichText::new(format!("Texture ID: {}", RenderTextureAsset::texture_id(&name.to_string_lossy())))
+                    .small()
+                    .color(egui::Color32::GRAY),
+            );
+        }
+        ui.add_space(10.0);
+        
+        egui::Grid::new("render_texture_grid")
+            .num_columns(2)
+            .spacing([10.0, 8.0])
+            .show(ui, |ui| {
+                ui.label("Size");
+                ui.horizontal(|ui| {
+                    self.has_changes |= ui.add(egui::DragValue::new(&mut self.render_texture.width).range(1..=4096)).changed();
+                    ui.label("×");
+                    self.has_changes |= ui.add(egui::DragValue::new(&mut self.render_texture.height).range(1..=4096)).changed();
+                });
+                ui.end_row();
+                
+                ui.label("Color Format");
+                egui::ComboBox::from_id_salt("render_texture_format")
+                    .selected_text(match self.render_texture.format {
+                        RenderTextureFormat::Srgb => "sRGB (Color)",
+                        RenderTextureFormat::Linear => "Linear",
+                    })
+                    .show_ui(ui, |ui| {
+                        self.has_changes |= ui.selectable_value(&mut self.render_texture.format, RenderTextureFormat::Srgb, "sRGB (Color)").changed();
+                        self.has_changes |= ui.selectable_value(&mut self.render_texture.format, RenderTextureFormat::Linear, "Linear").changed();
+                    });
+                ui.end_row();
+                
+                ui.label("Depth Buffer");
+                self.has_changes |= ui.checkbox(&mut self.render_texture.depth_buffer, "Own depth buffer")
+                    .on_hover_text("Off: share one depth buffer with other render textures of the same size")
+                    .changed();
+                ui.end_row();
+            });
+        
+        ui.add_space(10.0);
+        ui.separator();
+        self.action_buttons(ui)
+    }
+}
+
+/// Named pivots offered in the Pivot combo box
+const PIVOT_PRESETS: &[(&str, [f32; 2])] = &[
+    ("Center", [0.5, 0.5]),
+    ("Top Left", [0.0, 1.0]),
+    ("Top", [0.5, 1.0]),
+    ("Top Right", [1.0, 1.0]),
+    ("Left", [0.0, 0.5]),
+    ("Right", [1.0, 0.5]),
+    ("Bottom Left", [0.0, 0.0]),
+    ("Bottom", [0.5, 0.0]),
+    ("Bottom Right", [1.0, 0.0]),
+];
+
+impl TextureInspector {
+    /// Preset combo plus X/Y fields; returns true when the pivot changed
+    fn pivot_editor(ui: &mut egui::Ui, pivot: &mut [f32; 2]) -> bool {
+        let mut changed = false;
+        ui.vertical(|ui| {
+            let selected = PIVOT_PRESETS
+                .iter()
+                .find(|(_, value)| value == pivot)
+                .map_or("Custom", |(name, _)| name);
+            egui::ComboBox::from_id_salt("sprite_pivot")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (name, value) in PIVOT_PRESETS {
+                        if ui.selectable_label(pivot == value, *name).clicked() {
+                            *pivot = *value;
+                            changed = true;
+                        }
+                    }
+                });
+            ui.horizontal(|ui| {
+                ui.label("X");
+                changed |= ui.add(egui::DragValue::new(&mut pivot[0]).speed(0.01).range(0.0..=1.0)).changed();
+                ui.label("Y");
+                changed |= ui.add(egui::DragValue::new(&mut pivot[1]).speed(0.01).range(0.0..=1.0)).changed();
+            });
+        });
+        changed
+    }
 }
 
 /// Actions that can be triggered from the inspector
@@ -0,0 +1,244 @@
+//! Tile Palette
+//!
+//! Picks the TileSet of the selected Tilemap layer, a single tile or a multi-tile stamp,
+//! and the painting tool used in the 2D scene view.
+//! Painting is active while the window is open and a tool is chosen.
+
+use crate::tools::tile_painting::{self, TileBrush, TilePaintTool, TileStamp};
+use ecs::{Entity, TileSet, World};
+use egui;
+use engine::texture_manager::TextureManager;
+use std::path::Path;
+
+const CELL_SIZE: f32 = 32.0;
+
+fn open_request_id() -> egui::Id {
+    egui::Id::new("tile_palette_open_request")
+}
+
+/// Ask the editor to show the Tile Palette (View menu)
+pub fn open(ctx: &egui::Context) {
+    ctx.data_mut(|d| d.insert_temp(open_request_id(), true));
+}
+
+/// Tile Palette window state
+pub struct TilePalette {
+    pub open: bool,
+    tool: Option<TilePaintTool>,
+    /// Selected tileset cells as (column, row) corners
+    selection: ((u32, u32), (u32, u32)),
+    /// Corner where the current selection drag started
+    selection_anchor: Option<(u32, u32)>,
+}
+
+impl Default for TilePalette {
+    fn default() -> Self {
+        Self {
+            open: false,
+            tool: Some(TilePaintTool::Brush),
+            selection: ((1, 0), (1, 0)),
+            selection_anchor: None,
+        }
+    }
+}
+
+/// Texture UVs of a tile, or None for ids outside the tileset
+pub fn tile_uv(tileset: &TileSet, texture_size: [usize; 2], tile_id: u32) -> Option<egui::Rect> {
+    let (x, y) = tileset.get_tile_coords(tile_id)?;
+    let (width, height) = (texture_size[0] as f32, texture_size[1] as f32);
+    if width <= 0.0 || height <= 0.0 {
+        return None;
+    }
+    Some(egui::Rect::from_min_size(
+        egui::pos2(x as f32 / width, y as f32 / height),
+        egui::vec2(tileset.tile_width as f32 / width, tileset.tile_height as f32 / height),
+    ))
+}
+
+/// Load a tileset's texture, normalizing the path the same way the scene view does
+pub fn tileset_texture(ctx: &egui::Context, tileset: &TileSet, texture_manager: &mut TextureManager) -> Option<egui::TextureHandle> {
+    let normalized_path = tileset.texture_path.replace('/', std::path::MAIN_SEPARATOR_STR);
+    texture_manager.load_texture(ctx, &tileset.texture_id, Path::new(&normalized_path)).cloned()
+}
+
+impl TilePalette {
+    pub fn render(
+        &mut self,
+        ctx: &egui::Context,
+        world: &mut World,
+        selected_entity: Option<Entity>,
+        texture_manager: &mut TextureManager,
+    ) -> bool {
+        if ctx.data_mut(|d| d.remove_temp::<bool>(open_request_id())).unwrap_or(false) {
+            self.open = true;
+        }
+        if !self.open {
+            tile_painting::set_brush(ctx, None);
+            return false;
+        }
+
+        let mut changed = false;
+        let mut brush = None;
+        let mut open = self.open;
+        egui::Window::new("🧱 Tile Palette")
+            .open(&mut open)
+            .default_size([320.0, 420.0])
+            .resizable(true)
+            .show(ctx, |ui| {
+                (changed, brush) = self.render_contents(ui, world, selected_entity, texture_manager);
+            });
+        self.open = open;
+
+        tile_painting::set_brush(ctx, brush.filter(|_| self.open));
+        changed
+    }
+
+    fn render_contents(
+        &mut self,
+        ui: &mut egui::Ui,
+        world: &mut World,
+        selected_entity: Option<Entity>,
+        texture_manager: &mut TextureManager,
+    ) -> (bool, Option<TileBrush>) {
+        let mut changed = false;
+        let Some(entity) = selected_entity.filter(|e| world.tilemaps.contains_key(e)) else {
+            ui.label("Select a Tilemap layer to paint.");
+            return (false, None);
+        };
+
+        if let Some(tilemap) = world.tilemaps.get(&entity) {
+            ui.label(format!("Layer: {} ({}×{})", tilemap.name, tilemap.width, tilemap.height));
+        }
+
+        // TileSet picker: any tileset in the scene can be assigned to this layer
+        let mut tilesets: Vec<TileSet> = Vec::new();
+        for tileset in world.tilesets.values() {
+            if !tilesets.iter().any(|t| t.name == tileset.name) {
+                tilesets.push(tileset.clone());
+            }
+        }
+        tilesets.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let current = world.tilesets.get(&entity).map(|t| t.name.clone());
+        let mut assign = None;
+        ui.horizontal(|ui| {
+            ui.label("TileSet");
+            egui::ComboBox::from_id_salt("tile_palette_tileset")
+                .selected_text(current.clone().unwrap_or_else(|| "None".to_string()))
+                .show_ui(ui, |ui| {
+                    for tileset in &tilesets {
+                        if ui.selectable_label(current.as_deref() == Some(tileset.name.as_str()), &tileset.name).clicked() {
+                            assign = Some(tileset.clone());
+                        }
+                    }
+                });
+        });
+        if let Some(tileset) = assign {
+            if let Some(tilemap) = world.tilemaps.get_mut(&entity) {
+                tilemap.tileset_id = tileset.name.clone();
+            }
+            world.tilesets.insert(entity, tileset);
+            changed = true;
+        }
+
+        ui.horizontal(|ui| {
+            for tool in TilePaintTool::ALL {
+                if ui.selectable_label(self.tool == Some(tool), tool.label()).clicked() {
+                    self.tool = if self.tool == Some(tool) { None } else { Some(tool) };
+                }
+            }
+        });
+        ui.separator();
+
+        let Some(tileset) = world.tilesets.get(&entity).cloned() else {
+            ui.label(egui::RichText::new("Assign a TileSet to pick tiles").color(egui::Color32::GRAY));
+            return (changed, None);
+        };
+
+        self.render_tile_grid(ui, &tileset, texture_manager);
+
+        let ((x0, y0), (x1, y1)) = self.selection;
+        let stamp = TileStamp {
+            width: x1 - x0 + 1,
+            height: y1 - y0 + 1,
+            tiles: (y0..=y1).flat_map(|y| (x0..=x1).map(move |x| y * tileset.columns + x)).collect(),
+        };
+        ui.label(egui::RichText::new(if stamp.tiles.len() == 1 {
+            format!("Tile {}", stamp.tiles[0])
+        } else {
+            format!("Stamp {}×{}", stamp.width, stamp.height)
+        }).small().color(egui::Color32::from_rgb(150, 150, 150)));
+        ui.label(egui::RichText::new(
+            "💡 Drag in the palette for a stamp; tile 0 is empty. Paint with the left mouse button in the 2D scene view."
+        ).small().color(egui::Color32::from_rgb(150, 150, 150)));
+
+        (changed, self.tool.map(|tool| TileBrush { tool, stamp }))
+    }
+
+    fn render_tile_grid(&mut self, ui: &mut egui::Ui, tileset: &TileSet, texture_manager: &mut TextureManager) {
+        let columns = tileset.columns.max(1);
+        let rows = tileset.tile_count.div_ceil(columns).max(1);
+        let texture = tileset_texture(ui.ctx(), tileset, texture_manager);
+
+        // Keep the selection inside the tileset (it may have changed)
+        let clamp = |(x, y): (u32, u32)| (x.min(columns - 1), y.min(rows - 1));
+        self.selection = (clamp(self.selection.0), clamp(self.selection.1));
+
+        egui::ScrollArea::both().max_height(ui.available_height() - 40.0).show(ui, |ui| {
+            let size = egui::vec2(columns as f32 * CELL_SIZE, rows as f32 * CELL_SIZE);
+            let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 0.0, egui::Color32::from_gray(30));
+
+            let cell_rect = |x: u32, y: u32| egui::Rect::from_min_size(
+                rect.min + egui::vec2(x as f32 * CELL_SIZE, y as f32 * CELL_SIZE),
+                egui::vec2(CELL_SIZE, CELL_SIZE),
+            );
+
+            for tile_id in 0..tileset.tile_count {
+                let cell = cell_rect(tile_id % columns, tile_id / columns).shrink(1.0);
+                if tile_id == 0 {
+                    // Tile 0 paints empty cells
+                    painter.rect_stroke(cell, 0.0, egui::Stroke::new(1.0, egui::Color32::from_gray(80)), egui::StrokeKind::Inside);
+                    painter.line_segment([cell.left_top(), cell.right_bottom()], egui::Stroke::new(1.0, egui::Color32::from_gray(80)));
+                    continue;
+                }
+                match (&texture, texture.as_ref().and_then(|t| tile_uv(tileset, t.size(), tile_id))) {
+                    (Some(texture), Some(uv)) => {
+                        painter.image(texture.id(), cell, uv, egui::Color32::WHITE);
+                    }
+                    _ => {
+                        painter.text(cell.center(), egui::Align2::CENTER_CENTER, tile_id.to_string(), egui::FontId::proportional(10.0), egui::Color32::GRAY);
+                    }
+                }
+            }
+
+            let cell_under = |pos: egui::Pos2| {
+                let local = pos - rect.min;
+                clamp(((local.x / CELL_SIZE).max(0.0) as u32, (local.y / CELL_SIZE).max(0.0) as u32))
+            };
+            let shift = ui.input(|i| i.modifiers.shift);
+            if response.drag_started() || response.clicked() {
+                if let Some(pos) = response.interact_pointer_pos() {
+                    let cell = cell_under(pos);
+                    let anchor = if shift { self.selection.0 } else { cell };
+                    self.selection_anchor = Some(anchor);
+                    self.selection = ((anchor.0.min(cell.0), anchor.1.min(cell.1)), (anchor.0.max(cell.0), anchor.1.max(cell.1)));
+                }
+            }
+            if response.dragged() {
+                if let (Some(anchor), Some(pos)) = (self.selection_anchor, response.interact_pointer_pos()) {
+                    let cell = cell_under(pos);
+                    self.selection = ((anchor.0.min(cell.0), anchor.1.min(cell.1)), (anchor.0.max(cell.0), anchor.1.max(cell.1)));
+                }
+            }
+            if response.drag_stopped() || response.clicked() {
+                self.selection_anchor = None;
+            }
+
+            let ((x0, y0), (x1, y1)) = self.selection;
+            let selected = cell_rect(x0, y0).union(cell_rect(x1, y1));
+            painter.rect_stroke(selected, 0.0, egui::Stroke::new(2.0, egui::Color32::from_rgb(255, 200, 0)), egui::StrokeKind::Inside);
+        });
+    }
+}
@@ -0,0 +1,177 @@
+//! UI Events panel: what the game UI did with the pointer and which script callbacks
+//! ran, for finding out why a button doesn't respond
+
+use egui::{Color32, RichText};
+use engine::ui_manager::{UIHitInfo, UIManager};
+
+/// Event names the log can hold, for the type filter (pointer events, then the ones
+/// scripts listen to)
+const EVENT_TYPES: [&str; 15] = [
+    "PointerEnter", "PointerExit", "PointerDown", "PointerUp", "PointerClick",
+    "BeginDrag", "Drag", "EndDrag", "Scroll", "HoverEnter", "HoverExit",
+    "Click", "ValueChanged", "Submit", "Drop",
+];
+
+const TARGET_COLOR: Color32 = Color32::from_rgb(80, 220, 120);
+const SKIPPED_COLOR: Color32 = Color32::from_rgb(255, 160, 60);
+
+/// Panel settings, kept in egui temp data like the profiler's sort order
+#[derive(Clone)]
+struct PanelState {
+    filter: ui::UIEventLogFilter,
+    /// Highlight and list what is under the pointer in the Game view
+    inspect_hover: bool,
+    auto_scroll: bool,
+}
+
+impl Default for PanelState {
+    fn default() -> Self {
+        Self { filter: ui::UIEventLogFilter::default(), inspect_hover: false, auto_scroll: true }
+    }
+}
+
+fn state_id() -> egui::Id {
+    egui::Id::new("ui_events_panel_state")
+}
+
+fn probe_id() -> egui::Id {
+    egui::Id::new("ui_events_panel_probe")
+}
+
+/// Whether the Game view should run inspect_game_view this frame
+pub fn inspect_hover_enabled(ctx: &egui::Context) -> bool {
+    ctx.data(|d| d.get_temp::<PanelState>(state_id())).is_some_and(|state| state.inspect_hover)
+}
+
+/// Outline the elements under the pointer in the Game view (green: the one the pointer
+/// hits, orange: the ones it passes over) and keep them for the panel's list
+pub fn inspect_game_view(ui: &egui::Ui, ui_manager: &mut UIManager, game_rect: egui::Rect) {
+    let hits = match ui.ctx().pointer_hover_pos().filter(|pos| game_rect.contains(*pos)) {
+        Some(pos) => ui_manager.inspect_point(pos),
+        None => Vec::new(),
+    };
+    let painter = ui.painter_at(game_rect);
+    // Skipped elements first so the target's outline stays on top
+    for hit in hits.iter().rev() {
+        let color = if hit.skipped.is_none() { TARGET_COLOR } else { SKIPPED_COLOR };
+        let width = if hit.skipped.is_none() { 2.0 } else { 1.0 };
+        let mut outline = hit.corners.to_vec();
+        outline.push(hit.corners[0]);
+        painter.add(egui::Shape::line(outline, egui::Stroke::new(width, color)));
+    }
+    ui.ctx().data_mut(|d| d.insert_temp(probe_id(), hits));
+}
+
+/// Contents of the UI Events dock tab
+pub fn render_panel(ui: &mut egui::Ui, ui_manager: &mut UIManager, is_playing: bool) {
+    let mut state = ui.data_mut(|d| d.get_temp::<PanelState>(state_id())).unwrap_or_default();
+
+    ui.horizontal(|ui| {
+        let mut recording = ui_manager.event_log().is_enabled();
+        if ui.checkbox(&mut recording, "Record").on_hover_text("Log pointer and script events of the game UI").changed() {
+            ui_manager.event_log_mut().set_enabled(recording);
+        }
+        ui.checkbox(&mut state.inspect_hover, "Inspect Hover")
+            .on_hover_text("Outline and list the elements under the pointer in the Game view");
+        ui.checkbox(&mut state.auto_scroll, "Auto Scroll");
+        if ui.button("Clear").clicked() {
+            ui_manager.event_log_mut().clear();
+        }
+        ui.separator();
+        ui.label("🔍");
+        ui.add(egui::TextEdit::singleline(&mut state.filter.path).hint_text("Element path").desired_width(140.0));
+        ui.menu_button("Events", |ui| {
+            for event in EVENT_TYPES {
+                let mut shown = !state.filter.hidden_events.contains(event);
+                if ui.checkbox(&mut shown, event).changed() {
+                    if shown {
+                        state.filter.hidden_events.remove(event);
+                    } else {
+                        state.filter.hidden_events.insert(event.to_string());
+                    }
+                }
+            }
+        });
+    });
+    if !is_playing {
+        ui.label(RichText::new("Events are logged while the game is playing").color(Color32::GRAY));
+    }
+    ui.separator();
+
+    if state.inspect_hover {
+        egui::CollapsingHeader::new("Under Pointer").default_open(true).show(ui, |ui| {
+            let hits: Vec<UIHitInfo> = ui.data(|d| d.get_temp(probe_id())).unwrap_or_default();
+            if hits.is_empty() {
+                ui.label(RichText::new("Hover the Game view").color(Color32::GRAY));
+            }
+            for hit in &hits {
+                ui.horizontal(|ui| {
+                    match &hit.skipped {
+                        None => ui.label(RichText::new("● hit").color(TARGET_COLOR)),
+                        Some(reason) => ui.label(RichText::new(format!("○ {}", reason)).color(SKIPPED_COLOR)),
+                    };
+                    ui.label(RichText::new(&hit.path).monospace());
+                });
+            }
+        });
+    }
+
+    egui::CollapsingHeader::new("Callbacks").show(ui, |ui| {
+        let callbacks: Vec<_> = ui_manager.element_callbacks().into_iter()
+            .filter(|callback| {
+                state.filter.path.is_empty()
+                    || callback.element_path.to_lowercase().contains(&state.filter.path.to_lowercase())
+            })
+            .collect();
+        if callbacks.is_empty() {
+            ui.label(RichText::new("No callbacks registered").color(Color32::GRAY));
+            return;
+        }
+        egui::Grid::new("ui_events_callbacks").striped(true).show(ui, |ui| {
+            for callback in &callbacks {
+                ui.label(RichText::new(&callback.element_path).monospace());
+                ui.label(&callback.event);
+                ui.label(RichText::new(&callback.callback).monospace());
+                match callback.owner {
+                    Some(owner) => ui.label(RichText::new(format!("entity {:?}", owner)).color(Color32::GRAY)),
+                    None => ui.label(""),
+                };
+                ui.end_row();
+            }
+        });
+    });
+    ui.separator();
+
+    let entries: Vec<_> = ui_manager.event_log().entries().filter(|entry| state.filter.matches(entry)).collect();
+    if entries.is_empty() {
+        let message = if ui_manager.event_log().is_enabled() { "No events yet" } else { "Recording is off" };
+        ui.label(RichText::new(message).color(Color32::GRAY));
+    }
+    let row_height = ui.text_style_height(&egui::TextStyle::Monospace) + ui.spacing().item_spacing.y;
+    egui::ScrollArea::both()
+        .id_salt("ui_events_log")
+        .auto_shrink([false, false])
+        .stick_to_bottom(state.auto_scroll)
+        .show_rows(ui, row_height, entries.len(), |ui, rows| {
+            for entry in &entries[rows] {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("{:>6}", entry.frame)).monospace().color(Color32::GRAY));
+                    ui.label(RichText::new(format!("{:<12}", entry.event)).monospace().strong());
+                    ui.label(RichText::new(&entry.target).monospace());
+                    ui.label(RichText::new(format!("[{}]", entry.canvas)).color(Color32::GRAY));
+                    match &entry.consumed_by {
+                        Some(path) => ui.label(RichText::new(format!("→ {}", path)).color(TARGET_COLOR)),
+                        None => ui.label(RichText::new("→ not handled").color(Color32::GRAY)),
+                    };
+                    if !entry.detail.is_empty() {
+                        ui.label(RichText::new(&entry.detail).color(Color32::LIGHT_GRAY));
+                    }
+                });
+            }
+        });
+
+    ui.data_mut(|d| d.insert_temp(state_id(), state));
+    if ui_manager.event_log().is_enabled() {
+        ui.ctx().request_repaint();
+    }
+}
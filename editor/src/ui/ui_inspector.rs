@@ -0,0 +1,515 @@
+//! UI Element Inspector (Unity-style)
+//!
+//! Shows the RectTransform, components and style of a UI element selected under
+//! the Canvas node of the hierarchy. Edits go straight to the live UI instance,
+//! are undoable, and can be written back to the prefab asset ("Apply to Prefab").
+
+use egui;
+use engine::ui_manager::UIManager;
+use std::collections::BTreeSet;
+use ui::prefab::UIPrefabElement;
+use super::inspector::color_edit;
+
+/// One committed inspector edit (components only; children are never touched)
+struct UIElementEdit {
+    path: String,
+    before: UIPrefabElement,
+    after: UIPrefabElement,
+}
+
+/// What the caller should do after render
+pub enum UIInspectorAction {
+    /// Write the edited elements of an instance back to its prefab file
+    ApplyToPrefab { instance: String, paths: Vec<String> },
+}
+
+/// UI Inspector state
+#[derive(Default)]
+pub struct UIInspector {
+    /// Selected element path ("instance" or "instance/child/...")
+    pub selected: Option<String>,
+    undo_stack: Vec<UIElementEdit>,
+    redo_stack: Vec<UIElementEdit>,
+    /// State before the edit in progress (drag or focused text field)
+    pending: Option<(String, UIPrefabElement)>,
+    /// Elements edited since they were last applied to their prefab
+    edited: BTreeSet<String>,
+    /// Style picked in the style combo
+    style_class: String,
+}
+
+impl UIInspector {
+    pub fn select(&mut self, element_path: String) {
+        self.selected = Some(element_path);
+    }
+
+    /// Whether a UI element is selected (and shown instead of the entity inspector)
+    pub fn is_active(&self) -> bool {
+        self.selected.is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.selected = None;
+    }
+
+    /// Undo the last UI edit. Returns its description.
+    pub fn undo(&mut self, ui_manager: &mut UIManager) -> Option<String> {
+        self.commit(ui_manager);
+        let edit = self.undo_stack.pop()?;
+        if let Err(e) = ui_manager.set_element(&edit.path, &edit.before) {
+            log::warn!("Cannot undo UI edit: {}", e);
+        }
+        let description = format!("Edit UI {}", edit.path);
+        self.redo_stack.push(edit);
+        Some(description)
+    }
+
+    /// Redo the last undone UI edit. Returns its description.
+    pub fn redo(&mut self, ui_manager: &mut UIManager) -> Option<String> {
+        let edit = self.redo_stack.pop()?;
+        if let Err(e) = ui_manager.set_element(&edit.path, &edit.after) {
+            log::warn!("Cannot redo UI edit: {}", e);
+        }
+        let description = format!("Edit UI {}", edit.path);
+        self.edited.insert(edit.path.clone());
+        self.undo_stack.push(edit);
+        Some(description)
+    }
+
+    /// Edits of `instance` were written to its prefab
+    pub fn mark_applied(&mut self, instance: &str) {
+        let prefix = format!("{}/", instance);
+        self.edited.retain(|path| path != instance && !path.starts_with(&prefix));
+    }
+
+    /// Drop the history (e.g. project change); the selection is kept
+    pub fn reset_history(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.pending = None;
+        self.edited.clear();
+    }
+
+    /// Turn the edit in progress into one undo step
+    fn commit(&mut self, ui_manager: &UIManager) {
+        let Some((path, before)) = self.pending.take() else {
+            return;
+        };
+        let Some(after) = ui_manager.element(&path).cloned() else {
+            return;
+        };
+        if same_components(&before, &after) {
+            return; // Dragged back to where it started
+        }
+        self.edited.insert(path.clone());
+        self.redo_stack.clear();
+        self.undo_stack.push(UIElementEdit { path, before, after });
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui, ui_manager: &mut UIManager) -> Option<UIInspectorAction> {
+        let path = self.selected.clone()?;
+        let instance = path.split('/').next().unwrap_or(&path).to_string();
+
+        let Some(live) = ui_manager.element(&path).cloned() else {
+            ui.heading("UI Element");
+            ui.label(format!("'{}' is no longer active", path));
+            return None;
+        };
+
+        let mut action = None;
+        ui.horizontal(|ui| {
+            ui.heading(format!("🖼 {}", live.name));
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.add_enabled(!self.redo_stack.is_empty(), egui::Button::new("↪")).on_hover_text("Redo UI edit (Ctrl+Y)").clicked() {
+                    self.redo(ui_manager);
+                }
+                if ui.add_enabled(!self.undo_stack.is_empty() || self.pending.is_some(), egui::Button::new("↩")).on_hover_text("Undo UI edit (Ctrl+Z)").clicked() {
+                    self.undo(ui_manager);
+                }
+            });
+        });
+        ui.label(egui::RichText::new(&path).weak());
+
+        let prefix = format!("{}/", instance);
+        let instance_edits: Vec<String> = self.edited.iter()
+            .filter(|p| **p == instance || p.starts_with(&prefix))
+            .cloned()
+            .collect();
+        ui.horizontal(|ui| {
+            match ui_manager.instance_source(&instance) {
+                Some(source) => ui.label(format!("Prefab: {}", source)),
+                None => ui.label("Prefab: (unknown)"),
+            };
+            let label = format!("Apply to Prefab ({})", instance_edits.len());
+            if ui.add_enabled(!instance_edits.is_empty(), egui::Button::new(label))
+                .on_hover_text("Write the edited elements of this instance back to the prefab asset")
+                .clicked()
+            {
+                action = Some(UIInspectorAction::ApplyToPrefab { instance: instance.clone(), paths: instance_edits.clone() });
+            }
+        });
+        ui.separator();
+
+        let mut element = live.clone();
+        egui::ScrollArea::vertical().id_salt("ui_inspector_scroll").show(ui, |ui| {
+            render_rect_transform(ui, &mut element.rect_transform);
+            render_ui_element(ui, &mut element.ui_element);
+            if let Some(text) = &mut element.text {
+                render_text(ui, text);
+            }
+            if let Some(image) = &mut element.image {
+                render_image(ui, image);
+            }
+            if let Some(button) = &mut element.button {
+                render_button(ui, button);
+            }
+            if let Some(panel) = &mut element.panel {
+                render_panel(ui, panel);
+            }
+            render_safe_area(ui, &mut element.safe_area);
+            render_canvas_group(ui, &mut element.canvas_group);
+            render_other_components(ui, &element);
+            self.render_style(ui, ui_manager, &mut element);
+        });
+
+        if !same_components(&element, &live) {
+            match ui_manager.set_element(&path, &element) {
+                Ok(()) => {
+                    if self.pending.as_ref().is_some_and(|(p, _)| *p != path) {
+                        self.commit(ui_manager);
+                    }
+                    self.pending.get_or_insert((path, live));
+                }
+                Err(e) => log::warn!("UI edit failed: {}", e),
+            }
+        }
+
+        // A drag or text edit stays one undo step until it ends
+        let interacting = ui.ctx().input(|i| i.pointer.any_down()) || ui.ctx().memory(|m| m.focused().is_some());
+        if !interacting {
+            self.commit(ui_manager);
+        }
+
+        action
+    }
+
+    fn render_style(&mut self, ui: &mut egui::Ui, ui_manager: &UIManager, element: &mut UIPrefabElement) {
+        let mut styles: Vec<String> = ui_manager.theme().styles.keys().cloned().collect();
+        styles.sort();
+        if !styles.contains(&self.style_class) {
+            self.style_class = ui_manager.theme().active_style.clone();
+        }
+
+        ui.collapsing("🎨 Style", |ui| {
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_id_salt("ui_inspector_style")
+                    .selected_text(&self.style_class)
+                    .show_ui(ui, |ui| {
+                        for style in &styles {
+                            ui.selectable_value(&mut self.style_class, style.clone(), style);
+                        }
+                    });
+                if ui.button("Apply").on_hover_text("Restyle this element's panel, button and text with the theme style").clicked() {
+                    ui_manager.apply_style_class(element, Some(&self.style_class));
+                }
+            });
+        });
+    }
+}
+
+/// Compare everything but the children (scripts may add/remove those at any time)
+fn same_components(a: &UIPrefabElement, b: &UIPrefabElement) -> bool {
+    let strip = |e: &UIPrefabElement| serde_json::to_value(UIPrefabElement { children: Vec::new(), ..e.clone() }).ok();
+    strip(a) == strip(b)
+}
+
+fn vec2_row(ui: &mut egui::Ui, label: &str, value: &mut ui::Vec2, speed: f64, names: [&str; 2]) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.add(egui::DragValue::new(&mut value.x).speed(speed).prefix(names[0]));
+        ui.add(egui::DragValue::new(&mut value.y).speed(speed).prefix(names[1]));
+    });
+}
+
+fn render_rect_transform(ui: &mut egui::Ui, rect: &mut ui::RectTransform) {
+    egui::CollapsingHeader::new("📐 Rect Transform").default_open(true).show(ui, |ui| {
+        vec2_row(ui, "Position", &mut rect.anchored_position, 1.0, ["X ", "Y "]);
+        vec2_row(ui, "Size Delta", &mut rect.size_delta, 1.0, ["W ", "H "]);
+        ui.label(egui::RichText::new("Anchors").strong());
+        vec2_row(ui, "Min", &mut rect.anchor_min, 0.01, ["X ", "Y "]);
+        vec2_row(ui, "Max", &mut rect.anchor_max, 0.01, ["X ", "Y "]);
+        vec2_row(ui, "Pivot", &mut rect.pivot, 0.01, ["X ", "Y "]);
+        for value in [&mut rect.anchor_min, &mut rect.anchor_max] {
+            *value = value.clamp(ui::Vec2::ZERO, ui::Vec2::ONE);
+        }
+        ui.horizontal(|ui| {
+            ui.label("Rotation");
+            ui.add(egui::DragValue::new(&mut rect.rotation).speed(1.0).suffix("°"));
+        });
+        vec2_row(ui, "Scale", &mut rect.scale, 0.01, ["X ", "Y "]);
+    });
+}
+
+fn render_ui_element(ui: &mut egui::Ui, element: &mut ui::UIElement) {
+    egui::CollapsingHeader::new("🔲 UI Element").default_open(true).show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.label("Color");
+            color_edit(ui, &mut element.color);
+        });
+        ui.add(egui::Slider::new(&mut element.alpha, 0.0..=1.0).text("Alpha"));
+        ui.checkbox(&mut element.raycast_target, "Raycast Target");
+        ui.checkbox(&mut element.interactable, "Interactable");
+        ui.checkbox(&mut element.draggable, "Draggable");
+        ui.checkbox(&mut element.pass_through, "Pass Through")
+            .on_hover_text("Clicks on this element still reach the game");
+    });
+}
+
+fn render_text(ui: &mut egui::Ui, text: &mut ui::UIText) {
+    egui::CollapsingHeader::new("📝 Text").default_open(true).show(ui, |ui| {
+        ui.add(egui::TextEdit::multiline(&mut text.text).desired_rows(2));
+        ui.horizontal(|ui| {
+            ui.label("Font");
+            ui.text_edit_singleline(&mut text.font);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Size");
+            ui.add(egui::DragValue::new(&mut text.font_size).speed(0.5).range(1.0..=512.0));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Color");
+            color_edit(ui, &mut text.color);
+        });
+        egui::ComboBox::from_label("Alignment")
+            .selected_text(format!("{:?}", text.alignment))
+            .show_ui(ui, |ui| {
+                use ui::TextAlignment::*;
+                for alignment in [TopLeft, TopCenter, TopRight, MiddleLeft, MiddleCenter, MiddleRight, BottomLeft, BottomCenter, BottomRight] {
+                    ui.selectable_value(&mut text.alignment, alignment, format!("{:?}", alignment));
+                }
+            });
+        ui.add(egui::Slider::new(&mut text.line_spacing, 0.5..=3.0).text("Line Spacing"));
+        ui.checkbox(&mut text.rich_text, "Rich Text");
+        ui.checkbox(&mut text.best_fit, "Best Fit");
+    });
+}
+
+fn render_image(ui: &mut egui::Ui, image: &mut ui::UIImage) {
+    egui::CollapsingHeader::new("🖼 Image").default_open(true).show(ui, |ui| {
+        let mut sprite = image.sprite.clone().unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.label("Sprite");
+            if ui.text_edit_singleline(&mut sprite).changed() {
+                image.sprite = (!sprite.is_empty()).then_some(sprite);
+            }
+        });
+        egui::ComboBox::from_label("Image Type")
+            .selected_text(format!("{:?}", image.image_type))
+            .show_ui(ui, |ui| {
+                for image_type in [ui::ImageType::Simple, ui::ImageType::Sliced, ui::ImageType::Tiled, ui::ImageType::Filled] {
+                    let label = format!("{:?}", image_type);
+                    ui.selectable_value(&mut image.image_type, image_type, label);
+                }
+            });
+        if image.image_type == ui::ImageType::Filled {
+            ui.add(egui::Slider::new(&mut image.fill_amount, 0.0..=1.0).text("Fill Amount"));
+        }
+        ui.checkbox(&mut image.preserve_aspect, "Preserve Aspect");
+    });
+}
+
+fn render_button(ui: &mut egui::Ui, button: &mut ui::UIButton) {
+    egui::CollapsingHeader::new("🔘 Button").default_open(true).show(ui, |ui| {
+        egui::ComboBox::from_label("Transition")
+            .selected_text(format!("{:?}", button.transition))
+            .show_ui(ui, |ui| {
+                for transition in [
+                    ui::ButtonTransition::None,
+                    ui::ButtonTransition::ColorTint,
+                    ui::ButtonTransition::SpriteSwap,
+                    ui::ButtonTransition::Animation,
+                ] {
+                    let label = format!("{:?}", transition);
+                    ui.selectable_value(&mut button.transition, transition, label);
+                }
+            });
+        for (label, color) in [
+            ("Normal", &mut button.normal_color),
+            ("Highlighted", &mut button.highlighted_color),
+            ("Pressed", &mut button.pressed_color),
+            ("Selected", &mut button.selected_color),
+            ("Disabled", &mut button.disabled_color),
+        ] {
+            ui.horizontal(|ui| {
+                ui.label(label);
+                color_edit(ui, color);
+            });
+        }
+        ui.horizontal(|ui| {
+            ui.label("Fade Duration");
+            ui.add(egui::DragValue::new(&mut button.fade_duration).speed(0.01).range(0.0..=5.0).suffix("s"));
+        });
+        ui.horizontal(|ui| {
+            ui.label("On Click");
+            match &button.on_click {
+                // Engine actions are edited in the prefab file
+                Some(binding) if binding.function().is_none() => {
+                    ui.label(binding.action().describe());
+                }
+                binding => {
+                    let mut on_click = binding.as_ref().and_then(ui::UIEventBinding::function).unwrap_or_default().to_string();
+                    if ui.text_edit_singleline(&mut on_click).changed() {
+                        button.on_click = (!on_click.is_empty()).then(|| ui::UIEventBinding::lua(on_click));
+                    }
+                }
+            }
+        });
+    });
+}
+
+fn render_panel(ui: &mut egui::Ui, panel: &mut ui::UIPanel) {
+    egui::CollapsingHeader::new("📄 Panel").default_open(true).show(ui, |ui| {
+        let mut background = panel.background.clone().unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.label("Background");
+            if ui.text_edit_singleline(&mut background).changed() {
+                panel.background = (!background.is_empty()).then_some(background);
+            }
+        });
+        ui.checkbox(&mut panel.use_nine_slice, "9-Slice");
+        ui.horizontal(|ui| {
+            ui.label("Padding");
+            ui.add(egui::DragValue::new(&mut panel.padding.x).prefix("L "));
+            ui.add(egui::DragValue::new(&mut panel.padding.y).prefix("B "));
+            ui.add(egui::DragValue::new(&mut panel.padding.z).prefix("R "));
+            ui.add(egui::DragValue::new(&mut panel.padding.w).prefix("T "));
+        });
+    });
+}
+
+/// Keep the element inside the device safe area (simulated in Project Settings > Rendering)
+fn render_safe_area(ui: &mut egui::Ui, safe_area: &mut Option<ui::UISafeArea>) {
+    egui::CollapsingHeader::new("📱 Safe Area").default_open(safe_area.is_some()).show(ui, |ui| {
+        let mut enabled = safe_area.is_some();
+        if ui.checkbox(&mut enabled, "Fit to Safe Area").changed() {
+            *safe_area = enabled.then(ui::UISafeArea::default);
+        }
+        if let Some(safe_area) = safe_area {
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut safe_area.left, "Left");
+                ui.checkbox(&mut safe_area.right, "Right");
+                ui.checkbox(&mut safe_area.top, "Top");
+                ui.checkbox(&mut safe_area.bottom, "Bottom");
+            });
+        }
+    });
+}
+
+/// Fade/disable the element together with everything below it
+fn render_canvas_group(ui: &mut egui::Ui, group: &mut Option<ui::UICanvasGroup>) {
+    egui::CollapsingHeader::new("🔲 Canvas Group").default_open(group.is_some()).show(ui, |ui| {
+        let mut enabled = group.is_some();
+        if ui.checkbox(&mut enabled, "Canvas Group").changed() {
+            *group = enabled.then(ui::UICanvasGroup::default);
+        }
+        if let Some(group) = group {
+            ui.horizontal(|ui| {
+                ui.label("Alpha");
+                ui.add(egui::Slider::new(&mut group.alpha, 0.0..=1.0));
+            });
+            ui.checkbox(&mut group.interactable, "Interactable");
+            ui.checkbox(&mut group.blocks_raycasts, "Blocks Raycasts");
+            ui.checkbox(&mut group.ignore_parent_groups, "Ignore Parent Groups");
+        }
+    });
+}
+
+/// Components without an editor here are listed so the element isn't mistaken for bare
+fn render_other_components(ui: &mut egui::Ui, element: &UIPrefabElement) {
+    let others = [
+        ("Slider", element.slider.is_some()),
+        ("Toggle", element.toggle.is_some()),
+        ("Toggle Group", element.toggle_group.is_some()),
+        ("Dropdown", element.dropdown.is_some()),
+        ("Input Field", element.input_field.is_some()),
+        ("Scroll View", element.scroll_view.is_some()),
+        ("Mask", element.mask.is_some()),
+        ("Horizontal Layout", element.horizontal_layout.is_some()),
+        ("Vertical Layout", element.vertical_layout.is_some()),
+        ("Grid Layout", element.grid_layout.is_some()),
+        ("Virtual Input", element.virtual_input.is_some()),
+        ("Tooltip", element.tooltip.is_some()),
+    ];
+    let attached: Vec<&str> = others.iter().filter(|(_, has)| *has).map(|(name, _)| *name).collect();
+    if !attached.is_empty() {
+        ui.label(egui::RichText::new(format!("Also attached: {} (edit in the Prefab Editor)", attached.join(", "))).weak());
+    }
+}
+
+/// Canvas node for the hierarchy panel: every active UI instance as a subtree.
+/// Returns the element path clicked this frame.
+pub fn render_canvas_hierarchy(ui: &mut egui::Ui, ui_manager: &UIManager, selected: Option<&str>) -> Option<String> {
+    let instances = ui_manager.instance_names();
+    if instances.is_empty() {
+        return None;
+    }
+
+    let mut clicked = None;
+    let canvas_id = ui.make_persistent_id("hierarchy_ui_canvas");
+    egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), canvas_id, true)
+        .show_header(ui, |ui| {
+            ui.label("🖼 Canvas");
+        })
+        .body(|ui| {
+            for instance in &instances {
+                if let Some(prefab) = ui_manager.instance(instance) {
+                    draw_ui_element_node(ui, &prefab.root, instance.clone(), instance, selected, &mut clicked);
+                }
+            }
+        });
+    clicked
+}
+
+fn draw_ui_element_node(
+    ui: &mut egui::Ui,
+    element: &UIPrefabElement,
+    path: String,
+    label: &str,
+    selected: Option<&str>,
+    clicked: &mut Option<String>,
+) {
+    let icon = if element.text.is_some() {
+        "📝"
+    } else if element.button.is_some() {
+        "🔘"
+    } else if element.image.is_some() {
+        "🖼"
+    } else {
+        "▭"
+    };
+    let is_selected = selected == Some(path.as_str());
+    let text = format!("{} {}", icon, label);
+
+    if element.children.is_empty() {
+        ui.horizontal(|ui| {
+            ui.add_space(18.0);
+            if ui.selectable_label(is_selected, text).clicked() {
+                *clicked = Some(path.clone());
+            }
+        });
+        return;
+    }
+
+    let id = ui.make_persistent_id(("hierarchy_ui_element", &path));
+    egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false)
+        .show_header(ui, |ui| {
+            if ui.selectable_label(is_selected, text).clicked() {
+                *clicked = Some(path.clone());
+            }
+        })
+        .body(|ui| {
+            for child in &element.children {
+                draw_ui_element_node(ui, child, format!("{}/{}", path, child.name), &child.name, selected, clicked);
+            }
+        });
+}
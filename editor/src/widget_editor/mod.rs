@@ -3,10 +3,12 @@
 //! Visual editor for creating and editing UI prefabs (Unity-style UI editor)
 
 pub mod canvas;
+pub mod preview;
 pub mod properties;
 pub mod state;
 
 pub use canvas::PrefabCanvas;
+pub use preview::PrefabPreview;
 pub use properties::PropertiesPanel;
 pub use state::{PrefabEditorState, EditorTool, DragMode};
 
@@ -14,11 +16,22 @@ use egui;
 use ui::prefab::UIPrefab;
 use std::path::PathBuf;
 
+/// What the center of the editor shows
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PrefabView {
+    /// Editing canvas (move/resize tools)
+    Edit,
+    /// The prefab as the game draws it, at simulated resolutions
+    Preview,
+}
+
 /// Main prefab editor
 pub struct PrefabEditor {
     pub state: PrefabEditorState,
     pub canvas: PrefabCanvas,
+    pub preview: PrefabPreview,
     pub properties: PropertiesPanel,
+    pub view: PrefabView,
 }
 
 impl PrefabEditor {
@@ -26,7 +39,9 @@ impl PrefabEditor {
         Self {
             state: PrefabEditorState::new(),
             canvas: PrefabCanvas::new(),
+            preview: PrefabPreview::new(),
             properties: PropertiesPanel::new(),
+            view: PrefabView::Edit,
         }
     }
     
@@ -161,6 +176,7 @@ impl PrefabEditor {
     pub fn new_prefab(&mut self, name: String) {
         let prefab = UIPrefab {
             name: name.clone(),
+            canvas_scaler: None,
             root: ui::prefab::UIPrefabElement {
                 name: "Root".to_string(),
                 rect_transform: ui::RectTransform::default(),
@@ -171,6 +187,7 @@ impl PrefabEditor {
                 panel: None,
                 slider: None,
                 toggle: None,
+                toggle_group: None,
                 dropdown: None,
                 input_field: None,
                 scroll_view: None,
@@ -178,6 +195,10 @@ impl PrefabEditor {
                 horizontal_layout: None,
                 vertical_layout: None,
                 grid_layout: None,
+                virtual_input: None,
+                tooltip: None,
+                safe_area: None,
+                canvas_group: None,
                 children: vec![],
             },
         };
@@ -202,9 +223,12 @@ impl PrefabEditor {
                 self.properties.render(ui, &mut self.state);
             });
         
-        // Canvas (center)
+        // Canvas or preview (center)
         egui::CentralPanel::default().show_inside(ui, |ui| {
-            self.canvas.render(ui, &mut self.state);
+            match self.view {
+                PrefabView::Edit => self.canvas.render(ui, &mut self.state),
+                PrefabView::Preview => self.preview.render(ui, &mut self.state),
+            }
         });
     }
     
@@ -223,6 +247,12 @@ impl PrefabEditor {
             
             ui.separator();
             
+            // View
+            ui.selectable_value(&mut self.view, PrefabView::Edit, "✏️ Edit");
+            ui.selectable_value(&mut self.view, PrefabView::Preview, "👁 Preview");
+            
+            ui.separator();
+            
             // Tools
             if ui.selectable_label(self.state.current_tool == EditorTool::Select, "🖱️ Select").clicked() {
                 self.state.current_tool = EditorTool::Select;
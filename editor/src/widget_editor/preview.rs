@@ -0,0 +1,286 @@
+//! Prefab Preview
+//!
+//! The prefab drawn by the game's UI renderer at simulated screen sizes, with anchor
+//! and pivot handles (Unity's RectTransform gizmo) for the selected element
+
+use super::state::PrefabEditorState;
+use ui::prefab::UIPrefab;
+use engine::runtime::GameViewResolution;
+use engine::ui_manager::{PreviewElement, UIManager};
+
+const ANCHOR_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 100, 0);
+const PIVOT_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 255, 0);
+const SELECTION_COLOR: egui::Color32 = egui::Color32::from_rgb(0, 150, 255);
+
+/// Length of an anchor handle's sides
+const ANCHOR_HANDLE_SIZE: f32 = 12.0;
+const PIVOT_RADIUS: f32 = 5.0;
+
+/// Anchors this close to 0, 0.5 or 1 snap to it
+const ANCHOR_SNAP: f32 = 0.02;
+
+/// Part of the selected element's gizmo
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Handle {
+    /// Anchor corner: whether its x and y come from anchor_max (else anchor_min)
+    Anchor { max_x: bool, max_y: bool },
+    Pivot,
+}
+
+pub struct PrefabPreview {
+    /// Simulated screen of each viewport (the second one shows in side-by-side mode)
+    pub resolutions: [GameViewResolution; 2],
+    pub side_by_side: bool,
+    renderer: UIManager,
+    /// Handle being dragged and the viewport it's dragged in
+    dragging: Option<(usize, Handle)>,
+}
+
+impl PrefabPreview {
+    pub fn new() -> Self {
+        Self {
+            resolutions: [GameViewResolution::FullHD, GameViewResolution::PhonePortrait],
+            side_by_side: false,
+            renderer: UIManager::new(),
+            dragging: None,
+        }
+    }
+
+    pub fn render(&mut self, ui: &mut egui::Ui, state: &mut PrefabEditorState) {
+        ui.horizontal(|ui| {
+            Self::resolution_combo(ui, "prefab_preview_resolution_0", &mut self.resolutions[0]);
+            ui.checkbox(&mut self.side_by_side, "Side by side");
+            if self.side_by_side {
+                Self::resolution_combo(ui, "prefab_preview_resolution_1", &mut self.resolutions[1]);
+            }
+        });
+
+        let available = ui.available_rect_before_wrap();
+        ui.painter().rect_filled(available, 0.0, egui::Color32::from_rgb(40, 40, 45));
+
+        // Drawn from a copy: dragging a handle edits the prefab while it's shown
+        let Some(prefab) = state.current_prefab.clone() else {
+            ui.label("No prefab loaded");
+            return;
+        };
+
+        let areas = if self.side_by_side {
+            let middle = available.center().x;
+            vec![
+                egui::Rect::from_min_max(available.min, egui::pos2(middle, available.max.y)),
+                egui::Rect::from_min_max(egui::pos2(middle, available.min.y), available.max),
+            ]
+        } else {
+            vec![available]
+        };
+        for (index, area) in areas.into_iter().enumerate() {
+            self.render_viewport(ui, index, area, &prefab, state);
+        }
+    }
+
+    fn resolution_combo(ui: &mut egui::Ui, id: &str, resolution: &mut GameViewResolution) {
+        egui::ComboBox::from_id_salt(id)
+            .selected_text(resolution.get_name())
+            .show_ui(ui, |ui| {
+                let mut category = String::new();
+                for preset in GameViewResolution::PRESETS {
+                    if preset.get_category() != category {
+                        category = preset.get_category().to_string();
+                        ui.label(egui::RichText::new(&category).small().color(egui::Color32::GRAY));
+                    }
+                    ui.selectable_value(resolution, preset, preset.get_name());
+                }
+            });
+    }
+
+    fn render_viewport(&mut self, ui: &mut egui::Ui, index: usize, area: egui::Rect, prefab: &UIPrefab, state: &mut PrefabEditorState) {
+        let resolution = self.resolutions[index];
+        let (width, height) = resolution.get_size();
+        // Room for the resolution label above the screen
+        let area = egui::Rect::from_min_max(area.min + egui::vec2(12.0, 28.0), area.max - egui::vec2(12.0, 12.0));
+        let rect = fit_rect(area, width as f32 / height as f32);
+
+        ui.painter().rect_filled(rect, 0.0, egui::Color32::from_rgb(30, 30, 35));
+        let elements = self.renderer.render_preview(ui, prefab, (width, height), rect);
+        ui.painter().rect_stroke(
+            rect,
+            0.0,
+            egui::Stroke::new(2.0, egui::Color32::from_rgb(100, 100, 100)),
+            egui::epaint::StrokeKind::Outside,
+        );
+        ui.painter().text(
+            rect.left_top() - egui::vec2(0.0, 6.0),
+            egui::Align2::LEFT_BOTTOM,
+            resolution.get_name(),
+            egui::FontId::proportional(12.0),
+            egui::Color32::from_gray(150),
+        );
+
+        let selected = state.selected_element.as_ref()
+            .and_then(|name| elements.iter().find(|element| &element.name == name));
+        if let Some(element) = selected {
+            let active = self.dragging.filter(|(viewport, _)| *viewport == index).map(|(_, handle)| handle);
+            draw_gizmo(&ui.painter_at(rect.expand(ANCHOR_HANDLE_SIZE)), element, active);
+        }
+
+        let response = ui.interact(rect, ui.id().with(("prefab_preview", index)), egui::Sense::click_and_drag());
+        if response.drag_stopped() {
+            self.dragging = None;
+        }
+        let Some(pointer) = response.interact_pointer_pos() else {
+            return;
+        };
+        let on_handle = selected.and_then(|element| handle_at(element, pointer));
+
+        if response.drag_started() {
+            self.dragging = on_handle.map(|handle| (index, handle));
+        }
+        if response.dragged() {
+            if let (Some((viewport, handle)), Some(element)) = (self.dragging, selected) {
+                if viewport == index {
+                    drag_handle(state, element, handle, pointer);
+                }
+            }
+        }
+        if response.clicked() && on_handle.is_none() {
+            // Topmost element under the pointer (elements come in draw order)
+            match elements.iter().rev().find(|element| element.rect.contains(pointer)) {
+                Some(element) => state.select_element(element.name.clone()),
+                None => state.deselect(),
+            }
+        }
+    }
+}
+
+impl Default for PrefabPreview {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Largest rect of `aspect` (width / height) centered in `area`
+fn fit_rect(area: egui::Rect, aspect: f32) -> egui::Rect {
+    let size = if area.width() / area.height().max(1.0) > aspect {
+        egui::vec2(area.height() * aspect, area.height())
+    } else {
+        egui::vec2(area.width(), area.width() / aspect)
+    };
+    egui::Rect::from_center_size(area.center(), size)
+}
+
+/// Screen position of a normalized (y-up) point of `rect`
+fn normalized_point(rect: egui::Rect, x: f32, y: f32) -> egui::Pos2 {
+    egui::pos2(rect.min.x + rect.width() * x, rect.max.y - rect.height() * y)
+}
+
+/// Anchor handles, each as its corner and the screen direction it points away from the anchors
+fn anchor_handles(element: &PreviewElement) -> [(Handle, egui::Pos2, egui::Vec2); 4] {
+    let transform = &element.transform;
+    [(false, false), (true, false), (false, true), (true, true)].map(|(max_x, max_y)| {
+        let x = if max_x { transform.anchor_max.x } else { transform.anchor_min.x };
+        let y = if max_y { transform.anchor_max.y } else { transform.anchor_min.y };
+        let outward = egui::vec2(if max_x { 1.0 } else { -1.0 }, if max_y { -1.0 } else { 1.0 });
+        (Handle::Anchor { max_x, max_y }, normalized_point(element.parent_rect, x, y), outward)
+    })
+}
+
+fn pivot_point(element: &PreviewElement) -> egui::Pos2 {
+    normalized_point(element.rect, element.transform.pivot.x, element.transform.pivot.y)
+}
+
+/// Handle under `pointer`; the pivot wins over anchors sitting on it
+fn handle_at(element: &PreviewElement, pointer: egui::Pos2) -> Option<Handle> {
+    if pointer.distance(pivot_point(element)) < PIVOT_RADIUS + 3.0 {
+        return Some(Handle::Pivot);
+    }
+    anchor_handles(element).into_iter()
+        .find(|(_, corner, outward)| pointer.distance(*corner + *outward * ANCHOR_HANDLE_SIZE * 0.4) < ANCHOR_HANDLE_SIZE * 0.6)
+        .map(|(handle, _, _)| handle)
+}
+
+fn draw_gizmo(painter: &egui::Painter, element: &PreviewElement, active: Option<Handle>) {
+    let thin = |color: egui::Color32| egui::Stroke::new(1.0, color);
+
+    // What the anchors are relative to, and the area they span
+    painter.rect_stroke(element.parent_rect, 0.0, thin(ANCHOR_COLOR.gamma_multiply(0.4)), egui::epaint::StrokeKind::Middle);
+    let transform = &element.transform;
+    if transform.anchor_min != transform.anchor_max {
+        let anchor_rect = egui::Rect::from_two_pos(
+            normalized_point(element.parent_rect, transform.anchor_min.x, transform.anchor_min.y),
+            normalized_point(element.parent_rect, transform.anchor_max.x, transform.anchor_max.y),
+        );
+        painter.rect_stroke(anchor_rect, 0.0, thin(ANCHOR_COLOR), egui::epaint::StrokeKind::Middle);
+    }
+
+    painter.rect_stroke(element.rect, 0.0, egui::Stroke::new(2.0, SELECTION_COLOR), egui::epaint::StrokeKind::Outside);
+
+    for (handle, corner, outward) in anchor_handles(element) {
+        let fill = if active == Some(handle) { egui::Color32::WHITE } else { ANCHOR_COLOR };
+        let points = vec![
+            corner,
+            corner + egui::vec2(outward.x * ANCHOR_HANDLE_SIZE, outward.y * ANCHOR_HANDLE_SIZE * 0.45),
+            corner + egui::vec2(outward.x * ANCHOR_HANDLE_SIZE * 0.45, outward.y * ANCHOR_HANDLE_SIZE),
+        ];
+        painter.add(egui::Shape::convex_polygon(points, fill, thin(egui::Color32::BLACK)));
+    }
+
+    let pivot = pivot_point(element);
+    let fill = if active == Some(Handle::Pivot) { egui::Color32::WHITE } else { PIVOT_COLOR };
+    painter.circle(pivot, PIVOT_RADIUS, egui::Color32::TRANSPARENT, egui::Stroke::new(2.0, fill));
+    painter.circle_filled(pivot, 1.5, fill);
+}
+
+fn snap_anchor(value: f32) -> f32 {
+    let snapped = (value * 2.0).round() / 2.0;
+    if (value - snapped).abs() < ANCHOR_SNAP { snapped } else { value }
+}
+
+/// Move `handle` of the selected element to `pointer`. The element keeps its rect: the
+/// offsets change with the anchors or pivot, like dragging them in Unity.
+fn drag_handle(state: &mut PrefabEditorState, element: &PreviewElement, handle: Handle, pointer: egui::Pos2) {
+    let parent = element.parent_rect;
+    if parent.width() <= 0.0 || parent.height() <= 0.0 || element.scale <= 0.0 {
+        return;
+    }
+    let Some(selected) = state.get_selected_element_mut() else {
+        return;
+    };
+    let transform = &mut selected.rect_transform;
+    // Parent size in reference units, and the element's size and pivot point with the
+    // current anchors (calculate_rect: anchor rect center + anchored position)
+    let parent_size = ui::Vec2::new(parent.width(), parent.height()) / element.scale;
+    let size = (transform.anchor_max - transform.anchor_min) * parent_size + transform.size_delta;
+    let pivot_point = (transform.anchor_min + transform.anchor_max) * 0.5 * parent_size + transform.anchored_position;
+
+    match handle {
+        Handle::Anchor { max_x, max_y } => {
+            let x = snap_anchor(((pointer.x - parent.min.x) / parent.width()).clamp(0.0, 1.0));
+            let y = snap_anchor(((parent.max.y - pointer.y) / parent.height()).clamp(0.0, 1.0));
+            if max_x {
+                transform.anchor_max.x = x.max(transform.anchor_min.x);
+            } else {
+                transform.anchor_min.x = x.min(transform.anchor_max.x);
+            }
+            if max_y {
+                transform.anchor_max.y = y.max(transform.anchor_min.y);
+            } else {
+                transform.anchor_min.y = y.min(transform.anchor_max.y);
+            }
+            transform.size_delta = size - (transform.anchor_max - transform.anchor_min) * parent_size;
+            transform.anchored_position = pivot_point - (transform.anchor_min + transform.anchor_max) * 0.5 * parent_size;
+        }
+        Handle::Pivot => {
+            let rect = element.rect;
+            if rect.width() <= 0.0 || rect.height() <= 0.0 {
+                return;
+            }
+            let pivot = ui::Vec2::new(
+                ((pointer.x - rect.min.x) / rect.width()).clamp(0.0, 1.0),
+                ((rect.max.y - pointer.y) / rect.height()).clamp(0.0, 1.0),
+            );
+            transform.anchored_position += (pivot - transform.pivot) * size;
+            transform.pivot = pivot;
+        }
+    }
+    state.mark_modified();
+}
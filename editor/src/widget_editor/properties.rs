@@ -85,6 +85,14 @@ impl PropertiesPanel {
             });
         }
         
+        if has_prefab {
+            ui.collapsing("Canvas Scaler", |ui| {
+                if Self::render_canvas_scaler(ui, state) {
+                    state.mark_modified();
+                }
+            });
+        }
+        
         ui.separator();
         ui.heading("Properties");
         ui.separator();
@@ -92,51 +100,22 @@ impl PropertiesPanel {
         if let Some(element) = state.get_selected_element() {
             ui.label(format!("Name: {}", element.name));
             ui.separator();
-            
-            // RectTransform properties
-            ui.heading("RectTransform");
-            
-            // Anchored Position
-            ui.label("Anchored Position:");
-            ui.horizontal(|ui| {
-                ui.label(format!("X: {:.1}", element.rect_transform.anchored_position.x));
-                ui.label(format!("Y: {:.1}", element.rect_transform.anchored_position.y));
-            });
-            
-            // Size
-            let size = element.rect_transform.get_size();
-            ui.label("Size:");
-            ui.horizontal(|ui| {
-                ui.label(format!("W: {:.1}", size.x));
-                ui.label(format!("H: {:.1}", size.y));
-            });
-            
-            // Anchor Min/Max
-            ui.label("Anchor Min:");
-            ui.horizontal(|ui| {
-                ui.label(format!("X: {:.2}", element.rect_transform.anchor_min.x));
-                ui.label(format!("Y: {:.2}", element.rect_transform.anchor_min.y));
-            });
-            
-            ui.label("Anchor Max:");
-            ui.horizontal(|ui| {
-                ui.label(format!("X: {:.2}", element.rect_transform.anchor_max.x));
-                ui.label(format!("Y: {:.2}", element.rect_transform.anchor_max.y));
-            });
-            
-            // Pivot
-            ui.label("Pivot:");
-            ui.horizontal(|ui| {
-                ui.label(format!("X: {:.2}", element.rect_transform.pivot.x));
-                ui.label(format!("Y: {:.2}", element.rect_transform.pivot.y));
-            });
-            
+        }
+
+        // Edited in place: the canvas and preview show the new values the same frame
+        if Self::render_rect_transform(ui, state) {
+            state.mark_modified();
+        }
+
+        if let Some(element) = state.get_selected_element() {
             ui.separator();
             
             // UIElement properties
             ui.heading("UIElement");
             ui.label(format!("Raycast Target: {}", element.ui_element.raycast_target));
             ui.label(format!("Interactable: {}", element.ui_element.interactable));
+            ui.label(format!("Draggable: {}", element.ui_element.draggable));
+            ui.label(format!("Pass Through: {}", element.ui_element.pass_through));
             ui.label(format!("Alpha: {:.2}", element.ui_element.alpha));
             
             ui.separator();
@@ -145,7 +124,11 @@ impl PropertiesPanel {
             if let Some(text) = &element.text {
                 ui.heading("Text");
                 ui.label(format!("Text: {}", text.text));
+                ui.label(format!("Font: {}", text.font));
                 ui.label(format!("Font Size: {}", text.font_size));
+                if let Some(key) = &text.localization_key {
+                    ui.label(format!("Localization Key: {}", key));
+                }
             }
             
             if element.button.is_some() {
@@ -172,21 +155,69 @@ impl PropertiesPanel {
                 ui.heading("Toggle");
                 ui.label("Toggle component attached");
             }
+
+            if let Some(group) = &element.toggle_group {
+                ui.heading("Toggle Group");
+                ui.label(format!("Id: {}", group.id));
+                ui.label(if group.allow_switch_off { "Allow switch off" } else { "One option always on" });
+            }
             
             if element.dropdown.is_some() {
                 ui.heading("Dropdown");
                 ui.label("Dropdown component attached");
             }
             
-            if element.input_field.is_some() {
+            if let Some(field) = &element.input_field {
                 ui.heading("Input Field");
-                ui.label("Input Field component attached");
+                ui.label(format!("Text: {}", field.text));
+                if !field.placeholder_text.is_empty() {
+                    ui.label(format!("Placeholder: {}", field.placeholder_text));
+                }
+                ui.label(format!("Validation: {:?}, limit: {}", field.character_validation, field.character_limit));
             }
             
             if element.scroll_view.is_some() {
                 ui.heading("Scroll View");
                 ui.label("Scroll View component attached");
             }
+
+            if let Some(virtual_input) = &element.virtual_input {
+                ui.heading("Virtual Input");
+                match virtual_input {
+                    ui::UIVirtualInput::Joystick { radius, dead_zone, floating } => {
+                        ui.label(format!("Joystick (radius {:.0}, dead zone {:.2})", radius, dead_zone));
+                        ui.label(if *floating { "Origin: floating" } else { "Origin: fixed" });
+                    }
+                    ui::UIVirtualInput::Button { action } => {
+                        ui.label(format!("Button → action \"{}\"", action));
+                    }
+                }
+            }
+
+            if let Some(tooltip) = &element.tooltip {
+                ui.heading("Tooltip");
+                ui.label(format!("Text: {}", tooltip.text));
+                if let Some(prefab) = &tooltip.prefab {
+                    ui.label(format!("Prefab: {}", prefab));
+                }
+                ui.label(format!("Delay: {:.2}s, placement: {:?}", tooltip.show_delay, tooltip.placement));
+            }
+
+            if let Some(safe_area) = &element.safe_area {
+                ui.heading("Safe Area");
+                let sides: Vec<&str> = [("left", safe_area.left), ("right", safe_area.right), ("top", safe_area.top), ("bottom", safe_area.bottom)]
+                    .iter().filter(|(_, on)| *on).map(|(side, _)| *side).collect();
+                ui.label(format!("Inset sides: {}", sides.join(", ")));
+            }
+
+            if let Some(group) = &element.canvas_group {
+                ui.heading("Canvas Group");
+                ui.label(format!("Alpha: {:.2}", group.alpha));
+                ui.label(format!("Interactable: {}, blocks raycasts: {}", group.interactable, group.blocks_raycasts));
+                if group.ignore_parent_groups {
+                    ui.label("Ignores parent groups");
+                }
+            }
             
             // Layout components
             if element.horizontal_layout.is_some() {
@@ -210,6 +241,95 @@ impl PropertiesPanel {
         }
     }
     
+    /// The selected element's RectTransform; returns true when changed
+    fn render_rect_transform(ui: &mut egui::Ui, state: &mut PrefabEditorState) -> bool {
+        let Some(element) = state.get_selected_element_mut() else {
+            return false;
+        };
+        let transform = &mut element.rect_transform;
+        let mut changed = false;
+
+        ui.heading("RectTransform");
+        egui::Grid::new("prefab_rect_transform").num_columns(3).show(ui, |ui| {
+            let mut row = |ui: &mut egui::Ui, label: &str, value: &mut ui::Vec2, speed: f64, range: Option<std::ops::RangeInclusive<f32>>| {
+                ui.label(label);
+                for component in [&mut value.x, &mut value.y] {
+                    let mut drag = egui::DragValue::new(component).speed(speed);
+                    if let Some(range) = &range {
+                        drag = drag.range(range.clone());
+                    }
+                    changed |= ui.add(drag).changed();
+                }
+                ui.end_row();
+            };
+            row(ui, "Anchored Position", &mut transform.anchored_position, 1.0, None);
+            row(ui, "Size Delta", &mut transform.size_delta, 1.0, None);
+            row(ui, "Anchor Min", &mut transform.anchor_min, 0.01, Some(0.0..=1.0));
+            row(ui, "Anchor Max", &mut transform.anchor_max, 0.01, Some(0.0..=1.0));
+            row(ui, "Pivot", &mut transform.pivot, 0.01, Some(0.0..=1.0));
+            row(ui, "Scale", &mut transform.scale, 0.01, None);
+        });
+        ui.horizontal(|ui| {
+            ui.label("Rotation");
+            changed |= ui.add(egui::DragValue::new(&mut transform.rotation).speed(1.0).suffix("°")).changed();
+        });
+
+        // Typing a min past its max (or the other way) keeps the pair ordered
+        transform.anchor_max.x = transform.anchor_max.x.max(transform.anchor_min.x);
+        transform.anchor_max.y = transform.anchor_max.y.max(transform.anchor_min.y);
+        changed
+    }
+
+    /// How the prefab scales with the window; returns true when changed
+    fn render_canvas_scaler(ui: &mut egui::Ui, state: &mut PrefabEditorState) -> bool {
+        let Some(prefab) = &mut state.current_prefab else {
+            return false;
+        };
+        let mut changed = false;
+
+        let mut enabled = prefab.canvas_scaler.is_some();
+        if ui.checkbox(&mut enabled, "Scale with window").changed() {
+            prefab.canvas_scaler = enabled.then(|| ui::CanvasScaler::scale_with_screen_size(1920.0, 1080.0));
+            changed = true;
+        }
+        let Some(scaler) = &mut prefab.canvas_scaler else {
+            ui.label("One unit per point (no scaling)");
+            return changed;
+        };
+
+        egui::ComboBox::from_id_salt("canvas_scale_mode")
+            .selected_text(format!("{:?}", scaler.mode))
+            .show_ui(ui, |ui| {
+                for mode in [ui::ScaleMode::ConstantPixelSize, ui::ScaleMode::ScaleWithScreenSize, ui::ScaleMode::ConstantPhysicalSize] {
+                    let label = format!("{:?}", mode);
+                    changed |= ui.selectable_value(&mut scaler.mode, mode, label).changed();
+                }
+            });
+
+        match scaler.mode {
+            ui::ScaleMode::ScaleWithScreenSize => {
+                ui.horizontal(|ui| {
+                    ui.label("Reference:");
+                    changed |= ui.add(egui::DragValue::new(&mut scaler.reference_resolution.0).range(1.0..=8192.0)).changed();
+                    ui.label("x");
+                    changed |= ui.add(egui::DragValue::new(&mut scaler.reference_resolution.1).range(1.0..=8192.0)).changed();
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Match:");
+                    changed |= ui.add(egui::Slider::new(&mut scaler.match_width_or_height, 0.0..=1.0).text("width ↔ height")).changed();
+                });
+            }
+            ui::ScaleMode::ConstantPhysicalSize => {
+                ui.horizontal(|ui| {
+                    ui.label("Reference DPI:");
+                    changed |= ui.add(egui::DragValue::new(&mut scaler.reference_dpi).range(1.0..=600.0)).changed();
+                });
+            }
+            ui::ScaleMode::ConstantPixelSize => {}
+        }
+        changed
+    }
+
     fn render_hierarchy_tree(&self, ui: &mut egui::Ui, element: &ui::prefab::UIPrefabElement, state: &mut PrefabEditorState, depth: usize) {
         let indent = depth as f32 * 20.0;
         
@@ -0,0 +1,58 @@
+// Parallel system execution: animated sprite advancement and transform propagation
+// for 10k animated entities, on one thread vs. the rayon pool.
+//
+// Run with: cargo bench -p engine --bench parallel_systems
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use ecs::{AnimatedSprite, SpriteSheet, Transform, World};
+use engine::runtime::animation_system::AnimatedSpriteSystem;
+use engine::runtime::transform_system::GlobalTransformSystem;
+use engine_core::executor::SystemExecutor;
+
+const ENTITY_COUNT: usize = 10_000;
+
+fn animated_world() -> World {
+    let mut world = World::new();
+    for i in 0..ENTITY_COUNT {
+        let entity = world.spawn();
+        world.transforms.insert(entity, Transform {
+            position: [(i % 100) as f32, (i / 100) as f32, 0.0],
+            ..Default::default()
+        });
+        world.sprite_sheets.insert(entity, SpriteSheet::from_grid("hero.png", "hero", 256, 32, 32, 32, 0, 0));
+        // Staggered frame durations so entities don't all step on the same frame
+        world.animated_sprites.insert(entity, AnimatedSprite::new("hero", 0.05 + (i % 7) as f32 * 0.01));
+    }
+    world
+}
+
+fn executor(parallel: bool) -> SystemExecutor<World> {
+    let mut executor = SystemExecutor::new();
+    executor.add_system(Box::new(AnimatedSpriteSystem::new()));
+    executor.add_system(Box::new(GlobalTransformSystem));
+    executor.set_parallel(parallel);
+    executor
+}
+
+fn bench_parallel_systems(c: &mut Criterion) {
+    let mut group = c.benchmark_group("animated_entities_10k");
+    let single_thread = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+
+    group.bench_function("single_threaded", |b| {
+        let mut world = animated_world();
+        let mut executor = executor(false);
+        // Per-entity work inside systems uses whichever pool is installed
+        single_thread.install(|| b.iter(|| executor.run(black_box(&mut world), 1.0 / 60.0)));
+    });
+
+    group.bench_function("parallel", |b| {
+        let mut world = animated_world();
+        let mut executor = executor(true);
+        b.iter(|| executor.run(black_box(&mut world), 1.0 / 60.0));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_systems);
+criterion_main!(benches);
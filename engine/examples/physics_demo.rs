@@ -38,7 +38,7 @@ fn main() {
         sprite.texture_id = "player".to_string();
         sprite.width = 40.0;
         sprite.height = 40.0;
-        sprite.color = [0.2, 0.6, 1.0, 1.0];
+        sprite.color = ecs::Color::rgba(0.2, 0.6, 1.0, 1.0);
     }
 
     // ตั้งค่า Collider
@@ -67,7 +67,7 @@ fn main() {
     if let Some(sprite) = world.sprites.get_mut(&ground) {
         sprite.width = 200.0;
         sprite.height = 20.0;
-        sprite.color = [0.3, 0.3, 0.3, 1.0];
+        sprite.color = ecs::Color::rgba(0.3, 0.3, 0.3, 1.0);
     }
     if let Some(collider) = world.colliders.get_mut(&ground) {
         collider.width = 200.0;
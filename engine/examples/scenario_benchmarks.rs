@@ -0,0 +1,48 @@
+//! Scenario Benchmarks
+//!
+//! Full-frame simulations (platformer_1k, ui_heavy, script_heavy) run headless,
+//! with per-system timings. Results can be written to JSON and compared with a
+//! previous run, e.g. in CI:
+//!
+//! cargo run --release --example scenario_benchmarks -- --output bench.json --baseline previous.json
+//!
+//! Options:
+//!   --seconds <n>      simulated seconds per scenario (default 60)
+//!   --output <file>    write the results as JSON
+//!   --baseline <file>  print the change against an earlier JSON run
+
+use ecs::BenchmarkSuite;
+use engine::benchmark_scenarios::ScenarioRunner;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut runner = ScenarioRunner::default();
+    let mut output = None;
+    let mut baseline = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seconds" => runner.simulated_seconds = args.next().ok_or("--seconds needs a value")?.parse()?,
+            "--output" => output = Some(args.next().ok_or("--output needs a file")?),
+            "--baseline" => baseline = Some(args.next().ok_or("--baseline needs a file")?),
+            other => return Err(format!("Unknown argument: {}", other).into()),
+        }
+    }
+
+    println!("Running scenario benchmarks ({}s simulated each)...\n", runner.simulated_seconds);
+    let suite = runner.run_all();
+    println!("{}", suite.generate_report());
+
+    if let Some(path) = baseline {
+        let previous = BenchmarkSuite::load_from_file(&path)?;
+        println!("Compared with {}:", path);
+        println!("{}", suite.compare(&previous));
+    }
+
+    if let Some(path) = output {
+        suite.save_to_file(&path)?;
+        println!("Results saved to {}", path);
+    }
+
+    Ok(())
+}
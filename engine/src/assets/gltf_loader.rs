@@ -173,7 +173,8 @@ impl GltfLoader {
              let sampler = device.create_sampler(&wgpu::SamplerDescriptor::default());
              
              pbr_material.albedo_texture = Some(Arc::new(Texture {
-                 texture, view, sampler, bind_group: None, width: 1, height: 1
+                 texture, view, sampler, bind_group: None, width: 1, height: 1,
+                 options: render::TextureOptions::default(),
              }));
         }
 
@@ -0,0 +1,124 @@
+use std::any::Any;
+use std::marker::PhantomData;
+use std::sync::{Arc, RwLock};
+
+/// Load progress of an asset requested through the AssetServer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LoadState {
+    /// Queued, waiting for a loader thread (or never requested)
+    NotLoaded,
+    /// Being read / decoded on a loader thread
+    Loading,
+    Loaded,
+    Failed(String),
+}
+
+impl LoadState {
+    /// Loaded or failed - nothing more will happen without a new request
+    pub fn is_finished(&self) -> bool {
+        matches!(self, LoadState::Loaded | LoadState::Failed(_))
+    }
+}
+
+pub(crate) enum SlotState {
+    Queued,
+    Loading,
+    Loaded(Arc<dyn Any + Send + Sync>),
+    Failed(String),
+}
+
+/// Shared storage for one asset. Handles keep it alive; the AssetManager only holds a weak ref.
+pub(crate) struct AssetSlot {
+    pub(crate) path: String,
+    pub(crate) state: RwLock<SlotState>,
+}
+
+impl AssetSlot {
+    pub(crate) fn new(path: String) -> Self {
+        Self { path, state: RwLock::new(SlotState::Queued) }
+    }
+
+    pub(crate) fn set(&self, state: SlotState) {
+        *self.state.write().unwrap() = state;
+    }
+
+    fn load_state(&self) -> LoadState {
+        match &*self.state.read().unwrap() {
+            SlotState::Queued => LoadState::NotLoaded,
+            SlotState::Loading => LoadState::Loading,
+            SlotState::Loaded(_) => LoadState::Loaded,
+            SlotState::Failed(e) => LoadState::Failed(e.clone()),
+        }
+    }
+}
+
+/// Typed reference to an asset that may still be loading.
+/// Returned immediately by `AssetServer::load`; the asset is freed once every handle is dropped.
+pub struct Handle<T> {
+    slot: Arc<AssetSlot>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self { slot: self.slot.clone(), _marker: PhantomData }
+    }
+}
+
+impl<T> std::fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Handle").field("path", &self.slot.path).field("state", &self.slot.load_state()).finish()
+    }
+}
+
+impl<T: Any + Send + Sync> Handle<T> {
+    pub(crate) fn new(slot: Arc<AssetSlot>) -> Self {
+        Self { slot, _marker: PhantomData }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.slot.path
+    }
+
+    pub fn state(&self) -> LoadState {
+        self.slot.load_state()
+    }
+
+    pub fn is_loaded(&self) -> bool {
+        matches!(&*self.slot.state.read().unwrap(), SlotState::Loaded(_))
+    }
+
+    /// The asset, once loaded
+    pub fn get(&self) -> Option<Arc<T>> {
+        match &*self.slot.state.read().unwrap() {
+            SlotState::Loaded(asset) => asset.clone().downcast::<T>().ok(),
+            _ => None,
+        }
+    }
+
+    pub fn untyped(&self) -> UntypedHandle {
+        UntypedHandle { slot: self.slot.clone() }
+    }
+}
+
+/// Handle with the asset type erased - enough to track load state (preload lists).
+#[derive(Clone)]
+pub struct UntypedHandle {
+    slot: Arc<AssetSlot>,
+}
+
+impl UntypedHandle {
+    pub fn path(&self) -> &str {
+        &self.slot.path
+    }
+
+    pub fn state(&self) -> LoadState {
+        self.slot.load_state()
+    }
+}
+
+impl std::fmt::Debug for UntypedHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UntypedHandle").field("path", &self.slot.path).field("state", &self.state()).finish()
+    }
+}
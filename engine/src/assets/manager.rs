@@ -1,7 +1,8 @@
-use std::any::Any;
+use std::any::{Any, TypeId};
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, RwLock, Weak};
 use crate::assets::core::AssetId;
+use crate::assets::handle::AssetSlot;
 
 /// A lightweight handle to an asset.
 /// Takes ownership of the underlying Arc, keeping the asset alive.
@@ -41,16 +42,53 @@ type AnyAsset = Arc<dyn Any + Send + Sync>;
 pub struct AssetManager {
     /// Cache of loaded assets, type-erased.
     cache: RwLock<HashMap<AssetId, AnyAsset>>,
-    
-    // In a real implementation, we'd need a way to map ID -> Path (or a loader trait).
-    // For now, we'll keep it simple or assume we can register loaders.
+
+    /// Assets loaded by path through the AssetServer, keyed by (path, type).
+    /// Reference-counted: entries are weak, so an asset is freed when its last Handle drops.
+    slots: RwLock<HashMap<(String, TypeId), Weak<AssetSlot>>>,
 }
 
 impl AssetManager {
     pub fn new() -> Self {
         Self {
             cache: RwLock::new(HashMap::new()),
+            slots: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Existing live slot for `path`, or a new queued one (second value is true when created).
+    pub(crate) fn slot(&self, path: &str, type_id: TypeId) -> (Arc<AssetSlot>, bool) {
+        let key = (path.to_string(), type_id);
+        if let Some(slot) = self.slots.read().unwrap().get(&key).and_then(Weak::upgrade) {
+            return (slot, false);
+        }
+
+        let mut slots = self.slots.write().unwrap();
+        // Another thread may have created it between the locks
+        if let Some(slot) = slots.get(&key).and_then(Weak::upgrade) {
+            return (slot, false);
         }
+        let slot = Arc::new(AssetSlot::new(path.to_string()));
+        slots.insert(key, Arc::downgrade(&slot));
+        (slot, true)
+    }
+
+    /// Live slot for `path`, if any handle to it still exists
+    pub(crate) fn existing_slot(&self, path: &str, type_id: TypeId) -> Option<Arc<AssetSlot>> {
+        self.slots.read().unwrap().get(&(path.to_string(), type_id)).and_then(Weak::upgrade)
+    }
+
+    /// Number of path-loaded assets that still have handles
+    pub fn live_asset_count(&self) -> usize {
+        self.slots.read().unwrap().values().filter(|slot| slot.strong_count() > 0).count()
+    }
+
+    /// Forget entries whose handles were all dropped. Returns how many were removed.
+    pub fn purge_unused(&self) -> usize {
+        let mut slots = self.slots.write().unwrap();
+        let before = slots.len();
+        slots.retain(|_, slot| slot.strong_count() > 0);
+        before - slots.len()
     }
 
     /// Checks if an asset is already loaded.
@@ -1,6 +1,8 @@
 pub mod core;
 pub mod metadata;
 pub mod manager;
+pub mod handle;
+pub mod server;
 
 pub mod gltf_loader;
 pub mod model_manager;
@@ -10,6 +12,8 @@ pub mod xsg_loader;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod native_loader;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod pack;
 
 #[cfg(target_arch = "wasm32")]
 pub mod web_loader;
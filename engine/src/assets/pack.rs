@@ -0,0 +1,141 @@
+//! Asset Pack
+//!
+//! Single-file archive used by exported games (`data.pak`).
+//! Layout: magic, index length (u64 LE), bincode index, then the file blobs.
+//! Paths are project-relative with forward slashes, so the same strings work
+//! with the directory-based NativeAssetLoader.
+
+use engine_core::assets::AssetLoader;
+use anyhow::{Context, Result, bail};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+const PACK_MAGIC: &[u8; 4] = b"XPAK";
+const PACK_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PackEntry {
+    path: String,
+    offset: u64,
+    size: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PackIndex {
+    version: u32,
+    entries: Vec<PackEntry>,
+}
+
+/// Normalize a relative asset path to the form stored in the index
+pub fn normalize_pack_path(path: &str) -> String {
+    path.replace('\\', "/").trim_start_matches("./").trim_start_matches('/').to_string()
+}
+
+/// Write `files` (pack path, source file) into a pack at `output`. Returns the total data size.
+pub fn write_pack(output: &Path, files: &[(String, PathBuf)]) -> Result<u64> {
+    let mut entries = Vec::with_capacity(files.len());
+    let mut offset = 0u64;
+    for (pack_path, source) in files {
+        let size = std::fs::metadata(source)
+            .with_context(|| format!("Failed to read {:?}", source))?
+            .len();
+        entries.push(PackEntry { path: normalize_pack_path(pack_path), offset, size });
+        offset += size;
+    }
+
+    let index = bincode::serialize(&PackIndex { version: PACK_VERSION, entries })?;
+
+    let mut writer = BufWriter::new(
+        File::create(output).with_context(|| format!("Failed to create pack {:?}", output))?,
+    );
+    writer.write_all(PACK_MAGIC)?;
+    writer.write_all(&(index.len() as u64).to_le_bytes())?;
+    writer.write_all(&index)?;
+    for (_, source) in files {
+        let mut file = File::open(source).with_context(|| format!("Failed to open {:?}", source))?;
+        std::io::copy(&mut file, &mut writer)?;
+    }
+    writer.flush()?;
+    Ok(offset)
+}
+
+/// AssetLoader reading from a pack file. The index is kept in memory;
+/// file data is read on demand.
+pub struct PackAssetLoader {
+    pack_path: PathBuf,
+    data_start: u64,
+    entries: HashMap<String, PackEntry>,
+}
+
+impl PackAssetLoader {
+    pub fn open<P: AsRef<Path>>(pack_path: P) -> Result<Self> {
+        let pack_path = pack_path.as_ref().to_path_buf();
+        let mut file = File::open(&pack_path).with_context(|| format!("Failed to open pack {:?}", pack_path))?;
+
+        let mut magic = [0u8; 4];
+        file.read_exact(&mut magic)?;
+        if &magic != PACK_MAGIC {
+            bail!("{:?} is not an asset pack", pack_path);
+        }
+
+        let mut len = [0u8; 8];
+        file.read_exact(&mut len)?;
+        let index_len = u64::from_le_bytes(len);
+        let mut index_bytes = vec![0u8; index_len as usize];
+        file.read_exact(&mut index_bytes)?;
+        let index: PackIndex = bincode::deserialize(&index_bytes)?;
+        if index.version != PACK_VERSION {
+            bail!("Unsupported asset pack version {}", index.version);
+        }
+
+        Ok(Self {
+            pack_path,
+            data_start: (PACK_MAGIC.len() + 8) as u64 + index_len,
+            entries: index.entries.into_iter().map(|e| (e.path.clone(), e)).collect(),
+        })
+    }
+
+    pub fn contains(&self, path: &str) -> bool {
+        self.entries.contains_key(&normalize_pack_path(path))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn read(&self, path: &str) -> Result<Vec<u8>> {
+        let entry = self
+            .entries
+            .get(&normalize_pack_path(path))
+            .with_context(|| format!("Asset not found in pack: {}", path))?;
+
+        let mut file = File::open(&self.pack_path)?;
+        file.seek(SeekFrom::Start(self.data_start + entry.offset))?;
+        let mut bytes = vec![0u8; entry.size as usize];
+        file.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+}
+
+#[async_trait]
+impl AssetLoader for PackAssetLoader {
+    async fn load_text(&self, path: &str) -> Result<String> {
+        String::from_utf8(self.read(path)?).with_context(|| format!("Asset is not valid UTF-8: {}", path))
+    }
+
+    async fn load_binary(&self, path: &str) -> Result<Vec<u8>> {
+        self.read(path)
+    }
+
+    fn get_base_path(&self) -> String {
+        self.pack_path.to_string_lossy().to_string()
+    }
+}
@@ -0,0 +1,340 @@
+//! Asset Server
+//!
+//! Non-blocking asset loading on top of the AssetLoader trait.
+//! - `load::<T>(path)` returns a Handle immediately; a loader thread reads and decodes the file
+//! - Systems poll `handle.state()` / `handle.get()` instead of stalling the frame
+//! - Loaded assets live in the AssetManager's reference-counted cache
+//! - `preload(paths)` tracks a batch (scene preload lists) with a progress value
+
+use std::any::{Any, TypeId};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use engine_core::assets::AssetLoader;
+use super::handle::{Handle, LoadState, SlotState, UntypedHandle};
+use super::manager::AssetManager;
+
+/// Upper bound on loader threads; file IO and image decoding rarely benefit from more
+const MAX_LOADER_THREADS: usize = 4;
+
+/// An asset type the server can decode from raw bytes
+pub trait Asset: Any + Send + Sync + Sized {
+    fn decode(bytes: Vec<u8>, path: &str) -> anyhow::Result<Self>;
+}
+
+/// Decoded RGBA8 image (textures, tilesets)
+pub struct ImageAsset {
+    pub image: image::RgbaImage,
+}
+
+impl Asset for ImageAsset {
+    fn decode(bytes: Vec<u8>, _path: &str) -> anyhow::Result<Self> {
+        Ok(Self { image: image::load_from_memory(&bytes)?.to_rgba8() })
+    }
+}
+
+/// UTF-8 text (scripts, shaders)
+pub struct TextAsset(pub String);
+
+impl Asset for TextAsset {
+    fn decode(bytes: Vec<u8>, _path: &str) -> anyhow::Result<Self> {
+        Ok(Self(String::from_utf8(bytes)?))
+    }
+}
+
+/// Parsed JSON (scenes, LDtk maps, prefabs)
+pub struct JsonAsset(pub serde_json::Value);
+
+impl Asset for JsonAsset {
+    fn decode(bytes: Vec<u8>, _path: &str) -> anyhow::Result<Self> {
+        Ok(Self(serde_json::from_slice(&bytes)?))
+    }
+}
+
+/// Raw bytes (audio, models, anything without a decoder)
+pub struct BinaryAsset(pub Vec<u8>);
+
+impl Asset for BinaryAsset {
+    fn decode(bytes: Vec<u8>, _path: &str) -> anyhow::Result<Self> {
+        Ok(Self(bytes))
+    }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Fixed set of loader threads fed from a shared queue
+struct LoadPool {
+    sender: Option<Sender<Job>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl LoadPool {
+    fn new(threads: usize) -> Self {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+
+        let workers = (0..threads.max(1))
+            .map(|i| {
+                let receiver = receiver.clone();
+                std::thread::Builder::new()
+                    .name(format!("asset-loader-{}", i))
+                    .spawn(move || loop {
+                        // Hold the lock only while waiting for the next job
+                        let job = receiver.lock().unwrap().recv();
+                        match job {
+                            Ok(job) => job(),
+                            Err(_) => break, // Server dropped
+                        }
+                    })
+                    .expect("failed to spawn asset loader thread")
+            })
+            .collect();
+
+        Self { sender: Some(sender), workers }
+    }
+
+    fn spawn(&self, job: Job) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(job);
+        }
+    }
+}
+
+impl Drop for LoadPool {
+    fn drop(&mut self) {
+        self.sender = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+pub struct AssetServer {
+    loader: Arc<dyn AssetLoader>,
+    manager: Arc<AssetManager>,
+    pool: LoadPool,
+}
+
+impl AssetServer {
+    pub fn new(loader: Arc<dyn AssetLoader>) -> Self {
+        let threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(MAX_LOADER_THREADS);
+        Self::with_threads(loader, threads)
+    }
+
+    pub fn with_threads(loader: Arc<dyn AssetLoader>, threads: usize) -> Self {
+        Self {
+            loader,
+            manager: Arc::new(AssetManager::new()),
+            pool: LoadPool::new(threads),
+        }
+    }
+
+    pub fn manager(&self) -> &Arc<AssetManager> {
+        &self.manager
+    }
+
+    pub fn loader(&self) -> &Arc<dyn AssetLoader> {
+        &self.loader
+    }
+
+    /// Start loading `path` (or join an in-flight/loaded request) and return a handle right away.
+    /// A previously failed asset is retried.
+    pub fn load<T: Asset>(&self, path: &str) -> Handle<T> {
+        let path = normalize_path(path);
+        let (slot, created) = self.manager.slot(&path, TypeId::of::<T>());
+        let retry = !created && matches!(*slot.state.read().unwrap(), SlotState::Failed(_));
+
+        if created || retry {
+            slot.set(SlotState::Queued);
+            let weak = Arc::downgrade(&slot);
+            let loader = self.loader.clone();
+            self.pool.spawn(Box::new(move || {
+                // Only weak refs are held while loading, so dropping every handle
+                // mid-load frees the slot and the result is discarded.
+                let Some(path) = weak.upgrade().map(|slot| {
+                    slot.set(SlotState::Loading);
+                    slot.path.clone()
+                }) else {
+                    return;
+                };
+
+                let result = pollster::block_on(loader.load_binary(&path))
+                    .and_then(|bytes| T::decode(bytes, &path));
+                let state = match result {
+                    Ok(asset) => SlotState::Loaded(Arc::new(asset)),
+                    Err(e) => {
+                        log::error!("Failed to load asset '{}': {:#}", path, e);
+                        SlotState::Failed(format!("{:#}", e))
+                    }
+                };
+                if let Some(slot) = weak.upgrade() {
+                    slot.set(state);
+                }
+            }));
+        }
+
+        Handle::new(slot)
+    }
+
+    /// Load with the decoder picked from the file extension (used for preload lists)
+    pub fn load_untyped(&self, path: &str) -> UntypedHandle {
+        let extension = std::path::Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        match extension.as_str() {
+            "png" | "jpg" | "jpeg" | "bmp" | "gif" | "tga" | "webp" => self.load::<ImageAsset>(path).untyped(),
+            "json" | "ldtk" | "scene" | "prefab" | "uiprefab" => self.load::<JsonAsset>(path).untyped(),
+            "lua" | "txt" | "wgsl" => self.load::<TextAsset>(path).untyped(),
+            _ => self.load::<BinaryAsset>(path).untyped(),
+        }
+    }
+
+    /// Handle to an asset that is already requested, without starting a load
+    pub fn get_handle<T: Asset>(&self, path: &str) -> Option<Handle<T>> {
+        self.manager
+            .existing_slot(&normalize_path(path), TypeId::of::<T>())
+            .map(Handle::new)
+    }
+
+    /// State of `path` as type `T`; NotLoaded when nothing holds a handle to it
+    pub fn load_state<T: Asset>(&self, path: &str) -> LoadState {
+        self.get_handle::<T>(path).map(|h| h.state()).unwrap_or(LoadState::NotLoaded)
+    }
+
+    /// Start loading every path and track them as one batch
+    pub fn preload<S: AsRef<str>>(&self, paths: &[S]) -> PreloadTracker {
+        PreloadTracker {
+            handles: paths.iter().map(|p| self.load_untyped(p.as_ref())).collect(),
+        }
+    }
+}
+
+fn normalize_path(path: &str) -> String {
+    path.replace('\\', "/")
+}
+
+/// A batch of assets being loaded together (e.g. a scene's preload list).
+/// Holding the tracker keeps the assets alive.
+#[derive(Debug, Clone, Default)]
+pub struct PreloadTracker {
+    handles: Vec<UntypedHandle>,
+}
+
+impl PreloadTracker {
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Assets that finished loading (successfully or not)
+    pub fn finished_count(&self) -> usize {
+        self.handles.iter().filter(|h| h.state().is_finished()).count()
+    }
+
+    /// 0.0..=1.0, for progress bars
+    pub fn progress(&self) -> f32 {
+        if self.handles.is_empty() {
+            1.0
+        } else {
+            self.finished_count() as f32 / self.handles.len() as f32
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.handles.iter().all(|h| h.state().is_finished())
+    }
+
+    /// (path, error) for every asset that failed
+    pub fn failures(&self) -> Vec<(String, String)> {
+        self.handles
+            .iter()
+            .filter_map(|h| match h.state() {
+                LoadState::Failed(e) => Some((h.path().to_string(), e)),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::collections::HashMap;
+    use std::time::{Duration, Instant};
+
+    struct MemoryLoader(HashMap<String, Vec<u8>>);
+
+    #[async_trait]
+    impl AssetLoader for MemoryLoader {
+        async fn load_text(&self, path: &str) -> anyhow::Result<String> {
+            Ok(String::from_utf8(self.load_binary(path).await?)?)
+        }
+
+        async fn load_binary(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+            self.0.get(path).cloned().ok_or_else(|| anyhow::anyhow!("not found: {}", path))
+        }
+
+        fn get_base_path(&self) -> String {
+            String::new()
+        }
+    }
+
+    fn server() -> AssetServer {
+        let files = HashMap::from([
+            ("scripts/a.lua".to_string(), b"print('a')".to_vec()),
+            ("maps/level.ldtk".to_string(), br#"{"levels":[]}"#.to_vec()),
+        ]);
+        AssetServer::with_threads(Arc::new(MemoryLoader(files)), 2)
+    }
+
+    fn wait_until(condition: impl Fn() -> bool) {
+        let start = Instant::now();
+        while !condition() {
+            assert!(start.elapsed() < Duration::from_secs(5), "timed out waiting for loader");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_preload_reports_progress_and_failures() {
+        let server = server();
+        let tracker = server.preload(&["scripts/a.lua", "maps/level.ldtk", "missing.png"]);
+        wait_until(|| tracker.is_done());
+
+        assert_eq!(tracker.progress(), 1.0);
+        let failures = tracker.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].0, "missing.png");
+
+        let script = server.get_handle::<TextAsset>("scripts/a.lua").unwrap();
+        assert_eq!(script.state(), LoadState::Loaded);
+        assert_eq!(script.get().unwrap().0, "print('a')");
+    }
+
+    #[test]
+    fn test_dropping_handles_frees_asset() {
+        let server = server();
+        let handle = server.load::<TextAsset>("scripts/a.lua");
+        let again = server.load::<TextAsset>("scripts/a.lua");
+        wait_until(|| handle.is_loaded());
+        assert!(again.is_loaded());
+        assert_eq!(server.manager().live_asset_count(), 1);
+
+        drop(handle);
+        drop(again);
+        // The loader thread may still be finishing with its temporary reference
+        wait_until(|| server.manager().live_asset_count() == 0);
+        assert_eq!(server.load_state::<TextAsset>("scripts/a.lua"), LoadState::NotLoaded);
+        assert_eq!(server.manager().purge_unused(), 1);
+    }
+}
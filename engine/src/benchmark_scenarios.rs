@@ -0,0 +1,349 @@
+//! Scenario Benchmarks
+//!
+//! End-to-end frame simulations on a headless World, complementing the raw ECS
+//! operation benchmarks in `ecs::BenchmarkRunner`. Each scenario produces a
+//! `BenchmarkResult` with per-system timings in `system_breakdown`:
+//!
+//! - `platformer_1k`: 1000 rigidbodies with colliders falling onto a tilemap floor
+//! - `ui_heavy`: 500 UI elements in layout groups, relayouted every frame
+//! - `script_heavy`: 200 entities each running a small Lua `Update`
+//!
+//! "render_prep" is the CPU side of a frame only (global transforms and sprite
+//! draw order); nothing touches the GPU, so the suite runs without a window.
+//! Each scenario also reports its entity setup per available ECS backend, which
+//! lists more than `Custom` when `ecs` is built with backend features.
+
+use ecs::traits::EcsWorld;
+use ecs::{BenchmarkResult, BenchmarkSuite, ComponentManager, ComponentType, DynamicWorld, EcsBackendType, World};
+use input::InputSystem;
+use physics::PhysicsWorld;
+use script::ScriptEngine;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::runtime::{script_system, transform_system};
+
+const PLATFORMER_BODIES: usize = 1000;
+const UI_ELEMENTS: usize = 500;
+/// Children per layout group in `ui_heavy`
+const UI_GROUP_SIZE: usize = 10;
+const SCRIPTED_ENTITIES: usize = 200;
+
+const BENCH_SCRIPT: &str = r#"
+function Update(dt)
+    local p = get_position()
+    if p then
+        set_position(p.x + math.sin(p.y + dt), p.y, p.z)
+    end
+end
+"#;
+
+/// Runs the frame scenarios
+pub struct ScenarioRunner {
+    /// Simulated time per scenario, in seconds
+    pub simulated_seconds: f32,
+    /// Seconds per simulated frame
+    pub dt: f32,
+}
+
+impl Default for ScenarioRunner {
+    fn default() -> Self {
+        Self {
+            simulated_seconds: 60.0,
+            dt: 1.0 / 60.0,
+        }
+    }
+}
+
+/// Accumulated time per system over a scenario
+#[derive(Default)]
+struct SystemTimer {
+    breakdown: BTreeMap<String, Duration>,
+}
+
+impl SystemTimer {
+    fn time<R>(&mut self, system: &str, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        *self.breakdown.entry(system.to_string()).or_default() += start.elapsed();
+        result
+    }
+}
+
+impl ScenarioRunner {
+    pub fn new(simulated_seconds: f32) -> Self {
+        Self {
+            simulated_seconds,
+            ..Default::default()
+        }
+    }
+
+    fn frame_count(&self) -> usize {
+        ((self.simulated_seconds / self.dt).round() as usize).max(1)
+    }
+
+    fn result(&self, test_name: &str, duration: Duration, timer: SystemTimer) -> BenchmarkResult {
+        BenchmarkResult {
+            backend: EcsBackendType::Custom,
+            test_name: test_name.to_string(),
+            duration,
+            operations_per_second: self.frame_count() as f64 / duration.as_secs_f64().max(f64::EPSILON),
+            memory_usage_estimate: None,
+            system_breakdown: timer.breakdown,
+        }
+    }
+
+    /// Run every scenario
+    pub fn run_all(&self) -> BenchmarkSuite {
+        let mut suite = BenchmarkSuite::new();
+        suite.add_result(self.platformer_1k());
+        suite.add_result(self.ui_heavy());
+        match self.script_heavy() {
+            Ok(result) => suite.add_result(result),
+            Err(e) => log::error!("script_heavy failed: {}", e),
+        }
+        for (scenario, entity_count) in [
+            ("platformer_1k", PLATFORMER_BODIES),
+            ("ui_heavy", UI_ELEMENTS),
+            ("script_heavy", SCRIPTED_ENTITIES),
+        ] {
+            for backend in EcsBackendType::available_backends() {
+                match entity_setup(backend, scenario, entity_count) {
+                    Ok(result) => suite.add_result(result),
+                    Err(e) => log::error!("{} setup on {:?} failed: {}", scenario, backend, e),
+                }
+            }
+        }
+        suite
+    }
+
+    /// 1000 falling bodies landing on a tilemap floor
+    pub fn platformer_1k(&self) -> BenchmarkResult {
+        let mut world = platformer_world(PLATFORMER_BODIES);
+        let mut physics = PhysicsWorld::new();
+        physics.set_world_bounds(None);
+        let mut timer = SystemTimer::default();
+
+        let start = Instant::now();
+        for _ in 0..self.frame_count() {
+            timer.time("physics", || physics.step(self.dt, &mut world));
+            timer.time("render_prep", || render_prep(&mut world));
+        }
+        self.result("platformer_1k", start.elapsed(), timer)
+    }
+
+    /// 500 UI elements in vertical layout groups; the screen size changes every
+    /// frame so every RectTransform and layout is recomputed
+    pub fn ui_heavy(&self) -> BenchmarkResult {
+        let mut rect_transforms: HashMap<ui::Entity, ui::RectTransform> = HashMap::new();
+        let mut ui_elements: HashMap<ui::Entity, ui::UIElement> = HashMap::new();
+        let mut vertical_layouts: HashMap<ui::Entity, ui::VerticalLayoutGroup> = HashMap::new();
+        let mut parents: HashMap<ui::Entity, ui::Entity> = HashMap::new();
+        let mut children: HashMap<ui::Entity, Vec<ui::Entity>> = HashMap::new();
+
+        let groups = UI_ELEMENTS / UI_GROUP_SIZE;
+        let mut next: ui::Entity = 0;
+        for group in 0..groups {
+            let group_entity = next;
+            next += 1;
+            let column = (group % 10) as f32;
+            let row = (group / 10) as f32;
+            rect_transforms.insert(group_entity, ui::RectTransform::anchored(
+                ui::Vec2::ZERO,
+                ui::Vec2::new(column * 190.0, row * 210.0),
+                ui::Vec2::new(180.0, 200.0),
+            ));
+            ui_elements.insert(group_entity, ui::UIElement::default());
+            vertical_layouts.insert(group_entity, ui::VerticalLayoutGroup { spacing: 2.0, ..Default::default() });
+
+            for _ in 0..UI_GROUP_SIZE - 1 {
+                let child = next;
+                next += 1;
+                rect_transforms.insert(child, ui::RectTransform::default());
+                ui_elements.insert(child, ui::UIElement::default());
+                parents.insert(child, group_entity);
+                children.entry(group_entity).or_default().push(child);
+            }
+        }
+
+        let mut rect_system = ui::RectTransformSystem::new();
+        let mut layout_system = ui::LayoutSystem::new();
+        let mut timer = SystemTimer::default();
+
+        let start = Instant::now();
+        for frame in 0..self.frame_count() {
+            // Animated window resize: forces a full relayout
+            let screen_size = ui::Vec2::new(1920.0 + (frame % 2) as f32, 1080.0);
+            timer.time("rect_transforms", || rect_system.update(&mut rect_transforms, &parents, screen_size));
+            timer.time("layout", || layout_system.update_layouts(
+                &mut rect_transforms,
+                &ui_elements,
+                &HashMap::new(),
+                &vertical_layouts,
+                &HashMap::new(),
+                &children,
+            ));
+        }
+        self.result("ui_heavy", start.elapsed(), timer)
+    }
+
+    /// 200 entities each running a small Lua Update
+    pub fn script_heavy(&self) -> anyhow::Result<BenchmarkResult> {
+        let mut world = World::new();
+        let loader: Arc<dyn engine_core::assets::AssetLoader> = Arc::new(crate::assets::native_loader::NativeAssetLoader::new("."));
+        let mut script_engine = ScriptEngine::new(loader)?;
+        let input = InputSystem::new();
+
+        for i in 0..SCRIPTED_ENTITIES {
+            let entity = world.spawn();
+            world.add_component(entity, ComponentType::Transform).map_err(anyhow::Error::msg)?;
+            world.add_component(entity, ComponentType::Sprite).map_err(anyhow::Error::msg)?;
+            if let Some(transform) = world.transforms.get_mut(&entity) {
+                transform.position = [(i % 20) as f32, (i / 20) as f32, 0.0];
+            }
+            world.scripts.insert(entity, ecs::Script {
+                script_name: "bench".to_string(),
+                enabled: true,
+                parameters: HashMap::new(),
+                lifecycle_state: Default::default(),
+            });
+            script_engine.load_script_for_entity(entity, BENCH_SCRIPT, &mut world)?;
+        }
+
+        let mut timer = SystemTimer::default();
+        let start = Instant::now();
+        for _ in 0..self.frame_count() {
+            timer.time("scripts", || script_system::update_scripts(&mut script_engine, &mut world, &input, self.dt));
+            timer.time("render_prep", || render_prep(&mut world));
+        }
+        Ok(self.result("script_heavy", start.elapsed(), timer))
+    }
+}
+
+/// Tilemap floor (one collider per merged rectangle of solid tiles) with a grid
+/// of dynamic bodies above it
+fn platformer_world(body_count: usize) -> World {
+    const FLOOR_WIDTH: u32 = 120;
+    // Thicker than a body moves per step at landing speed, so nothing tunnels through
+    const FLOOR_HEIGHT: u32 = 4;
+    let floor_origin = [-60.0, -40.0];
+
+    let mut world = World::new();
+    let floor = world.spawn();
+    let mut tilemap = ecs::Tilemap::new("Floor", "tiles", FLOOR_WIDTH, FLOOR_HEIGHT);
+    for y in 0..FLOOR_HEIGHT {
+        for x in 0..FLOOR_WIDTH {
+            tilemap.set_tile(x, y, ecs::Tile::new(1));
+        }
+    }
+    world.transforms.insert(floor, ecs::Transform {
+        position: [floor_origin[0], floor_origin[1], 0.0],
+        ..Default::default()
+    });
+
+    // Scene convention: 1 unit per tile, transform at the tilemap's top-left
+    let mut solid: Vec<Vec<bool>> = (0..tilemap.height)
+        .map(|y| (0..tilemap.width).map(|x| tilemap.get_tile(x, y).is_some_and(|t| !t.is_empty())).collect())
+        .collect();
+    for rect in ecs::loaders::ldtk_loader::find_rectangles(&mut solid, tilemap.width, tilemap.height) {
+        let collider = world.spawn();
+        world.transforms.insert(collider, ecs::Transform {
+            position: [
+                floor_origin[0] + rect.x as f32 + rect.width as f32 / 2.0,
+                floor_origin[1] - rect.y as f32 - rect.height as f32 / 2.0,
+                0.0,
+            ],
+            ..Default::default()
+        });
+        world.colliders.insert(collider, ecs::Collider::new(rect.width as f32, rect.height as f32));
+    }
+    world.tilemaps.insert(floor, tilemap);
+
+    for i in 0..body_count {
+        let body = world.spawn();
+        let _ = world.add_component(body, ComponentType::Transform);
+        let _ = world.add_component(body, ComponentType::Sprite);
+        let _ = world.add_component(body, ComponentType::BoxCollider);
+        let _ = world.add_component(body, ComponentType::Rigidbody);
+        if let Some(transform) = world.transforms.get_mut(&body) {
+            transform.position = [(i % 50) as f32 * 2.0 - 50.0, (i / 50) as f32 * 2.0 - 30.0, 0.0];
+        }
+    }
+    world
+}
+
+/// CPU-side frame preparation: global transforms, then sprites in draw order
+fn render_prep(world: &mut World) -> usize {
+    transform_system::update_global_transforms(world);
+    let mut draw_list: Vec<(ecs::Entity, f32)> = world.sprites.keys()
+        .filter(|entity| world.is_active_in_hierarchy(**entity))
+        .filter_map(|entity| world.global_transforms.get(entity).map(|g| (*entity, g.matrix[14])))
+        .collect();
+    draw_list.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+    draw_list.len()
+}
+
+/// Spawning a scenario's entities (with a shallow hierarchy) on one ECS backend
+fn entity_setup(backend: EcsBackendType, scenario: &str, entity_count: usize) -> Result<BenchmarkResult, Box<dyn std::error::Error>> {
+    const ITERATIONS: usize = 20;
+    let mut world = DynamicWorld::new(backend)?;
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        world.clear();
+        let root = world.spawn();
+        for i in 0..entity_count {
+            let entity = world.spawn();
+            if i % UI_GROUP_SIZE == 0 {
+                let _ = world.set_parent(entity, Some(root));
+            }
+        }
+    }
+    let duration = start.elapsed();
+
+    Ok(BenchmarkResult {
+        backend,
+        test_name: format!("{}_entity_setup", scenario),
+        duration,
+        operations_per_second: (ITERATIONS * entity_count) as f64 / duration.as_secs_f64().max(f64::EPSILON),
+        memory_usage_estimate: None,
+        system_breakdown: BTreeMap::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scenarios_report_system_breakdown() {
+        let runner = ScenarioRunner { simulated_seconds: 0.05, dt: 1.0 / 60.0 };
+
+        let platformer = runner.platformer_1k();
+        assert!(platformer.system_breakdown.contains_key("physics"));
+        assert!(platformer.system_breakdown.contains_key("render_prep"));
+
+        let ui = runner.ui_heavy();
+        assert!(ui.system_breakdown.contains_key("layout"));
+
+        let scripts = runner.script_heavy().unwrap();
+        assert!(scripts.system_breakdown.contains_key("scripts"));
+    }
+
+    #[test]
+    fn test_platformer_bodies_land_on_tilemap_floor() {
+        let mut world = platformer_world(100);
+        let mut physics = PhysicsWorld::new();
+        physics.set_world_bounds(None);
+        for _ in 0..180 {
+            physics.step(1.0 / 60.0, &mut world);
+        }
+        // Floor top is at y = -40; nothing fell through it
+        let lowest = world.rigidbodies.keys()
+            .filter_map(|e| world.transforms.get(e))
+            .map(|t| t.position[1])
+            .fold(f32::MAX, f32::min);
+        assert!(lowest > -41.0, "body fell through the floor: {}", lowest);
+    }
+}
@@ -1,17 +1,20 @@
 use anyhow::Result;
-use engine_core::EngineContext;
-use ecs::World;
+use engine_core::{EngineContext, MAX_FIXED_STEPS_PER_FRAME};
+use engine_core::frame_clock::{self, fixed_steps, FrameClock, FramePace};
+use ecs::{EntityOrdered, World};
 use script::ScriptEngine;
 #[cfg(feature = "rapier")]
 use physics::rapier_backend::RapierPhysicsWorld;
 #[cfg(not(feature = "rapier"))]
 use physics::PhysicsWorld;
 use render::RenderModule;
+use engine_core::project_settings::{ProjectSettings, PROJECT_SETTINGS_FILE};
+use engine_core::display_settings::DisplaySettings;
 
 use winit::{
     event::*,
     event_loop::{ControlFlow, EventLoop},
-    window::WindowBuilder,
+    window::Window,
 };
 
 // Use the engine library
@@ -85,14 +88,82 @@ fn main() -> Result<()> {
     env_logger::init();
     log::info!("=== Game Player Runtime Starting ===");
 
+    // Exported game: game.json next to the executable picks the bundle, scene and window
+    let manifest = engine::export::GameManifest::find();
+    if let Some((root, manifest)) = &manifest {
+        log::info!("Found game manifest for '{}' in {:?}", manifest.name, root);
+    }
+
+    let asset_loader: std::sync::Arc<dyn engine_core::assets::AssetLoader> = match &manifest {
+        Some((root, manifest)) => manifest.asset_loader(root)?,
+        None => std::sync::Arc::new(engine::assets::native_loader::NativeAssetLoader::new(".")),
+    };
+
+    // Load Game Project
+    // Exported games use the manifest's bundle; otherwise run a project from the working directory
+    let mut project_path = match &manifest {
+        Some((root, engine::export::GameManifest { assets: engine::export::AssetBundle::Directory(dir), .. })) => root.join(dir),
+        Some((root, _)) => root.clone(),
+        None => std::env::current_dir()?,
+    };
+
+    // If we're in the root directory, look for FPS 3D Example project
+    if manifest.is_none() && !project_path.join("scenes").exists() {
+        let fps_path = project_path.join("projects/FPS 3D Example");
+        if fps_path.exists() {
+            project_path = fps_path;
+        }
+    }
+
+    log::info!("Loading project from: {:?}", project_path);
+
+    // Project settings (settings.json is bundled with the other project .json files)
+    let project_settings = match &manifest {
+        Some(_) => pollster::block_on(asset_loader.load_text(PROJECT_SETTINGS_FILE))
+            .ok()
+            .map(|json| ProjectSettings::from_json(&json).unwrap_or_else(|e| {
+                log::error!("Failed to parse {}: {}", PROJECT_SETTINGS_FILE, e);
+                ProjectSettings::default()
+            }))
+            .unwrap_or_default(),
+        None => ProjectSettings::load(&project_path),
+    };
+
+    let mut window_settings = manifest
+        .as_ref()
+        .map(|(_, m)| m.window.clone())
+        .unwrap_or_else(|| engine::export::WindowSettings {
+            title: project_settings.application.window_title.clone(),
+            width: project_settings.application.window_width,
+            height: project_settings.application.window_height,
+            resizable: project_settings.application.resizable,
+            vsync: project_settings.rendering.vsync,
+            ..Default::default()
+        });
+
+    // Display choices from the game's options menu (Screen.set_*) override the defaults
+    let project_name = match &manifest {
+        Some((_, manifest)) => manifest.name.clone(),
+        None => engine_core::project::ProjectManager::get_project_name(&project_path),
+    };
+    let display_path = DisplaySettings::path_for_project(&project_name);
+    let display_settings = DisplaySettings::load(&display_path);
+    window_settings.fullscreen = display_settings.fullscreen.unwrap_or(window_settings.fullscreen);
+    window_settings.vsync = display_settings.vsync.unwrap_or(window_settings.vsync);
+    let window_size = display_settings.resolution
+        .map(|[width, height]| winit::dpi::Size::from(winit::dpi::PhysicalSize::new(width, height)))
+        .unwrap_or_else(|| winit::dpi::LogicalSize::new(window_settings.width, window_settings.height).into());
+    let mut window_controller = runtime::WindowController::player(display_path, display_settings);
+
     let event_loop = EventLoop::new()?;
-    let window = WindowBuilder::new()
-        .with_title("Rust 2D Game Engine Player")
-        .with_inner_size(winit::dpi::LogicalSize::new(1280, 720))
-        .build(&event_loop)?;
+    let window_attributes = Window::default_attributes()
+        .with_title(&window_settings.title)
+        .with_inner_size(window_size)
+        .with_resizable(window_settings.resizable)
+        .with_fullscreen(window_settings.fullscreen.then(|| winit::window::Fullscreen::Borderless(None)));
+    let window = event_loop.create_window(window_attributes)?;
 
     // Initialize systems
-    let asset_loader: std::sync::Arc<dyn engine_core::assets::AssetLoader> = std::sync::Arc::new(engine::assets::native_loader::NativeAssetLoader::new("."));
     let mut ctx = EngineContext::new(asset_loader.clone());
     let mut script_engine = ScriptEngine::new(asset_loader.clone())?;
     
@@ -100,9 +171,28 @@ fn main() -> Result<()> {
     let mut physics = RapierPhysicsWorld::new();
     #[cfg(not(feature = "rapier"))]
     let mut physics = PhysicsWorld::new();
+    #[cfg(feature = "rapier")]
+    runtime::physics_system::apply_rapier_physics_settings(&mut physics, &project_settings.physics);
+    #[cfg(not(feature = "rapier"))]
+    runtime::physics_system::apply_physics_settings(&mut physics, &project_settings.physics);
+    script_engine.sync_physics_settings(&project_settings.physics);
+    script_engine.configure_modules(None, &project_settings.scripting.lib_paths);
+    script_engine.set_vec2_values(project_settings.scripting.vec2_values);
 
     // Init Renderer
     let mut renderer = pollster::block_on(RenderModule::new(&window))?;
+    renderer.set_clear_color(project_settings.rendering.clear_color);
+    renderer.texture_manager.set_streaming(engine::texture_manager::texture_streaming(&project_settings.rendering));
+    renderer.set_vsync(window_settings.vsync);
+    let sorting_layers = ecs::sorting::SortingLayers::new(project_settings.rendering.sorting_layers.clone());
+    let aspect_policy = project_settings.rendering.aspect_policy();
+    // Screenshots and the F9 GIF buffer (settings.json capture section), saved next to the game
+    renderer.screen_capture.set_output_dir(match &manifest {
+        Some((root, _)) => root.clone(),
+        None => project_path.clone(),
+    });
+    renderer.screen_capture.set_gif_settings(runtime::script_system::gif_settings(&project_settings.capture));
+    renderer.screen_capture.set_gif_recording(project_settings.capture.gif_enabled);
     let mut texture_manager = TextureManager::new();
     let mut ui_manager = UIManager::new();
     let mut render_cache = engine::runtime::render_system::RenderCache::new();
@@ -115,63 +205,131 @@ fn main() -> Result<()> {
         &window,
         Some(window.scale_factor() as f32),
         None,
+        None,
     );
     let mut egui_renderer = egui_wgpu::Renderer::new(
         &renderer.device,
         renderer.config.format,
-        Some(wgpu::TextureFormat::Depth32Float),
+        egui_wgpu::RendererOptions {
+            depth_stencil_format: Some(wgpu::TextureFormat::Depth32Float),
+            ..Default::default()
+        },
     );
  
-    // Load Game Project
-    // In a real export, these paths would be relative to the executable
-    let mut project_path = std::env::current_dir()?;
+    // Load persistent save data before any script runs
+    if let Err(e) = script_engine.open_save_data(&project_name) {
+        log::error!("Failed to load save data: {}", e);
+    }
 
-    // If we're in the root directory, look for FPS 3D Example project
-    if !project_path.join("scenes").exists() {
-        let fps_path = project_path.join("projects/FPS 3D Example");
-        if fps_path.exists() {
-            project_path = fps_path;
+    // Action mapping (input.json is bundled with the other project .json files)
+    let action_map = &project_settings.input.action_map;
+    let input_config = match &manifest {
+        Some(_) => pollster::block_on(asset_loader.load_text(action_map))
+            .ok()
+            .map(|json| input::InputConfig::from_json(&json).map_err(|e| e.to_string())),
+        None if project_path.join(action_map).exists() => {
+            Some(input::InputConfig::load(&project_path.join(action_map)).map_err(|e| e.to_string()))
         }
+        None => None,
+    };
+    ctx.input.set_ui_blocks_game_input(project_settings.input.ui_blocks_game_input);
+    match input_config {
+        Some(Ok(config)) => ctx.input.set_config(config),
+        Some(Err(e)) => log::error!("Failed to load input config: {}", e),
+        None => {}
     }
 
-    log::info!("Loading project from: {:?}", project_path);
+    // String tables from the project's localization/ folder (directory bundles ship it as is)
+    match ui::Localization::load_for_project(&project_path) {
+        Ok(localization) => ui_manager.set_localization(localization),
+        Err(e) => log::error!("Failed to load localization: {}", e),
+    }
+    // UIText fonts are asset paths ("fonts/Title.ttf")
+    ui_manager.set_font_base_path(project_path.join("assets"));
+    ui_manager.set_safe_area(project_settings.rendering.safe_area.active_rect());
 
     // Initial World
     let mut world = World::new();
-    let mut scene_path = project_path.join("scenes/main.json");
-    
-    // Check if defaults exist, otherwise try to find scene in arguments?
-    // For now we assume typical export structure
-    if !scene_path.exists() {
-        log::warn!("Scene not found at {:?}, trying 'scenes/main.scene'", scene_path);
-        let alt_path = project_path.join("scenes/main.scene");
-        if alt_path.exists() {
-            scene_path = alt_path;
-        } else {
-             log::error!("No scene file found!");
+    let scene_json = match &manifest {
+        Some((_, manifest)) => {
+            // Boot straight into the startup scene from the bundle
+            match pollster::block_on(asset_loader.load_text(&manifest.startup_scene)) {
+                Ok(json) => Some((manifest.startup_scene.clone(), json)),
+                Err(e) => {
+                    log::error!("Failed to load startup scene '{}': {}", manifest.startup_scene, e);
+                    None
+                }
+            }
         }
-    }
-
-    if scene_path.exists() {
-        if let Ok(json) = std::fs::read_to_string(&scene_path) {
-            if let Err(e) = world.load_from_json(&json) {
-                log::error!("Failed to load scene: {}", e);
-            } else {
-                log::info!("Scene loaded successfully");
-                
-                // Load scripts after scene is loaded
-                // scripts_folder is no longer needed as argument, simpler call:
-                if let Err(e) = runtime::script_loader::load_all_scripts(&mut world, &mut script_engine) {
-                    log::error!("Failed to load scripts: {}", e);
+        None => {
+            // Startup scene from settings.json, else the conventional main scene
+            let mut scene_path = project_path.join(
+                project_settings.application.startup_scene.as_deref().unwrap_or("scenes/main.json")
+            );
+
+            // Check if defaults exist, otherwise try to find scene in arguments?
+            // For now we assume typical export structure
+            if !scene_path.exists() {
+                log::warn!("Scene not found at {:?}, trying 'scenes/main.scene'", scene_path);
+                let alt_path = project_path.join("scenes/main.scene");
+                if alt_path.exists() {
+                    scene_path = alt_path;
                 } else {
-                    log::info!("Scripts loaded successfully");
+                     log::error!("No scene file found!");
                 }
             }
+            let relative_path = scene_path.strip_prefix(&project_path).unwrap_or(&scene_path).to_string_lossy().replace('\\', "/");
+            std::fs::read_to_string(&scene_path).ok().map(|json| (relative_path, json))
+        }
+    };
+
+    // Scene.load / Scene.load_additive from scripts, applied at the end of each frame
+    let mut scene_manager = runtime::SceneManager::new();
+
+    if let Some((scene_path, json)) = scene_json {
+        if let Err(e) = world.load_from_json(&json) {
+            log::error!("Failed to load scene: {}", e);
+        } else {
+            log::info!("Scene loaded successfully");
+
+            // Load scripts after scene is loaded
+            // scripts_folder is no longer needed as argument, simpler call:
+            if let Err(e) = runtime::script_loader::load_all_scripts(&mut world, &mut script_engine) {
+                log::error!("Failed to load scripts: {}", e);
+            } else {
+                log::info!("Scripts loaded successfully");
+            }
+            scene_manager.begin(&scene_path, &world);
+
+            // The scene's gravity override (Scene Settings) wins over the project's
+            #[cfg(feature = "rapier")]
+            runtime::physics_system::apply_rapier_scene_gravity(&mut physics, &project_settings.physics, &world);
+            #[cfg(not(feature = "rapier"))]
+            runtime::physics_system::apply_scene_gravity(&mut physics, &project_settings.physics, &world);
         }
     }
 
+    // Textures load on background threads; a placeholder is drawn until they arrive
+    let asset_server = std::sync::Arc::new(engine::assets::server::AssetServer::new(asset_loader.clone()));
+    texture_manager.set_asset_server(asset_server.clone());
+
     // Set texture base path
-    texture_manager.set_base_path(project_path.join("assets"));
+    if manifest.is_some() {
+        // Bundle paths are relative to the asset loader root
+        texture_manager.set_base_path(std::path::PathBuf::from("assets"));
+    } else {
+        texture_manager.set_base_path(project_path.join("assets"));
+    }
+
+    // Wait for the scene's preload list before the first frame (kept alive for the session)
+    let scene_preload = asset_server.preload(&world.preload_assets);
+    while !scene_preload.is_done() {
+        log::info!("Preloading assets... {:.0}%", scene_preload.progress() * 100.0);
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    for (path, error) in scene_preload.failures() {
+        log::warn!("Preload failed for '{}': {}", path, error);
+    }
 
     // [SCENE POST-PROCESSING] Load External Assets (GLTF)
     // Use shared function explicitly
@@ -186,12 +344,27 @@ fn main() -> Result<()> {
         &*asset_loader,
     );
 
-    let mut last_frame_time = std::time::Instant::now();
-    const FIXED_TIMESTEP: f32 = 1.0 / 60.0;
-    let mut physics_accumulator: f32 = 0.0;
+    // Render textures (.rendertexture assets) cameras can draw into
+    for error in renderer.render_textures.load_directory(&renderer.device, &mut renderer.texture_manager, &project_path) {
+        log::warn!("{}", error);
+    }
 
-    // Start scripts (Init) - call for all entities with scripts
-    let entities_with_scripts: Vec<_> = world.scripts.keys().copied().collect();
+    // Measured frame deltas, the frame cap and background throttling (settings.json)
+    let mut frame_clock = FrameClock::new(&project_settings.rendering);
+    let fixed_timestep = project_settings.fixed_timestep();
+    let mut physics_accumulator: f32 = 0.0;
+    ctx.fixed_timestep = fixed_timestep;
+    if let Err(e) = ctx.load_modules() {
+        log::error!("{}", e);
+    }
+    // Script time budget warnings (settings.json scripting section)
+    let mut script_budget = project_settings.scripting.budget().map(|budget| {
+        profiler::scripts::set_budget_tracking(true);
+        profiler::scripts::ScriptBudget::new(budget, project_settings.scripting.budget_frames)
+    });
+
+    // Start scripts (Init) - call for all entities with scripts, in entity-id order
+    let entities_with_scripts: Vec<_> = world.scripts.sorted_entities();
     for entity in entities_with_scripts {
         if let Err(e) = script_engine.call_start_for_entity(entity, &mut world) {
             log::error!("Script start error for entity {:?}: {}", entity, e);
@@ -210,6 +383,10 @@ fn main() -> Result<()> {
                     WindowEvent::KeyboardInput { event, .. } => {
                         use winit::keyboard::PhysicalKey;
                         if let PhysicalKey::Code(keycode) = event.physical_key {
+                            // F9 saves the last few seconds as a GIF
+                            if keycode == winit::keyboard::KeyCode::F9 && event.state == winit::event::ElementState::Pressed && !event.repeat {
+                                renderer.screen_capture.save_gif(None);
+                            }
                             if let Some(key) = map_winit_keycode(keycode) {
                                 match event.state {
                                     winit::event::ElementState::Pressed => {
@@ -222,85 +399,139 @@ fn main() -> Result<()> {
                             }
                         }
                     }
+                    WindowEvent::Touch(touch) => {
+                        // Touch positions in logical pixels, the space the UI is laid out in
+                        let position = touch.location.to_logical::<f32>(window.scale_factor());
+                        let phase = match touch.phase {
+                            winit::event::TouchPhase::Started => input::TouchPhase::Started,
+                            winit::event::TouchPhase::Moved => input::TouchPhase::Moved,
+                            winit::event::TouchPhase::Ended => input::TouchPhase::Ended,
+                            winit::event::TouchPhase::Cancelled => input::TouchPhase::Cancelled,
+                        };
+                        ctx.input.add_touch(touch.id, position.x, position.y, phase);
+                    }
+                    WindowEvent::CursorMoved { position, .. } if !window_controller.cursor_locked() => {
+                        // Physical pixels, the space Input.pick_entity expects
+                        ctx.input.set_mouse_position(position.x as f32, position.y as f32);
+                    }
+                    WindowEvent::MouseInput { state, button, .. } => {
+                        let mouse_button = match button {
+                            winit::event::MouseButton::Left => Some(input::MouseButton::Left),
+                            winit::event::MouseButton::Right => Some(input::MouseButton::Right),
+                            winit::event::MouseButton::Middle => Some(input::MouseButton::Middle),
+                            _ => None,
+                        };
+                        if let Some(button) = mouse_button {
+                            match state {
+                                winit::event::ElementState::Pressed => ctx.input.press_mouse_button(button),
+                                winit::event::ElementState::Released => ctx.input.release_mouse_button(button),
+                            }
+                        }
+                    }
                     _ => {}
                 }
                 
                 match event {
                     WindowEvent::CloseRequested => target.exit(),
-                    WindowEvent::Resized(physical_size) => renderer.resize(*physical_size),
+                    WindowEvent::Resized(physical_size) => {
+                        frame_clock.set_minimized(physical_size.width == 0 || physical_size.height == 0);
+                        renderer.resize(*physical_size);
+                    }
+                    // Unfocused or hidden: run at the background frame rate
+                    WindowEvent::Focused(focused) => frame_clock.set_focused(*focused),
+                    WindowEvent::Occluded(occluded) => frame_clock.set_minimized(*occluded),
                     WindowEvent::RedrawRequested => {
-                        let now = std::time::Instant::now();
-                        let dt = (now - last_frame_time).as_secs_f32();
-                        last_frame_time = now;
+                        profiler::new_frame();
+                        if let Some(budget) = script_budget.as_mut() {
+                            for timing in budget.update(&profiler::scripts::last_frame()) {
+                                log::warn!("{}", budget.warning(&timing));
+                            }
+                        }
+
+                        // Real frame time, then the game's (Time.time_scale)
+                        let dt = script_engine.advance_time(frame_clock.tick());
 
                         // Scripts Update - use proper script system (before clearing input)
+                        runtime::window_system::sync_surface(&window, &mut renderer);
+                        window_controller.sync_script(&script_engine, &window, &renderer);
+                        runtime::script_system::sync_ui(&script_engine, &mut ui_manager);
+                        let screen = [0.0, 0.0, renderer.config.width as f32, renderer.config.height as f32];
+                        runtime::script_system::sync_screen(&script_engine, &world, &sorting_layers, screen, aspect_policy, ui_manager.canvas_scales(), |texture_id| {
+                            renderer.texture_manager.get_texture(texture_id).map_or([0.5, 0.5], |t| t.options.pivot)
+                        });
                         runtime::script_system::update_scripts(&mut script_engine, &mut world, &ctx.input, dt);
-
-                        // Process UI commands from Lua scripts
-                        let ui_commands = script_engine.take_ui_commands();
-                        for command in ui_commands {
-                            use script::UICommand;
-                            match command {
-                                UICommand::LoadPrefab { path } => {
-                                    if let Err(e) = ui_manager.load_prefab(&path) {
-                                        log::error!("Failed to load prefab '{}': {}", path, e);
-                                    }
-                                }
-                                UICommand::ActivatePrefab { path, instance_name } => {
-                                    if let Err(e) = ui_manager.activate_prefab(&path, &instance_name) {
-                                        log::error!("Failed to activate prefab '{}': {}", path, e);
-                                    }
-                                }
-                                UICommand::DeactivatePrefab { instance_name } => {
-                                    ui_manager.deactivate_prefab(&instance_name);
-                                }
-                                UICommand::SetText { element_path, text } => {
-                                    ui_manager.set_ui_data(&element_path, text);
-                                }
-                                UICommand::SetImageFill { element_path, fill_amount } => {
-                                    if let Some((instance, element)) = element_path.split_once('/') {
-                                        if let Err(e) = ui_manager.set_element_fill(instance, element, fill_amount) {
-                                            log::error!("Failed to set fill: {}", e);
-                                        }
-                                    }
-                                }
-                                UICommand::SetColor { element_path, r, g, b, a } => {
-                                    if let Some((instance, element)) = element_path.split_once('/') {
-                                        if let Err(e) = ui_manager.set_element_color(instance, element, r, g, b, a) {
-                                            log::error!("Failed to set color: {}", e);
-                                        }
-                                    }
-                                }
-                                UICommand::ShowElement { element_path } => {
-                                    if let Some((instance, element)) = element_path.split_once('/') {
-                                        if let Err(e) = ui_manager.show_element(instance, element) {
-                                            log::error!("Failed to show element: {}", e);
-                                        }
-                                    }
-                                }
-                                UICommand::HideElement { element_path } => {
-                                    if let Some((instance, element)) = element_path.split_once('/') {
-                                        if let Err(e) = ui_manager.hide_element(instance, element) {
-                                            log::error!("Failed to hide element: {}", e);
-                                        }
-                                    }
-                                }
-                            }
+                        runtime::script_system::apply_input_commands(&script_engine, &mut ctx.input);
+                        window_controller.apply_commands(&script_engine, &window, &mut renderer);
+                        for note in window_controller.take_notes() {
+                            log::warn!("{}", note);
+                        }
+                        runtime::script_system::apply_capture_commands(&script_engine, &mut renderer.screen_capture, &project_settings.capture);
+                        #[cfg(feature = "rapier")]
+                        runtime::physics_system::apply_rapier_character_moves(&mut physics, &script_engine, &mut world);
+                        #[cfg(not(feature = "rapier"))]
+                        runtime::physics_system::apply_character_moves(&script_engine, &mut world);
+
+                        // UI commands from Lua scripts, in order, laid out in one pass
+                        for error in runtime::script_system::apply_ui_commands(&script_engine, &mut ui_manager) {
+                            log::error!("{}", error);
                         }
+                        for warning in ui_manager.take_warnings() {
+                            log::warn!("{}", warning);
+                        }
+
+                        // On-screen joystick/buttons follow the active UI, which also
+                        // takes the clicks and typing aimed at it
+                        ui_manager.sync_virtual_controls(&mut ctx.input);
+                        ui_manager.sync_input_capture(&mut ctx.input);
+
+                        // Engine modules, stage by stage (FixedUpdate at the physics timestep)
+                        ctx.update(dt);
 
                         // Clear per-frame input state AFTER scripts have read it
                         ctx.input.begin_frame();
 
                         // Physics
-                        physics_accumulator += dt;
-                        while physics_accumulator >= FIXED_TIMESTEP {
-                            physics.step(FIXED_TIMESTEP, &mut world);
-                            physics_accumulator -= FIXED_TIMESTEP;
+                        for _ in 0..fixed_steps(&mut physics_accumulator, dt, fixed_timestep, MAX_FIXED_STEPS_PER_FRAME) {
+                            physics.step(fixed_timestep, &mut world);
+                        }
+                        let collision_events = physics.take_collision_events();
+                        runtime::script_system::dispatch_collision_events(&mut script_engine, &mut world, collision_events);
+                        let trigger_zone_events = physics.take_trigger_zone_events();
+                        runtime::script_system::dispatch_trigger_zone_events(&script_engine, &mut world, trigger_zone_events);
+
+                        // Sprite animations
+                        let animation_events = runtime::animation_system::update_animated_sprites(&mut world, dt);
+                        runtime::script_system::dispatch_animation_events(&script_engine, &mut world, animation_events);
+                        let fade_completions = runtime::animation_system::update_color_fades(&mut world, dt);
+                        runtime::script_system::apply_fade_completions(&mut script_engine, &mut world, fade_completions);
+
+                        // Children follow their parents from here on (rendering, picking)
+                        runtime::transform_system::update_global_transforms(&mut world);
+                        world.index_sprite_sheets();
+
+                        // Scene switches requested by scripts this frame
+                        let transition = scene_manager.process_commands(&mut script_engine, &mut world, &mut ui_manager, &mut texture_manager);
+                        #[cfg(feature = "rapier")]
+                        for &entity in &transition.destroyed {
+                            physics.remove_entity(entity);
+                        }
+                        for texture_id in &transition.unloaded_textures {
+                            renderer.texture_manager.remove_texture(texture_id);
+                        }
+                        if transition.replaced {
+                            #[cfg(feature = "rapier")]
+                            runtime::physics_system::apply_rapier_scene_gravity(&mut physics, &project_settings.physics, &world);
+                            #[cfg(not(feature = "rapier"))]
+                            runtime::physics_system::apply_scene_gravity(&mut physics, &project_settings.physics, &world);
                         }
 
+                        // Cameras targeting render textures draw first, so sprites and UI show this frame
+                        runtime::render_system::render_camera_targets(&mut render_cache, &world, &sorting_layers, &mut renderer);
+                        ui_manager.sync_render_textures(&mut egui_renderer, &renderer.device, &renderer.render_textures);
+
                         // Render
                         let raw_input = egui_state.take_egui_input(&window);
-                        egui_ctx.begin_frame(raw_input);
+                        egui_ctx.begin_pass(raw_input);
 
                         egui::CentralPanel::default().show(&egui_ctx, |ui| {
                             // Render the game view fullscreen
@@ -310,10 +541,11 @@ fn main() -> Result<()> {
                                 &mut texture_manager,
                                 Some(&mut ui_manager),
                                 None, // Default settings (fullscreen)
+                                &aspect_policy,
                             );
                         });
 
-                        let full_output = egui_ctx.end_frame();
+                        let full_output = egui_ctx.end_pass();
                         
                         // Frame rendering using egui_wgpu
                          let paint_jobs = egui_ctx.tessellate(full_output.shapes, full_output.pixels_per_point);
@@ -326,8 +558,22 @@ fn main() -> Result<()> {
                             egui_renderer.update_texture(&renderer.device, &renderer.queue, *id, image_delta);
                         }
 
-                        let screen_width = renderer.config.width;
-                        let screen_height = renderer.config.height;
+                        // The world draws into the letterboxed part of the window
+                        let [game_x, game_y, game_width, game_height] = aspect_policy.game_rect(
+                            [0.0, 0.0, renderer.config.width as f32, renderer.config.height as f32],
+                        );
+                        renderer.screen_capture.set_game_rect([game_x, game_y, game_width, game_height]);
+
+                        // The main camera's background, or the scene's when it has no camera
+                        // (lowest depth, then lowest entity id, like ecs::picking::main_camera)
+                        let background = match world.cameras.iter()
+                            .filter(|(_, camera)| camera.target_texture.is_none())
+                            .min_by_key(|(entity, camera)| (camera.depth, **entity))
+                            .map(|(_, camera)| camera)
+                        {
+                            Some(camera) => runtime::render_system::camera_background(camera, &world.scene_settings),
+                            None => runtime::render_system::scene_background(&world.scene_settings, project_settings.rendering.clear_color),
+                        };
 
                         let res = renderer.render_with_callback(|device, queue, encoder, view, depth_view, texture_manager, tilemap_renderer, batch_renderer, mesh_renderer, camera_binding, light_binding| {
                             egui_renderer.update_buffers(
@@ -344,7 +590,7 @@ fn main() -> Result<()> {
                                     view: view,
                                     resolve_target: None,
                                     ops: wgpu::Operations {
-                                        load: wgpu::LoadOp::Clear(wgpu::Color { r: 0.1, g: 0.1, b: 0.1, a: 1.0 }),
+                                        load: wgpu::LoadOp::Clear(background),
                                         store: wgpu::StoreOp::Store,
                                     },
                                     depth_slice: None,
@@ -360,14 +606,17 @@ fn main() -> Result<()> {
                                 occlusion_query_set: None,
                                 timestamp_writes: None,
                             });
-                            
+                            rpass.set_viewport(game_x, game_y, game_width.max(1.0), game_height.max(1.0), 0.0, 1.0);
+
                             // Find Main Camera and Calculate ViewProj
                             let mut view_proj = glam::Mat4::IDENTITY;
                             if let Some(main_camera) = world.cameras.iter()
-                                .min_by_key(|(_, camera)| camera.depth)
+                                .filter(|(_, camera)| camera.target_texture.is_none())
+                                .min_by_key(|(entity, camera)| (camera.depth, **entity))
                             {
                                 let (entity, camera) = main_camera;
-                                if let Some(transform) = world.transforms.get(entity) {
+                                let camera = &ecs::picking::framed_camera(camera, &aspect_policy, [0.0, 0.0, game_width, game_height]);
+                                if let Some(transform) = world.world_transform(*entity) {
                                      use glam::{Vec3, Quat, Mat4, EulerRot};
                                      let rot_rad = Vec3::new(
                                         transform.rotation[0].to_radians(),
@@ -379,8 +628,7 @@ fn main() -> Result<()> {
                                     let forward = cam_rotation * Vec3::Z;
                                     let up = cam_rotation * Vec3::Y;
                                     let view = Mat4::look_at_rh(cam_pos, cam_pos + forward, up);
-                                    let max_dim = screen_width.max(1) as f32;
-                                    let aspect = screen_width as f32 / screen_height.max(1) as f32;
+                                    let aspect = game_width / game_height.max(1.0);
                                     let projection = match camera.projection {
                                         ecs::CameraProjection::Perspective => {
                                             Mat4::perspective_rh(camera.fov.to_radians(), aspect, camera.near_clip, camera.far_clip)
@@ -403,6 +651,7 @@ fn main() -> Result<()> {
                             runtime::render_system::render_game_world(
                                 &mut render_cache,
                                 &world,
+                                &sorting_layers,
                                 tilemap_renderer,
                                 batch_renderer,
                                 mesh_renderer,
@@ -411,11 +660,16 @@ fn main() -> Result<()> {
                                 texture_manager,
                                 queue,
                                 device,
+                                winit::dpi::PhysicalSize::new(screen_descriptor.size_in_pixels[0], screen_descriptor.size_in_pixels[1]),
                                 &mut rpass,
                                 view_proj,
+                                None,
                             );
 
-                            // Render UI on top
+                            // Render UI on top (bars included). egui_wgpu wants a 'static pass;
+                            // forget_lifetime keeps the encoder borrowed until the pass ends.
+                            let mut rpass = rpass.forget_lifetime();
+                            rpass.set_viewport(0.0, 0.0, screen_descriptor.size_in_pixels[0] as f32, screen_descriptor.size_in_pixels[1] as f32, 0.0, 1.0);
                             egui_renderer.render(
                                 &mut rpass,
                                 &paint_jobs,
@@ -429,15 +683,35 @@ fn main() -> Result<()> {
 
                         match res {
                             Ok(_) => {}
-                            Err(wgpu::SurfaceError::Lost) => renderer.resize(renderer.size),
+                            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => renderer.resize(window.inner_size()),
                             Err(wgpu::SurfaceError::OutOfMemory) => target.exit(),
                             Err(e) => eprintln!("{:?}", e),
                         }
+                        for result in renderer.screen_capture.take_results() {
+                            match result {
+                                Ok(path) => log::info!("Saved {}", path.display()),
+                                Err(e) => log::error!("{}", e),
+                            }
+                        }
                     }
                     _ => {}
                 }
             }
-            Event::AboutToWait => window.request_redraw(),
+            // Locked cursor: mouse-look reads raw motion, the cursor position stays put
+            Event::DeviceEvent { event: DeviceEvent::MouseMotion { delta }, .. } if window_controller.cursor_locked() => {
+                ctx.input.add_mouse_motion(delta.0 as f32, delta.1 as f32);
+            }
+            Event::AboutToWait => {
+                frame_clock.set_vsync(renderer.vsync());
+                match frame_clock.pace() {
+                    FramePace::Redraw => window.request_redraw(),
+                    FramePace::SleepUntil(due) => {
+                        frame_clock::sleep_until(due);
+                        window.request_redraw();
+                    }
+                    FramePace::WaitUntil(wake) => target.set_control_flow(ControlFlow::WaitUntil(wake)),
+                }
+            }
             _ => {}
         }
     })?;
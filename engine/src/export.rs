@@ -0,0 +1,536 @@
+//! Game Export
+//!
+//! Packages a project into a standalone folder that runs without the editor:
+//! - `<game name>[.exe]` - the player runtime binary
+//! - `data/` or `data.pak` - scenes, scripts, textures, maps, audio
+//! - `game.json` - manifest with the startup scene and window settings
+//!
+//! Scenes and prefabs are scanned for asset references before anything is written;
+//! references that can't be resolved inside the project fail the export.
+
+use engine_core::assets::AssetLoader;
+use engine_core::project::ProjectConfig;
+use engine_core::project_settings::ProjectSettings;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use walkdir::WalkDir;
+
+/// File name of the manifest the player looks for next to its executable
+pub const MANIFEST_FILE: &str = "game.json";
+/// Directory bundle name
+pub const DATA_DIR: &str = "data";
+/// Archive bundle name
+pub const DATA_PACK: &str = "data.pak";
+
+/// Files copied into the bundle. Anything else in the project (docs, launch scripts) is left out.
+const ASSET_EXTENSIONS: &[&str] = &[
+    // Scenes, prefabs, data
+    "json", "scene", "prefab", "uiprefab", "sprite", "anim", "atlas",
+    // Scripts
+    "lua",
+    // Textures
+    "png", "jpg", "jpeg", "bmp", "gif", "tga", "webp",
+    // Import settings (texture .meta files)
+    "meta",
+    // Maps
+    "ldtk", "ldtkl", "tmx", "tsx",
+    // Audio
+    "wav", "ogg", "mp3", "flac",
+    // Models
+    "gltf", "glb", "bin", "xsg",
+    // Fonts
+    "ttf", "otf",
+];
+
+/// Window settings written to the manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WindowSettings {
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+    #[serde(default)]
+    pub fullscreen: bool,
+    #[serde(default = "default_true")]
+    pub resizable: bool,
+    #[serde(default = "default_true")]
+    pub vsync: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self {
+            title: "Game".to_string(),
+            width: 1280,
+            height: 720,
+            fullscreen: false,
+            resizable: true,
+            vsync: true,
+        }
+    }
+}
+
+/// Where the exported assets live, relative to the manifest
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetBundle {
+    Directory(String),
+    Archive(String),
+}
+
+/// `game.json` - presence of this file makes the player boot straight into the game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameManifest {
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+    /// Bundle-relative scene path, e.g. `scenes/main.json`
+    pub startup_scene: String,
+    pub assets: AssetBundle,
+    #[serde(default)]
+    pub window: WindowSettings,
+}
+
+impl GameManifest {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&json)?)
+    }
+
+    /// Look for `game.json` next to the executable, then in the working directory.
+    /// Returns the manifest and the folder containing it.
+    pub fn find() -> Option<(PathBuf, Self)> {
+        let exe_dir = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf));
+        let cwd = std::env::current_dir().ok();
+
+        exe_dir.into_iter().chain(cwd).find_map(|dir| {
+            let path = dir.join(MANIFEST_FILE);
+            if !path.is_file() {
+                return None;
+            }
+            match Self::load(&path) {
+                Ok(manifest) => Some((dir, manifest)),
+                Err(e) => {
+                    log::error!("Invalid game manifest {:?}: {}", path, e);
+                    None
+                }
+            }
+        })
+    }
+
+    /// Asset loader over the exported bundle
+    pub fn asset_loader(&self, root: &Path) -> anyhow::Result<Arc<dyn AssetLoader>> {
+        Ok(match &self.assets {
+            AssetBundle::Directory(dir) => Arc::new(crate::assets::native_loader::NativeAssetLoader::new(root.join(dir))),
+            AssetBundle::Archive(file) => Arc::new(crate::assets::pack::PackAssetLoader::open(root.join(file))?),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PackFormat {
+    /// Plain `data/` folder
+    #[default]
+    Directory,
+    /// Single `data.pak` archive
+    Archive,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub project_path: PathBuf,
+    pub output_dir: PathBuf,
+    pub game_name: String,
+    /// Project-relative scene; defaults to the project's game startup scene
+    pub startup_scene: Option<PathBuf>,
+    pub window: WindowSettings,
+    pub pack_format: PackFormat,
+    /// Player binary to ship; defaults to `player` next to the running executable
+    pub runtime_binary: Option<PathBuf>,
+}
+
+impl ExportOptions {
+    /// Window size, resizability and vsync start from the project's settings.json
+    pub fn new(project_path: impl Into<PathBuf>, output_dir: impl Into<PathBuf>, game_name: impl Into<String>) -> Self {
+        let game_name = game_name.into();
+        let project_path = project_path.into();
+        let settings = ProjectSettings::load(&project_path);
+        Self {
+            project_path,
+            output_dir: output_dir.into(),
+            window: WindowSettings {
+                title: game_name.clone(),
+                width: settings.application.window_width,
+                height: settings.application.window_height,
+                resizable: settings.application.resizable,
+                vsync: settings.rendering.vsync,
+                ..Default::default()
+            },
+            game_name,
+            startup_scene: None,
+            pack_format: PackFormat::default(),
+            runtime_binary: None,
+        }
+    }
+}
+
+/// An asset referenced by a scene or prefab that doesn't exist in the project
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingAsset {
+    pub path: String,
+    pub referenced_by: String,
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    NoStartupScene,
+    StartupSceneNotFound(PathBuf),
+    RuntimeNotFound(PathBuf),
+    MissingAssets(Vec<MissingAsset>),
+    Io(anyhow::Error),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::NoStartupScene => write!(f, "No startup scene set and scenes/main.json does not exist"),
+            ExportError::StartupSceneNotFound(path) => write!(f, "Startup scene not found: {}", path.display()),
+            ExportError::RuntimeNotFound(path) => write!(f, "Player runtime not found: {}", path.display()),
+            ExportError::MissingAssets(missing) => {
+                write!(f, "{} referenced asset(s) are missing:", missing.len())?;
+                for asset in missing {
+                    write!(f, "\n  {} (referenced by {})", asset.path, asset.referenced_by)?;
+                }
+                Ok(())
+            }
+            ExportError::Io(e) => write!(f, "{:#}", e),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<std::io::Error> for ExportError {
+    fn from(e: std::io::Error) -> Self {
+        ExportError::Io(e.into())
+    }
+}
+
+impl From<anyhow::Error> for ExportError {
+    fn from(e: anyhow::Error) -> Self {
+        ExportError::Io(e)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ExportReport {
+    pub executable: PathBuf,
+    pub manifest: GameManifest,
+    pub file_count: usize,
+    pub total_bytes: u64,
+}
+
+/// Files to bundle: bundle-relative path -> source file
+pub type AssetList = BTreeMap<String, PathBuf>;
+
+/// Export `options.project_path` into `options.output_dir`. `log` receives progress lines.
+pub fn export_project(options: &ExportOptions, log: &mut dyn FnMut(String)) -> Result<ExportReport, ExportError> {
+    let project = &options.project_path;
+    let startup_scene = resolve_startup_scene(project, options.startup_scene.as_deref())?;
+    log(format!("Startup scene: {}", startup_scene));
+
+    let runtime = options.runtime_binary.clone().unwrap_or_else(default_runtime_binary);
+    if !runtime.is_file() {
+        return Err(ExportError::RuntimeNotFound(runtime));
+    }
+
+    log("Collecting assets...".to_string());
+    let assets = collect_assets(project, &options.output_dir)?;
+    log(format!("Found {} asset file(s), validating references...", assets.len()));
+    let assets = validate_references(project, assets)?;
+
+    fs::create_dir_all(&options.output_dir)?;
+
+    let executable = options
+        .output_dir
+        .join(format!("{}{}", options.game_name, std::env::consts::EXE_SUFFIX));
+    log(format!("Copying runtime to {}", executable.display()));
+    fs::copy(&runtime, &executable)?;
+
+    let files: Vec<(String, PathBuf)> = assets.into_iter().collect();
+    let (bundle, total_bytes) = match options.pack_format {
+        PackFormat::Directory => {
+            log(format!("Copying {} file(s) into {}/", files.len(), DATA_DIR));
+            let data_dir = options.output_dir.join(DATA_DIR);
+            if data_dir.exists() {
+                fs::remove_dir_all(&data_dir)?;
+            }
+            let mut total = 0;
+            for (rel, source) in &files {
+                let dest = data_dir.join(rel);
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                total += fs::copy(source, &dest)?;
+            }
+            (AssetBundle::Directory(DATA_DIR.to_string()), total)
+        }
+        PackFormat::Archive => {
+            log(format!("Packing {} file(s) into {}", files.len(), DATA_PACK));
+            let total = crate::assets::pack::write_pack(&options.output_dir.join(DATA_PACK), &files)?;
+            (AssetBundle::Archive(DATA_PACK.to_string()), total)
+        }
+    };
+
+    let manifest = GameManifest {
+        name: options.game_name.clone(),
+        version: read_project_config(project).map(|c| c.version).unwrap_or_default(),
+        startup_scene,
+        assets: bundle,
+        window: options.window.clone(),
+    };
+    fs::write(
+        options.output_dir.join(MANIFEST_FILE),
+        serde_json::to_string_pretty(&manifest).map_err(anyhow::Error::from)?,
+    )?;
+    log(format!("Wrote {}", MANIFEST_FILE));
+
+    Ok(ExportReport { executable, manifest, file_count: files.len(), total_bytes })
+}
+
+/// `player[.exe]` next to the running executable (editor and player share a target dir)
+pub fn default_runtime_binary() -> PathBuf {
+    let name = format!("player{}", std::env::consts::EXE_SUFFIX);
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(&name)))
+        .unwrap_or_else(|| PathBuf::from(name))
+}
+
+fn read_project_config(project: &Path) -> Option<ProjectConfig> {
+    let json = fs::read_to_string(project.join("project.json")).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Explicit scene, else the startup scene from settings.json (which falls back to the
+/// project's game startup scene), else `scenes/main.json`. Returned as a bundle-relative path.
+fn resolve_startup_scene(project: &Path, explicit: Option<&Path>) -> Result<String, ExportError> {
+    let scene = explicit
+        .map(Path::to_path_buf)
+        .or_else(|| ProjectSettings::load(project).application.startup_scene.map(PathBuf::from))
+        .or_else(|| {
+            let default = PathBuf::from("scenes/main.json");
+            project.join(&default).is_file().then_some(default)
+        })
+        .ok_or(ExportError::NoStartupScene)?;
+
+    let relative = scene.strip_prefix(project).unwrap_or(&scene);
+    if !project.join(relative).is_file() {
+        return Err(ExportError::StartupSceneNotFound(project.join(relative)));
+    }
+    Ok(bundle_path(relative))
+}
+
+fn bundle_path(relative: &Path) -> String {
+    crate::assets::pack::normalize_pack_path(&relative.to_string_lossy())
+}
+
+fn has_asset_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| ASSET_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+}
+
+/// Editor leftovers that shouldn't ship: autosaves and backups
+fn is_editor_artifact(path: &Path) -> bool {
+    let name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+    name.contains("~autosave") || name.ends_with(".backup")
+}
+
+/// Every shippable file in the project. Hidden folders (`.cache`) and the output folder are skipped.
+pub fn collect_assets(project: &Path, output_dir: &Path) -> Result<AssetList, ExportError> {
+    let output_dir = output_dir.canonicalize().ok();
+    let mut assets = AssetList::new();
+
+    let walker = WalkDir::new(project).into_iter().filter_entry(|entry| {
+        let hidden = entry.depth() > 0 && entry.file_name().to_string_lossy().starts_with('.');
+        let is_output = output_dir
+            .as_ref()
+            .is_some_and(|out| entry.path().canonicalize().ok().as_ref() == Some(out));
+        !hidden && !is_output
+    });
+
+    for entry in walker {
+        let entry = entry.map_err(|e| ExportError::Io(e.into()))?;
+        let path = entry.path();
+        if !entry.file_type().is_file() || !has_asset_extension(path) || is_editor_artifact(path) {
+            continue;
+        }
+        let relative = path.strip_prefix(project).unwrap_or(path);
+        assets.insert(bundle_path(relative), path.to_path_buf());
+    }
+
+    Ok(assets)
+}
+
+/// Check every reference in scenes and prefabs resolves to a project file.
+/// Referenced files outside the default asset set are added to the list.
+pub fn validate_references(project: &Path, mut assets: AssetList) -> Result<AssetList, ExportError> {
+    let project_dir_name = project.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let mut missing = Vec::new();
+    let mut extra = Vec::new();
+
+    for (rel, source) in &assets {
+        let scans_references = rel.starts_with("scenes/")
+            || source.extension().is_some_and(|e| e == "prefab" || e == "uiprefab");
+        if !scans_references {
+            continue;
+        }
+        let Ok(json) = fs::read_to_string(source) else { continue };
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(&json) else { continue };
+
+        let mut references = Vec::new();
+        collect_references(&value, &mut references);
+        for reference in references {
+            match resolve_reference(project, &project_dir_name, &reference) {
+                Some(resolved) => {
+                    if !assets.contains_key(&resolved) {
+                        extra.push(resolved);
+                    }
+                }
+                None => missing.push(MissingAsset { path: reference, referenced_by: rel.clone() }),
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        missing.sort_by(|a, b| (&a.referenced_by, &a.path).cmp(&(&b.referenced_by, &b.path)));
+        missing.dedup();
+        return Err(ExportError::MissingAssets(missing));
+    }
+
+    for rel in extra {
+        let source = project.join(&rel);
+        assets.insert(rel, source);
+    }
+    Ok(assets)
+}
+
+/// String values that look like asset paths, plus `scripts/<name>.lua` for script components
+fn collect_references(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                match (key.as_str(), value) {
+                    ("script_name", serde_json::Value::String(name)) if !name.is_empty() => {
+                        out.push(format!("scripts/{}.lua", name));
+                    }
+                    _ => collect_references(value, out),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter().for_each(|v| collect_references(v, out)),
+        serde_json::Value::String(s) if has_asset_extension(Path::new(s)) => out.push(s.clone()),
+        _ => {}
+    }
+}
+
+/// Resolve a reference the way the runtime does: relative to the project root or
+/// its `assets/` folder. Older scenes store paths through `projects/<name>/` (sometimes
+/// absolute, from another machine); everything up to that folder is stripped.
+fn resolve_reference(project: &Path, project_dir_name: &str, reference: &str) -> Option<String> {
+    let normalized = reference.replace('\\', "/");
+    let marker = format!("projects/{}/", project_dir_name);
+
+    let relative = if let Some((_, inside)) = normalized.split_once(&marker) {
+        PathBuf::from(inside)
+    } else if Path::new(&normalized).is_absolute() {
+        Path::new(&normalized).strip_prefix(project).ok()?.to_path_buf()
+    } else {
+        PathBuf::from(&normalized)
+    };
+
+    [relative.clone(), Path::new("assets").join(&relative)]
+        .into_iter()
+        .find(|candidate| project.join(candidate).is_file())
+        .map(|candidate| bundle_path(&candidate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, rel: &str, contents: &str) {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn sample_project(scene: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "project.json", r#"{"name":"Demo","description":"","version":"1.2.0","game_startup_scene":"scenes/main.json"}"#);
+        write(dir.path(), "scenes/main.json", scene);
+        write(dir.path(), "scenes/main~autosave_1.json", r#"{"texture_id":"C:/elsewhere/gone.png"}"#);
+        write(dir.path(), "scripts/player.lua", "function Update(dt) end");
+        write(dir.path(), "assets/hero.png", "png");
+        write(dir.path(), "README.md", "docs");
+        dir
+    }
+
+    #[test]
+    fn test_missing_references_fail_export() {
+        let project = sample_project(r#"{"sprites":[[1,{"texture_id":"hero.png"}],[2,{"texture_id":"enemy.png"}]],
+            "scripts":[[1,{"script_name":"player"}],[2,{"script_name":"boss"}]]}"#);
+        let out = tempfile::tempdir().unwrap();
+        let runtime = project.path().join("player.bin.exe");
+        fs::write(&runtime, "exe").unwrap();
+
+        let mut options = ExportOptions::new(project.path(), out.path().join("build"), "Demo");
+        options.runtime_binary = Some(runtime);
+
+        match export_project(&options, &mut |_| {}) {
+            Err(ExportError::MissingAssets(missing)) => {
+                let paths: Vec<_> = missing.iter().map(|m| m.path.as_str()).collect();
+                assert_eq!(paths, vec!["enemy.png", "scripts/boss.lua"]);
+            }
+            other => panic!("expected missing assets, got {:?}", other.map(|r| r.file_count)),
+        }
+        assert!(!out.path().join("build").exists());
+    }
+
+    #[test]
+    fn test_export_archive_is_readable_through_loader() {
+        let project = sample_project(r#"{"sprites":[[1,{"texture_id":"hero.png"}]],"scripts":[[1,{"script_name":"player"}]]}"#);
+        let out = tempfile::tempdir().unwrap();
+        let runtime = project.path().join("runtime-binary");
+        fs::write(&runtime, "exe").unwrap();
+
+        let mut options = ExportOptions::new(project.path(), out.path(), "Demo");
+        options.runtime_binary = Some(runtime);
+        options.pack_format = PackFormat::Archive;
+        let report = export_project(&options, &mut |_| {}).unwrap();
+
+        assert!(report.executable.is_file());
+        let (root, manifest) = (out.path(), GameManifest::load(&out.path().join(MANIFEST_FILE)).unwrap());
+        assert_eq!(manifest, report.manifest);
+        assert_eq!(manifest.startup_scene, "scenes/main.json");
+        assert_eq!(manifest.version, "1.2.0");
+
+        let loader = manifest.asset_loader(root).unwrap();
+        let script = pollster::block_on(loader.load_text("scripts/player.lua")).unwrap();
+        assert_eq!(script, "function Update(dt) end");
+        assert_eq!(pollster::block_on(loader.load_binary("assets/hero.png")).unwrap(), b"png");
+        // Autosaves and docs are not shipped
+        assert!(pollster::block_on(loader.load_text("scenes/main~autosave_1.json")).is_err());
+        assert!(pollster::block_on(loader.load_text("README.md")).is_err());
+    }
+}
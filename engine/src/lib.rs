@@ -1,6 +1,11 @@
 // Library interface for engine (for benchmarks and tests)
 
 pub mod assets;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod benchmark_scenarios;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod export;
+pub mod replay;
 pub mod runtime;
 pub mod texture_manager;
 pub mod ui_manager;
@@ -1,17 +1,95 @@
 use anyhow::Result;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+use engine::export::{export_project, ExportOptions, PackFormat};
+
+#[derive(Parser, Debug)]
+#[command(name = "engine")]
+#[command(about = "Rust 2D Game Engine", long_about = None)]
+struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Package a project into a standalone game folder
+    Export {
+        /// Project folder (contains project.json)
+        project: PathBuf,
+
+        /// Output folder
+        #[arg(short, long, value_name = "DIR")]
+        output: PathBuf,
+
+        /// Game name (executable and window title); defaults to the project name
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Startup scene relative to the project; defaults to the project's game startup scene
+        #[arg(short, long, value_name = "SCENE")]
+        scene: Option<PathBuf>,
+
+        /// Bundle assets into a single data.pak instead of a data/ folder
+        #[arg(short, long)]
+        archive: bool,
+
+        /// Player binary to ship (defaults to `player` next to this executable)
+        #[arg(long, value_name = "FILE")]
+        runtime: Option<PathBuf>,
+
+        /// Window size
+        #[arg(long, default_value_t = 1280)]
+        width: u32,
+        #[arg(long, default_value_t = 720)]
+        height: u32,
+
+        /// Start fullscreen
+        #[arg(long)]
+        fullscreen: bool,
+    },
+}
 
 fn main() -> Result<()> {
+    let args = Args::parse();
+
+    match args.command {
+        Some(Command::Export { project, output, name, scene, archive, runtime, width, height, fullscreen }) => {
+            let name = name.unwrap_or_else(|| engine_core::project::ProjectManager::get_project_name(&project));
+            let mut options = ExportOptions::new(project, output, name);
+            options.startup_scene = scene;
+            options.runtime_binary = runtime;
+            options.pack_format = if archive { PackFormat::Archive } else { PackFormat::Directory };
+            options.window.width = width;
+            options.window.height = height;
+            options.window.fullscreen = fullscreen;
+
+            let report = export_project(&options, &mut |line| println!("{}", line))?;
+            println!(
+                "✅ Exported {} file(s) ({} KB) to {}",
+                report.file_count,
+                report.total_bytes / 1024,
+                options.output_dir.display()
+            );
+        }
+        None => print_usage(),
+    }
+
+    Ok(())
+}
+
+fn print_usage() {
     println!("🎮 Rust 2D Game Engine");
     println!();
     println!("Available commands:");
     println!("  cargo run --bin player    - Run the game player");
     println!("  cargo run -p editor       - Run the game editor");
+    println!("  cargo run -- export <project> -o <dir>  - Export a standalone game");
     println!();
     println!("For game development, use the editor:");
     println!("  cargo run -p editor");
     println!();
     println!("To play exported games, use the player:");
     println!("  cargo run --bin player");
-    
-    Ok(())
-}
\ No newline at end of file
+}
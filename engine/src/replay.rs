@@ -0,0 +1,377 @@
+//! Input Replays
+//!
+//! Records every input event of a play session (with its frame index), the scene
+//! it started from and the RNG seed into a `.replay` file. Playing the file back
+//! feeds the same events into the InputSystem with the same fixed dt, so the run
+//! repeats exactly - as long as all randomness goes through the engine RNG
+//! (Lua `math.random`, see `engine_core::random`).
+//!
+//! Every `checkpoint_interval` frames a hash of all transforms is stored; playback
+//! compares against it and reports the first frame where the run diverged.
+
+use ecs::World;
+use input::{InputEvent, InputSystem};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// File extension for replay files
+pub const REPLAY_EXTENSION: &str = "replay";
+/// Bumped when the file layout changes incompatibly
+pub const REPLAY_VERSION: u32 = 1;
+/// Default frames between transform checkpoints
+pub const DEFAULT_CHECKPOINT_INTERVAL: u64 = 30;
+/// Frames a single update may run (fast playback / hitches)
+pub const MAX_FRAMES_PER_UPDATE: u32 = 8;
+
+/// Input events applied before one frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub frame: u64,
+    pub events: Vec<InputEvent>,
+}
+
+/// Transform hash taken after a frame ran
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReplayCheckpoint {
+    pub frame: u64,
+    pub hash: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReplayFile {
+    pub version: u32,
+    /// Scene the recording started from (project-relative when possible)
+    pub scene_path: String,
+    /// Seed of the engine RNG for the whole run
+    pub seed: u64,
+    pub fixed_dt: f32,
+    #[serde(default = "default_checkpoint_interval")]
+    pub checkpoint_interval: u64,
+    /// Frames simulated while recording
+    pub frame_count: u64,
+    /// Held input when recording started
+    #[serde(default)]
+    pub initial_input: Vec<InputEvent>,
+    /// Only frames that had events, in frame order
+    #[serde(default)]
+    pub frames: Vec<ReplayFrame>,
+    #[serde(default)]
+    pub checkpoints: Vec<ReplayCheckpoint>,
+}
+
+fn default_checkpoint_interval() -> u64 {
+    DEFAULT_CHECKPOINT_INTERVAL
+}
+
+impl ReplayFile {
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let replay: ReplayFile = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if replay.version > REPLAY_VERSION {
+            anyhow::bail!("Replay version {} is newer than supported ({})", replay.version, REPLAY_VERSION);
+        }
+        Ok(replay)
+    }
+
+    pub fn save(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// Hash of every entity's transform, in entity order (FNV-1a over the f32 bits).
+/// Any difference in position/rotation/scale changes the hash.
+pub fn hash_transforms(world: &World) -> u64 {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut entities: Vec<_> = world.transforms.keys().copied().collect();
+    entities.sort_unstable();
+
+    let mut hash = FNV_OFFSET;
+    let mut write = |bytes: &[u8]| {
+        for &byte in bytes {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+    };
+    for entity in entities {
+        let transform = &world.transforms[&entity];
+        write(&entity.to_le_bytes());
+        for value in transform.position.iter().chain(&transform.rotation).chain(&transform.scale) {
+            write(&value.to_bits().to_le_bytes());
+        }
+    }
+    hash
+}
+
+/// Turns real frame time into a whole number of fixed-dt frames
+#[derive(Debug, Clone, Default)]
+pub struct FixedFrameClock {
+    accumulator: f32,
+}
+
+impl FixedFrameClock {
+    /// Frames to simulate for `real_dt` seconds at `speed`x (capped at `MAX_FRAMES_PER_UPDATE`)
+    pub fn advance(&mut self, real_dt: f32, fixed_dt: f32, speed: f32) -> u32 {
+        if fixed_dt <= 0.0 {
+            return 0;
+        }
+        self.accumulator += real_dt * speed.max(0.0);
+        let frames = (self.accumulator / fixed_dt).floor() as u32;
+        self.accumulator -= frames as f32 * fixed_dt;
+        if frames > MAX_FRAMES_PER_UPDATE {
+            // Don't try to catch up after a long stall
+            self.accumulator = 0.0;
+            return MAX_FRAMES_PER_UPDATE;
+        }
+        frames
+    }
+}
+
+/// Builds a ReplayFile while a session runs
+#[derive(Debug, Clone)]
+pub struct ReplayRecorder {
+    replay: ReplayFile,
+}
+
+impl ReplayRecorder {
+    /// Start recording; also starts event capture on `input`
+    pub fn start(scene_path: String, seed: u64, fixed_dt: f32, input: &mut InputSystem) -> Self {
+        Self {
+            replay: ReplayFile {
+                version: REPLAY_VERSION,
+                scene_path,
+                seed,
+                fixed_dt,
+                checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+                frame_count: 0,
+                initial_input: input.start_recording(),
+                frames: Vec::new(),
+                checkpoints: Vec::new(),
+            },
+        }
+    }
+
+    /// Index of the next frame to run
+    pub fn frame(&self) -> u64 {
+        self.replay.frame_count
+    }
+
+    /// Claim the events captured since the last frame. Call right before the frame runs.
+    pub fn begin_frame(&mut self, input: &mut InputSystem) {
+        let events = input.take_recorded();
+        if !events.is_empty() {
+            self.replay.frames.push(ReplayFrame { frame: self.replay.frame_count, events });
+        }
+    }
+
+    /// Call after the frame ran (stores a checkpoint every `checkpoint_interval` frames)
+    pub fn end_frame(&mut self, world: &World) {
+        let frame = self.replay.frame_count;
+        if frame.is_multiple_of(self.replay.checkpoint_interval) {
+            self.replay.checkpoints.push(ReplayCheckpoint { frame, hash: hash_transforms(world) });
+        }
+        self.replay.frame_count += 1;
+    }
+
+    /// Stop capture and return the finished replay
+    pub fn finish(self, input: &mut InputSystem) -> ReplayFile {
+        // Events after the last frame never affected the run
+        input.stop_recording();
+        self.replay
+    }
+}
+
+/// A desync found during playback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayDivergence {
+    pub frame: u64,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+/// Feeds a ReplayFile back into the InputSystem
+#[derive(Debug, Clone)]
+pub struct ReplayPlayer {
+    replay: ReplayFile,
+    frame: u64,
+    next_events: usize,
+    next_checkpoint: usize,
+    diverged: Option<ReplayDivergence>,
+    /// Playback speed multiplier (1.0 = recorded speed)
+    pub speed: f32,
+}
+
+impl ReplayPlayer {
+    /// Start playback; device input is ignored on `input` until `finish`
+    pub fn start(replay: ReplayFile, input: &mut InputSystem) -> Self {
+        input.set_playback(true);
+        for event in &replay.initial_input {
+            input.apply_event(event);
+        }
+        // Held state only, nothing was "pressed this frame" when recording started
+        input.begin_frame();
+
+        Self {
+            replay,
+            frame: 0,
+            next_events: 0,
+            next_checkpoint: 0,
+            diverged: None,
+            speed: 1.0,
+        }
+    }
+
+    pub fn replay(&self) -> &ReplayFile {
+        &self.replay
+    }
+
+    /// Index of the next frame to run
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.replay.frame_count
+    }
+
+    /// First divergence found so far
+    pub fn divergence(&self) -> Option<ReplayDivergence> {
+        self.diverged
+    }
+
+    /// Apply the recorded events for the next frame. Call right before the frame runs.
+    pub fn begin_frame(&mut self, input: &mut InputSystem) {
+        while let Some(recorded) = self.replay.frames.get(self.next_events) {
+            if recorded.frame > self.frame {
+                break;
+            }
+            if recorded.frame == self.frame {
+                for event in &recorded.events {
+                    input.apply_event(event);
+                }
+            }
+            self.next_events += 1;
+        }
+    }
+
+    /// Call after the frame ran. Returns the divergence the first time the
+    /// transforms don't match a recorded checkpoint.
+    pub fn end_frame(&mut self, world: &World) -> Option<ReplayDivergence> {
+        let frame = self.frame;
+        self.frame += 1;
+
+        while let Some(checkpoint) = self.replay.checkpoints.get(self.next_checkpoint) {
+            if checkpoint.frame > frame {
+                break;
+            }
+            self.next_checkpoint += 1;
+            if checkpoint.frame < frame || self.diverged.is_some() {
+                continue;
+            }
+            let actual = hash_transforms(world);
+            if actual != checkpoint.hash {
+                let divergence = ReplayDivergence { frame, expected: checkpoint.hash, actual };
+                self.diverged = Some(divergence);
+                return Some(divergence);
+            }
+        }
+        None
+    }
+
+    /// Hand device input back to the InputSystem
+    pub fn finish(self, input: &mut InputSystem) {
+        input.set_playback(false);
+        input.reset_state();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use input::Key;
+
+    /// Tiny deterministic "game": holding D moves every transform right
+    fn simulate(world: &mut World, input: &InputSystem, fixed_dt: f32) {
+        if input.is_key_down(Key::D) {
+            for transform in world.transforms.values_mut() {
+                transform.position[0] += 10.0 * fixed_dt;
+            }
+        }
+    }
+
+    fn world_with_player() -> World {
+        let mut world = World::new();
+        let entity = world.spawn();
+        world.transforms.insert(entity, ecs::Transform::default());
+        world
+    }
+
+    #[test]
+    fn test_playback_matches_recording_and_detects_desync() {
+        let fixed_dt = 1.0 / 60.0;
+        let mut input = InputSystem::default();
+        let mut world = world_with_player();
+        let mut recorder = ReplayRecorder::start("scenes/test.json".to_string(), 42, fixed_dt, &mut input);
+
+        for frame in 0..90 {
+            if frame == 10 {
+                input.press_key(Key::D);
+            }
+            if frame == 50 {
+                input.release_key(Key::D);
+            }
+            recorder.begin_frame(&mut input);
+            simulate(&mut world, &input, fixed_dt);
+            input.begin_frame();
+            recorder.end_frame(&world);
+        }
+        let replay = recorder.finish(&mut input);
+        assert_eq!(replay.frame_count, 90);
+        assert_eq!(replay.frames.len(), 2);
+        assert_eq!(replay.checkpoints.len(), 3);
+
+        // Same run: no divergence, even with device input arriving during playback
+        let mut played = world_with_player();
+        let mut player = ReplayPlayer::start(replay.clone(), &mut input);
+        input.press_key(Key::D);
+        while !player.is_finished() {
+            player.begin_frame(&mut input);
+            simulate(&mut played, &input, fixed_dt);
+            input.begin_frame();
+            assert_eq!(player.end_frame(&played), None);
+        }
+        player.finish(&mut input);
+        assert_eq!(hash_transforms(&played), hash_transforms(&world));
+
+        // Different starting state: reported once, at the first checkpoint
+        let mut desynced = world_with_player();
+        desynced.transforms.values_mut().for_each(|t| t.position[1] = 1.0);
+        let mut player = ReplayPlayer::start(replay, &mut input);
+        let mut divergences = Vec::new();
+        while !player.is_finished() {
+            player.begin_frame(&mut input);
+            simulate(&mut desynced, &input, fixed_dt);
+            input.begin_frame();
+            divergences.extend(player.end_frame(&desynced));
+        }
+        assert_eq!(divergences.len(), 1);
+        assert_eq!(divergences[0].frame, 0);
+    }
+
+    #[test]
+    fn test_frame_clock_scales_and_caps() {
+        // Powers of two keep the float math exact
+        let fixed_dt = 0.25;
+        let mut clock = FixedFrameClock::default();
+        assert_eq!(clock.advance(0.625, fixed_dt, 1.0), 2);
+        assert_eq!(clock.advance(0.125, fixed_dt, 1.0), 1);
+        assert_eq!(clock.advance(0.25, fixed_dt, 0.5), 0);
+        assert_eq!(clock.advance(0.25, fixed_dt, 0.5), 1);
+        assert_eq!(clock.advance(10.0, fixed_dt, 4.0), MAX_FRAMES_PER_UPDATE);
+    }
+}
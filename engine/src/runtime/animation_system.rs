@@ -0,0 +1,153 @@
+use ecs::{AnimatedSprite, FadeCompleteAction, World};
+use engine_core::executor::{self, ParallelSystem, SystemAccess, SystemWrites};
+use std::sync::{Arc, Mutex};
+
+/// Advanced sprites and the events they reached, in entity order
+type AnimationResults = Vec<(ecs::Entity, AnimatedSprite, Vec<String>)>;
+
+/// Advance every active AnimatedSprite on a copy, in parallel (reads only)
+fn advance_animated_sprites(world: &World, delta_time: f32) -> AnimationResults {
+    let mut entities: Vec<ecs::Entity> = world.animated_sprites.keys()
+        .copied()
+        .filter(|entity| world.is_active_in_hierarchy(*entity))
+        .collect();
+    entities.sort_unstable();
+
+    executor::par_map(&entities, |&entity| {
+        let total_frames = world.sprite_sheets.get(&entity).map(|sheet| sheet.frames.len()).unwrap_or(0);
+        let mut animated_sprite = world.animated_sprites[&entity].clone();
+        animated_sprite.update(delta_time, total_frames);
+        let events = animated_sprite.take_events();
+        (entity, animated_sprite, events)
+    })
+}
+
+/// Write advanced sprites back and collect their events
+fn apply_animated_sprites(world: &mut World, results: AnimationResults) -> Vec<(ecs::Entity, String)> {
+    let mut events = Vec::new();
+    for (entity, animated_sprite, entity_events) in results {
+        world.animated_sprites.insert(entity, animated_sprite);
+        events.extend(entity_events.into_iter().map(|event| (entity, event)));
+    }
+    events
+}
+
+/// Advances AnimatedSprite frames. Entities that are inactive in the hierarchy keep
+/// their current frame until they are activated again.
+///
+/// Returns the clip keyframe events reached this frame, in entity order.
+pub fn update_animated_sprites(world: &mut World, delta_time: f32) -> Vec<(ecs::Entity, String)> {
+    profiler::profile_scope!("animation.update");
+
+    let results = advance_animated_sprites(world, delta_time);
+    apply_animated_sprites(world, results)
+}
+
+/// Advances ColorFade tints. A fade pauses while its entity is inactive in the hierarchy
+/// and holds its target tint once finished.
+///
+/// Returns the on-complete actions of the fades that finished this frame, in entity
+/// order (script_system::apply_fade_completions runs them).
+pub fn update_color_fades(world: &mut World, delta_time: f32) -> Vec<(ecs::Entity, FadeCompleteAction)> {
+    profiler::profile_scope!("animation.color_fades");
+
+    let mut entities: Vec<ecs::Entity> = world.color_fades.keys()
+        .copied()
+        .filter(|entity| world.is_active_in_hierarchy(*entity))
+        .collect();
+    entities.sort_unstable();
+
+    let mut completed = Vec::new();
+    for entity in entities {
+        let Some(fade) = world.color_fades.get_mut(&entity) else {
+            continue;
+        };
+        if fade.tint.is_some() && fade.is_finished() {
+            continue;
+        }
+        fade.elapsed = (fade.elapsed + delta_time).min(fade.delay + fade.duration.max(0.0));
+        let easing = ui::animation::EasingFunction::from_name(&fade.easing).unwrap_or(ui::animation::EasingFunction::Linear);
+        fade.tint = Some(fade.color_at(easing.evaluate(fade.progress())));
+        if fade.is_finished() {
+            completed.push((entity, fade.on_complete));
+        }
+    }
+    completed
+}
+
+/// `update_animated_sprites` as a parallel system. Events reached each run are
+/// queued on `events()` for the caller to dispatch.
+#[derive(Default)]
+pub struct AnimatedSpriteSystem {
+    events: Arc<Mutex<Vec<(ecs::Entity, String)>>>,
+}
+
+impl AnimatedSpriteSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shared event queue (keep a handle before boxing the system into an executor)
+    pub fn events(&self) -> Arc<Mutex<Vec<(ecs::Entity, String)>>> {
+        self.events.clone()
+    }
+}
+
+impl ParallelSystem<World> for AnimatedSpriteSystem {
+    fn name(&self) -> &str {
+        "animated_sprites"
+    }
+
+    fn access(&self) -> SystemAccess {
+        SystemAccess::new()
+            .read("active")
+            .read("parents")
+            .read("sprite_sheets")
+            .write("animated_sprites")
+    }
+
+    fn run(&mut self, world: &World, dt: f32) -> SystemWrites<World> {
+        profiler::profile_scope!("animation.update");
+        let results = advance_animated_sprites(world, dt);
+        let events = self.events.clone();
+        Box::new(move |world| {
+            let reached = apply_animated_sprites(world, results);
+            events.lock().unwrap().extend(reached);
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::script_system;
+    use crate::runtime::test_support::script_engine;
+    use script::ReplLine;
+
+    #[test]
+    fn test_color_fade_multiplies_and_completes() {
+        let mut script_engine = script_engine(&[]);
+        let mut world = World::new();
+        let sprite = world.spawn();
+        world.transforms.insert(sprite, ecs::Transform::default());
+        world.sprites.insert(sprite, ecs::Sprite { texture_id: "a.png".to_string(), ..Default::default() });
+        let input = input::InputSystem::default();
+
+        // The fade tints whatever color the sprite has; get_sprite_color reads the sprite's own
+        let code = format!(
+            "set_sprite_color({0}, 1, 0.5, 0) fade_sprite({0}, 1, 1, 1, 0, 1.0, 'linear', 'destroy') return get_sprite_color({0}).g",
+            sprite
+        );
+        assert_eq!(script_engine.run_repl(&code, None, &mut world, &input), [ReplLine::Value("0.5".to_string())]);
+        assert!(update_color_fades(&mut world, 0.5).is_empty());
+        assert_eq!(world.sprite_draw_color(sprite, &world.sprites[&sprite]), ecs::Color::rgba(1.0, 0.5, 0.0, 0.5));
+
+        // Finishing runs on_complete once
+        let completions = update_color_fades(&mut world, 0.6);
+        assert_eq!(completions, [(sprite, FadeCompleteAction::Destroy)]);
+        script_system::apply_fade_completions(&mut script_engine, &mut world, completions);
+        assert!(world.entities().is_empty());
+        assert!(world.color_fades.is_empty());
+        assert!(update_color_fades(&mut world, 0.1).is_empty());
+    }
+}
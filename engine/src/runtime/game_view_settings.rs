@@ -18,6 +18,7 @@ pub enum GameViewResolution {
     IPhone14,    // 1170x2532 (19.5:9)
     IPhone14Pro, // 1179x2556 (19.5:9)
     IPhoneSE,    // 750x1334 (16:9)
+    PhonePortrait, // 390x844 (logical size of a modern phone)
     Pixel7,      // 1080x2400 (20:9)
     GalaxyS23,   // 1080x2340 (19.5:9)
     
@@ -38,6 +39,26 @@ pub enum GameViewResolution {
 }
 
 impl GameViewResolution {
+    /// Every fixed-size preset, grouped by category
+    pub const PRESETS: [GameViewResolution; 16] = [
+        GameViewResolution::FullHD,
+        GameViewResolution::HD,
+        GameViewResolution::WXGA,
+        GameViewResolution::QHD,
+        GameViewResolution::UHD4K,
+        GameViewResolution::IPhone14,
+        GameViewResolution::IPhone14Pro,
+        GameViewResolution::IPhoneSE,
+        GameViewResolution::PhonePortrait,
+        GameViewResolution::Pixel7,
+        GameViewResolution::GalaxyS23,
+        GameViewResolution::IPhone14Landscape,
+        GameViewResolution::IPhone14ProLandscape,
+        GameViewResolution::Pixel7Landscape,
+        GameViewResolution::IPadPro,
+        GameViewResolution::IPadAir,
+    ];
+
     pub fn get_size(&self) -> (u32, u32) {
         match self {
             // PC
@@ -51,6 +72,7 @@ impl GameViewResolution {
             GameViewResolution::IPhone14 => (1170, 2532),
             GameViewResolution::IPhone14Pro => (1179, 2556),
             GameViewResolution::IPhoneSE => (750, 1334),
+            GameViewResolution::PhonePortrait => (390, 844),
             GameViewResolution::Pixel7 => (1080, 2400),
             GameViewResolution::GalaxyS23 => (1080, 2340),
             
@@ -84,6 +106,7 @@ impl GameViewResolution {
             GameViewResolution::IPhone14 => "iPhone 14 (1170x2532)",
             GameViewResolution::IPhone14Pro => "iPhone 14 Pro (1179x2556)",
             GameViewResolution::IPhoneSE => "iPhone SE (750x1334)",
+            GameViewResolution::PhonePortrait => "Phone (390x844)",
             GameViewResolution::Pixel7 => "Pixel 7 (1080x2400)",
             GameViewResolution::GalaxyS23 => "Galaxy S23 (1080x2340)",
             GameViewResolution::IPhone14Landscape => "iPhone 14 Landscape",
@@ -103,7 +126,7 @@ impl GameViewResolution {
             GameViewResolution::UHD4K => "PC",
             
             GameViewResolution::IPhone14 | GameViewResolution::IPhone14Pro | 
-            GameViewResolution::IPhoneSE | GameViewResolution::Pixel7 | 
+            GameViewResolution::IPhoneSE | GameViewResolution::PhonePortrait | GameViewResolution::Pixel7 | 
             GameViewResolution::GalaxyS23 => "Mobile (Portrait)",
             
             GameViewResolution::IPhone14Landscape | GameViewResolution::IPhone14ProLandscape | 
@@ -130,6 +153,10 @@ pub struct GameViewSettings {
     pub scale: f32,  // Scale factor (0.1 to 1.0)
     pub show_safe_area: bool,  // Show safe area guides
     pub show_resolution_info: bool,  // Show resolution info overlay
+    #[serde(default)]
+    pub show_performance_hud: bool,  // Show FPS, draw calls and the slowest scripts while playing
+    #[serde(default)]
+    pub show_debug_draws: bool,  // Draw the debug lines (script, physics) over the game view too
     pub background_color: [f32; 4],  // Background color outside game view
 }
 
@@ -140,6 +167,8 @@ impl Default for GameViewSettings {
             scale: 1.0,
             show_safe_area: false,
             show_resolution_info: true,
+            show_performance_hud: false,
+            show_debug_draws: false,
             background_color: [0.1, 0.1, 0.1, 1.0],
         }
     }
@@ -4,12 +4,20 @@ pub mod renderer;
 pub mod render_system;
 pub mod physics_system;
 pub mod script_system;
+pub mod animation_system;
 pub mod systems;
 pub mod ldtk_runtime;
 pub mod game_view_settings;
 pub mod transform_system;
+pub mod scene_manager;
+pub mod scene_preload;
+pub mod window_system;
+#[cfg(test)]
+mod test_support;
 
 // Re-exports for convenience
 pub use renderer::render_game_view;
 pub use ldtk_runtime::LdtkRuntime;
 pub use game_view_settings::{GameViewSettings, GameViewResolution};
+pub use scene_manager::{SceneManager, SceneTransition};
+pub use window_system::WindowController;
@@ -1,7 +1,68 @@
 // Physics system for runtime
 use ecs::World;
+use engine_core::project_settings::PhysicsSettings;
 use physics::PhysicsWorld;
+use physics::rapier_backend::RapierPhysicsWorld;
+use script::ScriptEngine;
 
 pub fn update_physics(physics_world: &mut PhysicsWorld, world: &mut World, delta_time: f32) {
     physics_world.step(delta_time, world);
-}
\ No newline at end of file
+}
+
+/// Apply the project's Physics settings (settings.json) to the simple backend
+pub fn apply_physics_settings(physics_world: &mut PhysicsWorld, settings: &PhysicsSettings) {
+    physics_world.set_gravity(settings.gravity);
+    physics_world.set_world_bounds(settings.world_bounds.enabled.then_some((settings.world_bounds.min, settings.world_bounds.max)));
+}
+
+/// Apply the project's Physics settings (settings.json) to the Rapier backend
+pub fn apply_rapier_physics_settings(physics_world: &mut RapierPhysicsWorld, settings: &PhysicsSettings) {
+    physics_world.set_gravity(settings.gravity);
+}
+
+/// Use the loaded scene's gravity (Scene Settings), or the project's when the scene
+/// doesn't override it. Call after each scene load.
+pub fn apply_scene_gravity(physics_world: &mut PhysicsWorld, settings: &PhysicsSettings, world: &World) {
+    physics_world.set_gravity(world.scene_settings.gravity_or(settings.gravity));
+}
+
+/// Rapier version of `apply_scene_gravity`
+pub fn apply_rapier_scene_gravity(physics_world: &mut RapierPhysicsWorld, settings: &PhysicsSettings, world: &World) {
+    physics_world.set_gravity(world.scene_settings.gravity_or(settings.gravity));
+}
+
+/// Apply this frame's Physics.move_and_slide calls with the simple backend; scripts read the
+/// floor/wall/ceiling flags through Physics.is_on_floor etc.
+pub fn apply_character_moves(script_engine: &ScriptEngine, world: &mut World) {
+    for command in script_engine.take_character_moves() {
+        script_engine.set_character_move(command.entity, PhysicsWorld::move_and_slide(world, command.entity, command.delta));
+    }
+}
+
+/// Apply this frame's Physics.move_and_slide calls with Rapier's character controller
+pub fn apply_rapier_character_moves(physics_world: &mut RapierPhysicsWorld, script_engine: &ScriptEngine, world: &mut World) {
+    for command in script_engine.take_character_moves() {
+        script_engine.set_character_move(command.entity, physics_world.move_and_slide(world, command.entity, command.delta));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scene_gravity_overrides_the_project_gravity() {
+        let mut physics = PhysicsWorld::new();
+        let project = PhysicsSettings::default();
+        let mut world = World::new();
+
+        world.scene_settings.gravity = Some(20.0);
+        apply_scene_gravity(&mut physics, &project, &world);
+        assert_eq!(physics.gravity, 20.0);
+
+        // A scene without an override goes back to the project's
+        world.scene_settings = ecs::SceneSettings::default();
+        apply_scene_gravity(&mut physics, &project, &world);
+        assert_eq!(physics.gravity, project.gravity);
+    }
+}
@@ -1,12 +1,17 @@
-use ecs::World;
-use render::{BatchRenderer, MeshRenderer, TilemapRenderer, TextureManager, CameraBinding, LightBinding, Mesh, PbrMaterialUniform, ObjectUniform, PbrMaterial};
+use ecs::{EntityOrdered, World};
+use ecs::sorting::{SortKey, SortingLayers};
+use render::{BatchRenderer, MeshRenderer, TilemapRenderer, TilemapChunkMesh, TextureManager, CameraBinding, LightBinding, Mesh, PbrMaterialUniform, ObjectUniform, PbrMaterial, GpuTimer, RenderModule};
 use glam::{Vec3, Quat, Mat4};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use wgpu::util::DeviceExt;
 use crate::assets::model_manager::get_model_manager;
 use anyhow;
 
+/// Cached chunk meshes of one tilemap: chunk -> (mesh, chunk revision it was built from)
+pub type TilemapChunks = HashMap<(u32, u32), (TilemapChunkMesh, u64)>;
+
 // Simple mesh cache to avoid regenerating meshes every frame
 // Render Cache Struct to replace static mut (Global State)
 pub struct RenderCache {
@@ -15,8 +20,11 @@ pub struct RenderCache {
     pub material_assets: HashMap<String, Arc<PbrMaterial>>,
     pub material_bind_group_cache: HashMap<String, wgpu::BindGroup>,
     
-    // Tilemap Cache: Entity -> (Vertex Buffer, Index Buffer, Index Count)
-    pub tilemap_cache: HashMap<ecs::Entity, (wgpu::Buffer, wgpu::Buffer, u32)>,
+    // Tilemap Cache: Entity -> Chunk -> (Chunk Mesh, Chunk Revision)
+    pub tilemap_cache: HashMap<ecs::Entity, TilemapChunks>,
+
+    // Global clock for animated tiles: every chunk shows the same frame at the same time
+    pub tile_animation_clock: Instant,
     
     // Entity Object Uniform Cache: Entity ID -> (Buffer, BindGroup)
     pub entity_cache: HashMap<u32, (wgpu::Buffer, wgpu::BindGroup)>,
@@ -36,6 +44,7 @@ impl RenderCache {
             material_assets: HashMap::new(),
             material_bind_group_cache: HashMap::new(),
             tilemap_cache: HashMap::new(),
+            tile_animation_clock: Instant::now(),
             entity_cache: HashMap::new(),
             entity_material_cache: HashMap::new(),
             model_node_cache: HashMap::new(),
@@ -169,6 +178,7 @@ pub fn post_process_asset_meshes(
 pub fn render_game_world<'a>(
     render_cache: &'a mut RenderCache,
     world: &'a World,
+    sorting_layers: &SortingLayers,
     tilemap_renderer: &'a TilemapRenderer,
     batch_renderer: &'a mut BatchRenderer,
     mesh_renderer: &'a mut MeshRenderer,
@@ -180,7 +190,10 @@ pub fn render_game_world<'a>(
     _screen_size: winit::dpi::PhysicalSize<u32>, // Unused now that projection is passed in
     render_pass: &mut wgpu::RenderPass<'a>,
     view_proj: Mat4, // <--- Added Argument
+    mut gpu_timer: Option<&mut GpuTimer>,
 ) {
+    profiler::profile_scope!("render.game_world");
+
     // 0. Update Light (Simple directional light for now)
     // TODO: Find Light component in world
     // Default light at (2.0, 5.0, 2.0) with white color
@@ -189,6 +202,7 @@ pub fn render_game_world<'a>(
     // Ensure default textures exist before any immutable borrows (Fixes E0502)
     let _ = texture_manager.get_white_texture(device, queue);
     let _ = texture_manager.get_normal_texture(device, queue);
+    let _ = texture_manager.get_missing_texture(device, queue);
 
     // 1. Update Camera Uniform for Sprites
     // REMOVED: batch_renderer.update_camera(queue, view_proj);
@@ -204,30 +218,47 @@ pub fn render_game_world<'a>(
     }
 
     // ------------------------------------------------------------------------
-    // 0. Render Tilemaps (Background)
+    // 0. Tilemap Chunk Meshes (drawn in the 2D sort below)
     // ------------------------------------------------------------------------
     
-    // Pass 1: Ensure geometry is cached
+    // Pass 1: Ensure geometry is cached, rebuilding only chunks edited since they were built,
+    // and move animated tiles to their current frame in place
+    let tile_time = render_cache.tile_animation_clock.elapsed().as_secs_f32();
     for (entity, tilemap) in &world.tilemaps {
         if !tilemap.visible {
             continue;
         }
 
-        if !render_cache.tilemap_cache.contains_key(entity) {
-             // Find corresponding Tileset to generate mesh
-            let tileset = world.tilesets.values().find(|ts| ts.texture_id == tilemap.tileset_id);
-            if let Some(tileset) = tileset {
-                // Get Transform for offset (default to Zero if missing)
-                 let pos = if let Some(transform) = world.transforms.get(entity) {
-                    glam::Vec3::from(transform.position)
-                } else {
-                    glam::Vec3::ZERO
-                };
-                
-                // Prepare Mesh (Geometry) with Scale and Offset
-                // Default pixels_per_unit = 8.0 (1 tile = 1 unit)
-                let mesh_data = tilemap_renderer.prepare_mesh(device, tilemap, tileset, pos, 8.0);
-                render_cache.tilemap_cache.insert(*entity, mesh_data);
+         // Find corresponding Tileset to generate mesh
+        let tileset = world.tilesets.iter_sorted().into_iter().map(|(_, ts)| ts).find(|ts| ts.texture_id == tilemap.tileset_id);
+        if let Some(tileset) = tileset {
+            // Get Transform for offset (default to Zero if missing)
+             let pos = if let Some(transform) = world.world_transform(*entity) {
+                glam::Vec3::from(transform.position)
+            } else {
+                glam::Vec3::ZERO
+            };
+
+            let chunks = render_cache.tilemap_cache.entry(*entity).or_default();
+            let (chunks_x, chunks_y) = tilemap.chunk_count();
+            for chunk_y in 0..chunks_y {
+                for chunk_x in 0..chunks_x {
+                    let chunk = (chunk_x, chunk_y);
+                    let revision = tilemap.chunk_revision(chunk);
+                    if let Some((mesh, cached_revision)) = chunks.get_mut(&chunk) {
+                        if *cached_revision == revision {
+                            tilemap_renderer.animate_chunk(queue, tileset, mesh, tile_time);
+                            continue;
+                        }
+                    }
+
+                    // Prepare Mesh (Geometry) with Scale and Offset
+                    // Default pixels_per_unit = 8.0 (1 tile = 1 unit)
+                    let mesh = tilemap_renderer.prepare_chunk_mesh(
+                        device, tilemap, tileset, pos, 8.0, chunk, ecs::TILEMAP_CHUNK_SIZE, tile_time,
+                    );
+                    chunks.insert(chunk, (mesh, revision));
+                }
             }
         }
     }
@@ -238,13 +269,15 @@ pub fn render_game_world<'a>(
     
     // 1. Prepare/Sort Meshes
     // Collect and sort meshes by Z position (back to front for transparency, front to back for opaque)
-    let mut mesh_entities: Vec<_> = world.meshes.iter().collect();
+    let mut mesh_entities: Vec<_> = world.meshes.iter()
+        .filter(|(entity, _)| world.is_active_in_hierarchy(**entity))
+        .collect();
     
-    // Sort by Z position (front to back for better depth testing)
+    // Sort by Z position (front to back for better depth testing), equal Z by entity id
     mesh_entities.sort_by(|a, b| {
         let z_a = world.transforms.get(a.0).map(|t| t.position[2]).unwrap_or(0.0);
         let z_b = world.transforms.get(b.0).map(|t| t.position[2]).unwrap_or(0.0);
-        z_a.partial_cmp(&z_b).unwrap_or(std::cmp::Ordering::Equal)
+        z_a.partial_cmp(&z_b).unwrap_or(std::cmp::Ordering::Equal).then(a.0.cmp(b.0))
     });
 
     // 2. Update Procedural Mesh Cache
@@ -485,124 +518,112 @@ pub fn render_game_world<'a>(
     // RENDER PHASE (Immutable Access)
     // ------------------------------------------------------------------------
 
-    // Pass 2: Render
-    for (entity, tilemap) in &world.tilemaps {
-        if !tilemap.visible {
-            continue;
-        }
-
-        if let Some((vertex_buffer, index_buffer, index_count)) = render_cache.tilemap_cache.get(entity) {
-            // Find tileset to get texture
-            let tileset = world.tilesets.values().find(|ts| ts.texture_id == tilemap.tileset_id);
-            if let Some(tileset) = tileset {
-                if let Some(texture) = texture_manager.get_texture(&tileset.texture_path) {
-                     tilemap_renderer.render(
-                        render_pass,
-                        vertex_buffer,
-                        index_buffer,
-                        *index_count,
-                        texture,
-                        &camera_binding.bind_group
-                    );
-                } else {
-                    static mut TEX_FAIL_LOGGED: bool = false;
-                    unsafe {
-                        if !TEX_FAIL_LOGGED {
-                            println!("DEBUG: Rendering FAILED for tilemap {:?}. Texture '{}' not found in TextureManager. Make sure it is loaded.", entity, tileset.texture_path);
-                            TEX_FAIL_LOGGED = true;
-                        }
-                    }
-                }
-            } else {
-                static mut TS_FAIL_LOGGED: bool = false;
-                unsafe {
-                    if !TS_FAIL_LOGGED {
-                        println!("DEBUG: Rendering FAILED for tilemap {:?}. Tileset '{}' not found in World.", entity, tilemap.tileset_id);
-                        TS_FAIL_LOGGED = true;
-                    }
-                }
-            }
-        }
-    }
-
     // ------------------------------------------------------------------------
-    // 2. Sort/Group Sprites by Texture and Unity-style Sorting
+    // 2. Sort Tilemaps and Sprites into one 2D Draw Order
     // ------------------------------------------------------------------------
-    // To minimize draw calls and state changes, we group sprites that share the same texture.
+    // Sorting Layer (project order) -> Order in Layer -> Z -> Entity, shared with sprite
+    // picking (ecs::sorting) so tile layers interleave with sprites and the topmost hit is
+    // the sprite drawn last. Consecutive sprites sharing a texture and material go into
+    // one batch.
 
     // Start by clearing transient buffers from previous frame
     batch_renderer.start_frame();
 
-    // Debug: Count sprites (commented out - too spammy)
-    // let sprite_count = world.sprites.len();
-    // if sprite_count > 0 {
-    //     println!("DEBUG: Rendering {} sprites", sprite_count);
-    // }
-
-    struct SpriteInfo<'a> {
-        _entity: ecs::Entity,
-        sprite: &'a ecs::Sprite,
-        transform: &'a ecs::Transform,
+    enum Drawable2D<'a> {
+        Tilemap(&'a ecs::Entity, &'a ecs::Tilemap),
+        /// Sprite, its texture rect and whether that is packed rotated (named frame
+        /// resolved), draw color (fade applied), material and world transform
+        Sprite(&'a ecs::Sprite, Option<[u32; 4]>, bool, ecs::Color, Option<&'a ecs::SpriteMaterial>, ecs::Transform),
     }
 
-    let mut visible_sprites = Vec::new();
+    let mut drawables = Vec::new();
+
+    // Scene Settings can hide whole sorting layers
+    let scene = &world.scene_settings;
+
+    for (entity, tilemap) in &world.tilemaps {
+        if !tilemap.visible || !world.is_active_in_hierarchy(*entity) {
+            continue;
+        }
+        let layer = world.tilemap_renderers.get(entity).map_or(ecs::sorting::DEFAULT_SORTING_LAYER, |r| r.sorting_layer.as_str());
+        if !scene.is_layer_visible(layer) {
+            continue;
+        }
+        let z = world.world_transform(*entity).map_or(0.0, |t| t.position[2]);
+        let key = SortKey::tilemap(sorting_layers, *entity, world.tilemap_renderers.get(entity), z);
+        drawables.push((key, Drawable2D::Tilemap(entity, tilemap)));
+    }
 
     for (entity, sprite) in &world.sprites {
+        if !world.is_active_in_hierarchy(*entity) || !scene.is_layer_visible(&sprite.sorting_layer) {
+            continue;
+        }
         // TODO: Add visible field to Sprite component
-        if let Some(transform) = world.transforms.get(entity) {
-             visible_sprites.push(SpriteInfo {
-                 _entity: *entity,
-                 sprite,
-                 transform,
-             });
+        if let Some(transform) = world.world_transform(*entity) {
+            let key = SortKey::sprite(sorting_layers, *entity, sprite, &transform);
+            let sprite_rect = world.sprite_rect_for(*entity, sprite);
+            let rect_rotated = world.sprite_rect_rotated_for(*entity, sprite);
+            let color = world.sprite_draw_color(*entity, sprite);
+            drawables.push((key, Drawable2D::Sprite(sprite, sprite_rect, rect_rotated, color, world.sprite_materials.get(entity), transform)));
         }
     }
-    
-    // Sort logic: Sorting Layer -> Order in Layer -> Z Depth (Back to Front)
-    visible_sprites.sort_by(|a, b| {
-        // 1. Sorting Layer (String comparison for now)
-        let layer_cmp = a.sprite.sorting_layer.cmp(&b.sprite.sorting_layer);
-        if layer_cmp != std::cmp::Ordering::Equal { return layer_cmp; }
-        
-        // 2. Order in Layer (Higher order = On top)
-        let order_cmp = a.sprite.order_in_layer.cmp(&b.sprite.order_in_layer);
-        if order_cmp != std::cmp::Ordering::Equal { return order_cmp; }
-        
-        // 3. Z-Depth (Back to Front) aka Painter's Algorithm
-        // For standard 2D with -Z camera, Back (-Z) to Front (+Z)?
-        // Wait, if Camera is at +Z looking at -Z.
-        // Far (-100) -> Near (0).
-        // Standard sort is Ascending. (-100, -99, ...).
-        // Draw -100 first, then -99. 
-        // So Ascending Z is correct if "Lower is Farther".
-        a.transform.position[2].partial_cmp(&b.transform.position[2]).unwrap_or(std::cmp::Ordering::Equal)
-    });
 
+    drawables.sort_by_key(|(key, _)| *key);
 
+    // 3. Prepare Batches: each run of sprites between two tilemaps is a range of batches
+    enum Segment2D<'a> {
+        Sprites(std::ops::Range<usize>),
+        Tilemap(&'a ecs::Entity, &'a ecs::Tilemap),
+    }
 
-    // 3. Prepare Batches
+    let mut segments = Vec::new();
+    let mut run_start = 0;
     let mut current_texture_id = String::new();
+    let mut current_material: Option<&ecs::SpriteMaterial> = None;
     
     batch_renderer.begin_frame(); 
     
-    for info in visible_sprites {
-        // Check for texture change
-        if info.sprite.texture_id != current_texture_id {
+    for (_, drawable) in drawables {
+        let (sprite, sprite_rect, rect_rotated, color, material, transform) = match drawable {
+            Drawable2D::Tilemap(entity, tilemap) => {
+                if !current_texture_id.is_empty() {
+                    batch_renderer.finish_material_batch(device, std::mem::take(&mut current_texture_id), current_material);
+                }
+                let run_end = batch_renderer.batch_count();
+                if run_end > run_start {
+                    segments.push(Segment2D::Sprites(run_start..run_end));
+                }
+                run_start = run_end;
+                segments.push(Segment2D::Tilemap(entity, tilemap));
+                continue;
+            }
+            Drawable2D::Sprite(sprite, sprite_rect, rect_rotated, color, material, transform) => {
+                (sprite, sprite_rect, rect_rotated, color, material, transform)
+            }
+        };
+        let transform = &transform;
+
+        // Sprites whose texture file is missing draw the magenta placeholder
+        // (render textures may just not be registered yet)
+        let missing = !sprite.texture_id.is_empty()
+            && texture_manager.get_texture(&sprite.texture_id).is_none()
+            && ecs::RenderTextureAsset::name_from_texture_id(&sprite.texture_id).is_none();
+        let texture_id = if missing { render::texture::MISSING_TEXTURE_ID } else { sprite.texture_id.as_str() };
+
+        // Check for texture or material change
+        if texture_id != current_texture_id || material != current_material {
             if !current_texture_id.is_empty() {
                 // Finish previous batch
-                batch_renderer.finish_batch(device, current_texture_id.clone());
+                batch_renderer.finish_material_batch(device, current_texture_id.clone(), current_material);
             }
-            current_texture_id = info.sprite.texture_id.clone();
+            current_texture_id = texture_id.to_string();
+            current_material = material;
         }
         
-        if let Some(texture) = texture_manager.get_texture(&info.sprite.texture_id) {
+        if let Some(texture) = texture_manager.get_texture(texture_id) {
             let tex_w = texture.width as f32;
             let tex_h = texture.height as f32;
 
-            // Draw Sprite
-            let sprite = info.sprite;
-            let transform = info.transform;
-
             // Debug: Print first sprite being rendered
             static mut FIRST_SPRITE_LOGGED: bool = false;
             unsafe {
@@ -612,11 +633,16 @@ pub fn render_game_world<'a>(
                 }
             }
             
-            let rect = sprite.sprite_rect.unwrap_or([0, 0, sprite.width as u32, sprite.height as u32]);
-            let u_min = rect[0] as f32 / tex_w;
-            let v_min = rect[1] as f32 / tex_h;
-            let u_scale = rect[2] as f32 / tex_w;
-            let v_scale = rect[3] as f32 / tex_h;
+            // Render textures are shown whole, whatever size they currently have
+            let default_rect = if missing || ecs::RenderTextureAsset::name_from_texture_id(&sprite.texture_id).is_some() {
+                [0, 0, texture.width, texture.height]
+            } else {
+                [0, 0, sprite.width as u32, sprite.height as u32]
+            };
+            let rect = if missing { default_rect } else { sprite_rect.unwrap_or(default_rect) };
+            let rotated = !missing && sprite_rect.is_some() && rect_rotated;
+            // Flips mirror the texture inside the rect, so atlas neighbours never show
+            let uv = ecs::SpriteUv::from_rect(rect, [tex_w, tex_h], sprite.flip_x, sprite.flip_y, rotated);
             
             let pos = Vec3::new(transform.position[0], transform.position[1], transform.position[2]);
             
@@ -639,18 +665,77 @@ pub fn render_game_world<'a>(
             let world_height = sprite.height / sprite.pixels_per_unit;
             let scale = Vec3::new(transform.scale[0] * world_width, transform.scale[1] * world_height, 1.0);
 
-            batch_renderer.draw_sprite(pos, rot, scale, sprite.color, [u_min, v_min], [u_scale, v_scale]);
+            // The entity position is the sprite's pivot; the quad is centered, so offset it
+            let [pivot_x, pivot_y] = sprite.pivot.unwrap_or(texture.options.pivot);
+            let pivot_x = if sprite.flip_x { 1.0 - pivot_x } else { pivot_x };
+            let pivot_y = if sprite.flip_y { 1.0 - pivot_y } else { pivot_y };
+            let pos = pos + rot * Vec3::new((0.5 - pivot_x) * scale.x, (0.5 - pivot_y) * scale.y, 0.0);
+
+            batch_renderer.draw_sprite(pos, rot, scale, color.to_array(), uv.offset, uv.scale, uv.rotated);
         }
     }
     
     // Flush final batch
     if !current_texture_id.is_empty() {
-         batch_renderer.finish_batch(device, current_texture_id);
+         batch_renderer.finish_material_batch(device, current_texture_id, current_material);
+    }
+    let run_end = batch_renderer.batch_count();
+    if run_end > run_start {
+        segments.push(Segment2D::Sprites(run_start..run_end));
     }
 
-    // 4. Render All Batches
+    // 4. Render Tilemaps and Sprite Batches in Order
     // Pass external camera binding (Scene Camera or Game Camera)
-    batch_renderer.render(render_pass, texture_manager, &camera_binding.bind_group);
+    if let Some(timer) = gpu_timer.as_deref_mut() {
+        timer.begin_span(render_pass, "render.2d");
+    }
+    let batch_renderer: &'a BatchRenderer = batch_renderer;
+    for segment in segments {
+        match segment {
+            Segment2D::Sprites(range) => {
+                batch_renderer.render_range(render_pass, texture_manager, &camera_binding.bind_group, range);
+            }
+            Segment2D::Tilemap(entity, tilemap) => {
+                if let Some(chunks) = render_cache.tilemap_cache.get(entity) {
+                    // Find tileset to get texture
+                    let tileset = world.tilesets.iter_sorted().into_iter().map(|(_, ts)| ts).find(|ts| ts.texture_id == tilemap.tileset_id);
+                    if let Some(tileset) = tileset {
+                        if let Some(texture) = texture_manager.get_texture(&tileset.texture_path) {
+                            for (mesh, _) in chunks.values() {
+                                tilemap_renderer.render(
+                                    render_pass,
+                                    &mesh.vertex_buffer,
+                                    &mesh.index_buffer,
+                                    mesh.index_count,
+                                    texture,
+                                    &camera_binding.bind_group
+                                );
+                            }
+                        } else {
+                            static mut TEX_FAIL_LOGGED: bool = false;
+                            unsafe {
+                                if !TEX_FAIL_LOGGED {
+                                    println!("DEBUG: Rendering FAILED for tilemap {:?}. Texture '{}' not found in TextureManager. Make sure it is loaded.", entity, tileset.texture_path);
+                                    TEX_FAIL_LOGGED = true;
+                                }
+                            }
+                        }
+                    } else {
+                        static mut TS_FAIL_LOGGED: bool = false;
+                        unsafe {
+                            if !TS_FAIL_LOGGED {
+                                println!("DEBUG: Rendering FAILED for tilemap {:?}. Tileset '{}' not found in World.", entity, tilemap.tileset_id);
+                                TS_FAIL_LOGGED = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if let Some(timer) = gpu_timer {
+        timer.end_span(render_pass);
+    }
 
 
 
@@ -736,6 +821,9 @@ pub fn render_game_world<'a>(
     // Pass B: Render (Immutable access)
     // We traverse again to submit draw calls
     for (entity, model_3d) in &world.model_3ds {
+        if !world.is_active_in_hierarchy(*entity) {
+            continue;
+        }
         if let Some(xsg) = model_manager.get_model(&model_3d.asset_id) {
              let root_transform = if let Some(global) = world.global_transforms.get(entity) {
                  Mat4::from_cols_array(&global.matrix)
@@ -812,3 +900,119 @@ pub fn render_game_world<'a>(
         }
     }
 }
+
+/// View and projection matrices for a game camera, as the Game View computes them
+pub fn camera_view_projection(camera: &ecs::Camera, transform: &ecs::Transform, aspect: f32) -> (Mat4, Mat4) {
+    ecs::picking::camera_view_projection(camera, transform, aspect)
+}
+
+/// Color a camera clears to: its own background with SolidColor clear flags, otherwise
+/// the scene's background (Scene Settings) when it sets one
+pub fn camera_background(camera: &ecs::Camera, scene: &ecs::SceneSettings) -> wgpu::Color {
+    let color = match camera.clear_flags {
+        ecs::CameraClearFlags::SolidColor => camera.background_color,
+        _ => scene.background_color.unwrap_or(camera.background_color),
+    };
+    wgpu_color(color)
+}
+
+/// Color the game view clears to without a camera: the scene's background, else the
+/// project's clear color
+pub fn scene_background(scene: &ecs::SceneSettings, project_clear_color: [f32; 4]) -> wgpu::Color {
+    wgpu_color(scene.background_or(project_clear_color.into()))
+}
+
+fn wgpu_color(color: ecs::Color) -> wgpu::Color {
+    wgpu::Color { r: color.r as f64, g: color.g as f64, b: color.b as f64, a: color.a as f64 }
+}
+
+/// Render every active camera with a `target_texture` into that render texture, lowest
+/// depth first (then lowest entity id). Call before drawing anything that samples the textures. Each camera is
+/// submitted separately because they all share `renderer.camera_binding`.
+pub fn render_camera_targets(render_cache: &mut RenderCache, world: &World, sorting_layers: &SortingLayers, renderer: &mut RenderModule) {
+    profiler::profile_scope!("render.render_textures");
+
+    let mut cameras: Vec<_> = world.cameras.iter()
+        .filter(|(entity, _)| world.is_active_in_hierarchy(**entity))
+        .filter_map(|(entity, camera)| {
+            let name = camera.target_texture.as_deref()?;
+            world.world_transform(*entity).map(|transform| (*entity, name, camera, transform))
+        })
+        .collect();
+    cameras.sort_by_key(|(entity, _, camera, _)| (camera.depth, *entity));
+
+    for (_, name, camera, transform) in cameras {
+        // Unknown names (asset deleted or not loaded yet) have nothing to draw into
+        let Some((color_view, depth_view)) = renderer.render_textures.attachments(&renderer.device, name)
+            .map(|(color, depth)| (color.clone(), depth.clone()))
+        else {
+            continue;
+        };
+        let Some(target) = renderer.render_textures.get(name) else {
+            continue;
+        };
+        let (width, height) = (target.texture.width(), target.texture.height());
+
+        let (view, projection) = camera_view_projection(camera, &transform, width as f32 / height as f32);
+        renderer.camera_binding.update(&renderer.queue, view, projection, Vec3::from(transform.position));
+
+        let background = camera_background(camera, &world.scene_settings);
+        let (color_load, depth_load) = match camera.clear_flags {
+            ecs::CameraClearFlags::SolidColor | ecs::CameraClearFlags::Skybox => {
+                (wgpu::LoadOp::Clear(background), wgpu::LoadOp::Clear(1.0))
+            }
+            ecs::CameraClearFlags::DepthOnly => (wgpu::LoadOp::Load, wgpu::LoadOp::Clear(1.0)),
+            ecs::CameraClearFlags::DontClear => (wgpu::LoadOp::Load, wgpu::LoadOp::Load),
+        };
+
+        // A texture can't be sampled while it is being drawn to, so sprites showing
+        // this one are skipped for this camera
+        let texture_id = ecs::RenderTextureAsset::texture_id(name);
+        let sampled = renderer.texture_manager.remove_texture(&texture_id);
+
+        let mut encoder = renderer.device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Render Texture Encoder"),
+        });
+        {
+            let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Render Texture Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &color_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations { load: color_load, store: wgpu::StoreOp::Store },
+                    depth_slice: None,
+                })],
+                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                    view: &depth_view,
+                    depth_ops: Some(wgpu::Operations { load: depth_load, store: wgpu::StoreOp::Store }),
+                    stencil_ops: None,
+                }),
+                occlusion_query_set: None,
+                timestamp_writes: renderer.gpu_timer.pass_timestamp_writes("render.render_texture"),
+            });
+
+            render_game_world(
+                render_cache,
+                world,
+                sorting_layers,
+                &renderer.tilemap_renderer,
+                &mut renderer.batch_renderer,
+                &mut renderer.mesh_renderer,
+                &renderer.camera_binding,
+                &renderer.light_binding,
+                &mut renderer.texture_manager,
+                &renderer.queue,
+                &renderer.device,
+                winit::dpi::PhysicalSize::new(width, height),
+                &mut rpass,
+                projection * view,
+                Some(&mut renderer.gpu_timer),
+            );
+        }
+        renderer.queue.submit(std::iter::once(encoder.finish()));
+
+        if let Some(texture) = sampled {
+            renderer.texture_manager.insert_texture(&texture_id, texture);
+        }
+    }
+}
@@ -4,8 +4,9 @@
 //! This is separate from the editor's scene view.
 
 use ecs::{World, Entity, Camera, CameraProjection};
+use engine_core::project_settings::AspectPolicy;
 use egui;
-use crate::texture_manager::TextureManager;
+use crate::texture_manager::{pivot_rect, sprite_quad_shape, TextureManager};
 use glam::{Vec3, Mat4, Quat, EulerRot};
 
 /// Render the game view using the main camera
@@ -15,6 +16,7 @@ pub fn render_game_view(
     _texture_manager: &mut TextureManager,
     ui_manager: Option<&mut crate::ui_manager::UIManager>,
     game_view_settings: Option<&crate::runtime::GameViewSettings>,
+    aspect: &AspectPolicy,
 ) {
     let available_rect = ui.available_rect_before_wrap();
     
@@ -38,7 +40,8 @@ pub fn render_game_view(
         }
     }
     
-    let rect = game_rect;
+    // Letterbox/pillarbox bars; the world is drawn into the rect between them
+    let rect = letterbox(ui, game_rect, aspect);
     let painter = ui.painter_at(rect);
 
     // Find the main camera (first active camera with lowest depth)
@@ -91,6 +94,29 @@ pub fn render_game_view(
     }
 }
 
+/// Paint the bars `aspect` puts around the game inside `area` and return the rect
+/// the game draws in
+pub fn letterbox(ui: &egui::Ui, area: egui::Rect, aspect: &AspectPolicy) -> egui::Rect {
+    let [x, y, width, height] = aspect.game_rect([area.min.x, area.min.y, area.width(), area.height()]);
+    let inner = egui::Rect::from_min_size(egui::pos2(x, y), egui::vec2(width, height));
+    if inner != area {
+        let [r, g, b, a] = aspect.bar_color.map(|c| (c.clamp(0.0, 1.0) * 255.0) as u8);
+        let color = egui::Color32::from_rgba_unmultiplied(r, g, b, a);
+        let painter = ui.painter();
+        for bar in [
+            egui::Rect::from_min_max(area.min, egui::pos2(area.max.x, inner.min.y)),
+            egui::Rect::from_min_max(egui::pos2(area.min.x, inner.max.y), area.max),
+            egui::Rect::from_min_max(egui::pos2(area.min.x, inner.min.y), egui::pos2(inner.min.x, inner.max.y)),
+            egui::Rect::from_min_max(egui::pos2(inner.max.x, inner.min.y), egui::pos2(area.max.x, inner.max.y)),
+        ] {
+            if bar.is_positive() {
+                painter.rect_filled(bar, 0.0, color);
+            }
+        }
+    }
+    inner
+}
+
 /// Render game view overlays (resolution info, safe area guides)
 fn render_game_view_overlays(
     ui: &mut egui::Ui,
@@ -182,12 +208,12 @@ fn render_game_view_overlays(
     }
 }
 
-/// Find the main camera (lowest depth, active)
+/// Find the main camera (lowest depth, then lowest entity id, active)
 fn find_main_camera(world: &World) -> Option<(Entity, &Camera, &ecs::Transform)> {
     let mut cameras: Vec<_> = world.cameras.iter()
         .filter_map(|(entity, camera)| {
-            // Check if entity is active
-            if world.active.get(entity).copied().unwrap_or(true) {
+            // Check if entity is active; render texture cameras don't draw to the screen
+            if camera.target_texture.is_none() && world.is_active_in_hierarchy(*entity) {
                 world.transforms.get(entity).map(|transform| (*entity, camera, transform))
             } else {
                 None
@@ -195,8 +221,8 @@ fn find_main_camera(world: &World) -> Option<(Entity, &Camera, &ecs::Transform)>
         })
         .collect();
 
-    // Sort by depth (lowest first)
-    cameras.sort_by_key(|(_, camera, _)| camera.depth);
+    // Sort by depth (lowest first); equal depths by entity id, like ecs::picking::main_camera
+    cameras.sort_by_key(|(entity, camera, _)| (camera.depth, *entity));
 
     cameras.into_iter().next()
 }
@@ -365,7 +391,7 @@ fn render_orthographic(
     // Render tilemaps first (background layers)
     for (&entity, tilemap) in &world.tilemaps {
         // Skip if not active or not visible
-        if !world.active.get(&entity).copied().unwrap_or(true) || !tilemap.visible {
+        if !world.is_active_in_hierarchy(entity) || !tilemap.visible {
             continue;
         }
 
@@ -390,7 +416,7 @@ fn render_orthographic(
     // Render all entities
     for (entity, transform) in &world.transforms {
         // Skip if not active
-        if !world.active.get(entity).copied().unwrap_or(true) {
+        if !world.is_active_in_hierarchy(*entity) {
             continue;
         }
 
@@ -423,11 +449,12 @@ fn render_orthographic(
                     
                     // Get sprite color if exists, otherwise white
                     let color = if let Some(sprite) = world.sprites.get(entity) {
+                        let tint = world.sprite_draw_color(*entity, sprite);
                         egui::Color32::from_rgba_unmultiplied(
-                            (sprite.color[0] * 255.0) as u8,
-                            (sprite.color[1] * 255.0) as u8,
-                            (sprite.color[2] * 255.0) as u8,
-                            (sprite.color[3] * 255.0) as u8,
+                            (tint.r * 255.0) as u8,
+                            (tint.g * 255.0) as u8,
+                            (tint.b * 255.0) as u8,
+                            (tint.a * 255.0) as u8,
                         )
                     } else {
                         egui::Color32::WHITE
@@ -436,25 +463,13 @@ fn render_orthographic(
                     // Try to load and render texture
                     let texture_path = std::path::Path::new(&sprite_sheet.texture_path);
                     if let Some(texture) = texture_manager.load_texture(ctx, &sprite_sheet.texture_id, texture_path) {
-                        // Calculate UV coordinates for the frame
-                        let uv_min = egui::pos2(
-                            frame.x as f32 / sprite_sheet.sheet_width as f32,
-                            frame.y as f32 / sprite_sheet.sheet_height as f32,
-                        );
-                        let uv_max = egui::pos2(
-                            (frame.x + frame.width) as f32 / sprite_sheet.sheet_width as f32,
-                            (frame.y + frame.height) as f32 / sprite_sheet.sheet_height as f32,
-                        );
+                        // The frame's UVs, mirrored by the sprite's flips like a single sprite
+                        let (flip_x, flip_y) = world.sprites.get(entity).map_or((false, false), |sprite| (sprite.flip_x, sprite.flip_y));
+                        let uv = frame.uv([sprite_sheet.sheet_width as f32, sprite_sheet.sheet_height as f32], flip_x, flip_y);
                         
                         let rect = egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), size);
                         
-                        // Draw the texture with UV coordinates
-                        painter.image(
-                            texture.id(),
-                            rect,
-                            egui::Rect::from_min_max(uv_min, uv_max),
-                            color,
-                        );
+                        painter.add(sprite_quad_shape(texture.id(), rect, &uv, color));
                     } else {
                         // Fallback: draw colored rectangle
                         painter.rect_filled(
@@ -484,54 +499,26 @@ fn render_orthographic(
                 world_width * transform_scale.x * zoom,
                 world_height * transform_scale.y * zoom
             );
+            let tint = world.sprite_draw_color(*entity, sprite);
             let color = egui::Color32::from_rgba_unmultiplied(
-                (sprite.color[0] * 255.0) as u8,
-                (sprite.color[1] * 255.0) as u8,
-                (sprite.color[2] * 255.0) as u8,
-                (sprite.color[3] * 255.0) as u8,
+                (tint.r * 255.0) as u8,
+                (tint.g * 255.0) as u8,
+                (tint.b * 255.0) as u8,
+                (tint.a * 255.0) as u8,
             );
 
             // Try to load and render texture
             if !sprite.texture_id.is_empty() {
                 let texture_path = std::path::Path::new(&sprite.texture_id);
-                if let Some(texture) = texture_manager.load_texture(ctx, &sprite.texture_id, texture_path) {
-                    // Render texture with color tint and flipping
-                    let mut mesh = egui::Mesh::with_texture(texture.id());
-
-                    let rect = egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), size);
-
-                    // Calculate UV coordinates based on sprite_rect (Unity-style)
-                    let (u_min_base, u_max_base, v_min_base, v_max_base) = if let Some(sprite_rect) = sprite.sprite_rect {
-                        // Use sprite rect to calculate UV coordinates
-                        let tex_size = texture.size();
-                        let tex_width = tex_size[0] as f32;
-                        let tex_height = tex_size[1] as f32;
-                        
-                        let u_min = sprite_rect[0] as f32 / tex_width;
-                        let v_min = sprite_rect[1] as f32 / tex_height;
-                        let u_max = (sprite_rect[0] + sprite_rect[2]) as f32 / tex_width;
-                        let v_max = (sprite_rect[1] + sprite_rect[3]) as f32 / tex_height;
-                        
-                        (u_min, u_max, v_min, v_max)
-                    } else {
-                        // Use full texture
-                        (0.0, 1.0, 0.0, 1.0)
-                    };
-
-                    // Apply flipping
-                    let (u_min, u_max) = if sprite.flip_x { (u_max_base, u_min_base) } else { (u_min_base, u_max_base) };
-                    let (v_min, v_max) = if sprite.flip_y { (v_max_base, v_min_base) } else { (v_min_base, v_max_base) };
-
-                    mesh.add_rect_with_uv(
-                        rect,
-                        egui::Rect::from_min_max(
-                            egui::pos2(u_min, v_min),
-                            egui::pos2(u_max, v_max),
-                        ),
-                        color,
-                    );
-
-                    painter.add(egui::Shape::mesh(mesh));
+                if let Some(texture) = texture_manager.load_texture(ctx, &sprite.texture_id, texture_path).cloned() {
+                    // The entity position is the sprite's pivot (sprite editor override, else the texture's import settings)
+                    let pivot = sprite.pivot.unwrap_or_else(|| texture_manager.sprite_pivot(&sprite.texture_id));
+                    let rect = pivot_rect(egui::pos2(screen_x, screen_y), size, pivot, sprite.flip_x, sprite.flip_y);
+
+                    // UVs from sprite_rect (Unity-style), flips mirrored inside it
+                    let tex_size = texture.size().map(|size| size as f32);
+                    let uv = world.sprite_uv_for(*entity, sprite, tex_size);
+                    painter.add(sprite_quad_shape(texture.id(), rect, &uv, color));
                 } else {
                     // Fallback to colored rectangle if texture load fails
                     painter.rect_filled(
@@ -614,17 +601,17 @@ fn render_perspective(
 
     // Collect and sort entities by depth
     let mut entities: Vec<_> = world.transforms.iter()
-        .filter(|(entity, _)| world.active.get(entity).copied().unwrap_or(true))
+        .filter(|(entity, _)| world.is_active_in_hierarchy(**entity))
         .collect();
 
     // Sort active entities by depth (Painter's Algorithm: Draw farthest first)
-    entities.sort_by(|(_, t1), (_, t2)| {
+    entities.sort_by(|(e1, t1), (e2, t2)| {
         let pos1 = Vec3::from(t1.position);
         let pos2 = Vec3::from(t2.position);
         let dist1 = (pos1 - cam_translation).length_squared();
         let dist2 = (pos2 - cam_translation).length_squared();
-        // Sort descending (farthest first)
-        dist2.partial_cmp(&dist1).unwrap_or(std::cmp::Ordering::Equal)
+        // Sort descending (farthest first); equal distances by entity id so they don't flicker
+        dist2.partial_cmp(&dist1).unwrap_or(std::cmp::Ordering::Equal).then(e1.cmp(e2))
     });
 
     // Render all entities
@@ -686,11 +673,12 @@ fn render_perspective(
                     );
                     
                     let color = if let Some(sprite) = world.sprites.get(entity) {
+                        let tint = world.sprite_draw_color(*entity, sprite);
                         egui::Color32::from_rgba_unmultiplied(
-                            (sprite.color[0] * 255.0) as u8,
-                            (sprite.color[1] * 255.0) as u8,
-                            (sprite.color[2] * 255.0) as u8,
-                            (sprite.color[3] * 255.0) as u8,
+                            (tint.r * 255.0) as u8,
+                            (tint.g * 255.0) as u8,
+                            (tint.b * 255.0) as u8,
+                            (tint.a * 255.0) as u8,
                         )
                     } else {
                         egui::Color32::WHITE
@@ -723,44 +711,25 @@ fn render_perspective(
             // Note: This logic assumes billboard behavior or 2D sprites in 3D world
             // Ideally, we should check sprite.billboard
              
+            let tint = world.sprite_draw_color(*entity, sprite);
+             
             let color = egui::Color32::from_rgba_unmultiplied(
-                (sprite.color[0] * 255.0) as u8,
-                (sprite.color[1] * 255.0) as u8,
-                (sprite.color[2] * 255.0) as u8,
-                (sprite.color[3] * 255.0) as u8,
+                (tint.r * 255.0) as u8,
+                (tint.g * 255.0) as u8,
+                (tint.b * 255.0) as u8,
+                (tint.a * 255.0) as u8,
             );
 
             // Try to load and render texture
             if !sprite.texture_id.is_empty() {
                 let texture_path = std::path::Path::new(&sprite.texture_id);
                 if let Some(texture) = texture_manager.load_texture(ctx, &sprite.texture_id, texture_path) {
-                    let mut mesh = egui::Mesh::with_texture(texture.id());
                     let rect = egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), size);
 
                     // UVs and flipping...
-                    let (u_min_base, u_max_base, v_min_base, v_max_base) = if let Some(sprite_rect) = sprite.sprite_rect {
-                        let tex_size = texture.size();
-                        let tex_width = tex_size[0] as f32;
-                        let tex_height = tex_size[1] as f32;
-                        (
-                            sprite_rect[0] as f32 / tex_width,
-                            (sprite_rect[0] + sprite_rect[2]) as f32 / tex_width,
-                            sprite_rect[1] as f32 / tex_height,
-                            (sprite_rect[1] + sprite_rect[3]) as f32 / tex_height
-                        )
-                    } else {
-                        (0.0, 1.0, 0.0, 1.0)
-                    };
-
-                    let (u_min, u_max) = if sprite.flip_x { (u_max_base, u_min_base) } else { (u_min_base, u_max_base) };
-                    let (v_min, v_max) = if sprite.flip_y { (v_max_base, v_min_base) } else { (v_min_base, v_max_base) };
-
-                    mesh.add_rect_with_uv(
-                        rect,
-                        egui::Rect::from_min_max(egui::pos2(u_min, v_min), egui::pos2(u_max, v_max)),
-                        color,
-                    );
-                    painter.add(egui::Shape::mesh(mesh));
+                    let tex_size = texture.size().map(|size| size as f32);
+                    let uv = world.sprite_uv_for(*entity, sprite, tex_size);
+                    painter.add(sprite_quad_shape(texture.id(), rect, &uv, color));
                 } else {
                     painter.rect_filled(
                         egui::Rect::from_center_size(egui::pos2(screen_x, screen_y), size),
@@ -0,0 +1,612 @@
+// Runtime scene switching (Scene.load / Scene.load_additive / Scene.unload from Lua),
+// background loading (Scene.preload / Scene.activate) and save games (SaveGame.save / SaveGame.load)
+use std::collections::{HashMap, HashSet};
+use ecs::{DynamicState, Entity, World};
+use script::{PreloadState, SaveGameFile, SceneCommand, ScriptEngine};
+use crate::texture_manager::TextureManager;
+use crate::ui_manager::UIManager;
+use super::scene_preload::{ActivationStep, ScenePreloader};
+use super::script_loader;
+
+/// What a batch of scene commands changed, so callers can clean up their own
+/// per-entity state (physics bodies, selection, GPU textures)
+#[derive(Debug, Default)]
+pub struct SceneTransition {
+    /// Entities despawned (including children)
+    pub destroyed: Vec<Entity>,
+    /// Entities spawned by loaded scenes
+    pub loaded: Vec<Entity>,
+    /// Texture ids dropped from the TextureManager
+    pub unloaded_textures: Vec<String>,
+    /// A full (non-additive) load happened
+    pub replaced: bool,
+}
+
+impl SceneTransition {
+    pub fn is_empty(&self) -> bool {
+        self.destroyed.is_empty() && self.loaded.is_empty() && !self.replaced
+    }
+}
+
+/// Tracks which scene each entity came from and which ones survive full loads
+#[derive(Debug, Default)]
+pub struct SceneManager {
+    entity_scenes: HashMap<Entity, String>,
+    persistent: HashSet<Entity>,
+    active_scene: Option<String>,
+    preloader: ScenePreloader,
+}
+
+impl SceneManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking `world` as the already loaded scene `scene_path`
+    pub fn begin(&mut self, scene_path: &str, world: &World) {
+        self.reset();
+        self.entity_scenes = world.entities().into_iter().map(|e| (e, scene_path.to_string())).collect();
+        self.active_scene = Some(scene_path.to_string());
+    }
+
+    pub fn reset(&mut self) {
+        self.entity_scenes.clear();
+        self.persistent.clear();
+        self.active_scene = None;
+        self.preloader.clear();
+    }
+
+    /// Scene loaded last with Scene.load (or begin)
+    pub fn active_scene(&self) -> Option<&str> {
+        self.active_scene.as_deref()
+    }
+
+    /// Scene `entity` was loaded from (None for persistent and runtime-spawned entities)
+    pub fn entity_scene(&self, entity: Entity) -> Option<&str> {
+        self.entity_scenes.get(&entity).map(String::as_str)
+    }
+
+    pub fn is_persistent(&self, entity: Entity) -> bool {
+        self.persistent.contains(&entity)
+    }
+
+    /// Scenes being preloaded (and not yet activated or cancelled)
+    pub fn preloader(&self) -> &ScenePreloader {
+        &self.preloader
+    }
+
+    /// Run the scene commands scripts queued this frame and advance the preloads (call at
+    /// the end of every frame)
+    pub fn process_commands(
+        &mut self,
+        script_engine: &mut ScriptEngine,
+        world: &mut World,
+        ui_manager: &mut UIManager,
+        texture_manager: &mut TextureManager,
+    ) -> SceneTransition {
+        let mut transition = SceneTransition::default();
+        let commands = script_engine.take_scene_commands();
+        if commands.is_empty() && self.preloader.is_idle() {
+            return transition;
+        }
+
+        for command in commands {
+            match command {
+                SceneCommand::Load { path } => {
+                    let Some(json) = Self::read_scene(script_engine, &path) else { continue };
+                    self.replace(script_engine, world, ui_manager, path, &json, None, &mut transition);
+                }
+                SceneCommand::LoadAdditive { path } => {
+                    let Some(json) = Self::read_scene(script_engine, &path) else { continue };
+                    self.merge(script_engine, world, &path, &json, None, &mut transition);
+                }
+                SceneCommand::SaveGame { slot } => self.save_game(script_engine, world, &slot),
+                SceneCommand::LoadGame { slot } => {
+                    let Some(file) = Self::read_save_game(script_engine, &slot) else { continue };
+                    let Some(json) = Self::read_scene(script_engine, &file.scene) else { continue };
+                    self.replace(script_engine, world, ui_manager, file.scene.clone(), &json, Some(&file.state), &mut transition);
+                    // After the old scene's OnDestroy, so only the scripts that stay hear it
+                    script_engine.global_state.borrow_mut().restore(file.globals);
+                    log::info!("Loaded game from slot '{}'", slot);
+                }
+                SceneCommand::Unload { path } => {
+                    let roots: Vec<Entity> = self.entity_scenes.iter()
+                        .filter(|(_, scene)| **scene == path)
+                        .map(|(e, _)| *e)
+                        .collect();
+                    let kept = self.with_descendants(world, self.persistent.iter().copied());
+                    let doomed: Vec<Entity> = self.with_descendants(world, roots).into_iter()
+                        .filter(|e| !kept.contains(e))
+                        .collect();
+                    self.destroy(script_engine, world, ui_manager, &doomed, &mut transition);
+                    log::info!("Unloaded scene {} ({} entities)", path, doomed.len());
+                }
+                SceneCommand::DontDestroyOnLoad { entity } => {
+                    // Like Unity, only root objects persist: detach it from its parent
+                    world.set_parent(entity, None);
+                    self.persistent.insert(entity);
+                    self.entity_scenes.remove(&entity);
+                }
+                SceneCommand::Duplicated { original, copies } => {
+                    // Copies belong to the original's scene and go with it on unload
+                    let copies: Vec<Entity> = copies.into_iter().filter(|e| world.active.contains_key(e)).collect();
+                    if let Some(scene) = self.entity_scenes.get(&original).cloned() {
+                        for &copy in &copies {
+                            self.entity_scenes.insert(copy, scene.clone());
+                        }
+                    }
+                    if let Err(e) = script_loader::load_scripts(world, script_engine, &copies) {
+                        log::error!("Failed to load scripts for duplicates of entity {}: {}", original, e);
+                    }
+                    transition.loaded.extend(copies);
+                }
+                SceneCommand::Preload { id, path } => {
+                    self.preloader.start(id, &path, self.active_scene.as_deref(), script_engine);
+                }
+                SceneCommand::Activate { id } => self.preloader.activate(id),
+                SceneCommand::CancelPreload { id } => self.preloader.cancel(id),
+            }
+        }
+
+        self.update_preloads(script_engine, world, ui_manager, texture_manager, &mut transition);
+
+        if !transition.destroyed.is_empty() {
+            let keep = Self::used_textures(world);
+            transition.unloaded_textures = texture_manager.retain_textures(&keep);
+        }
+        transition
+    }
+
+    /// Report preload progress to scripts, then swap in the scene Scene.activate waits
+    /// for once it is ready
+    fn update_preloads(
+        &mut self,
+        script_engine: &mut ScriptEngine,
+        world: &mut World,
+        ui_manager: &mut UIManager,
+        texture_manager: &TextureManager,
+        transition: &mut SceneTransition,
+    ) {
+        if self.preloader.is_idle() {
+            return;
+        }
+        let shared = std::rc::Rc::clone(&script_engine.scene_preloads);
+        for (path, progress) in self.preloader.poll(&shared, texture_manager) {
+            script_engine.call_scene_load_progress(&path, progress, world);
+        }
+
+        let config = shared.borrow().loading_screen.clone();
+        let ActivationStep::Swap { id, path, scene } = self.preloader.step_activation(&config, ui_manager) else {
+            return;
+        };
+        let json = match scene {
+            Some(scene) => scene.json.clone(),
+            // Preloaded while it was the active scene: load it again, like Scene.load
+            None => match Self::read_scene(script_engine, &path) {
+                Some(json) => json,
+                None => return,
+            },
+        };
+        self.replace(script_engine, world, ui_manager, path, &json, None, transition);
+        shared.borrow_mut().set_state(id, PreloadState::Activated);
+    }
+
+    /// Write the active scene's path, its dynamic state and Globals to a slot
+    fn save_game(&self, script_engine: &ScriptEngine, world: &mut World, slot: &str) {
+        let Some(path) = script_engine.save_game_path(slot) else {
+            log::error!("SaveGame.save('{}'): there is no persistent data folder to save to", slot);
+            return;
+        };
+        let Some(scene) = self.active_scene.clone() else {
+            log::error!("SaveGame.save('{}'): no scene is loaded", slot);
+            return;
+        };
+        let file = SaveGameFile {
+            scene,
+            state: world.capture_dynamic_state(),
+            globals: script_engine.global_state.borrow().values().clone(),
+        };
+        match file.write(&path) {
+            Ok(()) => log::info!("Saved game to {:?}", path),
+            Err(e) => log::error!("Failed to write save game {:?}: {}", path, e),
+        }
+    }
+
+    fn read_save_game(script_engine: &ScriptEngine, slot: &str) -> Option<SaveGameFile> {
+        let Some(path) = script_engine.save_game_path(slot) else {
+            log::error!("SaveGame.load('{}'): there is no persistent data folder to load from", slot);
+            return None;
+        };
+        match SaveGameFile::read(&path) {
+            Ok(file) => Some(file),
+            Err(e) => {
+                log::error!("Failed to read save game {:?}: {}", path, e);
+                None
+            }
+        }
+    }
+
+    fn read_scene(script_engine: &ScriptEngine, path: &str) -> Option<String> {
+        match pollster::block_on(script_engine.asset_loader.load_text(path)) {
+            Ok(json) => Some(json),
+            Err(e) => {
+                log::error!("Failed to load scene '{}': {}", path, e);
+                None
+            }
+        }
+    }
+
+    /// Everything but the persistent entities goes, then the scene comes in
+    #[allow(clippy::too_many_arguments)]
+    fn replace(
+        &mut self,
+        script_engine: &mut ScriptEngine,
+        world: &mut World,
+        ui_manager: &mut UIManager,
+        path: String,
+        json: &str,
+        saved_state: Option<&DynamicState>,
+        transition: &mut SceneTransition,
+    ) {
+        self.persistent.retain(|e| world.transforms.contains_key(e) || world.active.contains_key(e));
+        let kept = self.with_descendants(world, self.persistent.iter().copied());
+        let doomed: Vec<Entity> = world.entities().into_iter().filter(|e| !kept.contains(e)).collect();
+        self.destroy(script_engine, world, ui_manager, &doomed, transition);
+        ui_manager.deactivate_all_prefabs();
+        world.preload_assets.clear();
+        // Save games made in the new scene only know about its entities
+        world.destroyed_guids.clear();
+        // The new scene's background, ambient and gravity replace the old one's
+        // (additive loads keep the active scene's)
+        world.scene_settings = ecs::SceneSettings::from_scene_json(json).unwrap_or_default();
+        transition.replaced = true;
+        self.merge(script_engine, world, &path, json, saved_state, transition);
+        self.active_scene = Some(path);
+    }
+
+    /// Merge the scene into the world, put a save game's state on it, then run
+    /// Awake/Start for its scripts
+    fn merge(
+        &mut self,
+        script_engine: &mut ScriptEngine,
+        world: &mut World,
+        path: &str,
+        json: &str,
+        saved_state: Option<&DynamicState>,
+        transition: &mut SceneTransition,
+    ) {
+        let mut loaded = match world.merge_from_json(json) {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                log::error!("Failed to parse scene '{}': {}", path, e);
+                return;
+            }
+        };
+        if let Some(state) = saved_state {
+            // The scene may have been edited since the save
+            for guid in world.apply_dynamic_state(state) {
+                log::warn!("Save game entity {} is no longer in scene '{}', skipped", guid.0, path);
+            }
+            // Destroyed before the save
+            loaded.retain(|e| world.active.contains_key(e));
+        }
+        for &entity in &loaded {
+            self.entity_scenes.insert(entity, path.to_string());
+        }
+        if let Err(e) = script_loader::load_scripts(world, script_engine, &loaded) {
+            log::error!("Failed to load scripts for scene '{}': {}", path, e);
+        }
+        log::info!("Loaded scene {} ({} entities)", path, loaded.len());
+        transition.loaded.extend(loaded);
+    }
+
+    /// OnDestroy for every scripted entity first (they may still read each other), then despawn
+    fn destroy(
+        &mut self,
+        script_engine: &mut ScriptEngine,
+        world: &mut World,
+        ui_manager: &mut UIManager,
+        entities: &[Entity],
+        transition: &mut SceneTransition,
+    ) {
+        for &entity in entities {
+            if world.scripts.contains_key(&entity) {
+                script_engine.remove_entity_state(entity, world);
+            }
+            ui_manager.remove_entity_listeners(entity);
+        }
+        for &entity in entities {
+            world.despawn(entity);
+            self.entity_scenes.remove(&entity);
+            self.persistent.remove(&entity);
+        }
+        // Pooled instances go with their scene, and so do pools made from its entities
+        script_engine.forget_pooled(entities);
+        transition.destroyed.extend_from_slice(entities);
+    }
+
+    fn with_descendants(&self, world: &World, roots: impl IntoIterator<Item = Entity>) -> HashSet<Entity> {
+        let mut result = HashSet::new();
+        let mut stack: Vec<Entity> = roots.into_iter().collect();
+        while let Some(entity) = stack.pop() {
+            if result.insert(entity) {
+                stack.extend_from_slice(world.get_children(entity));
+            }
+        }
+        result
+    }
+
+    fn used_textures(world: &World) -> HashSet<String> {
+        world.sprites.values().map(|s| s.texture_id.clone())
+            .chain(world.sprite_sheets.values().map(|s| s.texture_id.clone()))
+            .chain(world.tilesets.values().map(|t| t.texture_id.clone()))
+            .filter(|id| !id.is_empty())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::test_support::{scene_json, script_engine, spawn_scripted, COUNTER};
+
+    fn engine() -> ScriptEngine {
+        let (a, b, hud) = (scene_json("a.png", 3), scene_json("b.png", 2), scene_json("hud.png", 1));
+        // A player and two chests that save games keep track of
+        let level = {
+            let mut world = World::new();
+            for _ in 0..3 {
+                let entity = world.spawn();
+                world.transforms.insert(entity, ecs::Transform::default());
+                world.save_states.insert(entity, ecs::SaveState::default());
+            }
+            world.assign_guids();
+            world.save_to_json().unwrap()
+        };
+        let night = {
+            let mut world = World::new();
+            world.scene_settings.ambient_color = ecs::Color::rgb(0.2, 0.2, 0.4);
+            world.scene_settings.gravity = Some(20.0);
+            world.save_to_json().unwrap()
+        };
+        script_engine(&[
+            ("scenes/a.json", &a),
+            ("scenes/b.json", &b),
+            ("scenes/hud.json", &hud),
+            ("scenes/level.json", &level),
+            ("scenes/night.json", &night),
+            ("scripts/counter.lua", COUNTER),
+            ("scripts/keeper.lua", "function Start() DontDestroyOnLoad(entity) end"),
+            ("scripts/bullet.lua", "function OnSpawn() spawned = true end"),
+            ("scripts/pooler.lua", r#"
+                function Start()
+                    Pool.create("bullet", "Bullet", 2, { policy = "reuse_oldest" })
+                    Pool.spawn("bullet", 1, 2)
+                end
+            "#),
+        ])
+    }
+
+    fn lua_memory(script_engine: &ScriptEngine) -> usize {
+        script_engine.lua_memory_usage().iter().map(|(_, bytes)| bytes).sum()
+    }
+
+    #[test]
+    fn test_repeated_loads_do_not_leak() {
+        let mut script_engine = engine();
+        let mut world = World::new();
+        let mut ui_manager = UIManager::new();
+        let mut texture_manager = TextureManager::new();
+        let ctx = egui::Context::default();
+        let png = {
+            let mut bytes = std::io::Cursor::new(Vec::new());
+            image::RgbaImage::new(1, 1).write_to(&mut bytes, image::ImageFormat::Png).unwrap();
+            bytes.into_inner()
+        };
+
+        let keeper = spawn_scripted(&mut world, "keeper");
+        script_loader::load_all_scripts(&mut world, &mut script_engine).unwrap();
+
+        let mut manager = SceneManager::new();
+        manager.begin("scenes/boot.json", &world);
+        manager.process_commands(&mut script_engine, &mut world, &mut ui_manager, &mut texture_manager);
+        assert!(manager.is_persistent(keeper));
+
+        let mut baseline = None;
+        for i in 0..40 {
+            let path = if i % 2 == 0 { "scenes/a.json" } else { "scenes/b.json" };
+            script_engine.scene_commands.borrow_mut().push(SceneCommand::Load { path: path.to_string() });
+            let transition = manager.process_commands(&mut script_engine, &mut world, &mut ui_manager, &mut texture_manager);
+            assert!(transition.replaced);
+
+            // What the renderer would load for this frame
+            for sprite in world.sprites.values() {
+                let id = sprite.texture_id.clone();
+                texture_manager.load_texture_from_bytes(&ctx, &id, &png, &Default::default());
+            }
+
+            let expected = if i % 2 == 0 { 3 } else { 2 };
+            assert_eq!(world.entities().len(), expected + 1);
+            assert_eq!(script_engine.entity_state_count(), expected + 1);
+            assert!(world.scripts.contains_key(&keeper));
+            assert_eq!(texture_manager.texture_count(), 1);
+            assert_eq!(manager.active_scene(), Some(path));
+
+            if i == 1 {
+                baseline = Some(lua_memory(&script_engine));
+            } else if i % 2 == 1 {
+                // Same scene, same scripts: memory stays flat within GC noise
+                let memory = lua_memory(&script_engine);
+                assert!(memory < baseline.unwrap() * 2, "Lua memory grew from {:?} to {}", baseline, memory);
+            }
+        }
+    }
+
+    #[test]
+    fn test_additive_load_and_unload() {
+        let mut script_engine = engine();
+        let mut world = World::new();
+        let mut ui_manager = UIManager::new();
+        let mut texture_manager = TextureManager::new();
+
+        let mut manager = SceneManager::new();
+        world.load_from_json(&scene_json("a.png", 3)).unwrap();
+        script_loader::load_all_scripts(&mut world, &mut script_engine).unwrap();
+        manager.begin("scenes/a.json", &world);
+
+        script_engine.scene_commands.borrow_mut().push(SceneCommand::LoadAdditive { path: "scenes/hud.json".to_string() });
+        let transition = manager.process_commands(&mut script_engine, &mut world, &mut ui_manager, &mut texture_manager);
+        assert_eq!(transition.loaded.len(), 1);
+        let hud = transition.loaded[0];
+        assert_eq!(hud, 3, "additive entities get fresh IDs");
+        assert_eq!(manager.entity_scene(hud), Some("scenes/hud.json"));
+        assert_eq!(manager.active_scene(), Some("scenes/a.json"));
+        assert_eq!(script_engine.entity_state_count(), 4);
+
+        script_engine.scene_commands.borrow_mut().push(SceneCommand::Unload { path: "scenes/hud.json".to_string() });
+        let transition = manager.process_commands(&mut script_engine, &mut world, &mut ui_manager, &mut texture_manager);
+        assert_eq!(transition.destroyed, vec![hud]);
+        assert!(!transition.replaced);
+        assert_eq!(world.entities(), vec![0, 1, 2]);
+        assert_eq!(script_engine.entity_state_count(), 3);
+    }
+
+    #[test]
+    fn test_duplicated_entities_start_their_scripts() {
+        let mut script_engine = engine();
+        let mut world = World::new();
+        world.load_from_json(&scene_json("a.png", 2)).unwrap();
+        script_loader::load_all_scripts(&mut world, &mut script_engine).unwrap();
+        let mut manager = SceneManager::new();
+        manager.begin("scenes/a.json", &world);
+
+        let copy = world.duplicate(0).unwrap();
+        let copies = world.subtree(copy);
+        assert_eq!(copies.len(), 2);
+        script_engine.scene_commands.borrow_mut().push(SceneCommand::Duplicated { original: 0, copies: copies.clone() });
+        let transition = manager.process_commands(&mut script_engine, &mut world, &mut UIManager::new(), &mut TextureManager::new());
+        assert_eq!(transition.loaded, copies);
+        assert_eq!(script_engine.entity_state_count(), 4);
+        assert_eq!(manager.entity_scene(copy), Some("scenes/a.json"));
+    }
+
+    #[test]
+    fn test_pooled_instances_spawn_and_go_with_their_scene() {
+        let mut script_engine = engine();
+        let mut world = World::new();
+        let template = spawn_scripted(&mut world, "bullet");
+        world.names.insert(template, "Bullet".to_string());
+        world.active.insert(template, false);
+        spawn_scripted(&mut world, "pooler");
+
+        let mut manager = SceneManager::new();
+        manager.begin("scenes/a.json", &world);
+        script_loader::load_all_scripts(&mut world, &mut script_engine).unwrap();
+        let transition = manager.process_commands(&mut script_engine, &mut world, &mut UIManager::new(), &mut TextureManager::new());
+        assert_eq!(transition.loaded.len(), 2);
+        let stats = script_engine.pools.borrow().manager.pool_stats("bullet").unwrap();
+        assert_eq!((stats.size, stats.active), (2, 1));
+
+        // OnSpawn once the instance's script is loaded, not for the free one
+        script_engine.update_active_states(&mut world);
+        let spawned_flag = |entity| script_engine.debug_globals(entity).iter().any(|(name, _)| name == "spawned");
+        let (active, free): (Vec<Entity>, Vec<Entity>) =
+            transition.loaded.iter().copied().partition(|e| world.is_active_in_hierarchy(*e));
+        assert_eq!(world.transforms[&active[0]].position, [1.0, 2.0, 0.0]);
+        assert!(spawned_flag(active[0]));
+        assert!(!spawned_flag(free[0]));
+        assert_eq!(manager.entity_scene(active[0]), Some("scenes/a.json"));
+
+        script_engine.scene_commands.borrow_mut().push(SceneCommand::Load { path: "scenes/b.json".to_string() });
+        manager.process_commands(&mut script_engine, &mut world, &mut UIManager::new(), &mut TextureManager::new());
+        assert!(script_engine.pools.borrow().manager.is_empty());
+    }
+
+    #[test]
+    fn test_scene_load_applies_scene_settings() {
+        let mut script_engine = engine();
+        let mut world = World::new();
+        let mut ui_manager = UIManager::new();
+        let mut texture_manager = TextureManager::new();
+        let mut manager = SceneManager::new();
+        world.load_from_json(&scene_json("a.png", 1)).unwrap();
+        manager.begin("scenes/a.json", &world);
+
+        script_engine.scene_commands.borrow_mut().push(SceneCommand::Load { path: "scenes/night.json".to_string() });
+        manager.process_commands(&mut script_engine, &mut world, &mut ui_manager, &mut texture_manager);
+        assert_eq!(world.scene_settings.gravity, Some(20.0));
+        assert_eq!(world.scene_settings.ambient_color, ecs::Color::rgb(0.2, 0.2, 0.4));
+
+        // Additive scenes keep the active scene's settings
+        script_engine.scene_commands.borrow_mut().push(SceneCommand::LoadAdditive { path: "scenes/hud.json".to_string() });
+        manager.process_commands(&mut script_engine, &mut world, &mut ui_manager, &mut texture_manager);
+        assert_eq!(world.scene_settings.gravity, Some(20.0));
+
+        script_engine.scene_commands.borrow_mut().push(SceneCommand::Load { path: "scenes/b.json".to_string() });
+        manager.process_commands(&mut script_engine, &mut world, &mut ui_manager, &mut texture_manager);
+        assert_eq!(world.scene_settings, ecs::SceneSettings::default());
+    }
+
+    #[test]
+    fn test_save_game_restores_scene_state_and_globals() {
+        use script::ReplLine;
+
+        let mut script_engine = engine();
+        let dir = std::env::temp_dir().join(format!("engine_save_game_test_{}", std::process::id()));
+        *script_engine.save_data.borrow_mut() = engine_core::save_data::SaveData::with_path(dir.join("save.json"));
+        let mut world = World::new();
+        let mut ui_manager = UIManager::new();
+        let mut texture_manager = TextureManager::new();
+        let input = input::InputSystem::default();
+        let mut manager = SceneManager::new();
+        world.load_from_json(&pollster::block_on(script_engine.asset_loader.load_text("scenes/level.json")).unwrap()).unwrap();
+        manager.begin("scenes/level.json", &world);
+
+        // Walk, open a chest (destroying it) and score, then save
+        world.transforms.get_mut(&0).unwrap().position = [4.0, 2.0, 0.0];
+        world.despawn(1);
+        script_engine.global_state.borrow_mut().set("score", serde_json::json!(5));
+        script_engine.run_repl("SaveGame.save('slot 1')", None, &mut world, &input);
+        manager.process_commands(&mut script_engine, &mut world, &mut ui_manager, &mut texture_manager);
+        assert_eq!(
+            script_engine.run_repl("return SaveGame.exists('slot 1'), SaveGame.exists('2')", None, &mut world, &input),
+            [ReplLine::Value("true".to_string()), ReplLine::Value("false".to_string())]
+        );
+
+        // Keep playing, then load: the scene comes back as saved
+        world.transforms.get_mut(&0).unwrap().position = [9.0, 9.0, 0.0];
+        script_engine.global_state.borrow_mut().set("score", serde_json::json!(99));
+        script_engine.global_state.borrow_mut().set("checkpoint", serde_json::json!("cave"));
+        script_engine.run_repl("SaveGame.load('slot 1')", None, &mut world, &input);
+        let transition = manager.process_commands(&mut script_engine, &mut world, &mut ui_manager, &mut texture_manager);
+        assert!(transition.replaced);
+        assert_eq!(transition.loaded.len(), 2, "the opened chest stays gone");
+        let player = transition.loaded[0];
+        assert_eq!(world.transforms[&player].position, [4.0, 2.0, 0.0]);
+        assert_eq!(manager.active_scene(), Some("scenes/level.json"));
+        let globals = script_engine.global_state.borrow();
+        assert_eq!(globals.get("score"), Some(&serde_json::json!(5)));
+        assert_eq!(globals.get("checkpoint"), None);
+        drop(globals);
+
+        assert_eq!(
+            script_engine.run_repl("return SaveGame.delete('slot 1'), SaveGame.exists('slot 1')", None, &mut world, &input),
+            [ReplLine::Value("true".to_string()), ReplLine::Value("false".to_string())]
+        );
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_missing_scene_keeps_current_world() {
+        let mut script_engine = engine();
+        let mut world = World::new();
+        world.load_from_json(&scene_json("a.png", 2)).unwrap();
+        let mut manager = SceneManager::new();
+        manager.begin("scenes/a.json", &world);
+
+        script_engine.scene_commands.borrow_mut().push(SceneCommand::Load { path: "scenes/missing.json".to_string() });
+        let transition = manager.process_commands(&mut script_engine, &mut world, &mut UIManager::new(), &mut TextureManager::new());
+        assert!(transition.is_empty());
+        assert_eq!(world.entities().len(), 2);
+    }
+}
@@ -0,0 +1,470 @@
+// Background scene loading (Scene.preload / Scene.activate from Lua)
+//
+// A preload reads and parses the scene file on an asset loader thread, then starts its
+// textures (through the TextureManager's AssetServer, so the renderer picks them up from
+// the same cache), its map files and its preload list (audio and anything else scripts
+// load by path). Activation only merges the parsed scene on the main thread.
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
+use ecs::World;
+use script::{LoadingScreenConfig, PreloadState, ScenePreloads, ScriptEngine};
+use crate::assets::handle::{Handle, LoadState};
+use crate::assets::server::{Asset, AssetServer, PreloadTracker};
+use crate::texture_manager::TextureManager;
+use crate::ui_manager::UIManager;
+
+/// UI instance the loading screen is shown as
+pub const LOADING_SCREEN_INSTANCE: &str = "__loading_screen";
+/// Prefab path of the built-in loading screen
+pub const BUILTIN_LOADING_SCREEN: &str = "builtin://loading_screen";
+/// Element whose filled image follows the load (built-in screen, and custom ones that have it)
+pub const LOADING_PROGRESS_ELEMENT: &str = "Progress";
+
+/// A scene file, parsed on the loader thread along with the list of assets it uses
+pub struct SceneAsset {
+    pub json: String,
+    /// Texture ids of its sprites, sprite sheets and tilesets
+    pub textures: Vec<String>,
+    /// Its map files and preload list
+    pub assets: Vec<String>,
+}
+
+impl Asset for SceneAsset {
+    fn decode(bytes: Vec<u8>, _path: &str) -> anyhow::Result<Self> {
+        let json = String::from_utf8(bytes)?;
+        let mut world = World::new();
+        world.load_from_json(&json)?;
+
+        let textures: BTreeSet<String> = world.sprites.values().map(|s| s.texture_id.clone())
+            .chain(world.sprite_sheets.values().map(|s| s.texture_id.clone()))
+            .chain(world.tilesets.values().map(|t| t.texture_id.clone()))
+            .filter(|id| !id.is_empty())
+            .collect();
+        let assets: BTreeSet<String> = world.maps.values().map(|m| m.file_path.clone())
+            .chain(world.preload_assets.iter().cloned())
+            .filter(|path| !path.is_empty())
+            .collect();
+        Ok(Self { json, textures: textures.into_iter().collect(), assets: assets.into_iter().collect() })
+    }
+}
+
+struct ScenePreload {
+    path: String,
+    /// None when it is the scene already active: there is nothing to load
+    scene: Option<Handle<SceneAsset>>,
+    /// Started once the scene file is in
+    assets: Option<Vec<PreloadTracker>>,
+    /// Last progress passed to OnSceneLoadProgress
+    reported: Option<f32>,
+}
+
+impl ScenePreload {
+    fn progress(&self) -> f32 {
+        let Some(scene) = &self.scene else { return 1.0 };
+        if !scene.is_loaded() {
+            return 0.0;
+        }
+        // The scene file counts as one asset
+        let (finished, total) = self.assets.iter().flatten()
+            .fold((1, 1), |(finished, total), tracker| (finished + tracker.finished_count(), total + tracker.len()));
+        finished as f32 / total as f32
+    }
+
+    fn is_ready(&self) -> bool {
+        match &self.scene {
+            None => true,
+            Some(scene) => scene.is_loaded() && self.assets.as_ref().is_some_and(|assets| assets.iter().all(PreloadTracker::is_done)),
+        }
+    }
+}
+
+/// Scene.activate waiting for its preload
+struct Activation {
+    id: u64,
+    since: Instant,
+    loading_screen: bool,
+}
+
+/// What to do about the activation after this frame's polling
+pub(crate) enum ActivationStep {
+    None,
+    /// Swap in the scene at `path`; `scene` is None when it was the active scene (reload it)
+    Swap { id: u64, path: String, scene: Option<Arc<SceneAsset>> },
+}
+
+/// The preloads of the SceneManager
+#[derive(Default)]
+pub struct ScenePreloader {
+    /// Loads scene files, maps and preload lists; made on the first preload
+    server: Option<Arc<AssetServer>>,
+    preloads: HashMap<u64, ScenePreload>,
+    activation: Option<Activation>,
+    /// Assets of the last activated scene, kept until the next one so the renderer finds
+    /// them loaded
+    activated: Vec<PreloadTracker>,
+}
+
+impl std::fmt::Debug for ScenePreloader {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ScenePreloader")
+            .field("preloads", &self.preloads.values().map(|p| &p.path).collect::<Vec<_>>())
+            .field("activation", &self.activation.as_ref().map(|a| a.id))
+            .finish()
+    }
+}
+
+impl ScenePreloader {
+    /// Nothing loading and nothing waiting to be activated
+    pub fn is_idle(&self) -> bool {
+        self.preloads.is_empty() && self.activation.is_none()
+    }
+
+    pub fn preload_count(&self) -> usize {
+        self.preloads.len()
+    }
+
+    pub fn server(&self) -> Option<&Arc<AssetServer>> {
+        self.server.as_ref()
+    }
+
+    /// Start preload `id` of `path`. The active scene is ready at once without loading anything.
+    pub fn start(&mut self, id: u64, path: &str, active_scene: Option<&str>, script_engine: &ScriptEngine) {
+        let scene = if active_scene == Some(path) {
+            None
+        } else {
+            let loader = script_engine.asset_loader.clone();
+            let server = self.server.get_or_insert_with(|| Arc::new(AssetServer::new(loader)));
+            Some(server.load::<SceneAsset>(path))
+        };
+        log::info!("Preloading scene {}", path);
+        self.preloads.insert(id, ScenePreload { path: path.to_string(), scene, assets: None, reported: None });
+    }
+
+    /// Stop preload `id`; dropping its handles frees whatever it loaded that nothing else
+    /// uses. An activation waiting for it is dropped on the next step_activation.
+    pub fn cancel(&mut self, id: u64) {
+        if let Some(preload) = self.preloads.remove(&id) {
+            log::info!("Cancelled preload of scene {}", preload.path);
+        }
+    }
+
+    /// Swap preload `id` in once it is ready (replaces an activation still waiting)
+    pub fn activate(&mut self, id: u64) {
+        if !self.preloads.contains_key(&id) {
+            log::warn!("Scene.activate: preload {} is no longer loading", id);
+            return;
+        }
+        // The loading screen (if it is up) stays for the new one
+        let mut loading_screen = false;
+        if let Some(previous) = self.activation.take() {
+            log::warn!("Scene.activate: replaces the activation still waiting for preload {}", previous.id);
+            loading_screen = previous.loading_screen;
+        }
+        self.activation = Some(Activation { id, since: Instant::now(), loading_screen });
+    }
+
+    /// Advance the preloads and write their state to `shared`. Returns the (path, progress)
+    /// of each preload whose progress changed, for OnSceneLoadProgress.
+    pub fn poll(&mut self, shared: &RefCell<ScenePreloads>, texture_manager: &TextureManager) -> Vec<(String, f32)> {
+        let mut changes = Vec::new();
+        let mut failed = Vec::new();
+        let mut ids: Vec<u64> = self.preloads.keys().copied().collect();
+        ids.sort_unstable();
+
+        for id in ids {
+            let preload = self.preloads.get_mut(&id).unwrap();
+            if let Some(scene) = &preload.scene {
+                if let LoadState::Failed(error) = scene.state() {
+                    log::error!("Failed to preload scene '{}': {}", preload.path, error);
+                    shared.borrow_mut().set_state(id, PreloadState::Failed(error));
+                    failed.push(id);
+                    continue;
+                }
+                if preload.assets.is_none() {
+                    if let Some(asset) = scene.get() {
+                        preload.assets = Some(Self::start_assets(self.server.as_deref(), &asset, texture_manager));
+                    }
+                }
+            }
+
+            let progress = preload.progress();
+            if preload.reported != Some(progress) {
+                preload.reported = Some(progress);
+                changes.push((preload.path.clone(), progress));
+            }
+            let mut entries = shared.borrow_mut();
+            entries.set_progress(id, progress);
+            if preload.is_ready() && entries.get(id).is_some_and(|entry| entry.state == PreloadState::Loading) {
+                for (path, error) in preload.assets.iter().flatten().flat_map(PreloadTracker::failures) {
+                    log::warn!("Preload of scene '{}': failed to load '{}': {}", preload.path, path, error);
+                }
+                entries.set_state(id, PreloadState::Ready);
+            }
+        }
+
+        for id in failed {
+            self.preloads.remove(&id);
+        }
+        changes
+    }
+
+    fn start_assets(server: Option<&AssetServer>, scene: &SceneAsset, texture_manager: &TextureManager) -> Vec<PreloadTracker> {
+        let mut trackers = Vec::new();
+        // Without an AssetServer the TextureManager reads textures when they are first drawn
+        if let Some(textures) = texture_manager.asset_server() {
+            let paths: Vec<String> = scene.textures.iter()
+                .map(|id| texture_manager.resolve_path(Path::new(id)).to_string_lossy().into_owned())
+                .collect();
+            trackers.push(textures.preload(&paths));
+        }
+        if let Some(server) = server {
+            trackers.push(server.preload(&scene.assets));
+        }
+        trackers
+    }
+
+    /// Progress of the preload the activation waits for
+    pub fn activation_progress(&self) -> Option<f32> {
+        let activation = self.activation.as_ref()?;
+        self.preloads.get(&activation.id).map(ScenePreload::progress)
+    }
+
+    /// Finish the activation if its preload is ready, otherwise bring up the loading
+    /// screen once it has waited longer than `config.delay`
+    pub(crate) fn step_activation(&mut self, config: &LoadingScreenConfig, ui_manager: &mut UIManager) -> ActivationStep {
+        let Some(activation) = &mut self.activation else {
+            return ActivationStep::None;
+        };
+        let id = activation.id;
+        let Some(preload) = self.preloads.get(&id) else {
+            // Failed (reported by poll) or cancelled
+            if activation.loading_screen {
+                hide_loading_screen(ui_manager);
+            }
+            self.activation = None;
+            return ActivationStep::None;
+        };
+
+        if !preload.is_ready() {
+            let progress = preload.progress();
+            if !activation.loading_screen && config.enabled && activation.since.elapsed().as_secs_f32() >= config.delay {
+                activation.loading_screen = show_loading_screen(ui_manager, config);
+            }
+            if activation.loading_screen {
+                set_loading_progress(ui_manager, progress);
+            }
+            return ActivationStep::None;
+        }
+
+        if activation.loading_screen {
+            hide_loading_screen(ui_manager);
+        }
+        self.activation = None;
+        let preload = self.preloads.remove(&id).unwrap();
+        self.activated = preload.assets.unwrap_or_default();
+        ActivationStep::Swap { id, path: preload.path, scene: preload.scene.and_then(|scene| scene.get()) }
+    }
+
+    /// Drop every preload (e.g. when leaving play mode)
+    pub fn clear(&mut self) {
+        self.preloads.clear();
+        self.activation = None;
+        self.activated.clear();
+    }
+}
+
+/// Show the configured loading screen; false if it couldn't be
+fn show_loading_screen(ui_manager: &mut UIManager, config: &LoadingScreenConfig) -> bool {
+    let path = match &config.prefab {
+        Some(path) => path.as_str(),
+        None => {
+            if !ui_manager.is_prefab_loaded(BUILTIN_LOADING_SCREEN) {
+                ui_manager.register_prefab(BUILTIN_LOADING_SCREEN, builtin_loading_screen());
+            }
+            BUILTIN_LOADING_SCREEN
+        }
+    };
+    let result = if ui_manager.is_prefab_loaded(path) { Ok(()) } else { ui_manager.load_prefab(path) };
+    match result.and_then(|()| ui_manager.activate_prefab(path, LOADING_SCREEN_INSTANCE)) {
+        Ok(()) => true,
+        Err(e) => {
+            log::error!("Failed to show loading screen: {}", e);
+            false
+        }
+    }
+}
+
+fn set_loading_progress(ui_manager: &mut UIManager, progress: f32) {
+    // Custom screens don't need a progress bar
+    let _ = ui_manager.set_element_fill(LOADING_SCREEN_INSTANCE, LOADING_PROGRESS_ELEMENT, progress);
+}
+
+fn hide_loading_screen(ui_manager: &mut UIManager) {
+    if ui_manager.instance(LOADING_SCREEN_INSTANCE).is_some() {
+        ui_manager.deactivate_prefab(LOADING_SCREEN_INSTANCE);
+    }
+}
+
+/// Dark full-screen panel with "Loading..." over a progress bar
+fn builtin_loading_screen() -> ui::UIPrefab {
+    let mut root = ui::UIPrefabElement::new("LoadingScreen", ui::RectTransform {
+        anchor_min: ui::Vec2::ZERO,
+        anchor_max: ui::Vec2::ONE,
+        size_delta: ui::Vec2::ZERO,
+        ..Default::default()
+    });
+    root.ui_element.color = ui::Color::rgba(0.05, 0.05, 0.07, 1.0);
+    // Above the game's UI, modals included
+    root.ui_element.z_order = 10_000;
+    root.image = Some(ui::UIImage::default());
+
+    let mut label = ui::UIPrefabElement::new("Label", ui::RectTransform::anchored(
+        ui::Vec2::new(0.5, 0.5),
+        ui::Vec2::new(0.0, -30.0),
+        ui::Vec2::new(300.0, 30.0),
+    ));
+    label.text = Some(ui::UIText {
+        text: "Loading...".to_string(),
+        font_size: 20.0,
+        color: ui::Color::rgba(0.9, 0.9, 0.9, 1.0),
+        ..Default::default()
+    });
+
+    let mut track = ui::UIPrefabElement::new("Track", ui::RectTransform::anchored(
+        ui::Vec2::new(0.5, 0.5),
+        ui::Vec2::new(0.0, 10.0),
+        ui::Vec2::new(300.0, 8.0),
+    ));
+    track.ui_element.color = ui::Color::rgba(0.2, 0.2, 0.25, 1.0);
+    track.image = Some(ui::UIImage::default());
+
+    let mut bar = ui::UIPrefabElement::new(LOADING_PROGRESS_ELEMENT, ui::RectTransform::anchored(
+        ui::Vec2::new(0.5, 0.5),
+        ui::Vec2::new(0.0, 10.0),
+        ui::Vec2::new(300.0, 8.0),
+    ));
+    bar.ui_element.color = ui::Color::rgba(0.85, 0.85, 0.9, 1.0);
+    bar.image = Some(ui::UIImage { image_type: ui::ImageType::Filled, fill_amount: 0.0, ..Default::default() });
+
+    root.children = vec![label, track, bar];
+    ui::UIPrefab { name: "LoadingScreen".to_string(), root, canvas_scaler: None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::scene_manager::SceneManager;
+    use crate::runtime::script_loader;
+    use crate::runtime::test_support::{scene_json, script_engine, spawn_scripted, COUNTER};
+
+    const PROGRESS: &str = r#"
+        function OnSceneLoadProgress(path, progress)
+            Globals.set("progress", Globals.get("progress", "") .. path .. "=" .. progress .. " ")
+        end
+    "#;
+
+    fn engine() -> ScriptEngine {
+        let (a, b) = (scene_json("a.png", 3), scene_json("b.png", 2));
+        script_engine(&[
+            ("scenes/a.json", &a),
+            ("scenes/b.json", &b),
+            ("scripts/counter.lua", COUNTER),
+            ("scripts/progress.lua", PROGRESS),
+        ])
+    }
+
+    fn eval(script_engine: &mut ScriptEngine, world: &mut World, code: &str) -> Vec<script::ReplLine> {
+        script_engine.run_repl(code, None, world, &input::InputSystem::default())
+    }
+
+    fn value(text: &str) -> Vec<script::ReplLine> {
+        vec![script::ReplLine::Value(text.to_string())]
+    }
+
+    /// Run frames until `code` evaluates to true
+    fn run_until(manager: &mut SceneManager, script_engine: &mut ScriptEngine, world: &mut World, code: &str) {
+        let start = std::time::Instant::now();
+        loop {
+            let transition = manager.process_commands(script_engine, world, &mut UIManager::new(), &mut TextureManager::new());
+            assert!(!transition.replaced, "swapped before activation");
+            if eval(script_engine, world, code) == value("true") {
+                return;
+            }
+            assert!(start.elapsed() < std::time::Duration::from_secs(5), "timed out waiting for: {}", code);
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_preload_then_activate_swaps_in_the_scene() {
+        let mut script_engine = engine();
+        let mut world = World::new();
+        world.load_from_json(&scene_json("a.png", 3)).unwrap();
+        spawn_scripted(&mut world, "progress");
+        script_loader::load_all_scripts(&mut world, &mut script_engine).unwrap();
+        let mut manager = SceneManager::new();
+        manager.begin("scenes/a.json", &world);
+
+        // Preloading the same scene twice shares one load
+        eval(&mut script_engine, &mut world, "loading = Scene.preload('scenes/b.json') again = Scene.preload('scenes/b.json')");
+        run_until(&mut manager, &mut script_engine, &mut world, "loading:is_ready()");
+        assert_eq!(manager.preloader().preload_count(), 1);
+        assert_eq!(eval(&mut script_engine, &mut world, "again:progress() == 1"), value("true"));
+        assert_eq!(world.entities().len(), 4, "nothing changes before activation");
+        let reported = eval(&mut script_engine, &mut world, "Globals.get('progress')");
+        assert!(matches!(&reported[..], [script::ReplLine::Value(log)] if log.ends_with("scenes/b.json=1.0 \"")), "{:?}", reported);
+
+        eval(&mut script_engine, &mut world, "Scene.activate(loading)");
+        let transition = manager.process_commands(&mut script_engine, &mut world, &mut UIManager::new(), &mut TextureManager::new());
+        assert!(transition.replaced);
+        assert_eq!(manager.active_scene(), Some("scenes/b.json"));
+        assert_eq!(world.entities().len(), 2);
+        assert_eq!(script_engine.entity_state_count(), 2);
+        assert_eq!(manager.preloader().preload_count(), 0);
+        assert!(matches!(&eval(&mut script_engine, &mut world, "Scene.activate(loading)")[..], [script::ReplLine::Error(_)]));
+    }
+
+    #[test]
+    fn test_preloading_the_active_scene_does_no_work() {
+        let mut script_engine = engine();
+        let mut world = World::new();
+        world.load_from_json(&scene_json("a.png", 3)).unwrap();
+        let mut manager = SceneManager::new();
+        manager.begin("scenes/a.json", &world);
+
+        eval(&mut script_engine, &mut world, "same = Scene.preload('scenes/a.json')");
+        manager.process_commands(&mut script_engine, &mut world, &mut UIManager::new(), &mut TextureManager::new());
+        assert_eq!(eval(&mut script_engine, &mut world, "same:is_ready()"), value("true"));
+        assert!(manager.preloader().server().is_none(), "no loader was started");
+        assert_eq!(world.entities().len(), 3);
+    }
+
+    #[test]
+    fn test_cancelled_preload_releases_its_assets() {
+        let mut script_engine = engine();
+        let mut world = World::new();
+        world.load_from_json(&scene_json("a.png", 3)).unwrap();
+        let mut manager = SceneManager::new();
+        manager.begin("scenes/a.json", &world);
+
+        eval(&mut script_engine, &mut world, "loading = Scene.preload('scenes/b.json')");
+        manager.process_commands(&mut script_engine, &mut world, &mut UIManager::new(), &mut TextureManager::new());
+        let server = manager.preloader().server().unwrap().clone();
+        assert_eq!(server.manager().live_asset_count(), 1);
+
+        eval(&mut script_engine, &mut world, "loading:cancel()");
+        manager.process_commands(&mut script_engine, &mut world, &mut UIManager::new(), &mut TextureManager::new());
+        assert_eq!(manager.preloader().preload_count(), 0);
+        // The loader thread may still be finishing with its temporary reference
+        let start = std::time::Instant::now();
+        while server.manager().live_asset_count() > 0 {
+            assert!(start.elapsed() < std::time::Duration::from_secs(5), "preloaded scene was not released");
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(matches!(&eval(&mut script_engine, &mut world, "Scene.activate(loading)")[..], [script::ReplLine::Error(_)]));
+        assert_eq!(manager.active_scene(), Some("scenes/a.json"));
+    }
+}
@@ -1,4 +1,4 @@
-use ecs::World;
+use ecs::{Entity, EntityOrdered, World};
 use script::ScriptEngine;
 use std::path::Path;
 use anyhow::Result;
@@ -10,7 +10,20 @@ pub fn load_all_scripts(
     script_engine: &mut ScriptEngine,
     // scripts_folder argument removed - we use AssetLoader with "scripts/" prefix
 ) -> Result<()> {
-    let entities_with_scripts: Vec<_> = world.scripts.keys().cloned().collect();
+    let entities_with_scripts: Vec<_> = world.scripts.sorted_entities();
+    load_scripts(world, script_engine, &entities_with_scripts)
+}
+
+/// Load and initialize the scripts of `entities` (e.g. a scene loaded at runtime):
+/// Awake() for all of them first, then Start()
+pub fn load_scripts(world: &mut World, script_engine: &mut ScriptEngine, entities: &[Entity]) -> Result<()> {
+    let entities_with_scripts: Vec<Entity> = entities.iter().copied().filter(|e| world.scripts.contains_key(e)).collect();
+
+    // Component.define calls (scripts/components.lua) before any script reads a component;
+    // instances saved with an older definition are brought up to date
+    for warning in script_engine.load_component_definitions(world) {
+        log::warn!("{}", warning);
+    }
 
     // Phase 1: Load scripts and call Awake() for all entities
     for entity in &entities_with_scripts {
@@ -29,7 +42,11 @@ pub fn load_all_scripts(
                         }
                     }
                     Err(e) => {
-                         log::warn!("Script file not found or failed to load: {} ({})", script_path, e);
+                        // Once per entity; the entity's Update is skipped while it has no script
+                        log::error!(
+                            "Missing script '{}' on entity {}: could not load {} ({})",
+                            script_name, entity, script_path, e
+                        );
                     }
                 }
             }
@@ -59,9 +76,12 @@ pub fn run_all_scripts(
     input: &input::InputSystem,
     dt: f32,
 ) -> Result<()> {
-    let entities_with_scripts: Vec<_> = world.scripts.keys().cloned().collect();
+    let entities_with_scripts: Vec<_> = world.scripts.sorted_entities();
 
     for entity in entities_with_scripts {
+        if !world.is_active_in_hierarchy(entity) {
+            continue;
+        }
         if let Some(script) = world.scripts.get(&entity) {
             if script.enabled {
                 // script_path info is effectively unused by run_script (it uses entity state),
@@ -1,39 +1,397 @@
 // Script system for runtime
-use ecs::World;
+use ecs::{EntityOrdered, World};
+use std::collections::HashMap;
+use ecs::sorting::SortingLayers;
+use engine_core::project_settings::{AspectPolicy, CaptureSettings};
 use script::ScriptEngine;
 use input::InputSystem;
+use crate::ui_manager::UIManager;
 
+/// One frame of scripts, in this order:
+/// 1. OnEnable/OnDisable (and deferred Start) for enabled state changed since last frame
+/// 2. Update for every enabled script, in entity-id order (the same every run)
+/// 3. LateUpdate for every script that is still enabled
+/// 4. Entities passed to destroy_entity() this frame: OnDestroy for all of them, then despawn
+/// 5. OnEnable/OnDisable for this frame's SetActive calls and Script.enabled changes
 pub fn update_scripts(
     script_engine: &mut ScriptEngine,
     world: &mut World,
     input: &InputSystem,
     delta_time: f32,
 ) {
+    profiler::profile_scope!("scripts.update");
+
+    // Keep Nav.find_path in sync with tilemap/collider changes from last frame
+    script_engine.update_navigation(world);
+
+    // OnEnable/OnDisable (and deferred Start) for active state changed since last frame
+    script_engine.update_active_states(world);
+
+    // Advance Timer/Tween state first so callbacks fire in this frame's run_script
+    script_engine.update_timers(delta_time, world);
+
     // Collect entities with scripts to avoid borrowing conflicts
-    let entities: Vec<ecs::Entity> = world.scripts.keys().cloned().collect();
+    let entities: Vec<ecs::Entity> = world.scripts.sorted_entities();
 
-    for entity in entities {
+    for &entity in &entities {
+        // Checked per entity: an earlier script may have just deactivated this one
         let should_run = if let Some(script) = world.scripts.get(&entity) {
-            script.enabled
+            script.enabled && world.is_active_in_hierarchy(entity)
         } else {
             false
         };
 
         if should_run {
+            // One scope per script so Lua hotspots show up under scripts.update, and a
+            // per-entity timing for the performance HUD and script budget warnings
+            let _scope = world.scripts.get(&entity).map(|s| {
+                (profiler::ScopeTimer::new(&s.script_name), profiler::scripts::ScriptTimer::new(entity, &s.script_name))
+            });
             let mut log_callback = |msg: String| {
                 log::info!("[Lua] {}", msg);
             };
 
             // Path is currently unused by run_script (it looks up by entity), so passing empty path is safe
             // The script content should have been loaded via load_script_for_entity previously (e.g. in Awake/Start)
-            let _ = script_engine.run_script(
+            if let Err(e) = script_engine.run_script(
                 std::path::Path::new(""),
                 entity,
                 world,
                 input,
                 delta_time,
                 &mut log_callback,
-            );
+            ) {
+                log::error!("Script update error for entity {}: {}", entity, e);
+            }
+        }
+    }
+
+    // LateUpdate once every Update has run (camera follow after movement)
+    for &entity in &entities {
+        let should_run = script_engine.has_late_update(entity)
+            && world.scripts.get(&entity).is_some_and(|script| script.enabled)
+            && world.is_active_in_hierarchy(entity);
+        if !should_run {
+            continue;
+        }
+        let _scope = world.scripts.get(&entity).map(|s| {
+            (profiler::ScopeTimer::new(&s.script_name), profiler::scripts::ScriptTimer::new(entity, &s.script_name))
+        });
+        let mut log_callback = |msg: String| {
+            log::info!("[Lua] {}", msg);
+        };
+        if let Err(e) = script_engine.run_late_update(entity, world, input, delta_time, &mut log_callback) {
+            log::error!("Script LateUpdate error for entity {}: {}", entity, e);
+        }
+    }
+
+    // destroy_entity() calls made this frame (OnDestroy, then despawn)
+    script_engine.apply_pending_destroys(world);
+
+    // SetActive calls made by this frame's scripts
+    script_engine.update_active_states(world);
+}
+
+/// Continue an Update that was suspended at a breakpoint, after a debugger command
+/// (ScriptEngine::take_debug_resume). The rest of the frame it stopped in already ran.
+pub fn resume_suspended_script(script_engine: &mut ScriptEngine, world: &mut World, input: &InputSystem, entity: ecs::Entity) {
+    let mut log_callback = |msg: String| {
+        log::info!("[Lua] {}", msg);
+    };
+    if let Err(e) = script_engine.run_script(std::path::Path::new(""), entity, world, input, 0.0, &mut log_callback) {
+        log::error!("Script update error for entity {}: {}", entity, e);
+    }
+}
+
+/// Forward animation clip events to each entity's on_animation_event callback
+pub fn dispatch_animation_events(script_engine: &ScriptEngine, world: &mut World, events: Vec<(ecs::Entity, String)>) {
+    for (entity, event) in events {
+        if !world.scripts.get(&entity).is_some_and(|script| script.enabled) {
+            continue;
         }
+        if let Err(e) = script_engine.call_animation_event(entity, &event, world) {
+            log::error!("Animation event '{}' error for entity {}: {}", event, entity, e);
+        }
+    }
+}
+
+/// Run the on-complete actions of finished ColorFades (animation_system::update_color_fades).
+/// Deactivated entities get OnDisable after next frame's scripts; destroyed ones go
+/// through the same path as destroy_entity() (OnDestroy, then despawn with children)
+/// right away.
+pub fn apply_fade_completions(script_engine: &mut ScriptEngine, world: &mut World, completions: Vec<(ecs::Entity, ecs::FadeCompleteAction)>) {
+    let mut destroying = false;
+    for (entity, action) in completions {
+        match action {
+            ecs::FadeCompleteAction::None => {}
+            ecs::FadeCompleteAction::Deactivate => world.set_active(entity, false),
+            ecs::FadeCompleteAction::Destroy => {
+                script_engine.queue_destroy(entity);
+                destroying = true;
+            }
+        }
+    }
+    if destroying {
+        script_engine.apply_pending_destroys(world);
+    }
+}
+
+/// Forward collision enter/stay/exit events to both entities' scripts
+pub fn dispatch_collision_events(script_engine: &mut ScriptEngine, world: &mut World, events: Vec<physics::CollisionEvent>) {
+    for event in events {
+        for (entity, other) in [(event.entity, event.other), (event.other, event.entity)] {
+            if !world.scripts.get(&entity).is_some_and(|script| script.enabled) {
+                continue;
+            }
+            if let Err(e) = script_engine.call_collision(entity, other, event.phase, world) {
+                log::error!("Collision error for entity {}: {}", entity, e);
+            }
+        }
+    }
+}
+
+/// Forward trigger zone enter/exit events to the zones' scripts and the event bus
+pub fn dispatch_trigger_zone_events(script_engine: &ScriptEngine, world: &mut World, events: Vec<physics::TriggerZoneEvent>) {
+    for event in events {
+        if let Err(e) = script_engine.call_trigger_zone(&event, world) {
+            log::error!("Trigger zone error for entity {}: {}", event.zone, e);
+        }
+    }
+}
+
+/// Apply Input.rebind / Input.rumble calls queued by this frame's scripts
+pub fn apply_input_commands(script_engine: &ScriptEngine, input: &mut InputSystem) {
+    use script::InputCommand;
+
+    for command in script_engine.take_input_commands() {
+        match command {
+            InputCommand::StartRebind { player, action, slot } => {
+                if let Err(e) = input.start_rebind(player, &action, slot) {
+                    log::warn!("[Lua] Input.rebind failed: {}", e);
+                }
+            }
+            InputCommand::CancelRebind => input.cancel_rebind(),
+            InputCommand::AcceptRebindConflict => {
+                if let Err(e) = input.accept_rebind_conflict() {
+                    log::warn!("[Lua] Input.accept_rebind_conflict failed: {}", e);
+                }
+            }
+            InputCommand::Rumble { gamepad, strong, weak, duration } => {
+                input.set_gamepad_rumble(gamepad, strong, weak, duration);
+            }
+        }
+    }
+}
+
+/// Hand Screenshot.capture / save_gif calls queued by this frame's scripts to `capture`
+/// (the frame is read back once it has been rendered)
+pub fn apply_capture_commands(script_engine: &ScriptEngine, capture: &mut render::ScreenCapture, settings: &CaptureSettings) {
+    use script::CaptureCommand;
+
+    for command in script_engine.take_capture_commands() {
+        match command {
+            CaptureCommand::Screenshot { path, crop_letterbox } => {
+                capture.request_screenshot(path.as_deref(), crop_letterbox.unwrap_or(settings.crop_letterbox));
+            }
+            CaptureCommand::SaveGif { path } => capture.save_gif(path.as_deref()),
+        }
+    }
+}
+
+/// The GIF buffer size from the project's capture settings
+pub fn gif_settings(settings: &CaptureSettings) -> render::GifSettings {
+    render::GifSettings { seconds: settings.gif_seconds, fps: settings.gif_fps, width: settings.gif_width }
+}
+/// Hand last frame's UI listener callbacks, input field text and language to scripts
+/// (call before update_scripts)
+pub fn sync_ui(script_engine: &ScriptEngine, ui_manager: &mut UIManager) {
+    script_engine.queue_ui_events(ui_manager.take_script_events());
+    script_engine.sync_input_fields(ui_manager.input_field_snapshots());
+    script_engine.sync_language(ui_manager.localization().language());
+}
+
+/// Apply the UI calls scripts made this frame, in order, as one batch (layouts included).
+/// Every failure goes back to scripts (UI.get_last_errors); unknown paths are already
+/// reported once through `UIManager::take_warnings`, the rest are returned for the log.
+pub fn apply_ui_commands(script_engine: &ScriptEngine, ui_manager: &mut UIManager) -> Vec<script::UICommandError> {
+    let errors: Vec<script::UICommandError> = ui_manager.apply_commands(script_engine.take_ui_commands())
+        .into_iter()
+        .filter_map(Result::err)
+        .collect();
+    let failures = errors.iter().filter(|error| !error.not_found).cloned().collect();
+    script_engine.sync_ui_errors(errors);
+    failures
+}
+
+/// Hand scripts the game's area on screen, in the coordinates of `InputSystem::mouse_position`,
+/// the texture pivots and sorting layers sprite picking needs, and the scale of each UI
+/// canvas (call before update_scripts). `rect` is the whole game area; the project's
+/// aspect policy letterboxes it here the same way the renderer does.
+pub fn sync_screen(
+    script_engine: &ScriptEngine,
+    world: &World,
+    sorting_layers: &SortingLayers,
+    rect: [f32; 4],
+    aspect: AspectPolicy,
+    canvas_scales: HashMap<String, f32>,
+    texture_pivot: impl Fn(&str) -> [f32; 2],
+) {
+    let texture_pivots = world.sprites.values()
+        .filter(|sprite| sprite.pivot.is_none())
+        .map(|sprite| (sprite.texture_id.clone(), texture_pivot(&sprite.texture_id)))
+        .collect();
+    script_engine.sync_screen_view(script::ScreenView {
+        rect: aspect.game_rect(rect),
+        texture_pivots,
+        sorting_layers: sorting_layers.clone(),
+        canvas_scales,
+        aspect,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runtime::script_loader;
+    use crate::runtime::test_support::{logged_frame, script_engine, spawn_scripted};
+
+    const LIFECYCLE: &str = r#"
+        local function log(what) Globals.set("log", Globals.get("log", "") .. what .. entity .. " ") end
+        function OnEnable() log("E") end
+        function OnDisable() log("X") end
+        function Update()
+            log("U")
+            local doomed = Globals.get("doomed")
+            if doomed then
+                destroy_entity(doomed)
+                Globals.set("doomed", nil)
+            end
+        end
+        function LateUpdate() log("L") end
+        function OnDestroy() log("D") end
+    "#;
+
+    const WAITER: &str = r#"
+        local function log(what) Globals.set("log", Globals.get("log", "") .. what .. " ") end
+        function Start()
+            Coroutine.start(function()
+                Coroutine.wait(1.0)
+                log("waited")
+            end)
+            Events.on("ping", function() log("heard") end)
+            Events.on("ping", function() log("kept") end, { while_disabled = "buffer" })
+        end
+        function OnEnable() log("E") end
+        function OnDisable() log("X") end
+    "#;
+
+    fn load_waiter() -> (ScriptEngine, World, ecs::Entity) {
+        let mut script_engine = script_engine(&[("scripts/waiter.lua", WAITER)]);
+        let mut world = World::new();
+        let waiter = spawn_scripted(&mut world, "waiter");
+        script_loader::load_all_scripts(&mut world, &mut script_engine).unwrap();
+        (script_engine, world, waiter)
+    }
+
+    #[test]
+    fn test_disabled_script_suspends_its_coroutines_and_events() {
+        let frame = |script_engine: &mut ScriptEngine, world: &mut World| logged_frame(script_engine, world, 0.25);
+
+        // Disabled for 0.25s (re-enabled before the 1s wait would have run out) and for 2s
+        // (long after): either way the wait keeps the 0.75s it had left and only counts
+        // down while the script is enabled
+        for disabled_frames in [1, 8] {
+            let (mut script_engine, mut world, waiter) = load_waiter();
+            assert_eq!(frame(&mut script_engine, &mut world), "");
+
+            // An event that arrives as the script is disabled only reaches the buffering handler
+            world.scripts.get_mut(&waiter).unwrap().enabled = false;
+            script_engine.emit_event(script::ScriptEvent::new("ping"));
+            assert_eq!(frame(&mut script_engine, &mut world), "X");
+            for _ in 1..disabled_frames {
+                script_engine.emit_event(script::ScriptEvent::new("ping"));
+                assert_eq!(frame(&mut script_engine, &mut world), "");
+            }
+            assert_eq!(script_engine.coroutines.borrow().active_count(), 1);
+
+            world.scripts.get_mut(&waiter).unwrap().enabled = true;
+            let kept = vec!["kept"; disabled_frames].join(" ");
+            assert_eq!(frame(&mut script_engine, &mut world), format!("E {}", kept));
+            assert_eq!(frame(&mut script_engine, &mut world), "");
+            assert_eq!(frame(&mut script_engine, &mut world), "waited");
+            assert_eq!(script_engine.coroutines.borrow().active_count(), 0);
+
+            // Enabled again, both handlers hear new events
+            script_engine.emit_event(script::ScriptEvent::new("ping"));
+            assert_eq!(frame(&mut script_engine, &mut world), "heard kept");
+        }
+
+        // Destroying a disabled script still drops its suspended coroutine
+        let (mut script_engine, mut world, waiter) = load_waiter();
+        world.scripts.get_mut(&waiter).unwrap().enabled = false;
+        assert_eq!(frame(&mut script_engine, &mut world), "X");
+        script_engine.queue_destroy(waiter);
+        script_engine.apply_pending_destroys(&mut world);
+        assert!(world.entities().is_empty());
+        assert_eq!(script_engine.entity_state_count(), 0);
+        assert_eq!(script_engine.coroutines.borrow().active_count(), 0);
+    }
+
+    #[test]
+    fn test_script_lifecycle_order() {
+        let mut script_engine = script_engine(&[("scripts/lifecycle.lua", LIFECYCLE)]);
+        let mut world = World::new();
+        let entities: Vec<ecs::Entity> = (0..3).map(|_| spawn_scripted(&mut world, "lifecycle")).collect();
+        let (first, doomed, child) = (entities[0], entities[1], entities[2]);
+        world.set_parent(child, Some(doomed));
+        script_loader::load_all_scripts(&mut world, &mut script_engine).unwrap();
+
+        let frame = |script_engine: &mut ScriptEngine, world: &mut World| -> Vec<String> {
+            logged_frame(script_engine, world, 0.016).split_whitespace().map(str::to_string).collect()
+        };
+        let sorted = |calls: &[String]| {
+            let mut calls = calls.to_vec();
+            calls.sort();
+            calls
+        };
+
+        // Update (all) -> LateUpdate (all) -> deferred destruction -> OnDestroy. The doomed
+        // entity still runs its Update and LateUpdate in the frame destroy_entity was called.
+        script_engine.global_state.borrow_mut().set("doomed", serde_json::json!(doomed));
+        let calls = frame(&mut script_engine, &mut world);
+        assert_eq!(calls.len(), 8, "{:?}", calls);
+        assert_eq!(sorted(&calls[0..3]), [format!("U{}", first), format!("U{}", doomed), format!("U{}", child)]);
+        assert_eq!(sorted(&calls[3..6]), [format!("L{}", first), format!("L{}", doomed), format!("L{}", child)]);
+        assert_eq!(calls[6..], [format!("D{}", doomed), format!("D{}", child)]);
+        assert_eq!(world.entities(), vec![first]);
+        assert_eq!(script_engine.entity_state_count(), 1);
+
+        // Disabling the Script component is a transition like SetActive(false)
+        world.scripts.get_mut(&first).unwrap().enabled = false;
+        assert_eq!(frame(&mut script_engine, &mut world), [format!("X{}", first)]);
+        assert!(frame(&mut script_engine, &mut world).is_empty());
+        world.scripts.get_mut(&first).unwrap().enabled = true;
+        assert_eq!(frame(&mut script_engine, &mut world), [format!("E{}", first), format!("U{}", first), format!("L{}", first)]);
+    }
+
+    #[test]
+    fn test_screenshot_calls_reach_capture() {
+        use script::CaptureCommand;
+
+        let mut script_engine = script_engine(&[]);
+        let mut world = World::new();
+        let input = input::InputSystem::default();
+        script_engine.run_repl("Screenshot.capture('shots/a.png', { crop_letterbox = false }) Screenshot.save_gif()", None, &mut world, &input);
+        assert_eq!(script_engine.take_capture_commands(), [
+            CaptureCommand::Screenshot { path: Some("shots/a.png".to_string()), crop_letterbox: Some(false) },
+            CaptureCommand::SaveGif { path: None },
+        ]);
+
+        // Nothing recorded: reported, not a panic
+        let mut capture = render::ScreenCapture::new();
+        script_engine.run_repl("Screenshot.save_gif('clip.gif')", None, &mut world, &input);
+        apply_capture_commands(&script_engine, &mut capture, &Default::default());
+        let results = capture.take_results();
+        assert!(matches!(&results[..], [Err(e)] if e.contains("clip.gif") && e.contains("recording is off")));
     }
-}
\ No newline at end of file
+}
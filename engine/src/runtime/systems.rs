@@ -6,22 +6,37 @@ use ecs::World;
 use physics::PhysicsWorld;
 use script::ScriptEngine;
 use input::InputSystem;
+use engine_core::executor::SystemExecutor;
+use std::sync::{Arc, Mutex};
 
 // Re-export specific functions if needed, but GameSystems struct is the main entry point
 pub use super::render_system;
 pub use super::physics_system;
 pub use super::script_system;
+pub use super::animation_system;
+pub use super::transform_system;
 
 pub struct GameSystems {
     pub physics_world: PhysicsWorld,
     pub script_engine: ScriptEngine,
+    /// Systems with disjoint component access, run concurrently after physics
+    pub executor: SystemExecutor<World>,
+    animation_events: Arc<Mutex<Vec<(ecs::Entity, String)>>>,
 }
 
 impl GameSystems {
     pub fn new(asset_loader: std::sync::Arc<dyn engine_core::assets::AssetLoader>) -> Result<Self> {
+        let animated_sprites = animation_system::AnimatedSpriteSystem::new();
+        let animation_events = animated_sprites.events();
+        let mut executor = SystemExecutor::new();
+        executor.add_system(Box::new(animated_sprites));
+        executor.add_system(Box::new(transform_system::GlobalTransformSystem));
+
         Ok(Self {
             physics_world: PhysicsWorld::new(),
             script_engine: ScriptEngine::new(asset_loader)?,
+            executor,
+            animation_events,
         })
     }
 
@@ -33,5 +48,11 @@ impl GameSystems {
         // 2. Update Physics
         // Physics applies forces and resolves collisions
         physics_system::update_physics(&mut self.physics_world, world, dt);
+
+        // 3. Advance sprite animations and propagate transforms (in parallel),
+        //    then let scripts react to clip events
+        self.executor.run(world, dt);
+        let animation_events = std::mem::take(&mut *self.animation_events.lock().unwrap());
+        script_system::dispatch_animation_events(&self.script_engine, world, animation_events);
     }
 }
\ No newline at end of file
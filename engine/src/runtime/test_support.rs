@@ -0,0 +1,80 @@
+// Fixtures shared by the runtime tests: a ScriptEngine over in-memory files and
+// scripted entities
+use async_trait::async_trait;
+use ecs::{Entity, World};
+use engine_core::assets::AssetLoader;
+use script::ScriptEngine;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Asset loader serving files from memory (path -> contents)
+pub struct MemoryLoader(pub HashMap<String, String>);
+
+#[async_trait]
+impl AssetLoader for MemoryLoader {
+    async fn load_text(&self, path: &str) -> anyhow::Result<String> {
+        self.0.get(path).cloned().ok_or_else(|| anyhow::anyhow!("not found: {}", path))
+    }
+
+    async fn load_binary(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        Ok(self.load_text(path).await?.into_bytes())
+    }
+
+    fn get_base_path(&self) -> String {
+        String::new()
+    }
+}
+
+/// A script with some Lua memory to it, that records Start and OnDestroy
+pub const COUNTER: &str = r#"
+    big = {}
+    function Awake() for i = 1, 1000 do big[i] = tostring(i) end end
+    function Start() started = true end
+    function OnDestroy() destroyed = true end
+"#;
+
+/// A ScriptEngine whose asset loader serves `files` ("scripts/<name>.lua", scenes, ...)
+pub fn script_engine(files: &[(&str, &str)]) -> ScriptEngine {
+    let files = files.iter().map(|(path, contents)| (path.to_string(), contents.to_string())).collect();
+    ScriptEngine::new(Arc::new(MemoryLoader(files))).unwrap()
+}
+
+/// The Script component for scripts/<name>.lua
+pub fn script(name: &str) -> ecs::Script {
+    ecs::Script {
+        script_name: name.to_string(),
+        enabled: true,
+        parameters: HashMap::new(),
+        lifecycle_state: Default::default(),
+    }
+}
+
+/// Spawn an entity with a Transform and scripts/<name>.lua
+pub fn spawn_scripted(world: &mut World, name: &str) -> Entity {
+    let entity = world.spawn();
+    world.transforms.insert(entity, ecs::Transform::default());
+    world.scripts.insert(entity, script(name));
+    entity
+}
+
+/// Scene file with `scripted` sprites of `texture` running scripts/counter.lua, each the
+/// child of the one before
+pub fn scene_json(texture: &str, scripted: usize) -> String {
+    let mut world = World::new();
+    for i in 0..scripted {
+        let entity = spawn_scripted(&mut world, "counter");
+        world.sprites.insert(entity, ecs::Sprite { texture_id: texture.to_string(), ..Default::default() });
+        if i > 0 {
+            world.set_parent(entity, Some(entity - 1));
+        }
+    }
+    world.save_to_json().unwrap()
+}
+
+/// Run one frame of scripts and return what they appended to the "log" global
+pub fn logged_frame(script_engine: &mut ScriptEngine, world: &mut World, dt: f32) -> String {
+    script_engine.global_state.borrow_mut().set("log", serde_json::Value::Null);
+    super::script_system::update_scripts(script_engine, world, &input::InputSystem::default(), dt);
+    let log = script_engine.global_state.borrow().get("log").and_then(|log| log.as_str().map(str::to_string));
+    log.unwrap_or_default().trim_end().to_string()
+}
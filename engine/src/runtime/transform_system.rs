@@ -1,63 +1,93 @@
 use ecs::World;
-use glam::{Vec3, Quat, Mat4};
+use glam::Mat4;
+use engine_core::executor::{self, ParallelSystem, SystemAccess, SystemWrites};
 
 /// Updates global transforms for all entities based on hierarchy
 pub fn update_global_transforms(world: &mut World) {
-    // Borrows need to be split manually because we are modifying one field while reading others
-    let transforms = &world.transforms;
-    let parents = &world.parents;
-    let children = &world.children;
-    let global_transforms = &mut world.global_transforms;
+    let global_transforms = compute_global_transforms(world);
+    world.global_transforms.extend(global_transforms);
+}
 
+/// Global transform of every entity reachable from a root; root subtrees are
+/// computed in parallel (reads only)
+fn compute_global_transforms(world: &World) -> Vec<(u32, ecs::GlobalTransform)> {
     // Identify root entities (entities that have a Transform but NO parent)
     let mut roots = Vec::new();
-    for (entity, _) in transforms.iter() {
-        if !parents.contains_key(entity) {
+    for (entity, _) in world.transforms.iter() {
+        if !world.parents.contains_key(entity) {
             roots.push(*entity);
         }
     }
 
     // Process hierarchy
-    for root in roots {
-        propagate_recursive(root, Mat4::IDENTITY, transforms, children, global_transforms);
+    executor::par_map(&roots, |&root| {
+        let mut global_transforms = Vec::new();
+        propagate_recursive(root, Mat4::IDENTITY, false, world, &mut global_transforms);
+        global_transforms
+    })
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// `update_global_transforms` as a parallel system
+pub struct GlobalTransformSystem;
+
+impl ParallelSystem<World> for GlobalTransformSystem {
+    fn name(&self) -> &str {
+        "global_transforms"
+    }
+
+    fn access(&self) -> SystemAccess {
+        SystemAccess::new()
+            .read("transforms")
+            .read("parents")
+            .read("children")
+            .write("global_transforms")
+    }
+
+    fn run(&mut self, world: &World, _dt: f32) -> SystemWrites<World> {
+        let global_transforms = compute_global_transforms(world);
+        Box::new(move |world| world.global_transforms.extend(global_transforms))
     }
 }
 
+/// Collects the global transform of `entity` and its descendants. Subtrees whose local
+/// transforms match the ones their cached GlobalTransform was computed from (and whose
+/// parent didn't change) keep their matrices and are only walked, not recomputed.
 fn propagate_recursive(
     entity: u32,
     parent_global_matrix: Mat4,
-    transforms: &std::collections::HashMap<u32, ecs::Transform>,
-    children_map: &std::collections::HashMap<u32, Vec<u32>>,
-    global_transforms: &mut std::collections::HashMap<u32, ecs::GlobalTransform>,
+    parent_dirty: bool,
+    world: &World,
+    global_transforms: &mut Vec<(u32, ecs::GlobalTransform)>,
 ) {
-    let local_transform = match transforms.get(&entity) {
+    let local_transform = match world.transforms.get(&entity) {
         Some(t) => t,
         None => return, // Should not happen if we iterate properly
     };
-    
-    // Calculate local matrix (Scale -> Rotate -> Translate)
-    let rot_rad = Vec3::new(
-        local_transform.rotation[0].to_radians(),
-        local_transform.rotation[1].to_radians(),
-        local_transform.rotation[2].to_radians(),
-    );
-    let rotation = Quat::from_euler(glam::EulerRot::XYZ, rot_rad.x, rot_rad.y, rot_rad.z);
-    let translation = Vec3::from(local_transform.position);
-    let scale = Vec3::from(local_transform.scale);
-    
-    // T * R * S
-    let local_matrix = Mat4::from_scale_rotation_translation(scale, rotation, translation);
-    
-    // Calculate global matrix: ParentGlobal * Local
-    let global_matrix = parent_global_matrix * local_matrix;
-    
-    // Store in World
-    global_transforms.insert(entity, ecs::GlobalTransform { matrix: global_matrix.to_cols_array() });
-    
+
+    let cached = world.global_transforms.get(&entity);
+    let dirty = parent_dirty || cached.is_none_or(|global| global.local.as_ref() != Some(local_transform));
+
+    let global_matrix = match cached {
+        Some(global) if !dirty => global.to_mat4(),
+        _ => {
+            // Calculate global matrix: ParentGlobal * Local (T * R * S)
+            let global_matrix = parent_global_matrix * local_transform.matrix();
+            // Collected here, written to World by the caller
+            global_transforms.push((entity, ecs::GlobalTransform {
+                matrix: global_matrix.to_cols_array(),
+                local: Some(local_transform.clone()),
+            }));
+            global_matrix
+        }
+    };
+
     // Recurse to children
-    if let Some(children) = children_map.get(&entity) {
+    if let Some(children) = world.children.get(&entity) {
         for child in children {
-            propagate_recursive(*child, global_matrix, transforms, children_map, global_transforms);
+            propagate_recursive(*child, global_matrix, dirty, world, global_transforms);
         }
     }
 }
@@ -0,0 +1,206 @@
+// Runtime window control (Screen / Cursor from Lua), applied between frames
+use std::path::PathBuf;
+use engine_core::display_settings::DisplaySettings;
+use render::RenderModule;
+use script::{DisplayInfo, ScriptEngine, WindowCommand};
+use winit::dpi::PhysicalSize;
+use winit::monitor::MonitorHandle;
+use winit::window::{CursorGrabMode, Fullscreen, Window};
+
+/// Owns the window state scripts can change. The standalone player accepts every
+/// command and persists display choices; the editor's play mode shares its window with
+/// the editor, so it only takes vsync and cursor changes and puts them back on stop.
+pub struct WindowController {
+    /// display.json to persist fullscreen / resolution / vsync to (player only)
+    settings_path: Option<PathBuf>,
+    settings: DisplaySettings,
+    cursor_visible: bool,
+    cursor_locked: bool,
+    /// Vsync to restore when play mode stops (editor only)
+    restore_vsync: Option<bool>,
+    monitor: Option<MonitorHandle>,
+    resolutions: Vec<[u32; 2]>,
+    notes: Vec<String>,
+}
+
+impl WindowController {
+    /// Standalone player: `settings` were loaded from `settings_path` at startup
+    pub fn player(settings_path: PathBuf, settings: DisplaySettings) -> Self {
+        Self::new(Some(settings_path), settings, None)
+    }
+
+    /// Editor play mode: vsync and cursor only, undone by `restore`
+    pub fn play_mode(renderer: &RenderModule) -> Self {
+        Self::new(None, DisplaySettings::default(), Some(renderer.vsync()))
+    }
+
+    fn new(settings_path: Option<PathBuf>, settings: DisplaySettings, restore_vsync: Option<bool>) -> Self {
+        Self {
+            settings_path,
+            settings,
+            cursor_visible: true,
+            cursor_locked: false,
+            restore_vsync,
+            monitor: None,
+            resolutions: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+
+    fn accepts_display_modes(&self) -> bool {
+        self.settings_path.is_some()
+    }
+
+    /// Mouse-look: raw device motion should drive the mouse delta
+    pub fn cursor_locked(&self) -> bool {
+        self.cursor_locked
+    }
+
+    /// Apply the Screen / Cursor calls scripts made this frame. Call at a safe point
+    /// (after scripts, before rendering), never from inside event handling.
+    pub fn apply_commands(&mut self, script_engine: &ScriptEngine, window: &Window, renderer: &mut RenderModule) {
+        let mut changed = false;
+        for command in script_engine.take_window_commands() {
+            match command {
+                WindowCommand::SetVsync(vsync) => {
+                    renderer.set_vsync(vsync);
+                    if self.accepts_display_modes() {
+                        self.settings.vsync = Some(vsync);
+                        changed = true;
+                    }
+                }
+                WindowCommand::SetCursorVisible(visible) => {
+                    window.set_cursor_visible(visible);
+                    self.cursor_visible = visible;
+                }
+                WindowCommand::SetCursorLocked(locked) => self.set_cursor_locked(window, locked),
+                WindowCommand::SetFullscreen(fullscreen) if self.accepts_display_modes() => {
+                    // Borderless on the window's current monitor; back in a window at the chosen size
+                    window.set_fullscreen(fullscreen.then_some(Fullscreen::Borderless(None)));
+                    if let (false, Some([width, height])) = (fullscreen, self.settings.resolution) {
+                        self.request_size(window, renderer, width, height);
+                    }
+                    self.settings.fullscreen = Some(fullscreen);
+                    changed = true;
+                }
+                WindowCommand::SetResolution { width, height } if self.accepts_display_modes() => {
+                    // Fullscreen keeps the monitor's size; the resolution applies when windowed
+                    if window.fullscreen().is_none() {
+                        self.request_size(window, renderer, width, height);
+                    }
+                    self.settings.resolution = Some([width, height]);
+                    changed = true;
+                }
+                WindowCommand::SetTitle(title) if self.accepts_display_modes() => window.set_title(&title),
+                command => self.notes.push(format!(
+                    "{} is ignored in play mode (the editor window is shared); use Build & Run to try it",
+                    command_name(&command)
+                )),
+            }
+        }
+
+        // Resizes that already happened reach the surface before this frame renders
+        sync_surface(window, renderer);
+
+        if let (true, Some(path)) = (changed, &self.settings_path) {
+            if let Err(e) = self.settings.save(path) {
+                self.notes.push(format!("Failed to save display settings: {}", e));
+            }
+        }
+    }
+
+    fn request_size(&self, window: &Window, renderer: &mut RenderModule, width: u32, height: u32) {
+        // Some platforms resize right away; the rest send a Resized event later
+        if let Some(size) = window.request_inner_size(PhysicalSize::new(width, height)) {
+            renderer.resize(size);
+        }
+    }
+
+    fn set_cursor_locked(&mut self, window: &Window, locked: bool) {
+        let result = if locked {
+            // Platforms without Locked (Windows) keep the cursor inside the window instead
+            window.set_cursor_grab(CursorGrabMode::Locked)
+                .or_else(|_| window.set_cursor_grab(CursorGrabMode::Confined))
+        } else {
+            window.set_cursor_grab(CursorGrabMode::None)
+        };
+        match result {
+            Ok(()) => self.cursor_locked = locked,
+            Err(e) => self.notes.push(format!("Cursor.set_locked({}) failed: {}", locked, e)),
+        }
+    }
+
+    /// Tell scripts the current window state (Screen.get_* / Cursor.is_*)
+    pub fn sync_script(&mut self, script_engine: &ScriptEngine, window: &Window, renderer: &RenderModule) {
+        let monitor = window.current_monitor();
+        if monitor != self.monitor {
+            self.resolutions = monitor.as_ref().map(monitor_resolutions).unwrap_or_default();
+            self.monitor = monitor;
+        }
+
+        script_engine.sync_display(DisplayInfo {
+            size: [renderer.config.width, renderer.config.height],
+            fullscreen: window.fullscreen().is_some(),
+            vsync: renderer.vsync(),
+            resolutions: self.resolutions.clone(),
+            cursor_visible: self.cursor_visible,
+            cursor_locked: self.cursor_locked,
+        });
+    }
+
+    /// Show and unlock the cursor; false if it was already free
+    pub fn release_cursor(&mut self, window: &Window) -> bool {
+        let captured = !self.cursor_visible || self.cursor_locked;
+        if !self.cursor_visible {
+            window.set_cursor_visible(true);
+            self.cursor_visible = true;
+        }
+        if self.cursor_locked {
+            self.set_cursor_locked(window, false);
+        }
+        captured
+    }
+
+    /// Give the editor its cursor and vsync back when play mode stops
+    pub fn restore(&mut self, window: &Window, renderer: &mut RenderModule) {
+        self.release_cursor(window);
+        if let Some(vsync) = self.restore_vsync.filter(|&vsync| vsync != renderer.vsync()) {
+            renderer.set_vsync(vsync);
+        }
+    }
+
+    /// Messages about ignored or failed commands, for the log or console
+    pub fn take_notes(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.notes)
+    }
+}
+
+/// Match the render surface to the window (fullscreen switches and set_resolution can
+/// land between events), so a frame is never drawn at the old size
+pub fn sync_surface(window: &Window, renderer: &mut RenderModule) {
+    let size = window.inner_size();
+    if size != renderer.size {
+        renderer.resize(size);
+    }
+}
+
+/// Video mode sizes of a monitor, largest first, without repeats (refresh rates differ)
+fn monitor_resolutions(monitor: &MonitorHandle) -> Vec<[u32; 2]> {
+    let mut resolutions: Vec<[u32; 2]> = monitor.video_modes()
+        .map(|mode| [mode.size().width, mode.size().height])
+        .collect();
+    resolutions.sort_unstable_by(|a, b| b.cmp(a));
+    resolutions.dedup();
+    resolutions
+}
+
+fn command_name(command: &WindowCommand) -> &'static str {
+    match command {
+        WindowCommand::SetFullscreen(_) => "Screen.set_fullscreen",
+        WindowCommand::SetResolution { .. } => "Screen.set_resolution",
+        WindowCommand::SetVsync(_) => "Screen.set_vsync",
+        WindowCommand::SetTitle(_) => "Screen.set_title",
+        WindowCommand::SetCursorVisible(_) => "Cursor.set_visible",
+        WindowCommand::SetCursorLocked(_) => "Cursor.set_locked",
+    }
+}
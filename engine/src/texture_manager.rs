@@ -1,25 +1,39 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use engine_core::assets::AssetId;
+use std::sync::Arc;
+use engine_core::assets::{AssetId, AssetLoader};
+use serde::{Deserialize, Serialize};
+use crate::assets::handle::{Handle, LoadState};
+use crate::assets::server::{AssetServer, ImageAsset};
 use egui::{ColorImage, TextureHandle, TextureOptions};
 
-// Default texture import settings for when editor is not available
-#[derive(Debug, Clone)]
+/// Runtime view of a texture's import settings.
+/// Read from the `.meta` file the editor writes next to the asset (`player.png.meta`);
+/// fields the runtime doesn't use are ignored, missing ones fall back to the defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TextureImportSettings {
     pub pixels_per_unit: f32,
     pub filter_mode: FilterMode,
     pub wrap_mode: WrapMode,
     pub max_size: u32,
+    pub srgb: bool,
+    /// Source pixels already have color multiplied by alpha
+    pub premultiplied_alpha: bool,
+    /// Default sprite pivot, (0,0) = bottom-left, (1,1) = top-right
+    pub pivot: [f32; 2],
+    /// Mipmaps for Bilinear/Trilinear filtering; Point (pixel art) never gets them
+    pub generate_mipmaps: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FilterMode {
     Point,
     Bilinear,
     Trilinear,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WrapMode {
     Clamp,
     Repeat,
@@ -33,21 +47,139 @@ impl Default for TextureImportSettings {
             filter_mode: FilterMode::Point, // Use Point filtering for crisp pixel art
             wrap_mode: WrapMode::Clamp,
             max_size: 2048,
+            srgb: true,
+            premultiplied_alpha: false,
+            pivot: [0.5, 0.5],
+            generate_mipmaps: true,
         }
     }
 }
 
 impl TextureImportSettings {
-    pub fn load(_path: &Path) -> Result<Self, std::io::Error> {
-        // For now, just return default settings
-        // In the future, this could load from a .meta file
-        Ok(Self::default())
+    /// Settings for the texture at `path`; defaults when it has no .meta file
+    pub fn load(path: &Path) -> Result<Self, std::io::Error> {
+        match std::fs::read_to_string(Self::meta_path(path)) {
+            Ok(contents) => Self::from_json(&contents),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Same as `load`, reading through an AssetLoader (packed game builds)
+    pub fn load_with(loader: &dyn AssetLoader, path: &str) -> Self {
+        let meta_path = Self::meta_path(Path::new(path));
+        match pollster::block_on(loader.load_text(&meta_path.to_string_lossy())) {
+            Ok(contents) => Self::from_json(&contents).unwrap_or_else(|e| {
+                log::warn!("Ignoring invalid texture settings {}: {}", meta_path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn from_json(contents: &str) -> Result<Self, std::io::Error> {
+        serde_json::from_str(contents).map_err(std::io::Error::other)
+    }
+
+    /// `.meta` file for a texture: the full file name plus `.meta`
+    pub fn meta_path(texture_path: &Path) -> PathBuf {
+        let mut meta_path = texture_path.as_os_str().to_owned();
+        meta_path.push(".meta");
+        PathBuf::from(meta_path)
+    }
+
+    /// Options for creating the WGPU texture
+    pub fn gpu_options(&self) -> render::TextureOptions {
+        render::TextureOptions {
+            filter: match self.filter_mode {
+                FilterMode::Point => wgpu::FilterMode::Nearest,
+                FilterMode::Bilinear | FilterMode::Trilinear => wgpu::FilterMode::Linear,
+            },
+            address_mode: match self.wrap_mode {
+                WrapMode::Clamp => wgpu::AddressMode::ClampToEdge,
+                WrapMode::Repeat => wgpu::AddressMode::Repeat,
+                WrapMode::Mirror => wgpu::AddressMode::MirrorRepeat,
+            },
+            srgb: self.srgb,
+            premultiplied_alpha: self.premultiplied_alpha,
+            pivot: self.pivot,
+            mip_filter: self.mip_filter(),
+        }
+    }
+
+    /// Filter between mip levels, or None for no mipmaps: Trilinear blends two levels,
+    /// Bilinear picks the nearest one, and Point filtering is never mipmapped
+    fn mip_filter(&self) -> Option<wgpu::FilterMode> {
+        match self.filter_mode {
+            FilterMode::Point => None,
+            _ if !self.generate_mipmaps => None,
+            FilterMode::Bilinear => Some(wgpu::FilterMode::Nearest),
+            FilterMode::Trilinear => Some(wgpu::FilterMode::Linear),
+        }
+    }
+}
+
+/// Streaming threshold and texture budget from the project's rendering settings
+pub fn texture_streaming(rendering: &engine_core::project_settings::RenderingSettings) -> render::TextureStreaming {
+    render::TextureStreaming {
+        threshold: rendering.texture_streaming_size,
+        budget_bytes: rendering.texture_budget_mb as u64 * 1024 * 1024,
+    }
+}
+
+/// Screen rect of a sprite whose pivot sits at `anchor` (screen space, y down).
+/// Flipping mirrors the pivot, so a flipped sprite turns around its pivot.
+pub fn pivot_rect(anchor: egui::Pos2, size: egui::Vec2, pivot: [f32; 2], flip_x: bool, flip_y: bool) -> egui::Rect {
+    let pivot_x = if flip_x { 1.0 - pivot[0] } else { pivot[0] };
+    let pivot_y = if flip_y { 1.0 - pivot[1] } else { pivot[1] };
+    let min = egui::pos2(anchor.x - size.x * pivot_x, anchor.y - size.y * (1.0 - pivot_y));
+    egui::Rect::from_min_size(min, size)
+}
+
+/// Add `rect` showing the part of the mesh's texture that `uv` maps (sprite rect, flips and
+/// rotated packing included) to `mesh`
+pub fn add_sprite_quad(mesh: &mut egui::Mesh, rect: egui::Rect, uv: &ecs::SpriteUv, color: egui::Color32) {
+    let base = mesh.vertices.len() as u32;
+    let positions = [rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom()];
+    for (pos, [u, v]) in positions.into_iter().zip(uv.corners()) {
+        mesh.vertices.push(egui::epaint::Vertex { pos, uv: egui::pos2(u, v), color });
     }
+    mesh.add_triangle(base, base + 1, base + 2);
+    mesh.add_triangle(base, base + 2, base + 3);
+}
+
+/// A single sprite quad as a shape, for painters
+pub fn sprite_quad_shape(texture_id: egui::TextureId, rect: egui::Rect, uv: &ecs::SpriteUv, color: egui::Color32) -> egui::Shape {
+    let mut mesh = egui::Mesh::with_texture(texture_id);
+    add_sprite_quad(&mut mesh, rect, uv, color);
+    egui::Shape::mesh(mesh)
+}
+
+/// White pixels with the image's alpha
+fn silhouette_image(rgba: &image::RgbaImage) -> ColorImage {
+    let size = [rgba.width() as usize, rgba.height() as usize];
+    let pixels = rgba.pixels()
+        .map(|pixel| egui::Color32::from_rgba_unmultiplied(255, 255, 255, pixel[3]))
+        .collect();
+    ColorImage::new(size, pixels)
 }
 
 pub struct TextureManager {
     textures: HashMap<String, TextureHandle>,
     base_path: Option<PathBuf>,
+    /// When set, textures load on the server's threads and a placeholder is shown meanwhile
+    asset_server: Option<Arc<AssetServer>>,
+    pending: HashMap<String, Handle<ImageAsset>>,
+    failed: HashSet<String>,
+    placeholder_image: ColorImage,
+    placeholder: Option<TextureHandle>,
+    /// Import settings each loaded texture was created with
+    settings: HashMap<String, TextureImportSettings>,
+    /// Resolved file each texture id was loaded from, for reimporting
+    sources: HashMap<String, PathBuf>,
+    reimported: Vec<String>,
+    /// White copies of textures keeping their alpha (None when the source can't be read)
+    silhouettes: HashMap<String, Option<TextureHandle>>,
 }
 
 impl TextureManager {
@@ -55,6 +187,15 @@ impl TextureManager {
         Self {
             textures: HashMap::new(),
             base_path: None,
+            asset_server: None,
+            pending: HashMap::new(),
+            failed: HashSet::new(),
+            placeholder_image: Self::checker_image(),
+            placeholder: None,
+            settings: HashMap::new(),
+            sources: HashMap::new(),
+            reimported: Vec::new(),
+            silhouettes: HashMap::new(),
         }
     }
 
@@ -66,6 +207,160 @@ impl TextureManager {
         self.base_path.as_ref()
     }
 
+    /// File a texture path is loaded from: `path` under the base path
+    pub fn resolve_path(&self, path: &Path) -> PathBuf {
+        if let Some(base) = &self.base_path {
+            let joined = base.join(path);
+            // Check for common "assets/assets" duplication error
+            let joined_str = joined.to_string_lossy();
+            if joined_str.contains("assets\\assets") || joined_str.contains("assets/assets") {
+                // Try to fix it by removing one "assets"
+                let fixed_str = joined_str.replace("assets\\assets", "assets")
+                                         .replace("assets/assets", "assets");
+                PathBuf::from(fixed_str)
+            } else {
+                joined
+            }
+        } else {
+            path.to_path_buf()
+        }
+    }
+
+    /// Load textures asynchronously through `server`. Paths are resolved against the base path
+    /// and then handed to the server's AssetLoader.
+    pub fn set_asset_server(&mut self, server: Arc<AssetServer>) {
+        self.asset_server = Some(server);
+    }
+
+    /// Server textures load through, when set
+    pub fn asset_server(&self) -> Option<&Arc<AssetServer>> {
+        self.asset_server.as_ref()
+    }
+
+    /// Image shown for textures that are still loading (defaults to a magenta checkerboard)
+    pub fn set_placeholder(&mut self, image: ColorImage) {
+        self.placeholder_image = image;
+        self.placeholder = None;
+    }
+
+    /// Textures requested but not loaded yet
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Import settings `texture_id` was loaded with
+    pub fn import_settings(&self, texture_id: &str) -> Option<&TextureImportSettings> {
+        self.settings.get(texture_id)
+    }
+
+    /// Default sprite pivot from the texture's import settings (center when unknown)
+    pub fn sprite_pivot(&self, texture_id: &str) -> [f32; 2] {
+        self.settings.get(texture_id).map_or([0.5, 0.5], |s| s.pivot)
+    }
+
+    /// Drop every texture loaded from `asset_path` so the next `load_texture` re-creates it
+    /// with the current .meta settings. Returns the affected texture ids.
+    pub fn reimport(&mut self, asset_path: &Path) -> Vec<String> {
+        let target = asset_path.canonicalize().unwrap_or_else(|_| asset_path.to_path_buf());
+        let ids: Vec<String> = self
+            .sources
+            .iter()
+            .filter(|(_, source)| source.canonicalize().unwrap_or_else(|_| source.to_path_buf()) == target)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for id in &ids {
+            self.textures.remove(id);
+            self.settings.remove(id);
+            self.pending.remove(id);
+            self.failed.remove(id);
+            self.silhouettes.remove(id);
+            log::info!("Reimporting texture {}", id);
+        }
+        self.reimported.extend(ids.iter().cloned());
+        ids
+    }
+
+    /// Texture ids reimported since the last call, so other texture caches (WGPU) can follow
+    pub fn take_reimported(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.reimported)
+    }
+
+    /// Drop every texture whose id is not in `keep` (e.g. after a scene change), so the
+    /// GPU handles are freed. Returns the removed texture ids.
+    pub fn retain_textures(&mut self, keep: &HashSet<String>) -> Vec<String> {
+        let removed: Vec<String> = self
+            .textures
+            .keys()
+            .chain(self.pending.keys())
+            .chain(self.failed.iter())
+            .filter(|id| !keep.contains(*id))
+            .cloned()
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        for id in &removed {
+            self.textures.remove(id);
+            self.pending.remove(id);
+            self.failed.remove(id);
+            self.settings.remove(id);
+            self.sources.remove(id);
+            self.silhouettes.remove(id);
+        }
+        removed
+    }
+
+    /// Textures currently loaded
+    pub fn texture_count(&self) -> usize {
+        self.textures.len()
+    }
+
+    fn checker_image() -> ColorImage {
+        const SIZE: usize = 8;
+        let magenta = egui::Color32::from_rgb(255, 0, 255);
+        let pixels = (0..SIZE * SIZE)
+            .map(|i| if (i % SIZE < SIZE / 2) == (i / SIZE < SIZE / 2) { magenta } else { egui::Color32::BLACK })
+            .collect();
+        ColorImage::new([SIZE, SIZE], pixels)
+    }
+
+    fn placeholder(&mut self, ctx: &egui::Context) -> &TextureHandle {
+        let image = self.placeholder_image.clone();
+        self.placeholder
+            .get_or_insert_with(|| ctx.load_texture("__loading_placeholder", image, TextureOptions::NEAREST))
+    }
+
+    /// Async path of `load_texture`: start/poll the request, placeholder until it's ready
+    fn load_texture_async(&mut self, ctx: &egui::Context, texture_id: &str, full_path: &Path, settings: &TextureImportSettings) -> Option<&TextureHandle> {
+        if self.failed.contains(texture_id) {
+            return None;
+        }
+        let Some(server) = &self.asset_server else {
+            return None;
+        };
+
+        let handle = self
+            .pending
+            .entry(texture_id.to_string())
+            .or_insert_with(|| server.load::<ImageAsset>(&full_path.to_string_lossy()));
+
+        match handle.state() {
+            LoadState::Loaded => {
+                let handle = self.pending.remove(texture_id)?;
+                let image = handle.get()?;
+                let texture = self.upload_image(ctx, texture_id, image::DynamicImage::ImageRgba8(image.image.clone()), settings);
+                Some(texture)
+            }
+            LoadState::Failed(e) => {
+                log::error!("Failed to load texture {}: {}", full_path.display(), e);
+                self.pending.remove(texture_id);
+                self.failed.insert(texture_id.to_string());
+                None
+            }
+            LoadState::NotLoaded | LoadState::Loading => Some(self.placeholder(ctx)),
+        }
+    }
+
     /// Load texture with explicit path (no base path prepending)
     /// Useful when the path is already absolute or needs custom handling
     pub fn load_texture_absolute(&mut self, ctx: &egui::Context, texture_id: &str, full_path: &Path) -> Option<&TextureHandle> {
@@ -90,7 +385,8 @@ impl TextureManager {
         log::info!("Loading texture (absolute): {} from {}", texture_id, full_path.display());
         match std::fs::read(full_path) {
             Ok(bytes) => {
-                let settings = TextureImportSettings::default(); // Use default for absolute
+                let settings = TextureImportSettings::load(full_path).unwrap_or_default();
+                self.sources.insert(texture_id.to_string(), full_path.to_path_buf());
                 self.load_texture_from_bytes(ctx, texture_id, &bytes, &settings)
             }
             Err(e) => {
@@ -111,6 +407,7 @@ impl TextureManager {
         if self.textures.contains_key(texture_id) {
             return self.textures.get(texture_id);
         }
+        profiler::profile_scope!("textures.load");
 
         // Skip .sprite metadata files (they are not images)
         if let Some(ext) = path.extension() {
@@ -120,30 +417,23 @@ impl TextureManager {
             }
         }
 
-        // Resolve full path
-        let full_path = if let Some(base) = &self.base_path {
-            let joined = base.join(path);
-            // Check for common "assets/assets" duplication error
-            let joined_str = joined.to_string_lossy();
-            if joined_str.contains("assets\\assets") || joined_str.contains("assets/assets") {
-                // Try to fix it by removing one "assets"
-                let fixed_str = joined_str.replace("assets\\assets", "assets")
-                                         .replace("assets/assets", "assets");
-                PathBuf::from(fixed_str)
-            } else {
-                joined
-            }
-        } else {
-            path.to_path_buf()
-        };
+        let full_path = self.resolve_path(path);
 
         // Load import settings from .meta file
-        let settings = TextureImportSettings::load(&full_path).unwrap_or_default();
+        let settings = match &self.asset_server {
+            Some(server) => TextureImportSettings::load_with(server.loader().as_ref(), &full_path.to_string_lossy()),
+            None => TextureImportSettings::load(&full_path).unwrap_or_default(),
+        };
+        self.sources.insert(texture_id.to_string(), full_path.clone());
         
         // Load image
         log::info!("Loading texture: {} from {} (PPU: {}, Filter: {:?})", 
             texture_id, full_path.display(), settings.pixels_per_unit, settings.filter_mode);
         
+        if self.asset_server.is_some() {
+            return self.load_texture_async(ctx, texture_id, &full_path, &settings);
+        }
+
         match std::fs::read(&full_path) {
             Ok(bytes) => {
                 self.load_texture_from_bytes(ctx, texture_id, &bytes, &settings)
@@ -157,39 +447,8 @@ impl TextureManager {
 
     pub fn load_texture_from_bytes(&mut self, ctx: &egui::Context, texture_id: &str, bytes: &[u8], settings: &TextureImportSettings) -> Option<&TextureHandle> {
         match image::load_from_memory(bytes) {
-             Ok(mut img) => {
-                // Apply max size constraint
-                let (width, height) = (img.width(), img.height());
-                if width > settings.max_size || height > settings.max_size {
-                    let scale = (settings.max_size as f32 / width.max(height) as f32).min(1.0);
-                    let new_width = (width as f32 * scale) as u32;
-                    let new_height = (height as f32 * scale) as u32;
-                    log::info!("Resizing texture from {}x{} to {}x{}", width, height, new_width, new_height);
-                    img = img.resize(new_width, new_height, image::imageops::FilterType::Lanczos3);
-                }
-                
-                let rgba = img.to_rgba8();
-                let size = [rgba.width() as usize, rgba.height() as usize];
-                let pixels = rgba.as_flat_samples();
-                
-                log::info!("Texture loaded successfully: {}x{}", size[0], size[1]);
-                
-                let color_image = ColorImage::from_rgba_unmultiplied(
-                    size,
-                    pixels.as_slice(),
-                );
-
-                // Apply texture options based on import settings
-                let texture_options = Self::get_texture_options(&settings);
-                
-                let texture = ctx.load_texture(
-                    texture_id,
-                    color_image,
-                    texture_options,
-                );
-
-                self.textures.insert(texture_id.to_string(), texture);
-                self.textures.get(texture_id)
+             Ok(img) => {
+                Some(self.upload_image(ctx, texture_id, img, settings))
             }
             Err(e) => {
                 log::error!("Failed to load texture image from bytes for {}: {}", texture_id, e);
@@ -198,11 +457,75 @@ impl TextureManager {
         }
     }
 
+    /// Apply import settings (max size, filtering) and upload as `texture_id`
+    fn upload_image(&mut self, ctx: &egui::Context, texture_id: &str, mut img: image::DynamicImage, settings: &TextureImportSettings) -> &TextureHandle {
+        // Apply max size constraint
+        let (width, height) = (img.width(), img.height());
+        if width > settings.max_size || height > settings.max_size {
+            let scale = (settings.max_size as f32 / width.max(height) as f32).min(1.0);
+            let new_width = (width as f32 * scale) as u32;
+            let new_height = (height as f32 * scale) as u32;
+            log::info!("Resizing texture from {}x{} to {}x{}", width, height, new_width, new_height);
+            img = img.resize(new_width, new_height, image::imageops::FilterType::Lanczos3);
+        }
+
+        let rgba = img.to_rgba8();
+        let size = [rgba.width() as usize, rgba.height() as usize];
+        let pixels = rgba.as_flat_samples();
+
+        log::info!("Texture loaded successfully: {}x{}", size[0], size[1]);
+
+        let color_image = if settings.premultiplied_alpha {
+            ColorImage::from_rgba_premultiplied(size, pixels.as_slice())
+        } else {
+            ColorImage::from_rgba_unmultiplied(size, pixels.as_slice())
+        };
+
+        // Apply texture options based on import settings
+        let texture_options = Self::get_texture_options(settings);
+
+        let texture = ctx.load_texture(
+            texture_id,
+            color_image,
+            texture_options,
+        );
+
+        self.settings.insert(texture_id.to_string(), settings.clone());
+        self.textures.entry(texture_id.to_string()).insert_entry(texture).into_mut()
+    }
+
     pub fn get_texture(&self, texture_id: &str) -> Option<&TextureHandle> {
         self.textures.get(texture_id)
     }
+
+    /// White copy of a loaded texture with the same alpha, to draw a sprite's visible
+    /// pixels in any color (the scene view's selection outline). Made from the source
+    /// file on first use.
+    pub fn silhouette(&mut self, ctx: &egui::Context, texture_id: &str) -> Option<&TextureHandle> {
+        if !self.silhouettes.contains_key(texture_id) {
+            let source = self.sources.get(texture_id)?;
+            let bytes = match &self.asset_server {
+                Some(server) => pollster::block_on(server.loader().load_binary(&source.to_string_lossy())).ok(),
+                None => std::fs::read(source).ok(),
+            };
+            let silhouette = bytes.and_then(|bytes| image::load_from_memory(&bytes).ok()).map(|img| {
+                let settings = self.settings.get(texture_id).cloned().unwrap_or_default();
+                ctx.load_texture(
+                    format!("{}#silhouette", texture_id),
+                    silhouette_image(&img.to_rgba8()),
+                    Self::get_texture_options(&settings),
+                )
+            });
+            if silhouette.is_none() {
+                log::warn!("Can't read {} for the selection outline of {}", source.display(), texture_id);
+            }
+            self.silhouettes.insert(texture_id.to_string(), silhouette);
+        }
+        self.silhouettes.get(texture_id)?.as_ref()
+    }
     
-    /// Convert import settings to egui TextureOptions
+    /// Convert import settings to egui TextureOptions.
+    /// egui always treats texture colors as sRGB, so the srgb flag only affects WGPU textures.
     fn get_texture_options(settings: &TextureImportSettings) -> TextureOptions {
         
         let magnification = match settings.filter_mode {
@@ -225,7 +548,85 @@ impl TextureManager {
             magnification,
             minification,
             wrap_mode,
-            mipmap_mode: Default::default(),
+            mipmap_mode: settings.mip_filter().map(|filter| match filter {
+                wgpu::FilterMode::Nearest => egui::TextureFilter::Nearest,
+                wgpu::FilterMode::Linear => egui::TextureFilter::Linear,
+            }),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_editor_meta_and_defaults_missing_fields() {
+        let meta = r#"{
+            "texture_type": "Sprite2D",
+            "filter_mode": "Bilinear",
+            "wrap_mode": "Repeat",
+            "pivot": [0.5, 0.0],
+            "compression": "NormalQuality"
+        }"#;
+        let settings = TextureImportSettings::from_json(meta).unwrap();
+        assert_eq!(settings.filter_mode, FilterMode::Bilinear);
+        assert_eq!(settings.wrap_mode, WrapMode::Repeat);
+        assert_eq!(settings.pivot, [0.5, 0.0]);
+        assert!(settings.srgb);
+        assert!(!settings.premultiplied_alpha);
+
+        let options = settings.gpu_options();
+        assert_eq!(options.filter, wgpu::FilterMode::Linear);
+        assert_eq!(options.address_mode, wgpu::AddressMode::Repeat);
+        assert_eq!(options.mip_filter, Some(wgpu::FilterMode::Nearest));
+
+        assert_eq!(
+            TextureImportSettings::meta_path(Path::new("assets/player.png")),
+            PathBuf::from("assets/player.png.meta")
+        );
+    }
+
+    #[test]
+    fn test_pixel_art_is_never_mipmapped() {
+        let pixel_art = TextureImportSettings::default();
+        assert!(pixel_art.generate_mipmaps);
+        assert_eq!(pixel_art.gpu_options().mip_filter, None, "Point filtering ignores generate_mipmaps");
+        assert_eq!(pixel_art.gpu_options().mip_level_count(4096, 4096), 1);
+
+        let background = TextureImportSettings { filter_mode: FilterMode::Trilinear, ..Default::default() };
+        assert_eq!(background.gpu_options().mip_filter, Some(wgpu::FilterMode::Linear));
+        assert_eq!(background.gpu_options().mip_level_count(4096, 2048), 13);
+
+        let opted_out = TextureImportSettings { generate_mipmaps: false, ..background };
+        assert_eq!(opted_out.gpu_options().mip_level_count(4096, 2048), 1);
+    }
+
+    #[test]
+    fn test_silhouette_keeps_alpha_only() {
+        let mut rgba = image::RgbaImage::new(2, 1);
+        rgba.put_pixel(0, 0, image::Rgba([200, 10, 10, 255]));
+        rgba.put_pixel(1, 0, image::Rgba([10, 200, 10, 0]));
+        let silhouette = silhouette_image(&rgba);
+        assert_eq!(silhouette.size, [2, 1]);
+        assert_eq!(silhouette.pixels[0], egui::Color32::WHITE);
+        assert_eq!(silhouette.pixels[1].a(), 0);
+    }
+
+    #[test]
+    fn test_pivot_rect_places_pivot_at_anchor() {
+        let size = egui::vec2(20.0, 10.0);
+        let anchor = egui::pos2(100.0, 100.0);
+
+        let centered = pivot_rect(anchor, size, [0.5, 0.5], false, false);
+        assert_eq!(centered.center(), anchor);
+
+        // Bottom-center pivot: sprite stands on the anchor (screen y grows down)
+        let feet = pivot_rect(anchor, size, [0.5, 0.0], false, false);
+        assert_eq!(feet.center_bottom(), anchor);
+
+        // Flipping mirrors the pivot
+        let flipped = pivot_rect(anchor, size, [0.0, 0.0], true, false);
+        assert_eq!(flipped.right_bottom(), anchor);
+    }
+}
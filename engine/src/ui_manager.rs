@@ -7,8 +7,21 @@
 //! Full integration will be completed in future updates.
 
 use ecs::World;
-use ui::{UIPrefab, UIPrefabElement};
-use std::collections::HashMap;
+use ui::{Alignment, Axis, Corner, GridConstraint, UIPrefab, UIPrefabElement, UITheme, UITooltip, UIVirtualInput};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// System fonts with Thai glyphs (first one found is the fallback)
+const SYSTEM_FALLBACK_FONTS: &[&str] = &[
+    "C:\\Windows\\Fonts\\LeelawUI.ttf",
+    "C:\\Windows\\Fonts\\tahoma.ttf",
+    "/System/Library/Fonts/Supplemental/Ayuthaya.ttf",
+    "/System/Library/Fonts/Thonburi.ttc",
+    "/usr/share/fonts/truetype/noto/NotoSansThai-Regular.ttf",
+    "/usr/share/fonts/noto/NotoSansThai-Regular.ttf",
+    "/usr/share/fonts/truetype/tlwg/Garuda.ttf",
+    "/usr/share/fonts/TTF/Garuda.ttf",
+];
 
 /// UI System Manager - coordinates all UI systems
 pub struct UIManager {
@@ -17,10 +30,267 @@ pub struct UIManager {
     
     /// Active UI instances (name -> prefab)
     active_uis: HashMap<String, UIPrefab>,
+
+    /// Prefab file each active instance was created from (instance name -> path)
+    instance_sources: HashMap<String, String>,
     
     /// UI data for dynamic updates (element_path -> value)
     /// element_path format: "prefab_name/element_name"
     ui_data: HashMap<String, String>,
+
+    /// Screen rect of the last render (virtual controls are laid out in it)
+    screen_rect: Option<egui::Rect>,
+
+    /// Window scale factor of the last render (egui-winit keeps it equal to winit's)
+    pixels_per_point: f32,
+
+    /// Normalized safe area `[x, y, width, height]` (bottom-left origin) that elements
+    /// with a SafeArea component stay inside
+    safe_area: [f32; 4],
+
+    /// Virtual control state from the input system, for drawing
+    virtual_joysticks: HashMap<String, input::VirtualJoystickState>,
+    virtual_buttons_down: HashSet<String>,
+
+    /// Theme used to style elements created from scripts
+    style_system: ui::StyleSystem,
+
+    /// Instances whose layout groups need recomputing (done once per batch, see apply_pending_layouts)
+    layout_dirty: HashSet<String>,
+
+    /// Preferred size of each layout-driven element (full path -> size), so expanding
+    /// children to fill a group doesn't feed back into the next layout pass
+    layout_sizes: HashMap<String, egui::Vec2>,
+
+    /// Element with a tooltip the pointer (or focus) rests on
+    tooltip_hover: Option<TooltipHover>,
+
+    /// Element selected by gamepad/keyboard navigation (full path)
+    focused_element: Option<String>,
+
+    /// Where the tooltip was drawn last frame
+    tooltip_rect: Option<egui::Rect>,
+
+    /// Typing into input fields
+    input_system: ui::InputFieldSystem,
+
+    /// Pointer events and drag-and-drop between elements
+    pointer: ui::UIInputHandler,
+    drag_drop: ui::DragDropSystem,
+
+    /// String tables for UIText localization keys
+    localization: ui::Localization,
+
+    /// Localization revision the texts were last resolved for
+    localized_revision: u64,
+
+    /// `{name}` values for localized texts (full path -> params)
+    text_params: HashMap<String, HashMap<String, String>>,
+
+    /// Folder UIText font ids are relative to (the project's assets)
+    font_base_path: Option<PathBuf>,
+
+    /// Fonts for UIText.font (font id -> .ttf/.otf bytes, already validated)
+    fonts: HashMap<String, Vec<u8>>,
+
+    /// Font ids that failed to load (drawn with the default font), reported once
+    missing_fonts: BTreeSet<String>,
+    font_warnings: Vec<String>,
+
+    /// Font ids whose egui family is live this frame (set_fonts applies a frame later)
+    active_fonts: HashSet<String>,
+
+    /// Fonts or glyph fallbacks changed since they were installed in the egui context
+    fonts_changed: bool,
+
+    /// Stand-in entities for the ui crate's systems (full path -> entity; 0 means none)
+    element_entities: HashMap<String, ui::Entity>,
+    next_element_entity: ui::Entity,
+
+    /// Script listeners (UI.on_submit / UI.on_value_changed)
+    listeners: Vec<UIListener>,
+
+    /// Listener callbacks fired since the last take_script_events
+    script_events: Vec<script::UIScriptEvent>,
+
+    /// Event bindings declared in the active instances' prefab files (on_click /
+    /// on_value_changed), registered on activation
+    bindings: Vec<UIBinding>,
+
+    /// Script entity that activated each instance (runs its Lua bindings)
+    instance_owners: HashMap<String, ecs::Entity>,
+
+    /// Button, toggle or slider the primary button went down on (full path)
+    pressed_control: Option<String>,
+
+    /// Stack of modal instances (UI.show_modal): drawn above the other UIs, and only
+    /// the top one takes input
+    popups: ui::PopupManager,
+
+    /// Render textures registered with egui for UIImage (name -> texture, generation)
+    render_textures: HashMap<String, (egui::TextureId, u64)>,
+
+    /// Unknown paths scripts used (each reported once, with the closest existing path)
+    reported_paths: HashSet<String>,
+    path_warnings: Vec<String>,
+
+    /// Running UI.fade_group tweens, advanced by render
+    group_fades: Vec<GroupFade>,
+
+    /// State look of every button, toggle, slider and dropdown on screen (full path ->
+    /// visual), advanced by render
+    control_visuals: HashMap<String, ControlVisual>,
+
+    /// Recent pointer and script events, for the editor's UI Events panel (records
+    /// only while enabled)
+    event_log: ui::UIEventLog,
+
+    /// Pointer events over every element on screen, tracked while the event log records
+    inspect_pointer: ui::UIInputHandler,
+}
+
+/// Script callback registered for an element's event
+struct UIListener {
+    element_path: String,
+    event: script::UIEventKind,
+    owner: ecs::Entity,
+    callback: String,
+    /// Whether events that fire while the owner's script is disabled are kept for it
+    while_disabled: script::DisabledPolicy,
+}
+
+/// Prefab event binding of an element in an active instance
+struct UIBinding {
+    element_path: String,
+    event: script::UIEventKind,
+    action: ui::UIAction,
+}
+
+/// Canvas group alpha tween of an element (UI.fade_group)
+struct GroupFade {
+    element_path: String,
+    animation: ui::UIAnimation,
+    /// Script that started it (runs the animation's on_complete)
+    owner: ecs::Entity,
+}
+
+/// Animated look of a control (see UIManager::update_control_visuals)
+struct ControlVisual {
+    state: ui::InteractableState,
+    /// Tint fading toward the state's color
+    tint: ui::UIAnimation,
+    /// Slider fill/knob position (normalized value) smoothing toward the value
+    slider: Option<ui::UIAnimation>,
+    /// Toggle checkmark visibility, 0 (off) to 1 (on)
+    checkmark: Option<ui::UIAnimation>,
+}
+
+impl ControlVisual {
+    fn tint(&self) -> ui::Color {
+        match self.tint.get_current_value() {
+            ui::AnimationValue::Color(color) => color,
+            _ => ui::Color::WHITE,
+        }
+    }
+
+    fn float(animation: &ui::UIAnimation) -> f32 {
+        match animation.get_current_value() {
+            ui::AnimationValue::Float(value) => value,
+            _ => 0.0,
+        }
+    }
+
+    /// An animation that is already at `value`
+    fn settled(entity: ui::Entity, property: ui::AnimatedProperty, value: ui::AnimationValue) -> ui::UIAnimation {
+        ui::UIAnimation::new(entity, property, value.clone(), value, 0.0)
+    }
+
+    /// Restart `animation` from where it is toward `to`, unless it is already headed there
+    fn retarget(animation: &mut ui::UIAnimation, to: ui::AnimationValue, duration: f32) {
+        let same = match (&animation.to, &to) {
+            (ui::AnimationValue::Float(a), ui::AnimationValue::Float(b)) => (a - b).abs() <= f32::EPSILON,
+            (ui::AnimationValue::Color(a), ui::AnimationValue::Color(b)) => a == b,
+            _ => false,
+        };
+        if !same {
+            let mut next = ui::UIAnimation::new(
+                animation.entity,
+                animation.property.clone(),
+                animation.get_current_value(),
+                to,
+                duration.max(0.0),
+            );
+            next.easing = ui::EasingFunction::EaseOutQuad;
+            *animation = next;
+        }
+    }
+}
+
+/// Dwell state of the element under the pointer/focus
+struct TooltipHover {
+    path: String,
+    /// egui input time when the hover started
+    since: f64,
+    /// Pressed since: hidden until the hover moves to another element
+    dismissed: bool,
+}
+
+/// Where an element of a preview (see UIManager::preview_layout) was placed, in the
+/// coordinates the preview was drawn in
+#[derive(Debug, Clone)]
+pub struct PreviewElement {
+    pub name: String,
+    /// Full path, starting with the prefab's name
+    pub path: String,
+    /// Bounds of the element
+    pub rect: egui::Rect,
+    /// Bounds of the rect its anchors are relative to: the parent's, shrunk to the safe
+    /// area if the element has a SafeArea
+    pub parent_rect: egui::Rect,
+    /// The RectTransform it was placed with (a layout group's, for children of one)
+    pub transform: ui::RectTransform,
+    /// Points per reference unit
+    pub scale: f32,
+}
+
+/// An element under a point, from UIManager::inspect_point
+#[derive(Debug, Clone, PartialEq)]
+pub struct UIHitInfo {
+    /// Full path, starting with the instance name
+    pub path: String,
+    /// Why the pointer passes over it (None: it is the one hit)
+    pub skipped: Option<String>,
+    /// Corners on screen (top-left, top-right, bottom-right, bottom-left)
+    pub corners: [egui::Pos2; 4],
+}
+
+/// Script callback or prefab binding of an element, from UIManager::element_callbacks
+#[derive(Debug, Clone, PartialEq)]
+pub struct UICallbackInfo {
+    pub element_path: String,
+    /// "Click", "ValueChanged", "Submit" or "Drop"
+    pub event: String,
+    /// Lua function, or what a prefab binding does ("show Panel")
+    pub callback: String,
+    /// Script the callback runs in (None for engine actions, or a Lua binding of an
+    /// instance no script activated)
+    pub owner: Option<ecs::Entity>,
+}
+
+/// Element on screen, as the event log and inspect_point see it
+struct InspectTarget {
+    entity: ui::Entity,
+    path: String,
+    placement: Placement,
+    /// A button, toggle or slider that takes clicks (what update_controls picks from)
+    control: bool,
+}
+
+/// Element with a tooltip, as placed on screen this frame
+struct TooltipTarget {
+    path: String,
+    placement: Placement,
+    tooltip: UITooltip,
 }
 
 impl UIManager {
@@ -28,7 +298,81 @@ impl UIManager {
         Self {
             loaded_prefabs: HashMap::new(),
             active_uis: HashMap::new(),
+            instance_sources: HashMap::new(),
             ui_data: HashMap::new(),
+            screen_rect: None,
+            pixels_per_point: 1.0,
+            safe_area: [0.0, 0.0, 1.0, 1.0],
+            virtual_joysticks: HashMap::new(),
+            virtual_buttons_down: HashSet::new(),
+            style_system: ui::StyleSystem::new(),
+            layout_dirty: HashSet::new(),
+            layout_sizes: HashMap::new(),
+            tooltip_hover: None,
+            focused_element: None,
+            tooltip_rect: None,
+            input_system: ui::InputFieldSystem::new(),
+            pointer: ui::UIInputHandler::new(),
+            drag_drop: ui::DragDropSystem::new(),
+            localization: ui::Localization::new(),
+            localized_revision: 0,
+            text_params: HashMap::new(),
+            font_base_path: None,
+            fonts: HashMap::new(),
+            missing_fonts: BTreeSet::new(),
+            font_warnings: Vec::new(),
+            active_fonts: HashSet::new(),
+            fonts_changed: true,
+            element_entities: HashMap::new(),
+            next_element_entity: 1,
+            listeners: Vec::new(),
+            script_events: Vec::new(),
+            bindings: Vec::new(),
+            instance_owners: HashMap::new(),
+            pressed_control: None,
+            popups: ui::PopupManager::new(),
+            render_textures: HashMap::new(),
+            reported_paths: HashSet::new(),
+            path_warnings: Vec::new(),
+            group_fades: Vec::new(),
+            control_visuals: HashMap::new(),
+            event_log: ui::UIEventLog::new(),
+            inspect_pointer: ui::UIInputHandler::new(),
+        }
+    }
+
+    /// Make render textures available to UIImages (`sprite: "rendertexture://name"`).
+    /// Call once per frame: new ones are registered with egui, re-created (resized)
+    /// ones re-bound and removed ones freed.
+    pub fn sync_render_textures(
+        &mut self,
+        egui_renderer: &mut egui_wgpu::Renderer,
+        device: &wgpu::Device,
+        render_textures: &render::RenderTextureManager,
+    ) {
+        self.render_textures.retain(|name, (id, _)| {
+            let exists = render_textures.get(name).is_some();
+            if !exists {
+                egui_renderer.free_texture(id);
+            }
+            exists
+        });
+
+        for name in render_textures.names() {
+            let Some(target) = render_textures.get(name) else {
+                continue;
+            };
+            match self.render_textures.get_mut(name) {
+                Some((_, generation)) if *generation == target.generation => {}
+                Some((id, generation)) => {
+                    egui_renderer.update_egui_texture_from_wgpu_texture(device, &target.sampled_view, wgpu::FilterMode::Linear, *id);
+                    *generation = target.generation;
+                }
+                None => {
+                    let id = egui_renderer.register_native_texture(device, &target.sampled_view, wgpu::FilterMode::Linear);
+                    self.render_textures.insert(name.to_string(), (id, target.generation));
+                }
+            }
         }
     }
 
@@ -41,293 +385,4396 @@ impl UIManager {
             .map_err(|e| format!("Failed to parse prefab {}: {}", path, e))?;
         
         log::info!("Loaded UI prefab: {} from {}", prefab.name, path);
+        for warning in Self::validate_bindings(&prefab) {
+            log::warn!("{}: {}", path, warning);
+        }
         self.loaded_prefabs.insert(path.to_string(), prefab);
         Ok(())
     }
 
+    /// Add a prefab built in code, activated with `path` like a loaded one
+    pub fn register_prefab(&mut self, path: &str, prefab: UIPrefab) {
+        self.loaded_prefabs.insert(path.to_string(), prefab);
+    }
+
+    pub fn is_prefab_loaded(&self, path: &str) -> bool {
+        self.loaded_prefabs.contains_key(path)
+    }
+
+    /// Problems with a prefab's event bindings: show/hide targets that aren't elements of
+    /// the prefab, set_canvas prefab files that don't exist, empty Lua function names
+    pub fn validate_bindings(prefab: &UIPrefab) -> Vec<String> {
+        let mut warnings = Vec::new();
+        let mut nodes = Vec::new();
+        Self::collect_indices(&prefab.root, &mut Vec::new(), &mut nodes);
+        for indices in &nodes {
+            let element_path = Self::canonical_path(&prefab.name, &prefab.root, indices);
+            for (event, binding) in Self::element_bindings(Self::element_at(&prefab.root, indices)) {
+                let problem = match binding.action() {
+                    ui::UIAction::Lua { function } if function.trim().is_empty() => Some("no Lua function".to_string()),
+                    ui::UIAction::Show { target } | ui::UIAction::Hide { target } if Self::find_path(&prefab.root, &target).is_none() => {
+                        Some(format!("target element '{}' not found", target))
+                    }
+                    ui::UIAction::SetCanvas { target } if !Path::new(&target).is_file() => {
+                        Some(format!("prefab file '{}' not found", target))
+                    }
+                    _ => None,
+                };
+                if let Some(problem) = problem {
+                    warnings.push(format!("{:?} binding on '{}': {}", event, element_path, problem));
+                }
+            }
+        }
+        warnings
+    }
+
+    /// on_click / on_value_changed bindings declared on an element
+    fn element_bindings(element: &UIPrefabElement) -> Vec<(script::UIEventKind, &ui::UIEventBinding)> {
+        let click = element.button.as_ref().and_then(|button| button.on_click.as_ref());
+        let changed = [
+            element.slider.as_ref().and_then(|slider| slider.on_value_changed.as_ref()),
+            element.toggle.as_ref().and_then(|toggle| toggle.on_value_changed.as_ref()),
+        ];
+        click.map(|binding| (script::UIEventKind::Click, binding))
+            .into_iter()
+            .chain(changed.into_iter().flatten().map(|binding| (script::UIEventKind::ValueChanged, binding)))
+            .collect()
+    }
+
     /// Activate a loaded prefab (make it visible)
     pub fn activate_prefab(&mut self, path: &str, instance_name: &str) -> Result<(), String> {
+        self.activate(path, instance_name, None)
+    }
+
+    /// Activate a loaded prefab on behalf of a script: the prefab's Lua bindings call
+    /// functions in `owner`'s script
+    pub fn activate_prefab_for(&mut self, path: &str, instance_name: &str, owner: ecs::Entity) -> Result<(), String> {
+        self.activate(path, instance_name, Some(owner))
+    }
+
+    fn activate(&mut self, path: &str, instance_name: &str, owner: Option<ecs::Entity>) -> Result<(), String> {
         let prefab = self.loaded_prefabs.get(path)
             .ok_or_else(|| format!("Prefab not loaded: {}", path))?
             .clone();
         
         log::info!("Activated UI prefab: {} as {}", prefab.name, instance_name);
+        self.popups.remove(instance_name);
+        self.remove_bindings(instance_name);
         self.active_uis.insert(instance_name.to_string(), prefab);
+        self.instance_sources.insert(instance_name.to_string(), path.to_string());
+        match owner {
+            Some(owner) => self.instance_owners.insert(instance_name.to_string(), owner),
+            None => self.instance_owners.remove(instance_name),
+        };
+        self.register_bindings(instance_name);
+        self.clear_layout_sizes(instance_name);
+        self.layout_dirty.insert(instance_name.to_string());
         Ok(())
     }
 
-    /// Deactivate a UI instance
+    /// Register the event bindings declared in an active instance's prefab
+    fn register_bindings(&mut self, instance_name: &str) {
+        let Some(prefab) = self.active_uis.get(instance_name) else { return };
+        let mut nodes = Vec::new();
+        Self::collect_indices(&prefab.root, &mut Vec::new(), &mut nodes);
+        for indices in &nodes {
+            let element_path = Self::canonical_path(instance_name, &prefab.root, indices);
+            for (event, binding) in Self::element_bindings(Self::element_at(&prefab.root, indices)) {
+                self.bindings.push(UIBinding { element_path: element_path.clone(), event, action: binding.action() });
+            }
+        }
+        let has_lua = self.bindings.iter()
+            .any(|binding| Self::in_instance(&binding.element_path, instance_name) && matches!(binding.action, ui::UIAction::Lua { .. }));
+        if has_lua && !self.instance_owners.contains_key(instance_name) {
+            log::warn!("UI '{}' has Lua event bindings but wasn't activated by a script; they won't run", instance_name);
+        }
+    }
+
+    fn remove_bindings(&mut self, instance_name: &str) {
+        self.bindings.retain(|binding| !Self::in_instance(&binding.element_path, instance_name));
+    }
+
+    /// `element_path` is the instance itself or one of its elements
+    fn in_instance(element_path: &str, instance_name: &str) -> bool {
+        element_path.split_once('/').map_or(element_path, |(instance, _)| instance) == instance_name
+    }
+
+    /// Deactivate a UI instance at once (a modal deactivated this way doesn't call its
+    /// on_closed)
     pub fn deactivate_prefab(&mut self, instance_name: &str) {
+        self.popups.remove(instance_name);
+        self.remove_bindings(instance_name);
+        self.instance_owners.remove(instance_name);
         self.active_uis.remove(instance_name);
+        self.instance_sources.remove(instance_name);
+        self.layout_dirty.remove(instance_name);
+        self.clear_layout_sizes(instance_name);
+        self.group_fades.retain(|fade| !Self::in_instance(&fade.element_path, instance_name));
+        self.control_visuals.retain(|path, _| !Self::in_instance(path, instance_name));
         log::info!("Deactivated UI: {}", instance_name);
     }
 
+    /// Deactivate every UI instance and drop the per-element state (data, listeners,
+    /// pending script events) that belonged to them, e.g. on a full scene load
+    pub fn deactivate_all_prefabs(&mut self) {
+        let instances: Vec<String> = self.active_uis.keys().cloned().collect();
+        for instance_name in instances {
+            self.deactivate_prefab(&instance_name);
+        }
+        self.ui_data.clear();
+        self.text_params.clear();
+        self.listeners.clear();
+        self.script_events.clear();
+        self.pressed_control = None;
+        self.popups.clear();
+        self.element_entities.clear();
+        self.drag_drop.cancel();
+        self.focused_element = None;
+        self.tooltip_hover = None;
+        self.reported_paths.clear();
+    }
+
+    /// Number of active UI instances
+    pub fn active_instance_count(&self) -> usize {
+        self.active_uis.len()
+    }
+
+    /// Names of the active UI instances, sorted
+    pub fn instance_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.active_uis.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Live (possibly script-modified) prefab data of an active instance
+    pub fn instance(&self, instance_name: &str) -> Option<&UIPrefab> {
+        self.active_uis.get(instance_name)
+    }
+
+    /// Prefab file an active instance was created from
+    pub fn instance_source(&self, instance_name: &str) -> Option<&str> {
+        self.instance_sources.get(instance_name).map(String::as_str)
+    }
+
+    /// Element at "instance" or "instance/child/..." in an active instance
+    pub fn element(&self, element_path: &str) -> Option<&UIPrefabElement> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
+        let prefab = self.active_uis.get(instance_name)?;
+        let indices = Self::find_path(&prefab.root, relative_path)?;
+        Some(Self::element_at(&prefab.root, &indices))
+    }
+
+    /// Replace the components of an element (its children are kept), e.g. after an
+    /// inspector edit. The instance is laid out again on the next apply_pending_layouts.
+    pub fn set_element(&mut self, element_path: &str, element: &UIPrefabElement) -> Result<(), String> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        let indices = Self::find_path(&prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+        let target = Self::element_at_mut(&mut prefab.root, &indices);
+        let children = std::mem::take(&mut target.children);
+        *target = UIPrefabElement { children, ..element.clone() };
+        self.layout_dirty.insert(instance_name.to_string());
+        Ok(())
+    }
+
+    /// Write the live components of `element_paths` (all in `instance_name`) back into
+    /// the prefab file the instance came from ("apply to prefab"). Elements the file
+    /// doesn't have (e.g. created by scripts) are skipped. Returns (file, elements written).
+    pub fn apply_instance_to_prefab(&mut self, instance_name: &str, element_paths: &[String]) -> Result<(String, usize), String> {
+        let path = self.instance_sources.get(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?
+            .clone();
+        let file_content = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read prefab file {}: {}", path, e))?;
+        let mut prefab: UIPrefab = serde_json::from_str(&file_content)
+            .map_err(|e| format!("Failed to parse prefab {}: {}", path, e))?;
+
+        let mut written = 0;
+        for element_path in element_paths {
+            let relative_path = match element_path.split_once('/') {
+                Some((instance, relative)) if instance == instance_name => relative,
+                None if element_path == instance_name => "",
+                _ => continue,
+            };
+            let (Some(live), Some(indices)) = (self.element(element_path), Self::find_path(&prefab.root, relative_path)) else {
+                continue;
+            };
+            let target = Self::element_at_mut(&mut prefab.root, &indices);
+            let children = std::mem::take(&mut target.children);
+            *target = UIPrefabElement { children, ..live.clone() };
+            written += 1;
+        }
+
+        let json = serde_json::to_string_pretty(&prefab)
+            .map_err(|e| format!("Failed to serialize prefab {}: {}", path, e))?;
+        std::fs::write(&path, json)
+            .map_err(|e| format!("Failed to write prefab file {}: {}", path, e))?;
+        self.loaded_prefabs.insert(path.clone(), prefab);
+        Ok((path, written))
+    }
+
+    /// Apply a theme style to `element` (unknown classes fall back to the active style),
+    /// the same way add_element styles new elements
+    pub fn apply_style_class(&self, element: &mut UIPrefabElement, style_class: Option<&str>) {
+        let style = match style_class {
+            Some(class) => self.style_system.get_style(class).or_else(|| {
+                log::warn!("Unknown UI style '{}', using the active style", class);
+                self.style_system.theme().get_active_style()
+            }),
+            None => self.style_system.theme().get_active_style(),
+        };
+        if let Some(style) = style {
+            if let Some(panel) = &mut element.panel {
+                self.style_system.apply_style_to_panel(style, panel);
+                element.ui_element.color = style.background_color;
+            }
+            if let Some(button) = &mut element.button {
+                self.style_system.apply_style_to_button(style, button);
+                element.ui_element.color = style.primary_color;
+            }
+            if let Some(text) = &mut element.text {
+                self.style_system.apply_style_to_text(style, text);
+            }
+        }
+    }
+
     /// Update UI element data (for dynamic text, values, etc.)
     pub fn set_ui_data(&mut self, element_path: &str, value: String) {
         self.ui_data.insert(element_path.to_string(), value);
     }
 
+    /// Text shown by a UIText (UI.set_text), kept under the element's full path so any
+    /// path that finds the element ("hud/score" for "hud/panel/score") shows it
+    pub fn set_text(&mut self, element_path: &str, text: String) -> Result<(), String> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
+        let prefab = self.active_uis.get(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        let indices = Self::find_path(&prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+        let path = Self::canonical_path(instance_name, &prefab.root, &indices);
+        self.ui_data.insert(path, text);
+        Ok(())
+    }
+
     /// Get UI element data
     pub fn get_ui_data(&self, element_path: &str) -> Option<&String> {
         self.ui_data.get(element_path)
     }
 
-    /// Update all UI systems
-    pub fn update(&mut self, _world: &mut World, _dt: f32, _screen_size: (u32, u32)) {
-        // Future: Update animations, layouts, etc.
+    /// Theme used to style elements created with add_element
+    pub fn theme(&self) -> &UITheme {
+        self.style_system.theme()
     }
 
-    /// Render UI (to be called during game view rendering)
-    pub fn render(&mut self, ui: &mut egui::Ui, _world: &World, rect: egui::Rect) {
-        // Debug: Log when render is called
-        if !self.active_uis.is_empty() {
-            log::debug!("UIManager::render called with {} active UIs", self.active_uis.len());
-        }
-        
-        // Render all active UI instances
-        for (instance_name, prefab) in &self.active_uis {
-            log::debug!("Rendering UI instance: {}", instance_name);
-            self.render_prefab(ui, rect, instance_name, prefab);
-        }
+    pub fn set_theme(&mut self, theme: UITheme) {
+        self.style_system.set_theme(theme);
     }
 
-    /// Render a single prefab
-    fn render_prefab(&self, ui: &mut egui::Ui, screen_rect: egui::Rect, instance_name: &str, prefab: &UIPrefab) {
-        let painter = ui.painter_at(screen_rect);
-        
-        // Render root and all children recursively
-        self.render_element(
-            &painter,
-            screen_rect,
-            instance_name,
-            &prefab.root,
-            screen_rect.size(),
-        );
-    }
+    /// Add an element under `parent_path` ("instance" for the root, or "instance/child/...").
+    /// The element is styled with `style_class` (or the theme's active style), then `color`
+    /// if given. Layout groups pick it up on the next apply_pending_layouts.
+    /// Returns the new element's path.
+    pub fn add_element(
+        &mut self,
+        parent_path: &str,
+        mut element: UIPrefabElement,
+        style_class: Option<&str>,
+        color: Option<ui::Color>,
+    ) -> Result<String, String> {
+        self.apply_style_class(&mut element, style_class);
+        if let Some(color) = color {
+            element.ui_element.color = color;
+        }
 
-    /// Render a UI element recursively
-    fn render_element(
-        &self,
-        painter: &egui::Painter,
-        parent_rect: egui::Rect,
-        instance_name: &str,
-        element: &UIPrefabElement,
-        canvas_size: egui::Vec2,
-    ) {
-        // Calculate element rect based on RectTransform
-        let element_rect = self.calculate_rect(parent_rect, &element.rect_transform, canvas_size);
-        
-        // Debug: Log element position (use RUST_LOG=debug to see)
-        log::debug!(
-            "Element '{}': anchor=[{:.1},{:.1}]->[{:.1},{:.1}], pos=[{:.1},{:.1}], rect={:?}",
-            element.name,
-            element.rect_transform.anchor_min.x,
-            element.rect_transform.anchor_min.y,
-            element.rect_transform.anchor_max.x,
-            element.rect_transform.anchor_max.y,
-            element.rect_transform.anchored_position.x,
-            element.rect_transform.anchored_position.y,
-            element_rect
+        let (instance_name, relative_path) = parent_path.split_once('/').unwrap_or((parent_path, ""));
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        let indices = Self::find_path(&prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", parent_path))?;
+        let element_path = format!(
+            "{}/{}",
+            Self::canonical_path(instance_name, &prefab.root, &indices),
+            element.name
         );
-        
-        // Render background image if present
-        if let Some(image) = &element.image {
-            let color = egui::Color32::from_rgba_unmultiplied(
-                (element.ui_element.color[0] * 255.0) as u8,
-                (element.ui_element.color[1] * 255.0) as u8,
-                (element.ui_element.color[2] * 255.0) as u8,
-                (element.ui_element.color[3] * element.ui_element.alpha * 255.0) as u8,
-            );
-            
-            // For filled images, adjust width based on fill_amount
-            let render_rect = if matches!(image.image_type, ui::ImageType::Filled) {
-                let fill_width = element_rect.width() * image.fill_amount;
-                egui::Rect::from_min_size(
-                    element_rect.min,
-                    egui::vec2(fill_width, element_rect.height()),
-                )
-            } else {
-                element_rect
-            };
-            
-            painter.rect_filled(render_rect, 2.0, color);
+
+        let parent = Self::element_at_mut(&mut prefab.root, &indices);
+        if parent.children.iter().any(|child| child.name == element.name) {
+            return Err(format!("Element already exists: {}", element_path));
         }
-        
-        // Render text if present
-        if let Some(text) = &element.text {
-            let element_path = format!("{}/{}", instance_name, element.name);
-            let display_text = self.ui_data.get(&element_path)
-                .map(|s| s.as_str())
-                .unwrap_or(&text.text);
-            
-            let color = egui::Color32::from_rgba_unmultiplied(
-                (text.color[0] * 255.0) as u8,
-                (text.color[1] * 255.0) as u8,
-                (text.color[2] * 255.0) as u8,
-                (text.color[3] * element.ui_element.alpha * 255.0) as u8,
-            );
-            
-            let align = match text.alignment {
-                ui::TextAlignment::TopLeft | ui::TextAlignment::MiddleLeft | ui::TextAlignment::BottomLeft => egui::Align2::LEFT_CENTER,
-                ui::TextAlignment::TopCenter | ui::TextAlignment::MiddleCenter | ui::TextAlignment::BottomCenter => egui::Align2::CENTER_CENTER,
-                ui::TextAlignment::TopRight | ui::TextAlignment::MiddleRight | ui::TextAlignment::BottomRight => egui::Align2::RIGHT_CENTER,
-            };
-            
-            painter.text(
-                element_rect.center(),
-                align,
-                display_text,
-                egui::FontId::proportional(text.font_size),
-                color,
-            );
+
+        parent.children.push(element);
+        self.layout_dirty.insert(instance_name.to_string());
+        Ok(element_path)
+    }
+
+    /// Remove an element (and its children) by path
+    pub fn destroy_element(&mut self, element_path: &str) -> Result<(), String> {
+        let (instance_name, relative_path) = element_path.split_once('/')
+            .ok_or_else(|| format!("Cannot destroy the root of a UI instance: {}", element_path))?;
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        let mut indices = Self::find_path(&prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+        let canonical = Self::canonical_path(instance_name, &prefab.root, &indices);
+        let index = indices.pop()
+            .ok_or_else(|| format!("Cannot destroy the root of a UI instance: {}", element_path))?;
+        Self::element_at_mut(&mut prefab.root, &indices).children.remove(index);
+
+        let prefix = format!("{}/", canonical);
+        self.ui_data.retain(|path, _| *path != canonical && !path.starts_with(&prefix));
+        self.text_params.retain(|path, _| *path != canonical && !path.starts_with(&prefix));
+        self.layout_sizes.retain(|path, _| *path != canonical && !path.starts_with(&prefix));
+        self.listeners.retain(|listener| listener.element_path != canonical && !listener.element_path.starts_with(&prefix));
+        let removed = |path: &String| *path == canonical || path.starts_with(&prefix);
+        self.group_fades.retain(|fade| !removed(&fade.element_path));
+        self.control_visuals.retain(|path, _| !removed(path));
+        let dragged = self.drag_drop.active_drag().map(|drag| drag.source);
+        if self.element_entities.iter().any(|(path, entity)| Some(*entity) == dragged && removed(path)) {
+            self.drag_drop.cancel();
         }
-        
-        // Render children
-        for child in &element.children {
-            self.render_element(painter, element_rect, instance_name, child, canvas_size);
+        self.element_entities.retain(|path, _| !removed(path));
+        self.layout_dirty.insert(instance_name.to_string());
+        Ok(())
+    }
+
+    /// Move an element among its siblings: `None` puts it last (drawn on top, e.g. a
+    /// popup), `Some(index)` at that position (clamped). Layout groups follow the same order.
+    pub fn set_sibling_index(&mut self, element_path: &str, index: Option<usize>) -> Result<(), String> {
+        let (instance_name, relative_path) = element_path.split_once('/')
+            .ok_or_else(|| format!("The root of a UI instance has no siblings: {}", element_path))?;
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        let mut indices = Self::find_path(&prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+        let current = indices.pop()
+            .ok_or_else(|| format!("The root of a UI instance has no siblings: {}", element_path))?;
+
+        let siblings = &mut Self::element_at_mut(&mut prefab.root, &indices).children;
+        let element = siblings.remove(current);
+        let index = index.unwrap_or(siblings.len()).min(siblings.len());
+        siblings.insert(index, element);
+        self.layout_dirty.insert(instance_name.to_string());
+        Ok(())
+    }
+
+    pub fn set_as_first_sibling(&mut self, element_path: &str) -> Result<(), String> {
+        self.set_sibling_index(element_path, Some(0))
+    }
+
+    pub fn set_as_last_sibling(&mut self, element_path: &str) -> Result<(), String> {
+        self.set_sibling_index(element_path, None)
+    }
+
+    /// Position of an element among its siblings
+    pub fn get_sibling_index(&self, element_path: &str) -> Option<usize> {
+        let (instance_name, relative_path) = element_path.split_once('/')?;
+        let prefab = self.active_uis.get(instance_name)?;
+        Self::find_path(&prefab.root, relative_path)?.pop()
+    }
+
+    /// Set the tooltip text of an element, adding a default tooltip if it has none.
+    /// Empty text removes the tooltip.
+    pub fn set_tooltip(&mut self, element_path: &str, text: String) -> Result<(), String> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        let element = Self::find_element_mut(&mut prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+
+        if text.is_empty() {
+            element.tooltip = None;
+        } else if let Some(tooltip) = &mut element.tooltip {
+            tooltip.text = text;
+        } else {
+            element.tooltip = Some(UITooltip::with_text(text));
         }
+        Ok(())
     }
 
-    /// Calculate screen rect from RectTransform (Unity-style)
-    fn calculate_rect(
-        &self,
-        parent_rect: egui::Rect,
-        transform: &ui::RectTransform,
-        _canvas_size: egui::Vec2,
-    ) -> egui::Rect {
-        let parent_size = parent_rect.size();
-        
-        // Unity uses bottom-up Y (0=bottom, 1=top)
-        // egui uses top-down Y (0=top, 1=bottom)
-        // So we need to flip the Y anchors
-        let flipped_anchor_min_y = 1.0 - transform.anchor_max.y;
-        let flipped_anchor_max_y = 1.0 - transform.anchor_min.y;
-        
-        // Calculate anchor points in parent space (Unity-style with Y-flip)
-        let anchor_min_pos = egui::pos2(
-            parent_rect.min.x + parent_size.x * transform.anchor_min.x,
-            parent_rect.min.y + parent_size.y * flipped_anchor_min_y,
-        );
-        
-        let anchor_max_pos = egui::pos2(
-            parent_rect.min.x + parent_size.x * transform.anchor_max.x,
-            parent_rect.min.y + parent_size.y * flipped_anchor_max_y,
-        );
-        
-        // Calculate the rect between anchors
-        let anchored_rect = egui::Rect::from_min_max(anchor_min_pos, anchor_max_pos);
-        
-        // Apply size_delta (offset from anchored size)
-        // If anchors are the same point, size_delta defines the full size
-        // If anchors are different, size_delta is added to the anchored size
-        let final_size = egui::vec2(
-            anchored_rect.width() + transform.size_delta.x,
-            anchored_rect.height() + transform.size_delta.y,
-        );
-        
-        // Calculate the center of the anchored rect
-        let anchor_center = anchored_rect.center();
-        
-        // Apply anchored_position (offset from anchor center)
-        // Flip Y offset because Unity uses bottom-up Y
-        let offset_center = egui::pos2(
-            anchor_center.x + transform.anchored_position.x,
-            anchor_center.y - transform.anchored_position.y,  // Flip Y
-        );
-        
-        // Apply pivot to determine the actual position
-        // Unity pivot: (0,0)=bottom-left, (0.5,0.5)=center, (1,1)=top-right
-        // egui origin: top-left
-        // So we need to flip pivot.y: Unity (0,0)=bottom-left → egui (0,1)=bottom-left
-        let flipped_pivot_y = 1.0 - transform.pivot.y;
-        
-        let pivot_offset = egui::vec2(
-            -final_size.x * transform.pivot.x,
-            -final_size.y * flipped_pivot_y,
-        );
-        
-        let final_min = egui::pos2(
-            offset_center.x + pivot_offset.x,
-            offset_center.y + pivot_offset.y,
-        );
-        
-        egui::Rect::from_min_size(final_min, final_size)
+    /// The canvas group of an element, added with default values if it has none
+    fn canvas_group_mut(&mut self, element_path: &str) -> Result<&mut ui::UICanvasGroup, String> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        let element = Self::find_element_mut(&mut prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+        Ok(element.canvas_group.get_or_insert_with(ui::UICanvasGroup::default))
     }
-    
-    /// Set element color (for images)
-    pub fn set_element_color(&mut self, instance_name: &str, element_name: &str, r: f32, g: f32, b: f32, a: f32) -> Result<(), String> {
+
+    /// Set the canvas group alpha of an element (fading it and everything below it),
+    /// stopping a fade_group running on it
+    pub fn set_group_alpha(&mut self, element_path: &str, alpha: f32) -> Result<(), String> {
+        self.canvas_group_mut(element_path)?.alpha = alpha.clamp(0.0, 1.0);
+        self.group_fades.retain(|fade| fade.element_path != element_path);
+        Ok(())
+    }
+
+    /// Enable or disable the controls of an element and everything below it
+    pub fn set_group_interactable(&mut self, element_path: &str, interactable: bool) -> Result<(), String> {
+        self.canvas_group_mut(element_path)?.interactable = interactable;
+        Ok(())
+    }
+
+    /// Enable or disable the control of a single element (canvas groups above it still
+    /// apply). A disabled control shows its disabled look and takes no clicks.
+    pub fn set_interactable(&mut self, element_path: &str, interactable: bool) -> Result<(), String> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
         let prefab = self.active_uis.get_mut(instance_name)
             .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
-        
-        if let Some(element) = Self::find_element_mut(&mut prefab.root, element_name) {
-            element.ui_element.color = [r, g, b, a];
-            Ok(())
-        } else {
-            Err(format!("Element not found: {}", element_name))
+        let element = Self::find_element_mut(&mut prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+        element.ui_element.interactable = interactable;
+        Ok(())
+    }
+
+    /// Set a slider's value (clamped to its range, rounded for whole numbers) without
+    /// firing value-changed. Its fill and knob catch up over its smoothing_time.
+    pub fn set_slider(&mut self, element_path: &str, value: f32) -> Result<(), String> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        let element = Self::find_element_mut(&mut prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+        let slider = element.slider.as_mut()
+            .ok_or_else(|| format!("Element '{}' has no slider component", element_path))?;
+        slider.value = value;
+        ui::SliderSystem::clamp_slider_value(slider);
+        Ok(())
+    }
+
+    /// Tween the canvas group alpha of an element from its current value to `to`. A fade
+    /// already running on it is replaced (without its on_complete); this one's
+    /// `on_complete` runs in `owner`'s script when it ends.
+    pub fn fade_group(
+        &mut self,
+        element_path: &str,
+        to: f32,
+        duration: f32,
+        easing: ui::EasingFunction,
+        owner: ecs::Entity,
+        on_complete: Option<String>,
+    ) -> Result<(), String> {
+        let from = self.canvas_group_mut(element_path)?.alpha;
+        let mut animation = ui::UIAnimation::new(
+            self.element_entity(element_path),
+            ui::AnimatedProperty::GroupAlpha,
+            ui::AnimationValue::Float(from),
+            ui::AnimationValue::Float(to.clamp(0.0, 1.0)),
+            duration,
+        );
+        animation.easing = easing;
+        animation.on_complete = on_complete;
+        self.group_fades.retain(|fade| fade.element_path != element_path);
+        self.group_fades.push(GroupFade { element_path: element_path.to_string(), animation, owner });
+        Ok(())
+    }
+
+    /// Advance the fade_group tweens. Fades of elements that are gone end silently.
+    fn update_group_fades(&mut self, dt: f32) {
+        for mut fade in std::mem::take(&mut self.group_fades) {
+            let running = fade.animation.update(dt);
+            let Ok(group) = self.canvas_group_mut(&fade.element_path) else {
+                continue;
+            };
+            fade.animation.apply_to_canvas_group(group);
+            if running {
+                self.group_fades.push(fade);
+            } else if let Some(callback) = fade.animation.on_complete {
+                // A one-shot result the script asked for: kept for it like a timer callback
+                self.script_events.push(script::UIScriptEvent {
+                    owner: fade.owner,
+                    callback,
+                    element_path: fade.element_path,
+                    value: String::new(),
+                    while_disabled: script::DisabledPolicy::Buffer,
+                });
+            }
         }
     }
-    
-    /// Set element fill amount (for filled images)
-    pub fn set_element_fill(&mut self, instance_name: &str, element_name: &str, fill_amount: f32) -> Result<(), String> {
+
+    /// Turn a toggle on/off with the same toggle group rules as a click: turning a member
+    /// on turns the rest of its group off, and the active member only turns off when the
+    /// group allows it. Groups are matched by id within the element's instance.
+    pub fn set_toggle(&mut self, element_path: &str, is_on: bool) -> Result<(), String> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
         let prefab = self.active_uis.get_mut(instance_name)
             .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
-        
-        if let Some(element) = Self::find_element_mut(&mut prefab.root, element_name) {
-            if let Some(image) = &mut element.image {
-                image.fill_amount = fill_amount.clamp(0.0, 1.0);
-                Ok(())
-            } else {
-                Err(format!("Element '{}' has no image component", element_name))
+        let target = Self::find_path(&prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+
+        // ToggleSystem works on entities: number the instance's elements in tree order
+        let mut nodes = Vec::new();
+        Self::collect_indices(&prefab.root, &mut Vec::new(), &mut nodes);
+        let mut toggles = HashMap::new();
+        let mut groups = HashMap::new();
+        for (entity, indices) in nodes.iter().enumerate() {
+            let element = Self::element_at(&prefab.root, indices);
+            if let Some(toggle) = &element.toggle {
+                toggles.insert(entity as ui::Entity, toggle.clone());
             }
-        } else {
-            Err(format!("Element not found: {}", element_name))
+            if let Some(group) = &element.toggle_group {
+                groups.insert(entity as ui::Entity, group.clone());
+            }
+        }
+        let entity = nodes.iter().position(|indices| *indices == target).unwrap_or_default() as ui::Entity;
+        if !toggles.contains_key(&entity) {
+            return Err(format!("Element '{}' has no toggle component", element_path));
+        }
+
+        let change = ui::ToggleSystem::set_toggle_state(entity, is_on, &mut toggles, &groups, &mut HashMap::new());
+        for (entity, toggle) in toggles {
+            Self::element_at_mut(&mut prefab.root, &nodes[entity as usize]).toggle = Some(toggle);
+        }
+        if let Some(ui::UIEvent::ToggleGroupChanged(group, active)) = change {
+            let path = |entity: ui::Entity| Self::canonical_path(instance_name, &prefab.root, &nodes[entity as usize]);
+            log::debug!("Toggle group '{}' active: {:?}", path(group), active.map(path));
         }
+        Ok(())
     }
-    
-    /// Show element (set alpha to 1.0)
-    pub fn show_element(&mut self, instance_name: &str, element_name: &str) -> Result<(), String> {
+
+    /// Replace an input field's text (checked against its content type, cut to its
+    /// character limit). Doesn't fire value-changed.
+    pub fn set_input_text(&mut self, element_path: &str, text: String) -> Result<(), String> {
+        let field = self.input_field_mut(element_path)?;
+        if !ui::InputFieldSystem::validate_text(&text, &field.content_type) {
+            return Err(format!("'{}' is not valid {:?} content for {}", text, field.content_type, element_path));
+        }
+        let mut fields = HashMap::from([(0, std::mem::take(field))]);
+        ui::InputFieldSystem::set_text(0, text, &mut fields, &mut HashMap::new());
+        *field = fields.remove(&0).unwrap_or_default();
+        Ok(())
+    }
+
+    /// Text shown while an input field is empty
+    pub fn set_placeholder(&mut self, element_path: &str, text: String) -> Result<(), String> {
+        self.input_field_mut(element_path)?.placeholder_text = text;
+        Ok(())
+    }
+
+    /// Which characters an input field accepts from typing
+    pub fn set_character_validation(&mut self, element_path: &str, validation: ui::CharacterValidation) -> Result<(), String> {
+        self.input_field_mut(element_path)?.character_validation = validation;
+        Ok(())
+    }
+
+    fn input_field_mut(&mut self, element_path: &str) -> Result<&mut ui::UIInputField, String> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
         let prefab = self.active_uis.get_mut(instance_name)
             .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
-        
-        if let Some(element) = Self::find_element_mut(&mut prefab.root, element_name) {
-            element.ui_element.alpha = 1.0;
-            Ok(())
+        Self::find_element_mut(&mut prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?
+            .input_field
+            .as_mut()
+            .ok_or_else(|| format!("Element '{}' has no input field component", element_path))
+    }
+
+    /// Run `callback` in `owner`'s script when `event` fires on the element. Registering
+    /// the same callback again only updates its `while_disabled` policy.
+    pub fn add_event_listener(
+        &mut self,
+        element_path: &str,
+        event: script::UIEventKind,
+        owner: ecs::Entity,
+        callback: String,
+        while_disabled: script::DisabledPolicy,
+    ) -> Result<(), String> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
+        let prefab = self.active_uis.get(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        let indices = Self::find_path(&prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+        let element_path = Self::canonical_path(instance_name, &prefab.root, &indices);
+
+        let existing = self.listeners.iter_mut().find(|listener| {
+            listener.element_path == element_path && listener.event == event && listener.owner == owner && listener.callback == callback
+        });
+        match existing {
+            Some(listener) => listener.while_disabled = while_disabled,
+            None => self.listeners.push(UIListener { element_path, event, owner, callback, while_disabled }),
+        }
+        Ok(())
+    }
+
+    /// Drop the listeners (and pending events) of a destroyed entity's script
+    pub fn remove_entity_listeners(&mut self, owner: ecs::Entity) {
+        self.listeners.retain(|listener| listener.owner != owner);
+        self.instance_owners.retain(|_, instance_owner| *instance_owner != owner);
+        self.script_events.retain(|event| event.owner != owner);
+    }
+
+    /// Script callbacks fired since the last call (hand them to ScriptEngine::queue_ui_events)
+    pub fn take_script_events(&mut self) -> Vec<script::UIScriptEvent> {
+        std::mem::take(&mut self.script_events)
+    }
+
+    /// Current text and validation of every input field, by full path and by the short
+    /// "instance/name" form (for ScriptEngine::sync_input_fields)
+    pub fn input_field_snapshots(&self) -> HashMap<String, script::InputFieldSnapshot> {
+        let mut snapshots = HashMap::new();
+        for (instance_name, prefab) in &self.active_uis {
+            Self::collect_input_snapshots(&prefab.root, instance_name, instance_name, &mut snapshots);
+        }
+        snapshots
+    }
+
+    fn collect_input_snapshots(
+        element: &UIPrefabElement,
+        instance_name: &str,
+        element_path: &str,
+        out: &mut HashMap<String, script::InputFieldSnapshot>,
+    ) {
+        if let Some(field) = &element.input_field {
+            let snapshot = script::InputFieldSnapshot {
+                text: field.text.clone(),
+                character_validation: field.character_validation,
+            };
+            out.entry(format!("{}/{}", instance_name, element.name)).or_insert_with(|| snapshot.clone());
+            out.insert(element_path.to_string(), snapshot);
+        }
+        for child in &element.children {
+            let child_path = format!("{}/{}", element_path, child.name);
+            Self::collect_input_snapshots(child, instance_name, &child_path, out);
+        }
+    }
+
+    /// Replace the string tables (e.g. `ui::Localization::load_for_project`); texts
+    /// re-resolve on the next layout pass
+    pub fn set_localization(&mut self, localization: ui::Localization) {
+        self.localization = localization;
+        self.localized_revision = u64::MAX;
+        self.fonts_changed = true;
+    }
+
+    pub fn localization(&self) -> &ui::Localization {
+        &self.localization
+    }
+
+    /// Set the normalized safe area `[x, y, width, height]` (bottom-left origin), e.g.
+    /// from `SafeAreaSettings::active_rect`
+    pub fn set_safe_area(&mut self, safe_area: [f32; 4]) {
+        if self.safe_area != safe_area {
+            self.safe_area = safe_area;
+            self.layout_dirty.extend(self.active_uis.keys().cloned());
+        }
+    }
+
+    /// Safe area in the screen coordinates of `screen_rect`
+    fn safe_rect(&self, screen_rect: egui::Rect) -> egui::Rect {
+        let [x, y, width, height] = self.safe_area;
+        let size = screen_rect.size();
+        egui::Rect::from_min_size(
+            egui::pos2(screen_rect.min.x + x * size.x, screen_rect.min.y + (1.0 - y - height) * size.y),
+            egui::vec2(width * size.x, height * size.y),
+        )
+    }
+
+    /// Placement of the screen itself, the parent of every instance's root
+    fn root_placement(&self, screen_rect: egui::Rect) -> Placement {
+        Placement { safe_rect: self.safe_rect(screen_rect), ..Placement::screen(screen_rect) }
+    }
+
+    /// Pixels per reference unit of each active instance, for converting window pixel
+    /// positions to canvas coordinates (see ui::CanvasSpace)
+    pub fn canvas_scales(&self) -> HashMap<String, f32> {
+        let Some(screen_rect) = self.screen_rect else {
+            return HashMap::new();
+        };
+        self.active_uis.iter()
+            .map(|(name, prefab)| (name.clone(), Self::canvas_scale(prefab, screen_rect, self.pixels_per_point) * self.pixels_per_point))
+            .collect()
+    }
+
+    /// Switch language; every localized text refreshes on the next layout pass
+    pub fn set_language(&mut self, language: &str) -> Result<(), String> {
+        self.localization.set_language(language)
+    }
+
+    /// Localization keys the active language is missing, fonts that failed to load and
+    /// unknown paths scripts used (each reported once), for the console
+    pub fn take_warnings(&mut self) -> Vec<String> {
+        let mut warnings = std::mem::take(&mut self.font_warnings);
+        warnings.extend(self.localization.take_missing_warning());
+        warnings.append(&mut self.path_warnings);
+        warnings
+    }
+
+    /// Apply one frame's UI commands as a batch, in the order scripts queued them. Each
+    /// command sees the ones before it (elements of a prefab loaded and activated earlier
+    /// in the batch can be addressed right away), and layouts are recomputed once at the
+    /// end, so the whole batch shows up together in the next render.
+    /// Returns one result per command, in order.
+    pub fn apply_commands(&mut self, commands: Vec<script::UICommand>) -> Vec<Result<(), script::UICommandError>> {
+        let results = commands.into_iter().map(|command| self.apply_command(command)).collect();
+        self.apply_pending_layouts();
+        results
+    }
+
+    fn apply_command(&mut self, mut command: script::UICommand) -> Result<(), script::UICommandError> {
+        use script::UICommand;
+
+        let (name, path) = Self::command_target(&command);
+        let failed = |path: String, message: String| script::UICommandError { command: name, path, not_found: false, suggestion: None, message };
+        if command.element_path_mut().is_none() {
+            return self.run_command(command).map_err(|message| failed(path, message));
+        }
+
+        let mut targets = self.resolve(&path).map_err(|message| failed(path.clone(), message))?;
+        if targets.is_empty() {
+            return Err(self.path_not_found(name, path));
+        }
+        match command {
+            UICommand::SetSiblingIndex { .. } if targets.len() > 1 => {
+                let message = format!("Path matches {} elements; set_sibling_index needs exactly one", targets.len());
+                return Err(failed(path, message));
+            }
+            // Later siblings first, so "Name[n]" paths of the remaining matches stay valid
+            UICommand::DestroyElement { .. } => targets.reverse(),
+            _ => {}
+        }
+
+        let last = targets.pop().expect("at least one target");
+        let mut first_error = None;
+        for target in targets {
+            let mut copy = command.clone();
+            *copy.element_path_mut().expect("element command") = target.clone();
+            if let Err(message) = self.run_command(copy) {
+                first_error.get_or_insert(failed(target, message));
+            }
+        }
+        *command.element_path_mut().expect("element command") = last.clone();
+        if let Err(message) = self.run_command(command) {
+            first_error.get_or_insert(failed(last, message));
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+
+    fn run_command(&mut self, command: script::UICommand) -> Result<(), String> {
+        use script::UICommand;
+
+        // Element paths are "instance/element/..." ("instance" alone is the root)
+        let split = |path: &str| -> (String, String) {
+            let (instance, element) = path.split_once('/').unwrap_or((path, ""));
+            (instance.to_string(), element.to_string())
+        };
+        match command {
+            UICommand::LoadPrefab { path } => self.load_prefab(&path),
+            UICommand::ActivatePrefab { path, instance_name, owner } => self.activate_prefab_for(&path, &instance_name, owner),
+            UICommand::DeactivatePrefab { instance_name } => {
+                self.deactivate_prefab(&instance_name);
+                Ok(())
+            }
+            UICommand::SetText { element_path, text } => self.set_text(&element_path, text),
+            UICommand::SetImageFill { element_path, fill_amount } => {
+                let (instance, element) = split(&element_path);
+                self.set_element_fill(&instance, &element, fill_amount)
+            }
+            UICommand::SetColor { element_path, r, g, b, a } => {
+                let (instance, element) = split(&element_path);
+                self.set_element_color(&instance, &element, r, g, b, a)
+            }
+            UICommand::ShowElement { element_path } => {
+                let (instance, element) = split(&element_path);
+                self.show_element(&instance, &element)
+            }
+            UICommand::HideElement { element_path } => {
+                let (instance, element) = split(&element_path);
+                self.hide_element(&instance, &element)
+            }
+            UICommand::CreateElement { parent_path, element, style_class, color } => {
+                self.add_element(&parent_path, *element, style_class.as_deref(), color).map(|_| ())
+            }
+            UICommand::DestroyElement { element_path } => self.destroy_element(&element_path),
+            UICommand::SetSiblingIndex { element_path, index } => self.set_sibling_index(&element_path, index),
+            UICommand::SetTooltip { element_path, text } => self.set_tooltip(&element_path, text),
+            UICommand::SetToggle { element_path, is_on } => self.set_toggle(&element_path, is_on),
+            UICommand::SetInputText { element_path, text } => self.set_input_text(&element_path, text),
+            UICommand::SetPlaceholder { element_path, text } => self.set_placeholder(&element_path, text),
+            UICommand::SetCharacterValidation { element_path, validation } => self.set_character_validation(&element_path, validation),
+            UICommand::AddEventListener { element_path, event, owner, callback, while_disabled } => {
+                self.add_event_listener(&element_path, event, owner, callback, while_disabled)
+            }
+            UICommand::SetTextParams { element_path, params } => self.set_text_params(&element_path, params),
+            UICommand::SetLanguage { language } => self.set_language(&language),
+            UICommand::ShowModal { path, instance_name, owner, options } => self.show_modal(&path, &instance_name, owner, options),
+            UICommand::CloseModal { result } => self.close_modal(&result),
+            UICommand::SetGroupAlpha { element_path, alpha } => self.set_group_alpha(&element_path, alpha),
+            UICommand::SetGroupInteractable { element_path, interactable } => {
+                self.set_group_interactable(&element_path, interactable)
+            }
+            UICommand::FadeGroup { element_path, to, duration, easing, owner, on_complete } => {
+                self.fade_group(&element_path, to, duration, easing, owner, on_complete)
+            }
+            UICommand::SetInteractable { element_path, interactable } => self.set_interactable(&element_path, interactable),
+            UICommand::SetSlider { element_path, value } => self.set_slider(&element_path, value),
+        }
+    }
+
+    /// Lua function name and the path (or file, language) the command refers to
+    fn command_target(command: &script::UICommand) -> (&'static str, String) {
+        use script::UICommand;
+        match command {
+            UICommand::LoadPrefab { path } => ("load_prefab", path.clone()),
+            UICommand::ActivatePrefab { path, .. } => ("activate_prefab", path.clone()),
+            UICommand::DeactivatePrefab { instance_name } => ("deactivate_prefab", instance_name.clone()),
+            UICommand::SetLanguage { language } => ("set_language", language.clone()),
+            UICommand::ShowModal { path, .. } => ("show_modal", path.clone()),
+            UICommand::CloseModal { result } => ("close_modal", result.clone()),
+            UICommand::SetText { element_path, .. } => ("set_text", element_path.clone()),
+            UICommand::SetImageFill { element_path, .. } => ("set_image_fill", element_path.clone()),
+            UICommand::SetColor { element_path, .. } => ("set_color", element_path.clone()),
+            UICommand::ShowElement { element_path } => ("show_element", element_path.clone()),
+            UICommand::HideElement { element_path } => ("hide_element", element_path.clone()),
+            UICommand::CreateElement { parent_path, .. } => ("create_element", parent_path.clone()),
+            UICommand::DestroyElement { element_path } => ("destroy_element", element_path.clone()),
+            UICommand::SetSiblingIndex { element_path, .. } => ("set_sibling_index", element_path.clone()),
+            UICommand::SetTooltip { element_path, .. } => ("set_tooltip", element_path.clone()),
+            UICommand::SetToggle { element_path, .. } => ("set_toggle", element_path.clone()),
+            UICommand::SetInputText { element_path, .. } => ("set_input_text", element_path.clone()),
+            UICommand::SetPlaceholder { element_path, .. } => ("set_placeholder", element_path.clone()),
+            UICommand::SetCharacterValidation { element_path, .. } => ("set_character_validation", element_path.clone()),
+            UICommand::AddEventListener { element_path, .. } => ("add_event_listener", element_path.clone()),
+            UICommand::SetTextParams { element_path, .. } => ("set_text_params", element_path.clone()),
+            UICommand::SetGroupAlpha { element_path, .. } => ("set_group_alpha", element_path.clone()),
+            UICommand::SetGroupInteractable { element_path, .. } => ("set_group_interactable", element_path.clone()),
+            UICommand::FadeGroup { element_path, .. } => ("fade_group", element_path.clone()),
+            UICommand::SetInteractable { element_path, .. } => ("set_interactable", element_path.clone()),
+            UICommand::SetSlider { element_path, .. } => ("set_slider", element_path.clone()),
+        }
+    }
+
+    /// Every element a command path names, as exact paths ("instance/list/Row[2]/label").
+    /// Besides plain "instance/a/b" paths, "@instance/..." starts strictly at the root of
+    /// that activated instance, "*" matches every child of one level and "Name[n]" the
+    /// n-th child called Name (counting from 1, as in Lua). Wildcard matches come depth
+    /// first in sibling order. A plain path whose first segment isn't a root child is
+    /// looked up by name anywhere in the instance, as find_path does ("Name[n]" there
+    /// is the n-th element with that name, depth first).
+    /// Err for a malformed path; an empty list when nothing matches.
+    fn resolve(&self, path: &str) -> Result<Vec<String>, String> {
+        let parsed = ElementPath::parse(path)?;
+        let Some(prefab) = self.active_uis.get(&parsed.instance) else {
+            return Ok(Vec::new());
+        };
+
+        let mut matches = Vec::new();
+        Self::match_segments(&prefab.root, &parsed.segments, &mut Vec::new(), &mut matches);
+        if matches.is_empty() && !parsed.scoped {
+            let start = match parsed.segments.split_first() {
+                Some((PathSegment::Name(name), rest)) => Some((name, 1, rest)),
+                Some((PathSegment::Nth(name, n), rest)) => Some((name, *n, rest)),
+                _ => None,
+            };
+            if let Some((name, n, rest)) = start {
+                if let Some(mut start) = Self::find_nth_by_name(&prefab.root, name, n) {
+                    let element = Self::element_at(&prefab.root, &start);
+                    Self::match_segments(element, rest, &mut start, &mut matches);
+                }
+            }
+        }
+        Ok(matches.iter().map(|indices| Self::exact_path(&parsed.instance, &prefab.root, indices)).collect())
+    }
+
+    fn match_segments(element: &UIPrefabElement, segments: &[PathSegment], prefix: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+        let Some((segment, rest)) = segments.split_first() else {
+            out.push(prefix.clone());
+            return;
+        };
+        let indices: Vec<usize> = match segment {
+            PathSegment::Any => (0..element.children.len()).collect(),
+            PathSegment::Name(name) => Self::nth_child(element, name, 1).into_iter().collect(),
+            PathSegment::Nth(name, n) => Self::nth_child(element, name, *n).into_iter().collect(),
+        };
+        for index in indices {
+            prefix.push(index);
+            Self::match_segments(&element.children[index], rest, prefix, out);
+            prefix.pop();
+        }
+    }
+
+    /// Index of the n-th child (from 1) named `name`
+    fn nth_child(element: &UIPrefabElement, name: &str, n: usize) -> Option<usize> {
+        element.children.iter()
+            .enumerate()
+            .filter(|(_, child)| child.name == name)
+            .nth(n.checked_sub(1)?)
+            .map(|(index, _)| index)
+    }
+
+    /// Path naming exactly the element at `indices`: like canonical_path, with "Name[n]"
+    /// where earlier siblings share the name
+    fn exact_path(instance_name: &str, root: &UIPrefabElement, indices: &[usize]) -> String {
+        let mut path = instance_name.to_string();
+        let mut current = root;
+        for &index in indices {
+            let earlier = current.children[..index].iter().filter(|child| child.name == current.children[index].name).count();
+            current = &current.children[index];
+            path.push('/');
+            path.push_str(&current.name);
+            if earlier > 0 {
+                path.push_str(&format!("[{}]", earlier + 1));
+            }
+        }
+        path
+    }
+
+    /// Error for a path that names nothing, with the closest existing path; the first
+    /// time a path comes up it's also queued as a warning
+    fn path_not_found(&mut self, command: &'static str, path: String) -> script::UICommandError {
+        let instance_name = path.split_once('/').map_or(path.as_str(), |(instance, _)| instance).trim_start_matches('@');
+        let message = if self.active_uis.contains_key(instance_name) {
+            format!("Element not found: {}", path)
         } else {
-            Err(format!("Element not found: {}", element_name))
+            format!("UI instance not found: {}", instance_name)
+        };
+        let error = script::UICommandError {
+            command,
+            suggestion: self.closest_path(&path),
+            path,
+            not_found: true,
+            message,
+        };
+        if self.reported_paths.insert(error.path.clone()) {
+            self.path_warnings.push(error.to_string());
         }
+        error
     }
-    
-    /// Hide element (set alpha to 0.0)
-    pub fn hide_element(&mut self, instance_name: &str, element_name: &str) -> Result<(), String> {
-        let prefab = self.active_uis.get_mut(instance_name)
+
+    /// Full path of an existing element (or instance) that `path` is most likely a typo
+    /// of, if any is close enough
+    fn closest_path(&self, path: &str) -> Option<String> {
+        let mut candidates = Vec::new();
+        for (instance_name, prefab) in &self.active_uis {
+            let mut nodes = Vec::new();
+            Self::collect_indices(&prefab.root, &mut Vec::new(), &mut nodes);
+            candidates.extend(nodes.iter().map(|indices| Self::canonical_path(instance_name, &prefab.root, indices)));
+        }
+        let max_distance = (path.chars().count() / 3).max(2);
+        candidates.into_iter()
+            .map(|candidate| (edit_distance(path, &candidate), candidate))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min()
+            .map(|(_, candidate)| candidate)
+    }
+
+    /// `{name}` values for a text with a localization key ("Collected {count}/{total}")
+    pub fn set_text_params(&mut self, element_path: &str, params: HashMap<String, String>) -> Result<(), String> {
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
+        let prefab = self.active_uis.get(instance_name)
             .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
-        
-        if let Some(element) = Self::find_element_mut(&mut prefab.root, element_name) {
-            element.ui_element.alpha = 0.0;
-            Ok(())
+        let indices = Self::find_path(&prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+        let element = Self::element_at(&prefab.root, &indices);
+        if element.text.as_ref().and_then(|text| text.localization_key.as_ref()).is_none() {
+            return Err(format!("Element '{}' has no localized text", element_path));
+        }
+
+        let canonical = Self::canonical_path(instance_name, &prefab.root, &indices);
+        self.text_params.insert(canonical, params);
+        self.layout_dirty.insert(instance_name.to_string());
+        Ok(())
+    }
+
+    /// Resolve localization keys in instances about to be laid out (new or changed), or in
+    /// all of them after a language change. Instances whose text changed get laid out again.
+    fn apply_localization(&mut self) {
+        let revision = self.localization.revision();
+        let instances: Vec<String> = if revision != self.localized_revision {
+            self.active_uis.keys().cloned().collect()
         } else {
-            Err(format!("Element not found: {}", element_name))
+            self.layout_dirty.iter().cloned().collect()
+        };
+        self.localized_revision = revision;
+
+        for instance_name in instances {
+            let Some(prefab) = self.active_uis.get_mut(&instance_name) else { continue };
+            if Self::localize_element(&mut prefab.root, &instance_name, &mut self.localization, &self.text_params) {
+                self.layout_dirty.insert(instance_name);
+            }
         }
     }
-    
-    /// Find element by name (recursive, mutable)
-    fn find_element_mut<'a>(element: &'a mut UIPrefabElement, name: &str) -> Option<&'a mut UIPrefabElement> {
-        if element.name == name {
-            return Some(element);
+
+    /// Returns true if any text changed
+    fn localize_element(
+        element: &mut UIPrefabElement,
+        element_path: &str,
+        localization: &mut ui::Localization,
+        params: &HashMap<String, HashMap<String, String>>,
+    ) -> bool {
+        let mut changed = false;
+        if let Some(text) = &mut element.text {
+            if let Some(key) = &text.localization_key {
+                let resolved = localization.resolve(key, params.get(element_path).unwrap_or(&HashMap::new()));
+                if resolved != text.text {
+                    text.text = resolved;
+                    changed = true;
+                }
+            }
         }
-        
         for child in &mut element.children {
-            if let Some(found) = Self::find_element_mut(child, name) {
-                return Some(found);
+            let child_path = format!("{}/{}", element_path, child.name);
+            changed |= Self::localize_element(child, &child_path, localization, params);
+        }
+        changed
+    }
+
+    /// Folder UIText font ids are looked up in ("fonts/Title.ttf" -> `<path>/fonts/Title.ttf`)
+    pub fn set_font_base_path(&mut self, path: impl Into<PathBuf>) {
+        self.font_base_path = Some(path.into());
+        // Fonts that were missing may exist there
+        self.missing_fonts.clear();
+        self.layout_dirty.extend(self.active_uis.keys().cloned());
+    }
+
+    /// Load a .ttf/.otf file for texts whose font is `font_id`
+    pub fn register_font(&mut self, font_id: &str, path: &Path) -> Result<(), String> {
+        let data = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        // egui panics on font data it can't parse, so check it first
+        ab_glyph::FontRef::try_from_slice(&data).map_err(|e| format!("{}: {}", path.display(), e))?;
+        self.fonts.insert(font_id.to_string(), data);
+        self.missing_fonts.remove(font_id);
+        self.fonts_changed = true;
+        Ok(())
+    }
+
+    /// Load the fonts texts in instances about to be laid out ask for. Fonts that can't
+    /// be loaded are drawn with the default font and reported once.
+    fn load_text_fonts(&mut self) {
+        let mut wanted = BTreeSet::new();
+        for instance_name in &self.layout_dirty {
+            if let Some(prefab) = self.active_uis.get(instance_name) {
+                Self::collect_fonts(&prefab.root, &mut wanted);
+            }
+        }
+        for font_id in wanted {
+            if self.fonts.contains_key(&font_id) || self.missing_fonts.contains(&font_id) {
+                continue;
+            }
+            let result = match &self.font_base_path {
+                Some(base) => self.register_font(&font_id, &base.join(&font_id)),
+                None => Err("no font folder set".to_string()),
+            };
+            if let Err(e) = result {
+                self.font_warnings.push(format!("Font '{}' not loaded ({}), using the default font", font_id, e));
+                self.missing_fonts.insert(font_id);
             }
         }
-        
-        None
     }
-}
 
-impl Default for UIManager {
-    fn default() -> Self {
-        Self::new()
+    fn collect_fonts(element: &UIPrefabElement, out: &mut BTreeSet<String>) {
+        if let Some(text) = &element.text {
+            if !text.font.is_empty() && text.font != "default" {
+                out.insert(text.font.clone());
+            }
+        }
+        for child in &element.children {
+            Self::collect_fonts(child, out);
+        }
+    }
+
+    /// Bring the egui context's fonts up to date: each loaded font becomes a family of its
+    /// own (falling back to the proportional fonts for glyphs it lacks), and glyph
+    /// fallbacks - the project's `localization/fonts/`, then a system font covering Thai -
+    /// go behind the default fonts so scripts they lack don't draw as boxes
+    fn install_fonts(&mut self, ctx: &egui::Context) {
+        self.active_fonts = ctx.fonts(|fonts| {
+            fonts.definitions().families.keys()
+                .filter_map(|family| match family {
+                    egui::FontFamily::Name(name) => Some(name.to_string()),
+                    _ => None,
+                })
+                .filter(|name| self.fonts.contains_key(name))
+                .collect()
+        });
+        if !self.fonts_changed {
+            return;
+        }
+        self.fonts_changed = false;
+
+        let mut definitions = ctx.fonts(|fonts| fonts.definitions().clone());
+        let system_fonts = SYSTEM_FALLBACK_FONTS.iter().map(PathBuf::from).find(|path| path.exists());
+        for path in self.localization.font_files().iter().cloned().chain(system_fonts) {
+            let name = format!("fallback:{}", path.display());
+            if definitions.font_data.contains_key(&name) {
+                continue;
+            }
+            let data = match std::fs::read(&path) {
+                Ok(data) => data,
+                Err(e) => {
+                    log::warn!("Failed to read fallback font {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+            if let Err(e) = ab_glyph::FontRef::try_from_slice(&data) {
+                log::warn!("Failed to parse fallback font {}: {}", path.display(), e);
+                continue;
+            }
+            definitions.font_data.insert(name.clone(), std::sync::Arc::new(egui::FontData::from_owned(data)));
+            for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+                definitions.families.entry(family).or_default().push(name.clone());
+            }
+        }
+
+        let fallbacks = definitions.families.get(&egui::FontFamily::Proportional).cloned().unwrap_or_default();
+        for (font_id, data) in &self.fonts {
+            let name = format!("font:{}", font_id);
+            definitions.font_data.insert(name.clone(), std::sync::Arc::new(egui::FontData::from_owned(data.clone())));
+            let family = std::iter::once(name).chain(fallbacks.iter().cloned()).collect();
+            definitions.families.insert(egui::FontFamily::Name(font_id.as_str().into()), family);
+        }
+        ctx.set_fonts(definitions);
+    }
+
+    /// egui font for a text's font id: its own family once installed, the default
+    /// proportional font until then (or if it never loaded)
+    fn font_id(&self, font: &str, size: f32) -> egui::FontId {
+        if self.active_fonts.contains(font) {
+            egui::FontId::new(size, egui::FontFamily::Name(font.into()))
+        } else {
+            egui::FontId::proportional(size)
+        }
+    }
+
+    /// Move gamepad/keyboard focus (None clears it). A focused element shows its tooltip
+    /// after the same delay as a resting pointer, anchored to the element.
+    pub fn set_focused_element(&mut self, element_path: Option<&str>) -> Result<(), String> {
+        let Some(element_path) = element_path else {
+            self.focused_element = None;
+            return Ok(());
+        };
+        let (instance_name, relative_path) = element_path.split_once('/').unwrap_or((element_path, ""));
+        let prefab = self.active_uis.get(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        let indices = Self::find_path(&prefab.root, relative_path)
+            .ok_or_else(|| format!("Element not found: {}", element_path))?;
+        // Focus stays inside the top modal until it closes
+        if let Some(modal) = self.popups.top().filter(|modal| modal.instance_name != instance_name) {
+            return Err(format!("'{}' is outside the open modal '{}'", element_path, modal.instance_name));
+        }
+        self.focused_element = Some(Self::canonical_path(instance_name, &prefab.root, &indices));
+        Ok(())
+    }
+
+    pub fn focused_element(&self) -> Option<&str> {
+        self.focused_element.as_deref()
+    }
+
+    /// Open a prefab as a modal on top of the others (loaded first if needed), behind
+    /// its dim panel. Focus moves to its first control; the element focused before
+    /// gets it back when the modal closes.
+    pub fn show_modal(&mut self, path: &str, instance_name: &str, owner: ecs::Entity, options: ui::ModalOptions) -> Result<(), String> {
+        if self.popups.is_open(instance_name) {
+            return Err(format!("modal '{}' is already open", instance_name));
+        }
+        if !self.loaded_prefabs.contains_key(path) {
+            self.load_prefab(path)?;
+        }
+        let previous_focus = self.focused_element.clone();
+        self.activate(path, instance_name, Some(owner))?;
+        self.popups.open(instance_name, options, previous_focus);
+
+        // Nothing below keeps a press, drag or typed text going
+        self.pressed_control = None;
+        self.drag_drop.cancel();
+        self.input_system.clear_focus(&mut HashMap::new());
+        self.focused_element = self.first_control(instance_name);
+        Ok(())
+    }
+
+    /// Close the top modal: it fades out without taking input, focus goes back to
+    /// where it was and its on_closed callback gets `result`
+    pub fn close_modal(&mut self, result: &str) -> Result<(), String> {
+        let closed = self.popups.close_top(result).ok_or_else(|| "no modal is open".to_string())?;
+        if self.pressed_control.as_deref().is_some_and(|path| Self::in_instance(path, &closed.instance_name)) {
+            self.pressed_control = None;
+        }
+        // The element may have been removed while the modal was open
+        self.focused_element = closed.previous_focus.filter(|path| self.element(path).is_some());
+        if let Some(callback) = closed.on_closed {
+            match self.instance_owners.get(&closed.instance_name) {
+                Some(&owner) => self.script_events.push(script::UIScriptEvent {
+                    owner,
+                    callback,
+                    element_path: closed.instance_name.clone(),
+                    value: closed.result,
+                    while_disabled: script::DisabledPolicy::Buffer,
+                }),
+                None => log::warn!("Modal '{}' has an on_closed callback but no script opened it", closed.instance_name),
+            }
+        }
+        Ok(())
+    }
+
+    /// The modal on top of the stack, if any
+    pub fn top_modal(&self) -> Option<&str> {
+        self.popups.top().map(|modal| modal.instance_name.as_str())
+    }
+
+    /// First visible, interactable button, toggle, slider, dropdown or input field of an
+    /// instance, in draw order
+    fn first_control(&self, instance_name: &str) -> Option<String> {
+        let prefab = self.active_uis.get(instance_name)?;
+        let indices = Self::find_control(&prefab.root, ui::CanvasGroupState::default(), &mut Vec::new())?;
+        Some(Self::canonical_path(instance_name, &prefab.root, &indices))
+    }
+
+    /// Indices of the first usable control in and below `element`, in draw order;
+    /// `group` combines the canvas groups above it
+    fn find_control(element: &UIPrefabElement, group: ui::CanvasGroupState, indices: &mut Vec<usize>) -> Option<Vec<usize>> {
+        let group = group.with(element.canvas_group.as_ref());
+        let control = element.button.is_some()
+            || element.toggle.is_some()
+            || element.slider.is_some()
+            || element.dropdown.is_some()
+            || element.input_field.is_some();
+        let usable = element.ui_element.interactable && element.ui_element.alpha > 0.0 && group.interactable && group.visible();
+        if control && usable {
+            return Some(indices.clone());
+        }
+        for (index, child) in element.children.iter().enumerate() {
+            indices.push(index);
+            let found = Self::find_control(child, group, indices);
+            indices.pop();
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+
+    /// Screen rect of the tooltip drawn last frame, if any
+    pub fn tooltip_rect(&self) -> Option<egui::Rect> {
+        self.tooltip_rect
+    }
+
+    /// Recompute layout groups of instances changed since the last call. Deferred so that
+    /// creating many elements in one frame costs one layout pass; render calls it too.
+    pub fn apply_pending_layouts(&mut self) {
+        self.apply_localization();
+        self.load_text_fonts();
+        let Some(screen_rect) = self.screen_rect else {
+            return;
+        };
+        if self.layout_dirty.is_empty() {
+            return;
+        }
+        profiler::profile_scope!("ui.layout");
+        let safe_rect = self.safe_rect(screen_rect);
+
+        for instance_name in std::mem::take(&mut self.layout_dirty) {
+            if let Some(prefab) = self.active_uis.get_mut(&instance_name) {
+                let scale = Self::canvas_scale(prefab, screen_rect, self.pixels_per_point);
+                let root_rect = match &prefab.root.safe_area {
+                    Some(safe_area) => safe_area_rect(safe_area, screen_rect, safe_rect),
+                    None => screen_rect,
+                };
+                Self::layout_element(&mut prefab.root, root_rect, safe_rect, scale, &instance_name, &mut self.layout_sizes);
+            }
+        }
+    }
+
+    /// Update all UI systems
+    pub fn update(&mut self, _world: &mut World, _dt: f32, _screen_size: (u32, u32)) {
+        // Future: Update animations, layouts, etc.
+    }
+
+    /// Report on-screen joysticks/buttons (and the interactable UI that blocks them)
+    /// to the input system, and pick up their state for drawing. Call once per frame.
+    pub fn sync_virtual_controls(&mut self, input: &mut input::InputSystem) {
+        input.set_virtual_controls(self.virtual_controls_layout());
+
+        self.virtual_joysticks.clear();
+        self.virtual_buttons_down.clear();
+        let mut names = Vec::new();
+        for prefab in self.active_uis.values() {
+            Self::collect_virtual_inputs(&prefab.root, &mut names);
+        }
+        for (name, virtual_input) in names {
+            match virtual_input {
+                UIVirtualInput::Joystick { .. } => {
+                    if let Some(state) = input.virtual_controls().joystick(Some(&name)) {
+                        self.virtual_joysticks.insert(name, *state);
+                    }
+                }
+                UIVirtualInput::Button { .. } => {
+                    if input.is_virtual_button_down(&name) {
+                        self.virtual_buttons_down.insert(name);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Tell the input system which clicks and keys belong to the UI (interactable,
+    /// visible elements that aren't pass-through; a focused input field takes the
+    /// keyboard), so gameplay input queries skip them. Call once per frame, after the
+    /// frame's UI changes.
+    pub fn sync_input_capture(&self, input: &mut input::InputSystem) {
+        input.set_ui_capture(self.input_capture());
+    }
+
+    /// What the UI takes input from, in mouse coordinates (physical pixels) of the last render
+    pub fn input_capture(&self) -> input::UICapture {
+        let mut capture = input::UICapture::default();
+        let Some(screen_rect) = self.screen_rect else {
+            return capture;
+        };
+        // An open modal's dim panel takes everything: clicks anywhere and the keyboard
+        if self.popups.has_modal() {
+            capture.blockers.push(input::ScreenRect::new(
+                glam::Vec2::new(screen_rect.min.x, screen_rect.min.y) * self.pixels_per_point,
+                glam::Vec2::new(screen_rect.max.x, screen_rect.max.y) * self.pixels_per_point,
+            ));
+            capture.keyboard = true;
+        }
+        let mut focused_field_visible = false;
+        let focused_field = self.input_system.get_focused_field();
+        self.visit_placed(screen_rect, &mut |path, element, placement| {
+            let ui_element = &placement.effective(&element.ui_element);
+            let usable = ui_element.alpha > 0.0 && ui_element.interactable && ui_element.raycast_target;
+            if focused_field.is_some() && self.element_entities.get(path).copied() == focused_field && usable {
+                focused_field_visible = true;
+            }
+            let interactive = element.button.is_some()
+                || element.slider.is_some()
+                || element.toggle.is_some()
+                || element.dropdown.is_some()
+                || element.input_field.is_some()
+                || element.scroll_view.is_some()
+                || ui_element.draggable;
+            if usable && interactive && !ui_element.pass_through {
+                // Rotated elements block over their bounding box
+                let bounds = placement.bounds(placement.rect);
+                capture.blockers.push(input::ScreenRect::new(
+                    glam::Vec2::new(bounds.min.x, bounds.min.y) * self.pixels_per_point,
+                    glam::Vec2::new(bounds.max.x, bounds.max.y) * self.pixels_per_point,
+                ));
+            }
+        });
+        capture.keyboard |= focused_field_visible;
+        capture
+    }
+
+    /// Virtual controls of all active UIs, in screen coordinates of the last render
+    pub fn virtual_controls_layout(&self) -> input::VirtualControlsLayout {
+        let mut layout = input::VirtualControlsLayout::default();
+        let Some(screen_rect) = self.screen_rect else {
+            return layout;
+        };
+
+        // Sorted so "the first joystick" doesn't depend on HashMap order
+        let mut instances: Vec<_> = self.active_uis.iter().collect();
+        instances.sort_by(|a, b| a.0.cmp(b.0));
+        for (_, prefab) in instances {
+            let scale = Self::canvas_scale(prefab, screen_rect, self.pixels_per_point);
+            self.collect_virtual_layout(&prefab.root, self.root_placement(screen_rect), scale, &mut layout);
+        }
+        layout
+    }
+
+    fn collect_virtual_layout(
+        &self,
+        element: &UIPrefabElement,
+        parent: Placement,
+        scale: f32,
+        layout: &mut input::VirtualControlsLayout,
+    ) {
+        let placement = parent.place_element(element, scale);
+        // Rotated elements take touches over their bounding box
+        let bounds = placement.bounds(placement.rect);
+        let region = input::ScreenRect::new(
+            glam::Vec2::new(bounds.min.x, bounds.min.y),
+            glam::Vec2::new(bounds.max.x, bounds.max.y),
+        );
+        // Hidden elements (hide_element, faded out groups) stop taking touches
+        let ui_element = placement.effective(&element.ui_element);
+        let visible = ui_element.alpha > 0.0;
+
+        match &element.virtual_input {
+            Some(UIVirtualInput::Joystick { radius, dead_zone, floating }) if visible => {
+                layout.joysticks.push(input::VirtualJoystickLayout {
+                    name: element.name.clone(),
+                    region,
+                    radius: radius * scale * placement.scale(),
+                    dead_zone: *dead_zone,
+                    floating: *floating,
+                });
+            }
+            Some(UIVirtualInput::Button { action }) if visible => {
+                layout.buttons.push(input::VirtualButtonLayout {
+                    name: element.name.clone(),
+                    region,
+                    action: action.clone(),
+                });
+            }
+            Some(_) => {}
+            None => {
+                let interactive = element.button.is_some()
+                    || element.slider.is_some()
+                    || element.toggle.is_some()
+                    || element.dropdown.is_some()
+                    || element.input_field.is_some()
+                    || element.scroll_view.is_some();
+                if visible && interactive && ui_element.interactable && ui_element.raycast_target {
+                    layout.blockers.push(region);
+                }
+            }
+        }
+
+        for child in &element.children {
+            self.collect_virtual_layout(child, placement, scale, layout);
+        }
+    }
+
+    fn collect_virtual_inputs(element: &UIPrefabElement, out: &mut Vec<(String, UIVirtualInput)>) {
+        if let Some(virtual_input) = &element.virtual_input {
+            out.push((element.name.clone(), virtual_input.clone()));
+        }
+        for child in &element.children {
+            Self::collect_virtual_inputs(child, out);
+        }
+    }
+
+    /// Render UI (to be called during game view rendering)
+    pub fn render(&mut self, ui: &mut egui::Ui, _world: &World, rect: egui::Rect) {
+        profiler::profile_scope!("ui.canvas");
+        let pixels_per_point = ui.ctx().pixels_per_point();
+        if self.screen_rect != Some(rect) || self.pixels_per_point != pixels_per_point {
+            // Window resized or moved to a monitor with another DPI: canvas scale and
+            // stretching groups depend on both
+            self.layout_dirty.extend(self.active_uis.keys().cloned());
+        }
+        self.screen_rect = Some(rect);
+        self.pixels_per_point = pixels_per_point;
+        self.event_log.begin_frame();
+        self.apply_pending_layouts();
+        self.install_fonts(ui.ctx());
+
+        // Debug: Log when render is called
+        if !self.active_uis.is_empty() {
+            log::debug!("UIManager::render called with {} active UIs", self.active_uis.len());
+        }
+        
+        let dt = ui.ctx().input(|i| i.stable_dt);
+        for instance_name in self.popups.update(dt) {
+            self.deactivate_prefab(&instance_name);
+        }
+        self.update_group_fades(dt);
+        let hover_pos = ui.ctx().input(|i| i.pointer.hover_pos());
+        let controls_moving = self.update_control_visuals(rect, hover_pos, dt);
+        if self.popups.is_fading() || !self.group_fades.is_empty() || controls_moving {
+            ui.ctx().request_repaint();
+        }
+
+        // Render all active UI instances, modals last (each above its dim panel)
+        for (instance_name, prefab) in self.instances_in_draw_order() {
+            log::debug!("Rendering UI instance: {}", instance_name);
+            let opacity = match self.popups.modal(instance_name) {
+                Some(modal) => {
+                    let ui::Color { r, g, b, a } = modal.options.dim_color;
+                    let dim = egui::Color32::from_rgba_unmultiplied(
+                        (r * 255.0) as u8,
+                        (g * 255.0) as u8,
+                        (b * 255.0) as u8,
+                        (a * modal.opacity * 255.0) as u8,
+                    );
+                    ui.painter_at(rect).rect_filled(rect, 0.0, dim);
+                    modal.opacity
+                }
+                None => 1.0,
+            };
+            self.render_prefab(ui, rect, instance_name, prefab, opacity);
+        }
+
+        // A click that dismissed a modal doesn't reach the UI beneath it
+        if !self.update_modal_input(ui.ctx(), rect) {
+            self.update_input_fields(ui.ctx(), rect);
+            self.update_controls(ui.ctx(), rect);
+            self.update_drag_drop(ui.ctx(), rect);
+        }
+        if self.event_log.is_enabled() {
+            self.log_pointer_events(ui.ctx(), rect);
+        }
+        self.update_tooltip(ui.ctx(), rect);
+    }
+
+    /// Active instances sorted by draw order: by modal stack position (other UIs first),
+    /// then by name
+    fn instances_in_draw_order(&self) -> Vec<(&String, &UIPrefab)> {
+        let mut instances: Vec<_> = self.active_uis.iter().collect();
+        instances.sort_by_key(|(instance_name, _)| (self.popups.sort_order(instance_name), *instance_name));
+        instances
+    }
+
+    /// Close the top modal with "cancel" on Escape or a click on its dim panel, if its
+    /// options allow. Returns true if it closed.
+    fn update_modal_input(&mut self, ctx: &egui::Context, screen_rect: egui::Rect) -> bool {
+        let Some(modal) = self.popups.top() else { return false };
+        let (instance_name, options) = (modal.instance_name.clone(), modal.options.clone());
+        let (press, escape) = ctx.input(|i| {
+            (i.pointer.primary_pressed().then(|| i.pointer.interact_pos()).flatten(), i.key_pressed(egui::Key::Escape))
+        });
+
+        // Escape first cancels a drag or leaves the input field being typed in
+        let busy = self.drag_drop.active_drag().is_some()
+            || self.input_system.get_focused_field().is_some()
+            || ctx.wants_keyboard_input();
+        let mut dismiss = escape && options.close_on_escape && !busy;
+        if let Some(pos) = press.filter(|_| options.close_on_background_click) {
+            // The modal's root stretches over the screen: only its children are the popup
+            let mut on_popup = false;
+            self.visit_placed(screen_rect, &mut |path, element, placement| {
+                let ui_element = &placement.effective(&element.ui_element);
+                if path != instance_name && ui_element.alpha > 0.0 && ui_element.raycast_target && placement.contains(pos) {
+                    on_popup = true;
+                }
+            });
+            dismiss |= !on_popup;
+        }
+        if !dismiss {
+            return false;
+        }
+        if let Err(e) = self.close_modal(ui::popup_manager::CANCEL_RESULT) {
+            log::warn!("Closing modal '{}' failed: {}", instance_name, e);
+        }
+        true
+    }
+
+    /// Draw `prefab` the way render would on a `resolution` screen, shrunk to fit `rect`
+    /// (the prefab editor's preview). Nothing is activated and no input is handled.
+    pub fn render_preview(&self, ui: &mut egui::Ui, prefab: &UIPrefab, resolution: (u32, u32), rect: egui::Rect) -> Vec<PreviewElement> {
+        let scale = Self::preview_scale(prefab, resolution, rect);
+        let (laid_out, elements) = self.preview_layout(prefab, resolution, rect);
+        let painter = ui.painter_at(rect);
+        self.render_element(&painter, self.root_placement(rect), &laid_out.name, &laid_out.name, &laid_out.root, scale);
+        elements
+    }
+
+    /// Lay out a copy of `prefab` as on a `resolution` screen (in points, window scale 1),
+    /// shrunk to fit `rect`: the canvas scaler and layout groups apply like in the game.
+    /// Returns the laid-out copy and every element's place, in draw order.
+    pub fn preview_layout(&self, prefab: &UIPrefab, resolution: (u32, u32), rect: egui::Rect) -> (UIPrefab, Vec<PreviewElement>) {
+        let scale = Self::preview_scale(prefab, resolution, rect);
+        let mut laid_out = prefab.clone();
+        let safe_rect = self.safe_rect(rect);
+        let root_rect = match &laid_out.root.safe_area {
+            Some(safe_area) => safe_area_rect(safe_area, rect, safe_rect),
+            None => rect,
+        };
+        // Layout sizes of its own, so a preview never feeds back into the game's layouts
+        let mut layout_sizes = HashMap::new();
+        Self::layout_element(&mut laid_out.root, root_rect, safe_rect, scale, &prefab.name, &mut layout_sizes);
+
+        let mut elements = Vec::new();
+        Self::collect_preview(&laid_out.root, self.root_placement(rect), scale, &prefab.name, &mut elements);
+        (laid_out, elements)
+    }
+
+    /// Canvas scale on the simulated screen, times how much the screen is shrunk to fit `rect`
+    fn preview_scale(prefab: &UIPrefab, resolution: (u32, u32), rect: egui::Rect) -> f32 {
+        let size = egui::vec2(resolution.0.max(1) as f32, resolution.1.max(1) as f32);
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, size);
+        Self::canvas_scale(prefab, screen, 1.0) * rect.width() / size.x
+    }
+
+    fn collect_preview(element: &UIPrefabElement, parent: Placement, scale: f32, element_path: &str, out: &mut Vec<PreviewElement>) {
+        let parent_rect = match &element.safe_area {
+            Some(safe_area) => safe_area_rect(safe_area, parent.rect, parent.safe_rect),
+            None => parent.rect,
+        };
+        let placement = parent.place_element(element, scale);
+        out.push(PreviewElement {
+            name: element.name.clone(),
+            path: element_path.to_string(),
+            rect: placement.bounds(placement.rect),
+            parent_rect: parent.bounds(parent_rect),
+            transform: element.rect_transform.clone(),
+            scale,
+        });
+        for child in &element.children {
+            let child_path = format!("{}/{}", element_path, child.name);
+            Self::collect_preview(child, placement, scale, &child_path, out);
+        }
+    }
+
+    /// Advance the look of every button, toggle, slider and dropdown: the tint of its
+    /// state (normal, hover, pressed, selected, disabled) fades toward the new state's,
+    /// slider fills and knobs smooth toward the value and toggle checkmarks fade in and
+    /// out. Returns true while any of it is still moving.
+    fn update_control_visuals(&mut self, screen_rect: egui::Rect, hover_pos: Option<egui::Pos2>, dt: f32) -> bool {
+        let mut controls = Vec::new();
+        self.visit_placed(screen_rect, &mut |path, element, placement| {
+            if element.button.is_some() || element.toggle.is_some() || element.slider.is_some() || element.dropdown.is_some() {
+                let ui_element = placement.effective(&element.ui_element);
+                controls.push((path.to_string(), placement, ui_element.interactable, ui_element.alpha > 0.0));
+            }
+        });
+        // Topmost usable control under the pointer
+        let hovered = hover_pos.and_then(|pos| {
+            controls.iter().rev()
+                .find(|(_, placement, interactable, visible)| *interactable && *visible && placement.contains(pos))
+                .map(|(path, ..)| path.clone())
+        });
+
+        let mut previous = std::mem::take(&mut self.control_visuals);
+        let mut moving = false;
+        for (path, _, interactable, _) in controls {
+            let Some(element) = self.element(&path) else { continue };
+            let Some(transition) = self.state_transition(element) else { continue };
+            let pressed = self.pressed_control.as_ref() == Some(&path);
+            let state = ui::InteractableState::resolve(
+                interactable,
+                pressed,
+                hovered.as_ref() == Some(&path),
+                self.focused_element.as_ref() == Some(&path),
+            );
+            // Dragging a slider follows the pointer; values set from code smooth
+            let slider = element.slider.as_ref()
+                .map(|slider| (slider.normalized_value(), if pressed { 0.0 } else { slider.smoothing_time }));
+            let checkmark = element.toggle.as_ref().map(|toggle| {
+                let duration = if toggle.toggle_transition == ui::ToggleTransition::None { 0.0 } else { toggle.checkmark_duration };
+                (if toggle.is_on { 1.0 } else { 0.0 }, duration)
+            });
+
+            let entity = self.element_entity(&path);
+            let tint = ui::AnimationValue::Color(transition.color(state));
+            let mut visual = previous.remove(&path).unwrap_or_else(|| ControlVisual {
+                state,
+                tint: ControlVisual::settled(entity, ui::AnimatedProperty::Color, tint.clone()),
+                slider: None,
+                checkmark: None,
+            });
+            visual.state = state;
+            ControlVisual::retarget(&mut visual.tint, tint, transition.fade_duration);
+            let follow = |animation: Option<ui::UIAnimation>, property: ui::AnimatedProperty, (value, duration): (f32, f32)| {
+                let value = ui::AnimationValue::Float(value);
+                match animation {
+                    Some(mut animation) => {
+                        ControlVisual::retarget(&mut animation, value, duration);
+                        animation
+                    }
+                    None => ControlVisual::settled(entity, property, value),
+                }
+            };
+            visual.slider = slider.map(|target| follow(visual.slider.take(), ui::AnimatedProperty::AnchoredPosition, target));
+            visual.checkmark = checkmark.map(|target| follow(visual.checkmark.take(), ui::AnimatedProperty::Alpha, target));
+
+            for animation in [Some(&mut visual.tint), visual.slider.as_mut(), visual.checkmark.as_mut()].into_iter().flatten() {
+                moving |= animation.update(dt);
+            }
+            self.control_visuals.insert(path, visual);
+        }
+        moving
+    }
+
+    /// Click buttons and toggles (press and release on the same element) and drag
+    /// sliders. Clicks and value changes go to listeners and prefab bindings.
+    fn update_controls(&mut self, ctx: &egui::Context, screen_rect: egui::Rect) {
+        let mut targets = Vec::new();
+        self.visit_placed(screen_rect, &mut |path, element, placement| {
+            let ui_element = placement.effective(&element.ui_element);
+            let usable = ui_element.interactable && ui_element.alpha > 0.0;
+            if usable && (element.button.is_some() || element.toggle.is_some() || element.slider.is_some()) {
+                targets.push((path.to_string(), placement));
+            }
+        });
+
+        let (pointer, pressed, released, down) = ctx.input(|i| {
+            (i.pointer.interact_pos(), i.pointer.primary_pressed(), i.pointer.primary_released(), i.pointer.primary_down())
+        });
+        if pressed {
+            // Topmost control under the pointer
+            self.pressed_control = pointer
+                .and_then(|pos| targets.iter().rev().find(|(_, placement)| placement.contains(pos)))
+                .map(|(path, _)| path.clone());
+        }
+        let Some(path) = self.pressed_control.clone() else { return };
+        let (Some((_, placement)), Some(element)) = (targets.iter().find(|(target, _)| *target == path), self.element(&path)) else {
+            // Hidden, disabled or removed while held
+            self.pressed_control = None;
+            return;
+        };
+        let placement = *placement;
+        let (slider, toggle, is_button) = (element.slider.clone(), element.toggle.clone(), element.button.is_some());
+
+        if let (Some(mut slider), Some(pos)) = (slider, pointer) {
+            if pressed || down {
+                let local = placement.transform.inverse().transform_point2(glam::Vec2::new(pos.x, pos.y));
+                let rect = placement.rect;
+                let along = |value: f32, min: f32, size: f32| if size > 0.0 { ((value - min) / size).clamp(0.0, 1.0) } else { 0.0 };
+                let normalized = match slider.direction {
+                    ui::SliderDirection::LeftToRight => along(local.x, rect.min.x, rect.width()),
+                    ui::SliderDirection::RightToLeft => 1.0 - along(local.x, rect.min.x, rect.width()),
+                    // Screen y points down
+                    ui::SliderDirection::BottomToTop => 1.0 - along(local.y, rect.min.y, rect.height()),
+                    ui::SliderDirection::TopToBottom => along(local.y, rect.min.y, rect.height()),
+                };
+                let previous = slider.value;
+                slider.value = slider.min_value + normalized * (slider.max_value - slider.min_value);
+                ui::SliderSystem::clamp_slider_value(&mut slider);
+                if (slider.value - previous).abs() > f32::EPSILON {
+                    let value = slider.value.to_string();
+                    let (instance_name, relative_path) = path.split_once('/').unwrap_or((&path, ""));
+                    if let Some(prefab) = self.active_uis.get_mut(instance_name) {
+                        if let Some(element) = Self::find_element_mut(&mut prefab.root, relative_path) {
+                            element.slider = Some(slider);
+                        }
+                    }
+                    self.fire_event(&path, script::UIEventKind::ValueChanged, value);
+                }
+            }
+        }
+
+        if released {
+            self.pressed_control = None;
+            let clicked = pointer.is_some_and(|pos| placement.contains(pos));
+            if clicked && is_button {
+                self.fire_event(&path, script::UIEventKind::Click, String::new());
+            }
+            if let (true, Some(toggle)) = (clicked, toggle) {
+                self.click_toggle(&path, !toggle.is_on);
+            }
+        }
+    }
+
+    /// Flip a clicked toggle and fire value-changed for every toggle of its instance that
+    /// changed (a toggle group turns the others off)
+    fn click_toggle(&mut self, element_path: &str, is_on: bool) {
+        let instance_name = element_path.split_once('/').map_or(element_path, |(instance, _)| instance).to_string();
+        let toggle_states = |manager: &Self| {
+            let mut states = Vec::new();
+            if let Some(prefab) = manager.active_uis.get(&instance_name) {
+                let mut nodes = Vec::new();
+                Self::collect_indices(&prefab.root, &mut Vec::new(), &mut nodes);
+                for indices in &nodes {
+                    if let Some(toggle) = &Self::element_at(&prefab.root, indices).toggle {
+                        states.push((Self::canonical_path(&instance_name, &prefab.root, indices), toggle.is_on));
+                    }
+                }
+            }
+            states
+        };
+
+        let before = toggle_states(self);
+        if let Err(e) = self.set_toggle(element_path, is_on) {
+            log::warn!("Toggle click on '{}' failed: {}", element_path, e);
+            return;
+        }
+        for ((path, was_on), (_, is_on)) in before.into_iter().zip(toggle_states(self)) {
+            if was_on != is_on {
+                self.fire_event(&path, script::UIEventKind::ValueChanged, is_on.to_string());
+            }
+        }
+    }
+
+    /// Fire an element event: listeners get their callback queued and the element's prefab
+    /// bindings run (Lua ones in the script that activated the instance)
+    fn fire_event(&mut self, element_path: &str, event: script::UIEventKind, value: String) {
+        if self.event_log.is_enabled() {
+            let handlers: Vec<String> = self.listeners.iter()
+                .filter(|l| l.element_path == element_path && l.event == event)
+                .map(|l| l.callback.clone())
+                .chain(self.bindings.iter()
+                    .filter(|binding| binding.element_path == element_path && binding.event == event)
+                    .map(|binding| binding.action.describe()))
+                .collect();
+            let consumed_by = (!handlers.is_empty()).then_some(element_path);
+            self.event_log.push(&format!("{:?}", event), element_path, consumed_by, event_detail(&value, &handlers));
+        }
+
+        for listener in self.listeners.iter().filter(|l| l.element_path == element_path && l.event == event) {
+            self.script_events.push(script::UIScriptEvent {
+                owner: listener.owner,
+                callback: listener.callback.clone(),
+                element_path: element_path.to_string(),
+                value: value.clone(),
+                while_disabled: listener.while_disabled,
+            });
+        }
+
+        let actions: Vec<ui::UIAction> = self.bindings.iter()
+            .filter(|binding| binding.element_path == element_path && binding.event == event)
+            .map(|binding| binding.action.clone())
+            .collect();
+        let instance_name = element_path.split_once('/').map_or(element_path, |(instance, _)| instance).to_string();
+        for action in actions {
+            let result = match &action {
+                ui::UIAction::Lua { function } => match self.instance_owners.get(&instance_name) {
+                    Some(&owner) => {
+                        self.script_events.push(script::UIScriptEvent {
+                            owner,
+                            callback: function.clone(),
+                            element_path: element_path.to_string(),
+                            value: value.clone(),
+                            while_disabled: script::DisabledPolicy::Drop,
+                        });
+                        Ok(())
+                    }
+                    None => Err(format!("no script activated '{}'", instance_name)),
+                },
+                ui::UIAction::Show { target } => self.show_element(&instance_name, target),
+                ui::UIAction::Hide { target } => self.hide_element(&instance_name, target),
+                ui::UIAction::SetCanvas { target } => {
+                    let owner = self.instance_owners.get(&instance_name).copied();
+                    let loaded = match self.loaded_prefabs.contains_key(target) {
+                        true => Ok(()),
+                        false => self.load_prefab(target),
+                    };
+                    loaded.and_then(|()| self.activate(target, &instance_name, owner))
+                }
+            };
+            if let Err(e) = result {
+                log::warn!("UI action '{}' on '{}' failed: {}", action.describe(), element_path, e);
+            }
+        }
+    }
+
+    /// Stable stand-in entity for the element at `path`
+    fn element_entity(&mut self, path: &str) -> ui::Entity {
+        if let Some(entity) = self.element_entities.get(path) {
+            return *entity;
+        }
+        let entity = self.next_element_entity;
+        self.next_element_entity += 1;
+        self.element_entities.insert(path.to_string(), entity);
+        entity
+    }
+
+    /// Drag draggable elements (ui::UIInputHandler rules: threshold, drop target under the
+    /// pointer). A ghost follows the pointer on a top layer; Escape cancels. Drops go to
+    /// UI.on_drop listeners on the target or its nearest ancestor with one.
+    fn update_drag_drop(&mut self, ctx: &egui::Context, screen_rect: egui::Rect) {
+        let mut placed = Vec::new();
+        self.visit_placed(screen_rect, &mut |path, element, placement| {
+            placed.push((path.to_string(), placement.effective(&element.ui_element), placement));
+        });
+        if !placed.iter().any(|(_, element, _)| element.draggable) {
+            self.drag_drop.cancel();
+            return;
+        }
+
+        let mut raycast_elements = Vec::new();
+        let mut elements = HashMap::new();
+        let mut paths = HashMap::new();
+        for (path, element, placement) in placed {
+            let entity = self.element_entity(&path);
+            raycast_elements.push(ui::RaycastElement {
+                entity,
+                rect: ui::Rect::new(placement.rect.min.x, placement.rect.min.y, placement.rect.width(), placement.rect.height()),
+                transform: placement.transform,
+                raycast_target: element.raycast_target,
+                blocks_raycasts: element.blocks_raycasts,
+                // Drawing order decides what's on top (visit order is render order)
+                z_order: 0,
+                canvas_sort_order: 0,
+                visible: element.alpha > 0.0,
+                interactable: element.interactable,
+                draggable: element.draggable,
+            });
+            elements.insert(entity, element);
+            paths.insert(entity, path);
+        }
+        let mut raycast = ui::UIRaycastSystem::new();
+        raycast.update_elements(raycast_elements);
+
+        let (pointer, pressed, released, dt, escape) = ctx.input(|i| {
+            (
+                i.pointer.latest_pos(),
+                i.pointer.primary_pressed(),
+                i.pointer.primary_released(),
+                i.stable_dt,
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        self.pointer.begin_frame(dt);
+        if let Some(pointer) = pointer {
+            self.pointer.set_mouse_position(glam::Vec2::new(pointer.x, pointer.y));
+        }
+        if pressed {
+            self.pointer.press_button(ui::MouseButton::Left);
+        }
+        if released {
+            self.pointer.release_button(ui::MouseButton::Left);
+        }
+        let events = self.pointer.process_input(&raycast);
+        if escape {
+            self.drag_drop.cancel();
+        }
+
+        for drop in self.drag_drop.update_from_events(&events, &elements) {
+            let ui::UIEvent::Drop(target, source, _) = drop else { continue };
+            let (Some(target_path), Some(source_path)) = (paths.get(&target), paths.get(&source)) else { continue };
+
+            // The pointer may be over a slot's child (its icon): the slot handles the drop
+            let mut path = target_path.as_str();
+            let mut handlers = Vec::new();
+            loop {
+                for listener in self.listeners.iter().filter(|l| l.element_path == path && l.event == script::UIEventKind::Drop) {
+                    self.script_events.push(script::UIScriptEvent {
+                        owner: listener.owner,
+                        callback: listener.callback.clone(),
+                        element_path: path.to_string(),
+                        value: source_path.clone(),
+                        while_disabled: listener.while_disabled,
+                    });
+                    handlers.push(listener.callback.clone());
+                }
+                match path.rsplit_once('/') {
+                    Some((parent, _)) if handlers.is_empty() => path = parent,
+                    _ => break,
+                }
+            }
+            let consumed_by = (!handlers.is_empty()).then_some(path);
+            self.event_log.push("Drop", target_path, consumed_by, event_detail(source_path, &handlers));
+        }
+
+        if let Some(drag) = self.drag_drop.active_drag() {
+            if let Some(source_path) = paths.get(&drag.source) {
+                self.paint_drag_ghost(ctx, screen_rect, source_path, glam::Vec2::new(drag.offset().x, drag.offset().y));
+            }
+        }
+    }
+
+    /// Draw a copy of the dragged element moved by `offset`, above every UI instance
+    fn paint_drag_ghost(&self, ctx: &egui::Context, screen_rect: egui::Rect, source_path: &str, offset: glam::Vec2) {
+        let (instance_name, relative_path) = source_path.split_once('/').unwrap_or((source_path, ""));
+        let Some(prefab) = self.active_uis.get(instance_name) else { return };
+        let Some(indices) = Self::find_path(&prefab.root, relative_path) else { return };
+        let element = Self::element_at(&prefab.root, &indices).clone();
+
+        let parent_path = source_path.rsplit_once('/').map(|(parent, _)| parent);
+        let mut parent = self.root_placement(screen_rect);
+        self.visit_placed(screen_rect, &mut |path, _, placement| {
+            if Some(path) == parent_path {
+                parent = placement;
+            }
+        });
+        let parent = Placement {
+            transform: glam::Affine2::from_translation(offset) * parent.transform,
+            ..parent
+        };
+
+        let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Foreground, egui::Id::new("ui_manager_drag")));
+        let scale = Self::canvas_scale(prefab, screen_rect, self.pixels_per_point);
+        self.render_element(&painter, parent, instance_name, source_path, &element, scale);
+    }
+
+    /// Focus input fields on click and type into the focused one (ui::InputFieldSystem
+    /// rules: validation, character limit, Enter submits). Fired events go to listeners.
+    fn update_input_fields(&mut self, ctx: &egui::Context, screen_rect: egui::Rect) {
+        let mut targets = Vec::new();
+        self.visit_placed(screen_rect, &mut |path, element, placement| {
+            if let Some(field) = &element.input_field {
+                let ui_element = placement.effective(&element.ui_element);
+                let usable = ui_element.interactable && ui_element.alpha > 0.0;
+                targets.push((path.to_string(), placement, field.clone(), usable));
+            }
+        });
+        if targets.is_empty() {
+            return;
+        }
+
+        let mut fields = HashMap::new();
+        let mut elements = HashMap::new();
+        let mut paths = HashMap::new();
+        for (path, _, field, usable) in &targets {
+            let entity = self.element_entity(path);
+            fields.insert(entity, field.clone());
+            elements.insert(entity, ui::UIElement { interactable: *usable, ..Default::default() });
+            paths.insert(entity, path.clone());
+        }
+
+        let (press, events) = ctx.input(|i| {
+            (i.pointer.any_pressed().then(|| i.pointer.interact_pos()).flatten(), i.events.clone())
+        });
+        if let Some(pos) = press {
+            // Topmost field under the pointer; entity 0 (no field) drops focus
+            let clicked = targets.iter().rev()
+                .find(|(_, placement, _, _)| placement.contains(pos))
+                .map_or(0, |(path, ..)| self.element_entities[path]);
+            let click = ui::UIEvent::PointerClick(clicked, glam::Vec2::new(pos.x, pos.y));
+            self.input_system.update_from_events(&[click], &mut fields, &elements, &ui::UIEventDispatcher::new());
+        }
+
+        // Leave the keyboard to egui widgets (editor panels) that have it
+        if self.input_system.get_focused_field().is_some() && !ctx.wants_keyboard_input() {
+            for event in events {
+                match event {
+                    egui::Event::Text(text) => {
+                        for character in text.chars() {
+                            self.input_system.handle_text_input(character, &mut fields);
+                        }
+                    }
+                    egui::Event::Key { key, pressed: true, .. } => match key {
+                        egui::Key::Backspace => self.input_system.handle_backspace(&mut fields),
+                        egui::Key::Delete => self.input_system.handle_delete(&mut fields),
+                        egui::Key::ArrowLeft => self.input_system.move_caret_left(&mut fields),
+                        egui::Key::ArrowRight => self.input_system.move_caret_right(&mut fields),
+                        egui::Key::Home => self.input_system.set_caret_position(0, &mut fields),
+                        egui::Key::End => self.input_system.set_caret_position(i32::MAX, &mut fields),
+                        egui::Key::Enter => self.input_system.handle_submit(&mut fields),
+                        egui::Key::Escape => self.input_system.clear_focus(&mut fields),
+                        _ => {}
+                    },
+                    _ => {}
+                }
+            }
+        }
+        let fired = self.input_system.end_frame(&fields, &ui::UIEventDispatcher::new());
+
+        for (entity, field) in fields {
+            let path = &paths[&entity];
+            let (instance_name, relative_path) = path.split_once('/').unwrap_or((path, ""));
+            if let Some(prefab) = self.active_uis.get_mut(instance_name) {
+                if let Some(element) = Self::find_element_mut(&mut prefab.root, relative_path) {
+                    element.input_field = Some(field);
+                }
+            }
+        }
+
+        for event in fired {
+            let (entity, kind, value) = match event {
+                ui::UIEvent::InputValueChanged(entity, text) => (entity, script::UIEventKind::ValueChanged, text),
+                ui::UIEvent::InputSubmit(entity, text) => (entity, script::UIEventKind::Submit, text),
+                _ => continue,
+            };
+            let path = &paths[&entity];
+            let mut handlers = Vec::new();
+            for listener in self.listeners.iter().filter(|l| l.element_path == *path && l.event == kind) {
+                self.script_events.push(script::UIScriptEvent {
+                    owner: listener.owner,
+                    callback: listener.callback.clone(),
+                    element_path: path.clone(),
+                    value: value.clone(),
+                    while_disabled: listener.while_disabled,
+                });
+                handlers.push(listener.callback.clone());
+            }
+            let consumed_by = (!handlers.is_empty()).then_some(path.as_str());
+            self.event_log.push(&format!("{:?}", kind), path, consumed_by, event_detail(&value, &handlers));
+        }
+    }
+
+    /// Track the element the pointer (or focus) rests on and draw its tooltip once the
+    /// show delay has passed. Any press hides it until the hover moves on.
+    fn update_tooltip(&mut self, ctx: &egui::Context, screen_rect: egui::Rect) {
+        self.tooltip_rect = None;
+        let (pointer, pressed, time) = ctx.input(|i| (i.pointer.hover_pos(), i.pointer.any_pressed(), i.time));
+
+        let mut targets = Vec::new();
+        self.visit_placed(screen_rect, &mut |path, element, placement| {
+            // Hidden elements (hide_element, faded out groups) show no tooltip
+            if let Some(tooltip) = element.tooltip.as_ref().filter(|_| placement.effective(&element.ui_element).alpha > 0.0) {
+                targets.push(TooltipTarget { path: path.to_string(), placement, tooltip: tooltip.clone() });
+            }
+        });
+        // Later targets are drawn on top
+        let hovered = pointer.and_then(|pos| targets.iter().rev().find(|target| target.placement.contains(pos)));
+        let focused = self.focused_element.as_deref()
+            .and_then(|path| targets.iter().find(|target| target.path == path));
+        let Some(target) = hovered.or(focused) else {
+            self.tooltip_hover = None;
+            return;
+        };
+
+        if self.tooltip_hover.as_ref().is_none_or(|hover| hover.path != target.path) {
+            self.tooltip_hover = Some(TooltipHover { path: target.path.clone(), since: time, dismissed: false });
+            if let Some(prefab_path) = &target.tooltip.prefab {
+                if !self.loaded_prefabs.contains_key(prefab_path) {
+                    if let Err(e) = self.load_prefab(prefab_path) {
+                        log::error!("Failed to load tooltip prefab '{}': {}", prefab_path, e);
+                    }
+                }
+            }
+        }
+        let Some(hover) = &mut self.tooltip_hover else {
+            return;
+        };
+        hover.dismissed |= pressed;
+        if hover.dismissed {
+            return;
+        }
+        let remaining = target.tooltip.show_delay as f64 - (time - hover.since);
+        if remaining > 0.0 {
+            ctx.request_repaint_after(std::time::Duration::from_secs_f64(remaining));
+            return;
+        }
+
+        // Focus tooltips stay anchored to the element
+        let cursor = if hovered.is_some() { pointer } else { None };
+        self.tooltip_rect = Some(self.paint_tooltip(ctx, screen_rect, target, cursor));
+    }
+
+    /// The event log (recent pointer and script events, see ui::UIEventLog)
+    pub fn event_log(&self) -> &ui::UIEventLog {
+        &self.event_log
+    }
+
+    /// The event log, to enable or clear it
+    pub fn event_log_mut(&mut self) -> &mut ui::UIEventLog {
+        &mut self.event_log
+    }
+
+    /// Every element on screen that takes input, in draw order, with a raycast system
+    /// over them. Groups are folded in separately so probes can tell which one did what.
+    fn inspect_targets(&mut self, screen_rect: egui::Rect) -> (ui::UIRaycastSystem, Vec<InspectTarget>) {
+        let mut placed = Vec::new();
+        self.visit_placed(screen_rect, &mut |path, element, placement| {
+            let control = element.button.is_some() || element.toggle.is_some() || element.slider.is_some();
+            placed.push((path.to_string(), element.ui_element.clone(), placement, control));
+        });
+
+        let mut raycast_elements = Vec::new();
+        let mut groups = Vec::new();
+        let mut targets = Vec::new();
+        for (path, element, placement, control) in placed {
+            let entity = self.element_entity(&path);
+            raycast_elements.push(ui::RaycastElement {
+                entity,
+                rect: ui::Rect::new(placement.rect.min.x, placement.rect.min.y, placement.rect.width(), placement.rect.height()),
+                transform: placement.transform,
+                raycast_target: element.raycast_target,
+                blocks_raycasts: element.blocks_raycasts,
+                // Drawing order decides what's on top, as in update_drag_drop
+                z_order: 0,
+                canvas_sort_order: 0,
+                visible: element.alpha > 0.0,
+                interactable: element.interactable,
+                draggable: element.draggable,
+            });
+            groups.push((entity, placement.group));
+            let effective = placement.effective(&element);
+            let control = control && effective.interactable && effective.alpha > 0.0;
+            targets.push(InspectTarget { entity, path, placement, control });
+        }
+        let mut raycast = ui::UIRaycastSystem::new();
+        raycast.update_elements(raycast_elements);
+        raycast.apply_group_states(groups);
+        (raycast, targets)
+    }
+
+    /// Log the pointer events ui::UIInputHandler raises over the elements on screen. A
+    /// press, release, click, drag or scroll is consumed by the control update_controls
+    /// gives it to (the topmost usable button, toggle or slider under the pointer).
+    fn log_pointer_events(&mut self, ctx: &egui::Context, screen_rect: egui::Rect) {
+        let (raycast, targets) = self.inspect_targets(screen_rect);
+        let (pointer, pressed, released, dt) = ctx.input(|i| {
+            (i.pointer.latest_pos(), i.pointer.primary_pressed(), i.pointer.primary_released(), i.stable_dt)
+        });
+        self.inspect_pointer.begin_frame(dt);
+        if let Some(pointer) = pointer {
+            self.inspect_pointer.set_mouse_position(glam::Vec2::new(pointer.x, pointer.y));
+        }
+        if pressed {
+            self.inspect_pointer.press_button(ui::MouseButton::Left);
+        }
+        if released {
+            self.inspect_pointer.release_button(ui::MouseButton::Left);
+        }
+
+        let control_at = |pos: glam::Vec2| {
+            targets.iter().rev()
+                .find(|target| target.control && target.placement.contains(egui::pos2(pos.x, pos.y)))
+                .map(|target| target.path.as_str())
+        };
+        for event in self.inspect_pointer.process_input(&raycast) {
+            if matches!(event, ui::UIEvent::Drop(..)) {
+                // Logged by update_drag_drop, with the listener that handled it
+                continue;
+            }
+            let Some(target) = targets.iter().find(|target| target.entity == event.target()) else { continue };
+            let (consumed_by, detail) = match &event {
+                ui::UIEvent::PointerDown(_, pos)
+                | ui::UIEvent::PointerUp(_, pos)
+                | ui::UIEvent::PointerClick(_, pos)
+                | ui::UIEvent::BeginDrag(_, pos)
+                | ui::UIEvent::EndDrag(_, pos) => (control_at(*pos), format!("at ({:.0}, {:.0})", pos.x, pos.y)),
+                ui::UIEvent::Drag(_, pos, delta) => {
+                    (control_at(*pos), format!("at ({:.0}, {:.0}) by ({:.1}, {:.1})", pos.x, pos.y, delta.x, delta.y))
+                }
+                ui::UIEvent::Scroll(_, delta) => {
+                    let consumed_by = pointer.and_then(|pos| control_at(glam::Vec2::new(pos.x, pos.y)));
+                    (consumed_by, format!("by {:.1}", delta))
+                }
+                _ => (None, String::new()),
+            };
+            self.event_log.push(event.event_type().name(), &target.path, consumed_by, detail);
+        }
+    }
+
+    /// Every element under `pos` in the last render's layout, topmost first, each with
+    /// why the pointer passes over it (all but the one it hits)
+    pub fn inspect_point(&mut self, pos: egui::Pos2) -> Vec<UIHitInfo> {
+        let Some(screen_rect) = self.screen_rect else { return Vec::new() };
+        let (raycast, targets) = self.inspect_targets(screen_rect);
+        let by_entity: HashMap<ui::Entity, &InspectTarget> = targets.iter().map(|target| (target.entity, target)).collect();
+        let name = |entity: ui::Entity| by_entity.get(&entity).map_or_else(|| "?".to_string(), |target| format!("'{}'", target.path));
+
+        raycast.probe(glam::Vec2::new(pos.x, pos.y)).into_iter()
+            .filter_map(|hit| {
+                let target = by_entity.get(&hit.entity)?;
+                let rect = target.placement.rect;
+                Some(UIHitInfo {
+                    path: target.path.clone(),
+                    skipped: hit.skipped.map(|skip| skip.describe(name)),
+                    corners: [rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom()]
+                        .map(|corner| target.placement.point(corner)),
+                })
+            })
+            .collect()
+    }
+
+    /// Script listeners and prefab bindings of the active instances' elements, by path
+    pub fn element_callbacks(&self) -> Vec<UICallbackInfo> {
+        let mut callbacks: Vec<UICallbackInfo> = self.listeners.iter()
+            .map(|listener| UICallbackInfo {
+                element_path: listener.element_path.clone(),
+                event: format!("{:?}", listener.event),
+                callback: listener.callback.clone(),
+                owner: Some(listener.owner),
+            })
+            .collect();
+        for binding in &self.bindings {
+            let instance_name = binding.element_path.split_once('/').map_or(binding.element_path.as_str(), |(instance, _)| instance);
+            let (callback, owner) = match &binding.action {
+                ui::UIAction::Lua { function } => (function.clone(), self.instance_owners.get(instance_name).copied()),
+                action => (action.describe(), None),
+            };
+            callbacks.push(UICallbackInfo {
+                element_path: binding.element_path.clone(),
+                event: format!("{:?}", binding.event),
+                callback,
+                owner,
+            });
+        }
+        callbacks.sort_by(|a, b| a.element_path.cmp(&b.element_path));
+        callbacks
+    }
+
+    /// Call `visit` with the full path and screen placement of every element of the
+    /// active UIs that take input (all of them, or only the top modal while one is
+    /// open), in draw order (later elements are on top)
+    fn visit_placed(&self, screen_rect: egui::Rect, visit: &mut dyn FnMut(&str, &UIPrefabElement, Placement)) {
+        for (instance_name, prefab) in self.instances_in_draw_order() {
+            if !self.popups.accepts_input(instance_name) {
+                continue;
+            }
+            let scale = Self::canvas_scale(prefab, screen_rect, self.pixels_per_point);
+            Self::visit_placed_element(&prefab.root, self.root_placement(screen_rect), scale, instance_name, visit);
+        }
+    }
+
+    fn visit_placed_element(
+        element: &UIPrefabElement,
+        parent: Placement,
+        scale: f32,
+        element_path: &str,
+        visit: &mut dyn FnMut(&str, &UIPrefabElement, Placement),
+    ) {
+        let placement = parent.place_element(element, scale);
+        visit(element_path, element, placement);
+        for child in &element.children {
+            let child_path = format!("{}/{}", element_path, child.name);
+            Self::visit_placed_element(child, placement, scale, &child_path, visit);
+        }
+    }
+
+    /// Draw a tooltip on egui's tooltip layer (above every UI instance and window), kept
+    /// inside the screen. Returns where it went.
+    fn paint_tooltip(
+        &self,
+        ctx: &egui::Context,
+        screen_rect: egui::Rect,
+        target: &TooltipTarget,
+        cursor: Option<egui::Pos2>,
+    ) -> egui::Rect {
+        let painter = ctx.layer_painter(egui::LayerId::new(egui::Order::Tooltip, egui::Id::new("ui_manager_tooltip")));
+        let tooltip = &target.tooltip;
+        let scale = target.placement.scale();
+        let bounds = target.placement.bounds(target.placement.rect);
+        let place = |size: egui::Vec2| {
+            let position = ui::place_tooltip(
+                glam::Vec2::new(size.x, size.y),
+                (glam::Vec2::new(bounds.min.x, bounds.min.y), glam::Vec2::new(bounds.max.x, bounds.max.y)),
+                cursor.map(|cursor| glam::Vec2::new(cursor.x, cursor.y)),
+                (glam::Vec2::new(screen_rect.min.x, screen_rect.min.y), glam::Vec2::new(screen_rect.max.x, screen_rect.max.y)),
+                tooltip.placement,
+                tooltip.offset,
+            );
+            egui::Rect::from_min_size(egui::pos2(position.x, position.y), size)
+        };
+
+        if let Some(prefab) = tooltip.prefab.as_ref().and_then(|path| self.loaded_prefabs.get(path)) {
+            let prefab_scale = Self::canvas_scale(prefab, screen_rect, self.pixels_per_point);
+            let size = prefab.root.rect_transform.size_delta * prefab_scale;
+            let rect = place(egui::vec2(size.x, size.y));
+
+            // The prefab's text elements show the tooltip text
+            let mut root = prefab.root.clone();
+            if !tooltip.text.is_empty() {
+                Self::set_texts(&mut root, &tooltip.text);
+            }
+            self.render_element(&painter, Placement::screen(rect), "tooltip", "tooltip", &root, prefab_scale);
+            return rect;
+        }
+
+        let default_style = ui::UIStyle::default();
+        let style = self.style_system.theme().get_active_style().unwrap_or(&default_style);
+        let color = |ui::Color { r, g, b, a }: ui::Color| {
+            egui::Color32::from_rgba_unmultiplied((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8, (a * 255.0) as u8)
+        };
+        let padding = 6.0 * scale;
+        let galley = painter.layout(
+            tooltip.text.clone(),
+            self.font_id(&style.default_font, style.default_font_size * scale),
+            color(style.text_color),
+            320.0 * scale,
+        );
+        let rect = place(galley.size() + egui::Vec2::splat(padding * 2.0));
+        painter.rect_filled(rect, 4.0 * scale, color(style.background_color));
+        painter.galley(rect.min + egui::Vec2::splat(padding), galley, color(style.text_color));
+        rect
+    }
+
+    fn set_texts(element: &mut UIPrefabElement, value: &str) {
+        if let Some(text) = &mut element.text {
+            text.text = value.to_string();
+        }
+        for child in &mut element.children {
+            Self::set_texts(child, value);
+        }
+    }
+
+    /// Render a single prefab
+    fn render_prefab(&self, ui: &mut egui::Ui, screen_rect: egui::Rect, instance_name: &str, prefab: &UIPrefab, opacity: f32) {
+        let mut painter = ui.painter_at(screen_rect);
+        painter.multiply_opacity(opacity);
+        let scale = Self::canvas_scale(prefab, screen_rect, self.pixels_per_point);
+        
+        // Render root and all children recursively
+        self.render_element(
+            &painter,
+            self.root_placement(screen_rect),
+            instance_name,
+            instance_name,
+            &prefab.root,
+            scale,
+        );
+    }
+
+    /// Render a UI element recursively
+    fn render_element(
+        &self,
+        painter: &egui::Painter,
+        parent: Placement,
+        instance_name: &str,
+        element_path: &str,
+        element: &UIPrefabElement,
+        scale: f32,
+    ) {
+        // Calculate element rect based on RectTransform (rotation/scale go in the placement)
+        let placement = parent.place_element(element, scale);
+        let element_rect = placement.rect;
+        let ui_element = placement.effective(&element.ui_element);
+        // Controls are tinted (or swap sprites) by state, faded by update_control_visuals;
+        // ones that can't be used (themselves or a canvas group) look disabled
+        let visual = self.control_visuals.get(element_path);
+        let transition = self.state_transition(element);
+        let state = visual.map_or(
+            if ui_element.interactable { ui::InteractableState::Normal } else { ui::InteractableState::Disabled },
+            |visual| visual.state,
+        );
+        let tint = transition.as_ref().map(|transition| visual.map_or_else(|| transition.color(state), ControlVisual::tint));
+        
+        // Debug: Log element position (use RUST_LOG=debug to see)
+        log::debug!(
+            "Element '{}': anchor=[{:.1},{:.1}]->[{:.1},{:.1}], pos=[{:.1},{:.1}], rect={:?}",
+            element.name,
+            element.rect_transform.anchor_min.x,
+            element.rect_transform.anchor_min.y,
+            element.rect_transform.anchor_max.x,
+            element.rect_transform.anchor_max.y,
+            element.rect_transform.anchored_position.x,
+            element.rect_transform.anchored_position.y,
+            element_rect
+        );
+        
+        // Render background image if present
+        if let Some(image) = &element.image {
+            let base = tint.map_or(ui_element.color, |tint| ui_element.color * tint);
+            let color = egui::Color32::from_rgba_unmultiplied(
+                (base[0] * 255.0) as u8,
+                (base[1] * 255.0) as u8,
+                (base[2] * 255.0) as u8,
+                (base[3] * ui_element.alpha * 255.0) as u8,
+            );
+            
+            // For filled images, adjust width based on fill_amount
+            let render_rect = if matches!(image.image_type, ui::ImageType::Filled) {
+                let fill_width = element_rect.width() * image.fill_amount;
+                egui::Rect::from_min_size(
+                    element_rect.min,
+                    egui::vec2(fill_width, element_rect.height()),
+                )
+            } else {
+                element_rect
+            };
+            
+            let sprite = transition.as_ref().and_then(|transition| transition.sprite(state)).or(image.sprite.as_deref());
+            let render_texture = sprite
+                .and_then(ecs::RenderTextureAsset::name_from_texture_id)
+                .and_then(|name| self.render_textures.get(name));
+            if let Some((texture, _)) = render_texture {
+                let fill = if matches!(image.image_type, ui::ImageType::Filled) { image.fill_amount } else { 1.0 };
+                let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(fill, 1.0));
+                placement.paint_image(painter, render_rect, *texture, uv, color);
+            } else {
+                placement.paint_rect(painter, render_rect, color);
+            }
+        }
+        
+        // Render on-screen controls
+        if let Some(virtual_input) = &element.virtual_input {
+            self.render_virtual_input(painter, element, placement, virtual_input, scale);
+        }
+
+        // Render text if present (an input field draws its own text in the text's style)
+        if let Some(field) = &element.input_field {
+            self.render_input_field(painter, element, field, placement, scale);
+        } else if let Some(text) = &element.text {
+            // Full path first (script-created elements), then the short "instance/name" form
+            let display_text = self.ui_data.get(element_path)
+                .or_else(|| self.ui_data.get(&format!("{}/{}", instance_name, element.name)))
+                .map(|s| s.as_str())
+                .unwrap_or(&text.text);
+            
+            let base = tint.map_or(text.color, |tint| text.color * tint);
+            let color = egui::Color32::from_rgba_unmultiplied(
+                (base[0] * 255.0) as u8,
+                (base[1] * 255.0) as u8,
+                (base[2] * 255.0) as u8,
+                (base[3] * ui_element.alpha * 255.0) as u8,
+            );
+            
+            let align = match text.alignment {
+                ui::TextAlignment::TopLeft | ui::TextAlignment::MiddleLeft | ui::TextAlignment::BottomLeft => egui::Align2::LEFT_CENTER,
+                ui::TextAlignment::TopCenter | ui::TextAlignment::MiddleCenter | ui::TextAlignment::BottomCenter => egui::Align2::CENTER_CENTER,
+                ui::TextAlignment::TopRight | ui::TextAlignment::MiddleRight | ui::TextAlignment::BottomRight => egui::Align2::RIGHT_CENTER,
+            };
+            
+            let font = self.font_id(&text.font, text.font_size * scale * placement.scale());
+            let anchor = placement.point(element_rect.center());
+            let angle = placement.angle();
+            if angle.abs() < f32::EPSILON {
+                painter.text(anchor, align, display_text, font, color);
+            } else {
+                // Rotate the laid-out text around its anchor
+                let galley = painter.layout_no_wrap(display_text.to_string(), font, color);
+                let offset = align.anchor_size(egui::Pos2::ZERO, galley.size()).min.to_vec2();
+                let offset = egui::emath::Rot2::from_angle(angle) * offset;
+                painter.add(egui::epaint::TextShape::new(anchor + offset, galley, color).with_angle(angle));
+            }
+        }
+        
+        // Render children
+        for child in &element.children {
+            let child_path = format!("{}/{}", element_path, child.name);
+            match Self::control_child(element, visual, child) {
+                Some(child) => self.render_element(painter, placement, instance_name, &child_path, &child, scale),
+                None => self.render_element(painter, placement, instance_name, &child_path, child, scale),
+            }
+        }
+    }
+
+    /// Per-state look of a control: a button's own colors and sprites, a toggle's,
+    /// slider's or dropdown's override or else the theme's. None for other elements.
+    fn state_transition(&self, element: &UIPrefabElement) -> Option<ui::StateTransition> {
+        if let Some(button) = &element.button {
+            return Some(button.state_transition());
+        }
+        let own = element.toggle.as_ref().map(|toggle| &toggle.transition)
+            .or(element.slider.as_ref().map(|slider| &slider.transition))
+            .or(element.dropdown.as_ref().map(|dropdown| &dropdown.transition))?;
+        Some(own.clone().unwrap_or_else(|| {
+            let default_style = ui::UIStyle::default();
+            self.style_system.theme().get_active_style().unwrap_or(&default_style).state_transition()
+        }))
+    }
+
+    /// `child` of a control as it is drawn: a slider's fill or knob moved to the
+    /// (smoothed) value, a toggle's checkmark faded (and scaled) to its visibility.
+    /// None for any other child.
+    fn control_child(element: &UIPrefabElement, visual: Option<&ControlVisual>, child: &UIPrefabElement) -> Option<UIPrefabElement> {
+        let is = |name: &Option<String>| name.as_deref() == Some(child.name.as_str());
+        if let Some(slider) = element.slider.as_ref().filter(|slider| is(&slider.fill_element) || is(&slider.handle_element)) {
+            let value = visual.and_then(|visual| visual.slider.as_ref()).map_or(slider.normalized_value(), ControlVisual::float);
+            let mut child = child.clone();
+            if is(&slider.fill_element) {
+                ui::SliderSystem::place_fill(&mut child.rect_transform, &slider.direction, value);
+            } else {
+                ui::SliderSystem::place_handle(&mut child.rect_transform, &slider.direction, value);
+            }
+            return Some(child);
+        }
+        let toggle = element.toggle.as_ref().filter(|toggle| is(&toggle.checkmark))?;
+        let shown = visual.and_then(|visual| visual.checkmark.as_ref())
+            .map_or(if toggle.is_on { 1.0 } else { 0.0 }, ControlVisual::float);
+        let mut child = child.clone();
+        child.ui_element.alpha *= shown;
+        if toggle.toggle_transition == ui::ToggleTransition::Scale {
+            child.rect_transform.scale *= shown;
+        }
+        Some(child)
+    }
+
+    /// Draw an input field's text (masked for passwords) or its placeholder, and the
+    /// caret while focused
+    fn render_input_field(
+        &self,
+        painter: &egui::Painter,
+        element: &UIPrefabElement,
+        field: &ui::UIInputField,
+        placement: Placement,
+        scale: f32,
+    ) {
+        let (font, font_size, ui::Color { r, g, b, a }) = element.text.as_ref()
+            .map_or(("default", 14.0, ui::Color::BLACK), |text| (text.font.as_str(), text.font_size, text.color));
+        let color = |opacity: f32| {
+            egui::Color32::from_rgba_unmultiplied(
+                (r * 255.0) as u8,
+                (g * 255.0) as u8,
+                (b * 255.0) as u8,
+                (a * element.ui_element.alpha * placement.group.alpha * opacity * 255.0) as u8,
+            )
+        };
+        let font = self.font_id(font, font_size * scale * placement.scale());
+
+        let masked = matches!(field.content_type, ui::ContentType::Password | ui::ContentType::Pin)
+            || field.input_type == ui::InputType::Password;
+        let shown = |text: &str| if masked { "•".repeat(text.chars().count()) } else { text.to_string() };
+        let (display, opacity) = if field.text.is_empty() {
+            (field.placeholder_text.clone(), 0.5)
+        } else {
+            (shown(&field.text), 1.0)
+        };
+
+        let galley = painter.layout_no_wrap(display, font.clone(), color(opacity));
+        let padding = 6.0 * scale;
+        let origin = egui::pos2(placement.rect.min.x + padding, placement.rect.center().y - galley.size().y * 0.5);
+        let height = galley.size().y;
+        painter.add(egui::epaint::TextShape::new(placement.point(origin), galley, color(opacity)).with_angle(placement.angle()));
+
+        if field.is_focused {
+            let before_caret = field.text.get(..field.caret_position.max(0) as usize).unwrap_or(&field.text);
+            let caret_x = painter.layout_no_wrap(shown(before_caret), font, color(1.0)).size().x;
+            let top = origin + egui::vec2(caret_x, 0.0);
+            painter.line_segment(
+                [placement.point(top), placement.point(top + egui::vec2(0.0, height))],
+                egui::Stroke::new(field.caret_width.max(1) as f32 * placement.scale(), color(1.0)),
+            );
+        }
+    }
+
+    /// Draw a virtual joystick (base + knob) or button
+    fn render_virtual_input(
+        &self,
+        painter: &egui::Painter,
+        element: &UIPrefabElement,
+        placement: Placement,
+        virtual_input: &UIVirtualInput,
+        scale: f32,
+    ) {
+        let element_rect = placement.rect;
+        let ui::Color { r, g, b, a } = element.ui_element.color;
+        let alpha = a * element.ui_element.alpha * placement.group.alpha;
+        let color = |opacity: f32| {
+            egui::Color32::from_rgba_unmultiplied(
+                (r * 255.0) as u8,
+                (g * 255.0) as u8,
+                (b * 255.0) as u8,
+                (alpha * opacity * 255.0) as u8,
+            )
+        };
+
+        match virtual_input {
+            UIVirtualInput::Joystick { radius, .. } => {
+                let radius = radius * scale * placement.scale();
+                let state = self.virtual_joysticks.get(&element.name);
+                let origin = state
+                    .map(|s| egui::pos2(s.origin.x, s.origin.y))
+                    .unwrap_or(placement.point(element_rect.center()));
+                let offset = state.map(|s| egui::vec2(s.offset.x, s.offset.y)).unwrap_or(egui::Vec2::ZERO);
+                let active = state.is_some_and(|s| s.active);
+
+                painter.circle(origin, radius, color(0.15), egui::Stroke::new(2.0, color(0.5)));
+                painter.circle_filled(origin + offset, radius * 0.45, color(if active { 0.8 } else { 0.5 }));
+            }
+            UIVirtualInput::Button { .. } => {
+                let down = self.virtual_buttons_down.contains(&element.name);
+                let radius = element_rect.width().min(element_rect.height()) * 0.5 * placement.scale();
+                painter.circle(
+                    placement.point(element_rect.center()),
+                    radius,
+                    color(if down { 0.6 } else { 0.25 }),
+                    egui::Stroke::new(2.0, color(0.7)),
+                );
+            }
+        }
+    }
+
+    /// Points per reference unit for a prefab drawn in `screen_rect`
+    fn canvas_scale(prefab: &UIPrefab, screen_rect: egui::Rect, pixels_per_point: f32) -> f32 {
+        match &prefab.canvas_scaler {
+            Some(scaler) => scaler.points_per_unit(screen_rect.width(), screen_rect.height(), pixels_per_point),
+            None => 1.0,
+        }
+    }
+
+    /// Calculate screen rect from RectTransform (Unity-style).
+    /// Positions and sizes are in reference units, `scale` points each.
+    fn calculate_rect(
+        parent_rect: egui::Rect,
+        transform: &ui::RectTransform,
+        scale: f32,
+    ) -> egui::Rect {
+        let parent_size = parent_rect.size();
+        
+        // Unity uses bottom-up Y (0=bottom, 1=top)
+        // egui uses top-down Y (0=top, 1=bottom)
+        // So we need to flip the Y anchors
+        let flipped_anchor_min_y = 1.0 - transform.anchor_max.y;
+        let flipped_anchor_max_y = 1.0 - transform.anchor_min.y;
+        
+        // Calculate anchor points in parent space (Unity-style with Y-flip)
+        let anchor_min_pos = egui::pos2(
+            parent_rect.min.x + parent_size.x * transform.anchor_min.x,
+            parent_rect.min.y + parent_size.y * flipped_anchor_min_y,
+        );
+        
+        let anchor_max_pos = egui::pos2(
+            parent_rect.min.x + parent_size.x * transform.anchor_max.x,
+            parent_rect.min.y + parent_size.y * flipped_anchor_max_y,
+        );
+        
+        // Calculate the rect between anchors
+        let anchored_rect = egui::Rect::from_min_max(anchor_min_pos, anchor_max_pos);
+        
+        // Apply size_delta (offset from anchored size)
+        // If anchors are the same point, size_delta defines the full size
+        // If anchors are different, size_delta is added to the anchored size
+        let final_size = egui::vec2(
+            anchored_rect.width() + transform.size_delta.x * scale,
+            anchored_rect.height() + transform.size_delta.y * scale,
+        );
+        
+        // Calculate the center of the anchored rect
+        let anchor_center = anchored_rect.center();
+        
+        // Apply anchored_position (offset from anchor center)
+        // Flip Y offset because Unity uses bottom-up Y
+        let offset_center = egui::pos2(
+            anchor_center.x + transform.anchored_position.x * scale,
+            anchor_center.y - transform.anchored_position.y * scale,  // Flip Y
+        );
+        
+        // Apply pivot to determine the actual position
+        // Unity pivot: (0,0)=bottom-left, (0.5,0.5)=center, (1,1)=top-right
+        // egui origin: top-left
+        // So we need to flip pivot.y: Unity (0,0)=bottom-left → egui (0,1)=bottom-left
+        let flipped_pivot_y = 1.0 - transform.pivot.y;
+        
+        let pivot_offset = egui::vec2(
+            -final_size.x * transform.pivot.x,
+            -final_size.y * flipped_pivot_y,
+        );
+        
+        let final_min = egui::pos2(
+            offset_center.x + pivot_offset.x,
+            offset_center.y + pivot_offset.y,
+        );
+        
+        egui::Rect::from_min_size(final_min, final_size)
+    }
+    
+    /// Set element color (for images)
+    pub fn set_element_color(&mut self, instance_name: &str, element_name: &str, r: f32, g: f32, b: f32, a: f32) -> Result<(), String> {
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        
+        if let Some(element) = Self::find_element_mut(&mut prefab.root, element_name) {
+            element.ui_element.color = ui::Color::rgba(r, g, b, a);
+            Ok(())
+        } else {
+            Err(format!("Element not found: {}", element_name))
+        }
+    }
+    
+    /// Set element fill amount (for filled images)
+    pub fn set_element_fill(&mut self, instance_name: &str, element_name: &str, fill_amount: f32) -> Result<(), String> {
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        
+        if let Some(element) = Self::find_element_mut(&mut prefab.root, element_name) {
+            if let Some(image) = &mut element.image {
+                image.fill_amount = fill_amount.clamp(0.0, 1.0);
+                Ok(())
+            } else {
+                Err(format!("Element '{}' has no image component", element_name))
+            }
+        } else {
+            Err(format!("Element not found: {}", element_name))
+        }
+    }
+    
+    /// Show element (set alpha to 1.0)
+    pub fn show_element(&mut self, instance_name: &str, element_name: &str) -> Result<(), String> {
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        
+        if let Some(element) = Self::find_element_mut(&mut prefab.root, element_name) {
+            element.ui_element.alpha = 1.0;
+            Ok(())
+        } else {
+            Err(format!("Element not found: {}", element_name))
+        }
+    }
+    
+    /// Hide element (set alpha to 0.0)
+    pub fn hide_element(&mut self, instance_name: &str, element_name: &str) -> Result<(), String> {
+        let prefab = self.active_uis.get_mut(instance_name)
+            .ok_or_else(|| format!("UI instance not found: {}", instance_name))?;
+        
+        if let Some(element) = Self::find_element_mut(&mut prefab.root, element_name) {
+            element.ui_element.alpha = 0.0;
+            Ok(())
+        } else {
+            Err(format!("Element not found: {}", element_name))
+        }
+    }
+    
+    /// Find element by path below the root ("a/b"). A path that doesn't start at a root
+    /// child is matched from the first element named like its first segment (the n-th,
+    /// depth first, for "Name[n]").
+    fn find_element_mut<'a>(root: &'a mut UIPrefabElement, path: &str) -> Option<&'a mut UIPrefabElement> {
+        let indices = Self::find_path(root, path)?;
+        Some(Self::element_at_mut(root, &indices))
+    }
+
+    /// Child indices leading from `root` to the element at `path` ("" is the root itself).
+    /// A "Name[n]" segment picks the n-th child with that name (see resolve).
+    fn find_path(root: &UIPrefabElement, path: &str) -> Option<Vec<usize>> {
+        if path.is_empty() {
+            return Some(Vec::new());
+        }
+        let segments: Vec<&str> = path.split('/').collect();
+        if let Some(indices) = Self::descend(root, &segments) {
+            return Some(indices);
+        }
+
+        let mut indices = match PathSegment::parse(segments[0]).ok()? {
+            PathSegment::Name(name) => Self::find_nth_by_name(root, &name, 1)?,
+            PathSegment::Nth(name, n) => Self::find_nth_by_name(root, &name, n)?,
+            PathSegment::Any => return None,
+        };
+        let start = Self::element_at(root, &indices);
+        indices.extend(Self::descend(start, &segments[1..])?);
+        Some(indices)
+    }
+
+    fn descend(element: &UIPrefabElement, segments: &[&str]) -> Option<Vec<usize>> {
+        let mut indices = Vec::with_capacity(segments.len());
+        let mut current = element;
+        for segment in segments {
+            let index = match PathSegment::parse(segment).ok()? {
+                PathSegment::Name(name) => Self::nth_child(current, &name, 1)?,
+                PathSegment::Nth(name, n) => Self::nth_child(current, &name, n)?,
+                PathSegment::Any => return None,
+            };
+            indices.push(index);
+            current = &current.children[index];
+        }
+        Some(indices)
+    }
+
+    /// Indices of the n-th element (from 1) named `name` at or below `element`, counting
+    /// depth-first with `element` itself first
+    fn find_nth_by_name(element: &UIPrefabElement, name: &str, n: usize) -> Option<Vec<usize>> {
+        let mut all = Vec::new();
+        Self::collect_indices(element, &mut Vec::new(), &mut all);
+        all.into_iter()
+            .filter(|indices| Self::element_at(element, indices).name == name)
+            .nth(n.checked_sub(1)?)
+    }
+
+    /// Child indices of every element below `element` (itself first), depth-first
+    fn collect_indices(element: &UIPrefabElement, prefix: &mut Vec<usize>, out: &mut Vec<Vec<usize>>) {
+        out.push(prefix.clone());
+        for (index, child) in element.children.iter().enumerate() {
+            prefix.push(index);
+            Self::collect_indices(child, prefix, out);
+            prefix.pop();
+        }
+    }
+
+    fn element_at<'a>(root: &'a UIPrefabElement, indices: &[usize]) -> &'a UIPrefabElement {
+        indices.iter().fold(root, |element, &index| &element.children[index])
+    }
+
+    fn element_at_mut<'a>(root: &'a mut UIPrefabElement, indices: &[usize]) -> &'a mut UIPrefabElement {
+        indices.iter().fold(root, |element, &index| &mut element.children[index])
+    }
+
+    /// "instance/child/..." path of the element at `indices` (as used for ui_data)
+    fn canonical_path(instance_name: &str, root: &UIPrefabElement, indices: &[usize]) -> String {
+        let mut path = instance_name.to_string();
+        let mut current = root;
+        for &index in indices {
+            current = &current.children[index];
+            path.push('/');
+            path.push_str(&current.name);
+        }
+        path
+    }
+
+    fn clear_layout_sizes(&mut self, instance_name: &str) {
+        let prefix = format!("{}/", instance_name);
+        self.layout_sizes.retain(|path, _| !path.starts_with(&prefix));
+    }
+
+    /// Position the children of layout groups (recursively). Children are pinned to the
+    /// group's top-left corner, like ui::LayoutSystem does for ECS-based UI.
+    fn layout_element(
+        element: &mut UIPrefabElement,
+        rect: egui::Rect,
+        safe_rect: egui::Rect,
+        scale: f32,
+        element_path: &str,
+        layout_sizes: &mut HashMap<String, egui::Vec2>,
+    ) {
+        let placements = Self::layout_children(element, rect.size() / scale, element_path, layout_sizes);
+        for (index, position, size) in placements {
+            let transform = &mut element.children[index].rect_transform;
+            transform.anchor_min = ui::Vec2::new(0.0, 1.0);
+            transform.anchor_max = ui::Vec2::new(0.0, 1.0);
+            transform.pivot = ui::Vec2::new(0.0, 1.0);
+            transform.anchored_position = ui::Vec2::new(position.x, -position.y);
+            transform.size_delta = ui::Vec2::new(size.x, size.y);
+        }
+
+        for child in &mut element.children {
+            let parent_rect = match &child.safe_area {
+                Some(safe_area) => safe_area_rect(safe_area, rect, safe_rect),
+                None => rect,
+            };
+            let child_rect = Self::calculate_rect(parent_rect, &child.rect_transform, scale);
+            let child_path = format!("{}/{}", element_path, child.name);
+            Self::layout_element(child, child_rect, safe_rect, scale, &child_path, layout_sizes);
+        }
+    }
+
+    /// (child index, offset from the top-left corner, size) for each laid-out child,
+    /// all in reference units
+    fn layout_children(
+        element: &UIPrefabElement,
+        size: egui::Vec2,
+        element_path: &str,
+        layout_sizes: &mut HashMap<String, egui::Vec2>,
+    ) -> Vec<(usize, egui::Vec2, egui::Vec2)> {
+        let children: Vec<(usize, egui::Vec2)> = element.children.iter()
+            .enumerate()
+            .filter(|(_, child)| !child.ui_element.ignore_layout)
+            .map(|(index, child)| {
+                let preferred = *layout_sizes
+                    .entry(format!("{}/{}", element_path, child.name))
+                    .or_insert(egui::vec2(child.rect_transform.size_delta.x, child.rect_transform.size_delta.y));
+                (index, preferred)
+            })
+            .collect();
+        if children.is_empty() {
+            return Vec::new();
+        }
+
+        if let Some(layout) = &element.vertical_layout {
+            let padding = layout.padding;
+            let available = egui::vec2(size.x - padding.x - padding.z, size.y - padding.y - padding.w);
+            let (align_x, align_y) = Self::alignment_factors(&layout.child_alignment);
+            let spacing = layout.spacing * (children.len() - 1) as f32;
+            let content: f32 = children.iter().map(|(_, preferred)| preferred.y).sum::<f32>() + spacing;
+            let extra = if layout.child_force_expand_height {
+                ((available.y - content) / children.len() as f32).max(0.0)
+            } else {
+                0.0
+            };
+
+            let mut y = padding.w + (available.y - content - extra * children.len() as f32).max(0.0) * align_y;
+            children.into_iter().map(|(index, preferred)| {
+                let width = if layout.child_control_width { available.x } else { preferred.x };
+                let child_size = egui::vec2(width, preferred.y + extra);
+                let x = padding.x + (available.x - width) * align_x;
+                let placement = (index, egui::vec2(x, y), child_size);
+                y += child_size.y + layout.spacing;
+                placement
+            }).collect()
+        } else if let Some(layout) = &element.horizontal_layout {
+            let padding = layout.padding;
+            let available = egui::vec2(size.x - padding.x - padding.z, size.y - padding.y - padding.w);
+            let (align_x, align_y) = Self::alignment_factors(&layout.child_alignment);
+            let spacing = layout.spacing * (children.len() - 1) as f32;
+            let content: f32 = children.iter().map(|(_, preferred)| preferred.x).sum::<f32>() + spacing;
+            let extra = if layout.child_force_expand_width {
+                ((available.x - content) / children.len() as f32).max(0.0)
+            } else {
+                0.0
+            };
+
+            let mut x = padding.x + (available.x - content - extra * children.len() as f32).max(0.0) * align_x;
+            children.into_iter().map(|(index, preferred)| {
+                let height = if layout.child_control_height { available.y } else { preferred.y };
+                let child_size = egui::vec2(preferred.x + extra, height);
+                let y = padding.w + (available.y - height) * align_y;
+                let placement = (index, egui::vec2(x, y), child_size);
+                x += child_size.x + layout.spacing;
+                placement
+            }).collect()
+        } else if let Some(layout) = &element.grid_layout {
+            let padding = layout.padding;
+            let available = egui::vec2(size.x - padding.x - padding.z, size.y - padding.y - padding.w);
+            let cell = egui::vec2(layout.cell_size.x, layout.cell_size.y);
+            let spacing = egui::vec2(layout.spacing.x, layout.spacing.y);
+            let count = children.len();
+            let fit = |available: f32, cell: f32, spacing: f32| ((available + spacing) / (cell + spacing).max(1.0)).floor().max(1.0) as usize;
+
+            let (columns, rows) = match (&layout.constraint, &layout.start_axis) {
+                (GridConstraint::FixedColumnCount, _) => {
+                    let columns = layout.constraint_count.max(1) as usize;
+                    (columns, count.div_ceil(columns))
+                }
+                (GridConstraint::FixedRowCount, _) => {
+                    let rows = layout.constraint_count.max(1) as usize;
+                    (count.div_ceil(rows), rows)
+                }
+                (GridConstraint::Flexible, Axis::Horizontal) => {
+                    let columns = fit(available.x, cell.x, spacing.x).min(count);
+                    (columns, count.div_ceil(columns))
+                }
+                (GridConstraint::Flexible, Axis::Vertical) => {
+                    let rows = fit(available.y, cell.y, spacing.y).min(count);
+                    (count.div_ceil(rows), rows)
+                }
+            };
+
+            let block = egui::vec2(
+                columns as f32 * cell.x + (columns - 1) as f32 * spacing.x,
+                rows as f32 * cell.y + (rows - 1) as f32 * spacing.y,
+            );
+            let (align_x, align_y) = Self::alignment_factors(&layout.child_alignment);
+            let origin = egui::vec2(
+                padding.x + (available.x - block.x).max(0.0) * align_x,
+                padding.w + (available.y - block.y).max(0.0) * align_y,
+            );
+            let flip_x = matches!(layout.start_corner, Corner::UpperRight | Corner::LowerRight);
+            let flip_y = matches!(layout.start_corner, Corner::LowerLeft | Corner::LowerRight);
+
+            children.into_iter().enumerate().map(|(slot, (index, _))| {
+                let (mut column, mut row) = match layout.start_axis {
+                    Axis::Horizontal => (slot % columns, slot / columns),
+                    Axis::Vertical => (slot / rows, slot % rows),
+                };
+                if flip_x {
+                    column = columns - 1 - column;
+                }
+                if flip_y {
+                    row = rows - 1 - row;
+                }
+                let position = origin + egui::vec2(column as f32 * (cell.x + spacing.x), row as f32 * (cell.y + spacing.y));
+                (index, position, cell)
+            }).collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Horizontal and vertical (from the top) share of free space before the children
+    fn alignment_factors(alignment: &Alignment) -> (f32, f32) {
+        match alignment {
+            Alignment::UpperLeft => (0.0, 0.0),
+            Alignment::UpperCenter => (0.5, 0.0),
+            Alignment::UpperRight => (1.0, 0.0),
+            Alignment::MiddleLeft => (0.0, 0.5),
+            Alignment::MiddleCenter => (0.5, 0.5),
+            Alignment::MiddleRight => (1.0, 0.5),
+            Alignment::LowerLeft => (0.0, 1.0),
+            Alignment::LowerCenter => (0.5, 1.0),
+            Alignment::LowerRight => (1.0, 1.0),
+        }
+    }
+}
+
+impl Default for UIManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `parent` shrunk to the screen's safe area on the sides a SafeArea enables
+/// (ui::Rect is y-up, so both rects are flipped on the way in and out)
+fn safe_area_rect(safe_area: &ui::UISafeArea, parent: egui::Rect, safe_rect: egui::Rect) -> egui::Rect {
+    let flip = |rect: egui::Rect| ui::Rect::new(rect.min.x, -rect.max.y, rect.width(), rect.height());
+    let inset = safe_area.apply(flip(parent), flip(safe_rect));
+    egui::Rect::from_min_max(
+        egui::pos2(inset.x, -(inset.y + inset.height)),
+        egui::pos2(inset.x + inset.width, -inset.y),
+    )
+}
+
+/// Where an element is drawn: its rect from anchors/offsets (unrotated, screen
+/// coordinates) and the rotation/scale of it and its ancestors applied on top
+#[derive(Clone, Copy)]
+struct Placement {
+    rect: egui::Rect,
+    transform: glam::Affine2,
+    /// Screen safe area, inherited unchanged (SafeArea elements sit under unrotated parents)
+    safe_rect: egui::Rect,
+    /// Canvas groups of the element and its ancestors, combined on the way down so no
+    /// element walks up the tree for them
+    group: ui::CanvasGroupState,
+}
+
+impl Placement {
+    fn screen(rect: egui::Rect) -> Self {
+        Self { rect, transform: glam::Affine2::IDENTITY, safe_rect: rect, group: ui::CanvasGroupState::default() }
+    }
+
+    /// Place a child element, first shrinking this rect to the safe area if it has a
+    /// SafeArea, and add its canvas group to the inherited ones
+    fn place_element(&self, element: &UIPrefabElement, scale: f32) -> Self {
+        let parent = Self { group: self.group.with(element.canvas_group.as_ref()), ..*self };
+        match &element.safe_area {
+            Some(safe_area) => {
+                let parent = Self { rect: safe_area_rect(safe_area, self.rect, self.safe_rect), ..parent };
+                parent.place(&element.rect_transform, scale)
+            }
+            None => parent.place(&element.rect_transform, scale),
+        }
+    }
+
+    /// `element` as its canvas groups leave it: alpha multiplied by theirs, and not
+    /// interactable or a raycast target where a group says so
+    fn effective(&self, element: &ui::UIElement) -> ui::UIElement {
+        ui::UIElement {
+            alpha: element.alpha * self.group.alpha,
+            interactable: element.interactable && self.group.interactable,
+            raycast_target: element.raycast_target && self.group.blocks_raycasts,
+            blocks_raycasts: element.blocks_raycasts && self.group.blocks_raycasts,
+            ..element.clone()
+        }
+    }
+
+    /// Place a child inside this element: it inherits the rotation/scale and adds its own
+    /// around its pivot
+    fn place(&self, transform: &ui::RectTransform, scale: f32) -> Self {
+        let rect = UIManager::calculate_rect(self.rect, transform, scale);
+        // Unity pivots are bottom-up; egui y points down, so angles flip too
+        let pivot = glam::Vec2::new(
+            rect.min.x + rect.width() * transform.pivot.x,
+            rect.max.y - rect.height() * transform.pivot.y,
+        );
+        let local = glam::Affine2::from_translation(pivot)
+            * glam::Affine2::from_scale_angle_translation(transform.scale, -transform.rotation.to_radians(), glam::Vec2::ZERO)
+            * glam::Affine2::from_translation(-pivot);
+        Self { rect, transform: self.transform * local, ..*self }
+    }
+
+    /// `point` (screen coordinates) is inside the rotated/scaled rect
+    fn contains(&self, point: egui::Pos2) -> bool {
+        let local = self.transform.inverse().transform_point2(glam::Vec2::new(point.x, point.y));
+        self.rect.contains(egui::pos2(local.x, local.y))
+    }
+
+    fn point(&self, point: egui::Pos2) -> egui::Pos2 {
+        let point = self.transform.transform_point2(glam::Vec2::new(point.x, point.y));
+        egui::pos2(point.x, point.y)
+    }
+
+    fn is_axis_aligned(&self) -> bool {
+        self.transform.matrix2 == glam::Mat2::IDENTITY
+    }
+
+    fn corners(&self, rect: egui::Rect) -> [egui::Pos2; 4] {
+        [rect.left_top(), rect.right_top(), rect.right_bottom(), rect.left_bottom()].map(|corner| self.point(corner))
+    }
+
+    /// Screen-space bounding box of `rect` (given in this placement's unrotated space)
+    fn bounds(&self, rect: egui::Rect) -> egui::Rect {
+        egui::Rect::from_points(&self.corners(rect))
+    }
+
+    /// Uniform scale of the transform (for font sizes and radii)
+    fn scale(&self) -> f32 {
+        self.transform.matrix2.determinant().abs().sqrt()
+    }
+
+    /// Rotation of the transform in radians (egui's clockwise-positive)
+    fn angle(&self) -> f32 {
+        let x_axis = self.transform.matrix2.x_axis;
+        x_axis.y.atan2(x_axis.x)
+    }
+
+    fn paint_rect(&self, painter: &egui::Painter, rect: egui::Rect, color: egui::Color32) {
+        if self.is_axis_aligned() {
+            painter.rect_filled(self.bounds(rect), 2.0, color);
+        } else {
+            painter.add(egui::Shape::convex_polygon(self.corners(rect).to_vec(), color, egui::Stroke::NONE));
+        }
+    }
+
+    /// `rect` textured with the `uv` part of `texture`, tinted by `color`
+    fn paint_image(&self, painter: &egui::Painter, rect: egui::Rect, texture: egui::TextureId, uv: egui::Rect, color: egui::Color32) {
+        let mut mesh = egui::Mesh::with_texture(texture);
+        let uvs = [uv.left_top(), uv.right_top(), uv.right_bottom(), uv.left_bottom()];
+        for (pos, uv) in self.corners(rect).into_iter().zip(uvs) {
+            mesh.vertices.push(egui::epaint::Vertex { pos, uv, color });
+        }
+        mesh.indices.extend_from_slice(&[0, 1, 2, 0, 2, 3]);
+        painter.add(mesh);
+    }
+}
+
+/// A UI command path: "instance/a/b", "@instance/a/b", "instance/*/Fill", "instance/Heart[2]"
+#[derive(Debug, PartialEq)]
+struct ElementPath {
+    instance: String,
+    /// "@instance": the segments start at the instance's root
+    scoped: bool,
+    segments: Vec<PathSegment>,
+}
+
+#[derive(Debug, PartialEq)]
+enum PathSegment {
+    /// The first child with this name
+    Name(String),
+    /// "Name[n]": the n-th child (from 1) with this name
+    Nth(String, usize),
+    /// "*": every child
+    Any,
+}
+
+impl ElementPath {
+    fn parse(path: &str) -> Result<Self, String> {
+        let mut parts = path.split('/');
+        let first = parts.next().unwrap_or_default();
+        let (instance, scoped) = match first.strip_prefix('@') {
+            Some(instance) => (instance, true),
+            None => (first, false),
+        };
+        if instance.is_empty() {
+            return Err("Invalid path: missing instance name".to_string());
+        }
+        if instance.contains(['@', '*', '[', ']']) {
+            return Err(format!("Invalid path: '{}' is not an instance name", first));
+        }
+        let segments = parts.map(PathSegment::parse).collect::<Result<_, _>>()?;
+        Ok(Self { instance: instance.to_string(), scoped, segments })
+    }
+}
+
+impl PathSegment {
+    fn parse(segment: &str) -> Result<Self, String> {
+        if segment == "*" {
+            return Ok(PathSegment::Any);
+        }
+        if segment.is_empty() {
+            return Err("Invalid path: empty segment".to_string());
+        }
+        if segment.contains(['*', '@']) {
+            return Err(format!("Invalid path: '{}' ('*' must be a whole segment, '@' only starts the instance)", segment));
+        }
+        let Some((name, rest)) = segment.split_once('[') else {
+            if segment.contains(']') {
+                return Err(format!("Invalid path: unmatched ']' in '{}'", segment));
+            }
+            return Ok(PathSegment::Name(segment.to_string()));
+        };
+        let index = rest.strip_suffix(']')
+            .filter(|index| !name.is_empty() && !index.contains(['[', ']']))
+            .ok_or_else(|| format!("Invalid path: '{}' should look like Name[n]", segment))?;
+        let n = index.parse::<usize>().ok()
+            .filter(|&n| n >= 1)
+            .ok_or_else(|| format!("Invalid path: index in '{}' must be a number from 1", segment))?;
+        Ok(PathSegment::Nth(name.to_string(), n))
+    }
+}
+
+/// Event log detail: the value and the callbacks that ran ("no callbacks" if none)
+fn event_detail(value: &str, handlers: &[String]) -> String {
+    let handlers = match handlers.is_empty() {
+        true => "no callbacks".to_string(),
+        false => handlers.join(", "),
+    };
+    match value.is_empty() {
+        true => handlers,
+        false => format!("'{}' → {}", value, handlers),
+    }
+}
+
+/// Single-character insertions, deletions and substitutions turning `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stretched(name: &str) -> UIPrefabElement {
+        let rect_transform = ui::RectTransform {
+            anchor_min: ui::Vec2::ZERO,
+            anchor_max: ui::Vec2::ONE,
+            size_delta: ui::Vec2::ZERO,
+            ..Default::default()
+        };
+        UIPrefabElement::new(name, rect_transform)
+    }
+
+    fn row(name: &str) -> UIPrefabElement {
+        let rect_transform = ui::RectTransform { size_delta: ui::Vec2::new(50.0, 20.0), ..Default::default() };
+        let mut element = UIPrefabElement::new(name, rect_transform);
+        element.text = Some(ui::UIText::default());
+        element
+    }
+
+    #[test]
+    fn test_created_rows_follow_vertical_layout() {
+        let mut list = stretched("list");
+        list.vertical_layout = Some(ui::VerticalLayoutGroup {
+            spacing: 2.0,
+            child_force_expand_height: false,
+            ..Default::default()
+        });
+        let mut root = stretched("root");
+        root.children.push(list);
+
+        let mut manager = UIManager::new();
+        manager.active_uis.insert("hud".to_string(), UIPrefab { name: "hud".to_string(), root, canvas_scaler: None });
+        manager.screen_rect = Some(egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(300.0, 200.0)));
+
+        for i in 0..100 {
+            let path = manager.add_element("hud/list", row(&format!("row_{}", i)), None, None).unwrap();
+            assert_eq!(path, format!("hud/list/row_{}", i));
+        }
+        assert!(manager.add_element("hud/list", row("row_0"), None, None).is_err());
+
+        // Layout runs once for the whole batch
+        manager.apply_pending_layouts();
+        let list = &manager.active_uis["hud"].root.children[0];
+        assert_eq!(list.children.len(), 100);
+        for (i, child) in list.children.iter().enumerate() {
+            assert_eq!(child.rect_transform.anchored_position, ui::Vec2::new(0.0, -(i as f32) * 22.0));
+            assert_eq!(child.rect_transform.size_delta, ui::Vec2::new(300.0, 20.0));
+        }
+        let text_color = manager.theme().get_active_style().unwrap().text_color;
+        assert_eq!(list.children[0].text.as_ref().unwrap().color, text_color);
+
+        // Removing a row closes the gap
+        manager.set_ui_data("hud/list/row_0", "gone".to_string());
+        manager.destroy_element("hud/list/row_0").unwrap();
+        assert!(manager.get_ui_data("hud/list/row_0").is_none());
+        manager.apply_pending_layouts();
+        let list = &manager.active_uis["hud"].root.children[0];
+        assert_eq!(list.children[0].name, "row_1");
+        assert_eq!(list.children[0].rect_transform.anchored_position, ui::Vec2::ZERO);
+
+        // Nested paths reach created elements
+        manager.set_element_color("hud", "list/row_1", 1.0, 0.0, 0.0, 1.0).unwrap();
+        assert_eq!(manager.active_uis["hud"].root.children[0].children[0].ui_element.color, [1.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_command_batch_applies_in_order_with_one_layout() {
+        use script::UICommand;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("inventory.uiprefab").to_string_lossy().to_string();
+        let mut list = stretched("list");
+        list.vertical_layout = Some(ui::VerticalLayoutGroup { child_force_expand_height: false, ..Default::default() });
+        let mut title = row("title");
+        title.image = Some(ui::UIImage::default());
+        let mut root = stretched("root");
+        root.children.push(title);
+        root.children.push(list);
+        let prefab = UIPrefab { name: "inventory".to_string(), root, canvas_scaler: None };
+        std::fs::write(&path, serde_json::to_string(&prefab).unwrap()).unwrap();
+
+        let mut manager = UIManager::new();
+        manager.screen_rect = Some(egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(300.0, 200.0)));
+
+        // Load, activate and fill the prefab in the same frame
+        let mut commands = vec![
+            UICommand::LoadPrefab { path: path.clone() },
+            UICommand::ActivatePrefab { path: path.clone(), instance_name: "inv".to_string(), owner: 1 },
+            UICommand::SetText { element_path: "inv/title".to_string(), text: "Items".to_string() },
+        ];
+        for i in 0..15 {
+            let name = format!("slot_{}", i);
+            commands.push(UICommand::CreateElement { parent_path: "inv/list".to_string(), element: Box::new(row(&name)), style_class: None, color: None });
+            commands.push(UICommand::SetText { element_path: format!("inv/list/{}", name), text: format!("Item {}", i) });
+            commands.push(UICommand::SetColor { element_path: format!("inv/{}", name), r: 1.0, g: 0.0, b: 0.0, a: 1.0 });
+        }
+        commands.push(UICommand::HideElement { element_path: "inv/slot_3".to_string() });
+        commands.push(UICommand::ShowElement { element_path: "inv/slot_3".to_string() });
+        commands.push(UICommand::HideElement { element_path: "inv/slot_4".to_string() });
+        commands.push(UICommand::DestroyElement { element_path: "inv/list/slot_0".to_string() });
+        commands.push(UICommand::SetSiblingIndex { element_path: "inv/list/slot_14".to_string(), index: Some(0) });
+        commands.push(UICommand::SetImageFill { element_path: "inv/title".to_string(), fill_amount: 0.5 });
+        commands.push(UICommand::SetTooltip { element_path: "inv/title".to_string(), text: "Your bag".to_string() });
+        assert_eq!(commands.len(), 55);
+
+        let results = manager.apply_commands(commands);
+        assert_eq!(results.len(), 55);
+        assert!(results.iter().all(Result::is_ok), "{:?}", results.iter().find(|r| r.is_err()));
+
+        assert_eq!(manager.get_ui_data("inv/title"), Some(&"Items".to_string()));
+        assert_eq!(manager.get_ui_data("inv/list/slot_5"), Some(&"Item 5".to_string()));
+        assert!(manager.get_ui_data("inv/list/slot_0").is_none());
+        assert_eq!(manager.element("inv/slot_3").unwrap().ui_element.alpha, 1.0);
+        assert_eq!(manager.element("inv/slot_4").unwrap().ui_element.alpha, 0.0);
+        assert_eq!(manager.element("inv/slot_5").unwrap().ui_element.color, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(manager.element("inv/title").unwrap().image.as_ref().unwrap().fill_amount, 0.5);
+
+        // Laid out before the next render, in the reordered sequence
+        let list = manager.element("inv/list").unwrap();
+        assert_eq!(list.children.len(), 14);
+        assert_eq!(list.children[0].name, "slot_14");
+        for (i, child) in list.children.iter().enumerate() {
+            assert_eq!(child.rect_transform.anchored_position, ui::Vec2::new(0.0, -(i as f32) * 20.0));
+        }
+        assert!(manager.layout_dirty.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_path_suggests_closest_and_warns_once() {
+        use script::UICommand;
+
+        let mut root = stretched("root");
+        let mut panel = stretched("panel");
+        panel.children.push(row("score"));
+        root.children.push(panel);
+        let mut manager = UIManager::new();
+        manager.active_uis.insert("hud".to_string(), UIPrefab { name: "hud".to_string(), root, canvas_scaler: None });
+
+        let typo = || UICommand::SetText { element_path: "hud/panel/scroe".to_string(), text: "10".to_string() };
+        let results = manager.apply_commands(vec![typo(), typo()]);
+        let error = results[0].as_ref().unwrap_err();
+        assert!(error.not_found);
+        assert_eq!(error.command, "set_text");
+        assert_eq!(error.suggestion.as_deref(), Some("hud/panel/score"));
+        assert_eq!(results[1].as_ref().unwrap_err(), error);
+
+        let warnings = manager.take_warnings();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("did you mean 'hud/panel/score'"));
+        manager.apply_commands(vec![typo()]);
+        assert!(manager.take_warnings().is_empty());
+
+        // Unknown instance; nothing close enough to suggest
+        let results = manager.apply_commands(vec![UICommand::ShowElement { element_path: "pause_menu/resume".to_string() }]);
+        let error = results[0].as_ref().unwrap_err();
+        assert_eq!(error.message, "UI instance not found: pause_menu");
+        assert_eq!(error.suggestion, None);
+
+        assert_eq!(edit_distance("scroe", "score"), 2);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    /// hud: root > hearts > heart x3 (each with "fill"), label
+    fn hearts_manager() -> UIManager {
+        let mut hearts = stretched("hearts");
+        for _ in 0..3 {
+            let mut heart = stretched("heart");
+            let mut fill = row("fill");
+            fill.image = Some(ui::UIImage::default());
+            heart.children.push(fill);
+            hearts.children.push(heart);
+        }
+        let mut root = stretched("root");
+        root.children.push(hearts);
+        root.children.push(row("label"));
+        let mut manager = UIManager::new();
+        manager.active_uis.insert("hud".to_string(), UIPrefab { name: "hud".to_string(), root, canvas_scaler: None });
+        manager
+    }
+
+    #[test]
+    fn test_path_parsing() {
+        let path = ElementPath::parse("@hud/hearts/*/heart[2]").unwrap();
+        assert_eq!(path, ElementPath {
+            instance: "hud".to_string(),
+            scoped: true,
+            segments: vec![
+                PathSegment::Name("hearts".to_string()),
+                PathSegment::Any,
+                PathSegment::Nth("heart".to_string(), 2),
+            ],
+        });
+        assert_eq!(ElementPath::parse("hud").unwrap().segments, []);
+
+        for malformed in ["", "@", "hud//fill", "hud/", "hud/he*rt", "hud/a@b", "hud/heart[", "hud/heart]",
+                          "hud/heart[0]", "hud/heart[x]", "hud/[2]", "hud/heart[1]x", "h*d/fill"] {
+            assert!(ElementPath::parse(malformed).is_err(), "{:?}", malformed);
+        }
+    }
+
+    #[test]
+    fn test_wildcard_applies_to_every_match_in_sibling_order() {
+        use script::UICommand;
+
+        let mut manager = hearts_manager();
+        assert_eq!(manager.resolve("hud/hearts/*/fill").unwrap(), [
+            "hud/hearts/heart/fill",
+            "hud/hearts/heart[2]/fill",
+            "hud/hearts/heart[3]/fill",
+        ]);
+        assert_eq!(manager.resolve("hud/*").unwrap(), ["hud/hearts", "hud/label"]);
+
+        let results = manager.apply_commands(vec![
+            UICommand::SetImageFill { element_path: "hud/hearts/*/fill".to_string(), fill_amount: 0.25 },
+            UICommand::HideElement { element_path: "hud/hearts/*".to_string() },
+        ]);
+        assert!(results.iter().all(Result::is_ok));
+        for heart in &manager.element("hud/hearts").unwrap().children {
+            assert_eq!(heart.ui_element.alpha, 0.0);
+            assert_eq!(heart.children[0].image.as_ref().unwrap().fill_amount, 0.25);
+        }
+
+        // One failing match fails the command; the others still apply
+        let results = manager.apply_commands(vec![UICommand::SetImageFill { element_path: "hud/*".to_string(), fill_amount: 1.0 }]);
+        assert!(!results[0].as_ref().unwrap_err().not_found);
+
+        // Reordering needs a single element; destroying takes them all
+        let results = manager.apply_commands(vec![
+            UICommand::SetSiblingIndex { element_path: "hud/hearts/*".to_string(), index: Some(0) },
+            UICommand::DestroyElement { element_path: "hud/hearts/*/fill".to_string() },
+        ]);
+        assert!(results[0].as_ref().unwrap_err().message.contains("matches 3 elements"));
+        assert!(results[1].is_ok());
+        assert!(manager.element("hud/hearts").unwrap().children.iter().all(|heart| heart.children.is_empty()));
+    }
+
+    #[test]
+    fn test_index_selector_picks_nth_sibling_with_name() {
+        use script::UICommand;
+
+        let mut manager = hearts_manager();
+        assert_eq!(manager.resolve("hud/hearts/heart[2]").unwrap(), ["hud/hearts/heart[2]"]);
+        assert_eq!(manager.resolve("hud/hearts/heart").unwrap(), ["hud/hearts/heart"]);
+        assert!(manager.resolve("hud/hearts/heart[4]").unwrap().is_empty());
+
+        let results = manager.apply_commands(vec![
+            UICommand::SetColor { element_path: "hud/hearts/heart[2]".to_string(), r: 1.0, g: 0.0, b: 0.0, a: 1.0 },
+            UICommand::SetImageFill { element_path: "hud/heart[3]/fill".to_string(), fill_amount: 0.0 },
+        ]);
+        assert!(results.iter().all(Result::is_ok));
+        let hearts = &manager.element("hud/hearts").unwrap().children;
+        assert_ne!(hearts[0].ui_element.color, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(hearts[1].ui_element.color, [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(hearts[2].children[0].image.as_ref().unwrap().fill_amount, 0.0);
+        assert_eq!(hearts[0].children[0].image.as_ref().unwrap().fill_amount, 1.0);
+    }
+
+    #[test]
+    fn test_scoped_path_starts_at_instance_root() {
+        use script::UICommand;
+
+        let mut manager = hearts_manager();
+        assert_eq!(manager.resolve("@hud/hearts/heart/fill").unwrap(), ["hud/hearts/heart/fill"]);
+        // Plain paths may start anywhere in the instance; scoped ones may not
+        assert_eq!(manager.resolve("hud/heart/fill").unwrap(), ["hud/hearts/heart/fill"]);
+        assert!(manager.resolve("@hud/heart/fill").unwrap().is_empty());
+
+        let results = manager.apply_commands(vec![
+            UICommand::SetText { element_path: "@hud/label".to_string(), text: "3 lives".to_string() },
+            UICommand::SetText { element_path: "@menu/label".to_string(), text: "".to_string() },
+        ]);
+        assert!(results[0].is_ok());
+        assert_eq!(manager.get_ui_data("hud/label"), Some(&"3 lives".to_string()));
+        assert_eq!(results[1].as_ref().unwrap_err().message, "UI instance not found: menu");
+    }
+
+    #[test]
+    fn test_malformed_path_is_reported_not_treated_as_name() {
+        use script::UICommand;
+
+        let mut manager = hearts_manager();
+        let results = manager.apply_commands(vec![
+            UICommand::ShowElement { element_path: "hud/hearts/heart[two]".to_string() },
+            UICommand::SetToggle { element_path: "hud//label".to_string(), is_on: true },
+        ]);
+        for result in &results {
+            let error = result.as_ref().unwrap_err();
+            assert!(!error.not_found);
+            assert!(error.message.starts_with("Invalid path"), "{}", error.message);
+        }
+        assert!(manager.take_warnings().is_empty());
+    }
+
+    #[test]
+    fn test_canvas_scaler_keeps_centered_button_proportional() {
+        let mut scaler = ui::CanvasScaler::scale_with_screen_size(1280.0, 720.0);
+        scaler.set_match_width_or_height(0.5);
+        let mut root = stretched("root");
+        root.children.push(UIPrefabElement::new("button", ui::RectTransform {
+            size_delta: ui::Vec2::new(200.0, 100.0),
+            ..Default::default()
+        }));
+        let prefab = UIPrefab { name: "menu".to_string(), root, canvas_scaler: Some(scaler) };
+
+        for (width, height) in [(1280.0, 720.0), (1920.0, 1080.0), (800.0, 600.0)] {
+            let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(width, height));
+            let mut rects = Vec::new();
+            // Doubling the window scale factor (HiDPI) doesn't change the layout in points
+            for pixels_per_point in [1.0, 2.0] {
+                let scale = UIManager::canvas_scale(&prefab, screen, pixels_per_point);
+                let root_rect = UIManager::calculate_rect(screen, &prefab.root.rect_transform, scale);
+                rects.push(UIManager::calculate_rect(root_rect, &prefab.root.children[0].rect_transform, scale));
+            }
+            let button = rects[0];
+            assert!((button.min - rects[1].min).length() < 1e-3 && (button.size() - rects[1].size()).length() < 1e-3);
+            assert!((button.center() - screen.center()).length() < 1e-3);
+            assert!((button.width() / button.height() - 2.0).abs() < 1e-4);
+            if width / height == 16.0 / 9.0 {
+                assert!((button.width() / width - 200.0 / 1280.0).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_preview_is_the_game_layout_shrunk() {
+        let mut root = stretched("root");
+        root.children.push(UIPrefabElement::new("button", ui::RectTransform {
+            size_delta: ui::Vec2::new(200.0, 100.0),
+            ..Default::default()
+        }));
+        root.children.push(UIPrefabElement::new("corner", ui::RectTransform {
+            anchor_min: ui::Vec2::ONE,
+            anchor_max: ui::Vec2::ONE,
+            pivot: ui::Vec2::ONE,
+            size_delta: ui::Vec2::new(50.0, 50.0),
+            ..Default::default()
+        }));
+        let scaler = ui::CanvasScaler::scale_with_screen_size(1280.0, 720.0);
+        let prefab = UIPrefab { name: "menu".to_string(), root, canvas_scaler: Some(scaler) };
+        let manager = UIManager::new();
+
+        for (width, height) in [(1280, 720), (800, 600), (390, 844)] {
+            let size = egui::vec2(width as f32, height as f32);
+            let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, size);
+            let preview = egui::Rect::from_min_size(egui::pos2(10.0, 20.0), size * 0.5);
+            let (_, elements) = manager.preview_layout(&prefab, (width, height), preview);
+
+            let scale = UIManager::canvas_scale(&prefab, screen, 1.0);
+            let root_rect = UIManager::calculate_rect(screen, &prefab.root.rect_transform, scale);
+            for child in &prefab.root.children {
+                let game = UIManager::calculate_rect(root_rect, &child.rect_transform, scale);
+                let shown = elements.iter().find(|e| e.name == child.name).unwrap();
+                assert_eq!(shown.path, format!("menu/{}", child.name));
+                assert!((shown.rect.min - (preview.min + game.min.to_vec2() * 0.5)).length() < 1e-3);
+                assert!((shown.rect.size() - game.size() * 0.5).length() < 1e-3);
+                assert!((shown.parent_rect.size() - preview.size()).length() < 1e-3);
+            }
+            let corner = elements.iter().find(|e| e.name == "corner").unwrap();
+            assert!((corner.rect.right_top() - preview.right_top()).length() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_set_as_last_sibling_reorders_layout() {
+        let mut list = stretched("list");
+        list.vertical_layout = Some(ui::VerticalLayoutGroup { child_force_expand_height: false, ..Default::default() });
+        for name in ["a", "b", "c"] {
+            list.children.push(row(name));
+        }
+        let mut root = stretched("root");
+        root.children.push(list);
+
+        let mut manager = UIManager::new();
+        manager.active_uis.insert("menu".to_string(), UIPrefab { name: "menu".to_string(), root, canvas_scaler: None });
+        manager.screen_rect = Some(egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(300.0, 200.0)));
+
+        manager.set_as_last_sibling("menu/list/a").unwrap();
+        assert_eq!(manager.get_sibling_index("menu/list/a"), Some(2));
+        manager.set_sibling_index("menu/list/c", Some(0)).unwrap();
+        assert!(manager.set_as_last_sibling("menu").is_err());
+
+        manager.apply_pending_layouts();
+        let list = &manager.active_uis["menu"].root.children[0];
+        let order: Vec<_> = list.children.iter().map(|child| child.name.as_str()).collect();
+        assert_eq!(order, ["c", "b", "a"]);
+        assert_eq!(list.children[2].rect_transform.anchored_position, ui::Vec2::new(0.0, -40.0));
+    }
+
+    #[test]
+    fn test_rotation_and_scale_apply_around_pivot() {
+        let screen = Placement::screen(egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(400.0, 400.0)));
+        let panel = ui::RectTransform {
+            size_delta: ui::Vec2::new(200.0, 100.0),
+            rotation: 90.0,
+            ..Default::default()
+        };
+        let placed = screen.place(&panel, 1.0);
+
+        // Rotating around the (centered) pivot keeps the center and swaps the extents
+        let bounds = placed.bounds(placed.rect);
+        assert!((bounds.center() - egui::pos2(200.0, 200.0)).length() < 1e-3);
+        assert!((bounds.size() - egui::vec2(100.0, 200.0)).length() < 1e-3);
+        // Counter-clockwise on screen: the right edge now points up
+        assert!((placed.point(placed.rect.right_center()) - egui::pos2(200.0, 100.0)).length() < 1e-3);
+
+        // Children inherit the rotation, and scale grows them around their own pivot
+        let child = ui::RectTransform {
+            anchor_min: ui::Vec2::new(1.0, 0.5),
+            anchor_max: ui::Vec2::new(1.0, 0.5),
+            size_delta: ui::Vec2::new(20.0, 20.0),
+            scale: ui::Vec2::splat(2.0),
+            ..Default::default()
+        };
+        let child = placed.place(&child, 1.0);
+        let bounds = child.bounds(child.rect);
+        assert!((bounds.center() - egui::pos2(200.0, 100.0)).length() < 1e-3);
+        assert!((bounds.size() - egui::vec2(40.0, 40.0)).length() < 1e-3);
+        assert!((child.scale() - 2.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_safe_area_insets_only_enabled_sides() {
+        let mut hud = stretched("hud");
+        hud.safe_area = Some(ui::UISafeArea { bottom: false, ..Default::default() });
+        let mut root = stretched("root");
+        root.children.push(hud);
+        root.children.push(stretched("background"));
+
+        let mut manager = UIManager::new();
+        manager.active_uis.insert("game".to_string(), UIPrefab { name: "game".to_string(), root, canvas_scaler: None });
+        // 10% notch on the left, 5% status bar at the top, 5% home indicator at the bottom
+        manager.set_safe_area([0.1, 0.05, 0.9, 0.9]);
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(400.0, 200.0));
+
+        let mut rects = HashMap::new();
+        manager.visit_placed(screen, &mut |path, _, placement| {
+            rects.insert(path.to_string(), placement.rect);
+        });
+        assert_eq!(rects["game/background"], screen);
+        let hud = rects["game/hud"];
+        assert!((hud.min - egui::pos2(40.0, 10.0)).length() < 1e-3);
+        assert!((hud.max - egui::pos2(400.0, 200.0)).length() < 1e-3);
+    }
+
+    #[test]
+    fn test_tooltip_shows_after_delay_and_stays_on_screen() {
+        let mut button = row("save");
+        button.rect_transform.anchor_min = ui::Vec2::new(1.0, 0.0);
+        button.rect_transform.anchor_max = ui::Vec2::new(1.0, 0.0);
+        button.rect_transform.pivot = ui::Vec2::new(1.0, 0.0);
+        button.rect_transform.size_delta = ui::Vec2::new(100.0, 40.0);
+        let mut root = stretched("root");
+        root.children.push(button);
+
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(400.0, 300.0));
+        let mut manager = UIManager::new();
+        manager.active_uis.insert("hud".to_string(), UIPrefab { name: "hud".to_string(), root, canvas_scaler: None });
+        manager.set_tooltip("hud/save", "Save the game".to_string()).unwrap();
+        assert!(manager.set_tooltip("hud/missing", "x".to_string()).is_err());
+
+        // Pointer resting on the button in the bottom-right corner
+        let ctx = egui::Context::default();
+        let mut frame = |time: f64, press: bool| {
+            let mut events = vec![egui::Event::PointerMoved(egui::pos2(390.0, 290.0))];
+            if press {
+                events.push(egui::Event::PointerButton {
+                    pos: egui::pos2(390.0, 290.0),
+                    button: egui::PointerButton::Primary,
+                    pressed: true,
+                    modifiers: Default::default(),
+                });
+            }
+            let input = egui::RawInput { time: Some(time), events, screen_rect: Some(screen), ..Default::default() };
+            let _ = ctx.run(input, |ctx| manager.update_tooltip(ctx, screen));
+            manager.tooltip_rect()
+        };
+        assert_eq!(frame(0.0, false), None);
+        assert_eq!(frame(0.3, false), None);
+        let rect = frame(0.6, false).expect("tooltip after the delay");
+        // Flipped above-left of the cursor to stay inside the screen
+        assert!(screen.contains_rect(rect));
+        assert!(rect.max.x <= 390.0 && rect.max.y <= 290.0);
+
+        // A press hides it while the pointer stays on the same element
+        assert_eq!(frame(0.7, true), None);
+        assert_eq!(frame(2.0, false), None);
+    }
+
+    #[test]
+    fn test_set_toggle_enforces_group() {
+        let mut quality = stretched("quality");
+        quality.toggle_group = Some(ui::UIToggleGroup { id: "quality".to_string(), ..Default::default() });
+        for (name, is_on) in [("low", false), ("medium", true), ("high", false)] {
+            let mut option = row(name);
+            option.toggle = Some(ui::UIToggle {
+                is_on,
+                group: Some(ui::ToggleGroupRef::Id("quality".to_string())),
+                ..Default::default()
+            });
+            quality.children.push(option);
+        }
+        let mut root = stretched("root");
+        root.children.push(quality);
+
+        let mut manager = UIManager::new();
+        manager.active_uis.insert("settings".to_string(), UIPrefab { name: "settings".to_string(), root, canvas_scaler: None });
+        let states = |manager: &UIManager| -> Vec<bool> {
+            manager.active_uis["settings"].root.children[0].children.iter()
+                .map(|option| option.toggle.as_ref().unwrap().is_on)
+                .collect()
+        };
+
+        manager.set_toggle("settings/quality/high", true).unwrap();
+        assert_eq!(states(&manager), [false, false, true]);
+        // The active option can't be switched off: one always stays selected
+        manager.set_toggle("settings/high", false).unwrap();
+        assert_eq!(states(&manager), [false, false, true]);
+        assert!(manager.set_toggle("settings/quality", true).is_err());
+    }
+
+    #[test]
+    fn test_typing_fires_value_changed_once_then_submit() {
+        let mut name = stretched("name");
+        name.input_field = Some(ui::UIInputField { character_limit: 3, ..Default::default() });
+        let mut root = stretched("root");
+        root.children.push(name);
+
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(400.0, 300.0));
+        let mut manager = UIManager::new();
+        manager.active_uis.insert("form".to_string(), UIPrefab { name: "form".to_string(), root, canvas_scaler: None });
+        manager.add_event_listener("form/name", script::UIEventKind::ValueChanged, 7, "on_changed".to_string(), script::DisabledPolicy::Drop).unwrap();
+        manager.add_event_listener("form/name", script::UIEventKind::Submit, 7, "on_submit".to_string(), script::DisabledPolicy::Drop).unwrap();
+        assert!(manager.add_event_listener("form/missing", script::UIEventKind::Submit, 7, "x".to_string(), script::DisabledPolicy::Drop).is_err());
+
+        let ctx = egui::Context::default();
+        let mut frame = |events: Vec<egui::Event>| {
+            let input = egui::RawInput { events, screen_rect: Some(screen), ..Default::default() };
+            let _ = ctx.run(input, |ctx| manager.update_input_fields(ctx, screen));
+            manager.take_script_events()
+        };
+        let enter = egui::Event::Key {
+            key: egui::Key::Enter,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Default::default(),
+        };
+
+        // Click to focus, then type past the character limit and press Enter in one frame
+        frame(vec![egui::Event::PointerButton {
+            pos: egui::pos2(20.0, 20.0),
+            button: egui::PointerButton::Primary,
+            pressed: true,
+            modifiers: Default::default(),
+        }]);
+        let fired = frame(vec![egui::Event::Text("ab".to_string()), egui::Event::Text("cd".to_string()), enter]);
+        let fired: Vec<_> = fired.iter().map(|e| (e.callback.as_str(), e.value.as_str(), e.owner)).collect();
+        assert_eq!(fired, [("on_changed", "abc", 7), ("on_submit", "abc", 7)]);
+
+        // Submitting a single-line field drops focus, so more typing is ignored
+        assert!(frame(vec![egui::Event::Text("z".to_string())]).is_empty());
+        assert_eq!(manager.input_field_snapshots()["form/name"].text, "abc");
+    }
+
+    #[test]
+    fn test_prefab_bindings_run_on_click_and_value_change_until_deactivated() {
+        let third = |name: &str, index: f32| {
+            let mut element = stretched(name);
+            element.rect_transform.anchor_min = ui::Vec2::new(index / 3.0, 0.0);
+            element.rect_transform.anchor_max = ui::Vec2::new((index + 1.0) / 3.0, 1.0);
+            element
+        };
+        let action = |json: &str| Some(serde_json::from_str::<ui::UIEventBinding>(json).unwrap());
+        let mut start = third("start", 0.0);
+        start.button = Some(ui::UIButton { on_click: action(r#"{ "action": "lua", "function": "OnStartClicked" }"#), ..Default::default() });
+        let mut open_options = third("open_options", 1.0);
+        open_options.button = Some(ui::UIButton { on_click: action(r#"{ "action": "show", "target": "menus/options" }"#), ..Default::default() });
+        let mut volume = third("volume", 2.0);
+        volume.slider = Some(ui::UISlider { on_value_changed: action(r#""OnVolume""#), ..Default::default() });
+        let mut options = stretched("options");
+        options.ui_element.alpha = 0.0;
+        let mut menus = stretched("menus");
+        menus.children.push(options);
+        let mut root = stretched("root");
+        root.children.extend([start, open_options, volume, menus]);
+        let prefab = UIPrefab { name: "main_menu".to_string(), root, canvas_scaler: None };
+        assert!(UIManager::validate_bindings(&prefab).is_empty());
+
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(300.0, 100.0));
+        let mut manager = UIManager::new();
+        manager.loaded_prefabs.insert("menu.uiprefab".to_string(), prefab);
+        manager.activate_prefab_for("menu.uiprefab", "menu", 7).unwrap();
+
+        let ctx = egui::Context::default();
+        let click = |manager: &mut UIManager, x: f32| {
+            let pos = egui::pos2(x, 50.0);
+            let button = |pressed| egui::Event::PointerButton { pos, button: egui::PointerButton::Primary, pressed, modifiers: Default::default() };
+            let input = egui::RawInput { events: vec![egui::Event::PointerMoved(pos), button(true)], screen_rect: Some(screen), ..Default::default() };
+            let _ = ctx.run(input, |ctx| manager.update_controls(ctx, screen));
+            let input = egui::RawInput { events: vec![button(false)], screen_rect: Some(screen), ..Default::default() };
+            let _ = ctx.run(input, |ctx| manager.update_controls(ctx, screen));
+            manager.take_script_events()
+        };
+
+        // Lua bindings run in the activating script's entity
+        let fired = click(&mut manager, 50.0);
+        let fired: Vec<_> = fired.iter().map(|e| (e.owner, e.callback.as_str(), e.element_path.as_str(), e.value.as_str())).collect();
+        assert_eq!(fired, [(7, "OnStartClicked", "menu/start", "")]);
+
+        // Engine actions run right away
+        assert!(click(&mut manager, 150.0).is_empty());
+        assert_eq!(manager.element("menu/menus/options").unwrap().ui_element.alpha, 1.0);
+
+        let fired = click(&mut manager, 250.0);
+        let fired: Vec<_> = fired.iter().map(|e| (e.callback.as_str(), e.value.as_str())).collect();
+        assert_eq!(fired, [("OnVolume", "0.5")]);
+        assert_eq!(manager.element("menu/volume").unwrap().slider.as_ref().unwrap().value, 0.5);
+
+        // Deactivating unregisters the bindings
+        manager.deactivate_prefab("menu");
+        assert!(manager.bindings.is_empty());
+        manager.activate_prefab("menu.uiprefab", "menu").unwrap();
+        assert!(click(&mut manager, 50.0).is_empty());
+    }
+
+    #[test]
+    fn test_input_capture_skips_hidden_and_pass_through_elements() {
+        let half = |name: &str, x: f32| {
+            let mut element = stretched(name);
+            element.rect_transform.anchor_min = ui::Vec2::new(x, 0.0);
+            element.rect_transform.anchor_max = ui::Vec2::new(x + 0.5, 1.0);
+            element
+        };
+        let mut fire = half("fire", 0.0);
+        fire.button = Some(ui::UIButton::default());
+        let mut name = half("name", 0.5);
+        name.input_field = Some(ui::UIInputField::default());
+        let mut minimap = stretched("minimap");
+        minimap.button = Some(ui::UIButton::default());
+        minimap.ui_element.pass_through = true;
+        let mut root = stretched("root");
+        root.children.extend([fire, name, minimap]);
+
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(400.0, 300.0));
+        let mut manager = UIManager::new();
+        manager.active_uis.insert("hud".to_string(), UIPrefab { name: "hud".to_string(), root, canvas_scaler: None });
+        manager.screen_rect = Some(screen);
+        manager.pixels_per_point = 2.0;
+
+        // Physical pixels, like the mouse position
+        let capture = manager.input_capture();
+        assert_eq!(capture.blockers, [
+            input::ScreenRect::new(glam::Vec2::new(0.0, 0.0), glam::Vec2::new(400.0, 600.0)),
+            input::ScreenRect::new(glam::Vec2::new(400.0, 0.0), glam::Vec2::new(800.0, 600.0)),
+        ]);
+        assert!(!capture.keyboard);
+
+        // Focusing the field takes the keyboard; hiding it gives it back
+        let ctx = egui::Context::default();
+        let press = egui::Event::PointerButton {
+            pos: egui::pos2(300.0, 100.0),
+            button: egui::PointerButton::Primary,
+            pressed: true,
+            modifiers: Default::default(),
+        };
+        let input = egui::RawInput { events: vec![press], screen_rect: Some(screen), ..Default::default() };
+        let _ = ctx.run(input, |ctx| manager.update_input_fields(ctx, screen));
+        assert!(manager.input_capture().keyboard);
+
+        manager.hide_element("hud", "name").unwrap();
+        let capture = manager.input_capture();
+        assert!(!capture.keyboard);
+        assert_eq!(capture.blockers.len(), 1);
+    }
+
+    #[test]
+    fn test_binding_validation_reports_missing_targets() {
+        let mut button = stretched("play");
+        button.button = Some(ui::UIButton {
+            on_click: Some(ui::UIEventBinding::Action(ui::UIAction::Show { target: "Menus/Optoins".to_string() })),
+            ..Default::default()
+        });
+        let mut toggle = stretched("mute");
+        toggle.toggle = Some(ui::UIToggle {
+            on_value_changed: Some(ui::UIEventBinding::Action(ui::UIAction::SetCanvas { target: "ui/missing.uiprefab".to_string() })),
+            ..Default::default()
+        });
+        let mut root = stretched("root");
+        root.children.extend([button, toggle]);
+        let prefab = UIPrefab { name: "title".to_string(), root, canvas_scaler: None };
+
+        let warnings = UIManager::validate_bindings(&prefab);
+        assert_eq!(warnings, [
+            "Click binding on 'title/play': target element 'Menus/Optoins' not found",
+            "ValueChanged binding on 'title/mute': prefab file 'ui/missing.uiprefab' not found",
+        ]);
+    }
+
+    #[test]
+    fn test_drag_item_onto_slot_child_reaches_slot_listener() {
+        let half = |name: &str, x: f32| {
+            let mut slot = stretched(name);
+            slot.rect_transform.anchor_min = ui::Vec2::new(x, 0.0);
+            slot.rect_transform.anchor_max = ui::Vec2::new(x + 0.5, 1.0);
+            slot
+        };
+        let mut slot_a = half("slot_a", 0.0);
+        let mut item = stretched("item");
+        item.ui_element.draggable = true;
+        slot_a.children.push(item);
+        let mut slot_b = half("slot_b", 0.5);
+        slot_b.children.push(stretched("icon"));
+        let mut root = stretched("root");
+        root.children.extend([slot_a, slot_b]);
+
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(400.0, 300.0));
+        let mut manager = UIManager::new();
+        manager.active_uis.insert("inv".to_string(), UIPrefab { name: "inv".to_string(), root, canvas_scaler: None });
+        manager.add_event_listener("inv/slot_b", script::UIEventKind::Drop, 3, "on_item_dropped".to_string(), script::DisabledPolicy::Drop).unwrap();
+
+        let ctx = egui::Context::default();
+        let mut frame = |events: Vec<egui::Event>| {
+            let input = egui::RawInput { events, screen_rect: Some(screen), ..Default::default() };
+            let _ = ctx.run(input, |ctx| manager.update_drag_drop(ctx, screen));
+            (manager.drag_drop.active_drag(), manager.take_script_events())
+        };
+        let button = |x: f32, pressed: bool| egui::Event::PointerButton {
+            pos: egui::pos2(x, 150.0),
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        };
+        let moved = |x: f32| egui::Event::PointerMoved(egui::pos2(x, 150.0));
+
+        frame(vec![moved(100.0), button(100.0, true)]);
+        let (drag, _) = frame(vec![moved(300.0)]);
+        assert_eq!(drag.map(|drag| drag.offset()), Some(glam::Vec2::new(200.0, 0.0)));
+
+        let (drag, fired) = frame(vec![button(300.0, false)]);
+        assert_eq!(drag, None);
+        let fired: Vec<_> = fired.iter().map(|e| (e.callback.as_str(), e.element_path.as_str(), e.value.as_str())).collect();
+        assert_eq!(fired, [("on_item_dropped", "inv/slot_b", "inv/slot_a/item")]);
+
+        // Escape cancels: releasing over the slot afterwards drops nothing
+        frame(vec![moved(100.0), button(100.0, true)]);
+        frame(vec![moved(300.0)]);
+        frame(vec![egui::Event::Key {
+            key: egui::Key::Escape,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Default::default(),
+        }]);
+        let (drag, fired) = frame(vec![button(300.0, false)]);
+        assert_eq!(drag, None);
+        assert!(fired.is_empty());
+    }
+
+    #[test]
+    fn test_localized_texts_follow_language_and_params() {
+        let mut title = row("title");
+        title.text.as_mut().unwrap().localization_key = Some("menu.title".to_string());
+        let mut coins = row("coins");
+        coins.text.as_mut().unwrap().localization_key = Some("coins".to_string());
+        let mut root = stretched("root");
+        root.children.extend([title, coins, row("plain")]);
+
+        let mut localization = ui::Localization::new();
+        localization.add_csv("key,en,th\nmenu.title,Adventure,การผจญภัย\ncoins,Collected {count}/{total},\n").unwrap();
+        let mut manager = UIManager::new();
+        manager.set_localization(localization);
+        manager.active_uis.insert("hud".to_string(), UIPrefab { name: "hud".to_string(), root, canvas_scaler: None });
+        let texts = |manager: &UIManager| -> Vec<String> {
+            manager.active_uis["hud"].root.children.iter()
+                .map(|child| child.text.as_ref().unwrap().text.clone())
+                .collect()
+        };
+
+        let params = HashMap::from([("count".to_string(), "3".to_string()), ("total".to_string(), "10".to_string())]);
+        manager.set_text_params("hud/coins", params).unwrap();
+        assert!(manager.set_text_params("hud/plain", HashMap::new()).is_err());
+        manager.apply_pending_layouts();
+        assert_eq!(texts(&manager), ["Adventure", "Collected 3/10", ""]);
+
+        manager.set_language("th").unwrap();
+        manager.apply_pending_layouts();
+        // "coins" has no Thai string: English, reported once
+        assert_eq!(texts(&manager), ["การผจญภัย", "Collected 3/10", ""]);
+        assert!(matches!(manager.take_warnings().as_slice(), [warning] if warning.contains("coins")));
+        assert!(manager.take_warnings().is_empty());
+        assert!(manager.set_language("de").is_err());
+    }
+
+    #[test]
+    fn test_text_fonts_load_from_assets_with_default_fallback() {
+        let assets = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(assets.path().join("fonts")).unwrap();
+        std::fs::write(assets.path().join("fonts/Title.ttf"), epaint_default_fonts::HACK_REGULAR).unwrap();
+        std::fs::write(assets.path().join("fonts/Broken.ttf"), b"not a font").unwrap();
+
+        let mut root = stretched("root");
+        for (name, font) in [("title", "fonts/Title.ttf"), ("broken", "fonts/Broken.ttf"), ("missing", "fonts/Gone.otf")] {
+            let mut child = row(name);
+            child.text.as_mut().unwrap().font = font.to_string();
+            root.children.push(child);
+        }
+        let mut manager = UIManager::new();
+        manager.set_font_base_path(assets.path());
+        manager.active_uis.insert("hud".to_string(), UIPrefab { name: "hud".to_string(), root, canvas_scaler: None });
+        manager.layout_dirty.insert("hud".to_string());
+        manager.apply_pending_layouts();
+
+        assert!(manager.fonts.contains_key("fonts/Title.ttf"));
+        let warnings = manager.take_warnings();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().any(|warning| warning.contains("fonts/Broken.ttf")));
+        assert!(warnings.iter().any(|warning| warning.contains("fonts/Gone.otf")));
+        manager.layout_dirty.insert("hud".to_string());
+        manager.apply_pending_layouts();
+        assert!(manager.take_warnings().is_empty());
+
+        // The family is usable once egui has applied the new fonts (next frame)
+        let ctx = egui::Context::default();
+        let _ = ctx.run(Default::default(), |ctx| manager.install_fonts(ctx));
+        assert_eq!(manager.font_id("fonts/Title.ttf", 20.0), egui::FontId::proportional(20.0));
+        let _ = ctx.run(Default::default(), |ctx| manager.install_fonts(ctx));
+        assert_eq!(manager.font_id("fonts/Title.ttf", 20.0).family, egui::FontFamily::Name("fonts/Title.ttf".into()));
+        assert_eq!(manager.font_id("fonts/Gone.otf", 20.0), egui::FontId::proportional(20.0));
+    }
+
+    #[test]
+    fn test_inspector_edits_apply_back_to_prefab_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("hud.uiprefab").to_string_lossy().to_string();
+        let mut root = stretched("root");
+        root.children.push(row("score"));
+        let prefab = UIPrefab { name: "hud".to_string(), root, canvas_scaler: None };
+        std::fs::write(&path, serde_json::to_string(&prefab).unwrap()).unwrap();
+
+        let mut manager = UIManager::new();
+        manager.load_prefab(&path).unwrap();
+        manager.activate_prefab(&path, "hud").unwrap();
+        assert_eq!(manager.instance_source("hud"), Some(path.as_str()));
+
+        let mut score = manager.element("hud/score").unwrap().clone();
+        score.rect_transform.anchored_position = ui::Vec2::new(12.0, -4.0);
+        score.text.as_mut().unwrap().font_size = 30.0;
+        manager.set_element("hud/score", &score).unwrap();
+        manager.add_element("hud", row("runtime_only"), None, None).unwrap();
+
+        let paths = vec!["hud/score".to_string(), "hud/runtime_only".to_string()];
+        let (written_path, written) = manager.apply_instance_to_prefab("hud", &paths).unwrap();
+        assert_eq!((written_path.as_str(), written), (path.as_str(), 1));
+
+        let saved: UIPrefab = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(saved.root.children.len(), 1, "runtime-created elements stay out of the asset");
+        let saved_score = &saved.root.children[0];
+        assert_eq!(saved_score.rect_transform.anchored_position, ui::Vec2::new(12.0, -4.0));
+        assert_eq!(saved_score.text.as_ref().unwrap().font_size, 30.0);
+
+        // New instances start from the updated asset
+        manager.activate_prefab(&path, "hud2").unwrap();
+        assert_eq!(manager.element("hud2/score").unwrap().rect_transform.anchored_position, ui::Vec2::new(12.0, -4.0));
+    }
+
+    #[test]
+    fn test_modal_blocks_ui_below_and_reports_its_result() {
+        let action = |function: &str| Some(serde_json::from_str::<ui::UIEventBinding>(&format!(r#""{}""#, function)).unwrap());
+        let mut play = stretched("play");
+        play.rect_transform.anchor_max = ui::Vec2::new(1.0 / 3.0, 1.0);
+        play.button = Some(ui::UIButton { on_click: action("OnPlay"), ..Default::default() });
+        let mut hud = stretched("root");
+        hud.children.push(play);
+
+        // A 100x50 dialog in the middle of the screen
+        let mut yes = stretched("yes");
+        yes.button = Some(ui::UIButton { on_click: action("OnYes"), ..Default::default() });
+        let mut dialog = UIPrefabElement::new("dialog", ui::RectTransform { size_delta: ui::Vec2::new(100.0, 50.0), ..Default::default() });
+        dialog.children.push(yes);
+        let mut confirm = stretched("root");
+        confirm.children.push(dialog);
+
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(300.0, 100.0));
+        let mut manager = UIManager::new();
+        manager.screen_rect = Some(screen);
+        manager.loaded_prefabs.insert("hud.uiprefab".to_string(), UIPrefab { name: "hud".to_string(), root: hud, canvas_scaler: None });
+        manager.loaded_prefabs.insert("confirm.uiprefab".to_string(), UIPrefab { name: "confirm".to_string(), root: confirm, canvas_scaler: None });
+        manager.activate_prefab_for("hud.uiprefab", "hud", 7).unwrap();
+        manager.set_focused_element(Some("hud/play")).unwrap();
+
+        let options = ui::ModalOptions {
+            close_on_background_click: true,
+            on_closed: Some("OnConfirmClosed".to_string()),
+            ..Default::default()
+        };
+        manager.show_modal("confirm.uiprefab", "confirm", 7, options.clone()).unwrap();
+        assert!(manager.show_modal("confirm.uiprefab", "confirm", 7, options).is_err());
+        assert_eq!(manager.top_modal(), Some("confirm"));
+        assert_eq!(manager.focused_element(), Some("confirm/dialog/yes"));
+        assert!(manager.set_focused_element(Some("hud/play")).is_err(), "focus is trapped in the modal");
+        let names: Vec<&str> = manager.instances_in_draw_order().iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["hud", "confirm"]);
+        let capture = manager.input_capture();
+        assert!(capture.keyboard);
+        assert_eq!(capture.blockers[0], input::ScreenRect::new(glam::Vec2::ZERO, glam::Vec2::new(300.0, 100.0)));
+
+        let ctx = egui::Context::default();
+        let click = |manager: &mut UIManager, x: f32| {
+            let pos = egui::pos2(x, 50.0);
+            let button = |pressed| egui::Event::PointerButton { pos, button: egui::PointerButton::Primary, pressed, modifiers: Default::default() };
+            for events in [vec![egui::Event::PointerMoved(pos), button(true)], vec![button(false)]] {
+                let input = egui::RawInput { events, screen_rect: Some(screen), ..Default::default() };
+                let _ = ctx.run(input, |ctx| {
+                    if !manager.update_modal_input(ctx, screen) {
+                        manager.update_controls(ctx, screen);
+                    }
+                });
+            }
+            let fired = manager.take_script_events();
+            fired.iter().map(|e| (e.callback.clone(), e.value.clone())).collect::<Vec<_>>()
+        };
+
+        assert_eq!(click(&mut manager, 150.0), [("OnYes".to_string(), String::new())]);
+        // A click on the dim panel closes the modal and stops there
+        assert_eq!(click(&mut manager, 50.0), [("OnConfirmClosed".to_string(), "cancel".to_string())]);
+        assert_eq!(manager.top_modal(), None);
+        assert_eq!(manager.focused_element(), Some("hud/play"));
+
+        // Fading out: still drawn, no longer clickable
+        assert!(manager.instance("confirm").is_some());
+        assert!(click(&mut manager, 150.0).is_empty());
+        assert_eq!(click(&mut manager, 50.0), [("OnPlay".to_string(), String::new())]);
+        assert_eq!(manager.popups.update(1.0), ["confirm"]);
+
+        // close_modal's result reaches the script that opened it
+        manager.show_modal("confirm.uiprefab", "confirm", 7, ui::ModalOptions { on_closed: Some("OnConfirmClosed".to_string()), ..Default::default() }).unwrap();
+        manager.close_modal("confirm").unwrap();
+        let fired = manager.take_script_events();
+        assert_eq!((fired[0].owner, fired[0].element_path.as_str(), fired[0].value.as_str()), (7, "confirm", "confirm"));
+        assert!(manager.close_modal("confirm").is_err());
+    }
+
+    #[test]
+    fn test_canvas_group_fades_and_disables_its_subtree() {
+        let action = |function: &str| Some(serde_json::from_str::<ui::UIEventBinding>(&format!(r#""{}""#, function)).unwrap());
+        let mut play = stretched("play");
+        play.button = Some(ui::UIButton { on_click: action("OnPlay"), ..Default::default() });
+        let mut panel = stretched("panel");
+        panel.canvas_group = Some(ui::UICanvasGroup { alpha: 0.5, ..Default::default() });
+        panel.children.push(play);
+        let mut root = stretched("root");
+        root.children.push(panel);
+
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(300.0, 100.0));
+        let mut manager = UIManager::new();
+        manager.screen_rect = Some(screen);
+        manager.loaded_prefabs.insert("menu.uiprefab".to_string(), UIPrefab { name: "menu".to_string(), root, canvas_scaler: None });
+        manager.activate_prefab_for("menu.uiprefab", "menu", 7).unwrap();
+
+        // Groups multiply down the tree
+        manager.set_group_alpha("menu", 0.5).unwrap();
+        let mut alphas = HashMap::new();
+        manager.visit_placed(screen, &mut |path, element, placement| {
+            alphas.insert(path.to_string(), placement.effective(&element.ui_element).alpha);
+        });
+        assert_eq!(alphas["menu/panel/play"], 0.25);
+
+        let ctx = egui::Context::default();
+        let click = |manager: &mut UIManager| {
+            let pos = egui::pos2(150.0, 50.0);
+            let button = |pressed| egui::Event::PointerButton { pos, button: egui::PointerButton::Primary, pressed, modifiers: Default::default() };
+            for events in [vec![egui::Event::PointerMoved(pos), button(true)], vec![button(false)]] {
+                let input = egui::RawInput { events, screen_rect: Some(screen), ..Default::default() };
+                let _ = ctx.run(input, |ctx| manager.update_controls(ctx, screen));
+            }
+            manager.take_script_events().into_iter().map(|e| e.callback).collect::<Vec<_>>()
+        };
+        assert_eq!(click(&mut manager), ["OnPlay"]);
+
+        // Faded out: nothing below takes input
+        manager.set_group_alpha("menu/panel", 0.0).unwrap();
+        assert!(click(&mut manager).is_empty());
+        assert!(manager.input_capture().blockers.is_empty());
+        assert_eq!(manager.first_control("menu"), None);
+
+        // fade_group tweens it back in and reports the end to the script
+        manager.fade_group("menu/panel", 1.0, 1.0, ui::EasingFunction::Linear, 7, Some("OnShown".to_string())).unwrap();
+        manager.update_group_fades(0.5);
+        assert_eq!(manager.element("menu/panel").unwrap().canvas_group.unwrap().alpha, 0.5);
+        assert!(manager.take_script_events().is_empty());
+        manager.update_group_fades(0.6);
+        assert_eq!(manager.element("menu/panel").unwrap().canvas_group.unwrap().alpha, 1.0);
+        assert!(manager.group_fades.is_empty());
+        let fired = manager.take_script_events();
+        assert_eq!((fired[0].owner, fired[0].callback.as_str(), fired[0].element_path.as_str()), (7, "OnShown", "menu/panel"));
+
+        // Not interactable: shown, but disabled
+        manager.set_group_interactable("menu/panel", false).unwrap();
+        assert!(click(&mut manager).is_empty());
+        assert_eq!(manager.first_control("menu"), None);
+        manager.update_control_visuals(screen, None, 0.0);
+        let visual = &manager.control_visuals["menu/panel/play"];
+        assert_eq!((visual.state, visual.tint()), (ui::InteractableState::Disabled, ui::UIButton::default().disabled_color));
+    }
+
+    fn controls_manager(root: UIPrefabElement) -> (UIManager, egui::Rect) {
+        let screen = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(300.0, 100.0));
+        let mut manager = UIManager::new();
+        manager.screen_rect = Some(screen);
+        manager.loaded_prefabs.insert("menu.uiprefab".to_string(), UIPrefab { name: "menu".to_string(), root, canvas_scaler: None });
+        manager.activate_prefab_for("menu.uiprefab", "menu", 7).unwrap();
+        (manager, screen)
+    }
+
+    #[test]
+    fn test_control_state_tints_fade_between_states() {
+        let hover = ui::Color::rgba(0.8, 0.8, 0.8, 1.0);
+        let mut ok = stretched("ok");
+        ok.button = Some(ui::UIButton { highlighted_color: hover, fade_duration: 0.2, ..Default::default() });
+        let mut root = stretched("root");
+        root.children.push(ok);
+        let (mut manager, screen) = controls_manager(root);
+
+        assert!(!manager.update_control_visuals(screen, None, 0.1));
+        assert_eq!(manager.control_visuals["menu/ok"].tint(), ui::Color::WHITE);
+
+        // Hovered: fades to the highlighted color over fade_duration
+        let inside = Some(egui::pos2(150.0, 50.0));
+        assert!(manager.update_control_visuals(screen, inside, 0.1));
+        let visual = &manager.control_visuals["menu/ok"];
+        assert_eq!(visual.state, ui::InteractableState::Hover);
+        assert!(visual.tint().r < 1.0 && visual.tint().r > hover.r, "{:?}", visual.tint());
+        assert!(!manager.update_control_visuals(screen, inside, 0.2));
+        assert_eq!(manager.control_visuals["menu/ok"].tint(), hover);
+
+        // UI.set_interactable(path, false): disabled look, not hovered, not focusable
+        let results = manager.apply_commands(vec![script::UICommand::SetInteractable {
+            element_path: "menu/ok".to_string(),
+            interactable: false,
+        }]);
+        assert!(results[0].is_ok());
+        manager.update_control_visuals(screen, inside, 1.0);
+        let visual = &manager.control_visuals["menu/ok"];
+        assert_eq!((visual.state, visual.tint()), (ui::InteractableState::Disabled, ui::UIButton::default().disabled_color));
+        assert_eq!(manager.first_control("menu"), None);
+    }
+
+    #[test]
+    fn test_event_log_and_inspect_point() {
+        let mut ok = stretched("ok");
+        ok.button = Some(ui::UIButton::default());
+        let mut label = stretched("label");
+        label.ui_element.raycast_target = false;
+        let mut root = stretched("root");
+        root.children.extend([ok, label]);
+        let (mut manager, screen) = controls_manager(root);
+        manager.add_event_listener("menu/ok", script::UIEventKind::Click, 7, "on_ok".to_string(), script::DisabledPolicy::Drop).unwrap();
+        manager.event_log_mut().set_enabled(true);
+
+        // The label on top lets the pointer through to the button, which hides the root
+        let hits: Vec<_> = manager.inspect_point(egui::pos2(150.0, 50.0)).into_iter().map(|hit| (hit.path, hit.skipped)).collect();
+        assert_eq!(hits, vec![
+            ("menu/label".to_string(), Some("not a raycast target".to_string())),
+            ("menu/ok".to_string(), None),
+            ("menu".to_string(), Some("blocked by 'menu/ok'".to_string())),
+        ]);
+        assert!(manager.inspect_point(egui::pos2(400.0, 50.0)).is_empty());
+
+        let ctx = egui::Context::default();
+        let pos = egui::pos2(150.0, 50.0);
+        let button = |pressed| egui::Event::PointerButton { pos, button: egui::PointerButton::Primary, pressed, modifiers: Default::default() };
+        for events in [vec![egui::Event::PointerMoved(pos), button(true)], vec![button(false)]] {
+            let input = egui::RawInput { events, screen_rect: Some(screen), ..Default::default() };
+            let _ = ctx.run(input, |ctx| {
+                manager.update_controls(ctx, screen);
+                manager.log_pointer_events(ctx, screen);
+            });
+        }
+        let logged = |event: &str| manager.event_log().entries().find(|entry| entry.event == event).cloned();
+        let down = logged("PointerDown").expect("pointer down logged");
+        assert_eq!((down.target.as_str(), down.consumed_by.as_deref()), ("menu/ok", Some("menu/ok")));
+        let click = logged("Click").expect("click logged");
+        assert_eq!((click.canvas.as_str(), click.consumed_by.as_deref(), click.detail.as_str()), ("menu", Some("menu/ok"), "on_ok"));
+
+        let callbacks = manager.element_callbacks();
+        assert_eq!(callbacks.len(), 1);
+        assert_eq!((callbacks[0].event.as_str(), callbacks[0].callback.as_str(), callbacks[0].owner), ("Click", "on_ok", Some(7)));
+    }
+
+    #[test]
+    fn test_slider_knob_smooths_and_checkmark_fades() {
+        let mut volume = stretched("volume");
+        volume.slider = Some(ui::UISlider {
+            max_value: 10.0,
+            fill_element: Some("Fill".to_string()),
+            handle_element: Some("Handle".to_string()),
+            smoothing_time: 0.2,
+            ..Default::default()
+        });
+        volume.children.push(stretched("Fill"));
+        volume.children.push(stretched("Handle"));
+        let mut mute = stretched("mute");
+        mute.toggle = Some(ui::UIToggle {
+            toggle_transition: ui::ToggleTransition::Scale,
+            checkmark: Some("Check".to_string()),
+            checkmark_duration: 0.1,
+            ..Default::default()
+        });
+        mute.children.push(stretched("Check"));
+        let mut root = stretched("root");
+        root.children.push(volume);
+        root.children.push(mute);
+        let (mut manager, screen) = controls_manager(root);
+        // How a child of a control is drawn this frame
+        let drawn = |manager: &UIManager, path: &str, child: usize| {
+            let element = manager.element(path).unwrap();
+            UIManager::control_child(element, manager.control_visuals.get(path), &element.children[child]).unwrap()
+        };
+        manager.update_control_visuals(screen, None, 0.0);
+        assert_eq!(drawn(&manager, "menu/volume", 0).rect_transform.anchor_max.x, 0.0);
+        assert_eq!(drawn(&manager, "menu/mute", 0).ui_element.alpha, 0.0);
+
+        // Set from code (clamped): the fill and knob catch up over smoothing_time
+        manager.set_slider("menu/volume", 20.0).unwrap();
+        assert_eq!(manager.element("menu/volume").unwrap().slider.as_ref().unwrap().value, 10.0);
+        assert!(manager.update_control_visuals(screen, None, 0.1));
+        let knob = drawn(&manager, "menu/volume", 1).rect_transform.anchor_min.x;
+        assert!(knob > 0.0 && knob < 1.0, "{}", knob);
+        assert!(!manager.update_control_visuals(screen, None, 0.2));
+        assert_eq!(drawn(&manager, "menu/volume", 0).rect_transform.anchor_max.x, 1.0);
+        assert_eq!(drawn(&manager, "menu/volume", 1).rect_transform.anchor_min.x, 1.0);
+
+        // The checkmark fades and grows in
+        manager.set_toggle("menu/mute", true).unwrap();
+        assert!(manager.update_control_visuals(screen, None, 0.05));
+        let check = drawn(&manager, "menu/mute", 0);
+        assert!(check.ui_element.alpha > 0.0 && check.ui_element.alpha < 1.0);
+        assert!(check.rect_transform.scale.x < 1.0);
+        assert!(!manager.update_control_visuals(screen, None, 0.1));
+        let check = drawn(&manager, "menu/mute", 0);
+        assert_eq!((check.ui_element.alpha, check.rect_transform.scale.x), (1.0, 1.0));
     }
 }
@@ -38,12 +38,13 @@ fn create_sprite_at_depth(world: &mut World, z: f32) -> Entity {
         texture_id: "test".to_string(),
         width: 32.0,
         height: 32.0,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
         billboard: false,
         flip_x: false,
         flip_y: false,
         sprite_rect: None,
         pixels_per_unit: 100.0,
+        pivot: None,
     });
     
     entity
@@ -142,12 +143,13 @@ proptest! {
             texture_id: "test".to_string(),
             width: 32.0,
             height: 32.0,
-            color: [1.0, 1.0, 1.0, 1.0],
+            color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
             billboard: false,
             flip_x: false,
             flip_y: false,
             sprite_rect: None,
             pixels_per_unit: 100.0,
+            pivot: None,
         });
         
         let entity2 = world.spawn();
@@ -160,12 +162,13 @@ proptest! {
             texture_id: "test".to_string(),
             width: 32.0,
             height: 32.0,
-            color: [1.0, 1.0, 1.0, 1.0],
+            color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
             billboard: false,
             flip_x: false,
             flip_y: false,
             sprite_rect: None,
             pixels_per_unit: 100.0,
+            pivot: None,
         });
         
         // Calculate depths from camera
@@ -19,12 +19,13 @@ fn create_entity_at_depth(world: &mut World, z: f32, has_alpha: bool) -> Entity
         texture_id: "test".to_string(),
         width: 10.0,
         height: 10.0,
-        color: [1.0, 1.0, 1.0, alpha],
+        color: ecs::Color::rgba(1.0, 1.0, 1.0, alpha),
         billboard: false,
         flip_x: false,
         flip_y: false,
         sprite_rect: None,
         pixels_per_unit: 100.0,
+        pivot: None,
     });
     
     entity
@@ -18,6 +18,9 @@ fn create_sprite_at_depth(entity: Entity, z: f32) -> SpriteRenderData {
         scale: Vec2::new(1.0, 1.0),
         texture_id: format!("sprite_{}", entity),
         sprite_rect: None,
+        rect_rotated: false,
+        flip_x: false,
+        flip_y: false,
         color: [1.0, 1.0, 1.0, 1.0],
         billboard: false,
         width: 32.0,
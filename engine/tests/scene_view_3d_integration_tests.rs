@@ -21,12 +21,13 @@ fn test_3d_rendering_integration_with_sprites() {
         texture_id: "test1".to_string(),
         width: 32.0,
         height: 32.0,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
         billboard: false,
         flip_x: false,
         flip_y: false,
         sprite_rect: None,
         pixels_per_unit: 100.0,
+        pivot: None,
     });
     
     let entity2 = world.spawn();
@@ -39,12 +40,13 @@ fn test_3d_rendering_integration_with_sprites() {
         texture_id: "test2".to_string(),
         width: 32.0,
         height: 32.0,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
         billboard: false,
         flip_x: false,
         flip_y: false,
         sprite_rect: None,
         pixels_per_unit: 100.0,
+        pivot: None,
     });
     
     // Verify sprites were created
@@ -69,12 +71,13 @@ fn test_billboard_sprite_in_3d() {
         texture_id: "billboard".to_string(),
         width: 64.0,
         height: 64.0,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
         billboard: true, // Billboard mode enabled
         flip_x: false,
         flip_y: false,
         sprite_rect: None,
         pixels_per_unit: 100.0,
+        pivot: None,
     });
     
     // Verify billboard sprite was created
@@ -99,12 +102,13 @@ fn test_transparent_sprite_rendering() {
         texture_id: "opaque".to_string(),
         width: 32.0,
         height: 32.0,
-        color: [1.0, 1.0, 1.0, 1.0], // Fully opaque
+        color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0), // Fully opaque
         billboard: false,
         flip_x: false,
         flip_y: false,
         sprite_rect: None,
         pixels_per_unit: 100.0,
+        pivot: None,
     });
     
     // Transparent sprite
@@ -118,12 +122,13 @@ fn test_transparent_sprite_rendering() {
         texture_id: "transparent".to_string(),
         width: 32.0,
         height: 32.0,
-        color: [1.0, 1.0, 1.0, 0.5], // Semi-transparent
+        color: ecs::Color::rgba(1.0, 1.0, 1.0, 0.5), // Semi-transparent
         billboard: false,
         flip_x: false,
         flip_y: false,
         sprite_rect: None,
         pixels_per_unit: 100.0,
+        pivot: None,
     });
     
     // Verify both sprites were created
@@ -17,6 +17,9 @@ fn create_test_sprite(position: Vec3, scale: Vec2, entity_id: u32) -> SpriteRend
         scale,
         texture_id: "test_texture".to_string(),
         sprite_rect: None,
+        rect_rotated: false,
+        flip_x: false,
+        flip_y: false,
         color: [1.0, 1.0, 1.0, 1.0],
         billboard: false,
         width: 100.0,
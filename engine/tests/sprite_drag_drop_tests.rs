@@ -31,12 +31,13 @@ fn test_sprite_entity_has_required_components() {
         texture_id: "test_texture.png".to_string(),
         width: 32.0,
         height: 32.0,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
         billboard: false,
         flip_x: false,
         flip_y: false,
         sprite_rect: None,
         pixels_per_unit: 100.0,
+        pivot: None,
     });
     
     // Add SpriteSheet component
@@ -52,6 +53,7 @@ fn test_sprite_entity_has_required_components() {
         width: 32,
         height: 32,
         name: Some("sprite_0".to_string()),
+        rotated: false,
     });
     world.sprite_sheets.insert(entity, sprite_sheet);
     
@@ -111,6 +113,7 @@ fn test_sprite_sheet_has_frames() {
         width: 32,
         height: 32,
         name: Some("sprite_0".to_string()),
+        rotated: false,
     });
     sprite_sheet.add_frame(ecs::SpriteFrame {
         x: 32,
@@ -118,6 +121,7 @@ fn test_sprite_sheet_has_frames() {
         width: 32,
         height: 32,
         name: Some("sprite_1".to_string()),
+        rotated: false,
     });
     
     world.sprite_sheets.insert(entity, sprite_sheet);
@@ -162,12 +166,13 @@ fn test_sprite_component_matches_first_frame() {
         texture_id: "test_texture.png".to_string(),
         width: frame_width,
         height: frame_height,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: ecs::Color::rgba(1.0, 1.0, 1.0, 1.0),
         billboard: false,
         flip_x: false,
         flip_y: false,
         sprite_rect: None,
         pixels_per_unit: 100.0,
+        pivot: None,
     });
     
     let sprite = world.sprites.get(&entity).unwrap();
@@ -0,0 +1,309 @@
+use serde::{Deserialize, Serialize};
+
+// ==================================================================================
+// Animation Curves (hand-authored easing)
+// ==================================================================================
+
+/// One key of an [`AnimationCurve`]. Tangents are slopes (value per unit of time) on
+/// each side of the key, so a key can have a corner.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CurveKey {
+    pub time: f32,
+    pub value: f32,
+    #[serde(default)]
+    pub in_tangent: f32,
+    #[serde(default)]
+    pub out_tangent: f32,
+}
+
+impl CurveKey {
+    pub fn new(time: f32, value: f32) -> Self {
+        Self { time, value, in_tangent: 0.0, out_tangent: 0.0 }
+    }
+
+    pub fn with_tangents(time: f32, value: f32, in_tangent: f32, out_tangent: f32) -> Self {
+        Self { time, value, in_tangent, out_tangent }
+    }
+}
+
+/// Easing curve made of keyframes over normalized time 0..1, cubic Hermite between keys
+/// (the same shape as a Bezier segment with its handles a third of the way along).
+///
+/// Keys are always kept sorted by time with no two at the same time, so the curve is a
+/// function of t: `evaluate` walks forward through the keys as t grows. Outside 0..1
+/// (and before the first / after the last key) it holds the nearest key's value. An
+/// empty curve is linear.
+///
+/// Serialized as its list of keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "Vec<CurveKey>", into = "Vec<CurveKey>")]
+pub struct AnimationCurve {
+    keys: Vec<CurveKey>,
+}
+
+impl AnimationCurve {
+    /// Curve through `keys`; times are clamped to 0..1, non-finite keys are dropped and
+    /// of several keys at one time the last wins
+    pub fn new(keys: Vec<CurveKey>) -> Self {
+        let mut curve = Self { keys: Vec::with_capacity(keys.len()) };
+        for key in keys {
+            curve.add_key(key);
+        }
+        curve
+    }
+
+    /// Straight line from (0, 0) to (1, 1)
+    pub fn linear() -> Self {
+        Self::new(vec![
+            CurveKey::with_tangents(0.0, 0.0, 1.0, 1.0),
+            CurveKey::with_tangents(1.0, 1.0, 1.0, 1.0),
+        ])
+    }
+
+    pub fn keys(&self) -> &[CurveKey] {
+        &self.keys
+    }
+
+    /// Insert a key (replacing one at the same time); returns its index
+    pub fn add_key(&mut self, key: CurveKey) -> Option<usize> {
+        let values = [key.time, key.value, key.in_tangent, key.out_tangent];
+        if values.iter().any(|v| !v.is_finite()) {
+            return None;
+        }
+        let key = CurveKey { time: key.time.clamp(0.0, 1.0), ..key };
+        let index = self.keys.partition_point(|k| k.time < key.time);
+        match self.keys.get(index) {
+            Some(existing) if existing.time == key.time => self.keys[index] = key,
+            _ => self.keys.insert(index, key),
+        }
+        Some(index)
+    }
+
+    pub fn remove_key(&mut self, index: usize) -> Option<CurveKey> {
+        (index < self.keys.len()).then(|| self.keys.remove(index))
+    }
+
+    /// Move a key (tangents kept); returns its new index, which changes when it passes a
+    /// neighbour. A key moved onto another key's time replaces it.
+    pub fn move_key(&mut self, index: usize, time: f32, value: f32) -> Option<usize> {
+        let key = self.remove_key(index)?;
+        self.add_key(CurveKey { time, value, ..key }).or_else(|| {
+            self.keys.insert(index, key);
+            None
+        })
+    }
+
+    pub fn set_tangents(&mut self, index: usize, in_tangent: f32, out_tangent: f32) {
+        if let Some(key) = self.keys.get_mut(index).filter(|_| in_tangent.is_finite() && out_tangent.is_finite()) {
+            key.in_tangent = in_tangent;
+            key.out_tangent = out_tangent;
+        }
+    }
+
+    /// Smooth tangents for every key that keep each segment between rising (or falling)
+    /// keys rising (or falling) too, with flat tangents at peaks (Fritsch-Carlson)
+    pub fn auto_tangents(&mut self) {
+        let n = self.keys.len();
+        if n < 2 {
+            return;
+        }
+        let secants: Vec<f32> = self.keys.windows(2)
+            .map(|pair| (pair[1].value - pair[0].value) / (pair[1].time - pair[0].time))
+            .collect();
+
+        let mut tangents = vec![0.0; n];
+        tangents[0] = secants[0];
+        tangents[n - 1] = secants[n - 2];
+        for i in 1..n - 1 {
+            let (before, after) = (secants[i - 1], secants[i]);
+            if before * after > 0.0 {
+                tangents[i] = (before + after) / 2.0;
+            }
+        }
+        // Limit the tangents so no segment overshoots its end keys
+        for (i, &secant) in secants.iter().enumerate() {
+            if secant == 0.0 {
+                tangents[i] = 0.0;
+                tangents[i + 1] = 0.0;
+                continue;
+            }
+            let a = tangents[i] / secant;
+            let b = tangents[i + 1] / secant;
+            let length = (a * a + b * b).sqrt();
+            if length > 3.0 {
+                tangents[i] = 3.0 * a / length * secant;
+                tangents[i + 1] = 3.0 * b / length * secant;
+            }
+        }
+        for (key, tangent) in self.keys.iter_mut().zip(tangents) {
+            key.in_tangent = tangent;
+            key.out_tangent = tangent;
+        }
+    }
+
+    /// Value at normalized time `t` (clamped to 0..1). At a key's time this is exactly
+    /// the key's value. O(log keys); see `bake` for a table to sample many times a frame.
+    pub fn evaluate(&self, t: f32) -> f32 {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let (first, last) = match (self.keys.first(), self.keys.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return t,
+        };
+        if t <= first.time {
+            return first.value;
+        }
+        if t >= last.time {
+            return last.value;
+        }
+
+        // Segment [index - 1, index] with keys[index - 1].time <= t < keys[index].time
+        let index = self.keys.partition_point(|key| key.time <= t);
+        let (k0, k1) = (&self.keys[index - 1], &self.keys[index]);
+        if t == k0.time {
+            return k0.value;
+        }
+        let dt = k1.time - k0.time;
+        let s = (t - k0.time) / dt;
+        let s2 = s * s;
+        let s3 = s2 * s;
+        let h00 = 2.0 * s3 - 3.0 * s2 + 1.0;
+        let h10 = s3 - 2.0 * s2 + s;
+        let h01 = -2.0 * s3 + 3.0 * s2;
+        let h11 = s3 - s2;
+        h00 * k0.value + h10 * dt * k0.out_tangent + h01 * k1.value + h11 * dt * k1.in_tangent
+    }
+
+    /// Lookup table of `samples` evenly spaced values (at least 2), linearly
+    /// interpolated: constant time per call, close to `evaluate` for a few hundred samples
+    pub fn bake(&self, samples: usize) -> BakedCurve {
+        let samples = samples.max(2);
+        let last = (samples - 1) as f32;
+        BakedCurve {
+            values: (0..samples).map(|i| self.evaluate(i as f32 / last)).collect(),
+        }
+    }
+}
+
+impl Default for AnimationCurve {
+    fn default() -> Self {
+        Self::linear()
+    }
+}
+
+impl From<Vec<CurveKey>> for AnimationCurve {
+    fn from(keys: Vec<CurveKey>) -> Self {
+        Self::new(keys)
+    }
+}
+
+impl From<AnimationCurve> for Vec<CurveKey> {
+    fn from(curve: AnimationCurve) -> Self {
+        curve.keys
+    }
+}
+
+/// An [`AnimationCurve`] sampled into a table (`AnimationCurve::bake`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct BakedCurve {
+    values: Vec<f32>,
+}
+
+impl BakedCurve {
+    /// Value at normalized time `t` (clamped to 0..1)
+    pub fn evaluate(&self, t: f32) -> f32 {
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+        let position = t * (self.values.len() - 1) as f32;
+        let index = (position as usize).min(self.values.len() - 2);
+        let fraction = position - index as f32;
+        self.values[index] + (self.values[index + 1] - self.values[index]) * fraction
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounce_in() -> AnimationCurve {
+        AnimationCurve::new(vec![
+            CurveKey::new(0.0, 0.0),
+            CurveKey::with_tangents(0.6, 1.2, 2.0, -1.0),
+            CurveKey::new(0.8, 0.9),
+            CurveKey::new(1.0, 1.0),
+        ])
+    }
+
+    #[test]
+    fn test_evaluate_hits_keys_and_clamps() {
+        let curve = bounce_in();
+        for key in curve.keys() {
+            assert_eq!(curve.evaluate(key.time), key.value);
+        }
+        assert_eq!(curve.evaluate(-3.0), 0.0);
+        assert_eq!(curve.evaluate(7.0), 1.0);
+        assert_eq!(curve.evaluate(f32::NAN), 0.0);
+
+        // Holds the end keys' values outside them
+        let late = AnimationCurve::new(vec![CurveKey::new(0.25, 0.5), CurveKey::new(0.75, 1.0)]);
+        assert_eq!(late.evaluate(0.1), 0.5);
+        assert_eq!(late.evaluate(0.9), 1.0);
+
+        assert_eq!(AnimationCurve::new(Vec::new()).evaluate(0.3), 0.3);
+        assert!((AnimationCurve::linear().evaluate(0.3) - 0.3).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_keys_stay_sorted_through_edits() {
+        let mut curve = AnimationCurve::new(vec![CurveKey::new(1.0, 1.0), CurveKey::new(0.0, 0.0), CurveKey::new(2.0, 5.0)]);
+        // 2.0 was clamped onto 1.0 and replaced it
+        assert_eq!(curve.keys(), [CurveKey::new(0.0, 0.0), CurveKey::new(1.0, 5.0)]);
+
+        assert_eq!(curve.add_key(CurveKey::new(0.5, 0.2)), Some(1));
+        assert_eq!(curve.add_key(CurveKey::new(f32::NAN, 0.2)), None);
+        // Dragging the middle key past the first moves it to the front
+        curve.set_tangents(1, 3.0, 4.0);
+        assert_eq!(curve.move_key(1, 0.0, 0.3), Some(0));
+        assert_eq!(curve.keys().len(), 2);
+        assert_eq!(curve.keys()[0], CurveKey::with_tangents(0.0, 0.3, 3.0, 4.0));
+        assert!(curve.keys().windows(2).all(|pair| pair[0].time < pair[1].time));
+    }
+
+    #[test]
+    fn test_auto_tangents_keep_rising_keys_rising() {
+        let mut curve = AnimationCurve::new(vec![
+            CurveKey::new(0.0, 0.0),
+            CurveKey::new(0.1, 0.8),
+            CurveKey::new(0.5, 0.85),
+            CurveKey::new(1.0, 1.0),
+        ]);
+        curve.auto_tangents();
+        let mut previous = curve.evaluate(0.0);
+        for i in 1..=1000 {
+            let value = curve.evaluate(i as f32 / 1000.0);
+            assert!(value >= previous - 1e-6, "falls at t={}", i as f32 / 1000.0);
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn test_baked_curve_matches_evaluate() {
+        let curve = bounce_in();
+        let baked = curve.bake(512);
+        for i in 0..=100 {
+            let t = i as f32 / 100.0;
+            assert!((baked.evaluate(t) - curve.evaluate(t)).abs() < 0.01, "t={}", t);
+        }
+        assert_eq!(baked.evaluate(1.5), curve.evaluate(1.0));
+    }
+
+    #[test]
+    fn test_serializes_as_key_list() {
+        let curve = bounce_in();
+        let json = serde_json::to_string(&curve).unwrap();
+        assert!(json.starts_with("[{\"time\":0.0"));
+        assert_eq!(serde_json::from_str::<AnimationCurve>(&json).unwrap(), curve);
+
+        // Unsorted hand-written keys are put in order; tangents default to flat
+        let curve: AnimationCurve = serde_json::from_str(r#"[{"time":1,"value":1},{"time":0,"value":0}]"#).unwrap();
+        assert_eq!(curve.keys(), [CurveKey::new(0.0, 0.0), CurveKey::new(1.0, 1.0)]);
+    }
+}
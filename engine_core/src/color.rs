@@ -0,0 +1,361 @@
+// ==================================================================================
+// Shared RGBA Color
+// ==================================================================================
+//
+// One color type for sprites, cameras, debug lines and UI components. Components are
+// floats in [0, 1], not premultiplied, in sRGB space like the editor color pickers.
+//
+// Serialized as `[r, g, b, a]` (the old array form, so existing scenes and prefabs are
+// unchanged); also read from `{ "r": .., "g": .., "b": .., "a": .. }` and hex strings
+// ("#ff8800", "#ff8800cc", "f80").
+
+use serde::de::{self, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeTuple, Serializer};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::{Index, IndexMut, Mul};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Color {
+    pub const WHITE: Color = Color::rgba(1.0, 1.0, 1.0, 1.0);
+    pub const BLACK: Color = Color::rgba(0.0, 0.0, 0.0, 1.0);
+    pub const TRANSPARENT: Color = Color::rgba(0.0, 0.0, 0.0, 0.0);
+
+    pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub const fn rgb(r: f32, g: f32, b: f32) -> Self {
+        Self::rgba(r, g, b, 1.0)
+    }
+
+    pub const fn to_array(self) -> [f32; 4] {
+        [self.r, self.g, self.b, self.a]
+    }
+
+    /// Parse "#rgb", "#rgba", "#rrggbb" or "#rrggbbaa" (the '#' is optional)
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let digits = hex.trim().trim_start_matches('#');
+        let nibble = |i: usize| {
+            digits.get(i..i + 1)
+                .and_then(|d| u8::from_str_radix(d, 16).ok())
+                .ok_or_else(|| format!("invalid hex color '{}'", hex))
+        };
+        let byte = |i: usize| {
+            digits.get(i..i + 2)
+                .and_then(|d| u8::from_str_radix(d, 16).ok())
+                .ok_or_else(|| format!("invalid hex color '{}'", hex))
+        };
+
+        let channels: Vec<u8> = match digits.len() {
+            3 | 4 => (0..digits.len()).map(|i| nibble(i).map(|n| n * 17)).collect::<Result<_, _>>()?,
+            6 | 8 => (0..digits.len()).step_by(2).map(byte).collect::<Result<_, _>>()?,
+            _ => return Err(format!("invalid hex color '{}' (expected 3, 4, 6 or 8 digits)", hex)),
+        };
+        let channel = |i: usize| channels.get(i).map_or(1.0, |&c| c as f32 / 255.0);
+        Ok(Self::rgba(channel(0), channel(1), channel(2), channel(3)))
+    }
+
+    /// "#rrggbb", or "#rrggbbaa" when not opaque
+    pub fn to_hex(self) -> String {
+        let byte = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+        if self.a >= 1.0 {
+            format!("#{:02x}{:02x}{:02x}", byte(self.r), byte(self.g), byte(self.b))
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", byte(self.r), byte(self.g), byte(self.b), byte(self.a))
+        }
+    }
+
+    /// From hue (degrees), saturation and value in [0, 1]
+    pub fn from_hsv(hue: f32, saturation: f32, value: f32, alpha: f32) -> Self {
+        let chroma = value * saturation;
+        let [r, g, b] = hue_to_rgb(hue, chroma);
+        let m = value - chroma;
+        Self::rgba(r + m, g + m, b + m, alpha)
+    }
+
+    /// (hue in degrees, saturation, value)
+    pub fn to_hsv(self) -> (f32, f32, f32) {
+        let (max, min) = (self.max_channel(), self.min_channel());
+        let saturation = if max > 0.0 { (max - min) / max } else { 0.0 };
+        (self.hue(), saturation, max)
+    }
+
+    /// From hue (degrees), saturation and lightness in [0, 1]
+    pub fn from_hsl(hue: f32, saturation: f32, lightness: f32, alpha: f32) -> Self {
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let [r, g, b] = hue_to_rgb(hue, chroma);
+        let m = lightness - chroma / 2.0;
+        Self::rgba(r + m, g + m, b + m, alpha)
+    }
+
+    /// (hue in degrees, saturation, lightness)
+    pub fn to_hsl(self) -> (f32, f32, f32) {
+        let (max, min) = (self.max_channel(), self.min_channel());
+        let lightness = (max + min) / 2.0;
+        let saturation = if max == min { 0.0 } else { (max - min) / (1.0 - (2.0 * lightness - 1.0).abs()) };
+        (self.hue(), saturation, lightness)
+    }
+
+    /// Componentwise interpolation (alpha included)
+    pub fn lerp(self, to: Color, t: f32) -> Self {
+        Self::rgba(
+            self.r + (to.r - self.r) * t,
+            self.g + (to.g - self.g) * t,
+            self.b + (to.b - self.b) * t,
+            self.a + (to.a - self.a) * t,
+        )
+    }
+
+    pub fn with_alpha(self, a: f32) -> Self {
+        Self { a, ..self }
+    }
+
+    /// Move toward white by `amount` (0 = unchanged, 1 = white); alpha is kept
+    pub fn lighten(self, amount: f32) -> Self {
+        Self::WHITE.with_alpha(self.a).lerp(self, 1.0 - amount)
+    }
+
+    /// Move toward black by `amount` (0 = unchanged, 1 = black); alpha is kept
+    pub fn darken(self, amount: f32) -> Self {
+        Self::BLACK.with_alpha(self.a).lerp(self, 1.0 - amount)
+    }
+
+    /// Color channels multiplied by alpha
+    pub fn premultiplied(self) -> Self {
+        Self::rgba(self.r * self.a, self.g * self.a, self.b * self.a, self.a)
+    }
+
+    /// sRGB to linear (alpha is already linear)
+    pub fn to_linear(self) -> Self {
+        let f = |c: f32| if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) };
+        Self::rgba(f(self.r), f(self.g), f(self.b), self.a)
+    }
+
+    /// Linear to sRGB (alpha is kept linear)
+    pub fn to_srgb(self) -> Self {
+        let f = |c: f32| if c <= 0.0031308 { c * 12.92 } else { 1.055 * c.powf(1.0 / 2.4) - 0.055 };
+        Self::rgba(f(self.r), f(self.g), f(self.b), self.a)
+    }
+
+    fn max_channel(self) -> f32 {
+        self.r.max(self.g).max(self.b)
+    }
+
+    fn min_channel(self) -> f32 {
+        self.r.min(self.g).min(self.b)
+    }
+
+    fn hue(self) -> f32 {
+        let (max, min) = (self.max_channel(), self.min_channel());
+        let delta = max - min;
+        if delta == 0.0 {
+            return 0.0;
+        }
+        let sector = if max == self.r {
+            ((self.g - self.b) / delta).rem_euclid(6.0)
+        } else if max == self.g {
+            (self.b - self.r) / delta + 2.0
+        } else {
+            (self.r - self.g) / delta + 4.0
+        };
+        sector * 60.0
+    }
+}
+
+/// RGB (without the lightness offset) for a hue in degrees and a chroma
+fn hue_to_rgb(hue: f32, chroma: f32) -> [f32; 3] {
+    let sector = hue.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    match sector as u32 {
+        0 => [chroma, x, 0.0],
+        1 => [x, chroma, 0.0],
+        2 => [0.0, chroma, x],
+        3 => [0.0, x, chroma],
+        4 => [x, 0.0, chroma],
+        _ => [chroma, 0.0, x],
+    }
+}
+
+impl From<[f32; 4]> for Color {
+    fn from([r, g, b, a]: [f32; 4]) -> Self {
+        Self::rgba(r, g, b, a)
+    }
+}
+
+impl From<Color> for [f32; 4] {
+    fn from(color: Color) -> Self {
+        color.to_array()
+    }
+}
+
+impl From<glam::Vec4> for Color {
+    fn from(v: glam::Vec4) -> Self {
+        Self::rgba(v.x, v.y, v.z, v.w)
+    }
+}
+
+impl From<Color> for glam::Vec4 {
+    fn from(color: Color) -> Self {
+        glam::Vec4::new(color.r, color.g, color.b, color.a)
+    }
+}
+
+impl PartialEq<[f32; 4]> for Color {
+    fn eq(&self, other: &[f32; 4]) -> bool {
+        self.to_array() == *other
+    }
+}
+
+/// `color[0]`..`color[3]` are r, g, b, a (code written against the old array type)
+impl Index<usize> for Color {
+    type Output = f32;
+
+    fn index(&self, index: usize) -> &f32 {
+        match index {
+            0 => &self.r,
+            1 => &self.g,
+            2 => &self.b,
+            3 => &self.a,
+            _ => panic!("color index {} out of range", index),
+        }
+    }
+}
+
+impl IndexMut<usize> for Color {
+    fn index_mut(&mut self, index: usize) -> &mut f32 {
+        match index {
+            0 => &mut self.r,
+            1 => &mut self.g,
+            2 => &mut self.b,
+            3 => &mut self.a,
+            _ => panic!("color index {} out of range", index),
+        }
+    }
+}
+
+/// Componentwise product (a tint applied to a sprite color)
+impl Mul for Color {
+    type Output = Color;
+
+    fn mul(self, other: Color) -> Color {
+        Self::rgba(self.r * other.r, self.g * other.g, self.b * other.b, self.a * other.a)
+    }
+}
+
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut tuple = serializer.serialize_tuple(4)?;
+        for channel in self.to_array() {
+            tuple.serialize_element(&channel)?;
+        }
+        tuple.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ColorVisitor)
+    }
+}
+
+struct ColorVisitor;
+
+impl<'de> Visitor<'de> for ColorVisitor {
+    type Value = Color;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an [r, g, b, a] array, an {r, g, b, a} map or a hex color string")
+    }
+
+    fn visit_str<E: de::Error>(self, hex: &str) -> Result<Color, E> {
+        Color::from_hex(hex).map_err(E::custom)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Color, A::Error> {
+        let mut channels = [0.0, 0.0, 0.0, 1.0];
+        let mut count = 0;
+        while let Some(channel) = seq.next_element::<f32>()? {
+            if count == 4 {
+                return Err(de::Error::invalid_length(5, &self));
+            }
+            channels[count] = channel;
+            count += 1;
+        }
+        if count < 3 {
+            return Err(de::Error::invalid_length(count, &self));
+        }
+        Ok(channels.into())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Color, A::Error> {
+        let mut color = Color::WHITE;
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value::<f32>()?;
+            match key.as_str() {
+                "r" => color.r = value,
+                "g" => color.g = value,
+                "b" => color.b = value,
+                "a" => color.a = value,
+                other => return Err(de::Error::unknown_field(other, &["r", "g", "b", "a"])),
+            }
+        }
+        Ok(color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: Color, b: Color) -> bool {
+        a.to_array().iter().zip(b.to_array()).all(|(x, y)| (x - y).abs() < 1.0 / 255.0)
+    }
+
+    #[test]
+    fn test_hex_and_hsv_round_trip() {
+        let orange = Color::from_hex("#ff8800").unwrap();
+        assert!(close(orange, Color::rgb(1.0, 136.0 / 255.0, 0.0)));
+        assert_eq!(Color::from_hex("#ff8800cc").unwrap().to_hex(), "#ff8800cc");
+        assert_eq!(Color::from_hex("f80").unwrap().to_hex(), "#ff8800");
+        assert!(Color::from_hex("#ff88").is_ok());
+        assert!(Color::from_hex("#ff880").is_err());
+        assert!(Color::from_hex("#gg8800").is_err());
+
+        let (h, s, v) = orange.to_hsv();
+        assert!(close(Color::from_hsv(h, s, v, 1.0), orange));
+        let (h, s, l) = orange.to_hsl();
+        assert!(close(Color::from_hsl(h, s, l, 1.0), orange));
+        assert!(close(Color::from_hsv(240.0, 1.0, 1.0, 0.5), Color::rgba(0.0, 0.0, 1.0, 0.5)));
+
+        let gray = Color::rgb(0.5, 0.5, 0.5);
+        assert!(close(gray.to_linear().to_srgb(), gray));
+        assert!(close(Color::rgba(1.0, 0.5, 0.0, 0.5).premultiplied(), Color::rgba(0.5, 0.25, 0.0, 0.5)));
+        assert!(close(Color::BLACK.lerp(Color::WHITE, 0.25), Color::rgb(0.25, 0.25, 0.25)));
+        assert!(close(gray.lighten(0.5), Color::rgb(0.75, 0.75, 0.75)));
+        assert!(close(Color::rgba(1.0, 0.5, 0.2, 0.8) * Color::WHITE.with_alpha(0.5), Color::rgba(1.0, 0.5, 0.2, 0.4)));
+    }
+
+    #[test]
+    fn test_deserialize_accepts_array_map_and_hex() {
+        let from_array: Color = serde_json::from_str("[1.0, 0.5, 0.0, 0.25]").unwrap();
+        let from_map: Color = serde_json::from_str(r#"{"r": 1.0, "g": 0.5, "b": 0.0, "a": 0.25}"#).unwrap();
+        let from_hex: Color = serde_json::from_str(r##""#ff800040""##).unwrap();
+        assert_eq!(from_array, from_map);
+        assert!(close(from_hex, from_array));
+
+        // Written back in the old array form
+        assert_eq!(serde_json::to_string(&from_array).unwrap(), "[1.0,0.5,0.0,0.25]");
+        // RGB arrays and maps without alpha are opaque
+        let rgb: Color = serde_json::from_str("[0.0, 0.0, 1.0]").unwrap();
+        assert_eq!(rgb, [0.0, 0.0, 1.0, 1.0]);
+        assert!(serde_json::from_str::<Color>("[1.0]").is_err());
+    }
+}
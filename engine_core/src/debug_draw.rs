@@ -0,0 +1,153 @@
+// ==================================================================================
+// Debug Draw
+// ==================================================================================
+//
+// Lines, boxes, circles, crosses and text queued by Lua scripts and Rust systems
+// (physics contacts, navigation) and drawn by the editor's scene view. Every draw
+// stays for `duration` seconds (0 = the current frame only) and belongs to a category
+// the editor can hide.
+//
+// The queue is shared through a `DebugDrawHandle`. Without the `debug-draw` feature
+// (release game builds) nothing is ever queued and every call compiles to nothing.
+
+use crate::Color;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// False when debug drawing is compiled out; lets callers skip building draws
+pub const ENABLED: bool = cfg!(feature = "debug-draw");
+
+/// Who queued a draw (each has its own visibility toggle in the editor)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DebugCategory {
+    /// Lua debug_draw_* functions
+    Script,
+    /// Contact points and broad-phase bounds from the physics backends
+    Physics,
+    /// Navigation grid and paths
+    Navigation,
+    /// Other Rust systems
+    Engine,
+}
+
+/// Shape of a draw, in world space
+#[derive(Clone, Debug, PartialEq)]
+pub enum DebugShape {
+    Line { start: [f32; 3], end: [f32; 3] },
+    /// Axis-aligned rectangle outline
+    Box { center: [f32; 3], size: [f32; 2] },
+    /// Circle outline
+    Circle { center: [f32; 3], radius: f32 },
+    /// Plus sign marking a point; `size` is the full width of each arm
+    Cross { center: [f32; 3], size: f32 },
+    /// Label anchored at a point (fixed size on screen)
+    Text { position: [f32; 3], text: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DebugDraw {
+    pub shape: DebugShape,
+    pub color: Color,
+    pub category: DebugCategory,
+    /// Seconds left before the draw expires
+    pub remaining: f32,
+}
+
+/// Queue of draws shared by everything that draws
+#[derive(Debug, Default)]
+pub struct DebugDraws {
+    draws: Vec<DebugDraw>,
+}
+
+/// Shared debug draw queue (the script engine, physics and the editor hold clones)
+pub type DebugDrawHandle = Rc<RefCell<DebugDraws>>;
+
+impl DebugDraws {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// New shared queue
+    pub fn handle() -> DebugDrawHandle {
+        Rc::new(RefCell::new(Self::new()))
+    }
+
+    /// Queue a draw shown for `duration` seconds (0 = this frame only)
+    #[cfg_attr(not(feature = "debug-draw"), allow(unused_variables))]
+    pub fn draw(&mut self, category: DebugCategory, shape: DebugShape, color: Color, duration: f32) {
+        #[cfg(feature = "debug-draw")]
+        self.draws.push(DebugDraw { shape, color, category, remaining: duration.max(0.0) });
+    }
+
+    pub fn draw_line(&mut self, category: DebugCategory, start: [f32; 3], end: [f32; 3], color: Color, duration: f32) {
+        self.draw(category, DebugShape::Line { start, end }, color, duration);
+    }
+
+    pub fn draw_box(&mut self, category: DebugCategory, center: [f32; 3], size: [f32; 2], color: Color, duration: f32) {
+        self.draw(category, DebugShape::Box { center, size }, color, duration);
+    }
+
+    pub fn draw_circle(&mut self, category: DebugCategory, center: [f32; 3], radius: f32, color: Color, duration: f32) {
+        self.draw(category, DebugShape::Circle { center, radius }, color, duration);
+    }
+
+    pub fn draw_cross(&mut self, category: DebugCategory, center: [f32; 3], size: f32, color: Color, duration: f32) {
+        self.draw(category, DebugShape::Cross { center, size }, color, duration);
+    }
+
+    pub fn draw_text(&mut self, category: DebugCategory, position: [f32; 3], text: impl Into<String>, color: Color, duration: f32) {
+        self.draw(category, DebugShape::Text { position, text: text.into() }, color, duration);
+    }
+
+    /// Advance time by `dt` (call once per frame before anything draws); draws whose
+    /// duration has run out are dropped, so a 0-duration draw lasts exactly one frame
+    pub fn update(&mut self, dt: f32) {
+        self.draws.retain_mut(|draw| {
+            draw.remaining -= dt;
+            draw.remaining > 0.0
+        });
+    }
+
+    /// Live draws, oldest first
+    pub fn draws(&self) -> &[DebugDraw] {
+        &self.draws
+    }
+
+    pub fn len(&self) -> usize {
+        self.draws.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.draws.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.draws.clear();
+    }
+}
+
+#[cfg(all(test, feature = "debug-draw"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_durations_persist_and_expire() {
+        let mut draws = DebugDraws::new();
+        draws.draw_cross(DebugCategory::Script, [0.0, 0.0, 0.0], 1.0, Color::WHITE, 0.0);
+        draws.draw_circle(DebugCategory::Physics, [1.0, 2.0, 0.0], 3.0, Color::WHITE, 2.0);
+        assert_eq!(draws.len(), 2);
+
+        // The one-frame draw goes at the next frame; the 2 second one stays until 2 seconds pass
+        draws.update(1.0 / 60.0);
+        assert_eq!(draws.len(), 1);
+        assert_eq!(draws.draws()[0].category, DebugCategory::Physics);
+        for _ in 0..100 {
+            draws.update(1.0 / 60.0);
+        }
+        assert_eq!(draws.len(), 1);
+        for _ in 0..30 {
+            draws.update(1.0 / 60.0);
+        }
+        assert!(draws.is_empty());
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+
+use crate::save_data::project_data_dir;
+
+// ==================================================================================
+// Display Settings (options menu choices, display.json)
+// ==================================================================================
+
+/// File name of the display settings inside a project's persistent data folder.
+pub const DISPLAY_SETTINGS_FILE: &str = "display.json";
+
+/// Display options the player picked at runtime (Screen.set_fullscreen / set_resolution /
+/// set_vsync), stored next to the save file so they survive a restart.
+///
+/// Unset fields keep the window settings of the project (or the exported game's manifest).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DisplaySettings {
+    pub fullscreen: Option<bool>,
+    /// Windowed size in physical pixels
+    pub resolution: Option<[u32; 2]>,
+    pub vsync: Option<bool>,
+}
+
+impl DisplaySettings {
+    /// `display.json` in the project's persistent data folder
+    pub fn path_for_project(project_name: &str) -> PathBuf {
+        project_data_dir(project_name).join(DISPLAY_SETTINGS_FILE)
+    }
+
+    /// Settings stored at `path`, or none set if the file is missing or can't be read
+    pub fn load(path: &Path) -> Self {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|e| {
+                log::warn!("Invalid {}, using project window settings: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Write atomically (temp file + rename), like the save file
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("engine_core_display_test_{}", std::process::id()));
+        let path = dir.join(DISPLAY_SETTINGS_FILE);
+
+        // Nothing saved yet: every field falls back to the project
+        assert_eq!(DisplaySettings::load(&path), DisplaySettings::default());
+
+        let settings = DisplaySettings { fullscreen: Some(true), resolution: Some([1920, 1080]), vsync: None };
+        settings.save(&path).unwrap();
+        assert_eq!(DisplaySettings::load(&path), settings);
+        assert!(!path.with_extension("json.tmp").exists());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
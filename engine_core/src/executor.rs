@@ -0,0 +1,208 @@
+use rayon::prelude::*;
+
+// ==================================================================================
+// Parallel System Executor
+// ==================================================================================
+//
+// Systems never get `&mut World` while running. Each one reads a shared `&W` (on a
+// worker thread when it shares a batch with others) and returns its writes as a
+// deferred closure; the executor applies those closures on the calling thread in
+// declaration order once the whole batch has finished. Declared access decides the
+// batches: two systems share a batch only if neither writes a component the other
+// reads or writes, so every system sees exactly what it would in a serial run.
+//
+// Build with the `single-threaded-systems` feature to run everything on the calling
+// thread (same batches, same results) when chasing a determinism bug.
+
+/// Writes a system wants to make, applied after its batch has finished
+pub type SystemWrites<W> = Box<dyn FnOnce(&mut W) + Send>;
+
+/// Components (by name, e.g. "animated_sprites") a system reads and writes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SystemAccess {
+    pub reads: Vec<&'static str>,
+    pub writes: Vec<&'static str>,
+}
+
+impl SystemAccess {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn read(mut self, component: &'static str) -> Self {
+        self.reads.push(component);
+        self
+    }
+
+    pub fn write(mut self, component: &'static str) -> Self {
+        self.writes.push(component);
+        self
+    }
+
+    /// True if running both against the same world snapshot could differ from running them in order
+    pub fn conflicts_with(&self, other: &SystemAccess) -> bool {
+        let writes_into = |a: &SystemAccess, b: &SystemAccess| {
+            a.writes.iter().any(|c| b.reads.contains(c) || b.writes.contains(c))
+        };
+        writes_into(self, other) || writes_into(other, self)
+    }
+}
+
+/// A system the executor may run concurrently with others in its stage
+pub trait ParallelSystem<W>: Send {
+    fn name(&self) -> &str;
+    fn access(&self) -> SystemAccess;
+    /// Read the world and return the writes to apply (may run on a worker thread)
+    fn run(&mut self, world: &W, dt: f32) -> SystemWrites<W>;
+}
+
+/// True unless the crate was built with `single-threaded-systems`
+pub const fn parallel_enabled() -> bool {
+    !cfg!(feature = "single-threaded-systems")
+}
+
+/// Map over items on the rayon pool (in order on the calling thread with
+/// `single-threaded-systems`); results keep the input order
+pub fn par_map<T, R, F>(items: &[T], f: F) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync + Send,
+{
+    if parallel_enabled() {
+        items.par_iter().map(f).collect()
+    } else {
+        items.iter().map(f).collect()
+    }
+}
+
+/// Runs a stage's systems in declaration order, batching non-conflicting neighbours
+pub struct SystemExecutor<W> {
+    systems: Vec<Box<dyn ParallelSystem<W>>>,
+    /// Index ranges into `systems`; each batch runs concurrently
+    batches: Vec<std::ops::Range<usize>>,
+    parallel: bool,
+}
+
+impl<W> Default for SystemExecutor<W> {
+    fn default() -> Self {
+        Self {
+            systems: Vec::new(),
+            batches: Vec::new(),
+            parallel: parallel_enabled(),
+        }
+    }
+}
+
+impl<W: Sync> SystemExecutor<W> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a system. It joins the last batch if its access doesn't conflict with
+    /// any system already there, otherwise it starts a new one.
+    pub fn add_system(&mut self, system: Box<dyn ParallelSystem<W>>) {
+        let access = system.access();
+        let index = self.systems.len();
+        let joins_last = self.batches.last().is_some_and(|batch| {
+            self.systems[batch.clone()].iter().all(|other| !other.access().conflicts_with(&access))
+        });
+        match self.batches.last_mut() {
+            Some(batch) if joins_last => batch.end = index + 1,
+            _ => self.batches.push(index..index + 1),
+        }
+        self.systems.push(system);
+    }
+
+    /// Turn concurrent execution off (or back on) at runtime; always off with `single-threaded-systems`
+    pub fn set_parallel(&mut self, parallel: bool) {
+        self.parallel = parallel && parallel_enabled();
+    }
+
+    pub fn is_parallel(&self) -> bool {
+        self.parallel
+    }
+
+    /// System names per batch, in run order
+    pub fn batches(&self) -> Vec<Vec<&str>> {
+        self.batches
+            .iter()
+            .map(|batch| self.systems[batch.clone()].iter().map(|s| s.name()).collect())
+            .collect()
+    }
+
+    pub fn run(&mut self, world: &mut W, dt: f32) {
+        for batch in self.batches.clone() {
+            let systems = &mut self.systems[batch];
+            if !self.parallel || systems.len() == 1 {
+                // Same batches, so applying each system's writes right away gives the same result
+                for system in systems.iter_mut() {
+                    let writes = system.run(world, dt);
+                    writes(world);
+                }
+                continue;
+            }
+
+            let shared: &W = world;
+            let writes: Vec<SystemWrites<W>> = systems.par_iter_mut().map(|system| system.run(shared, dt)).collect();
+            for write in writes {
+                write(world);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Counters {
+        a: u32,
+        b: u32,
+        sum: u32,
+    }
+
+    struct Increment(&'static str);
+
+    impl ParallelSystem<Counters> for Increment {
+        fn name(&self) -> &str { self.0 }
+        fn access(&self) -> SystemAccess { SystemAccess::new().write(self.0) }
+        fn run(&mut self, world: &Counters, _dt: f32) -> SystemWrites<Counters> {
+            let field = self.0;
+            let value = if field == "a" { world.a } else { world.b } + 1;
+            Box::new(move |world| if field == "a" { world.a = value } else { world.b = value })
+        }
+    }
+
+    struct Sum;
+
+    impl ParallelSystem<Counters> for Sum {
+        fn name(&self) -> &str { "sum" }
+        fn access(&self) -> SystemAccess { SystemAccess::new().read("a").read("b").write("sum") }
+        fn run(&mut self, world: &Counters, _dt: f32) -> SystemWrites<Counters> {
+            let sum = world.a + world.b;
+            Box::new(move |world| world.sum = sum)
+        }
+    }
+
+    #[test]
+    fn test_conflicting_systems_get_separate_batches() {
+        let mut executor = SystemExecutor::new();
+        executor.add_system(Box::new(Increment("a")));
+        executor.add_system(Box::new(Increment("b")));
+        executor.add_system(Box::new(Sum));
+        assert_eq!(executor.batches(), vec![vec!["a", "b"], vec!["sum"]]);
+
+        let mut world = Counters::default();
+        executor.run(&mut world, 0.0);
+        executor.run(&mut world, 0.0);
+        assert_eq!((world.a, world.b, world.sum), (2, 2, 4));
+    }
+
+    #[test]
+    fn test_par_map_keeps_order() {
+        let items: Vec<u32> = (0..1000).collect();
+        assert_eq!(par_map(&items, |x| x * 2), items.iter().map(|x| x * 2).collect::<Vec<_>>());
+    }
+}
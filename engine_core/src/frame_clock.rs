@@ -0,0 +1,274 @@
+//! Frame timing for the main loops (player and editor)
+//!
+//! `FrameClock` measures the real time between frames and decides when the next one is
+//! due: right away, at the target frame rate, or at the background rate while the window
+//! is unfocused or minimized. `GameTime` is the game's side of it (scaled delta, running
+//! time, frame count), what Lua's `Time` table reads and `Time.time_scale` writes.
+
+use crate::project_settings::RenderingSettings;
+use std::time::{Duration, Instant};
+
+/// OS sleeps overshoot by a millisecond or more: the last stretch before a frame is spun
+const SPIN_MARGIN: Duration = Duration::from_millis(2);
+
+/// What the event loop does before the next frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FramePace {
+    /// Draw now
+    Redraw,
+    /// Due very soon: sleep precisely until then, then draw
+    SleepUntil(Instant),
+    /// Wait for events until then (the wake-up lands a little early, then sleeps precisely)
+    WaitUntil(Instant),
+}
+
+pub struct FrameClock {
+    last_frame: Option<Instant>,
+    /// Longest delta a frame reports; a hitch (or a breakpoint) slows the game down
+    /// instead of turning into one huge step
+    max_delta: f32,
+    target_interval: Option<Duration>,
+    background_interval: Option<Duration>,
+    vsync: bool,
+    focused: bool,
+    minimized: bool,
+}
+
+fn interval(fps: u32) -> Option<Duration> {
+    (fps > 0).then(|| Duration::from_secs_f64(1.0 / fps as f64))
+}
+
+impl FrameClock {
+    pub fn new(rendering: &RenderingSettings) -> Self {
+        let mut clock = Self {
+            last_frame: None,
+            max_delta: 0.1,
+            target_interval: None,
+            background_interval: None,
+            vsync: rendering.vsync,
+            focused: true,
+            minimized: false,
+        };
+        clock.configure(rendering);
+        clock
+    }
+
+    /// Take the frame cap, background rate and max delta from the project's settings
+    pub fn configure(&mut self, rendering: &RenderingSettings) {
+        self.max_delta = rendering.max_delta.max(0.001);
+        self.target_interval = interval(rendering.target_fps);
+        self.background_interval = interval(rendering.background_fps);
+    }
+
+    /// Frame cap in frames per second (0 = none)
+    pub fn set_target_fps(&mut self, fps: u32) {
+        self.target_interval = interval(fps);
+    }
+
+    /// With vsync, presenting already waits for the display: an uncapped loop just draws
+    /// and a capped one needn't spin for its last millisecond
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.vsync = vsync;
+    }
+
+    pub fn set_focused(&mut self, focused: bool) {
+        self.focused = focused;
+    }
+
+    pub fn set_minimized(&mut self, minimized: bool) {
+        self.minimized = minimized;
+    }
+
+    /// Whether frames run at the background rate (window unfocused or minimized)
+    pub fn is_throttled(&self) -> bool {
+        (!self.focused || self.minimized) && self.background_interval.is_some()
+    }
+
+    /// Time between frames, or None to draw as fast as presenting allows
+    pub fn frame_interval(&self) -> Option<Duration> {
+        if self.is_throttled() {
+            // The background rate never speeds a slower frame cap up
+            return self.background_interval.max(self.target_interval);
+        }
+        self.target_interval
+    }
+
+    /// Start a frame: real seconds since the previous one, clamped to the max delta
+    /// (0 for the first frame)
+    pub fn tick(&mut self) -> f32 {
+        self.tick_at(Instant::now())
+    }
+
+    pub fn tick_at(&mut self, now: Instant) -> f32 {
+        let delta = self.last_frame.map_or(0.0, |last| now.saturating_duration_since(last).as_secs_f32());
+        self.last_frame = Some(now);
+        delta.min(self.max_delta)
+    }
+
+    pub fn pace(&self) -> FramePace {
+        self.pace_at(Instant::now())
+    }
+
+    pub fn pace_at(&self, now: Instant) -> FramePace {
+        let (Some(last), Some(interval)) = (self.last_frame, self.frame_interval()) else {
+            return FramePace::Redraw;
+        };
+        let due = last + interval;
+        if due <= now {
+            return FramePace::Redraw;
+        }
+        let margin = if self.vsync && !self.is_throttled() { Duration::ZERO } else { SPIN_MARGIN };
+        if due - now <= margin {
+            FramePace::SleepUntil(due)
+        } else {
+            FramePace::WaitUntil(due - margin)
+        }
+    }
+}
+
+/// Sleep until `deadline`: a coarse sleep for most of it, then spin for the last stretch
+pub fn sleep_until(deadline: Instant) {
+    loop {
+        let now = Instant::now();
+        if now >= deadline {
+            return;
+        }
+        let remaining = deadline - now;
+        if remaining > SPIN_MARGIN {
+            std::thread::sleep(remaining - SPIN_MARGIN);
+        } else {
+            std::thread::yield_now();
+        }
+    }
+}
+
+/// Fixed-timestep accumulator: add this frame's `dt` and return how many `step`s it pays
+/// for. A backlog past `max_steps` is dropped rather than caught up.
+pub fn fixed_steps(accumulator: &mut f32, dt: f32, step: f32, max_steps: u32) -> u32 {
+    if step <= 0.0 {
+        return 0;
+    }
+    *accumulator += dt;
+    let mut steps = 0;
+    while *accumulator >= step && steps < max_steps {
+        *accumulator -= step;
+        steps += 1;
+    }
+    if steps == max_steps {
+        *accumulator = accumulator.min(step);
+    }
+    steps
+}
+
+/// The game's clock: real frame time scaled by `time_scale` (0 freezes the game, physics
+/// and animations included; the editor UI keeps running on real time)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GameTime {
+    /// Scaled seconds of this frame
+    pub delta: f32,
+    /// Real seconds of this frame (after the max-delta clamp)
+    pub unscaled_delta: f32,
+    /// Scaled seconds since the game started
+    pub time: f64,
+    /// Frames since the game started
+    pub frame_count: u64,
+    pub time_scale: f32,
+}
+
+impl Default for GameTime {
+    fn default() -> Self {
+        Self { delta: 0.0, unscaled_delta: 0.0, time: 0.0, frame_count: 0, time_scale: 1.0 }
+    }
+}
+
+impl GameTime {
+    /// Advance one frame of `unscaled_delta` real seconds; returns the scaled delta
+    pub fn advance(&mut self, unscaled_delta: f32) -> f32 {
+        self.unscaled_delta = unscaled_delta;
+        self.delta = unscaled_delta * self.time_scale;
+        self.time += self.delta as f64;
+        self.frame_count += 1;
+        self.delta
+    }
+
+    /// Negative or non-finite scales are ignored
+    pub fn set_time_scale(&mut self, time_scale: f32) -> bool {
+        if !time_scale.is_finite() || time_scale < 0.0 {
+            return false;
+        }
+        self.time_scale = time_scale;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(target_fps: u32, background_fps: u32, vsync: bool) -> RenderingSettings {
+        RenderingSettings { target_fps, background_fps, vsync, ..Default::default() }
+    }
+
+    #[test]
+    fn test_delta_is_measured_and_clamped() {
+        let mut clock = FrameClock::new(&settings(0, 10, true));
+        let start = Instant::now();
+        assert_eq!(clock.tick_at(start), 0.0);
+        let dt = clock.tick_at(start + Duration::from_millis(20));
+        assert!((dt - 0.02).abs() < 1e-6);
+        assert_eq!(clock.tick_at(start + Duration::from_secs(5)), 0.1);
+    }
+
+    #[test]
+    fn test_pacing_follows_target_and_background_rate() {
+        let mut clock = FrameClock::new(&settings(0, 10, true));
+        let start = Instant::now();
+        clock.tick_at(start);
+        // Uncapped with vsync: presenting paces the loop
+        assert_eq!(clock.pace_at(start), FramePace::Redraw);
+
+        clock.set_target_fps(50);
+        clock.set_vsync(false);
+        let due = start + Duration::from_millis(20);
+        assert_eq!(clock.pace_at(start), FramePace::WaitUntil(due - SPIN_MARGIN));
+        assert_eq!(clock.pace_at(due - Duration::from_millis(1)), FramePace::SleepUntil(due));
+        assert_eq!(clock.pace_at(due), FramePace::Redraw);
+
+        clock.set_focused(false);
+        assert!(clock.is_throttled());
+        assert_eq!(clock.frame_interval(), Some(Duration::from_millis(100)));
+        clock.set_focused(true);
+        clock.set_minimized(true);
+        assert_eq!(clock.frame_interval(), Some(Duration::from_millis(100)));
+
+        // background_fps 0 turns throttling off
+        clock.configure(&settings(50, 0, false));
+        assert!(!clock.is_throttled());
+        assert_eq!(clock.frame_interval(), Some(Duration::from_millis(20)));
+    }
+
+    #[test]
+    fn test_fixed_steps_drop_backlog() {
+        let mut accumulator = 0.0;
+        assert_eq!(fixed_steps(&mut accumulator, 0.01, 0.02, 8), 0);
+        assert_eq!(fixed_steps(&mut accumulator, 0.035, 0.02, 8), 2);
+        assert!((accumulator - 0.005).abs() < 1e-5);
+        assert_eq!(fixed_steps(&mut accumulator, 1.0, 0.02, 8), 8);
+        assert!(accumulator <= 0.02);
+    }
+
+    #[test]
+    fn test_game_time_scale() {
+        let mut time = GameTime::default();
+        assert_eq!(time.advance(0.02), 0.02);
+        assert!(time.set_time_scale(0.5));
+        assert_eq!(time.advance(0.02), 0.01);
+        assert!(!time.set_time_scale(-1.0));
+        assert!(!time.set_time_scale(f32::NAN));
+        assert!(time.set_time_scale(0.0));
+        assert_eq!(time.advance(0.02), 0.0);
+        assert_eq!(time.unscaled_delta, 0.02);
+        assert_eq!(time.frame_count, 3);
+        assert!((time.time - 0.03).abs() < 1e-6);
+    }
+}
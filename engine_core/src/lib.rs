@@ -2,12 +2,32 @@ use std::collections::HashMap;
 use anyhow::Result;
 use std::any::Any;
 
+pub mod animation_curve;
 pub mod assets;
+pub mod color;
+pub mod debug_draw;
+pub mod display_settings;
+pub mod executor;
+pub mod frame_clock;
 pub mod project;
+pub mod project_settings;
+pub mod random;
+pub mod save_data;
+pub mod schedule;
+
+pub use color::Color;
+pub use schedule::{ModuleOrder, ModuleStage};
+use schedule::ScheduleEntry;
 
 pub trait EngineModule: Any {
     fn name(&self) -> &str;
+    /// Frame stage this module runs in
+    fn stage(&self) -> ModuleStage { ModuleStage::Update }
+    /// Before/after constraints against other modules in the same stage
+    fn ordering(&self) -> Vec<ModuleOrder> { Vec::new() }
     fn on_load(&mut self, _ctx: &mut EngineContext) -> Result<()> { Ok(()) }
+    /// Called once per frame, or once per fixed step for `ModuleStage::FixedUpdate`
+    /// (`dt` is then the fixed timestep)
     fn on_update(&mut self, _ctx: &mut EngineContext, _dt: f32) {}
     fn on_unload(&mut self, _ctx: &mut EngineContext) {}
     fn as_any(&mut self) -> &mut dyn Any;
@@ -17,11 +37,21 @@ use std::sync::Arc;
 use crate::assets::AssetLoader;
 use input::InputSystem;
 
+/// Most fixed steps run in one frame; a larger backlog (e.g. after a hitch) is dropped
+pub const MAX_FIXED_STEPS_PER_FRAME: u32 = 8;
+
 pub struct EngineContext {
     pub modules: HashMap<String, Box<dyn EngineModule>>,
     pub should_quit: bool,
     pub input: InputSystem,
     pub asset_loader: Arc<dyn AssetLoader>,
+    /// Seconds per `FixedUpdate` step (the physics timestep)
+    pub fixed_timestep: f32,
+    fixed_accumulator: f32,
+    entries: Vec<ScheduleEntry>,
+    schedule: Vec<(ModuleStage, Vec<String>)>,
+    /// Registered modules whose on_load hasn't run yet
+    pending_load: Vec<String>,
 }
 
 impl EngineContext {
@@ -31,22 +61,104 @@ impl EngineContext {
             should_quit: false,
             input: InputSystem::new(),
             asset_loader,
+            fixed_timestep: 1.0 / 60.0,
+            fixed_accumulator: 0.0,
+            entries: Vec::new(),
+            schedule: Vec::new(),
+            pending_load: Vec::new(),
         }
     }
 
-    pub fn register_module<M: EngineModule + 'static>(&mut self, mut module: M) {
+    /// Add a module and re-sort the schedule. Its on_load runs from `load_modules`
+    /// (or the next `update`) in schedule order, after the modules it depends on.
+    /// A module whose constraints form a cycle is rejected and the schedule is unchanged.
+    pub fn register_module<M: EngineModule + 'static>(&mut self, module: M) -> Result<()> {
         let name = module.name().to_string();
-        // call on_load
-        let _ = module.on_load(self);
-        self.modules.insert(name, Box::new(module));
+        let entry = ScheduleEntry {
+            name: name.clone(),
+            stage: module.stage(),
+            ordering: module.ordering(),
+        };
+
+        let mut entries = self.entries.clone();
+        match entries.iter_mut().find(|e| e.name == name) {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+        self.schedule = schedule::resolve_schedule(&entries)?;
+        self.entries = entries;
+        log::info!("Engine module schedule:\n{}", self.describe_schedule());
+
+        self.modules.insert(name.clone(), Box::new(module));
+        if !self.pending_load.contains(&name) {
+            self.pending_load.push(name);
+        }
+        Ok(())
+    }
+
+    /// Run on_load for newly registered modules in schedule order
+    pub fn load_modules(&mut self) -> Result<()> {
+        if self.pending_load.is_empty() {
+            return Ok(());
+        }
+        let order: Vec<String> = self.schedule.iter().flat_map(|(_, names)| names.iter().cloned()).collect();
+        for name in order {
+            if let Some(index) = self.pending_load.iter().position(|n| *n == name) {
+                self.pending_load.remove(index);
+                if let Some(mut module) = self.modules.remove(&name) {
+                    let result = module.on_load(self);
+                    self.modules.insert(name.clone(), module);
+                    result.map_err(|e| anyhow::anyhow!("Engine module '{}' failed to load: {}", name, e))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Module names per stage in run order
+    pub fn schedule(&self) -> &[(ModuleStage, Vec<String>)] {
+        &self.schedule
+    }
+
+    /// The resolved schedule, one stage per line
+    pub fn describe_schedule(&self) -> String {
+        schedule::describe_schedule(&self.schedule)
     }
 
     pub fn update(&mut self, dt: f32) {
-        let keys: Vec<String> = self.modules.keys().cloned().collect();
-        for key in keys {
-            if let Some(mut m) = self.modules.remove(&key) {
+        if let Err(e) = self.load_modules() {
+            log::error!("{}", e);
+        }
+
+        for (stage, names) in self.schedule.clone() {
+            if names.is_empty() {
+                continue;
+            }
+            if stage != ModuleStage::FixedUpdate {
+                self.run_modules(&names, dt);
+                continue;
+            }
+
+            let step = if self.fixed_timestep > 0.0 { self.fixed_timestep } else { 1.0 / 60.0 };
+            self.fixed_accumulator += dt;
+            let mut steps = 0;
+            while self.fixed_accumulator >= step {
+                if steps == MAX_FIXED_STEPS_PER_FRAME {
+                    self.fixed_accumulator = 0.0;
+                    break;
+                }
+                self.run_modules(&names, step);
+                self.fixed_accumulator -= step;
+                steps += 1;
+            }
+        }
+    }
+
+    fn run_modules(&mut self, names: &[String], dt: f32) {
+        for name in names {
+            if let Some(mut m) = self.modules.remove(name) {
                 m.on_update(self, dt);
-                self.modules.insert(key, m);
+                self.modules.insert(name.clone(), m);
             }
         }
     }
@@ -62,3 +174,63 @@ impl Time {
         Self { dt: 0.0, time: 0.0 }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct NullLoader;
+
+    #[async_trait::async_trait]
+    impl AssetLoader for NullLoader {
+        async fn load_text(&self, _path: &str) -> Result<String> { Ok(String::new()) }
+        async fn load_binary(&self, _path: &str) -> Result<Vec<u8>> { Ok(Vec::new()) }
+        fn get_base_path(&self) -> String { String::new() }
+    }
+
+    struct Probe {
+        name: &'static str,
+        stage: ModuleStage,
+        ordering: Vec<ModuleOrder>,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl EngineModule for Probe {
+        fn name(&self) -> &str { self.name }
+        fn stage(&self) -> ModuleStage { self.stage }
+        fn ordering(&self) -> Vec<ModuleOrder> { self.ordering.clone() }
+        fn on_load(&mut self, _ctx: &mut EngineContext) -> Result<()> {
+            self.log.lock().unwrap().push(format!("load {}", self.name));
+            Ok(())
+        }
+        fn on_update(&mut self, _ctx: &mut EngineContext, _dt: f32) {
+            self.log.lock().unwrap().push(self.name.to_string());
+        }
+        fn as_any(&mut self) -> &mut dyn Any { self }
+    }
+
+    #[test]
+    fn test_modules_load_and_update_in_schedule_order() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let probe = |name, stage, ordering| Probe { name, stage, ordering, log: log.clone() };
+        let mut ctx = EngineContext::new(Arc::new(NullLoader));
+        ctx.fixed_timestep = 0.1;
+
+        ctx.register_module(probe("physics", ModuleStage::FixedUpdate, vec![])).unwrap();
+        ctx.register_module(probe("render", ModuleStage::Render, vec![])).unwrap();
+        ctx.register_module(probe("camera", ModuleStage::Update, vec![ModuleOrder::After("scripts".into())])).unwrap();
+        ctx.register_module(probe("scripts", ModuleStage::Update, vec![])).unwrap();
+        assert!(ctx.register_module(probe("loop", ModuleStage::Update, vec![
+            ModuleOrder::Before("scripts".into()),
+            ModuleOrder::After("camera".into()),
+        ])).is_err());
+        assert!(!ctx.modules.contains_key("loop"));
+
+        ctx.update(0.25);
+        assert_eq!(*log.lock().unwrap(), vec![
+            "load scripts", "load camera", "load physics", "load render",
+            "scripts", "camera", "physics", "physics", "render",
+        ]);
+    }
+}
@@ -266,6 +266,24 @@ impl ProjectManager {
         Ok(())
     }
 
+    /// Project name from project.json, falling back to the folder name.
+    /// Used to namespace per-project persistent data (save files).
+    pub fn get_project_name(project_path: &Path) -> String {
+        let config_path = project_path.join("project.json");
+        if let Ok(config_str) = fs::read_to_string(&config_path) {
+            if let Ok(config) = serde_json::from_str::<ProjectConfig>(&config_str) {
+                if !config.name.is_empty() {
+                    return config.name;
+                }
+            }
+        }
+
+        project_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Untitled".to_string())
+    }
+
     pub fn get_example_projects() -> Vec<(&'static str, &'static str)> {
         vec![
             ("Celeste Demo", "Platformer demo with Celeste-style movement (Run, Jump, Dash)"),
@@ -0,0 +1,493 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use anyhow::Result;
+
+// ==================================================================================
+// Project Settings (settings.json)
+// ==================================================================================
+
+/// File name of the settings asset inside a project folder.
+pub const PROJECT_SETTINGS_FILE: &str = "settings.json";
+
+/// Most collision layers a project can define (one bit each in the layer matrix).
+pub const MAX_COLLISION_LAYERS: usize = 32;
+
+/// Per-project configuration shared by the editor and the player runtime.
+///
+/// Every section and field falls back to its default, so a partial or older
+/// `settings.json` merges with the defaults instead of failing to load.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectSettings {
+    pub physics: PhysicsSettings,
+    pub rendering: RenderingSettings,
+    pub input: InputSettings,
+    pub application: ApplicationSettings,
+    pub scripting: ScriptingSettings,
+    pub capture: CaptureSettings,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PhysicsSettings {
+    /// Downward acceleration in world units/s²
+    pub gravity: f32,
+    /// Seconds per physics step
+    pub fixed_timestep: f32,
+    /// Collision layer names; index = layer number
+    pub layers: Vec<String>,
+    /// Bitmask per layer of the layers it collides with (missing rows collide with everything)
+    pub layer_matrix: Vec<u32>,
+    /// Non-kinematic bodies are kept inside these bounds (simple backend)
+    pub world_bounds: WorldBounds,
+}
+
+impl Default for PhysicsSettings {
+    fn default() -> Self {
+        Self {
+            gravity: 150.0,
+            fixed_timestep: 1.0 / 60.0,
+            layers: vec!["Default".to_string()],
+            layer_matrix: Vec::new(),
+            world_bounds: WorldBounds::default(),
+        }
+    }
+}
+
+impl PhysicsSettings {
+    fn layer_mask(&self, layer: usize) -> u32 {
+        self.layer_matrix.get(layer).copied().unwrap_or(u32::MAX)
+    }
+
+    /// True if bodies on the two layers collide (both rows must allow it)
+    pub fn layers_collide(&self, a: usize, b: usize) -> bool {
+        if a >= MAX_COLLISION_LAYERS || b >= MAX_COLLISION_LAYERS {
+            return true;
+        }
+        self.layer_mask(a) & (1 << b) != 0 && self.layer_mask(b) & (1 << a) != 0
+    }
+
+    /// Layers that bodies on `layer` collide with, as a bit mask for physics queries
+    pub fn collision_mask(&self, layer: usize) -> u32 {
+        (0..MAX_COLLISION_LAYERS)
+            .filter(|&other| self.layers_collide(layer, other))
+            .fold(0, |mask, other| mask | 1 << other)
+    }
+
+    /// Set one cell of the matrix (kept symmetric)
+    pub fn set_layers_collide(&mut self, a: usize, b: usize, collide: bool) {
+        if a >= MAX_COLLISION_LAYERS || b >= MAX_COLLISION_LAYERS {
+            return;
+        }
+        let rows = a.max(b) + 1;
+        if self.layer_matrix.len() < rows {
+            self.layer_matrix.resize(rows, u32::MAX);
+        }
+        for (row, bit) in [(a, b), (b, a)] {
+            if collide {
+                self.layer_matrix[row] |= 1 << bit;
+            } else {
+                self.layer_matrix[row] &= !(1 << bit);
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorldBounds {
+    pub enabled: bool,
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+}
+
+impl Default for WorldBounds {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min: [-100.0, -100.0],
+            max: [100.0, 100.0],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RenderingSettings {
+    /// RGBA, 0-1
+    pub clear_color: [f32; 4],
+    pub vsync: bool,
+    /// Frame cap for the player (and the editor while playing); 0 = unlimited
+    pub target_fps: u32,
+    /// Frame rate while the window is unfocused or minimized; 0 = no throttling
+    pub background_fps: u32,
+    /// Longest frame delta the game sees, in seconds (longer frames slow the game down)
+    pub max_delta: f32,
+    /// Resolution the game is designed for (UI scaling, camera framing)
+    pub reference_resolution: [u32; 2],
+    /// Sprite/tilemap sorting layer names, drawn first to last
+    pub sorting_layers: Vec<String>,
+    /// Safe area used by UI elements with a SafeArea component
+    pub safe_area: SafeAreaSettings,
+    /// How the game fills a window whose shape differs from `reference_resolution`
+    pub aspect_mode: AspectMode,
+    /// RGBA, 0-1: bars around the game with `AspectMode::FixedAspect`
+    pub letterbox_color: [f32; 4],
+    /// Textures with a side longer than this many pixels show a small preview at once
+    /// and stream in their full-size mip levels in the background; 0 = off
+    pub texture_streaming_size: u32,
+    /// Texture memory budget in MB: past it, streamed textures keep their preview and
+    /// a warning is logged. 0 = unlimited
+    pub texture_budget_mb: u32,
+}
+
+impl Default for RenderingSettings {
+    fn default() -> Self {
+        Self {
+            clear_color: [0.15, 0.15, 0.2, 1.0],
+            vsync: true,
+            target_fps: 0,
+            background_fps: 10,
+            max_delta: 0.1,
+            reference_resolution: [1920, 1080],
+            sorting_layers: vec!["Default".to_string()],
+            safe_area: SafeAreaSettings::default(),
+            aspect_mode: AspectMode::Free,
+            letterbox_color: [0.0, 0.0, 0.0, 1.0],
+            texture_streaming_size: 0,
+            texture_budget_mb: 1024,
+        }
+    }
+}
+
+impl RenderingSettings {
+    pub fn aspect_policy(&self) -> AspectPolicy {
+        let [width, height] = self.reference_resolution;
+        AspectPolicy {
+            mode: self.aspect_mode,
+            reference_aspect: width.max(1) as f32 / height.max(1) as f32,
+            bar_color: self.letterbox_color,
+        }
+    }
+}
+
+/// Aspect ratio policy of the game view
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum AspectMode {
+    /// Use the whole window; the camera size sets the visible height
+    #[default]
+    Free,
+    /// Keep the reference aspect ratio, with bars on the sides (pillarbox) or
+    /// top and bottom (letterbox)
+    FixedAspect,
+    /// Same world height at any aspect; wider windows see more to the sides
+    FixedVertical,
+    /// Same world width as at the reference aspect; taller windows see more above
+    /// and below
+    FixedHorizontal,
+}
+
+impl AspectMode {
+    pub const ALL: [AspectMode; 4] = [
+        AspectMode::Free,
+        AspectMode::FixedAspect,
+        AspectMode::FixedVertical,
+        AspectMode::FixedHorizontal,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AspectMode::Free => "Free",
+            AspectMode::FixedAspect => "Fixed Aspect (Letterbox)",
+            AspectMode::FixedVertical => "Fixed Vertical",
+            AspectMode::FixedHorizontal => "Fixed Horizontal",
+        }
+    }
+}
+
+/// `AspectMode` with the reference aspect it keeps, for the renderer, picking and
+/// UI layout (`RenderingSettings::aspect_policy`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AspectPolicy {
+    pub mode: AspectMode,
+    /// Width / height of the reference resolution
+    pub reference_aspect: f32,
+    /// RGBA, 0-1
+    pub bar_color: [f32; 4],
+}
+
+impl Default for AspectPolicy {
+    fn default() -> Self {
+        RenderingSettings::default().aspect_policy()
+    }
+}
+
+impl AspectPolicy {
+    /// Part of `screen` (`[x, y, width, height]`, top-left origin) the game draws in:
+    /// the whole screen, or the centered rect with the reference aspect for FixedAspect
+    pub fn game_rect(&self, screen: [f32; 4]) -> [f32; 4] {
+        let [x, y, width, height] = screen;
+        if self.mode != AspectMode::FixedAspect || width <= 0.0 || height <= 0.0 {
+            return screen;
+        }
+        let (fit_width, fit_height) = if width / height > self.reference_aspect {
+            (height * self.reference_aspect, height)
+        } else {
+            (width, width / self.reference_aspect)
+        };
+        [x + (width - fit_width) * 0.5, y + (height - fit_height) * 0.5, fit_width, fit_height]
+    }
+
+    /// Factor on the camera's visible height at `aspect` (width / height): below 1
+    /// on wide screens and above 1 on tall ones for FixedHorizontal, 1 otherwise
+    pub fn vertical_scale(&self, aspect: f32) -> f32 {
+        if self.mode == AspectMode::FixedHorizontal && aspect > 0.0 {
+            self.reference_aspect / aspect
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Simulated device safe area, for testing notch/rounded-corner layouts on desktop
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SafeAreaSettings {
+    pub simulate: bool,
+    /// Normalized `[x, y, width, height]` of the screen, bottom-left origin
+    pub rect: [f32; 4],
+}
+
+impl Default for SafeAreaSettings {
+    fn default() -> Self {
+        Self {
+            simulate: false,
+            rect: [0.05, 0.0, 0.9, 0.95],
+        }
+    }
+}
+
+impl SafeAreaSettings {
+    /// Normalized safe area to lay UI out in (the whole screen unless simulating)
+    pub fn active_rect(&self) -> [f32; 4] {
+        if self.simulate { self.rect } else { [0.0, 0.0, 1.0, 1.0] }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputSettings {
+    /// Project-relative action map file (edited in Project Settings > Input)
+    pub action_map: String,
+    /// Clicks and typing that go to the game UI are hidden from gameplay input
+    pub ui_blocks_game_input: bool,
+}
+
+impl Default for InputSettings {
+    fn default() -> Self {
+        Self {
+            action_map: input::INPUT_CONFIG_FILE.to_string(),
+            ui_blocks_game_input: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApplicationSettings {
+    /// Project-relative scene the game starts in
+    pub startup_scene: Option<String>,
+    pub window_title: String,
+    pub window_width: u32,
+    pub window_height: u32,
+    pub resizable: bool,
+}
+
+impl Default for ApplicationSettings {
+    fn default() -> Self {
+        Self {
+            startup_scene: None,
+            window_title: "Game".to_string(),
+            window_width: 1280,
+            window_height: 720,
+            resizable: true,
+        }
+    }
+}
+
+/// Script time budget: a script running longer than `budget_ms` on one entity for more
+/// than `budget_frames` frames in a row logs a warning (once per script)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptingSettings {
+    /// 0 turns the warnings off
+    pub budget_ms: f32,
+    pub budget_frames: u32,
+    /// Project-relative folders require() searches after scripts/ ("lib" finds "lib/utils/math2d.lua")
+    pub lib_paths: Vec<String>,
+    /// get_position / get_velocity return native Vec2 values instead of {x, y, z} tables
+    pub vec2_values: bool,
+}
+
+impl Default for ScriptingSettings {
+    fn default() -> Self {
+        Self {
+            budget_ms: 2.0,
+            budget_frames: 60,
+            lib_paths: Vec::new(),
+            vec2_values: false,
+        }
+    }
+}
+
+impl ScriptingSettings {
+    /// The per-script budget, None when warnings are off
+    pub fn budget(&self) -> Option<std::time::Duration> {
+        (self.budget_ms > 0.0).then(|| std::time::Duration::from_secs_f32(self.budget_ms / 1000.0))
+    }
+}
+
+/// Screenshots and the rolling GIF buffer (F9 saves the last `gif_seconds`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptureSettings {
+    /// Keep the last `gif_seconds` x `gif_fps` frames in memory for GIFs
+    pub gif_enabled: bool,
+    pub gif_seconds: f32,
+    pub gif_fps: u32,
+    /// Frames are scaled down to this width
+    pub gif_width: u32,
+    /// Screenshots keep only the game area, without letterbox bars
+    pub crop_letterbox: bool,
+}
+
+impl Default for CaptureSettings {
+    fn default() -> Self {
+        Self {
+            gif_enabled: true,
+            gif_seconds: 5.0,
+            gif_fps: 12,
+            gif_width: 320,
+            crop_letterbox: true,
+        }
+    }
+}
+
+impl ProjectSettings {
+    /// Parse settings; missing sections and fields take their defaults
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// `<project>/settings.json`, or the defaults if the project has none or it can't be read.
+    /// Projects without a startup scene here use the game startup scene from project.json.
+    pub fn load(project_path: &Path) -> Self {
+        let path = project_path.join(PROJECT_SETTINGS_FILE);
+        let mut settings = match fs::read_to_string(&path) {
+            Ok(json) => Self::from_json(&json).unwrap_or_else(|e| {
+                log::warn!("Invalid {}, using defaults: {}", path.display(), e);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+
+        if settings.application.startup_scene.is_none() {
+            settings.application.startup_scene = fs::read_to_string(project_path.join("project.json"))
+                .ok()
+                .and_then(|json| serde_json::from_str::<crate::project::ProjectConfig>(&json).ok())
+                .and_then(|config| config.game_startup_scene.or(config.startup_scene))
+                .map(|scene| scene.to_string_lossy().replace('\\', "/"));
+        }
+        settings
+    }
+
+    pub fn save(&self, project_path: &Path) -> Result<()> {
+        fs::write(project_path.join(PROJECT_SETTINGS_FILE), self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Seconds per physics step, guarded against zero/negative values from hand-edited files
+    pub fn fixed_timestep(&self) -> f32 {
+        if self.physics.fixed_timestep > 0.0 {
+            self.physics.fixed_timestep
+        } else {
+            PhysicsSettings::default().fixed_timestep
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_settings_merge_with_defaults() {
+        let settings = ProjectSettings::from_json(r#"{ "physics": { "gravity": 9.8 }, "application": { "window_title": "Demo" } }"#).unwrap();
+        assert_eq!(settings.physics.gravity, 9.8);
+        assert_eq!(settings.physics.fixed_timestep, PhysicsSettings::default().fixed_timestep);
+        assert_eq!(settings.application.window_title, "Demo");
+        assert_eq!(settings.application.window_width, 1280);
+        assert_eq!(settings.rendering, RenderingSettings::default());
+
+        let empty = ProjectSettings::from_json("{}").unwrap();
+        assert_eq!(empty, ProjectSettings::default());
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("engine_core_settings_test_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("project.json"), r#"{"name":"Demo","description":"","version":"1.0","game_startup_scene":"scenes/level1.json"}"#).unwrap();
+
+        // No settings.json yet: defaults, startup scene from project.json
+        let mut settings = ProjectSettings::load(&dir);
+        assert_eq!(settings.application.startup_scene.as_deref(), Some("scenes/level1.json"));
+
+        settings.physics.gravity = 300.0;
+        settings.rendering.vsync = false;
+        settings.save(&dir).unwrap();
+        let reloaded = ProjectSettings::load(&dir);
+        assert_eq!(reloaded, settings);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_fixed_aspect_letterboxes_and_pillarboxes() {
+        let mut rendering = RenderingSettings { aspect_mode: AspectMode::FixedAspect, ..Default::default() };
+        rendering.reference_resolution = [1920, 1080];
+        let policy = rendering.aspect_policy();
+
+        // Too wide: bars left and right
+        assert_eq!(policy.game_rect([0.0, 0.0, 2000.0, 900.0]), [200.0, 0.0, 1600.0, 900.0]);
+        // Too tall: bars top and bottom, offset kept
+        assert_eq!(policy.game_rect([10.0, 20.0, 1600.0, 1200.0]), [10.0, 170.0, 1600.0, 900.0]);
+        assert_eq!(policy.vertical_scale(2.0), 1.0);
+
+        rendering.aspect_mode = AspectMode::FixedHorizontal;
+        let policy = rendering.aspect_policy();
+        assert_eq!(policy.game_rect([0.0, 0.0, 2000.0, 900.0]), [0.0, 0.0, 2000.0, 900.0]);
+        // Half as wide as the reference: twice the height to keep the width
+        assert!((policy.vertical_scale(16.0 / 18.0) - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_layer_matrix_is_symmetric() {
+        let mut physics = PhysicsSettings::default();
+        assert!(physics.layers_collide(0, 3));
+
+        physics.set_layers_collide(1, 3, false);
+        assert!(!physics.layers_collide(1, 3));
+        assert!(!physics.layers_collide(3, 1));
+        assert!(physics.layers_collide(0, 3));
+        assert_eq!(physics.collision_mask(1), !(1 << 3));
+
+        physics.set_layers_collide(3, 1, true);
+        assert!(physics.layers_collide(1, 3));
+    }
+}
@@ -0,0 +1,320 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::Result;
+
+// ==================================================================================
+// Persistent Save Data (PlayerPrefs-style)
+// ==================================================================================
+
+/// File name of the save file inside a project's persistent data folder.
+pub const SAVE_FILE_NAME: &str = "save.json";
+
+/// A single value stored in the save file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum SaveValue {
+    Int(i64),
+    Float(f64),
+    String(String),
+    Bool(bool),
+}
+
+impl SaveValue {
+    /// Short type name used by the editor's saved data panel.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            SaveValue::Int(_) => "int",
+            SaveValue::Float(_) => "float",
+            SaveValue::String(_) => "string",
+            SaveValue::Bool(_) => "bool",
+        }
+    }
+}
+
+impl std::fmt::Display for SaveValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveValue::Int(v) => write!(f, "{}", v),
+            SaveValue::Float(v) => write!(f, "{}", v),
+            SaveValue::String(v) => write!(f, "\"{}\"", v),
+            SaveValue::Bool(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// Key-value store for game progress, persisted as JSON.
+///
+/// Every project gets its own folder under [`persistent_data_dir`], so keys never
+/// collide between projects. Writes go to a temporary file which is then renamed
+/// over the real one, so a crash mid-save leaves the previous file intact.
+#[derive(Debug, Clone, Default)]
+pub struct SaveData {
+    path: Option<PathBuf>,
+    values: BTreeMap<String, SaveValue>,
+    dirty: bool,
+}
+
+impl SaveData {
+    /// Create an in-memory store that is never written to disk.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a store backed by a specific file.
+    pub fn with_path(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            values: BTreeMap::new(),
+            dirty: false,
+        }
+    }
+
+    /// Create a store for a project, located in the project's persistent data folder.
+    pub fn for_project(project_name: &str) -> Self {
+        Self::with_path(project_data_dir(project_name).join(SAVE_FILE_NAME))
+    }
+
+    /// Path of the backing file (None for in-memory stores).
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Reload all values from disk, discarding unsaved changes.
+    /// A missing file is not an error and simply yields an empty store.
+    pub fn load(&mut self) -> Result<()> {
+        self.values.clear();
+        self.dirty = false;
+
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(path)?;
+        self.values = serde_json::from_str(&content)?;
+        Ok(())
+    }
+
+    /// Flush all values to disk atomically (write temp file + rename).
+    pub fn save(&mut self) -> Result<()> {
+        let Some(path) = &self.path else {
+            self.dirty = false;
+            return Ok(());
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let json = serde_json::to_string_pretty(&self.values)?;
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)?;
+        fs::rename(&tmp_path, path)?;
+
+        self.dirty = false;
+        Ok(())
+    }
+
+    /// True if values changed since the last load/save.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: SaveValue) {
+        self.values.insert(key.into(), value);
+        self.dirty = true;
+    }
+
+    pub fn get(&self, key: &str) -> Option<&SaveValue> {
+        self.values.get(key)
+    }
+
+    pub fn set_int(&mut self, key: impl Into<String>, value: i64) {
+        self.set(key, SaveValue::Int(value));
+    }
+
+    pub fn set_float(&mut self, key: impl Into<String>, value: f64) {
+        self.set(key, SaveValue::Float(value));
+    }
+
+    pub fn set_string(&mut self, key: impl Into<String>, value: impl Into<String>) {
+        self.set(key, SaveValue::String(value.into()));
+    }
+
+    pub fn set_bool(&mut self, key: impl Into<String>, value: bool) {
+        self.set(key, SaveValue::Bool(value));
+    }
+
+    /// Get an int, falling back to `default` if missing or of another type.
+    /// Floats are truncated so values written from Lua numbers still read back.
+    pub fn get_int(&self, key: &str, default: i64) -> i64 {
+        match self.values.get(key) {
+            Some(SaveValue::Int(v)) => *v,
+            Some(SaveValue::Float(v)) => *v as i64,
+            _ => default,
+        }
+    }
+
+    pub fn get_float(&self, key: &str, default: f64) -> f64 {
+        match self.values.get(key) {
+            Some(SaveValue::Float(v)) => *v,
+            Some(SaveValue::Int(v)) => *v as f64,
+            _ => default,
+        }
+    }
+
+    pub fn get_string(&self, key: &str, default: &str) -> String {
+        match self.values.get(key) {
+            Some(SaveValue::String(v)) => v.clone(),
+            _ => default.to_string(),
+        }
+    }
+
+    pub fn get_bool(&self, key: &str, default: bool) -> bool {
+        match self.values.get(key) {
+            Some(SaveValue::Bool(v)) => *v,
+            _ => default,
+        }
+    }
+
+    pub fn has_key(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Remove a key. Returns true if it existed.
+    pub fn delete(&mut self, key: &str) -> bool {
+        let removed = self.values.remove(key).is_some();
+        if removed {
+            self.dirty = true;
+        }
+        removed
+    }
+
+    /// Remove all keys.
+    pub fn clear(&mut self) {
+        if !self.values.is_empty() {
+            self.values.clear();
+            self.dirty = true;
+        }
+    }
+
+    /// Iterate over all stored entries, sorted by key.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &SaveValue)> {
+        self.values.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// Root folder for persistent game data (saves, settings written at runtime).
+///
+/// - Windows: `%APPDATA%/RustGameEngine`
+/// - macOS: `~/Library/Application Support/RustGameEngine`
+/// - Other: `$XDG_DATA_HOME/RustGameEngine` or `~/.local/share/RustGameEngine`
+pub fn persistent_data_dir() -> PathBuf {
+    const APP_DIR: &str = "RustGameEngine";
+
+    #[cfg(target_os = "windows")]
+    if let Some(appdata) = std::env::var_os("APPDATA") {
+        return PathBuf::from(appdata).join(APP_DIR);
+    }
+
+    #[cfg(target_os = "macos")]
+    if let Some(home) = std::env::var_os("HOME") {
+        return PathBuf::from(home).join("Library/Application Support").join(APP_DIR);
+    }
+
+    if let Some(xdg) = std::env::var_os("XDG_DATA_HOME") {
+        return PathBuf::from(xdg).join(APP_DIR);
+    }
+    if let Some(home) = std::env::var_os("HOME") {
+        return PathBuf::from(home).join(".local/share").join(APP_DIR);
+    }
+
+    PathBuf::from(".").join(".persistent")
+}
+
+/// Persistent data folder for a single project.
+/// The name is sanitized so it is always a valid single path component.
+pub fn project_data_dir(project_name: &str) -> PathBuf {
+    let sanitized: String = project_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect();
+    let sanitized = sanitized.trim();
+    let folder = if sanitized.is_empty() { "Untitled" } else { sanitized };
+    persistent_data_dir().join(folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_save_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("engine_core_save_test_{}_{}", name, std::process::id()))
+            .join(SAVE_FILE_NAME)
+    }
+
+    #[test]
+    fn test_get_returns_default_for_missing_or_mismatched() {
+        let mut data = SaveData::new();
+        assert_eq!(data.get_int("coins", 7), 7);
+
+        data.set_string("coins", "many");
+        assert_eq!(data.get_int("coins", 7), 7);
+        assert_eq!(data.get_string("coins", ""), "many");
+    }
+
+    #[test]
+    fn test_save_and_load_roundtrip() {
+        let path = temp_save_path("roundtrip");
+        let mut data = SaveData::with_path(&path);
+        data.set_int("level", 3);
+        data.set_float("volume", 0.5);
+        data.set_bool("tutorial_done", true);
+        data.set_string("player", "Ana");
+        data.save().unwrap();
+        assert!(!data.is_dirty());
+
+        let mut reloaded = SaveData::with_path(&path);
+        reloaded.load().unwrap();
+        assert_eq!(reloaded.get_int("level", 0), 3);
+        assert_eq!(reloaded.get_float("volume", 0.0), 0.5);
+        assert!(reloaded.get_bool("tutorial_done", false));
+        assert_eq!(reloaded.get_string("player", ""), "Ana");
+
+        // No temp file left behind after an atomic save
+        assert!(!path.with_extension("json.tmp").exists());
+
+        fs::remove_dir_all(path.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_delete_and_clear() {
+        let mut data = SaveData::new();
+        data.set_int("a", 1);
+        data.set_int("b", 2);
+        assert!(data.delete("a"));
+        assert!(!data.delete("a"));
+        assert_eq!(data.len(), 1);
+        data.clear();
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn test_project_dirs_are_namespaced() {
+        assert_ne!(project_data_dir("Game A"), project_data_dir("Game B"));
+        assert!(project_data_dir("../evil").starts_with(persistent_data_dir()));
+    }
+}
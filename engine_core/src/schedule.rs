@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+use std::fmt;
+
+// ==================================================================================
+// Module Schedule (stage + before/after ordering)
+// ==================================================================================
+
+/// Frame stage an engine module runs in. Stages run in declaration order every frame;
+/// `FixedUpdate` runs zero or more times per frame at the fixed timestep.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ModuleStage {
+    PreUpdate,
+    Update,
+    FixedUpdate,
+    PostUpdate,
+    Render,
+}
+
+impl ModuleStage {
+    pub const ALL: [ModuleStage; 5] = [
+        ModuleStage::PreUpdate,
+        ModuleStage::Update,
+        ModuleStage::FixedUpdate,
+        ModuleStage::PostUpdate,
+        ModuleStage::Render,
+    ];
+}
+
+/// Ordering constraint against another module (by name) in the same stage.
+/// Constraints naming modules that aren't registered, or that are in another stage, are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModuleOrder {
+    Before(String),
+    After(String),
+}
+
+/// What the scheduler needs to know about a registered module
+#[derive(Debug, Clone)]
+pub struct ScheduleEntry {
+    pub name: String,
+    pub stage: ModuleStage,
+    pub ordering: Vec<ModuleOrder>,
+}
+
+/// Ordering constraints that can't all hold; `modules` is the cycle in order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleCycleError {
+    pub modules: Vec<String>,
+}
+
+impl fmt::Display for ScheduleCycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut path = self.modules.clone();
+        if let Some(first) = self.modules.first() {
+            path.push(first.clone());
+        }
+        write!(f, "Engine module dependency cycle: {}", path.join(" -> "))
+    }
+}
+
+impl std::error::Error for ScheduleCycleError {}
+
+/// Module names per stage in run order. Within a stage, modules without constraints
+/// between them keep their registration order.
+pub fn resolve_schedule(entries: &[ScheduleEntry]) -> Result<Vec<(ModuleStage, Vec<String>)>, ScheduleCycleError> {
+    let mut stages = Vec::new();
+    for stage in ModuleStage::ALL {
+        let members: Vec<&ScheduleEntry> = entries.iter().filter(|e| e.stage == stage).collect();
+        stages.push((stage, sort_stage(&members)?));
+    }
+    Ok(stages)
+}
+
+fn sort_stage(members: &[&ScheduleEntry]) -> Result<Vec<String>, ScheduleCycleError> {
+    let index: HashMap<&str, usize> = members.iter().enumerate().map(|(i, e)| (e.name.as_str(), i)).collect();
+
+    // Edge a -> b: a runs before b
+    let mut successors: Vec<Vec<usize>> = vec![Vec::new(); members.len()];
+    for (i, entry) in members.iter().enumerate() {
+        for order in &entry.ordering {
+            let (from, to) = match order {
+                ModuleOrder::Before(other) => match index.get(other.as_str()) {
+                    Some(&j) => (i, j),
+                    None => continue,
+                },
+                ModuleOrder::After(other) => match index.get(other.as_str()) {
+                    Some(&j) => (j, i),
+                    None => continue,
+                },
+            };
+            if !successors[from].contains(&to) {
+                successors[from].push(to);
+            }
+        }
+    }
+
+    let mut in_degree = vec![0usize; members.len()];
+    for edges in &successors {
+        for &to in edges {
+            in_degree[to] += 1;
+        }
+    }
+
+    // Kahn's algorithm, always taking the earliest-registered ready module
+    let mut order = Vec::with_capacity(members.len());
+    let mut done = vec![false; members.len()];
+    while let Some(next) = (0..members.len()).find(|&i| !done[i] && in_degree[i] == 0) {
+        done[next] = true;
+        order.push(members[next].name.clone());
+        for &to in &successors[next] {
+            in_degree[to] -= 1;
+        }
+    }
+
+    if order.len() < members.len() {
+        let remaining: Vec<usize> = (0..members.len()).filter(|&i| !done[i]).collect();
+        let cycle = find_cycle(&successors, &remaining);
+        return Err(ScheduleCycleError {
+            modules: cycle.into_iter().map(|i| members[i].name.clone()).collect(),
+        });
+    }
+    Ok(order)
+}
+
+/// Walk predecessors among the unsorted modules until one repeats; every unsorted
+/// module still has an unsorted predecessor, so the walk always closes a loop
+fn find_cycle(successors: &[Vec<usize>], remaining: &[usize]) -> Vec<usize> {
+    let Some(&start) = remaining.first() else {
+        return Vec::new();
+    };
+    let mut path = vec![start];
+    let mut current = start;
+    loop {
+        let Some(&previous) = remaining.iter().find(|&&p| successors[p].contains(&current)) else {
+            return path;
+        };
+        if let Some(position) = path.iter().position(|&i| i == previous) {
+            // Built backwards: reverse into run order
+            let mut cycle = path.split_off(position);
+            cycle.reverse();
+            return cycle;
+        }
+        path.push(previous);
+        current = previous;
+    }
+}
+
+/// Human-readable schedule, one stage per line (for logs and the console)
+pub fn describe_schedule(schedule: &[(ModuleStage, Vec<String>)]) -> String {
+    schedule
+        .iter()
+        .map(|(stage, modules)| {
+            let modules = if modules.is_empty() { "-".to_string() } else { modules.join(" -> ") };
+            format!("{:?}: {}", stage, modules)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, stage: ModuleStage, ordering: Vec<ModuleOrder>) -> ScheduleEntry {
+        ScheduleEntry { name: name.to_string(), stage, ordering }
+    }
+
+    #[test]
+    fn test_constraints_reorder_within_stage() {
+        let entries = vec![
+            entry("physics", ModuleStage::Update, vec![ModuleOrder::After("scripts".into())]),
+            entry("audio", ModuleStage::PostUpdate, vec![]),
+            entry("scripts", ModuleStage::Update, vec![]),
+            entry("input", ModuleStage::Update, vec![ModuleOrder::Before("scripts".into()), ModuleOrder::After("missing".into())]),
+        ];
+        let schedule = resolve_schedule(&entries).unwrap();
+        assert_eq!(schedule[1], (ModuleStage::Update, vec!["input".to_string(), "scripts".to_string(), "physics".to_string()]));
+        assert_eq!(schedule[3].1, vec!["audio".to_string()]);
+        assert!(describe_schedule(&schedule).contains("Update: input -> scripts -> physics"));
+    }
+
+    #[test]
+    fn test_cycle_names_modules() {
+        let entries = vec![
+            entry("free", ModuleStage::Update, vec![]),
+            entry("downstream", ModuleStage::Update, vec![ModuleOrder::After("a".into())]),
+            entry("a", ModuleStage::Update, vec![ModuleOrder::Before("b".into())]),
+            entry("b", ModuleStage::Update, vec![ModuleOrder::Before("c".into())]),
+            entry("c", ModuleStage::Update, vec![ModuleOrder::Before("a".into())]),
+        ];
+        let error = resolve_schedule(&entries).unwrap_err();
+        let mut modules = error.modules.clone();
+        modules.sort();
+        assert_eq!(modules, vec!["a", "b", "c"]);
+        assert!(error.to_string().contains("->"));
+    }
+}
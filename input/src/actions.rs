@@ -0,0 +1,361 @@
+// Input Actions - named actions bound to keys, mouse buttons and gamepad buttons/axes
+// A project keeps one profile per player in `input.json`; profile N reads the gamepad
+// set in `InputProfile::gamepad`.
+
+use crate::{GamepadAxis, GamepadButton, Key, MouseButton};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+
+/// Project-relative path of the input config
+pub const INPUT_CONFIG_FILE: &str = "input.json";
+
+/// Built-in axis actions behind `InputSystem::get_movement_input`
+pub const MOVE_X: &str = "move_x";
+pub const MOVE_Y: &str = "move_y";
+
+/// Default action that an on-screen button also feeds into `get_action_button`
+pub const JUMP: &str = "jump";
+
+/// Axis values at or beyond this count as "down" for button checks
+pub const AXIS_PRESS_THRESHOLD: f32 = 0.5;
+
+// ============================================================================
+// BINDINGS
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ActionKind {
+    /// On/off (jump, fire)
+    #[default]
+    Button,
+    /// -1.0 to 1.0 (move_x)
+    Axis,
+}
+
+/// One physical input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "device")]
+pub enum InputSource {
+    Key { key: Key },
+    Mouse { button: MouseButton },
+    GamepadButton { button: GamepadButton },
+    GamepadAxis { axis: GamepadAxis },
+}
+
+impl InputSource {
+    pub fn is_axis(&self) -> bool {
+        matches!(self, InputSource::GamepadAxis { .. })
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Key { key } => write!(f, "Key {:?}", key),
+            InputSource::Mouse { button } => write!(f, "Mouse {:?}", button),
+            InputSource::GamepadButton { button } => write!(f, "Gamepad {:?}", button),
+            InputSource::GamepadAxis { axis } => write!(f, "Gamepad {:?}", axis),
+        }
+    }
+}
+
+/// An input source assigned to an action
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ActionBinding {
+    #[serde(flatten)]
+    pub source: InputSource,
+    /// Multiplier: -1.0 makes a key push an axis action negative, or makes a button
+    /// action fire on the negative side of a stick
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl ActionBinding {
+    pub fn new(source: InputSource) -> Self {
+        Self { source, scale: 1.0 }
+    }
+
+    pub fn key(key: Key) -> Self {
+        Self::new(InputSource::Key { key })
+    }
+
+    pub fn mouse(button: MouseButton) -> Self {
+        Self::new(InputSource::Mouse { button })
+    }
+
+    pub fn gamepad_button(button: GamepadButton) -> Self {
+        Self::new(InputSource::GamepadButton { button })
+    }
+
+    pub fn gamepad_axis(axis: GamepadAxis) -> Self {
+        Self::new(InputSource::GamepadAxis { axis })
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Two bindings fire from the same physical input. Opposite directions of one
+    /// stick axis don't overlap.
+    pub fn overlaps(&self, other: &ActionBinding) -> bool {
+        self.source == other.source
+            && (!self.source.is_axis() || self.scale.signum() == other.scale.signum())
+    }
+}
+
+impl fmt::Display for ActionBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.source.is_axis(), self.scale < 0.0) {
+            (true, true) => write!(f, "{} (-)", self.source),
+            (true, false) => write!(f, "{} (+)", self.source),
+            (false, true) => write!(f, "{} (-1)", self.source),
+            (false, false) => write!(f, "{}", self.source),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputAction {
+    pub name: String,
+    #[serde(default)]
+    pub kind: ActionKind,
+    #[serde(default)]
+    pub bindings: Vec<ActionBinding>,
+}
+
+impl InputAction {
+    pub fn new(name: impl Into<String>, kind: ActionKind, bindings: Vec<ActionBinding>) -> Self {
+        Self { name: name.into(), kind, bindings }
+    }
+}
+
+// ============================================================================
+// PROFILES
+// ============================================================================
+
+/// Why a binding was refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum BindingError {
+    UnknownPlayer(usize),
+    UnknownAction(String),
+    /// The input is already used by these actions; nothing was changed
+    Conflict { binding: ActionBinding, actions: Vec<String> },
+}
+
+impl fmt::Display for BindingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindingError::UnknownPlayer(player) => write!(f, "no input profile for player {}", player),
+            BindingError::UnknownAction(name) => write!(f, "unknown action '{}'", name),
+            BindingError::Conflict { binding, actions } => {
+                write!(f, "{} is already bound to {}", binding, actions.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for BindingError {}
+
+/// Actions of one player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputProfile {
+    pub name: String,
+    /// Gamepad this player's gamepad bindings read
+    #[serde(default)]
+    pub gamepad: usize,
+    #[serde(default)]
+    pub actions: Vec<InputAction>,
+}
+
+impl InputProfile {
+    /// Profile with the built-in actions
+    pub fn new(name: impl Into<String>, gamepad: usize) -> Self {
+        Self { name: name.into(), gamepad, actions: Self::default_actions() }
+    }
+
+    /// move_x / move_y (WASD, arrows, left stick), jump and fire
+    pub fn default_actions() -> Vec<InputAction> {
+        vec![
+            InputAction::new(MOVE_X, ActionKind::Axis, vec![
+                ActionBinding::key(Key::A).with_scale(-1.0),
+                ActionBinding::key(Key::Left).with_scale(-1.0),
+                ActionBinding::key(Key::D),
+                ActionBinding::key(Key::Right),
+                ActionBinding::gamepad_axis(GamepadAxis::LeftStickX),
+            ]),
+            // Screen-style Y (down is positive), same as get_movement_input always returned
+            InputAction::new(MOVE_Y, ActionKind::Axis, vec![
+                ActionBinding::key(Key::W).with_scale(-1.0),
+                ActionBinding::key(Key::Up).with_scale(-1.0),
+                ActionBinding::key(Key::S),
+                ActionBinding::key(Key::Down),
+                ActionBinding::gamepad_axis(GamepadAxis::LeftStickY),
+            ]),
+            InputAction::new(JUMP, ActionKind::Button, vec![
+                ActionBinding::key(Key::Space),
+                ActionBinding::gamepad_button(GamepadButton::South),
+            ]),
+            InputAction::new("fire", ActionKind::Button, vec![
+                ActionBinding::mouse(MouseButton::Left),
+                ActionBinding::gamepad_button(GamepadButton::West),
+            ]),
+        ]
+    }
+
+    pub fn action(&self, name: &str) -> Option<&InputAction> {
+        self.actions.iter().find(|action| action.name == name)
+    }
+
+    pub fn action_mut(&mut self, name: &str) -> Option<&mut InputAction> {
+        self.actions.iter_mut().find(|action| action.name == name)
+    }
+
+    /// Actions other than `except` that already use `binding`'s input
+    pub fn conflicts(&self, binding: &ActionBinding, except: Option<&str>) -> Vec<String> {
+        self.actions
+            .iter()
+            .filter(|action| Some(action.name.as_str()) != except)
+            .filter(|action| action.bindings.iter().any(|b| b.overlaps(binding)))
+            .map(|action| action.name.clone())
+            .collect()
+    }
+
+    /// Bind `binding` to `action`, replacing binding `slot` (or appending when None or
+    /// out of range). Fails without changing anything if another action uses the input.
+    pub fn bind(&mut self, action: &str, binding: ActionBinding, slot: Option<usize>) -> Result<(), BindingError> {
+        if self.action(action).is_none() {
+            return Err(BindingError::UnknownAction(action.to_string()));
+        }
+        let conflicts = self.conflicts(&binding, Some(action));
+        if !conflicts.is_empty() {
+            return Err(BindingError::Conflict { binding, actions: conflicts });
+        }
+        self.set_binding(action, binding, slot);
+        Ok(())
+    }
+
+    /// Like `bind`, but removes the input from any conflicting action first.
+    /// Returns the actions that lost the binding.
+    pub fn bind_replacing(&mut self, action: &str, binding: ActionBinding, slot: Option<usize>) -> Result<Vec<String>, BindingError> {
+        if self.action(action).is_none() {
+            return Err(BindingError::UnknownAction(action.to_string()));
+        }
+        let conflicts = self.conflicts(&binding, Some(action));
+        for other in &mut self.actions {
+            if conflicts.contains(&other.name) {
+                other.bindings.retain(|b| !b.overlaps(&binding));
+            }
+        }
+        self.set_binding(action, binding, slot);
+        Ok(conflicts)
+    }
+
+    fn set_binding(&mut self, action: &str, binding: ActionBinding, slot: Option<usize>) {
+        let Some(action) = self.action_mut(action) else {
+            return;
+        };
+        match slot.filter(|&slot| slot < action.bindings.len()) {
+            Some(slot) => action.bindings[slot] = binding,
+            None if action.bindings.iter().any(|b| b.overlaps(&binding)) => {}
+            None => action.bindings.push(binding),
+        }
+    }
+
+    /// Add any missing built-in action (move_x / move_y)
+    pub fn ensure_builtin_actions(&mut self) {
+        for builtin in Self::default_actions() {
+            let is_movement = builtin.name == MOVE_X || builtin.name == MOVE_Y;
+            if is_movement && self.action(&builtin.name).is_none() {
+                self.actions.push(builtin);
+            }
+        }
+    }
+}
+
+// ============================================================================
+// PROJECT CONFIG
+// ============================================================================
+
+/// Contents of a project's `input.json`: one profile per player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InputConfig {
+    pub profiles: Vec<InputProfile>,
+}
+
+impl Default for InputConfig {
+    fn default() -> Self {
+        Self { profiles: vec![InputProfile::new("Player 1", 0)] }
+    }
+}
+
+impl InputConfig {
+    /// Parse a config; missing built-in actions and an empty profile list are filled in
+    pub fn from_json(json: &str) -> serde_json::Result<Self> {
+        let mut config: InputConfig = serde_json::from_str(json)?;
+        if config.profiles.is_empty() {
+            config.profiles.push(InputProfile::new("Player 1", 0));
+        }
+        for profile in &mut config.profiles {
+            profile.ensure_builtin_actions();
+        }
+        Ok(config)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let json = std::fs::read_to_string(path)?;
+        Self::from_json(&json).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// `<project>/input.json`, or the defaults if the project has none
+    pub fn load_for_project(project_path: &Path) -> std::io::Result<Self> {
+        let path = project_path.join(INPUT_CONFIG_FILE);
+        if path.exists() {
+            Self::load(&path)
+        } else {
+            Ok(Self::default())
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        let json = self.to_json().map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, json)
+    }
+
+    pub fn profile(&self, player: usize) -> Option<&InputProfile> {
+        self.profiles.get(player)
+    }
+
+    pub fn profile_mut(&mut self, player: usize) -> Option<&mut InputProfile> {
+        self.profiles.get_mut(player)
+    }
+
+    /// Every input bound to more than one action: (player, binding, actions)
+    pub fn find_conflicts(&self) -> Vec<(usize, ActionBinding, Vec<String>)> {
+        let mut found: Vec<(usize, ActionBinding, Vec<String>)> = Vec::new();
+        for (player, profile) in self.profiles.iter().enumerate() {
+            for action in &profile.actions {
+                for binding in &action.bindings {
+                    let already_reported = found.iter().any(|(p, b, _)| *p == player && b.overlaps(binding));
+                    if already_reported {
+                        continue;
+                    }
+                    let actions = profile.conflicts(binding, None);
+                    if actions.len() > 1 {
+                        found.push((player, *binding, actions));
+                    }
+                }
+            }
+        }
+        found
+    }
+}
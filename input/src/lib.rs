@@ -5,6 +5,18 @@ use glam::Vec2;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+pub mod actions;
+pub use actions::{
+    ActionBinding, ActionKind, BindingError, InputAction, InputConfig, InputProfile, InputSource,
+    INPUT_CONFIG_FILE,
+};
+
+pub mod virtual_controls;
+pub use virtual_controls::{
+    ScreenRect, VirtualButtonLayout, VirtualControls, VirtualControlsLayout, VirtualJoystickLayout,
+    VirtualJoystickState,
+};
+
 // ============================================================================
 // KEYBOARD INPUT
 // ============================================================================
@@ -34,6 +46,19 @@ pub enum Key {
 }
 
 impl Key {
+    /// Every key, in declaration order (for binding pickers)
+    pub const ALL: &'static [Key] = &[
+        Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I, Key::J, Key::K, Key::L, Key::M,
+        Key::N, Key::O, Key::P, Key::Q, Key::R, Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z,
+        Key::Num0, Key::Num1, Key::Num2, Key::Num3, Key::Num4, Key::Num5, Key::Num6, Key::Num7, Key::Num8, Key::Num9,
+        Key::Up, Key::Down, Key::Left, Key::Right,
+        Key::F1, Key::F2, Key::F3, Key::F4, Key::F5, Key::F6, Key::F7, Key::F8, Key::F9, Key::F10, Key::F11, Key::F12,
+        Key::Space, Key::Enter, Key::Escape, Key::Tab, Key::Backspace, Key::Delete,
+        Key::LShift, Key::RShift, Key::LCtrl, Key::RCtrl, Key::LAlt, Key::RAlt,
+        Key::Minus, Key::Equals, Key::LeftBracket, Key::RightBracket,
+        Key::Semicolon, Key::Quote, Key::Comma, Key::Period, Key::Slash, Key::Backslash,
+    ];
+
     /// Convert from winit key code name
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
@@ -149,6 +174,12 @@ pub enum MouseButton {
     Forward,
 }
 
+impl MouseButton {
+    pub const ALL: &'static [MouseButton] = &[
+        MouseButton::Left, MouseButton::Right, MouseButton::Middle, MouseButton::Back, MouseButton::Forward,
+    ];
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct MouseState {
     pub position: Vec2,           // Screen position
@@ -177,6 +208,15 @@ pub enum GamepadButton {
     DPadUp, DPadDown, DPadLeft, DPadRight,
 }
 
+impl GamepadButton {
+    pub const ALL: &'static [GamepadButton] = &[
+        GamepadButton::South, GamepadButton::East, GamepadButton::North, GamepadButton::West,
+        GamepadButton::L1, GamepadButton::R1, GamepadButton::L2, GamepadButton::R2,
+        GamepadButton::L3, GamepadButton::R3, GamepadButton::Start, GamepadButton::Select,
+        GamepadButton::DPadUp, GamepadButton::DPadDown, GamepadButton::DPadLeft, GamepadButton::DPadRight,
+    ];
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GamepadAxis {
     LeftStickX,
@@ -187,6 +227,13 @@ pub enum GamepadAxis {
     RightTrigger,
 }
 
+impl GamepadAxis {
+    pub const ALL: &'static [GamepadAxis] = &[
+        GamepadAxis::LeftStickX, GamepadAxis::LeftStickY, GamepadAxis::RightStickX, GamepadAxis::RightStickY,
+        GamepadAxis::LeftTrigger, GamepadAxis::RightTrigger,
+    ];
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct GamepadState {
     pub connected: bool,
@@ -194,6 +241,7 @@ pub struct GamepadState {
     pub buttons_pressed: HashSet<GamepadButton>,
     pub buttons_released: HashSet<GamepadButton>,
     pub axes: HashMap<GamepadAxis, f32>,
+    pub axes_last_frame: HashMap<GamepadAxis, f32>,  // For action pressed/released on axes
     pub left_stick: Vec2,   // -1.0 to 1.0
     pub right_stick: Vec2,  // -1.0 to 1.0
 }
@@ -205,7 +253,7 @@ pub struct GamepadState {
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TouchId(pub u64);
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum TouchPhase {
     Started,
     Moved,
@@ -227,6 +275,77 @@ pub struct TouchState {
     pub ended_this_frame: Vec<Touch>,
 }
 
+// ============================================================================
+// UI CAPTURE
+// ============================================================================
+
+/// What the game UI takes input from this frame (reported by the UI, see
+/// `InputSystem::set_ui_capture`)
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct UICapture {
+    /// Interactable UI in mouse coordinates: presses starting here belong to the UI
+    pub blockers: Vec<ScreenRect>,
+    /// A UI text field has keyboard focus: keys pressed now are typing, not gameplay
+    pub keyboard: bool,
+}
+
+// ============================================================================
+// INPUT EVENTS (recording / replay)
+// ============================================================================
+
+/// One raw state change. Every device update goes through an event so a run can
+/// be recorded and fed back later (see `start_recording` / `set_playback`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum InputEvent {
+    KeyPressed { key: Key },
+    KeyReleased { key: Key },
+    MouseMoved { x: f32, y: f32 },
+    /// Raw pointer motion (locked cursor): adds to the frame's delta, position stays put
+    MouseMotion { dx: f32, dy: f32 },
+    MouseButtonPressed { button: MouseButton },
+    MouseButtonReleased { button: MouseButton },
+    MouseScroll { x: f32, y: f32 },
+    GamepadConnected { id: usize, connected: bool },
+    GamepadButtonPressed { id: usize, button: GamepadButton },
+    GamepadButtonReleased { id: usize, button: GamepadButton },
+    /// Value after deadzone and Y inversion
+    GamepadAxisChanged { id: usize, axis: GamepadAxis, value: f32 },
+    Touch { id: u64, x: f32, y: f32, phase: TouchPhase },
+}
+
+// ============================================================================
+// REBINDING
+// ============================================================================
+
+/// Outcome of `InputSystem::start_rebind`
+#[derive(Debug, Clone, PartialEq)]
+pub enum RebindResult {
+    Bound { player: usize, action: String, binding: ActionBinding },
+    /// The captured input is used by other actions; bindings are unchanged until
+    /// `accept_rebind_conflict` is called
+    Conflict { player: usize, action: String, slot: Option<usize>, binding: ActionBinding, conflicts: Vec<String> },
+    /// Escape was pressed
+    Cancelled { player: usize, action: String },
+}
+
+#[derive(Debug, Clone)]
+struct RebindRequest {
+    player: usize,
+    action: String,
+    slot: Option<usize>,
+}
+
+/// Playing rumble effects per gamepad (dropping an effect stops it)
+#[derive(Default)]
+struct RumbleEffects(HashMap<usize, gilrs::ff::Effect>);
+
+impl std::fmt::Debug for RumbleEffects {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
 // ============================================================================
 // UNIFIED INPUT SYSTEM
 // ============================================================================
@@ -246,9 +365,32 @@ pub struct InputSystem {
 
     // Touch
     pub touch: TouchState,
+    // On-screen joystick/buttons (player 1)
+    virtual_controls: VirtualControls,
 
     // Gilrs context for gamepad support
     gilrs: Option<gilrs::Gilrs>,
+
+    // Events captured since the last `take_recorded` (None = not recording)
+    recording: Option<Vec<InputEvent>>,
+    // Replay playback: device input is ignored, only `apply_event` changes state
+    playback: bool,
+
+    // Action mapping (one profile per player)
+    config: InputConfig,
+    // Waiting for the next pressed input to bind
+    rebind: Option<RebindRequest>,
+    rebind_result: Option<RebindResult>,
+
+    // Force feedback
+    rumble: RumbleEffects,
+
+    // UI in front of the game; presses/keys it consumed stay hidden until released
+    ui_capture: UICapture,
+    ui_mouse: HashSet<MouseButton>,
+    ui_keys: HashSet<Key>,
+    // Gameplay queries see consumed input too (UI doesn't block)
+    ui_passthrough: bool,
 }
 
 impl InputSystem {
@@ -262,7 +404,18 @@ impl InputSystem {
             mouse: MouseState::default(),
             gamepads: Default::default(),
             touch: TouchState::default(),
+            virtual_controls: VirtualControls::default(),
             gilrs,
+            recording: None,
+            playback: false,
+            config: InputConfig::default(),
+            rebind: None,
+            rebind_result: None,
+            rumble: RumbleEffects::default(),
+            ui_capture: UICapture::default(),
+            ui_mouse: HashSet::new(),
+            ui_keys: HashSet::new(),
+            ui_passthrough: false,
         }
     }
 
@@ -270,51 +423,46 @@ impl InputSystem {
     // KEYBOARD METHODS
     // ========================================================================
 
-    /// Check if a key is currently pressed
+    /// Check if a key is currently pressed (not while typing into UI)
     pub fn is_key_down(&self, key: Key) -> bool {
-        self.keys.contains(&key)
+        self.keys.contains(&key) && !self.is_key_consumed(key)
     }
 
-    /// Check if a key was just pressed this frame
+    /// Check if a key was just pressed this frame (not while typing into UI)
     pub fn is_key_pressed(&self, key: Key) -> bool {
-        self.keys_pressed.contains(&key)
+        self.keys_pressed.contains(&key) && !self.is_key_consumed(key)
     }
 
-    /// Check if a key was just released this frame
+    /// Check if a key was just released this frame (not while typing into UI)
     pub fn is_key_released(&self, key: Key) -> bool {
-        self.keys_released.contains(&key)
+        self.keys_released.contains(&key) && !self.is_key_consumed(key)
     }
 
     /// Press a key
     pub fn press_key(&mut self, key: Key) {
-        if !self.keys.contains(&key) {
-            self.keys_pressed.insert(key);
-        }
-        self.keys.insert(key);
+        self.submit(InputEvent::KeyPressed { key });
     }
 
     /// Release a key
     pub fn release_key(&mut self, key: Key) {
-        if self.keys.contains(&key) {
-            self.keys_released.insert(key);
-        }
-        self.keys.remove(&key);
+        self.submit(InputEvent::KeyReleased { key });
     }
 
     // ========================================================================
     // MOUSE METHODS
     // ========================================================================
 
+    /// Button held, unless the press went to the UI
     pub fn is_mouse_button_down(&self, button: MouseButton) -> bool {
-        self.mouse.buttons.contains(&button)
+        self.mouse.buttons.contains(&button) && !self.is_mouse_button_consumed(button)
     }
 
     pub fn is_mouse_button_pressed(&self, button: MouseButton) -> bool {
-        self.mouse.buttons_pressed.contains(&button)
+        self.mouse.buttons_pressed.contains(&button) && !self.is_mouse_button_consumed(button)
     }
 
     pub fn is_mouse_button_released(&self, button: MouseButton) -> bool {
-        self.mouse.buttons_released.contains(&button)
+        self.mouse.buttons_released.contains(&button) && !self.is_mouse_button_consumed(button)
     }
 
     pub fn mouse_position(&self) -> Vec2 {
@@ -330,27 +478,24 @@ impl InputSystem {
     }
 
     pub fn press_mouse_button(&mut self, button: MouseButton) {
-        if !self.mouse.buttons.contains(&button) {
-            self.mouse.buttons_pressed.insert(button);
-        }
-        self.mouse.buttons.insert(button);
+        self.submit(InputEvent::MouseButtonPressed { button });
     }
 
     pub fn release_mouse_button(&mut self, button: MouseButton) {
-        if self.mouse.buttons.contains(&button) {
-            self.mouse.buttons_released.insert(button);
-        }
-        self.mouse.buttons.remove(&button);
+        self.submit(InputEvent::MouseButtonReleased { button });
     }
 
     pub fn set_mouse_position(&mut self, x: f32, y: f32) {
-        let new_pos = Vec2::new(x, y);
-        self.mouse.delta = new_pos - self.mouse.position;
-        self.mouse.position = new_pos;
+        self.submit(InputEvent::MouseMoved { x, y });
+    }
+
+    /// Relative motion from the device, for mouse-look while the cursor is locked
+    pub fn add_mouse_motion(&mut self, dx: f32, dy: f32) {
+        self.submit(InputEvent::MouseMotion { dx, dy });
     }
 
     pub fn set_mouse_scroll(&mut self, x: f32, y: f32) {
-        self.mouse.scroll_delta = Vec2::new(x, y);
+        self.submit(InputEvent::MouseScroll { x, y });
     }
 
     // ========================================================================
@@ -417,52 +562,92 @@ impl InputSystem {
     }
 
     pub fn add_touch(&mut self, id: u64, x: f32, y: f32, phase: TouchPhase) {
-        let touch = Touch {
-            id: TouchId(id),
-            position: Vec2::new(x, y),
-            phase,
-        };
+        self.submit(InputEvent::Touch { id, x, y, phase });
+    }
 
-        match phase {
-            TouchPhase::Started => {
-                self.touch.touches.insert(TouchId(id), touch);
-                self.touch.started_this_frame.push(touch);
-            }
-            TouchPhase::Moved => {
-                self.touch.touches.insert(TouchId(id), touch);
-            }
-            TouchPhase::Ended | TouchPhase::Cancelled => {
-                self.touch.touches.remove(&TouchId(id));
-                self.touch.ended_this_frame.push(touch);
-            }
-        }
+    /// Touch is held by an on-screen control or started over interactable UI
+    pub fn is_touch_claimed(&self, id: TouchId) -> bool {
+        self.virtual_controls.owns_touch(id)
+    }
+
+    // ========================================================================
+    // VIRTUAL CONTROLS (on-screen joystick / buttons)
+    // ========================================================================
+
+    /// Update on-screen control placement (reported by the UI every frame)
+    pub fn set_virtual_controls(&mut self, layout: VirtualControlsLayout) {
+        self.virtual_controls.set_layout(layout);
+    }
+
+    pub fn virtual_controls(&self) -> &VirtualControls {
+        &self.virtual_controls
+    }
+
+    /// Deflection of the named on-screen joystick (or the first one)
+    pub fn virtual_joystick(&self, name: Option<&str>) -> Vec2 {
+        self.virtual_controls.joystick(name).map(|state| state.value).unwrap_or(Vec2::ZERO)
+    }
+
+    pub fn is_virtual_button_down(&self, name: &str) -> bool {
+        self.virtual_controls.button_down(name)
+    }
+
+    // ========================================================================
+    // UI CAPTURE (clicks and typing that belong to the game UI)
+    // ========================================================================
+
+    /// Update what the UI takes input from (reported by the UI every frame). A mouse press
+    /// starting over a blocker, or a key pressed while a text field has focus, is hidden
+    /// from the key/mouse queries and actions until released, even if the UI under it
+    /// goes away meanwhile.
+    pub fn set_ui_capture(&mut self, capture: UICapture) {
+        self.ui_capture = capture;
+    }
+
+    pub fn ui_capture(&self) -> &UICapture {
+        &self.ui_capture
+    }
+
+    /// The mouse is over interactable UI
+    pub fn pointer_over_ui(&self) -> bool {
+        self.ui_capture.blockers.iter().any(|rect| rect.contains(self.mouse.position))
+    }
+
+    /// Hide UI-consumed input from gameplay (the default). Off, gameplay sees every
+    /// click and key, as before the UI consumed input.
+    pub fn set_ui_blocks_game_input(&mut self, blocks: bool) {
+        self.ui_passthrough = !blocks;
+    }
+
+    /// The button's current (or just released) press went to the UI
+    pub fn is_mouse_button_consumed(&self, button: MouseButton) -> bool {
+        !self.ui_passthrough && self.ui_mouse.contains(&button)
+    }
+
+    /// The key was pressed while a UI text field had focus
+    pub fn is_key_consumed(&self, key: Key) -> bool {
+        !self.ui_passthrough && self.ui_keys.contains(&key)
     }
 
     // ========================================================================
     // VIRTUAL INPUT (for flexible control schemes)
     // ========================================================================
 
-    /// Get movement vector from WASD/Arrow keys OR gamepad left stick OR touch virtual joystick
+    /// Movement vector from the built-in move_x / move_y actions (WASD/Arrow keys OR
+    /// gamepad left stick by default) OR the on-screen joystick. Uses the profile playing
+    /// on `gamepad_id`, or player 1's bindings read from that gamepad.
     pub fn get_movement_input(&self, gamepad_id: usize) -> Vec2 {
-        let mut input = Vec2::ZERO;
-
-        // Keyboard input (priority 1)
-        if self.is_key_down(Key::W) || self.is_key_down(Key::Up) {
-            input.y -= 1.0;
-        }
-        if self.is_key_down(Key::S) || self.is_key_down(Key::Down) {
-            input.y += 1.0;
-        }
-        if self.is_key_down(Key::A) || self.is_key_down(Key::Left) {
-            input.x -= 1.0;
-        }
-        if self.is_key_down(Key::D) || self.is_key_down(Key::Right) {
-            input.x += 1.0;
-        }
+        let player = self.config.profiles.iter()
+            .position(|profile| profile.gamepad == gamepad_id)
+            .unwrap_or(0);
+        let Some(profile) = self.config.profile(player) else {
+            return Vec2::ZERO;
+        };
+        let axis = |name: &str| profile.action(name).map(|action| self.evaluate_axis(action, gamepad_id)).unwrap_or(0.0);
+        let mut input = Vec2::new(axis(actions::MOVE_X), axis(actions::MOVE_Y));
 
-        // If no keyboard input, try gamepad
-        if input == Vec2::ZERO && gamepad_id < 4 && self.gamepads[gamepad_id].connected {
-            input = self.gamepads[gamepad_id].left_stick;
+        if input == Vec2::ZERO && player == 0 {
+            input = self.virtual_joystick(None);
         }
 
         // Normalize diagonal movement
@@ -473,12 +658,14 @@ impl InputSystem {
         input
     }
 
-    /// Get action button state (Space/Enter OR gamepad South button OR touch tap)
+    /// Get action button state (Space/Enter OR gamepad South button OR on-screen "jump"
+    /// button OR a touch tap outside the on-screen controls and UI)
     pub fn get_action_button(&self, gamepad_id: usize) -> bool {
         self.is_key_down(Key::Space) ||
         self.is_key_down(Key::Enter) ||
         self.is_gamepad_button_down(gamepad_id, GamepadButton::South) ||
-        self.touch_count() > 0
+        self.virtual_controls.action_down(actions::JUMP) ||
+        self.touch.touches.keys().any(|id| !self.virtual_controls.owns_touch(*id))
     }
 
     /// Get action button just pressed
@@ -486,7 +673,312 @@ impl InputSystem {
         self.is_key_pressed(Key::Space) ||
         self.is_key_pressed(Key::Enter) ||
         self.is_gamepad_button_pressed(gamepad_id, GamepadButton::South) ||
-        !self.touch.started_this_frame.is_empty()
+        self.virtual_controls.action_pressed(actions::JUMP) ||
+        self.touch.started_this_frame.iter().any(|touch| !self.virtual_controls.owns_touch(touch.id))
+    }
+
+    // ========================================================================
+    // ACTIONS
+    // ========================================================================
+
+    /// Action mapping in use (defaults until `set_config`)
+    pub fn config(&self) -> &InputConfig {
+        &self.config
+    }
+
+    pub fn config_mut(&mut self) -> &mut InputConfig {
+        &mut self.config
+    }
+
+    /// Replace the action mapping (e.g. with the project's input.json)
+    pub fn set_config(&mut self, config: InputConfig) {
+        self.config = config;
+        self.rebind = None;
+    }
+
+    /// Player 1's action is held
+    pub fn action_down(&self, action: &str) -> bool {
+        self.player_action_down(0, action)
+    }
+
+    /// Player 1's action started this frame
+    pub fn action_pressed(&self, action: &str) -> bool {
+        self.player_action_pressed(0, action)
+    }
+
+    /// Player 1's action ended this frame
+    pub fn action_released(&self, action: &str) -> bool {
+        self.player_action_released(0, action)
+    }
+
+    /// Player 1's action value (-1.0 to 1.0; 0.0 or 1.0 for button actions)
+    pub fn action_axis(&self, action: &str) -> f32 {
+        self.player_action_axis(0, action)
+    }
+
+    pub fn player_action_down(&self, player: usize, action: &str) -> bool {
+        self.with_action(player, action, |action, gamepad| self.player_action_active(player, action, gamepad, false))
+            .unwrap_or(false)
+    }
+
+    pub fn player_action_pressed(&self, player: usize, action: &str) -> bool {
+        self.with_action(player, action, |action, gamepad| {
+            self.player_action_active(player, action, gamepad, false)
+                && !self.player_action_active(player, action, gamepad, true)
+        })
+        .unwrap_or(false)
+    }
+
+    pub fn player_action_released(&self, player: usize, action: &str) -> bool {
+        self.with_action(player, action, |action, gamepad| {
+            !self.player_action_active(player, action, gamepad, false)
+                && self.player_action_active(player, action, gamepad, true)
+        })
+        .unwrap_or(false)
+    }
+
+    pub fn player_action_axis(&self, player: usize, action: &str) -> f32 {
+        self.with_action(player, action, |action, gamepad| match action.kind {
+            ActionKind::Axis => {
+                let value = self.evaluate_axis(action, gamepad);
+                // On-screen joystick drives player 1's movement axes
+                match action.name.as_str() {
+                    actions::MOVE_X if value == 0.0 && player == 0 => self.virtual_joystick(None).x,
+                    actions::MOVE_Y if value == 0.0 && player == 0 => self.virtual_joystick(None).y,
+                    _ => value,
+                }
+            }
+            ActionKind::Button => if self.player_action_active(player, action, gamepad, false) { 1.0 } else { 0.0 },
+        })
+        .unwrap_or(0.0)
+    }
+
+    fn with_action<T>(&self, player: usize, action: &str, f: impl FnOnce(&InputAction, usize) -> T) -> Option<T> {
+        let profile = self.config.profile(player)?;
+        let action = profile.action(action)?;
+        Some(f(action, profile.gamepad))
+    }
+
+    /// Bindings plus (for player 1) on-screen buttons feeding the action
+    fn player_action_active(&self, player: usize, action: &InputAction, gamepad: usize, previous: bool) -> bool {
+        if self.action_active(action, gamepad, previous) {
+            return true;
+        }
+        if player != 0 {
+            return false;
+        }
+        let controls = &self.virtual_controls;
+        let down = controls.action_down(&action.name);
+        if previous {
+            (down && !controls.action_pressed(&action.name)) || controls.action_released(&action.name)
+        } else {
+            down
+        }
+    }
+
+    /// Any binding held (`previous` = as of the end of last frame)
+    fn action_active(&self, action: &InputAction, gamepad: usize, previous: bool) -> bool {
+        action.bindings.iter().any(|binding| {
+            if binding.source.is_axis() {
+                binding.scale * self.source_value(binding.source, gamepad, previous) >= actions::AXIS_PRESS_THRESHOLD
+            } else {
+                self.source_value(binding.source, gamepad, previous) > 0.0
+            }
+        })
+    }
+
+    /// Digital bindings (keys, buttons) win over analog ones, like the old keyboard-first
+    /// movement: their sum is used if non-zero, otherwise the strongest stick value
+    fn evaluate_axis(&self, action: &InputAction, gamepad: usize) -> f32 {
+        let mut digital = 0.0;
+        let mut analog: f32 = 0.0;
+        for binding in &action.bindings {
+            let value = binding.scale * self.source_value(binding.source, gamepad, false);
+            if binding.source.is_axis() {
+                if value.abs() > analog.abs() {
+                    analog = value;
+                }
+            } else {
+                digital += value;
+            }
+        }
+        let value = if digital != 0.0 { digital } else { analog };
+        value.clamp(-1.0, 1.0)
+    }
+
+    /// Raw value of an input (1.0/0.0 for keys and buttons)
+    fn source_value(&self, source: InputSource, gamepad: usize, previous: bool) -> f32 {
+        let held = |down: bool, pressed: bool, released: bool| {
+            // Last frame's state: held now and not just pressed, or just released
+            let was_down = (down && !pressed) || released;
+            if (previous && was_down) || (!previous && down) { 1.0 } else { 0.0 }
+        };
+        match source {
+            InputSource::Key { key } if self.is_key_consumed(key) => 0.0,
+            InputSource::Mouse { button } if self.is_mouse_button_consumed(button) => 0.0,
+            InputSource::Key { key } => {
+                held(self.keys.contains(&key), self.keys_pressed.contains(&key), self.keys_released.contains(&key))
+            }
+            InputSource::Mouse { button } => held(
+                self.mouse.buttons.contains(&button),
+                self.mouse.buttons_pressed.contains(&button),
+                self.mouse.buttons_released.contains(&button),
+            ),
+            InputSource::GamepadButton { button } => {
+                let Some(state) = self.gamepads.get(gamepad) else {
+                    return 0.0;
+                };
+                held(
+                    state.buttons.contains(&button),
+                    state.buttons_pressed.contains(&button),
+                    state.buttons_released.contains(&button),
+                )
+            }
+            InputSource::GamepadAxis { axis } => {
+                let Some(state) = self.gamepads.get(gamepad) else {
+                    return 0.0;
+                };
+                let axes = if previous { &state.axes_last_frame } else { &state.axes };
+                axes.get(&axis).copied().unwrap_or(0.0)
+            }
+        }
+    }
+
+    // ========================================================================
+    // REBINDING
+    // ========================================================================
+
+    /// Bind the next pressed key, mouse button, gamepad button or stick direction to
+    /// `action` (replacing binding `slot`, or adding one). Escape cancels. The outcome
+    /// is reported through `rebind_result`; conflicts are never applied automatically.
+    pub fn start_rebind(&mut self, player: usize, action: &str, slot: Option<usize>) -> Result<(), BindingError> {
+        let profile = self.config.profile(player).ok_or(BindingError::UnknownPlayer(player))?;
+        if profile.action(action).is_none() {
+            return Err(BindingError::UnknownAction(action.to_string()));
+        }
+        self.rebind = Some(RebindRequest { player, action: action.to_string(), slot });
+        self.rebind_result = None;
+        Ok(())
+    }
+
+    pub fn cancel_rebind(&mut self) {
+        if let Some(request) = self.rebind.take() {
+            self.rebind_result = Some(RebindResult::Cancelled { player: request.player, action: request.action });
+        }
+    }
+
+    pub fn is_rebinding(&self) -> bool {
+        self.rebind.is_some()
+    }
+
+    /// Outcome of the last rebind (kept until the next `start_rebind`)
+    pub fn rebind_result(&self) -> Option<&RebindResult> {
+        self.rebind_result.as_ref()
+    }
+
+    /// Apply a reported conflict anyway: the input moves from the conflicting actions
+    pub fn accept_rebind_conflict(&mut self) -> Result<(), BindingError> {
+        let Some(RebindResult::Conflict { player, action, slot, binding, .. }) = self.rebind_result.clone() else {
+            return Ok(());
+        };
+        let profile = self.config.profile_mut(player).ok_or(BindingError::UnknownPlayer(player))?;
+        profile.bind_replacing(&action, binding, slot)?;
+        self.rebind_result = Some(RebindResult::Bound { player, action, binding });
+        Ok(())
+    }
+
+    fn capture_rebind(&mut self, event: &InputEvent) {
+        let Some(request) = &self.rebind else {
+            return;
+        };
+        let Some(profile) = self.config.profile(request.player) else {
+            self.rebind = None;
+            return;
+        };
+        let Some(action) = profile.action(&request.action) else {
+            self.rebind = None;
+            return;
+        };
+        let slot_scale = request.slot.and_then(|slot| action.bindings.get(slot)).map(|b| b.scale).unwrap_or(1.0);
+
+        let binding = match *event {
+            InputEvent::KeyPressed { key: Key::Escape } => {
+                self.cancel_rebind();
+                return;
+            }
+            InputEvent::KeyPressed { key } => ActionBinding::key(key).with_scale(slot_scale),
+            InputEvent::MouseButtonPressed { button } => ActionBinding::mouse(button).with_scale(slot_scale),
+            InputEvent::GamepadButtonPressed { id, button } if id == profile.gamepad => {
+                ActionBinding::gamepad_button(button).with_scale(slot_scale)
+            }
+            InputEvent::GamepadAxisChanged { id, axis, value }
+                if id == profile.gamepad && value.abs() >= actions::AXIS_PRESS_THRESHOLD =>
+            {
+                // Axis actions take the whole axis; button actions the pushed direction
+                let scale = match action.kind {
+                    ActionKind::Axis => 1.0,
+                    ActionKind::Button => value.signum(),
+                };
+                ActionBinding::gamepad_axis(axis).with_scale(scale)
+            }
+            _ => return,
+        };
+
+        let Some(request) = self.rebind.take() else {
+            return;
+        };
+        let RebindRequest { player, action, slot } = request;
+        let result = match self.config.profile_mut(player).map(|profile| profile.bind(&action, binding, slot)) {
+            Some(Err(BindingError::Conflict { binding, actions })) => {
+                RebindResult::Conflict { player, action, slot, binding, conflicts: actions }
+            }
+            _ => RebindResult::Bound { player, action, binding },
+        };
+        self.rebind_result = Some(result);
+    }
+
+    // ========================================================================
+    // RUMBLE
+    // ========================================================================
+
+    /// Vibrate a gamepad. `strong`/`weak` are motor strengths (0.0 to 1.0), `duration` in
+    /// seconds; zero strengths stop it. Returns false (and does nothing) when the gamepad
+    /// is missing or has no force feedback.
+    pub fn set_gamepad_rumble(&mut self, gamepad_id: usize, strong: f32, weak: f32, duration: f32) -> bool {
+        use gilrs::ff::{BaseEffect, BaseEffectType, EffectBuilder, Replay, Repeat, Ticks};
+
+        // Replacing (or dropping) the previous effect stops it
+        self.rumble.0.remove(&gamepad_id);
+        let Some(gilrs) = &mut self.gilrs else {
+            return false;
+        };
+        if strong <= 0.0 && weak <= 0.0 {
+            return true;
+        }
+        let Some(id) = gilrs.gamepads()
+            .find(|(id, gamepad)| usize::from(*id) == gamepad_id && gamepad.is_ff_supported())
+            .map(|(id, _)| id)
+        else {
+            return false;
+        };
+
+        let play_for = Ticks::from_ms((duration.max(0.0) * 1000.0) as u32);
+        let magnitude = |strength: f32| (strength.clamp(0.0, 1.0) * u16::MAX as f32) as u16;
+        let scheduling = Replay { play_for, ..Default::default() };
+        let effect = EffectBuilder::new()
+            .add_effect(BaseEffect { kind: BaseEffectType::Strong { magnitude: magnitude(strong) }, scheduling, ..Default::default() })
+            .add_effect(BaseEffect { kind: BaseEffectType::Weak { magnitude: magnitude(weak) }, scheduling, ..Default::default() })
+            .repeat(Repeat::For(play_for))
+            .gamepads(&[id])
+            .finish(gilrs);
+
+        match effect.and_then(|effect| effect.play().map(|_| effect)) {
+            Ok(effect) => {
+                self.rumble.0.insert(gamepad_id, effect);
+                true
+            }
+            Err(_) => false,
+        }
     }
 
     // ========================================================================
@@ -499,6 +991,10 @@ impl InputSystem {
         self.keys_pressed.clear();
         self.keys_released.clear();
 
+        // Consumed input stays consumed until released
+        self.ui_keys.retain(|key| self.keys.contains(key));
+        self.ui_mouse.retain(|button| self.mouse.buttons.contains(button));
+
         // Clear mouse frame state
         self.mouse.buttons_pressed.clear();
         self.mouse.buttons_released.clear();
@@ -509,81 +1005,255 @@ impl InputSystem {
         for gamepad in &mut self.gamepads {
             gamepad.buttons_pressed.clear();
             gamepad.buttons_released.clear();
+            gamepad.axes_last_frame.clone_from(&gamepad.axes);
         }
 
         // Clear touch frame state
         self.touch.started_this_frame.clear();
         self.touch.ended_this_frame.clear();
+        self.virtual_controls.begin_frame();
     }
 
     /// Update gamepad state from gilrs
     pub fn update_gamepads(&mut self) {
-        if let Some(ref mut gilrs) = self.gilrs {
-            while let Some(event) = gilrs.next_event() {
-                let gamepad_id: usize = event.id.into();
-                if gamepad_id >= 4 { continue; }
-
-                match event.event {
-                    gilrs::EventType::Connected => {
-                        self.gamepads[gamepad_id].connected = true;
+        let Some(ref mut gilrs) = self.gilrs else {
+            return;
+        };
+
+        let mut events = Vec::new();
+        while let Some(event) = gilrs.next_event() {
+            let id: usize = event.id.into();
+            if id >= 4 { continue; }
+
+            match event.event {
+                gilrs::EventType::Connected => {
+                    events.push(InputEvent::GamepadConnected { id, connected: true });
+                }
+                gilrs::EventType::Disconnected => {
+                    events.push(InputEvent::GamepadConnected { id, connected: false });
+                }
+                gilrs::EventType::ButtonPressed(button, _) => {
+                    if let Some(button) = Self::map_gilrs_button(button) {
+                        events.push(InputEvent::GamepadButtonPressed { id, button });
+                    }
+                }
+                gilrs::EventType::ButtonReleased(button, _) => {
+                    if let Some(button) = Self::map_gilrs_button(button) {
+                        events.push(InputEvent::GamepadButtonReleased { id, button });
+                    }
+                }
+                gilrs::EventType::AxisChanged(axis, value, _) => {
+                    // Apply deadzone
+                    let value = if value.abs() < 0.15 { 0.0 } else { value };
+
+                    let mapped = match axis {
+                        gilrs::Axis::LeftStickX => Some((GamepadAxis::LeftStickX, value)),
+                        gilrs::Axis::LeftStickY => Some((GamepadAxis::LeftStickY, -value)), // Invert Y
+                        gilrs::Axis::RightStickX => Some((GamepadAxis::RightStickX, value)),
+                        gilrs::Axis::RightStickY => Some((GamepadAxis::RightStickY, -value)), // Invert Y
+                        gilrs::Axis::LeftZ => Some((GamepadAxis::LeftTrigger, value)),
+                        gilrs::Axis::RightZ => Some((GamepadAxis::RightTrigger, value)),
+                        _ => None,
+                    };
+                    if let Some((axis, value)) = mapped {
+                        events.push(InputEvent::GamepadAxisChanged { id, axis, value });
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        for event in events {
+            self.submit(event);
+        }
+    }
+
+    // ========================================================================
+    // RECORDING / PLAYBACK
+    // ========================================================================
+
+    /// Route a device event: dropped during playback, captured while recording
+    fn submit(&mut self, event: InputEvent) {
+        if self.playback {
+            return;
+        }
+        if let Some(recorded) = &mut self.recording {
+            recorded.push(event.clone());
+        }
+        self.apply_event(&event);
+    }
+
+    /// Apply one event to the input state (used directly by replay playback)
+    pub fn apply_event(&mut self, event: &InputEvent) {
+        self.apply_state(event);
+        // Captured here rather than in `submit` so a replayed rebind completes too
+        if self.rebind.is_some() {
+            self.capture_rebind(event);
+        }
+    }
+
+    fn apply_state(&mut self, event: &InputEvent) {
+        match *event {
+            InputEvent::KeyPressed { key } => {
+                if !self.keys.contains(&key) {
+                    self.keys_pressed.insert(key);
+                    if self.ui_capture.keyboard {
+                        self.ui_keys.insert(key);
                     }
-                    gilrs::EventType::Disconnected => {
-                        self.gamepads[gamepad_id].connected = false;
+                }
+                self.keys.insert(key);
+            }
+            InputEvent::KeyReleased { key } => {
+                if self.keys.contains(&key) {
+                    self.keys_released.insert(key);
+                }
+                self.keys.remove(&key);
+            }
+            InputEvent::MouseMoved { x, y } => {
+                let new_pos = Vec2::new(x, y);
+                self.mouse.delta = new_pos - self.mouse.position;
+                self.mouse.position = new_pos;
+            }
+            InputEvent::MouseMotion { dx, dy } => {
+                self.mouse.delta += Vec2::new(dx, dy);
+            }
+            InputEvent::MouseButtonPressed { button } => {
+                if !self.mouse.buttons.contains(&button) {
+                    self.mouse.buttons_pressed.insert(button);
+                    if self.pointer_over_ui() {
+                        self.ui_mouse.insert(button);
                     }
-                    gilrs::EventType::ButtonPressed(button, _) => {
-                        if let Some(mapped) = Self::map_gilrs_button(button) {
-                            let gamepad = &mut self.gamepads[gamepad_id];
-                            if !gamepad.buttons.contains(&mapped) {
-                                gamepad.buttons_pressed.insert(mapped);
-                            }
-                            gamepad.buttons.insert(mapped);
-                        }
+                }
+                self.mouse.buttons.insert(button);
+            }
+            InputEvent::MouseButtonReleased { button } => {
+                if self.mouse.buttons.contains(&button) {
+                    self.mouse.buttons_released.insert(button);
+                }
+                self.mouse.buttons.remove(&button);
+            }
+            InputEvent::MouseScroll { x, y } => {
+                self.mouse.scroll_delta = Vec2::new(x, y);
+            }
+            InputEvent::GamepadConnected { id, connected } => {
+                if id < 4 {
+                    self.gamepads[id].connected = connected;
+                }
+            }
+            InputEvent::GamepadButtonPressed { id, button } => {
+                if id >= 4 { return; }
+                let gamepad = &mut self.gamepads[id];
+                if !gamepad.buttons.contains(&button) {
+                    gamepad.buttons_pressed.insert(button);
+                }
+                gamepad.buttons.insert(button);
+            }
+            InputEvent::GamepadButtonReleased { id, button } => {
+                if id >= 4 { return; }
+                let gamepad = &mut self.gamepads[id];
+                if gamepad.buttons.contains(&button) {
+                    gamepad.buttons_released.insert(button);
+                }
+                gamepad.buttons.remove(&button);
+            }
+            InputEvent::GamepadAxisChanged { id, axis, value } => {
+                if id >= 4 { return; }
+                let gamepad = &mut self.gamepads[id];
+                gamepad.axes.insert(axis, value);
+                match axis {
+                    GamepadAxis::LeftStickX => gamepad.left_stick.x = value,
+                    GamepadAxis::LeftStickY => gamepad.left_stick.y = value,
+                    GamepadAxis::RightStickX => gamepad.right_stick.x = value,
+                    GamepadAxis::RightStickY => gamepad.right_stick.y = value,
+                    GamepadAxis::LeftTrigger | GamepadAxis::RightTrigger => {}
+                }
+            }
+            InputEvent::Touch { id, x, y, phase } => {
+                let touch = Touch {
+                    id: TouchId(id),
+                    position: Vec2::new(x, y),
+                    phase,
+                };
+
+                match phase {
+                    TouchPhase::Started => {
+                        self.touch.touches.insert(TouchId(id), touch);
+                        self.touch.started_this_frame.push(touch);
                     }
-                    gilrs::EventType::ButtonReleased(button, _) => {
-                        if let Some(mapped) = Self::map_gilrs_button(button) {
-                            let gamepad = &mut self.gamepads[gamepad_id];
-                            if gamepad.buttons.contains(&mapped) {
-                                gamepad.buttons_released.insert(mapped);
-                            }
-                            gamepad.buttons.remove(&mapped);
-                        }
+                    TouchPhase::Moved => {
+                        self.touch.touches.insert(TouchId(id), touch);
                     }
-                    gilrs::EventType::AxisChanged(axis, value, _) => {
-                        let gamepad = &mut self.gamepads[gamepad_id];
-
-                        // Apply deadzone
-                        let value = if value.abs() < 0.15 { 0.0 } else { value };
-
-                        match axis {
-                            gilrs::Axis::LeftStickX => {
-                                gamepad.axes.insert(GamepadAxis::LeftStickX, value);
-                                gamepad.left_stick.x = value;
-                            }
-                            gilrs::Axis::LeftStickY => {
-                                gamepad.axes.insert(GamepadAxis::LeftStickY, -value); // Invert Y
-                                gamepad.left_stick.y = -value;
-                            }
-                            gilrs::Axis::RightStickX => {
-                                gamepad.axes.insert(GamepadAxis::RightStickX, value);
-                                gamepad.right_stick.x = value;
-                            }
-                            gilrs::Axis::RightStickY => {
-                                gamepad.axes.insert(GamepadAxis::RightStickY, -value); // Invert Y
-                                gamepad.right_stick.y = -value;
-                            }
-                            gilrs::Axis::LeftZ => {
-                                gamepad.axes.insert(GamepadAxis::LeftTrigger, value);
-                            }
-                            gilrs::Axis::RightZ => {
-                                gamepad.axes.insert(GamepadAxis::RightTrigger, value);
-                            }
-                            _ => {}
-                        }
+                    TouchPhase::Ended | TouchPhase::Cancelled => {
+                        self.touch.touches.remove(&TouchId(id));
+                        self.touch.ended_this_frame.push(touch);
                     }
-                    _ => {}
                 }
+                self.virtual_controls.touch(&touch);
+            }
+        }
+    }
+
+    /// Start capturing events. Returns the events that rebuild the currently held
+    /// state (keys, buttons, sticks, touches) so playback can start from the same place.
+    pub fn start_recording(&mut self) -> Vec<InputEvent> {
+        let mut snapshot = vec![InputEvent::MouseMoved { x: self.mouse.position.x, y: self.mouse.position.y }];
+        snapshot.extend(self.keys.iter().map(|&key| InputEvent::KeyPressed { key }));
+        snapshot.extend(self.mouse.buttons.iter().map(|&button| InputEvent::MouseButtonPressed { button }));
+        for (id, gamepad) in self.gamepads.iter().enumerate() {
+            if !gamepad.connected {
+                continue;
             }
+            snapshot.push(InputEvent::GamepadConnected { id, connected: true });
+            snapshot.extend(gamepad.buttons.iter().map(|&button| InputEvent::GamepadButtonPressed { id, button }));
+            snapshot.extend(gamepad.axes.iter().map(|(&axis, &value)| InputEvent::GamepadAxisChanged { id, axis, value }));
         }
+        snapshot.extend(self.touch.touches.values().map(|touch| InputEvent::Touch {
+            id: touch.id.0,
+            x: touch.position.x,
+            y: touch.position.y,
+            phase: TouchPhase::Started,
+        }));
+
+        self.recording = Some(Vec::new());
+        snapshot
+    }
+
+    /// Events captured since the last call (empty when not recording)
+    pub fn take_recorded(&mut self) -> Vec<InputEvent> {
+        self.recording.as_mut().map(std::mem::take).unwrap_or_default()
+    }
+
+    /// Stop capturing; returns events not yet taken
+    pub fn stop_recording(&mut self) -> Vec<InputEvent> {
+        self.recording.take().unwrap_or_default()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Enter or leave replay playback. Entering clears all input state; while active,
+    /// keyboard/mouse/gamepad/touch updates are ignored and only `apply_event` counts.
+    pub fn set_playback(&mut self, playback: bool) {
+        if playback && !self.playback {
+            self.reset_state();
+        }
+        self.playback = playback;
+    }
+
+    pub fn is_playback(&self) -> bool {
+        self.playback
+    }
+
+    /// Release everything (keys, buttons, sticks, touches)
+    pub fn reset_state(&mut self) {
+        self.keys.clear();
+        self.keys_pressed.clear();
+        self.keys_released.clear();
+        self.mouse = MouseState::default();
+        self.gamepads = Default::default();
+        self.touch = TouchState::default();
+        self.virtual_controls.release_all();
     }
 
     fn map_gilrs_button(button: gilrs::Button) -> Option<GamepadButton> {
@@ -608,3 +1278,170 @@ impl InputSystem {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recorded_events_replay_to_same_state() {
+        let mut live = InputSystem::default();
+        live.press_key(Key::A);
+        let snapshot = live.start_recording();
+
+        live.set_mouse_position(10.0, 20.0);
+        live.press_key(Key::Space);
+        live.release_key(Key::A);
+        let events = live.take_recorded();
+        assert_eq!(events.len(), 3);
+
+        let mut replayed = InputSystem::default();
+        replayed.set_playback(true);
+        for event in snapshot.iter().chain(&events) {
+            replayed.apply_event(event);
+        }
+        // Device input is ignored during playback
+        replayed.press_key(Key::W);
+
+        assert!(replayed.is_key_down(Key::Space));
+        assert!(!replayed.is_key_down(Key::A));
+        assert!(!replayed.is_key_down(Key::W));
+        assert_eq!(replayed.mouse_position(), live.mouse_position());
+    }
+
+    #[test]
+    fn test_mouse_motion_adds_up_without_moving_cursor() {
+        let mut input = InputSystem::default();
+        input.set_mouse_position(100.0, 50.0);
+        input.begin_frame();
+
+        input.add_mouse_motion(3.0, -1.0);
+        input.add_mouse_motion(2.0, 4.0);
+        assert_eq!(input.mouse_delta(), Vec2::new(5.0, 3.0));
+        assert_eq!(input.mouse_position(), Vec2::new(100.0, 50.0));
+
+        input.begin_frame();
+        assert_eq!(input.mouse_delta(), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_actions_follow_bindings() {
+        let mut input = InputSystem::default();
+        input.press_key(Key::Space);
+        input.press_key(Key::A);
+        assert!(input.action_pressed("jump"));
+        assert!(input.action_down("jump"));
+        assert_eq!(input.action_axis(actions::MOVE_X), -1.0);
+        assert_eq!(input.get_movement_input(0), Vec2::new(-1.0, 0.0));
+
+        input.begin_frame();
+        assert!(!input.action_pressed("jump"));
+        input.release_key(Key::Space);
+        assert!(input.action_released("jump"));
+        assert!(!input.action_down("jump"));
+    }
+
+    #[test]
+    fn test_rebind_reports_conflicts() {
+        let mut input = InputSystem::default();
+        input.start_rebind(0, "fire", None).unwrap();
+        input.press_key(Key::Space);
+
+        // Space already jumps: reported, nothing changed
+        match input.rebind_result() {
+            Some(RebindResult::Conflict { conflicts, .. }) => assert_eq!(conflicts, &vec!["jump".to_string()]),
+            other => panic!("expected conflict, got {:?}", other),
+        }
+        assert!(!input.action_down("fire"));
+
+        input.accept_rebind_conflict().unwrap();
+        assert!(input.action_down("fire"));
+        assert!(!input.action_down("jump"));
+
+        input.start_rebind(0, "jump", Some(0)).unwrap();
+        input.press_key(Key::K);
+        assert!(matches!(input.rebind_result(), Some(RebindResult::Bound { .. })));
+        assert!(!input.is_rebinding());
+        assert!(input.action_down("jump"));
+    }
+
+    #[test]
+    fn test_virtual_controls_multi_touch() {
+        let mut input = InputSystem::default();
+        input.set_virtual_controls(VirtualControlsLayout {
+            joysticks: vec![VirtualJoystickLayout {
+                name: "stick".to_string(),
+                region: ScreenRect::new(Vec2::new(0.0, 0.0), Vec2::new(400.0, 600.0)),
+                radius: 100.0,
+                dead_zone: 0.0,
+                floating: true,
+            }],
+            buttons: vec![VirtualButtonLayout {
+                name: "jump_button".to_string(),
+                region: ScreenRect::new(Vec2::new(700.0, 500.0), Vec2::new(800.0, 600.0)),
+                action: "jump".to_string(),
+            }],
+            blockers: vec![ScreenRect::new(Vec2::new(0.0, 0.0), Vec2::new(100.0, 50.0))],
+        });
+
+        // Left thumb drags the floating joystick right, right thumb holds the button
+        input.add_touch(1, 200.0, 300.0, TouchPhase::Started);
+        input.add_touch(1, 300.0, 300.0, TouchPhase::Moved);
+        input.add_touch(2, 750.0, 550.0, TouchPhase::Started);
+        assert_eq!(input.get_movement_input(0), Vec2::new(1.0, 0.0));
+        assert_eq!(input.action_axis(actions::MOVE_X), 1.0);
+        assert!(input.action_pressed("jump"));
+        assert!(input.get_action_button(0));
+
+        // A touch on other UI drives nothing, even inside the joystick region
+        input.begin_frame();
+        input.add_touch(2, 750.0, 550.0, TouchPhase::Ended);
+        input.add_touch(3, 50.0, 20.0, TouchPhase::Started);
+        assert!(input.action_released("jump"));
+        assert!(!input.get_action_button(0));
+        assert_eq!(input.virtual_joystick(Some("stick")), Vec2::new(1.0, 0.0));
+
+        input.add_touch(1, 300.0, 300.0, TouchPhase::Ended);
+        assert_eq!(input.get_movement_input(0), Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_ui_consumes_clicks_and_typing_until_released() {
+        let mut input = InputSystem::default();
+        input.set_ui_capture(UICapture {
+            blockers: vec![ScreenRect::new(Vec2::new(0.0, 0.0), Vec2::new(100.0, 50.0))],
+            keyboard: false,
+        });
+
+        // Clicking the button doesn't shoot
+        input.set_mouse_position(50.0, 20.0);
+        assert!(input.pointer_over_ui());
+        input.press_mouse_button(MouseButton::Left);
+        assert!(!input.is_mouse_button_pressed(MouseButton::Left));
+        assert!(!input.action_pressed("fire"));
+        assert!(input.is_mouse_button_consumed(MouseButton::Left));
+
+        // The button is hidden while held: the press still belongs to the UI
+        input.set_ui_capture(UICapture::default());
+        input.begin_frame();
+        assert!(!input.is_mouse_button_down(MouseButton::Left));
+        input.release_mouse_button(MouseButton::Left);
+        assert!(!input.action_released("fire"));
+
+        // The next click reaches the game
+        input.begin_frame();
+        input.press_mouse_button(MouseButton::Left);
+        assert!(!input.pointer_over_ui());
+        assert!(input.action_pressed("fire"));
+
+        // Typing into a focused text field doesn't jump
+        input.set_ui_capture(UICapture { blockers: Vec::new(), keyboard: true });
+        input.press_key(Key::Space);
+        assert!(!input.is_key_pressed(Key::Space));
+        assert!(!input.action_down("jump"));
+
+        // Unless the project lets gameplay see UI input
+        input.set_ui_blocks_game_input(false);
+        assert!(input.action_down("jump"));
+    }
+}
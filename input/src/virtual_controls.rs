@@ -0,0 +1,277 @@
+// Virtual Controls - on-screen joystick and buttons driven by touches
+// Layout comes from the UI (elements tagged as virtual inputs) each frame;
+// touches are routed here by InputSystem so they feed movement and actions.
+
+use crate::{Touch, TouchId, TouchPhase};
+use glam::Vec2;
+use std::collections::HashSet;
+
+/// Axis-aligned rectangle in the same coordinates as touch positions
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScreenRect {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl ScreenRect {
+    pub fn new(min: Vec2, max: Vec2) -> Self {
+        Self { min, max }
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x >= self.min.x && point.x <= self.max.x && point.y >= self.min.y && point.y <= self.max.y
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) * 0.5
+    }
+}
+
+/// On-screen joystick: a touch starting inside `region` grabs it
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualJoystickLayout {
+    pub name: String,
+    pub region: ScreenRect,
+    /// Distance from the origin that gives full deflection
+    pub radius: f32,
+    /// Fraction of the radius ignored around the origin (0.0 to 1.0)
+    pub dead_zone: f32,
+    /// Origin follows the touch start point instead of the region center
+    pub floating: bool,
+}
+
+/// On-screen button feeding the named action
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualButtonLayout {
+    pub name: String,
+    pub region: ScreenRect,
+    pub action: String,
+}
+
+/// Everything the UI reports for one frame
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VirtualControlsLayout {
+    pub joysticks: Vec<VirtualJoystickLayout>,
+    pub buttons: Vec<VirtualButtonLayout>,
+    /// Other interactable UI: touches starting here never reach the controls
+    pub blockers: Vec<ScreenRect>,
+}
+
+/// Current state of a joystick (for gameplay and for drawing it)
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct VirtualJoystickState {
+    /// Where the stick is centered (the region center when idle)
+    pub origin: Vec2,
+    /// Touch offset from the origin, clamped to the radius
+    pub offset: Vec2,
+    /// Output after dead zone (-1.0 to 1.0 per axis, length <= 1.0)
+    pub value: Vec2,
+    pub active: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Joystick {
+    layout: VirtualJoystickLayout,
+    touch: Option<TouchId>,
+    state: VirtualJoystickState,
+}
+
+#[derive(Debug, Clone)]
+struct Button {
+    layout: VirtualButtonLayout,
+    touch: Option<TouchId>,
+    pressed: bool,
+    released: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VirtualControls {
+    joysticks: Vec<Joystick>,
+    buttons: Vec<Button>,
+    blockers: Vec<ScreenRect>,
+    // Touches that started over other UI (ignored until they end)
+    blocked: HashSet<TouchId>,
+    // Touches taken since the last begin_frame (a tap can start and end in one frame)
+    claimed_this_frame: HashSet<TouchId>,
+}
+
+impl VirtualControls {
+    /// Replace the layout, keeping held touches on controls whose name is unchanged
+    pub fn set_layout(&mut self, layout: VirtualControlsLayout) {
+        let mut joysticks = Vec::with_capacity(layout.joysticks.len());
+        for joystick_layout in layout.joysticks {
+            let previous = self.joysticks.iter().position(|j| j.layout.name == joystick_layout.name);
+            let joystick = match previous {
+                Some(index) => {
+                    let mut joystick = self.joysticks.swap_remove(index);
+                    if !joystick.state.active {
+                        joystick.state.origin = joystick_layout.region.center();
+                    }
+                    joystick.layout = joystick_layout;
+                    joystick
+                }
+                None => Joystick {
+                    state: VirtualJoystickState { origin: joystick_layout.region.center(), ..Default::default() },
+                    layout: joystick_layout,
+                    touch: None,
+                },
+            };
+            joysticks.push(joystick);
+        }
+
+        let mut buttons = Vec::with_capacity(layout.buttons.len());
+        for button_layout in layout.buttons {
+            let button = match self.buttons.iter().position(|b| b.layout.name == button_layout.name) {
+                Some(index) => {
+                    let mut button = self.buttons.swap_remove(index);
+                    button.layout = button_layout;
+                    button
+                }
+                None => Button { layout: button_layout, touch: None, pressed: false, released: false },
+            };
+            buttons.push(button);
+        }
+
+        self.joysticks = joysticks;
+        self.buttons = buttons;
+        self.blockers = layout.blockers;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.joysticks.is_empty() && self.buttons.is_empty()
+    }
+
+    /// Route a touch update. Each control follows at most one touch, so a thumb on the
+    /// joystick and another on a button work independently.
+    pub fn touch(&mut self, touch: &Touch) {
+        match touch.phase {
+            TouchPhase::Started => self.touch_started(touch),
+            TouchPhase::Moved => {
+                if let Some(joystick) = self.joysticks.iter_mut().find(|j| j.touch == Some(touch.id)) {
+                    joystick.move_to(touch.position);
+                }
+            }
+            TouchPhase::Ended | TouchPhase::Cancelled => self.touch_ended(touch.id),
+        }
+    }
+
+    fn touch_started(&mut self, touch: &Touch) {
+        let position = touch.position;
+        self.claimed_this_frame.insert(touch.id);
+        if self.blockers.iter().any(|blocker| blocker.contains(position)) {
+            self.blocked.insert(touch.id);
+            return;
+        }
+        // Buttons first: they usually sit inside a large joystick region
+        if let Some(button) = self.buttons.iter_mut().find(|b| b.touch.is_none() && b.layout.region.contains(position)) {
+            button.touch = Some(touch.id);
+            button.pressed = true;
+            return;
+        }
+        if let Some(joystick) = self.joysticks.iter_mut().find(|j| j.touch.is_none() && j.layout.region.contains(position)) {
+            joystick.touch = Some(touch.id);
+            joystick.state.active = true;
+            joystick.state.origin = if joystick.layout.floating { position } else { joystick.layout.region.center() };
+            joystick.move_to(position);
+        } else {
+            self.claimed_this_frame.remove(&touch.id);
+        }
+    }
+
+    fn touch_ended(&mut self, id: TouchId) {
+        self.blocked.remove(&id);
+        for button in self.buttons.iter_mut().filter(|b| b.touch == Some(id)) {
+            button.touch = None;
+            button.released = true;
+        }
+        for joystick in self.joysticks.iter_mut().filter(|j| j.touch == Some(id)) {
+            joystick.release();
+        }
+    }
+
+    /// Drop every held touch (e.g. when all input is released)
+    pub fn release_all(&mut self) {
+        for button in &mut self.buttons {
+            if button.touch.take().is_some() {
+                button.released = true;
+            }
+        }
+        for joystick in &mut self.joysticks {
+            joystick.release();
+        }
+        self.blocked.clear();
+    }
+
+    pub fn begin_frame(&mut self) {
+        self.claimed_this_frame.clear();
+        for button in &mut self.buttons {
+            button.pressed = false;
+            button.released = false;
+        }
+    }
+
+    /// The touch is driving a control or started over other UI
+    pub fn owns_touch(&self, id: TouchId) -> bool {
+        self.blocked.contains(&id)
+            || self.claimed_this_frame.contains(&id)
+            || self.buttons.iter().any(|b| b.touch == Some(id))
+            || self.joysticks.iter().any(|j| j.touch == Some(id))
+    }
+
+    /// Named joystick, or the first one when `name` is None
+    pub fn joystick(&self, name: Option<&str>) -> Option<&VirtualJoystickState> {
+        match name {
+            Some(name) => self.joysticks.iter().find(|j| j.layout.name == name),
+            None => self.joysticks.first(),
+        }
+        .map(|joystick| &joystick.state)
+    }
+
+    pub fn button_down(&self, name: &str) -> bool {
+        self.buttons.iter().any(|b| b.layout.name == name && b.touch.is_some())
+    }
+
+    pub fn button_pressed(&self, name: &str) -> bool {
+        self.buttons.iter().any(|b| b.layout.name == name && b.pressed)
+    }
+
+    pub fn button_released(&self, name: &str) -> bool {
+        self.buttons.iter().any(|b| b.layout.name == name && b.released)
+    }
+
+    /// Any button bound to `action` is held
+    pub fn action_down(&self, action: &str) -> bool {
+        self.buttons.iter().any(|b| b.layout.action == action && b.touch.is_some())
+    }
+
+    pub fn action_pressed(&self, action: &str) -> bool {
+        self.buttons.iter().any(|b| b.layout.action == action && b.pressed)
+    }
+
+    pub fn action_released(&self, action: &str) -> bool {
+        self.buttons.iter().any(|b| b.layout.action == action && b.released)
+    }
+}
+
+impl Joystick {
+    fn move_to(&mut self, position: Vec2) {
+        let radius = self.layout.radius.max(1.0);
+        let offset = (position - self.state.origin).clamp_length_max(radius);
+        let deflection = offset / radius;
+        let length = deflection.length();
+        let dead_zone = self.layout.dead_zone.clamp(0.0, 0.99);
+
+        self.state.offset = offset;
+        self.state.value = if length <= dead_zone {
+            Vec2::ZERO
+        } else {
+            // Rescale so output starts at 0 at the dead zone edge
+            deflection / length * ((length - dead_zone) / (1.0 - dead_zone))
+        };
+    }
+
+    fn release(&mut self) {
+        self.touch = None;
+        self.state = VirtualJoystickState { origin: self.layout.region.center(), ..Default::default() };
+    }
+}
@@ -0,0 +1,53 @@
+//! Navigation Benchmarks
+//!
+//! A* queries on a 512x512 grid with staggered walls, so the path has to zig-zag
+//! through the whole grid.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use navigation::{NavGrid, PathOptions};
+
+const SIZE: u32 = 512;
+
+/// Vertical walls every 8 columns, with the gap alternating between the top and bottom
+fn maze_grid() -> NavGrid {
+    let mut grid = NavGrid::new(SIZE, SIZE, [0.0, 0.0], 1.0);
+    for (wall, x) in (4..SIZE as i32 - 4).step_by(8).enumerate() {
+        let gap = if wall % 2 == 0 { SIZE as i32 - 3 } else { 0 };
+        for y in 0..SIZE as i32 {
+            if !(gap..gap + 3).contains(&y) {
+                grid.set_blocked(x, y, true);
+            }
+        }
+    }
+    grid
+}
+
+fn bench_find_path(c: &mut Criterion) {
+    let mut group = c.benchmark_group("NavGrid 512x512");
+    group.sample_size(20);
+    let from = [0.5, 0.5];
+    let to = [SIZE as f32 - 0.5, SIZE as f32 - 0.5];
+
+    for (name, options) in [
+        ("diagonal_smoothed", PathOptions::default()),
+        ("four_way_raw", PathOptions { allow_diagonal: false, cut_corners: false, smooth: false }),
+    ] {
+        let mut grid = maze_grid();
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                let path = grid.find_path(black_box(from), black_box(to), &options);
+                black_box(path)
+            });
+        });
+    }
+
+    let mut open = NavGrid::new(SIZE, SIZE, [0.0, 0.0], 1.0);
+    group.bench_function("open_field", |b| {
+        b.iter(|| black_box(open.find_path(black_box(from), black_box(to), &PathOptions::default())));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_find_path);
+criterion_main!(benches);
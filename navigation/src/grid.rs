@@ -0,0 +1,483 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+const SQRT_2: f32 = std::f32::consts::SQRT_2;
+/// How far (in cells) a blocked start/goal is moved to find a walkable cell
+const SNAP_RADIUS: i32 = 3;
+
+/// Search options for `NavGrid::find_path`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PathOptions {
+    /// Allow 8-way movement
+    pub allow_diagonal: bool,
+    /// Let diagonal moves squeeze past a blocked corner (one neighbour blocked).
+    /// Moving between two diagonally touching walls is never allowed.
+    pub cut_corners: bool,
+    /// Remove waypoints that have line of sight to a later one (string pulling)
+    pub smooth: bool,
+}
+
+impl Default for PathOptions {
+    fn default() -> Self {
+        Self {
+            allow_diagonal: true,
+            cut_corners: false,
+            smooth: true,
+        }
+    }
+}
+
+/// Walkability grid in world space.
+///
+/// Cell (0, 0) is the bottom-left cell; `origin` is its bottom-left corner and Y
+/// points up, like the rest of the 2D world. Each cell counts the obstacles
+/// covering it so sources can be added and removed independently.
+/// Everything outside the grid is open space.
+#[derive(Debug, Clone, Default)]
+pub struct NavGrid {
+    width: u32,
+    height: u32,
+    origin: [f32; 2],
+    cell_size: f32,
+    blocked: Vec<u16>,
+    scratch: SearchScratch,
+}
+
+impl NavGrid {
+    pub fn new(width: u32, height: u32, origin: [f32; 2], cell_size: f32) -> Self {
+        Self {
+            width,
+            height,
+            origin,
+            cell_size: cell_size.max(f32::EPSILON),
+            blocked: vec![0; (width * height) as usize],
+            scratch: SearchScratch::default(),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn origin(&self) -> [f32; 2] {
+        self.origin
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// Covered world rect as [min_x, min_y, max_x, max_y]
+    pub fn bounds(&self) -> [f32; 4] {
+        [
+            self.origin[0],
+            self.origin[1],
+            self.origin[0] + self.width as f32 * self.cell_size,
+            self.origin[1] + self.height as f32 * self.cell_size,
+        ]
+    }
+
+    pub fn in_bounds(&self, x: i32, y: i32) -> bool {
+        x >= 0 && y >= 0 && (x as u32) < self.width && (y as u32) < self.height
+    }
+
+    fn index(&self, x: i32, y: i32) -> usize {
+        y as usize * self.width as usize + x as usize
+    }
+
+    /// In bounds and not blocked
+    pub fn is_cell_walkable(&self, x: i32, y: i32) -> bool {
+        self.in_bounds(x, y) && self.blocked[self.index(x, y)] == 0
+    }
+
+    /// Mark a single cell blocked / free (replaces whatever obstacles covered it)
+    pub fn set_blocked(&mut self, x: i32, y: i32, blocked: bool) {
+        if self.in_bounds(x, y) {
+            let index = self.index(x, y);
+            self.blocked[index] = blocked as u16;
+        }
+    }
+
+    pub(crate) fn add_obstacle(&mut self, index: u32) {
+        let count = &mut self.blocked[index as usize];
+        *count = count.saturating_add(1);
+    }
+
+    pub(crate) fn remove_obstacle(&mut self, index: u32) {
+        let count = &mut self.blocked[index as usize];
+        *count = count.saturating_sub(1);
+    }
+
+    pub fn world_to_cell(&self, position: [f32; 2]) -> (i32, i32) {
+        (
+            ((position[0] - self.origin[0]) / self.cell_size).floor() as i32,
+            ((position[1] - self.origin[1]) / self.cell_size).floor() as i32,
+        )
+    }
+
+    pub fn cell_center(&self, x: i32, y: i32) -> [f32; 2] {
+        [
+            self.origin[0] + (x as f32 + 0.5) * self.cell_size,
+            self.origin[1] + (y as f32 + 0.5) * self.cell_size,
+        ]
+    }
+
+    /// Cell range overlapped by a world rect, clipped to the grid (None if outside).
+    /// Rects that only touch a cell edge don't count as overlapping it.
+    pub fn cells_in_rect(&self, min: [f32; 2], max: [f32; 2]) -> Option<((i32, i32), (i32, i32))> {
+        let inset = self.cell_size * 1e-3;
+        let (x0, y0) = self.world_to_cell([min[0] + inset, min[1] + inset]);
+        let (x1, y1) = self.world_to_cell([max[0] - inset, max[1] - inset]);
+        let (x0, y0) = (x0.max(0), y0.max(0));
+        let (x1, y1) = (x1.min(self.width as i32 - 1), y1.min(self.height as i32 - 1));
+        (x0 <= x1 && y0 <= y1).then_some(((x0, y0), (x1, y1)))
+    }
+
+    /// Whether a world position is walkable (outside the grid counts as open)
+    pub fn is_walkable(&self, position: [f32; 2]) -> bool {
+        let (x, y) = self.world_to_cell(position);
+        !self.in_bounds(x, y) || self.blocked[self.index(x, y)] == 0
+    }
+
+    /// Walk the cells a straight segment crosses (Amanatides & Woo). Segments that
+    /// pass exactly through a corner need the neighbouring cells free as well.
+    pub fn has_line_of_sight(&self, from: [f32; 2], to: [f32; 2], cut_corners: bool) -> bool {
+        let ax = (from[0] - self.origin[0]) / self.cell_size;
+        let ay = (from[1] - self.origin[1]) / self.cell_size;
+        let bx = (to[0] - self.origin[0]) / self.cell_size;
+        let by = (to[1] - self.origin[1]) / self.cell_size;
+
+        let open = |x: i32, y: i32| !self.in_bounds(x, y) || self.blocked[self.index(x, y)] == 0;
+
+        let (mut x, mut y) = (ax.floor() as i32, ay.floor() as i32);
+        let (end_x, end_y) = (bx.floor() as i32, by.floor() as i32);
+        if !open(x, y) {
+            return false;
+        }
+
+        let (dx, dy) = (bx - ax, by - ay);
+        let step_x = if dx > 0.0 { 1 } else { -1 };
+        let step_y = if dy > 0.0 { 1 } else { -1 };
+        let axis = |start: f32, cell: i32, delta: f32, step: i32| -> (f32, f32) {
+            if delta == 0.0 {
+                return (f32::INFINITY, f32::INFINITY);
+            }
+            let boundary = if step > 0 { cell as f32 + 1.0 } else { cell as f32 };
+            ((boundary - start) / delta, 1.0 / delta.abs())
+        };
+        let (mut t_max_x, t_delta_x) = axis(ax, x, dx, step_x);
+        let (mut t_max_y, t_delta_y) = axis(ay, y, dy, step_y);
+
+        let max_steps = (end_x - x).abs() + (end_y - y).abs() + 2;
+        for _ in 0..max_steps {
+            if (x, y) == (end_x, end_y) {
+                return true;
+            }
+            if (t_max_x - t_max_y).abs() < 1e-5 {
+                // Exactly through a corner: diagonal step between the two side cells
+                let side_x = open(x + step_x, y);
+                let side_y = open(x, y + step_y);
+                if !(side_x && side_y) && !(cut_corners && (side_x || side_y)) {
+                    return false;
+                }
+                x += step_x;
+                y += step_y;
+                t_max_x += t_delta_x;
+                t_max_y += t_delta_y;
+            } else if t_max_x < t_max_y {
+                x += step_x;
+                t_max_x += t_delta_x;
+            } else {
+                y += step_y;
+                t_max_y += t_delta_y;
+            }
+            if !open(x, y) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Nearest walkable cell within `SNAP_RADIUS` (rings of growing size)
+    fn nearest_walkable(&self, x: i32, y: i32) -> Option<(i32, i32)> {
+        let x = x.clamp(0, self.width as i32 - 1);
+        let y = y.clamp(0, self.height as i32 - 1);
+        if self.is_cell_walkable(x, y) {
+            return Some((x, y));
+        }
+        for radius in 1..=SNAP_RADIUS {
+            let mut best: Option<((i32, i32), i32)> = None;
+            for cy in (y - radius)..=(y + radius) {
+                for cx in (x - radius)..=(x + radius) {
+                    let on_ring = (cx - x).abs() == radius || (cy - y).abs() == radius;
+                    if !on_ring || !self.is_cell_walkable(cx, cy) {
+                        continue;
+                    }
+                    let distance = (cx - x).pow(2) + (cy - y).pow(2);
+                    if best.is_none_or(|(_, d)| distance < d) {
+                        best = Some(((cx, cy), distance));
+                    }
+                }
+            }
+            if let Some((cell, _)) = best {
+                return Some(cell);
+            }
+        }
+        None
+    }
+
+    /// A* from `from` to `to` (world positions). Returns the waypoints to walk,
+    /// excluding the start and ending at `to` (or the nearest walkable cell when
+    /// `to` is blocked). None if there is no path.
+    ///
+    /// Endpoints outside the grid are clamped onto its border.
+    pub fn find_path(&mut self, from: [f32; 2], to: [f32; 2], options: &PathOptions) -> Option<Vec<[f32; 2]>> {
+        profiler::profile_scope!("nav.find_path");
+
+        if self.width == 0 || self.height == 0 {
+            return Some(vec![to]);
+        }
+
+        let (sx, sy) = self.world_to_cell(from);
+        let (gx, gy) = self.world_to_cell(to);
+        let start = self.nearest_walkable(sx, sy)?;
+        let goal = self.nearest_walkable(gx, gy)?;
+
+        let cells = self.search(start, goal, options)?;
+
+        let mut points: Vec<[f32; 2]> = Vec::with_capacity(cells.len() + 1);
+        points.push(if start == (sx, sy) { from } else { self.cell_center(start.0, start.1) });
+        points.extend(cells.iter().skip(1).map(|&(x, y)| self.cell_center(x, y)));
+        let goal_point = if goal == (gx, gy) { to } else { self.cell_center(goal.0, goal.1) };
+        if cells.len() > 1 {
+            *points.last_mut().unwrap() = goal_point;
+        } else {
+            points.push(goal_point);
+        }
+
+        if options.smooth {
+            points = self.string_pull(&points, options.cut_corners);
+        }
+        points.remove(0);
+        Some(points)
+    }
+
+    /// Keep only the waypoints needed to stay in line of sight
+    fn string_pull(&self, points: &[[f32; 2]], cut_corners: bool) -> Vec<[f32; 2]> {
+        if points.len() <= 2 {
+            return points.to_vec();
+        }
+        let mut result = vec![points[0]];
+        let mut anchor = 0;
+        while anchor < points.len() - 1 {
+            let mut next = anchor + 1;
+            while next + 1 < points.len() && self.has_line_of_sight(points[anchor], points[next + 1], cut_corners) {
+                next += 1;
+            }
+            result.push(points[next]);
+            anchor = next;
+        }
+        result
+    }
+
+    /// Grid A* returning cells from start to goal (inclusive)
+    fn search(&mut self, start: (i32, i32), goal: (i32, i32), options: &PathOptions) -> Option<Vec<(i32, i32)>> {
+        let cell_count = (self.width * self.height) as usize;
+        self.scratch.prepare(cell_count);
+        let generation = self.scratch.generation;
+
+        let width = self.width as i32;
+        let start_index = self.index(start.0, start.1) as u32;
+        let goal_index = self.index(goal.0, goal.1) as u32;
+        let heuristic = |x: i32, y: i32| {
+            let dx = (x - goal.0).abs() as f32;
+            let dy = (y - goal.1).abs() as f32;
+            if options.allow_diagonal {
+                dx.max(dy) + (SQRT_2 - 1.0) * dx.min(dy)
+            } else {
+                dx + dy
+            }
+        };
+
+        let mut open = BinaryHeap::new();
+        self.scratch.touch(start_index, generation, 0.0, u32::MAX);
+        open.push(OpenNode { f: heuristic(start.0, start.1), g: 0.0, index: start_index });
+
+        const NEIGHBOURS: [(i32, i32, f32); 8] = [
+            (1, 0, 1.0), (-1, 0, 1.0), (0, 1, 1.0), (0, -1, 1.0),
+            (1, 1, SQRT_2), (-1, 1, SQRT_2), (1, -1, SQRT_2), (-1, -1, SQRT_2),
+        ];
+        let neighbour_count = if options.allow_diagonal { 8 } else { 4 };
+
+        while let Some(node) = open.pop() {
+            if node.index == goal_index {
+                return Some(self.scratch.trace(goal_index, width));
+            }
+            let i = node.index as usize;
+            if self.scratch.closed[i] == generation || node.g > self.scratch.g[i] {
+                continue;
+            }
+            self.scratch.closed[i] = generation;
+
+            let (x, y) = (node.index as i32 % width, node.index as i32 / width);
+            for &(dx, dy, cost) in &NEIGHBOURS[..neighbour_count] {
+                let (nx, ny) = (x + dx, y + dy);
+                if !self.is_cell_walkable(nx, ny) {
+                    continue;
+                }
+                if dx != 0 && dy != 0 {
+                    let side_x = self.is_cell_walkable(x + dx, y);
+                    let side_y = self.is_cell_walkable(x, y + dy);
+                    let allowed = if options.cut_corners { side_x || side_y } else { side_x && side_y };
+                    if !allowed {
+                        continue;
+                    }
+                }
+
+                let n = self.index(nx, ny);
+                if self.scratch.closed[n] == generation {
+                    continue;
+                }
+                let g = node.g + cost;
+                if self.scratch.seen[n] != generation || g < self.scratch.g[n] {
+                    self.scratch.touch(n as u32, generation, g, node.index);
+                    open.push(OpenNode { f: g + heuristic(nx, ny), g, index: n as u32 });
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Per-cell search state reused between queries; `generation` marks which
+/// entries belong to the current search so nothing has to be cleared.
+#[derive(Debug, Clone, Default)]
+struct SearchScratch {
+    generation: u32,
+    seen: Vec<u32>,
+    closed: Vec<u32>,
+    g: Vec<f32>,
+    parent: Vec<u32>,
+}
+
+impl SearchScratch {
+    fn prepare(&mut self, cell_count: usize) {
+        if self.seen.len() != cell_count || self.generation == u32::MAX {
+            self.seen = vec![0; cell_count];
+            self.closed = vec![0; cell_count];
+            self.g = vec![0.0; cell_count];
+            self.parent = vec![u32::MAX; cell_count];
+            self.generation = 0;
+        }
+        self.generation += 1;
+    }
+
+    fn touch(&mut self, index: u32, generation: u32, g: f32, parent: u32) {
+        let i = index as usize;
+        self.seen[i] = generation;
+        self.g[i] = g;
+        self.parent[i] = parent;
+    }
+
+    fn trace(&self, goal: u32, width: i32) -> Vec<(i32, i32)> {
+        let mut cells = Vec::new();
+        let mut current = goal;
+        while current != u32::MAX {
+            cells.push((current as i32 % width, current as i32 / width));
+            current = self.parent[current as usize];
+        }
+        cells.reverse();
+        cells
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct OpenNode {
+    f: f32,
+    g: f32,
+    index: u32,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    /// Min-heap on f; ties prefer the node further along (higher g)
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.f.total_cmp(&self.f).then_with(|| self.g.total_cmp(&other.g))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 10x10 grid with a vertical wall at x = 5 from y = 0 to y = 8
+    fn walled_grid() -> NavGrid {
+        let mut grid = NavGrid::new(10, 10, [0.0, 0.0], 1.0);
+        for y in 0..9 {
+            grid.set_blocked(5, y, true);
+        }
+        grid
+    }
+
+    #[test]
+    fn test_path_goes_around_wall() {
+        let mut grid = walled_grid();
+        let options = PathOptions { smooth: false, ..Default::default() };
+        let path = grid.find_path([1.5, 1.5], [8.5, 1.5], &options).unwrap();
+
+        assert_eq!(*path.last().unwrap(), [8.5, 1.5]);
+        // Has to pass over the top of the wall
+        assert!(path.iter().any(|p| p[1] > 9.0));
+        assert!(path.iter().all(|&p| grid.is_walkable(p)));
+
+        // Sealing the gap leaves no path
+        grid.set_blocked(5, 9, true);
+        assert!(grid.find_path([1.5, 1.5], [8.5, 1.5], &options).is_none());
+    }
+
+    #[test]
+    fn test_no_corner_cutting_between_diagonal_walls() {
+        let mut grid = NavGrid::new(3, 3, [0.0, 0.0], 1.0);
+        grid.set_blocked(1, 0, true);
+        grid.set_blocked(0, 1, true);
+        // (0,0) only touches the rest of the grid diagonally
+        let cut = PathOptions { cut_corners: true, ..Default::default() };
+        assert!(grid.find_path([0.5, 0.5], [2.5, 2.5], &cut).is_none());
+
+        grid.set_blocked(0, 1, false);
+        assert!(grid.find_path([0.5, 0.5], [1.5, 1.5], &cut).unwrap().len() == 1);
+        let strict = PathOptions { smooth: false, ..Default::default() };
+        assert_eq!(grid.find_path([0.5, 0.5], [1.5, 1.5], &strict).unwrap(), vec![[0.5, 1.5], [1.5, 1.5]]);
+    }
+
+    #[test]
+    fn test_smoothing_removes_visible_waypoints() {
+        let mut grid = walled_grid();
+        let path = grid.find_path([1.5, 1.5], [8.5, 1.5], &PathOptions::default()).unwrap();
+        // Up to the gap above the wall, then down to the goal
+        assert!(path.len() <= 4, "{:?}", path);
+        let mut previous = [1.5, 1.5];
+        for &point in &path {
+            assert!(grid.has_line_of_sight(previous, point, false));
+            previous = point;
+        }
+        assert!(!grid.has_line_of_sight([1.5, 1.5], [8.5, 1.5], false));
+    }
+}
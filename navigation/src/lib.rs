@@ -0,0 +1,15 @@
+//! Navigation
+//!
+//! Grid-based pathfinding for 2D levels:
+//! - `NavGrid` - walkable cells, A* (4 or 8-way, optional corner cutting) and
+//!   line-of-sight string pulling
+//! - `Navigation` - a NavGrid built from a World's tilemaps, LDtk IntGrid layers and
+//!   static colliders, updated incrementally as they change
+//!
+//! Scripts reach it through the Lua `Nav` table (see the script crate).
+
+mod grid;
+mod world;
+
+pub use grid::{NavGrid, PathOptions};
+pub use world::{NavPath, NavSettings, Navigation};
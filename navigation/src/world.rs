@@ -0,0 +1,427 @@
+use crate::grid::{NavGrid, PathOptions};
+use ecs::{Entity, World};
+use std::collections::HashMap;
+
+/// Cells of padding kept free around the obstacles so paths can go around the edge
+const BORDER_CELLS: f32 = 4.0;
+
+/// How the navigation grid is built and searched
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavSettings {
+    /// World units per cell (1.0 = one tile)
+    pub cell_size: f32,
+    pub allow_diagonal: bool,
+    pub cut_corners: bool,
+    pub smooth: bool,
+    /// Rasterize static colliders (no rigidbody, or kinematic) into the grid
+    pub include_colliders: bool,
+    /// Largest grid side in cells; bigger worlds are clipped
+    pub max_dimension: u32,
+}
+
+impl Default for NavSettings {
+    fn default() -> Self {
+        Self {
+            cell_size: 1.0,
+            allow_diagonal: true,
+            cut_corners: false,
+            smooth: true,
+            include_colliders: true,
+            max_dimension: 2048,
+        }
+    }
+}
+
+impl NavSettings {
+    pub fn path_options(&self) -> PathOptions {
+        PathOptions {
+            allow_diagonal: self.allow_diagonal,
+            cut_corners: self.cut_corners,
+            smooth: self.smooth,
+        }
+    }
+}
+
+/// Last path found through `Navigation::find_path` (editor overlay)
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavPath {
+    pub from: [f32; 2],
+    pub to: [f32; 2],
+    /// Empty when no path was found
+    pub waypoints: Vec<[f32; 2]>,
+}
+
+/// Something in the world that blocks cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NavSource {
+    /// Tilemap with a TilemapCollider
+    Tilemap(Entity),
+    /// LDtk map with an LdtkIntGridCollider
+    IntGrid(Entity),
+    /// Static collider
+    Collider(Entity),
+}
+
+struct SourceState {
+    fingerprint: u64,
+    /// Grid cells this source blocked when it was last rasterized
+    cells: Vec<u32>,
+}
+
+/// Blocked world-space rects of one source
+struct SourceShape {
+    fingerprint: u64,
+    bounds: [f32; 4],
+    rects: Vec<[f32; 4]>,
+}
+
+/// Walkable grid kept in sync with a World.
+///
+/// Obstacles come from tilemaps with a TilemapCollider, LDtk maps with an
+/// LdtkIntGridCollider and (optionally) static colliders. `update` only
+/// re-rasterizes sources whose data changed; the whole grid is rebuilt when the
+/// obstacles outgrow it or the cell size changes.
+#[derive(Default)]
+pub struct Navigation {
+    pub settings: NavSettings,
+    grid: NavGrid,
+    sources: HashMap<NavSource, SourceState>,
+    last_path: Option<NavPath>,
+}
+
+impl Navigation {
+    pub fn new(settings: NavSettings) -> Self {
+        Self { settings, ..Default::default() }
+    }
+
+    pub fn grid(&self) -> &NavGrid {
+        &self.grid
+    }
+
+    pub fn last_path(&self) -> Option<&NavPath> {
+        self.last_path.as_ref()
+    }
+
+    /// Forget the grid (e.g. when play mode stops)
+    pub fn clear(&mut self) {
+        self.grid = NavGrid::default();
+        self.sources.clear();
+        self.last_path = None;
+    }
+
+    pub fn is_walkable(&self, x: f32, y: f32) -> bool {
+        self.grid.is_walkable([x, y])
+    }
+
+    /// Path between two world positions with the current settings (remembered for the overlay)
+    pub fn find_path(&mut self, from: [f32; 2], to: [f32; 2]) -> Option<Vec<[f32; 2]>> {
+        let options = self.settings.path_options();
+        let path = self.grid.find_path(from, to, &options);
+        self.last_path = Some(NavPath { from, to, waypoints: path.clone().unwrap_or_default() });
+        path
+    }
+
+    /// Bring the grid up to date with `world`
+    pub fn update(&mut self, world: &World) {
+        profiler::profile_scope!("nav.update");
+
+        let shapes = self.collect_sources(world);
+
+        let Some(needed) = shapes.values().map(|shape| shape.bounds).reduce(union) else {
+            if self.grid.width() > 0 || !self.sources.is_empty() {
+                self.grid = NavGrid::default();
+                self.sources.clear();
+            }
+            return;
+        };
+
+        let cell_size = self.settings.cell_size.max(0.01);
+        let current = self.grid.bounds();
+        let fits = self.grid.width() > 0
+            && self.grid.cell_size() == cell_size
+            && needed[0] >= current[0]
+            && needed[1] >= current[1]
+            && needed[2] <= current[2]
+            && needed[3] <= current[3];
+
+        if !fits {
+            self.rebuild(needed, cell_size, shapes);
+            return;
+        }
+
+        // Incremental: drop removed sources, re-rasterize changed ones
+        let removed: Vec<NavSource> = self.sources.keys().filter(|key| !shapes.contains_key(key)).copied().collect();
+        for key in removed {
+            if let Some(state) = self.sources.remove(&key) {
+                state.cells.iter().for_each(|&cell| self.grid.remove_obstacle(cell));
+            }
+        }
+        for (key, shape) in shapes {
+            if self.sources.get(&key).is_some_and(|state| state.fingerprint == shape.fingerprint) {
+                continue;
+            }
+            if let Some(state) = self.sources.remove(&key) {
+                state.cells.iter().for_each(|&cell| self.grid.remove_obstacle(cell));
+            }
+            let state = self.rasterize(shape);
+            self.sources.insert(key, state);
+        }
+    }
+
+    fn rebuild(&mut self, needed: [f32; 4], cell_size: f32, shapes: HashMap<NavSource, SourceShape>) {
+        let border = BORDER_CELLS * cell_size;
+        let min_x = ((needed[0] - border) / cell_size).floor() * cell_size;
+        let min_y = ((needed[1] - border) / cell_size).floor() * cell_size;
+        let max_dimension = self.settings.max_dimension.max(1);
+        let width = (((needed[2] + border - min_x) / cell_size).ceil() as u32).clamp(1, max_dimension);
+        let height = (((needed[3] + border - min_y) / cell_size).ceil() as u32).clamp(1, max_dimension);
+        if width == max_dimension || height == max_dimension {
+            log::warn!("Navigation grid clipped to {}x{} cells; increase the cell size for large worlds", width, height);
+        }
+
+        self.grid = NavGrid::new(width, height, [min_x, min_y], cell_size);
+        self.sources.clear();
+        for (key, shape) in shapes {
+            let state = self.rasterize(shape);
+            self.sources.insert(key, state);
+        }
+        log::debug!("Navigation grid rebuilt: {}x{} cells, {} sources", width, height, self.sources.len());
+    }
+
+    fn rasterize(&mut self, shape: SourceShape) -> SourceState {
+        let mut cells = Vec::new();
+        for rect in &shape.rects {
+            if let Some(((x0, y0), (x1, y1))) = self.grid.cells_in_rect([rect[0], rect[1]], [rect[2], rect[3]]) {
+                for y in y0..=y1 {
+                    for x in x0..=x1 {
+                        cells.push(y as u32 * self.grid.width() + x as u32);
+                    }
+                }
+            }
+        }
+        // Overlapping rects of one source count once
+        cells.sort_unstable();
+        cells.dedup();
+        cells.iter().for_each(|&cell| self.grid.add_obstacle(cell));
+        SourceState { fingerprint: shape.fingerprint, cells }
+    }
+
+    fn collect_sources(&self, world: &World) -> HashMap<NavSource, SourceShape> {
+        let mut shapes = HashMap::new();
+        let active = |entity: &Entity| world.is_active_in_hierarchy(*entity);
+
+        for (&entity, tilemap) in &world.tilemaps {
+            let (Some(collider), true) = (world.tilemap_colliders.get(&entity), active(&entity)) else {
+                continue;
+            };
+            if collider.is_trigger || collider.mode == ecs::TilemapColliderMode::None {
+                continue;
+            }
+            let position = world.transforms.get(&entity).map(|t| t.position).unwrap_or_default();
+            if let Some(shape) = tilemap_shape(tilemap, collider, [position[0], position[1]]) {
+                shapes.insert(NavSource::Tilemap(entity), shape);
+            }
+        }
+
+        for (&entity, map) in &world.ldtk_maps {
+            let (Some(collider), true) = (world.ldtk_intgrid_colliders.get(&entity), active(&entity)) else {
+                continue;
+            };
+            if collider.is_trigger {
+                continue;
+            }
+            if let Some(shape) = intgrid_shape(map, collider.collision_value) {
+                shapes.insert(NavSource::IntGrid(entity), shape);
+            }
+        }
+
+        if self.settings.include_colliders {
+            for (&entity, collider) in &world.colliders {
+                let is_static = world.rigidbodies.get(&entity).is_none_or(|rb| rb.is_kinematic);
+                if collider.is_trigger || !is_static || !active(&entity) {
+                    continue;
+                }
+                let Some(transform) = world.transforms.get(&entity) else {
+                    continue;
+                };
+                let half = [
+                    collider.get_world_width(transform.scale[0]).abs() / 2.0,
+                    collider.get_world_height(transform.scale[1]).abs() / 2.0,
+                ];
+                let offset = collider.get_flipped_offset(transform.scale[0], transform.scale[1], world.sprite_flip(entity));
+                let center = [transform.position[0] + offset[0], transform.position[1] + offset[1]];
+                let rect = [center[0] - half[0], center[1] - half[1], center[0] + half[0], center[1] + half[1]];
+                let mut hasher = Fnv::new();
+                rect.iter().for_each(|v| hasher.write_u32(v.to_bits()));
+                shapes.insert(NavSource::Collider(entity), SourceShape { fingerprint: hasher.finish(), bounds: rect, rects: vec![rect] });
+            }
+        }
+
+        shapes
+    }
+}
+
+/// Solid tiles of a tilemap. Tile (x, y) covers [tx + x, tx + x + 1] horizontally and
+/// [ty - y - 1, ty - y] vertically (rows go down from the tilemap's position).
+fn tilemap_shape(tilemap: &ecs::Tilemap, collider: &ecs::TilemapCollider, position: [f32; 2]) -> Option<SourceShape> {
+    let mut hasher = Fnv::new();
+    hasher.write_u32(position[0].to_bits());
+    hasher.write_u32(position[1].to_bits());
+    hasher.write_u32(tilemap.width);
+    collider.collision_tiles.iter().for_each(|&id| hasher.write_u32(id));
+
+    let mut rects = Vec::new();
+    for y in 0..tilemap.height {
+        // One rect per horizontal run of solid tiles
+        let mut run_start: Option<u32> = None;
+        for x in 0..=tilemap.width {
+            let solid = x < tilemap.width
+                && tilemap.get_tile(x, y).is_some_and(|tile| collider.should_collide(tile.tile_id));
+            if solid {
+                hasher.write_u32(y * tilemap.width + x);
+            }
+            match (solid, run_start) {
+                (true, None) => run_start = Some(x),
+                (false, Some(start)) => {
+                    let top = position[1] - y as f32;
+                    rects.push([position[0] + start as f32, top - 1.0, position[0] + x as f32, top]);
+                    run_start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if rects.is_empty() {
+        return None;
+    }
+    let bounds = rects.iter().copied().reduce(union)?;
+    Some(SourceShape { fingerprint: hasher.finish(), bounds, rects })
+}
+
+/// IntGrid cells equal to `collision_value`, placed the same way the LDtk loader does
+/// (1 world unit = the project's default grid size, Y flipped)
+fn intgrid_shape(map: &ecs::LdtkMap, collision_value: i32) -> Option<SourceShape> {
+    let pixels_per_unit = map.default_grid_size.max(1) as f32;
+    let mut hasher = Fnv::new();
+    let mut rects = Vec::new();
+
+    for level in &map.levels {
+        for layer in level.layer_instances.iter().flatten() {
+            if layer.__type != "IntGrid" || layer.int_grid_csv.is_empty() {
+                continue;
+            }
+            let cell = layer.__grid_size as f32 / pixels_per_unit;
+            let left = (level.world_x + layer.__px_total_offset_x) as f32 / pixels_per_unit;
+            let top = -((level.world_y + layer.__px_total_offset_y) as f32) / pixels_per_unit;
+            hasher.write_u32(left.to_bits());
+            hasher.write_u32(top.to_bits());
+
+            let width = layer.__c_wid.max(0) as usize;
+            for (index, &value) in layer.int_grid_csv.iter().enumerate() {
+                if value != collision_value || width == 0 {
+                    continue;
+                }
+                hasher.write_u32(index as u32);
+                let (x, y) = ((index % width) as f32, (index / width) as f32);
+                let cell_top = top - y * cell;
+                rects.push([left + x * cell, cell_top - cell, left + (x + 1.0) * cell, cell_top]);
+            }
+        }
+    }
+
+    if rects.is_empty() {
+        return None;
+    }
+    let bounds = rects.iter().copied().reduce(union)?;
+    Some(SourceShape { fingerprint: hasher.finish(), bounds, rects })
+}
+
+fn union(a: [f32; 4], b: [f32; 4]) -> [f32; 4] {
+    [a[0].min(b[0]), a[1].min(b[1]), a[2].max(b[2]), a[3].max(b[3])]
+}
+
+/// FNV-1a; cheap enough to fingerprint large tilemaps every frame
+struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write_u32(&mut self, value: u32) {
+        for byte in value.to_le_bytes() {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world_with_wall() -> (World, Entity) {
+        let mut world = World::new();
+        let entity = world.spawn();
+        let mut tilemap = ecs::Tilemap::new("Walls", "tiles", 10, 10);
+        for y in 0..9 {
+            tilemap.set_tile_id(5, y, 1);
+        }
+        world.tilemaps.insert(entity, tilemap);
+        world.tilemap_colliders.insert(entity, ecs::TilemapCollider::new());
+        world.transforms.insert(entity, ecs::Transform::with_position(0.0, 10.0, 0.0));
+        (world, entity)
+    }
+
+    #[test]
+    fn test_tilemap_walls_block_and_update_incrementally() {
+        let (mut world, entity) = world_with_wall();
+        let mut navigation = Navigation::default();
+        navigation.update(&world);
+
+        // Tile (5, 0) sits just below the tilemap's top edge
+        assert!(!navigation.is_walkable(5.5, 9.5));
+        assert!(navigation.is_walkable(4.5, 9.5));
+        // Row 9 is open, so there is a way over the wall
+        assert!(navigation.find_path([1.5, 5.5], [8.5, 5.5]).is_some());
+        assert_eq!(navigation.last_path().unwrap().to, [8.5, 5.5]);
+
+        let origin = navigation.grid().origin();
+        world.tilemaps.get_mut(&entity).unwrap().set_tile_id(5, 4, 0);
+        navigation.update(&world);
+        assert!(navigation.is_walkable(5.5, 5.5));
+        // Same bounds: updated in place rather than rebuilt
+        assert_eq!(navigation.grid().origin(), origin);
+
+        world.tilemap_colliders.remove(&entity);
+        navigation.update(&world);
+        assert!(navigation.is_walkable(5.5, 9.5));
+    }
+
+    #[test]
+    fn test_static_colliders_are_rasterized() {
+        let mut world = World::new();
+        let wall = world.spawn();
+        world.transforms.insert(wall, ecs::Transform::with_position(0.0, 0.0, 0.0));
+        world.colliders.insert(wall, ecs::Collider::new(1.0, 6.0));
+
+        // Dynamic bodies don't block
+        let player = world.spawn();
+        world.transforms.insert(player, ecs::Transform::with_position(-3.0, 0.0, 0.0));
+        world.colliders.insert(player, ecs::Collider::new(1.0, 1.0));
+        world.rigidbodies.insert(player, ecs::Rigidbody2D::default());
+
+        let mut navigation = Navigation::default();
+        navigation.update(&world);
+        assert!(!navigation.is_walkable(0.0, 2.0));
+        assert!(navigation.is_walkable(-3.0, 0.0));
+
+        let path = navigation.find_path([-3.0, 0.0], [3.0, 0.0]).unwrap();
+        assert!(path.iter().all(|p| p[1].abs() > 3.0 || p[0].abs() > 0.5));
+    }
+}
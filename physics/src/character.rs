@@ -0,0 +1,82 @@
+//! Character Movement
+//!
+//! Kinematic move-and-slide: the character moves by a requested offset, stops at solid
+//! colliders and slides along them. The simple backend resolves it with the narrow phase
+//! (one axis at a time); the Rapier backend uses its character controller.
+
+use crate::narrow_phase;
+use ecs::{Entity, World};
+
+/// Surface normals within 45° of straight up count as floor, of straight down as ceiling
+const FLOOR_MIN_UP: f32 = std::f32::consts::FRAC_1_SQRT_2;
+/// Push-out passes per axis (one per overlapping collider in practice)
+const MAX_RESOLVE_PASSES: usize = 4;
+
+/// Result of a move_and_slide
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CharacterMove {
+    /// How far the character actually moved
+    pub translation: [f32; 2],
+    pub on_floor: bool,
+    pub on_wall: bool,
+    pub on_ceiling: bool,
+}
+
+impl CharacterMove {
+    /// Record a surface the character ran into; `normal` points from the surface towards the
+    /// character (engine coordinates, +Y up)
+    pub fn hit(&mut self, normal: [f32; 2]) {
+        if normal[1] >= FLOOR_MIN_UP {
+            self.on_floor = true;
+        } else if normal[1] <= -FLOOR_MIN_UP {
+            self.on_ceiling = true;
+        } else {
+            self.on_wall = true;
+        }
+    }
+}
+
+pub(crate) fn move_and_slide(world: &mut World, entity: Entity, delta: [f32; 2]) -> CharacterMove {
+    let mut result = CharacterMove::default();
+    let Some(start) = world.world_position(entity) else {
+        return result;
+    };
+
+    // Solid colliders that aren't part of the character itself
+    let own = world.subtree(entity);
+    let obstacles: Vec<Entity> = crate::PhysicsWorld::collider_entities(world)
+        .into_iter()
+        .filter(|e| !own.contains(e) && world.is_active_in_hierarchy(*e) && !crate::is_trigger(world, *e))
+        .collect();
+
+    // One axis at a time, so motion blocked on one axis still slides along the other
+    for axis in 0..2 {
+        if delta[axis] == 0.0 {
+            continue;
+        }
+        let Some(mut position) = world.world_position(entity) else {
+            break;
+        };
+        position[axis] += delta[axis];
+        world.set_world_position(entity, position);
+
+        for _ in 0..MAX_RESOLVE_PASSES {
+            let deepest = obstacles
+                .iter()
+                .filter_map(|&other| narrow_phase::entity_contact(world, entity, other))
+                .max_by(|a, b| a.depth.total_cmp(&b.depth));
+            let Some(contact) = deepest else {
+                break;
+            };
+            position[0] += contact.normal[0] * contact.depth;
+            position[1] += contact.normal[1] * contact.depth;
+            world.set_world_position(entity, position);
+            result.hit(contact.normal);
+        }
+    }
+
+    if let Some(end) = world.world_position(entity) {
+        result.translation = [end[0] - start[0], end[1] - start[1]];
+    }
+    result
+}
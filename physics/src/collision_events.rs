@@ -0,0 +1,113 @@
+//! Collision Events
+//!
+//! Enter/Stay/Exit for pairs of touching colliders, the same for both backends: after every
+//! step the backend reports which pairs touch, and the tracker compares that with the
+//! previous step. Events queue up until the game loop takes them (once per frame).
+
+use ecs::Entity;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollisionPhase {
+    /// First step the pair touches
+    Enter,
+    /// Every later step it still touches
+    Stay,
+    /// First step it no longer touches (or one of them was removed)
+    Exit,
+}
+
+/// A pair of colliding entities; `entity` is always the lower id
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollisionEvent {
+    pub entity: Entity,
+    pub other: Entity,
+    pub phase: CollisionPhase,
+}
+
+/// Turns the touching pairs of each step into enter/stay/exit events
+#[derive(Debug, Default)]
+pub struct CollisionTracker {
+    touching: BTreeSet<(Entity, Entity)>,
+    events: Vec<CollisionEvent>,
+}
+
+impl CollisionTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the pairs touching after a step (either order, duplicates are fine)
+    pub fn record_step(&mut self, pairs: impl IntoIterator<Item = (Entity, Entity)>) {
+        let now: BTreeSet<(Entity, Entity)> = pairs
+            .into_iter()
+            .filter(|(a, b)| a != b)
+            .map(|(a, b)| (a.min(b), a.max(b)))
+            .collect();
+
+        for &(entity, other) in self.touching.difference(&now) {
+            self.events.push(CollisionEvent { entity, other, phase: CollisionPhase::Exit });
+        }
+        for &(entity, other) in &now {
+            let phase = if self.touching.contains(&(entity, other)) { CollisionPhase::Stay } else { CollisionPhase::Enter };
+            self.events.push(CollisionEvent { entity, other, phase });
+        }
+        self.touching = now;
+    }
+
+    /// An entity left the simulation: its pairs exit now
+    pub fn remove_entity(&mut self, entity: Entity) {
+        let gone: Vec<_> = self.touching.iter().copied().filter(|&(a, b)| a == entity || b == entity).collect();
+        for pair in gone {
+            self.touching.remove(&pair);
+            self.events.push(CollisionEvent { entity: pair.0, other: pair.1, phase: CollisionPhase::Exit });
+        }
+    }
+
+    /// Whether two entities touched after the last step
+    pub fn is_touching(&self, a: Entity, b: Entity) -> bool {
+        self.touching.contains(&(a.min(b), a.max(b)))
+    }
+
+    /// Events since the last call, in step order
+    pub fn take_events(&mut self) -> Vec<CollisionEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn clear(&mut self) {
+        self.touching.clear();
+        self.events.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn phases(events: &[CollisionEvent]) -> Vec<(Entity, Entity, CollisionPhase)> {
+        events.iter().map(|e| (e.entity, e.other, e.phase)).collect()
+    }
+
+    #[test]
+    fn test_enter_stay_exit() {
+        let mut tracker = CollisionTracker::new();
+        tracker.record_step([(2, 1)]);
+        tracker.record_step([(1, 2), (3, 1)]);
+        tracker.record_step([(1, 3)]);
+        assert_eq!(
+            phases(&tracker.take_events()),
+            vec![
+                (1, 2, CollisionPhase::Enter),
+                (1, 2, CollisionPhase::Stay),
+                (1, 3, CollisionPhase::Enter),
+                (1, 2, CollisionPhase::Exit),
+                (1, 3, CollisionPhase::Stay),
+            ]
+        );
+        assert!(tracker.take_events().is_empty());
+
+        tracker.remove_entity(3);
+        assert_eq!(phases(&tracker.take_events()), vec![(1, 3, CollisionPhase::Exit)]);
+        assert!(!tracker.is_touching(1, 3));
+    }
+}
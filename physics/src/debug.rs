@@ -0,0 +1,106 @@
+//! Physics Debug Draw
+//!
+//! Optional contact points and broad-phase bounds (one AABB per collider) queued on the
+//! shared debug draw handle, for both backends. The Rapier backend also draws collider
+//! outlines, contact normals and joint anchors from Rapier's debug render pipeline.
+//! Everything is off until a handle is set and a toggle is on.
+
+use engine_core::debug_draw::{DebugCategory, DebugDrawHandle};
+use engine_core::Color;
+
+/// Cross size for contact points, in world units
+const CONTACT_SIZE: f32 = 0.2;
+
+/// What a physics debug line shows; each has its own color and Gizmos toggle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PhysicsDebugLayer {
+    /// Collider outlines (Rapier)
+    Collider,
+    /// Collider AABBs, as tested by the broad phase
+    Broadphase,
+    /// Contact points and penetration depth
+    Contact,
+    /// Contact normals (Rapier)
+    ContactNormal,
+    /// Joint anchors and the separation between them (Rapier)
+    Joint,
+}
+
+impl PhysicsDebugLayer {
+    pub fn color(self) -> Color {
+        match self {
+            Self::Collider => Color::rgba(0.4, 1.0, 0.4, 0.8),
+            Self::Broadphase => Color::rgba(0.3, 0.8, 1.0, 0.6),
+            Self::Contact => Color::rgb(1.0, 0.3, 0.2),
+            Self::ContactNormal => Color::rgb(1.0, 0.9, 0.2),
+            Self::Joint => Color::rgb(0.9, 0.4, 1.0),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct PhysicsDebugDraw {
+    /// Where draws go (None = physics never draws)
+    pub handle: Option<DebugDrawHandle>,
+    /// Cross at every contact point found this step (and the normals, with Rapier)
+    pub draw_contacts: bool,
+    /// Bounds the broad phase tests for every collider
+    pub draw_broadphase: bool,
+    /// Collider outlines (Rapier only)
+    pub draw_colliders: bool,
+    /// Joint anchors (Rapier only)
+    pub draw_joints: bool,
+    /// Seconds contact draws stay up (0 = the frame they were found in)
+    pub contact_duration: f32,
+}
+
+impl PhysicsDebugDraw {
+    fn enabled(&self, toggle: bool) -> bool {
+        engine_core::debug_draw::ENABLED && toggle && self.handle.is_some()
+    }
+
+    pub(crate) fn contacts_enabled(&self) -> bool {
+        self.enabled(self.draw_contacts)
+    }
+
+    pub(crate) fn broadphase_enabled(&self) -> bool {
+        self.enabled(self.draw_broadphase)
+    }
+
+    #[cfg_attr(not(feature = "rapier"), allow(dead_code))]
+    pub(crate) fn colliders_enabled(&self) -> bool {
+        self.enabled(self.draw_colliders)
+    }
+
+    #[cfg_attr(not(feature = "rapier"), allow(dead_code))]
+    pub(crate) fn joints_enabled(&self) -> bool {
+        self.enabled(self.draw_joints)
+    }
+
+    pub(crate) fn contact(&self, point: [f32; 2]) {
+        if let Some(handle) = &self.handle {
+            let color = PhysicsDebugLayer::Contact.color();
+            handle.borrow_mut().draw_cross(DebugCategory::Physics, [point[0], point[1], 0.0], CONTACT_SIZE, color, self.contact_duration);
+        }
+    }
+
+    pub(crate) fn bounds(&self, min: [f32; 2], max: [f32; 2]) {
+        if let Some(handle) = &self.handle {
+            let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0, 0.0];
+            let size = [max[0] - min[0], max[1] - min[1]];
+            handle.borrow_mut().draw_box(DebugCategory::Physics, center, size, PhysicsDebugLayer::Broadphase.color(), 0.0);
+        }
+    }
+
+    /// Line in engine coordinates; contact layers stay up for `contact_duration`
+    #[cfg_attr(not(feature = "rapier"), allow(dead_code))]
+    pub(crate) fn line(&self, layer: PhysicsDebugLayer, a: [f32; 2], b: [f32; 2]) {
+        if let Some(handle) = &self.handle {
+            let duration = match layer {
+                PhysicsDebugLayer::Contact | PhysicsDebugLayer::ContactNormal => self.contact_duration,
+                _ => 0.0,
+            };
+            handle.borrow_mut().draw_line(DebugCategory::Physics, [a[0], a[1], 0.0], [b[0], b[1], 0.0], layer.color(), duration);
+        }
+    }
+}
@@ -6,7 +6,18 @@
 //! - Simple backend: Custom lightweight physics (default for learning)
 //! - Rapier backend: Production-ready physics engine (recommended for production)
 
-use ecs::{World, Entity};
+use ecs::{EntityOrdered, World, Entity};
+
+pub mod narrow_phase;
+pub mod debug;
+pub mod collision_events;
+pub mod character;
+pub mod trigger_zones;
+
+pub use debug::{PhysicsDebugDraw, PhysicsDebugLayer};
+pub use collision_events::{CollisionEvent, CollisionPhase, CollisionTracker};
+pub use character::CharacterMove;
+pub use trigger_zones::{TriggerZoneArea, TriggerZoneEvent, TriggerZonePhase, TriggerZoneTracker};
 
 #[cfg(feature = "rapier")]
 pub mod rapier_backend;
@@ -19,6 +30,10 @@ pub struct PhysicsWorld {
     pub gravity: f32,           // Gravity acceleration (pixels/s²)
     pub enabled: bool,          // Enable/disable physics
     pub time_scale: f32,        // Time scale for slow motion effects
+    pub world_bounds: Option<([f32; 2], [f32; 2])>,  // (min, max) non-kinematic bodies are kept inside
+    pub debug_draw: PhysicsDebugDraw, // Contact points / broad-phase bounds (off by default)
+    collisions: CollisionTracker,       // Enter/stay/exit between touching colliders
+    trigger_zones: TriggerZoneTracker,  // Enter/exit of TriggerZone areas
 }
 
 impl Default for PhysicsWorld {
@@ -27,6 +42,10 @@ impl Default for PhysicsWorld {
             gravity: 150.0,     // Further reduced for easier jumping (was 300, originally 980)
             enabled: true,
             time_scale: 1.0,
+            world_bounds: Some(([-100.0, -100.0], [100.0, 100.0])),
+            debug_draw: PhysicsDebugDraw::default(),
+            collisions: CollisionTracker::new(),
+            trigger_zones: TriggerZoneTracker::new(),
         }
     }
 }
@@ -36,8 +55,10 @@ impl PhysicsWorld {
         Self::default()
     }
 
-    /// Update physics simulation
+    /// Update physics simulation. Every pass visits bodies in entity-id order, so the
+    /// same scene gives the same result on every run and machine.
     pub fn step(&mut self, dt: f32, world: &mut World) {
+        profiler::profile_scope!("physics.step");
         if !self.enabled {
             return;
         }
@@ -57,16 +78,33 @@ impl PhysicsWorld {
 
         // Check and resolve collisions
         self.check_collisions(world);
+
+        // Trigger zones see where everything ended up
+        self.trigger_zones.update(world, |world, area, layer_mask| match area {
+            TriggerZoneArea::Box { min, max } => Self::overlap_aabb(world, min, max, layer_mask),
+            TriggerZoneArea::Circle { center, radius } => Self::overlap_circle(world, center, radius, layer_mask),
+        });
+
+        if self.debug_draw.broadphase_enabled() {
+            for entity in Self::collider_entities(world) {
+                if !world.is_active_in_hierarchy(entity) {
+                    continue;
+                }
+                for shape in narrow_phase::entity_shapes(world, entity) {
+                    let (min, max) = narrow_phase::bounds(&shape);
+                    self.debug_draw.bounds(min, max);
+                }
+            }
+        }
     }
 
     /// Apply gravity to all entities with Rigidbody
     fn apply_gravity(&self, dt: f32, world: &mut World) {
-        let entities: Vec<Entity> = world.rigidbodies.keys().cloned().collect();
+        let entities: Vec<Entity> = world.rigidbodies.sorted_entities();
 
         for entity in entities {
             // Skip if entity is not active
-            let is_active = world.active.get(&entity).copied().unwrap_or(true);
-            if !is_active {
+            if !world.is_active_in_hierarchy(entity) {
                 continue;
             }
 
@@ -98,11 +136,11 @@ impl PhysicsWorld {
 
     /// Update positions based on velocity (Euler integration)
     fn update_positions(&self, dt: f32, world: &mut World) {
-        let entities: Vec<Entity> = world.rigidbodies.keys().cloned().collect();
+        let entities: Vec<Entity> = world.rigidbodies.sorted_entities();
 
         for entity in entities {
             // Skip if entity is not active
-            if !world.active.get(&entity).copied().unwrap_or(true) {
+            if !world.is_active_in_hierarchy(entity) {
                 continue;
             }
 
@@ -127,13 +165,11 @@ impl PhysicsWorld {
 
     /// Apply world bounds to prevent objects from falling infinitely
     fn apply_world_bounds(&self, world: &mut World) {
-        // Define world bounds (can be made configurable later)
-        let min_y = -100.0;  // Bottom bound
-        let max_y = 100.0;   // Top bound (optional)
-        let min_x = -100.0;  // Left bound (optional)
-        let max_x = 100.0;   // Right bound (optional)
+        let Some(([min_x, min_y], [max_x, max_y])) = self.world_bounds else {
+            return;
+        };
 
-        let entities: Vec<Entity> = world.rigidbodies.keys().cloned().collect();
+        let entities: Vec<Entity> = world.rigidbodies.sorted_entities();
 
         for entity in entities {
             // Check if kinematic first
@@ -206,8 +242,10 @@ impl PhysicsWorld {
     }
 
     /// Check collisions between all entities with colliders and resolve them
-    fn check_collisions(&self, world: &mut World) {
-        let entities_with_colliders: Vec<Entity> = world.colliders.keys().cloned().collect();
+    fn check_collisions(&mut self, world: &mut World) {
+        let entities_with_colliders = Self::collider_entities(world);
+        // Overlapping pairs this step (before resolution pushes them apart), for collision events
+        let mut touching = Vec::new();
 
         // Simple O(n²) collision detection and response
         for i in 0..entities_with_colliders.len() {
@@ -216,47 +254,150 @@ impl PhysicsWorld {
                 let e2 = entities_with_colliders[j];
 
                 // Skip if either entity is not active
-                if !world.active.get(&e1).copied().unwrap_or(true) ||
-                   !world.active.get(&e2).copied().unwrap_or(true) {
+                if !world.is_active_in_hierarchy(e1) || !world.is_active_in_hierarchy(e2) {
+                    continue;
+                }
+
+                // Static colliders never collide with each other (same as Rapier's fixed bodies)
+                if !world.rigidbodies.contains_key(&e1) && !world.rigidbodies.contains_key(&e2) {
+                    continue;
+                }
+
+                // Broad phase: bounds (of the rotated corners for rotated boxes) must overlap
+                if !Self::bounds_overlap(world, e1, e2) {
+                    continue;
+                }
+
+                // Triggers only report overlaps, they never push bodies apart
+                if is_trigger(world, e1) || is_trigger(world, e2) {
+                    if Self::check_collision(world, e1, e2) {
+                        touching.push((e1, e2));
+                    }
+                    continue;
+                }
+
+                // Polygon and rotated colliders go through the SAT narrow phase
+                if Self::needs_narrow_phase(world, e1, e2) {
+                    if let Some(contact) = narrow_phase::entity_contact(world, e1, e2) {
+                        touching.push((e1, e2));
+                        self.draw_contact(world, e1, e2);
+                        Self::resolve_contact(world, e1, e2, contact);
+                    }
                     continue;
                 }
 
                 if Self::check_collision(world, e1, e2) {
                     // Collision detected - resolve it
+                    touching.push((e1, e2));
+                    self.draw_contact(world, e1, e2);
                     Self::resolve_collision(world, e1, e2);
                 }
             }
         }
+
+        self.collisions.record_step(touching);
+    }
+
+    /// Collision enter/stay/exit events since the last call (one batch per step)
+    pub fn take_collision_events(&mut self) -> Vec<CollisionEvent> {
+        self.collisions.take_events()
+    }
+
+    /// Trigger zone enter/exit events since the last call (one batch per step)
+    pub fn take_trigger_zone_events(&mut self) -> Vec<TriggerZoneEvent> {
+        self.trigger_zones.take_events()
+    }
+
+    /// Move a character by `delta`, stopping at colliders and sliding along them, and report
+    /// what it touched. Works on the ECS colliders, so scripts get the same result with either backend.
+    pub fn move_and_slide(world: &mut World, entity: Entity, delta: [f32; 2]) -> CharacterMove {
+        character::move_and_slide(world, entity, delta)
+    }
+
+    /// Debug cross where two colliding entities touch: the point of `e2`'s collider closest to `e1`'s center
+    fn draw_contact(&self, world: &World, e1: Entity, e2: Entity) {
+        if !self.debug_draw.contacts_enabled() {
+            return;
+        }
+        let Some(shape) = narrow_phase::entity_shapes(world, e1).into_iter().next() else {
+            return;
+        };
+        let (min, max) = narrow_phase::bounds(&shape);
+        let center = [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0];
+        if let Some(point) = Self::closest_point_on_collider(world, e2, center) {
+            self.debug_draw.contact(point);
+        }
+    }
+
+    /// Entities with a box/circle collider or a polygon collider, sorted
+    pub(crate) fn collider_entities(world: &World) -> Vec<Entity> {
+        let mut entities: Vec<Entity> = world.colliders.keys().chain(world.polygon_colliders.keys()).cloned().collect();
+        entities.sort();
+        entities.dedup();
+        entities
+    }
+
+    fn has_polygon(world: &World, entity: Entity) -> bool {
+        world.polygon_colliders.contains_key(&entity)
+    }
+
+    /// Pairs the AABB test can't handle: polygons and boxes rotated off the axes
+    fn needs_narrow_phase(world: &World, e1: Entity, e2: Entity) -> bool {
+        [e1, e2].into_iter().any(|e| Self::has_polygon(world, e) || narrow_phase::is_rotated(world, e))
+    }
+
+    fn bounds_overlap(world: &World, e1: Entity, e2: Entity) -> bool {
+        match (narrow_phase::entity_bounds(world, e1), narrow_phase::entity_bounds(world, e2)) {
+            (Some((min1, max1)), Some((min2, max2))) => {
+                min1[0] <= max2[0] && min2[0] <= max1[0] && min1[1] <= max2[1] && min2[1] <= max1[1]
+            }
+            _ => false,
+        }
+    }
+
+    /// Separate two entities along a narrow-phase contact normal (same rigidbody rules as `resolve_collision`)
+    fn resolve_contact(world: &mut World, e1: Entity, e2: Entity, contact: narrow_phase::Contact) {
+        let is_dynamic = |e: Entity| world.rigidbodies.get(&e).map(|rb| !rb.is_kinematic).unwrap_or(false);
+        let [nx, ny] = contact.normal;
+
+        // (entity, push along the normal)
+        let pushes: Vec<(Entity, f32)> = match (is_dynamic(e1), is_dynamic(e2)) {
+            (true, true) => vec![(e1, contact.depth / 2.0), (e2, -contact.depth / 2.0)],
+            (true, false) => vec![(e1, contact.depth)],
+            (false, true) => vec![(e2, -contact.depth)],
+            (false, false) => Vec::new(),
+        };
+
+        for (entity, push) in pushes {
+            if let Some(transform) = world.transforms.get_mut(&entity) {
+                transform.position[0] += nx * push;
+                transform.position[1] += ny * push;
+            }
+            if let Some(rb) = world.rigidbodies.get_mut(&entity) {
+                // Stop velocity moving into the other body
+                let along = rb.velocity.0 * nx + rb.velocity.1 * ny;
+                if along * push.signum() < 0.0 {
+                    rb.velocity.0 -= nx * along;
+                    rb.velocity.1 -= ny * along;
+                }
+                world.velocities.insert(entity, rb.velocity);
+            }
+        }
     }
 
     /// Resolve collision between two entities (separate them)
     fn resolve_collision(world: &mut World, e1: Entity, e2: Entity) {
-        // Get collision data
-        let t1 = world.transforms.get(&e1).cloned();
-        let t2 = world.transforms.get(&e2).cloned();
-        let c1 = world.colliders.get(&e1).cloned();
-        let c2 = world.colliders.get(&e2).cloned();
-
-        if let (Some(t1), Some(t2), Some(c1), Some(c2)) = (t1, t2, c1, c2) {
-            // Get world-space collider dimensions
-            let width1 = c1.get_world_width(t1.scale[0]);
-            let height1 = c1.get_world_height(t1.scale[1]);
-            let width2 = c2.get_world_width(t2.scale[0]);
-            let height2 = c2.get_world_height(t2.scale[1]);
-            
-            // Get world-space offsets
-            let offset1 = c1.get_world_offset(t1.scale[0], t1.scale[1]);
-            let offset2 = c2.get_world_offset(t2.scale[0], t2.scale[1]);
-            
-            // Calculate centers (position + offset)
-            let center1_x = t1.position[0] + offset1[0];
-            let center1_y = t1.position[1] + offset1[1];
-            let center2_x = t2.position[0] + offset2[0];
-            let center2_y = t2.position[1] + offset2[1];
+        // Get collision data (unrotated boxes: the offset is already flipped with the sprite)
+        let p1 = narrow_phase::collider_placement(world, e1);
+        let p2 = narrow_phase::collider_placement(world, e2);
+
+        if let (Some(p1), Some(p2)) = (p1, p2) {
+            let [center1_x, center1_y] = p1.center;
+            let [center2_x, center2_y] = p2.center;
 
             // Calculate overlap on each axis
-            let overlap_x = (width1 / 2.0 + width2 / 2.0) - (center1_x - center2_x).abs();
-            let overlap_y = (height1 / 2.0 + height2 / 2.0) - (center1_y - center2_y).abs();
+            let overlap_x = (p1.half_extents[0] + p2.half_extents[0]) - (center1_x - center2_x).abs();
+            let overlap_y = (p1.half_extents[1] + p2.half_extents[1]) - (center1_y - center2_y).abs();
 
             // Determine which axis has less overlap (separate on that axis)
             let has_rigidbody1 = world.rigidbodies.contains_key(&e1);
@@ -384,29 +525,26 @@ impl PhysicsWorld {
         }
     }
 
-    /// Check collision between two entities using AABB
+    /// Check collision between two entities using AABB (SAT when either has a polygon
+    /// collider or a rotated box)
     pub fn check_collision(world: &World, e1: Entity, e2: Entity) -> bool {
-        let t1 = world.transforms.get(&e1);
-        let t2 = world.transforms.get(&e2);
-        let c1 = world.colliders.get(&e1);
-        let c2 = world.colliders.get(&e2);
-
-        if let (Some(t1), Some(t2), Some(c1), Some(c2)) = (t1, t2, c1, c2) {
-            // Get world-space collider dimensions (size * transform.scale)
-            let width1 = c1.get_world_width(t1.scale[0]);
-            let height1 = c1.get_world_height(t1.scale[1]);
-            let width2 = c2.get_world_width(t2.scale[0]);
-            let height2 = c2.get_world_height(t2.scale[1]);
-            
-            // Get world-space offsets
-            let offset1 = c1.get_world_offset(t1.scale[0], t1.scale[1]);
-            let offset2 = c2.get_world_offset(t2.scale[0], t2.scale[1]);
-            
-            // Calculate AABB bounds (with offset)
-            let x1 = t1.position[0] + offset1[0] - width1 / 2.0;
-            let y1 = t1.position[1] + offset1[1] - height1 / 2.0;
-            let x2 = t2.position[0] + offset2[0] - width2 / 2.0;
-            let y2 = t2.position[1] + offset2[1] - height2 / 2.0;
+        if Self::needs_narrow_phase(world, e1, e2) {
+            return narrow_phase::entity_contact(world, e1, e2).is_some();
+        }
+
+        let p1 = narrow_phase::collider_placement(world, e1);
+        let p2 = narrow_phase::collider_placement(world, e2);
+
+        if let (Some(p1), Some(p2)) = (p1, p2) {
+            // World-space collider dimensions (size * transform.scale)
+            let [width1, height1] = [p1.half_extents[0] * 2.0, p1.half_extents[1] * 2.0];
+            let [width2, height2] = [p2.half_extents[0] * 2.0, p2.half_extents[1] * 2.0];
+
+            // Calculate AABB bounds (with the flipped offset)
+            let x1 = p1.center[0] - width1 / 2.0;
+            let y1 = p1.center[1] - height1 / 2.0;
+            let x2 = p2.center[0] - width2 / 2.0;
+            let y2 = p2.center[1] - height2 / 2.0;
 
             // AABB collision test
             let collision = x1 < x2 + width2 &&
@@ -423,25 +561,64 @@ impl PhysicsWorld {
     pub fn get_collisions(world: &World, entity: Entity) -> Vec<Entity> {
         let mut collisions = Vec::new();
 
-        if !world.colliders.contains_key(&entity) {
+        if !world.colliders.contains_key(&entity) && !Self::has_polygon(world, entity) {
             return collisions;
         }
 
-        for other_entity in world.colliders.keys() {
-            if *other_entity != entity
-                && Self::check_collision(world, entity, *other_entity) {
-                    collisions.push(*other_entity);
+        for other_entity in Self::collider_entities(world) {
+            if other_entity != entity
+                && Self::check_collision(world, entity, other_entity) {
+                    collisions.push(other_entity);
                 }
         }
 
         collisions
     }
 
+    /// Active entities whose colliders overlap the rectangle from `min` to `max`
+    /// (shapes that only touch don't count), filtered to layers set in `layer_mask`
+    pub fn overlap_aabb(world: &World, min: [f32; 2], max: [f32; 2], layer_mask: u32) -> Vec<Entity> {
+        let query = narrow_phase::Shape::Polygon(vec![[min[0], min[1]], [max[0], min[1]], [max[0], max[1]], [min[0], max[1]]]);
+        Self::overlap_shape(world, &query, layer_mask)
+    }
+
+    /// Active entities whose colliders overlap the circle, filtered to layers set in `layer_mask`
+    pub fn overlap_circle(world: &World, center: [f32; 2], radius: f32, layer_mask: u32) -> Vec<Entity> {
+        let query = narrow_phase::Shape::Circle { center, radius };
+        Self::overlap_shape(world, &query, layer_mask)
+    }
+
+    fn overlap_shape(world: &World, query: &narrow_phase::Shape, layer_mask: u32) -> Vec<Entity> {
+        Self::collider_entities(world)
+            .into_iter()
+            .filter(|&entity| world.is_active_in_hierarchy(entity) && in_layer_mask(world, entity, layer_mask))
+            .filter(|&entity| {
+                narrow_phase::entity_shapes(world, entity)
+                    .iter()
+                    .any(|shape| narrow_phase::shape_contact(shape, query).is_some())
+            })
+            .collect()
+    }
+
+    /// Closest point on an entity's collider to `point` (the point itself when inside),
+    /// e.g. to push a hit target away from where it was struck
+    pub fn closest_point_on_collider(world: &World, entity: Entity, point: [f32; 2]) -> Option<[f32; 2]> {
+        narrow_phase::entity_shapes(world, entity)
+            .iter()
+            .map(|shape| narrow_phase::closest_point(shape, point))
+            .min_by(|a, b| distance_sq(*a, point).total_cmp(&distance_sq(*b, point)))
+    }
+
     /// Set gravity
     pub fn set_gravity(&mut self, gravity: f32) {
         self.gravity = gravity;
     }
 
+    /// Set world bounds as (min, max), or None to let bodies move freely
+    pub fn set_world_bounds(&mut self, bounds: Option<([f32; 2], [f32; 2])>) {
+        self.world_bounds = bounds;
+    }
+
     /// Enable/disable physics
     pub fn set_enabled(&mut self, enabled: bool) {
         self.enabled = enabled;
@@ -453,6 +630,22 @@ impl PhysicsWorld {
     }
 }
 
+/// Whether an entity's collider only reports overlaps
+pub fn is_trigger(world: &World, entity: Entity) -> bool {
+    world.colliders.get(&entity).map(|c| c.is_trigger).unwrap_or(false)
+        || world.polygon_colliders.get(&entity).map(|c| c.is_trigger).unwrap_or(false)
+}
+
+/// True if the entity's layer (`World::layers`, default 0) is set in `layer_mask`
+pub fn in_layer_mask(world: &World, entity: Entity, layer_mask: u32) -> bool {
+    let layer = world.layers.get(&entity).copied().unwrap_or(0);
+    layer >= 32 || layer_mask & (1 << layer) != 0
+}
+
+fn distance_sq(a: [f32; 2], b: [f32; 2]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)
+}
+
 /// Physics helper functions
 pub mod helpers {
     use ecs::{World, Entity};
@@ -634,6 +827,92 @@ mod tests {
         assert!(!PhysicsWorld::check_collision(&world, e1, e2));
     }
 
+    #[test]
+    fn test_polygon_collider_rests_on_box() {
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+        physics.gravity = 0.0;
+
+        let ground = world.spawn();
+        world.add_component(ground, ComponentType::Transform).unwrap();
+        world.add_component(ground, ComponentType::BoxCollider).unwrap();
+        world.transforms.get_mut(&ground).unwrap().scale = [10.0, 1.0, 1.0];
+
+        // Triangle whose base sinks 0.25 into the top of the ground
+        let body = world.spawn();
+        world.add_component(body, ComponentType::Transform).unwrap();
+        world.add_component(body, ComponentType::Rigidbody).unwrap();
+        world.transforms.get_mut(&body).unwrap().position = [0.0, 0.25, 0.0];
+        world.polygon_colliders.insert(body, ecs::PolygonCollider::new(vec![[-0.5, 0.0], [0.5, 0.0], [0.0, 1.0]]));
+
+        assert!(PhysicsWorld::check_collision(&world, body, ground));
+        assert_eq!(PhysicsWorld::get_collisions(&world, ground), vec![body]);
+
+        physics.step(0.0, &mut world);
+        let position = world.transforms.get(&body).unwrap().position;
+        assert!((position[1] - 0.5).abs() < 1e-4, "pushed out to {:?}", position);
+        assert!(world.transforms.get(&ground).unwrap().position[1].abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_box_rests_on_rotated_platform() {
+        let mut world = World::new();
+        let mut physics = PhysicsWorld::new();
+        physics.set_gravity(10.0);
+
+        // 10 x 1 platform turned 45°: its top face is the line y = x + √2/2
+        let platform = world.spawn();
+        world.transforms.insert(platform, ecs::Transform { rotation: [0.0, 0.0, 45.0], ..Default::default() });
+        world.colliders.insert(platform, ecs::Collider::new(10.0, 1.0));
+
+        // Inside the platform's unrotated AABB but clear of the slope: no phantom contact
+        let crate_box = world.spawn();
+        world.transforms.insert(crate_box, ecs::Transform::with_position(4.0, 0.0, 0.0));
+        world.colliders.insert(crate_box, ecs::Collider::new(1.0, 1.0));
+        assert!(!PhysicsWorld::check_collision(&world, crate_box, platform));
+
+        world.transforms.get_mut(&crate_box).unwrap().position = [2.0, 5.0, 0.0];
+        world.rigidbodies.insert(crate_box, ecs::Rigidbody2D::default());
+        for _ in 0..40 {
+            physics.step(1.0 / 60.0, &mut world);
+        }
+        // Landed with its bottom-right corner on the slope (the AABB would have stopped it at y = 1)
+        let [x, y, _] = world.transforms[&crate_box].position;
+        let surface = (x + 0.5) + std::f32::consts::FRAC_1_SQRT_2;
+        assert!(y > 1.0 && (y - 0.5 - surface).abs() < 0.01, "box at ({}, {}), slope under its corner at {}", x, y, surface);
+    }
+
+    #[test]
+    fn test_overlap_queries() {
+        let mut world = World::new();
+        let spawn = |world: &mut World, position: [f32; 2], collider: ecs::Collider| {
+            let entity = world.spawn();
+            world.add_component(entity, ComponentType::Transform).unwrap();
+            world.transforms.get_mut(&entity).unwrap().position = [position[0], position[1], 0.0];
+            world.colliders.insert(entity, collider);
+            entity
+        };
+        let wall = spawn(&mut world, [0.0, 0.0], ecs::Collider::new(2.0, 2.0));
+        let coin = spawn(&mut world, [5.0, 0.0], ecs::Collider { shape: ecs::ColliderShape::Circle, ..ecs::Collider::new(1.0, 1.0) });
+
+        // A box touching the wall's right edge doesn't overlap it; one reaching past it does
+        assert!(PhysicsWorld::overlap_aabb(&world, [1.0, -1.0], [3.0, 1.0], u32::MAX).is_empty());
+        assert_eq!(PhysicsWorld::overlap_aabb(&world, [0.9, -1.0], [3.0, 1.0], u32::MAX), vec![wall]);
+
+        // Circles sharing a center overlap, whatever their radii
+        assert_eq!(PhysicsWorld::overlap_circle(&world, [5.0, 0.0], 0.1, u32::MAX), vec![coin]);
+        assert_eq!(PhysicsWorld::overlap_circle(&world, [5.0, 0.0], 10.0, u32::MAX), vec![wall, coin]);
+
+        // Layers missing from the mask and inactive entities are skipped
+        world.layers.insert(coin, 3);
+        assert_eq!(PhysicsWorld::overlap_circle(&world, [5.0, 0.0], 10.0, !(1 << 3)), vec![wall]);
+        world.set_active(wall, false);
+        assert_eq!(PhysicsWorld::overlap_circle(&world, [5.0, 0.0], 10.0, u32::MAX), vec![coin]);
+
+        assert_eq!(PhysicsWorld::closest_point_on_collider(&world, wall, [4.0, 0.5]), Some([1.0, 0.5]));
+        assert_eq!(PhysicsWorld::closest_point_on_collider(&world, coin, [5.0, 3.0]), Some([5.0, 0.5]));
+    }
+
     #[test]
     fn test_physics_helpers() {
         let mut world = World::new();
@@ -657,4 +936,124 @@ mod tests {
         let vel = helpers::get_velocity(&world, entity).unwrap();
         assert_eq!(vel, (0.0, 0.0));
     }
+
+    #[test]
+    fn test_step_is_independent_of_insertion_order() {
+        // Ground and a stack of overlapping boxes, with components added in different orders
+        let run = |order: &[usize]| {
+            let mut world = World::new();
+            let entities: Vec<Entity> = (0..6).map(|_| world.spawn()).collect();
+            for &index in order {
+                let entity = entities[index];
+                let position = if index == 0 { [0.0, 0.0, 0.0] } else { [index as f32 * 0.3, index as f32 * 0.8, 0.0] };
+                world.transforms.insert(entity, ecs::Transform { position, ..Default::default() });
+                let width = if index == 0 { 20.0 } else { 1.0 };
+                world.colliders.insert(entity, ecs::Collider::new(width, 1.0));
+                if index > 0 {
+                    world.rigidbodies.insert(entity, ecs::Rigidbody2D::default());
+                }
+            }
+            let mut physics = PhysicsWorld::new();
+            physics.set_gravity(20.0);
+            let mut events = Vec::new();
+            for _ in 0..90 {
+                physics.step(1.0 / 60.0, &mut world);
+                events.extend(physics.take_collision_events().into_iter().map(|event| (event.entity, event.other, event.phase)));
+            }
+            let positions: Vec<[f32; 3]> = entities.iter().map(|entity| world.transforms[entity].position).collect();
+            (positions, events)
+        };
+
+        let forward = run(&[0, 1, 2, 3, 4, 5]);
+        assert_eq!(forward, run(&[5, 3, 1, 0, 4, 2]));
+        assert_eq!(forward, run(&[4, 0, 2, 5, 1, 3]));
+    }
+
+    /// Ground and a wall (static colliders), a falling box and a kinematic character
+    fn parity_scene() -> (World, [Entity; 4]) {
+        let mut world = World::new();
+        let mut spawn = |position: [f32; 2], size: [f32; 2], rigidbody: Option<ecs::Rigidbody2D>| {
+            let entity = world.spawn();
+            world.add_component(entity, ComponentType::Transform).unwrap();
+            world.transforms.get_mut(&entity).unwrap().position = [position[0], position[1], 0.0];
+            world.colliders.insert(entity, ecs::Collider::new(size[0], size[1]));
+            if let Some(rigidbody) = rigidbody {
+                world.rigidbodies.insert(entity, rigidbody);
+            }
+            entity
+        };
+        let ground = spawn([0.0, 0.0], [20.0, 1.0], None);
+        let wall = spawn([6.0, 2.0], [1.0, 4.0], None);
+        let crate_box = spawn([-3.0, 3.0], [1.0, 1.0], Some(ecs::Rigidbody2D::default()));
+        let character = spawn([3.0, 1.0], [1.0, 1.0], Some(ecs::Rigidbody2D { is_kinematic: true, ..Default::default() }));
+        (world, [ground, wall, crate_box, character])
+    }
+
+    /// What the test scene reports: box height, collision events between box and ground,
+    /// character x and the flags of its last move
+    fn run_parity_scene(
+        world: &mut World,
+        [ground, _, crate_box, character]: [Entity; 4],
+        mut step: impl FnMut(&mut World) -> Vec<CollisionEvent>,
+        mut move_and_slide: impl FnMut(&mut World, Entity, [f32; 2]) -> CharacterMove,
+    ) -> (f32, Vec<CollisionPhase>, f32, CharacterMove) {
+        let mut phases = Vec::new();
+        let mut last_move = CharacterMove::default();
+        for _ in 0..120 {
+            last_move = move_and_slide(world, character, [0.2, -0.1]);
+            for event in step(world) {
+                if (event.entity, event.other) == (ground, crate_box) && !phases.contains(&event.phase) {
+                    phases.push(event.phase);
+                }
+            }
+        }
+        (world.transforms[&crate_box].position[1], phases, world.transforms[&character].position[0], last_move)
+    }
+
+    #[test]
+    fn test_collision_events_and_character_movement() {
+        let (mut world, entities) = parity_scene();
+        let mut physics = PhysicsWorld::new();
+        physics.set_gravity(20.0);
+        let (box_y, phases, character_x, last_move) = run_parity_scene(
+            &mut world,
+            entities,
+            |world| {
+                physics.step(1.0 / 60.0, world);
+                physics.take_collision_events()
+            },
+            PhysicsWorld::move_and_slide,
+        );
+        assert!((box_y - 1.0).abs() < 0.05, "box should rest on the ground, y = {}", box_y);
+        assert_eq!(phases, vec![CollisionPhase::Enter, CollisionPhase::Stay]);
+        // Stopped by the wall (its left side is at 5.5), standing on the ground
+        assert!((character_x - 5.0).abs() < 0.05, "character x = {}", character_x);
+        assert!(last_move.on_floor && last_move.on_wall && !last_move.on_ceiling, "{:?}", last_move);
+
+        #[cfg(feature = "rapier")]
+        {
+            // Same scene through Rapier: same results within tolerance
+            let (mut world, entities) = parity_scene();
+            let physics = std::cell::RefCell::new(RapierPhysicsWorld::new());
+            physics.borrow_mut().set_gravity(20.0);
+            physics.borrow_mut().step(1.0 / 60.0, &mut world);
+            let (rapier_box_y, rapier_phases, rapier_character_x, rapier_move) = run_parity_scene(
+                &mut world,
+                entities,
+                |world| {
+                    let mut physics = physics.borrow_mut();
+                    physics.step(1.0 / 60.0, world);
+                    physics.take_collision_events()
+                },
+                |world, entity, delta| physics.borrow_mut().move_and_slide(world, entity, delta),
+            );
+            assert!((rapier_box_y - box_y).abs() < 0.05, "rapier box y = {}", rapier_box_y);
+            assert_eq!(rapier_phases, phases);
+            assert!((rapier_character_x - character_x).abs() < 0.05, "rapier character x = {}", rapier_character_x);
+            assert_eq!(
+                (rapier_move.on_floor, rapier_move.on_wall, rapier_move.on_ceiling),
+                (last_move.on_floor, last_move.on_wall, last_move.on_ceiling)
+            );
+        }
+    }
 }
@@ -0,0 +1,367 @@
+//! Narrow Phase
+//!
+//! Separating axis tests used for polygon colliders and rotated box colliders. A polygon
+//! collider is split into convex parts; box and capsule colliders become their (possibly
+//! rotated) box, circle colliders circles. Unrotated boxes take the simple backend's
+//! AABB test instead.
+
+use ecs::{ColliderShape, Entity, World};
+
+/// Convex shape in world space
+#[derive(Debug, Clone, PartialEq)]
+pub enum Shape {
+    /// Convex polygon (either winding)
+    Polygon(Vec<[f32; 2]>),
+    Circle { center: [f32; 2], radius: f32 },
+}
+
+/// Overlap between two shapes
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contact {
+    /// Unit direction to move the first shape to separate it from the second
+    pub normal: [f32; 2],
+    /// Penetration depth along `normal`
+    pub depth: f32,
+}
+
+/// Where an entity's box/circle/capsule collider sits in world space: the offset scaled,
+/// mirrored with the sprite's flips (`flip_with_sprite`) and rotated with the entity
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColliderPlacement {
+    pub center: [f32; 2],
+    /// Half width and height before rotation (the radius on both axes for circles)
+    pub half_extents: [f32; 2],
+    /// Z rotation in radians
+    pub rotation: f32,
+}
+
+impl ColliderPlacement {
+    /// Whether the box is turned off the axes (any rotation but whole turns)
+    pub fn is_rotated(&self) -> bool {
+        let turns = self.rotation / std::f32::consts::TAU;
+        (turns - turns.round()).abs() > 1e-5
+    }
+
+    /// Box corners, counter-clockwise from the bottom left
+    pub fn corners(&self) -> Vec<[f32; 2]> {
+        let [hw, hh] = self.half_extents;
+        [[-hw, -hh], [hw, -hh], [hw, hh], [-hw, hh]]
+            .iter()
+            .map(|&corner| add(self.center, rotate(corner, self.rotation)))
+            .collect()
+    }
+}
+
+pub fn collider_placement(world: &World, entity: Entity) -> Option<ColliderPlacement> {
+    let collider = world.colliders.get(&entity)?;
+    let transform = world.world_transform(entity)?;
+    let rotation = transform.rotation[2].to_radians();
+    let offset = collider.get_flipped_offset(transform.scale[0], transform.scale[1], world.sprite_flip(entity));
+    let half_extents = match collider.shape {
+        ColliderShape::Circle => [collider.get_world_radius(transform.scale[0]); 2],
+        ColliderShape::Box | ColliderShape::Capsule => [
+            collider.get_world_width(transform.scale[0]).abs() / 2.0,
+            collider.get_world_height(transform.scale[1]).abs() / 2.0,
+        ],
+    };
+    Some(ColliderPlacement {
+        center: add([transform.position[0], transform.position[1]], rotate(offset, rotation)),
+        half_extents,
+        rotation,
+    })
+}
+
+/// Whether an entity's box collider is rotated (and so needs the SAT test rather than AABBs)
+pub fn is_rotated(world: &World, entity: Entity) -> bool {
+    collider_placement(world, entity).is_some_and(|placement| placement.is_rotated())
+}
+
+/// World-space shapes of an entity's 2D collider; a polygon collider takes precedence over a box collider
+pub fn entity_shapes(world: &World, entity: Entity) -> Vec<Shape> {
+    let Some(transform) = world.world_transform(entity) else {
+        return Vec::new();
+    };
+    let position = [transform.position[0], transform.position[1]];
+    let scale = [transform.scale[0], transform.scale[1]];
+    let rotation = transform.rotation[2].to_radians();
+
+    if let Some(polygon) = world.polygon_colliders.get(&entity) {
+        return polygon
+            .convex_parts()
+            .into_iter()
+            .map(|part| {
+                Shape::Polygon(part.iter().map(|p| add(position, rotate([p[0] * scale[0], p[1] * scale[1]], rotation))).collect())
+            })
+            .collect();
+    }
+
+    let (Some(collider), Some(placement)) = (world.colliders.get(&entity), collider_placement(world, entity)) else {
+        return Vec::new();
+    };
+    match collider.shape {
+        ColliderShape::Circle => vec![Shape::Circle { center: placement.center, radius: placement.half_extents[0] }],
+        ColliderShape::Box | ColliderShape::Capsule => vec![Shape::Polygon(placement.corners())],
+    }
+}
+
+/// Conservative world-space bounds of all of an entity's collider shapes (rotated boxes
+/// included), for the broad phase
+pub fn entity_bounds(world: &World, entity: Entity) -> Option<([f32; 2], [f32; 2])> {
+    entity_shapes(world, entity).iter().map(bounds).reduce(|(min1, max1), (min2, max2)| {
+        ([min1[0].min(min2[0]), min1[1].min(min2[1])], [max1[0].max(max2[0]), max1[1].max(max2[1])])
+    })
+}
+
+/// Deepest contact between any parts of two entities' colliders
+pub fn entity_contact(world: &World, e1: Entity, e2: Entity) -> Option<Contact> {
+    let shapes1 = entity_shapes(world, e1);
+    let shapes2 = entity_shapes(world, e2);
+    shapes1
+        .iter()
+        .flat_map(|a| shapes2.iter().filter_map(move |b| shape_contact(a, b)))
+        .max_by(|a, b| a.depth.total_cmp(&b.depth))
+}
+
+/// Contact between two convex shapes, None if they don't overlap
+pub fn shape_contact(a: &Shape, b: &Shape) -> Option<Contact> {
+    match (a, b) {
+        (Shape::Circle { center: c1, radius: r1 }, Shape::Circle { center: c2, radius: r2 }) => {
+            let delta = sub(*c1, *c2);
+            let distance = length(delta);
+            let depth = r1 + r2 - distance;
+            if depth <= 0.0 {
+                return None;
+            }
+            let normal = if distance > f32::EPSILON { scale(delta, 1.0 / distance) } else { [0.0, 1.0] };
+            Some(Contact { normal, depth })
+        }
+        (Shape::Polygon(points), Shape::Circle { center, radius }) => {
+            let closest = points.iter().copied().min_by(|p, q| length(sub(*p, *center)).total_cmp(&length(sub(*q, *center))))?;
+            let axes = edge_normals(points).chain(normalize(sub(*center, closest)));
+            sat(axes, centroid(points), *center, |axis| project(points, axis), |axis| {
+                let c = dot(*center, axis);
+                (c - radius, c + radius)
+            })
+        }
+        (Shape::Circle { .. }, Shape::Polygon(_)) => shape_contact(b, a).map(flip),
+        (Shape::Polygon(p1), Shape::Polygon(p2)) => {
+            let axes = edge_normals(p1).chain(edge_normals(p2));
+            sat(axes, centroid(p1), centroid(p2), |axis| project(p1, axis), |axis| project(p2, axis))
+        }
+    }
+}
+
+/// Closest point of a convex shape to `point`; the point itself when it lies inside
+pub fn closest_point(shape: &Shape, point: [f32; 2]) -> [f32; 2] {
+    match shape {
+        Shape::Circle { center, radius } => {
+            let delta = sub(point, *center);
+            let distance = length(delta);
+            if distance <= *radius {
+                return point;
+            }
+            let offset = scale(delta, radius / distance);
+            [center[0] + offset[0], center[1] + offset[1]]
+        }
+        Shape::Polygon(points) => {
+            if contains(points, point) {
+                return point;
+            }
+            let n = points.len();
+            (0..n)
+                .map(|i| closest_on_segment(points[i], points[(i + 1) % n], point))
+                .min_by(|p, q| length(sub(*p, point)).total_cmp(&length(sub(*q, point))))
+                .unwrap_or(point)
+        }
+    }
+}
+
+/// Axis-aligned bounds of a shape as (min, max)
+pub fn bounds(shape: &Shape) -> ([f32; 2], [f32; 2]) {
+    match shape {
+        Shape::Circle { center, radius } => ([center[0] - radius, center[1] - radius], [center[0] + radius, center[1] + radius]),
+        Shape::Polygon(points) => points.iter().fold(([f32::MAX, f32::MAX], [f32::MIN, f32::MIN]), |(min, max), p| {
+            ([min[0].min(p[0]), min[1].min(p[1])], [max[0].max(p[0]), max[1].max(p[1])])
+        }),
+    }
+}
+
+/// Point inside (or on the edge of) a convex polygon of either winding
+fn contains(points: &[[f32; 2]], point: [f32; 2]) -> bool {
+    let n = points.len();
+    let sides = (0..n).map(|i| {
+        let edge = sub(points[(i + 1) % n], points[i]);
+        let to_point = sub(point, points[i]);
+        edge[0] * to_point[1] - edge[1] * to_point[0]
+    });
+    let (mut left, mut right) = (false, false);
+    for side in sides {
+        left |= side > 0.0;
+        right |= side < 0.0;
+    }
+    n >= 3 && !(left && right)
+}
+
+fn closest_on_segment(a: [f32; 2], b: [f32; 2], point: [f32; 2]) -> [f32; 2] {
+    let ab = sub(b, a);
+    let len_sq = dot(ab, ab);
+    let t = if len_sq > f32::EPSILON { (dot(sub(point, a), ab) / len_sq).clamp(0.0, 1.0) } else { 0.0 };
+    [a[0] + ab[0] * t, a[1] + ab[1] * t]
+}
+
+/// Minimum overlap over all candidate axes; the normal is oriented from `center2` towards `center1`
+fn sat(
+    axes: impl Iterator<Item = [f32; 2]>,
+    center1: [f32; 2],
+    center2: [f32; 2],
+    project1: impl Fn([f32; 2]) -> (f32, f32),
+    project2: impl Fn([f32; 2]) -> (f32, f32),
+) -> Option<Contact> {
+    let mut best: Option<Contact> = None;
+    for axis in axes {
+        let (min1, max1) = project1(axis);
+        let (min2, max2) = project2(axis);
+        let depth = max1.min(max2) - min1.max(min2);
+        if depth <= 0.0 {
+            return None;
+        }
+        if best.is_none_or(|contact| depth < contact.depth) {
+            let normal = if dot(sub(center1, center2), axis) < 0.0 { scale(axis, -1.0) } else { axis };
+            best = Some(Contact { normal, depth });
+        }
+    }
+    best
+}
+
+fn edge_normals(points: &[[f32; 2]]) -> impl Iterator<Item = [f32; 2]> + '_ {
+    let n = points.len();
+    (0..n).filter_map(move |i| {
+        let edge = sub(points[(i + 1) % n], points[i]);
+        normalize([-edge[1], edge[0]])
+    })
+}
+
+fn project(points: &[[f32; 2]], axis: [f32; 2]) -> (f32, f32) {
+    points.iter().fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), p| {
+        let d = dot(*p, axis);
+        (min.min(d), max.max(d))
+    })
+}
+
+fn centroid(points: &[[f32; 2]]) -> [f32; 2] {
+    let sum = points.iter().fold([0.0, 0.0], |acc, p| [acc[0] + p[0], acc[1] + p[1]]);
+    scale(sum, 1.0 / points.len().max(1) as f32)
+}
+
+fn flip(contact: Contact) -> Contact {
+    Contact { normal: scale(contact.normal, -1.0), depth: contact.depth }
+}
+
+fn add(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] + b[0], a[1] + b[1]]
+}
+
+/// Counter-clockwise by `angle` radians (Y up)
+fn rotate(v: [f32; 2], angle: f32) -> [f32; 2] {
+    if angle == 0.0 {
+        return v;
+    }
+    let (sin, cos) = angle.sin_cos();
+    [v[0] * cos - v[1] * sin, v[0] * sin + v[1] * cos]
+}
+
+fn sub(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+fn scale(v: [f32; 2], s: f32) -> [f32; 2] {
+    [v[0] * s, v[1] * s]
+}
+
+fn dot(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+fn length(v: [f32; 2]) -> f32 {
+    dot(v, v).sqrt()
+}
+
+fn normalize(v: [f32; 2]) -> Option<[f32; 2]> {
+    let len = length(v);
+    (len > f32::EPSILON).then(|| scale(v, 1.0 / len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(x: f32, y: f32, half: f32) -> Shape {
+        Shape::Polygon(vec![[x - half, y - half], [x + half, y - half], [x + half, y + half], [x - half, y + half]])
+    }
+
+    #[test]
+    fn test_polygon_contacts() {
+        // Triangle resting 0.25 into a box below it
+        let triangle = Shape::Polygon(vec![[-1.0, 0.75], [1.0, 0.75], [0.0, 2.0]]);
+        let contact = shape_contact(&triangle, &square(0.0, 0.0, 1.0)).unwrap();
+        assert!((contact.depth - 0.25).abs() < 1e-5);
+        assert!((contact.normal[1] - 1.0).abs() < 1e-5);
+
+        assert!(shape_contact(&triangle, &square(0.0, -2.0, 1.0)).is_none());
+
+        // Circle touching the box's right side, pushed right
+        let circle = Shape::Circle { center: [1.5, 0.0], radius: 1.0 };
+        let contact = shape_contact(&circle, &square(0.0, 0.0, 1.0)).unwrap();
+        assert!((contact.depth - 0.5).abs() < 1e-5);
+        assert!((contact.normal[0] - 1.0).abs() < 1e-5);
+
+        // A circle near the box's corner but outside it
+        let circle = Shape::Circle { center: [1.8, 1.8], radius: 1.0 };
+        assert!(shape_contact(&square(0.0, 0.0, 1.0), &circle).is_none());
+    }
+
+    #[test]
+    fn test_closest_point() {
+        let square = square(0.0, 0.0, 1.0);
+        assert_eq!(closest_point(&square, [0.5, 0.25]), [0.5, 0.25]);
+        assert_eq!(closest_point(&square, [3.0, 0.5]), [1.0, 0.5]);
+        assert_eq!(closest_point(&square, [3.0, -4.0]), [1.0, -1.0]);
+
+        let circle = Shape::Circle { center: [1.0, 1.0], radius: 2.0 };
+        assert_eq!(closest_point(&circle, [1.0, 6.0]), [1.0, 3.0]);
+        assert_eq!(closest_point(&circle, [2.0, 1.0]), [2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_collider_placement_flips_and_rotates() {
+        let mut world = World::new();
+        let entity = world.spawn();
+        world.transforms.insert(entity, ecs::Transform::with_position(10.0, 0.0, 0.0));
+        world.colliders.insert(entity, ecs::Collider::with_offset(1.0, 0.5, 2.0, 1.0));
+        assert_eq!(collider_placement(&world, entity).unwrap().center, [11.0, 0.5]);
+
+        // Facing left: the offset moves to the other side with the sprite
+        world.sprites.insert(entity, ecs::Sprite { flip_x: true, ..Default::default() });
+        assert_eq!(collider_placement(&world, entity).unwrap().center, [9.0, 0.5]);
+        world.colliders.get_mut(&entity).unwrap().flip_with_sprite = false;
+        assert_eq!(collider_placement(&world, entity).unwrap().center, [11.0, 0.5]);
+
+        // A quarter turn swings the offset round and stands the box up
+        world.transforms.get_mut(&entity).unwrap().rotation[2] = 90.0;
+        let placement = collider_placement(&world, entity).unwrap();
+        assert!(placement.is_rotated());
+        assert!((placement.center[0] - 9.5).abs() < 1e-5 && (placement.center[1] - 1.0).abs() < 1e-5, "{:?}", placement.center);
+        let (min, max) = bounds(&entity_shapes(&world, entity)[0]);
+        assert!((max[0] - min[0] - 1.0).abs() < 1e-5 && (max[1] - min[1] - 2.0).abs() < 1e-5);
+
+        world.transforms.get_mut(&entity).unwrap().rotation[2] = 360.0;
+        assert!(!collider_placement(&world, entity).unwrap().is_rotated());
+    }
+
+    #[test]
+    fn test_bounds() {
+        let triangle = Shape::Polygon(vec![[-1.0, 0.75], [1.0, 0.75], [0.0, 2.0]]);
+        assert_eq!(bounds(&triangle), ([-1.0, 0.75], [1.0, 2.0]));
+        assert_eq!(bounds(&Shape::Circle { center: [1.0, 1.0], radius: 2.0 }), ([-1.0, -1.0], [3.0, 3.0]));
+    }
+}
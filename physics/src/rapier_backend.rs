@@ -17,7 +17,8 @@
 //! - Engine Y → Rapier Y (vertical)
 //! - Engine Z → ignored (depth is not simulated)
 
-use ecs::{World, Entity};
+use ecs::{World, Entity, ColliderShape};
+use rapier2d::control::KinematicCharacterController;
 use rapier2d::prelude::*;
 use std::collections::HashMap;
 
@@ -43,6 +44,16 @@ pub struct RapierPhysicsWorld {
     // Mapping between ECS entities and Rapier handles
     entity_to_body: HashMap<Entity, RigidBodyHandle>,
     body_to_entity: HashMap<RigidBodyHandle, Entity>,
+
+    /// Contact points / broad-phase bounds / collider outlines / joints (off by default)
+    pub debug_draw: crate::PhysicsDebugDraw,
+    /// Rapier's debug renderer, its mode set from the debug_draw toggles every step
+    debug_pipeline: DebugRenderPipeline,
+
+    /// Touching entity pairs (count of touching collider pairs) from Rapier's collision events
+    touching: HashMap<(Entity, Entity), usize>,
+    collisions: crate::CollisionTracker,
+    trigger_zones: crate::TriggerZoneTracker,
 }
 
 impl Default for RapierPhysicsWorld {
@@ -72,33 +83,54 @@ impl RapierPhysicsWorld {
             
             entity_to_body: HashMap::new(),
             body_to_entity: HashMap::new(),
+            debug_draw: crate::PhysicsDebugDraw::default(),
+            debug_pipeline: DebugRenderPipeline::new(DebugRenderStyle::default(), DebugRenderMode::empty()),
+
+            touching: HashMap::new(),
+            collisions: crate::CollisionTracker::new(),
+            trigger_zones: crate::TriggerZoneTracker::new(),
         }
     }
     
     /// Sync ECS world to Rapier world
     pub fn sync_from_ecs(&mut self, world: &World) {
-        // Add/update rigid bodies from ECS
-        for (entity, rigidbody) in &world.rigidbodies {
+        // Add/update rigid bodies from ECS; colliders without a Rigidbody2D become fixed bodies
+        let mut entities: Vec<Entity> = world.rigidbodies.keys()
+            .chain(world.colliders.keys())
+            .chain(world.polygon_colliders.keys())
+            .copied()
+            .collect();
+        entities.sort();
+        entities.dedup();
+
+        for entity in &entities {
+            let rigidbody = world.rigidbodies.get(entity);
             if !self.entity_to_body.contains_key(entity) {
                 // Create new rigid body
-                let rb_type = if rigidbody.is_kinematic {
-                    RigidBodyType::KinematicPositionBased
-                } else {
-                    RigidBodyType::Dynamic
+                let rb_type = match rigidbody {
+                    Some(rigidbody) if rigidbody.is_kinematic => RigidBodyType::KinematicPositionBased,
+                    Some(_) => RigidBodyType::Dynamic,
+                    None => RigidBodyType::Fixed,
                 };
                 
                 // Map 3D position to 2D: X stays X, Y inverted
                 // Engine: -Y=down, Rapier: +Y=down, so negate Y
-                let position = world.transforms.get(entity)
-                    .map(|t| vector![t.position[0], -t.position[1]])  // Negate Y
+                let position = world.world_position(*entity)
+                    .map(|p| vector![p[0], -p[1]])  // Negate Y
                     .unwrap_or(vector![0.0, 0.0]);
+                let rotation = Self::body_rotation(world, *entity);
                 
-                let rigid_body = RigidBodyBuilder::new(rb_type)
+                let mut builder = RigidBodyBuilder::new(rb_type)
                     .translation(position)
-                    .linvel(vector![rigidbody.velocity.0, -rigidbody.velocity.1])  // Negate Y velocity
-                    .gravity_scale(rigidbody.gravity_scale)
-                    .ccd_enabled(rigidbody.enable_ccd) // Enable CCD if requested (prevents tunneling)
-                    .build();
+                    .rotation(rotation)
+                    .enabled(world.is_active_in_hierarchy(*entity));
+                if let Some(rigidbody) = rigidbody {
+                    builder = builder
+                        .linvel(vector![rigidbody.velocity.0, -rigidbody.velocity.1])  // Negate Y velocity
+                        .gravity_scale(rigidbody.gravity_scale)
+                        .ccd_enabled(rigidbody.enable_ccd); // Enable CCD if requested (prevents tunneling)
+                }
+                let rigid_body = builder.build();
                 
                 let handle = self.rigid_body_set.insert(rigid_body);
                 self.entity_to_body.insert(*entity, handle);
@@ -109,16 +141,26 @@ impl RapierPhysicsWorld {
                 
                 // Add collider if exists
                 if let Some(collider) = world.colliders.get(entity) {
-                    let transform = world.transforms.get(entity).unwrap();
+                    // No Transform to size it from: the body stays, without a shape
+                    let Some(transform) = world.world_transform(*entity) else { continue };
                     // Map 3D to 2D: width uses scale[0] (X), height uses scale[1] (Y)
                     let half_width = collider.get_world_width(transform.scale[0]) / 2.0;
                     let half_height = collider.get_world_height(transform.scale[1]) / 2.0;
-                    let offset = collider.get_world_offset(transform.scale[0], transform.scale[1]);
+                    let offset = collider.get_flipped_offset(transform.scale[0], transform.scale[1], world.sprite_flip(*entity));
                     
                     log::info!("🔧 Rapier: Creating collider for entity {}, half_size=({:.2}, {:.2}), offset=({:.2}, {:.2})", 
                         entity, half_width, half_height, offset[0], offset[1]);
                     
-                    let collider_shape = ColliderBuilder::cuboid(half_width, half_height)
+                    let builder = match collider.shape {
+                        ColliderShape::Box => ColliderBuilder::cuboid(half_width, half_height),
+                        ColliderShape::Circle => ColliderBuilder::ball(collider.get_world_radius(transform.scale[0])),
+                        ColliderShape::Capsule => {
+                            let radius = collider.get_world_radius(transform.scale[0]);
+                            ColliderBuilder::capsule_y((half_height.abs() - radius).max(0.0), radius)
+                        }
+                    };
+                    let collider_shape = Self::with_events(builder, *entity)
+                        .sensor(collider.is_trigger)
                         .translation(vector![offset[0], -offset[1]]) // Negate Y offset for Rapier
                         .friction(0.0) // No friction for platformer
                         .restitution(0.0) // No bounce
@@ -126,6 +168,21 @@ impl RapierPhysicsWorld {
                     
                     self.collider_set.insert_with_parent(collider_shape, handle, &mut self.rigid_body_set);
                     log::info!("✅ Rapier: Collider created for entity {}", entity);
+                } else if let Some(polygon) = world.polygon_colliders.get(entity) {
+                    let scale = world.world_transform(*entity).map(|t| t.scale).unwrap_or([1.0, 1.0, 1.0]);
+                    // One convex collider per part; Y negated for Rapier
+                    for part in polygon.convex_parts() {
+                        let points: Vec<_> = part.iter().map(|p| point![p[0] * scale[0], -p[1] * scale[1]]).collect();
+                        if let Some(builder) = ColliderBuilder::convex_polyline(points) {
+                            let collider_shape = Self::with_events(builder, *entity)
+                                .sensor(polygon.is_trigger)
+                                .friction(0.0)
+                                .restitution(0.0)
+                                .build();
+                            self.collider_set.insert_with_parent(collider_shape, handle, &mut self.rigid_body_set);
+                        }
+                    }
+                    log::info!("✅ Rapier: Polygon collider created for entity {}", entity);
                 } else {
                     log::warn!("⚠️ Rapier: Entity {} has rigidbody but NO collider!", entity);
                 }
@@ -133,6 +190,43 @@ impl RapierPhysicsWorld {
                 // Update existing rigid body velocity only (don't update position - let Rapier handle it)
                 let handle = self.entity_to_body[entity];
                 if let Some(rb) = self.rigid_body_set.get_mut(handle) {
+                    // Inactive entities (or inactive parents) drop out of the simulation
+                    let active = world.is_active_in_hierarchy(*entity);
+                    if rb.is_enabled() != active {
+                        rb.set_enabled(active);
+                        // Moved while it was out (a pooled entity respawning): start from there
+                        if let (true, Some(position)) = (active, world.world_position(*entity)) {
+                            rb.set_translation(vector![position[0], -position[1]], true);
+                            rb.set_rotation(Rotation::new(Self::body_rotation(world, *entity)), true);
+                            rb.set_angvel(0.0, true);
+                        }
+                    }
+
+                    // Static and kinematic bodies follow their entity when a script moves it
+                    let target = world.world_position(*entity).map(|p| vector![p[0], -p[1]]);
+                    let moved = target.filter(|target| (rb.translation() - target).norm() > 1e-4);
+                    let target_rotation = Self::body_rotation(world, *entity);
+                    let turned = (rb.rotation().angle() - target_rotation).abs() > 1e-4;
+                    let Some(rigidbody) = rigidbody else {
+                        if let Some(target) = moved {
+                            rb.set_translation(target, true);
+                        }
+                        if turned {
+                            rb.set_rotation(Rotation::new(target_rotation), true);
+                        }
+                        continue;
+                    };
+                    if let (true, Some(target)) = (rigidbody.is_kinematic, moved) {
+                        rb.set_next_kinematic_translation(target);
+                    }
+                    if rigidbody.is_kinematic && turned {
+                        rb.set_next_kinematic_rotation(Rotation::new(target_rotation));
+                    }
+
+                    if rb.is_ccd_enabled() != rigidbody.enable_ccd {
+                        rb.enable_ccd(rigidbody.enable_ccd);
+                    }
+
                     // Only update velocity if it changed significantly
                     let current_vel = rb.linvel();
                     let new_vel = vector![rigidbody.velocity.0, -rigidbody.velocity.1];
@@ -143,18 +237,33 @@ impl RapierPhysicsWorld {
             }
         }
     }
+
+    /// Body angle in Rapier's frame (Y flipped, so the engine's CCW Z rotation negates)
+    fn body_rotation(world: &World, entity: Entity) -> Real {
+        world.world_transform(entity).map_or(0.0, |transform| -transform.rotation[2].to_radians())
+    }
+
+    /// Colliders report collision events and remember their entity (also for pairs with
+    /// kinematic and fixed bodies, which Rapier skips by default; fixed-fixed pairs stay off)
+    fn with_events(builder: ColliderBuilder, entity: Entity) -> ColliderBuilder {
+        builder
+            .user_data(entity as u128)
+            .active_events(ActiveEvents::COLLISION_EVENTS)
+            .active_collision_types(ActiveCollisionTypes::all() & !ActiveCollisionTypes::FIXED_FIXED)
+    }
     
     /// Sync Rapier world back to ECS
     pub fn sync_to_ecs(&self, world: &mut World) {
         for (handle, entity) in &self.body_to_entity {
-            if let Some(rb) = self.rigid_body_set.get(*handle) {
+            // Static colliders (fixed bodies) never move on their own
+            if let Some(rb) = self.rigid_body_set.get(*handle).filter(|rb| !rb.is_fixed()) {
                 // Update transform: Map 2D back to 3D (X stays X, Y inverted)
-                if let Some(transform) = world.transforms.get_mut(entity) {
+                if let Some(old) = world.world_position(*entity) {
                     let translation = rb.translation();
-                    let old_y = transform.position[1];
-                    transform.position[0] = translation.x;
-                    transform.position[1] = -translation.y; // Negate Y back to engine convention
+                    let old_y = old[1];
+                    // Body positions are world-space; children get them back-solved into local
                     // Keep Z (depth) unchanged
+                    world.set_world_position(*entity, [translation.x, -translation.y, old[2]]); // Negate Y back to engine convention
                     
                     // Debug: log significant Y changes
                     if (old_y - (-translation.y)).abs() > 0.5 {
@@ -173,8 +282,31 @@ impl RapierPhysicsWorld {
         }
     }
     
+    /// Drop the body (and its colliders) of an entity that left the world
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.touching.retain(|&(a, b), _| a != entity && b != entity);
+        self.collisions.remove_entity(entity);
+        if let Some(handle) = self.entity_to_body.remove(&entity) {
+            self.body_to_entity.remove(&handle);
+            self.rigid_body_set.remove(
+                handle,
+                &mut self.island_manager,
+                &mut self.collider_set,
+                &mut self.impulse_joint_set,
+                &mut self.multibody_joint_set,
+                true,
+            );
+        }
+    }
+
+    /// Number of rigid bodies in the simulation
+    pub fn body_count(&self) -> usize {
+        self.rigid_body_set.len()
+    }
+
     /// Physics step
     pub fn step(&mut self, dt: f32, world: &mut World) {
+        profiler::profile_scope!("physics.step");
         if !self.enabled {
             log::warn!("⚠️ Rapier: Physics disabled!");
             return;
@@ -189,6 +321,9 @@ impl RapierPhysicsWorld {
             scaled_dt, self.rigid_body_set.len(), self.collider_set.len());
         
         // Run physics simulation
+        let (collision_send, collision_recv) = rapier2d::crossbeam::channel::unbounded();
+        let (contact_force_send, _contact_force_recv) = rapier2d::crossbeam::channel::unbounded();
+        let event_handler = ChannelEventCollector::new(collision_send, contact_force_send);
         self.integration_parameters.dt = scaled_dt;
         self.physics_pipeline.step(
             &self.gravity,
@@ -203,8 +338,9 @@ impl RapierPhysicsWorld {
             &mut self.ccd_solver,
             None,
             &(),
-            &(),
+            &event_handler,
         );
+        self.forward_collision_events(collision_recv.try_iter());
         
         // Update query pipeline for raycasts (Rapier 0.22 only needs colliders)
         self.query_pipeline.update(&self.collider_set);
@@ -215,8 +351,87 @@ impl RapierPhysicsWorld {
             log::info!("🔧 Rapier: {} contact pairs after physics step", contact_count);
         }
         
+        self.draw_debug();
+
         // Sync back to ECS
         self.sync_to_ecs(world);
+
+        // Trigger zones query the pipeline updated above
+        let mut trigger_zones = std::mem::take(&mut self.trigger_zones);
+        trigger_zones.update(world, |world, area, layer_mask| match area {
+            crate::TriggerZoneArea::Box { min, max } => self.overlap_aabb(world, min, max, layer_mask),
+            crate::TriggerZoneArea::Circle { center, radius } => self.overlap_circle(world, center, radius, layer_mask),
+        });
+        self.trigger_zones = trigger_zones;
+    }
+
+    /// Turn Rapier's started/stopped events into the shared enter/stay/exit events.
+    /// Entity pairs are counted per collider pair (polygon colliders have several parts).
+    fn forward_collision_events(&mut self, events: impl Iterator<Item = rapier2d::geometry::CollisionEvent>) {
+        for event in events {
+            let entity = |handle: ColliderHandle| self.collider_set.get(handle).map(|collider| collider.user_data as Entity);
+            let (Some(e1), Some(e2)) = (entity(event.collider1()), entity(event.collider2())) else {
+                continue; // Removed colliders: remove_entity already ended their pairs
+            };
+            let pair = (e1.min(e2), e1.max(e2));
+            if event.started() {
+                *self.touching.entry(pair).or_insert(0) += 1;
+            } else if let Some(count) = self.touching.get_mut(&pair) {
+                *count -= 1;
+                if *count == 0 {
+                    self.touching.remove(&pair);
+                }
+            }
+        }
+        self.collisions.record_step(self.touching.keys().copied());
+    }
+
+    /// Collision enter/stay/exit events since the last call (one batch per step)
+    pub fn take_collision_events(&mut self) -> Vec<crate::CollisionEvent> {
+        self.collisions.take_events()
+    }
+
+    /// Trigger zone enter/exit events since the last call (one batch per step)
+    pub fn take_trigger_zone_events(&mut self) -> Vec<crate::TriggerZoneEvent> {
+        self.trigger_zones.take_events()
+    }
+
+    /// Queue contact points, and the debug render pipeline's lines, when enabled
+    fn draw_debug(&mut self) {
+        if self.debug_draw.contacts_enabled() {
+            for pair in self.narrow_phase.contact_pairs().filter(|pair| pair.has_any_active_contact) {
+                for contact in pair.manifolds.iter().flat_map(|manifold| &manifold.data.solver_contacts) {
+                    self.debug_draw.contact([contact.point.x, -contact.point.y]);
+                }
+            }
+        }
+        let mut mode = DebugRenderMode::empty();
+        mode.set(DebugRenderMode::COLLIDER_SHAPES, self.debug_draw.colliders_enabled());
+        mode.set(DebugRenderMode::COLLIDER_AABBS, self.debug_draw.broadphase_enabled());
+        mode.set(DebugRenderMode::JOINTS, self.debug_draw.joints_enabled());
+        mode.set(DebugRenderMode::CONTACTS, self.debug_draw.contacts_enabled());
+        if mode.is_empty() {
+            return;
+        }
+        for (layer, a, b) in self.debug_lines(mode) {
+            self.debug_draw.line(layer, a, b);
+        }
+    }
+
+    /// Lines Rapier's debug render pipeline draws in `mode`, by layer, in engine coordinates
+    fn debug_lines(&mut self, mode: DebugRenderMode) -> Vec<(crate::PhysicsDebugLayer, [f32; 2], [f32; 2])> {
+        self.debug_pipeline.mode = mode;
+
+        let mut lines = DebugLines { normal_color: self.debug_pipeline.style.contact_normal_color, lines: Vec::new() };
+        self.debug_pipeline.render(
+            &mut lines,
+            &self.rigid_body_set,
+            &self.collider_set,
+            &self.impulse_joint_set,
+            &self.multibody_joint_set,
+            &self.narrow_phase,
+        );
+        lines.lines
     }
     
     /// Check if entity is grounded (touching ground below)
@@ -307,10 +522,13 @@ impl RapierPhysicsWorld {
     /// Casts ray from bottom of collider downward
     /// Returns (is_grounded, ray_start, ray_end) for debug visualization
     pub fn raycast_ground(&self, entity: Entity, world: &World, distance: f32) -> bool {
-        if let Some(transform) = world.transforms.get(&entity) {
+        if let Some(transform) = world.world_transform(entity) {
             // Get collider height to cast from bottom
             let collider_half_height = if let Some(collider) = world.colliders.get(&entity) {
-                collider.get_world_height(transform.scale[1]) / 2.0
+                match collider.shape {
+                    ColliderShape::Circle => collider.get_world_radius(transform.scale[0]),
+                    _ => collider.get_world_height(transform.scale[1]) / 2.0,
+                }
             } else {
                 0.0
             };
@@ -345,6 +563,104 @@ impl RapierPhysicsWorld {
         false
     }
     
+    /// Active entities whose colliders overlap the rectangle from `min` to `max` (engine coordinates),
+    /// filtered to layers set in `layer_mask`
+    pub fn overlap_aabb(&self, world: &World, min: [f32; 2], max: [f32; 2], layer_mask: u32) -> Vec<Entity> {
+        let half_extents = vector![(max[0] - min[0]).abs() / 2.0, (max[1] - min[1]).abs() / 2.0];
+        let center = Isometry::translation((min[0] + max[0]) / 2.0, -(min[1] + max[1]) / 2.0);
+        self.overlap_shape(world, &center, &Cuboid::new(half_extents), layer_mask)
+    }
+
+    /// Active entities whose colliders overlap the circle (engine coordinates), filtered to layers set in `layer_mask`
+    pub fn overlap_circle(&self, world: &World, center: [f32; 2], radius: f32, layer_mask: u32) -> Vec<Entity> {
+        let center = Isometry::translation(center[0], -center[1]);
+        self.overlap_shape(world, &center, &Ball::new(radius), layer_mask)
+    }
+
+    fn overlap_shape(&self, world: &World, position: &Isometry<Real>, shape: &dyn Shape, layer_mask: u32) -> Vec<Entity> {
+        let mut entities = Vec::new();
+        self.query_pipeline.intersections_with_shape(
+            &self.rigid_body_set,
+            &self.collider_set,
+            position,
+            shape,
+            QueryFilter::default(),
+            |handle| {
+                let body = self.collider_set.get(handle).and_then(|collider| collider.parent());
+                entities.extend(body.and_then(|body| self.body_to_entity.get(&body)).copied());
+                true
+            },
+        );
+        entities.sort();
+        entities.dedup();
+        entities.retain(|&entity| world.is_active_in_hierarchy(entity) && crate::in_layer_mask(world, entity, layer_mask));
+        entities
+    }
+
+    /// Closest point on an entity's colliders to `point` (engine coordinates; the point itself when inside)
+    pub fn closest_point_on_collider(&self, entity: Entity, point: [f32; 2]) -> Option<[f32; 2]> {
+        let body = self.rigid_body_set.get(*self.entity_to_body.get(&entity)?)?;
+        let query = point![point[0], -point[1]];
+        body.colliders()
+            .iter()
+            .filter_map(|handle| self.collider_set.get(*handle))
+            .map(|collider| collider.shape().project_point(collider.position(), &query, true).point)
+            .min_by(|a, b| (a - query).norm().total_cmp(&(b - query).norm()))
+            .map(|closest| [closest.x, -closest.y])
+    }
+
+    /// Move a character by `delta` (engine coordinates) with Rapier's character controller:
+    /// shape casts stop it at solid colliders and slide it along them. The entity and its
+    /// body move right away; entities without a body just move.
+    pub fn move_and_slide(&mut self, world: &mut World, entity: Entity, delta: [f32; 2]) -> crate::CharacterMove {
+        let mut result = crate::CharacterMove::default();
+        let Some(position) = world.world_position(entity) else {
+            return result;
+        };
+        let Some(body_handle) = self.entity_to_body.get(&entity).copied() else {
+            result.translation = delta;
+            world.set_world_position(entity, [position[0] + delta[0], position[1] + delta[1], position[2]]);
+            return result;
+        };
+        let Some(collider) = self.rigid_body_set.get(body_handle)
+            .and_then(|body| body.colliders().first())
+            .and_then(|handle| self.collider_set.get(*handle))
+        else {
+            return result;
+        };
+
+        let controller = KinematicCharacterController {
+            up: -Vector::y_axis(), // Rapier +Y is down
+            ..Default::default()
+        };
+        let filter = QueryFilter::default().exclude_rigid_body(body_handle).exclude_sensors();
+        let movement = controller.move_shape(
+            self.integration_parameters.dt,
+            &self.rigid_body_set,
+            &self.collider_set,
+            &self.query_pipeline,
+            collider.shape(),
+            collider.position(),
+            vector![delta[0], -delta[1]],
+            filter,
+            // normal1 is the obstacle's surface normal, pointing at the character
+            |collision| result.hit([collision.hit.normal1.x, -collision.hit.normal1.y]),
+        );
+        result.on_floor |= movement.grounded;
+        result.translation = [movement.translation.x, -movement.translation.y];
+
+        let target = [position[0] + result.translation[0], position[1] + result.translation[1], position[2]];
+        world.set_world_position(entity, target);
+        if let Some(body) = self.rigid_body_set.get_mut(body_handle) {
+            if body.is_kinematic() {
+                body.set_next_kinematic_translation(vector![target[0], -target[1]]);
+            } else {
+                body.set_translation(vector![target[0], -target[1]], true);
+            }
+        }
+        result
+    }
+
     /// Set gravity
     pub fn set_gravity(&mut self, gravity: f32) {
         self.gravity = vector![0.0, gravity]; // Positive Y is down in Rapier
@@ -361,6 +677,35 @@ impl RapierPhysicsWorld {
     }
 }
 
+/// Collects the debug render pipeline's lines by layer (the pipeline's own colors are
+/// replaced by the layer colors)
+struct DebugLines {
+    /// Contact lines in this color are normals, the others penetration depth
+    normal_color: DebugColor,
+    lines: Vec<(crate::PhysicsDebugLayer, [f32; 2], [f32; 2])>,
+}
+
+impl DebugRenderBackend for DebugLines {
+    fn filter_object(&self, object: DebugRenderObject) -> bool {
+        // Bodies only draw their axes; colliders are what the Gizmos toggles are about
+        !matches!(object, DebugRenderObject::RigidBody(..))
+    }
+
+    fn draw_line(&mut self, object: DebugRenderObject, a: Point<Real>, b: Point<Real>, color: [f32; 4]) {
+        use crate::PhysicsDebugLayer as Layer;
+        let layer = match object {
+            DebugRenderObject::RigidBody(..) => return,
+            DebugRenderObject::Collider(..) => Layer::Collider,
+            DebugRenderObject::ColliderAabb(..) => Layer::Broadphase,
+            DebugRenderObject::ImpulseJoint(..) | DebugRenderObject::MultibodyJoint(..) => Layer::Joint,
+            DebugRenderObject::ContactPair(..) if color == self.normal_color => Layer::ContactNormal,
+            DebugRenderObject::ContactPair(..) => Layer::Contact,
+        };
+        // Negate Y back to engine convention
+        self.lines.push((layer, [a.x, -a.y], [b.x, -b.y]));
+    }
+}
+
 /// Helper functions for Rapier backend
 pub mod helpers {
     use super::*;
@@ -413,3 +758,57 @@ pub mod helpers {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PhysicsDebugLayer;
+
+    #[test]
+    fn test_debug_lines_in_engine_coordinates() {
+        let mut world = World::new();
+        let ground = world.spawn();
+        world.transforms.insert(ground, ecs::Transform::with_position(2.0, 3.0, 0.0));
+        world.colliders.insert(ground, ecs::Collider::new(2.0, 1.0));
+
+        let mut physics = RapierPhysicsWorld::new();
+        physics.sync_from_ecs(&world);
+
+        // The outline traces the box spanning x 1..3, y 2.5..3.5 (not mirrored below the origin)
+        let outline = physics.debug_lines(DebugRenderMode::COLLIDER_SHAPES);
+        assert!(!outline.is_empty());
+        for (layer, a, b) in &outline {
+            assert_eq!(*layer, PhysicsDebugLayer::Collider);
+            for [x, y] in [a, b] {
+                let inside = *x > 1.0 - 1e-4 && *x < 3.0 + 1e-4 && *y > 2.5 - 1e-4 && *y < 3.5 + 1e-4;
+                assert!(inside, "({}, {})", x, y);
+            }
+        }
+        let top = outline.iter().any(|(_, a, b)| (a[1] - 3.5).abs() < 1e-4 && (b[1] - 3.5).abs() < 1e-4);
+        assert!(top, "no edge along y = 3.5");
+
+        let aabbs = physics.debug_lines(DebugRenderMode::COLLIDER_AABBS);
+        assert!(!aabbs.is_empty() && aabbs.iter().all(|(layer, _, _)| *layer == PhysicsDebugLayer::Broadphase));
+        assert!(physics.debug_lines(DebugRenderMode::empty()).is_empty());
+    }
+
+    #[test]
+    fn test_collider_without_transform_is_skipped() {
+        let mut world = World::new();
+        let orphan = world.spawn();
+        world.colliders.insert(orphan, ecs::Collider::new(1.0, 1.0));
+        world.rigidbodies.insert(orphan, ecs::Rigidbody2D::default());
+        let ground = world.spawn();
+        world.transforms.insert(ground, ecs::Transform::with_position(0.0, -5.0, 0.0));
+        world.colliders.insert(ground, ecs::Collider::new(10.0, 1.0));
+
+        let mut physics = RapierPhysicsWorld::new();
+        physics.sync_from_ecs(&world);
+        assert_eq!(physics.body_count(), 2);
+        assert_eq!(physics.collider_set.len(), 1, "only the ground has a shape");
+
+        // Stepping with the shapeless body doesn't panic either
+        physics.step(1.0 / 60.0, &mut world);
+        assert!(!world.transforms.contains_key(&orphan));
+    }
+}
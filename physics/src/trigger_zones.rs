@@ -0,0 +1,214 @@
+//! Trigger Zones
+//!
+//! Enter/exit for entities inside TriggerZone areas, the same for both backends: after
+//! every step each zone asks the backend's overlap query what is inside it and compares
+//! that with the previous step. Events queue up until the game loop takes them.
+
+use ecs::{Entity, TriggerZoneShape, World};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriggerZonePhase {
+    Enter,
+    Exit,
+}
+
+/// An entity entering or leaving a trigger zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TriggerZoneEvent {
+    pub zone: Entity,
+    pub entity: Entity,
+    pub phase: TriggerZonePhase,
+}
+
+/// A zone's area in world space
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TriggerZoneArea {
+    Box { min: [f32; 2], max: [f32; 2] },
+    Circle { center: [f32; 2], radius: f32 },
+}
+
+/// World-space area of an entity's TriggerZone: its offset and size follow the entity's
+/// scale (circles take the larger axis)
+pub fn zone_area(world: &World, zone: Entity) -> Option<TriggerZoneArea> {
+    let trigger = world.trigger_zones.get(&zone)?;
+    let transform = world.world_transform(zone)?;
+    let [sx, sy] = [transform.scale[0].abs(), transform.scale[1].abs()];
+    let center = [
+        transform.position[0] + trigger.offset[0] * transform.scale[0],
+        transform.position[1] + trigger.offset[1] * transform.scale[1],
+    ];
+    Some(match trigger.shape {
+        TriggerZoneShape::Box { width, height } => {
+            let half = [width.abs() * sx / 2.0, height.abs() * sy / 2.0];
+            TriggerZoneArea::Box {
+                min: [center[0] - half[0], center[1] - half[1]],
+                max: [center[0] + half[0], center[1] + half[1]],
+            }
+        }
+        TriggerZoneShape::Circle { radius } => TriggerZoneArea::Circle { center, radius: radius.abs() * sx.max(sy) },
+    })
+}
+
+/// Turns what each trigger zone overlaps after a step into enter/exit events
+#[derive(Debug, Default)]
+pub struct TriggerZoneTracker {
+    occupants: BTreeMap<Entity, BTreeSet<Entity>>,
+    events: Vec<TriggerZoneEvent>,
+}
+
+impl TriggerZoneTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Evaluate every zone after a step. `overlap` is the backend's overlap query: active
+    /// entities whose colliders touch the area, on layers set in the mask.
+    ///
+    /// Inactive zones empty out (their occupants exit). A one-shot zone reports only its
+    /// first enter, then is marked `fired` and skipped from then on.
+    pub fn update(&mut self, world: &mut World, overlap: impl Fn(&World, TriggerZoneArea, u32) -> Vec<Entity>) {
+        // Zones removed since the last step forget their occupants
+        self.occupants.retain(|zone, _| world.trigger_zones.contains_key(zone));
+
+        let mut zones: Vec<Entity> = world.trigger_zones.keys().copied().collect();
+        zones.sort_unstable();
+        let mut fired = Vec::new();
+
+        for zone in zones {
+            let trigger = &world.trigger_zones[&zone];
+            let before = self.occupants.remove(&zone).unwrap_or_default();
+            if !trigger.is_armed() {
+                continue;
+            }
+
+            let now: BTreeSet<Entity> = match zone_area(world, zone).filter(|_| world.is_active_in_hierarchy(zone)) {
+                Some(area) => overlap(world, area, trigger.layer_mask)
+                    .into_iter()
+                    .filter(|&entity| entity != zone && trigger.accepts_tag(world.tags.get(&entity)))
+                    .collect(),
+                None => BTreeSet::new(),
+            };
+
+            for &entity in before.difference(&now) {
+                self.events.push(TriggerZoneEvent { zone, entity, phase: TriggerZonePhase::Exit });
+            }
+            let mut entered = now.difference(&before).copied();
+            if trigger.one_shot {
+                if let Some(entity) = entered.next() {
+                    self.events.push(TriggerZoneEvent { zone, entity, phase: TriggerZonePhase::Enter });
+                    fired.push(zone);
+                }
+                continue;
+            }
+            for entity in entered {
+                self.events.push(TriggerZoneEvent { zone, entity, phase: TriggerZonePhase::Enter });
+            }
+            if !now.is_empty() {
+                self.occupants.insert(zone, now);
+            }
+        }
+
+        for zone in fired {
+            if let Some(trigger) = world.trigger_zones.get_mut(&zone) {
+                trigger.fired = true;
+            }
+        }
+    }
+
+    /// Whether `entity` was inside `zone` after the last step
+    pub fn is_inside(&self, zone: Entity, entity: Entity) -> bool {
+        self.occupants.get(&zone).is_some_and(|occupants| occupants.contains(&entity))
+    }
+
+    /// Events since the last call, in step order
+    pub fn take_events(&mut self) -> Vec<TriggerZoneEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    pub fn clear(&mut self) {
+        self.occupants.clear();
+        self.events.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ecs::{EntityTag, Transform, TriggerZone};
+    use std::cell::RefCell;
+
+    fn phases(events: &[TriggerZoneEvent]) -> Vec<(Entity, Entity, TriggerZonePhase)> {
+        events.iter().map(|e| (e.zone, e.entity, e.phase)).collect()
+    }
+
+    fn zone_world(trigger: TriggerZone) -> (World, Entity) {
+        let mut world = World::new();
+        let zone = world.spawn();
+        world.transforms.insert(zone, Transform::default());
+        world.trigger_zones.insert(zone, trigger);
+        (world, zone)
+    }
+
+    #[test]
+    fn test_enter_and_exit_against_previous_step() {
+        let (mut world, zone) = zone_world(TriggerZone::default());
+        let inside = RefCell::new(vec![zone, 7]);
+        let overlap = |_: &World, _: TriggerZoneArea, _: u32| inside.borrow().clone();
+        let mut tracker = TriggerZoneTracker::new();
+
+        tracker.update(&mut world, overlap);
+        tracker.update(&mut world, overlap);
+        *inside.borrow_mut() = vec![8];
+        tracker.update(&mut world, overlap);
+        assert_eq!(
+            phases(&tracker.take_events()),
+            vec![(zone, 7, TriggerZonePhase::Enter), (zone, 7, TriggerZonePhase::Exit), (zone, 8, TriggerZonePhase::Enter)]
+        );
+        assert!(tracker.is_inside(zone, 8));
+
+        // Deactivating the zone empties it
+        world.set_active(zone, false);
+        tracker.update(&mut world, overlap);
+        assert_eq!(phases(&tracker.take_events()), vec![(zone, 8, TriggerZonePhase::Exit)]);
+    }
+
+    #[test]
+    fn test_tag_filter_and_one_shot() {
+        let (mut world, zone) = zone_world(TriggerZone { tag: Some(EntityTag::Player), one_shot: true, ..Default::default() });
+        world.tags.insert(3, EntityTag::Item);
+        world.tags.insert(4, EntityTag::Player);
+        world.tags.insert(5, EntityTag::Player);
+        let inside = RefCell::new(vec![3]);
+        let overlap = |_: &World, _: TriggerZoneArea, _: u32| inside.borrow().clone();
+        let mut tracker = TriggerZoneTracker::new();
+
+        tracker.update(&mut world, overlap);
+        assert!(tracker.take_events().is_empty());
+
+        *inside.borrow_mut() = vec![3, 4, 5];
+        tracker.update(&mut world, overlap);
+        assert_eq!(phases(&tracker.take_events()), vec![(zone, 4, TriggerZonePhase::Enter)]);
+        assert!(world.trigger_zones[&zone].fired);
+
+        // Fired: no exit, no more enters
+        *inside.borrow_mut() = Vec::new();
+        tracker.update(&mut world, overlap);
+        *inside.borrow_mut() = vec![4];
+        tracker.update(&mut world, overlap);
+        assert!(tracker.take_events().is_empty());
+    }
+
+    #[test]
+    fn test_zone_area_follows_transform() {
+        let trigger = TriggerZone { shape: TriggerZoneShape::Box { width: 2.0, height: 4.0 }, offset: [1.0, 0.0], ..Default::default() };
+        let (mut world, zone) = zone_world(trigger);
+        let transform = world.transforms.get_mut(&zone).unwrap();
+        transform.position = [10.0, 5.0, 0.0];
+        transform.scale = [2.0, 1.0, 1.0];
+        assert_eq!(zone_area(&world, zone), Some(TriggerZoneArea::Box { min: [10.0, 3.0], max: [14.0, 7.0] }));
+
+        world.trigger_zones.get_mut(&zone).unwrap().shape = TriggerZoneShape::Circle { radius: 1.5 };
+        assert_eq!(zone_area(&world, zone), Some(TriggerZoneArea::Circle { center: [12.0, 5.0], radius: 3.0 }));
+    }
+}
@@ -0,0 +1,334 @@
+//! Frame profiler
+//!
+//! Collects hierarchical scope timings per frame into a ring buffer of the last
+//! `HISTORY_FRAMES` frames.
+//! - `new_frame()` is called once at the top of the main loop
+//! - `ScopeTimer` / `profile_scope!` record a scope until dropped
+//! - `push_scope` / `pop_scope` are the manual pair used by script bindings
+//!
+//! Collection is off by default; while disabled every call returns after a single
+//! atomic load. Only scopes on the thread that called `new_frame` are recorded.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::thread::ThreadId;
+use std::time::{Duration, Instant};
+
+/// Frames kept for graphs and averages
+pub const HISTORY_FRAMES: usize = 300;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static COLLECTOR: Mutex<Collector> = Mutex::new(Collector::new());
+
+/// One timed scope inside a frame
+#[derive(Debug, Clone)]
+pub struct ScopeRecord {
+    pub name: String,
+    /// Index of the enclosing scope in `FrameRecord::scopes`
+    pub parent: Option<usize>,
+    pub depth: u32,
+    /// Offset from the start of the frame
+    pub start: Duration,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct FrameRecord {
+    pub index: u64,
+    /// Time from this frame's `new_frame` to the next one
+    pub duration: Duration,
+    /// Scopes in the order they were opened (parents before children)
+    pub scopes: Vec<ScopeRecord>,
+}
+
+/// Per-frame averages for every scope name
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScopeStats {
+    pub name: String,
+    pub calls: f32,
+    pub total: Duration,
+    /// Total minus time spent in child scopes
+    pub self_time: Duration,
+}
+
+struct OpenScope {
+    index: usize,
+    manual: bool,
+}
+
+struct CurrentFrame {
+    thread: ThreadId,
+    start: Instant,
+    scopes: Vec<ScopeRecord>,
+    stack: Vec<OpenScope>,
+}
+
+impl CurrentFrame {
+    fn open(&mut self, name: &str, manual: bool) -> usize {
+        let index = self.scopes.len();
+        self.scopes.push(ScopeRecord {
+            name: name.to_string(),
+            parent: self.stack.last().map(|open| open.index),
+            depth: self.stack.len() as u32,
+            start: self.start.elapsed(),
+            duration: Duration::ZERO,
+        });
+        self.stack.push(OpenScope { index, manual });
+        index
+    }
+
+    /// Close the innermost open scope
+    fn close_top(&mut self) {
+        if let Some(open) = self.stack.pop() {
+            let scope = &mut self.scopes[open.index];
+            scope.duration = self.start.elapsed().saturating_sub(scope.start);
+        }
+    }
+}
+
+struct Collector {
+    history: VecDeque<FrameRecord>,
+    current: Option<CurrentFrame>,
+    frame_index: u64,
+}
+
+impl Collector {
+    const fn new() -> Self {
+        Self {
+            history: VecDeque::new(),
+            current: None,
+            frame_index: 0,
+        }
+    }
+
+    /// The open frame, if this is the thread that started it
+    fn current_on_this_thread(&mut self) -> Option<&mut CurrentFrame> {
+        let thread = std::thread::current().id();
+        self.current.as_mut().filter(|frame| frame.thread == thread)
+    }
+}
+
+fn collector() -> MutexGuard<'static, Collector> {
+    COLLECTOR.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Whether scopes are being recorded
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Turn collection on or off. Enabling starts from an empty history.
+pub fn set_enabled(enabled: bool) {
+    if ENABLED.swap(enabled, Ordering::Relaxed) == enabled {
+        return;
+    }
+    let mut collector = collector();
+    collector.history.clear();
+    collector.current = None;
+}
+
+/// Finish the previous frame (closing any scopes left open) and start a new one.
+/// Script timings, frame times and draw call counts roll over even while disabled.
+pub fn new_frame() {
+    crate::scripts::new_frame();
+    crate::gpu::finish_frame_counters();
+    if !is_enabled() {
+        return;
+    }
+    let mut collector = collector();
+    if let Some(mut frame) = collector.current.take() {
+        while !frame.stack.is_empty() {
+            frame.close_top();
+        }
+        let record = FrameRecord {
+            index: collector.frame_index,
+            duration: frame.start.elapsed(),
+            scopes: frame.scopes,
+        };
+        collector.frame_index += 1;
+        if collector.history.len() == HISTORY_FRAMES {
+            collector.history.pop_front();
+        }
+        collector.history.push_back(record);
+    }
+    collector.current = Some(CurrentFrame {
+        thread: std::thread::current().id(),
+        start: Instant::now(),
+        scopes: Vec::new(),
+        stack: Vec::new(),
+    });
+}
+
+/// Open a scope; returns its index for `end_scope`, or None when not recorded
+pub fn begin_scope(name: &str) -> Option<usize> {
+    if !is_enabled() {
+        return None;
+    }
+    collector().current_on_this_thread().map(|frame| frame.open(name, false))
+}
+
+/// Close the scope opened by `begin_scope`, along with anything still open inside it
+pub fn end_scope(index: usize) {
+    if !is_enabled() {
+        return;
+    }
+    let mut collector = collector();
+    let Some(frame) = collector.current_on_this_thread() else {
+        return;
+    };
+    if let Some(position) = frame.stack.iter().position(|open| open.index == index) {
+        while frame.stack.len() > position {
+            frame.close_top();
+        }
+    }
+}
+
+/// Open a scope closed later by `pop_scope` (script bindings)
+pub fn push_scope(name: &str) {
+    if !is_enabled() {
+        return;
+    }
+    if let Some(frame) = collector().current_on_this_thread() {
+        frame.open(name, true);
+    }
+}
+
+/// Close the innermost `push_scope` scope. Returns false when there is none open,
+/// so an unbalanced call can't close a scope owned by a `ScopeTimer`.
+pub fn pop_scope() -> bool {
+    if !is_enabled() {
+        return false;
+    }
+    let mut collector = collector();
+    let Some(frame) = collector.current_on_this_thread() else {
+        return false;
+    };
+    if frame.stack.last().is_some_and(|open| open.manual) {
+        frame.close_top();
+        true
+    } else {
+        false
+    }
+}
+
+/// Read the finished frames, oldest first
+pub fn with_history<R>(f: impl FnOnce(&VecDeque<FrameRecord>) -> R) -> R {
+    f(&collector().history)
+}
+
+/// Average per-frame stats for the last `frames` finished frames, slowest total first
+pub fn scope_stats(frames: usize) -> Vec<ScopeStats> {
+    with_history(|history| {
+        let skip = history.len().saturating_sub(frames);
+        summarize(history.iter().skip(skip))
+    })
+}
+
+/// Average per-frame stats over `frames`, slowest total first
+pub fn summarize<'a>(frames: impl IntoIterator<Item = &'a FrameRecord>) -> Vec<ScopeStats> {
+    let mut totals: HashMap<&str, (u32, Duration, Duration)> = HashMap::new();
+    let mut frame_count = 0u32;
+
+    for frame in frames {
+        frame_count += 1;
+        let mut child_time = vec![Duration::ZERO; frame.scopes.len()];
+        for scope in &frame.scopes {
+            if let Some(parent) = scope.parent {
+                child_time[parent] += scope.duration;
+            }
+        }
+        for (scope, children) in frame.scopes.iter().zip(child_time) {
+            let entry = totals.entry(scope.name.as_str()).or_default();
+            entry.0 += 1;
+            entry.1 += scope.duration;
+            entry.2 += scope.duration.saturating_sub(children);
+        }
+    }
+
+    if frame_count == 0 {
+        return Vec::new();
+    }
+
+    let mut stats: Vec<ScopeStats> = totals
+        .into_iter()
+        .map(|(name, (calls, total, self_time))| ScopeStats {
+            name: name.to_string(),
+            calls: calls as f32 / frame_count as f32,
+            total: total / frame_count,
+            self_time: self_time / frame_count,
+        })
+        .collect();
+    stats.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.name.cmp(&b.name)));
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(name: &str, parent: Option<usize>, duration_ms: u64) -> ScopeRecord {
+        ScopeRecord {
+            name: name.to_string(),
+            parent,
+            depth: parent.map_or(0, |_| 1),
+            start: Duration::ZERO,
+            duration: Duration::from_millis(duration_ms),
+        }
+    }
+
+    #[test]
+    fn test_summarize_splits_self_and_total_time() {
+        let frames = [
+            FrameRecord {
+                index: 0,
+                duration: Duration::from_millis(16),
+                scopes: vec![scope("scripts", None, 10), scope("enemy", Some(0), 4), scope("enemy", Some(0), 2)],
+            },
+            FrameRecord {
+                index: 1,
+                duration: Duration::from_millis(16),
+                scopes: vec![scope("scripts", None, 6), scope("enemy", Some(0), 2)],
+            },
+        ];
+
+        let stats = summarize(&frames);
+        assert_eq!(stats[0].name, "scripts");
+        assert_eq!(stats[0].total, Duration::from_millis(8));
+        assert_eq!(stats[0].self_time, Duration::from_millis(4));
+        assert_eq!(stats[1].name, "enemy");
+        assert_eq!(stats[1].calls, 1.5);
+        assert_eq!(stats[1].total, Duration::from_millis(4));
+    }
+
+    #[test]
+    fn test_collects_nested_scopes_only_while_enabled() {
+        // The collector is global; keep everything touching it in this one test
+        set_enabled(false);
+        new_frame();
+        assert!(begin_scope("ignored").is_none());
+
+        set_enabled(true);
+        new_frame();
+        {
+            let _outer = crate::ScopeTimer::new("physics.step");
+            push_scope("lua.hotspot");
+            assert!(pop_scope());
+            // Unbalanced script call must not close the timer's scope
+            assert!(!pop_scope());
+        }
+        new_frame();
+
+        let frames = with_history(|history| history.iter().cloned().collect::<Vec<_>>());
+        assert_eq!(frames.len(), 1);
+        let scopes = &frames[0].scopes;
+        assert_eq!(scopes.len(), 2);
+        assert_eq!(scopes[0].name, "physics.step");
+        assert_eq!(scopes[1].parent, Some(0));
+        assert_eq!(scopes[1].depth, 1);
+
+        set_enabled(false);
+        assert!(with_history(|history| history.is_empty()));
+    }
+}
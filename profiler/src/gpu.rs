@@ -2,6 +2,8 @@ use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub static DRAW_CALLS: AtomicUsize = AtomicUsize::new(0);
 pub static TRIANGLE_COUNT: AtomicUsize = AtomicUsize::new(0);
+static LAST_DRAW_CALLS: AtomicUsize = AtomicUsize::new(0);
+static LAST_TRIANGLE_COUNT: AtomicUsize = AtomicUsize::new(0);
 
 pub fn reset_frame_counters() {
     DRAW_CALLS.store(0, Ordering::Relaxed);
@@ -19,3 +21,151 @@ pub fn get_stats() -> (usize, usize) {
         TRIANGLE_COUNT.load(Ordering::Relaxed),
     )
 }
+
+/// Put the frame counters back to `stats` (from `get_stats`), leaving out draws made since,
+/// e.g. the editor's scene view so only the game's draws are counted
+pub fn restore_counters(stats: (usize, usize)) {
+    DRAW_CALLS.store(stats.0, Ordering::Relaxed);
+    TRIANGLE_COUNT.store(stats.1, Ordering::Relaxed);
+}
+
+/// Keep the counts of the frame that just ended for `last_frame_stats` and start from zero
+pub fn finish_frame_counters() {
+    LAST_DRAW_CALLS.store(DRAW_CALLS.swap(0, Ordering::Relaxed), Ordering::Relaxed);
+    LAST_TRIANGLE_COUNT.store(TRIANGLE_COUNT.swap(0, Ordering::Relaxed), Ordering::Relaxed);
+}
+
+/// Draw calls and triangles of the last finished frame
+pub fn last_frame_stats() -> (usize, usize) {
+    (
+        LAST_DRAW_CALLS.load(Ordering::Relaxed),
+        LAST_TRIANGLE_COUNT.load(Ordering::Relaxed),
+    )
+}
+
+// ==================================================================================
+// GPU Pass Timings
+// ==================================================================================
+//
+// Filled by the renderer's timestamp-query timer (render::gpu_timer) a few frames
+// after the GPU ran them, and read by the profiler overlay. Kept free of wgpu so the
+// profiler stays a leaf crate.
+
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicU8;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// GPU frames kept for averages
+pub const GPU_HISTORY_FRAMES: usize = 120;
+
+/// Whether the adapter can report GPU timings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GpuTimingSupport {
+    /// No renderer has reported yet
+    Unknown,
+    Supported,
+    /// The adapter lacks `TIMESTAMP_QUERY`
+    Unsupported,
+}
+
+/// One timed pass (or span inside a pass) of a GPU frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuPassTiming {
+    pub name: String,
+    /// 0 for whole passes, 1 for spans inside a pass
+    pub depth: u32,
+    pub duration: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GpuFrameTiming {
+    pub passes: Vec<GpuPassTiming>,
+}
+
+impl GpuFrameTiming {
+    /// GPU time of the frame: the sum of its whole passes
+    pub fn total(&self) -> Duration {
+        self.passes.iter().filter(|pass| pass.depth == 0).map(|pass| pass.duration).sum()
+    }
+}
+
+static SUPPORT: AtomicU8 = AtomicU8::new(0);
+static GPU_HISTORY: Mutex<VecDeque<GpuFrameTiming>> = Mutex::new(VecDeque::new());
+
+pub fn set_timing_support(supported: bool) {
+    SUPPORT.store(if supported { 1 } else { 2 }, Ordering::Relaxed);
+}
+
+pub fn timing_support() -> GpuTimingSupport {
+    match SUPPORT.load(Ordering::Relaxed) {
+        1 => GpuTimingSupport::Supported,
+        2 => GpuTimingSupport::Unsupported,
+        _ => GpuTimingSupport::Unknown,
+    }
+}
+
+/// Store a resolved GPU frame
+pub fn record_frame(frame: GpuFrameTiming) {
+    let mut history = GPU_HISTORY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if history.len() == GPU_HISTORY_FRAMES {
+        history.pop_front();
+    }
+    history.push_back(frame);
+}
+
+pub fn clear_history() {
+    GPU_HISTORY.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clear();
+}
+
+/// Average GPU frame time and per-pass durations over the last `frames` resolved frames,
+/// or None before any frame was resolved
+pub fn average_timings(frames: usize) -> Option<(Duration, Vec<GpuPassTiming>)> {
+    let history = GPU_HISTORY.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    let recent: Vec<&GpuFrameTiming> = history.iter().rev().take(frames).collect();
+    if recent.is_empty() {
+        return None;
+    }
+
+    let count = recent.len() as u32;
+    let total = recent.iter().map(|frame| frame.total()).sum::<Duration>() / count;
+    let mut passes: Vec<GpuPassTiming> = Vec::new();
+    for pass in recent.iter().flat_map(|frame| frame.passes.iter()) {
+        match passes.iter_mut().find(|p| p.name == pass.name) {
+            Some(existing) => existing.duration += pass.duration,
+            None => passes.push(pass.clone()),
+        }
+    }
+    for pass in &mut passes {
+        pass.duration /= count;
+    }
+    Some((total, passes))
+}
+
+/// GPU bound: the GPU misses the frame budget while the CPU's own work fits in it,
+/// i.e. the CPU spends the rest of the frame waiting on the GPU
+pub fn is_gpu_bound(gpu_time: Duration, cpu_busy: Duration, budget: Duration) -> bool {
+    gpu_time > budget && cpu_busy < budget
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pass(name: &str, depth: u32, ms: u64) -> GpuPassTiming {
+        GpuPassTiming { name: name.to_string(), depth, duration: Duration::from_millis(ms) }
+    }
+
+    #[test]
+    fn test_frame_total_counts_whole_passes_only() {
+        let frame = GpuFrameTiming {
+            passes: vec![pass("render.game_view", 0, 6), pass("render.tilemap", 1, 2), pass("render.ui", 0, 1)],
+        };
+        assert_eq!(frame.total(), Duration::from_millis(7));
+
+        let budget = Duration::from_micros(16_667);
+        assert!(is_gpu_bound(Duration::from_millis(25), Duration::from_millis(3), budget));
+        assert!(!is_gpu_bound(Duration::from_millis(25), Duration::from_millis(20), budget));
+        assert!(!is_gpu_bound(Duration::from_millis(7), Duration::from_millis(3), budget));
+    }
+}
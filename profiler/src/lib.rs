@@ -2,29 +2,39 @@ pub mod memory;
 pub mod platform;
 pub mod macros;
 pub mod gpu;
+pub mod frame;
+pub mod scripts;
+pub mod pools;
+pub mod textures;
 
-#[cfg(feature = "enable_profiling")]
-pub struct ScopeTimer<'a> {
-    name: &'a str,
-    start: std::time::Instant,
+pub use frame::{is_enabled, new_frame, pop_scope, push_scope, set_enabled};
+
+/// Records a named scope in the frame profiler until dropped.
+/// Costs one atomic load when the profiler is disabled.
+/// With `enable_profiling`, allocations made inside are also attributed to the scope name.
+pub struct ScopeTimer {
+    index: Option<usize>,
+    #[cfg(feature = "enable_profiling")]
+    memory_tag: u16,
 }
 
-#[cfg(feature = "enable_profiling")]
-impl<'a> ScopeTimer<'a> {
-    pub fn new(name: &'a str) -> Self {
+impl ScopeTimer {
+    pub fn new(name: &str) -> Self {
         Self {
-            name,
-            start: std::time::Instant::now(),
+            #[cfg(feature = "enable_profiling")]
+            memory_tag: memory::enter_scope(name),
+            index: frame::begin_scope(name),
         }
     }
 }
 
-#[cfg(feature = "enable_profiling")]
-impl<'a> Drop for ScopeTimer<'a> {
+impl Drop for ScopeTimer {
     fn drop(&mut self) {
-        let duration = self.start.elapsed();
-        // In a real engine, you'd record this to a global profiler instance
-        // log::trace!("Profile [{}]: {:?}", self.name, duration);
+        if let Some(index) = self.index {
+            frame::end_scope(index);
+        }
+        #[cfg(feature = "enable_profiling")]
+        memory::exit_scope(self.memory_tag);
     }
 }
 
@@ -1,7 +1,6 @@
 #[macro_export]
 macro_rules! profile_scope {
     ($name:expr) => {
-        #[cfg(feature = "enable_profiling")]
         let _timer = $crate::ScopeTimer::new($name);
     };
 }
@@ -54,3 +54,257 @@ unsafe impl<A: GlobalAlloc> GlobalAlloc for TrackingAllocator<A> {
         self.allocated_bytes.fetch_sub(layout.size(), Ordering::Relaxed);
     }
 }
+
+// ==================================================================================
+// Scoped Allocation Tracking
+// ==================================================================================
+//
+// With the `enable_profiling` feature, `ScopedAllocator` (installed as the binary's
+// `#[global_allocator]`) attributes every allocation to the innermost `ScopeTimer`
+// open on the allocating thread, by scope name. Each block carries a small header
+// recording its scope, so a free is credited back to the scope that allocated it
+// wherever it happens. Without the feature none of this is compiled: `ScopeTimer`
+// doesn't touch it and `snapshot()` returns nothing.
+
+/// Live allocations attributed to one scope name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeMemory {
+    pub name: String,
+    pub live_bytes: i64,
+    pub live_allocations: i64,
+    /// Allocations made since startup (including freed ones)
+    pub total_allocations: u64,
+}
+
+impl ScopeMemory {
+    /// Subsystem a scope belongs to: the part of its name before the first '.'
+    /// ("scripts.update" -> "scripts")
+    pub fn subsystem(&self) -> &str {
+        self.name.split('.').next().unwrap_or(&self.name)
+    }
+}
+
+/// A scope whose live bytes grew between two snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeGrowth {
+    pub name: String,
+    pub before_bytes: i64,
+    pub after_bytes: i64,
+}
+
+impl ScopeGrowth {
+    pub fn grown_bytes(&self) -> i64 {
+        self.after_bytes - self.before_bytes
+    }
+}
+
+/// Scopes with more live bytes in `after` than in `before` (missing = 0), largest growth first
+pub fn growth(before: &[ScopeMemory], after: &[ScopeMemory]) -> Vec<ScopeGrowth> {
+    let mut grown: Vec<ScopeGrowth> = after
+        .iter()
+        .map(|scope| ScopeGrowth {
+            name: scope.name.clone(),
+            before_bytes: before.iter().find(|b| b.name == scope.name).map_or(0, |b| b.live_bytes),
+            after_bytes: scope.live_bytes,
+        })
+        .filter(|g| g.grown_bytes() > 0)
+        .collect();
+    grown.sort_by(|a, b| b.grown_bytes().cmp(&a.grown_bytes()).then_with(|| a.name.cmp(&b.name)));
+    grown
+}
+
+/// True if the crate was built with allocation tracking
+pub const fn tracking_enabled() -> bool {
+    cfg!(feature = "enable_profiling")
+}
+
+/// Live allocations per scope name ("untracked" = outside any scope); empty unless
+/// built with `enable_profiling` and `ScopedAllocator` is the global allocator
+pub fn snapshot() -> Vec<ScopeMemory> {
+    #[cfg(feature = "enable_profiling")]
+    {
+        scoped::snapshot()
+    }
+    #[cfg(not(feature = "enable_profiling"))]
+    {
+        Vec::new()
+    }
+}
+
+#[cfg(feature = "enable_profiling")]
+pub use scoped::{enter_scope, exit_scope, ScopedAllocator};
+
+#[cfg(feature = "enable_profiling")]
+mod scoped {
+    use super::ScopeMemory;
+    use std::alloc::{GlobalAlloc, Layout};
+    use std::cell::Cell;
+    use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+    use std::sync::Mutex;
+
+    /// Distinct scope names tracked; later names share the last slot ("other")
+    const MAX_SCOPES: usize = 256;
+    const UNTRACKED: u16 = 0;
+    const OTHER: u16 = (MAX_SCOPES - 1) as u16;
+
+    static LIVE_BYTES: [AtomicI64; MAX_SCOPES] = [const { AtomicI64::new(0) }; MAX_SCOPES];
+    static LIVE_ALLOCATIONS: [AtomicI64; MAX_SCOPES] = [const { AtomicI64::new(0) }; MAX_SCOPES];
+    static TOTAL_ALLOCATIONS: [AtomicU64; MAX_SCOPES] = [const { AtomicU64::new(0) }; MAX_SCOPES];
+    /// Scope names by tag, from tag 1 (0 is untracked)
+    static NAMES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    thread_local! {
+        // Const-initialized and without Drop, so it is safe to read from the allocator
+        static CURRENT: Cell<u16> = const { Cell::new(UNTRACKED) };
+    }
+
+    fn current_tag() -> u16 {
+        CURRENT.try_with(Cell::get).unwrap_or(UNTRACKED)
+    }
+
+    /// Attribute this thread's allocations to `name` until `exit_scope`; returns the tag to restore
+    pub fn enter_scope(name: &str) -> u16 {
+        let tag = {
+            let mut names = NAMES.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            match names.iter().position(|n| n == name) {
+                Some(index) => (index + 1) as u16,
+                None if names.len() + 1 < OTHER as usize => {
+                    names.push(name.to_string());
+                    names.len() as u16
+                }
+                None => OTHER,
+            }
+        };
+        CURRENT.try_with(|current| current.replace(tag)).unwrap_or(UNTRACKED)
+    }
+
+    pub fn exit_scope(previous: u16) {
+        let _ = CURRENT.try_with(|current| current.set(previous));
+    }
+
+    pub fn snapshot() -> Vec<ScopeMemory> {
+        let names = NAMES.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+        let name_of = |tag: usize| match tag {
+            0 => "untracked".to_string(),
+            t if t == OTHER as usize => "other".to_string(),
+            t => names.get(t - 1).cloned().unwrap_or_default(),
+        };
+        (0..MAX_SCOPES)
+            .filter(|&tag| TOTAL_ALLOCATIONS[tag].load(Ordering::Relaxed) > 0)
+            .map(|tag| ScopeMemory {
+                name: name_of(tag),
+                live_bytes: LIVE_BYTES[tag].load(Ordering::Relaxed),
+                live_allocations: LIVE_ALLOCATIONS[tag].load(Ordering::Relaxed),
+                total_allocations: TOTAL_ALLOCATIONS[tag].load(Ordering::Relaxed),
+            })
+            .collect()
+    }
+
+    /// Global allocator wrapper recording live bytes per scope
+    pub struct ScopedAllocator<A: GlobalAlloc> {
+        inner: A,
+    }
+
+    impl<A: GlobalAlloc> ScopedAllocator<A> {
+        pub const fn new(inner: A) -> Self {
+            Self { inner }
+        }
+
+        /// Bytes in front of each block holding its tag; keeps the block aligned
+        fn header(layout: Layout) -> usize {
+            layout.align().max(std::mem::size_of::<u64>())
+        }
+
+        fn outer_layout(layout: Layout) -> Option<Layout> {
+            Layout::from_size_align(layout.size() + Self::header(layout), layout.align()).ok()
+        }
+
+        fn record(tag: u16, bytes: i64, allocations: i64) {
+            let tag = tag as usize;
+            LIVE_BYTES[tag].fetch_add(bytes, Ordering::Relaxed);
+            LIVE_ALLOCATIONS[tag].fetch_add(allocations, Ordering::Relaxed);
+            if allocations > 0 {
+                TOTAL_ALLOCATIONS[tag].fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    unsafe impl<A: GlobalAlloc> GlobalAlloc for ScopedAllocator<A> {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let Some(outer) = Self::outer_layout(layout) else {
+                return std::ptr::null_mut();
+            };
+            let base = self.inner.alloc(outer);
+            if base.is_null() {
+                return base;
+            }
+            let tag = current_tag();
+            let ptr = base.add(Self::header(layout));
+            ptr.sub(std::mem::size_of::<u64>()).cast::<u64>().write_unaligned(tag as u64);
+            Self::record(tag, layout.size() as i64, 1);
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            let tag = ptr.sub(std::mem::size_of::<u64>()).cast::<u64>().read_unaligned() as u16;
+            Self::record(tag, -(layout.size() as i64), -1);
+            let outer = Layout::from_size_align_unchecked(layout.size() + Self::header(layout), layout.align());
+            self.inner.dealloc(ptr.sub(Self::header(layout)), outer);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            // Resized blocks stay with the scope that allocated them
+            let header = Self::header(layout);
+            let tag = ptr.sub(std::mem::size_of::<u64>()).cast::<u64>().read_unaligned() as u16;
+            let outer = Layout::from_size_align_unchecked(layout.size() + header, layout.align());
+            let base = self.inner.realloc(ptr.sub(header), outer, new_size + header);
+            if base.is_null() {
+                return base;
+            }
+            Self::record(tag, new_size as i64 - layout.size() as i64, 0);
+            base.add(header)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scope(name: &str, live_bytes: i64) -> ScopeMemory {
+        ScopeMemory { name: name.to_string(), live_bytes, live_allocations: 1, total_allocations: 1 }
+    }
+
+    #[test]
+    fn test_growth_lists_only_grown_scopes() {
+        let before = [scope("scripts.update", 1000), scope("textures.load", 500)];
+        let after = [scope("scripts.update", 4000), scope("textures.load", 200), scope("ui.layout", 100)];
+
+        let grown = growth(&before, &after);
+        assert_eq!(grown.len(), 2);
+        assert_eq!(grown[0].name, "scripts.update");
+        assert_eq!(grown[0].grown_bytes(), 3000);
+        assert_eq!(grown[1].name, "ui.layout");
+        assert_eq!(after[0].subsystem(), "scripts");
+    }
+
+    #[cfg(feature = "enable_profiling")]
+    #[test]
+    fn test_scoped_allocator_credits_frees_to_allocating_scope() {
+        use std::alloc::System;
+        let allocator = ScopedAllocator::new(System);
+        let layout = Layout::from_size_align(100, 32).unwrap();
+        let live = |name: &str| snapshot().into_iter().find(|s| s.name == name).map_or(0, |s| s.live_bytes);
+
+        let previous = enter_scope("test.scoped_alloc");
+        let ptr = unsafe { allocator.alloc(layout) };
+        exit_scope(previous);
+        assert_eq!(ptr as usize % 32, 0);
+        assert_eq!(live("test.scoped_alloc"), 100);
+
+        let ptr = unsafe { allocator.realloc(ptr, layout, 300) };
+        assert_eq!(live("test.scoped_alloc"), 300);
+        unsafe { allocator.dealloc(ptr, Layout::from_size_align(300, 32).unwrap()) };
+        assert_eq!(live("test.scoped_alloc"), 0);
+    }
+}
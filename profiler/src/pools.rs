@@ -0,0 +1,38 @@
+//! Entity pool statistics for the profiler overlay
+//!
+//! The script engine owns the pools; it publishes their numbers once per frame and the
+//! overlay reads the latest copy.
+
+use std::sync::{Mutex, MutexGuard};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PoolStat {
+    pub name: String,
+    /// Instances made so far
+    pub size: usize,
+    /// Instances currently spawned
+    pub active: usize,
+    pub peak_active: usize,
+    pub spawns: u64,
+    /// Spawns that made a new instance
+    pub grown: u64,
+    /// Spawns that took back an instance still in use
+    pub reused: u64,
+    /// Spawns refused because the pool was full
+    pub exhausted: u64,
+}
+
+static POOLS: Mutex<Vec<PoolStat>> = Mutex::new(Vec::new());
+
+fn pools() -> MutexGuard<'static, Vec<PoolStat>> {
+    POOLS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Replace the published statistics (an empty list when no pools exist)
+pub fn publish(stats: Vec<PoolStat>) {
+    *pools() = stats;
+}
+
+pub fn snapshot() -> Vec<PoolStat> {
+    pools().clone()
+}
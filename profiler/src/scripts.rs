@@ -0,0 +1,273 @@
+//! Script timings and frame stats
+//!
+//! Feeds the play-mode performance HUD, the script budget warnings and Lua's
+//! `Profiler.get_frame_stats()`.
+//! - `ScriptTimer` times one entity's script update until dropped
+//! - `new_frame()` (called by `frame::new_frame`) moves this frame's timings to
+//!   `last_frame()` and records the frame time
+//!
+//! Script timings are only recorded while something reads them: the HUD is visible,
+//! budget tracking is on, or a script asked for stats during the previous frame.
+//! Otherwise `ScriptTimer` costs a few atomic loads.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Frame times kept for the HUD graph and the FPS average
+pub const FRAME_TIME_HISTORY: usize = 120;
+/// Scripts listed by the HUD and `Profiler.get_frame_stats()`
+pub const TOP_SCRIPTS: usize = 5;
+
+static HUD_VISIBLE: AtomicBool = AtomicBool::new(false);
+static BUDGET_TRACKING: AtomicBool = AtomicBool::new(false);
+/// Set by `request_stats` and moved to `REQUESTED_LAST_FRAME` by `new_frame`
+static REQUESTED: AtomicBool = AtomicBool::new(false);
+static REQUESTED_LAST_FRAME: AtomicBool = AtomicBool::new(false);
+static TIMINGS: Mutex<Timings> = Mutex::new(Timings::new());
+
+/// Time one entity's script took in a frame
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptTiming {
+    pub entity: u32,
+    pub script: String,
+    pub duration: Duration,
+}
+
+/// Numbers shown by the performance HUD and returned by `Profiler.get_frame_stats()`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameStats {
+    /// Averaged over the last second or so of frames
+    pub fps: f32,
+    pub frame_time: Duration,
+    pub draw_calls: usize,
+    /// Slowest scripts of the last frame, slowest first
+    pub scripts: Vec<ScriptTiming>,
+}
+
+struct Timings {
+    current: Vec<ScriptTiming>,
+    last: Vec<ScriptTiming>,
+    frame_start: Option<Instant>,
+    frame_times: VecDeque<Duration>,
+}
+
+impl Timings {
+    const fn new() -> Self {
+        Self {
+            current: Vec::new(),
+            last: Vec::new(),
+            frame_start: None,
+            frame_times: VecDeque::new(),
+        }
+    }
+}
+
+fn timings() -> MutexGuard<'static, Timings> {
+    TIMINGS.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Show or hide the performance HUD; script timings are recorded while it is visible
+pub fn set_hud_visible(visible: bool) {
+    HUD_VISIBLE.store(visible, Ordering::Relaxed);
+}
+
+pub fn is_hud_visible() -> bool {
+    HUD_VISIBLE.load(Ordering::Relaxed)
+}
+
+/// Record script timings for `ScriptBudget` even while the HUD is hidden
+pub fn set_budget_tracking(enabled: bool) {
+    BUDGET_TRACKING.store(enabled, Ordering::Relaxed);
+}
+
+/// A script read the stats: keep timing scripts through the next frame
+pub fn request_stats() {
+    REQUESTED.store(true, Ordering::Relaxed);
+}
+
+/// Whether `ScriptTimer`s record this frame
+#[inline]
+pub fn is_recording() -> bool {
+    HUD_VISIBLE.load(Ordering::Relaxed)
+        || BUDGET_TRACKING.load(Ordering::Relaxed)
+        || REQUESTED_LAST_FRAME.load(Ordering::Relaxed)
+}
+
+/// Times one script update until dropped
+pub struct ScriptTimer {
+    running: Option<(u32, String, Instant)>,
+}
+
+impl ScriptTimer {
+    pub fn new(entity: u32, script: &str) -> Self {
+        Self {
+            running: is_recording().then(|| (entity, script.to_string(), Instant::now())),
+        }
+    }
+}
+
+impl Drop for ScriptTimer {
+    fn drop(&mut self) {
+        if let Some((entity, script, start)) = self.running.take() {
+            record(entity, script, start.elapsed());
+        }
+    }
+}
+
+/// Add time to `script` on `entity` for the current frame
+pub fn record(entity: u32, script: String, duration: Duration) {
+    let mut timings = timings();
+    match timings.current.iter_mut().find(|t| t.entity == entity && t.script == script) {
+        Some(timing) => timing.duration += duration,
+        None => timings.current.push(ScriptTiming { entity, script, duration }),
+    }
+}
+
+/// Finish the frame's script timings and record how long the frame took
+pub fn new_frame() {
+    REQUESTED_LAST_FRAME.store(REQUESTED.swap(false, Ordering::Relaxed), Ordering::Relaxed);
+
+    let now = Instant::now();
+    let mut timings = timings();
+    if let Some(start) = timings.frame_start.replace(now) {
+        if timings.frame_times.len() == FRAME_TIME_HISTORY {
+            timings.frame_times.pop_front();
+        }
+        timings.frame_times.push_back(now - start);
+    }
+    let finished = std::mem::take(&mut timings.current);
+    timings.last = finished;
+}
+
+/// Script timings of the last finished frame, in the order the scripts ran
+pub fn last_frame() -> Vec<ScriptTiming> {
+    timings().last.clone()
+}
+
+/// Frame times of the last `FRAME_TIME_HISTORY` frames, oldest first
+pub fn frame_times() -> Vec<Duration> {
+    timings().frame_times.iter().copied().collect()
+}
+
+/// Stats of the last finished frame with its `top` slowest scripts
+pub fn frame_stats(top: usize) -> FrameStats {
+    let timings = timings();
+    let frame_time = timings.frame_times.back().copied().unwrap_or_default();
+    let recent = timings.frame_times.iter().rev().take(60);
+    let (count, total) = recent.fold((0u32, Duration::ZERO), |(count, total), time| (count + 1, total + *time));
+    let fps = if total.is_zero() { 0.0 } else { count as f32 / total.as_secs_f32() };
+
+    FrameStats {
+        fps,
+        frame_time,
+        draw_calls: crate::gpu::last_frame_stats().0,
+        scripts: slowest(&timings.last, top),
+    }
+}
+
+/// The `top` slowest timings, slowest first
+pub fn slowest(timings: &[ScriptTiming], top: usize) -> Vec<ScriptTiming> {
+    let mut sorted = timings.to_vec();
+    sorted.sort_by(|a, b| b.duration.cmp(&a.duration).then_with(|| a.entity.cmp(&b.entity)));
+    sorted.truncate(top);
+    sorted
+}
+
+/// Warns once per script name when one entity's script stays over its time budget
+/// for more than `frames` consecutive frames
+#[derive(Debug, Clone)]
+pub struct ScriptBudget {
+    pub budget: Duration,
+    pub frames: u32,
+    over: HashMap<(u32, String), u32>,
+    warned: Vec<String>,
+}
+
+impl ScriptBudget {
+    pub fn new(budget: Duration, frames: u32) -> Self {
+        Self { budget, frames, over: HashMap::new(), warned: Vec::new() }
+    }
+
+    /// Forget streaks and warnings (e.g. when play mode starts again)
+    pub fn reset(&mut self) {
+        self.over.clear();
+        self.warned.clear();
+    }
+
+    /// Feed one frame of timings; returns the scripts that just went over budget
+    /// for too long, each script name at most once
+    pub fn update(&mut self, timings: &[ScriptTiming]) -> Vec<ScriptTiming> {
+        let mut over = HashMap::new();
+        let mut exceeded = Vec::new();
+        for timing in timings.iter().filter(|t| t.duration > self.budget) {
+            let key = (timing.entity, timing.script.clone());
+            let streak = self.over.get(&key).copied().unwrap_or(0) + 1;
+            if streak > self.frames && !self.warned.contains(&timing.script) {
+                self.warned.push(timing.script.clone());
+                exceeded.push(timing.clone());
+            }
+            over.insert(key, streak);
+        }
+        // Streaks end on the first frame a script is back under budget
+        self.over = over;
+        exceeded
+    }
+
+    /// Console message for a script returned by `update`
+    pub fn warning(&self, timing: &ScriptTiming) -> String {
+        format!(
+            "Script '{}' (entity {}) took {:.2}ms per frame, over its {:.1}ms budget for {} frames in a row",
+            timing.script,
+            timing.entity,
+            timing.duration.as_secs_f64() * 1000.0,
+            self.budget.as_secs_f64() * 1000.0,
+            self.frames,
+        )
+    }
+}
+
+impl Default for ScriptBudget {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(2), 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timing(entity: u32, script: &str, ms: u64) -> ScriptTiming {
+        ScriptTiming { entity, script: script.to_string(), duration: Duration::from_millis(ms) }
+    }
+
+    #[test]
+    fn test_budget_warns_once_after_consecutive_frames() {
+        let mut budget = ScriptBudget::new(Duration::from_millis(2), 3);
+        let slow = [timing(1, "enemy", 5), timing(2, "enemy", 5), timing(3, "player", 1)];
+
+        for _ in 0..3 {
+            assert!(budget.update(&slow).is_empty());
+        }
+        let exceeded = budget.update(&slow);
+        assert_eq!(exceeded, vec![timing(1, "enemy", 5)]);
+        // Same script name on another entity or later frames stays quiet
+        assert!(budget.update(&slow).is_empty());
+
+        budget.reset();
+        for _ in 0..3 {
+            budget.update(&slow);
+        }
+        // A frame under budget restarts the streak
+        budget.update(&[timing(1, "enemy", 1), timing(2, "enemy", 1)]);
+        assert!(budget.update(&slow).is_empty());
+    }
+
+    #[test]
+    fn test_slowest_sorts_and_truncates() {
+        let timings = [timing(1, "a", 1), timing(2, "b", 4), timing(3, "c", 2)];
+        let top = slowest(&timings, 2);
+        assert_eq!(top, vec![timing(2, "b", 4), timing(3, "c", 2)]);
+    }
+}
@@ -0,0 +1,74 @@
+//! Texture memory by category for the memory panel
+//!
+//! The renderer's texture manager (render::TextureManager) publishes it whenever its
+//! textures change and the editor reads the latest copy. Kept free of wgpu like the
+//! GPU timings.
+
+use std::sync::Mutex;
+
+/// GPU memory of the textures of one category ("Sprites & UI", "Streamed", ...)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextureCategoryMemory {
+    pub category: String,
+    pub textures: usize,
+    pub bytes: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TextureMemory {
+    pub categories: Vec<TextureCategoryMemory>,
+    /// Memory streamed textures may grow into; 0 = unlimited
+    pub budget_bytes: u64,
+    /// Streamed textures still showing their preview while their full mip chain is built
+    pub streaming: usize,
+    /// Streamed textures kept at their preview because the budget was reached
+    pub held_back: usize,
+}
+
+impl TextureMemory {
+    pub fn total_bytes(&self) -> u64 {
+        self.categories.iter().map(|category| category.bytes).sum()
+    }
+
+    /// Share of the budget in use (above 1 when full-size loads alone exceed it), or
+    /// None without a budget
+    pub fn budget_used(&self) -> Option<f32> {
+        (self.budget_bytes > 0).then(|| self.total_bytes() as f32 / self.budget_bytes as f32)
+    }
+}
+
+static TEXTURE_MEMORY: Mutex<Option<TextureMemory>> = Mutex::new(None);
+
+pub fn publish(memory: TextureMemory) {
+    *TEXTURE_MEMORY.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(memory);
+}
+
+/// Latest report, or None before a renderer published one
+pub fn texture_memory() -> Option<TextureMemory> {
+    TEXTURE_MEMORY.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(name: &str, bytes: u64) -> TextureCategoryMemory {
+        TextureCategoryMemory { category: name.to_string(), textures: 1, bytes }
+    }
+
+    #[test]
+    fn test_totals_and_budget() {
+        let mut memory = TextureMemory {
+            categories: vec![category("Sprites & UI", 300), category("Streamed", 500)],
+            ..Default::default()
+        };
+        assert_eq!(memory.total_bytes(), 800);
+        assert_eq!(memory.budget_used(), None);
+
+        memory.budget_bytes = 1000;
+        assert_eq!(memory.budget_used(), Some(0.8));
+
+        publish(memory.clone());
+        assert_eq!(texture_memory(), Some(memory));
+    }
+}
@@ -2,6 +2,8 @@ use wgpu::util::DeviceExt;
 use crate::texture::Texture;
 use crate::sprite_renderer::Vertex;
 use crate::texture::TextureManager; // Added import
+use crate::material::SpriteMaterialUniform;
+use ecs::{SpriteMaterial, SpriteShader};
 
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -10,6 +12,8 @@ pub struct InstanceRaw {
     color: [f32; 4],
     uv_offset: [f32; 2],
     uv_scale: [f32; 2],
+    /// 1.0 for frames packed turned 90° clockwise (see ecs::SpriteUv)
+    uv_rotated: f32,
 }
 
 impl InstanceRaw {
@@ -59,6 +63,12 @@ impl InstanceRaw {
                     shader_location: 8,
                     format: wgpu::VertexFormat::Float32x2,
                 },
+                // UV Rotated
+                wgpu::VertexAttribute {
+                    offset: mem::size_of::<[f32; 24]>() as wgpu::BufferAddress,
+                    shader_location: 9,
+                    format: wgpu::VertexFormat::Float32,
+                },
             ],
         }
     }
@@ -88,10 +98,17 @@ struct BatchData {
     buffer: wgpu::Buffer,
     texture_id: String,
     count: u32,
+    /// Shader variant and its parameter bind group (None = default shader)
+    material: Option<(SpriteShader, wgpu::BindGroup)>,
 }
 
 pub struct BatchRenderer {
     render_pipeline: wgpu::RenderPipeline,
+    // Sprite material variants (group 2 = material uniform)
+    material_bind_group_layout: wgpu::BindGroupLayout,
+    flash_pipeline: wgpu::RenderPipeline,
+    outline_pipeline: wgpu::RenderPipeline,
+    dissolve_pipeline: wgpu::RenderPipeline,
     vertex_buffer: wgpu::Buffer,
     index_buffer: wgpu::Buffer,
     num_indices: u32,
@@ -163,50 +180,34 @@ impl BatchRenderer {
             push_constant_ranges: &[],
         });
 
-        // 4. Render Pipeline
-        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: Some("Batch Render Pipeline"),
-            layout: Some(&render_pipeline_layout),
-            vertex: wgpu::VertexState {
-                module: &shader,
-                entry_point: Some("vs_main"),
-                buffers: &[Vertex::desc(), InstanceRaw::desc()],
-                compilation_options: Default::default(),
-            },
-            fragment: Some(wgpu::FragmentState {
-                module: &shader,
-                entry_point: Some("fs_main"),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: config.format,
-                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                strip_index_format: None,
-                front_face: wgpu::FrontFace::Ccw,
-                cull_mode: None,
-                polygon_mode: wgpu::PolygonMode::Fill,
-                unclipped_depth: false,
-                conservative: false,
-            },
-            depth_stencil: Some(wgpu::DepthStencilState {
-                format: wgpu::TextureFormat::Depth32Float,
-                depth_write_enabled: true,
-                depth_compare: wgpu::CompareFunction::Less, // Standard Z
-                stencil: wgpu::StencilState::default(),
-                bias: wgpu::DepthBiasState::default(),
-            }),
-            multisample: wgpu::MultisampleState {
-                count: 1,
-                mask: !0,
-                alpha_to_coverage_enabled: false,
-            },
-            cache: None,
-            multiview: None,
+        // 4. Render Pipelines (default, then one per sprite material variant)
+        let render_pipeline = Self::create_pipeline(device, config, &shader, &render_pipeline_layout, "fs_main", "Batch Render Pipeline");
+
+        let material_bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+            label: Some("sprite_material_bind_group_layout"),
+        });
+        let material_pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("Sprite Material Pipeline Layout"),
+            bind_group_layouts: &[
+                &texture_bind_group_layout,  // Group 0
+                &camera_bind_group_layout,   // Group 1
+                &material_bind_group_layout, // Group 2
+            ],
+            push_constant_ranges: &[],
         });
+        let flash_pipeline = Self::create_pipeline(device, config, &shader, &material_pipeline_layout, "fs_flash", "Sprite Flash Pipeline");
+        let outline_pipeline = Self::create_pipeline(device, config, &shader, &material_pipeline_layout, "fs_outline", "Sprite Outline Pipeline");
+        let dissolve_pipeline = Self::create_pipeline(device, config, &shader, &material_pipeline_layout, "fs_dissolve", "Sprite Dissolve Pipeline");
 
         // 5. Geometry (Quad)
         let vertices = &[
@@ -239,6 +240,10 @@ impl BatchRenderer {
 
         Self {
             render_pipeline,
+            material_bind_group_layout,
+            flash_pipeline,
+            outline_pipeline,
+            dissolve_pipeline,
             vertex_buffer,
             index_buffer,
             num_indices: indices.len() as u32,
@@ -251,6 +256,68 @@ impl BatchRenderer {
         }
     }
 
+    fn create_pipeline(
+        device: &wgpu::Device,
+        config: &wgpu::SurfaceConfiguration,
+        shader: &wgpu::ShaderModule,
+        layout: &wgpu::PipelineLayout,
+        fragment_entry: &str,
+        label: &str,
+    ) -> wgpu::RenderPipeline {
+        device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some(label),
+            layout: Some(layout),
+            vertex: wgpu::VertexState {
+                module: shader,
+                entry_point: Some("vs_main"),
+                buffers: &[Vertex::desc(), InstanceRaw::desc()],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: shader,
+                entry_point: Some(fragment_entry),
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: config.format,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                unclipped_depth: false,
+                conservative: false,
+            },
+            depth_stencil: Some(wgpu::DepthStencilState {
+                format: wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::Less, // Standard Z
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            }),
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            cache: None,
+            multiview: None,
+        })
+    }
+
+    fn pipeline(&self, variant: Option<SpriteShader>) -> &wgpu::RenderPipeline {
+        match variant {
+            Some(SpriteShader::Flash) => &self.flash_pipeline,
+            Some(SpriteShader::Outline) => &self.outline_pipeline,
+            Some(SpriteShader::Dissolve) => &self.dissolve_pipeline,
+            Some(SpriteShader::Default) | None => &self.render_pipeline,
+        }
+    }
+
     pub fn start_frame(&mut self) {
         self.batches.clear();
         self.instances.clear();
@@ -282,6 +349,7 @@ impl BatchRenderer {
         color: [f32; 4],
         uv_offset: [f32; 2],
         uv_scale: [f32; 2],
+        uv_rotated: bool,
     ) {
         // Build transform matrix: T * R * S (Translation, Rotation, Scale)
         // from_scale_rotation_translation applies them in the correct order
@@ -302,6 +370,7 @@ impl BatchRenderer {
             color,
             uv_offset,
             uv_scale,
+            uv_rotated: if uv_rotated { 1.0 } else { 0.0 },
         };
         self.instances.push(instance);
     }
@@ -311,11 +380,41 @@ impl BatchRenderer {
         &mut self,
         device: &wgpu::Device,
         texture_id: String,
+    ) {
+        self.finish_material_batch(device, texture_id, None);
+    }
+
+    /// Complete the current batch, drawn with `material`'s shader variant and parameters
+    /// (None or an unknown variant = the default shader)
+    pub fn finish_material_batch(
+        &mut self,
+        device: &wgpu::Device,
+        texture_id: String,
+        material: Option<&SpriteMaterial>,
     ) {
         if self.instances.is_empty() {
             return;
         }
 
+        let material = material
+            .filter(|material| material.variant() != SpriteShader::Default)
+            .map(|material| {
+                let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: Some("Sprite Material Buffer"),
+                    contents: bytemuck::cast_slice(&[SpriteMaterialUniform::new(material)]),
+                    usage: wgpu::BufferUsages::UNIFORM,
+                });
+                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &self.material_bind_group_layout,
+                    entries: &[wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: buffer.as_entire_binding(),
+                    }],
+                    label: Some("sprite_material_bind_group"),
+                });
+                (material.variant(), bind_group)
+            });
+
         let instance_bytes = bytemuck::cast_slice(&self.instances);
         let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
              label: Some("Batch Instance Buffer"),
@@ -327,11 +426,17 @@ impl BatchRenderer {
             buffer,
             texture_id,
             count: self.instances.len() as u32,
+            material,
         });
         
         self.instances.clear();
     }
     
+    /// Number of batches finished so far this frame
+    pub fn batch_count(&self) -> usize {
+        self.batches.len()
+    }
+
     /// Render all collected batches
     pub fn render<'a>(
         &'a self,
@@ -339,15 +444,35 @@ impl BatchRenderer {
         texture_manager: &'a TextureManager,
         camera_bind_group: &'a wgpu::BindGroup,
     ) {
-        if self.batches.is_empty() {
+        self.render_range(render_pass, texture_manager, camera_bind_group, 0..self.batches.len());
+    }
+
+    /// Render the batches in `range` (finish order), so other draws can go between them
+    pub fn render_range<'a>(
+        &'a self,
+        render_pass: &mut wgpu::RenderPass<'a>,
+        texture_manager: &'a TextureManager,
+        camera_bind_group: &'a wgpu::BindGroup,
+        range: std::ops::Range<usize>,
+    ) {
+        let Some(batches) = self.batches.get(range).filter(|batches| !batches.is_empty()) else {
              return;
-        }
+        };
          
-        render_pass.set_pipeline(&self.render_pipeline);
-        // Use the passed camera bind group (from CameraBinding) instead of internal one
-        render_pass.set_bind_group(1, camera_bind_group, &[]);
-        
-        for batch in &self.batches {
+        // Pipeline changes only between batches with different shader variants
+        let mut current_variant = None;
+        for (i, batch) in batches.iter().enumerate() {
+             let variant = batch.material.as_ref().map(|(variant, _)| *variant);
+             if i == 0 || variant != current_variant {
+                 render_pass.set_pipeline(self.pipeline(variant));
+                 // Use the passed camera bind group (from CameraBinding) instead of internal one
+                 render_pass.set_bind_group(1, camera_bind_group, &[]);
+                 current_variant = variant;
+             }
+             if let Some((_, material_bind_group)) = &batch.material {
+                 render_pass.set_bind_group(2, material_bind_group, &[]);
+             }
+
              if let Some(texture) = texture_manager.get_texture(&batch.texture_id) {
                  if let Some(bind_group) = &texture.bind_group {
                      // Bind Texture
@@ -360,6 +485,7 @@ impl BatchRenderer {
                      
                      // Draw
                      render_pass.draw_indexed(0..self.num_indices, 0, 0..batch.count);
+                     profiler::gpu::add_draw_call(self.num_indices as usize / 3 * batch.count as usize);
                  }
              }
         }
@@ -0,0 +1,245 @@
+//! GPU pass timing with timestamp queries
+//!
+//! Each frame, passes opt in by taking `pass_timestamp_writes` for their descriptor;
+//! spans inside a pass (`begin_span` / `end_span`) additionally need
+//! `TIMESTAMP_QUERY_INSIDE_PASSES` and are skipped without it. `end_frame` resolves the
+//! frame's queries into one of a few readback slots, and `collect` picks finished
+//! slots up on later frames without waiting on the GPU. Results go to `profiler::gpu`.
+//!
+//! Timing only runs while the profiler is enabled. Adapters without `TIMESTAMP_QUERY`
+//! report "unsupported" and every call is a no-op.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Frames in flight before a slot's results must have been read back
+const READBACK_SLOTS: usize = 3;
+/// Timestamps per frame (two per pass or span)
+const MAX_QUERIES: u32 = 64;
+
+const SLOT_IDLE: u8 = 0;
+const SLOT_PENDING: u8 = 1;
+const SLOT_MAPPED: u8 = 2;
+
+/// Features to request for GPU timing, limited to what the adapter offers
+pub fn timing_features(adapter: &wgpu::Adapter) -> wgpu::Features {
+    adapter.features() & (wgpu::Features::TIMESTAMP_QUERY | wgpu::Features::TIMESTAMP_QUERY_INSIDE_PASSES)
+}
+
+struct Span {
+    name: String,
+    depth: u32,
+    begin: u32,
+    end: Option<u32>,
+}
+
+struct ReadbackSlot {
+    resolve_buffer: wgpu::Buffer,
+    readback_buffer: wgpu::Buffer,
+    spans: Vec<Span>,
+    query_count: u32,
+    state: Arc<AtomicU8>,
+}
+
+pub struct GpuTimer {
+    query_set: Option<wgpu::QuerySet>,
+    inside_passes: bool,
+    /// Nanoseconds per timestamp tick
+    period: f32,
+    slots: Vec<ReadbackSlot>,
+    current: usize,
+    /// Whether the current frame is being timed
+    recording: bool,
+    spans: Vec<Span>,
+    next_query: u32,
+}
+
+impl GpuTimer {
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        let supported = device.features().contains(wgpu::Features::TIMESTAMP_QUERY);
+        profiler::gpu::set_timing_support(supported);
+        if !supported {
+            log::info!("GPU timing unavailable: adapter does not support TIMESTAMP_QUERY");
+        }
+
+        let query_set = supported.then(|| {
+            device.create_query_set(&wgpu::QuerySetDescriptor {
+                label: Some("GPU Timer Queries"),
+                ty: wgpu::QueryType::Timestamp,
+                count: MAX_QUERIES,
+            })
+        });
+
+        let buffer_size = MAX_QUERIES as u64 * std::mem::size_of::<u64>() as u64;
+        let slots = if supported {
+            (0..READBACK_SLOTS)
+                .map(|_| ReadbackSlot {
+                    resolve_buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                        label: Some("GPU Timer Resolve Buffer"),
+                        size: buffer_size,
+                        usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+                        mapped_at_creation: false,
+                    }),
+                    readback_buffer: device.create_buffer(&wgpu::BufferDescriptor {
+                        label: Some("GPU Timer Readback Buffer"),
+                        size: buffer_size,
+                        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+                        mapped_at_creation: false,
+                    }),
+                    spans: Vec::new(),
+                    query_count: 0,
+                    state: Arc::new(AtomicU8::new(SLOT_IDLE)),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        Self {
+            query_set,
+            inside_passes: device.features().contains(wgpu::Features::TIMESTAMP_QUERY_INSIDE_PASSES),
+            period: queue.get_timestamp_period(),
+            slots,
+            current: 0,
+            recording: false,
+            spans: Vec::new(),
+            next_query: 0,
+        }
+    }
+
+    pub fn is_supported(&self) -> bool {
+        self.query_set.is_some()
+    }
+
+    /// Start timing a frame. Skipped while the profiler is off or when the slot this
+    /// frame would use still holds unread results (the GPU is that far behind).
+    pub fn begin_frame(&mut self) {
+        self.spans.clear();
+        self.next_query = 0;
+        self.recording = self.is_supported()
+            && profiler::is_enabled()
+            && self.slots[self.current].state.load(Ordering::Acquire) == SLOT_IDLE;
+    }
+
+    fn allocate_queries(&mut self) -> Option<u32> {
+        if !self.recording || self.next_query + 2 > MAX_QUERIES {
+            return None;
+        }
+        let first = self.next_query;
+        self.next_query += 2;
+        Some(first)
+    }
+
+    /// Timestamp writes for a whole render pass, named `name` in the profiler
+    pub fn pass_timestamp_writes(&mut self, name: &str) -> Option<wgpu::RenderPassTimestampWrites<'_>> {
+        let first = self.allocate_queries()?;
+        self.spans.push(Span { name: name.to_string(), depth: 0, begin: first, end: Some(first + 1) });
+        Some(wgpu::RenderPassTimestampWrites {
+            query_set: self.query_set.as_ref()?,
+            beginning_of_pass_write_index: Some(first),
+            end_of_pass_write_index: Some(first + 1),
+        })
+    }
+
+    /// Open a span inside `pass` (no-op without `TIMESTAMP_QUERY_INSIDE_PASSES`)
+    pub fn begin_span(&mut self, pass: &mut wgpu::RenderPass, name: &str) {
+        if !self.inside_passes {
+            return;
+        }
+        let Some(first) = self.allocate_queries() else {
+            return;
+        };
+        if let Some(query_set) = &self.query_set {
+            pass.write_timestamp(query_set, first);
+            self.spans.push(Span { name: name.to_string(), depth: 1, begin: first, end: None });
+        }
+    }
+
+    /// Close the most recent open span inside `pass`
+    pub fn end_span(&mut self, pass: &mut wgpu::RenderPass) {
+        let Some(query_set) = &self.query_set else {
+            return;
+        };
+        if let Some(span) = self.spans.iter_mut().rev().find(|span| span.end.is_none()) {
+            let index = span.begin + 1;
+            pass.write_timestamp(query_set, index);
+            span.end = Some(index);
+        }
+    }
+
+    /// Resolve this frame's queries. Call on the last encoder of the frame, then
+    /// `after_submit` once it has been submitted.
+    pub fn end_frame(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if !self.recording || self.next_query == 0 {
+            self.recording = false;
+            return;
+        }
+        let (Some(query_set), slot) = (&self.query_set, &mut self.slots[self.current]) else {
+            return;
+        };
+        encoder.resolve_query_set(query_set, 0..self.next_query, &slot.resolve_buffer, 0);
+        let size = self.next_query as u64 * std::mem::size_of::<u64>() as u64;
+        encoder.copy_buffer_to_buffer(&slot.resolve_buffer, 0, &slot.readback_buffer, 0, size);
+        // Unclosed spans are dropped
+        slot.spans = std::mem::take(&mut self.spans).into_iter().filter(|span| span.end.is_some()).collect();
+        slot.query_count = self.next_query;
+    }
+
+    /// Request the readback of the frame resolved by `end_frame`
+    pub fn after_submit(&mut self) {
+        if !self.recording {
+            return;
+        }
+        self.recording = false;
+
+        let slot = &self.slots[self.current];
+        slot.state.store(SLOT_PENDING, Ordering::Release);
+        let state = slot.state.clone();
+        let size = slot.query_count as u64 * std::mem::size_of::<u64>() as u64;
+        slot.readback_buffer.map_async(wgpu::MapMode::Read, 0..size, move |result| {
+            // A failed map leaves nothing to read; free the slot again
+            state.store(if result.is_ok() { SLOT_MAPPED } else { SLOT_IDLE }, Ordering::Release);
+        });
+        self.current = (self.current + 1) % self.slots.len();
+    }
+
+    /// Publish any frames whose results have arrived. Never blocks.
+    pub fn collect(&mut self, device: &wgpu::Device) {
+        if !self.is_supported() {
+            return;
+        }
+        let _ = device.poll(wgpu::PollType::Poll);
+
+        for slot in &mut self.slots {
+            if slot.state.load(Ordering::Acquire) != SLOT_MAPPED {
+                continue;
+            }
+            let size = slot.query_count as u64 * std::mem::size_of::<u64>() as u64;
+            let timestamps: Vec<u64> = {
+                let data = slot.readback_buffer.slice(0..size).get_mapped_range();
+                data.chunks_exact(8)
+                    .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+                    .collect()
+            };
+            slot.readback_buffer.unmap();
+            slot.state.store(SLOT_IDLE, Ordering::Release);
+
+            let passes = slot
+                .spans
+                .drain(..)
+                .filter_map(|span| {
+                    let begin = *timestamps.get(span.begin as usize)?;
+                    let end = *timestamps.get(span.end? as usize)?;
+                    let nanos = end.saturating_sub(begin) as f64 * self.period as f64;
+                    Some(profiler::gpu::GpuPassTiming {
+                        name: span.name,
+                        depth: span.depth,
+                        duration: Duration::from_nanos(nanos as u64),
+                    })
+                })
+                .collect();
+            profiler::gpu::record_frame(profiler::gpu::GpuFrameTiming { passes });
+        }
+    }
+}
@@ -96,5 +96,6 @@ impl GridRenderer {
         render_pass.set_pipeline(&self.render_pipeline);
         render_pass.set_bind_group(0, camera_bind_group, &[]);
         render_pass.draw(0..6, 0..1); // Draw 6 vertices (1 instance)
+        profiler::gpu::add_draw_call(2);
     }
 }
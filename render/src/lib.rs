@@ -5,9 +5,9 @@ pub mod texture;
 pub mod sprite_renderer;
 pub mod tilemap_renderer;
 
-pub use texture::{Texture, TextureManager};
+pub use texture::{Texture, TextureCategory, TextureManager, TextureOptions, TextureStreaming};
 pub use sprite_renderer::SpriteRenderer;
-pub use tilemap_renderer::TilemapRenderer;
+pub use tilemap_renderer::{TilemapRenderer, TilemapChunkMesh, AnimatedTileQuad};
 pub mod batch_renderer;
 pub use batch_renderer::BatchRenderer;
 pub mod mesh;
@@ -17,6 +17,9 @@ pub mod grid_renderer;
 pub mod camera;
 pub mod lighting;
 pub mod material;
+pub mod gpu_timer;
+pub mod render_texture;
+pub mod screen_capture;
 
 pub use mesh::{Mesh, ModelVertex};
 pub use mesh_generation::generate_mesh;
@@ -24,7 +27,10 @@ pub use mesh_renderer::{MeshRenderer, ObjectUniform};
 pub use grid_renderer::GridRenderer;
 pub use camera::{CameraBinding, CameraUniform};
 pub use lighting::{LightBinding, LightUniform};
-pub use material::{PbrMaterial, PbrMaterialUniform, ToonMaterial, ToonMaterialUniform};
+pub use material::{PbrMaterial, PbrMaterialUniform, ToonMaterial, ToonMaterialUniform, SpriteMaterialUniform};
+pub use gpu_timer::GpuTimer;
+pub use render_texture::{RenderTexture, RenderTextureManager};
+pub use screen_capture::{CaptureResult, GifSettings, ScreenCapture};
 
 
 pub struct RenderModule {
@@ -43,6 +49,14 @@ pub struct RenderModule {
     pub mesh_renderer: MeshRenderer,
     pub camera_binding: CameraBinding,
     pub light_binding: LightBinding,
+    pub clear_color: wgpu::Color,
+    pub gpu_timer: GpuTimer,
+    pub render_textures: RenderTextureManager,
+    /// Screenshots and the GIF buffer
+    pub screen_capture: ScreenCapture,
+    /// Capture from the swapchain in `render_with_callback` (the player); the editor
+    /// records its game view texture instead
+    pub capture_surface: bool,
 }
 
 impl RenderModule {
@@ -69,7 +83,8 @@ impl RenderModule {
 
         let (device, queue) = adapter.request_device(
             &wgpu::DeviceDescriptor {
-                required_features: wgpu::Features::empty(),
+                // Timestamp queries when available, for the GPU profiler
+                required_features: gpu_timer::timing_features(&adapter),
                 required_limits: wgpu::Limits::default(),
                 label: None,
                 memory_hints: Default::default(),
@@ -85,7 +100,8 @@ impl RenderModule {
             .unwrap_or(surface_caps.formats[0]);
 
         let config = wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            // COPY_SRC where supported, so screenshots can read the swapchain back
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | (surface_caps.usages & wgpu::TextureUsages::COPY_SRC),
             format: surface_format,
             width: size.width,
             height: size.height,
@@ -192,6 +208,9 @@ impl RenderModule {
             &light_binding.bind_group_layout
         );
 
+        let gpu_timer = GpuTimer::new(&device, &queue);
+        let render_textures = RenderTextureManager::new(config.format);
+
         Ok(Self {
             surface,
             device,
@@ -208,8 +227,34 @@ impl RenderModule {
             mesh_renderer,
             camera_binding,
             light_binding,
+            clear_color: wgpu::Color { r: 0.15, g: 0.15, b: 0.2, a: 1.0 },
+            gpu_timer,
+            render_textures,
+            screen_capture: ScreenCapture::new(),
+            capture_surface: true,
         })
     }
+
+    /// Background color the frame is cleared to (RGBA, 0-1)
+    pub fn set_clear_color(&mut self, color: [f32; 4]) {
+        self.clear_color = wgpu::Color {
+            r: color[0] as f64,
+            g: color[1] as f64,
+            b: color[2] as f64,
+            a: color[3] as f64,
+        };
+    }
+
+    /// Whether presentation waits for the display (the surface's current present mode)
+    pub fn vsync(&self) -> bool {
+        !matches!(self.config.present_mode, wgpu::PresentMode::Immediate | wgpu::PresentMode::Mailbox | wgpu::PresentMode::AutoNoVsync)
+    }
+
+    /// Wait for vertical blank when presenting (Auto modes fall back to what the surface supports)
+    pub fn set_vsync(&mut self, vsync: bool) {
+        self.config.present_mode = if vsync { wgpu::PresentMode::AutoVsync } else { wgpu::PresentMode::AutoNoVsync };
+        self.surface.configure(&self.device, &self.config);
+    }
     
     pub fn resize(&mut self, new_size: winit::dpi::PhysicalSize<u32>) {
         if new_size.width > 0 && new_size.height > 0 {
@@ -259,12 +304,7 @@ impl RenderModule {
                     view: &view,
                     resolve_target: None,
                     ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: 0.15,
-                            g: 0.15,
-                            b: 0.2,
-                            a: 1.0,
-                        }),
+                        load: wgpu::LoadOp::Clear(self.clear_color),
                         store: wgpu::StoreOp::Store,
                     },
                     depth_slice: None,
@@ -282,11 +322,19 @@ impl RenderModule {
             });
         }
 
+        // Streamed textures whose full mip chain is ready
+        self.texture_manager.update_streaming(&self.device, &self.queue);
+
         // Callback for overlay (egui) and game render
         callback(&self.device, &self.queue, &mut encoder, &view, &self.depth_view, &mut self.texture_manager, &mut self.tilemap_renderer, &mut self.batch_renderer, &mut self.mesh_renderer, &mut self.camera_binding, &self.light_binding);
+        if self.capture_surface {
+            self.screen_capture.record(&self.device, &mut encoder, &output.texture);
+        }
         
         self.queue.submit(std::iter::once(encoder.finish()));
+        self.screen_capture.after_submit();
         output.present();
+        self.screen_capture.collect(&self.device);
         
         Ok(())
     }
@@ -85,3 +85,21 @@ impl ToonMaterialUniform {
         }
     }
 }
+
+/// Uniform block for a sprite material variant (see sprite_shader.wgsl)
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct SpriteMaterialUniform {
+    pub color: [f32; 4],
+    pub params: [f32; 4], // The variant's float parameters in slot order
+}
+
+impl SpriteMaterialUniform {
+    pub fn new(material: &ecs::SpriteMaterial) -> Self {
+        let (params, color) = material.packed();
+        Self {
+            color: color.to_array(),
+            params,
+        }
+    }
+}
@@ -336,6 +336,7 @@ impl MeshRenderer {
         render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
         render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
         render_pass.draw_indexed(0..mesh.num_elements, 0, 0..1);
+        profiler::gpu::add_draw_call(mesh.num_elements as usize / 3);
     }
 
     pub fn render_toon<'a>(
@@ -356,11 +357,13 @@ impl MeshRenderer {
         render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
         render_pass.set_index_buffer(mesh.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
         render_pass.draw_indexed(0..mesh.num_elements, 0, 0..1);
+        profiler::gpu::add_draw_call(mesh.num_elements as usize / 3);
 
         // Main Toon Pass (2nd)
         render_pass.set_pipeline(&self.toon_pipeline);
         render_pass.set_bind_group(3, object_bind_group, &[]);
         render_pass.draw_indexed(0..mesh.num_elements, 0, 0..1);
+        profiler::gpu::add_draw_call(mesh.num_elements as usize / 3);
     }
 
     pub fn create_material_bind_group(
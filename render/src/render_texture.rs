@@ -0,0 +1,240 @@
+//! Render textures
+//!
+//! Offscreen color targets (with a depth buffer) that cameras render into. Each one is
+//! also registered in the `TextureManager` under `RenderTextureAsset::texture_id(name)`,
+//! so Sprites and UIImages sample it like any other texture. Re-creating one (resize,
+//! format change) replaces that registration, so every lookup picks up the new texture.
+
+use crate::texture::{Texture, TextureManager, TextureOptions};
+use ecs::{RenderTextureAsset, RenderTextureFormat, RENDER_TEXTURE_EXTENSION};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float;
+
+pub struct RenderTexture {
+    pub asset: RenderTextureAsset,
+    pub texture: wgpu::Texture,
+    /// Attachment view, in the format the render pipelines were built for
+    pub target_view: wgpu::TextureView,
+    /// View sampled by sprites and UI (sRGB or linear per the asset's format)
+    pub sampled_view: wgpu::TextureView,
+    depth: Option<(wgpu::Texture, wgpu::TextureView)>,
+    /// Changes every time the GPU texture is re-created (for caches keyed on the view)
+    pub generation: u64,
+}
+
+pub struct RenderTextureManager {
+    target_format: wgpu::TextureFormat,
+    targets: HashMap<String, RenderTexture>,
+    /// Depth buffers for render textures without their own, one per size
+    shared_depth: HashMap<(u32, u32), (wgpu::Texture, wgpu::TextureView)>,
+    next_generation: u64,
+    source_dir: Option<PathBuf>,
+}
+
+fn create_depth(device: &wgpu::Device, label: &str, width: u32, height: u32) -> (wgpu::Texture, wgpu::TextureView) {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some(label),
+        size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: DEPTH_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+/// `.rendertexture` files below `dir` (hidden folders skipped)
+fn find_render_texture_files(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            find_render_texture_files(&path, out);
+        } else if path.extension().is_some_and(|ext| ext == RENDER_TEXTURE_EXTENSION) {
+            out.push(path);
+        }
+    }
+}
+
+impl RenderTextureManager {
+    /// `target_format` must be the color format the sprite/tilemap/mesh pipelines use
+    pub fn new(target_format: wgpu::TextureFormat) -> Self {
+        Self {
+            target_format,
+            targets: HashMap::new(),
+            shared_depth: HashMap::new(),
+            next_generation: 1,
+            source_dir: None,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&RenderTexture> {
+        self.targets.get(name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.targets.keys().map(|name| name.as_str())
+    }
+
+    /// Create the render texture `name`, or re-create it if its asset changed.
+    /// Returns true when a GPU texture was (re)created.
+    pub fn create(
+        &mut self,
+        device: &wgpu::Device,
+        texture_manager: &mut TextureManager,
+        name: &str,
+        asset: &RenderTextureAsset,
+    ) -> bool {
+        if self.targets.get(name).is_some_and(|existing| &existing.asset == asset) {
+            return false;
+        }
+
+        let width = asset.width.max(1);
+        let height = asset.height.max(1);
+        let sampled_format = match asset.format {
+            RenderTextureFormat::Srgb => self.target_format.add_srgb_suffix(),
+            RenderTextureFormat::Linear => self.target_format.remove_srgb_suffix(),
+        };
+        let view_formats: Vec<wgpu::TextureFormat> =
+            [sampled_format].into_iter().filter(|format| *format != self.target_format).collect();
+
+        let label = format!("Render Texture '{}'", name);
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some(&label),
+            size: wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.target_format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &view_formats,
+        });
+        let target_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampled_view = texture.create_view(&wgpu::TextureViewDescriptor {
+            format: Some(sampled_format),
+            ..Default::default()
+        });
+        let depth = asset.depth_buffer.then(|| create_depth(device, &format!("{} Depth", label), width, height));
+
+        // Registered like a loaded image so sprite batches and UI find it by ID
+        let options = TextureOptions {
+            srgb: asset.format == RenderTextureFormat::Srgb,
+            ..Default::default()
+        };
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: options.address_mode,
+            address_mode_v: options.address_mode,
+            address_mode_w: options.address_mode,
+            mag_filter: options.filter,
+            min_filter: options.filter,
+            mipmap_filter: options.filter,
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: texture_manager.bind_group_layout(device),
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: wgpu::BindingResource::TextureView(&sampled_view) },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::Sampler(&sampler) },
+            ],
+            label: Some("render_texture_bind_group"),
+        });
+        texture_manager.insert_texture(
+            &RenderTextureAsset::texture_id(name),
+            Texture {
+                texture: texture.clone(),
+                view: sampled_view.clone(),
+                sampler,
+                bind_group: Some(bind_group),
+                width,
+                height,
+                options,
+            },
+        );
+
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        self.targets.insert(
+            name.to_string(),
+            RenderTexture {
+                asset: asset.clone(),
+                texture,
+                target_view,
+                sampled_view,
+                depth,
+                generation,
+            },
+        );
+        log::info!("Render texture '{}' created ({}x{}, {:?})", name, width, height, asset.format);
+        true
+    }
+
+    pub fn remove(&mut self, texture_manager: &mut TextureManager, name: &str) -> bool {
+        texture_manager.remove_texture(&RenderTextureAsset::texture_id(name));
+        self.targets.remove(name).is_some()
+    }
+
+    /// Color and depth attachments for rendering into `name`
+    pub fn attachments(&mut self, device: &wgpu::Device, name: &str) -> Option<(&wgpu::TextureView, &wgpu::TextureView)> {
+        let target = self.targets.get(name)?;
+        let depth_view = match &target.depth {
+            Some((_, view)) => view,
+            None => {
+                let size = (target.texture.width(), target.texture.height());
+                &self
+                    .shared_depth
+                    .entry(size)
+                    .or_insert_with(|| create_depth(device, "Shared Render Texture Depth", size.0, size.1))
+                    .1
+            }
+        };
+        Some((&target.target_view, depth_view))
+    }
+
+    /// Directory the render textures were last loaded from
+    pub fn source_dir(&self) -> Option<&Path> {
+        self.source_dir.as_deref()
+    }
+
+    /// Load every `.rendertexture` below `dir` (named by file stem): new ones are created,
+    /// changed ones re-created and ones whose file is gone removed. Returns load errors.
+    pub fn load_directory(&mut self, device: &wgpu::Device, texture_manager: &mut TextureManager, dir: &Path) -> Vec<String> {
+        let mut files = Vec::new();
+        find_render_texture_files(dir, &mut files);
+
+        let mut errors = Vec::new();
+        let mut found = Vec::new();
+        for path in files {
+            let Some(name) = path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string) else {
+                continue;
+            };
+            match RenderTextureAsset::load(&path) {
+                Ok(asset) => {
+                    self.create(device, texture_manager, &name, &asset);
+                    found.push(name);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        let stale: Vec<String> = self.targets.keys().filter(|name| !found.contains(name)).cloned().collect();
+        for name in stale {
+            self.remove(texture_manager, &name);
+        }
+        // Sizes no longer in use free their shared depth buffer
+        let sizes: Vec<(u32, u32)> = self.targets.values().map(|t| (t.texture.width(), t.texture.height())).collect();
+        self.shared_depth.retain(|size, _| sizes.contains(size));
+
+        self.source_dir = Some(dir.to_path_buf());
+        errors
+    }
+}
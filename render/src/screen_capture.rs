@@ -0,0 +1,469 @@
+//! Screenshots and the rolling GIF buffer
+//!
+//! `record` copies a rendered texture (the player's swapchain image or the editor's game
+//! view target) into a readback buffer when a screenshot was requested or the GIF
+//! buffer is due a frame; `after_submit` maps it without waiting, and `collect` picks
+//! mapped buffers up on later frames, like `GpuTimer`. PNG/GIF encoding and the file
+//! write run on a worker thread, and their outcome comes back from `take_results` so
+//! a bad path is reported to the console instead of panicking.
+//!
+//! The texture needs `COPY_SRC` usage. Pixels are saved as displayed: sRGB and plain
+//! 8-bit formats are copied as is (BGRA swizzled to RGBA), float targets are encoded
+//! from linear to sRGB.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::mpsc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use image::RgbaImage;
+
+const READBACK_PENDING: u8 = 0;
+const READBACK_MAPPED: u8 = 1;
+const READBACK_FAILED: u8 = 2;
+
+/// Size of the rolling GIF buffer: the last `seconds` at `fps`, scaled down to at most
+/// `width` pixels wide
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GifSettings {
+    pub seconds: f32,
+    pub fps: u32,
+    pub width: u32,
+}
+
+impl Default for GifSettings {
+    fn default() -> Self {
+        Self { seconds: 5.0, fps: 12, width: 320 }
+    }
+}
+
+impl GifSettings {
+    fn max_frames(&self) -> usize {
+        (self.seconds.max(0.0) * self.fps as f32).ceil() as usize
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs_f32(1.0 / self.fps.max(1) as f32)
+    }
+}
+
+/// A saved file, or why it couldn't be saved
+pub type CaptureResult = Result<PathBuf, String>;
+
+enum ReadbackKind {
+    Screenshot(PathBuf),
+    GifFrame,
+}
+
+struct Readback {
+    kind: ReadbackKind,
+    buffer: wgpu::Buffer,
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    format: wgpu::TextureFormat,
+    state: Arc<AtomicU8>,
+    map_requested: bool,
+}
+
+pub struct ScreenCapture {
+    /// Requested screenshots (path, crop letterbox), read back by the next `record`
+    requested: Vec<(PathBuf, bool)>,
+    readbacks: Vec<Readback>,
+    output_dir: PathBuf,
+    /// Letterboxed game area in texture pixels, None for the whole texture
+    game_rect: Option<[f32; 4]>,
+    gif: GifSettings,
+    gif_recording: bool,
+    gif_frames: VecDeque<RgbaImage>,
+    last_gif_frame: Option<Instant>,
+    results_tx: mpsc::Sender<CaptureResult>,
+    results_rx: mpsc::Receiver<CaptureResult>,
+}
+
+impl Default for ScreenCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScreenCapture {
+    pub fn new() -> Self {
+        let (results_tx, results_rx) = mpsc::channel();
+        Self {
+            requested: Vec::new(),
+            readbacks: Vec::new(),
+            output_dir: PathBuf::new(),
+            game_rect: None,
+            gif: GifSettings::default(),
+            gif_recording: false,
+            gif_frames: VecDeque::new(),
+            last_gif_frame: None,
+            results_tx,
+            results_rx,
+        }
+    }
+
+    /// Directory that relative and default paths are saved under
+    pub fn set_output_dir(&mut self, dir: impl Into<PathBuf>) {
+        self.output_dir = dir.into();
+    }
+
+    /// The game area inside the captured texture ([x, y, width, height] in pixels), for
+    /// letterbox cropping and GIF frames
+    pub fn set_game_rect(&mut self, rect: [f32; 4]) {
+        self.game_rect = Some(rect);
+    }
+
+    /// Change the GIF buffer size; frames of a different width are dropped
+    pub fn set_gif_settings(&mut self, settings: GifSettings) {
+        if settings.width != self.gif.width {
+            self.gif_frames.clear();
+        }
+        self.gif = settings;
+        let max_frames = self.gif.max_frames();
+        while self.gif_frames.len() > max_frames {
+            self.gif_frames.pop_front();
+        }
+    }
+
+    /// Start or stop keeping the last few seconds for `save_gif`; stopping frees them
+    pub fn set_gif_recording(&mut self, recording: bool) {
+        self.gif_recording = recording;
+        if !recording {
+            self.gif_frames.clear();
+            self.last_gif_frame = None;
+        }
+    }
+
+    pub fn is_gif_recording(&self) -> bool {
+        self.gif_recording
+    }
+
+    /// Save the next rendered frame as a PNG at `path` (default
+    /// `screenshots/screenshot_<time>.png`); `crop_letterbox` keeps only the game rect
+    pub fn request_screenshot(&mut self, path: Option<&str>, crop_letterbox: bool) {
+        let path = self.resolve_path(path, "screenshot", "png");
+        self.requested.push((path, crop_letterbox));
+    }
+
+    /// Save the recorded frames as an animated GIF at `path` (default
+    /// `screenshots/clip_<time>.gif`)
+    pub fn save_gif(&mut self, path: Option<&str>) {
+        let path = self.resolve_path(path, "clip", "gif");
+        if self.gif_frames.is_empty() {
+            let reason = if self.gif_recording { "no frames recorded yet" } else { "GIF recording is off" };
+            let _ = self.results_tx.send(Err(format!("Can't save {}: {}", path.display(), reason)));
+            return;
+        }
+        let frames: Vec<RgbaImage> = self.gif_frames.iter().cloned().collect();
+        let delay = self.gif.interval();
+        let results = self.results_tx.clone();
+        std::thread::spawn(move || {
+            let _ = results.send(write_gif(&path, frames, delay).map(|_| path.clone()));
+        });
+    }
+
+    /// Report requested screenshots as failed (nothing was rendered to capture)
+    pub fn cancel_screenshots(&mut self, reason: &str) {
+        for (path, _) in self.requested.drain(..) {
+            let _ = self.results_tx.send(Err(format!("Can't save {}: {}", path.display(), reason)));
+        }
+    }
+
+    fn gif_frame_due(&self) -> bool {
+        let in_flight = self.readbacks.iter().any(|r| matches!(r.kind, ReadbackKind::GifFrame));
+        self.gif_recording
+            && self.gif.max_frames() > 0
+            && !in_flight
+            && self.last_gif_frame.is_none_or(|last| last.elapsed() >= self.gif.interval())
+    }
+
+    /// Copy `texture` for pending screenshots and the GIF buffer
+    pub fn record(&mut self, device: &wgpu::Device, encoder: &mut wgpu::CommandEncoder, texture: &wgpu::Texture) {
+        let gif_due = self.gif_frame_due();
+        if self.requested.is_empty() && !gif_due {
+            return;
+        }
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            self.cancel_screenshots("the render target doesn't allow reading back (no COPY_SRC)");
+            if gif_due {
+                let _ = self.results_tx.send(Err("GIF recording stopped: the render target doesn't allow reading back (no COPY_SRC)".to_string()));
+                self.set_gif_recording(false);
+            }
+            return;
+        }
+
+        let full = [0, 0, texture.width(), texture.height()];
+        let letterbox = match self.game_rect {
+            Some(rect) => clamp_rect(rect, texture.width(), texture.height()),
+            None => full,
+        };
+        for (path, crop) in std::mem::take(&mut self.requested) {
+            let region = if crop { letterbox } else { full };
+            match copy_region(device, encoder, texture, region) {
+                Some(readback) => self.readbacks.push(Readback { kind: ReadbackKind::Screenshot(path), ..readback }),
+                None => {
+                    let _ = self.results_tx.send(Err(format!(
+                        "Can't save {}: {:?} textures can't be read back",
+                        path.display(),
+                        texture.format()
+                    )));
+                }
+            }
+        }
+        if gif_due {
+            self.last_gif_frame = Some(Instant::now());
+            if let Some(readback) = copy_region(device, encoder, texture, letterbox) {
+                self.readbacks.push(readback);
+            }
+        }
+    }
+
+    /// Map this frame's readbacks (call after the encoder passed to `record` was submitted)
+    pub fn after_submit(&mut self) {
+        for readback in self.readbacks.iter_mut().filter(|r| !r.map_requested) {
+            readback.map_requested = true;
+            let state = Arc::clone(&readback.state);
+            readback.buffer.slice(..).map_async(wgpu::MapMode::Read, move |result| {
+                let next = if result.is_ok() { READBACK_MAPPED } else { READBACK_FAILED };
+                state.store(next, Ordering::Release);
+            });
+        }
+    }
+
+    /// Pick up readbacks the GPU has finished, without waiting for the rest
+    pub fn collect(&mut self, device: &wgpu::Device) {
+        if self.readbacks.is_empty() {
+            return;
+        }
+        let _ = device.poll(wgpu::PollType::Poll);
+
+        let mut index = 0;
+        while index < self.readbacks.len() {
+            let state = self.readbacks[index].state.load(Ordering::Acquire);
+            if state == READBACK_PENDING {
+                index += 1;
+                continue;
+            }
+            let readback = self.readbacks.remove(index);
+            let data = (state == READBACK_MAPPED).then(|| {
+                let data = readback.buffer.slice(..).get_mapped_range().to_vec();
+                readback.buffer.unmap();
+                data
+            });
+            match (readback.kind, data) {
+                (ReadbackKind::Screenshot(path), Some(data)) => {
+                    let (width, height, bytes_per_row, format) =
+                        (readback.width, readback.height, readback.padded_bytes_per_row, readback.format);
+                    let results = self.results_tx.clone();
+                    std::thread::spawn(move || {
+                        let result = to_rgba8(&data, width, height, bytes_per_row, format)
+                            .and_then(|image| write_png(&path, &image))
+                            .map(|_| path.clone());
+                        let _ = results.send(result);
+                    });
+                }
+                (ReadbackKind::Screenshot(path), None) => {
+                    let _ = self.results_tx.send(Err(format!("Can't save {}: reading the frame back failed", path.display())));
+                }
+                (ReadbackKind::GifFrame, Some(data)) => {
+                    if let Ok(frame) = to_rgba8(&data, readback.width, readback.height, readback.padded_bytes_per_row, readback.format) {
+                        self.push_gif_frame(frame);
+                    }
+                }
+                (ReadbackKind::GifFrame, None) => {}
+            }
+        }
+    }
+
+    fn push_gif_frame(&mut self, frame: RgbaImage) {
+        if !self.gif_recording {
+            return;
+        }
+        let frame = if frame.width() > self.gif.width && self.gif.width > 0 {
+            let height = ((frame.height() as u64 * self.gif.width as u64) / frame.width() as u64).max(1) as u32;
+            image::imageops::resize(&frame, self.gif.width, height, image::imageops::FilterType::Triangle)
+        } else {
+            frame
+        };
+        // The game view was resized: older frames no longer match
+        if self.gif_frames.back().is_some_and(|last| last.dimensions() != frame.dimensions()) {
+            self.gif_frames.clear();
+        }
+        self.gif_frames.push_back(frame);
+        while self.gif_frames.len() > self.gif.max_frames() {
+            self.gif_frames.pop_front();
+        }
+    }
+
+    /// Screenshots and GIFs finished (or failed) since the last call
+    pub fn take_results(&mut self) -> Vec<CaptureResult> {
+        self.results_rx.try_iter().collect()
+    }
+
+    fn resolve_path(&self, path: Option<&str>, prefix: &str, extension: &str) -> PathBuf {
+        let path = match path.map(str::trim).filter(|p| !p.is_empty()) {
+            Some(path) => PathBuf::from(path),
+            None => {
+                let stamp = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis())
+                    .unwrap_or(0);
+                Path::new("screenshots").join(format!("{}_{}.{}", prefix, stamp, extension))
+            }
+        };
+        if path.is_absolute() {
+            path
+        } else {
+            self.output_dir.join(path)
+        }
+    }
+}
+
+/// `rect` rounded to whole pixels inside a `width` x `height` texture
+fn clamp_rect(rect: [f32; 4], width: u32, height: u32) -> [u32; 4] {
+    let x = (rect[0].max(0.0).round() as u32).min(width.saturating_sub(1));
+    let y = (rect[1].max(0.0).round() as u32).min(height.saturating_sub(1));
+    let w = (rect[2].max(1.0).round() as u32).clamp(1, width - x);
+    let h = (rect[3].max(1.0).round() as u32).clamp(1, height - y);
+    [x, y, w, h]
+}
+
+/// Encode a copy of `region` of `texture` into a new readback buffer
+fn copy_region(
+    device: &wgpu::Device,
+    encoder: &mut wgpu::CommandEncoder,
+    texture: &wgpu::Texture,
+    region: [u32; 4],
+) -> Option<Readback> {
+    let format = texture.format();
+    let bytes_per_pixel = format.block_copy_size(None)?;
+    let [x, y, width, height] = region;
+    let padded_bytes_per_row = (width * bytes_per_pixel).div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+        * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Screen Capture Readback Buffer"),
+        size: padded_bytes_per_row as u64 * height as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+    encoder.copy_texture_to_buffer(
+        wgpu::TexelCopyTextureInfo {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d { x, y, z: 0 },
+            aspect: wgpu::TextureAspect::All,
+        },
+        wgpu::TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_bytes_per_row),
+                rows_per_image: Some(height),
+            },
+        },
+        wgpu::Extent3d { width, height, depth_or_array_layers: 1 },
+    );
+    Some(Readback {
+        kind: ReadbackKind::GifFrame,
+        buffer,
+        width,
+        height,
+        padded_bytes_per_row,
+        format,
+        state: Arc::new(AtomicU8::new(READBACK_PENDING)),
+        map_requested: false,
+    })
+}
+
+/// Opaque RGBA8 (sRGB) pixels from a readback of `format` with padded rows
+pub fn to_rgba8(
+    data: &[u8],
+    width: u32,
+    height: u32,
+    padded_bytes_per_row: u32,
+    format: wgpu::TextureFormat,
+) -> Result<RgbaImage, String> {
+    use wgpu::TextureFormat as F;
+    let bytes_per_pixel = match format {
+        F::Rgba8Unorm | F::Rgba8UnormSrgb | F::Bgra8Unorm | F::Bgra8UnormSrgb => 4,
+        F::Rgba16Float => 8,
+        other => return Err(format!("{:?} textures can't be saved", other)),
+    };
+    if (padded_bytes_per_row as usize) < width as usize * bytes_per_pixel
+        || data.len() < padded_bytes_per_row as usize * height as usize
+    {
+        return Err("the readback is smaller than the image".to_string());
+    }
+
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    for row in data.chunks_exact(padded_bytes_per_row as usize).take(height as usize) {
+        for pixel in row[..width as usize * bytes_per_pixel].chunks_exact(bytes_per_pixel) {
+            let rgb = match format {
+                F::Bgra8Unorm | F::Bgra8UnormSrgb => [pixel[2], pixel[1], pixel[0]],
+                F::Rgba16Float => {
+                    let channel = |i: usize| linear_to_srgb(f16_to_f32(u16::from_le_bytes([pixel[i * 2], pixel[i * 2 + 1]])));
+                    [channel(0), channel(1), channel(2)]
+                }
+                _ => [pixel[0], pixel[1], pixel[2]],
+            };
+            pixels.extend_from_slice(&[rgb[0], rgb[1], rgb[2], 255]);
+        }
+    }
+    RgbaImage::from_raw(width, height, pixels).ok_or_else(|| "the readback is smaller than the image".to_string())
+}
+
+/// A linear color channel as an sRGB byte
+fn linear_to_srgb(value: f32) -> u8 {
+    let value = if value.is_nan() { 0.0 } else { value.clamp(0.0, 1.0) };
+    let encoded = if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    };
+    (encoded * 255.0).round() as u8
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+    let exponent = ((bits >> 10) & 0x1f) as i32;
+    let mantissa = (bits & 0x3ff) as f32;
+    match exponent {
+        0 => sign * mantissa * 2f32.powi(-24),
+        0x1f if mantissa == 0.0 => sign * f32::INFINITY,
+        0x1f => f32::NAN,
+        _ => sign * (1.0 + mantissa / 1024.0) * 2f32.powi(exponent - 15),
+    }
+}
+
+fn create_parent_dir(path: &Path) -> Result<(), String> {
+    match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Can't save {}: {}", path.display(), e)),
+        _ => Ok(()),
+    }
+}
+
+fn write_png(path: &Path, image: &RgbaImage) -> Result<(), String> {
+    create_parent_dir(path)?;
+    image
+        .save_with_format(path, image::ImageFormat::Png)
+        .map_err(|e| format!("Can't save {}: {}", path.display(), e))
+}
+
+fn write_gif(path: &Path, frames: Vec<RgbaImage>, delay: Duration) -> Result<(), String> {
+    use image::codecs::gif::{GifEncoder, Repeat};
+    let error = |e: &dyn std::fmt::Display| format!("Can't save {}: {}", path.display(), e);
+    create_parent_dir(path)?;
+    let file = std::fs::File::create(path).map_err(|e| error(&e))?;
+    let mut encoder = GifEncoder::new_with_speed(std::io::BufWriter::new(file), 10);
+    encoder.set_repeat(Repeat::Infinite).map_err(|e| error(&e))?;
+    let delay = image::Delay::from_saturating_duration(delay);
+    encoder
+        .encode_frames(frames.into_iter().map(|frame| image::Frame::from_parts(frame, 0, 0, delay)))
+        .map_err(|e| error(&e))
+}
+
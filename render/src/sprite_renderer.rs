@@ -149,6 +149,7 @@ impl SpriteRenderer {
             render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
             render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
             render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
+            profiler::gpu::add_draw_call(self.num_indices as usize / 3);
         }
     }
 
@@ -191,6 +192,7 @@ impl SpriteRenderer {
             render_pass.set_vertex_buffer(0, self.custom_vertex_buffer.as_ref().unwrap().slice(..));
             render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
             render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
+            profiler::gpu::add_draw_call(self.num_indices as usize / 3);
         }
     }
 }
@@ -21,12 +21,18 @@ struct InstanceInput {
     @location(6) color: vec4<f32>,
     @location(7) uv_offset: vec2<f32>,
     @location(8) uv_scale: vec2<f32>,
+    @location(9) uv_rotated: f32,
 };
 
 struct VertexOutput {
     @builtin(position) clip_position: vec4<f32>,
     @location(0) tex_coords: vec2<f32>,
     @location(1) color: vec4<f32>,
+    // Position on the quad (0..1), for effects that shouldn't depend on the atlas
+    @location(2) local_uv: vec2<f32>,
+    // Sprite rect in the texture, so effects never sample a neighbouring frame
+    @location(3) uv_offset: vec2<f32>,
+    @location(4) uv_scale: vec2<f32>,
 };
 
 @vertex
@@ -42,8 +48,17 @@ fn vs_main(
     );
 
     var out: VertexOutput;
-    out.tex_coords = model.tex_coords * instance.uv_scale + instance.uv_offset;
+    // Frames packed turned clockwise are turned back: (u, v) = (1 - y, x)
+    let oriented = select(
+        model.tex_coords,
+        vec2<f32>(1.0 - model.tex_coords.y, model.tex_coords.x),
+        instance.uv_rotated > 0.5,
+    );
+    out.tex_coords = oriented * instance.uv_scale + instance.uv_offset;
     out.color = instance.color;
+    out.local_uv = model.tex_coords;
+    out.uv_offset = instance.uv_offset;
+    out.uv_scale = instance.uv_scale;
     out.clip_position = camera.view_proj * model_matrix * vec4<f32>(model.position, 1.0);
     return out;
 }
@@ -60,3 +75,77 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let tex_color = textureSample(t_diffuse, s_diffuse, in.tex_coords);
     return tex_color * in.color;
 }
+
+// Sprite material variants (SpriteMaterial component)
+// Group 2: material parameters; which slot means what depends on the variant
+struct SpriteMaterialUniform {
+    color: vec4<f32>,
+    params: vec4<f32>,
+};
+@group(2) @binding(0)
+var<uniform> material: SpriteMaterialUniform;
+
+// Flash: params.x = flash_amount, color = flash_color
+@fragment
+fn fs_flash(in: VertexOutput) -> @location(0) vec4<f32> {
+    let base = textureSample(t_diffuse, s_diffuse, in.tex_coords) * in.color;
+    let amount = clamp(material.params.x, 0.0, 1.0) * material.color.a;
+    return vec4<f32>(mix(base.rgb, material.color.rgb, amount), base.a);
+}
+
+// Alpha at `uv`, 0 outside the sprite rect
+fn rect_alpha(uv: vec2<f32>, rect_min: vec2<f32>, rect_max: vec2<f32>) -> f32 {
+    let alpha = textureSample(t_diffuse, s_diffuse, uv).a;
+    let inside = all(uv >= rect_min) && all(uv <= rect_max);
+    return select(0.0, alpha, inside);
+}
+
+// Outline: params.x = thickness in texels, color = outline_color.
+// Drawn on transparent pixels next to opaque ones, so it stays inside the quad.
+@fragment
+fn fs_outline(in: VertexOutput) -> @location(0) vec4<f32> {
+    let base = textureSample(t_diffuse, s_diffuse, in.tex_coords) * in.color;
+    let texel = material.params.x / vec2<f32>(textureDimensions(t_diffuse));
+    let rect_min = min(in.uv_offset, in.uv_offset + in.uv_scale);
+    let rect_max = max(in.uv_offset, in.uv_offset + in.uv_scale);
+
+    var neighbour = 0.0;
+    neighbour = max(neighbour, rect_alpha(in.tex_coords + vec2<f32>(texel.x, 0.0), rect_min, rect_max));
+    neighbour = max(neighbour, rect_alpha(in.tex_coords - vec2<f32>(texel.x, 0.0), rect_min, rect_max));
+    neighbour = max(neighbour, rect_alpha(in.tex_coords + vec2<f32>(0.0, texel.y), rect_min, rect_max));
+    neighbour = max(neighbour, rect_alpha(in.tex_coords - vec2<f32>(0.0, texel.y), rect_min, rect_max));
+    neighbour = max(neighbour, rect_alpha(in.tex_coords + texel, rect_min, rect_max));
+    neighbour = max(neighbour, rect_alpha(in.tex_coords - texel, rect_min, rect_max));
+    neighbour = max(neighbour, rect_alpha(in.tex_coords + vec2<f32>(texel.x, -texel.y), rect_min, rect_max));
+    neighbour = max(neighbour, rect_alpha(in.tex_coords + vec2<f32>(-texel.x, texel.y), rect_min, rect_max));
+
+    let outline = vec4<f32>(material.color.rgb, material.color.a * neighbour * in.color.a);
+    return mix(outline, base, base.a);
+}
+
+fn hash21(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+fn value_noise(p: vec2<f32>) -> f32 {
+    let i = floor(p);
+    let f = fract(p);
+    let u = f * f * (3.0 - 2.0 * f);
+    let bottom = mix(hash21(i), hash21(i + vec2<f32>(1.0, 0.0)), u.x);
+    let top = mix(hash21(i + vec2<f32>(0.0, 1.0)), hash21(i + vec2<f32>(1.0, 1.0)), u.x);
+    return mix(bottom, top, u.y);
+}
+
+// Dissolve: params.x = threshold (0 = whole, 1 = gone), params.y = edge_width,
+// params.z = noise_scale (noise cells across the sprite), color = edge_color
+@fragment
+fn fs_dissolve(in: VertexOutput) -> @location(0) vec4<f32> {
+    let base = textureSample(t_diffuse, s_diffuse, in.tex_coords) * in.color;
+    let threshold = material.params.x;
+    let noise = value_noise(in.local_uv * max(material.params.z, 1.0));
+    if (threshold > 0.0 && noise < threshold) {
+        discard;
+    }
+    let edge = select(0.0, 1.0 - smoothstep(threshold, threshold + material.params.y, noise), threshold > 0.0);
+    return vec4<f32>(mix(base.rgb, material.color.rgb, edge * material.color.a), base.a);
+}
@@ -1,8 +1,60 @@
 use anyhow::*;
 use image::GenericImageView;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::result::Result::{Ok, Err};
+use std::sync::{Arc, Mutex};
+
+/// Texture ID of the placeholder from `TextureManager::get_missing_texture`
+pub const MISSING_TEXTURE_ID: &str = "default_missing";
+
+/// Longest side (in pixels) of the preview level a streamed texture shows until its
+/// full mip chain is ready
+pub const STREAM_PREVIEW_SIZE: u32 = 256;
+
+/// Upload/sampling options taken from a texture's import settings (`.meta` file)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextureOptions {
+    pub filter: wgpu::FilterMode,
+    pub address_mode: wgpu::AddressMode,
+    /// Color data (sRGB format); false for data textures like normal maps
+    pub srgb: bool,
+    /// Source pixels already have color multiplied by alpha
+    pub premultiplied_alpha: bool,
+    /// Default sprite pivot, (0,0) = bottom-left, (1,1) = top-right
+    pub pivot: [f32; 2],
+    /// Filter between mip levels, or None for a single level. Only linear-filtered
+    /// textures get mipmaps: nearest-filtered ones (pixel art) ignore it.
+    pub mip_filter: Option<wgpu::FilterMode>,
+}
+
+impl Default for TextureOptions {
+    fn default() -> Self {
+        Self {
+            filter: wgpu::FilterMode::Nearest,
+            address_mode: wgpu::AddressMode::ClampToEdge,
+            srgb: true,
+            premultiplied_alpha: false,
+            pivot: [0.5, 0.5],
+            mip_filter: None,
+        }
+    }
+}
+
+impl TextureOptions {
+    /// Mip levels a `width` x `height` texture is created with
+    pub fn mip_level_count(&self, width: u32, height: u32) -> u32 {
+        match (self.filter, self.mip_filter) {
+            (wgpu::FilterMode::Linear, Some(_)) => full_mip_count(width, height),
+            _ => 1,
+        }
+    }
+}
+
+/// Levels of a full mip chain, down to 1x1
+pub fn full_mip_count(width: u32, height: u32) -> u32 {
+    u32::BITS - width.max(height).max(1).leading_zeros()
+}
 
 #[derive(Debug)]
 pub struct Texture {
@@ -12,6 +64,7 @@ pub struct Texture {
     pub bind_group: Option<wgpu::BindGroup>,
     pub width: u32,
     pub height: u32,
+    pub options: TextureOptions,
 }
 
 impl Texture {
@@ -45,9 +98,20 @@ impl Texture {
         bytes: &[u8],
         label: Option<&str>,
         layout: Option<&wgpu::BindGroupLayout>,
+    ) -> Result<Self> {
+        Self::from_bytes_with_options(device, queue, bytes, label, layout, TextureOptions::default())
+    }
+
+    pub fn from_bytes_with_options(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        bytes: &[u8],
+        label: Option<&str>,
+        layout: Option<&wgpu::BindGroupLayout>,
+        options: TextureOptions,
     ) -> Result<Self> {
         let img = image::load_from_memory(bytes)?;
-        Self::from_image(device, queue, &img, label, layout)
+        Self::from_image_with_options(device, queue, &img, label, layout, options)
     }
 
     pub fn from_image(
@@ -57,44 +121,91 @@ impl Texture {
         label: Option<&str>,
         layout: Option<&wgpu::BindGroupLayout>,
     ) -> Result<Self> {
-        let rgba = img.to_rgba8();
-        let dimensions = img.dimensions();
+        Self::from_image_with_options(device, queue, img, label, layout, TextureOptions::default())
+    }
+
+    pub fn from_image_with_options(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        img: &image::DynamicImage,
+        label: Option<&str>,
+        layout: Option<&wgpu::BindGroupLayout>,
+        options: TextureOptions,
+    ) -> Result<Self> {
+        let mut rgba = img.to_rgba8();
+        if options.premultiplied_alpha {
+            // Sprite pipelines blend straight alpha
+            unpremultiply(&mut rgba);
+        }
+        let (width, height) = img.dimensions();
+        let levels = build_mip_chain(rgba, options.mip_level_count(width, height));
+        Ok(Self::from_levels(device, queue, &levels, width, height, label, layout, options))
+    }
 
+    /// Create a texture from a ready mip chain (`levels[0]` the largest, straight alpha).
+    /// `width`/`height` are the size sprites are laid out with, which a streamed
+    /// texture's preview keeps while its pixels are smaller.
+    #[allow(clippy::too_many_arguments)]
+    fn from_levels(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        levels: &[image::RgbaImage],
+        width: u32,
+        height: u32,
+        label: Option<&str>,
+        layout: Option<&wgpu::BindGroupLayout>,
+        options: TextureOptions,
+    ) -> Self {
         let size = wgpu::Extent3d {
-            width: dimensions.0,
-            height: dimensions.1,
+            width: levels[0].width(),
+            height: levels[0].height(),
             depth_or_array_layers: 1,
         };
         let texture = device.create_texture(&wgpu::TextureDescriptor {
             label,
             size,
-            mip_level_count: 1,
+            mip_level_count: levels.len() as u32,
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            format: if options.srgb {
+                wgpu::TextureFormat::Rgba8UnormSrgb
+            } else {
+                wgpu::TextureFormat::Rgba8Unorm
+            },
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
             view_formats: &[],
         });
 
-        queue.write_texture(
-            texture.as_image_copy(),
-            &rgba,
-            wgpu::TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(4 * dimensions.0),
-                rows_per_image: Some(dimensions.1),
-            },
-            size,
-        );
+        for (mip_level, level) in levels.iter().enumerate() {
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &texture,
+                    mip_level: mip_level as u32,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                level,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(4 * level.width()),
+                    rows_per_image: Some(level.height()),
+                },
+                wgpu::Extent3d {
+                    width: level.width(),
+                    height: level.height(),
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
 
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Nearest,
-            min_filter: wgpu::FilterMode::Nearest,
-            mipmap_filter: wgpu::FilterMode::Nearest,
+            address_mode_u: options.address_mode,
+            address_mode_v: options.address_mode,
+            address_mode_w: options.address_mode,
+            mag_filter: options.filter,
+            min_filter: options.filter,
+            mipmap_filter: options.mip_filter.unwrap_or(wgpu::FilterMode::Nearest),
             ..Default::default()
         });
 
@@ -115,20 +226,156 @@ impl Texture {
             })
         });
 
-        Ok(Self {
+        Self {
             texture,
             view,
             sampler,
             bind_group,
-            width: dimensions.0,
-            height: dimensions.1,
-        })
+            width,
+            height,
+            options,
+        }
+    }
+
+    /// GPU memory of the texture's pixels, all mip levels included
+    pub fn memory_bytes(&self) -> u64 {
+        let bytes_per_texel = self.texture.format().block_copy_size(None).unwrap_or(4) as u64;
+        let size = self.texture.size();
+        (0..self.texture.mip_level_count())
+            .map(|level| {
+                let level = size.mip_level_size(level, self.texture.dimension());
+                level.width as u64 * level.height as u64 * level.depth_or_array_layers as u64
+            })
+            .sum::<u64>()
+            * bytes_per_texel
     }
 }
 
+/// `base` followed by `levels - 1` successively halved copies. Texels are averaged
+/// with premultiplied alpha, so transparent ones don't darken the edges of smaller levels.
+pub fn build_mip_chain(base: image::RgbaImage, levels: u32) -> Vec<image::RgbaImage> {
+    let mut chain = Vec::with_capacity(levels.max(1) as usize);
+    let mut previous = base.clone();
+    premultiply(&mut previous);
+    chain.push(base);
+    for _ in 1..levels {
+        let width = (previous.width() / 2).max(1);
+        let height = (previous.height() / 2).max(1);
+        previous = image::imageops::resize(&previous, width, height, image::imageops::FilterType::Triangle);
+        let mut level = previous.clone();
+        unpremultiply(&mut level);
+        chain.push(level);
+    }
+    chain
+}
+
+/// `image` scaled so its longest side is at most `max_side`, averaged like mip levels
+fn downsample(image: &image::RgbaImage, max_side: u32) -> image::RgbaImage {
+    let scale = max_side as f32 / image.width().max(image.height()) as f32;
+    if scale >= 1.0 {
+        return image.clone();
+    }
+    let width = ((image.width() as f32 * scale).round() as u32).max(1);
+    let height = ((image.height() as f32 * scale).round() as u32).max(1);
+    let mut premultiplied = image.clone();
+    premultiply(&mut premultiplied);
+    let mut small = image::imageops::resize(&premultiplied, width, height, image::imageops::FilterType::Triangle);
+    unpremultiply(&mut small);
+    small
+}
+
+fn levels_bytes(levels: &[image::RgbaImage]) -> u64 {
+    levels.iter().map(|level| level.as_raw().len() as u64).sum()
+}
+
+fn premultiply(image: &mut image::RgbaImage) {
+    for pixel in image.pixels_mut() {
+        let alpha = pixel[3] as u32;
+        if alpha < 255 {
+            for channel in 0..3 {
+                pixel[channel] = ((pixel[channel] as u32 * alpha + 127) / 255) as u8;
+            }
+        }
+    }
+}
+
+fn unpremultiply(image: &mut image::RgbaImage) {
+    for pixel in image.pixels_mut() {
+        let alpha = pixel[3] as u32;
+        if alpha > 0 && alpha < 255 {
+            for channel in 0..3 {
+                pixel[channel] = ((pixel[channel] as u32 * 255 + alpha / 2) / alpha).min(255) as u8;
+            }
+        }
+    }
+}
+
+/// Large-texture streaming and the texture memory budget (project rendering settings)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TextureStreaming {
+    /// Textures with a side longer than this many pixels show a small preview level at
+    /// once and get their full mip chain from a background thread; 0 = off
+    pub threshold: u32,
+    /// Texture memory streamed textures may grow into; past it they keep their
+    /// preview level. 0 = unlimited
+    pub budget_bytes: u64,
+}
+
+impl TextureStreaming {
+    pub fn streams(&self, width: u32, height: u32) -> bool {
+        self.threshold > 0 && width.max(height) > self.threshold
+    }
+
+    pub fn fits(&self, total_bytes: u64) -> bool {
+        self.budget_bytes == 0 || total_bytes <= self.budget_bytes
+    }
+}
+
+/// What a texture is used for, for the memory report
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TextureCategory {
+    /// Sprite, tileset and UI images loaded at full size
+    Sprite,
+    /// Images above the streaming threshold
+    Streamed,
+    /// Render targets registered with `insert_texture`
+    RenderTexture,
+    /// White, flat normal and missing-texture placeholders
+    Builtin,
+}
+
+impl TextureCategory {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TextureCategory::Sprite => "Sprites & UI",
+            TextureCategory::Streamed => "Streamed",
+            TextureCategory::RenderTexture => "Render textures",
+            TextureCategory::Builtin => "Built-in",
+        }
+    }
+}
+
+/// Full mip chain of a streamed texture, built on a background thread
+struct StreamedChain {
+    id: String,
+    generation: u64,
+    levels: Vec<image::RgbaImage>,
+    width: u32,
+    height: u32,
+    options: TextureOptions,
+}
+
 pub struct TextureManager {
     textures: HashMap<String, Texture>,
+    categories: HashMap<String, TextureCategory>,
     bind_group_layout: Option<wgpu::BindGroupLayout>,
+    streaming: TextureStreaming,
+    /// Chain generation each streamed texture waits for (reloading it makes older ones stale)
+    pending_streams: HashMap<String, u64>,
+    next_stream_generation: u64,
+    finished_streams: Arc<Mutex<Vec<StreamedChain>>>,
+    /// Streamed textures kept at their preview because the budget was reached
+    held_back: HashSet<String>,
 }
 
 impl TextureManager {
@@ -136,7 +383,21 @@ impl TextureManager {
         let bind_group_layout = device.map(|d| Texture::create_bind_group_layout(d));
         Self {
             textures: HashMap::new(),
+            categories: HashMap::new(),
             bind_group_layout,
+            streaming: TextureStreaming::default(),
+            pending_streams: HashMap::new(),
+            next_stream_generation: 0,
+            finished_streams: Arc::new(Mutex::new(Vec::new())),
+            held_back: HashSet::new(),
+        }
+    }
+
+    /// Streaming threshold and budget for textures loaded from now on
+    pub fn set_streaming(&mut self, streaming: TextureStreaming) {
+        if self.streaming != streaming {
+            self.streaming = streaming;
+            self.publish_memory();
         }
     }
 
@@ -146,17 +407,134 @@ impl TextureManager {
         queue: &wgpu::Queue,
         bytes: &[u8],
         id: &str,
+    ) -> Result<()> {
+        self.load_texture_from_bytes_with_options(device, queue, bytes, id, TextureOptions::default())
+    }
+
+    /// Load (or replace) `id` using the asset's import options. Images above the
+    /// streaming threshold show a preview level until `update_streaming` swaps in
+    /// their full mip chain.
+    pub fn load_texture_from_bytes_with_options(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        bytes: &[u8],
+        id: &str,
+        options: TextureOptions,
     ) -> Result<()> {
         // Ensure layout exists
         if self.bind_group_layout.is_none() {
             self.bind_group_layout = Some(Texture::create_bind_group_layout(device));
         }
 
-        let texture = Texture::from_bytes(device, queue, bytes, Some(id), self.bind_group_layout.as_ref())?;
-        self.textures.insert(id.to_string(), texture);
+        let img = image::load_from_memory(bytes)?;
+        self.pending_streams.remove(id);
+        self.held_back.remove(id);
+        let (width, height) = img.dimensions();
+        if self.streaming.streams(width, height) {
+            let texture = self.start_stream(device, queue, &img, id, options);
+            self.store(id, texture, TextureCategory::Streamed);
+        } else {
+            let texture = Texture::from_image_with_options(device, queue, &img, Some(id), self.bind_group_layout.as_ref(), options)?;
+            self.store(id, texture, TextureCategory::Sprite);
+        }
         Ok(())
     }
 
+    /// Upload a small preview of `img` and build its full mip chain in the background
+    fn start_stream(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        img: &image::DynamicImage,
+        id: &str,
+        options: TextureOptions,
+    ) -> Texture {
+        let (width, height) = img.dimensions();
+        let mut rgba = img.to_rgba8();
+        if options.premultiplied_alpha {
+            unpremultiply(&mut rgba);
+        }
+        let preview = downsample(&rgba, STREAM_PREVIEW_SIZE);
+        let preview_levels = options.mip_level_count(preview.width(), preview.height());
+        let preview = build_mip_chain(preview, preview_levels);
+
+        let generation = self.next_stream_generation;
+        self.next_stream_generation += 1;
+        self.pending_streams.insert(id.to_string(), generation);
+        let finished = Arc::clone(&self.finished_streams);
+        let id_owned = id.to_string();
+        let build = move || {
+            let levels = build_mip_chain(rgba, options.mip_level_count(width, height));
+            finished.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push(StreamedChain {
+                id: id_owned,
+                generation,
+                levels,
+                width,
+                height,
+                options,
+            });
+        };
+        // No threads on the web: the chain is ready on the next update instead
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(build);
+        #[cfg(target_arch = "wasm32")]
+        build();
+
+        Texture::from_levels(device, queue, &preview, width, height, Some(id), self.bind_group_layout.as_ref(), options)
+    }
+
+    /// Swap in at most one streamed texture whose full mip chain is ready (its new bind
+    /// group is used from the next draw). Call once per frame: one upload a frame keeps
+    /// large chains from stalling it. A chain that would take texture memory past the
+    /// budget is dropped with a warning and the texture keeps its preview.
+    pub fn update_streaming(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        let chain = {
+            let mut finished = self.finished_streams.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            if finished.is_empty() {
+                return;
+            }
+            finished.remove(0)
+        };
+        // Reloaded or removed since the chain was started
+        if self.pending_streams.get(&chain.id) != Some(&chain.generation) {
+            return;
+        }
+        self.pending_streams.remove(&chain.id);
+
+        let preview_bytes = self.textures.get(&chain.id).map_or(0, Texture::memory_bytes);
+        let total = self.memory_bytes() - preview_bytes + levels_bytes(&chain.levels);
+        if !self.streaming.fits(total) {
+            log::warn!(
+                "Texture memory budget of {} MB reached: '{}' ({}x{}) stays at its preview size",
+                self.streaming.budget_bytes / (1024 * 1024),
+                chain.id,
+                chain.width,
+                chain.height
+            );
+            self.held_back.insert(chain.id);
+            self.publish_memory();
+            return;
+        }
+
+        let texture = Texture::from_levels(
+            device,
+            queue,
+            &chain.levels,
+            chain.width,
+            chain.height,
+            Some(&chain.id),
+            self.bind_group_layout.as_ref(),
+            chain.options,
+        );
+        self.store(&chain.id, texture, TextureCategory::Streamed);
+    }
+
+    /// Streamed textures still showing their preview while their chain is built
+    pub fn is_streaming(&self, id: &str) -> bool {
+        self.pending_streams.contains_key(id)
+    }
+
     pub fn load_texture(
         &mut self,
         device: &wgpu::Device,
@@ -172,6 +550,64 @@ impl TextureManager {
         self.textures.get(id)
     }
 
+    /// Drop a texture so it is re-created on the next load (e.g. after its import settings changed)
+    pub fn remove_texture(&mut self, id: &str) -> Option<Texture> {
+        self.categories.remove(id);
+        self.pending_streams.remove(id);
+        self.held_back.remove(id);
+        let texture = self.textures.remove(id);
+        self.publish_memory();
+        texture
+    }
+
+    /// Register a texture created elsewhere (e.g. a render texture), replacing any with the same ID
+    pub fn insert_texture(&mut self, id: &str, texture: Texture) {
+        self.store(id, texture, TextureCategory::RenderTexture);
+    }
+
+    fn store(&mut self, id: &str, texture: Texture, category: TextureCategory) {
+        self.textures.insert(id.to_string(), texture);
+        self.categories.insert(id.to_string(), category);
+        self.publish_memory();
+    }
+
+    /// GPU memory of every texture
+    pub fn memory_bytes(&self) -> u64 {
+        self.textures.values().map(Texture::memory_bytes).sum()
+    }
+
+    /// Report texture memory by category to the profiler (the editor's memory panel)
+    fn publish_memory(&self) {
+        let mut categories: Vec<profiler::textures::TextureCategoryMemory> = Vec::new();
+        let mut by_category: Vec<(TextureCategory, &Texture)> = self.textures.iter()
+            .map(|(id, texture)| (self.categories.get(id).copied().unwrap_or(TextureCategory::Sprite), texture))
+            .collect();
+        by_category.sort_by_key(|(category, _)| *category);
+        for (category, texture) in by_category {
+            match categories.last_mut() {
+                Some(last) if last.category == category.label() => {
+                    last.textures += 1;
+                    last.bytes += texture.memory_bytes();
+                }
+                _ => categories.push(profiler::textures::TextureCategoryMemory {
+                    category: category.label().to_string(),
+                    textures: 1,
+                    bytes: texture.memory_bytes(),
+                }),
+            }
+        }
+        profiler::textures::publish(profiler::textures::TextureMemory {
+            categories,
+            budget_bytes: self.streaming.budget_bytes,
+            streaming: self.pending_streams.len(),
+            held_back: self.held_back.len(),
+        });
+    }
+
+    /// Layout of the per-texture bind groups sprites are drawn with
+    pub fn bind_group_layout(&mut self, device: &wgpu::Device) -> &wgpu::BindGroupLayout {
+        self.bind_group_layout.get_or_insert_with(|| Texture::create_bind_group_layout(device))
+    }
     pub fn get_white_texture(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) -> Option<&Texture> {
         if !self.textures.contains_key("default_white") {
              if self.bind_group_layout.is_none() {
@@ -185,7 +621,7 @@ impl TextureManager {
             
             match Texture::from_image(device, queue, &image, Some("default_white"), self.bind_group_layout.as_ref()) {
                 Ok(texture) => {
-                    self.textures.insert("default_white".to_string(), texture);
+                    self.store("default_white", texture, TextureCategory::Builtin);
                 }
                 Err(_) => {}
             }
@@ -206,11 +642,35 @@ impl TextureManager {
             
             match Texture::from_image(device, queue, &image, Some("default_normal"), self.bind_group_layout.as_ref()) {
                 Ok(texture) => {
-                    self.textures.insert("default_normal".to_string(), texture);
+                    self.store("default_normal", texture, TextureCategory::Builtin);
                 }
                 Err(_) => {}
             }
         }
         self.textures.get("default_normal")
     }
+
+    /// Magenta/black checkerboard drawn in place of textures that failed to load
+    pub fn get_missing_texture(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) -> Option<&Texture> {
+        if !self.textures.contains_key(MISSING_TEXTURE_ID) {
+             if self.bind_group_layout.is_none() {
+                self.bind_group_layout = Some(Texture::create_bind_group_layout(device));
+            }
+
+            // 8x8 checker, 4x4 cells
+            let buf = image::RgbaImage::from_fn(8, 8, |x, y| {
+                if (x / 4 + y / 4) % 2 == 0 {
+                    image::Rgba([255, 0, 255, 255])
+                } else {
+                    image::Rgba([0, 0, 0, 255])
+                }
+            });
+            let image = image::DynamicImage::ImageRgba8(buf);
+
+            if let Ok(texture) = Texture::from_image(device, queue, &image, Some(MISSING_TEXTURE_ID), self.bind_group_layout.as_ref()) {
+                self.store(MISSING_TEXTURE_ID, texture, TextureCategory::Builtin);
+            }
+        }
+        self.textures.get(MISSING_TEXTURE_ID)
+    }
 }
@@ -1,7 +1,27 @@
 use wgpu::util::DeviceExt;
 use crate::texture::Texture;
 use crate::sprite_renderer::Vertex;
-use ecs::{Tilemap, TileSet};
+use ecs::{Tile, Tilemap, TileSet};
+
+/// GPU mesh of one tilemap chunk
+pub struct TilemapChunkMesh {
+    pub vertex_buffer: wgpu::Buffer,
+    pub index_buffer: wgpu::Buffer,
+    pub index_count: u32,
+    /// Quads of animated tiles, rewritten in place as their frame changes
+    pub animated: Vec<AnimatedTileQuad>,
+}
+
+/// An animated tile's quad inside a chunk's vertex buffer
+pub struct AnimatedTileQuad {
+    /// Index of the quad's first of 4 vertices
+    pub first_vertex: u32,
+    /// Tile as placed in the tilemap (its id is the animation's)
+    pub tile: Tile,
+    pub corners: [[f32; 3]; 4],
+    /// Frame currently in the vertex buffer
+    pub shown_tile_id: u32,
+}
 
 pub struct TilemapRenderer {
     render_pipeline: wgpu::RenderPipeline,
@@ -93,33 +113,104 @@ impl TilemapRenderer {
         transform_pos: glam::Vec3,
         pixels_per_unit: f32,
     ) -> (wgpu::Buffer, wgpu::Buffer, u32) {
+        let cells = (0..tilemap.height).flat_map(|y| (0..tilemap.width).map(move |x| (x, y)));
+        let mesh = Self::build_mesh(device, tilemap, tileset, transform_pos, pixels_per_unit, cells, 0.0);
+
+        // Check if mesh was generated
+        if mesh.index_count == 0 {
+             println!("DEBUG: Tilemap Mesh Empty! Tile Count: {}", tilemap.tiles.len());
+        }
+
+        (mesh.vertex_buffer, mesh.index_buffer, mesh.index_count)
+    }
+
+    /// Mesh for one `chunk_size` x `chunk_size` chunk, so edits only rebuild the chunks they touch.
+    /// Animated tiles start on their frame at `time` (see `animate_chunk`).
+    #[allow(clippy::too_many_arguments)]
+    pub fn prepare_chunk_mesh(
+        &self,
+        device: &wgpu::Device,
+        tilemap: &Tilemap,
+        tileset: &TileSet,
+        transform_pos: glam::Vec3,
+        pixels_per_unit: f32,
+        chunk: (u32, u32),
+        chunk_size: u32,
+        time: f32,
+    ) -> TilemapChunkMesh {
+        let x_range = (chunk.0 * chunk_size)..((chunk.0 + 1) * chunk_size).min(tilemap.width);
+        let y_range = (chunk.1 * chunk_size)..((chunk.1 + 1) * chunk_size).min(tilemap.height);
+        let cells = y_range.flat_map(move |y| x_range.clone().map(move |x| (x, y)));
+        Self::build_mesh(device, tilemap, tileset, transform_pos, pixels_per_unit, cells, time)
+    }
+
+    /// Show the frame of every animated tile in the chunk at `time` on the shared tile
+    /// clock, rewriting only the quads whose frame changed (the chunk isn't rebuilt)
+    pub fn animate_chunk(&self, queue: &wgpu::Queue, tileset: &TileSet, mesh: &mut TilemapChunkMesh, time: f32) {
+        for quad in &mut mesh.animated {
+            let tile_id = tileset.animated_tile_id(quad.tile.tile_id, time);
+            if tile_id == quad.shown_tile_id {
+                continue;
+            }
+            let Some(uvs) = Self::tile_uvs(tileset, tile_id, quad.tile.flip_h, quad.tile.flip_v) else {
+                continue;
+            };
+            let vertices: Vec<Vertex> = quad.corners.iter().zip(uvs)
+                .map(|(&position, tex_coords)| Vertex { position, tex_coords })
+                .collect();
+            let offset = quad.first_vertex as u64 * std::mem::size_of::<Vertex>() as u64;
+            queue.write_buffer(&mesh.vertex_buffer, offset, bytemuck::cast_slice(&vertices));
+            quad.shown_tile_id = tile_id;
+        }
+    }
+
+    /// UVs of a tile's quad corners (top left, top right, bottom right, bottom left)
+    fn tile_uvs(tileset: &TileSet, tile_id: u32, flip_h: bool, flip_v: bool) -> Option<[[f32; 2]; 4]> {
+        let (tx, ty) = tileset.get_tile_coords(tile_id)?;
+
+        // Texture Size in Pixels (for UVs)
+        let tex_width = (tileset.columns * tileset.tile_width) as f32;
+        let tex_height = (tileset.tile_count as f32 / tileset.columns as f32).ceil() * tileset.tile_height as f32;
+
+        let u0 = tx as f32 / tex_width;
+        let v0 = ty as f32 / tex_height;
+        let u1 = (tx as f32 + tileset.tile_width as f32) / tex_width;
+        let v1 = (ty as f32 + tileset.tile_height as f32) / tex_height;
+
+        // Handle flip flags
+        let (u0, u1) = if flip_h { (u1, u0) } else { (u0, u1) };
+        let (v0, v1) = if flip_v { (v1, v0) } else { (v0, v1) };
+        Some([[u0, v0], [u1, v0], [u1, v1], [u0, v1]])
+    }
+
+    fn build_mesh(
+        device: &wgpu::Device,
+        tilemap: &Tilemap,
+        tileset: &TileSet,
+        transform_pos: glam::Vec3,
+        pixels_per_unit: f32,
+        cells: impl Iterator<Item = (u32, u32)>,
+        time: f32,
+    ) -> TilemapChunkMesh {
         let mut vertices = Vec::new();
         let mut indices = Vec::new();
         let mut index_count = 0;
+        let mut animated = Vec::new();
 
         // Visual Size in World Units
         let world_tile_width = tileset.tile_width as f32 / pixels_per_unit;
         let world_tile_height = tileset.tile_height as f32 / pixels_per_unit;
-
-        // Texture Size in Pixels (for UVs)
-        let tile_width_px = tileset.tile_width as f32;
-        let tile_height_px = tileset.tile_height as f32;
-        let tex_width = (tileset.columns * tileset.tile_width) as f32;
-        let tex_height = (tileset.tile_count as f32 / tileset.columns as f32).ceil() * tileset.tile_height as f32;
         
-        // We need texture dimensions to calculate UVs correctly
-        // Assuming tileset.columns and tileset.tile_count are correct
-        let _cols = tileset.columns;
-        
-        for (i, tile) in tilemap.tiles.iter().enumerate() {
+        for (x_idx, y_idx) in cells {
+            let Some(tile) = tilemap.get_tile(x_idx, y_idx) else {
+                continue;
+            };
+
             // Skip empty tiles
             if tile.is_empty() {
                 continue;
             }
 
-            let x_idx = (i as u32) % tilemap.width;
-            let y_idx = (i as u32) / tilemap.width;
-            
             // Calculate world position relative to Tilemap Origin
             // Note: y_idx increases downwards. In 2D World Space (Y-Up), this means decreasing Y.
             // But we typically want the Tilemap Anchor to be Top-Left.
@@ -128,30 +219,25 @@ impl TilemapRenderer {
             let y = transform_pos.y - (y_idx as f32 * world_tile_height);
             let z = transform_pos.z;
 
-            // Calculate UVs for the tile
-            if let Some((tx, ty)) = tileset.get_tile_coords(tile.tile_id) {
-                // Calculate UV coordinates
-                let u0 = tx as f32 / tex_width;
-                let v0 = ty as f32 / tex_height;
-                let u1 = (tx as f32 + tile_width_px) / tex_width;
-                let v1 = (ty as f32 + tile_height_px) / tex_height;
-
-                // Handle flip flags
-                let (u0, u1) = if tile.flip_h { (u1, u0) } else { (u0, u1) };
-                let (v0, v1) = if tile.flip_v { (v1, v0) } else { (v0, v1) };
-
+            // Calculate UVs for the tile (its current frame if animated)
+            let shown_tile_id = tileset.animated_tile_id(tile.tile_id, time);
+            if let Some(uvs) = Self::tile_uvs(tileset, shown_tile_id, tile.flip_h, tile.flip_v) {
                 // Add vertices
                 let start_idx = vertices.len() as u16;
                 
-                // Quad vertices (x, y, z, u, v)
-                // Top Left (Anchor)
-                vertices.push(Vertex { position: [x, y, z], tex_coords: [u0, v0] });
-                // Top Right
-                vertices.push(Vertex { position: [x + world_tile_width, y, z], tex_coords: [u1, v0] });
-                // Bottom Right
-                vertices.push(Vertex { position: [x + world_tile_width, y - world_tile_height, z], tex_coords: [u1, v1] });
-                // Bottom Left
-                vertices.push(Vertex { position: [x, y - world_tile_height, z], tex_coords: [u0, v1] });
+                // Quad corners: Top Left (Anchor), Top Right, Bottom Right, Bottom Left
+                let corners = [
+                    [x, y, z],
+                    [x + world_tile_width, y, z],
+                    [x + world_tile_width, y - world_tile_height, z],
+                    [x, y - world_tile_height, z],
+                ];
+                for (position, tex_coords) in corners.iter().zip(uvs) {
+                    vertices.push(Vertex { position: *position, tex_coords });
+                }
+                if tileset.is_animated(tile.tile_id) {
+                    animated.push(AnimatedTileQuad { first_vertex: start_idx as u32, tile: tile.clone(), corners, shown_tile_id });
+                }
 
                 // Add indices (two triangles)
                 indices.push(start_idx);
@@ -165,15 +251,11 @@ impl TilemapRenderer {
             }
         }
 
-        // Check if mesh was generated
-        if vertices.len() == 0 {
-             println!("DEBUG: Tilemap Mesh Empty! Tile Count: {}", tilemap.tiles.len());
-        }
-
+        // COPY_DST so animated tiles can be rewritten in place
         let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: Some("Tilemap Vertex Buffer"),
             contents: bytemuck::cast_slice(&vertices),
-            usage: wgpu::BufferUsages::VERTEX,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
         });
 
         let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -182,7 +264,7 @@ impl TilemapRenderer {
             usage: wgpu::BufferUsages::INDEX,
         });
 
-        (vertex_buffer, index_buffer, index_count)
+        TilemapChunkMesh { vertex_buffer, index_buffer, index_count, animated }
     }
 
     pub fn render<'a>(
@@ -205,6 +287,7 @@ impl TilemapRenderer {
             render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
             render_pass.set_index_buffer(index_buffer.slice(..), wgpu::IndexFormat::Uint16);
             render_pass.draw_indexed(0..index_count, 0, 0..1);
+            profiler::gpu::add_draw_call(index_count as usize / 3);
         } else {
              // Only log once per frame/texture ideally, but for now strict debug
              println!("DEBUG: ERROR! Texture has no BindGroup. Cannot render tilemap.");
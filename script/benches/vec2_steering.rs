@@ -0,0 +1,67 @@
+//! Vec2 Benchmarks
+//!
+//! 1000 agents seeking a moving target (arrive + steering force limits), once with the
+//! pure-Lua engine.vec2 module and once with the native Vec2 userdata. The Lua code is
+//! the same for both; only the Vec2 it is handed differs.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mlua::{Function, Lua, Table};
+
+const STEERING: &str = r#"
+    local Vec2 = ...
+    local COUNT, MAX_SPEED, MAX_FORCE, SLOW_RADIUS = 1000, 80, 200, 30
+    local agents = {}
+    for i = 1, COUNT do
+        agents[i] = { position = Vec2((i % 40) * 10, (i // 40) * 10), velocity = Vec2(0, 0) }
+    end
+    local time = 0
+    return function(dt)
+        time = time + dt
+        local target = Vec2(200 + 100 * math.cos(time), 120 + 100 * math.sin(time))
+        for i = 1, COUNT do
+            local agent = agents[i]
+            local to_target = target - agent.position
+            local distance = to_target:length()
+            local speed = MAX_SPEED
+            if distance < SLOW_RADIUS then
+                speed = MAX_SPEED * distance / SLOW_RADIUS
+            end
+            local desired = to_target:normalized() * speed
+            local steering = (desired - agent.velocity):clamped(MAX_FORCE * dt)
+            agent.velocity = (agent.velocity + steering):clamped(MAX_SPEED)
+            agent.position = agent.position:move_towards(target, agent.velocity:length() * dt)
+        end
+    end
+"#;
+
+fn lua_vec2(lua: &Lua) -> Table<'_> {
+    let (_, source) = script::modules::ENGINE_MODULES.iter()
+        .find(|(name, _)| *name == "engine.vec2")
+        .expect("engine.vec2 module");
+    lua.load(*source).set_name("engine.vec2").eval().unwrap()
+}
+
+fn steering_step<'lua>(lua: &'lua Lua, vec2: Table<'lua>) -> Function<'lua> {
+    lua.load(STEERING).set_name("steering").call(vec2).unwrap()
+}
+
+fn bench_steering(c: &mut Criterion) {
+    let mut group = c.benchmark_group("steering_1000_agents");
+
+    group.bench_function("lua_tables", |b| {
+        let lua = Lua::new();
+        let step = steering_step(&lua, lua_vec2(&lua));
+        b.iter(|| step.call::<_, ()>(black_box(1.0 / 60.0)).unwrap());
+    });
+
+    group.bench_function("native_vec2", |b| {
+        let lua = Lua::new();
+        let step = steering_step(&lua, script::math::create_vec2_table(&lua).unwrap());
+        b.iter(|| step.call::<_, ()>(black_box(1.0 / 60.0)).unwrap());
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_steering);
+criterion_main!(benches);
@@ -0,0 +1,55 @@
+-- engine.class: minimal classes with single inheritance
+--
+--   local class = require("engine.class")
+--   local Enemy = class()
+--   function Enemy:init(hp) self.hp = hp end
+--
+--   local Boss = class(Enemy)
+--   function Boss:init(hp, phase)
+--       Enemy.init(self, hp)
+--       self.phase = phase
+--   end
+--
+--   local boss = Boss(500, 1)      -- or Boss.new(500, 1)
+--   print(boss:is_a(Enemy))        -- true
+
+---@class Class
+---@field super Class? The parent class
+---@field init fun(self: any, ...)? Constructor, called by new()
+---@field new fun(...): any Create an instance
+---@field is_a fun(self: any, class: Class): boolean
+
+---@param parent? Class
+---@return Class
+local function class(parent)
+    local cls = {}
+    cls.__index = cls
+    cls.super = parent
+
+    function cls.new(...)
+        local instance = setmetatable({}, cls)
+        if instance.init then
+            instance:init(...)
+        end
+        return instance
+    end
+
+    --- Whether this instance is of `other` or a class derived from it
+    function cls:is_a(other)
+        local current = getmetatable(self)
+        while current do
+            if current == other then
+                return true
+            end
+            current = current.super
+        end
+        return false
+    end
+
+    return setmetatable(cls, {
+        __index = parent,
+        __call = function(_, ...) return cls.new(...) end,
+    })
+end
+
+return class
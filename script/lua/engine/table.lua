@@ -0,0 +1,182 @@
+-- engine.table: table helpers
+--
+--   local tbl = require("engine.table")
+--   local alive = tbl.filter(enemies, function(e) return IsActive(e) end)
+
+local M = {}
+
+--- Shallow copy
+---@generic T: table
+---@param t T
+---@return T
+function M.copy(t)
+    local result = {}
+    for k, v in pairs(t) do
+        result[k] = v
+    end
+    return result
+end
+
+--- Copy nested tables too (keeps metatables, handles cycles)
+---@generic T
+---@param value T
+---@return T
+function M.deep_copy(value, seen)
+    if type(value) ~= "table" then
+        return value
+    end
+    seen = seen or {}
+    if seen[value] then
+        return seen[value]
+    end
+    local result = {}
+    seen[value] = result
+    for k, v in pairs(value) do
+        result[M.deep_copy(k, seen)] = M.deep_copy(v, seen)
+    end
+    return setmetatable(result, getmetatable(value))
+end
+
+---@param t table
+---@return any[]
+function M.keys(t)
+    local result = {}
+    for k in pairs(t) do
+        result[#result + 1] = k
+    end
+    return result
+end
+
+---@param t table
+---@return any[]
+function M.values(t)
+    local result = {}
+    for _, v in pairs(t) do
+        result[#result + 1] = v
+    end
+    return result
+end
+
+---@param t table
+---@return integer
+function M.count(t)
+    local n = 0
+    for _ in pairs(t) do
+        n = n + 1
+    end
+    return n
+end
+
+---@param t table
+---@return boolean
+function M.is_empty(t)
+    return next(t) == nil
+end
+
+--- New array with fn(value, index) of every element
+---@param list any[]
+---@param fn fun(value: any, index: integer): any
+---@return any[]
+function M.map(list, fn)
+    local result = {}
+    for i, v in ipairs(list) do
+        result[i] = fn(v, i)
+    end
+    return result
+end
+
+--- Elements for which fn(value, index) is truthy
+---@param list any[]
+---@param fn fun(value: any, index: integer): any
+---@return any[]
+function M.filter(list, fn)
+    local result = {}
+    for i, v in ipairs(list) do
+        if fn(v, i) then
+            result[#result + 1] = v
+        end
+    end
+    return result
+end
+
+---@param list any[]
+---@param fn fun(accumulator: any, value: any, index: integer): any
+---@param initial any
+---@return any
+function M.reduce(list, fn, initial)
+    local accumulator = initial
+    for i, v in ipairs(list) do
+        accumulator = fn(accumulator, v, i)
+    end
+    return accumulator
+end
+
+--- First element matching fn, and its index
+---@param list any[]
+---@param fn fun(value: any, index: integer): any
+---@return any value
+---@return integer? index
+function M.find(list, fn)
+    for i, v in ipairs(list) do
+        if fn(v, i) then
+            return v, i
+        end
+    end
+    return nil, nil
+end
+
+---@param list any[]
+---@param value any
+---@return integer?
+function M.index_of(list, value)
+    for i, v in ipairs(list) do
+        if v == value then
+            return i
+        end
+    end
+    return nil
+end
+
+---@param list any[]
+---@param value any
+---@return boolean
+function M.contains(list, value)
+    return M.index_of(list, value) ~= nil
+end
+
+--- Remove the first occurrence of value; true if it was there
+---@param list any[]
+---@param value any
+---@return boolean
+function M.remove_value(list, value)
+    local index = M.index_of(list, value)
+    if index then
+        table.remove(list, index)
+        return true
+    end
+    return false
+end
+
+--- Keys of `source` copied onto `target` (returns target)
+---@param target table
+---@param source table
+---@return table
+function M.merge(target, source)
+    for k, v in pairs(source) do
+        target[k] = v
+    end
+    return target
+end
+
+--- Shuffle in place with math.random (the engine RNG, so replays repeat it)
+---@param list any[]
+---@return any[]
+function M.shuffle(list)
+    for i = #list, 2, -1 do
+        local j = math.random(i)
+        list[i], list[j] = list[j], list[i]
+    end
+    return list
+end
+
+return M
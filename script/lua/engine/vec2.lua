@@ -0,0 +1,172 @@
+-- engine.vec2: 2D vector math
+--
+--   local Vec2 = require("engine.vec2")
+--   local velocity = Vec2(3, 4)
+--   local step = velocity:normalized() * speed * dt
+
+---@class Vec2
+---@field x number
+---@field y number
+---@operator add(Vec2): Vec2
+---@operator sub(Vec2): Vec2
+---@operator mul(number|Vec2): Vec2
+---@operator div(number|Vec2): Vec2
+---@operator unm: Vec2
+local Vec2 = {}
+Vec2.__index = Vec2
+
+---@param x? number
+---@param y? number
+---@return Vec2
+function Vec2.new(x, y)
+    return setmetatable({ x = x or 0, y = y or 0 }, Vec2)
+end
+
+---@param value any
+---@return boolean
+function Vec2.is_vec2(value)
+    return getmetatable(value) == Vec2
+end
+
+--- Unit vector at `radians` from the +x axis
+---@param radians number
+---@return Vec2
+function Vec2.from_angle(radians)
+    return Vec2.new(math.cos(radians), math.sin(radians))
+end
+
+--- Vec2 from a {x=, y=} table (e.g. get_position())
+---@param t {x: number, y: number}
+---@return Vec2
+function Vec2.from_table(t)
+    return Vec2.new(t.x, t.y)
+end
+
+---@return Vec2
+function Vec2.zero() return Vec2.new(0, 0) end
+---@return Vec2
+function Vec2.one() return Vec2.new(1, 1) end
+---@return Vec2
+function Vec2.up() return Vec2.new(0, 1) end
+---@return Vec2
+function Vec2.right() return Vec2.new(1, 0) end
+
+local function operands(a, b)
+    if type(a) == "number" then a = Vec2.new(a, a) end
+    if type(b) == "number" then b = Vec2.new(b, b) end
+    return a, b
+end
+
+function Vec2.__add(a, b) return Vec2.new(a.x + b.x, a.y + b.y) end
+function Vec2.__sub(a, b) return Vec2.new(a.x - b.x, a.y - b.y) end
+function Vec2.__mul(a, b)
+    a, b = operands(a, b)
+    return Vec2.new(a.x * b.x, a.y * b.y)
+end
+function Vec2.__div(a, b)
+    a, b = operands(a, b)
+    return Vec2.new(a.x / b.x, a.y / b.y)
+end
+function Vec2.__unm(a) return Vec2.new(-a.x, -a.y) end
+function Vec2.__eq(a, b) return a.x == b.x and a.y == b.y end
+function Vec2.__tostring(a) return string.format("Vec2(%g, %g)", a.x, a.y) end
+
+---@return Vec2
+function Vec2:copy()
+    return Vec2.new(self.x, self.y)
+end
+
+---@return number x
+---@return number y
+function Vec2:unpack()
+    return self.x, self.y
+end
+
+---@return number
+function Vec2:length()
+    return math.sqrt(self.x * self.x + self.y * self.y)
+end
+
+---@return number
+function Vec2:length_squared()
+    return self.x * self.x + self.y * self.y
+end
+
+--- Unit vector in the same direction (zero stays zero)
+---@return Vec2
+function Vec2:normalized()
+    local length = self:length()
+    if length == 0 then
+        return Vec2.new(0, 0)
+    end
+    return Vec2.new(self.x / length, self.y / length)
+end
+
+---@param other Vec2
+---@return number
+function Vec2:dot(other)
+    return self.x * other.x + self.y * other.y
+end
+
+--- z of the 3D cross product (positive when `other` is counter-clockwise)
+---@param other Vec2
+---@return number
+function Vec2:cross(other)
+    return self.x * other.y - self.y * other.x
+end
+
+---@param other Vec2
+---@return number
+function Vec2:distance(other)
+    return (other - self):length()
+end
+
+---@param other Vec2
+---@param t number 0-1
+---@return Vec2
+function Vec2:lerp(other, t)
+    return Vec2.new(self.x + (other.x - self.x) * t, self.y + (other.y - self.y) * t)
+end
+
+--- Angle from the +x axis in radians
+---@return number
+function Vec2:angle()
+    return math.atan(self.y, self.x)
+end
+
+---@param radians number
+---@return Vec2
+function Vec2:rotated(radians)
+    local c, s = math.cos(radians), math.sin(radians)
+    return Vec2.new(self.x * c - self.y * s, self.x * s + self.y * c)
+end
+
+--- Same direction, at most `max_length` long
+---@param max_length number
+---@return Vec2
+function Vec2:clamped(max_length)
+    local length = self:length()
+    if length <= max_length or length == 0 then
+        return self:copy()
+    end
+    return self * (max_length / length)
+end
+
+---@param other Vec2
+---@param max_delta number
+---@return Vec2
+function Vec2:move_towards(other, max_delta)
+    local delta = other - self
+    local distance = delta:length()
+    if distance <= max_delta or distance == 0 then
+        return other:copy()
+    end
+    return self + delta * (max_delta / distance)
+end
+
+setmetatable(Vec2, {
+    __call = function(_, x, y) return Vec2.new(x, y) end,
+})
+
+---@type Vec2|fun(x?: number, y?: number): Vec2
+return Vec2
@@ -0,0 +1,190 @@
+// Custom components for scripts (Component.define / GetComponent / SetComponent / ...)
+//
+// A project declares its components in scripts/components.lua:
+//   Component.define("Health", { max = { type = "float", default = 100 }, current = "float", regen = "bool" })
+// Definitions go in the registry shared by every Lua state, so a script may also define
+// one itself before using it. The instances live on the world (ecs::custom_components),
+// so GetComponent returns a copy: change it with SetComponent. The world functions are
+// registered inside each lua.scope() that runs script code, like the Pool table.
+
+use crate::globals::{to_json, to_lua};
+use crate::modules::{self, ModuleRegistry};
+use ecs::{ComponentRegistry, ComponentSchema, Entity, FieldSchema, FieldType, World};
+use engine_core::assets::AssetLoader;
+use mlua::{Lua, Scope, Table, Value};
+use std::cell::RefCell;
+use std::path::Path;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Module with the project's Component.define calls (scripts/components.lua)
+pub const DEFINITIONS_MODULE: &str = "components";
+
+/// A field spec: a type name ("float") or { type = "float", default = 100 }
+fn parse_field(component: &str, name: &str, spec: Value) -> Result<FieldSchema, String> {
+    let (type_name, default) = match spec {
+        Value::String(type_name) => (type_name.to_str().map_err(|e| e.to_string())?.to_string(), None),
+        Value::Table(spec) => {
+            let type_name: Option<String> = spec.get("type").map_err(|e| e.to_string())?;
+            let type_name = type_name.ok_or_else(|| format!("{}.{} has no type", component, name))?;
+            let default: Value = spec.get("default").map_err(|e| e.to_string())?;
+            let default = match default {
+                Value::Nil => None,
+                value => Some(to_json(value, &format!("{}.{}", component, name), 0)?),
+            };
+            (type_name, default)
+        }
+        other => return Err(format!("{}.{} must be a type name or a table, got a {}", component, name, other.type_name())),
+    };
+    let field_type = FieldType::from_name(&type_name).ok_or_else(|| {
+        let known: Vec<&str> = FieldType::ALL.iter().map(FieldType::name).collect();
+        format!("{}.{} has unknown type '{}' (use {})", component, name, type_name, known.join(", "))
+    })?;
+    FieldSchema::new(name, field_type, default.as_ref()).map_err(|e| format!("{}: {}", component, e))
+}
+
+/// Register the Component table (definitions only; usable outside the world scope)
+pub(crate) fn register_component_define_api(lua: &Lua, registry: Rc<RefCell<ComponentRegistry>>) -> mlua::Result<()> {
+    let component_table = lua.create_table()?;
+
+    // Component.define(name, { field = "float" | { type = "float", default = 1 }, ... })
+    // Defining it again replaces the schema; instances conform the next time they're read
+    let defined = Rc::clone(&registry);
+    component_table.set("define", lua.create_function(move |_, (name, fields): (String, Table)| {
+        if name.trim().is_empty() {
+            return Err(mlua::Error::RuntimeError("Component.define: name must not be empty".to_string()));
+        }
+        let mut schema_fields = Vec::new();
+        for pair in fields.pairs::<String, Value>() {
+            let (field, spec) = pair?;
+            let field = parse_field(&name, &field, spec)
+                .map_err(|e| mlua::Error::RuntimeError(format!("Component.define: {}", e)))?;
+            schema_fields.push(field);
+        }
+        defined.borrow_mut().define(ComponentSchema::new(&name, schema_fields));
+        Ok(())
+    })?)?;
+
+    // Component.exists(name)
+    let exists = Rc::clone(&registry);
+    component_table.set("exists", lua.create_function(move |_, name: String| {
+        Ok(exists.borrow().get(&name).is_some())
+    })?)?;
+
+    // Component.names(): defined component names, sorted
+    component_table.set("names", lua.create_function(move |_, ()| {
+        Ok(registry.borrow().schemas().map(|schema| schema.name.clone()).collect::<Vec<String>>())
+    })?)?;
+
+    lua.globals().set("Component", component_table)
+}
+
+fn undefined(function: &str, name: &str) -> mlua::Error {
+    mlua::Error::RuntimeError(format!(
+        "{}: component '{}' is not defined (Component.define in scripts/{}.lua)",
+        function, name, DEFINITIONS_MODULE
+    ))
+}
+
+/// Register the world functions on custom components for the script code run inside `scope`
+pub fn register_component_api<'lua, 'scope>(
+    lua: &'lua Lua,
+    scope: &Scope<'lua, 'scope>,
+    world_cell: &'scope RefCell<&mut World>,
+    registry: &'scope Rc<RefCell<ComponentRegistry>>,
+) -> mlua::Result<()> {
+    let globals = lua.globals();
+
+    // GetComponent(entity, name): a copy of its fields, or nil when the entity doesn't
+    // have it. A stored instance from an older definition is brought up to date first.
+    globals.set("GetComponent", scope.create_function(move |lua, (entity, name): (Entity, String)| {
+        let mut world = world_cell.borrow_mut();
+        let Some(value) = world.custom_component_mut(entity, &name) else {
+            return Ok(Value::Nil);
+        };
+        if let Some(schema) = registry.borrow().get(&name) {
+            for change in schema.conform(value) {
+                log::warn!("Entity {}: {}", entity, change);
+            }
+        }
+        to_lua(lua, value)
+    })?)?;
+
+    // SetComponent(entity, name, { field = value, ... }): set some fields, adding the
+    // component (other fields at their defaults) if the entity doesn't have it
+    globals.set("SetComponent", scope.create_function(move |_, (entity, name, values): (Entity, String, Value)| {
+        let registry = registry.borrow();
+        let schema = registry.get(&name).ok_or_else(|| undefined("SetComponent", &name))?;
+        let values = to_json(values, &name, 0).map_err(mlua::Error::RuntimeError)?;
+        let mut world = world_cell.borrow_mut();
+        let mut base = world.custom_component(entity, &name).cloned().unwrap_or_else(|| schema.default_value());
+        schema.conform(&mut base);
+        let value = schema.apply(&base, &values)
+            .map_err(|e| mlua::Error::RuntimeError(format!("SetComponent: {}", e)))?;
+        world.set_custom_component(entity, &name, value);
+        Ok(())
+    })?)?;
+
+    // AddComponent(entity, name, values?): false (and no change) if it already has it
+    globals.set("AddComponent", scope.create_function(move |_, (entity, name, values): (Entity, String, Option<Value>)| {
+        let registry = registry.borrow();
+        let schema = registry.get(&name).ok_or_else(|| undefined("AddComponent", &name))?;
+        let mut world = world_cell.borrow_mut();
+        if world.has_custom_component(entity, &name) {
+            return Ok(false);
+        }
+        let mut value = schema.default_value();
+        if let Some(values) = values {
+            let values = to_json(values, &name, 0).map_err(mlua::Error::RuntimeError)?;
+            value = schema.apply(&value, &values)
+                .map_err(|e| mlua::Error::RuntimeError(format!("AddComponent: {}", e)))?;
+        }
+        world.set_custom_component(entity, &name, value);
+        Ok(true)
+    })?)?;
+
+    // RemoveComponent(entity, name): false if it didn't have it
+    globals.set("RemoveComponent", scope.create_function(move |_, (entity, name): (Entity, String)| {
+        Ok(world_cell.borrow_mut().remove_custom_component(entity, &name).is_some())
+    })?)?;
+
+    // HasComponent(entity, name)
+    globals.set("HasComponent", scope.create_function(move |_, (entity, name): (Entity, String)| {
+        Ok(world_cell.borrow().has_custom_component(entity, &name))
+    })?)?;
+
+    // FindEntitiesWith(name): entities with the component, sorted by id
+    globals.set("FindEntitiesWith", scope.create_function(move |_, name: String| {
+        Ok(world_cell.borrow().entities_with_custom_component(&name))
+    })?)?;
+
+    Ok(())
+}
+
+/// Run scripts/components.lua (when the project has one) in `lua`, whose Component
+/// table fills the registry. require() caches it, so this only runs it again after the
+/// file changed.
+pub(crate) fn require_definitions(lua: &Lua, modules: &Rc<RefCell<ModuleRegistry>>) -> Result<(), String> {
+    if !modules.borrow().exists(DEFINITIONS_MODULE) {
+        return Ok(());
+    }
+    let require: mlua::Function = lua.globals().get("require").map_err(|e| e.to_string())?;
+    require.call::<_, Value>(DEFINITIONS_MODULE).map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// The project's component definitions, read outside play mode (editor inspector)
+pub fn load_definitions(
+    asset_loader: Arc<dyn AssetLoader>,
+    project_root: &Path,
+    lib_paths: &[String],
+) -> Result<ComponentRegistry, String> {
+    let lua = Lua::new();
+    let modules = Rc::new(RefCell::new(ModuleRegistry::new(asset_loader)));
+    modules.borrow_mut().configure(Some(project_root), lib_paths);
+    modules::register_require(&lua, &modules).map_err(|e| e.to_string())?;
+    let registry = Rc::new(RefCell::new(ComponentRegistry::new()));
+    register_component_define_api(&lua, Rc::clone(&registry)).map_err(|e| e.to_string())?;
+    require_definitions(&lua, &modules)?;
+    let registry = registry.borrow().clone();
+    Ok(registry)
+}
@@ -0,0 +1,204 @@
+// Coroutines for Lua scripts (Coroutine.start / Coroutine.wait / Coroutine.stop)
+//
+// A coroutine is a Lua thread of the entity that started it. It runs right away up to
+// its first wait, then resumes inside the owner's run_script() (after timer callbacks),
+// so it has the full per-frame script API. While the owner's script is disabled its
+// coroutines are suspended: waits don't count down and nothing resumes until OnEnable.
+
+use ecs::Entity;
+use mlua::{Function, Lua, RegistryKey, ThreadStatus, UserData, UserDataMethods, Value};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+
+struct Coroutine {
+    id: u64,
+    owner: Entity,
+    thread: RegistryKey,
+    /// Seconds until the next resume (0 or less: the owner's next run_script)
+    remaining: f32,
+}
+
+/// Per-engine storage for all script coroutines
+#[derive(Default)]
+pub struct ScriptCoroutines {
+    next_id: u64,
+    coroutines: Vec<Coroutine>,
+    /// Owners whose script is disabled (their waits are frozen)
+    paused: HashSet<Entity>,
+}
+
+impl ScriptCoroutines {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn allocate_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn add(&mut self, id: u64, owner: Entity, thread: RegistryKey, remaining: f32) {
+        self.coroutines.push(Coroutine { id, owner, thread, remaining });
+    }
+
+    /// Stop a coroutine. It will not resume.
+    pub fn cancel(&mut self, id: u64) {
+        self.coroutines.retain(|c| c.id != id);
+    }
+
+    pub fn is_active(&self, id: u64) -> bool {
+        self.coroutines.iter().any(|c| c.id == id)
+    }
+
+    /// Freeze (or thaw) the waits of `owner`'s coroutines, for a disabled script
+    pub fn set_paused(&mut self, owner: Entity, paused: bool) {
+        if paused {
+            self.paused.insert(owner);
+        } else {
+            self.paused.remove(&owner);
+        }
+    }
+
+    /// Drop everything an entity started (called from remove_entity_state)
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.coroutines.retain(|c| c.owner != entity);
+        self.paused.remove(&entity);
+    }
+
+    pub fn clear(&mut self) {
+        self.coroutines.clear();
+        self.paused.clear();
+    }
+
+    /// Number of running coroutines
+    pub fn active_count(&self) -> usize {
+        self.coroutines.len()
+    }
+
+    /// Count down the waits of coroutines whose owner is enabled
+    pub fn update(&mut self, dt: f32) {
+        let paused = &self.paused;
+        for coroutine in self.coroutines.iter_mut().filter(|c| !paused.contains(&c.owner)) {
+            coroutine.remaining -= dt;
+        }
+    }
+
+    /// Ids of `entity`'s coroutines whose wait is over
+    fn due(&self, entity: Entity) -> Vec<u64> {
+        self.coroutines.iter().filter(|c| c.owner == entity && c.remaining <= 0.0).map(|c| c.id).collect()
+    }
+
+    fn thread_key(&self, id: u64) -> Option<&RegistryKey> {
+        self.coroutines.iter().find(|c| c.id == id).map(|c| &c.thread)
+    }
+
+    fn set_remaining(&mut self, id: u64, remaining: f32) {
+        if let Some(coroutine) = self.coroutines.iter_mut().find(|c| c.id == id) {
+            coroutine.remaining = remaining;
+        }
+    }
+
+    /// Remove a finished coroutine, releasing its registry value
+    fn release(&mut self, lua: &Lua, id: u64) {
+        if let Some(index) = self.coroutines.iter().position(|c| c.id == id) {
+            let coroutine = self.coroutines.remove(index);
+            let _ = lua.remove_registry_value(coroutine.thread);
+        }
+    }
+}
+
+/// Seconds a yield asks to wait: Coroutine.wait(seconds) yields a number, a bare
+/// coroutine.yield() waits for the next frame
+fn wait_seconds(value: &Value) -> f32 {
+    match value {
+        Value::Integer(seconds) => *seconds as f32,
+        Value::Number(seconds) => *seconds as f32,
+        _ => 0.0,
+    }
+}
+
+/// Resume a coroutine's thread once. Returns the seconds it now waits, or None once it
+/// has finished (or failed, which is logged).
+fn resume(thread: &mlua::Thread, entity: Entity) -> Option<f32> {
+    match thread.resume::<_, Value>(()) {
+        Ok(value) if thread.status() == ThreadStatus::Resumable => Some(wait_seconds(&value)),
+        Ok(_) => None,
+        Err(e) => {
+            log::error!("Coroutine error for entity {}: {}", entity, e);
+            None
+        }
+    }
+}
+
+/// Resume `entity`'s coroutines whose wait is over, inside its own Lua state.
+/// Must be called while the entity's per-frame API is injected (inside lua.scope).
+pub fn run_due_coroutines(coroutines: &Rc<RefCell<ScriptCoroutines>>, lua: &Lua, entity: Entity) -> mlua::Result<()> {
+    // Coroutines started while these run wait for the next frame
+    let ids = coroutines.borrow().due(entity);
+    for id in ids {
+        // Release the borrow before resuming (the coroutine may start or stop others)
+        let thread = match coroutines.borrow().thread_key(id) {
+            Some(key) => lua.registry_value::<mlua::Thread>(key)?,
+            None => continue, // Stopped by one that resumed earlier
+        };
+        match resume(&thread, entity) {
+            Some(wait) => coroutines.borrow_mut().set_remaining(id, wait),
+            None => coroutines.borrow_mut().release(lua, id),
+        }
+    }
+    Ok(())
+}
+
+/// Handle returned to Lua by Coroutine.start
+#[derive(Clone)]
+pub struct CoroutineHandle {
+    pub id: u64,
+    coroutines: Rc<RefCell<ScriptCoroutines>>,
+}
+
+impl UserData for CoroutineHandle {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("stop", |_, this, ()| {
+            this.coroutines.borrow_mut().cancel(this.id);
+            Ok(())
+        });
+        methods.add_method("is_active", |_, this, ()| {
+            Ok(this.coroutines.borrow().is_active(this.id))
+        });
+    }
+}
+
+/// Register the Coroutine table in an entity's Lua state
+pub fn register_coroutine_api(lua: &Lua, owner: Entity, coroutines: Rc<RefCell<ScriptCoroutines>>) -> mlua::Result<()> {
+    let coroutine_table = lua.create_table()?;
+
+    // Coroutine.start(fn, ...): runs fn(...) up to its first wait
+    let coroutines_ref = Rc::clone(&coroutines);
+    coroutine_table.set("start", lua.create_function(move |lua, (body, args): (Function, mlua::MultiValue)| {
+        let thread = lua.create_thread(body)?;
+        let id = coroutines_ref.borrow_mut().allocate_id();
+        // Errors before the first wait go to the caller, like a plain call
+        let value = thread.resume::<_, Value>(args)?;
+        if thread.status() == ThreadStatus::Resumable {
+            let key = lua.create_registry_value(thread)?;
+            coroutines_ref.borrow_mut().add(id, owner, key, wait_seconds(&value));
+        }
+        Ok(CoroutineHandle { id, coroutines: Rc::clone(&coroutines_ref) })
+    })?)?;
+
+    // Coroutine.wait(seconds) (WaitForSeconds): only from inside a coroutine; a plain
+    // Lua function so it can yield
+    let wait: Function = lua.load("return function(seconds) coroutine.yield(seconds or 0) end").eval()?;
+    coroutine_table.set("wait", wait)?;
+
+    let coroutines_ref = Rc::clone(&coroutines);
+    coroutine_table.set("stop", lua.create_function(move |_, handle: mlua::AnyUserData| {
+        let handle = handle.borrow::<CoroutineHandle>()?;
+        coroutines_ref.borrow_mut().cancel(handle.id);
+        Ok(())
+    })?)?;
+
+    lua.globals().set("Coroutine", coroutine_table)?;
+    Ok(())
+}
@@ -0,0 +1,598 @@
+//! Lua script debugger
+//!
+//! While any breakpoint is set (or a break is being stepped through), every entity's
+//! Lua state gets a line hook that checks breakpoints and step requests. Update runs
+//! as a coroutine in that case (see `ScriptEngine::run_script`), so a hit inside it
+//! suspends the coroutine with `lua_yield`: the entity's state keeps its stack until
+//! Continue/Step resumes it, and no other state is touched. Hits anywhere else (Start,
+//! collision and animation callbacks, Lua called back from Rust) can't be suspended;
+//! they are still reported with their stack and locals, and execution carries on.
+
+use ecs::Entity;
+use mlua::{ffi, Lua, LightUserData, RegistryKey, Table, Thread, ThreadStatus, Value};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::ffi::{c_char, c_int, c_void, CStr};
+use std::rc::Rc;
+
+/// Registry keys the hook reads
+const DEBUGGER_KEY: &CStr = c"__script_debugger";
+const ENTITY_KEY: &CStr = c"__script_debugger_entity";
+/// Update coroutine currently allowed to suspend
+const THREAD_KEY: &CStr = c"__script_debugger_thread";
+
+const MAX_FRAMES: c_int = 16;
+const MAX_LOCALS: c_int = 64;
+const MAX_TABLE_PREVIEW: usize = 6;
+const MAX_STRING_PREVIEW: usize = 80;
+
+/// Breakpoint on a script line. `file` is the chunk name without its `@`,
+/// e.g. `scripts/player.lua` (see `ScriptEngine::load_script_for_entity`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Breakpoint {
+    pub file: String,
+    pub line: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct StackFrame {
+    pub function: String,
+    pub file: String,
+    pub line: u32,
+    /// Name and printed value, in declaration order
+    pub locals: Vec<(String, String)>,
+}
+
+/// Where execution stopped (or passed, when it couldn't be suspended)
+#[derive(Clone, Debug)]
+pub struct DebugBreak {
+    pub entity: Entity,
+    pub file: String,
+    pub line: u32,
+    /// Innermost first
+    pub frames: Vec<StackFrame>,
+    /// The entity's Update is suspended here until a `DebugCommand`
+    pub suspended: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DebugCommand {
+    Continue,
+    /// Next line in the same function or its callers
+    StepOver,
+    /// Next line anywhere, including called functions
+    StepInto,
+    /// Next line after the current function returns
+    StepOut,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum Step {
+    #[default]
+    None,
+    Into,
+    /// Break at a stack depth of at most this
+    Over(u32),
+    /// Break at a stack depth below this
+    Out(u32),
+}
+
+/// Debugger state shared by the engine and the hooks of all entity states
+#[derive(Default)]
+pub struct DebuggerState {
+    breakpoints: HashSet<Breakpoint>,
+    step: Step,
+    /// Entity being stepped through
+    step_entity: Option<Entity>,
+    /// Stack depth of the current break (for step over and out)
+    break_depth: u32,
+    current: Option<DebugBreak>,
+    /// Hit not yet seen by `take_hit`
+    new_hit: bool,
+    /// Entity whose suspended Update a command asked to resume
+    resume: Option<Entity>,
+}
+
+impl DebuggerState {
+    pub fn set_breakpoints(&mut self, breakpoints: impl IntoIterator<Item = Breakpoint>) {
+        self.breakpoints = breakpoints.into_iter().collect();
+    }
+
+    /// Hooks are needed: breakpoints exist, or a break is suspended or being stepped from
+    pub fn is_armed(&self) -> bool {
+        !self.breakpoints.is_empty() || self.suspended_entity().is_some() || self.step != Step::None
+    }
+
+    /// Latest break (suspended, or passed through outside Update)
+    pub fn current(&self) -> Option<&DebugBreak> {
+        self.current.as_ref()
+    }
+
+    /// Entity whose Update is suspended at a breakpoint
+    pub fn suspended_entity(&self) -> Option<Entity> {
+        self.current.as_ref().filter(|b| b.suspended).map(|b| b.entity)
+    }
+
+    /// The latest hit, once
+    pub fn take_hit(&mut self) -> Option<DebugBreak> {
+        if !std::mem::take(&mut self.new_hit) {
+            return None;
+        }
+        self.current.clone()
+    }
+
+    /// Continue or step from the suspended break. Returns false when nothing is suspended.
+    pub fn command(&mut self, command: DebugCommand) -> bool {
+        let Some(entity) = self.suspended_entity() else {
+            return false;
+        };
+        self.step = match command {
+            DebugCommand::Continue => Step::None,
+            DebugCommand::StepInto => Step::Into,
+            DebugCommand::StepOver => Step::Over(self.break_depth),
+            DebugCommand::StepOut => Step::Out(self.break_depth),
+        };
+        self.step_entity = Some(entity);
+        self.current = None;
+        self.new_hit = false;
+        self.resume = Some(entity);
+        true
+    }
+
+    /// Entity to resume after a command, once
+    pub fn take_resume(&mut self) -> Option<Entity> {
+        self.resume.take()
+    }
+
+    /// Forget the current break and stepping (play mode stopped)
+    pub fn reset(&mut self) {
+        self.current = None;
+        self.step = Step::None;
+        self.step_entity = None;
+        self.new_hit = false;
+        self.resume = None;
+    }
+
+    /// `entity`'s state is gone: drop its break and stepping
+    pub fn remove_entity(&mut self, entity: Entity) {
+        if self.current.as_ref().is_some_and(|b| b.entity == entity) {
+            self.current = None;
+            self.new_hit = false;
+        }
+        if self.step_entity == Some(entity) {
+            self.step = Step::None;
+            self.step_entity = None;
+        }
+        if self.resume == Some(entity) {
+            self.resume = None;
+        }
+    }
+
+    fn should_break(&self, entity: Entity, file: &str, line: u32, in_update: bool, depth: u32) -> bool {
+        // A suspended break stays current until it is resumed
+        if self.suspended_entity().is_some() {
+            return false;
+        }
+        let stepping = self.step_entity == Some(entity)
+            && in_update
+            && match self.step {
+                Step::None => false,
+                Step::Into => true,
+                Step::Over(max_depth) => depth <= max_depth,
+                Step::Out(max_depth) => depth < max_depth,
+            };
+        stepping || self.breakpoints.contains(&Breakpoint { file: file.to_string(), line })
+    }
+
+    fn hit(&mut self, hit: DebugBreak, depth: u32) {
+        self.step = Step::None;
+        self.step_entity = None;
+        self.break_depth = depth;
+        self.current = Some(hit);
+        self.new_hit = true;
+    }
+}
+
+/// Register `entity`'s state with the debugger (the hook itself is set by `set_hook_enabled`)
+pub fn register_state(lua: &Lua, entity: Entity, state: &Rc<RefCell<DebuggerState>>) -> mlua::Result<()> {
+    lua.set_named_registry_value(
+        registry_name(DEBUGGER_KEY),
+        LightUserData(Rc::as_ptr(state) as *mut c_void),
+    )?;
+    lua.set_named_registry_value(registry_name(ENTITY_KEY), entity)
+}
+
+/// Install or remove the line hook on `lua`'s main thread. Coroutines created later
+/// inherit it.
+pub fn set_hook_enabled(lua: &Lua, enabled: bool) -> mlua::Result<()> {
+    let function = if enabled { install_hook } else { remove_hook };
+    // SAFETY: both functions only call lua_sethook on the state they run on
+    let function = unsafe { lua.create_c_function(function)? };
+    function.call::<_, ()>(())
+}
+
+/// Run (or continue) an Update coroutine that the hook may suspend. Returns the
+/// coroutine if a breakpoint suspended it; otherwise Update is over, and so is
+/// stepping through it.
+pub fn resume_update<'lua>(
+    lua: &'lua Lua,
+    thread: Thread<'lua>,
+    args: impl mlua::IntoLuaMulti<'lua>,
+    debugger: &RefCell<DebuggerState>,
+    entity: Entity,
+) -> mlua::Result<Option<RegistryKey>> {
+    lua.set_named_registry_value(registry_name(THREAD_KEY), thread.clone())?;
+    let result = thread.resume::<_, ()>(args);
+    lua.unset_named_registry_value(registry_name(THREAD_KEY))?;
+
+    // A script's own coroutine.yield in Update also leaves it resumable; that one is dropped
+    let suspended = result.is_ok()
+        && thread.status() == ThreadStatus::Resumable
+        && debugger.borrow().suspended_entity() == Some(entity);
+    if !suspended {
+        let mut debugger = debugger.borrow_mut();
+        if debugger.step_entity == Some(entity) {
+            debugger.step = Step::None;
+            debugger.step_entity = None;
+        }
+    }
+    result?;
+    Ok(if suspended { Some(lua.create_registry_value(thread)?) } else { None })
+}
+
+/// Non-function globals of a state, printed, sorted by name. Libraries and engine
+/// API tables are left out.
+pub fn globals(lua: &Lua) -> Vec<(String, String)> {
+    const HIDDEN: &[&str] = &[
+        "_G", "_VERSION", "coroutine", "math", "string", "table", "utf8", "os", "io", "package",
+        "debug", "UI", "Input", "Time", "Save", "Profiler", "Nav", "Loc", "Timer", "Tween", "RenderTexture", "Scene", "Pool", "Globals", "SaveGame",
+    ];
+    let mut globals: Vec<(String, String)> = lua
+        .globals()
+        .pairs::<Value, Value>()
+        .flatten()
+        .filter_map(|(key, value)| {
+            let Value::String(name) = key else {
+                return None;
+            };
+            let name = name.to_str().ok()?.to_string();
+            if HIDDEN.contains(&name.as_str()) || matches!(value, Value::Function(_)) {
+                return None;
+            }
+            Some((name, format_value(&value, true)))
+        })
+        .collect();
+    globals.sort();
+    globals
+}
+
+pub(crate) fn format_value(value: &Value, preview_tables: bool) -> String {
+    match value {
+        Value::Nil => "nil".to_string(),
+        Value::Boolean(b) => b.to_string(),
+        Value::Integer(i) => i.to_string(),
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => quote(&s.to_string_lossy()),
+        Value::Table(table) if preview_tables => preview_table(table),
+        Value::Table(_) => "{…}".to_string(),
+        Value::Function(_) => "function".to_string(),
+        Value::Thread(_) => "thread".to_string(),
+        _ => "userdata".to_string(),
+    }
+}
+
+fn preview_table(table: &Table) -> String {
+    let mut entries = Vec::new();
+    let mut more = false;
+    for (key, value) in table.clone().pairs::<Value, Value>().flatten() {
+        if entries.len() == MAX_TABLE_PREVIEW {
+            more = true;
+            break;
+        }
+        let key = match &key {
+            Value::String(s) => s.to_string_lossy().to_string(),
+            other => format!("[{}]", format_value(other, false)),
+        };
+        entries.push(format!("{} = {}", key, format_value(&value, false)));
+    }
+    if more {
+        entries.push("…".to_string());
+    }
+    format!("{{{}}}", entries.join(", "))
+}
+
+fn quote(s: &str) -> String {
+    if s.chars().count() > MAX_STRING_PREVIEW {
+        format!("\"{}…\"", s.chars().take(MAX_STRING_PREVIEW).collect::<String>())
+    } else {
+        format!("\"{}\"", s)
+    }
+}
+
+fn registry_name(key: &CStr) -> &str {
+    key.to_str().unwrap()
+}
+
+unsafe extern "C-unwind" fn install_hook(state: *mut ffi::lua_State) -> c_int {
+    ffi::lua_sethook(state, Some(line_hook), ffi::LUA_MASKLINE, 0);
+    0
+}
+
+unsafe extern "C-unwind" fn remove_hook(state: *mut ffi::lua_State) -> c_int {
+    ffi::lua_sethook(state, None, 0, 0);
+    0
+}
+
+unsafe extern "C-unwind" fn line_hook(state: *mut ffi::lua_State, ar: *mut ffi::lua_Debug) {
+    if ffi::lua_checkstack(state, 8) == 0 {
+        return;
+    }
+    ffi::lua_getfield(state, ffi::LUA_REGISTRYINDEX, DEBUGGER_KEY.as_ptr());
+    let debugger = ffi::lua_touserdata(state, -1) as *const RefCell<DebuggerState>;
+    ffi::lua_pop(state, 1);
+    if debugger.is_null() {
+        return;
+    }
+    // SAFETY: the pointer was registered by the ScriptEngine that owns this state
+    // and outlives it; the engine never holds a borrow while running Lua
+    let Ok(mut debugger) = (*debugger).try_borrow_mut() else {
+        return;
+    };
+    if !debugger.is_armed() || ffi::lua_getinfo(state, c"Sl".as_ptr(), ar) == 0 {
+        return;
+    }
+
+    let file = chunk_file((*ar).source);
+    let line = (*ar).currentline.max(0) as u32;
+    ffi::lua_getfield(state, ffi::LUA_REGISTRYINDEX, ENTITY_KEY.as_ptr());
+    let entity = ffi::lua_tointeger(state, -1) as Entity;
+    ffi::lua_pop(state, 1);
+    ffi::lua_getfield(state, ffi::LUA_REGISTRYINDEX, THREAD_KEY.as_ptr());
+    let in_update = ffi::lua_tothread(state, -1) == state;
+    ffi::lua_pop(state, 1);
+    let depth = stack_depth(state);
+
+    if !debugger.should_break(entity, &file, line, in_update, depth) {
+        return;
+    }
+    let suspend = in_update && ffi::lua_isyieldable(state) != 0;
+    let frames = capture_frames(state);
+    debugger.hit(DebugBreak { entity, file, line, frames, suspended: suspend }, depth);
+    drop(debugger);
+
+    if suspend {
+        // Line hooks may yield (with no values); Lua unwinds once the hook returns
+        ffi::lua_yield(state, 0);
+    }
+}
+
+/// Chunk name as used by breakpoints: "@scripts/a.lua" -> "scripts/a.lua"
+unsafe fn chunk_file(source: *const c_char) -> String {
+    if source.is_null() {
+        return String::new();
+    }
+    let source = CStr::from_ptr(source).to_string_lossy();
+    source.strip_prefix('@').or_else(|| source.strip_prefix('=')).unwrap_or(&source).to_string()
+}
+
+unsafe fn stack_depth(state: *mut ffi::lua_State) -> u32 {
+    let mut ar: ffi::lua_Debug = std::mem::zeroed();
+    let mut depth = 0;
+    while ffi::lua_getstack(state, depth, &mut ar) != 0 {
+        depth += 1;
+    }
+    depth as u32
+}
+
+unsafe fn capture_frames(state: *mut ffi::lua_State) -> Vec<StackFrame> {
+    let mut frames = Vec::new();
+    for level in 0..MAX_FRAMES {
+        let mut ar: ffi::lua_Debug = std::mem::zeroed();
+        if ffi::lua_getstack(state, level, &mut ar) == 0 {
+            break;
+        }
+        ffi::lua_getinfo(state, c"Sln".as_ptr(), &mut ar);
+        let what = c_str(ar.what);
+        let function = if !ar.name.is_null() {
+            c_str(ar.name)
+        } else if what == "main" {
+            "main chunk".to_string()
+        } else if what == "Lua" {
+            // Coroutine bodies (Update) have no calling instruction to name them by
+            format!("function <{}:{}>", chunk_file(ar.source), ar.linedefined)
+        } else {
+            "?".to_string()
+        };
+
+        let mut locals = Vec::new();
+        for index in 1..=MAX_LOCALS {
+            let name = ffi::lua_getlocal(state, &ar, index);
+            if name.is_null() {
+                break;
+            }
+            let name = c_str(name);
+            // "(temporary)", "(for state)" and the like are VM internals
+            if !name.starts_with('(') {
+                locals.push((name, format_stack_value(state, -1, true)));
+            }
+            ffi::lua_pop(state, 1);
+        }
+
+        frames.push(StackFrame {
+            function,
+            file: if what == "C" { "[C]".to_string() } else { chunk_file(ar.source) },
+            line: ar.currentline.max(0) as u32,
+            locals,
+        });
+    }
+    frames
+}
+
+unsafe fn c_str(s: *const c_char) -> String {
+    if s.is_null() {
+        String::new()
+    } else {
+        CStr::from_ptr(s).to_string_lossy().into_owned()
+    }
+}
+
+/// Print the value at `index` without calling metamethods or converting it in place
+unsafe fn format_stack_value(state: *mut ffi::lua_State, index: c_int, preview_tables: bool) -> String {
+    let index = if index < 0 { ffi::lua_gettop(state) + index + 1 } else { index };
+    match ffi::lua_type(state, index) {
+        ffi::LUA_TNIL => "nil".to_string(),
+        ffi::LUA_TBOOLEAN => (ffi::lua_toboolean(state, index) != 0).to_string(),
+        ffi::LUA_TNUMBER if ffi::lua_isinteger(state, index) != 0 => ffi::lua_tointeger(state, index).to_string(),
+        ffi::LUA_TNUMBER => ffi::lua_tonumber(state, index).to_string(),
+        ffi::LUA_TSTRING => {
+            let mut len = 0;
+            let ptr = ffi::lua_tolstring(state, index, &mut len);
+            let bytes = std::slice::from_raw_parts(ptr as *const u8, len);
+            quote(&String::from_utf8_lossy(bytes))
+        }
+        ffi::LUA_TTABLE if preview_tables && ffi::lua_checkstack(state, 4) != 0 => {
+            let mut entries = Vec::new();
+            let mut more = false;
+            ffi::lua_pushnil(state);
+            while ffi::lua_next(state, index) != 0 {
+                if entries.len() == MAX_TABLE_PREVIEW {
+                    more = true;
+                    ffi::lua_pop(state, 2);
+                    break;
+                }
+                let key = if ffi::lua_type(state, -2) == ffi::LUA_TSTRING {
+                    c_str(ffi::lua_tolstring(state, -2, std::ptr::null_mut()))
+                } else {
+                    format!("[{}]", format_stack_value(state, -2, false))
+                };
+                entries.push(format!("{} = {}", key, format_stack_value(state, -1, false)));
+                ffi::lua_pop(state, 1);
+            }
+            if more {
+                entries.push("…".to_string());
+            }
+            format!("{{{}}}", entries.join(", "))
+        }
+        ffi::LUA_TTABLE => "{…}".to_string(),
+        ffi::LUA_TFUNCTION => "function".to_string(),
+        ffi::LUA_TTHREAD => "thread".to_string(),
+        _ => "userdata".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{global, run, script_engine, spawn_scripted};
+    use crate::ScriptEngine;
+    use ecs::World;
+
+    // Line numbers matter: bump's body is lines 3-4, Update's 7-9
+    const STEPPER: &str = r#"calls = 0
+local function bump()
+    calls = calls + 1
+    return calls
+end
+function Update(dt)
+    local a = bump()
+    after = a
+    done = true
+end
+"#;
+
+    fn breakpoint(line: u32) -> Breakpoint {
+        Breakpoint { file: "scripts/stepper.lua".to_string(), line }
+    }
+
+    /// An engine with a stepper entity whose Update is suspended at `line`
+    fn suspended_at(line: u32) -> (ScriptEngine, World, Entity) {
+        let mut script_engine = script_engine();
+        let mut world = World::new();
+        let stepper = spawn_scripted(&mut script_engine, &mut world, "stepper", STEPPER);
+        script_engine.set_breakpoints([breakpoint(line)]);
+        run(&mut script_engine, &mut world, stepper, 0.016);
+        (script_engine, world, stepper)
+    }
+
+    /// Give `command`, run the resumed Update and return where it stopped next
+    fn step(script_engine: &mut ScriptEngine, world: &mut World, entity: Entity, command: DebugCommand) -> Option<DebugBreak> {
+        assert!(script_engine.debug_command(command));
+        assert_eq!(script_engine.take_debug_resume(), Some(entity));
+        run(script_engine, world, entity, 0.016);
+        script_engine.take_debug_hit()
+    }
+
+    #[test]
+    fn test_breakpoint_suspends_update() {
+        let (mut script_engine, mut world, stepper) = suspended_at(7);
+
+        let hit = script_engine.take_debug_hit().unwrap();
+        assert_eq!((hit.entity, hit.file.as_str(), hit.line, hit.suspended), (stepper, "scripts/stepper.lua", 7, true));
+        assert!(script_engine.take_debug_hit().is_none());
+        assert_eq!(script_engine.debug_suspended_entity(), Some(stepper));
+
+        // Frames run while suspended don't restart Update
+        run(&mut script_engine, &mut world, stepper, 0.016);
+        assert_eq!(global(&script_engine, stepper, "calls").as_deref(), Some("0"));
+        assert_eq!(global(&script_engine, stepper, "after"), None);
+    }
+
+    #[test]
+    fn test_step_over_runs_the_call() {
+        let (mut script_engine, mut world, stepper) = suspended_at(7);
+
+        let hit = step(&mut script_engine, &mut world, stepper, DebugCommand::StepOver).unwrap();
+        assert_eq!((hit.line, hit.suspended), (8, true));
+        assert_eq!(global(&script_engine, stepper, "calls").as_deref(), Some("1"));
+        assert_eq!(global(&script_engine, stepper, "after"), None);
+    }
+
+    #[test]
+    fn test_step_into_enters_the_call() {
+        let (mut script_engine, mut world, stepper) = suspended_at(7);
+
+        let hit = step(&mut script_engine, &mut world, stepper, DebugCommand::StepInto).unwrap();
+        assert_eq!(hit.line, 3);
+        assert_eq!(hit.frames[0].function, "bump");
+        assert_eq!(hit.frames[1].line, 7);
+        assert_eq!(global(&script_engine, stepper, "calls").as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn test_step_out_returns_to_the_caller() {
+        let (mut script_engine, mut world, stepper) = suspended_at(3);
+        script_engine.set_breakpoints([]);
+
+        let hit = step(&mut script_engine, &mut world, stepper, DebugCommand::StepOut).unwrap();
+        assert_eq!(hit.line, 8);
+        assert_eq!(global(&script_engine, stepper, "calls").as_deref(), Some("1"));
+        assert_eq!(global(&script_engine, stepper, "after"), None);
+    }
+
+    #[test]
+    fn test_continue_finishes_update() {
+        let (mut script_engine, mut world, stepper) = suspended_at(7);
+
+        assert!(step(&mut script_engine, &mut world, stepper, DebugCommand::Continue).is_none());
+        assert_eq!(script_engine.debug_suspended_entity(), None);
+        assert_eq!(global(&script_engine, stepper, "after").as_deref(), Some("1"));
+        assert_eq!(global(&script_engine, stepper, "done").as_deref(), Some("true"));
+
+        // The breakpoint is still set, so the next frame stops there again
+        run(&mut script_engine, &mut world, stepper, 0.016);
+        assert_eq!(script_engine.take_debug_hit().map(|hit| hit.line), Some(7));
+    }
+
+    #[test]
+    fn test_removed_entity_drops_its_break() {
+        let (mut script_engine, mut world, stepper) = suspended_at(7);
+
+        script_engine.remove_entity_state(stepper, &mut world);
+        assert_eq!(script_engine.debug_suspended_entity(), None);
+        assert!(script_engine.debug_break().is_none());
+        assert!(script_engine.take_debug_hit().is_none());
+        assert!(!script_engine.debug_command(DebugCommand::Continue));
+        assert_eq!(script_engine.take_debug_resume(), None);
+    }
+}
@@ -0,0 +1,318 @@
+// Global event bus (Events.on / Events.off / Events.emit)
+//
+// Scripts subscribe by name; an emitted event is queued for every entity subscribed at
+// that moment and its handlers run inside that entity's next run_script() (before
+// Update), like UI events, with the full script API. The engine emits on the same bus
+// (trigger zones). Event data is a flat table of numbers, strings and booleans, copied
+// into each listener's own Lua state. While a listener's script is disabled its events
+// are dropped, except for handlers registered with { while_disabled = "buffer" }, which
+// get them once the script is enabled again.
+
+use ecs::Entity;
+use mlua::{Function, Lua, Table, Value};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::rc::Rc;
+
+// Per-state table of handlers: event name -> array of functions
+const LISTENERS_KEY: &str = "event_listeners";
+// Per-state table of handlers that buffer while disabled: event name -> { [function] = true }
+const BUFFERED_KEY: &str = "event_buffered";
+
+/// What a listener registration (Events.on, UI.on_*) does with events that arrive while
+/// its script is disabled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DisabledPolicy {
+    /// Skip them
+    #[default]
+    Drop,
+    /// Keep them and deliver them, in order, after OnEnable
+    Buffer,
+}
+
+impl DisabledPolicy {
+    /// The `while_disabled` field ("drop" or "buffer") of a registration's options table
+    pub(crate) fn from_options(function: &str, options: Option<Table>) -> mlua::Result<Self> {
+        let Some(name) = options.map(|options| options.get::<_, Option<String>>("while_disabled")).transpose()?.flatten() else {
+            return Ok(Self::Drop);
+        };
+        match name.as_str() {
+            "drop" => Ok(Self::Drop),
+            "buffer" => Ok(Self::Buffer),
+            other => Err(mlua::Error::RuntimeError(format!(
+                "{}: while_disabled must be \"drop\" or \"buffer\", got \"{}\"",
+                function, other
+            ))),
+        }
+    }
+}
+
+/// A field of an event's data table
+#[derive(Clone, Debug, PartialEq)]
+pub enum EventValue {
+    Bool(bool),
+    Integer(i64),
+    Number(f64),
+    String(String),
+}
+
+/// An event on the bus: `name` and its data fields
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScriptEvent {
+    pub name: String,
+    pub data: Vec<(String, EventValue)>,
+}
+
+impl ScriptEvent {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), data: Vec::new() }
+    }
+
+    pub fn with(mut self, field: impl Into<String>, value: EventValue) -> Self {
+        self.data.push((field.into(), value));
+        self
+    }
+}
+
+/// An event waiting for a listener's next run_script()
+struct PendingEvent {
+    event: ScriptEvent,
+    /// Arrived while the listener was disabled: only its buffering handlers get it
+    while_disabled: bool,
+}
+
+#[derive(Default)]
+pub struct ScriptEvents {
+    listeners: HashMap<String, BTreeSet<Entity>>,
+    /// (listener, event name) pairs with at least one buffering handler
+    buffering: HashSet<(Entity, String)>,
+    /// Listeners whose script is disabled
+    suspended: HashSet<Entity>,
+    pending: HashMap<Entity, Vec<PendingEvent>>,
+}
+
+impl ScriptEvents {
+    /// Queue an event for everything subscribed to its name (disabled listeners only
+    /// get it when they buffer it)
+    pub fn emit(&mut self, event: ScriptEvent) {
+        let Some(listeners) = self.listeners.get(&event.name) else {
+            return;
+        };
+        for &entity in listeners {
+            let while_disabled = self.suspended.contains(&entity);
+            if while_disabled && !self.buffering.contains(&(entity, event.name.clone())) {
+                continue;
+            }
+            self.pending.entry(entity).or_default().push(PendingEvent { event: event.clone(), while_disabled });
+        }
+    }
+
+    fn subscribe(&mut self, entity: Entity, name: &str, buffering: bool) {
+        self.listeners.entry(name.to_string()).or_default().insert(entity);
+        if buffering {
+            self.buffering.insert((entity, name.to_string()));
+        } else {
+            self.buffering.remove(&(entity, name.to_string()));
+        }
+    }
+
+    fn unsubscribe(&mut self, entity: Entity, name: &str) {
+        if let Some(listeners) = self.listeners.get_mut(name) {
+            listeners.remove(&entity);
+            if listeners.is_empty() {
+                self.listeners.remove(name);
+            }
+        }
+        self.buffering.remove(&(entity, name.to_string()));
+    }
+
+    /// The listener's script was disabled (or enabled again). Events already queued for
+    /// it count as arriving while disabled, so only buffering handlers keep them.
+    pub fn set_suspended(&mut self, entity: Entity, suspended: bool) {
+        if !suspended {
+            self.suspended.remove(&entity);
+            return;
+        }
+        self.suspended.insert(entity);
+        if let Some(pending) = self.pending.get_mut(&entity) {
+            let buffering = &self.buffering;
+            pending.retain_mut(|pending| {
+                pending.while_disabled = true;
+                buffering.contains(&(entity, pending.event.name.clone()))
+            });
+        }
+    }
+
+    fn take(&mut self, entity: Entity) -> Vec<PendingEvent> {
+        self.pending.remove(&entity).unwrap_or_default()
+    }
+
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.pending.remove(&entity);
+        self.suspended.remove(&entity);
+        self.buffering.retain(|(listener, _)| *listener != entity);
+        self.listeners.retain(|_, listeners| {
+            listeners.remove(&entity);
+            !listeners.is_empty()
+        });
+    }
+}
+
+fn listeners_table(lua: &Lua) -> mlua::Result<Table<'_>> {
+    lua.named_registry_value::<Table>(LISTENERS_KEY)
+}
+
+/// The `{ [function] = true }` set of `name`'s buffering handlers (created on demand)
+fn buffered_handlers<'lua>(lua: &'lua Lua, name: &str) -> mlua::Result<Table<'lua>> {
+    let buffered = lua.named_registry_value::<Table>(BUFFERED_KEY)?;
+    match buffered.get::<_, Option<Table>>(name)? {
+        Some(handlers) => Ok(handlers),
+        None => {
+            let handlers = lua.create_table()?;
+            buffered.set(name, handlers.clone())?;
+            Ok(handlers)
+        }
+    }
+}
+
+fn event_data(name: &str, data: Option<Table>) -> mlua::Result<Vec<(String, EventValue)>> {
+    let Some(data) = data else {
+        return Ok(Vec::new());
+    };
+    let mut fields = Vec::new();
+    for pair in data.pairs::<String, Value>() {
+        let (field, value) = pair?;
+        let value = match value {
+            Value::Boolean(b) => EventValue::Bool(b),
+            Value::Integer(i) => EventValue::Integer(i),
+            Value::Number(n) => EventValue::Number(n),
+            Value::String(s) => EventValue::String(s.to_str()?.to_string()),
+            other => {
+                return Err(mlua::Error::RuntimeError(format!(
+                    "Events.emit('{}'): field '{}' is a {}; event data can only hold numbers, strings and booleans",
+                    name, field, other.type_name()
+                )));
+            }
+        };
+        fields.push((field, value));
+    }
+    fields.sort_by(|a, b| a.0.cmp(&b.0));
+    Ok(fields)
+}
+
+/// Register the Events table for `owner`'s Lua state
+pub fn register_events_api(lua: &Lua, owner: Entity, events: Rc<RefCell<ScriptEvents>>) -> mlua::Result<()> {
+    lua.set_named_registry_value(LISTENERS_KEY, lua.create_table()?)?;
+    lua.set_named_registry_value(BUFFERED_KEY, lua.create_table()?)?;
+    let events_table = lua.create_table()?;
+
+    // Events.on(name, fn, { while_disabled = "drop" | "buffer" }?): fn(data) runs for
+    // every later emit of `name`
+    let bus = Rc::clone(&events);
+    events_table.set("on", lua.create_function(move |lua, (name, handler, options): (String, Function, Option<Table>)| {
+        let policy = DisabledPolicy::from_options("Events.on", options)?;
+        let buffered = buffered_handlers(lua, &name)?;
+        if policy == DisabledPolicy::Buffer {
+            buffered.set(handler.clone(), true)?;
+        }
+        let listeners = listeners_table(lua)?;
+        let handlers: Table = match listeners.get::<_, Option<Table>>(name.as_str())? {
+            Some(handlers) => handlers,
+            None => {
+                let handlers = lua.create_table()?;
+                listeners.set(name.as_str(), handlers.clone())?;
+                handlers
+            }
+        };
+        handlers.push(handler)?;
+        let buffering = !buffered.is_empty();
+        bus.borrow_mut().subscribe(owner, &name, buffering);
+        Ok(())
+    })?)?;
+
+    // Events.off(name, fn?): remove one handler, or all of this script's handlers for `name`
+    let bus = Rc::clone(&events);
+    events_table.set("off", lua.create_function(move |lua, (name, handler): (String, Option<Function>)| {
+        let listeners = listeners_table(lua)?;
+        let remaining: Vec<Function> = match (listeners.get::<_, Option<Table>>(name.as_str())?, handler) {
+            (Some(handlers), Some(handler)) => handlers
+                .sequence_values::<Function>()
+                .filter(|existing| existing.as_ref().map_or(true, |existing| *existing != handler))
+                .collect::<mlua::Result<_>>()?,
+            _ => Vec::new(),
+        };
+        let buffered = buffered_handlers(lua, &name)?;
+        if remaining.is_empty() {
+            listeners.set(name.as_str(), Value::Nil)?;
+            lua.named_registry_value::<Table>(BUFFERED_KEY)?.set(name.as_str(), Value::Nil)?;
+            bus.borrow_mut().unsubscribe(owner, &name);
+        } else {
+            // Forget the policy of handlers that are gone
+            let mut buffering = false;
+            for handler in &remaining {
+                buffering |= buffered.contains_key(handler.clone())?;
+            }
+            for pair in buffered.clone().pairs::<Function, bool>() {
+                let (handler, _) = pair?;
+                if !remaining.contains(&handler) {
+                    buffered.set(handler, Value::Nil)?;
+                }
+            }
+            listeners.set(name.as_str(), lua.create_sequence_from(remaining)?)?;
+            bus.borrow_mut().subscribe(owner, &name, buffering);
+        }
+        Ok(())
+    })?)?;
+
+    // Events.emit(name, data?): delivered to listeners from their next frame
+    events_table.set("emit", lua.create_function(move |_, (name, data): (String, Option<Table>)| {
+        let data = event_data(&name, data)?;
+        events.borrow_mut().emit(ScriptEvent { name, data });
+        Ok(())
+    })?)?;
+
+    lua.globals().set("Events", events_table)?;
+    Ok(())
+}
+
+/// Run `entity`'s queued event handlers (call inside its run_script scope)
+pub fn run_pending_events(events: &Rc<RefCell<ScriptEvents>>, lua: &Lua, entity: Entity) -> mlua::Result<()> {
+    // Take first: handlers may emit events of their own (delivered next frame)
+    let pending = events.borrow_mut().take(entity);
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let listeners = listeners_table(lua)?;
+    for PendingEvent { event, while_disabled } in pending {
+        let Some(handlers) = listeners.get::<_, Option<Table>>(event.name.as_str())? else {
+            continue;
+        };
+        // Copy the list: a handler may call Events.off
+        let mut handlers: Vec<Function> = handlers.sequence_values().collect::<mlua::Result<_>>()?;
+        if while_disabled {
+            let buffered = buffered_handlers(lua, &event.name)?;
+            let mut kept = Vec::new();
+            for handler in handlers {
+                if buffered.contains_key(handler.clone())? {
+                    kept.push(handler);
+                }
+            }
+            handlers = kept;
+        }
+        let data = lua.create_table()?;
+        for (field, value) in &event.data {
+            match value {
+                EventValue::Bool(b) => data.set(field.as_str(), *b)?,
+                EventValue::Integer(i) => data.set(field.as_str(), *i)?,
+                EventValue::Number(n) => data.set(field.as_str(), *n)?,
+                EventValue::String(s) => data.set(field.as_str(), s.as_str())?,
+            }
+        }
+        for handler in handlers {
+            if let Err(e) = handler.call::<_, ()>(data.clone()) {
+                log::error!("Event handler '{}' error for entity {}: {}", event.name, entity, e);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,357 @@
+// Global game state (Globals.set / get / increment / subscribe)
+//
+// One store shared by every entity's Lua state, kept across Scene.load and cleared when
+// play mode stops. Values are plain data - numbers, strings, booleans and tables of
+// them - kept as JSON and copied into a state on get. A change is queued for every
+// entity subscribed to the key; its callbacks run in that entity's next run_script()
+// (after bus events, before Update) with the new and the old value.
+
+use ecs::Entity;
+use mlua::{Function, Lua, Table, Value};
+use serde_json::Value as Json;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::rc::Rc;
+
+// Per-state table of callbacks: key -> array of functions
+const SUBSCRIBERS_KEY: &str = "global_subscribers";
+
+/// Tables nested deeper than this are rejected (usually a table that contains itself)
+const MAX_DEPTH: usize = 32;
+
+/// A value that changed, as delivered to a subscriber
+#[derive(Clone, Debug, PartialEq)]
+pub struct GlobalChange {
+    pub key: String,
+    /// New value (null once removed)
+    pub value: Json,
+    pub old: Json,
+}
+
+#[derive(Default)]
+pub struct GlobalState {
+    values: BTreeMap<String, Json>,
+    subscribers: HashMap<String, BTreeSet<Entity>>,
+    pending: HashMap<Entity, Vec<GlobalChange>>,
+}
+
+impl GlobalState {
+    pub fn get(&self, key: &str) -> Option<&Json> {
+        self.values.get(key)
+    }
+
+    /// Store `value` (null removes the key); subscribers hear about it if it changed
+    pub fn set(&mut self, key: &str, value: Json) {
+        let old = if value.is_null() {
+            self.values.remove(key)
+        } else {
+            self.values.insert(key.to_string(), value.clone())
+        }
+        .unwrap_or(Json::Null);
+        if old == value {
+            return;
+        }
+        let Some(subscribers) = self.subscribers.get(key) else {
+            return;
+        };
+        let change = GlobalChange { key: key.to_string(), value, old };
+        for &entity in subscribers {
+            self.pending.entry(entity).or_default().push(change.clone());
+        }
+    }
+
+    /// Every value, by key (the editor's Globals window)
+    pub fn values(&self) -> &BTreeMap<String, Json> {
+        &self.values
+    }
+
+    /// Number of entities subscribed to `key`
+    pub fn subscriber_count(&self, key: &str) -> usize {
+        self.subscribers.get(key).map_or(0, BTreeSet::len)
+    }
+
+    /// Replace every value (a loaded save game); subscribers hear about each change
+    pub fn restore(&mut self, values: BTreeMap<String, Json>) {
+        let removed: Vec<String> = self.values.keys().filter(|key| !values.contains_key(*key)).cloned().collect();
+        for key in removed {
+            self.set(&key, Json::Null);
+        }
+        for (key, value) in values {
+            self.set(&key, value);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.values.clear();
+        self.subscribers.clear();
+        self.pending.clear();
+    }
+
+    fn subscribe(&mut self, entity: Entity, key: &str) {
+        self.subscribers.entry(key.to_string()).or_default().insert(entity);
+    }
+
+    fn unsubscribe(&mut self, entity: Entity, key: &str) {
+        if let Some(subscribers) = self.subscribers.get_mut(key) {
+            subscribers.remove(&entity);
+            if subscribers.is_empty() {
+                self.subscribers.remove(key);
+            }
+        }
+    }
+
+    fn take(&mut self, entity: Entity) -> Vec<GlobalChange> {
+        self.pending.remove(&entity).unwrap_or_default()
+    }
+
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.pending.remove(&entity);
+        self.subscribers.retain(|_, subscribers| {
+            subscribers.remove(&entity);
+            !subscribers.is_empty()
+        });
+    }
+}
+
+/// Copy a Lua value into JSON; `path` names it in errors ("score", "inventory.items[2]")
+pub(crate) fn to_json(value: Value, path: &str, depth: usize) -> Result<Json, String> {
+    if depth > MAX_DEPTH {
+        return Err(format!("'{}' is nested too deeply (does a table contain itself?)", path));
+    }
+    match value {
+        Value::Nil => Ok(Json::Null),
+        Value::Boolean(b) => Ok(Json::Bool(b)),
+        Value::Integer(i) => Ok(Json::from(i)),
+        Value::Number(n) => serde_json::Number::from_f64(n)
+            .map(Json::Number)
+            .ok_or_else(|| format!("'{}' is {}; only finite numbers can be stored", path, n)),
+        Value::String(s) => s.to_str().map(|s| Json::String(s.to_string())).map_err(|e| e.to_string()),
+        Value::Table(table) => table_to_json(table, path, depth),
+        other => Err(format!(
+            "'{}' is a {}; values can only be numbers, strings, booleans and tables of them",
+            path, other.type_name()
+        )),
+    }
+}
+
+/// A table whose keys are 1..n becomes an array, one with string keys an object
+fn table_to_json(table: Table, path: &str, depth: usize) -> Result<Json, String> {
+    let length = table.raw_len();
+    let pairs: Vec<(Value, Value)> = table.pairs::<Value, Value>().collect::<mlua::Result<_>>().map_err(|e| e.to_string())?;
+    let is_list = !pairs.is_empty()
+        && length == pairs.len()
+        && pairs.iter().all(|(key, _)| matches!(key, Value::Integer(i) if *i >= 1 && *i as usize <= length));
+    if is_list {
+        let mut items = vec![Json::Null; length];
+        for (key, value) in pairs {
+            let Value::Integer(index) = key else {
+                unreachable!("checked above");
+            };
+            let index = index as usize;
+            items[index - 1] = to_json(value, &format!("{}[{}]", path, index), depth + 1)?;
+        }
+        return Ok(Json::Array(items));
+    }
+
+    let mut fields = serde_json::Map::new();
+    for (key, value) in pairs {
+        let Value::String(key) = key else {
+            return Err(format!(
+                "'{}' has a {} key; tables need string keys, or be a list numbered from 1",
+                path, key.type_name()
+            ));
+        };
+        let key = key.to_str().map_err(|e| e.to_string())?.to_string();
+        let value = to_json(value, &format!("{}.{}", path, key), depth + 1)?;
+        fields.insert(key, value);
+    }
+    Ok(Json::Object(fields))
+}
+
+/// Copy a stored value into `lua` (whole numbers come back as integers)
+pub(crate) fn to_lua<'lua>(lua: &'lua Lua, value: &Json) -> mlua::Result<Value<'lua>> {
+    Ok(match value {
+        Json::Null => Value::Nil,
+        Json::Bool(b) => Value::Boolean(*b),
+        Json::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Number(n.as_f64().unwrap_or(0.0)),
+        },
+        Json::String(s) => Value::String(lua.create_string(s)?),
+        Json::Array(items) => {
+            let table = lua.create_table_with_capacity(items.len(), 0)?;
+            for item in items {
+                table.push(to_lua(lua, item)?)?;
+            }
+            Value::Table(table)
+        }
+        Json::Object(fields) => {
+            let table = lua.create_table_with_capacity(0, fields.len())?;
+            for (key, item) in fields {
+                table.set(key.as_str(), to_lua(lua, item)?)?;
+            }
+            Value::Table(table)
+        }
+    })
+}
+
+fn subscribers_table(lua: &Lua) -> mlua::Result<Table<'_>> {
+    lua.named_registry_value::<Table>(SUBSCRIBERS_KEY)
+}
+
+/// Register the Globals table for `owner`'s Lua state
+pub fn register_globals_api(lua: &Lua, owner: Entity, state: Rc<RefCell<GlobalState>>) -> mlua::Result<()> {
+    lua.set_named_registry_value(SUBSCRIBERS_KEY, lua.create_table()?)?;
+    let globals_table = lua.create_table()?;
+
+    // Globals.set(key, value): nil removes the key
+    let store = Rc::clone(&state);
+    globals_table.set("set", lua.create_function(move |_, (key, value): (String, Value)| {
+        let value = to_json(value, &key, 0)
+            .map_err(|e| mlua::Error::RuntimeError(format!("Globals.set('{}'): {}", key, e)))?;
+        store.borrow_mut().set(&key, value);
+        Ok(())
+    })?)?;
+
+    // Globals.get(key, default?): a copy of the value, or `default` when it isn't set
+    let store = Rc::clone(&state);
+    globals_table.set("get", lua.create_function(move |lua, (key, default): (String, Value)| {
+        match store.borrow().get(&key) {
+            Some(value) => to_lua(lua, value),
+            None => Ok(default),
+        }
+    })?)?;
+
+    // Globals.increment(key, amount = 1): add to a number (missing counts as 0), returns
+    // the new value
+    let store = Rc::clone(&state);
+    globals_table.set("increment", lua.create_function(move |lua, (key, amount): (String, Option<Value>)| {
+        let error = |message: String| mlua::Error::RuntimeError(format!("Globals.increment('{}'): {}", key, message));
+        let current = store.borrow().get(&key).cloned().unwrap_or_else(|| Json::from(0));
+        let Json::Number(current) = current else {
+            return Err(error(format!("the value isn't a number ({})", current)));
+        };
+        let float = |n: f64| serde_json::Number::from_f64(n)
+            .map(Json::Number)
+            .ok_or_else(|| error(format!("the result is {}", n)));
+        let as_float = current.as_f64().unwrap_or(0.0);
+        // Integers stay integers; anything with a fraction becomes a float
+        let value = match (current.as_i64(), amount) {
+            (Some(current), None) => Json::from(current.saturating_add(1)),
+            (Some(current), Some(Value::Integer(amount))) => Json::from(current.saturating_add(amount)),
+            (None, None) => float(as_float + 1.0)?,
+            (None, Some(Value::Integer(amount))) => float(as_float + amount as f64)?,
+            (_, Some(Value::Number(amount))) => float(as_float + amount)?,
+            (_, Some(other)) => return Err(error(format!("the amount must be a number, got a {}", other.type_name()))),
+        };
+        let result = to_lua(lua, &value)?;
+        store.borrow_mut().set(&key, value);
+        Ok(result)
+    })?)?;
+
+    // Globals.subscribe(key, fn): fn(value, old) runs for every later change of `key`
+    let store = Rc::clone(&state);
+    globals_table.set("subscribe", lua.create_function(move |lua, (key, callback): (String, Function)| {
+        let subscribers = subscribers_table(lua)?;
+        let callbacks: Table = match subscribers.get::<_, Option<Table>>(key.as_str())? {
+            Some(callbacks) => callbacks,
+            None => {
+                let callbacks = lua.create_table()?;
+                subscribers.set(key.as_str(), callbacks.clone())?;
+                callbacks
+            }
+        };
+        callbacks.push(callback)?;
+        store.borrow_mut().subscribe(owner, &key);
+        Ok(())
+    })?)?;
+
+    // Globals.unsubscribe(key, fn?): remove one callback, or all of this script's for `key`
+    globals_table.set("unsubscribe", lua.create_function(move |lua, (key, callback): (String, Option<Function>)| {
+        let subscribers = subscribers_table(lua)?;
+        let remaining: Vec<Function> = match (subscribers.get::<_, Option<Table>>(key.as_str())?, callback) {
+            (Some(callbacks), Some(callback)) => callbacks
+                .sequence_values::<Function>()
+                .filter(|existing| existing.as_ref().map_or(true, |existing| *existing != callback))
+                .collect::<mlua::Result<_>>()?,
+            _ => Vec::new(),
+        };
+        if remaining.is_empty() {
+            subscribers.set(key.as_str(), Value::Nil)?;
+            state.borrow_mut().unsubscribe(owner, &key);
+        } else {
+            subscribers.set(key.as_str(), lua.create_sequence_from(remaining)?)?;
+        }
+        Ok(())
+    })?)?;
+
+    lua.globals().set("Globals", globals_table)?;
+    Ok(())
+}
+
+/// Run `entity`'s callbacks for values changed since it last ran (call inside its
+/// run_script scope)
+pub fn run_pending_changes(state: &Rc<RefCell<GlobalState>>, lua: &Lua, entity: Entity) -> mlua::Result<()> {
+    // Take first: callbacks may change values themselves (delivered next frame)
+    let pending = state.borrow_mut().take(entity);
+    if pending.is_empty() {
+        return Ok(());
+    }
+    let subscribers = subscribers_table(lua)?;
+    for change in pending {
+        let Some(callbacks) = subscribers.get::<_, Option<Table>>(change.key.as_str())? else {
+            continue;
+        };
+        // Copy the list: a callback may unsubscribe
+        let callbacks: Vec<Function> = callbacks.sequence_values().collect::<mlua::Result<_>>()?;
+        for callback in callbacks {
+            let args = (to_lua(lua, &change.value)?, to_lua(lua, &change.old)?);
+            if let Err(e) = callback.call::<_, ()>(args) {
+                log::error!("Globals.subscribe('{}') callback error for entity {}: {}", change.key, entity, e);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{global, run, script_engine, spawn_scripted};
+    use ecs::World;
+
+    const SCORER: &str = r#"
+        function Start()
+            Globals.subscribe("score", function(value, old) heard = tostring(old) .. ">" .. value end)
+            Globals.increment("score", 5)
+            Globals.set("inventory", { items = { "sword", "key" }, gold = 12 })
+            rejected = not pcall(Globals.set, "callback", function() end)
+        end
+    "#;
+
+    #[test]
+    fn test_globals_outlive_the_scripts_that_set_them() {
+        let mut script_engine = script_engine();
+        let mut world = World::new();
+        let scorer = spawn_scripted(&mut script_engine, &mut world, "scorer", SCORER);
+
+        // The change made in Start reaches the subscriber on its next run, before Update
+        assert_eq!(global(&script_engine, scorer, "heard"), None);
+        run(&mut script_engine, &mut world, scorer, 0.016);
+        assert!(global(&script_engine, scorer, "heard").is_some_and(|heard| heard.contains("nil>5")));
+        assert_eq!(global(&script_engine, scorer, "rejected").as_deref(), Some("true"));
+
+        // What a scene load does to the scripts of the old scene
+        script_engine.queue_destroy(scorer);
+        script_engine.apply_pending_destroys(&mut world);
+        {
+            let state = script_engine.global_state.borrow();
+            assert_eq!(state.get("score"), Some(&serde_json::json!(5)));
+            assert_eq!(state.get("inventory"), Some(&serde_json::json!({ "items": ["sword", "key"], "gold": 12 })));
+            assert!(state.get("callback").is_none());
+            assert_eq!(state.subscriber_count("score"), 0);
+        }
+
+        script_engine.clear_global_state();
+        assert!(script_engine.global_state.borrow().values().is_empty());
+    }
+}
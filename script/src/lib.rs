@@ -1,122 +1,1071 @@
 // Force rebuild - UI commands fix v2
 use mlua::{Lua, Function, Table, Value};
 use anyhow::Result;
-use ecs::{World, Entity, EntityTag};
+use ecs::{World, Entity, EntityOrdered, EntityTag};
 use input::{InputSystem, Key, MouseButton, GamepadButton};
 use std::rc::Rc;
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use engine_core::assets::AssetLoader;
+use engine_core::debug_draw::{DebugCategory, DebugDrawHandle, DebugDraws};
+use engine_core::frame_clock::GameTime;
+use engine_core::project_settings::PhysicsSettings;
+use engine_core::random::EngineRng;
+use engine_core::save_data::SaveData;
+use navigation::Navigation;
 use std::sync::Arc;
 
 #[cfg(feature = "rapier")]
 mod rapier_bindings;
 
-// Debug draw structures (simple versions for Lua)
-#[derive(Clone, Debug)]
-pub struct DebugLine {
-    pub start: [f32; 3],
-    pub end: [f32; 3],
-    pub color: [f32; 4], // RGBA
-    pub duration: f32,
+mod timers;
+pub use timers::{CurveHandle, ScriptTimers, TimerHandle, TweenProperty};
+
+mod ui_elements;
+pub use ui_elements::UIElementParams;
+
+mod ui_events;
+pub use ui_events::{InputFieldSnapshot, UIEventKind, UIScriptEvent, UIScriptEvents};
+
+mod events;
+pub use events::{DisabledPolicy, EventValue, ScriptEvent, ScriptEvents};
+
+mod coroutines;
+pub use coroutines::{CoroutineHandle, ScriptCoroutines};
+
+pub mod math;
+
+mod globals;
+pub use globals::{GlobalChange, GlobalState};
+
+mod pools;
+pub use pools::ScriptPools;
+
+pub mod components;
+
+mod scene_preload;
+pub use scene_preload::{LoadingScreenConfig, PreloadEntry, PreloadState, SceneHandle, ScenePreloads};
+
+mod repl;
+pub use repl::ReplLine;
+
+mod save_game;
+pub use save_game::{SaveGameFile, SAVES_FOLDER};
+
+pub mod modules;
+use modules::ModuleRegistry;
+
+mod debugger;
+pub use debugger::{Breakpoint, DebugBreak, DebugCommand, StackFrame};
+use debugger::DebuggerState;
+
+#[cfg(test)]
+mod test_support;
+
+// Physics.move_and_slide request (Lua -> Engine)
+#[derive(Clone, Copy, Debug)]
+pub struct CharacterMoveCommand {
+    pub entity: Entity,
+    pub delta: [f32; 2],
 }
 
 // UI command types for Lua -> Engine communication
 #[derive(Clone, Debug)]
 pub enum UICommand {
     LoadPrefab { path: String },
-    ActivatePrefab { path: String, instance_name: String },
+    /// `owner` is the activating script's entity: the prefab's Lua bindings run in its script
+    ActivatePrefab { path: String, instance_name: String, owner: Entity },
     DeactivatePrefab { instance_name: String },
     SetText { element_path: String, text: String },
     SetImageFill { element_path: String, fill_amount: f32 },
     SetColor { element_path: String, r: f32, g: f32, b: f32, a: f32 },
     ShowElement { element_path: String },
     HideElement { element_path: String },
+    /// UI.create_*: `element` goes under `parent_path` ("instance" or "instance/a/b")
+    CreateElement { parent_path: String, element: Box<ui::UIPrefabElement>, style_class: Option<String>, color: Option<ui::Color> },
+    DestroyElement { element_path: String },
+    /// Reorder among siblings (draw and layout order): `None` = last, on top
+    SetSiblingIndex { element_path: String, index: Option<usize> },
+    /// Change the hover tooltip text (empty text removes the tooltip)
+    SetTooltip { element_path: String, text: String },
+    /// Turn a toggle on/off (toggle groups enforced like clicks)
+    SetToggle { element_path: String, is_on: bool },
+    /// Replace an input field's text (no value-changed event)
+    SetInputText { element_path: String, text: String },
+    SetPlaceholder { element_path: String, text: String },
+    SetCharacterValidation { element_path: String, validation: ui::CharacterValidation },
+    /// UI.on_submit / UI.on_value_changed / UI.on_drop: run `callback` in `owner`'s script when `event`
+    /// fires (`while_disabled`: what happens to events that fire while that script is disabled)
+    AddEventListener { element_path: String, event: UIEventKind, owner: Entity, callback: String, while_disabled: DisabledPolicy },
+    /// UI.set_text_params: `{name}` values for a localized text
+    SetTextParams { element_path: String, params: HashMap<String, String> },
+    /// Loc.set_language
+    SetLanguage { language: String },
+    /// UI.show_modal: load and activate `path` as `instance_name` on top of the modal stack
+    ShowModal { path: String, instance_name: String, owner: Entity, options: ui::ModalOptions },
+    /// UI.close_modal: close the top modal, passing `result` to its on_closed callback
+    CloseModal { result: String },
+    /// Canvas group alpha of an element (adds a group if it has none); stops a running fade
+    SetGroupAlpha { element_path: String, alpha: f32 },
+    SetGroupInteractable { element_path: String, interactable: bool },
+    /// UI.fade_group: tween the canvas group alpha to `to`, then run `on_complete` in `owner`'s script
+    FadeGroup { element_path: String, to: f32, duration: f32, easing: ui::EasingFunction, owner: Entity, on_complete: Option<String> },
+    /// Enable/disable one element's control (disabled look, no clicks or hover)
+    SetInteractable { element_path: String, interactable: bool },
+    /// Set a slider's value (clamped, no value-changed event); the knob smooths to it
+    SetSlider { element_path: String, value: f32 },
+}
+
+impl UICommand {
+    /// Element path the command addresses (the parent for create_*), if any
+    pub fn element_path_mut(&mut self) -> Option<&mut String> {
+        match self {
+            UICommand::LoadPrefab { .. }
+            | UICommand::ActivatePrefab { .. }
+            | UICommand::DeactivatePrefab { .. }
+            | UICommand::SetLanguage { .. }
+            | UICommand::ShowModal { .. }
+            | UICommand::CloseModal { .. } => None,
+            UICommand::CreateElement { parent_path, .. } => Some(parent_path),
+            UICommand::SetText { element_path, .. }
+            | UICommand::SetImageFill { element_path, .. }
+            | UICommand::SetColor { element_path, .. }
+            | UICommand::ShowElement { element_path }
+            | UICommand::HideElement { element_path }
+            | UICommand::DestroyElement { element_path }
+            | UICommand::SetSiblingIndex { element_path, .. }
+            | UICommand::SetTooltip { element_path, .. }
+            | UICommand::SetToggle { element_path, .. }
+            | UICommand::SetInputText { element_path, .. }
+            | UICommand::SetPlaceholder { element_path, .. }
+            | UICommand::SetCharacterValidation { element_path, .. }
+            | UICommand::AddEventListener { element_path, .. }
+            | UICommand::SetTextParams { element_path, .. }
+            | UICommand::SetGroupAlpha { element_path, .. }
+            | UICommand::SetGroupInteractable { element_path, .. }
+            | UICommand::FadeGroup { element_path, .. }
+            | UICommand::SetInteractable { element_path, .. }
+            | UICommand::SetSlider { element_path, .. } => Some(element_path),
+        }
+    }
+}
+
+// A UI command that failed when its batch was applied (Engine -> Lua, UI.get_last_errors)
+#[derive(Clone, Debug, PartialEq)]
+pub struct UICommandError {
+    /// UI function that queued the command ("set_text")
+    pub command: &'static str,
+    /// Element path, instance, prefab file or language the command referred to
+    pub path: String,
+    /// `path` matched no element or instance
+    pub not_found: bool,
+    /// Closest existing path, for typos
+    pub suggestion: Option<String>,
+    pub message: String,
+}
+
+impl std::fmt::Display for UICommandError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UI.{}('{}'): {}", self.command, self.path, self.message)?;
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean '{}'?)", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+// Input command types for Lua -> Engine communication (rebinding and rumble need `&mut InputSystem`)
+#[derive(Clone, Debug)]
+pub enum InputCommand {
+    StartRebind { player: usize, action: String, slot: Option<usize> },
+    CancelRebind,
+    AcceptRebindConflict,
+    Rumble { gamepad: usize, strong: f32, weak: f32, duration: f32 },
+}
+
+// Window command types for Lua -> Engine communication (applied between frames)
+#[derive(Clone, Debug, PartialEq)]
+pub enum WindowCommand {
+    /// Borderless fullscreen on the window's current monitor
+    SetFullscreen(bool),
+    /// Windowed size in physical pixels
+    SetResolution { width: u32, height: u32 },
+    SetVsync(bool),
+    SetTitle(String),
+    SetCursorVisible(bool),
+    /// Keep the cursor in place (or inside the window where locking isn't supported)
+    SetCursorLocked(bool),
+}
+
+// Capture command types for Lua -> Engine communication (Screenshot.capture / save_gif)
+#[derive(Clone, Debug, PartialEq)]
+pub enum CaptureCommand {
+    /// `path` None saves under screenshots/ with a timestamp; `crop_letterbox` None uses
+    /// the project setting
+    Screenshot { path: Option<String>, crop_letterbox: Option<bool> },
+    SaveGif { path: Option<String> },
+}
+
+// Display state synced from the engine (Engine -> Lua, Screen.get_* / Cursor.is_*)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DisplayInfo {
+    /// Window size in physical pixels
+    pub size: [u32; 2],
+    pub fullscreen: bool,
+    pub vsync: bool,
+    /// Video modes of the current monitor, largest first, without repeats
+    pub resolutions: Vec<[u32; 2]>,
+    pub cursor_visible: bool,
+    pub cursor_locked: bool,
+}
+
+// Scene command types for Lua -> Engine communication (run at the end of the frame)
+#[derive(Clone, Debug, PartialEq)]
+pub enum SceneCommand {
+    Load { path: String },
+    LoadAdditive { path: String },
+    Unload { path: String },
+    DontDestroyOnLoad { entity: Entity },
+    /// Copies made by Duplicate() this frame (root first); their scripts get Awake/Start
+    Duplicated { original: Entity, copies: Vec<Entity> },
+    /// SaveGame.save / SaveGame.load
+    SaveGame { slot: String },
+    LoadGame { slot: String },
+    /// Scene.preload: start loading `path` in the background (id of its ScenePreloads entry)
+    Preload { id: u64, path: String },
+    /// Scene.activate: swap the preloaded scene in once it is ready
+    Activate { id: u64 },
+    /// handle:cancel(): stop the preload and release what it loaded
+    CancelPreload { id: u64 },
+}
+
+/// What run_frame_callback() runs inside the world scope
+#[derive(Clone, Copy)]
+enum FramePhase<'a> {
+    Update,
+    LateUpdate,
+    /// Console code; its output goes to `output`
+    Eval { code: &'a str, output: &'a RefCell<Vec<ReplLine>> },
+}
+
+// Where the game is drawn, for screen -> world conversion in Lua (Engine -> Lua)
+#[derive(Clone, Debug, Default)]
+pub struct ScreenView {
+    /// Game area in mouse coordinates: [x, y, width, height] (the editor Game View is offset)
+    pub rect: [f32; 4],
+    /// Import pivot per texture, for sprites without their own
+    pub texture_pivots: HashMap<String, [f32; 2]>,
+    /// Project sorting layers, so the picked sprite is the one drawn on top
+    pub sorting_layers: ecs::sorting::SortingLayers,
+    /// Pixels per reference unit of each active UI instance (UI.world_to_canvas)
+    pub canvas_scales: HashMap<String, f32>,
+    /// Project aspect policy; `rect` is already letterboxed by it
+    pub aspect: engine_core::project_settings::AspectPolicy,
+}
+
+impl ScreenView {
+    /// Canvas space of a UI instance; without one, the first active instance by name
+    /// (one unit per pixel when no UI is active)
+    fn canvas_space(&self, instance: Option<&str>) -> Option<ui::CanvasSpace> {
+        let scale = match instance {
+            Some(instance) => *self.canvas_scales.get(instance)?,
+            None => self.canvas_scales.iter().min_by(|a, b| a.0.cmp(b.0)).map_or(1.0, |(_, scale)| *scale),
+        };
+        Some(ui::CanvasSpace::new(self.rect, scale))
+    }
+
+    /// The main camera, framed by the aspect policy like the renderer draws it
+    fn main_camera(&self, world: &ecs::World) -> Option<(ecs::Camera, ecs::Transform)> {
+        let (_, camera, transform) = ecs::picking::main_camera(world)?;
+        Some((ecs::picking::framed_camera(camera, &self.aspect, self.rect), transform))
+    }
 }
 
 pub struct ScriptEngine {
     lua: Lua,
     // Per-entity Lua states for proper lifecycle management
     entity_states: HashMap<Entity, Lua>,
-    // Store ground state for Rapier (temporary solution)
-    pub ground_states: HashMap<Entity, bool>,
-    // Debug draw queue (accessible from Lua scripts)
-    pub debug_lines: Rc<RefCell<Vec<DebugLine>>>,
+    // Entities whose Start() has run (Start waits until the entity is active in the hierarchy)
+    started: HashSet<Entity>,
+    // Last enabled state (Script enabled and active in the hierarchy) each script saw
+    // (drives OnEnable/OnDisable)
+    enabled_states: HashMap<Entity, bool>,
+    // destroy_entity() calls, applied by apply_pending_destroys() at the end of the frame
+    pending_destroys: RefCell<Vec<Entity>>,
+    // Physics.move_and_slide requests (Lua -> Engine, applied by the active physics backend)
+    pub character_moves: Rc<RefCell<Vec<CharacterMoveCommand>>>,
+    // Floor/wall/ceiling flags of each entity's last applied move (Engine -> Lua)
+    pub character_states: Rc<RefCell<HashMap<Entity, physics::CharacterMove>>>,
+    // Debug draw queue (Lua debug_draw_*), shared with whoever renders it
+    pub debug_draw: DebugDrawHandle,
     // UI command queue (Lua -> Engine)
     pub ui_commands: Rc<RefCell<Vec<UICommand>>>,
+    // Input command queue (Lua -> Engine)
+    pub input_commands: Rc<RefCell<Vec<InputCommand>>>,
+    // Scene command queue (Lua -> Engine)
+    pub scene_commands: Rc<RefCell<Vec<SceneCommand>>>,
+    // Scene preloads and their progress (Lua -> Engine -> Lua)
+    pub scene_preloads: Rc<RefCell<ScenePreloads>>,
+    // Window command queue (Lua -> Engine)
+    pub window_commands: Rc<RefCell<Vec<WindowCommand>>>,
+    // Screenshot / GIF command queue (Lua -> Engine)
+    pub capture_commands: Rc<RefCell<Vec<CaptureCommand>>>,
+    // Window and cursor state synced from the engine (Screen / Cursor queries)
+    pub display: Rc<RefCell<DisplayInfo>>,
+    // UI events for script listeners (Engine -> Lua)
+    pub ui_events: Rc<RefCell<UIScriptEvents>>,
+    // Global event bus (Events.on / Events.emit, trigger zones)
+    pub events: Rc<RefCell<ScriptEvents>>,
+    // Game state shared by every script and kept across scene loads (Globals.set / get)
+    pub global_state: Rc<RefCell<GlobalState>>,
+    // Failures of the last applied UI command batch (UI.get_last_errors)
+    pub ui_errors: Rc<RefCell<Vec<UICommandError>>>,
+    // Input field values synced from the engine (element path -> state)
+    pub ui_inputs: Rc<RefCell<HashMap<String, InputFieldSnapshot>>>,
+    // Active localization language synced from the engine (Loc.get_language)
+    pub language: Rc<RefCell<String>>,
+    // Game area on screen and texture pivots synced from the engine (Input.pick_entity)
+    pub screen_view: Rc<RefCell<ScreenView>>,
+    // Project physics settings synced from the engine (layer matrix for Physics.overlap_*)
+    pub physics_settings: Rc<RefCell<PhysicsSettings>>,
     // Asset Loader for loading scripts/modules
     pub asset_loader: Arc<dyn AssetLoader>,
+    // Where require() finds modules (shared by every Lua state)
+    modules: Rc<RefCell<ModuleRegistry>>,
+    // Persistent key-value save data (shared by all entity Lua states)
+    pub save_data: Rc<RefCell<SaveData>>,
+    // Timers and tweens created from Lua (Timer.after / Tween.to)
+    pub timers: Rc<RefCell<ScriptTimers>>,
+    // Coroutines started from Lua (Coroutine.start / Coroutine.wait)
+    pub coroutines: Rc<RefCell<ScriptCoroutines>>,
+    // Entity pools created from Lua (Pool.create / Pool.spawn / Pool.release)
+    pub pools: Rc<RefCell<ScriptPools>>,
+    // Custom component definitions from Lua (Component.define), shared by every Lua state
+    pub components: Rc<RefCell<ecs::ComponentRegistry>>,
+    // Editor play-mode pause state (exposed as Time.is_paused())
+    paused: Rc<Cell<bool>>,
+    // Game clock advanced by the engine each frame (Time.delta, Time.time_scale, ...)
+    time: Rc<RefCell<GameTime>>,
+    // Engine RNG behind math.random (seeded per run so replays are deterministic)
+    rng: Rc<RefCell<EngineRng>>,
+    // get_position / get_velocity return Vec2 userdata instead of {x, y} tables
+    // (Project Settings > Scripting, off for projects written against tables)
+    vec2_values: bool,
+    // Navigation grid behind the Nav table (also drawn by the editor overlay)
+    pub navigation: Rc<RefCell<Navigation>>,
+    // Breakpoints and the current break, shared with every entity state's line hook
+    debugger: Rc<RefCell<DebuggerState>>,
+    // Update coroutines suspended at a breakpoint (at most one in practice)
+    suspended_updates: HashMap<Entity, mlua::RegistryKey>,
+    // State the editor console evaluates in when no entity is targeted (created on first use)
+    repl_state: Option<Lua>,
 }
 
 impl ScriptEngine {
     pub fn new(asset_loader: Arc<dyn AssetLoader>) -> Result<Self> {
         let lua = Lua::new();
-        
-        // Register custom require searcher for the main Lua state
-        Self::register_require_searcher(&lua, asset_loader.clone())?;
+
+        // require() for the main Lua state
+        let modules = Rc::new(RefCell::new(ModuleRegistry::new(asset_loader.clone())));
+        modules::register_require(&lua, &modules)?;
+
+        // Main state uses the engine RNG too (no unseeded randomness anywhere)
+        let rng = Rc::new(RefCell::new(EngineRng::from_time()));
+        Self::register_random_api(&lua, Rc::clone(&rng))?;
+
+        // Component.define for the main state, which runs scripts/components.lua
+        let component_registry = Rc::new(RefCell::new(ecs::ComponentRegistry::new()));
+        components::register_component_define_api(&lua, Rc::clone(&component_registry))?;
 
         Ok(Self { 
             lua,
             entity_states: HashMap::new(),
-            ground_states: HashMap::new(),
-            debug_lines: Rc::new(RefCell::new(Vec::new())),
+            started: HashSet::new(),
+            enabled_states: HashMap::new(),
+            pending_destroys: RefCell::new(Vec::new()),
+            character_moves: Rc::new(RefCell::new(Vec::new())),
+            character_states: Rc::new(RefCell::new(HashMap::new())),
+            debug_draw: DebugDraws::handle(),
             ui_commands: Rc::new(RefCell::new(Vec::new())),
+            input_commands: Rc::new(RefCell::new(Vec::new())),
+            scene_commands: Rc::new(RefCell::new(Vec::new())),
+            scene_preloads: Rc::new(RefCell::new(ScenePreloads::default())),
+            window_commands: Rc::new(RefCell::new(Vec::new())),
+            capture_commands: Rc::new(RefCell::new(Vec::new())),
+            display: Rc::new(RefCell::new(DisplayInfo { cursor_visible: true, ..Default::default() })),
+            ui_events: Rc::new(RefCell::new(UIScriptEvents::default())),
+            events: Rc::new(RefCell::new(ScriptEvents::default())),
+            global_state: Rc::new(RefCell::new(GlobalState::default())),
+            ui_errors: Rc::new(RefCell::new(Vec::new())),
+            ui_inputs: Rc::new(RefCell::new(HashMap::new())),
+            language: Rc::new(RefCell::new(String::new())),
+            screen_view: Rc::new(RefCell::new(ScreenView::default())),
+            physics_settings: Rc::new(RefCell::new(PhysicsSettings::default())),
             asset_loader,
+            modules,
+            save_data: Rc::new(RefCell::new(SaveData::new())),
+            timers: Rc::new(RefCell::new(ScriptTimers::new())),
+            coroutines: Rc::new(RefCell::new(ScriptCoroutines::new())),
+            pools: Rc::new(RefCell::new(ScriptPools::default())),
+            components: component_registry,
+            paused: Rc::new(Cell::new(false)),
+            time: Rc::new(RefCell::new(GameTime::default())),
+            rng,
+            vec2_values: false,
+            navigation: Rc::new(RefCell::new(Navigation::default())),
+            debugger: Rc::new(RefCell::new(DebuggerState::default())),
+            suspended_updates: HashMap::new(),
+            repl_state: None,
         })
     }
     
-    // Helper to register AssetLoader-based require searcher
-    fn register_require_searcher(lua: &Lua, asset_loader: Arc<dyn AssetLoader>) -> Result<()> {
-        let globals = lua.globals();
-        let package: Table = globals.get("package")?;
-        // Try "searchers" (Lua 5.2+) first, then "loaders" (Lua 5.1/LuaJIT)
-        let searchers: Table = if let Ok(s) = package.get("searchers") {
-            s
-        } else {
-            package.get("loaders")?
+    // Helper to register the Time table (queries that stay valid outside Update).
+    // Time.delta, unscaled_delta, time and frame_count read the game clock;
+    // Time.time_scale can also be assigned (0 freezes the game).
+    fn register_time_api(lua: &Lua, paused: Rc<Cell<bool>>, time: Rc<RefCell<GameTime>>) -> Result<()> {
+        let time_table = lua.create_table()?;
+        time_table.set("is_paused", lua.create_function(move |_, ()| Ok(paused.get()))?)?;
+
+        let metatable = lua.create_table()?;
+        let clock = Rc::clone(&time);
+        metatable.set("__index", lua.create_function(move |_, (_, key): (Table, String)| {
+            let time = *clock.borrow();
+            Ok(match key.as_str() {
+                "delta" => Value::Number(time.delta as f64),
+                "unscaled_delta" => Value::Number(time.unscaled_delta as f64),
+                "time" => Value::Number(time.time),
+                "frame_count" => Value::Integer(time.frame_count as i64),
+                "time_scale" => Value::Number(time.time_scale as f64),
+                _ => Value::Nil,
+            })
+        })?)?;
+        metatable.set("__newindex", lua.create_function(move |_, (_, key, value): (Table, String, Value)| {
+            let scale = match (key.as_str(), value) {
+                ("time_scale", Value::Number(scale)) => scale as f32,
+                ("time_scale", Value::Integer(scale)) => scale as f32,
+                ("time_scale", other) => {
+                    return Err(mlua::Error::RuntimeError(format!("Time.time_scale must be a number, got {}", other.type_name())));
+                }
+                _ => return Err(mlua::Error::RuntimeError(format!("Time.{} is read-only", key))),
+            };
+            if !time.borrow_mut().set_time_scale(scale) {
+                return Err(mlua::Error::RuntimeError(format!("Time.time_scale must be 0 or more, got {}", scale)));
+            }
+            Ok(())
+        })?)?;
+        time_table.set_metatable(Some(metatable));
+
+        lua.globals().set("Time", time_table)?;
+        Ok(())
+    }
+
+    // Helper to register the Profiler table (scopes show up in the editor's profiler overlay).
+    // `end` is a Lua keyword, so scripts call Profiler["end"]() or its alias Profiler.finish().
+    fn register_profiler_api(lua: &Lua) -> Result<()> {
+        let profiler_table = lua.create_table()?;
+        profiler_table.set("begin", lua.create_function(|_, name: String| {
+            profiler::push_scope(&name);
+            Ok(())
+        })?)?;
+        let end_scope = lua.create_function(|_, ()| Ok(profiler::pop_scope()))?;
+        profiler_table.set("end", end_scope.clone())?;
+        profiler_table.set("finish", end_scope)?;
+        lua.globals().set("Profiler", profiler_table)?;
+        Ok(())
+    }
+
+    // Helper to replace math.random with the engine RNG.
+    // Scripts must not use any other source of randomness (os.time, os.clock, ...) for
+    // gameplay, or replays will desync. math.randomseed is a no-op: the seed is owned by
+    // the engine (ScriptEngine::set_random_seed) and stored in replay files.
+    fn register_random_api(lua: &Lua, rng: Rc<RefCell<EngineRng>>) -> Result<()> {
+        let math: Table = lua.globals().get("math")?;
+        math.set("random", lua.create_function(move |_, args: mlua::Variadic<i64>| {
+            let mut rng = rng.borrow_mut();
+            // Same argument rules as Lua's math.random
+            match args.as_slice() {
+                [] => Ok(Value::Number(rng.next_f64())),
+                [max] if *max >= 1 => Ok(Value::Integer(rng.range_i64(1, *max))),
+                [min, max] if min <= max => Ok(Value::Integer(rng.range_i64(*min, *max))),
+                [_] | [_, _] => Err(mlua::Error::RuntimeError("bad argument to 'random' (interval is empty)".to_string())),
+                _ => Err(mlua::Error::RuntimeError("wrong number of arguments to 'random'".to_string())),
+            }
+        })?)?;
+        math.set("randomseed", lua.create_function(|_, _: mlua::MultiValue| {
+            log::warn!("math.randomseed is ignored; the engine seeds math.random for each run");
+            Ok(())
+        })?)?;
+        Ok(())
+    }
+
+    // Helper to register the Nav table (grid pathfinding, see the navigation crate).
+    // Nav.find_path returns a list of {x, y} waypoints ending at the target, or nil.
+    fn register_nav_api(lua: &Lua, navigation: Rc<RefCell<Navigation>>) -> Result<()> {
+        let nav_table = lua.create_table()?;
+
+        let nav = Rc::clone(&navigation);
+        nav_table.set("find_path", lua.create_function(move |lua, (from_x, from_y, to_x, to_y): (f32, f32, f32, f32)| {
+            let Some(path) = nav.borrow_mut().find_path([from_x, from_y], [to_x, to_y]) else {
+                return Ok(Value::Nil);
+            };
+            let waypoints = lua.create_table()?;
+            for (i, point) in path.iter().enumerate() {
+                let waypoint = lua.create_table()?;
+                waypoint.set("x", point[0])?;
+                waypoint.set("y", point[1])?;
+                waypoints.set(i + 1, waypoint)?;
+            }
+            Ok(Value::Table(waypoints))
+        })?)?;
+
+        let nav = Rc::clone(&navigation);
+        nav_table.set("is_walkable", lua.create_function(move |_, (x, y): (f32, f32)| {
+            Ok(nav.borrow().is_walkable(x, y))
+        })?)?;
+
+        lua.globals().set("Nav", nav_table)?;
+        Ok(())
+    }
+
+    // Lua value of a custom tile property (Map.get_tile_property)
+    fn tile_property_to_lua<'lua>(lua: &'lua Lua, property: &ecs::TileProperty) -> mlua::Result<Value<'lua>> {
+        Ok(match property {
+            ecs::TileProperty::Bool(b) => Value::Boolean(*b),
+            ecs::TileProperty::Int(n) => Value::Integer(*n),
+            ecs::TileProperty::Float(f) => Value::Number(*f),
+            ecs::TileProperty::String(s) => Value::String(lua.create_string(s)?),
+        })
+    }
+
+    /// Array of query hits other than `self_entity`, optionally only those tagged `tag`
+    fn overlap_results_to_lua<'lua>(lua: &'lua Lua, world: &World, self_entity: Entity, hits: Vec<Entity>, tag: Option<String>) -> mlua::Result<Table<'lua>> {
+        let tag_matches = |hit: &Entity| match (&tag, world.tags.get(hit)) {
+            (None, _) => true,
+            (Some(tag), Some(EntityTag::Player)) => tag == "Player",
+            (Some(tag), Some(EntityTag::Item)) => tag == "Item",
+            (Some(_), None) => false,
         };
-        
-        let loader_arc = asset_loader;
-        let custom_loader = lua.create_function(move |lua, name: String| {
-             // Convert dotted package name to path (e.g. "game.utils" -> "scripts/game/utils.lua")
-             let path = format!("scripts/{}.lua", name.replace('.', "/"));
-             
-             // Block on async load
-             let result = pollster::block_on(loader_arc.load_text(&path));
-             
-             match result {
-                 Ok(content) => {
-                     let chunk = lua.load(&content).set_name(&path);
-                     let loader_func = chunk.into_function()?; 
-                     Ok(Value::Function(loader_func))
-                 },
-                 Err(e) => {
-                     // Lua expects an error string if module not found
-                     Ok(Value::String(lua.create_string(&format!("\n\tAssetLoader: failed to load '{}' ({})", path, e))?))
-                 }
-             }
-        })?;
-        
-        // Append to searchers
-        let len = searchers.len()?;
-        searchers.set(len + 1, custom_loader)?;
+        lua.create_sequence_from(hits.into_iter().filter(|&hit| hit != self_entity).filter(tag_matches))
+    }
+
+    fn register_loc_api(lua: &Lua, ui_commands: Rc<RefCell<Vec<UICommand>>>, language: Rc<RefCell<String>>) -> Result<()> {
+        let loc_table = lua.create_table()?;
+
+        loc_table.set("set_language", lua.create_function(move |_, language: String| {
+            ui_commands.borrow_mut().push(UICommand::SetLanguage { language });
+            Ok(())
+        })?)?;
+
+        // Updated by the engine each frame, so a switch shows up on the next frame
+        loc_table.set("get_language", lua.create_function(move |_, ()| {
+            Ok(language.borrow().clone())
+        })?)?;
+
+        lua.globals().set("Loc", loc_table)?;
         Ok(())
     }
 
-    
-    /// Get and clear debug lines (called by engine after rendering)
-    pub fn take_debug_lines(&self) -> Vec<DebugLine> {
-        self.debug_lines.borrow_mut().drain(..).collect()
+    // Helper to register the Scene table and DontDestroyOnLoad. Paths are relative to the
+    // project ("levels/level2.scene"); the engine switches scenes at the end of the frame.
+    fn register_scene_api(lua: &Lua, scene_commands: Rc<RefCell<Vec<SceneCommand>>>, scene_preloads: Rc<RefCell<ScenePreloads>>) -> Result<()> {
+        let scene_table = lua.create_table()?;
+
+        let commands = Rc::clone(&scene_commands);
+        scene_table.set("load", lua.create_function(move |_, path: String| {
+            commands.borrow_mut().push(SceneCommand::Load { path });
+            Ok(())
+        })?)?;
+
+        let commands = Rc::clone(&scene_commands);
+        scene_table.set("load_additive", lua.create_function(move |_, path: String| {
+            commands.borrow_mut().push(SceneCommand::LoadAdditive { path });
+            Ok(())
+        })?)?;
+
+        let commands = Rc::clone(&scene_commands);
+        scene_table.set("unload", lua.create_function(move |_, path: String| {
+            commands.borrow_mut().push(SceneCommand::Unload { path });
+            Ok(())
+        })?)?;
+
+        // Background loading: Scene.preload / Scene.activate / Scene.set_loading_screen
+        scene_preload::register_preload_api(lua, &scene_table, Rc::clone(&scene_commands), scene_preloads)?;
+
+        lua.globals().set("Scene", scene_table)?;
+
+        // Keep an entity (and its children) through Scene.load
+        lua.globals().set("DontDestroyOnLoad", lua.create_function(move |_, entity: Entity| {
+            scene_commands.borrow_mut().push(SceneCommand::DontDestroyOnLoad { entity });
+            Ok(())
+        })?)?;
+        Ok(())
+    }
+
+    // Helper to register the Screen and Cursor tables (options menus, mouse-look).
+    // Setters are queued and applied between frames; getters report the state the
+    // engine synced at the start of the frame, so a change shows up on the next frame.
+    fn register_window_api(lua: &Lua, window_commands: Rc<RefCell<Vec<WindowCommand>>>, display: Rc<RefCell<DisplayInfo>>) -> Result<()> {
+        let screen_table = lua.create_table()?;
+
+        let commands = Rc::clone(&window_commands);
+        screen_table.set("set_fullscreen", lua.create_function(move |_, fullscreen: bool| {
+            commands.borrow_mut().push(WindowCommand::SetFullscreen(fullscreen));
+            Ok(())
+        })?)?;
+
+        let commands = Rc::clone(&window_commands);
+        screen_table.set("set_resolution", lua.create_function(move |_, (width, height): (u32, u32)| {
+            if width == 0 || height == 0 {
+                return Err(mlua::Error::RuntimeError(format!("invalid resolution {}x{}", width, height)));
+            }
+            commands.borrow_mut().push(WindowCommand::SetResolution { width, height });
+            Ok(())
+        })?)?;
+
+        let commands = Rc::clone(&window_commands);
+        screen_table.set("set_vsync", lua.create_function(move |_, vsync: bool| {
+            commands.borrow_mut().push(WindowCommand::SetVsync(vsync));
+            Ok(())
+        })?)?;
+
+        let commands = Rc::clone(&window_commands);
+        screen_table.set("set_title", lua.create_function(move |_, title: String| {
+            commands.borrow_mut().push(WindowCommand::SetTitle(title));
+            Ok(())
+        })?)?;
+
+        let info = Rc::clone(&display);
+        screen_table.set("get_resolutions", lua.create_function(move |lua, ()| {
+            let resolutions = lua.create_table()?;
+            for (i, [width, height]) in info.borrow().resolutions.iter().enumerate() {
+                let resolution = lua.create_table()?;
+                resolution.set("width", *width)?;
+                resolution.set("height", *height)?;
+                resolutions.set(i + 1, resolution)?;
+            }
+            Ok(resolutions)
+        })?)?;
+
+        let info = Rc::clone(&display);
+        screen_table.set("get_resolution", lua.create_function(move |_, ()| {
+            let [width, height] = info.borrow().size;
+            Ok((width, height))
+        })?)?;
+
+        let info = Rc::clone(&display);
+        screen_table.set("is_fullscreen", lua.create_function(move |_, ()| Ok(info.borrow().fullscreen))?)?;
+
+        let info = Rc::clone(&display);
+        screen_table.set("get_vsync", lua.create_function(move |_, ()| Ok(info.borrow().vsync))?)?;
+
+        lua.globals().set("Screen", screen_table)?;
+
+        let cursor_table = lua.create_table()?;
+
+        let commands = Rc::clone(&window_commands);
+        cursor_table.set("set_visible", lua.create_function(move |_, visible: bool| {
+            commands.borrow_mut().push(WindowCommand::SetCursorVisible(visible));
+            Ok(())
+        })?)?;
+
+        cursor_table.set("set_locked", lua.create_function(move |_, locked: bool| {
+            window_commands.borrow_mut().push(WindowCommand::SetCursorLocked(locked));
+            Ok(())
+        })?)?;
+
+        let info = Rc::clone(&display);
+        cursor_table.set("is_visible", lua.create_function(move |_, ()| Ok(info.borrow().cursor_visible))?)?;
+
+        cursor_table.set("is_locked", lua.create_function(move |_, ()| Ok(display.borrow().cursor_locked))?)?;
+
+        lua.globals().set("Cursor", cursor_table)?;
+        Ok(())
+    }
+
+    // Helper to register the Screenshot table. Captures are queued and taken from the
+    // frame being rendered; the file is written in the background and the result (or
+    // the error) is logged.
+    fn register_screenshot_api(lua: &Lua, capture_commands: Rc<RefCell<Vec<CaptureCommand>>>) -> Result<()> {
+        let screenshot_table = lua.create_table()?;
+
+        // Screenshot.capture(path?, { crop_letterbox = bool }?)
+        let commands = Rc::clone(&capture_commands);
+        screenshot_table.set("capture", lua.create_function(move |_, (path, options): (Option<String>, Option<Table>)| {
+            let crop_letterbox = match options {
+                Some(options) => options.get::<_, Option<bool>>("crop_letterbox")?,
+                None => None,
+            };
+            commands.borrow_mut().push(CaptureCommand::Screenshot { path, crop_letterbox });
+            Ok(())
+        })?)?;
+
+        // Screenshot.save_gif(path?): the last few seconds, as recorded for F9
+        screenshot_table.set("save_gif", lua.create_function(move |_, path: Option<String>| {
+            capture_commands.borrow_mut().push(CaptureCommand::SaveGif { path });
+            Ok(())
+        })?)?;
+
+        lua.globals().set("Screenshot", screenshot_table)?;
+        Ok(())
+    }
+
+    // Helper to register the Save table (PlayerPrefs-style persistent storage)
+    fn register_save_api(lua: &Lua, save_data: Rc<RefCell<SaveData>>) -> Result<()> {
+        let save_table = lua.create_table()?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("set_int", lua.create_function(move |_, (key, value): (String, i64)| {
+            data.borrow_mut().set_int(key, value);
+            Ok(())
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("set_float", lua.create_function(move |_, (key, value): (String, f64)| {
+            data.borrow_mut().set_float(key, value);
+            Ok(())
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("set_string", lua.create_function(move |_, (key, value): (String, String)| {
+            data.borrow_mut().set_string(key, value);
+            Ok(())
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("set_bool", lua.create_function(move |_, (key, value): (String, bool)| {
+            data.borrow_mut().set_bool(key, value);
+            Ok(())
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("get_int", lua.create_function(move |_, (key, default): (String, Option<i64>)| {
+            Ok(data.borrow().get_int(&key, default.unwrap_or(0)))
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("get_float", lua.create_function(move |_, (key, default): (String, Option<f64>)| {
+            Ok(data.borrow().get_float(&key, default.unwrap_or(0.0)))
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("get_string", lua.create_function(move |_, (key, default): (String, Option<String>)| {
+            Ok(data.borrow().get_string(&key, default.as_deref().unwrap_or("")))
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("get_bool", lua.create_function(move |_, (key, default): (String, Option<bool>)| {
+            Ok(data.borrow().get_bool(&key, default.unwrap_or(false)))
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("has_key", lua.create_function(move |_, key: String| {
+            Ok(data.borrow().has_key(&key))
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("delete", lua.create_function(move |_, key: String| {
+            Ok(data.borrow_mut().delete(&key))
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("delete_all", lua.create_function(move |_, ()| {
+            data.borrow_mut().clear();
+            Ok(())
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("save", lua.create_function(move |_, ()| {
+            match data.borrow_mut().save() {
+                Ok(()) => Ok(true),
+                Err(e) => {
+                    log::error!("[Lua Save] Failed to write save data: {}", e);
+                    Ok(false)
+                }
+            }
+        })?)?;
+
+        let data = Rc::clone(&save_data);
+        save_table.set("load", lua.create_function(move |_, ()| {
+            match data.borrow_mut().load() {
+                Ok(()) => Ok(true),
+                Err(e) => {
+                    log::error!("[Lua Save] Failed to read save data: {}", e);
+                    Ok(false)
+                }
+            }
+        })?)?;
+
+        lua.globals().set("Save", save_table)?;
+        Ok(())
+    }
+
+    /// Set where require() finds project modules: scripts/ first, then each of
+    /// `lib_paths` (project-relative). With a project root, modules are read from disk
+    /// and reload when their file changes (see reload_changed_modules); without one
+    /// they go through the asset loader. Called when play mode starts (editor) or when
+    /// the game boots (player), before any script loads.
+    pub fn configure_modules(&mut self, project_root: Option<&std::path::Path>, lib_paths: &[String]) {
+        self.modules.borrow_mut().configure(project_root, lib_paths);
+    }
+
+    /// Whether get_position(_of) / get_velocity(_of) return Vec2 userdata (true) or the
+    /// {x, y, z} tables older scripts index (false, the default)
+    pub fn set_vec2_values(&mut self, enabled: bool) {
+        self.vec2_values = enabled;
+    }
+
+    /// Run the project's component definitions (scripts/components.lua) and bring the
+    /// world's custom components up to them. Returns what had to change in the world
+    /// (fields added or dropped since the scene was saved) and any definition error.
+    pub fn load_component_definitions(&mut self, world: &mut World) -> Vec<String> {
+        let mut warnings = Vec::new();
+        if let Err(e) = components::require_definitions(&self.lua, &self.modules) {
+            warnings.push(format!("Failed to load component definitions: {}", e));
+        }
+        warnings.extend(self.components.borrow().conform_world(world));
+        warnings
+    }
+
+    /// Drop modules whose file changed from every Lua state's cache, so the next require
+    /// loads the new version. Returns their names.
+    pub fn reload_changed_modules(&self) -> Vec<String> {
+        let changed = self.modules.borrow_mut().changed_modules();
+        for name in &changed {
+            self.unload_module(name);
+        }
+        changed
+    }
+
+    /// Drop one module from every Lua state's cache
+    pub fn invalidate_module(&self, name: &str) {
+        self.modules.borrow_mut().forget(name);
+        self.unload_module(name);
+    }
+
+    fn unload_module(&self, name: &str) {
+        for lua in std::iter::once(&self.lua).chain(self.entity_states.values()) {
+            if let Err(e) = modules::unload(lua, name) {
+                log::warn!("Failed to unload Lua module '{}': {}", name, e);
+            }
+        }
+    }
+
+    /// Point the Save API at a project's save file and load it.
+    /// Called when play mode starts (editor) or when the game boots (player).
+    pub fn open_save_data(&mut self, project_name: &str) -> Result<()> {
+        let mut data = SaveData::for_project(project_name);
+        let result = data.load();
+        // Replace contents in place so Lua closures keep sharing the same Rc
+        *self.save_data.borrow_mut() = data;
+        result
+    }
+
+    /// File of a SaveGame slot (None until open_save_data points Save at a project)
+    pub fn save_game_path(&self, slot: &str) -> Option<std::path::PathBuf> {
+        save_game::slot_path(&self.save_data.borrow(), slot)
+    }
+
+    /// Flush pending Save changes to disk (e.g. when play mode stops)
+    pub fn flush_save_data(&self) -> Result<()> {
+        let mut data = self.save_data.borrow_mut();
+        if data.is_dirty() {
+            data.save()?;
+        }
+        Ok(())
+    }
+
+    /// Set the pause state reported to scripts by Time.is_paused()
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused.set(paused);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    /// Advance the game clock by a frame of `unscaled_delta` real seconds and return the
+    /// scaled delta for scripts, physics and animations
+    pub fn advance_time(&self, unscaled_delta: f32) -> f32 {
+        self.time.borrow_mut().advance(unscaled_delta)
+    }
+
+    pub fn time(&self) -> GameTime {
+        *self.time.borrow()
+    }
+
+    pub fn time_scale(&self) -> f32 {
+        self.time.borrow().time_scale
+    }
+
+    /// Start the game clock over (time 0, frame 0, scale 1)
+    pub fn reset_time(&mut self) {
+        *self.time.borrow_mut() = GameTime::default();
+    }
+
+    /// Bytes held by each entity's Lua state (mlua `used_memory`), by entity
+    pub fn lua_memory_usage(&self) -> Vec<(Entity, usize)> {
+        let mut usage: Vec<(Entity, usize)> = self.entity_states.iter()
+            .map(|(entity, lua)| (*entity, lua.used_memory()))
+            .collect();
+        usage.sort_unstable();
+        usage
+    }
+
+    /// Restart math.random from `seed` (call before scripts load for a reproducible run)
+    pub fn set_random_seed(&mut self, seed: u64) {
+        self.rng.borrow_mut().reseed(seed);
+    }
+
+    /// Seed math.random was last started with
+    pub fn random_seed(&self) -> u64 {
+        self.rng.borrow().seed()
+    }
+
+    /// Replace the breakpoints (lines of `scripts/...lua` chunks) checked by every entity state
+    pub fn set_breakpoints(&mut self, breakpoints: impl IntoIterator<Item = Breakpoint>) {
+        self.debugger.borrow_mut().set_breakpoints(breakpoints);
+        self.refresh_debug_hooks();
+    }
+
+    /// Latest breakpoint hit (suspended, or reported while passing through)
+    pub fn debug_break(&self) -> Option<DebugBreak> {
+        self.debugger.borrow().current().cloned()
+    }
+
+    /// Breakpoint hit since the last call, once
+    pub fn take_debug_hit(&self) -> Option<DebugBreak> {
+        self.debugger.borrow_mut().take_hit()
+    }
+
+    /// Entity whose Update is suspended at a breakpoint (the simulation should stay paused)
+    pub fn debug_suspended_entity(&self) -> Option<Entity> {
+        self.debugger.borrow().suspended_entity()
+    }
+
+    /// Continue or step from the suspended break. The entity's Update continues in its
+    /// next run_script() call (see `take_debug_resume`).
+    pub fn debug_command(&mut self, command: DebugCommand) -> bool {
+        let accepted = self.debugger.borrow_mut().command(command);
+        self.refresh_debug_hooks();
+        accepted
+    }
+
+    /// Entity whose suspended Update should run again now that a command was given, once
+    pub fn take_debug_resume(&self) -> Option<Entity> {
+        let entity = self.debugger.borrow_mut().take_resume()?;
+        self.suspended_updates.contains_key(&entity).then_some(entity)
+    }
+
+    /// Printed non-function globals of an entity's script (libraries and engine APIs left out)
+    pub fn debug_globals(&self, entity: Entity) -> Vec<(String, String)> {
+        self.entity_states.get(&entity).map(debugger::globals).unwrap_or_default()
+    }
+
+    /// Drop the current break, stepping and suspended Updates (e.g. when leaving play mode)
+    pub fn clear_debug_session(&mut self) {
+        self.debugger.borrow_mut().reset();
+        self.suspended_updates.clear();
+        self.refresh_debug_hooks();
+    }
+
+    /// Line hooks cost a check per line, so they are only installed while needed
+    fn refresh_debug_hooks(&self) {
+        let armed = self.debugger.borrow().is_armed();
+        for (entity, lua) in &self.entity_states {
+            if let Err(e) = debugger::set_hook_enabled(lua, armed) {
+                log::error!("Failed to update debug hook for entity {}: {}", entity, e);
+            }
+        }
+    }
+
+    /// Sync the navigation grid with the world's tilemaps and colliders (only changed sources are re-rasterized)
+    pub fn update_navigation(&self, world: &World) {
+        self.navigation.borrow_mut().update(world);
+    }
+
+    /// Send Lua debug draws to a shared queue (the editor's scene view renders and expires them)
+    pub fn set_debug_draw(&mut self, handle: DebugDrawHandle) {
+        self.debug_draw = handle;
     }
     
     /// Get and clear UI commands (called by engine to process UI updates)
     pub fn take_ui_commands(&self) -> Vec<UICommand> {
         self.ui_commands.borrow_mut().drain(..).collect()
     }
+
+    /// Get and clear input commands (called by engine after running scripts)
+    /// Queue fired UI events; each runs in its owner's next run_script()
+    pub fn queue_ui_events(&self, events: Vec<UIScriptEvent>) {
+        let mut ui_events = self.ui_events.borrow_mut();
+        for event in events {
+            ui_events.queue(event);
+        }
+    }
+
+    /// Emit an event on the global bus; listeners get it in their next run_script()
+    pub fn emit_event(&self, event: ScriptEvent) {
+        self.events.borrow_mut().emit(event);
+    }
+
+    /// Replace the failures UI.get_last_errors reports (one batch's worth, possibly none)
+    pub fn sync_ui_errors(&self, errors: Vec<UICommandError>) {
+        *self.ui_errors.borrow_mut() = errors;
+    }
+
+    /// Replace the input field values UI.get_input_text reads
+    pub fn sync_input_fields(&self, fields: HashMap<String, InputFieldSnapshot>) {
+        *self.ui_inputs.borrow_mut() = fields;
+    }
+
+    /// Set the language Loc.get_language reports
+    pub fn sync_language(&self, language: &str) {
+        if *self.language.borrow() != language {
+            *self.language.borrow_mut() = language.to_string();
+        }
+    }
+
+    /// Set where the game is drawn for Input.pick_entity / Input.get_mouse_world_position
+    pub fn sync_screen_view(&self, view: ScreenView) {
+        *self.screen_view.borrow_mut() = view;
+    }
+
+    /// Set the project physics settings whose layer matrix filters Physics.overlap_box / overlap_circle
+    pub fn sync_physics_settings(&self, settings: &PhysicsSettings) {
+        *self.physics_settings.borrow_mut() = settings.clone();
+    }
+
+    pub fn take_input_commands(&self) -> Vec<InputCommand> {
+        self.input_commands.borrow_mut().drain(..).collect()
+    }
+
+    /// Get and clear Screen / Cursor changes (applied by the engine between frames)
+    pub fn take_window_commands(&self) -> Vec<WindowCommand> {
+        self.window_commands.borrow_mut().drain(..).collect()
+    }
+
+    /// Get and clear Screenshot.capture / save_gif calls
+    pub fn take_capture_commands(&self) -> Vec<CaptureCommand> {
+        self.capture_commands.borrow_mut().drain(..).collect()
+    }
+
+    /// Set the window state Screen.get_resolution / is_fullscreen and Cursor.is_* report
+    pub fn sync_display(&self, info: DisplayInfo) {
+        *self.display.borrow_mut() = info;
+    }
+
+    /// Get and clear scene commands (called by engine at the end of the frame)
+    pub fn take_scene_commands(&self) -> Vec<SceneCommand> {
+        self.scene_commands.borrow_mut().drain(..).collect()
+    }
     
-    /// Set ground state for entity (called by engine with Rapier result)
-    pub fn set_ground_state(&mut self, entity: Entity, is_grounded: bool) {
-        self.ground_states.insert(entity, is_grounded);
+    /// Get and clear Physics.move_and_slide requests, in call order (applied before the physics step)
+    pub fn take_character_moves(&self) -> Vec<CharacterMoveCommand> {
+        self.character_moves.borrow_mut().drain(..).collect()
+    }
+
+    /// Store the result of an applied move for Physics.is_on_floor / is_on_wall / is_on_ceiling
+    pub fn set_character_move(&self, entity: Entity, result: physics::CharacterMove) {
+        self.character_states.borrow_mut().insert(entity, result);
     }
 
     pub fn exec(&self, src: &str) -> Result<()> {
@@ -124,17 +1073,83 @@ impl ScriptEngine {
         Ok(())
     }
 
+    /// A Lua state with the engine API that lives outside the world scope (require, Save,
+    /// Time, Timer, Events, Globals, ...); `owner` gets its timer and event callbacks
+    fn new_lua_state(&self, owner: Entity) -> Result<Lua> {
+        let lua = Lua::new();
+
+        // require() for project modules and the engine library (cached per state)
+        modules::register_require(&lua, &self.modules)?;
+
+        // Persistent Save API (shared store, usable from Awake onwards)
+        Self::register_save_api(&lua, Rc::clone(&self.save_data))?;
+
+        // Time API (pause state is shared with the engine)
+        Self::register_time_api(&lua, Rc::clone(&self.paused), Rc::clone(&self.time))?;
+
+        // Profiler scopes (no-ops unless the profiler overlay is open)
+        Self::register_profiler_api(&lua)?;
+
+        // Deterministic math.random (shared engine RNG)
+        Self::register_random_api(&lua, Rc::clone(&self.rng))?;
+
+        // Native Vec2 and Mathf (Mathf.random_range uses the engine RNG too)
+        math::register_math_api(&lua, Rc::clone(&self.rng), Rc::clone(&self.time))?;
+
+        // Grid pathfinding (shared navigation grid)
+        Self::register_nav_api(&lua, Rc::clone(&self.navigation))?;
+
+        // Localization (language switches go through the UI command queue)
+        Self::register_loc_api(&lua, Rc::clone(&self.ui_commands), Rc::clone(&self.language))?;
+
+        // Scene switching (queued, runs at the end of the frame)
+        Self::register_scene_api(&lua, Rc::clone(&self.scene_commands), Rc::clone(&self.scene_preloads))?;
+
+        // Window and cursor control (queued, applied between frames)
+        Self::register_window_api(&lua, Rc::clone(&self.window_commands), Rc::clone(&self.display))?;
+
+        // Save games (queued with the scene commands)
+        save_game::register_save_game_api(&lua, Rc::clone(&self.scene_commands), Rc::clone(&self.save_data))?;
+
+        // Custom component definitions (shared registry)
+        components::register_component_define_api(&lua, Rc::clone(&self.components))?;
+
+        // Screenshots and GIF clips (queued, taken from the next rendered frame)
+        Self::register_screenshot_api(&lua, Rc::clone(&self.capture_commands))?;
+
+        // Timer and Tween API (handles persist across frames)
+        timers::register_timer_api(&lua, owner, Rc::clone(&self.timers))?;
+
+        // Coroutines (resumed before Update, after timer callbacks)
+        coroutines::register_coroutine_api(&lua, owner, Rc::clone(&self.coroutines))?;
+
+        // Global event bus (handlers run before Update)
+        events::register_events_api(&lua, owner, Rc::clone(&self.events))?;
+
+        // Global game state (change callbacks run before Update, after bus events)
+        globals::register_globals_api(&lua, owner, Rc::clone(&self.global_state))?;
+
+        Ok(lua)
+    }
+
     /// Load a script for a specific entity (Unity-style with backward compatibility)
     /// This creates a separate Lua state for each entity to properly manage lifecycle
     pub fn load_script_for_entity(&mut self, entity: Entity, content: &str, world: &mut World) -> Result<()> {
+        profiler::profile_scope!("lua.load_script");
         // Create a new Lua state for this entity
-        let lua = Lua::new();
-        
-        // Register custom require searcher for this entity's Lua state
-        Self::register_require_searcher(&lua, self.asset_loader.clone())?;
+        let lua = self.new_lua_state(entity)?;
+
+        // Breakpoints (the hook only runs while the debugger is armed)
+        debugger::register_state(&lua, entity, &self.debugger)?;
+        if self.debugger.borrow().is_armed() {
+            debugger::set_hook_enabled(&lua, true)?;
+        }
         
-        // Load the script content
-        lua.load(content).exec()?;
+        // Load the script content, named by file so errors read "scripts/player.lua:12: ..."
+        let chunk_name = world.scripts.get(&entity)
+            .map(|s| format!("@scripts/{}.lua", s.script_name))
+            .unwrap_or_else(|| "@script".to_string());
+        lua.load(content).set_name(chunk_name).exec()?;
 
         // Inject script parameters as globals before calling Awake
         if let Some(script) = world.scripts.get(&entity) {
@@ -148,6 +1163,7 @@ impl ScriptEngine {
                         ecs::ScriptParameter::Bool(v) => globals.set(name.as_str(), *v)?,
                         ecs::ScriptParameter::Entity(Some(e)) => globals.set(name.as_str(), *e)?,
                         ecs::ScriptParameter::Entity(None) => globals.set(name.as_str(), mlua::Nil)?,
+                        ecs::ScriptParameter::Curve(curve) => globals.set(name.as_str(), timers::CurveHandle(curve.clone()))?,
                     }
                 }
             } // Drop globals here
@@ -156,6 +1172,7 @@ impl ScriptEngine {
         // Inject basic API functions and call on_start within the same scope
         {
             let world_cell = RefCell::new(&mut *world);
+            let vec2_values = self.vec2_values;
             
             lua.scope(|scope| {
                 let globals = lua.globals();
@@ -171,6 +1188,17 @@ impl ScriptEngine {
                     Ok(table)
                 })?;
                 globals.set("get_all_entities", get_all_entities)?;
+
+                let set_active = scope.create_function_mut(|_, (target_entity, active): (Entity, bool)| {
+                    world_cell.borrow_mut().set_active(target_entity, active);
+                    Ok(())
+                })?;
+                globals.set("SetActive", set_active)?;
+
+                let is_active = scope.create_function(|_, target_entity: Entity| {
+                    Ok(world_cell.borrow().is_active_in_hierarchy(target_entity))
+                })?;
+                globals.set("IsActive", is_active)?;
                 
                 let get_tags = scope.create_function(|lua, query_entity: Entity| {
                     let table = lua.create_table()?;
@@ -187,11 +1215,8 @@ impl ScriptEngine {
                 
                 let get_position_of = scope.create_function(|lua, query_entity: Entity| {
                     if let Some(transform) = world_cell.borrow().transforms.get(&query_entity) {
-                        let table = lua.create_table()?;
-                        table.set("x", transform.position[0])?;
-                        table.set("y", transform.position[1])?;
-                        table.set("z", transform.position[2])?;
-                        Ok(Some(table))
+                        let [x, y, z] = transform.position;
+                        math::vec2_value(lua, x, y, Some(z), vec2_values).map(Some)
                     } else {
                         Ok(None)
                     }
@@ -200,22 +1225,24 @@ impl ScriptEngine {
                 
                 let get_position = scope.create_function(|lua, ()| {
                     if let Some(transform) = world_cell.borrow().transforms.get(&entity) {
-                        let table = lua.create_table()?;
-                        table.set("x", transform.position[0])?;
-                        table.set("y", transform.position[1])?;
-                        table.set("z", transform.position[2])?;
-                        Ok(Some(table))
+                        let [x, y, z] = transform.position;
+                        math::vec2_value(lua, x, y, Some(z), vec2_values).map(Some)
                     } else {
                         Ok(None)
                     }
                 })?;
                 globals.set("get_position", get_position)?;
                 
-                let set_position = scope.create_function_mut(|_, (x, y, z): (f32, f32, f32)| {
+                let set_position = scope.create_function_mut(|lua, args: mlua::MultiValue| {
+                    // set_position(x, y, z) or set_position(vec2[, z]); z stays as it is when left out
+                    let (x, y, rest) = math::xy_args("set_position", args)?;
+                    let z: Option<f32> = mlua::FromLuaMulti::from_lua_multi(rest, lua)?;
                     if let Some(transform) = world_cell.borrow_mut().transforms.get_mut(&entity) {
                         transform.position[0] = x;
                         transform.position[1] = y;
-                        transform.position[2] = z;
+                        if let Some(z) = z {
+                            transform.position[2] = z;
+                        }
                     }
                     Ok(())
                 })?;
@@ -227,6 +1254,9 @@ impl ScriptEngine {
                     Ok(())
                 })?;
                 globals.set("log", log_func)?;
+
+                pools::register_pool_api(&lua, scope, &world_cell, &self.pools, &self.scene_commands)?;
+                components::register_component_api(&lua, scope, &world_cell, &self.components)?;
                 
                 // Call Awake() or on_start() within the scope while functions are still valid
                 if let Ok(awake) = globals.get::<_, Function>("Awake") {
@@ -257,7 +1287,7 @@ impl ScriptEngine {
         let ui_commands_clone = Rc::clone(&self.ui_commands);
         let ui_activate_prefab = lua.create_function(move |_, (path, instance_name): (String, String)| {
             log::info!("🔧 [Lua UI] activate_prefab called: {} as {}", path, instance_name);
-            ui_commands_clone.borrow_mut().push(UICommand::ActivatePrefab { path, instance_name });
+            ui_commands_clone.borrow_mut().push(UICommand::ActivatePrefab { path, instance_name, owner: entity });
             log::info!("🔧 [Lua UI] Queue size after push: {}", ui_commands_clone.borrow().len());
             Ok(true)
         })?;
@@ -277,42 +1307,303 @@ impl ScriptEngine {
             log::info!("🔧 [Lua UI] Queue size after push: {}", ui_commands_clone.borrow().len());
             Ok(())
         })?;
-        
+        
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_image_fill = lua.create_function(move |_, (element_path, fill_amount): (String, f32)| {
+            log::info!("🔧 [Lua UI] set_image_fill called: {} = {}", element_path, fill_amount);
+            ui_commands_clone.borrow_mut().push(UICommand::SetImageFill { element_path, fill_amount });
+            log::info!("🔧 [Lua UI] Queue size after push: {}", ui_commands_clone.borrow().len());
+            Ok(())
+        })?;
+        
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_color = lua.create_function(move |_, (element_path, color): (String, mlua::Value)| {
+            let ui::Color { r, g, b, a } = ui::lua_bindings::color_from_lua(color)?;
+            log::info!("🔧 [Lua UI] set_color called: {} = ({}, {}, {}, {})", element_path, r, g, b, a);
+            ui_commands_clone.borrow_mut().push(UICommand::SetColor { element_path, r, g, b, a });
+            log::info!("🔧 [Lua UI] Queue size after push: {}", ui_commands_clone.borrow().len());
+            Ok(())
+        })?;
+        
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_show_element = lua.create_function(move |_, element_path: String| {
+            log::info!("🔧 [Lua UI] show_element called: {}", element_path);
+            ui_commands_clone.borrow_mut().push(UICommand::ShowElement { element_path });
+            log::info!("🔧 [Lua UI] Queue size after push: {}", ui_commands_clone.borrow().len());
+            Ok(())
+        })?;
+        
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_hide_element = lua.create_function(move |_, element_path: String| {
+            log::info!("🔧 [Lua UI] hide_element called: {}", element_path);
+            ui_commands_clone.borrow_mut().push(UICommand::HideElement { element_path });
+            log::info!("🔧 [Lua UI] Queue size after push: {}", ui_commands_clone.borrow().len());
+            Ok(())
+        })?;
+        
+        // Script-driven elements (no prefab): each returns the new element's path
+        let create_element = {
+            let ui_commands = Rc::clone(&self.ui_commands);
+            move |parent_path: String, element: ui::UIPrefabElement, params: UIElementParams| -> String {
+                let path = format!("{}/{}", parent_path.trim_end_matches('/'), element.name);
+                ui_commands.borrow_mut().push(UICommand::CreateElement {
+                    parent_path,
+                    element: Box::new(element),
+                    style_class: params.style_class,
+                    color: params.color,
+                });
+                path
+            }
+        };
+
+        let create = create_element.clone();
+        let ui_create_panel = lua.create_function(move |_, (parent_path, name, params): (String, String, Option<Table>)| {
+            let params = UIElementParams::from_lua(params, ui::Vec2::new(200.0, 200.0))?;
+            Ok(create(parent_path, ui_elements::panel(&name, &params), params))
+        })?;
+
+        let create = create_element.clone();
+        let ui_create_text = lua.create_function(move |_, (parent_path, name, text, style_class, params): (String, String, String, Option<String>, Option<Table>)| {
+            let mut params = UIElementParams::from_lua(params, ui::Vec2::new(160.0, 30.0))?;
+            params.style_class = style_class.or(params.style_class);
+            Ok(create(parent_path, ui_elements::text(&name, text, &params), params))
+        })?;
+
+        let create = create_element.clone();
+        let ui_create_image = lua.create_function(move |_, (parent_path, name, params): (String, String, Option<Table>)| {
+            let params = UIElementParams::from_lua(params, ui::Vec2::new(100.0, 100.0))?;
+            Ok(create(parent_path, ui_elements::image(&name, &params), params))
+        })?;
+
+        let create = create_element;
+        let ui_create_button = lua.create_function(move |_, (parent_path, name, label, style_class, params): (String, String, String, Option<String>, Option<Table>)| {
+            let mut params = UIElementParams::from_lua(params, ui::Vec2::new(160.0, 40.0))?;
+            params.style_class = style_class.or(params.style_class);
+            Ok(create(parent_path, ui_elements::button(&name, label, &params), params))
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_destroy_element = lua.create_function(move |_, element_path: String| {
+            ui_commands_clone.borrow_mut().push(UICommand::DestroyElement { element_path });
+            Ok(())
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_as_last_sibling = lua.create_function(move |_, element_path: String| {
+            ui_commands_clone.borrow_mut().push(UICommand::SetSiblingIndex { element_path, index: None });
+            Ok(())
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_as_first_sibling = lua.create_function(move |_, element_path: String| {
+            ui_commands_clone.borrow_mut().push(UICommand::SetSiblingIndex { element_path, index: Some(0) });
+            Ok(())
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_tooltip = lua.create_function(move |_, (element_path, text): (String, String)| {
+            ui_commands_clone.borrow_mut().push(UICommand::SetTooltip { element_path, text });
+            Ok(())
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_toggle = lua.create_function(move |_, (element_path, is_on): (String, bool)| {
+            ui_commands_clone.borrow_mut().push(UICommand::SetToggle { element_path, is_on });
+            Ok(())
+        })?;
+
+        let ui_inputs_clone = Rc::clone(&self.ui_inputs);
+        let ui_get_input_text = lua.create_function(move |_, element_path: String| {
+            Ok(ui_inputs_clone.borrow().get(&element_path).map(|field| field.text.clone()))
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_input_text = lua.create_function(move |_, (element_path, text): (String, String)| {
+            ui_commands_clone.borrow_mut().push(UICommand::SetInputText { element_path, text });
+            Ok(())
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_placeholder = lua.create_function(move |_, (element_path, text): (String, String)| {
+            ui_commands_clone.borrow_mut().push(UICommand::SetPlaceholder { element_path, text });
+            Ok(())
+        })?;
+
+        let ui_inputs_clone = Rc::clone(&self.ui_inputs);
+        let ui_get_character_validation = lua.create_function(move |_, element_path: String| {
+            Ok(ui_inputs_clone
+                .borrow()
+                .get(&element_path)
+                .map(|field| ui_events::character_validation_name(field.character_validation)))
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_character_validation = lua.create_function(move |_, (element_path, name): (String, String)| {
+            let validation = ui_events::parse_character_validation(&name).ok_or_else(|| {
+                mlua::Error::RuntimeError(format!(
+                    "Unknown character validation '{}' (none, integer, decimal, alphanumeric, name, email)",
+                    name
+                ))
+            })?;
+            ui_commands_clone.borrow_mut().push(UICommand::SetCharacterValidation { element_path, validation });
+            Ok(())
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_on_submit = lua.create_function(move |_, (element_path, callback, options): (String, String, Option<Table>)| {
+            let while_disabled = DisabledPolicy::from_options("UI.on_submit", options)?;
+            ui_commands_clone.borrow_mut().push(UICommand::AddEventListener {
+                element_path,
+                event: UIEventKind::Submit,
+                owner: entity,
+                callback,
+                while_disabled,
+            });
+            Ok(())
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_on_value_changed = lua.create_function(move |_, (element_path, callback, options): (String, String, Option<Table>)| {
+            let while_disabled = DisabledPolicy::from_options("UI.on_value_changed", options)?;
+            ui_commands_clone.borrow_mut().push(UICommand::AddEventListener {
+                element_path,
+                event: UIEventKind::ValueChanged,
+                owner: entity,
+                callback,
+                while_disabled,
+            });
+            Ok(())
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_on_drop = lua.create_function(move |_, (target_path, callback, options): (String, String, Option<Table>)| {
+            let while_disabled = DisabledPolicy::from_options("UI.on_drop", options)?;
+            ui_commands_clone.borrow_mut().push(UICommand::AddEventListener {
+                element_path: target_path,
+                event: UIEventKind::Drop,
+                owner: entity,
+                callback,
+                while_disabled,
+            });
+            Ok(())
+        })?;
+
+        // Values may be strings, numbers or booleans: UI.set_text_params(path, { count = 3, total = 10 })
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_text_params = lua.create_function(move |_, (element_path, table): (String, Table)| {
+            let mut params = HashMap::new();
+            for pair in table.pairs::<String, Value>() {
+                let (name, value) = pair?;
+                let value = match value {
+                    Value::String(text) => text.to_str()?.to_string(),
+                    Value::Integer(number) => number.to_string(),
+                    Value::Number(number) => number.to_string(),
+                    Value::Boolean(flag) => flag.to_string(),
+                    other => {
+                        return Err(mlua::Error::RuntimeError(format!(
+                            "UI.set_text_params: '{}' must be a string, number or boolean, got {}",
+                            name,
+                            other.type_name()
+                        )))
+                    }
+                };
+                params.insert(name, value);
+            }
+            ui_commands_clone.borrow_mut().push(UICommand::SetTextParams { element_path, params });
+            Ok(())
+        })?;
+
+        // Lua-style 1-based index
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_sibling_index = lua.create_function(move |_, (element_path, index): (String, usize)| {
+            let index = Some(index.saturating_sub(1));
+            ui_commands_clone.borrow_mut().push(UICommand::SetSiblingIndex { element_path, index });
+            Ok(())
+        })?;
+        
+        // UI.show_modal(path, options?) -> instance name; UI.close_modal(result?)
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_show_modal = lua.create_function(move |_, (path, options): (String, Option<Table>)| {
+            let (instance_name, options) = ui_elements::modal_options_from_lua(&path, options)?;
+            ui_commands_clone.borrow_mut().push(UICommand::ShowModal {
+                path,
+                instance_name: instance_name.clone(),
+                owner: entity,
+                options,
+            });
+            Ok(instance_name)
+        })?;
+
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_close_modal = lua.create_function(move |_, result: Option<String>| {
+            let result = result.unwrap_or_else(|| ui::popup_manager::CANCEL_RESULT.to_string());
+            ui_commands_clone.borrow_mut().push(UICommand::CloseModal { result });
+            Ok(())
+        })?;
+
+        // Canvas groups fade/disable an element with everything below it:
+        // UI.set_group_alpha(path, alpha), UI.set_group_interactable(path, interactable),
+        // UI.fade_group(path, to, duration, { easing = "ease_out_quad", on_complete = "OnFaded" }?)
+        let ui_commands_clone = Rc::clone(&self.ui_commands);
+        let ui_set_group_alpha = lua.create_function(move |_, (element_path, alpha): (String, f32)| {
+            ui_commands_clone.borrow_mut().push(UICommand::SetGroupAlpha { element_path, alpha: alpha.clamp(0.0, 1.0) });
+            Ok(())
+        })?;
+
         let ui_commands_clone = Rc::clone(&self.ui_commands);
-        let ui_set_image_fill = lua.create_function(move |_, (element_path, fill_amount): (String, f32)| {
-            log::info!("🔧 [Lua UI] set_image_fill called: {} = {}", element_path, fill_amount);
-            ui_commands_clone.borrow_mut().push(UICommand::SetImageFill { element_path, fill_amount });
-            log::info!("🔧 [Lua UI] Queue size after push: {}", ui_commands_clone.borrow().len());
+        let ui_set_group_interactable = lua.create_function(move |_, (element_path, interactable): (String, bool)| {
+            ui_commands_clone.borrow_mut().push(UICommand::SetGroupInteractable { element_path, interactable });
             Ok(())
         })?;
-        
+
         let ui_commands_clone = Rc::clone(&self.ui_commands);
-        let ui_set_color = lua.create_function(move |_, (element_path, color): (String, Table)| {
-            let r = color.get::<_, f32>("r").unwrap_or(1.0);
-            let g = color.get::<_, f32>("g").unwrap_or(1.0);
-            let b = color.get::<_, f32>("b").unwrap_or(1.0);
-            let a = color.get::<_, f32>("a").unwrap_or(1.0);
-            log::info!("🔧 [Lua UI] set_color called: {} = ({}, {}, {}, {})", element_path, r, g, b, a);
-            ui_commands_clone.borrow_mut().push(UICommand::SetColor { element_path, r, g, b, a });
-            log::info!("🔧 [Lua UI] Queue size after push: {}", ui_commands_clone.borrow().len());
+        let ui_fade_group = lua.create_function(move |_, (element_path, to, duration, options): (String, f32, f32, Option<Table>)| {
+            let (easing, on_complete) = match options {
+                Some(options) => (
+                    timers::easing_from_lua("UI.fade_group", options.get("easing")?)?,
+                    options.get::<_, Option<String>>("on_complete")?,
+                ),
+                None => (ui::EasingFunction::Linear, None),
+            };
+            ui_commands_clone.borrow_mut().push(UICommand::FadeGroup {
+                element_path,
+                to: to.clamp(0.0, 1.0),
+                duration: duration.max(0.0),
+                easing,
+                owner: entity,
+                on_complete,
+            });
             Ok(())
         })?;
-        
+
+        // UI.set_interactable(path, interactable), UI.set_slider(path, value)
         let ui_commands_clone = Rc::clone(&self.ui_commands);
-        let ui_show_element = lua.create_function(move |_, element_path: String| {
-            log::info!("🔧 [Lua UI] show_element called: {}", element_path);
-            ui_commands_clone.borrow_mut().push(UICommand::ShowElement { element_path });
-            log::info!("🔧 [Lua UI] Queue size after push: {}", ui_commands_clone.borrow().len());
+        let ui_set_interactable = lua.create_function(move |_, (element_path, interactable): (String, bool)| {
+            ui_commands_clone.borrow_mut().push(UICommand::SetInteractable { element_path, interactable });
             Ok(())
         })?;
-        
+
         let ui_commands_clone = Rc::clone(&self.ui_commands);
-        let ui_hide_element = lua.create_function(move |_, element_path: String| {
-            log::info!("🔧 [Lua UI] hide_element called: {}", element_path);
-            ui_commands_clone.borrow_mut().push(UICommand::HideElement { element_path });
-            log::info!("🔧 [Lua UI] Queue size after push: {}", ui_commands_clone.borrow().len());
+        let ui_set_slider = lua.create_function(move |_, (element_path, value): (String, f32)| {
+            ui_commands_clone.borrow_mut().push(UICommand::SetSlider { element_path, value });
             Ok(())
         })?;
+
+        // Failures of the commands queued last frame (they're applied between frames)
+        let ui_errors_clone = Rc::clone(&self.ui_errors);
+        let ui_get_last_errors = lua.create_function(move |lua, ()| {
+            let errors = lua.create_table()?;
+            for (i, error) in ui_errors_clone.borrow().iter().enumerate() {
+                let entry = lua.create_table()?;
+                entry.set("command", error.command)?;
+                entry.set("path", error.path.as_str())?;
+                entry.set("message", error.message.as_str())?;
+                entry.set("not_found", error.not_found)?;
+                entry.set("suggestion", error.suggestion.as_deref())?;
+                errors.set(i + 1, entry)?;
+            }
+            Ok(errors)
+        })?;
         
         // Create UI table and set it in globals (permanently)
         {
@@ -326,24 +1617,71 @@ impl ScriptEngine {
             ui_table.set("set_color", ui_set_color)?;
             ui_table.set("show_element", ui_show_element)?;
             ui_table.set("hide_element", ui_hide_element)?;
+            ui_table.set("create_panel", ui_create_panel)?;
+            ui_table.set("create_text", ui_create_text)?;
+            ui_table.set("create_image", ui_create_image)?;
+            ui_table.set("create_button", ui_create_button)?;
+            ui_table.set("destroy_element", ui_destroy_element)?;
+            ui_table.set("set_as_last_sibling", ui_set_as_last_sibling)?;
+            ui_table.set("set_as_first_sibling", ui_set_as_first_sibling)?;
+            ui_table.set("set_sibling_index", ui_set_sibling_index)?;
+            ui_table.set("set_tooltip", ui_set_tooltip)?;
+            ui_table.set("set_toggle", ui_set_toggle)?;
+            ui_table.set("get_input_text", ui_get_input_text)?;
+            ui_table.set("set_input_text", ui_set_input_text)?;
+            ui_table.set("set_placeholder", ui_set_placeholder)?;
+            ui_table.set("get_character_validation", ui_get_character_validation)?;
+            ui_table.set("set_character_validation", ui_set_character_validation)?;
+            ui_table.set("on_submit", ui_on_submit)?;
+            ui_table.set("on_value_changed", ui_on_value_changed)?;
+            ui_table.set("on_drop", ui_on_drop)?;
+            ui_table.set("set_text_params", ui_set_text_params)?;
+            ui_table.set("show_modal", ui_show_modal)?;
+            ui_table.set("close_modal", ui_close_modal)?;
+            ui_table.set("set_group_alpha", ui_set_group_alpha)?;
+            ui_table.set("set_group_interactable", ui_set_group_interactable)?;
+            ui_table.set("fade_group", ui_fade_group)?;
+            ui_table.set("set_interactable", ui_set_interactable)?;
+            ui_table.set("set_slider", ui_set_slider)?;
+            ui_table.set("get_last_errors", ui_get_last_errors)?;
             globals.set("UI", ui_table)?;
         }
 
-        // Store the Lua state for this entity
+        // Store the Lua state for this entity (a reloaded script starts its lifecycle over)
         self.entity_states.insert(entity, lua);
+        self.started.remove(&entity);
+        self.enabled_states.remove(&entity);
 
         Ok(())
     }
 
     /// Call Start() for an entity (should be called after all Awake() calls)
-    /// This needs world access to inject API functions
-    pub fn call_start_for_entity(&self, entity: Entity, world: &mut World) -> Result<()> {
+    /// This needs world access to inject API functions.
+    /// Entities that are inactive in the hierarchy (or whose Script is disabled) are skipped;
+    /// their Start() runs from update_active_states() once they are enabled. OnEnable() runs
+    /// right before Start().
+    pub fn call_start_for_entity(&mut self, entity: Entity, world: &mut World) -> Result<()> {
         log::info!("🔍 call_start_for_entity() called for entity {}", entity);
+
+        if self.entity_states.contains_key(&entity) {
+            if self.started.contains(&entity) {
+                return Ok(());
+            }
+            if !Self::is_script_enabled(world, entity) {
+                log::info!("⏸ Entity {} is inactive, Start() deferred until it is activated", entity);
+                return Ok(());
+            }
+            self.started.insert(entity);
+            if self.enabled_states.insert(entity, true) != Some(true) {
+                self.call_lifecycle_callback(entity, "OnEnable", world)?;
+            }
+        }
         
         if let Some(lua) = self.entity_states.get(&entity) {
             log::info!("✅ Found Lua state for entity {}", entity);
             // Use RefCell to work around borrow checker
             let world_cell = RefCell::new(&mut *world);
+            let vec2_values = self.vec2_values;
             
             log::info!("🔍 Entering lua.scope() for entity {}", entity);
             let result = lua.scope(|scope| {
@@ -351,7 +1689,8 @@ impl ScriptEngine {
                 globals.set("entity", entity)?;
                 
                 // Inject essential API functions for Start()
-                let set_velocity = scope.create_function_mut(|_, (vx, vy): (f32, f32)| {
+                let set_velocity = scope.create_function_mut(|_, args: mlua::MultiValue| {
+                    let (vx, vy, _) = math::xy_args("set_velocity", args)?;
                     world_cell.borrow_mut().velocities.insert(entity, (vx, vy));
                     if let Some(rigidbody) = world_cell.borrow_mut().rigidbodies.get_mut(&entity) {
                         rigidbody.velocity = (vx, vy);
@@ -378,6 +1717,17 @@ impl ScriptEngine {
                     Ok(table)
                 })?;
                 globals.set("get_all_entities", get_all_entities)?;
+
+                let set_active = scope.create_function_mut(|_, (target_entity, active): (Entity, bool)| {
+                    world_cell.borrow_mut().set_active(target_entity, active);
+                    Ok(())
+                })?;
+                globals.set("SetActive", set_active)?;
+
+                let is_active = scope.create_function(|_, target_entity: Entity| {
+                    Ok(world_cell.borrow().is_active_in_hierarchy(target_entity))
+                })?;
+                globals.set("IsActive", is_active)?;
                 
                 let get_tags = scope.create_function(|lua, query_entity: Entity| {
                     let table = lua.create_table()?;
@@ -394,11 +1744,8 @@ impl ScriptEngine {
                 
                 let get_position = scope.create_function(|lua, ()| {
                     if let Some(transform) = world_cell.borrow().transforms.get(&entity) {
-                        let table = lua.create_table()?;
-                        table.set("x", transform.position[0])?;
-                        table.set("y", transform.position[1])?;
-                        table.set("z", transform.position[2])?;
-                        Ok(Some(table))
+                        let [x, y, z] = transform.position;
+                        math::vec2_value(lua, x, y, Some(z), vec2_values).map(Some)
                     } else {
                         Ok(None)
                     }
@@ -407,11 +1754,8 @@ impl ScriptEngine {
                 
                 let get_position_of = scope.create_function(|lua, query_entity: Entity| {
                     if let Some(transform) = world_cell.borrow().transforms.get(&query_entity) {
-                        let table = lua.create_table()?;
-                        table.set("x", transform.position[0])?;
-                        table.set("y", transform.position[1])?;
-                        table.set("z", transform.position[2])?;
-                        Ok(Some(table))
+                        let [x, y, z] = transform.position;
+                        math::vec2_value(lua, x, y, Some(z), vec2_values).map(Some)
                     } else {
                         Ok(None)
                     }
@@ -428,16 +1772,81 @@ impl ScriptEngine {
                 })?;
                 globals.set("set_position_of", set_position_of)?;
                 
-                let set_position = scope.create_function_mut(|_, (x, y, z): (f32, f32, f32)| {
+                let set_position = scope.create_function_mut(|lua, args: mlua::MultiValue| {
+                    // set_position(x, y, z) or set_position(vec2[, z]); z stays as it is when left out
+                    let (x, y, rest) = math::xy_args("set_position", args)?;
+                    let z: Option<f32> = mlua::FromLuaMulti::from_lua_multi(rest, lua)?;
                     if let Some(transform) = world_cell.borrow_mut().transforms.get_mut(&entity) {
                         transform.position[0] = x;
                         transform.position[1] = y;
-                        transform.position[2] = z;
+                        if let Some(z) = z {
+                            transform.position[2] = z;
+                        }
                     }
                     Ok(())
                 })?;
                 globals.set("set_position", set_position)?;
 
+                // World-space position through the parent chain (get_position stays local)
+                let get_world_position = scope.create_function(|lua, ()| {
+                    if let Some(position) = world_cell.borrow().world_position(entity) {
+                        let table = lua.create_table()?;
+                        table.set("x", position[0])?;
+                        table.set("y", position[1])?;
+                        table.set("z", position[2])?;
+                        Ok(Some(table))
+                    } else {
+                        Ok(None)
+                    }
+                })?;
+                globals.set("get_world_position", get_world_position)?;
+
+                let get_world_position_of = scope.create_function(|lua, query_entity: Entity| {
+                    if let Some(position) = world_cell.borrow().world_position(query_entity) {
+                        let table = lua.create_table()?;
+                        table.set("x", position[0])?;
+                        table.set("y", position[1])?;
+                        table.set("z", position[2])?;
+                        Ok(Some(table))
+                    } else {
+                        Ok(None)
+                    }
+                })?;
+                globals.set("get_world_position_of", get_world_position_of)?;
+
+                // set_world_position(x, y, z): back-solves the local position under the current parent
+                let set_world_position = scope.create_function_mut(|_, (x, y, z): (f32, f32, f32)| {
+                    world_cell.borrow_mut().set_world_position(entity, [x, y, z]);
+                    Ok(())
+                })?;
+                globals.set("set_world_position", set_world_position)?;
+
+                // set_parent(child, parent | nil, keep_local): keeps the child's world position unless keep_local
+                let set_parent = scope.create_function_mut(|_, (child, parent, keep_local): (Entity, Option<Entity>, Option<bool>)| {
+                    world_cell.borrow_mut().reparent(child, parent, keep_local.unwrap_or(false));
+                    Ok(())
+                })?;
+                globals.set("set_parent", set_parent)?;
+
+                let get_parent = scope.create_function(|_, query_entity: Entity| {
+                    Ok(world_cell.borrow().get_parent(query_entity))
+                })?;
+                globals.set("get_parent", get_parent)?;
+
+                // Render textures: point a camera at one by name (nil renders to the screen again)
+                let set_camera_target = scope.create_function_mut(|_, (camera_entity, name): (Entity, Option<String>)| {
+                    match world_cell.borrow_mut().cameras.get_mut(&camera_entity) {
+                        Some(camera) => {
+                            camera.target_texture = name;
+                            Ok(true)
+                        }
+                        None => Ok(false),
+                    }
+                })?;
+                let render_texture_table = lua.create_table()?;
+                render_texture_table.set("set_camera_target", set_camera_target)?;
+                globals.set("RenderTexture", render_texture_table)?;
+
                 // 3D Rotation Support
                 let get_rotation_euler = scope.create_function(|lua, ()| {
                     if let Some(transform) = world_cell.borrow().transforms.get(&entity) {
@@ -484,15 +1893,9 @@ impl ScriptEngine {
 
                 let get_velocity_of = scope.create_function(|lua, query_entity: Entity| {
                     if let Some(vel) = world_cell.borrow().velocities.get(&query_entity) {
-                        let table = lua.create_table()?;
-                        table.set("x", vel.0)?;
-                        table.set("y", vel.1)?;
-                        Ok(Some(table))
+                        math::vec2_value(lua, vel.0, vel.1, None, vec2_values).map(Some)
                     } else if let Some(rb) = world_cell.borrow().rigidbodies.get(&query_entity) {
-                        let table = lua.create_table()?;
-                        table.set("x", rb.velocity.0)?;
-                        table.set("y", rb.velocity.1)?;
-                        Ok(Some(table))
+                        math::vec2_value(lua, rb.velocity.0, rb.velocity.1, None, vec2_values).map(Some)
                     } else {
                         Ok(None)
                     }
@@ -542,6 +1945,7 @@ impl ScriptEngine {
                                 ecs::ScriptParameter::String(v) => table.set("String", v.clone())?,
                                 ecs::ScriptParameter::Entity(Some(e)) => table.set("Entity", *e)?,
                                 ecs::ScriptParameter::Entity(None) => table.set("Entity", mlua::Nil)?,
+                                ecs::ScriptParameter::Curve(curve) => table.set("Curve", timers::CurveHandle(curve.clone()))?,
                             }
                             Ok(Some(table))
                         } else {
@@ -561,6 +1965,9 @@ impl ScriptEngine {
                     Ok(())
                 })?;
                 globals.set("log", log_func)?;
+
+                pools::register_pool_api(lua, scope, &world_cell, &self.pools, &self.scene_commands)?;
+                components::register_component_api(lua, scope, &world_cell, &self.components)?;
                 
                 // Call Start() or on_start() if it exists (Unity-style with backward compatibility)
                 if let Ok(start) = globals.get::<_, Function>("Start") {
@@ -599,9 +2006,251 @@ impl ScriptEngine {
         Ok(())
     }
 
-    /// Remove entity's Lua state when entity is destroyed
-    pub fn remove_entity_state(&mut self, entity: Entity) {
+    /// Number of entities with a loaded script state
+    pub fn entity_state_count(&self) -> usize {
+        self.entity_states.len()
+    }
+
+    /// Whether the entity's script is loaded (Awake ran)
+    pub fn has_entity_state(&self, entity: Entity) -> bool {
+        self.entity_states.contains_key(&entity)
+    }
+
+    /// The entity is being destroyed: call the script's OnDestroy() while the entity is
+    /// still in the world (it can clean up spawned children, unregister events, ...), then
+    /// drop its Lua state
+    pub fn remove_entity_state(&mut self, entity: Entity, world: &mut World) {
+        if let Err(e) = self.call_lifecycle_callback(entity, "OnDestroy", world) {
+            log::error!("OnDestroy error for entity {}: {}", entity, e);
+        }
+        self.timers.borrow_mut().remove_entity(entity);
+        self.coroutines.borrow_mut().remove_entity(entity);
+        self.ui_events.borrow_mut().remove_entity(entity);
+        self.events.borrow_mut().remove_entity(entity);
+        self.global_state.borrow_mut().remove_entity(entity);
+        self.debugger.borrow_mut().remove_entity(entity);
+        // Registry keys belong to the state being dropped
+        self.suspended_updates.remove(&entity);
         self.entity_states.remove(&entity);
+        self.started.remove(&entity);
+        self.enabled_states.remove(&entity);
+        self.character_states.borrow_mut().remove(&entity);
+    }
+
+    /// Despawn the entities scripts passed to destroy_entity() this frame, with their
+    /// children (call after LateUpdate). OnDestroy runs for all of them first, so they may
+    /// still read each other; OnDestroy may destroy more entities, which go in the same
+    /// pass. Returns the despawned entities.
+    pub fn apply_pending_destroys(&mut self, world: &mut World) -> Vec<Entity> {
+        let mut destroyed = Vec::new();
+        let mut seen = HashSet::new();
+        loop {
+            let roots = std::mem::take(&mut *self.pending_destroys.borrow_mut());
+            if roots.is_empty() {
+                break;
+            }
+            let alive: HashSet<Entity> = world.entities().into_iter().collect();
+            let mut doomed = Vec::new();
+            let mut stack = roots.clone();
+            while let Some(entity) = stack.pop() {
+                if alive.contains(&entity) && seen.insert(entity) {
+                    doomed.push(entity);
+                    stack.extend_from_slice(world.get_children(entity));
+                }
+            }
+            // Stable OnDestroy order
+            doomed.sort_unstable();
+            for &entity in &doomed {
+                if self.entity_states.contains_key(&entity) {
+                    self.remove_entity_state(entity, world);
+                }
+            }
+            for &root in &roots {
+                world.despawn(root);
+            }
+            destroyed.extend(doomed);
+        }
+        // Pooled instances that were destroyed leave their pools
+        self.forget_pooled(&destroyed);
+        destroyed
+    }
+
+    /// Destroy `entity` as if a script called destroy_entity() on it (engine-side
+    /// destroys, e.g. a ColorFade finishing); applied by apply_pending_destroys
+    pub fn queue_destroy(&self, entity: Entity) {
+        self.pending_destroys.borrow_mut().push(entity);
+    }
+
+    /// A script runs (and counts as enabled for OnEnable/OnDisable) while its Script
+    /// component is enabled and the entity is active in the hierarchy
+    fn is_script_enabled(world: &World, entity: Entity) -> bool {
+        world.scripts.get(&entity).is_some_and(|script| script.enabled) && world.is_active_in_hierarchy(entity)
+    }
+
+    /// Suspend (or resume) what a script left running while it is disabled: its timers,
+    /// tweens and coroutines keep their remaining time, and events for its UI and bus
+    /// listeners are dropped unless the listener was registered to buffer them
+    fn set_suspended(&self, entity: Entity, suspended: bool) {
+        self.timers.borrow_mut().set_paused(entity, suspended);
+        self.coroutines.borrow_mut().set_paused(entity, suspended);
+        self.ui_events.borrow_mut().set_suspended(entity, suspended);
+        self.events.borrow_mut().set_suspended(entity, suspended);
+    }
+
+    /// Fire OnEnable()/OnDisable() for scripts whose enabled state (Script.enabled and
+    /// active in the hierarchy) changed since the last call, and run deferred Start() calls
+    /// for newly enabled entities. Disabled scripts are suspended (set_suspended) until
+    /// they are enabled again, before their OnEnable runs.
+    pub fn update_active_states(&mut self, world: &mut World) {
+        let mut entities: Vec<Entity> = self.entity_states.keys().copied().collect();
+        // Stable callback order (HashMap iteration order isn't)
+        entities.sort_unstable();
+
+        for entity in entities {
+            let active = Self::is_script_enabled(world, entity);
+            let was_active = self.enabled_states.get(&entity).copied();
+
+            if active {
+                if !self.started.contains(&entity) {
+                    // Deferred Start (also fires OnEnable first)
+                    if let Err(e) = self.call_start_for_entity(entity, world) {
+                        log::error!("Script start error for entity {}: {}", entity, e);
+                    }
+                } else if was_active != Some(true) {
+                    self.enabled_states.insert(entity, true);
+                    self.set_suspended(entity, false);
+                    if let Err(e) = self.call_lifecycle_callback(entity, "OnEnable", world) {
+                        log::error!("OnEnable error for entity {}: {}", entity, e);
+                    }
+                }
+            } else if was_active == Some(true) {
+                self.enabled_states.insert(entity, false);
+                self.set_suspended(entity, true);
+                if let Err(e) = self.call_lifecycle_callback(entity, "OnDisable", world) {
+                    log::error!("OnDisable error for entity {}: {}", entity, e);
+                }
+            }
+        }
+
+        // OnSpawn for instances Pool.spawn handed out, after their OnEnable/Start
+        let spawned = self.pools.borrow_mut().take_spawned();
+        for entity in spawned {
+            if !self.pools.borrow().manager.is_spawned(entity) {
+                continue; // Released before its OnSpawn
+            }
+            if !self.entity_states.contains_key(&entity) {
+                if world.scripts.get(&entity).is_some_and(|script| script.enabled) {
+                    self.pools.borrow_mut().queue_spawned(entity);
+                }
+                continue;
+            }
+            if let Err(e) = self.call_lifecycle_callback(entity, "OnSpawn", world) {
+                log::error!("OnSpawn error for entity {}: {}", entity, e);
+            }
+        }
+        self.pools.borrow().publish_stats();
+    }
+
+    /// Call an argument-less lifecycle function (OnEnable/OnDisable/OnDestroy/OnSpawn) if the script defines it
+    fn call_lifecycle_callback(&self, entity: Entity, name: &str, world: &mut World) -> Result<()> {
+        self.call_hook(entity, &[name], (), world)
+    }
+
+    /// Call the first of `names` the entity's script defines with `args`, with the same
+    /// world functions as the lifecycle callbacks
+    fn call_hook<A: for<'lua> mlua::IntoLuaMulti<'lua>>(&self, entity: Entity, names: &[&str], args: A, world: &mut World) -> Result<()> {
+        let Some(lua) = self.entity_states.get(&entity) else {
+            return Ok(());
+        };
+        let globals = lua.globals();
+        let Some(callback) = names.iter().find_map(|name| globals.get::<_, Function>(*name).ok()) else {
+            return Ok(());
+        };
+
+        let world_cell = RefCell::new(&mut *world);
+        lua.scope(|scope| {
+            globals.set("entity", entity)?;
+
+            let set_active = scope.create_function_mut(|_, (target_entity, active): (Entity, bool)| {
+                world_cell.borrow_mut().set_active(target_entity, active);
+                Ok(())
+            })?;
+            globals.set("SetActive", set_active)?;
+
+            let is_active = scope.create_function(|_, target_entity: Entity| {
+                Ok(world_cell.borrow().is_active_in_hierarchy(target_entity))
+            })?;
+            globals.set("IsActive", is_active)?;
+
+            // Queued like in Update (OnDestroy cleaning up entities it spawned)
+            let pending_destroys = &self.pending_destroys;
+            let destroy_entity = scope.create_function_mut(move |_, target_entity: Entity| {
+                pending_destroys.borrow_mut().push(target_entity);
+                Ok(())
+            })?;
+            globals.set("destroy_entity", destroy_entity)?;
+
+            pools::register_pool_api(lua, scope, &world_cell, &self.pools, &self.scene_commands)?;
+            components::register_component_api(lua, scope, &world_cell, &self.components)?;
+
+            callback.call::<_, ()>(args)?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Call OnSceneLoadProgress(path, progress) (or on_scene_load_progress) in every enabled
+    /// script, in entity-id order, for a preload whose progress changed this frame
+    pub fn call_scene_load_progress(&self, path: &str, progress: f32, world: &mut World) {
+        for entity in world.scripts.sorted_entities() {
+            if !world.scripts.get(&entity).is_some_and(|script| script.enabled) {
+                continue;
+            }
+            let args = (path.to_string(), progress);
+            if let Err(e) = self.call_hook(entity, &["OnSceneLoadProgress", "on_scene_load_progress"], args, world) {
+                log::error!("OnSceneLoadProgress error for entity {}: {}", entity, e);
+            }
+        }
+    }
+
+    /// Advance script timers, tweens and coroutine waits. Tween values are written to the
+    /// world immediately; due callbacks and coroutines run in the owner's next run_script() call.
+    pub fn update_timers(&mut self, dt: f32, world: &mut World) {
+        self.timers.borrow_mut().update(dt, world);
+        self.coroutines.borrow_mut().update(dt);
+    }
+
+    /// Drop all pending timers, tweens and coroutines (e.g. when leaving play mode)
+    pub fn clear_timers(&mut self) {
+        self.timers.borrow_mut().clear();
+        self.coroutines.borrow_mut().clear();
+    }
+
+    /// Forget every scene preload and the loading screen setting (e.g. when leaving play mode)
+    pub fn clear_scene_preloads(&mut self) {
+        self.scene_preloads.borrow_mut().clear();
+    }
+
+    /// Drop every Globals value and subscription (e.g. when leaving play mode; scene
+    /// loads keep them)
+    pub fn clear_global_state(&mut self) {
+        self.global_state.borrow_mut().clear();
+    }
+
+    /// Forget every entity pool (e.g. when leaving play mode)
+    pub fn clear_pools(&mut self) {
+        let mut pools = self.pools.borrow_mut();
+        pools.clear();
+        pools.publish_stats();
+    }
+
+    /// Entities were despawned (scene unload): drop them from their pools, and pools
+    /// whose template went with them
+    pub fn forget_pooled(&mut self, entities: &[Entity]) {
+        let mut pools = self.pools.borrow_mut();
+        if !pools.manager.is_empty() {
+            pools.forget(entities);
+        }
     }
 
     pub fn call_update(&self, name: &str, dt: f32, world: &mut World) -> Result<()> {
@@ -632,17 +2281,96 @@ impl ScriptEngine {
         dt: f32,
         log_callback: &mut dyn FnMut(String),
     ) -> Result<()> {
-        // Get the entity's Lua state
-        let lua = match self.entity_states.get(&entity) {
+        self.run_frame_callback(entity, world, input, dt, log_callback, FramePhase::Update)
+    }
+
+    /// Call the script's LateUpdate(dt), with the same API as Update. Run it for every
+    /// entity after all of them ran Update (camera follow sees where everything moved to).
+    pub fn run_late_update(
+        &mut self,
+        entity: Entity,
+        world: &mut World,
+        input: &InputSystem,
+        dt: f32,
+        log_callback: &mut dyn FnMut(String),
+    ) -> Result<()> {
+        // Most scripts don't have one: skip building the API. An Update stopped at a
+        // breakpoint hasn't finished, so its LateUpdate waits for the next frame.
+        if !self.has_late_update(entity) || self.suspended_updates.contains_key(&entity) {
+            return Ok(());
+        }
+        self.run_frame_callback(entity, world, input, dt, log_callback, FramePhase::LateUpdate)
+    }
+
+    /// Run console code with the same world API as Update (call at the frame's script sync
+    /// point, never from UI code). `target` is an entity whose script state to run it in;
+    /// None uses the editor's own state, which keeps its globals between calls.
+    pub fn run_repl(&mut self, code: &str, target: Option<Entity>, world: &mut World, input: &InputSystem) -> Vec<ReplLine> {
+        let entity = match target {
+            Some(entity) if !self.entity_states.contains_key(&entity) => {
+                return vec![ReplLine::Error(format!("Entity {} has no running script", entity))];
+            }
+            Some(entity) => entity,
+            None => {
+                if self.repl_state.is_none() {
+                    match self.new_lua_state(repl::REPL_OWNER) {
+                        Ok(lua) => self.repl_state = Some(lua),
+                        Err(e) => return vec![ReplLine::Error(e.to_string())],
+                    }
+                }
+                repl::REPL_OWNER
+            }
+        };
+        let output = RefCell::new(Vec::new());
+        let mut log_callback = |msg: String| output.borrow_mut().push(ReplLine::Output(msg));
+        let phase = FramePhase::Eval { code, output: &output };
+        let dt = self.time.borrow().delta;
+        if let Err(e) = self.run_frame_callback(entity, world, input, dt, &mut log_callback, phase) {
+            output.borrow_mut().push(ReplLine::Error(e.to_string()));
+        }
+        output.into_inner()
+    }
+
+    /// Whether the entity's script defines LateUpdate()
+    pub fn has_late_update(&self, entity: Entity) -> bool {
+        self.entity_states.get(&entity).is_some_and(|lua| lua.globals().get::<_, Function>("LateUpdate").is_ok())
+    }
+
+    fn run_frame_callback(
+        &mut self,
+        entity: Entity,
+        world: &mut World,
+        input: &InputSystem,
+        dt: f32,
+        log_callback: &mut dyn FnMut(String),
+        phase: FramePhase,
+    ) -> Result<()> {
+        // Get the entity's Lua state (the editor's own one for console code)
+        let lua = match if entity == repl::REPL_OWNER { self.repl_state.as_ref() } else { self.entity_states.get(&entity) } {
             Some(lua) => lua,
             None => return Ok(()), // Entity has no loaded script
         };
 
+        // An Update suspended at a breakpoint continues (after a debugger command) instead
+        // of a new frame starting; the API functions below are re-created for it
+        let suspended_update = match phase {
+            FramePhase::Update => match self.suspended_updates.remove(&entity) {
+                Some(key) if self.debugger.borrow().suspended_entity() == Some(entity) => {
+                    self.suspended_updates.insert(entity, key);
+                    return Ok(());
+                }
+                suspended_update => suspended_update,
+            },
+            _ => None,
+        };
+        let debugger_armed = self.debugger.borrow().is_armed();
+        let vec2_values = self.vec2_values;
+
         // Use RefCell to work around borrow checker in scope
         let world_cell = RefCell::new(&mut *world);
         let log_callback_cell = RefCell::new(log_callback);
 
-        lua.scope(|scope| {
+        let suspended = lua.scope(|scope| {
             let globals = lua.globals();
             globals.set("entity", entity)?;
 
@@ -786,30 +2514,264 @@ impl ScriptEngine {
             // VIRTUAL INPUT (cross-platform)
             // ================================================================
 
-            let get_movement_input = scope.create_function(|lua, gamepad_id: Option<usize>| {
-                let movement = input.get_movement_input(gamepad_id.unwrap_or(0));
-                let table = lua.create_table()?;
-                table.set("x", movement.x)?;
-                table.set("y", movement.y)?;
-                Ok(table)
-            })?;
-            globals.set("get_movement_input", get_movement_input)?;
+            let get_movement_input = scope.create_function(|lua, gamepad_id: Option<usize>| {
+                let movement = input.get_movement_input(gamepad_id.unwrap_or(0));
+                let table = lua.create_table()?;
+                table.set("x", movement.x)?;
+                table.set("y", movement.y)?;
+                Ok(table)
+            })?;
+            globals.set("get_movement_input", get_movement_input)?;
+
+            let get_action_button = scope.create_function(|_, gamepad_id: Option<usize>| {
+                Ok(input.get_action_button(gamepad_id.unwrap_or(0)))
+            })?;
+            globals.set("get_action_button", get_action_button)?;
+
+            let get_action_button_pressed = scope.create_function(|_, gamepad_id: Option<usize>| {
+                Ok(input.get_action_button_pressed(gamepad_id.unwrap_or(0)))
+            })?;
+            globals.set("get_action_button_pressed", get_action_button_pressed)?;
+
+            // ================================================================
+            // INPUT ACTIONS (project input.json; players are 0-based, slots 1-based)
+            // ================================================================
+
+            let input_table = lua.create_table()?;
+
+            input_table.set("get_action", scope.create_function(|_, (action, player): (String, Option<usize>)| {
+                Ok(input.player_action_down(player.unwrap_or(0), &action))
+            })?)?;
+
+            input_table.set("get_action_pressed", scope.create_function(|_, (action, player): (String, Option<usize>)| {
+                Ok(input.player_action_pressed(player.unwrap_or(0), &action))
+            })?)?;
+
+            input_table.set("get_action_released", scope.create_function(|_, (action, player): (String, Option<usize>)| {
+                Ok(input.player_action_released(player.unwrap_or(0), &action))
+            })?)?;
+
+            input_table.set("get_axis", scope.create_function(|_, (action, player): (String, Option<usize>)| {
+                Ok(input.player_action_axis(player.unwrap_or(0), &action))
+            })?)?;
+
+            input_table.set("get_bindings", scope.create_function(|lua, (action, player): (String, Option<usize>)| {
+                let table = lua.create_table()?;
+                let bindings = input.config().profile(player.unwrap_or(0)).and_then(|profile| profile.action(&action));
+                if let Some(action) = bindings {
+                    for (i, binding) in action.bindings.iter().enumerate() {
+                        table.set(i + 1, binding.to_string())?;
+                    }
+                }
+                Ok(table)
+            })?)?;
+
+            input_table.set("get_virtual_joystick", scope.create_function(|lua, name: Option<String>| {
+                let value = input.virtual_joystick(name.as_deref());
+                let table = lua.create_table()?;
+                table.set("x", value.x)?;
+                table.set("y", value.y)?;
+                Ok(table)
+            })?)?;
+
+            input_table.set("is_virtual_button_down", scope.create_function(|_, name: String| {
+                Ok(input.is_virtual_button_down(&name))
+            })?)?;
+
+            // Mouse over interactable UI (its clicks don't reach is_mouse_button_pressed / actions)
+            input_table.set("is_pointer_over_ui", scope.create_function(|_, ()| Ok(input.pointer_over_ui()))?)?;
+
+            input_table.set("is_rebinding", scope.create_function(|_, ()| Ok(input.is_rebinding()))?)?;
+
+            input_table.set("get_rebind_result", scope.create_function(|lua, ()| {
+                let Some(result) = input.rebind_result() else {
+                    return Ok(None);
+                };
+                let table = lua.create_table()?;
+                match result {
+                    input::RebindResult::Bound { player, action, binding } => {
+                        table.set("status", "bound")?;
+                        table.set("player", *player)?;
+                        table.set("action", action.as_str())?;
+                        table.set("binding", binding.to_string())?;
+                    }
+                    input::RebindResult::Conflict { player, action, binding, conflicts, .. } => {
+                        table.set("status", "conflict")?;
+                        table.set("player", *player)?;
+                        table.set("action", action.as_str())?;
+                        table.set("binding", binding.to_string())?;
+                        table.set("conflicts", conflicts.clone())?;
+                    }
+                    input::RebindResult::Cancelled { player, action } => {
+                        table.set("status", "cancelled")?;
+                        table.set("player", *player)?;
+                        table.set("action", action.as_str())?;
+                    }
+                }
+                Ok(Some(table))
+            })?)?;
+
+            let input_commands = Rc::clone(&self.input_commands);
+            input_table.set("rebind", lua.create_function(move |_, (action, slot, player): (String, Option<usize>, Option<usize>)| {
+                let slot = slot.and_then(|slot| slot.checked_sub(1));
+                input_commands.borrow_mut().push(InputCommand::StartRebind { player: player.unwrap_or(0), action, slot });
+                Ok(())
+            })?)?;
+
+            let input_commands = Rc::clone(&self.input_commands);
+            input_table.set("cancel_rebind", lua.create_function(move |_, ()| {
+                input_commands.borrow_mut().push(InputCommand::CancelRebind);
+                Ok(())
+            })?)?;
+
+            let input_commands = Rc::clone(&self.input_commands);
+            input_table.set("accept_rebind_conflict", lua.create_function(move |_, ()| {
+                input_commands.borrow_mut().push(InputCommand::AcceptRebindConflict);
+                Ok(())
+            })?)?;
+
+            let input_commands = Rc::clone(&self.input_commands);
+            input_table.set("rumble", lua.create_function(move |_, (gamepad, strong, weak, duration): (usize, f32, Option<f32>, Option<f32>)| {
+                input_commands.borrow_mut().push(InputCommand::Rumble {
+                    gamepad,
+                    strong,
+                    weak: weak.unwrap_or(strong),
+                    duration: duration.unwrap_or(0.5),
+                });
+                Ok(())
+            })?)?;
+
+            // Mouse picking through the main camera (coordinates as get_mouse_position)
+            let picking_world = &world_cell;
+            let screen_view = Rc::clone(&self.screen_view);
+            input_table.set("pick_entity", scope.create_function(move |_, (x, y): (f32, f32)| {
+                let view = screen_view.borrow();
+                let world = picking_world.borrow();
+                let Some((camera, transform)) = view.main_camera(&world) else {
+                    return Ok(None);
+                };
+                let Some((origin, dir)) = ecs::picking::screen_ray(&camera, &transform, view.rect, glam::Vec2::new(x, y)) else {
+                    return Ok(None);
+                };
+                let pivot = |texture_id: &str| view.texture_pivots.get(texture_id).copied().unwrap_or([0.5, 0.5]);
+                Ok(ecs::picking::pick_sprites(&world, &view.sorting_layers, origin, dir, pivot).first().copied())
+            })?)?;
+
+            let screen_view = Rc::clone(&self.screen_view);
+            input_table.set("get_mouse_world_position", scope.create_function(move |lua, ()| {
+                let world = picking_world.borrow();
+                let view = screen_view.borrow();
+                let Some((camera, transform)) = view.main_camera(&world) else {
+                    return Ok(None);
+                };
+                let mouse = input.mouse_position();
+                let Some(point) = ecs::picking::screen_to_plane(&camera, &transform, view.rect, mouse, 0.0) else {
+                    return Ok(None);
+                };
+                let table = lua.create_table()?;
+                table.set("x", point.x)?;
+                table.set("y", point.y)?;
+                Ok(Some(table))
+            })?)?;
+
+            globals.set("Input", input_table)?;
+
+            // Canvas coordinates: reference units of a UI instance's canvas, bottom-left origin
+            // (what RectTransform positions use). `instance` defaults to the first active UI.
+            if let Ok(ui_table) = globals.get::<_, Table>("UI") {
+                fn point_table<'lua>(lua: &'lua Lua, point: glam::Vec2) -> mlua::Result<Table<'lua>> {
+                    let table = lua.create_table()?;
+                    table.set("x", point.x)?;
+                    table.set("y", point.y)?;
+                    Ok(table)
+                }
+
+                // UI.world_to_canvas(x, y, z?, instance?): {x, y, on_screen}; off-screen points and
+                // points behind the camera are clamped to the canvas edge (for off-screen indicators)
+                let screen_view = Rc::clone(&self.screen_view);
+                ui_table.set("world_to_canvas", scope.create_function(move |lua, (x, y, z, instance): (f32, f32, Option<f32>, Option<String>)| {
+                    let world = picking_world.borrow();
+                    let view = screen_view.borrow();
+                    let Some((camera, transform)) = view.main_camera(&world) else {
+                        return Ok(None);
+                    };
+                    let Some(space) = view.canvas_space(instance.as_deref()) else {
+                        return Ok(None);
+                    };
+                    let Some(point) = space.world_to_canvas(&camera, &transform, glam::Vec3::new(x, y, z.unwrap_or(0.0))) else {
+                        return Ok(None);
+                    };
+                    let table = point_table(lua, point.position)?;
+                    table.set("on_screen", point.on_screen)?;
+                    Ok(Some(table))
+                })?)?;
+
+                // UI.canvas_to_world(x, y, instance?): {x, y} on the z = 0 plane
+                let screen_view = Rc::clone(&self.screen_view);
+                ui_table.set("canvas_to_world", scope.create_function(move |lua, (x, y, instance): (f32, f32, Option<String>)| {
+                    let world = picking_world.borrow();
+                    let view = screen_view.borrow();
+                    let Some((camera, transform)) = view.main_camera(&world) else {
+                        return Ok(None);
+                    };
+                    let Some(space) = view.canvas_space(instance.as_deref()) else {
+                        return Ok(None);
+                    };
+                    space.canvas_to_world(&camera, &transform, glam::Vec2::new(x, y), 0.0)
+                        .map(|point| point_table(lua, point.truncate()))
+                        .transpose()
+                })?)?;
+
+                // UI.screen_to_canvas(x, y, instance?) / UI.canvas_to_screen: screen positions are
+                // in the coordinates of Input.get_mouse_position
+                let screen_view = Rc::clone(&self.screen_view);
+                ui_table.set("screen_to_canvas", scope.create_function(move |lua, (x, y, instance): (f32, f32, Option<String>)| {
+                    let Some(space) = screen_view.borrow().canvas_space(instance.as_deref()) else {
+                        return Ok(None);
+                    };
+                    point_table(lua, space.screen_to_canvas(glam::Vec2::new(x, y))).map(Some)
+                })?)?;
 
-            let get_action_button = scope.create_function(|_, gamepad_id: Option<usize>| {
-                Ok(input.get_action_button(gamepad_id.unwrap_or(0)))
-            })?;
-            globals.set("get_action_button", get_action_button)?;
+                let screen_view = Rc::clone(&self.screen_view);
+                ui_table.set("canvas_to_screen", scope.create_function(move |lua, (x, y, instance): (f32, f32, Option<String>)| {
+                    let Some(space) = screen_view.borrow().canvas_space(instance.as_deref()) else {
+                        return Ok(None);
+                    };
+                    point_table(lua, space.canvas_to_screen(glam::Vec2::new(x, y))).map(Some)
+                })?)?;
+            }
 
-            let get_action_button_pressed = scope.create_function(|_, gamepad_id: Option<usize>| {
-                Ok(input.get_action_button_pressed(gamepad_id.unwrap_or(0)))
-            })?;
-            globals.set("get_action_button_pressed", get_action_button_pressed)?;
+            // Profiler.get_frame_stats(): the performance HUD's numbers for the last frame,
+            // {fps, frame_time_ms, entity_count, draw_calls, scripts = {{entity, script, ms}}}.
+            // Calling it keeps script timings on, so `scripts` is empty the first time.
+            if let Ok(profiler_table) = globals.get::<_, Table>("Profiler") {
+                profiler_table.set("get_frame_stats", scope.create_function(move |lua, ()| {
+                    profiler::scripts::request_stats();
+                    let stats = profiler::scripts::frame_stats(profiler::scripts::TOP_SCRIPTS);
+                    let table = lua.create_table()?;
+                    table.set("fps", stats.fps)?;
+                    table.set("frame_time_ms", stats.frame_time.as_secs_f64() * 1000.0)?;
+                    table.set("entity_count", picking_world.borrow().transforms.len())?;
+                    table.set("draw_calls", stats.draw_calls)?;
+                    let scripts = lua.create_table()?;
+                    for (i, timing) in stats.scripts.iter().enumerate() {
+                        let entry = lua.create_table()?;
+                        entry.set("entity", timing.entity)?;
+                        entry.set("script", timing.script.as_str())?;
+                        entry.set("ms", timing.duration.as_secs_f64() * 1000.0)?;
+                        scripts.set(i + 1, entry)?;
+                    }
+                    table.set("scripts", scripts)?;
+                    Ok(table)
+                })?)?;
+            }
 
             // ================================================================
             // ENTITY/WORLD MANIPULATION
             // ================================================================
 
-            let set_velocity = scope.create_function_mut(|_, (vx, vy): (f32, f32)| {
+            let set_velocity = scope.create_function_mut(|_, args: mlua::MultiValue| {
+                let (vx, vy, _) = math::xy_args("set_velocity", args)?;
                 // Set velocity in both legacy and rigidbody systems
                 world_cell.borrow_mut().velocities.insert(entity, (vx, vy));
                 
@@ -829,10 +2791,7 @@ impl ScriptEngine {
                     world_cell.borrow().velocities.get(&entity).copied().unwrap_or((0.0, 0.0))
                 };
                 
-                let table = lua.create_table()?;
-                table.set("x", velocity.0)?;
-                table.set("y", velocity.1)?;
-                Ok(Some(table))
+                math::vec2_value(lua, velocity.0, velocity.1, None, vec2_values).map(Some)
             })?;
             globals.set("get_velocity", get_velocity)?;
 
@@ -855,11 +2814,8 @@ impl ScriptEngine {
 
             let get_position = scope.create_function(|lua, ()| {
                 if let Some(transform) = world_cell.borrow().transforms.get(&entity) {
-                    let table = lua.create_table()?;
-                    table.set("x", transform.position[0])?;
-                    table.set("y", transform.position[1])?;
-                    table.set("z", transform.position[2])?; // Expose Z for 3D
-                    Ok(Some(table))
+                    let [x, y, z] = transform.position;
+                    math::vec2_value(lua, x, y, Some(z), vec2_values).map(Some)
                 } else {
                     Ok(None)
                 }
@@ -929,6 +2885,238 @@ impl ScriptEngine {
             })?;
             globals.set("set_rotation_of", set_rotation_of)?;
 
+            // Render textures: point a camera at one by name (nil renders to the screen again)
+            let set_camera_target = scope.create_function_mut(|_, (camera_entity, name): (Entity, Option<String>)| {
+                match world_cell.borrow_mut().cameras.get_mut(&camera_entity) {
+                    Some(camera) => {
+                        camera.target_texture = name;
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            })?;
+            let render_texture_table = lua.create_table()?;
+            render_texture_table.set("set_camera_target", set_camera_target)?;
+            globals.set("RenderTexture", render_texture_table)?;
+
+            // ================================================================
+            // CAMERA (zoom: orthographic_size within the camera's Min/Max Size, FOV)
+            // ================================================================
+
+            let camera_table = lua.create_table()?;
+
+            // Camera.get_orthographic_size(camera): nil without a camera
+            camera_table.set("get_orthographic_size", scope.create_function(|_, camera_entity: Entity| {
+                Ok(world_cell.borrow().cameras.get(&camera_entity).map(|camera| camera.orthographic_size))
+            })?)?;
+
+            // Camera.set_orthographic_size(camera, size): clamped to the zoom limits
+            camera_table.set("set_orthographic_size", scope.create_function_mut(|_, (camera_entity, size): (Entity, f32)| {
+                Ok(timers::set_orthographic_size(&mut world_cell.borrow_mut(), camera_entity, size, None))
+            })?)?;
+
+            // Camera.set_fov(camera, degrees): perspective field of view (1-179)
+            camera_table.set("set_fov", scope.create_function_mut(|_, (camera_entity, degrees): (Entity, f32)| {
+                match world_cell.borrow_mut().cameras.get_mut(&camera_entity) {
+                    Some(camera) => {
+                        camera.fov = degrees.clamp(1.0, 179.0);
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            })?)?;
+
+            // Camera.zoom_to(camera, size, duration, easing?, focus?, on_complete?): animate the
+            // size. focus is nil (zoom on the center), "mouse" (keep the world point under the
+            // mouse in place) or {x=, y=} in world units.
+            let timers = &self.timers;
+            let zoom_world = &world_cell;
+            let screen_view = Rc::clone(&self.screen_view);
+            camera_table.set("zoom_to", scope.create_function_mut(move |lua, args: (Entity, f32, f32, Value, Value, Option<Function>)| {
+                let (camera_entity, size, duration, easing, focus, on_complete) = args;
+                let world = zoom_world.borrow();
+                let Some(camera) = world.cameras.get(&camera_entity) else {
+                    return Ok(None);
+                };
+                let size = camera.clamp_orthographic_size(size);
+                let focus = match focus {
+                    Value::Nil => None,
+                    Value::String(name) if name.to_str()? == "mouse" => {
+                        let view = screen_view.borrow();
+                        let camera = ecs::picking::framed_camera(camera, &view.aspect, view.rect);
+                        world.world_transform(camera_entity)
+                            .and_then(|transform| ecs::picking::screen_to_plane(&camera, &transform, view.rect, input.mouse_position(), 0.0))
+                            .map(|point| [point.x, point.y])
+                    }
+                    Value::Table(point) => Some([point.get::<_, f32>("x")?, point.get::<_, f32>("y")?]),
+                    other => {
+                        return Err(mlua::Error::RuntimeError(format!(
+                            "Camera.zoom_to: focus must be nil, \"mouse\" or {{x=, y=}}, got {}",
+                            other.type_name()
+                        )));
+                    }
+                };
+                let easing = timers::easing_from_lua("Camera.zoom_to", easing)?;
+                let on_complete = on_complete.map(|callback| lua.create_registry_value(callback)).transpose()?;
+                Ok(Some(timers::tween_camera_zoom(timers, entity, camera_entity, size, duration, easing, focus, on_complete)))
+            })?)?;
+
+            globals.set("Camera", camera_table)?;
+
+            // ================================================================
+            // MAP (per-tile custom properties from the tileset: damage, friction, is_ladder)
+            // ================================================================
+
+            let map_table = lua.create_table()?;
+
+            // Map.get_tile_property(tilemap_entity, x, y, name): nil for empty tiles or missing properties
+            map_table.set("get_tile_property", scope.create_function(|lua, (tilemap_entity, x, y, name): (Entity, u32, u32, String)| {
+                let world = world_cell.borrow();
+                let property = world.tilemaps.get(&tilemap_entity)
+                    .zip(world.tileset_for(tilemap_entity))
+                    .and_then(|(tilemap, tileset)| tilemap.get_tile_properties(tileset, x, y))
+                    .and_then(|properties| properties.get(&name));
+                match property {
+                    Some(property) => Self::tile_property_to_lua(lua, property),
+                    None => Ok(Value::Nil),
+                }
+            })?)?;
+
+            // Map.get_tile_properties(tilemap_entity, x, y): table of every property, or nil
+            map_table.set("get_tile_properties", scope.create_function(|lua, (tilemap_entity, x, y): (Entity, u32, u32)| {
+                let world = world_cell.borrow();
+                let Some(properties) = world.tilemaps.get(&tilemap_entity)
+                    .zip(world.tileset_for(tilemap_entity))
+                    .and_then(|(tilemap, tileset)| tilemap.get_tile_properties(tileset, x, y)) else {
+                    return Ok(Value::Nil);
+                };
+                let table = lua.create_table()?;
+                for (name, property) in properties {
+                    table.set(name.as_str(), Self::tile_property_to_lua(lua, property)?)?;
+                }
+                Ok(Value::Table(table))
+            })?)?;
+
+            globals.set("Map", map_table)?;
+
+            // ================================================================
+            // MATERIAL (SpriteMaterial parameters: flash_amount, outline_color, threshold...)
+            // ================================================================
+
+            let material_table = lua.create_table()?;
+
+            // Material.set_shader(target, name): switch variant ("flash", "outline", "dissolve"),
+            // adding the component if needed
+            material_table.set("set_shader", scope.create_function_mut(|_, (target, name): (Entity, String)| {
+                if ecs::SpriteShader::from_name(&name).is_none() {
+                    log::warn!("Material.set_shader: unknown shader '{}', using the default", name);
+                }
+                world_cell.borrow_mut().sprite_materials.entry(target).or_default().shader = name;
+                Ok(())
+            })?)?;
+
+            // Material.set_float(target, name, value): false when the target has no material
+            material_table.set("set_float", scope.create_function_mut(|_, (target, name, value): (Entity, String, f32)| {
+                match world_cell.borrow_mut().sprite_materials.get_mut(&target) {
+                    Some(material) => {
+                        material.set_float(&name, value);
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            })?)?;
+
+            // Material.get_float(target, name): the value (or the variant's default), nil without a material
+            material_table.set("get_float", scope.create_function(|_, (target, name): (Entity, String)| {
+                Ok(world_cell.borrow().sprite_materials.get(&target).map(|material| material.float(&name)))
+            })?)?;
+
+            // Material.set_color(target, name, r, g, b, a?)
+            material_table.set("set_color", scope.create_function_mut(|_, (target, name, r, g, b, a): (Entity, String, f32, f32, f32, Option<f32>)| {
+                match world_cell.borrow_mut().sprite_materials.get_mut(&target) {
+                    Some(material) => {
+                        material.set_color(&name, engine_core::Color::rgba(r, g, b, a.unwrap_or(1.0)));
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            })?)?;
+
+            // Material.flash(target, duration?): damage flash, flash_amount jumps to 1 and tweens
+            // back to 0 over `duration` (0.1s). Targets without a material get a flash material.
+            let timers = &self.timers;
+            let material_world = &world_cell;
+            material_table.set("flash", scope.create_function_mut(move |_, (target, duration): (Entity, Option<f32>)| {
+                material_world.borrow_mut().sprite_materials.entry(target)
+                    .or_insert_with(|| ecs::SpriteMaterial::new(ecs::SpriteShader::Flash))
+                    .set_float("flash_amount", 1.0);
+                Ok(timers::tween_material_float(timers, entity, target, "flash_amount", 0.0, duration.unwrap_or(0.1)))
+            })?)?;
+
+            globals.set("Material", material_table)?;
+
+            // ================================================================
+            // PHYSICS QUERIES (every active collider, filtered by this entity's row of the layer matrix)
+            // ================================================================
+
+            let physics_table = lua.create_table()?;
+            let query_world = &world_cell;
+            fn query_mask(world: &World, entity: Entity, settings: &PhysicsSettings) -> u32 {
+                settings.collision_mask(world.layers.get(&entity).copied().unwrap_or(0) as usize)
+            }
+
+            // Physics.overlap_box(x, y, w, h, tag?): entities overlapping the box centered on (x, y)
+            let physics_settings = Rc::clone(&self.physics_settings);
+            physics_table.set("overlap_box", scope.create_function(move |lua, (x, y, w, h, tag): (f32, f32, f32, f32, Option<String>)| {
+                let world = query_world.borrow();
+                let half = [w.abs() / 2.0, h.abs() / 2.0];
+                let mask = query_mask(&world, entity, &physics_settings.borrow());
+                let hits = physics::PhysicsWorld::overlap_aabb(&world, [x - half[0], y - half[1]], [x + half[0], y + half[1]], mask);
+                Self::overlap_results_to_lua(lua, &world, entity, hits, tag)
+            })?)?;
+
+            // Physics.overlap_circle(x, y, radius, tag?): entities overlapping the circle
+            let physics_settings = Rc::clone(&self.physics_settings);
+            physics_table.set("overlap_circle", scope.create_function(move |lua, (x, y, radius, tag): (f32, f32, f32, Option<String>)| {
+                let world = query_world.borrow();
+                let mask = query_mask(&world, entity, &physics_settings.borrow());
+                let hits = physics::PhysicsWorld::overlap_circle(&world, [x, y], radius, mask);
+                Self::overlap_results_to_lua(lua, &world, entity, hits, tag)
+            })?)?;
+
+            // Physics.closest_point(target, x, y): {x, y} on target's collider nearest to (x, y), e.g. for knockback
+            physics_table.set("closest_point", scope.create_function(|lua, (target, x, y): (Entity, f32, f32)| {
+                let Some(point) = physics::PhysicsWorld::closest_point_on_collider(&world_cell.borrow(), target, [x, y]) else {
+                    return Ok(None);
+                };
+                let table = lua.create_table()?;
+                table.set("x", point[0])?;
+                table.set("y", point[1])?;
+                Ok(Some(table))
+            })?)?;
+
+            // Physics.move_and_slide(target, dx, dy): move a character, stopping at colliders and sliding
+            // along them. Applied before this frame's physics step; the flags below update then.
+            let character_moves = &self.character_moves;
+            physics_table.set("move_and_slide", scope.create_function(move |_, (target, dx, dy): (Entity, f32, f32)| {
+                character_moves.borrow_mut().push(CharacterMoveCommand { entity: target, delta: [dx, dy] });
+                Ok(())
+            })?)?;
+
+            // Physics.is_on_floor(target?) / is_on_wall / is_on_ceiling: what the last move_and_slide touched
+            let character_states = &self.character_states;
+            physics_table.set("is_on_floor", scope.create_function(move |_, target: Option<Entity>| {
+                Ok(character_states.borrow().get(&target.unwrap_or(entity)).is_some_and(|m| m.on_floor))
+            })?)?;
+            physics_table.set("is_on_wall", scope.create_function(move |_, target: Option<Entity>| {
+                Ok(character_states.borrow().get(&target.unwrap_or(entity)).is_some_and(|m| m.on_wall))
+            })?)?;
+            physics_table.set("is_on_ceiling", scope.create_function(move |_, target: Option<Entity>| {
+                Ok(character_states.borrow().get(&target.unwrap_or(entity)).is_some_and(|m| m.on_ceiling))
+            })?)?;
+
+            globals.set("Physics", physics_table)?;
+
             let get_scale = scope.create_function(|lua, ()| {
                 if let Some(transform) = world_cell.borrow().transforms.get(&entity) {
                     let table = lua.create_table()?;
@@ -988,6 +3176,84 @@ impl ScriptEngine {
             })?;
             globals.set("get_sprite_flip_y", get_sprite_flip_y)?;
 
+            // set_sprite_color(target, r, g, b, a?): the sprite's own color; a ColorFade and a
+            // flash material still apply on top. False when the target has no sprite.
+            let set_sprite_color = scope.create_function_mut(|_, (target, r, g, b, a): (Entity, f32, f32, f32, Option<f32>)| {
+                match world_cell.borrow_mut().sprites.get_mut(&target) {
+                    Some(sprite) => {
+                        sprite.color = engine_core::Color::rgba(r, g, b, a.unwrap_or(1.0));
+                        Ok(true)
+                    }
+                    None => Ok(false),
+                }
+            })?;
+            globals.set("set_sprite_color", set_sprite_color)?;
+
+            // get_sprite_color(target): {r, g, b, a} (the sprite's own color, without its fade), or nil
+            let get_sprite_color = scope.create_function(|lua, target: Entity| {
+                let Some(color) = world_cell.borrow().sprites.get(&target).map(|sprite| sprite.color) else {
+                    return Ok(None);
+                };
+                let table = lua.create_table()?;
+                table.set("r", color.r)?;
+                table.set("g", color.g)?;
+                table.set("b", color.b)?;
+                table.set("a", color.a)?;
+                Ok(Some(table))
+            })?;
+            globals.set("get_sprite_color", get_sprite_color)?;
+
+            // fade_sprite(target, r, g, b, a, duration, easing?, on_complete?): tween the target's
+            // tint from where it is now, replacing its ColorFade. on_complete is "none",
+            // "deactivate" or "destroy".
+            let fade_sprite = scope.create_function_mut(|_, (target, r, g, b, a, duration, easing, on_complete): (Entity, f32, f32, f32, f32, f32, Option<String>, Option<String>)| {
+                let on_complete = match on_complete.as_deref() {
+                    None | Some("none") => ecs::FadeCompleteAction::None,
+                    Some("deactivate") => ecs::FadeCompleteAction::Deactivate,
+                    Some("destroy") => ecs::FadeCompleteAction::Destroy,
+                    Some(other) => {
+                        return Err(mlua::Error::RuntimeError(format!(
+                            "fade_sprite: unknown on_complete '{}' (none, deactivate or destroy)", other
+                        )));
+                    }
+                };
+                let mut world = world_cell.borrow_mut();
+                let from = world.color_fades.get(&target).and_then(|fade| fade.tint).unwrap_or(engine_core::Color::WHITE);
+                world.color_fades.insert(target, ecs::ColorFade {
+                    from,
+                    to: engine_core::Color::rgba(r, g, b, a),
+                    duration: duration.max(0.0),
+                    easing: easing.unwrap_or_else(|| "linear".to_string()),
+                    on_complete,
+                    ..Default::default()
+                });
+                Ok(())
+            })?;
+            globals.set("fade_sprite", fade_sprite)?;
+
+            // SetSpriteFrame(target, name): draw the named sprite sheet frame (flipbook scripts).
+            // The name is kept even when no sheet has the frame yet; returns whether one does.
+            let set_sprite_frame = scope.create_function_mut(|_, (target, name): (Entity, String)| {
+                let mut world = world_cell.borrow_mut();
+                let frame = world.sprite_sheet_for(target).and_then(|sheet| sheet.get_frame_by_name(&name)).cloned();
+                if let Some(sprite) = world.sprites.get_mut(&target) {
+                    sprite.frame_name = Some(name);
+                    if let Some(frame) = &frame {
+                        sprite.width = frame.width as f32;
+                        sprite.height = frame.height as f32;
+                        sprite.sprite_rect = Some(frame.rect());
+                        sprite.rect_rotated = frame.rotated;
+                    }
+                }
+                Ok(frame.is_some())
+            })?;
+            globals.set("SetSpriteFrame", set_sprite_frame)?;
+
+            let get_sprite_frame = scope.create_function(|_, target: Option<Entity>| {
+                Ok(world_cell.borrow().sprites.get(&target.unwrap_or(entity)).and_then(|sprite| sprite.frame_name.clone()))
+            })?;
+            globals.set("GetSpriteFrame", get_sprite_frame)?;
+
             // ================================================================
             // TAG & ENTITY QUERIES
             // ================================================================
@@ -1024,12 +3290,90 @@ impl ScriptEngine {
             // })?;
             // globals.set("get_name", get_name)?;
 
-            let destroy_entity = scope.create_function_mut(|_, target_entity: Entity| {
-                world_cell.borrow_mut().despawn(target_entity);
+            // destroy_entity(entity): despawned with its children after this frame's LateUpdate
+            // (OnDestroy first), so the rest of the frame still sees it
+            let pending_destroys = &self.pending_destroys;
+            let destroy_entity = scope.create_function_mut(move |_, target_entity: Entity| {
+                pending_destroys.borrow_mut().push(target_entity);
                 Ok(())
             })?;
             globals.set("destroy_entity", destroy_entity)?;
 
+            // SetActive(entity, bool): children follow their parent (OnEnable/OnDisable fire after this frame's scripts)
+            let set_active = scope.create_function_mut(|_, (target_entity, active): (Entity, bool)| {
+                world_cell.borrow_mut().set_active(target_entity, active);
+                Ok(())
+            })?;
+            globals.set("SetActive", set_active)?;
+
+            // IsActive(entity): false if the entity or any parent is inactive
+            let is_active = scope.create_function(|_, target_entity: Entity| {
+                Ok(world_cell.borrow().is_active_in_hierarchy(target_entity))
+            })?;
+            globals.set("IsActive", is_active)?;
+
+            // Duplicate(entity): deep copy with children next to the original, named "Enemy (1)".
+            // Returns the copy; its scripts get Awake/Start at the end of the frame.
+            let duplicate = scope.create_function_mut(|_, original: Entity| {
+                let mut world = world_cell.borrow_mut();
+                let copy = world.duplicate(original);
+                if let Some(copy) = copy {
+                    let copies = world.subtree(copy);
+                    self.scene_commands.borrow_mut().push(SceneCommand::Duplicated { original, copies });
+                }
+                Ok(copy)
+            })?;
+            globals.set("Duplicate", duplicate)?;
+
+            pools::register_pool_api(lua, scope, &world_cell, &self.pools, &self.scene_commands)?;
+            components::register_component_api(lua, scope, &world_cell, &self.components)?;
+
+            // World-space position through the parent chain (get_position stays local)
+            let get_world_position = scope.create_function(|lua, ()| {
+                if let Some(position) = world_cell.borrow().world_position(entity) {
+                    let table = lua.create_table()?;
+                    table.set("x", position[0])?;
+                    table.set("y", position[1])?;
+                    table.set("z", position[2])?;
+                    Ok(Some(table))
+                } else {
+                    Ok(None)
+                }
+            })?;
+            globals.set("get_world_position", get_world_position)?;
+
+            let get_world_position_of = scope.create_function(|lua, query_entity: Entity| {
+                if let Some(position) = world_cell.borrow().world_position(query_entity) {
+                    let table = lua.create_table()?;
+                    table.set("x", position[0])?;
+                    table.set("y", position[1])?;
+                    table.set("z", position[2])?;
+                    Ok(Some(table))
+                } else {
+                    Ok(None)
+                }
+            })?;
+            globals.set("get_world_position_of", get_world_position_of)?;
+
+            // set_world_position(x, y, z): back-solves the local position under the current parent
+            let set_world_position = scope.create_function_mut(|_, (x, y, z): (f32, f32, f32)| {
+                world_cell.borrow_mut().set_world_position(entity, [x, y, z]);
+                Ok(())
+            })?;
+            globals.set("set_world_position", set_world_position)?;
+
+            // set_parent(child, parent | nil, keep_local): keeps the child's world position unless keep_local
+            let set_parent = scope.create_function_mut(|_, (child, parent, keep_local): (Entity, Option<Entity>, Option<bool>)| {
+                world_cell.borrow_mut().reparent(child, parent, keep_local.unwrap_or(false));
+                Ok(())
+            })?;
+            globals.set("set_parent", set_parent)?;
+
+            let get_parent = scope.create_function(|_, query_entity: Entity| {
+                Ok(world_cell.borrow().get_parent(query_entity))
+            })?;
+            globals.set("get_parent", get_parent)?;
+
             // ================================================================
             // ENTITY QUERIES (for camera follow, etc.)
             // ================================================================
@@ -1062,11 +3406,8 @@ impl ScriptEngine {
             // Get position of another entity (separate function name)
             let get_position_of = scope.create_function(|lua, query_entity: Entity| {
                 if let Some(transform) = world_cell.borrow().transforms.get(&query_entity) {
-                    let table = lua.create_table()?;
-                    table.set("x", transform.position[0])?;
-                    table.set("y", transform.position[1])?;
-                    table.set("z", transform.position[2])?;
-                    Ok(Some(table))
+                    let [x, y, z] = transform.position;
+                    math::vec2_value(lua, x, y, Some(z), vec2_values).map(Some)
                 } else {
                     Ok(None)
                 }
@@ -1076,11 +3417,16 @@ impl ScriptEngine {
             globals.set("get_position_of", get_position_of)?;
             
             // Set position with z parameter
-            let set_position_xyz = scope.create_function_mut(|_, (x, y, z): (f32, f32, f32)| {
+            let set_position_xyz = scope.create_function_mut(|lua, args: mlua::MultiValue| {
+                // set_position(x, y, z) or set_position(vec2[, z]); z stays as it is when left out
+                let (x, y, rest) = math::xy_args("set_position", args)?;
+                let z: Option<f32> = mlua::FromLuaMulti::from_lua_multi(rest, lua)?;
                 if let Some(transform) = world_cell.borrow_mut().transforms.get_mut(&entity) {
                     transform.position[0] = x;
                     transform.position[1] = y;
-                    transform.position[2] = z;
+                    if let Some(z) = z {
+                        transform.position[2] = z;
+                    }
                 }
                 Ok(())
             })?;
@@ -1090,15 +3436,9 @@ impl ScriptEngine {
             // Get velocity of another entity (separate function to avoid conflict)
             let get_velocity_of = scope.create_function(|lua, query_entity: Entity| {
                 if let Some(vel) = world_cell.borrow().velocities.get(&query_entity) {
-                    let table = lua.create_table()?;
-                    table.set("x", vel.0)?;
-                    table.set("y", vel.1)?;
-                    Ok(Some(table))
+                    math::vec2_value(lua, vel.0, vel.1, None, vec2_values).map(Some)
                 } else if let Some(rb) = world_cell.borrow().rigidbodies.get(&query_entity) {
-                    let table = lua.create_table()?;
-                    table.set("x", rb.velocity.0)?;
-                    table.set("y", rb.velocity.1)?;
-                    Ok(Some(table))
+                    math::vec2_value(lua, rb.velocity.0, rb.velocity.1, None, vec2_values).map(Some)
                 } else {
                     Ok(None)
                 }
@@ -1124,58 +3464,77 @@ impl ScriptEngine {
             // DEBUG DRAW (Unity/Unreal style)
             // ================================================================
             
-            let debug_lines_ref = &self.debug_lines;
+            let debug_draw_ref = &self.debug_draw;
             
             // debug_draw_line(start_x, start_y, start_z, end_x, end_y, end_z, r, g, b, a, duration)
             let debug_draw_line = scope.create_function_mut(move |_, args: (f32, f32, f32, f32, f32, f32, f32, f32, f32, f32, f32)| {
                 let (sx, sy, sz, ex, ey, ez, r, g, b, a, duration) = args;
-                debug_lines_ref.borrow_mut().push(DebugLine {
-                    start: [sx, sy, sz],
-                    end: [ex, ey, ez],
-                    color: [r, g, b, a],
-                    duration,
-                });
+                debug_draw_ref.borrow_mut().draw_line(DebugCategory::Script, [sx, sy, sz], [ex, ey, ez], engine_core::Color::rgba(r, g, b, a), duration);
                 Ok(())
             })?;
             globals.set("debug_draw_line", debug_draw_line)?;
             
             // Simplified version for 2D: debug_draw_line_2d(start_x, start_y, end_x, end_y, r, g, b, duration)
-            let debug_lines_ref2 = &self.debug_lines;
             let debug_draw_line_2d = scope.create_function_mut(move |_, args: (f32, f32, f32, f32, f32, f32, f32, f32)| {
                 let (sx, sy, ex, ey, r, g, b, duration) = args;
-                debug_lines_ref2.borrow_mut().push(DebugLine {
-                    start: [sx, sy, 0.0],
-                    end: [ex, ey, 0.0],
-                    color: [r, g, b, 1.0],
-                    duration,
-                });
+                debug_draw_ref.borrow_mut().draw_line(DebugCategory::Script, [sx, sy, 0.0], [ex, ey, 0.0], engine_core::Color::rgb(r, g, b), duration);
                 Ok(())
             })?;
             globals.set("debug_draw_line_2d", debug_draw_line_2d)?;
             
             // Helper: debug_draw_ray(origin_x, origin_y, origin_z, dir_x, dir_y, dir_z, length, r, g, b, duration)
-            let debug_lines_ref3 = &self.debug_lines;
             let debug_draw_ray = scope.create_function_mut(move |_, args: (f32, f32, f32, f32, f32, f32, f32, f32, f32, f32, f32)| {
                 let (ox, oy, oz, dx, dy, dz, length, r, g, b, duration) = args;
-                let end_x = ox + dx * length;
-                let end_y = oy + dy * length;
-                let end_z = oz + dz * length;
-                debug_lines_ref3.borrow_mut().push(DebugLine {
-                    start: [ox, oy, oz],
-                    end: [end_x, end_y, end_z],
-                    color: [r, g, b, 1.0],
-                    duration,
-                });
+                let end = [ox + dx * length, oy + dy * length, oz + dz * length];
+                debug_draw_ref.borrow_mut().draw_line(DebugCategory::Script, [ox, oy, oz], end, engine_core::Color::rgb(r, g, b), duration);
                 Ok(())
             })?;
             globals.set("debug_draw_ray", debug_draw_ray)?;
 
+            // 2D shapes: color defaults to white, duration to 0 (this frame only)
+            type Optional = Option<f32>;
+            fn debug_color(r: Optional, g: Optional, b: Optional) -> engine_core::Color {
+                engine_core::Color::rgb(r.unwrap_or(1.0), g.unwrap_or(1.0), b.unwrap_or(1.0))
+            }
+
+            // debug_draw_box(center_x, center_y, width, height, r?, g?, b?, duration?)
+            let debug_draw_box = scope.create_function_mut(move |_, args: (f32, f32, f32, f32, Optional, Optional, Optional, Optional)| {
+                let (x, y, width, height, r, g, b, duration) = args;
+                debug_draw_ref.borrow_mut().draw_box(DebugCategory::Script, [x, y, 0.0], [width, height], debug_color(r, g, b), duration.unwrap_or(0.0));
+                Ok(())
+            })?;
+            globals.set("debug_draw_box", debug_draw_box)?;
+
+            // debug_draw_circle(center_x, center_y, radius, r?, g?, b?, duration?)
+            let debug_draw_circle = scope.create_function_mut(move |_, args: (f32, f32, f32, Optional, Optional, Optional, Optional)| {
+                let (x, y, radius, r, g, b, duration) = args;
+                debug_draw_ref.borrow_mut().draw_circle(DebugCategory::Script, [x, y, 0.0], radius, debug_color(r, g, b), duration.unwrap_or(0.0));
+                Ok(())
+            })?;
+            globals.set("debug_draw_circle", debug_draw_circle)?;
+
+            // debug_draw_cross(x, y, size, r?, g?, b?, duration?)
+            let debug_draw_cross = scope.create_function_mut(move |_, args: (f32, f32, f32, Optional, Optional, Optional, Optional)| {
+                let (x, y, size, r, g, b, duration) = args;
+                debug_draw_ref.borrow_mut().draw_cross(DebugCategory::Script, [x, y, 0.0], size, debug_color(r, g, b), duration.unwrap_or(0.0));
+                Ok(())
+            })?;
+            globals.set("debug_draw_cross", debug_draw_cross)?;
+
+            // debug_draw_text(x, y, text, r?, g?, b?, duration?)
+            let debug_draw_text = scope.create_function_mut(move |_, args: (f32, f32, String, Optional, Optional, Optional, Optional)| {
+                let (x, y, text, r, g, b, duration) = args;
+                debug_draw_ref.borrow_mut().draw_text(DebugCategory::Script, [x, y, 0.0], text, debug_color(r, g, b), duration.unwrap_or(0.0));
+                Ok(())
+            })?;
+            globals.set("debug_draw_text", debug_draw_text)?;
+
             // ================================================================
-            // PHYSICS - GROUND CHECK (Rapier support)
+            // PHYSICS - GROUND CHECK
             // ================================================================
             
-            // Get ground state from script engine (set by engine with Rapier result)
-            let is_grounded = self.ground_states.get(&entity).copied().unwrap_or(false);
+            // Older scripts: same as Physics.is_on_floor() (needs Physics.move_and_slide, either backend)
+            let is_grounded = self.character_states.borrow().get(&entity).is_some_and(|m| m.on_floor);
             globals.set("is_grounded_rapier", is_grounded)?;
 
             // ================================================================
@@ -1223,7 +3582,7 @@ impl ScriptEngine {
             })?;
             globals.set("GetVelocity", get_velocity_unity)?;
             
-            // GetScriptParameter(entity, parameter_name) -> {Float: value} or {Int: value} or {Bool: value} or {String: value} or {Entity: value} or nil
+            // GetScriptParameter(entity, parameter_name) -> {Float: value} or {Int: value} or {Bool: value} or {String: value} or {Entity: value} or {Curve: curve} or nil
             let get_script_parameter = scope.create_function(|lua, (query_entity, param_name): (Entity, String)| {
                 if let Some(script) = world_cell.borrow().scripts.get(&query_entity) {
                     if let Some(param_value) = script.parameters.get(&param_name) {
@@ -1235,6 +3594,7 @@ impl ScriptEngine {
                             ecs::ScriptParameter::String(v) => table.set("String", v.clone())?,
                             ecs::ScriptParameter::Entity(Some(e)) => table.set("Entity", *e)?,
                             ecs::ScriptParameter::Entity(None) => table.set("Entity", mlua::Nil)?,
+                            ecs::ScriptParameter::Curve(curve) => table.set("Curve", timers::CurveHandle(curve.clone()))?,
                         }
                         Ok(Some(table))
                     } else {
@@ -1260,6 +3620,7 @@ impl ScriptEngine {
                         ecs::ScriptParameter::Bool(v) => globals.set(name.as_str(), *v)?,
                         ecs::ScriptParameter::Entity(Some(e)) => globals.set(name.as_str(), *e)?,
                         ecs::ScriptParameter::Entity(None) => globals.set(name.as_str(), mlua::Nil)?,
+                        ecs::ScriptParameter::Curve(curve) => globals.set(name.as_str(), timers::CurveHandle(curve.clone()))?,
                     }
                 }
             }
@@ -1268,27 +3629,87 @@ impl ScriptEngine {
             // CALL LIFECYCLE FUNCTIONS (Unity-style with backward compatibility)
             // ================================================================
 
+            match phase {
+                FramePhase::Update => {}
+                FramePhase::LateUpdate => {
+                    if let Ok(late_update) = globals.get::<_, Function>("LateUpdate") {
+                        late_update.call::<_, ()>(dt)?;
+                    }
+                    return Ok(None);
+                }
+                FramePhase::Eval { code, output } => {
+                    if entity == repl::REPL_OWNER {
+                        globals.set("entity", mlua::Nil)?;
+                    }
+                    // print() shows up in the console instead of stdout
+                    let print: Value = globals.get("print")?;
+                    let capture = scope.create_function(move |_, args: mlua::MultiValue| {
+                        output.borrow_mut().push(ReplLine::Output(repl::print_line(&args)));
+                        Ok(())
+                    })?;
+                    globals.set("print", capture)?;
+                    let result = repl::eval(lua, code);
+                    globals.set("print", print)?;
+                    match result {
+                        Ok(values) => output.borrow_mut().extend(values.into_iter().map(ReplLine::Value)),
+                        Err(e) => output.borrow_mut().push(ReplLine::Error(e.to_string())),
+                    }
+                    return Ok(None);
+                }
+            }
+
+            if let Some(key) = suspended_update {
+                // Timers, UI events and bus events already ran in the frame the break happened
+                let thread: mlua::Thread = lua.registry_value(&key)?;
+                lua.remove_registry_value(key)?;
+                return debugger::resume_update(lua, thread, (), &self.debugger, entity);
+            }
+
+            // Timer/Tween callbacks that became due this frame run before Update
+            timers::run_due_callbacks(&self.timers, lua, entity)?;
+
+            // Then coroutines whose wait is over
+            coroutines::run_due_coroutines(&self.coroutines, lua, entity)?;
+
+            // Then UI events (UI.on_submit / UI.on_value_changed) from last frame
+            ui_events::run_pending_callbacks(&self.ui_events, lua, entity)?;
+
+            // Then events emitted on the bus since this script last ran
+            events::run_pending_events(&self.events, lua, entity)?;
+
+            // Then Globals.subscribe callbacks for values changed since then
+            globals::run_pending_changes(&self.global_state, lua, entity)?;
+
             // Try Unity-style Update() first, then fall back to on_update()
             if let Ok(update_func) = globals.get::<_, Function>("Update") {
-                // Unity-style: Update(dt)
+                // Unity-style: Update(dt). Run as a coroutine while debugging so a
+                // breakpoint can suspend it.
+                if debugger_armed {
+                    let thread = lua.create_thread(update_func)?;
+                    return debugger::resume_update(lua, thread, dt, &self.debugger, entity);
+                }
                 update_func.call::<_, ()>(dt)?;
             } else if let Ok(on_update) = globals.get::<_, Function>("on_update") {
                 // Backward compatibility: on_update(entity, dt)
                 on_update.call::<_, ()>((entity, dt))?;
             }
 
-            Ok(())
+            Ok(None)
         })?;
 
+        if let Some(key) = suspended {
+            self.suspended_updates.insert(entity, key);
+        }
         Ok(())
     }
 
-    /// Call on_collision callback for a script
+    /// Call a script's collision callback: OnCollisionEnter (or on_collision), OnCollisionStay
+    /// or OnCollisionExit, depending on the phase
     pub fn call_collision(
         &mut self,
-        _script_path: &std::path::Path,
         entity: Entity,
         other_entity: Entity,
+        phase: physics::CollisionPhase,
         world: &mut World,
     ) -> Result<()> {
         // Get the entity's Lua state
@@ -1297,6 +3718,16 @@ impl ScriptEngine {
             None => return Ok(()), // Entity has no loaded script
         };
 
+        // Stay fires every step: skip the setup below when the script has no callback for it
+        let callbacks: &[&str] = match phase {
+            physics::CollisionPhase::Enter => &["OnCollisionEnter", "on_collision"],
+            physics::CollisionPhase::Stay => &["OnCollisionStay"],
+            physics::CollisionPhase::Exit => &["OnCollisionExit"],
+        };
+        if !callbacks.iter().any(|name| lua.globals().get::<_, Function>(*name).is_ok()) {
+            return Ok(());
+        }
+
         // Use RefCell to work around borrow checker in scope
         let world_cell = RefCell::new(&mut *world);
 
@@ -1321,23 +3752,69 @@ impl ScriptEngine {
             })?;
             globals.set("get_tag", get_tag)?;
 
-            let destroy_entity = scope.create_function_mut(|_, target_entity: Entity| {
-                world_cell.borrow_mut().despawn(target_entity);
+            // destroy_entity(entity): despawned with its children after this frame's LateUpdate
+            // (OnDestroy first), so the rest of the frame still sees it
+            let pending_destroys = &self.pending_destroys;
+            let destroy_entity = scope.create_function_mut(move |_, target_entity: Entity| {
+                pending_destroys.borrow_mut().push(target_entity);
                 Ok(())
             })?;
             globals.set("destroy_entity", destroy_entity)?;
 
+            // SetActive(entity, bool): children follow their parent (OnEnable/OnDisable fire after this frame's scripts)
+            let set_active = scope.create_function_mut(|_, (target_entity, active): (Entity, bool)| {
+                world_cell.borrow_mut().set_active(target_entity, active);
+                Ok(())
+            })?;
+            globals.set("SetActive", set_active)?;
+
+            // IsActive(entity): false if the entity or any parent is inactive
+            let is_active = scope.create_function(|_, target_entity: Entity| {
+                Ok(world_cell.borrow().is_active_in_hierarchy(target_entity))
+            })?;
+            globals.set("IsActive", is_active)?;
+
+            // Duplicate(entity): deep copy with children next to the original, named "Enemy (1)".
+            // Returns the copy; its scripts get Awake/Start at the end of the frame.
+            let duplicate = scope.create_function_mut(|_, original: Entity| {
+                let mut world = world_cell.borrow_mut();
+                let copy = world.duplicate(original);
+                if let Some(copy) = copy {
+                    let copies = world.subtree(copy);
+                    self.scene_commands.borrow_mut().push(SceneCommand::Duplicated { original, copies });
+                }
+                Ok(copy)
+            })?;
+            globals.set("Duplicate", duplicate)?;
+
+            pools::register_pool_api(lua, scope, &world_cell, &self.pools, &self.scene_commands)?;
+            components::register_component_api(lua, scope, &world_cell, &self.components)?;
+
             // ================================================================
             // CALL COLLISION CALLBACKS (Unity-style with backward compatibility)
             // ================================================================
 
-            // Try Unity-style OnCollisionEnter first
-            if let Ok(on_collision_enter) = globals.get::<_, Function>("OnCollisionEnter") {
-                on_collision_enter.call::<_, ()>(other_entity)?;
-            }
-            // Backward compatibility: call on_collision
-            else if let Ok(on_collision) = globals.get::<_, Function>("on_collision") {
-                on_collision.call::<_, ()>(other_entity)?;
+            match phase {
+                physics::CollisionPhase::Enter => {
+                    // Try Unity-style OnCollisionEnter first
+                    if let Ok(on_collision_enter) = globals.get::<_, Function>("OnCollisionEnter") {
+                        on_collision_enter.call::<_, ()>(other_entity)?;
+                    }
+                    // Backward compatibility: call on_collision
+                    else if let Ok(on_collision) = globals.get::<_, Function>("on_collision") {
+                        on_collision.call::<_, ()>(other_entity)?;
+                    }
+                }
+                physics::CollisionPhase::Stay => {
+                    if let Ok(on_collision_stay) = globals.get::<_, Function>("OnCollisionStay") {
+                        on_collision_stay.call::<_, ()>(other_entity)?;
+                    }
+                }
+                physics::CollisionPhase::Exit => {
+                    if let Ok(on_collision_exit) = globals.get::<_, Function>("OnCollisionExit") {
+                        on_collision_exit.call::<_, ()>(other_entity)?;
+                    }
+                }
             }
 
             Ok(())
@@ -1345,4 +3822,140 @@ impl ScriptEngine {
 
         Ok(())
     }
+
+    /// Call on_animation_event(name) (or OnAnimationEvent) for an event marker on an animation clip keyframe
+    pub fn call_animation_event(&self, entity: Entity, event: &str, world: &mut World) -> Result<()> {
+        let Some(lua) = self.entity_states.get(&entity) else {
+            return Ok(());
+        };
+        let globals = lua.globals();
+        let Ok(callback) = globals
+            .get::<_, Function>("on_animation_event")
+            .or_else(|_| globals.get::<_, Function>("OnAnimationEvent"))
+        else {
+            return Ok(());
+        };
+
+        let world_cell = RefCell::new(&mut *world);
+        lua.scope(|scope| {
+            globals.set("entity", entity)?;
+
+            // destroy_entity(entity): despawned with its children after this frame's LateUpdate
+            // (OnDestroy first), so the rest of the frame still sees it
+            let pending_destroys = &self.pending_destroys;
+            let destroy_entity = scope.create_function_mut(move |_, target_entity: Entity| {
+                pending_destroys.borrow_mut().push(target_entity);
+                Ok(())
+            })?;
+            globals.set("destroy_entity", destroy_entity)?;
+
+            let set_active = scope.create_function_mut(|_, (target_entity, active): (Entity, bool)| {
+                world_cell.borrow_mut().set_active(target_entity, active);
+                Ok(())
+            })?;
+            globals.set("SetActive", set_active)?;
+
+            let is_active = scope.create_function(|_, target_entity: Entity| {
+                Ok(world_cell.borrow().is_active_in_hierarchy(target_entity))
+            })?;
+            globals.set("IsActive", is_active)?;
+
+            let duplicate = scope.create_function_mut(|_, original: Entity| {
+                let mut world = world_cell.borrow_mut();
+                let copy = world.duplicate(original);
+                if let Some(copy) = copy {
+                    let copies = world.subtree(copy);
+                    self.scene_commands.borrow_mut().push(SceneCommand::Duplicated { original, copies });
+                }
+                Ok(copy)
+            })?;
+            globals.set("Duplicate", duplicate)?;
+
+            pools::register_pool_api(lua, scope, &world_cell, &self.pools, &self.scene_commands)?;
+            components::register_component_api(lua, scope, &world_cell, &self.components)?;
+
+            callback.call::<_, ()>(event)?;
+            Ok(())
+        })?;
+        Ok(())
+    }
+
+    /// Deliver a trigger zone enter/exit: the zone's event (if set) goes out on the event
+    /// bus as { zone, entity, phase }, then its on_enter(entity) / on_exit(entity) runs in
+    /// the zone's own script
+    pub fn call_trigger_zone(&self, event: &physics::TriggerZoneEvent, world: &mut World) -> Result<()> {
+        let Some(zone) = world.trigger_zones.get(&event.zone) else {
+            return Ok(()); // Removed since the step
+        };
+        let (phase, callback) = match event.phase {
+            physics::TriggerZonePhase::Enter => ("enter", zone.on_enter.clone()),
+            physics::TriggerZonePhase::Exit => ("exit", zone.on_exit.clone()),
+        };
+        if let Some(name) = zone.event.as_deref().filter(|name| !name.is_empty()) {
+            self.emit_event(
+                ScriptEvent::new(name)
+                    .with("zone", EventValue::Integer(event.zone as i64))
+                    .with("entity", EventValue::Integer(event.entity as i64))
+                    .with("phase", EventValue::String(phase.to_string())),
+            );
+        }
+
+        let Some(callback) = callback.filter(|name| !name.is_empty()) else {
+            return Ok(());
+        };
+        if !world.scripts.get(&event.zone).is_some_and(|script| script.enabled) {
+            return Ok(());
+        }
+        let Some(lua) = self.entity_states.get(&event.zone) else {
+            return Ok(());
+        };
+        let globals = lua.globals();
+        let Ok(function) = globals.get::<_, Function>(callback.as_str()) else {
+            log::warn!("Trigger zone callback '{}' not found in script of entity {}", callback, event.zone);
+            return Ok(());
+        };
+
+        let world_cell = RefCell::new(&mut *world);
+        lua.scope(|scope| {
+            globals.set("entity", event.zone)?;
+
+            // destroy_entity(entity): despawned with its children after this frame's LateUpdate
+            // (OnDestroy first), so the rest of the frame still sees it
+            let pending_destroys = &self.pending_destroys;
+            let destroy_entity = scope.create_function_mut(move |_, target_entity: Entity| {
+                pending_destroys.borrow_mut().push(target_entity);
+                Ok(())
+            })?;
+            globals.set("destroy_entity", destroy_entity)?;
+
+            let set_active = scope.create_function_mut(|_, (target_entity, active): (Entity, bool)| {
+                world_cell.borrow_mut().set_active(target_entity, active);
+                Ok(())
+            })?;
+            globals.set("SetActive", set_active)?;
+
+            let is_active = scope.create_function(|_, target_entity: Entity| {
+                Ok(world_cell.borrow().is_active_in_hierarchy(target_entity))
+            })?;
+            globals.set("IsActive", is_active)?;
+
+            let duplicate = scope.create_function_mut(|_, original: Entity| {
+                let mut world = world_cell.borrow_mut();
+                let copy = world.duplicate(original);
+                if let Some(copy) = copy {
+                    let copies = world.subtree(copy);
+                    self.scene_commands.borrow_mut().push(SceneCommand::Duplicated { original, copies });
+                }
+                Ok(copy)
+            })?;
+            globals.set("Duplicate", duplicate)?;
+
+            pools::register_pool_api(lua, scope, &world_cell, &self.pools, &self.scene_commands)?;
+            components::register_component_api(lua, scope, &world_cell, &self.components)?;
+
+            function.call::<_, ()>(event.entity)?;
+            Ok(())
+        })?;
+        Ok(())
+    }
 }
@@ -0,0 +1,385 @@
+// Native 2D math for Lua scripts (the Vec2 type and the Mathf table)
+//
+// Vec2 is userdata backed by glam, so vector math in hot loops runs in Rust instead of
+// allocating and dispatching through Lua tables like the engine.vec2 module does. Angles
+// are in degrees, like Transform rotations. Wherever a Vec2 argument is expected an
+// {x=, y=} table (get_position() with table values) works too.
+//
+//   local to_target = Vec2(target.x, target.y) - Vec2(pos.x, pos.y)
+//   set_velocity(to_target:normalized() * speed)
+
+use engine_core::frame_clock::GameTime;
+use engine_core::random::EngineRng;
+use glam::Vec2;
+use mlua::{Lua, MetaMethod, MultiValue, Table, UserData, UserDataFields, UserDataMethods, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A Vec2 value in Lua
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LuaVec2(pub Vec2);
+
+impl UserData for LuaVec2 {
+    fn add_fields<'lua, F: UserDataFields<'lua, Self>>(fields: &mut F) {
+        fields.add_field_method_get("x", |_, this| Ok(this.0.x));
+        fields.add_field_method_get("y", |_, this| Ok(this.0.y));
+        fields.add_field_method_set("x", |_, this, x: f32| {
+            this.0.x = x;
+            Ok(())
+        });
+        fields.add_field_method_set("y", |_, this, y: f32| {
+            this.0.y = y;
+            Ok(())
+        });
+    }
+
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("length", |_, this, ()| Ok(this.0.length()));
+        methods.add_method("length_squared", |_, this, ()| Ok(this.0.length_squared()));
+        // Zero stays zero
+        methods.add_method("normalized", |_, this, ()| Ok(LuaVec2(this.0.normalize_or_zero())));
+        methods.add_method("dot", |_, this, other: Value| Ok(this.0.dot(vec2_arg(&other, "dot")?)));
+        // z of the 3D cross product (positive when `other` is counter-clockwise)
+        methods.add_method("cross", |_, this, other: Value| Ok(this.0.perp_dot(vec2_arg(&other, "cross")?)));
+        methods.add_method("distance", |_, this, other: Value| Ok(this.0.distance(vec2_arg(&other, "distance")?)));
+        methods.add_method("distance_squared", |_, this, other: Value| {
+            Ok(this.0.distance_squared(vec2_arg(&other, "distance_squared")?))
+        });
+        // Unit vector from this point towards `other` (zero if they coincide)
+        methods.add_method("direction_to", |_, this, other: Value| {
+            Ok(LuaVec2((vec2_arg(&other, "direction_to")? - this.0).normalize_or_zero()))
+        });
+        // Degrees from the +x axis, counter-clockwise
+        methods.add_method("angle", |_, this, ()| Ok(this.0.y.atan2(this.0.x).to_degrees()));
+        // Signed degrees to turn this vector's direction onto `other`'s (-180..180)
+        methods.add_method("angle_to", |_, this, other: Value| {
+            let other = vec2_arg(&other, "angle_to")?;
+            Ok(this.0.perp_dot(other).atan2(this.0.dot(other)).to_degrees())
+        });
+        // Rotated counter-clockwise by `degrees`
+        methods.add_method("rotated", |_, this, degrees: f32| {
+            Ok(LuaVec2(Vec2::from_angle(degrees.to_radians()).rotate(this.0)))
+        });
+        methods.add_method("lerp", |_, this, (other, t): (Value, f32)| {
+            Ok(LuaVec2(this.0.lerp(vec2_arg(&other, "lerp")?, t)))
+        });
+        // At most `max_delta` closer to `other`, without overshooting it
+        methods.add_method("move_towards", |_, this, (other, max_delta): (Value, f32)| {
+            Ok(LuaVec2(move_towards(this.0, vec2_arg(&other, "move_towards")?, max_delta)))
+        });
+        // Same direction, at most `max_length` long
+        methods.add_method("clamped", |_, this, max_length: f32| Ok(LuaVec2(this.0.clamp_length_max(max_length))));
+        methods.add_method("copy", |_, this, ()| Ok(*this));
+        methods.add_method("unpack", |_, this, ()| Ok((this.0.x, this.0.y)));
+        // {x=, y=} table, for APIs and scripts that expect one
+        methods.add_method("to_table", |lua, this, ()| {
+            let table = lua.create_table()?;
+            table.set("x", this.0.x)?;
+            table.set("y", this.0.y)?;
+            Ok(table)
+        });
+
+        methods.add_meta_function(MetaMethod::Add, |_, (a, b): (Value, Value)| {
+            Ok(LuaVec2(vec2_arg(&a, "+")? + vec2_arg(&b, "+")?))
+        });
+        methods.add_meta_function(MetaMethod::Sub, |_, (a, b): (Value, Value)| {
+            Ok(LuaVec2(vec2_arg(&a, "-")? - vec2_arg(&b, "-")?))
+        });
+        // Vec2 * number, number * Vec2 or component-wise Vec2 * Vec2
+        methods.add_meta_function(MetaMethod::Mul, |_, (a, b): (Value, Value)| {
+            Ok(LuaVec2(vec2_or_scalar(&a, "*")? * vec2_or_scalar(&b, "*")?))
+        });
+        methods.add_meta_function(MetaMethod::Div, |_, (a, b): (Value, Value)| {
+            Ok(LuaVec2(vec2_or_scalar(&a, "/")? / vec2_or_scalar(&b, "/")?))
+        });
+        methods.add_meta_method(MetaMethod::Unm, |_, this, ()| Ok(LuaVec2(-this.0)));
+        methods.add_meta_function(MetaMethod::Eq, |_, (a, b): (Value, Value)| {
+            Ok(vec2_arg(&a, "==")? == vec2_arg(&b, "==")?)
+        });
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("Vec2({}, {})", this.0.x, this.0.y)));
+    }
+}
+
+/// A Vec2 argument: Vec2 userdata or an {x=, y=} table
+pub fn vec2_arg(value: &Value, function: &str) -> mlua::Result<Vec2> {
+    match value {
+        Value::UserData(data) => {
+            if let Ok(vec) = data.borrow::<LuaVec2>() {
+                return Ok(vec.0);
+            }
+        }
+        Value::Table(table) => return Ok(Vec2::new(table.get("x")?, table.get("y")?)),
+        _ => {}
+    }
+    Err(mlua::Error::RuntimeError(format!("{}: expected a Vec2, got {}", function, value.type_name())))
+}
+
+/// Operand of * and /: a number scales both components
+fn vec2_or_scalar(value: &Value, function: &str) -> mlua::Result<Vec2> {
+    match value {
+        Value::Integer(scalar) => Ok(Vec2::splat(*scalar as f32)),
+        Value::Number(scalar) => Ok(Vec2::splat(*scalar as f32)),
+        other => vec2_arg(other, function),
+    }
+}
+
+/// Value of get_position / get_velocity: Vec2 userdata when the project opted in (the
+/// Scripting setting), otherwise the {x=, y=[, z=]} table existing scripts expect
+pub fn vec2_value<'lua>(lua: &'lua Lua, x: f32, y: f32, z: Option<f32>, as_vec2: bool) -> mlua::Result<Value<'lua>> {
+    if as_vec2 {
+        return lua.create_userdata(LuaVec2(Vec2::new(x, y))).map(Value::UserData);
+    }
+    let table = lua.create_table()?;
+    table.set("x", x)?;
+    table.set("y", y)?;
+    if let Some(z) = z {
+        table.set("z", z)?;
+    }
+    Ok(Value::Table(table))
+}
+
+/// Arguments of set_position / set_velocity: x and y as numbers or as one Vec2 (or
+/// {x=, y=} table), followed by the remaining arguments
+pub fn xy_args<'lua>(function: &str, args: MultiValue<'lua>) -> mlua::Result<(f32, f32, MultiValue<'lua>)> {
+    let mut args = args.into_vec();
+    let first = if args.is_empty() { Value::Nil } else { args.remove(0) };
+    if matches!(first, Value::UserData(_) | Value::Table(_)) {
+        let xy = vec2_arg(&first, function)?;
+        return Ok((xy.x, xy.y, MultiValue::from_vec(args)));
+    }
+    let number = |value: &Value| match value {
+        Value::Integer(n) => Ok(*n as f32),
+        Value::Number(n) => Ok(*n as f32),
+        other => Err(mlua::Error::RuntimeError(format!(
+            "{}: expected numbers or a Vec2, got {}", function, other.type_name()
+        ))),
+    };
+    let x = number(&first)?;
+    let second = if args.is_empty() { Value::Nil } else { args.remove(0) };
+    let y = number(&second)?;
+    Ok((x, y, MultiValue::from_vec(args)))
+}
+
+fn move_towards(current: Vec2, target: Vec2, max_delta: f32) -> Vec2 {
+    let delta = target - current;
+    let distance = delta.length();
+    if distance <= max_delta || distance == 0.0 {
+        target
+    } else {
+        current + delta * (max_delta / distance)
+    }
+}
+
+/// Unity's Mathf.Approximately: equal within a float epsilon scaled to the values
+fn approximately(a: f32, b: f32) -> bool {
+    (b - a).abs() < (1e-6 * a.abs().max(b.abs())).max(f32::EPSILON * 8.0)
+}
+
+/// Critically damped spring towards `target` (Unity's SmoothDamp). Returns the new value
+/// and velocity; Lua has no out parameters, so scripts keep the velocity themselves.
+fn smooth_damp(current: f32, target: f32, velocity: f32, smooth_time: f32, max_speed: f32, dt: f32) -> (f32, f32) {
+    if dt <= 0.0 {
+        return (current, velocity);
+    }
+    let smooth_time = smooth_time.max(0.0001);
+    let omega = 2.0 / smooth_time;
+    let x = omega * dt;
+    let exp = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+    // A negative max_speed caps the same as its magnitude; NaN means no cap
+    let max_change = if max_speed.is_nan() { f32::INFINITY } else { max_speed.abs() * smooth_time };
+    let change = (current - target).clamp(-max_change, max_change);
+    let temp = (velocity + omega * change) * dt;
+    let mut velocity = (velocity - omega * temp) * exp;
+    let mut output = (current - change) + (change + temp) * exp;
+    // Don't overshoot
+    if (target - current > 0.0) == (output > target) {
+        output = target;
+        velocity = 0.0;
+    }
+    (output, velocity)
+}
+
+/// Shortest signed difference between two angles in degrees (-180..180)
+fn delta_angle(current: f32, target: f32) -> f32 {
+    let delta = (target - current).rem_euclid(360.0);
+    if delta > 180.0 { delta - 360.0 } else { delta }
+}
+
+/// Register the Vec2 constructor table and Mathf in an entity's Lua state. Mathf's
+/// random_range draws from the engine RNG (seeded per run, like math.random) and
+/// smooth_damp defaults to this frame's Time.delta.
+pub fn register_math_api(lua: &Lua, rng: Rc<RefCell<EngineRng>>, time: Rc<RefCell<GameTime>>) -> mlua::Result<()> {
+    lua.globals().set("Vec2", create_vec2_table(lua)?)?;
+    lua.globals().set("Mathf", create_mathf_table(lua, rng, time)?)?;
+    Ok(())
+}
+
+/// Vec2(x, y) / Vec2.new(x, y) and the other constructors
+pub fn create_vec2_table(lua: &Lua) -> mlua::Result<Table<'_>> {
+    let vec2_table = lua.create_table()?;
+    let new = lua.create_function(|_, (x, y): (Option<f32>, Option<f32>)| {
+        Ok(LuaVec2(Vec2::new(x.unwrap_or(0.0), y.unwrap_or(0.0))))
+    })?;
+    vec2_table.set("new", new)?;
+    // Unit vector at `degrees` from the +x axis
+    vec2_table.set("from_angle", lua.create_function(|_, degrees: f32| {
+        Ok(LuaVec2(Vec2::from_angle(degrees.to_radians())))
+    })?)?;
+    // From an {x=, y=} table (get_position() with table values)
+    vec2_table.set("from_table", lua.create_function(|_, table: Table| {
+        Ok(LuaVec2(Vec2::new(table.get("x")?, table.get("y")?)))
+    })?)?;
+    vec2_table.set("is_vec2", lua.create_function(|_, value: Value| {
+        Ok(matches!(&value, Value::UserData(data) if data.is::<LuaVec2>()))
+    })?)?;
+    vec2_table.set("zero", lua.create_function(|_, ()| Ok(LuaVec2(Vec2::ZERO)))?)?;
+    vec2_table.set("one", lua.create_function(|_, ()| Ok(LuaVec2(Vec2::ONE)))?)?;
+    vec2_table.set("up", lua.create_function(|_, ()| Ok(LuaVec2(Vec2::Y)))?)?;
+    vec2_table.set("right", lua.create_function(|_, ()| Ok(LuaVec2(Vec2::X)))?)?;
+
+    let metatable = lua.create_table()?;
+    metatable.set("__call", lua.create_function(|_, (_, x, y): (Table, Option<f32>, Option<f32>)| {
+        Ok(LuaVec2(Vec2::new(x.unwrap_or(0.0), y.unwrap_or(0.0))))
+    })?)?;
+    vec2_table.set_metatable(Some(metatable));
+    Ok(vec2_table)
+}
+
+fn create_mathf_table(lua: &Lua, rng: Rc<RefCell<EngineRng>>, time: Rc<RefCell<GameTime>>) -> mlua::Result<Table<'_>> {
+    let mathf = lua.create_table()?;
+    mathf.set("PI", std::f64::consts::PI)?;
+    mathf.set("DEG2RAD", std::f64::consts::PI / 180.0)?;
+    mathf.set("RAD2DEG", 180.0 / std::f64::consts::PI)?;
+
+    mathf.set("deg_to_rad", lua.create_function(|_, degrees: f64| Ok(degrees.to_radians()))?)?;
+    mathf.set("rad_to_deg", lua.create_function(|_, radians: f64| Ok(radians.to_degrees()))?)?;
+    mathf.set("clamp", lua.create_function(|_, (value, min, max): (f64, f64, f64)| {
+        Ok(value.max(min).min(max))
+    })?)?;
+    mathf.set("clamp01", lua.create_function(|_, value: f64| Ok(value.clamp(0.0, 1.0)))?)?;
+    // -1, 0 or 1
+    mathf.set("sign", lua.create_function(|_, value: f64| {
+        Ok(if value > 0.0 { 1 } else if value < 0.0 { -1 } else { 0 })
+    })?)?;
+    mathf.set("approximately", lua.create_function(|_, (a, b): (f32, f32)| Ok(approximately(a, b)))?)?;
+    mathf.set("lerp", lua.create_function(|_, (a, b, t): (f64, f64, f64)| {
+        Ok(a + (b - a) * t.clamp(0.0, 1.0))
+    })?)?;
+    mathf.set("inverse_lerp", lua.create_function(|_, (a, b, value): (f64, f64, f64)| {
+        Ok(if a == b { 0.0 } else { ((value - a) / (b - a)).clamp(0.0, 1.0) })
+    })?)?;
+    mathf.set("move_towards", lua.create_function(|_, (current, target, max_delta): (f64, f64, f64)| {
+        Ok(if (target - current).abs() <= max_delta { target } else { current + max_delta.copysign(target - current) })
+    })?)?;
+    mathf.set("delta_angle", lua.create_function(|_, (current, target): (f32, f32)| Ok(delta_angle(current, target)))?)?;
+
+    // Mathf.smooth_damp(current, target, velocity, smooth_time, max_speed?, dt?) -> value, velocity
+    mathf.set("smooth_damp", lua.create_function(move |_, args: (f32, f32, f32, f32, Option<f32>, Option<f32>)| {
+        let (current, target, velocity, smooth_time, max_speed, dt) = args;
+        let dt = dt.unwrap_or_else(|| time.borrow().delta);
+        Ok(smooth_damp(current, target, velocity, smooth_time, max_speed.unwrap_or(f32::INFINITY), dt))
+    })?)?;
+
+    // Float in [min, max) from the engine RNG, so runs with the same seed repeat
+    mathf.set("random_range", lua.create_function(move |_, (min, max): (f64, f64)| {
+        Ok(min + (max - min) * rng.borrow_mut().next_f64())
+    })?)?;
+    Ok(mathf)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_support::{global, run, script_engine, spawn_scripted};
+    use ecs::World;
+
+    const MOVER: &str = r#"
+        function Update(dt)
+            local position = get_position()
+            kind = Vec2.is_vec2(position) and "vec2" or "table"
+            local step = Vec2(3, 4)
+            set_position(Vec2(position.x, position.y) + step:normalized() * 5)
+            set_velocity(step * 2)
+            angle = math.floor(Vec2(1, 0):angle_to(Vec2.from_angle(90)) + 0.5)
+            clamped = Mathf.clamp(7, 0, 5)
+        end
+    "#;
+
+    #[test]
+    fn test_vec2_values_setting() {
+        for vec2_values in [false, true] {
+            let mut script_engine = script_engine();
+            script_engine.set_vec2_values(vec2_values);
+            let mut world = World::new();
+            let mover = spawn_scripted(&mut script_engine, &mut world, "mover", MOVER);
+            world.transforms.get_mut(&mover).unwrap().position = [1.0, 2.0, 9.0];
+            run(&mut script_engine, &mut world, mover, 0.016);
+
+            let kind = if vec2_values { "\"vec2\"" } else { "\"table\"" };
+            assert_eq!(global(&script_engine, mover, "kind").as_deref(), Some(kind));
+            assert_eq!(global(&script_engine, mover, "angle").as_deref(), Some("90"));
+            assert_eq!(global(&script_engine, mover, "clamped").as_deref(), Some("5"));
+
+            // set_position / set_velocity take a Vec2 either way; a left out z stays
+            let position = world.transforms[&mover].position;
+            assert!((position[0] - 4.0).abs() < 1e-5 && (position[1] - 6.0).abs() < 1e-5, "{:?}", position);
+            assert_eq!(position[2], 9.0);
+            assert_eq!(world.velocities.get(&mover), Some(&(6.0, 8.0)));
+        }
+    }
+
+    #[test]
+    fn test_vec2_operators_and_methods() {
+        let mut script_engine = script_engine();
+        let mut world = World::new();
+        let probe = spawn_scripted(&mut script_engine, &mut world, "probe", r#"
+            local a, b = Vec2(3, 4), Vec2.new(1, 2)
+            sum = tostring(a + b)
+            scaled = tostring(2 * a / 4)
+            negated = tostring(-b)
+            equal = a == Vec2(3, 4) and a ~= b
+            length = a:length()
+            towards = tostring(Vec2.zero():move_towards({ x = 10, y = 0 }, 4))
+            rotated = tostring(Vec2.right():rotated(180):lerp(Vec2.zero(), 0.5))
+            ok, err = pcall(function() return a + 1 end)
+            -- mlua hands Rust errors to Lua as userdata; tostring gives the message
+            message = tostring(err)
+        "#);
+
+        let value = |name: &str| global(&script_engine, probe, name).unwrap_or_default();
+        assert_eq!(value("sum"), "\"Vec2(4, 6)\"");
+        assert_eq!(value("scaled"), "\"Vec2(1.5, 2)\"");
+        assert_eq!(value("negated"), "\"Vec2(-1, -2)\"");
+        assert_eq!(value("equal"), "true");
+        assert_eq!(value("length"), "5");
+        assert_eq!(value("towards"), "\"Vec2(4, 0)\"");
+        assert!(value("rotated").starts_with("\"Vec2(-0.5, "), "{}", value("rotated"));
+        assert_eq!(value("ok"), "false");
+        assert!(value("message").contains("expected a Vec2, got integer"), "{}", value("message"));
+    }
+
+    #[test]
+    fn test_mathf_helpers() {
+        use super::{approximately, delta_angle, smooth_damp};
+
+        assert!(approximately(0.1 + 0.2, 0.3));
+        assert!(!approximately(1.0, 1.001));
+        assert_eq!(delta_angle(350.0, 10.0), 20.0);
+        assert_eq!(delta_angle(10.0, 350.0), -20.0);
+
+        // Reaches the target without overshooting, and stays there
+        let (mut value, mut velocity) = (0.0, 0.0);
+        for _ in 0..200 {
+            (value, velocity) = smooth_damp(value, 10.0, velocity, 0.3, f32::INFINITY, 1.0 / 60.0);
+            assert!(value <= 10.0);
+        }
+        assert!((value - 10.0).abs() < 1e-3);
+        // max_speed caps the step
+        let (value, _) = smooth_damp(0.0, 100.0, 0.0, 0.1, 5.0, 1.0);
+        assert!(value <= 5.0 + 1e-4, "{}", value);
+        // A negative or NaN max_speed doesn't panic: negative caps by its magnitude, NaN doesn't cap
+        let (value, _) = smooth_damp(0.0, 100.0, 0.0, 0.1, -5.0, 1.0);
+        assert!(value > 0.0 && value <= 5.0 + 1e-4, "{}", value);
+        let (value, _) = smooth_damp(0.0, 100.0, 0.0, 0.1, f32::NAN, 1.0);
+        assert!(value > 5.0 && value <= 100.0, "{}", value);
+    }
+}
@@ -0,0 +1,406 @@
+// Lua modules: require() across project scripts
+//
+// Every Lua state (the main one and each entity's) gets its own `require`. "utils.math2d"
+// resolves to scripts/utils/math2d.lua (or scripts/utils/math2d/init.lua), then to the same
+// file under each library path from Project Settings > Scripting. "engine.*" names are the
+// standard library shipped with the engine (script/lua/engine). A module runs once per Lua
+// state and is cached in that state's package.loaded; when its file changes on disk the
+// cache entry is dropped in every state so the next require loads it again.
+
+use engine_core::assets::AssetLoader;
+use mlua::{Function, Lua, Table, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Folder project modules are looked up in first
+pub const SCRIPTS_DIR: &str = "scripts";
+
+/// Standard library modules shipped with the engine: (require name, source). The sources
+/// carry LuaLS annotations, so written out with `write_library_stubs` they double as
+/// autocomplete stubs.
+pub const ENGINE_MODULES: [(&str, &str); 3] = [
+    ("engine.vec2", include_str!("../lua/engine/vec2.lua")),
+    ("engine.table", include_str!("../lua/engine/table.lua")),
+    ("engine.class", include_str!("../lua/engine/class.lua")),
+];
+
+/// How often `changed_modules` looks at file modification times
+const CHANGE_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+
+struct LoadedModule {
+    /// Project-relative file the module came from (None for engine modules)
+    path: Option<String>,
+    /// Modification time when it was loaded, for files read from the project folder
+    modified: Option<SystemTime>,
+}
+
+/// Where modules are found, shared by every Lua state of a ScriptEngine
+pub struct ModuleRegistry {
+    asset_loader: Arc<dyn AssetLoader>,
+    /// Read modules straight from this folder instead of the asset loader (the editor,
+    /// where files can change while playing)
+    project_root: Option<PathBuf>,
+    /// Extra folders searched after scripts/, relative to the project
+    lib_paths: Vec<String>,
+    loaded: HashMap<String, LoadedModule>,
+    last_check: Option<Instant>,
+}
+
+impl ModuleRegistry {
+    pub fn new(asset_loader: Arc<dyn AssetLoader>) -> Self {
+        Self {
+            asset_loader,
+            project_root: None,
+            lib_paths: Vec::new(),
+            loaded: HashMap::new(),
+            last_check: None,
+        }
+    }
+
+    /// Set the search folders. Without a project root modules load through the asset loader.
+    pub fn configure(&mut self, project_root: Option<&Path>, lib_paths: &[String]) {
+        self.project_root = project_root.map(Path::to_path_buf);
+        self.lib_paths = lib_paths
+            .iter()
+            .map(|path| path.trim().trim_matches('/').replace('\\', "/"))
+            .filter(|path| !path.is_empty())
+            .collect();
+        self.loaded.clear();
+    }
+
+    /// Files tried for a module name, in order
+    pub fn candidates(&self, name: &str) -> Vec<String> {
+        let relative = name.replace('.', "/");
+        std::iter::once(SCRIPTS_DIR)
+            .chain(self.lib_paths.iter().map(String::as_str))
+            .flat_map(|dir| [format!("{}/{}.lua", dir, relative), format!("{}/{}/init.lua", dir, relative)])
+            .collect()
+    }
+
+    /// Whether require() would find a module, without running it
+    pub fn exists(&self, name: &str) -> bool {
+        if ENGINE_MODULES.iter().any(|(module, _)| *module == name) {
+            return true;
+        }
+        self.candidates(name).iter().any(|candidate| match &self.project_root {
+            Some(root) => root.join(candidate).is_file(),
+            None => pollster::block_on(self.asset_loader.load_text(candidate)).is_ok(),
+        })
+    }
+
+    /// Source of a module and the chunk name to run it under. The error lists the files tried.
+    fn load(&mut self, name: &str) -> Result<(String, String), String> {
+        if let Some((_, source)) = ENGINE_MODULES.iter().find(|(module, _)| *module == name) {
+            self.loaded.insert(name.to_string(), LoadedModule { path: None, modified: None });
+            let chunk_name = format!("{}.lua", name.replace('.', "/"));
+            return Ok((chunk_name, source.to_string()));
+        }
+        if name.is_empty() || name.split('.').any(|part| part.is_empty() || part == "..") {
+            return Err(format!("module '{}' not found: invalid module name", name));
+        }
+
+        let candidates = self.candidates(name);
+        for candidate in &candidates {
+            let loaded = match &self.project_root {
+                Some(root) => {
+                    let file = root.join(candidate);
+                    std::fs::read_to_string(&file).ok().map(|source| (source, modified_time(&file)))
+                }
+                None => pollster::block_on(self.asset_loader.load_text(candidate)).ok().map(|source| (source, None)),
+            };
+            if let Some((source, modified)) = loaded {
+                self.loaded.insert(name.to_string(), LoadedModule { path: Some(candidate.clone()), modified });
+                return Ok((candidate.clone(), source));
+            }
+        }
+        Err(format!("module '{}' not found; tried {}", name, candidates.join(", ")))
+    }
+
+    /// Modules whose file changed since it was loaded (checked at most twice a second).
+    /// They are forgotten here; drop them from package.loaded so require reloads them.
+    pub fn changed_modules(&mut self) -> Vec<String> {
+        let Some(root) = &self.project_root else {
+            return Vec::new();
+        };
+        if self.last_check.is_some_and(|at| at.elapsed() < CHANGE_CHECK_INTERVAL) {
+            return Vec::new();
+        }
+        self.last_check = Some(Instant::now());
+
+        let changed: Vec<String> = self
+            .loaded
+            .iter()
+            .filter(|(_, module)| {
+                module.path.as_ref().is_some_and(|path| modified_time(&root.join(path)) != module.modified)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &changed {
+            self.loaded.remove(name);
+        }
+        changed
+    }
+
+    /// Forget a module (the next require in each state loads it again once dropped from
+    /// package.loaded)
+    pub fn forget(&mut self, name: &str) {
+        self.loaded.remove(name);
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
+}
+
+/// Replace `require` in a Lua state with one that resolves through `modules`
+pub fn register_require(lua: &Lua, modules: &Rc<RefCell<ModuleRegistry>>) -> mlua::Result<()> {
+    let modules = Rc::clone(modules);
+    // Modules being loaded right now in this state, outermost first (cycle detection)
+    let loading: RefCell<Vec<String>> = RefCell::new(Vec::new());
+
+    let require = lua.create_function(move |lua, name: String| {
+        let package: Table = lua.globals().get("package")?;
+        let loaded: Table = package.get("loaded")?;
+        let cached: Value = loaded.get(name.as_str())?;
+        if !matches!(cached, Value::Nil) {
+            return Ok(cached);
+        }
+
+        let stack = loading.borrow();
+        if let Some(start) = stack.iter().position(|module| *module == name) {
+            let cycle: Vec<&str> = stack[start..].iter().map(String::as_str).chain([name.as_str()]).collect();
+            return Err(mlua::Error::RuntimeError(format!("circular require: {}", cycle.join(" -> "))));
+        }
+        drop(stack);
+
+        // package.preload still works for modules registered from Lua
+        let preload: Option<Function> = package.get::<_, Table>("preload")?.get(name.as_str())?;
+        let (chunk, chunk_name) = match preload {
+            Some(loader) => (loader, format!(":preload:{}", name)),
+            None => {
+                let (chunk_name, source) = modules.borrow_mut().load(&name).map_err(mlua::Error::RuntimeError)?;
+                // Named by file so errors read "scripts/utils/math2d.lua:12: ..."
+                let chunk = lua.load(&source).set_name(format!("@{}", chunk_name)).into_function()?;
+                (chunk, chunk_name)
+            }
+        };
+
+        loading.borrow_mut().push(name.clone());
+        let result = chunk.call::<_, Value>((name.as_str(), chunk_name));
+        loading.borrow_mut().pop();
+
+        // A module may fill package.loaded itself; otherwise its result (true for nil) is cached
+        let value = match (loaded.get::<_, Value>(name.as_str())?, result?) {
+            (Value::Nil, Value::Nil) => Value::Boolean(true),
+            (Value::Nil, value) => value,
+            (existing, _) => existing,
+        };
+        loaded.set(name.as_str(), value.clone())?;
+        Ok(value)
+    })?;
+
+    lua.globals().set("require", require)
+}
+
+/// Drop a module from a state's package.loaded
+pub fn unload(lua: &Lua, name: &str) -> mlua::Result<()> {
+    let package: Table = lua.globals().get("package")?;
+    package.get::<_, Table>("loaded")?.set(name, Value::Nil)
+}
+
+/// Write the engine's standard library (annotated for LuaLS) to `dir/engine/*.lua`, for
+/// editor autocomplete. Returns the files written.
+pub fn write_library_stubs(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    let mut written = Vec::new();
+    for (name, source) in ENGINE_MODULES {
+        let file = dir.join(format!("{}.lua", name.replace('.', "/")));
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&file, source)?;
+        written.push(file);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::memory_loader;
+
+    /// A Lua state whose require() resolves through `modules`
+    fn state(modules: &Rc<RefCell<ModuleRegistry>>) -> Lua {
+        let lua = Lua::new();
+        register_require(&lua, modules).unwrap();
+        lua
+    }
+
+    fn registry(files: &[(&str, &str)]) -> Rc<RefCell<ModuleRegistry>> {
+        Rc::new(RefCell::new(ModuleRegistry::new(memory_loader(files))))
+    }
+
+    fn write(root: &Path, file: &str, source: &str) {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, source).unwrap();
+    }
+
+    #[test]
+    fn test_candidates_try_scripts_before_lib_paths() {
+        let mut modules = ModuleRegistry::new(memory_loader(&[]));
+        modules.configure(None, &[" libs/ ".to_string(), String::new(), "vendor\\lua".to_string()]);
+        assert_eq!(modules.candidates("utils.math2d"), [
+            "scripts/utils/math2d.lua",
+            "scripts/utils/math2d/init.lua",
+            "libs/utils/math2d.lua",
+            "libs/utils/math2d/init.lua",
+            "vendor/lua/utils/math2d.lua",
+            "vendor/lua/utils/math2d/init.lua",
+        ]);
+    }
+
+    #[test]
+    fn test_project_files_resolve_in_search_order() {
+        let project = tempfile::tempdir().unwrap();
+        let root = project.path();
+        write(root, "scripts/shared.lua", "return 'scripts'");
+        write(root, "libs/shared.lua", "return 'libs'");
+        write(root, "libs/only_lib.lua", "return 'libs'");
+        write(root, "libs/pack/init.lua", "return 'pack init'");
+
+        let modules = registry(&[]);
+        modules.borrow_mut().configure(Some(root), &["libs".to_string()]);
+        let lua = state(&modules);
+        let require = |name: &str| lua.load(format!("return require('{}')", name)).eval::<String>();
+        assert_eq!(require("shared").unwrap(), "scripts");
+        assert_eq!(require("only_lib").unwrap(), "libs");
+        assert_eq!(require("pack").unwrap(), "pack init");
+        assert!(modules.borrow().exists("pack") && !modules.borrow().exists("missing"));
+
+        let error = require("missing").unwrap_err().to_string();
+        assert!(error.contains("module 'missing' not found; tried scripts/missing.lua"), "{}", error);
+        assert!(require("..secrets").unwrap_err().to_string().contains("invalid module name"));
+    }
+
+    #[test]
+    fn test_asset_loader_is_used_without_a_project_root() {
+        let modules = registry(&[("scripts/greet.lua", "return function(name) return 'hi ' .. name end")]);
+        let lua = state(&modules);
+        assert_eq!(lua.load("return require('greet')('bob')").eval::<String>().unwrap(), "hi bob");
+    }
+
+    #[test]
+    fn test_circular_require_is_an_error() {
+        let modules = registry(&[
+            ("scripts/a.lua", "return require('b')"),
+            ("scripts/b.lua", "return require('a')"),
+        ]);
+        let lua = state(&modules);
+        let error = lua.load("require('a')").exec().unwrap_err().to_string();
+        assert!(error.contains("circular require: a -> b -> a"), "{}", error);
+
+        // The failed load leaves nothing half cached
+        assert!(lua.load("return package.loaded.a == nil and package.loaded.b == nil").eval::<bool>().unwrap());
+    }
+
+    #[test]
+    fn test_modules_run_once_per_state() {
+        let modules = registry(&[("scripts/counter.lua", "runs = (runs or 0) + 1 return { id = runs }")]);
+        let first = state(&modules);
+        let second = state(&modules);
+
+        let same = "return require('counter') == require('counter')";
+        assert!(first.load(same).eval::<bool>().unwrap());
+        first.load("require('counter')").exec().unwrap();
+        assert_eq!(first.globals().get::<_, i64>("runs").unwrap(), 1);
+
+        // Each state runs the module itself: no tables shared across entities
+        second.load("require('counter')").exec().unwrap();
+        assert_eq!(second.globals().get::<_, i64>("runs").unwrap(), 1);
+
+        // A module that returns nothing is cached as true
+        let modules = registry(&[("scripts/setup.lua", "ran = (ran or 0) + 1")]);
+        let lua = state(&modules);
+        assert!(lua.load("return require('setup') == true and require('setup') == true and ran == 1").eval::<bool>().unwrap());
+    }
+
+    #[test]
+    fn test_changed_files_are_reloaded() {
+        let project = tempfile::tempdir().unwrap();
+        let root = project.path();
+        write(root, "scripts/config.lua", "return 1");
+        write(root, "scripts/other.lua", "return 'other'");
+
+        let modules = registry(&[]);
+        modules.borrow_mut().configure(Some(root), &[]);
+        let lua = state(&modules);
+        let value = || lua.load("return require('config')").eval::<i64>().unwrap();
+        assert_eq!(value(), 1);
+        lua.load("require('other')").exec().unwrap();
+        assert!(modules.borrow_mut().changed_modules().is_empty());
+
+        write(root, "scripts/config.lua", "return 2");
+        // Move the timestamp on explicitly: file systems with coarse mtimes could miss a quick rewrite
+        let file = std::fs::File::options().write(true).open(root.join("scripts/config.lua")).unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(5)).unwrap();
+
+        // Checked at most twice a second
+        assert!(modules.borrow_mut().changed_modules().is_empty());
+        modules.borrow_mut().last_check = None;
+        assert_eq!(modules.borrow_mut().changed_modules(), ["config"]);
+
+        // Still cached until dropped from the state's package.loaded
+        assert_eq!(value(), 1);
+        unload(&lua, "config").unwrap();
+        assert_eq!(value(), 2);
+
+        // Forgotten modules are not reported again
+        modules.borrow_mut().forget("other");
+        write(root, "scripts/other.lua", "return 'changed'");
+        std::fs::File::options().write(true).open(root.join("scripts/other.lua")).unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(10)).unwrap();
+        modules.borrow_mut().last_check = None;
+        assert!(modules.borrow_mut().changed_modules().is_empty());
+    }
+
+    #[test]
+    fn test_engine_modules() {
+        let modules = registry(&[]);
+        let lua = state(&modules);
+        let result = lua
+            .load(
+                r#"
+                local Vec2 = require("engine.vec2")
+                local tbl = require("engine.table")
+                local class = require("engine.class")
+
+                local v = Vec2(3, 4) + Vec2.new(1, 0) * 2
+                local Enemy = class()
+                function Enemy:init(hp) self.hp = hp end
+                local Boss = class(Enemy)
+                local boss = Boss(500)
+
+                local doubled = tbl.map({ 1, 2, 3 }, function(x) return x * 2 end)
+                local odd = tbl.filter({ 1, 2, 3 }, function(x) return x % 2 == 1 end)
+                return table.concat({
+                    tostring(v), Vec2(3, 4):length(), tostring(Vec2.is_vec2(v)),
+                    boss.hp, tostring(boss:is_a(Enemy)), tostring(Enemy(1):is_a(Boss)),
+                    table.concat(doubled, ","), #odd,
+                }, " ")
+                "#,
+            )
+            .eval::<String>()
+            .unwrap();
+        assert_eq!(result, "Vec2(5, 4) 5.0 true 500 true false 2,4,6 2");
+        assert!(ENGINE_MODULES.iter().all(|(name, _)| modules.borrow().exists(name)));
+
+        let dir = tempfile::tempdir().unwrap();
+        let written = write_library_stubs(dir.path()).unwrap();
+        assert_eq!(written.len(), ENGINE_MODULES.len());
+        assert!(dir.path().join("engine/class.lua").is_file());
+    }
+}
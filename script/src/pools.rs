@@ -0,0 +1,168 @@
+// Entity pools for scripts (Pool.create / Pool.spawn / Pool.release / Pool.stats)
+//
+// The template is any entity of the scene, usually an inactive one, found by name or
+// id. Instances load their script once, when the pool makes them (Awake, then Start on
+// first spawn); every later spawn calls OnSpawn instead. The functions need the world,
+// so they're registered inside each lua.scope() that runs script code.
+
+use crate::SceneCommand;
+use ecs::pool::{PoolManager, PoolPolicy, PoolStats};
+use ecs::{Entity, World};
+use mlua::{Lua, Scope, Table, Value};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[derive(Default)]
+pub struct ScriptPools {
+    pub manager: PoolManager,
+    // Spawned instances whose OnSpawn hasn't run yet
+    spawned: Vec<Entity>,
+}
+
+impl ScriptPools {
+    /// Spawned instances still waiting for OnSpawn, in spawn order
+    pub(crate) fn take_spawned(&mut self) -> Vec<Entity> {
+        std::mem::take(&mut self.spawned)
+    }
+
+    /// OnSpawn for `entity` once its script runs (a new instance's script loads at the
+    /// end of the frame it was made in)
+    pub(crate) fn queue_spawned(&mut self, entity: Entity) {
+        if !self.spawned.contains(&entity) {
+            self.spawned.push(entity);
+        }
+    }
+
+    /// Entities were destroyed: drop their instances and any pool they were the template of
+    pub fn forget(&mut self, entities: &[Entity]) {
+        self.manager.forget(entities);
+        self.spawned.retain(|e| !entities.contains(e));
+    }
+
+    pub fn clear(&mut self) {
+        self.manager.clear();
+        self.spawned.clear();
+    }
+
+    /// Current numbers of every pool, for the profiler overlay
+    pub fn publish_stats(&self) {
+        let stats = self.manager.stats().into_iter().map(|(name, stats)| profiler::pools::PoolStat {
+            name,
+            size: stats.size,
+            active: stats.active,
+            peak_active: stats.peak_active,
+            spawns: stats.spawns,
+            grown: stats.grown,
+            reused: stats.reused,
+            exhausted: stats.exhausted,
+        });
+        profiler::pools::publish(stats.collect());
+    }
+}
+
+fn find_template(world: &World, template: &Value) -> mlua::Result<Entity> {
+    let exists = |e: &Entity| world.transforms.contains_key(e) || world.active.contains_key(e);
+    let (found, described) = match template {
+        Value::Integer(id) => (Some(*id as Entity).filter(exists), format!("entity {}", id)),
+        Value::Number(id) => (Some(*id as Entity).filter(exists), format!("entity {}", id)),
+        Value::String(name) => {
+            let name = name.to_str()?;
+            // Lowest id when several share the name, so it doesn't depend on HashMap order
+            let found = world.names.iter().filter(|(_, n)| n.as_str() == name).map(|(e, _)| *e).min();
+            (found, format!("entity named '{}'", name))
+        }
+        other => {
+            return Err(mlua::Error::RuntimeError(format!(
+                "Pool.create: template must be an entity name or id, got a {}", other.type_name()
+            )));
+        }
+    };
+    found.ok_or_else(|| mlua::Error::RuntimeError(format!("Pool.create: no template {}", described)))
+}
+
+fn stats_table<'lua>(lua: &'lua Lua, stats: PoolStats) -> mlua::Result<Table<'lua>> {
+    let table = lua.create_table()?;
+    table.set("size", stats.size)?;
+    table.set("active", stats.active)?;
+    table.set("free", stats.size.saturating_sub(stats.active))?;
+    table.set("peak_active", stats.peak_active)?;
+    table.set("spawns", stats.spawns)?;
+    table.set("grown", stats.grown)?;
+    table.set("reused", stats.reused)?;
+    table.set("exhausted", stats.exhausted)?;
+    Ok(table)
+}
+
+/// Register the Pool table for the script code run inside `scope`
+pub fn register_pool_api<'lua, 'scope>(
+    lua: &'lua Lua,
+    scope: &Scope<'lua, 'scope>,
+    world_cell: &'scope RefCell<&mut World>,
+    pools: &'scope Rc<RefCell<ScriptPools>>,
+    scene_commands: &'scope Rc<RefCell<Vec<SceneCommand>>>,
+) -> mlua::Result<()> {
+    let pool_table = lua.create_table()?;
+
+    // Pool.create(name, template, size, {policy = "grow" | "reuse_oldest", max_size = n}?)
+    // Returns false if a pool with that name already exists
+    pool_table.set("create", scope.create_function_mut(
+        move |_, (name, template, size, options): (String, Value, usize, Option<Table>)| {
+            if pools.borrow().manager.contains(&name) {
+                return Ok(false);
+            }
+            let mut policy = PoolPolicy::Grow;
+            let mut max_size = 0;
+            if let Some(options) = options {
+                if let Some(policy_name) = options.get::<_, Option<String>>("policy")? {
+                    policy = PoolPolicy::from_name(&policy_name).ok_or_else(|| mlua::Error::RuntimeError(format!(
+                        "Pool.create('{}'): unknown policy '{}' (use \"grow\" or \"reuse_oldest\")", name, policy_name
+                    )))?;
+                }
+                max_size = options.get::<_, Option<usize>>("max_size")?.unwrap_or(0);
+            }
+
+            let mut world = world_cell.borrow_mut();
+            let template = find_template(&world, &template)?;
+            let created = pools.borrow_mut().manager
+                .create(&mut world, &name, template, size, policy, max_size)
+                .map_err(mlua::Error::RuntimeError)?;
+            if !created.is_empty() {
+                scene_commands.borrow_mut().push(SceneCommand::Duplicated { original: template, copies: created });
+            }
+            Ok(true)
+        },
+    )?)?;
+
+    // Pool.spawn(name, x, y): an instance reset to the template, active at (x, y), or nil
+    // when the pool is full. Its OnSpawn runs after this frame's scripts.
+    pool_table.set("spawn", scope.create_function_mut(move |_, (name, x, y): (String, f32, f32)| {
+        let mut world = world_cell.borrow_mut();
+        let mut pools = pools.borrow_mut();
+        let spawned = pools.manager.spawn(&mut world, &name, [x, y]).map_err(mlua::Error::RuntimeError)?;
+        let Some(spawned) = spawned else {
+            return Ok(None);
+        };
+        if let (false, Some(template)) = (spawned.created.is_empty(), pools.manager.template(&name)) {
+            scene_commands.borrow_mut().push(SceneCommand::Duplicated { original: template, copies: spawned.created });
+        }
+        pools.queue_spawned(spawned.entity);
+        Ok(Some(spawned.entity))
+    })?)?;
+
+    // Pool.release(entity): deactivate it and give it back; false if it isn't a spawned instance
+    pool_table.set("release", scope.create_function_mut(move |_, entity: Entity| {
+        let mut world = world_cell.borrow_mut();
+        Ok(pools.borrow_mut().manager.release(&mut world, entity))
+    })?)?;
+
+    // Pool.stats(name): {size, active, free, peak_active, spawns, grown, reused, exhausted} or nil
+    pool_table.set("stats", scope.create_function(move |lua, name: String| {
+        match pools.borrow().manager.pool_stats(&name) {
+            Some(stats) => Ok(Some(stats_table(lua, stats)?)),
+            None => Ok(None),
+        }
+    })?)?;
+
+    lua.globals().set("Pool", pool_table)?;
+    Ok(())
+}
@@ -0,0 +1,86 @@
+// Console REPL (editor Lua input)
+//
+// Code typed in the editor console runs at the frame's script sync point
+// (ScriptEngine::run_repl), never from the UI callback, with the same world API as
+// Update. It runs in a dedicated editor state, or during play in one entity's script
+// state to inspect and change its globals. An expression prints its values; print()
+// and log() output comes back as lines too.
+
+use ecs::Entity;
+use mlua::{Lua, MultiValue, Value};
+
+/// Owner of the editor REPL state (its Timer/Events callbacks never run)
+pub(crate) const REPL_OWNER: Entity = Entity::MAX;
+
+/// Chunk name in REPL error messages ("repl:1: attempt to ...")
+const CHUNK_NAME: &str = "=repl";
+
+/// One line of REPL output
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplLine {
+    /// print() / log() output
+    Output(String),
+    /// A value the code evaluated to
+    Value(String),
+    Error(String),
+}
+
+/// Run `code` in `lua`: as an expression when it is one (`GetTransform(3)`), otherwise
+/// as statements. Returns the printed values.
+pub(crate) fn eval(lua: &Lua, code: &str) -> mlua::Result<Vec<String>> {
+    let values: MultiValue = match lua.load(format!("return {}", code)).set_name(CHUNK_NAME).into_function() {
+        Ok(expression) => expression.call(())?,
+        Err(_) => lua.load(code).set_name(CHUNK_NAME).call(())?,
+    };
+    Ok(values.iter().map(|value| super::debugger::format_value(value, true)).collect())
+}
+
+/// print() arguments the way Lua prints them: strings as is, tab separated
+pub(crate) fn print_line(args: &MultiValue) -> String {
+    args.iter()
+        .map(|value| match value {
+            Value::String(s) => s.to_string_lossy().to_string(),
+            other => super::debugger::format_value(other, true),
+        })
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReplLine;
+    use crate::test_support::{script_engine, spawn_scripted};
+    use ecs::{Entity, World};
+
+    #[test]
+    fn test_console_repl() {
+        let mut script_engine = script_engine();
+        let mut world = World::new();
+        let scripted = spawn_scripted(&mut script_engine, &mut world, "starter", "function Start() started = true end");
+        let input = input::InputSystem::default();
+        let mut repl = |code: &str, target: Option<Entity>| script_engine.run_repl(code, target, &mut world, &input);
+
+        // The editor state keeps its globals between calls; expressions print their values
+        assert!(repl("x = 40", None).is_empty());
+        assert_eq!(repl("x + 2, 'two'", None), [ReplLine::Value("42".to_string()), ReplLine::Value("\"two\"".to_string())]);
+        assert_eq!(
+            repl("print('entities', 1) return #get_all_entities()", None),
+            [ReplLine::Output("entities\t1".to_string()), ReplLine::Value("1".to_string())]
+        );
+        assert!(matches!(&repl("x +", None)[..], [ReplLine::Error(_)]));
+
+        // An entity target sees its script's globals
+        assert_eq!(repl("started, entity", Some(scripted)), [ReplLine::Value("true".to_string()), ReplLine::Value(scripted.to_string())]);
+        assert_eq!(repl("x", Some(scripted)), [ReplLine::Value("nil".to_string())]);
+        assert!(matches!(&repl("x", Some(scripted + 1))[..], [ReplLine::Error(_)]));
+    }
+
+    #[test]
+    fn test_print_line_formats_like_lua() {
+        let lua = mlua::Lua::new();
+        let args = lua.load("return 'a', 1, nil, true").eval::<mlua::MultiValue>().unwrap();
+        assert_eq!(super::print_line(&args), "a\t1\tnil\ttrue");
+        assert_eq!(super::eval(&lua, "1 + 1").unwrap(), ["2"]);
+        assert!(super::eval(&lua, "local y = 3").unwrap().is_empty());
+    }
+}
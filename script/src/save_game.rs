@@ -0,0 +1,94 @@
+// Save games (SaveGame.save / load / exists / delete)
+//
+// A save game is the active scene's path, the dynamic state of its SaveState entities
+// (ecs::DynamicState) and the Globals store, one JSON file per slot in saves/ next to
+// the Save store's file (the project's persistent data folder). Saving and loading are
+// queued like Scene.load and done by the engine at the end of the frame: loading
+// reloads the saved scene, puts the snapshot on it before its scripts wake up, then
+// restores Globals.
+
+use crate::SceneCommand;
+use engine_core::save_data::SaveData;
+use mlua::Lua;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as Json;
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// Folder of the save game files, next to the Save store's file
+pub const SAVES_FOLDER: &str = "saves";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SaveGameFile {
+    /// Project-relative path of the scene that was active
+    pub scene: String,
+    #[serde(default)]
+    pub state: ecs::DynamicState,
+    #[serde(default)]
+    pub globals: BTreeMap<String, Json>,
+}
+
+impl SaveGameFile {
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+    }
+
+    /// Write atomically (temp file + rename), like the Save store
+    pub fn write(&self, path: &Path) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        std::fs::write(&tmp_path, serde_json::to_string_pretty(self)?)?;
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+}
+
+/// File of a save slot ("1", "autosave"), or None for an in-memory Save store. The
+/// slot is sanitized into a single file name.
+pub fn slot_path(save_data: &SaveData, slot: &str) -> Option<PathBuf> {
+    let dir = save_data.path()?.parent()?.join(SAVES_FOLDER);
+    let name: String = slot.trim()
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let name = if name.is_empty() { "default".to_string() } else { name };
+    Some(dir.join(format!("{}.json", name)))
+}
+
+pub(crate) fn register_save_game_api(
+    lua: &Lua,
+    scene_commands: Rc<RefCell<Vec<SceneCommand>>>,
+    save_data: Rc<RefCell<SaveData>>,
+) -> mlua::Result<()> {
+    let save_game_table = lua.create_table()?;
+
+    let commands = Rc::clone(&scene_commands);
+    save_game_table.set("save", lua.create_function(move |_, slot: String| {
+        commands.borrow_mut().push(SceneCommand::SaveGame { slot });
+        Ok(())
+    })?)?;
+
+    save_game_table.set("load", lua.create_function(move |_, slot: String| {
+        scene_commands.borrow_mut().push(SceneCommand::LoadGame { slot });
+        Ok(())
+    })?)?;
+
+    let data = Rc::clone(&save_data);
+    save_game_table.set("exists", lua.create_function(move |_, slot: String| {
+        Ok(slot_path(&data.borrow(), &slot).is_some_and(|path| path.exists()))
+    })?)?;
+
+    save_game_table.set("delete", lua.create_function(move |_, slot: String| {
+        let Some(path) = slot_path(&save_data.borrow(), &slot) else {
+            return Ok(false);
+        };
+        Ok(std::fs::remove_file(path).is_ok())
+    })?)?;
+
+    lua.globals().set("SaveGame", save_game_table)?;
+    Ok(())
+}
@@ -0,0 +1,223 @@
+// Scene preloading for scripts (Scene.preload / Scene.activate / Scene.set_loading_screen)
+//
+// Scene.preload(path) queues a SceneCommand::Preload and returns a SceneHandle. The engine
+// loads the scene file and the assets it uses on loader threads and writes the progress
+// back here at the end of every frame, so handle:progress() reports last frame's state.
+// Scene.activate(handle) swaps the scene in at the end of the frame it is ready in.
+
+use crate::SceneCommand;
+use mlua::{Lua, Table, UserData, UserDataMethods, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Seconds an activation waits for its preload before the loading screen comes up
+pub const DEFAULT_LOADING_SCREEN_DELAY: f32 = 0.5;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum PreloadState {
+    Loading,
+    Ready,
+    Failed(String),
+    Cancelled,
+    /// Swapped in by Scene.activate
+    Activated,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PreloadEntry {
+    pub path: String,
+    /// 0.0..=1.0
+    pub progress: f32,
+    pub state: PreloadState,
+}
+
+/// Scene.set_loading_screen: what activation shows while it waits
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoadingScreenConfig {
+    pub enabled: bool,
+    /// UI prefab to show; None for the built-in one
+    pub prefab: Option<String>,
+    /// Seconds to wait before showing it, so fast loads don't flash it
+    pub delay: f32,
+}
+
+impl Default for LoadingScreenConfig {
+    fn default() -> Self {
+        Self { enabled: true, prefab: None, delay: DEFAULT_LOADING_SCREEN_DELAY }
+    }
+}
+
+/// Preloads started by scripts, shared with the engine that runs them
+#[derive(Debug, Default)]
+pub struct ScenePreloads {
+    next_id: u64,
+    entries: HashMap<u64, PreloadEntry>,
+    pub loading_screen: LoadingScreenConfig,
+}
+
+impl ScenePreloads {
+    /// Id of a new preload of `path`, or of the one already loading (or ready) for it.
+    /// The bool is true when it is new and the engine has to start it.
+    pub fn start(&mut self, path: &str) -> (u64, bool) {
+        let pending = self.entries.iter()
+            .filter(|(_, entry)| entry.path == path && matches!(entry.state, PreloadState::Loading | PreloadState::Ready))
+            .map(|(id, _)| *id)
+            .min();
+        if let Some(id) = pending {
+            return (id, false);
+        }
+        self.next_id += 1;
+        let entry = PreloadEntry { path: path.to_string(), progress: 0.0, state: PreloadState::Loading };
+        self.entries.insert(self.next_id, entry);
+        (self.next_id, true)
+    }
+
+    pub fn get(&self, id: u64) -> Option<&PreloadEntry> {
+        self.entries.get(&id)
+    }
+
+    pub fn set_progress(&mut self, id: u64, progress: f32) {
+        if let Some(entry) = self.entries.get_mut(&id) {
+            entry.progress = progress.clamp(0.0, 1.0);
+        }
+    }
+
+    pub fn set_state(&mut self, id: u64, state: PreloadState) {
+        if let Some(entry) = self.entries.get_mut(&id) {
+            if state == PreloadState::Ready {
+                entry.progress = 1.0;
+            }
+            entry.state = state;
+        }
+    }
+
+    /// Forget every preload and the loading screen setting (e.g. when leaving play mode)
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.loading_screen = LoadingScreenConfig::default();
+    }
+}
+
+/// Handle returned to Lua by Scene.preload
+#[derive(Clone)]
+pub struct SceneHandle {
+    pub id: u64,
+    preloads: Rc<RefCell<ScenePreloads>>,
+    scene_commands: Rc<RefCell<Vec<SceneCommand>>>,
+}
+
+impl SceneHandle {
+    fn state(&self) -> Option<PreloadState> {
+        self.preloads.borrow().get(self.id).map(|entry| entry.state.clone())
+    }
+}
+
+impl UserData for SceneHandle {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("progress", |_, this, ()| {
+            Ok(this.preloads.borrow().get(this.id).map_or(0.0, |entry| entry.progress))
+        });
+        methods.add_method("is_ready", |_, this, ()| {
+            Ok(this.state() == Some(PreloadState::Ready))
+        });
+        // Error message of a failed preload, nil otherwise
+        methods.add_method("error", |_, this, ()| {
+            Ok(match this.state() {
+                Some(PreloadState::Failed(error)) => Some(error),
+                _ => None,
+            })
+        });
+        methods.add_method("path", |_, this, ()| {
+            Ok(this.preloads.borrow().get(this.id).map(|entry| entry.path.clone()))
+        });
+        // Stop loading and release what was loaded so far (no-op once activated)
+        methods.add_method("cancel", |_, this, ()| {
+            if matches!(this.state(), Some(PreloadState::Loading | PreloadState::Ready)) {
+                this.preloads.borrow_mut().set_state(this.id, PreloadState::Cancelled);
+                this.scene_commands.borrow_mut().push(SceneCommand::CancelPreload { id: this.id });
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Add Scene.preload, Scene.activate and Scene.set_loading_screen to the Scene table
+pub(crate) fn register_preload_api(
+    lua: &Lua,
+    scene_table: &Table,
+    scene_commands: Rc<RefCell<Vec<SceneCommand>>>,
+    preloads: Rc<RefCell<ScenePreloads>>,
+) -> mlua::Result<()> {
+    // Scene.preload(path): start loading in the background; preloading the scene that is
+    // already active does no work
+    let commands = Rc::clone(&scene_commands);
+    let shared = Rc::clone(&preloads);
+    scene_table.set("preload", lua.create_function(move |_, path: String| {
+        if path.trim().is_empty() {
+            return Err(mlua::Error::RuntimeError("Scene.preload: path must not be empty".to_string()));
+        }
+        let (id, created) = shared.borrow_mut().start(&path);
+        if created {
+            commands.borrow_mut().push(SceneCommand::Preload { id, path });
+        }
+        Ok(SceneHandle { id, preloads: Rc::clone(&shared), scene_commands: Rc::clone(&commands) })
+    })?)?;
+
+    // Scene.activate(handle): replace the scene with the preloaded one at the end of the
+    // frame it is ready in (the loading screen shows if that takes a while)
+    let commands = Rc::clone(&scene_commands);
+    let shared = Rc::clone(&preloads);
+    scene_table.set("activate", lua.create_function(move |_, handle: mlua::AnyUserData| {
+        let handle = handle.borrow::<SceneHandle>()?;
+        let Some(entry) = shared.borrow().get(handle.id).cloned() else {
+            return Err(mlua::Error::RuntimeError("Scene.activate: unknown scene handle".to_string()));
+        };
+        match entry.state {
+            PreloadState::Loading | PreloadState::Ready => {
+                commands.borrow_mut().push(SceneCommand::Activate { id: handle.id });
+                Ok(())
+            }
+            PreloadState::Failed(error) => Err(mlua::Error::RuntimeError(format!(
+                "Scene.activate: preloading '{}' failed: {}", entry.path, error
+            ))),
+            PreloadState::Cancelled => Err(mlua::Error::RuntimeError(format!(
+                "Scene.activate: the preload of '{}' was cancelled", entry.path
+            ))),
+            PreloadState::Activated => Err(mlua::Error::RuntimeError(format!(
+                "Scene.activate: '{}' was already activated", entry.path
+            ))),
+        }
+    })?)?;
+
+    // Scene.set_loading_screen(prefab, { delay = 0.5 }): prefab path, nil for the
+    // built-in screen or false for none
+    scene_table.set("set_loading_screen", lua.create_function(move |_, (prefab, options): (Value, Option<Table>)| {
+        let mut preloads = preloads.borrow_mut();
+        let config = &mut preloads.loading_screen;
+        match prefab {
+            Value::Nil => {
+                config.enabled = true;
+                config.prefab = None;
+            }
+            Value::Boolean(false) => config.enabled = false,
+            Value::String(path) => {
+                config.enabled = true;
+                config.prefab = Some(path.to_str()?.to_string());
+            }
+            other => {
+                return Err(mlua::Error::RuntimeError(format!(
+                    "Scene.set_loading_screen: expected a prefab path, nil or false, got {}", other.type_name()
+                )));
+            }
+        }
+        if let Some(options) = options {
+            if let Some(delay) = options.get::<_, Option<f32>>("delay")? {
+                config.delay = delay.max(0.0);
+            }
+        }
+        Ok(())
+    })?)?;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+// Fixtures shared by the script tests: assets served from memory and entities with
+// their script loaded
+
+use crate::ScriptEngine;
+use async_trait::async_trait;
+use ecs::{Entity, World};
+use engine_core::assets::AssetLoader;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Asset loader serving files from memory (path -> contents)
+pub struct MemoryLoader(pub HashMap<String, String>);
+
+#[async_trait]
+impl AssetLoader for MemoryLoader {
+    async fn load_text(&self, path: &str) -> anyhow::Result<String> {
+        self.0.get(path).cloned().ok_or_else(|| anyhow::anyhow!("not found: {}", path))
+    }
+
+    async fn load_binary(&self, path: &str) -> anyhow::Result<Vec<u8>> {
+        Ok(self.load_text(path).await?.into_bytes())
+    }
+
+    fn get_base_path(&self) -> String {
+        String::new()
+    }
+}
+
+pub fn memory_loader(files: &[(&str, &str)]) -> Arc<dyn AssetLoader> {
+    Arc::new(MemoryLoader(files.iter().map(|(path, contents)| (path.to_string(), contents.to_string())).collect()))
+}
+
+pub fn script_engine() -> ScriptEngine {
+    ScriptEngine::new(memory_loader(&[])).unwrap()
+}
+
+/// Spawn an entity with a Transform and `source` as its script, through Awake and Start
+pub fn spawn_scripted(script_engine: &mut ScriptEngine, world: &mut World, name: &str, source: &str) -> Entity {
+    let entity = world.spawn();
+    world.transforms.insert(entity, ecs::Transform::default());
+    world.scripts.insert(entity, ecs::Script {
+        script_name: name.to_string(),
+        enabled: true,
+        parameters: HashMap::new(),
+        lifecycle_state: Default::default(),
+    });
+    script_engine.load_script_for_entity(entity, source, world).unwrap();
+    script_engine.call_start_for_entity(entity, world).unwrap();
+    entity
+}
+
+/// One run_script (timers, events, then Update) for `entity`
+pub fn run(script_engine: &mut ScriptEngine, world: &mut World, entity: Entity, dt: f32) {
+    let input = input::InputSystem::default();
+    script_engine.run_script(std::path::Path::new(""), entity, world, &input, dt, &mut |_| {}).unwrap();
+}
+
+/// A global of `entity`'s Lua state, as the debugger shows it
+pub fn global(script_engine: &ScriptEngine, entity: Entity, name: &str) -> Option<String> {
+    script_engine.debug_globals(entity).into_iter().find(|(n, _)| n == name).map(|(_, value)| value)
+}
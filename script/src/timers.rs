@@ -0,0 +1,629 @@
+// Timer and Tween manager for Lua scripts
+//
+// Timers and tweens are owned by the entity whose script created them. Callbacks are
+// stored in the owner's Lua registry and are only invoked from inside that entity's
+// run_script() scope, so they have access to the full per-frame script API. While the
+// owner's script is disabled they are paused: they keep their remaining time.
+
+use ecs::{Entity, World};
+use mlua::{Function, Lua, RegistryKey, UserData, UserDataMethods, Value};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::rc::Rc;
+use ui::animation::{AnimationCurve, CurveKey, EasingFunction};
+
+/// Entity property a tween can animate
+#[derive(Clone, Debug, PartialEq)]
+pub enum TweenProperty {
+    Position,
+    Rotation,
+    Scale,
+    /// Sprite tint (RGBA)
+    Color,
+    /// A float parameter of the entity's SpriteMaterial ("material.flash_amount")
+    MaterialFloat(String),
+    /// Camera orthographic_size (Camera.zoom_to). With a focus point the camera moves so
+    /// that world point stays where it is on screen.
+    CameraZoom { focus: Option<[f32; 2]> },
+}
+
+impl TweenProperty {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "position" => Some(TweenProperty::Position),
+            "rotation" => Some(TweenProperty::Rotation),
+            "scale" => Some(TweenProperty::Scale),
+            "color" | "colour" => Some(TweenProperty::Color),
+            _ => match name.split_once('.') {
+                Some((prefix, param)) if prefix.eq_ignore_ascii_case("material") && !param.is_empty() => {
+                    Some(TweenProperty::MaterialFloat(param.to_string()))
+                }
+                _ => None,
+            },
+        }
+    }
+
+    /// Lua table keys for each component of this property
+    fn component_keys(&self) -> &'static [&'static str] {
+        match self {
+            TweenProperty::Color => &["r", "g", "b", "a"],
+            TweenProperty::MaterialFloat(_) => &["value"],
+            TweenProperty::CameraZoom { .. } => &["size"],
+            _ => &["x", "y", "z", "w"],
+        }
+    }
+
+    fn read(&self, world: &World, entity: Entity) -> Option<[f32; 4]> {
+        match self {
+            TweenProperty::Position => world.transforms.get(&entity).map(|t| {
+                [t.position[0], t.position[1], t.position[2], 0.0]
+            }),
+            TweenProperty::Rotation => world.transforms.get(&entity).map(|t| {
+                [t.rotation[0], t.rotation[1], t.rotation[2], 0.0]
+            }),
+            TweenProperty::Scale => world.transforms.get(&entity).map(|t| {
+                [t.scale[0], t.scale[1], t.scale[2], 0.0]
+            }),
+            TweenProperty::Color => world.sprites.get(&entity).map(|s| s.color.to_array()),
+            TweenProperty::MaterialFloat(name) => world.sprite_materials.get(&entity).map(|m| [m.float(name), 0.0, 0.0, 0.0]),
+            TweenProperty::CameraZoom { .. } => world.cameras.get(&entity).map(|c| [c.orthographic_size, 0.0, 0.0, 0.0]),
+        }
+    }
+
+    /// Whether a new tween on `other` takes over from one on this property
+    fn overlaps(&self, other: &TweenProperty) -> bool {
+        match (self, other) {
+            (TweenProperty::CameraZoom { .. }, TweenProperty::CameraZoom { .. }) => true,
+            _ => self == other,
+        }
+    }
+
+    fn write(&self, world: &mut World, entity: Entity, value: [f32; 4]) {
+        match self {
+            TweenProperty::Position => {
+                if let Some(t) = world.transforms.get_mut(&entity) {
+                    t.position = [value[0], value[1], value[2]];
+                }
+            }
+            TweenProperty::Rotation => {
+                if let Some(t) = world.transforms.get_mut(&entity) {
+                    t.rotation = [value[0], value[1], value[2]];
+                }
+            }
+            TweenProperty::Scale => {
+                if let Some(t) = world.transforms.get_mut(&entity) {
+                    t.scale = [value[0], value[1], value[2]];
+                }
+            }
+            TweenProperty::Color => {
+                if let Some(s) = world.sprites.get_mut(&entity) {
+                    s.color = value.into();
+                }
+            }
+            TweenProperty::MaterialFloat(name) => {
+                if let Some(m) = world.sprite_materials.get_mut(&entity) {
+                    m.set_float(name, value[0]);
+                }
+            }
+            TweenProperty::CameraZoom { focus } => {
+                set_orthographic_size(world, entity, value[0], *focus);
+            }
+        }
+    }
+}
+
+/// Set a camera's orthographic_size within its zoom limits. With a focus point (world
+/// x, y) the camera moves so that point stays put on screen. Returns false without a camera.
+pub(crate) fn set_orthographic_size(world: &mut World, entity: Entity, size: f32, focus: Option<[f32; 2]>) -> bool {
+    let Some(camera) = world.cameras.get_mut(&entity) else {
+        return false;
+    };
+    let previous = camera.orthographic_size;
+    camera.orthographic_size = camera.clamp_orthographic_size(size);
+    let ratio = camera.orthographic_size / previous.max(0.0001);
+
+    if let (Some([fx, fy]), Some(position)) = (focus, world.world_transform(entity).map(|t| t.position)) {
+        // Screen offsets scale with the size, so the focus keeps its offset from the center
+        // in screen terms: new center = focus - (focus - center) * ratio. That center is in
+        // world space; set_world_position brings it into a parented camera's local space.
+        let center = [fx - (fx - position[0]) * ratio, fy - (fy - position[1]) * ratio, position[2]];
+        world.set_world_position(entity, center);
+    }
+    true
+}
+
+struct Timer {
+    id: u64,
+    owner: Entity,
+    remaining: f32,
+    /// Some(interval) for Timer.every, None for Timer.after
+    interval: Option<f32>,
+    callback: RegistryKey,
+    finished: bool,
+}
+
+struct Tween {
+    id: u64,
+    owner: Entity,
+    target: Entity,
+    property: TweenProperty,
+    /// Captured from the world on the first update
+    from: Option<[f32; 4]>,
+    /// Target value per component (None = leave component untouched)
+    to: [Option<f32>; 4],
+    duration: f32,
+    elapsed: f32,
+    easing: EasingFunction,
+    on_complete: Option<RegistryKey>,
+    finished: bool,
+}
+
+/// Per-engine storage for all script timers and tweens
+#[derive(Default)]
+pub struct ScriptTimers {
+    next_id: u64,
+    timers: Vec<Timer>,
+    tweens: Vec<Tween>,
+    /// Handle ids whose callback is due, waiting for the owner's next run_script()
+    fired: Vec<(Entity, u64)>,
+    /// Owners whose script is disabled (their timers and tweens don't advance)
+    paused: HashSet<Entity>,
+}
+
+impl ScriptTimers {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn allocate_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    pub fn add_timer(&mut self, owner: Entity, delay: f32, interval: Option<f32>, callback: RegistryKey) -> u64 {
+        let id = self.allocate_id();
+        self.timers.push(Timer {
+            id,
+            owner,
+            remaining: delay.max(0.0),
+            interval: interval.map(|i| i.max(0.0001)),
+            callback,
+            finished: false,
+        });
+        id
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn add_tween(
+        &mut self,
+        owner: Entity,
+        target: Entity,
+        property: TweenProperty,
+        to: [Option<f32>; 4],
+        duration: f32,
+        easing: EasingFunction,
+        on_complete: Option<RegistryKey>,
+    ) -> u64 {
+        // A new tween on the same property replaces the old one (no fighting tweens)
+        for tween in self.tweens.iter_mut() {
+            if tween.target == target && tween.property.overlaps(&property) && !tween.finished {
+                tween.finished = true;
+                tween.on_complete = None;
+            }
+        }
+
+        let id = self.allocate_id();
+        self.tweens.push(Tween {
+            id,
+            owner,
+            target,
+            property,
+            from: None,
+            to,
+            duration: duration.max(0.0),
+            elapsed: 0.0,
+            easing,
+            on_complete,
+            finished: false,
+        });
+        id
+    }
+
+    /// Cancel a timer or tween. Its callback will not run.
+    pub fn cancel(&mut self, id: u64) {
+        self.timers.retain(|t| t.id != id);
+        self.tweens.retain(|t| t.id != id);
+        self.fired.retain(|(_, fired_id)| *fired_id != id);
+    }
+
+    pub fn is_active(&self, id: u64) -> bool {
+        self.timers.iter().any(|t| t.id == id && !t.finished)
+            || self.tweens.iter().any(|t| t.id == id && !t.finished)
+    }
+
+    /// Pause (or resume) the timers and tweens `owner`'s script created, for a disabled script
+    pub fn set_paused(&mut self, owner: Entity, paused: bool) {
+        if paused {
+            self.paused.insert(owner);
+        } else {
+            self.paused.remove(&owner);
+        }
+    }
+
+    /// Drop everything owned by or targeting an entity (called from remove_entity_state)
+    pub fn remove_entity(&mut self, entity: Entity) {
+        self.timers.retain(|t| t.owner != entity);
+        self.tweens.retain(|t| t.owner != entity && t.target != entity);
+        self.fired.retain(|(owner, _)| *owner != entity);
+        self.paused.remove(&entity);
+    }
+
+    pub fn clear(&mut self) {
+        self.timers.clear();
+        self.tweens.clear();
+        self.fired.clear();
+        self.paused.clear();
+    }
+
+    /// Number of running timers and tweens
+    pub fn active_count(&self) -> usize {
+        self.timers.iter().filter(|t| !t.finished).count()
+            + self.tweens.iter().filter(|t| !t.finished).count()
+    }
+
+    /// Advance all timers and tweens of enabled owners, applying tween values to the world.
+    /// Callbacks that become due are queued and run later in the owner's Lua state.
+    pub fn update(&mut self, dt: f32, world: &mut World) {
+        let paused = &self.paused;
+        for timer in self.timers.iter_mut().filter(|t| !t.finished && !paused.contains(&t.owner)) {
+            timer.remaining -= dt;
+            if timer.remaining <= 0.0 {
+                self.fired.push((timer.owner, timer.id));
+                match timer.interval {
+                    Some(interval) => timer.remaining += interval,
+                    None => timer.finished = true,
+                }
+            }
+        }
+
+        for tween in self.tweens.iter_mut().filter(|t| !t.finished && !paused.contains(&t.owner)) {
+            // Tweens targeting a despawned entity cancel themselves
+            let Some(current) = tween.property.read(world, tween.target) else {
+                tween.finished = true;
+                tween.on_complete = None;
+                continue;
+            };
+
+            let from = *tween.from.get_or_insert(current);
+            tween.elapsed += dt;
+            let t = if tween.duration > 0.0 { (tween.elapsed / tween.duration).min(1.0) } else { 1.0 };
+            let eased = tween.easing.evaluate(t);
+
+            let mut value = current;
+            for i in 0..4 {
+                if let Some(to) = tween.to[i] {
+                    value[i] = from[i] + (to - from[i]) * eased;
+                }
+            }
+            tween.property.write(world, tween.target, value);
+
+            if t >= 1.0 {
+                tween.finished = true;
+                if tween.on_complete.is_some() {
+                    self.fired.push((tween.owner, tween.id));
+                }
+            }
+        }
+
+        // Finished entries without a pending callback can go right away
+        let fired = &self.fired;
+        self.timers.retain(|t| !t.finished || fired.iter().any(|(_, id)| *id == t.id));
+        self.tweens.retain(|t| !t.finished || fired.iter().any(|(_, id)| *id == t.id));
+    }
+
+    /// Take the ids of callbacks due for an entity
+    fn take_fired(&mut self, entity: Entity) -> Vec<u64> {
+        let mut ids = Vec::new();
+        self.fired.retain(|(owner, id)| {
+            if *owner == entity {
+                ids.push(*id);
+                false
+            } else {
+                true
+            }
+        });
+        ids
+    }
+
+    fn callback_function<'lua>(&self, lua: &'lua Lua, id: u64) -> Option<Function<'lua>> {
+        let key = self.timers.iter().find(|t| t.id == id).map(|t| &t.callback)
+            .or_else(|| self.tweens.iter().find(|t| t.id == id).and_then(|t| t.on_complete.as_ref()))?;
+        lua.registry_value::<Function>(key).ok()
+    }
+
+    /// Remove finished entries after their callback ran, releasing registry values
+    fn release_finished(&mut self, lua: &Lua, id: u64) {
+        if let Some(index) = self.timers.iter().position(|t| t.id == id && t.finished) {
+            let timer = self.timers.remove(index);
+            let _ = lua.remove_registry_value(timer.callback);
+        }
+        if let Some(index) = self.tweens.iter().position(|t| t.id == id && t.finished) {
+            let tween = self.tweens.remove(index);
+            if let Some(key) = tween.on_complete {
+                let _ = lua.remove_registry_value(key);
+            }
+        }
+    }
+}
+
+/// Run all due timer/tween callbacks for an entity inside its own Lua state.
+/// Must be called while the entity's per-frame API is injected (inside lua.scope).
+pub fn run_due_callbacks(timers: &Rc<RefCell<ScriptTimers>>, lua: &Lua, entity: Entity) -> mlua::Result<()> {
+    let ids = timers.borrow_mut().take_fired(entity);
+    for id in ids {
+        // Release the borrow before calling into Lua (callbacks may create timers)
+        let callback = timers.borrow().callback_function(lua, id);
+        if let Some(callback) = callback {
+            if let Err(e) = callback.call::<_, ()>(()) {
+                log::error!("Timer callback error for entity {}: {}", entity, e);
+            }
+        }
+        timers.borrow_mut().release_finished(lua, id);
+    }
+    Ok(())
+}
+
+/// Handle returned to Lua by Timer.after / Timer.every / Tween.to
+#[derive(Clone)]
+pub struct TimerHandle {
+    pub id: u64,
+    timers: Rc<RefCell<ScriptTimers>>,
+}
+
+impl UserData for TimerHandle {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("cancel", |_, this, ()| {
+            this.timers.borrow_mut().cancel(this.id);
+            Ok(())
+        });
+        methods.add_method("is_active", |_, this, ()| {
+            Ok(this.timers.borrow().is_active(this.id))
+        });
+    }
+}
+
+/// An AnimationCurve in Lua (Curve.new / Curve.from_easing, curve script parameters);
+/// accepted wherever an easing name is
+#[derive(Clone)]
+pub struct CurveHandle(pub AnimationCurve);
+
+impl UserData for CurveHandle {
+    fn add_methods<'lua, M: UserDataMethods<'lua, Self>>(methods: &mut M) {
+        methods.add_method("evaluate", |_, this, t: f32| Ok(this.0.evaluate(t)));
+        methods.add_method("key_count", |_, this, ()| Ok(this.0.keys().len()));
+    }
+}
+
+/// Curve from a Lua list of keys: {{time=0, value=0, in_tangent=0, out_tangent=0}, ...}
+fn curve_from_table(keys: mlua::Table) -> mlua::Result<AnimationCurve> {
+    let keys = keys.sequence_values::<mlua::Table>()
+        .map(|key| {
+            let key = key?;
+            Ok(CurveKey::with_tangents(
+                key.get("time")?,
+                key.get("value")?,
+                key.get::<_, Option<f32>>("in_tangent")?.unwrap_or(0.0),
+                key.get::<_, Option<f32>>("out_tangent")?.unwrap_or(0.0),
+            ))
+        })
+        .collect::<mlua::Result<Vec<_>>>()?;
+    Ok(AnimationCurve::new(keys))
+}
+
+/// Easing argument of a Lua call: nil (linear), a name ("ease_out_back"), a curve or a
+/// list of curve keys. Unknown names fall back to linear with a warning.
+pub(crate) fn easing_from_lua(function: &str, easing: Value) -> mlua::Result<EasingFunction> {
+    match easing {
+        Value::Nil => Ok(EasingFunction::Linear),
+        Value::String(name) => {
+            let name = name.to_str()?;
+            Ok(EasingFunction::from_name(name).unwrap_or_else(|| {
+                log::warn!("{}: unknown easing '{}', using linear", function, name);
+                EasingFunction::Linear
+            }))
+        }
+        Value::UserData(curve) => Ok(EasingFunction::Curve(curve.borrow::<CurveHandle>()?.0.clone())),
+        Value::Table(keys) => Ok(EasingFunction::Curve(curve_from_table(keys)?)),
+        other => Err(mlua::Error::RuntimeError(format!(
+            "{}: easing must be a name, a curve or a list of keys, got {}",
+            function,
+            other.type_name()
+        ))),
+    }
+}
+
+/// Register the Timer and Tween tables in an entity's Lua state
+pub fn register_timer_api(lua: &Lua, owner: Entity, timers: Rc<RefCell<ScriptTimers>>) -> mlua::Result<()> {
+    let globals = lua.globals();
+
+    // Timer.after(seconds, fn) / Timer.every(interval, fn)
+    let timer_table = lua.create_table()?;
+
+    let timers_ref = Rc::clone(&timers);
+    timer_table.set("after", lua.create_function(move |lua, (seconds, callback): (f32, Function)| {
+        let key = lua.create_registry_value(callback)?;
+        let id = timers_ref.borrow_mut().add_timer(owner, seconds, None, key);
+        Ok(TimerHandle { id, timers: Rc::clone(&timers_ref) })
+    })?)?;
+
+    let timers_ref = Rc::clone(&timers);
+    timer_table.set("every", lua.create_function(move |lua, (interval, callback): (f32, Function)| {
+        let key = lua.create_registry_value(callback)?;
+        let id = timers_ref.borrow_mut().add_timer(owner, interval, Some(interval), key);
+        Ok(TimerHandle { id, timers: Rc::clone(&timers_ref) })
+    })?)?;
+
+    let timers_ref = Rc::clone(&timers);
+    timer_table.set("cancel", lua.create_function(move |_, handle: mlua::AnyUserData| {
+        let handle = handle.borrow::<TimerHandle>()?;
+        timers_ref.borrow_mut().cancel(handle.id);
+        Ok(())
+    })?)?;
+
+    globals.set("Timer", timer_table)?;
+
+    // Tween.to(entity, property, {x=, y=}, duration, easing, on_complete)
+    // (property "material.<name>" tweens a SpriteMaterial float: {value=} or a number)
+    let tween_table = lua.create_table()?;
+
+    let timers_ref = Rc::clone(&timers);
+    let tween_to = lua.create_function(move |lua, args: (Entity, String, Value, f32, Value, Option<Function>)| {
+        let (target, property_name, values, duration, easing, on_complete) = args;
+        let property = TweenProperty::from_name(&property_name).ok_or_else(|| {
+            mlua::Error::RuntimeError(format!("Tween.to: unknown property '{}'", property_name))
+        })?;
+        create_tween(lua, &timers_ref, owner, target, property, values, duration, easing, on_complete)
+    })?;
+    tween_table.set("to", tween_to)?;
+
+    let timers_ref = Rc::clone(&timers);
+    tween_table.set("to_position", lua.create_function(move |lua, args: (Entity, Value, f32, Value, Option<Function>)| {
+        let (target, values, duration, easing, on_complete) = args;
+        create_tween(lua, &timers_ref, owner, target, TweenProperty::Position, values, duration, easing, on_complete)
+    })?)?;
+
+    let timers_ref = Rc::clone(&timers);
+    tween_table.set("to_scale", lua.create_function(move |lua, args: (Entity, Value, f32, Value, Option<Function>)| {
+        let (target, values, duration, easing, on_complete) = args;
+        create_tween(lua, &timers_ref, owner, target, TweenProperty::Scale, values, duration, easing, on_complete)
+    })?)?;
+
+    let timers_ref = Rc::clone(&timers);
+    tween_table.set("to_color", lua.create_function(move |lua, args: (Entity, Value, f32, Value, Option<Function>)| {
+        let (target, values, duration, easing, on_complete) = args;
+        create_tween(lua, &timers_ref, owner, target, TweenProperty::Color, values, duration, easing, on_complete)
+    })?)?;
+
+    globals.set("Tween", tween_table)?;
+
+    // Curve.new({{time=, value=, in_tangent=, out_tangent=}, ...}) / Curve.from_easing(name)
+    let curve_table = lua.create_table()?;
+    curve_table.set("new", lua.create_function(|_, keys: Option<mlua::Table>| {
+        Ok(CurveHandle(keys.map(curve_from_table).transpose()?.unwrap_or_default()))
+    })?)?;
+    curve_table.set("from_easing", lua.create_function(|_, name: String| {
+        let easing = EasingFunction::from_name(&name)
+            .ok_or_else(|| mlua::Error::RuntimeError(format!("Curve.from_easing: unknown easing '{}'", name)))?;
+        Ok(CurveHandle(easing.to_curve()))
+    })?)?;
+    globals.set("Curve", curve_table)?;
+    Ok(())
+}
+
+/// Tween of a camera's orthographic_size owned by `owner` (Camera.zoom_to)
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn tween_camera_zoom(
+    timers: &Rc<RefCell<ScriptTimers>>,
+    owner: Entity,
+    camera: Entity,
+    size: f32,
+    duration: f32,
+    easing: EasingFunction,
+    focus: Option<[f32; 2]>,
+    on_complete: Option<RegistryKey>,
+) -> TimerHandle {
+    let property = TweenProperty::CameraZoom { focus };
+    let id = timers.borrow_mut().add_tween(owner, camera, property, [Some(size), None, None, None], duration, easing, on_complete);
+    TimerHandle { id, timers: Rc::clone(timers) }
+}
+
+/// Linear tween of a SpriteMaterial float owned by `owner` (Material.flash)
+pub(crate) fn tween_material_float(
+    timers: &Rc<RefCell<ScriptTimers>>,
+    owner: Entity,
+    target: Entity,
+    name: &str,
+    to: f32,
+    duration: f32,
+) -> TimerHandle {
+    let property = TweenProperty::MaterialFloat(name.to_string());
+    let id = timers.borrow_mut().add_tween(owner, target, property, [Some(to), None, None, None], duration, EasingFunction::Linear, None);
+    TimerHandle { id, timers: Rc::clone(timers) }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn create_tween(
+    lua: &Lua,
+    timers: &Rc<RefCell<ScriptTimers>>,
+    owner: Entity,
+    target: Entity,
+    property: TweenProperty,
+    values: Value,
+    duration: f32,
+    easing: Value,
+    on_complete: Option<Function>,
+) -> mlua::Result<TimerHandle> {
+    // Colors may also be given as a hex string ("#ff8800"); tables only set the listed channels
+    let mut to = [None; 4];
+    match values {
+        Value::String(hex) if property == TweenProperty::Color => {
+            let color = engine_core::Color::from_hex(hex.to_str()?).map_err(mlua::Error::RuntimeError)?;
+            to = color.to_array().map(Some);
+        }
+        // Material floats may also be given as a plain number
+        Value::Number(_) | Value::Integer(_) if matches!(property, TweenProperty::MaterialFloat(_)) => {
+            to[0] = Some(lua.unpack::<f32>(values)?);
+        }
+        Value::Table(values) => {
+            for (i, key) in property.component_keys().iter().enumerate() {
+                to[i] = values.get::<_, Option<f32>>(*key)?;
+            }
+        }
+        other => {
+            return Err(mlua::Error::RuntimeError(format!(
+                "Tween: expected a table of target values, got {}",
+                other.type_name()
+            )));
+        }
+    }
+
+    let easing = easing_from_lua("Tween", easing)?;
+
+    let on_complete = match on_complete {
+        Some(callback) => Some(lua.create_registry_value(callback)?),
+        None => None,
+    };
+
+    let id = timers.borrow_mut().add_tween(owner, target, property, to, duration, easing, on_complete);
+    Ok(TimerHandle { id, timers: Rc::clone(timers) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zoom_keeps_the_focus_under_a_scaled_parent() {
+        let mut world = World::new();
+        // A rig scaled 2x and shifted: the camera's local offsets are half its world offsets
+        let rig = world.spawn();
+        world.transforms.insert(rig, ecs::Transform {
+            position: [10.0, 0.0, 0.0],
+            scale: [2.0, 2.0, 1.0],
+            ..Default::default()
+        });
+        let camera = world.spawn();
+        world.transforms.insert(camera, ecs::Transform::default());
+        world.cameras.insert(camera, ecs::Camera { orthographic_size: 10.0, ..ecs::Camera::orthographic_2d() });
+        world.set_parent(camera, Some(rig));
+
+        // Zooming in 2x on a point 4 units right of the center halves its distance
+        assert!(set_orthographic_size(&mut world, camera, 5.0, Some([14.0, 0.0])));
+        assert_eq!(world.cameras[&camera].orthographic_size, 5.0);
+        let center = world.world_transform(camera).unwrap().position;
+        assert!((center[0] - 12.0).abs() < 1e-4 && center[1].abs() < 1e-4, "{:?}", center);
+        assert_eq!(world.transforms[&camera].position, [1.0, 0.0, 0.0]);
+
+        assert!(!set_orthographic_size(&mut world, rig, 5.0, None), "not a camera");
+    }
+}
@@ -0,0 +1,124 @@
+// Element construction for UI.create_* (script-driven UI without prefab files)
+//
+// Lua builds the element here and queues it as a UICommand; the engine inserts it under
+// the parent, applies the theme style and re-runs layout once per command batch.
+
+use mlua::Table;
+use ui::{RectTransform, UIButton, UIImage, UIPanel, UIPrefabElement, UIText, Vec2};
+
+/// Options accepted by every UI.create_* call (the `rect_params` table):
+/// x, y, width, height, anchor / anchor_min / anchor_max / pivot ({x, y}),
+/// color ({r, g, b, a}), style (style class) and sprite (images)
+#[derive(Clone, Debug, Default)]
+pub struct UIElementParams {
+    pub rect: RectTransform,
+    /// Explicit color: applied after the theme style
+    pub color: Option<ui::Color>,
+    pub style_class: Option<String>,
+    pub sprite: Option<String>,
+}
+
+impl UIElementParams {
+    pub fn from_lua(params: Option<Table>, default_size: Vec2) -> mlua::Result<Self> {
+        let mut rect = RectTransform { size_delta: default_size, ..Default::default() };
+        let Some(params) = params else {
+            return Ok(Self { rect, ..Default::default() });
+        };
+
+        rect.anchored_position.x = params.get::<_, Option<f32>>("x")?.unwrap_or(0.0);
+        rect.anchored_position.y = params.get::<_, Option<f32>>("y")?.unwrap_or(0.0);
+        rect.size_delta.x = params.get::<_, Option<f32>>("width")?.unwrap_or(default_size.x);
+        rect.size_delta.y = params.get::<_, Option<f32>>("height")?.unwrap_or(default_size.y);
+        if let Some(anchor) = read_vec2(&params, "anchor")? {
+            rect.anchor_min = anchor;
+            rect.anchor_max = anchor;
+        }
+        if let Some(anchor_min) = read_vec2(&params, "anchor_min")? {
+            rect.anchor_min = anchor_min;
+        }
+        if let Some(anchor_max) = read_vec2(&params, "anchor_max")? {
+            rect.anchor_max = anchor_max;
+        }
+        if let Some(pivot) = read_vec2(&params, "pivot")? {
+            rect.pivot = pivot;
+        }
+
+        let color = params.get::<_, Option<mlua::Value>>("color")?
+            .map(ui::lua_bindings::color_from_lua)
+            .transpose()?;
+
+        Ok(Self {
+            rect,
+            color,
+            style_class: params.get("style")?,
+            sprite: params.get("sprite")?,
+        })
+    }
+}
+
+fn read_vec2(params: &Table, key: &str) -> mlua::Result<Option<Vec2>> {
+    Ok(params.get::<_, Option<Table>>(key)?.map(|table| {
+        Vec2::new(table.get::<_, f32>("x").unwrap_or(0.5), table.get::<_, f32>("y").unwrap_or(0.5))
+    }))
+}
+
+/// Background container (drawn as a filled rect)
+pub fn panel(name: &str, params: &UIElementParams) -> UIPrefabElement {
+    let mut element = UIPrefabElement::new(name, params.rect.clone());
+    element.image = Some(UIImage { sprite: params.sprite.clone(), ..Default::default() });
+    element.panel = Some(UIPanel::default());
+    element
+}
+
+/// Label; not a raycast target so it never blocks input to what's behind it
+pub fn text(name: &str, text: String, params: &UIElementParams) -> UIPrefabElement {
+    let mut element = UIPrefabElement::new(name, params.rect.clone());
+    element.text = Some(UIText { text, ..Default::default() });
+    element.ui_element.raycast_target = false;
+    element.ui_element.blocks_raycasts = false;
+    element
+}
+
+pub fn image(name: &str, params: &UIElementParams) -> UIPrefabElement {
+    let mut element = UIPrefabElement::new(name, params.rect.clone());
+    element.image = Some(UIImage { sprite: params.sprite.clone(), ..Default::default() });
+    element
+}
+
+/// Background, button behavior and label on one element
+pub fn button(name: &str, label: String, params: &UIElementParams) -> UIPrefabElement {
+    let mut element = UIPrefabElement::new(name, params.rect.clone());
+    element.image = Some(UIImage { sprite: params.sprite.clone(), ..Default::default() });
+    element.button = Some(UIButton::default());
+    element.text = Some(UIText { text: label, ..Default::default() });
+    element
+}
+
+/// Options of UI.show_modal: name (instance name, the prefab file's stem by default),
+/// close_on_escape, close_on_background_click, dim_color ({r, g, b, a}), fade_duration
+/// (seconds) and on_closed (function name in the calling script)
+pub fn modal_options_from_lua(path: &str, options: Option<Table>) -> mlua::Result<(String, ui::ModalOptions)> {
+    let stem = std::path::Path::new(path)
+        .file_stem()
+        .map_or_else(|| path.to_string(), |stem| stem.to_string_lossy().into_owned());
+    let mut modal = ui::ModalOptions::default();
+    let Some(options) = options else {
+        return Ok((stem, modal));
+    };
+
+    if let Some(close_on_escape) = options.get("close_on_escape")? {
+        modal.close_on_escape = close_on_escape;
+    }
+    if let Some(close_on_background_click) = options.get("close_on_background_click")? {
+        modal.close_on_background_click = close_on_background_click;
+    }
+    if let Some(color) = options.get::<_, Option<mlua::Value>>("dim_color")? {
+        modal.dim_color = ui::lua_bindings::color_from_lua(color)?;
+    }
+    if let Some(fade_duration) = options.get::<_, Option<f32>>("fade_duration")? {
+        modal.fade_duration = fade_duration.max(0.0);
+    }
+    modal.on_closed = options.get("on_closed")?;
+    let name = options.get::<_, Option<String>>("name")?.unwrap_or(stem);
+    Ok((name, modal))
+}
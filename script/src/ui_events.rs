@@ -0,0 +1,126 @@
+// UI event delivery to scripts (UI.on_submit / UI.on_value_changed / UI.on_drop)
+//
+// Listeners are registered with the engine's UI manager through UICommands; the engine
+// hands fired events back here, queued per owner entity. They run inside the owner's
+// next run_script() (before Update), like timer callbacks, with the full script API.
+// Events for a disabled script are dropped unless their listener buffers them.
+
+use ecs::Entity;
+use mlua::{Function, Lua};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+use crate::events::DisabledPolicy;
+
+/// UI event a script can listen to
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UIEventKind {
+    /// Enter pressed in an input field: fn(text, element_path)
+    Submit,
+    /// Input field text changed (at most once per frame): fn(text, element_path). Also
+    /// slider and toggle changes from prefab on_value_changed bindings ("0.5", "true").
+    ValueChanged,
+    /// A draggable element was released over the element: fn(source_path, target_path)
+    Drop,
+    /// Button clicked (prefab on_click bindings): fn("", element_path)
+    Click,
+}
+
+/// A listener's callback to run: `callback(value, element_path)` in `owner`'s script
+#[derive(Clone, Debug, PartialEq)]
+pub struct UIScriptEvent {
+    pub owner: Entity,
+    pub callback: String,
+    pub element_path: String,
+    pub value: String,
+    /// The listener's policy for events that arrive while `owner`'s script is disabled
+    pub while_disabled: DisabledPolicy,
+}
+
+/// Input field state the engine syncs every frame (read by UI.get_input_text)
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputFieldSnapshot {
+    pub text: String,
+    pub character_validation: ui::CharacterValidation,
+}
+
+#[derive(Default)]
+pub struct UIScriptEvents {
+    pending: HashMap<Entity, Vec<UIScriptEvent>>,
+    /// Owners whose script is disabled
+    suspended: HashSet<Entity>,
+}
+
+impl UIScriptEvents {
+    pub fn queue(&mut self, event: UIScriptEvent) {
+        if self.suspended.contains(&event.owner) && event.while_disabled == DisabledPolicy::Drop {
+            return;
+        }
+        self.pending.entry(event.owner).or_default().push(event);
+    }
+
+    pub fn take(&mut self, owner: Entity) -> Vec<UIScriptEvent> {
+        self.pending.remove(&owner).unwrap_or_default()
+    }
+
+    /// The owner's script was disabled (or enabled again); events already queued for it
+    /// are kept only when their listener buffers them
+    pub fn set_suspended(&mut self, owner: Entity, suspended: bool) {
+        if !suspended {
+            self.suspended.remove(&owner);
+            return;
+        }
+        self.suspended.insert(owner);
+        if let Some(pending) = self.pending.get_mut(&owner) {
+            pending.retain(|event| event.while_disabled == DisabledPolicy::Buffer);
+        }
+    }
+
+    pub fn remove_entity(&mut self, owner: Entity) {
+        self.pending.remove(&owner);
+        self.suspended.remove(&owner);
+    }
+}
+
+/// Run `entity`'s queued UI callbacks (call inside its run_script scope)
+pub fn run_pending_callbacks(events: &Rc<RefCell<UIScriptEvents>>, lua: &Lua, entity: Entity) -> mlua::Result<()> {
+    // Take first: callbacks may queue UI commands that fire more events next frame
+    let pending = events.borrow_mut().take(entity);
+    for event in pending {
+        match lua.globals().get::<_, Function>(event.callback.as_str()) {
+            Ok(callback) => {
+                if let Err(e) = callback.call::<_, ()>((event.value, event.element_path)) {
+                    log::error!("UI callback '{}' error for entity {}: {}", event.callback, entity, e);
+                }
+            }
+            Err(_) => log::warn!("UI callback '{}' not found in script of entity {}", event.callback, entity),
+        }
+    }
+    Ok(())
+}
+
+/// Parse a CharacterValidation name from Lua ("integer", "decimal", ...)
+pub fn parse_character_validation(name: &str) -> Option<ui::CharacterValidation> {
+    use ui::CharacterValidation;
+    match name.to_ascii_lowercase().as_str() {
+        "none" => Some(CharacterValidation::None),
+        "integer" => Some(CharacterValidation::Integer),
+        "decimal" => Some(CharacterValidation::Decimal),
+        "alphanumeric" => Some(CharacterValidation::Alphanumeric),
+        "name" => Some(CharacterValidation::Name),
+        "email" | "emailaddress" | "email_address" => Some(CharacterValidation::EmailAddress),
+        _ => None,
+    }
+}
+
+pub fn character_validation_name(validation: ui::CharacterValidation) -> &'static str {
+    use ui::CharacterValidation;
+    match validation {
+        CharacterValidation::None => "none",
+        CharacterValidation::Integer => "integer",
+        CharacterValidation::Decimal => "decimal",
+        CharacterValidation::Alphanumeric => "alphanumeric",
+        CharacterValidation::Name => "name",
+        CharacterValidation::EmailAddress => "email",
+    }
+}
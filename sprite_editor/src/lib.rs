@@ -9,6 +9,7 @@
 //! - Auto-slicing for grid-based sprite sheets
 //! - Multiple export formats (JSON, XML, TexturePacker)
 //! - Sprite validation and statistics
+//! - Alpha-traced polygon physics shapes and per-sprite pivots
 //! - Hot-reloading of sprite metadata
 
 // Module declarations
@@ -16,6 +17,7 @@ pub mod metadata;
 pub mod statistics;
 pub mod auto_slicer;
 pub mod utils;
+pub mod outline;
 
 #[cfg(feature = "editor_ui")]
 pub mod ui;
@@ -24,6 +26,7 @@ pub mod ui;
 pub use metadata::{ExportFormat, SpriteDefinition, SpriteMetadata};
 pub use statistics::SpriteStatistics;
 pub use auto_slicer::AutoSlicer;
+pub use outline::AlphaMask;
 
 #[cfg(feature = "editor_ui")]
 pub use ui::{SpriteEditorWindow, SpriteEditorState, DragMode, ResizeHandle, TextureManager};
@@ -22,6 +22,21 @@ pub struct SpriteDefinition {
     pub y: u32,
     pub width: u32,
     pub height: u32,
+    /// Pivot in normalized sprite coordinates, (0, 0) = bottom-left, (0.5, 0.5) = center
+    #[serde(default = "default_pivot")]
+    pub pivot: [f32; 2],
+    /// Physics shape outline in pixels relative to the rect's top-left (y down).
+    /// Empty means the sprite has no polygon shape.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub polygon: Vec<[f32; 2]>,
+    /// Packed turned 90° clockwise: the rect covers height x width pixels of the texture
+    /// from (x, y), while width and height stay the sprite's as displayed
+    #[serde(default)]
+    pub rotated: bool,
+}
+
+fn default_pivot() -> [f32; 2] {
+    [0.5, 0.5]
 }
 
 impl SpriteDefinition {
@@ -33,8 +48,16 @@ impl SpriteDefinition {
             y,
             width,
             height,
+            pivot: default_pivot(),
+            polygon: Vec::new(),
+            rotated: false,
         }
     }
+
+    /// Rect as [x, y, width, height]
+    pub fn rect(&self) -> [u32; 4] {
+        [self.x, self.y, self.width, self.height]
+    }
 }
 
 /// Metadata for a sprite sheet containing multiple sprites
@@ -81,6 +104,22 @@ impl SpriteMetadata {
         self.sprites.iter().any(|s| s.name == name)
     }
 
+    /// Replace the sprites with a new slicing.
+    /// Pivots and polygons carry over to new sprites whose rect didn't change.
+    pub fn replace_sprites(&mut self, sliced: Vec<SpriteDefinition>) {
+        let previous = std::mem::take(&mut self.sprites);
+        self.sprites = sliced
+            .into_iter()
+            .map(|mut sprite| {
+                if let Some(old) = previous.iter().find(|old| old.rect() == sprite.rect()) {
+                    sprite.pivot = old.pivot;
+                    sprite.polygon = old.polygon.clone();
+                }
+                sprite
+            })
+            .collect();
+    }
+
     /// Save sprite metadata to a .sprite JSON file
     pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
         let path = path.as_ref();
@@ -179,6 +218,17 @@ impl SpriteMetadata {
             xml.push_str(&format!("      <Y>{}</Y>\n", sprite.y));
             xml.push_str(&format!("      <Width>{}</Width>\n", sprite.width));
             xml.push_str(&format!("      <Height>{}</Height>\n", sprite.height));
+            xml.push_str(&format!("      <Pivot x=\"{}\" y=\"{}\" />\n", sprite.pivot[0], sprite.pivot[1]));
+            if sprite.rotated {
+                xml.push_str("      <Rotated>true</Rotated>\n");
+            }
+            if !sprite.polygon.is_empty() {
+                xml.push_str("      <Polygon>\n");
+                for point in &sprite.polygon {
+                    xml.push_str(&format!("        <Point x=\"{}\" y=\"{}\" />\n", point[0], point[1]));
+                }
+                xml.push_str("      </Polygon>\n");
+            }
             xml.push_str("    </Sprite>\n");
         }
 
@@ -209,14 +259,14 @@ impl SpriteMetadata {
         if let Some(frames) = tp_data.get_mut("frames") {
             if let Some(frames_obj) = frames.as_object_mut() {
                 for sprite in &self.sprites {
-                    let frame_data = serde_json::json!({
+                    let mut frame_data = serde_json::json!({
                         "frame": {
                             "x": sprite.x,
                             "y": sprite.y,
                             "w": sprite.width,
                             "h": sprite.height
                         },
-                        "rotated": false,
+                        "rotated": sprite.rotated,
                         "trimmed": false,
                         "spriteSourceSize": {
                             "x": 0,
@@ -227,8 +277,16 @@ impl SpriteMetadata {
                         "sourceSize": {
                             "w": sprite.width,
                             "h": sprite.height
+                        },
+                        // TexturePacker pivots are measured from the top-left
+                        "pivot": {
+                            "x": sprite.pivot[0],
+                            "y": 1.0 - sprite.pivot[1]
                         }
                     });
+                    if !sprite.polygon.is_empty() {
+                        frame_data["vertices"] = serde_json::json!(sprite.polygon);
+                    }
 
                     frames_obj.insert(sprite.name.clone(), frame_data);
                 }
@@ -252,6 +310,33 @@ mod tests {
         assert_eq!(sprite.y, 20);
         assert_eq!(sprite.width, 30);
         assert_eq!(sprite.height, 40);
+        assert_eq!(sprite.pivot, [0.5, 0.5]);
+        assert!(sprite.polygon.is_empty());
+    }
+
+    #[test]
+    fn test_replace_sprites_keeps_shapes_of_unchanged_rects() {
+        let mut metadata = SpriteMetadata::new("test.png".to_string(), 64, 32);
+        let mut edited = SpriteDefinition::new("a".to_string(), 0, 0, 32, 32);
+        edited.pivot = [0.5, 0.0];
+        edited.polygon = vec![[0.0, 0.0], [32.0, 0.0], [16.0, 32.0]];
+        metadata.add_sprite(edited.clone());
+        metadata.add_sprite(SpriteDefinition::new("b".to_string(), 32, 0, 32, 32));
+
+        metadata.replace_sprites(vec![
+            SpriteDefinition::new("sprite_0".to_string(), 0, 0, 32, 32),
+            SpriteDefinition::new("sprite_1".to_string(), 32, 0, 16, 32),
+        ]);
+
+        assert_eq!(metadata.sprites[0].name, "sprite_0");
+        assert_eq!(metadata.sprites[0].pivot, edited.pivot);
+        assert_eq!(metadata.sprites[0].polygon, edited.polygon);
+        assert!(metadata.sprites[1].polygon.is_empty());
+
+        // Old files without pivot/polygon still load
+        let json = r#"{"name":"old","x":0,"y":0,"width":8,"height":8}"#;
+        let old: SpriteDefinition = serde_json::from_str(json).unwrap();
+        assert_eq!(old.pivot, [0.5, 0.5]);
     }
 
     #[test]
@@ -0,0 +1,275 @@
+//! Outline Tracing
+//!
+//! Alpha-based physics shape generation for sprites: marching squares traces the
+//! outline of the largest opaque region, Douglas-Peucker simplifies it.
+
+/// Alpha channel of a texture, row-major, top row first
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlphaMask {
+    pub width: u32,
+    pub height: u32,
+    pub alpha: Vec<u8>,
+}
+
+impl AlphaMask {
+    /// Build a mask from tightly packed RGBA8 pixels
+    pub fn from_rgba(width: u32, height: u32, rgba: &[u8]) -> Self {
+        Self {
+            width,
+            height,
+            alpha: rgba.chunks_exact(4).map(|pixel| pixel[3]).collect(),
+        }
+    }
+
+    /// Load the alpha channel of an image file
+    pub fn load<P: AsRef<std::path::Path>>(path: P) -> Result<Self, String> {
+        let image = image::open(path.as_ref())
+            .map_err(|e| format!("Failed to load texture: {}", e))?
+            .to_rgba8();
+        Ok(Self::from_rgba(image.width(), image.height(), image.as_raw()))
+    }
+
+    /// Alpha at a pixel; pixels outside the texture are transparent
+    pub fn alpha_at(&self, x: i64, y: i64) -> u8 {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return 0;
+        }
+        self.alpha[(y as u64 * self.width as u64 + x as u64) as usize]
+    }
+}
+
+/// Trace the outline of the largest opaque region inside a sprite rect.
+///
+/// Points are pixel corners relative to the rect's top-left, y down.
+/// Returns an empty outline if no pixel in the rect exceeds `alpha_threshold`.
+pub fn trace_outline(mask: &AlphaMask, rect: [u32; 4], alpha_threshold: u8) -> Vec<[f32; 2]> {
+    let [rect_x, rect_y, width, height] = rect;
+    let (width, height) = (width as usize, height as usize);
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+
+    let opaque: Vec<bool> = (0..width * height)
+        .map(|i| {
+            let (x, y) = (i % width, i / width);
+            mask.alpha_at(rect_x as i64 + x as i64, rect_y as i64 + y as i64) > alpha_threshold
+        })
+        .collect();
+
+    let Some(region) = largest_region(&opaque, width, height) else {
+        return Vec::new();
+    };
+
+    let solid = |x: i64, y: i64| -> bool {
+        x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height && region[y as usize * width + x as usize]
+    };
+
+    // Top-left-most pixel of the region; its top-left corner is on the outline
+    let start_index = region.iter().position(|&inside| inside).unwrap_or(0);
+    let start = ((start_index % width) as i64, (start_index / width) as i64);
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum Step { Up, Down, Left, Right }
+
+    let mut points = Vec::new();
+    let (mut x, mut y) = start;
+    let mut previous: Option<Step> = None;
+
+    // Bounded by the number of lattice points, so a degenerate mask can't loop forever
+    for _ in 0..=(width + 1) * (height + 1) * 2 {
+        let mut cell = 0;
+        if solid(x - 1, y - 1) { cell |= 1; }
+        if solid(x, y - 1) { cell |= 2; }
+        if solid(x - 1, y) { cell |= 4; }
+        if solid(x, y) { cell |= 8; }
+
+        let step = match cell {
+            1 | 5 | 13 => Step::Up,
+            8 | 10 | 11 => Step::Down,
+            4 | 12 | 14 => Step::Left,
+            2 | 3 | 7 => Step::Right,
+            6 => if previous == Some(Step::Up) { Step::Left } else { Step::Right },
+            9 => if previous == Some(Step::Right) { Step::Up } else { Step::Down },
+            _ => break,
+        };
+
+        // Only corners are kept; straight runs collapse to their end points
+        if previous != Some(step) {
+            points.push([x as f32, y as f32]);
+        }
+        previous = Some(step);
+
+        match step {
+            Step::Up => y -= 1,
+            Step::Down => y += 1,
+            Step::Left => x -= 1,
+            Step::Right => x += 1,
+        }
+
+        if (x, y) == start {
+            break;
+        }
+    }
+
+    points
+}
+
+/// Mask of the largest 4-connected opaque region
+fn largest_region(opaque: &[bool], width: usize, height: usize) -> Option<Vec<bool>> {
+    let mut labels = vec![0u32; opaque.len()];
+    let mut best: Option<(u32, usize)> = None;
+    let mut next_label = 0;
+    let mut stack = Vec::new();
+
+    for seed in 0..opaque.len() {
+        if !opaque[seed] || labels[seed] != 0 {
+            continue;
+        }
+        next_label += 1;
+        labels[seed] = next_label;
+        stack.push(seed);
+        let mut size = 0;
+
+        while let Some(index) = stack.pop() {
+            size += 1;
+            let (x, y) = (index % width, index / width);
+            let mut visit = |neighbor: usize| {
+                if opaque[neighbor] && labels[neighbor] == 0 {
+                    labels[neighbor] = next_label;
+                    stack.push(neighbor);
+                }
+            };
+            if x > 0 { visit(index - 1); }
+            if x + 1 < width { visit(index + 1); }
+            if y > 0 { visit(index - width); }
+            if y + 1 < height { visit(index + width); }
+        }
+
+        if best.is_none_or(|(_, best_size)| size > best_size) {
+            best = Some((next_label, size));
+        }
+    }
+
+    best.map(|(label, _)| labels.iter().map(|&l| l == label).collect())
+}
+
+/// Simplify a closed polygon with Douglas-Peucker.
+/// `tolerance` is the maximum distance (pixels) a removed point may be from the result.
+pub fn simplify_polygon(points: &[[f32; 2]], tolerance: f32) -> Vec<[f32; 2]> {
+    if points.len() <= 3 {
+        return points.to_vec();
+    }
+
+    // Split the ring at the point farthest from the first one and simplify both halves
+    let first = points[0];
+    let far = (1..points.len())
+        .max_by(|&a, &b| distance_sq(points[a], first).total_cmp(&distance_sq(points[b], first)))
+        .unwrap_or(points.len() / 2);
+
+    let mut keep = vec![false; points.len()];
+    keep[0] = true;
+    keep[far] = true;
+    douglas_peucker(points, 0, far, tolerance, &mut keep);
+
+    let mut ring: Vec<[f32; 2]> = points[far..].to_vec();
+    ring.push(first);
+    let mut keep_tail = vec![false; ring.len()];
+    douglas_peucker(&ring, 0, ring.len() - 1, tolerance, &mut keep_tail);
+    for (offset, kept) in keep_tail.iter().enumerate().take(ring.len() - 1).skip(1) {
+        if *kept {
+            keep[far + offset] = true;
+        }
+    }
+
+    let simplified: Vec<[f32; 2]> = points.iter().zip(&keep).filter(|(_, &k)| k).map(|(p, _)| *p).collect();
+    if simplified.len() < 3 {
+        points.to_vec()
+    } else {
+        simplified
+    }
+}
+
+fn douglas_peucker(points: &[[f32; 2]], start: usize, end: usize, tolerance: f32, keep: &mut [bool]) {
+    if end <= start + 1 {
+        return;
+    }
+    let (index, distance) = (start + 1..end)
+        .map(|i| (i, distance_to_segment(points[i], points[start], points[end])))
+        .fold((start, -1.0), |best, candidate| if candidate.1 > best.1 { candidate } else { best });
+
+    if distance > tolerance {
+        keep[index] = true;
+        douglas_peucker(points, start, index, tolerance, keep);
+        douglas_peucker(points, index, end, tolerance, keep);
+    }
+}
+
+fn distance_sq(a: [f32; 2], b: [f32; 2]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2)
+}
+
+fn distance_to_segment(p: [f32; 2], a: [f32; 2], b: [f32; 2]) -> f32 {
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let length_sq = dx * dx + dy * dy;
+    if length_sq == 0.0 {
+        return distance_sq(p, a).sqrt();
+    }
+    let t = (((p[0] - a[0]) * dx + (p[1] - a[1]) * dy) / length_sq).clamp(0.0, 1.0);
+    distance_sq(p, [a[0] + t * dx, a[1] + t * dy]).sqrt()
+}
+
+/// Trace and simplify the physics shape for a sprite rect
+pub fn generate_polygon(mask: &AlphaMask, rect: [u32; 4], alpha_threshold: u8, tolerance: f32) -> Vec<[f32; 2]> {
+    simplify_polygon(&trace_outline(mask, rect, alpha_threshold), tolerance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mask_from_rows(rows: &[&str]) -> AlphaMask {
+        let height = rows.len() as u32;
+        let width = rows[0].len() as u32;
+        let alpha = rows.iter()
+            .flat_map(|row| row.chars().map(|c| if c == '#' { 255 } else { 0 }))
+            .collect();
+        AlphaMask { width, height, alpha }
+    }
+
+    #[test]
+    fn test_trace_square_gives_four_corners() {
+        let mask = mask_from_rows(&[
+            "......",
+            ".####.",
+            ".####.",
+            "......",
+        ]);
+        let outline = trace_outline(&mask, [0, 0, 6, 4], 0);
+        assert_eq!(outline.len(), 4);
+        for corner in [[1.0, 1.0], [1.0, 3.0], [5.0, 3.0], [5.0, 1.0]] {
+            assert!(outline.contains(&corner), "missing corner {:?} in {:?}", corner, outline);
+        }
+
+        // Relative to the rect, and only the rect's pixels count
+        let outline = trace_outline(&mask, [1, 1, 2, 2], 0);
+        assert!(outline.contains(&[0.0, 0.0]) && outline.contains(&[2.0, 2.0]));
+    }
+
+    #[test]
+    fn test_largest_region_and_simplify() {
+        let mask = mask_from_rows(&[
+            "#.......",
+            "..#####.",
+            "..######",
+            "..#####.",
+        ]);
+        let outline = trace_outline(&mask, [0, 0, 8, 4], 0);
+        // The lone pixel at (0,0) is ignored
+        assert!(!outline.contains(&[0.0, 0.0]));
+        assert!(outline.len() > 4);
+
+        let simplified = simplify_polygon(&outline, 1.5);
+        assert!(simplified.len() >= 3 && simplified.len() < outline.len());
+        assert!(simplify_polygon(&outline, 0.0).len() == outline.len());
+    }
+}
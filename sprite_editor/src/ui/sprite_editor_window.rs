@@ -2,9 +2,10 @@
 //!
 //! Visual editor window for sprite sheets with egui
 
-use crate::{SpriteMetadata, SpriteDefinition, ExportFormat, SpriteStatistics};
+use crate::{SpriteMetadata, SpriteDefinition, ExportFormat, SpriteStatistics, AutoSlicer, AlphaMask};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::SystemTime;
 use egui::TextureHandle;
 
@@ -33,6 +34,20 @@ enum AutoSliceMode {
     CellSize,
 }
 
+/// Handle being dragged on the selected sprite
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ShapeDrag {
+    Pivot,
+    Vertex(usize),
+}
+
+/// Pivot presets (label, pivot) in a 3x3 grid, top row first; pivot y is up
+const PIVOT_PRESETS: [(&str, [f32; 2]); 9] = [
+    ("↖", [0.0, 1.0]), ("⬆", [0.5, 1.0]), ("↗", [1.0, 1.0]),
+    ("⬅", [0.0, 0.5]), ("⏺", [0.5, 0.5]), ("➡", [1.0, 0.5]),
+    ("↙", [0.0, 0.0]), ("⬇", [0.5, 0.0]), ("↘", [1.0, 0.0]),
+];
+
 /// Texture manager trait for loading textures
 /// This allows the sprite editor to work with different texture management systems
 pub trait TextureManager {
@@ -71,6 +86,10 @@ pub struct SpriteEditorState {
     
     // Texture
     pub texture_handle: Option<TextureHandle>,
+    /// Resolved texture file the handle was loaded from
+    pub texture_file: Option<PathBuf>,
+    /// Texture alpha for physics shape generation, loaded on first use
+    pub alpha_mask: Option<Arc<AlphaMask>>,
     
     // Hot-reloading
     pub last_modified: Option<SystemTime>,
@@ -159,6 +178,8 @@ impl SpriteEditorState {
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
             texture_handle: None,
+            texture_file: None,
+            alpha_mask: None,
             last_modified,
             check_interval: 1.0,
             time_since_check: 0.0,
@@ -194,6 +215,17 @@ impl SpriteEditorState {
     pub fn save(&self) -> Result<(), String> {
         self.metadata.save(&self.metadata_path)
     }
+
+    /// Load the texture's alpha channel if it isn't cached yet
+    pub fn ensure_alpha_mask(&mut self) -> Result<Arc<AlphaMask>, String> {
+        if let Some(mask) = &self.alpha_mask {
+            return Ok(mask.clone());
+        }
+        let path = self.texture_file.clone().unwrap_or_else(|| self.texture_path.clone());
+        let mask = Arc::new(AlphaMask::load(&path)?);
+        self.alpha_mask = Some(mask.clone());
+        Ok(mask)
+    }
     
     /// Load texture using texture manager
     pub fn load_texture<T: TextureManager>(&mut self, ctx: &egui::Context, texture_manager: &mut T) -> Result<(), String> {
@@ -206,6 +238,7 @@ impl SpriteEditorState {
                 log::info!("Sprite editor loading texture (relative): {}", self.texture_path.display());
                 if let Some(handle) = texture_manager.load_texture(ctx, &texture_id, &self.texture_path) {
                     self.texture_handle = Some(handle.clone());
+                    self.texture_file = Some(full_path);
                     let size = handle.size();
                     self.metadata.texture_width = size[0] as u32;
                     self.metadata.texture_height = size[1] as u32;
@@ -215,6 +248,7 @@ impl SpriteEditorState {
                 log::info!("Sprite editor loading texture (absolute): {}", self.texture_path.display());
                 if let Some(handle) = texture_manager.load_texture_absolute(ctx, &texture_id, &self.texture_path) {
                     self.texture_handle = Some(handle.clone());
+                    self.texture_file = Some(self.texture_path.clone());
                     let size = handle.size();
                     self.metadata.texture_width = size[0] as u32;
                     self.metadata.texture_height = size[1] as u32;
@@ -227,6 +261,7 @@ impl SpriteEditorState {
             log::info!("Sprite editor loading texture (no base): {}", self.texture_path.display());
             if let Some(handle) = texture_manager.load_texture_absolute(ctx, &texture_id, &self.texture_path) {
                 self.texture_handle = Some(handle.clone());
+                self.texture_file = Some(self.texture_path.clone());
                 let size = handle.size();
                 self.metadata.texture_width = size[0] as u32;
                 self.metadata.texture_height = size[1] as u32;
@@ -307,6 +342,10 @@ pub struct SpriteEditorWindow {
     export_message: Option<String>,
     export_error: Option<String>,
     statistics: SpriteStatistics,
+    shape_alpha_threshold: u8,
+    shape_tolerance: f32,
+    shape_error: Option<String>,
+    shape_drag: Option<ShapeDrag>,
 }
 
 impl SpriteEditorWindow {
@@ -333,6 +372,10 @@ impl SpriteEditorWindow {
             export_message: None,
             export_error: None,
             statistics,
+            shape_alpha_threshold: 10,
+            shape_tolerance: 1.5,
+            shape_error: None,
+            shape_drag: None,
         }
     }
     
@@ -492,6 +535,32 @@ impl SpriteEditorWindow {
         }
     }
 
+    /// Trace physics shapes from the texture alpha for the given sprites
+    fn generate_shapes(&mut self, indices: &[usize]) {
+        let mask = match self.state.ensure_alpha_mask() {
+            Ok(mask) => mask,
+            Err(e) => {
+                self.shape_error = Some(e);
+                return;
+            }
+        };
+        self.shape_error = None;
+        self.state.push_undo();
+
+        let mut empty = 0;
+        for &idx in indices {
+            if let Some(sprite) = self.state.metadata.sprites.get_mut(idx) {
+                sprite.polygon = crate::outline::generate_polygon(&mask, sprite.rect(), self.shape_alpha_threshold, self.shape_tolerance);
+                if sprite.polygon.is_empty() {
+                    empty += 1;
+                }
+            }
+        }
+        if empty > 0 {
+            self.shape_error = Some(format!("{} sprite(s) have no pixels above the alpha threshold", empty));
+        }
+    }
+
     /// Generate a unique name for duplicated sprite
     fn generate_duplicate_name(&self, original_name: &str) -> String {
         let base_name = if original_name.ends_with("_copy") {
@@ -921,12 +990,109 @@ impl SpriteEditorWindow {
                         self.update_statistics();
                     }
                 });
+
+                let mut rotated = sprite.rotated;
+                if ui.checkbox(&mut rotated, "Rotated")
+                    .on_hover_text("Packed turned 90° clockwise: Width and Height are as displayed, the frame covers Height x Width pixels of the texture")
+                    .changed()
+                {
+                    self.state.push_undo();
+                    if let Some(sprite_mut) = self.state.metadata.sprites.get_mut(idx) {
+                        sprite_mut.rotated = rotated;
+                    }
+                }
+
+                ui.add_space(10.0);
+                self.render_pivot_properties(ui, idx, &sprite);
+
+                ui.add_space(10.0);
+                self.render_shape_properties(ui, idx, &sprite);
             }
         } else {
             ui.label("No sprite selected");
         }
     }
     
+    fn render_pivot_properties(&mut self, ui: &mut egui::Ui, idx: usize, sprite: &SpriteDefinition) {
+        ui.label("Pivot:");
+
+        let mut new_pivot = None;
+        egui::Grid::new("pivot_presets").spacing([2.0, 2.0]).show(ui, |ui| {
+            for (i, (label, pivot)) in PIVOT_PRESETS.iter().enumerate() {
+                if ui.selectable_label(sprite.pivot == *pivot, *label).clicked() {
+                    new_pivot = Some(*pivot);
+                }
+                if i % 3 == 2 {
+                    ui.end_row();
+                }
+            }
+        });
+
+        let mut pivot = sprite.pivot;
+        ui.horizontal(|ui| {
+            ui.label("X:");
+            let x_changed = ui.add(egui::DragValue::new(&mut pivot[0]).speed(0.01).range(0.0..=1.0).max_decimals(3)).changed();
+            ui.label("Y:");
+            let y_changed = ui.add(egui::DragValue::new(&mut pivot[1]).speed(0.01).range(0.0..=1.0).max_decimals(3)).changed();
+            if x_changed || y_changed {
+                new_pivot = Some(pivot);
+            }
+        });
+
+        if let Some(pivot) = new_pivot {
+            self.state.push_undo();
+            if let Some(sprite_mut) = self.state.metadata.sprites.get_mut(idx) {
+                sprite_mut.pivot = pivot;
+            }
+        }
+    }
+
+    fn render_shape_properties(&mut self, ui: &mut egui::Ui, idx: usize, sprite: &SpriteDefinition) {
+        ui.label("Physics Shape:");
+        ui.label(
+            egui::RichText::new(if sprite.polygon.is_empty() {
+                "None".to_string()
+            } else {
+                format!("{} points", sprite.polygon.len())
+            })
+            .small()
+            .color(egui::Color32::GRAY)
+        );
+
+        ui.horizontal(|ui| {
+            ui.label("Alpha:");
+            ui.add(egui::Slider::new(&mut self.shape_alpha_threshold, 0..=254))
+                .on_hover_text("Pixels with alpha above this are solid");
+        });
+        ui.horizontal(|ui| {
+            ui.label("Tolerance:");
+            ui.add(egui::Slider::new(&mut self.shape_tolerance, 0.0..=10.0).suffix(" px"))
+                .on_hover_text("Higher values give fewer points");
+        });
+
+        ui.horizontal(|ui| {
+            if ui.button("🔷 Generate").clicked() {
+                self.generate_shapes(&[idx]);
+            }
+            if ui.button("Generate All").clicked() {
+                let all: Vec<usize> = (0..self.state.metadata.sprites.len()).collect();
+                self.generate_shapes(&all);
+            }
+            ui.add_enabled_ui(!sprite.polygon.is_empty(), |ui| {
+                if ui.button("Clear").clicked() {
+                    self.state.push_undo();
+                    if let Some(sprite_mut) = self.state.metadata.sprites.get_mut(idx) {
+                        sprite_mut.polygon.clear();
+                    }
+                }
+            });
+        });
+
+        if let Some(error) = &self.shape_error {
+            ui.colored_label(egui::Color32::from_rgb(255, 200, 100), format!("⚠ {}", error));
+        }
+    }
+
     fn render_statistics_panel(&self, ui: &mut egui::Ui) {
         ui.label("Texture Dimensions:");
         ui.label(format!(
@@ -975,9 +1141,11 @@ impl SpriteEditorWindow {
                 for (idx, sprite) in self.state.metadata.sprites.iter().enumerate() {
                     let is_selected = self.state.selected_sprite == Some(idx);
                     
+                    // Rotated frames cover height x width pixels of the texture
+                    let (width, height) = if sprite.rotated { (sprite.height, sprite.width) } else { (sprite.width, sprite.height) };
                     let sprite_rect = egui::Rect::from_min_size(
                         texture_rect.min + egui::vec2(sprite.x as f32 * self.state.zoom, sprite.y as f32 * self.state.zoom),
-                        egui::vec2(sprite.width as f32 * self.state.zoom, sprite.height as f32 * self.state.zoom),
+                        egui::vec2(width as f32 * self.state.zoom, height as f32 * self.state.zoom),
                     );
 
                     let color = if is_selected {
@@ -1006,14 +1174,76 @@ impl SpriteEditorWindow {
                     painter.galley(text_pos, text_galley, color);
                 }
                 
+                // Physics shape and pivot of the selected sprite
+                let zoom = self.state.zoom;
+                let selected = self.state.selected_sprite
+                    .and_then(|idx| self.state.metadata.sprites.get(idx).map(|sprite| (idx, sprite.clone())));
+                if let Some((idx, sprite)) = selected {
+                    let origin = texture_rect.min + egui::vec2(sprite.x as f32 * zoom, sprite.y as f32 * zoom);
+                    let to_screen = |p: [f32; 2]| origin + egui::vec2(p[0] * zoom, p[1] * zoom);
+                    let pivot_pos = to_screen([sprite.pivot[0] * sprite.width as f32, (1.0 - sprite.pivot[1]) * sprite.height as f32]);
+
+                    if sprite.polygon.len() >= 2 {
+                        let points: Vec<egui::Pos2> = sprite.polygon.iter().map(|p| to_screen(*p)).collect();
+                        painter.add(egui::Shape::closed_line(points.clone(), egui::Stroke::new(1.5, egui::Color32::from_rgb(0, 200, 255))));
+                        for point in points {
+                            painter.circle_filled(point, 3.0, egui::Color32::from_rgb(0, 200, 255));
+                        }
+                    }
+                    painter.circle_stroke(pivot_pos, 5.0, egui::Stroke::new(2.0, egui::Color32::from_rgb(0, 120, 255)));
+                    painter.circle_filled(pivot_pos, 2.0, egui::Color32::WHITE);
+
+                    // Drag the pivot or a polygon vertex
+                    if response.drag_started() {
+                        if let Some(pos) = response.interact_pointer_pos() {
+                            let grab = 6.0;
+                            self.shape_drag = if pos.distance(pivot_pos) <= grab {
+                                Some(ShapeDrag::Pivot)
+                            } else {
+                                sprite.polygon.iter()
+                                    .position(|p| pos.distance(to_screen(*p)) <= grab)
+                                    .map(ShapeDrag::Vertex)
+                            };
+                            if self.shape_drag.is_some() {
+                                self.state.push_undo();
+                            }
+                        }
+                    }
+                    if let (Some(drag), Some(pos)) = (self.shape_drag, response.interact_pointer_pos()) {
+                        if response.dragged() {
+                            let local = (pos - origin) / zoom;
+                            let (width, height) = (sprite.width as f32, sprite.height as f32);
+                            if let Some(sprite_mut) = self.state.metadata.sprites.get_mut(idx) {
+                                match drag {
+                                    ShapeDrag::Pivot => {
+                                        sprite_mut.pivot = [
+                                            (local.x / width).clamp(0.0, 1.0),
+                                            1.0 - (local.y / height).clamp(0.0, 1.0),
+                                        ];
+                                    }
+                                    ShapeDrag::Vertex(i) => {
+                                        if let Some(point) = sprite_mut.polygon.get_mut(i) {
+                                            *point = [local.x.clamp(0.0, width), local.y.clamp(0.0, height)];
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if response.drag_stopped() {
+                    self.shape_drag = None;
+                }
+
                 // Handle click to select sprite
                 if response.clicked() {
                     if let Some(pos) = response.interact_pointer_pos() {
                         let mut clicked_sprite = None;
                         for (idx, sprite) in self.state.metadata.sprites.iter().enumerate().rev() {
+                            let (width, height) = if sprite.rotated { (sprite.height, sprite.width) } else { (sprite.width, sprite.height) };
                             let sprite_rect = egui::Rect::from_min_size(
                                 texture_rect.min + egui::vec2(sprite.x as f32 * self.state.zoom, sprite.y as f32 * self.state.zoom),
-                                egui::vec2(sprite.width as f32 * self.state.zoom, sprite.height as f32 * self.state.zoom),
+                                egui::vec2(width as f32 * self.state.zoom, height as f32 * self.state.zoom),
                             );
                             if sprite_rect.contains(pos) {
                                 clicked_sprite = Some(idx);
@@ -1035,8 +1265,86 @@ impl SpriteEditorWindow {
             });
     }
     
-    fn render_auto_slice_dialog(&mut self, _ctx: &egui::Context) {
-        // TODO: Implement auto-slice dialog
+    fn render_auto_slice_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_auto_slice_dialog;
+        let mut apply = false;
+        let mut cancel = false;
+
+        egui::Window::new("✂ Auto Slice")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(&mut self.auto_slice_mode, AutoSliceMode::Grid, "Grid");
+                    ui.radio_value(&mut self.auto_slice_mode, AutoSliceMode::CellSize, "Cell Size");
+                });
+                ui.separator();
+
+                egui::Grid::new("auto_slice_grid").num_columns(2).spacing([8.0, 6.0]).show(ui, |ui| {
+                    match self.auto_slice_mode {
+                        AutoSliceMode::Grid => {
+                            ui.label("Columns:");
+                            ui.add(egui::DragValue::new(&mut self.auto_slice_columns).range(1..=256));
+                            ui.end_row();
+                            ui.label("Rows:");
+                            ui.add(egui::DragValue::new(&mut self.auto_slice_rows).range(1..=256));
+                            ui.end_row();
+                        }
+                        AutoSliceMode::CellSize => {
+                            ui.label("Cell Width:");
+                            ui.add(egui::DragValue::new(&mut self.auto_slice_cell_width).range(1..=4096).suffix(" px"));
+                            ui.end_row();
+                            ui.label("Cell Height:");
+                            ui.add(egui::DragValue::new(&mut self.auto_slice_cell_height).range(1..=4096).suffix(" px"));
+                            ui.end_row();
+                        }
+                    }
+                    ui.label("Padding:");
+                    ui.add(egui::DragValue::new(&mut self.auto_slice_padding).range(0..=1024).suffix(" px"));
+                    ui.end_row();
+                    ui.label("Spacing:");
+                    ui.add(egui::DragValue::new(&mut self.auto_slice_spacing).range(0..=1024).suffix(" px"));
+                    ui.end_row();
+                });
+
+                ui.add_space(5.0);
+                ui.label(
+                    egui::RichText::new("Replaces all sprites. Pivots and physics shapes are kept where a rect is unchanged.")
+                        .small()
+                        .color(egui::Color32::GRAY)
+                );
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    if ui.button("✂ Slice").clicked() {
+                        apply = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                });
+            });
+
+        if apply {
+            let (width, height) = (self.state.metadata.texture_width, self.state.metadata.texture_height);
+            let sliced = match self.auto_slice_mode {
+                AutoSliceMode::Grid => AutoSlicer::slice_by_grid(
+                    width, height, self.auto_slice_columns, self.auto_slice_rows, self.auto_slice_padding, self.auto_slice_spacing,
+                ),
+                AutoSliceMode::CellSize => AutoSlicer::slice_by_cell_size(
+                    width, height, self.auto_slice_cell_width, self.auto_slice_cell_height, self.auto_slice_padding, self.auto_slice_spacing,
+                ),
+            };
+            if !sliced.is_empty() {
+                self.state.push_undo();
+                self.state.metadata.replace_sprites(sliced);
+                self.state.selected_sprite = None;
+                self.update_statistics();
+            }
+            open = false;
+        }
+        self.show_auto_slice_dialog = open && !cancel;
     }
     
     fn render_export_dialog(&mut self, _ctx: &egui::Context) {
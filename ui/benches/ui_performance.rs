@@ -100,6 +100,7 @@ fn bench_hierarchy_operations(c: &mut Criterion) {
                 panel: None,
                 slider: None,
                 toggle: None,
+                toggle_group: None,
                 dropdown: None,
                 input_field: None,
                 scroll_view: None,
@@ -107,6 +108,10 @@ fn bench_hierarchy_operations(c: &mut Criterion) {
                 horizontal_layout: None,
                 vertical_layout: None,
                 grid_layout: None,
+                virtual_input: None,
+                tooltip: None,
+                safe_area: None,
+                canvas_group: None,
                 children: vec![],
             };
             
@@ -120,6 +125,7 @@ fn bench_hierarchy_operations(c: &mut Criterion) {
                 panel: None,
                 slider: None,
                 toggle: None,
+                toggle_group: None,
                 dropdown: None,
                 input_field: None,
                 scroll_view: None,
@@ -127,6 +133,10 @@ fn bench_hierarchy_operations(c: &mut Criterion) {
                 horizontal_layout: None,
                 vertical_layout: None,
                 grid_layout: None,
+                virtual_input: None,
+                tooltip: None,
+                safe_area: None,
+                canvas_group: None,
                 children: vec![],
             };
             
@@ -140,6 +150,7 @@ fn bench_hierarchy_operations(c: &mut Criterion) {
                 panel: None,
                 slider: None,
                 toggle: None,
+                toggle_group: None,
                 dropdown: None,
                 input_field: None,
                 scroll_view: None,
@@ -147,6 +158,10 @@ fn bench_hierarchy_operations(c: &mut Criterion) {
                 horizontal_layout: None,
                 vertical_layout: None,
                 grid_layout: None,
+                virtual_input: None,
+                tooltip: None,
+                safe_area: None,
+                canvas_group: None,
                 children: vec![child1, child2],
             };
             black_box(root);
@@ -196,6 +211,7 @@ fn bench_prefab_serialization(c: &mut Criterion) {
     
     let prefab = UIPrefab {
         name: "TestPrefab".to_string(),
+        canvas_scaler: None,
         root: UIPrefabElement {
             name: "Root".to_string(),
             rect_transform: RectTransform::default(),
@@ -205,7 +221,7 @@ fn bench_prefab_serialization(c: &mut Criterion) {
                 text: "Hello World".to_string(),
                 font: "default".to_string(),
                 font_size: 18.0,
-                color: [1.0, 1.0, 1.0, 1.0],
+                color: Color::rgba(1.0, 1.0, 1.0, 1.0),
                 alignment: TextAlignment::MiddleCenter,
                 horizontal_overflow: OverflowMode::Wrap,
                 vertical_overflow: OverflowMode::Truncate,
@@ -214,11 +230,13 @@ fn bench_prefab_serialization(c: &mut Criterion) {
                 best_fit: false,
                 best_fit_min_size: 10.0,
                 best_fit_max_size: 40.0,
+                localization_key: None,
             }),
             button: None,
             panel: None,
             slider: None,
             toggle: None,
+            toggle_group: None,
             dropdown: None,
             input_field: None,
             scroll_view: None,
@@ -226,6 +244,10 @@ fn bench_prefab_serialization(c: &mut Criterion) {
             horizontal_layout: None,
             vertical_layout: None,
             grid_layout: None,
+            virtual_input: None,
+            tooltip: None,
+            safe_area: None,
+            canvas_group: None,
             children: vec![],
         },
     };
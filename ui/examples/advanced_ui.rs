@@ -16,8 +16,8 @@ use ui::{
     UIDropdown, DropdownOption,
     UIInputField, ContentType, LineType, InputType, KeyboardType, CharacterValidation,
     UIAnimation, AnimatedProperty, AnimationValue, EasingFunction, LoopMode,
-    UIEventListener, UIEventType,
-    Vec2, Vec4,
+    UIEventListener, UIEventType, UIEventBinding,
+    Vec2, Vec4, Color,
 };
 
 type Entity = u64;
@@ -121,7 +121,11 @@ fn demo_slider() {
         max_value: 100.0,
         value: 50.0,
         whole_numbers: false,
-        on_value_changed: Some("on_slider_changed".to_string()),
+        fill_element: Some("Fill".to_string()),
+        handle_element: Some("Handle".to_string()),
+        smoothing_time: 0.1,
+        transition: None,
+        on_value_changed: Some(UIEventBinding::lua("on_slider_changed")),
     };
     
     println!("Created Slider:");
@@ -158,7 +162,11 @@ fn demo_toggle() {
         graphic: Some(2), // Checkmark entity
         is_on: false,
         toggle_transition: ToggleTransition::Fade,
-        on_value_changed: Some("on_toggle_changed".to_string()),
+        checkmark: Some("Checkmark".to_string()),
+        checkmark_duration: 0.1,
+        transition: None,
+        on_value_changed: Some(UIEventBinding::lua("on_toggle_changed")),
+        group: None,
     };
     
     println!("Created Toggle:");
@@ -212,6 +220,7 @@ fn demo_dropdown() {
         ],
         value: 1, // Selected index (Medium)
         on_value_changed: Some("on_dropdown_changed".to_string()),
+        transition: None,
     };
     
     println!("Created Dropdown:");
@@ -247,6 +256,7 @@ fn demo_input_field() {
     let text_field = UIInputField {
         text_component: Some(2),
         placeholder: Some(3),
+        placeholder_text: String::new(),
         text: String::new(),
         character_limit: 50,
         content_type: ContentType::Standard,
@@ -256,7 +266,7 @@ fn demo_input_field() {
         character_validation: CharacterValidation::None,
         caret_blink_rate: 0.85,
         caret_width: 1,
-        selection_color: [0.65, 0.8, 1.0, 0.75],
+        selection_color: Color::rgba(0.65, 0.8, 1.0, 0.75),
         read_only: false,
         on_value_changed: Some("on_text_changed".to_string()),
         on_end_edit: Some("on_text_submitted".to_string()),
@@ -375,8 +385,8 @@ fn demo_animations() {
     let color_anim = UIAnimation {
         entity: 1,
         property: AnimatedProperty::Color,
-        from: AnimationValue::Color([1.0, 1.0, 1.0, 1.0]),
-        to: AnimationValue::Color([1.0, 0.0, 0.0, 1.0]),
+        from: AnimationValue::Color(Color::rgba(1.0, 1.0, 1.0, 1.0)),
+        to: AnimationValue::Color(Color::rgba(1.0, 0.0, 0.0, 1.0)),
         duration: 2.0,
         easing: EasingFunction::Linear,
         delay: 0.5,
@@ -11,7 +11,7 @@
 use ui::{
     Canvas, CanvasRenderMode, CanvasScaler, ScaleMode,
     RectTransform, UIElement, UIImage, UIText, UIButton,
-    ButtonTransition, TextAlignment,
+    ButtonTransition, TextAlignment, UIEventBinding,
     Vec2, Vec4, Color,
 };
 
@@ -189,10 +189,12 @@ fn demo_button_creation() {
         raycast_target: true,
         blocks_raycasts: true,
         z_order: 0,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: Color::rgba(1.0, 1.0, 1.0, 1.0),
         alpha: 1.0,
         interactable: true,
         ignore_layout: false,
+        draggable: false,
+        pass_through: false,
         canvas_entity: None,
     };
     
@@ -201,19 +203,21 @@ fn demo_button_creation() {
     let button = UIButton {
         state: Default::default(),
         transition: ButtonTransition::ColorTint,
-        normal_color: [1.0, 1.0, 1.0, 1.0],
-        highlighted_color: [0.9, 0.9, 0.9, 1.0],
-        pressed_color: [0.7, 0.7, 0.7, 1.0],
-        disabled_color: [0.5, 0.5, 0.5, 0.5],
+        normal_color: Color::rgba(1.0, 1.0, 1.0, 1.0),
+        highlighted_color: Color::rgba(0.9, 0.9, 0.9, 1.0),
+        pressed_color: Color::rgba(0.7, 0.7, 0.7, 1.0),
+        disabled_color: Color::rgba(0.5, 0.5, 0.5, 0.5),
+        selected_color: Color::WHITE,
         fade_duration: 0.1,
         highlighted_sprite: None,
         pressed_sprite: None,
         disabled_sprite: None,
+        selected_sprite: None,
         normal_trigger: String::new(),
         highlighted_trigger: String::new(),
         pressed_trigger: String::new(),
         disabled_trigger: String::new(),
-        on_click: Some("on_button_clicked".to_string()),
+        on_click: Some(UIEventBinding::lua("on_button_clicked")),
     };
     
     println!("Created Button:");
@@ -236,7 +240,7 @@ fn demo_button_creation() {
         text: "Click Me!".to_string(),
         font: "default".to_string(),
         font_size: 16.0,
-        color: [0.0, 0.0, 0.0, 1.0],
+        color: Color::rgba(0.0, 0.0, 0.0, 1.0),
         alignment: TextAlignment::MiddleCenter,
         horizontal_overflow: ui::OverflowMode::Wrap,
         vertical_overflow: ui::OverflowMode::Truncate,
@@ -245,6 +249,7 @@ fn demo_button_creation() {
         best_fit: false,
         best_fit_min_size: 10.0,
         best_fit_max_size: 40.0,
+        localization_key: None,
     };
     
     println!("\nButton Text:");
@@ -283,7 +288,7 @@ fn demo_ui_hierarchy() {
         text: "Main Menu".to_string(),
         font: "default".to_string(),
         font_size: 24.0,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: Color::rgba(1.0, 1.0, 1.0, 1.0),
         alignment: TextAlignment::MiddleCenter,
         ..Default::default()
     };
@@ -50,7 +50,7 @@ fn create_simple_text_hud() -> HudAsset {
                 element_type: HudElementType::Text {
                     text: "Welcome to the Game!".to_string(),
                     font_size: 24.0,
-                    color: [1.0, 1.0, 1.0, 1.0],
+                    color: ui::Color::rgba(1.0, 1.0, 1.0, 1.0),
                 },
                 anchor: Anchor::TopCenter,
                 offset: [0.0, 20.0],
@@ -69,8 +69,8 @@ fn create_health_bar_hud() -> HudAsset {
                 id: "PlayerHealthBar".to_string(),
                 element_type: HudElementType::HealthBar {
                     binding: "player.health".to_string(),
-                    color: [1.0, 0.2, 0.2, 1.0],
-                    background_color: [0.2, 0.2, 0.2, 0.8],
+                    color: ui::Color::rgba(1.0, 0.2, 0.2, 1.0),
+                    background_color: ui::Color::rgba(0.2, 0.2, 0.2, 0.8),
                 },
                 anchor: Anchor::TopLeft,
                 offset: [10.0, 10.0],
@@ -90,8 +90,8 @@ fn create_game_hud() -> HudAsset {
                 id: "HealthBar".to_string(),
                 element_type: HudElementType::HealthBar {
                     binding: "player.health".to_string(),
-                    color: [1.0, 0.2, 0.2, 1.0],
-                    background_color: [0.2, 0.2, 0.2, 0.8],
+                    color: ui::Color::rgba(1.0, 0.2, 0.2, 1.0),
+                    background_color: ui::Color::rgba(0.2, 0.2, 0.2, 0.8),
                 },
                 anchor: Anchor::TopLeft,
                 offset: [10.0, 10.0],
@@ -104,8 +104,8 @@ fn create_game_hud() -> HudAsset {
                 id: "ManaBar".to_string(),
                 element_type: HudElementType::ProgressBar {
                     binding: "player.mana".to_string(),
-                    color: [0.2, 0.5, 1.0, 1.0],
-                    background_color: [0.2, 0.2, 0.2, 0.8],
+                    color: ui::Color::rgba(0.2, 0.5, 1.0, 1.0),
+                    background_color: ui::Color::rgba(0.2, 0.2, 0.2, 0.8),
                 },
                 anchor: Anchor::TopLeft,
                 offset: [10.0, 35.0],
@@ -119,7 +119,7 @@ fn create_game_hud() -> HudAsset {
                 element_type: HudElementType::DynamicText {
                     format: "Score: {score}".to_string(),
                     font_size: 20.0,
-                    color: [1.0, 1.0, 0.0, 1.0],
+                    color: ui::Color::rgba(1.0, 1.0, 0.0, 1.0),
                 },
                 anchor: Anchor::TopCenter,
                 offset: [0.0, 10.0],
@@ -133,7 +133,7 @@ fn create_game_hud() -> HudAsset {
                 element_type: HudElementType::DynamicText {
                     format: "Level: {level}".to_string(),
                     font_size: 18.0,
-                    color: [0.8, 0.8, 1.0, 1.0],
+                    color: ui::Color::rgba(0.8, 0.8, 1.0, 1.0),
                 },
                 anchor: Anchor::TopRight,
                 offset: [-10.0, 10.0],
@@ -146,7 +146,7 @@ fn create_game_hud() -> HudAsset {
                 id: "Minimap".to_string(),
                 element_type: HudElementType::Minimap {
                     zoom: 2.0,
-                    background_color: [0.1, 0.1, 0.1, 0.9],
+                    background_color: ui::Color::rgba(0.1, 0.1, 0.1, 0.9),
                 },
                 anchor: Anchor::BottomRight,
                 offset: [-150.0, 10.0],
@@ -159,7 +159,7 @@ fn create_game_hud() -> HudAsset {
                 id: "CoinIcon".to_string(),
                 element_type: HudElementType::Image {
                     texture: "coin.png".to_string(),
-                    tint: [1.0, 0.9, 0.3, 1.0],
+                    tint: ui::Color::rgba(1.0, 0.9, 0.3, 1.0),
                 },
                 anchor: Anchor::TopRight,
                 offset: [-150.0, 50.0],
@@ -173,7 +173,7 @@ fn create_game_hud() -> HudAsset {
                 element_type: HudElementType::DynamicText {
                     format: "{coins}".to_string(),
                     font_size: 18.0,
-                    color: [1.0, 1.0, 1.0, 1.0],
+                    color: ui::Color::rgba(1.0, 1.0, 1.0, 1.0),
                 },
                 anchor: Anchor::TopRight,
                 offset: [-110.0, 50.0],
@@ -197,7 +197,7 @@ fn create_container_hud() -> HudAsset {
                             element_type: HudElementType::Text {
                                 text: "Inventory".to_string(),
                                 font_size: 18.0,
-                                color: [1.0, 1.0, 1.0, 1.0],
+                                color: ui::Color::rgba(1.0, 1.0, 1.0, 1.0),
                             },
                             anchor: Anchor::TopCenter,
                             offset: [0.0, 10.0],
@@ -208,7 +208,7 @@ fn create_container_hud() -> HudAsset {
                             id: "ItemSlot1".to_string(),
                             element_type: HudElementType::Image {
                                 texture: "slot_empty.png".to_string(),
-                                tint: [1.0, 1.0, 1.0, 1.0],
+                                tint: ui::Color::rgba(1.0, 1.0, 1.0, 1.0),
                             },
                             anchor: Anchor::TopLeft,
                             offset: [10.0, 45.0],
@@ -219,7 +219,7 @@ fn create_container_hud() -> HudAsset {
                             id: "ItemSlot2".to_string(),
                             element_type: HudElementType::Image {
                                 texture: "slot_empty.png".to_string(),
-                                tint: [1.0, 1.0, 1.0, 1.0],
+                                tint: ui::Color::rgba(1.0, 1.0, 1.0, 1.0),
                             },
                             anchor: Anchor::TopLeft,
                             offset: [70.0, 45.0],
@@ -230,7 +230,7 @@ fn create_container_hud() -> HudAsset {
                             id: "ItemSlot3".to_string(),
                             element_type: HudElementType::Image {
                                 texture: "slot_empty.png".to_string(),
-                                tint: [1.0, 1.0, 1.0, 1.0],
+                                tint: ui::Color::rgba(1.0, 1.0, 1.0, 1.0),
                             },
                             anchor: Anchor::TopLeft,
                             offset: [130.0, 45.0],
@@ -86,6 +86,7 @@ fn main() {
 fn create_button_prefab() -> UIPrefab {
     UIPrefab {
         name: "Button".to_string(),
+        canvas_scaler: None,
         root: UIPrefabElement {
             name: "ButtonBackground".to_string(),
             rect_transform: RectTransform::anchored(
@@ -105,6 +106,7 @@ fn create_button_prefab() -> UIPrefab {
             panel: None,
             slider: None,
             toggle: None,
+            toggle_group: None,
             dropdown: None,
             input_field: None,
             scroll_view: None,
@@ -112,6 +114,10 @@ fn create_button_prefab() -> UIPrefab {
             horizontal_layout: None,
             vertical_layout: None,
             grid_layout: None,
+            virtual_input: None,
+            tooltip: None,
+            safe_area: None,
+            canvas_group: None,
             children: vec![
                 UIPrefabElement {
                     name: "ButtonText".to_string(),
@@ -133,6 +139,7 @@ fn create_button_prefab() -> UIPrefab {
                     panel: None,
                     slider: None,
                     toggle: None,
+                    toggle_group: None,
                     dropdown: None,
                     input_field: None,
                     scroll_view: None,
@@ -140,6 +147,10 @@ fn create_button_prefab() -> UIPrefab {
                     horizontal_layout: None,
                     vertical_layout: None,
                     grid_layout: None,
+                    virtual_input: None,
+                    tooltip: None,
+                    safe_area: None,
+                    canvas_group: None,
                     children: vec![],
                 },
             ],
@@ -151,6 +162,7 @@ fn create_button_prefab() -> UIPrefab {
 fn create_dialog_prefab() -> UIPrefab {
     UIPrefab {
         name: "Dialog".to_string(),
+        canvas_scaler: None,
         root: UIPrefabElement {
             name: "DialogPanel".to_string(),
             rect_transform: RectTransform::anchored(
@@ -169,6 +181,7 @@ fn create_dialog_prefab() -> UIPrefab {
             button: None,
             slider: None,
             toggle: None,
+            toggle_group: None,
             dropdown: None,
             input_field: None,
             scroll_view: None,
@@ -176,6 +189,10 @@ fn create_dialog_prefab() -> UIPrefab {
             horizontal_layout: None,
             vertical_layout: None,
             grid_layout: None,
+            virtual_input: None,
+            tooltip: None,
+            safe_area: None,
+            canvas_group: None,
             children: vec![
                 UIPrefabElement {
                     name: "DialogTitle".to_string(),
@@ -194,6 +211,7 @@ fn create_dialog_prefab() -> UIPrefab {
                     panel: None,
                     slider: None,
                     toggle: None,
+                    toggle_group: None,
                     dropdown: None,
                     input_field: None,
                     scroll_view: None,
@@ -201,6 +219,10 @@ fn create_dialog_prefab() -> UIPrefab {
                     horizontal_layout: None,
                     vertical_layout: None,
                     grid_layout: None,
+                    virtual_input: None,
+                    tooltip: None,
+                    safe_area: None,
+                    canvas_group: None,
                     children: vec![],
                 },
                 UIPrefabElement {
@@ -220,6 +242,7 @@ fn create_dialog_prefab() -> UIPrefab {
                     panel: None,
                     slider: None,
                     toggle: None,
+                    toggle_group: None,
                     dropdown: None,
                     input_field: None,
                     scroll_view: None,
@@ -227,6 +250,10 @@ fn create_dialog_prefab() -> UIPrefab {
                     horizontal_layout: None,
                     vertical_layout: None,
                     grid_layout: None,
+                    virtual_input: None,
+                    tooltip: None,
+                    safe_area: None,
+                    canvas_group: None,
                     children: vec![],
                 },
                 UIPrefabElement {
@@ -248,6 +275,7 @@ fn create_dialog_prefab() -> UIPrefab {
                     panel: None,
                     slider: None,
                     toggle: None,
+                    toggle_group: None,
                     dropdown: None,
                     input_field: None,
                     scroll_view: None,
@@ -255,6 +283,10 @@ fn create_dialog_prefab() -> UIPrefab {
                     horizontal_layout: None,
                     vertical_layout: None,
                     grid_layout: None,
+                    virtual_input: None,
+                    tooltip: None,
+                    safe_area: None,
+                    canvas_group: None,
                     children: vec![
                         UIPrefabElement {
                             name: "OkButtonText".to_string(),
@@ -276,6 +308,7 @@ fn create_dialog_prefab() -> UIPrefab {
                             panel: None,
                             slider: None,
                             toggle: None,
+                            toggle_group: None,
                             dropdown: None,
                             input_field: None,
                             scroll_view: None,
@@ -283,6 +316,10 @@ fn create_dialog_prefab() -> UIPrefab {
                             horizontal_layout: None,
                             vertical_layout: None,
                             grid_layout: None,
+                            virtual_input: None,
+                            tooltip: None,
+                            safe_area: None,
+                            canvas_group: None,
                             children: vec![],
                         },
                     ],
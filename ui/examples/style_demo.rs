@@ -9,7 +9,7 @@
 use ui::{
     StyleSystem, UIStyle, UITheme, StyledElement, StyleTransition,
     UIElement, UIButton, UIText, UIPanel,
-    HorizontalLayoutGroup,
+    HorizontalLayoutGroup, Color,
 };
 use glam::Vec4;
 
@@ -23,11 +23,11 @@ fn main() {
     // Create a custom style
     let mut dark_style = UIStyle::default();
     dark_style.name = "dark".to_string();
-    dark_style.primary_color = [0.2, 0.2, 0.3, 1.0];
-    dark_style.secondary_color = [0.3, 0.3, 0.4, 1.0];
-    dark_style.background_color = [0.1, 0.1, 0.15, 1.0];
-    dark_style.text_color = [0.9, 0.9, 0.9, 1.0];
-    dark_style.disabled_color = [0.4, 0.4, 0.4, 0.5];
+    dark_style.primary_color = Color::rgba(0.2, 0.2, 0.3, 1.0);
+    dark_style.secondary_color = Color::rgba(0.3, 0.3, 0.4, 1.0);
+    dark_style.background_color = Color::rgba(0.1, 0.1, 0.15, 1.0);
+    dark_style.text_color = Color::rgba(0.9, 0.9, 0.9, 1.0);
+    dark_style.disabled_color = Color::rgba(0.4, 0.4, 0.4, 0.5);
     dark_style.default_font_size = 16.0;
     dark_style.default_spacing = 8.0;
     dark_style.default_padding = Vec4::new(12.0, 12.0, 12.0, 12.0);
@@ -44,11 +44,11 @@ fn main() {
     // Create a light style
     let mut light_style = UIStyle::default();
     light_style.name = "light".to_string();
-    light_style.primary_color = [0.9, 0.9, 1.0, 1.0];
-    light_style.secondary_color = [0.7, 0.7, 0.8, 1.0];
-    light_style.background_color = [1.0, 1.0, 1.0, 1.0];
-    light_style.text_color = [0.1, 0.1, 0.1, 1.0];
-    light_style.disabled_color = [0.6, 0.6, 0.6, 0.5];
+    light_style.primary_color = Color::rgba(0.9, 0.9, 1.0, 1.0);
+    light_style.secondary_color = Color::rgba(0.7, 0.7, 0.8, 1.0);
+    light_style.background_color = Color::rgba(1.0, 1.0, 1.0, 1.0);
+    light_style.text_color = Color::rgba(0.1, 0.1, 0.1, 1.0);
+    light_style.disabled_color = Color::rgba(0.6, 0.6, 0.6, 0.5);
     
     style_system.theme_mut().add_style(light_style.clone());
     println!("Added light style to theme");
@@ -195,8 +195,8 @@ fn main() {
     // Demonstrate color interpolation
     println!("\n=== Color Interpolation ===");
     
-    let color_a = [1.0, 0.0, 0.0, 1.0]; // Red
-    let color_b = [0.0, 0.0, 1.0, 1.0]; // Blue
+    let color_a = Color::rgba(1.0, 0.0, 0.0, 1.0); // Red
+    let color_b = Color::rgba(0.0, 0.0, 1.0, 1.0); // Blue
     
     println!("Interpolating from red to blue:");
     for i in 0..=10 {
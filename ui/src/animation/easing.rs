@@ -55,6 +55,8 @@ pub fn evaluate(easing: &EasingFunction, t: f32) -> f32 {
         EasingFunction::EaseInBounce => ease_in_bounce(t),
         EasingFunction::EaseOutBounce => ease_out_bounce(t),
         EasingFunction::EaseInOutBounce => ease_in_out_bounce(t),
+
+        EasingFunction::Curve(curve) => curve.evaluate(t),
     }
 }
 
@@ -346,4 +348,64 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_curve_presets_follow_named_easings() {
+        for easing in EasingFunction::ALL {
+            let curve = EasingFunction::Curve(easing.to_curve());
+            for i in 0..=64 {
+                let t = i as f32 / 64.0;
+                let error = (curve.evaluate(t) - easing.evaluate(t)).abs();
+                assert!(error < 0.05, "{:?} preset is off by {} at t={}", easing, error, t);
+            }
+        }
+        let curve = EasingFunction::EaseOutBack.to_curve();
+        assert_eq!(EasingFunction::Curve(curve.clone()).to_curve(), curve);
+    }
+
+    mod curve_properties {
+        use super::*;
+        use crate::animation::{AnimationCurve, CurveKey};
+        use proptest::prelude::*;
+
+        fn keys() -> impl Strategy<Value = Vec<CurveKey>> {
+            prop::collection::vec((0.0f32..=1.0, -10.0f32..10.0, -50.0f32..50.0, -50.0f32..50.0), 1..12)
+                .prop_map(|keys| keys.into_iter().map(|(t, v, i, o)| CurveKey::with_tangents(t, v, i, o)).collect())
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(200))]
+
+            // Whatever the tangents, a curve passes exactly through every key
+            #[test]
+            fn evaluate_at_key_times_returns_key_values(keys in keys()) {
+                let curve = AnimationCurve::new(keys);
+                for key in curve.keys() {
+                    prop_assert_eq!(curve.evaluate(key.time), key.value);
+                    prop_assert_eq!(EasingFunction::Curve(curve.clone()).evaluate(key.time), key.value);
+                }
+            }
+
+            #[test]
+            fn evaluate_clamps_outside_unit_range(keys in keys(), beyond in 0.0f32..100.0) {
+                let curve = AnimationCurve::new(keys);
+                prop_assert_eq!(curve.evaluate(-beyond), curve.evaluate(0.0));
+                prop_assert_eq!(curve.evaluate(1.0 + beyond), curve.evaluate(1.0));
+            }
+
+            #[test]
+            fn keys_are_sorted_by_time(keys in keys()) {
+                let curve = AnimationCurve::new(keys);
+                prop_assert!(curve.keys().windows(2).all(|pair| pair[0].time < pair[1].time));
+            }
+        }
+    }
+
+    #[test]
+    fn test_easing_from_name() {
+        assert_eq!(EasingFunction::from_name("ease_out_quad"), Some(EasingFunction::EaseOutQuad));
+        assert_eq!(EasingFunction::from_name("EaseInOutBounce"), Some(EasingFunction::EaseInOutBounce));
+        assert_eq!(EasingFunction::from_name("linear"), Some(EasingFunction::Linear));
+        assert_eq!(EasingFunction::from_name("not_an_easing"), None);
+    }
 }
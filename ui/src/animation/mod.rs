@@ -4,6 +4,7 @@ pub mod easing;
 pub mod tween;
 
 pub use tween::{AnimationManager};
+pub use engine_core::animation_curve::{AnimationCurve, BakedCurve, CurveKey};
 
 use serde::{Deserialize, Serialize};
 use glam::Vec2;
@@ -54,6 +55,8 @@ pub enum AnimatedProperty {
     Color,
     Alpha,
     SizeDelta,
+    /// UICanvasGroup alpha (fades the element and everything below it)
+    GroupAlpha,
 }
 
 /// Animation value (union type for different value types)
@@ -78,6 +81,83 @@ pub enum EasingFunction {
     EaseInElastic, EaseOutElastic, EaseInOutElastic,
     EaseInBack, EaseOutBack, EaseInOutBack,
     EaseInBounce, EaseOutBounce, EaseInOutBounce,
+    /// Hand-authored curve (tweens, UI animations, camera zoom)
+    Curve(AnimationCurve),
+}
+
+impl EasingFunction {
+    /// All named easing functions, in declaration order
+    pub const ALL: [EasingFunction; 31] = [
+        EasingFunction::Linear,
+        EasingFunction::EaseInQuad, EasingFunction::EaseOutQuad, EasingFunction::EaseInOutQuad,
+        EasingFunction::EaseInCubic, EasingFunction::EaseOutCubic, EasingFunction::EaseInOutCubic,
+        EasingFunction::EaseInQuart, EasingFunction::EaseOutQuart, EasingFunction::EaseInOutQuart,
+        EasingFunction::EaseInQuint, EasingFunction::EaseOutQuint, EasingFunction::EaseInOutQuint,
+        EasingFunction::EaseInSine, EasingFunction::EaseOutSine, EasingFunction::EaseInOutSine,
+        EasingFunction::EaseInExpo, EasingFunction::EaseOutExpo, EasingFunction::EaseInOutExpo,
+        EasingFunction::EaseInCirc, EasingFunction::EaseOutCirc, EasingFunction::EaseInOutCirc,
+        EasingFunction::EaseInElastic, EasingFunction::EaseOutElastic, EasingFunction::EaseInOutElastic,
+        EasingFunction::EaseInBack, EasingFunction::EaseOutBack, EasingFunction::EaseInOutBack,
+        EasingFunction::EaseInBounce, EasingFunction::EaseOutBounce, EasingFunction::EaseInOutBounce,
+    ];
+
+    /// Parse an easing name. Accepts both "EaseOutQuad" and "ease_out_quad" spellings.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalized: String = name
+            .chars()
+            .filter(|c| *c != '_' && *c != '-' && *c != ' ')
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+
+        Self::ALL
+            .iter()
+            .find(|easing| format!("{:?}", easing).to_ascii_lowercase() == normalized)
+            .cloned()
+    }
+
+    /// Evaluate this easing function at normalized time t (0.0 to 1.0)
+    pub fn evaluate(&self, t: f32) -> f32 {
+        easing::evaluate(self, t)
+    }
+
+    /// An editable curve with this easing's shape (curve editor presets). Starts from
+    /// the two end keys and splits segments that stray from the easing, so smooth easings
+    /// get few keys and bounces get them near their corners.
+    pub fn to_curve(&self) -> AnimationCurve {
+        if let EasingFunction::Curve(curve) = self {
+            return curve.clone();
+        }
+        const TOLERANCE: f32 = 0.002;
+        const MIN_SPACING: f32 = 1.0 / 128.0;
+        const H: f32 = 1e-4;
+
+        // One-sided slopes, so corners stay corners
+        let key_at = |t: f32| {
+            let value = self.evaluate(t);
+            let in_tangent = if t <= 0.0 { (self.evaluate(H) - value) / H } else { (value - self.evaluate(t - H)) / H };
+            let out_tangent = if t >= 1.0 { in_tangent } else { (self.evaluate(t + H) - value) / H };
+            CurveKey::with_tangents(t, value, in_tangent, out_tangent)
+        };
+        let mut curve = AnimationCurve::new(vec![key_at(0.0), key_at(1.0)]);
+        loop {
+            let splits: Vec<f32> = curve.keys().windows(2)
+                .filter(|pair| pair[1].time - pair[0].time > MIN_SPACING)
+                .filter(|pair| {
+                    (1..4).any(|i| {
+                        let t = pair[0].time + (pair[1].time - pair[0].time) * i as f32 / 4.0;
+                        (curve.evaluate(t) - self.evaluate(t)).abs() > TOLERANCE
+                    })
+                })
+                .map(|pair| (pair[0].time + pair[1].time) / 2.0)
+                .collect();
+            if splits.is_empty() {
+                return curve;
+            }
+            for t in splits {
+                curve.add_key(key_at(t));
+            }
+        }
+    }
 }
 
 /// Loop mode for animations
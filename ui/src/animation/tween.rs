@@ -2,7 +2,7 @@
 
 use glam::Vec2;
 use crate::{Color, RectTransform};
-use crate::components::UIElement;
+use crate::components::{UIElement, UICanvasGroup};
 use super::{UIAnimation, AnimatedProperty, AnimationValue, LoopMode, easing};
 
 impl UIAnimation {
@@ -168,6 +168,16 @@ impl UIAnimation {
             }
         }
     }
+
+    /// Apply the animation to a UICanvasGroup component
+    pub fn apply_to_canvas_group(&self, group: &mut UICanvasGroup) {
+        if self.property != AnimatedProperty::GroupAlpha {
+            return;
+        }
+        if let AnimationValue::Float(alpha) = self.get_current_value() {
+            group.alpha = alpha.clamp(0.0, 1.0);
+        }
+    }
 }
 
 /// Linear interpolation for Vec2
@@ -182,12 +192,7 @@ fn lerp_f32(from: f32, to: f32, t: f32) -> f32 {
 
 /// Linear interpolation for Color (RGBA)
 fn lerp_color(from: Color, to: Color, t: f32) -> Color {
-    [
-        lerp_f32(from[0], to[0], t),
-        lerp_f32(from[1], to[1], t),
-        lerp_f32(from[2], to[2], t),
-        lerp_f32(from[3], to[3], t),
-    ]
+    from.lerp(to, t)
 }
 
 /// Animation manager that handles multiple animations
@@ -422,8 +427,8 @@ mod tests {
         let mut anim = UIAnimation::new(
             1,
             AnimatedProperty::Color,
-            AnimationValue::Color([0.0, 0.0, 0.0, 1.0]),
-            AnimationValue::Color([1.0, 1.0, 1.0, 1.0]),
+            AnimationValue::Color(Color::rgba(0.0, 0.0, 0.0, 1.0)),
+            AnimationValue::Color(Color::rgba(1.0, 1.0, 1.0, 1.0)),
             1.0,
         );
         anim.easing = EasingFunction::Linear;
@@ -475,6 +480,33 @@ mod tests {
         assert!((ui_element.alpha - 0.5).abs() < 0.01);
     }
 
+    #[test]
+    fn test_apply_to_canvas_group() {
+        let mut anim = UIAnimation::new(
+            1,
+            AnimatedProperty::GroupAlpha,
+            AnimationValue::Float(1.0),
+            AnimationValue::Float(0.0),
+            1.0,
+        );
+        let mut group = UICanvasGroup::default();
+
+        anim.update(0.25);
+        anim.apply_to_canvas_group(&mut group);
+        assert!((group.alpha - 0.75).abs() < 0.01);
+
+        // Completed animations hold their end value
+        assert!(!anim.update(1.0));
+        anim.apply_to_canvas_group(&mut group);
+        assert_eq!(group.alpha, 0.0);
+
+        // Other properties leave the group alone
+        anim.property = AnimatedProperty::Alpha;
+        group.alpha = 0.5;
+        anim.apply_to_canvas_group(&mut group);
+        assert_eq!(group.alpha, 0.5);
+    }
+
     #[test]
     fn test_animation_manager() {
         let mut manager = AnimationManager::new();
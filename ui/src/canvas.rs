@@ -60,6 +60,9 @@ pub enum CanvasRenderMode {
     WorldSpace,
 }
 
+/// DPI of a display with an OS scale factor of 1.0
+pub const BASE_DPI: f32 = 96.0;
+
 /// Canvas scaler handles UI scaling across different screen resolutions
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CanvasScaler {
@@ -207,6 +210,13 @@ impl CanvasScaler {
         screen_height: f32,
         screen_dpi: f32,
     ) -> f32 {
+        // Cache the calculated scale factor
+        self.scale_factor = self.scale_factor_for(screen_width, screen_height, screen_dpi);
+        self.scale_factor
+    }
+
+    /// Scale factor (screen pixels per reference unit) for a screen, without caching it
+    pub fn scale_factor_for(&self, screen_width: f32, screen_height: f32, screen_dpi: f32) -> f32 {
         let scale = match self.mode {
             ScaleMode::ConstantPixelSize => {
                 // Maintain constant pixel size - no scaling
@@ -238,12 +248,20 @@ impl CanvasScaler {
         };
 
         // Clamp the scale factor to min/max bounds
-        let clamped_scale = scale.clamp(self.min_scale, self.max_scale);
-        
-        // Cache the calculated scale factor
-        self.scale_factor = clamped_scale;
-        
-        clamped_scale
+        scale.clamp(self.min_scale, self.max_scale)
+    }
+
+    /// Scale factor for a window measured in logical points (as egui and winit report it).
+    /// `window_scale_factor` is the OS scale factor (physical pixels per point); the screen
+    /// DPI is derived from it. Returns points per reference unit.
+    pub fn points_per_unit(&self, logical_width: f32, logical_height: f32, window_scale_factor: f32) -> f32 {
+        let window_scale_factor = window_scale_factor.max(0.01);
+        let scale = self.scale_factor_for(
+            logical_width * window_scale_factor,
+            logical_height * window_scale_factor,
+            BASE_DPI * window_scale_factor,
+        );
+        scale / window_scale_factor
     }
 
     /// Get the current cached scale factor
@@ -0,0 +1,115 @@
+//! Canvas group system
+//!
+//! Resolves the combined UICanvasGroup state (alpha, interactable, blocks_raycasts) of
+//! every element once per frame, so rendering and raycasting look it up instead of
+//! walking up the hierarchy for each element.
+
+use crate::{CanvasGroupState, UICanvasGroup};
+use std::collections::HashMap;
+
+/// Entity type alias
+pub type Entity = u64;
+
+/// Per-frame cache of resolved canvas group states
+#[derive(Default)]
+pub struct CanvasGroupSystem {
+    resolved: HashMap<Entity, CanvasGroupState>,
+}
+
+impl CanvasGroupSystem {
+    /// Create a new canvas group system
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `entities` from this frame's groups and hierarchy (`parents`: child to
+    /// parent). Each ancestor is resolved once and shared by its descendants.
+    pub fn update(
+        &mut self,
+        groups: &HashMap<Entity, UICanvasGroup>,
+        parents: &HashMap<Entity, Entity>,
+        entities: impl IntoIterator<Item = Entity>,
+    ) {
+        self.resolved.clear();
+        for entity in entities {
+            self.resolve(entity, groups, parents);
+        }
+    }
+
+    fn resolve(
+        &mut self,
+        entity: Entity,
+        groups: &HashMap<Entity, UICanvasGroup>,
+        parents: &HashMap<Entity, Entity>,
+    ) -> CanvasGroupState {
+        if let Some(state) = self.resolved.get(&entity) {
+            return *state;
+        }
+        // Collect the unresolved ancestors, then fold back down from the first known one
+        let mut chain = vec![entity];
+        let mut state = CanvasGroupState::default();
+        while let Some(parent) = parents.get(chain.last().unwrap()) {
+            if let Some(known) = self.resolved.get(parent) {
+                state = *known;
+                break;
+            }
+            if chain.contains(parent) {
+                break;
+            }
+            chain.push(*parent);
+        }
+        for entity in chain.into_iter().rev() {
+            state = state.with(groups.get(&entity));
+            self.resolved.insert(entity, state);
+        }
+        state
+    }
+
+    /// Resolved state of `entity`; elements outside any group get the default
+    pub fn state(&self, entity: Entity) -> CanvasGroupState {
+        self.resolved.get(&entity).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_groups_cascade_down_the_hierarchy() {
+        // 1 (alpha 0.5) > 2 (not interactable) > 3, and 4 ignoring its parent 1
+        let groups = HashMap::from([
+            (1, UICanvasGroup { alpha: 0.5, ..Default::default() }),
+            (2, UICanvasGroup { alpha: 0.5, interactable: false, ..Default::default() }),
+            (4, UICanvasGroup { blocks_raycasts: false, ignore_parent_groups: true, ..Default::default() }),
+        ]);
+        let parents = HashMap::from([(2, 1), (3, 2), (4, 1), (5, 4)]);
+        let mut system = CanvasGroupSystem::new();
+        system.update(&groups, &parents, [3, 5, 6]);
+
+        let state = system.state(3);
+        assert_eq!(state.alpha, 0.25);
+        assert!(!state.interactable);
+        assert!(state.blocks_raycasts);
+        assert_eq!(system.state(1).alpha, 0.5);
+
+        let state = system.state(5);
+        assert_eq!(state.alpha, 1.0);
+        assert!(state.interactable);
+        assert!(!state.blocks_raycasts);
+
+        assert_eq!(system.state(6), CanvasGroupState::default());
+    }
+
+    #[test]
+    fn test_alpha_is_clamped_and_zero_hides() {
+        let groups = HashMap::from([(1, UICanvasGroup { alpha: 2.0, ..Default::default() })]);
+        let mut system = CanvasGroupSystem::new();
+        system.update(&groups, &HashMap::new(), [1]);
+        assert_eq!(system.state(1).alpha, 1.0);
+
+        let groups = HashMap::from([(1, UICanvasGroup { alpha: 0.0, ..Default::default() })]);
+        system.update(&groups, &HashMap::from([(2, 1)]), [2]);
+        assert!(!system.state(2).visible());
+    }
+}
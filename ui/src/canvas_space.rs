@@ -0,0 +1,143 @@
+//! Conversions between screen, world and canvas coordinates
+//!
+//! Canvas coordinates are in the canvas's reference units (what RectTransform
+//! positions are authored in) with the origin at the bottom-left corner and y up.
+//! Screen coordinates are pixels with a top-left origin, like the mouse position.
+
+use crate::Rect;
+use ecs::{Camera, Transform};
+use glam::{Vec2, Vec3};
+
+/// A position projected onto a canvas
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CanvasPoint {
+    /// Canvas position; clamped to the canvas edge when `on_screen` is false
+    pub position: Vec2,
+
+    /// The point is in front of the camera and inside the canvas
+    pub on_screen: bool,
+}
+
+/// Where a canvas is drawn on screen and how many pixels one reference unit covers
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CanvasSpace {
+    /// Screen area `[x, y, width, height]` (top-left origin) the canvas covers
+    pub screen: [f32; 4],
+
+    /// Pixels per reference unit (the canvas scaler's scale factor)
+    pub scale: f32,
+}
+
+impl CanvasSpace {
+    pub fn new(screen: [f32; 4], scale: f32) -> Self {
+        Self { screen, scale: scale.max(f32::EPSILON) }
+    }
+
+    /// Canvas size in reference units
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.screen[2], self.screen[3]) / self.scale
+    }
+
+    pub fn screen_to_canvas(&self, screen_pos: Vec2) -> Vec2 {
+        let [x, y, _, height] = self.screen;
+        Vec2::new(screen_pos.x - x, y + height - screen_pos.y) / self.scale
+    }
+
+    pub fn canvas_to_screen(&self, canvas_pos: Vec2) -> Vec2 {
+        let [x, y, _, height] = self.screen;
+        let pixels = canvas_pos * self.scale;
+        Vec2::new(x + pixels.x, y + height - pixels.y)
+    }
+
+    /// Canvas position of a world point seen through `camera`. Points off screen or
+    /// behind the camera are pushed to the canvas edge along the line from its center,
+    /// so an indicator placed there points towards them. None when the camera draws
+    /// nothing.
+    pub fn world_to_canvas(&self, camera: &Camera, camera_transform: &Transform, world_pos: Vec3) -> Option<CanvasPoint> {
+        let (screen_pos, in_front) = ecs::picking::world_to_screen(camera, camera_transform, self.screen, world_pos)?;
+        let position = self.screen_to_canvas(screen_pos);
+        let size = self.size();
+        let inside = position.cmpge(Vec2::ZERO).all() && position.cmple(size).all();
+        if in_front && inside {
+            return Some(CanvasPoint { position, on_screen: true });
+        }
+        Some(CanvasPoint { position: clamp_to_edge(position, size), on_screen: false })
+    }
+
+    /// World position on the plane z = `plane_z` under a canvas point, or None outside
+    /// the camera's viewport
+    pub fn canvas_to_world(&self, camera: &Camera, camera_transform: &Transform, canvas_pos: Vec2, plane_z: f32) -> Option<Vec3> {
+        let screen_pos = self.canvas_to_screen(canvas_pos);
+        ecs::picking::screen_to_plane(camera, camera_transform, self.screen, screen_pos, plane_z)
+    }
+
+    /// A normalized safe area `[x, y, width, height]` (bottom-left origin) in canvas units
+    pub fn safe_area(&self, normalized: [f32; 4]) -> Rect {
+        let size = self.size();
+        let [x, y, width, height] = normalized;
+        Rect::new(x * size.x, y * size.y, width * size.x, height * size.y)
+    }
+}
+
+/// Move `position` onto the border of the `size` rect along the ray from its center
+fn clamp_to_edge(position: Vec2, size: Vec2) -> Vec2 {
+    let center = size * 0.5;
+    let offset = position - center;
+    if offset.x.abs() < f32::EPSILON && offset.y.abs() < f32::EPSILON {
+        // Straight behind the camera: no direction to point in, use the bottom edge
+        return Vec2::new(center.x, 0.0);
+    }
+    let fit_x = if offset.x.abs() > f32::EPSILON { center.x / offset.x.abs() } else { f32::INFINITY };
+    let fit_y = if offset.y.abs() > f32::EPSILON { center.y / offset.y.abs() } else { f32::INFINITY };
+    center + offset * fit_x.min(fit_y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn camera() -> (Camera, Transform) {
+        (Camera { orthographic_size: 5.0, ..Default::default() }, Transform::with_position(0.0, 0.0, 10.0))
+    }
+
+    #[test]
+    fn test_screen_canvas_round_trip() {
+        let space = CanvasSpace::new([100.0, 50.0, 800.0, 600.0], 2.0);
+        assert_eq!(space.size(), Vec2::new(400.0, 300.0));
+        assert_eq!(space.screen_to_canvas(Vec2::new(100.0, 650.0)), Vec2::ZERO);
+        assert_eq!(space.screen_to_canvas(Vec2::new(900.0, 50.0)), Vec2::new(400.0, 300.0));
+        let point = Vec2::new(123.0, 45.0);
+        assert!((space.screen_to_canvas(space.canvas_to_screen(point)) - point).length() < 1e-4);
+    }
+
+    #[test]
+    fn test_world_to_canvas_clamps_off_screen_points() {
+        let (camera, transform) = camera();
+        let space = CanvasSpace::new([0.0, 0.0, 800.0, 600.0], 2.0);
+
+        let center = space.world_to_canvas(&camera, &transform, Vec3::ZERO).unwrap();
+        assert!(center.on_screen);
+        assert!((center.position - Vec2::new(200.0, 150.0)).length() < 1e-3);
+
+        // Far to the right and a little up: right edge, same side vertically
+        let off = space.world_to_canvas(&camera, &transform, Vec3::new(100.0, 1.0, 0.0)).unwrap();
+        assert!(!off.on_screen);
+        assert!((off.position.x - 400.0).abs() < 1e-3);
+        assert!(off.position.y > 150.0 && off.position.y < 300.0);
+
+        let world = space.canvas_to_world(&camera, &transform, Vec2::new(300.0, 150.0), 0.0).unwrap();
+        let back = space.world_to_canvas(&camera, &transform, world).unwrap();
+        assert!((back.position - Vec2::new(300.0, 150.0)).length() < 1e-3);
+    }
+
+    #[test]
+    fn test_points_behind_perspective_camera_are_off_screen() {
+        let camera = Camera { projection: ecs::CameraProjection::Perspective, ..Default::default() };
+        let transform = Transform::with_position(0.0, 0.0, 10.0);
+        let space = CanvasSpace::new([0.0, 0.0, 800.0, 600.0], 1.0);
+
+        let behind = space.world_to_canvas(&camera, &transform, Vec3::new(-1.0, 0.0, 20.0)).unwrap();
+        assert!(!behind.on_screen);
+        assert!((behind.position.x - 0.0).abs() < 1e-3);
+    }
+}
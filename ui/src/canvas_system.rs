@@ -3,7 +3,9 @@
 //! This module provides the CanvasSystem which manages Canvas entities,
 //! handles screen resolution changes, and updates scale factors.
 
-use crate::{Canvas, CanvasScaler};
+use crate::{Canvas, CanvasPoint, CanvasSpace, BASE_DPI};
+use ecs::{Camera, Transform};
+use glam::{Vec2, Vec3};
 use std::collections::HashMap;
 
 /// Entity ID type (using u64 as a simple entity identifier)
@@ -191,6 +193,13 @@ impl CanvasSystem {
         true
     }
 
+    /// Update screen DPI from the window's scale factor (winit's `scale_factor()`)
+    ///
+    /// Returns true if the DPI changed, false otherwise
+    pub fn update_window_scale_factor(&mut self, scale_factor: f64) -> bool {
+        self.update_screen_dpi(BASE_DPI * scale_factor as f32)
+    }
+
     /// Get current screen width
     pub fn screen_width(&self) -> u32 {
         self.screen_width
@@ -252,6 +261,34 @@ impl CanvasSystem {
             canvas.mark_dirty();
         }
     }
+
+    /// Screen placement and scale of a canvas (it covers the whole screen)
+    pub fn canvas_space(&self, canvas: Entity) -> Option<CanvasSpace> {
+        let canvas = self.canvases.get(&canvas)?;
+        let screen = [0.0, 0.0, self.screen_width as f32, self.screen_height as f32];
+        Some(CanvasSpace::new(screen, canvas.scaler.scale_factor))
+    }
+
+    /// Screen pixel position (top-left origin) to the canvas's reference units
+    /// (bottom-left origin)
+    pub fn screen_to_canvas(&self, canvas: Entity, screen_pos: Vec2) -> Option<Vec2> {
+        Some(self.canvas_space(canvas)?.screen_to_canvas(screen_pos))
+    }
+
+    pub fn canvas_to_screen(&self, canvas: Entity, canvas_pos: Vec2) -> Option<Vec2> {
+        Some(self.canvas_space(canvas)?.canvas_to_screen(canvas_pos))
+    }
+
+    /// Canvas position of a world point seen through `camera` (e.g. a health bar over
+    /// an enemy); see [`CanvasSpace::world_to_canvas`] for off-screen points
+    pub fn world_to_canvas(&self, camera: &Camera, camera_transform: &Transform, canvas: Entity, world_pos: Vec3) -> Option<CanvasPoint> {
+        self.canvas_space(canvas)?.world_to_canvas(camera, camera_transform, world_pos)
+    }
+
+    /// World position on the plane z = `plane_z` under a canvas point
+    pub fn canvas_to_world(&self, camera: &Camera, camera_transform: &Transform, canvas: Entity, canvas_pos: Vec2, plane_z: f32) -> Option<Vec3> {
+        self.canvas_space(canvas)?.canvas_to_world(camera, camera_transform, canvas_pos, plane_z)
+    }
 }
 
 impl Default for CanvasSystem {
@@ -263,7 +300,7 @@ impl Default for CanvasSystem {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::CanvasRenderMode;
+    use crate::{CanvasRenderMode, CanvasScaler};
 
     #[test]
     fn test_canvas_system_creation() {
@@ -495,4 +532,23 @@ mod tests {
         let canvas = system.get_canvas(entity).unwrap();
         assert!(canvas.scaler.get_scale_factor() <= 2.0);
     }
+
+    #[test]
+    fn test_canvas_coordinates_use_reference_units() {
+        let mut system = CanvasSystem::with_screen_settings(1920, 1080, 96.0);
+        let mut canvas = Canvas::new();
+        canvas.scaler = CanvasScaler::scale_with_screen_size(960.0, 540.0);
+        let entity = system.create_canvas_with_config(canvas);
+
+        // 2x scale: the top-right pixel is the reference resolution's corner
+        assert_eq!(system.screen_to_canvas(entity, Vec2::new(1920.0, 0.0)), Some(Vec2::new(960.0, 540.0)));
+        assert_eq!(system.canvas_to_screen(entity, Vec2::new(480.0, 0.0)), Some(Vec2::new(960.0, 1080.0)));
+
+        let camera = Camera { orthographic_size: 5.0, ..Default::default() };
+        let transform = Transform::with_position(0.0, 0.0, 10.0);
+        let center = system.world_to_canvas(&camera, &transform, entity, Vec3::ZERO).unwrap();
+        assert!(center.on_screen);
+        assert!((center.position - Vec2::new(480.0, 270.0)).length() < 1e-3);
+        assert!(system.world_to_canvas(&camera, &transform, 999, Vec3::ZERO).is_none());
+    }
 }
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 use crate::Color;
+use crate::events::UIEventBinding;
+use super::StateTransition;
 
 /// Button component with state management and transitions
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -18,6 +20,10 @@ pub struct UIButton {
     pub highlighted_color: Color,
     pub pressed_color: Color,
     pub disabled_color: Color,
+
+    /// Tint while it has keyboard/gamepad focus
+    #[serde(default = "default_selected_color")]
+    pub selected_color: Color,
     
     /// Color fade duration
     pub fade_duration: f32,
@@ -26,6 +32,8 @@ pub struct UIButton {
     pub highlighted_sprite: Option<String>,
     pub pressed_sprite: Option<String>,
     pub disabled_sprite: Option<String>,
+    #[serde(default)]
+    pub selected_sprite: Option<String>,
     
     /// Animation trigger (for Animation transition)
     pub normal_trigger: String,
@@ -33,8 +41,8 @@ pub struct UIButton {
     pub pressed_trigger: String,
     pub disabled_trigger: String,
     
-    /// Lua callback function name
-    pub on_click: Option<String>,
+    /// What a click does (a Lua function name or an engine action)
+    pub on_click: Option<UIEventBinding>,
 }
 
 impl Default for UIButton {
@@ -42,14 +50,16 @@ impl Default for UIButton {
         Self {
             state: ButtonState::Normal,
             transition: ButtonTransition::ColorTint,
-            normal_color: [1.0, 1.0, 1.0, 1.0],
-            highlighted_color: [0.9, 0.9, 0.9, 1.0],
-            pressed_color: [0.7, 0.7, 0.7, 1.0],
-            disabled_color: [0.5, 0.5, 0.5, 0.5],
+            normal_color: Color::rgba(1.0, 1.0, 1.0, 1.0),
+            highlighted_color: Color::rgba(0.9, 0.9, 0.9, 1.0),
+            pressed_color: Color::rgba(0.7, 0.7, 0.7, 1.0),
+            disabled_color: Color::rgba(0.5, 0.5, 0.5, 0.5),
+            selected_color: default_selected_color(),
             fade_duration: 0.1,
             highlighted_sprite: None,
             pressed_sprite: None,
             disabled_sprite: None,
+            selected_sprite: None,
             normal_trigger: String::new(),
             highlighted_trigger: String::new(),
             pressed_trigger: String::new(),
@@ -59,6 +69,29 @@ impl Default for UIButton {
     }
 }
 
+fn default_selected_color() -> Color {
+    Color::WHITE
+}
+
+impl UIButton {
+    /// The button's colors and sprites as the transition shared with the other controls
+    pub fn state_transition(&self) -> StateTransition {
+        StateTransition {
+            transition: self.transition.clone(),
+            normal_color: self.normal_color,
+            hover_color: self.highlighted_color,
+            pressed_color: self.pressed_color,
+            selected_color: self.selected_color,
+            disabled_color: self.disabled_color,
+            hover_sprite: self.highlighted_sprite.clone(),
+            pressed_sprite: self.pressed_sprite.clone(),
+            selected_sprite: self.selected_sprite.clone(),
+            disabled_sprite: self.disabled_sprite.clone(),
+            fade_duration: self.fade_duration,
+        }
+    }
+}
+
 /// Button state
 #[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
 pub enum ButtonState {
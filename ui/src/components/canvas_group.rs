@@ -0,0 +1,61 @@
+//! UICanvasGroup component
+
+use serde::{Deserialize, Serialize};
+
+/// Fades and disables a whole subtree at once: its values multiply into every element
+/// below it (alpha) or are ANDed with them (interactable, blocks_raycasts)
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UICanvasGroup {
+    /// Multiplies the color alpha of the element and all its descendants (0-1)
+    pub alpha: f32,
+
+    /// False puts the controls below into their disabled state (no clicks, disabled colors)
+    pub interactable: bool,
+
+    /// False lets the pointer pass through the subtree to whatever is behind it
+    pub blocks_raycasts: bool,
+
+    /// Start over from this group instead of combining with the groups above
+    pub ignore_parent_groups: bool,
+}
+
+impl Default for UICanvasGroup {
+    fn default() -> Self {
+        Self { alpha: 1.0, interactable: true, blocks_raycasts: true, ignore_parent_groups: false }
+    }
+}
+
+/// The combined effect of an element's own group and all groups above it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CanvasGroupState {
+    pub alpha: f32,
+    pub interactable: bool,
+    pub blocks_raycasts: bool,
+}
+
+impl Default for CanvasGroupState {
+    fn default() -> Self {
+        Self { alpha: 1.0, interactable: true, blocks_raycasts: true }
+    }
+}
+
+impl CanvasGroupState {
+    /// The state of a child with `group` (if any) under this one
+    pub fn with(&self, group: Option<&UICanvasGroup>) -> Self {
+        let Some(group) = group else {
+            return *self;
+        };
+        let parent = if group.ignore_parent_groups { Self::default() } else { *self };
+        Self {
+            alpha: parent.alpha * group.alpha.clamp(0.0, 1.0),
+            interactable: parent.interactable && group.interactable,
+            blocks_raycasts: parent.blocks_raycasts && group.blocks_raycasts,
+        }
+    }
+
+    /// Not faded out completely
+    pub fn visible(&self) -> bool {
+        self.alpha > 0.0
+    }
+}
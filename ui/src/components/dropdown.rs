@@ -1,6 +1,7 @@
 //! UIDropdown component
 
 use serde::{Deserialize, Serialize};
+use super::StateTransition;
 
 /// Dropdown component for option selection
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -22,6 +23,10 @@ pub struct UIDropdown {
     
     /// Lua callback for value changed
     pub on_value_changed: Option<String>,
+
+    /// Look per interactable state (None: the theme's)
+    #[serde(default)]
+    pub transition: Option<StateTransition>,
 }
 
 impl Default for UIDropdown {
@@ -33,6 +38,7 @@ impl Default for UIDropdown {
             options: Vec::new(),
             value: 0,
             on_value_changed: None,
+            transition: None,
         }
     }
 }
@@ -11,6 +11,10 @@ pub struct UIInputField {
     
     /// Placeholder entity
     pub placeholder: Option<u64>,
+
+    /// Text shown while the field is empty (when there's no placeholder entity)
+    #[serde(default)]
+    pub placeholder_text: String,
     
     /// Current text
     pub text: String,
@@ -63,6 +67,7 @@ impl Default for UIInputField {
         Self {
             text_component: None,
             placeholder: None,
+            placeholder_text: String::new(),
             text: String::new(),
             character_limit: 0,
             content_type: ContentType::Standard,
@@ -72,7 +77,7 @@ impl Default for UIInputField {
             character_validation: CharacterValidation::None,
             caret_blink_rate: 0.85,
             caret_width: 1,
-            selection_color: [0.65, 0.8, 1.0, 0.75],
+            selection_color: Color::rgba(0.65, 0.8, 1.0, 0.75),
             read_only: false,
             on_value_changed: None,
             on_end_edit: None,
@@ -128,7 +133,7 @@ pub enum KeyboardType {
 }
 
 /// Character validation mode
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub enum CharacterValidation {
     None,
     Integer,
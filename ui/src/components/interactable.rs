@@ -0,0 +1,105 @@
+//! Interactable state shared by buttons, toggles, sliders and dropdowns
+
+use serde::{Deserialize, Serialize};
+use crate::Color;
+use super::ButtonTransition;
+
+/// What a control is doing, for picking its look
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum InteractableState {
+    #[default]
+    Normal,
+    /// The pointer is over it
+    Hover,
+    /// The primary button went down on it and is still held
+    Pressed,
+    /// It has keyboard/gamepad focus
+    Selected,
+    /// Not interactable (itself or a canvas group above it)
+    Disabled,
+}
+
+impl InteractableState {
+    /// State of a control: disabled wins, then pressed, hover and focus
+    pub fn resolve(interactable: bool, pressed: bool, hovered: bool, selected: bool) -> Self {
+        match () {
+            _ if !interactable => Self::Disabled,
+            _ if pressed => Self::Pressed,
+            _ if hovered => Self::Hover,
+            _ if selected => Self::Selected,
+            _ => Self::Normal,
+        }
+    }
+}
+
+/// Look of a control in each InteractableState: a tint multiplied into its image and
+/// text colors (ColorTint) or a sprite replacing its image (SpriteSwap). The defaults
+/// leave it as drawn and only grey it out while disabled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StateTransition {
+    pub transition: ButtonTransition,
+
+    pub normal_color: Color,
+    pub hover_color: Color,
+    pub pressed_color: Color,
+    pub selected_color: Color,
+    pub disabled_color: Color,
+
+    /// Sprite swap (None keeps the image's own sprite)
+    pub hover_sprite: Option<String>,
+    pub pressed_sprite: Option<String>,
+    pub selected_sprite: Option<String>,
+    pub disabled_sprite: Option<String>,
+
+    /// Seconds to fade from one state's color to the next (0 switches at once)
+    pub fade_duration: f32,
+}
+
+impl Default for StateTransition {
+    fn default() -> Self {
+        Self {
+            transition: ButtonTransition::ColorTint,
+            normal_color: Color::WHITE,
+            hover_color: Color::WHITE,
+            pressed_color: Color::WHITE,
+            selected_color: Color::WHITE,
+            disabled_color: Color::rgba(0.5, 0.5, 0.5, 0.5),
+            hover_sprite: None,
+            pressed_sprite: None,
+            selected_sprite: None,
+            disabled_sprite: None,
+            fade_duration: 0.0,
+        }
+    }
+}
+
+impl StateTransition {
+    /// Tint in `state` (white unless the transition is ColorTint)
+    pub fn color(&self, state: InteractableState) -> Color {
+        if self.transition != ButtonTransition::ColorTint {
+            return Color::WHITE;
+        }
+        match state {
+            InteractableState::Normal => self.normal_color,
+            InteractableState::Hover => self.hover_color,
+            InteractableState::Pressed => self.pressed_color,
+            InteractableState::Selected => self.selected_color,
+            InteractableState::Disabled => self.disabled_color,
+        }
+    }
+
+    /// Sprite shown in `state` instead of the image's own (SpriteSwap only)
+    pub fn sprite(&self, state: InteractableState) -> Option<&str> {
+        if self.transition != ButtonTransition::SpriteSwap {
+            return None;
+        }
+        match state {
+            InteractableState::Normal => None,
+            InteractableState::Hover => self.hover_sprite.as_deref(),
+            InteractableState::Pressed => self.pressed_sprite.as_deref(),
+            InteractableState::Selected => self.selected_sprite.as_deref(),
+            InteractableState::Disabled => self.disabled_sprite.as_deref(),
+        }
+    }
+}
@@ -10,6 +10,11 @@ mod toggle;
 mod dropdown;
 mod input_field;
 mod scroll_view;
+mod virtual_input;
+mod tooltip;
+mod safe_area;
+mod canvas_group;
+mod interactable;
 
 pub use ui_element::UIElement;
 pub use image::{UIImage, ImageType, FillMethod};
@@ -17,7 +22,12 @@ pub use text::{UIText, TextAlignment, OverflowMode};
 pub use button::{UIButton, ButtonState, ButtonTransition};
 pub use panel::UIPanel;
 pub use slider::{UISlider, SliderDirection};
-pub use toggle::{UIToggle, ToggleTransition};
+pub use toggle::{UIToggle, ToggleTransition, ToggleGroupRef, UIToggleGroup};
 pub use dropdown::{UIDropdown, DropdownOption};
 pub use input_field::{UIInputField, ContentType, LineType, InputType, KeyboardType, CharacterValidation};
 pub use scroll_view::{UIScrollView, MovementType};
+pub use virtual_input::UIVirtualInput;
+pub use tooltip::{UITooltip, TooltipPlacement};
+pub use safe_area::UISafeArea;
+pub use canvas_group::{UICanvasGroup, CanvasGroupState};
+pub use interactable::{InteractableState, StateTransition};
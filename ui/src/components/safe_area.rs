@@ -0,0 +1,43 @@
+//! UISafeArea component
+
+use serde::{Deserialize, Serialize};
+use crate::Rect;
+
+/// Keeps the element inside the screen's safe area (notches, rounded corners, status
+/// bars): the parent rect it is anchored to is shrunk to the safe area on the enabled sides
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UISafeArea {
+    pub left: bool,
+    pub right: bool,
+    pub top: bool,
+    pub bottom: bool,
+}
+
+impl Default for UISafeArea {
+    fn default() -> Self {
+        Self { left: true, right: true, top: true, bottom: true }
+    }
+}
+
+impl UISafeArea {
+    /// `parent` inset to `safe_area` on the enabled sides (both y-up, same space).
+    /// Sides already inside the safe area are left alone.
+    pub fn apply(&self, parent: Rect, safe_area: Rect) -> Rect {
+        let mut min = parent.min();
+        let mut max = parent.max();
+        if self.left {
+            min.x = min.x.max(safe_area.min().x);
+        }
+        if self.bottom {
+            min.y = min.y.max(safe_area.min().y);
+        }
+        if self.right {
+            max.x = max.x.min(safe_area.max().x);
+        }
+        if self.top {
+            max.y = max.y.min(safe_area.max().y);
+        }
+        Rect::from_min_max(min, max.max(min))
+    }
+}
@@ -1,6 +1,8 @@
 //! UISlider component
 
 use serde::{Deserialize, Serialize};
+use crate::events::UIEventBinding;
+use super::StateTransition;
 
 /// Slider component for value selection
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -25,9 +27,26 @@ pub struct UISlider {
     
     /// Whether to use whole numbers
     pub whole_numbers: bool,
+
+    /// Name of the child stretched from the start to the value (None: not moved)
+    #[serde(default)]
+    pub fill_element: Option<String>,
+
+    /// Name of the child moved to the value, the knob (None: not moved)
+    #[serde(default)]
+    pub handle_element: Option<String>,
+
+    /// Seconds the fill and knob take to catch up with a value set from code (0 jumps;
+    /// dragging always follows the pointer)
+    #[serde(default)]
+    pub smoothing_time: f32,
+
+    /// Look per interactable state (None: the theme's)
+    #[serde(default)]
+    pub transition: Option<StateTransition>,
     
-    /// Lua callback for value changed
-    pub on_value_changed: Option<String>,
+    /// What a value change does (a Lua function name or an engine action)
+    pub on_value_changed: Option<UIEventBinding>,
 }
 
 impl Default for UISlider {
@@ -40,11 +59,27 @@ impl Default for UISlider {
             max_value: 1.0,
             value: 0.0,
             whole_numbers: false,
+            fill_element: None,
+            handle_element: None,
+            smoothing_time: 0.0,
+            transition: None,
             on_value_changed: None,
         }
     }
 }
 
+impl UISlider {
+    /// The value as a fraction of the range (0 at min_value, 1 at max_value)
+    pub fn normalized_value(&self) -> f32 {
+        let range = self.max_value - self.min_value;
+        if range.abs() > f32::EPSILON {
+            ((self.value - self.min_value) / range).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+}
+
 /// Slider direction
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum SliderDirection {
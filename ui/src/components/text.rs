@@ -39,6 +39,10 @@ pub struct UIText {
     /// Min and max font size for best fit
     pub best_fit_min_size: f32,
     pub best_fit_max_size: f32,
+
+    /// String table key; when set, `text` is replaced by the active language's string
+    #[serde(default)]
+    pub localization_key: Option<String>,
 }
 
 impl Default for UIText {
@@ -47,7 +51,7 @@ impl Default for UIText {
             text: String::new(),
             font: String::from("default"),
             font_size: 14.0,
-            color: [0.0, 0.0, 0.0, 1.0], // Black
+            color: Color::rgba(0.0, 0.0, 0.0, 1.0), // Black
             alignment: TextAlignment::MiddleCenter,
             horizontal_overflow: OverflowMode::Wrap,
             vertical_overflow: OverflowMode::Truncate,
@@ -56,6 +60,7 @@ impl Default for UIText {
             best_fit: false,
             best_fit_min_size: 10.0,
             best_fit_max_size: 40.0,
+            localization_key: None,
         }
     }
 }
@@ -1,6 +1,8 @@
 //! UIToggle component
 
 use serde::{Deserialize, Serialize};
+use crate::events::UIEventBinding;
+use super::StateTransition;
 
 /// Toggle component for checkboxes
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -11,11 +13,28 @@ pub struct UIToggle {
     /// Whether toggle is on
     pub is_on: bool,
     
-    /// Toggle transition
+    /// How the checkmark appears and disappears
     pub toggle_transition: ToggleTransition,
+
+    /// Name of the child drawn as the checkmark, shown while on (None: the children
+    /// are drawn as they are)
+    #[serde(default)]
+    pub checkmark: Option<String>,
+
+    /// Seconds the checkmark takes to fade (and scale) in or out
+    #[serde(default = "default_checkmark_duration")]
+    pub checkmark_duration: f32,
+
+    /// Look per interactable state (None: the theme's)
+    #[serde(default)]
+    pub transition: Option<StateTransition>,
     
-    /// Lua callback for value changed
-    pub on_value_changed: Option<String>,
+    /// What a value change does (a Lua function name or an engine action)
+    pub on_value_changed: Option<UIEventBinding>,
+
+    /// Toggle group this toggle belongs to (radio button behavior)
+    #[serde(default)]
+    pub group: Option<ToggleGroupRef>,
 }
 
 impl Default for UIToggle {
@@ -24,14 +43,46 @@ impl Default for UIToggle {
             graphic: None,
             is_on: false,
             toggle_transition: ToggleTransition::Fade,
+            checkmark: None,
+            checkmark_duration: default_checkmark_duration(),
+            transition: None,
             on_value_changed: None,
+            group: None,
         }
     }
 }
 
+fn default_checkmark_duration() -> f32 {
+    0.1
+}
+
 /// Toggle transition type
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum ToggleTransition {
     None,
     Fade,
+    /// Fade while growing from (or shrinking to) nothing
+    Scale,
+}
+
+/// Reference to a toggle group: its entity, or its id (for prefabs, where entities
+/// aren't known yet). Serialized as a number or a string.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ToggleGroupRef {
+    Entity(u64),
+    Id(String),
+}
+
+/// Toggle group: at most one member toggle is on
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct UIToggleGroup {
+    /// Id toggles use to join the group (ToggleGroupRef::Id)
+    pub id: String,
+
+    /// Clicking the active toggle turns it off (otherwise one member always stays on)
+    pub allow_switch_off: bool,
+
+    /// Lua callback for the active toggle changing
+    pub on_active_changed: Option<String>,
 }
@@ -0,0 +1,54 @@
+//! UITooltip component
+
+use glam::Vec2;
+use serde::{Deserialize, Serialize};
+
+/// Tooltip shown after the pointer (or gamepad focus) rests on the element
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UITooltip {
+    /// Tooltip text (also the text of the prefab's text elements when `prefab` is set)
+    pub text: String,
+
+    /// Prefab drawn as the tooltip instead of the default themed box
+    pub prefab: Option<String>,
+
+    /// Seconds the pointer must rest on the element before the tooltip appears
+    pub show_delay: f32,
+
+    /// Where the tooltip goes relative to the cursor or the element
+    pub placement: TooltipPlacement,
+
+    /// Gap between the tooltip and the cursor or element edge, in pixels
+    pub offset: Vec2,
+}
+
+impl Default for UITooltip {
+    fn default() -> Self {
+        Self {
+            text: String::new(),
+            prefab: None,
+            show_delay: 0.5,
+            placement: TooltipPlacement::FollowCursor,
+            offset: Vec2::new(12.0, 12.0),
+        }
+    }
+}
+
+impl UITooltip {
+    pub fn with_text(text: impl Into<String>) -> Self {
+        Self { text: text.into(), ..Default::default() }
+    }
+}
+
+/// Tooltip placement
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TooltipPlacement {
+    /// Below-right of the cursor, moving with it
+    FollowCursor,
+    /// Fixed against a side of the element (flipped to the opposite side near screen edges)
+    Above,
+    Below,
+    Left,
+    Right,
+}
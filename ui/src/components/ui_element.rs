@@ -26,6 +26,14 @@ pub struct UIElement {
     
     /// Whether to ignore parent groups (for layout)
     pub ignore_layout: bool,
+
+    /// Whether this element can be dragged onto drop targets (inventory items)
+    #[serde(default)]
+    pub draggable: bool,
+
+    /// Clicks on this element still reach the game (HUD that shouldn't block world clicks)
+    #[serde(default)]
+    pub pass_through: bool,
     
     /// Cached canvas entity (updated by hierarchy system)
     #[serde(skip)]
@@ -38,10 +46,12 @@ impl Default for UIElement {
             raycast_target: true,
             blocks_raycasts: true,
             z_order: 0,
-            color: [1.0, 1.0, 1.0, 1.0], // White
+            color: Color::rgba(1.0, 1.0, 1.0, 1.0), // White
             alpha: 1.0,
             interactable: true,
             ignore_layout: false,
+            draggable: false,
+            pass_through: false,
             canvas_entity: None,
         }
     }
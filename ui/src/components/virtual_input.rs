@@ -0,0 +1,36 @@
+//! UIVirtualInput component
+
+use serde::{Deserialize, Serialize};
+
+/// Marks an element as an on-screen control for touch input. The element's rect is
+/// the touch region; the engine reports it to the input system every frame.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum UIVirtualInput {
+    /// Drives `get_movement_input` (and the move_x / move_y actions)
+    Joystick {
+        /// Touch distance (in pixels) for full deflection
+        radius: f32,
+
+        /// Fraction of the radius ignored around the origin
+        dead_zone: f32,
+
+        /// Origin starts where the touch lands instead of at the region center
+        floating: bool,
+    },
+
+    /// Feeds the named input action while held
+    Button {
+        action: String,
+    },
+}
+
+impl Default for UIVirtualInput {
+    fn default() -> Self {
+        UIVirtualInput::Joystick {
+            radius: 80.0,
+            dead_zone: 0.15,
+            floating: true,
+        }
+    }
+}
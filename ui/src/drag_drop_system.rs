@@ -0,0 +1,158 @@
+//! Drag-and-drop system
+//!
+//! Tracks the draggable element being dragged (for drawing a ghost that follows the
+//! pointer) and filters Drop events from UIInputHandler down to live drags. Nothing
+//! moves until a drop target handles the Drop, so a cancelled drag leaves the element
+//! where it was.
+
+use crate::events::UIEvent;
+use crate::UIElement;
+use glam::Vec2;
+use std::collections::HashMap;
+
+/// Entity type alias
+pub type Entity = u64;
+
+/// Element currently being dragged
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ActiveDrag {
+    pub source: Entity,
+
+    /// Pointer position when the element was pressed
+    pub press_position: Vec2,
+
+    /// Current pointer position
+    pub pointer: Vec2,
+}
+
+impl ActiveDrag {
+    /// How far the dragged element (or its ghost) is moved from its own position
+    pub fn offset(&self) -> Vec2 {
+        self.pointer - self.press_position
+    }
+}
+
+#[derive(Default)]
+pub struct DragDropSystem {
+    active: Option<ActiveDrag>,
+
+    /// Where each element was last pressed (the drag starts past the threshold, later)
+    press_positions: HashMap<Entity, Vec2>,
+}
+
+impl DragDropSystem {
+    /// Create a new drag-and-drop system
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Follow this frame's pointer events. Returns the Drop events of drags that weren't
+    /// cancelled.
+    pub fn update_from_events(&mut self, events: &[UIEvent], elements: &HashMap<Entity, UIElement>) -> Vec<UIEvent> {
+        let mut drops = Vec::new();
+        for event in events {
+            match event {
+                UIEvent::PointerDown(entity, position) => {
+                    self.press_positions.insert(*entity, *position);
+                }
+                UIEvent::BeginDrag(entity, position) => {
+                    let draggable = elements
+                        .get(entity)
+                        .is_some_and(|element| element.draggable && element.interactable);
+                    if draggable {
+                        let press_position = self.press_positions.get(entity).copied().unwrap_or(*position);
+                        self.active = Some(ActiveDrag { source: *entity, press_position, pointer: *position });
+                    }
+                }
+                UIEvent::Drag(entity, position, _) => {
+                    if let Some(drag) = self.active.as_mut().filter(|drag| drag.source == *entity) {
+                        drag.pointer = *position;
+                    }
+                }
+                UIEvent::Drop(_, source, _) if self.active.is_some_and(|drag| drag.source == *source) => {
+                    drops.push(event.clone());
+                }
+                UIEvent::PointerUp(entity, _) => {
+                    self.press_positions.remove(entity);
+                }
+                _ => {}
+            }
+        }
+
+        // EndDrag comes before Drop in the same frame, so finish the drag last
+        for event in events {
+            if let UIEvent::EndDrag(entity, _) = event {
+                if self.active.is_some_and(|drag| drag.source == *entity) {
+                    self.active = None;
+                }
+            }
+        }
+        drops
+    }
+
+    /// Element being dragged, if any
+    pub fn active_drag(&self) -> Option<ActiveDrag> {
+        self.active
+    }
+
+    /// Abandon the drag (Escape, element destroyed): its Drop is ignored and the
+    /// element stays where it was
+    pub fn cancel(&mut self) {
+        self.active = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn elements() -> HashMap<Entity, UIElement> {
+        HashMap::from([
+            (1, UIElement { draggable: true, ..Default::default() }),
+            (2, UIElement::default()),
+        ])
+    }
+
+    #[test]
+    fn test_drag_follows_pointer_and_drops() {
+        let mut system = DragDropSystem::new();
+        let elements = elements();
+
+        system.update_from_events(&[UIEvent::PointerDown(1, Vec2::new(10.0, 10.0))], &elements);
+        assert_eq!(system.active_drag(), None);
+
+        system.update_from_events(&[
+            UIEvent::BeginDrag(1, Vec2::new(16.0, 10.0)),
+            UIEvent::Drag(1, Vec2::new(30.0, 25.0), Vec2::new(14.0, 15.0)),
+        ], &elements);
+        let drag = system.active_drag().unwrap();
+        assert_eq!(drag.source, 1);
+        assert_eq!(drag.offset(), Vec2::new(20.0, 15.0));
+
+        let drops = system.update_from_events(&[
+            UIEvent::PointerUp(1, Vec2::new(30.0, 25.0)),
+            UIEvent::EndDrag(1, Vec2::new(30.0, 25.0)),
+            UIEvent::Drop(2, 1, Vec2::new(30.0, 25.0)),
+        ], &elements);
+        assert!(matches!(drops.as_slice(), [UIEvent::Drop(2, 1, _)]));
+        assert_eq!(system.active_drag(), None);
+    }
+
+    #[test]
+    fn test_non_draggable_and_cancelled_drags_dont_drop() {
+        let mut system = DragDropSystem::new();
+        let elements = elements();
+
+        // Element 2 isn't draggable: its drag (a slider, a scroll view) is not a drag-and-drop
+        system.update_from_events(&[UIEvent::BeginDrag(2, Vec2::ZERO)], &elements);
+        assert_eq!(system.active_drag(), None);
+
+        system.update_from_events(&[UIEvent::BeginDrag(1, Vec2::ZERO)], &elements);
+        system.cancel();
+        let drops = system.update_from_events(&[
+            UIEvent::EndDrag(1, Vec2::ZERO),
+            UIEvent::Drop(2, 1, Vec2::ZERO),
+        ], &elements);
+        assert!(drops.is_empty());
+    }
+}
@@ -244,7 +244,7 @@ impl DropdownSystem {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{TextAlignment, OverflowMode};
+    use crate::{Color, TextAlignment, OverflowMode};
 
     fn create_test_dropdown() -> UIDropdown {
         UIDropdown {
@@ -267,6 +267,7 @@ mod tests {
             ],
             value: 0,
             on_value_changed: None,
+            transition: None,
         }
     }
 
@@ -275,10 +276,12 @@ mod tests {
             raycast_target: true,
             blocks_raycasts: true,
             z_order: 0,
-            color: [1.0, 1.0, 1.0, 1.0],
+            color: Color::rgba(1.0, 1.0, 1.0, 1.0),
             alpha: 1.0,
             interactable,
             ignore_layout: false,
+            draggable: false,
+            pass_through: false,
             canvas_entity: None,
         }
     }
@@ -288,7 +291,7 @@ mod tests {
             text: String::new(),
             font: "default".to_string(),
             font_size: 14.0,
-            color: [1.0, 1.0, 1.0, 1.0],
+            color: Color::rgba(1.0, 1.0, 1.0, 1.0),
             alignment: TextAlignment::MiddleCenter,
             horizontal_overflow: OverflowMode::Wrap,
             vertical_overflow: OverflowMode::Truncate,
@@ -297,6 +300,7 @@ mod tests {
             best_fit: false,
             best_fit_min_size: 10.0,
             best_fit_max_size: 40.0,
+            localization_key: None,
         }
     }
 
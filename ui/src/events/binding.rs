@@ -0,0 +1,92 @@
+//! Event bindings declared inline in prefab files
+
+use serde::{Deserialize, Serialize};
+
+/// What a button click or a slider/toggle change does. Either a Lua function name
+/// (`"on_click": "OnStartClicked"`) or an action object:
+///
+/// ```json
+/// "on_click": { "action": "lua", "function": "OnStartClicked" }
+/// "on_click": { "action": "show", "target": "Menus/Options" }
+/// "on_click": { "action": "set_canvas", "target": "ui/options.uiprefab" }
+/// ```
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum UIEventBinding {
+    /// Lua function name (the older string form)
+    Function(String),
+    Action(UIAction),
+}
+
+/// Action run by a UIEventBinding
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum UIAction {
+    /// Call `function(value, element_path)` in the script of the entity that activated
+    /// the prefab
+    Lua { function: String },
+    /// Show an element of the same prefab (path below its root, e.g. "Menus/Options")
+    Show { target: String },
+    /// Hide an element of the same prefab
+    Hide { target: String },
+    /// Show another prefab file in place of this one (same instance name)
+    SetCanvas { target: String },
+}
+
+impl UIEventBinding {
+    /// Binding that calls a Lua function
+    pub fn lua(function: impl Into<String>) -> Self {
+        Self::Function(function.into())
+    }
+
+    /// The action to run (a bare function name is a Lua action)
+    pub fn action(&self) -> UIAction {
+        match self {
+            Self::Function(function) => UIAction::Lua { function: function.clone() },
+            Self::Action(action) => action.clone(),
+        }
+    }
+
+    /// Lua function this binding calls, if it is a Lua binding
+    pub fn function(&self) -> Option<&str> {
+        match self {
+            Self::Function(function) | Self::Action(UIAction::Lua { function }) => Some(function),
+            Self::Action(_) => None,
+        }
+    }
+}
+
+impl UIAction {
+    /// Short description for logs and the inspector, e.g. "show Menus/Options"
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Lua { function } => format!("lua {}", function),
+            Self::Show { target } => format!("show {}", target),
+            Self::Hide { target } => format!("hide {}", target),
+            Self::SetCanvas { target } => format!("set_canvas {}", target),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binding_accepts_function_name_and_action_objects() {
+        let binding: UIEventBinding = serde_json::from_str(r#""OnStartClicked""#).unwrap();
+        assert_eq!(binding.action(), UIAction::Lua { function: "OnStartClicked".to_string() });
+
+        let binding: UIEventBinding = serde_json::from_str(r#"{ "action": "lua", "function": "OnStartClicked" }"#).unwrap();
+        assert_eq!(binding.function(), Some("OnStartClicked"));
+
+        let binding: UIEventBinding = serde_json::from_str(r#"{ "action": "show", "target": "Menus/Options" }"#).unwrap();
+        assert_eq!(binding.action(), UIAction::Show { target: "Menus/Options".to_string() });
+        assert_eq!(binding.function(), None);
+
+        let binding: UIEventBinding = serde_json::from_str(r#"{ "action": "set_canvas", "target": "ui/options.uiprefab" }"#).unwrap();
+        assert_eq!(serde_json::to_string(&binding).unwrap(), r#"{"action":"set_canvas","target":"ui/options.uiprefab"}"#);
+
+        assert!(serde_json::from_str::<UIEventBinding>(r#"{ "action": "explode" }"#).is_err());
+    }
+}
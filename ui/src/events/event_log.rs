@@ -0,0 +1,159 @@
+//! Recent UI events, for inspecting why an element does or doesn't respond
+
+use std::collections::{HashSet, VecDeque};
+
+/// Entries kept by default (older ones are dropped)
+const DEFAULT_CAPACITY: usize = 500;
+
+/// One logged event
+#[derive(Clone, Debug, PartialEq)]
+pub struct UIEventLogEntry {
+    /// Frame it happened in (UIEventLog::begin_frame count)
+    pub frame: u64,
+    /// Event name ("PointerDown", "Click", "ValueChanged", ...)
+    pub event: String,
+    /// Path of the element the event was for
+    pub target: String,
+    /// Canvas (UI instance) the target is in
+    pub canvas: String,
+    /// Element that handled it (None: nothing did)
+    pub consumed_by: Option<String>,
+    /// Value, position or callbacks, depending on the event
+    pub detail: String,
+}
+
+/// Ring buffer of recent UI events. Recording is off until enabled, so games that
+/// nobody inspects don't pay for it.
+#[derive(Clone, Debug)]
+pub struct UIEventLog {
+    entries: VecDeque<UIEventLogEntry>,
+    capacity: usize,
+    enabled: bool,
+    frame: u64,
+}
+
+impl Default for UIEventLog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UIEventLog {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { entries: VecDeque::new(), capacity: capacity.max(1), enabled: false, frame: 0 }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Count a new frame (entries pushed after this carry its number)
+    pub fn begin_frame(&mut self) {
+        self.frame += 1;
+    }
+
+    pub fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// Record an event (ignored while disabled)
+    pub fn push(&mut self, event: &str, target: &str, consumed_by: Option<&str>, detail: String) {
+        if !self.enabled {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        let canvas = target.split_once('/').map_or(target, |(canvas, _)| canvas);
+        self.entries.push_back(UIEventLogEntry {
+            frame: self.frame,
+            event: event.to_string(),
+            target: target.to_string(),
+            canvas: canvas.to_string(),
+            consumed_by: consumed_by.map(str::to_string),
+            detail,
+        });
+    }
+
+    /// Entries, oldest first
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &UIEventLogEntry> + ExactSizeIterator {
+        self.entries.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+/// Which log entries to show
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UIEventLogFilter {
+    /// Substring of the target path (case-insensitive; empty matches all)
+    pub path: String,
+    /// Event names left out
+    pub hidden_events: HashSet<String>,
+}
+
+impl UIEventLogFilter {
+    pub fn matches(&self, entry: &UIEventLogEntry) -> bool {
+        if self.hidden_events.contains(&entry.event) {
+            return false;
+        }
+        self.path.is_empty() || entry.target.to_lowercase().contains(&self.path.to_lowercase())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_records_only_while_enabled_and_keeps_the_newest() {
+        let mut log = UIEventLog::with_capacity(2);
+        log.push("Click", "HUD/Play", Some("HUD/Play"), String::new());
+        assert!(log.is_empty());
+
+        log.set_enabled(true);
+        for frame in 1..=3 {
+            log.begin_frame();
+            log.push("PointerDown", &format!("HUD/Button{}", frame), None, String::new());
+        }
+        let frames: Vec<_> = log.entries().map(|entry| entry.frame).collect();
+        assert_eq!(frames, vec![2, 3]);
+        let last = log.entries().last().unwrap();
+        assert_eq!((last.canvas.as_str(), last.target.as_str()), ("HUD", "HUD/Button3"));
+    }
+
+    #[test]
+    fn test_filter() {
+        let mut log = UIEventLog::new();
+        log.set_enabled(true);
+        log.push("PointerEnter", "Menu/Panel/Play", None, String::new());
+        log.push("Click", "Menu/Panel/Play", Some("Menu/Panel/Play"), String::new());
+        log.push("Click", "HUD/Pause", Some("HUD/Pause"), String::new());
+
+        let filter = UIEventLogFilter {
+            path: "panel/PLAY".to_string(),
+            hidden_events: HashSet::from(["PointerEnter".to_string()]),
+        };
+        let shown: Vec<_> = log.entries().filter(|entry| filter.matches(entry)).map(|entry| entry.event.as_str()).collect();
+        assert_eq!(shown, vec!["Click"]);
+        assert_eq!(log.entries().filter(|entry| UIEventLogFilter::default().matches(entry)).count(), 3);
+    }
+}
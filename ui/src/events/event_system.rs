@@ -7,6 +7,17 @@ use super::raycast::Entity;
 /// Callback function type for UI events
 pub type EventCallback = Box<dyn Fn(&UIEvent) + Send + Sync>;
 
+/// Listeners an event reaches (UIEventDispatcher::route)
+#[derive(Clone, Debug, PartialEq)]
+pub struct DispatchRoute {
+    pub target: Entity,
+    pub event_type: UIEventType,
+    /// Callback names registered for the target and event type, in call order
+    pub callbacks: Vec<String>,
+    /// Those of `callbacks` with no function registered under their name (never called)
+    pub unregistered: Vec<String>,
+}
+
 /// UI Event dispatcher
 pub struct UIEventDispatcher {
     /// Registered event listeners (entity -> event type -> callbacks)
@@ -73,29 +84,8 @@ impl UIEventDispatcher {
     
     /// Dispatch a single event
     pub fn dispatch_event(&self, event: &UIEvent) {
-        let entity = match event {
-            UIEvent::PointerEnter(e) => *e,
-            UIEvent::PointerExit(e) => *e,
-            UIEvent::PointerDown(e, _) => *e,
-            UIEvent::PointerUp(e, _) => *e,
-            UIEvent::PointerClick(e, _) => *e,
-            UIEvent::BeginDrag(e, _) => *e,
-            UIEvent::Drag(e, _, _) => *e,
-            UIEvent::EndDrag(e, _) => *e,
-            UIEvent::Scroll(e, _) => *e,
-        };
-        
-        let event_type = match event {
-            UIEvent::PointerEnter(_) => UIEventType::OnPointerEnter,
-            UIEvent::PointerExit(_) => UIEventType::OnPointerExit,
-            UIEvent::PointerDown(_, _) => UIEventType::OnPointerDown,
-            UIEvent::PointerUp(_, _) => UIEventType::OnPointerUp,
-            UIEvent::PointerClick(_, _) => UIEventType::OnPointerClick,
-            UIEvent::BeginDrag(_, _) => UIEventType::OnBeginDrag,
-            UIEvent::Drag(_, _, _) => UIEventType::OnDrag,
-            UIEvent::EndDrag(_, _) => UIEventType::OnEndDrag,
-            UIEvent::Scroll(_, _) => UIEventType::OnScroll,
-        };
+        let entity = event.target();
+        let event_type = event.event_type();
         
         // Find listeners for this entity and event type
         if let Some(entity_listeners) = self.listeners.get(&entity) {
@@ -109,6 +99,21 @@ impl UIEventDispatcher {
             }
         }
     }
+
+    /// Where `event` would go without dispatching it (for event inspectors)
+    pub fn route(&self, event: &UIEvent) -> DispatchRoute {
+        let target = event.target();
+        let event_type = event.event_type();
+        let callbacks = self.listeners.get(&target)
+            .and_then(|listeners| listeners.get(&event_type))
+            .cloned()
+            .unwrap_or_default();
+        let unregistered = callbacks.iter()
+            .filter(|name| !self.lua_callbacks.contains_key(*name))
+            .cloned()
+            .collect();
+        DispatchRoute { target, event_type, callbacks, unregistered }
+    }
     
     /// Dispatch multiple events
     pub fn dispatch_events(&self, events: &[UIEvent]) {
@@ -122,6 +127,11 @@ impl UIEventDispatcher {
         self.listeners.get(&entity)
     }
     
+    /// Every entity with listeners
+    pub fn listeners(&self) -> impl Iterator<Item = (Entity, &HashMap<UIEventType, Vec<String>>)> {
+        self.listeners.iter().map(|(entity, listeners)| (*entity, listeners))
+    }
+
     /// Check if an entity has any listeners
     pub fn has_listeners(&self, entity: Entity) -> bool {
         self.listeners.contains_key(&entity)
@@ -293,6 +303,24 @@ mod tests {
         // The callback should have been invoked (we can't easily test this without more infrastructure)
     }
 
+    #[test]
+    fn test_route() {
+        let mut dispatcher = UIEventDispatcher::new();
+        dispatcher.register_lua_callback("on_click".to_string(), Box::new(|_event: &UIEvent| {}));
+        dispatcher.register_listener(1, UIEventType::OnPointerClick, "on_click".to_string());
+        dispatcher.register_listener(1, UIEventType::OnPointerClick, "typo_click".to_string());
+
+        let route = dispatcher.route(&UIEvent::PointerClick(1, Vec2::ZERO));
+        assert_eq!(route.target, 1);
+        assert_eq!(route.event_type, UIEventType::OnPointerClick);
+        assert_eq!(route.callbacks, vec!["on_click".to_string(), "typo_click".to_string()]);
+        assert_eq!(route.unregistered, vec!["typo_click".to_string()]);
+
+        // Other event types and elements reach nothing
+        assert!(dispatcher.route(&UIEvent::PointerDown(1, Vec2::ZERO)).callbacks.is_empty());
+        assert!(dispatcher.route(&UIEvent::PointerClick(2, Vec2::ZERO)).callbacks.is_empty());
+    }
+
     #[test]
     fn test_button_state_manager() {
         let mut manager = ButtonStateManager::new();
@@ -133,6 +133,15 @@ pub struct UIInputHandler {
     
     /// Current time (updated each frame)
     current_time: f32,
+
+    /// Emit HoverEnter/HoverExit (with timestamps) for tooltips and other dwell consumers
+    track_hover: bool,
+
+    /// Element selected by gamepad/keyboard navigation (hovered while focused)
+    focused_element: Option<Entity>,
+
+    /// Focus change not yet turned into hover events
+    pending_focus: Option<Option<Entity>>,
 }
 
 impl Default for UIInputHandler {
@@ -153,6 +162,9 @@ impl UIInputHandler {
             click_time_threshold: 0.3,
             pointer_down_time: HashMap::new(),
             current_time: 0.0,
+            track_hover: false,
+            focused_element: None,
+            pending_focus: None,
         }
     }
     
@@ -166,6 +178,29 @@ impl UIInputHandler {
         self.click_time_threshold = threshold;
     }
     
+    /// Enable HoverEnter/HoverExit events (off by default)
+    pub fn set_track_hover(&mut self, track_hover: bool) {
+        self.track_hover = track_hover;
+    }
+
+    /// Move gamepad/keyboard focus. Focus counts as hovering, so dwell-driven
+    /// features (tooltips) work without a pointer.
+    pub fn set_focused_element(&mut self, entity: Option<Entity>) {
+        if entity != self.focused_element {
+            self.pending_focus = Some(entity);
+        }
+    }
+
+    /// Element with gamepad/keyboard focus (including a change not processed yet)
+    pub fn get_focused_element(&self) -> Option<Entity> {
+        self.pending_focus.unwrap_or(self.focused_element)
+    }
+
+    /// Current time as used in event timestamps
+    pub fn current_time(&self) -> f32 {
+        self.current_time
+    }
+
     /// Begin a new frame
     pub fn begin_frame(&mut self, delta_time: f32) {
         self.input_state.begin_frame();
@@ -203,6 +238,7 @@ impl UIInputHandler {
         
         // Process hover events (enter/exit)
         self.process_hover_events(current_entity, &mut events);
+        self.process_focus_events(&mut events);
         
         // Process pointer down events
         if self.input_state.is_button_pressed(MouseButton::Left) {
@@ -247,8 +283,16 @@ impl UIInputHandler {
                 
                 // End drag if this element was being dragged
                 if was_dragging {
-                    events.push(UIEvent::EndDrag(entity, self.input_state.mouse_position));
+                    let position = self.input_state.mouse_position;
+                    events.push(UIEvent::EndDrag(entity, position));
                     self.dragging_element = None;
+
+                    // Draggable elements drop onto whatever is under the pointer besides themselves
+                    if raycast_system.get_element(entity).is_some_and(|element| element.draggable) {
+                        if let Some(target) = raycast_system.raycast_topmost_excluding(position, entity) {
+                            events.push(UIEvent::Drop(target.entity, entity, position));
+                        }
+                    }
                 }
             }
         }
@@ -306,6 +350,9 @@ impl UIInputHandler {
         for entity in exited {
             self.hovered_elements.remove(&entity);
             events.push(UIEvent::PointerExit(entity));
+            if self.track_hover {
+                events.push(UIEvent::HoverExit(entity, self.current_time));
+            }
         }
         
         // Check if we entered a new element
@@ -313,9 +360,29 @@ impl UIInputHandler {
             if !self.hovered_elements.contains(&entity) {
                 self.hovered_elements.insert(entity);
                 events.push(UIEvent::PointerEnter(entity));
+                if self.track_hover {
+                    events.push(UIEvent::HoverEnter(entity, self.current_time));
+                }
             }
         }
     }
+
+    /// Turn a focus change into hover exit/enter events
+    fn process_focus_events(&mut self, events: &mut Vec<UIEvent>) {
+        let Some(focused) = self.pending_focus.take() else {
+            return;
+        };
+        let previous = std::mem::replace(&mut self.focused_element, focused);
+        if !self.track_hover {
+            return;
+        }
+        if let Some(entity) = previous {
+            events.push(UIEvent::HoverExit(entity, self.current_time));
+        }
+        if let Some(entity) = focused {
+            events.push(UIEvent::HoverEnter(entity, self.current_time));
+        }
+    }
     
     /// Get the current input state (for external systems)
     pub fn get_input_state(&self) -> &InputState {
@@ -427,4 +494,72 @@ mod tests {
         handler.set_click_time_threshold(0.5);
         assert_eq!(handler.click_time_threshold, 0.5);
     }
+
+    #[test]
+    fn test_focus_generates_hover_events() {
+        let raycast = UIRaycastSystem::new();
+        let mut handler = UIInputHandler::new();
+        handler.set_track_hover(true);
+
+        handler.begin_frame(1.0);
+        handler.set_focused_element(Some(3));
+        let events = handler.process_input(&raycast);
+        assert!(matches!(events.as_slice(), [UIEvent::HoverEnter(3, time)] if *time == 1.0));
+
+        handler.begin_frame(0.5);
+        handler.set_focused_element(Some(4));
+        let events = handler.process_input(&raycast);
+        assert!(matches!(events.as_slice(), [UIEvent::HoverExit(3, _), UIEvent::HoverEnter(4, time)] if *time == 1.5));
+        assert_eq!(handler.get_focused_element(), Some(4));
+    }
+
+    #[test]
+    fn test_drop_targets_element_under_dragged_one() {
+        let element = |entity, x, draggable| super::super::RaycastElement {
+            entity,
+            rect: crate::Rect::new(x, 0.0, 50.0, 50.0),
+            transform: glam::Affine2::IDENTITY,
+            raycast_target: true,
+            blocks_raycasts: true,
+            z_order: 0,
+            canvas_sort_order: 0,
+            visible: true,
+            interactable: true,
+            draggable,
+        };
+        let mut raycast = UIRaycastSystem::new();
+        let mut handler = UIInputHandler::new();
+
+        // Slot 2 at x 100; item 1 drawn on top of it after being dragged there
+        raycast.update_elements(vec![element(2, 100.0, false), element(1, 0.0, true)]);
+        handler.begin_frame(0.016);
+        handler.set_mouse_position(Vec2::new(25.0, 25.0));
+        handler.press_button(MouseButton::Left);
+        handler.process_input(&raycast);
+
+        handler.begin_frame(0.016);
+        handler.set_mouse_position(Vec2::new(125.0, 25.0));
+        handler.process_input(&raycast);
+        raycast.update_elements(vec![element(2, 100.0, false), element(1, 100.0, true)]);
+
+        handler.begin_frame(0.016);
+        handler.release_button(MouseButton::Left);
+        let events = handler.process_input(&raycast);
+        assert!(matches!(events.as_slice(), [.., UIEvent::EndDrag(1, _), UIEvent::Drop(2, 1, _)]));
+
+        // Released over nothing: the drag just ends
+        raycast.update_elements(vec![element(1, 0.0, true)]);
+        handler.begin_frame(0.016);
+        handler.set_mouse_position(Vec2::new(25.0, 25.0));
+        handler.press_button(MouseButton::Left);
+        handler.process_input(&raycast);
+        handler.begin_frame(0.016);
+        handler.set_mouse_position(Vec2::new(300.0, 25.0));
+        handler.process_input(&raycast);
+        handler.begin_frame(0.016);
+        handler.release_button(MouseButton::Left);
+        let events = handler.process_input(&raycast);
+        assert!(events.iter().any(|e| matches!(e, UIEvent::EndDrag(1, _))));
+        assert!(!events.iter().any(|e| matches!(e, UIEvent::Drop(..))));
+    }
 }
@@ -6,11 +6,15 @@ use glam::Vec2;
 pub mod raycast;
 pub mod input_handler;
 pub mod event_system;
+pub mod binding;
+pub mod event_log;
 
 // Re-export main types
-pub use raycast::{UIRaycastSystem, RaycastHit, RaycastElement, Entity};
+pub use raycast::{UIRaycastSystem, RaycastHit, RaycastElement, RaycastProbe, RaycastSkip, Entity};
 pub use input_handler::{UIInputHandler, InputState, MouseButton};
-pub use event_system::{UIEventDispatcher, ButtonStateManager, ButtonState, EventCallback};
+pub use event_system::{UIEventDispatcher, ButtonStateManager, ButtonState, EventCallback, DispatchRoute};
+pub use binding::{UIEventBinding, UIAction};
+pub use event_log::{UIEventLog, UIEventLogEntry, UIEventLogFilter};
 
 /// UI Event types
 #[derive(Clone, Debug)]
@@ -24,6 +28,59 @@ pub enum UIEvent {
     Drag(Entity, Vec2, Vec2), // entity, position, delta
     EndDrag(Entity, Vec2),
     Scroll(Entity, f32), // entity, delta
+    HoverEnter(Entity, f32), // entity, time (seconds since the handler started)
+    HoverExit(Entity, f32), // entity, time
+    ToggleChanged(Entity, bool), // toggle outside a group, new state
+    ToggleGroupChanged(Entity, Option<Entity>), // group, newly active toggle (None: all off)
+    InputValueChanged(Entity, String), // input field, text at the end of the frame
+    InputSubmit(Entity, String), // input field, submitted text
+    Drop(Entity, Entity, Vec2), // drop target, dragged element, position
+}
+
+impl UIEvent {
+    /// Element the event is for
+    pub fn target(&self) -> Entity {
+        match self {
+            UIEvent::PointerEnter(e)
+            | UIEvent::PointerExit(e)
+            | UIEvent::PointerDown(e, _)
+            | UIEvent::PointerUp(e, _)
+            | UIEvent::PointerClick(e, _)
+            | UIEvent::BeginDrag(e, _)
+            | UIEvent::Drag(e, _, _)
+            | UIEvent::EndDrag(e, _)
+            | UIEvent::Scroll(e, _)
+            | UIEvent::HoverEnter(e, _)
+            | UIEvent::HoverExit(e, _)
+            | UIEvent::ToggleChanged(e, _)
+            | UIEvent::ToggleGroupChanged(e, _)
+            | UIEvent::InputValueChanged(e, _)
+            | UIEvent::InputSubmit(e, _)
+            | UIEvent::Drop(e, _, _) => *e,
+        }
+    }
+
+    /// Listener type the event is dispatched to
+    pub fn event_type(&self) -> UIEventType {
+        match self {
+            UIEvent::PointerEnter(_) => UIEventType::OnPointerEnter,
+            UIEvent::PointerExit(_) => UIEventType::OnPointerExit,
+            UIEvent::PointerDown(_, _) => UIEventType::OnPointerDown,
+            UIEvent::PointerUp(_, _) => UIEventType::OnPointerUp,
+            UIEvent::PointerClick(_, _) => UIEventType::OnPointerClick,
+            UIEvent::BeginDrag(_, _) => UIEventType::OnBeginDrag,
+            UIEvent::Drag(_, _, _) => UIEventType::OnDrag,
+            UIEvent::EndDrag(_, _) => UIEventType::OnEndDrag,
+            UIEvent::Scroll(_, _) => UIEventType::OnScroll,
+            UIEvent::HoverEnter(_, _) => UIEventType::OnHoverEnter,
+            UIEvent::HoverExit(_, _) => UIEventType::OnHoverExit,
+            UIEvent::ToggleChanged(_, _) => UIEventType::OnToggleChanged,
+            UIEvent::ToggleGroupChanged(_, _) => UIEventType::OnToggleGroupChanged,
+            UIEvent::InputValueChanged(_, _) => UIEventType::OnInputValueChanged,
+            UIEvent::InputSubmit(_, _) => UIEventType::OnInputSubmit,
+            UIEvent::Drop(_, _, _) => UIEventType::OnDrop,
+        }
+    }
 }
 
 /// UI Event listener
@@ -44,6 +101,37 @@ pub enum UIEventType {
     OnDrag,
     OnEndDrag,
     OnScroll,
+    OnHoverEnter,
+    OnHoverExit,
+    OnToggleChanged,
+    OnToggleGroupChanged,
+    OnInputValueChanged,
+    OnInputSubmit,
+    OnDrop,
+}
+
+impl UIEventType {
+    /// Event name without the "On" (as shown in event logs)
+    pub fn name(&self) -> &'static str {
+        match self {
+            UIEventType::OnPointerEnter => "PointerEnter",
+            UIEventType::OnPointerExit => "PointerExit",
+            UIEventType::OnPointerDown => "PointerDown",
+            UIEventType::OnPointerUp => "PointerUp",
+            UIEventType::OnPointerClick => "PointerClick",
+            UIEventType::OnBeginDrag => "BeginDrag",
+            UIEventType::OnDrag => "Drag",
+            UIEventType::OnEndDrag => "EndDrag",
+            UIEventType::OnScroll => "Scroll",
+            UIEventType::OnHoverEnter => "HoverEnter",
+            UIEventType::OnHoverExit => "HoverExit",
+            UIEventType::OnToggleChanged => "ToggleChanged",
+            UIEventType::OnToggleGroupChanged => "ToggleGroupChanged",
+            UIEventType::OnInputValueChanged => "InputValueChanged",
+            UIEventType::OnInputSubmit => "InputSubmit",
+            UIEventType::OnDrop => "Drop",
+        }
+    }
 }
 
 /// Legacy UIEventHandler for backwards compatibility
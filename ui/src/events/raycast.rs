@@ -1,6 +1,7 @@
 //! UI raycasting system for finding UI elements at a point
 
 use glam::Vec2;
+use std::collections::HashMap;
 
 /// Entity ID type (using u64 as placeholder)
 pub type Entity = u64;
@@ -21,15 +22,66 @@ pub struct RaycastHit {
     pub position: Vec2,
 }
 
+/// Why an element under the point didn't get the pointer (see UIRaycastSystem::probe)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RaycastSkip {
+    /// Not visible (alpha 0)
+    Hidden,
+    /// A canvas group above it is faded out
+    HiddenByGroup,
+    /// Not interactable
+    Disabled,
+    /// A canvas group above it is not interactable
+    DisabledByGroup,
+    /// Its raycast_target is off
+    NotRaycastTarget,
+    /// A canvas group above it doesn't block raycasts, so the pointer passes through
+    GroupIgnoresRaycasts,
+    /// Under the element that got the pointer, which blocks raycasts
+    BlockedBy(Entity),
+    /// Under the element that got the pointer (only the topmost one does)
+    Behind(Entity),
+}
+
+impl RaycastSkip {
+    /// Reason for an inspector, with `name` naming the element in BlockedBy/Behind
+    pub fn describe(&self, name: impl Fn(Entity) -> String) -> String {
+        match self {
+            RaycastSkip::Hidden => "not visible".to_string(),
+            RaycastSkip::HiddenByGroup => "hidden by canvas group".to_string(),
+            RaycastSkip::Disabled => "disabled".to_string(),
+            RaycastSkip::DisabledByGroup => "disabled by canvas group".to_string(),
+            RaycastSkip::NotRaycastTarget => "not a raycast target".to_string(),
+            RaycastSkip::GroupIgnoresRaycasts => "canvas group doesn't block raycasts".to_string(),
+            RaycastSkip::BlockedBy(entity) => format!("blocked by {}", name(*entity)),
+            RaycastSkip::Behind(entity) => format!("behind {}", name(*entity)),
+        }
+    }
+}
+
+/// An element under a point, in raycast priority order (UIRaycastSystem::probe)
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaycastProbe {
+    pub entity: Entity,
+    pub z_order: i32,
+    pub canvas_sort_order: i32,
+    /// None for the element that gets the pointer
+    pub skipped: Option<RaycastSkip>,
+}
+
 /// UI element data needed for raycasting
 #[derive(Clone, Debug)]
 pub struct RaycastElement {
     /// Entity ID
     pub entity: Entity,
     
-    /// World-space rect bounds
+    /// Rect bounds (`RectTransform::rect`)
     pub rect: crate::Rect,
     
+    /// Maps `rect` to world space (`RectTransform::world_matrix`); points are tested
+    /// in the element's local space so rotated and scaled elements hit correctly
+    pub transform: glam::Affine2,
+    
     /// Whether this element is a raycast target
     pub raycast_target: bool,
     
@@ -47,12 +99,19 @@ pub struct RaycastElement {
     
     /// Whether the element is interactable
     pub interactable: bool,
+
+    /// Whether the element can be dropped onto other elements (`UIElement::draggable`)
+    pub draggable: bool,
 }
 
 /// UI Raycasting system
 pub struct UIRaycastSystem {
     /// Cached raycast elements (updated each frame)
     elements: Vec<RaycastElement>,
+
+    /// Canvas group state folded into each element, so probe can tell what the group
+    /// turned off from what the element did
+    group_states: HashMap<Entity, crate::CanvasGroupState>,
 }
 
 impl Default for UIRaycastSystem {
@@ -66,13 +125,38 @@ impl UIRaycastSystem {
     pub fn new() -> Self {
         Self {
             elements: Vec::new(),
+            group_states: HashMap::new(),
         }
     }
     
     /// Update the raycast elements cache
-    /// This should be called each frame with the current UI hierarchy
+    /// This should be called each frame with the current UI hierarchy, in render order
+    /// (on equal sort order and z-order, later elements are on top)
     pub fn update_elements(&mut self, elements: Vec<RaycastElement>) {
         self.elements = elements;
+        self.group_states.clear();
+    }
+
+    /// Fold this frame's resolved canvas groups into the cached elements: a faded out
+    /// group hides its elements, a non-interactable one disables them and one that
+    /// doesn't block raycasts lets the pointer through to what is behind it
+    pub fn apply_canvas_groups(&mut self, groups: &crate::CanvasGroupSystem) {
+        let states: Vec<_> = self.elements.iter().map(|element| (element.entity, groups.state(element.entity))).collect();
+        self.apply_group_states(states);
+    }
+
+    /// apply_canvas_groups with the combined group state of each element given directly
+    /// (for callers that resolve groups while walking the hierarchy)
+    pub fn apply_group_states(&mut self, states: impl IntoIterator<Item = (Entity, crate::CanvasGroupState)>) {
+        self.group_states.extend(states);
+        for element in &mut self.elements {
+            if let Some(group) = self.group_states.get(&element.entity) {
+                element.visible &= group.visible();
+                element.interactable &= group.interactable;
+                element.raycast_target &= group.blocks_raycasts;
+                element.blocks_raycasts &= group.blocks_raycasts;
+            }
+        }
     }
     
     /// Perform a raycast at the given point
@@ -80,8 +164,8 @@ impl UIRaycastSystem {
     pub fn raycast(&self, point: Vec2) -> Vec<RaycastHit> {
         let mut hits = Vec::new();
         
-        // Find all elements that contain the point
-        for element in &self.elements {
+        // Find all elements that contain the point (last drawn first, for ties)
+        for element in self.elements.iter().rev() {
             // Skip if not visible or not interactable
             if !element.visible || !element.interactable {
                 continue;
@@ -93,7 +177,7 @@ impl UIRaycastSystem {
             }
             
             // Check if point is inside the element's rect
-            if element.rect.contains(point) {
+            if element.rect.contains(element.transform.inverse().transform_point2(point)) {
                 hits.push(RaycastHit {
                     entity: element.entity,
                     z_order: element.z_order,
@@ -172,6 +256,54 @@ impl UIRaycastSystem {
         result
     }
     
+    /// Topmost hit ignoring `exclude` (the dragged element when looking for a drop target)
+    pub fn raycast_topmost_excluding(&self, point: Vec2, exclude: Entity) -> Option<RaycastHit> {
+        self.raycast(point).into_iter().find(|hit| hit.entity != exclude)
+    }
+
+    /// Every element whose rect contains `point`, in raycast priority order (topmost
+    /// first), with why each one but the pointer's target was passed over. For
+    /// inspecting why an element doesn't respond; raycast() skips the work of explaining.
+    pub fn probe(&self, point: Vec2) -> Vec<RaycastProbe> {
+        let mut under: Vec<&RaycastElement> = self.elements.iter().rev()
+            .filter(|element| element.rect.contains(element.transform.inverse().transform_point2(point)))
+            .collect();
+        // Same order as raycast(): canvas sort order, then z-order (stable, so later first on ties)
+        under.sort_by(|a, b| b.canvas_sort_order.cmp(&a.canvas_sort_order).then(b.z_order.cmp(&a.z_order)));
+
+        let mut target: Option<&RaycastElement> = None;
+        under.into_iter().map(|element| {
+            let group = self.group_states.get(&element.entity).copied().unwrap_or_default();
+            let skipped = if !element.visible {
+                Some(if group.visible() { RaycastSkip::Hidden } else { RaycastSkip::HiddenByGroup })
+            } else if !element.interactable {
+                Some(if group.interactable { RaycastSkip::Disabled } else { RaycastSkip::DisabledByGroup })
+            } else if !element.raycast_target {
+                Some(if group.blocks_raycasts { RaycastSkip::NotRaycastTarget } else { RaycastSkip::GroupIgnoresRaycasts })
+            } else {
+                match target {
+                    Some(top) if top.blocks_raycasts => Some(RaycastSkip::BlockedBy(top.entity)),
+                    Some(top) => Some(RaycastSkip::Behind(top.entity)),
+                    None => {
+                        target = Some(element);
+                        None
+                    }
+                }
+            };
+            RaycastProbe {
+                entity: element.entity,
+                z_order: element.z_order,
+                canvas_sort_order: element.canvas_sort_order,
+                skipped,
+            }
+        }).collect()
+    }
+
+    /// Cached data for an element
+    pub fn get_element(&self, entity: Entity) -> Option<&RaycastElement> {
+        self.elements.iter().find(|e| e.entity == entity)
+    }
+
     /// Get all raycast targets at a point (ignoring blocking)
     /// Useful for debugging or special cases
     pub fn get_all_at_point(&self, point: Vec2) -> Vec<Entity> {
@@ -198,12 +330,14 @@ mod tests {
         RaycastElement {
             entity,
             rect,
+            transform: glam::Affine2::IDENTITY,
             raycast_target,
             blocks_raycasts,
             z_order,
             canvas_sort_order,
             visible: true,
             interactable: true,
+            draggable: false,
         }
     }
 
@@ -379,6 +513,63 @@ mod tests {
         assert!(hits.is_empty()); // Non-interactable elements should not be hit
     }
 
+    #[test]
+    fn test_raycast_canvas_group_filtering() {
+        let mut system = UIRaycastSystem::new();
+
+        // 1 sits behind 2, whose group lets raycasts through; 3 is in a faded out group
+        system.update_elements(vec![
+            create_test_element(1, Rect::new(0.0, 0.0, 100.0, 100.0), 0, 0, true, true),
+            create_test_element(2, Rect::new(0.0, 0.0, 100.0, 100.0), 5, 0, true, true),
+            create_test_element(3, Rect::new(0.0, 0.0, 100.0, 100.0), 9, 0, true, true),
+        ]);
+        let mut groups = crate::CanvasGroupSystem::new();
+        groups.update(
+            &std::collections::HashMap::from([
+                (10, crate::UICanvasGroup { blocks_raycasts: false, ..Default::default() }),
+                (20, crate::UICanvasGroup { alpha: 0.0, ..Default::default() }),
+            ]),
+            &std::collections::HashMap::from([(2, 10), (3, 20)]),
+            [1, 2, 3],
+        );
+        system.apply_canvas_groups(&groups);
+
+        let hits = system.raycast_all_unblocked(Vec2::new(50.0, 50.0));
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].entity, 1);
+    }
+
+    #[test]
+    fn test_probe_explains_skipped_elements() {
+        let mut system = UIRaycastSystem::new();
+        let rect = Rect::new(0.0, 0.0, 100.0, 100.0);
+        let mut hidden = create_test_element(5, rect, 9, 0, true, true);
+        hidden.visible = false;
+        system.update_elements(vec![
+            create_test_element(1, rect, 0, 0, true, true),
+            create_test_element(2, rect, 2, 0, true, true),
+            create_test_element(3, rect, 4, 0, false, true),
+            create_test_element(4, rect, 6, 0, true, true),
+            hidden,
+            // Elsewhere: not listed at all
+            create_test_element(6, Rect::new(200.0, 0.0, 10.0, 10.0), 9, 0, true, true),
+        ]);
+        // 4's group lets the pointer through
+        system.apply_group_states([(4, crate::CanvasGroupState { blocks_raycasts: false, ..Default::default() })]);
+
+        let probe = system.probe(Vec2::new(50.0, 50.0));
+        let reasons: Vec<_> = probe.iter().map(|hit| (hit.entity, hit.skipped)).collect();
+        assert_eq!(reasons, vec![
+            (5, Some(RaycastSkip::Hidden)),
+            (4, Some(RaycastSkip::GroupIgnoresRaycasts)),
+            (3, Some(RaycastSkip::NotRaycastTarget)),
+            (2, None),
+            (1, Some(RaycastSkip::BlockedBy(2))),
+        ]);
+        // The target is what raycast_topmost picks
+        assert_eq!(system.raycast_topmost(Vec2::new(50.0, 50.0)).map(|hit| hit.entity), Some(2));
+    }
+
     #[test]
     fn test_get_all_at_point() {
         let mut system = UIRaycastSystem::new();
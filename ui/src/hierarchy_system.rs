@@ -280,6 +280,60 @@ impl UIHierarchySystem {
         }
     }
 
+    /// Position of `entity` among its parent's children (None for roots)
+    pub fn get_sibling_index(
+        &self,
+        entity: Entity,
+        parents: &HashMap<Entity, Entity>,
+        children: &HashMap<Entity, Vec<Entity>>,
+    ) -> Option<usize> {
+        let parent = parents.get(&entity)?;
+        children.get(parent)?.iter().position(|&child| child == entity)
+    }
+
+    /// Move `entity` to `index` among its siblings (clamped to the last position)
+    ///
+    /// Sibling order is draw order (later siblings render on top) and the order
+    /// layout groups place children in. Returns false if `entity` has no parent.
+    pub fn set_sibling_index(
+        &self,
+        entity: Entity,
+        index: usize,
+        parents: &HashMap<Entity, Entity>,
+        children: &mut HashMap<Entity, Vec<Entity>>,
+    ) -> bool {
+        let Some(siblings) = parents.get(&entity).and_then(|parent| children.get_mut(parent)) else {
+            return false;
+        };
+        let Some(current) = siblings.iter().position(|&child| child == entity) else {
+            return false;
+        };
+        siblings.remove(current);
+        let index = index.min(siblings.len());
+        siblings.insert(index, entity);
+        true
+    }
+
+    /// Move `entity` before its siblings (drawn first, behind them)
+    pub fn set_as_first_sibling(
+        &self,
+        entity: Entity,
+        parents: &HashMap<Entity, Entity>,
+        children: &mut HashMap<Entity, Vec<Entity>>,
+    ) -> bool {
+        self.set_sibling_index(entity, 0, parents, children)
+    }
+
+    /// Move `entity` after its siblings (drawn last, on top - e.g. for popups)
+    pub fn set_as_last_sibling(
+        &self,
+        entity: Entity,
+        parents: &HashMap<Entity, Entity>,
+        children: &mut HashMap<Entity, Vec<Entity>>,
+    ) -> bool {
+        self.set_sibling_index(entity, usize::MAX, parents, children)
+    }
+
     /// Handle entity destruction propagation
     ///
     /// When a UI element is destroyed, this returns a list of all descendant entities
@@ -326,16 +380,19 @@ impl Default for UIHierarchySystem {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Color;
 
     fn create_test_ui_element() -> UIElement {
         UIElement {
             raycast_target: true,
             blocks_raycasts: true,
             z_order: 0,
-            color: [1.0, 1.0, 1.0, 1.0],
+            color: Color::rgba(1.0, 1.0, 1.0, 1.0),
             alpha: 1.0,
             interactable: true,
             ignore_layout: false,
+            draggable: false,
+            pass_through: false,
             canvas_entity: None,
         }
     }
@@ -483,6 +540,44 @@ mod tests {
         assert_eq!(render_order[3], child3);
     }
 
+    #[test]
+    fn test_sibling_index_changes_render_order() {
+        let system = UIHierarchySystem::new();
+        let mut ui_elements = HashMap::new();
+        let mut canvases = HashMap::new();
+        let mut parents = HashMap::new();
+        let mut children = HashMap::new();
+
+        let canvas = 1;
+        let (popup, panel, label) = (2, 3, 4);
+        canvases.insert(canvas, create_test_canvas());
+        for entity in [canvas, popup, panel, label] {
+            ui_elements.insert(entity, create_test_ui_element());
+        }
+        for child in [popup, panel, label] {
+            parents.insert(child, canvas);
+        }
+        children.insert(canvas, vec![popup, panel, label]);
+
+        // Equal z-order: sibling order decides, so the popup moves on top
+        assert!(system.set_as_last_sibling(popup, &parents, &mut children));
+        assert_eq!(system.get_sibling_index(popup, &parents, &children), Some(2));
+        assert_eq!(
+            system.get_render_order(&ui_elements, &canvases, &parents, &children),
+            vec![canvas, panel, label, popup]
+        );
+
+        // Out-of-range indices clamp to the end
+        assert!(system.set_sibling_index(panel, 10, &parents, &mut children));
+        assert_eq!(children[&canvas], vec![label, popup, panel]);
+        assert!(system.set_as_first_sibling(panel, &parents, &mut children));
+        assert_eq!(children[&canvas], vec![panel, label, popup]);
+
+        // Roots have no siblings to reorder
+        assert!(!system.set_as_last_sibling(canvas, &parents, &mut children));
+        assert_eq!(system.get_sibling_index(canvas, &parents, &children), None);
+    }
+
     #[test]
     fn test_descendants_for_destruction() {
         let system = UIHierarchySystem::new();
@@ -35,6 +35,7 @@ impl HudToUIPrefabConverter {
             panel: None,
             slider: None,
             toggle: None,
+            toggle_group: None,
             dropdown: None,
             input_field: None,
             scroll_view: None,
@@ -42,6 +43,10 @@ impl HudToUIPrefabConverter {
             horizontal_layout: None,
             vertical_layout: None,
             grid_layout: None,
+            virtual_input: None,
+            tooltip: None,
+            safe_area: None,
+            canvas_group: None,
             children: Vec::new(),
         };
         
@@ -54,6 +59,7 @@ impl HudToUIPrefabConverter {
         UIPrefab {
             name: hud.name.clone(),
             root,
+            canvas_scaler: None,
         }
     }
     
@@ -97,6 +103,7 @@ impl HudToUIPrefabConverter {
             panel,
             slider: None,
             toggle: None,
+            toggle_group: None,
             dropdown: None,
             input_field: None,
             scroll_view: None,
@@ -104,6 +111,10 @@ impl HudToUIPrefabConverter {
             horizontal_layout: None,
             vertical_layout: None,
             grid_layout: None,
+            virtual_input: None,
+            tooltip: None,
+            safe_area: None,
+            canvas_group: None,
             children: Vec::new(),
         };
         
@@ -155,6 +166,8 @@ impl HudToUIPrefabConverter {
                 alpha: background_color[3],
                 interactable: false,
                 ignore_layout: false,
+                draggable: false,
+                pass_through: false,
                 canvas_entity: None,
             },
             image: Some(UIImage {
@@ -171,6 +184,7 @@ impl HudToUIPrefabConverter {
             panel: None,
             slider: None,
             toggle: None,
+            toggle_group: None,
             dropdown: None,
             input_field: None,
             scroll_view: None,
@@ -178,6 +192,10 @@ impl HudToUIPrefabConverter {
             horizontal_layout: None,
             vertical_layout: None,
             grid_layout: None,
+            virtual_input: None,
+            tooltip: None,
+            safe_area: None,
+            canvas_group: None,
             children: Vec::new(),
         };
         
@@ -193,6 +211,8 @@ impl HudToUIPrefabConverter {
                 alpha: color[3],
                 interactable: false,
                 ignore_layout: false,
+                draggable: false,
+                pass_through: false,
                 canvas_entity: None,
             },
             image: Some(UIImage {
@@ -209,6 +229,7 @@ impl HudToUIPrefabConverter {
             panel: None,
             slider: None,
             toggle: None,
+            toggle_group: None,
             dropdown: None,
             input_field: None,
             scroll_view: None,
@@ -216,6 +237,10 @@ impl HudToUIPrefabConverter {
             horizontal_layout: None,
             vertical_layout: None,
             grid_layout: None,
+            virtual_input: None,
+            tooltip: None,
+            safe_area: None,
+            canvas_group: None,
             children: Vec::new(),
         };
         
@@ -229,6 +254,7 @@ impl HudToUIPrefabConverter {
             panel: None,
             slider: None,
             toggle: None,
+            toggle_group: None,
             dropdown: None,
             input_field: None,
             scroll_view: None,
@@ -236,6 +262,10 @@ impl HudToUIPrefabConverter {
             horizontal_layout: None,
             vertical_layout: None,
             grid_layout: None,
+            virtual_input: None,
+            tooltip: None,
+            safe_area: None,
+            canvas_group: None,
             children: vec![background, fill],
         }
     }
@@ -276,6 +306,7 @@ impl HudToUIPrefabConverter {
             rotation: 0.0,
             scale: Vec2::ONE,
             world_corners: [Vec2::ZERO; 4],
+            world_matrix: glam::Affine2::IDENTITY,
             rect: crate::types::Rect::default(),
             dirty: true,
         }
@@ -480,7 +511,7 @@ impl From<HudElementTypeWrapper> for HudElementType {
             "Image" => {
                 let texture = wrapper.data["texture"].as_str().unwrap().to_string();
                 let tint = serde_json::from_value(wrapper.data["tint"].clone())
-                    .unwrap_or([1.0, 1.0, 1.0, 1.0]);
+                    .unwrap_or(Color::WHITE);
                 HudElementType::Image { texture, tint }
             }
             "Container" => {
@@ -16,6 +16,13 @@ pub struct InputFieldSystem {
     
     /// Pending text changes (entity -> new text)
     pending_changes: HashMap<Entity, String>,
+
+    /// Text of each field edited this frame as it was before the first edit
+    /// (value-changed fires once per frame, and not at all if the edits cancel out)
+    frame_start_text: HashMap<Entity, String>,
+
+    /// Submit events waiting for end_frame
+    pending_submits: Vec<UIEvent>,
 }
 
 impl Default for InputFieldSystem {
@@ -30,6 +37,8 @@ impl InputFieldSystem {
         Self {
             focused_field: None,
             pending_changes: HashMap::new(),
+            frame_start_text: HashMap::new(),
+            pending_submits: Vec::new(),
         }
     }
     
@@ -102,6 +111,7 @@ impl InputFieldSystem {
                 // Insert character at caret position
                 let caret_pos = field.caret_position as usize;
                 if caret_pos <= field.text.len() {
+                    self.frame_start_text.entry(focused).or_insert_with(|| field.text.clone());
                     field.text.insert(caret_pos, character);
                     field.caret_position += 1;
                     field.selection_anchor = field.caret_position;
@@ -124,6 +134,7 @@ impl InputFieldSystem {
                 if field.caret_position > 0 {
                     let caret_pos = field.caret_position as usize;
                     if caret_pos <= field.text.len() {
+                        self.frame_start_text.entry(focused).or_insert_with(|| field.text.clone());
                         field.text.remove(caret_pos - 1);
                         field.caret_position -= 1;
                         field.selection_anchor = field.caret_position;
@@ -146,12 +157,56 @@ impl InputFieldSystem {
                 
                 let caret_pos = field.caret_position as usize;
                 if caret_pos < field.text.len() {
+                    self.frame_start_text.entry(focused).or_insert_with(|| field.text.clone());
                     field.text.remove(caret_pos);
                 }
             }
         }
     }
     
+    /// Handle Enter for the focused field: single-line (and multi-line submit) fields
+    /// submit and lose focus, multi-line newline fields get a line break
+    pub fn handle_submit(
+        &mut self,
+        input_fields: &mut HashMap<Entity, UIInputField>,
+    ) {
+        let Some(focused) = self.focused_field else {
+            return;
+        };
+        let Some(field) = input_fields.get_mut(&focused) else {
+            return;
+        };
+        match field.line_type {
+            LineType::SingleLine | LineType::MultiLineSubmit => {
+                self.pending_submits.push(UIEvent::InputSubmit(focused, field.text.clone()));
+                field.is_focused = false;
+                self.focused_field = None;
+            }
+            LineType::MultiLineNewline => self.handle_text_input('\n', input_fields),
+        }
+    }
+
+    /// Emit this frame's value-changed (at most one per field) and submit events, in
+    /// that order. Call once per frame after all input handling.
+    pub fn end_frame(
+        &mut self,
+        input_fields: &HashMap<Entity, UIInputField>,
+        event_dispatcher: &UIEventDispatcher,
+    ) -> Vec<UIEvent> {
+        let mut edited: Vec<_> = self.frame_start_text.drain().collect();
+        edited.sort_by_key(|(entity, _)| *entity);
+        let mut events: Vec<UIEvent> = edited
+            .into_iter()
+            .filter_map(|(entity, start_text)| {
+                let field = input_fields.get(&entity)?;
+                (field.text != start_text).then(|| UIEvent::InputValueChanged(entity, field.text.clone()))
+            })
+            .collect();
+        events.append(&mut self.pending_submits);
+        event_dispatcher.dispatch_events(&events);
+        events
+    }
+    
     /// Move caret left
     pub fn move_caret_left(
         &mut self,
@@ -280,7 +335,7 @@ impl InputFieldSystem {
         }
     }
     
-    /// Set text programmatically
+    /// Set text programmatically (no value-changed event)
     pub fn set_text(
         entity: Entity,
         text: String,
@@ -341,18 +396,21 @@ impl InputFieldSystem {
     pub fn clear(&mut self) {
         self.focused_field = None;
         self.pending_changes.clear();
+        self.frame_start_text.clear();
+        self.pending_submits.clear();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{InputType, KeyboardType, LineType};
+    use crate::{Color, InputType, KeyboardType, LineType};
 
     fn create_test_input_field() -> UIInputField {
         UIInputField {
             text_component: Some(2),
             placeholder: Some(3),
+            placeholder_text: String::new(),
             text: String::new(),
             character_limit: 0,
             content_type: ContentType::Standard,
@@ -362,7 +420,7 @@ mod tests {
             character_validation: CharacterValidation::None,
             caret_blink_rate: 0.85,
             caret_width: 1,
-            selection_color: [0.65, 0.8, 1.0, 0.75],
+            selection_color: Color::rgba(0.65, 0.8, 1.0, 0.75),
             read_only: false,
             on_value_changed: None,
             on_end_edit: None,
@@ -377,10 +435,12 @@ mod tests {
             raycast_target: true,
             blocks_raycasts: true,
             z_order: 0,
-            color: [1.0, 1.0, 1.0, 1.0],
+            color: Color::rgba(1.0, 1.0, 1.0, 1.0),
             alpha: 1.0,
             interactable,
             ignore_layout: false,
+            draggable: false,
+            pass_through: false,
             canvas_entity: None,
         }
     }
@@ -617,4 +677,43 @@ mod tests {
         assert!(!input_fields.get(&1).unwrap().is_focused);
         assert_eq!(system.get_focused_field(), None);
     }
+
+    #[test]
+    fn test_value_changed_once_per_frame_and_submit() {
+        let mut system = InputFieldSystem::new();
+        let mut input_fields = HashMap::new();
+        let dispatcher = UIEventDispatcher::new();
+        input_fields.insert(1, create_test_input_field());
+        system.set_focus(1, &mut input_fields);
+
+        // A burst of keystrokes in one frame: one event with the final text
+        for character in "Ada".chars() {
+            system.handle_text_input(character, &mut input_fields);
+        }
+        let events = system.end_frame(&input_fields, &dispatcher);
+        assert!(matches!(events.as_slice(), [UIEvent::InputValueChanged(1, text)] if text == "Ada"));
+
+        // Edits that cancel out within a frame report nothing
+        system.handle_text_input('x', &mut input_fields);
+        system.handle_backspace(&mut input_fields);
+        assert!(system.end_frame(&input_fields, &dispatcher).is_empty());
+
+        // Enter submits a single-line field and drops focus
+        system.handle_text_input('!', &mut input_fields);
+        system.handle_submit(&mut input_fields);
+        let events = system.end_frame(&input_fields, &dispatcher);
+        assert!(matches!(
+            events.as_slice(),
+            [UIEvent::InputValueChanged(1, _), UIEvent::InputSubmit(1, text)] if text == "Ada!"
+        ));
+        assert_eq!(system.get_focused_field(), None);
+        assert!(!input_fields[&1].is_focused);
+
+        // Multi-line newline fields keep focus and get a line break
+        input_fields.get_mut(&1).unwrap().line_type = LineType::MultiLineNewline;
+        system.set_focus(1, &mut input_fields);
+        system.handle_submit(&mut input_fields);
+        assert_eq!(input_fields[&1].text, "Ada!\n");
+        assert_eq!(system.get_focused_field(), Some(1));
+    }
 }
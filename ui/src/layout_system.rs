@@ -48,6 +48,8 @@ impl LayoutSystem {
         grid_layouts: &HashMap<Entity, GridLayoutGroup>,
         children: &HashMap<Entity, Vec<Entity>>,
     ) {
+        profiler::profile_scope!("ui.layout");
+
         // Process horizontal layouts
         for (&entity, layout) in horizontal_layouts.iter() {
             if let Some(child_list) = children.get(&entity) {
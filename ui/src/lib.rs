@@ -28,6 +28,7 @@ pub use types::{Color, Rect};
 // Module declarations
 pub mod canvas;
 pub mod canvas_system;
+pub mod canvas_space;
 pub mod rect_transform;
 pub mod rect_transform_system;
 pub mod hierarchy_system;
@@ -37,6 +38,11 @@ pub mod slider_system;
 pub mod toggle_system;
 pub mod dropdown_system;
 pub mod input_field_system;
+pub mod tooltip_system;
+pub mod canvas_group_system;
+pub mod drag_drop_system;
+pub mod popup_manager;
+pub mod localization;
 pub mod components;
 pub mod layout;
 pub mod events;
@@ -49,8 +55,9 @@ pub mod lua_bindings;
 pub mod hud_converter;
 
 // Re-export main types for convenience
-pub use canvas::{Canvas, CanvasRenderMode, CanvasScaler, ScaleMode};
+pub use canvas::{Canvas, CanvasRenderMode, CanvasScaler, ScaleMode, BASE_DPI};
 pub use canvas_system::CanvasSystem;
+pub use canvas_space::{CanvasSpace, CanvasPoint};
 pub use rect_transform::RectTransform;
 pub use rect_transform_system::{RectTransformSystem, Entity};
 pub use hierarchy_system::UIHierarchySystem;
@@ -60,6 +67,11 @@ pub use slider_system::SliderSystem;
 pub use toggle_system::ToggleSystem;
 pub use dropdown_system::DropdownSystem;
 pub use input_field_system::InputFieldSystem;
+pub use tooltip_system::{TooltipSystem, ActiveTooltip, place_tooltip};
+pub use canvas_group_system::CanvasGroupSystem;
+pub use drag_drop_system::{DragDropSystem, ActiveDrag};
+pub use popup_manager::{PopupManager, ModalOptions, Modal, ClosedModal};
+pub use localization::Localization;
 
 // Re-export component types
 pub use components::{
@@ -69,10 +81,15 @@ pub use components::{
     UIButton, ButtonState, ButtonTransition,
     UIPanel,
     UISlider, SliderDirection,
-    UIToggle, ToggleTransition,
+    UIToggle, ToggleTransition, ToggleGroupRef, UIToggleGroup,
     UIDropdown, DropdownOption,
     UIInputField, ContentType, LineType, InputType, KeyboardType, CharacterValidation,
     UIScrollView, MovementType,
+    UIVirtualInput,
+    UITooltip, TooltipPlacement,
+    UISafeArea,
+    UICanvasGroup, CanvasGroupState,
+    InteractableState, StateTransition,
 };
 
 // Re-export layout types
@@ -86,15 +103,17 @@ pub use layout::{
 // Re-export event types
 pub use events::{
     UIEvent, UIEventHandler, UIEventListener, UIEventType,
-    UIRaycastSystem, RaycastHit, RaycastElement,
+    UIRaycastSystem, RaycastHit, RaycastElement, RaycastProbe, RaycastSkip,
     UIInputHandler, InputState, MouseButton,
-    UIEventDispatcher, ButtonStateManager, EventCallback,
+    UIEventDispatcher, ButtonStateManager, EventCallback, DispatchRoute,
+    UIEventBinding, UIAction,
+    UIEventLog, UIEventLogEntry, UIEventLogFilter,
 };
 
 // Re-export animation types
 pub use animation::{
     UIAnimation, AnimatedProperty, AnimationValue,
-    EasingFunction, LoopMode,
+    EasingFunction, LoopMode, AnimationCurve, CurveKey,
 };
 
 // Re-export rendering types
@@ -103,6 +122,7 @@ pub use rendering::{
     ClipRegion, ViewportClippingSystem,
     MaskingSystem, MaskState,
     TextRenderer, TextLayout, PositionedGlyph, Font, FontCache, Glyph,
+    GlyphAtlasCache, GlyphAtlas, AtlasGlyph, GlyphRenderMode,
 };
 
 // Re-export prefab types
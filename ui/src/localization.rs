@@ -0,0 +1,344 @@
+//! Localization
+//!
+//! Per-language string tables from the project's `localization/` folder:
+//! `<language>.json` files holding a flat `{"key": "text"}` object, and CSV files whose
+//! header row is `key,<language>,<language>,...`. UIText elements with a
+//! `localization_key` show the active language's string; keys missing from it fall back
+//! to the default language.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Folder inside the project holding the string tables
+pub const LOCALIZATION_DIR: &str = "localization";
+
+/// Fallback language when the project doesn't choose one
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+#[derive(Clone, Debug)]
+pub struct Localization {
+    /// language -> key -> text
+    tables: HashMap<String, HashMap<String, String>>,
+    default_language: String,
+    language: String,
+
+    /// Bumped when the language or the tables change (texts need resolving again)
+    revision: u64,
+
+    /// Keys already reported missing for the current language
+    reported: BTreeSet<String>,
+    unreported: BTreeSet<String>,
+
+    /// Extra fonts shipped in `localization/fonts/` (glyph coverage for the languages)
+    font_files: Vec<PathBuf>,
+}
+
+impl Default for Localization {
+    fn default() -> Self {
+        Self {
+            tables: HashMap::new(),
+            default_language: DEFAULT_LANGUAGE.to_string(),
+            language: DEFAULT_LANGUAGE.to_string(),
+            revision: 0,
+            reported: BTreeSet::new(),
+            unreported: BTreeSet::new(),
+            font_files: Vec::new(),
+        }
+    }
+}
+
+impl Localization {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every table in `<project>/localization/`, or no tables if the project has none
+    pub fn load_for_project(project_path: &Path) -> std::io::Result<Self> {
+        let mut localization = Self::default();
+        let dir = project_path.join(LOCALIZATION_DIR);
+        if !dir.is_dir() {
+            return Ok(localization);
+        }
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(&dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect();
+        paths.sort();
+        let invalid = |path: &Path, e: String| {
+            std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))
+        };
+        for path in paths {
+            let extension = path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase);
+            match extension.as_deref() {
+                Some("json") => {
+                    let language = path.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default().to_string();
+                    let json = std::fs::read_to_string(&path)?;
+                    localization.add_json(&language, &json).map_err(|e| invalid(&path, e))?;
+                }
+                Some("csv") => {
+                    let csv = std::fs::read_to_string(&path)?;
+                    localization.add_csv(&csv).map_err(|e| invalid(&path, e))?;
+                }
+                _ => {}
+            }
+        }
+
+        let fonts = dir.join("fonts");
+        if fonts.is_dir() {
+            for entry in std::fs::read_dir(fonts)?.flatten() {
+                let path = entry.path();
+                let extension = path.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase);
+                if matches!(extension.as_deref(), Some("ttf" | "otf")) {
+                    localization.font_files.push(path);
+                }
+            }
+            localization.font_files.sort();
+        }
+
+        // Without an English table, the first language is the fallback
+        if !localization.tables.contains_key(DEFAULT_LANGUAGE) {
+            if let Some(first) = localization.languages().first().map(|first| first.to_string()) {
+                localization.default_language = first.clone();
+                localization.language = first;
+            }
+        }
+        Ok(localization)
+    }
+
+    /// Add (or override) strings for a language
+    pub fn add_table(&mut self, language: &str, strings: HashMap<String, String>) {
+        self.tables.entry(language.to_string()).or_default().extend(strings);
+        self.revision += 1;
+    }
+
+    /// Add a `{"key": "text"}` table
+    pub fn add_json(&mut self, language: &str, json: &str) -> Result<(), String> {
+        let strings: HashMap<String, String> = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        self.add_table(language, strings);
+        Ok(())
+    }
+
+    /// Add a CSV table: header `key,<language>,...`, then one row per key. Fields may be
+    /// quoted ("a, b" / "say ""hi""") and quoted fields may span lines.
+    pub fn add_csv(&mut self, csv: &str) -> Result<(), String> {
+        let mut rows = parse_csv(csv).into_iter();
+        let header = rows.next().ok_or("empty CSV")?;
+        if header.len() < 2 {
+            return Err("CSV header needs a key column and at least one language".to_string());
+        }
+
+        let mut tables: Vec<HashMap<String, String>> = vec![HashMap::new(); header.len() - 1];
+        for (line, row) in rows.enumerate() {
+            let Some(key) = row.first().filter(|key| !key.is_empty()) else { continue };
+            if row.len() > header.len() {
+                return Err(format!("row {} has {} columns, header has {}", line + 2, row.len(), header.len()));
+            }
+            // Empty cells are untranslated: leave them to the fallback
+            for (column, text) in row.iter().enumerate().skip(1).filter(|(_, text)| !text.is_empty()) {
+                tables[column - 1].insert(key.clone(), text.clone());
+            }
+        }
+        for (language, strings) in header[1..].iter().zip(tables) {
+            self.add_table(language.trim(), strings);
+        }
+        Ok(())
+    }
+
+    /// Languages with a table, sorted
+    pub fn languages(&self) -> Vec<&str> {
+        let mut languages: Vec<&str> = self.tables.keys().map(String::as_str).collect();
+        languages.sort_unstable();
+        languages
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    /// Switch the active language (needs a table once any are loaded)
+    pub fn set_language(&mut self, language: &str) -> Result<(), String> {
+        if !self.tables.is_empty() && !self.tables.contains_key(language) {
+            return Err(format!("No string table for language '{}' (have: {})", language, self.languages().join(", ")));
+        }
+        if language != self.language {
+            self.language = language.to_string();
+            self.reported.clear();
+            self.unreported.clear();
+            self.revision += 1;
+        }
+        Ok(())
+    }
+
+    pub fn default_language(&self) -> &str {
+        &self.default_language
+    }
+
+    pub fn set_default_language(&mut self, language: &str) {
+        self.default_language = language.to_string();
+        self.revision += 1;
+    }
+
+    /// Changes whenever resolved texts may differ
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// String for `key` in the active language (no fallback)
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tables.get(&self.language)?.get(key).map(String::as_str)
+    }
+
+    /// String for `key` with `{name}` parameters filled in. Falls back to the default
+    /// language, then to the key itself; misses are reported once by `take_missing_warning`.
+    pub fn resolve(&mut self, key: &str, params: &HashMap<String, String>) -> String {
+        let text = match self.get(key) {
+            Some(text) => text,
+            None => {
+                if !self.reported.contains(key) {
+                    self.unreported.insert(key.to_string());
+                }
+                self.tables
+                    .get(&self.default_language)
+                    .and_then(|table| table.get(key))
+                    .map_or(key, String::as_str)
+            }
+        };
+        format_params(text, params)
+    }
+
+    /// Keys missing from the active language since the last call, as one warning
+    pub fn take_missing_warning(&mut self) -> Option<String> {
+        if self.unreported.is_empty() {
+            return None;
+        }
+        let keys: Vec<String> = std::mem::take(&mut self.unreported).into_iter().collect();
+        self.reported.extend(keys.iter().cloned());
+        Some(format!(
+            "Localization: {} key(s) missing for '{}' (using '{}'): {}",
+            keys.len(),
+            self.language,
+            self.default_language,
+            keys.join(", ")
+        ))
+    }
+
+    /// Fonts from `localization/fonts/`
+    pub fn font_files(&self) -> &[PathBuf] {
+        &self.font_files
+    }
+}
+
+/// Replace `{name}` with `params["name"]`; other braces stay as they are
+pub fn format_params(text: &str, params: &HashMap<String, String>) -> String {
+    if params.is_empty() {
+        return text.to_string();
+    }
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        result.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        match after.find('}').and_then(|close| params.get(&after[..close]).map(|value| (close, value))) {
+            Some((close, value)) => {
+                result.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn parse_csv(csv: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = csv.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|field| !field.is_empty()));
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_csv_tables_and_fallback() {
+        let mut loc = Localization::new();
+        loc.add_csv("key,en,th\r\nmenu.play,Play,เล่น\nmenu.quit,\"Quit, now\",\ncoins,\"Collected {count}/{total}\",เก็บได้ {count}/{total}\n").unwrap();
+        assert_eq!(loc.languages(), ["en", "th"]);
+        assert_eq!(loc.resolve("menu.quit", &HashMap::new()), "Quit, now");
+
+        let revision = loc.revision();
+        loc.set_language("th").unwrap();
+        assert!(loc.revision() > revision);
+        assert_eq!(loc.resolve("menu.play", &HashMap::new()), "เล่น");
+        assert_eq!(loc.resolve("coins", &params(&[("count", "3"), ("total", "10")])), "เก็บได้ 3/10");
+
+        // Untranslated: English, reported once
+        assert_eq!(loc.resolve("menu.quit", &HashMap::new()), "Quit, now");
+        assert_eq!(loc.resolve("menu.missing", &HashMap::new()), "menu.missing");
+        let warning = loc.take_missing_warning().unwrap();
+        assert!(warning.contains("'th'") && warning.contains("menu.missing, menu.quit"));
+        loc.resolve("menu.quit", &HashMap::new());
+        assert_eq!(loc.take_missing_warning(), None);
+
+        assert!(loc.set_language("de").is_err());
+    }
+
+    #[test]
+    fn test_format_params_leaves_unknown_braces() {
+        let values = params(&[("n", "5")]);
+        assert_eq!(format_params("{n} of {m} {", &values), "5 of {m} {");
+        assert_eq!(format_params("{{n}}", &values), "{5}");
+    }
+
+    #[test]
+    fn test_load_for_project_reads_json_and_csv() {
+        let project = tempfile::tempdir().unwrap();
+        let dir = project.path().join(LOCALIZATION_DIR);
+        std::fs::create_dir_all(dir.join("fonts")).unwrap();
+        std::fs::write(dir.join("th.json"), r#"{"hello": "สวัสดี"}"#).unwrap();
+        std::fs::write(dir.join("menu.csv"), "key,th\nbye,ลาก่อน\n").unwrap();
+        std::fs::write(dir.join("fonts/NotoSansThai.ttf"), []).unwrap();
+
+        let loc = Localization::load_for_project(project.path()).unwrap();
+        // No English table: Thai is both default and active
+        assert_eq!(loc.language(), "th");
+        assert_eq!(loc.get("hello"), Some("สวัสดี"));
+        assert_eq!(loc.get("bye"), Some("ลาก่อน"));
+        assert_eq!(loc.font_files().len(), 1);
+
+        let empty = Localization::load_for_project(&project.path().join("missing")).unwrap();
+        assert!(empty.languages().is_empty());
+    }
+}
@@ -318,23 +318,5 @@ pub fn inject_query_api<'lua, 'scope>(
 
 /// Helper function to parse easing function name
 fn parse_easing(name: &str) -> EasingFunction {
-    match name {
-        "Linear" => EasingFunction::Linear,
-        "EaseInQuad" => EasingFunction::EaseInQuad,
-        "EaseOutQuad" => EasingFunction::EaseOutQuad,
-        "EaseInOutQuad" => EasingFunction::EaseInOutQuad,
-        "EaseInCubic" => EasingFunction::EaseInCubic,
-        "EaseOutCubic" => EasingFunction::EaseOutCubic,
-        "EaseInOutCubic" => EasingFunction::EaseInOutCubic,
-        "EaseInSine" => EasingFunction::EaseInSine,
-        "EaseOutSine" => EasingFunction::EaseOutSine,
-        "EaseInOutSine" => EasingFunction::EaseInOutSine,
-        "EaseInBack" => EasingFunction::EaseInBack,
-        "EaseOutBack" => EasingFunction::EaseOutBack,
-        "EaseInOutBack" => EasingFunction::EaseInOutBack,
-        "EaseInBounce" => EasingFunction::EaseInBounce,
-        "EaseOutBounce" => EasingFunction::EaseOutBounce,
-        "EaseInOutBounce" => EasingFunction::EaseInOutBounce,
-        _ => EasingFunction::Linear,
-    }
+    EasingFunction::from_name(name).unwrap_or(EasingFunction::Linear)
 }
@@ -32,6 +32,27 @@ mod animation;
 pub use properties::inject_property_api;
 pub use animation::{inject_animation_api, inject_event_api, inject_query_api};
 
+/// Read a color argument from Lua: a `{r, g, b, a}` table (missing channels default
+/// to 1), an array `{r, g, b[, a]}`, or a hex string like `"#ff8800"` / `"ff880080"`
+pub fn color_from_lua(value: mlua::Value) -> mlua::Result<Color> {
+    match value {
+        mlua::Value::String(hex) => Color::from_hex(hex.to_str()?).map_err(mlua::Error::RuntimeError),
+        mlua::Value::Table(table) => {
+            if table.contains_key(1)? {
+                let channel = |i: usize| table.get::<_, Option<f32>>(i).map(|c| c.unwrap_or(1.0));
+                Ok(Color::rgba(channel(1)?, channel(2)?, channel(3)?, channel(4)?))
+            } else {
+                let channel = |name: &str| table.get::<_, Option<f32>>(name).map(|c| c.unwrap_or(1.0));
+                Ok(Color::rgba(channel("r")?, channel("g")?, channel("b")?, channel("a")?))
+            }
+        }
+        other => Err(mlua::Error::RuntimeError(format!(
+            "expected a color table or hex string, got {}",
+            other.type_name()
+        ))),
+    }
+}
+
 /// UI Lua API manager
 pub struct UILuaBindings {
     /// Registered event callbacks (entity -> event_type -> callback_name)
@@ -165,10 +186,12 @@ impl UILuaBindings {
                 raycast_target: false,
                 blocks_raycasts: true,
                 z_order: 0,
-                color: [1.0, 1.0, 1.0, 1.0],
+                color: Color::rgba(1.0, 1.0, 1.0, 1.0),
                 alpha: 1.0,
                 interactable: true,
                 ignore_layout: false,
+                draggable: false,
+                pass_through: false,
                 canvas_entity: Some(ui_entity),
             };
 
@@ -189,7 +212,7 @@ impl UILuaBindings {
         let create_image = scope.create_function_mut(|_lua, args: Table| {
             let parent = args.get::<_, Option<EcsEntity>>("parent")?;
             let sprite = args.get::<_, Option<String>>("sprite")?;
-            let color = args.get::<_, Option<Table>>("color")?;
+            let color = args.get::<_, Option<mlua::Value>>("color")?.map(color_from_lua).transpose()?;
 
             let ecs_entity = world.borrow_mut().spawn();
             let _ui_entity = ecs_entity as UIEntity;
@@ -206,19 +229,12 @@ impl UILuaBindings {
                 raycast_target: true,
                 blocks_raycasts: true,
                 z_order: 0,
-                color: if let Some(c) = color {
-                    [
-                        c.get::<_, f32>("r").unwrap_or(1.0),
-                        c.get::<_, f32>("g").unwrap_or(1.0),
-                        c.get::<_, f32>("b").unwrap_or(1.0),
-                        c.get::<_, f32>("a").unwrap_or(1.0),
-                    ]
-                } else {
-                    [1.0, 1.0, 1.0, 1.0]
-                },
+                color: color.unwrap_or(Color::WHITE),
                 alpha: 1.0,
                 interactable: true,
                 ignore_layout: false,
+                draggable: false,
+                pass_through: false,
                 canvas_entity: parent.map(|e| e as UIEntity),
             };
 
@@ -251,7 +267,7 @@ impl UILuaBindings {
             let parent = args.get::<_, Option<EcsEntity>>("parent")?;
             let text = args.get::<_, Option<String>>("text")?.unwrap_or_default();
             let font_size = args.get::<_, Option<f32>>("font_size")?.unwrap_or(14.0);
-            let color = args.get::<_, Option<Table>>("color")?;
+            let color = args.get::<_, Option<mlua::Value>>("color")?.map(color_from_lua).transpose()?;
 
             let ecs_entity = world.borrow_mut().spawn();
             let _ui_entity = ecs_entity as UIEntity;
@@ -264,16 +280,7 @@ impl UILuaBindings {
             );
 
             // Create UIElement
-            let ui_element_color = if let Some(c) = color {
-                [
-                    c.get::<_, f32>("r").unwrap_or(1.0),
-                    c.get::<_, f32>("g").unwrap_or(1.0),
-                    c.get::<_, f32>("b").unwrap_or(1.0),
-                    c.get::<_, f32>("a").unwrap_or(1.0),
-                ]
-            } else {
-                [0.0, 0.0, 0.0, 1.0]
-            };
+            let ui_element_color = color.unwrap_or(Color::BLACK);
 
             let _ui_element = UIElement {
                 raycast_target: false,
@@ -283,6 +290,8 @@ impl UILuaBindings {
                 alpha: 1.0,
                 interactable: false,
                 ignore_layout: false,
+                draggable: false,
+                pass_through: false,
                 canvas_entity: parent.map(|e| e as UIEntity),
             };
 
@@ -300,6 +309,7 @@ impl UILuaBindings {
                 best_fit: false,
                 best_fit_min_size: 10.0,
                 best_fit_max_size: 40.0,
+                localization_key: None,
             };
 
             // Store components
@@ -334,10 +344,12 @@ impl UILuaBindings {
                 raycast_target: true,
                 blocks_raycasts: true,
                 z_order: 0,
-                color: [1.0, 1.0, 1.0, 1.0],
+                color: Color::rgba(1.0, 1.0, 1.0, 1.0),
                 alpha: 1.0,
                 interactable: true,
                 ignore_layout: false,
+                draggable: false,
+                pass_through: false,
                 canvas_entity: parent.map(|e| e as UIEntity),
             };
 
@@ -345,19 +357,21 @@ impl UILuaBindings {
             let _ui_button = UIButton {
                 state: ButtonState::Normal,
                 transition: ButtonTransition::ColorTint,
-                normal_color: [1.0, 1.0, 1.0, 1.0],
-                highlighted_color: [0.9, 0.9, 0.9, 1.0],
-                pressed_color: [0.7, 0.7, 0.7, 1.0],
-                disabled_color: [0.5, 0.5, 0.5, 0.5],
+                normal_color: Color::rgba(1.0, 1.0, 1.0, 1.0),
+                highlighted_color: Color::rgba(0.9, 0.9, 0.9, 1.0),
+                pressed_color: Color::rgba(0.7, 0.7, 0.7, 1.0),
+                disabled_color: Color::rgba(0.5, 0.5, 0.5, 0.5),
+                selected_color: Color::WHITE,
                 fade_duration: 0.1,
                 highlighted_sprite: None,
                 pressed_sprite: None,
                 disabled_sprite: None,
+                selected_sprite: None,
                 normal_trigger: String::new(),
                 highlighted_trigger: String::new(),
                 pressed_trigger: String::new(),
                 disabled_trigger: String::new(),
-                on_click: on_click.clone(),
+                on_click: on_click.clone().map(UIEventBinding::lua),
             };
 
             // Store components
@@ -397,10 +411,12 @@ impl UILuaBindings {
                 raycast_target: true,
                 blocks_raycasts: true,
                 z_order: 0,
-                color: [1.0, 1.0, 1.0, 1.0],
+                color: Color::rgba(1.0, 1.0, 1.0, 1.0),
                 alpha: 1.0,
                 interactable: false,
                 ignore_layout: false,
+                draggable: false,
+                pass_through: false,
                 canvas_entity: parent.map(|e| e as UIEntity),
             };
 
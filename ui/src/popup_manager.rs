@@ -0,0 +1,267 @@
+//! Popup manager
+//!
+//! Keeps the stack of open modal popups (UI.show_modal). Only the top modal takes
+//! input: its dim panel swallows pointer and keyboard input meant for everything drawn
+//! beneath it, and focus stays inside it until it closes. A closed modal fades out from
+//! a separate list, so opening another modal (even the same prefab again) during the
+//! fade never touches the stack.
+
+use crate::Color;
+
+/// Sort order of the first modal; every modal above it draws STEP higher
+pub const MODAL_SORT_ORDER: i32 = 1000;
+const SORT_ORDER_STEP: i32 = 10;
+
+/// Result passed to on_closed when a modal is dismissed by Escape or a background click
+pub const CANCEL_RESULT: &str = "cancel";
+
+/// How a modal behaves (the options table of UI.show_modal)
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModalOptions {
+    /// Escape closes it with "cancel"
+    pub close_on_escape: bool,
+    /// A click on the dim panel outside the popup closes it with "cancel"
+    pub close_on_background_click: bool,
+    /// Color of the panel drawn behind the popup (transparent still blocks input)
+    pub dim_color: Color,
+    /// Seconds the popup and its dim panel take to fade in and out
+    pub fade_duration: f32,
+    /// Lua function of the opening script called with the result string when it closes
+    pub on_closed: Option<String>,
+}
+
+impl Default for ModalOptions {
+    fn default() -> Self {
+        Self {
+            close_on_escape: true,
+            close_on_background_click: false,
+            dim_color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+            fade_duration: 0.15,
+            on_closed: None,
+        }
+    }
+}
+
+/// An open (or closing) modal
+#[derive(Clone, Debug)]
+pub struct Modal {
+    pub instance_name: String,
+    pub options: ModalOptions,
+    /// Element that had focus when the modal opened, focused again when it closes
+    pub previous_focus: Option<String>,
+    pub sort_order: i32,
+    /// 0 (hidden) to 1 (fully shown)
+    pub opacity: f32,
+}
+
+impl Modal {
+    fn fade(&mut self, dt: f32, target: f32) {
+        if self.options.fade_duration <= 0.0 {
+            self.opacity = target;
+            return;
+        }
+        let step = dt / self.options.fade_duration;
+        self.opacity = if target > self.opacity {
+            (self.opacity + step).min(target)
+        } else {
+            (self.opacity - step).max(target)
+        };
+    }
+}
+
+/// What closing a modal leaves for the caller to do
+#[derive(Clone, Debug, PartialEq)]
+pub struct ClosedModal {
+    pub instance_name: String,
+    pub result: String,
+    pub on_closed: Option<String>,
+    pub previous_focus: Option<String>,
+}
+
+#[derive(Default)]
+pub struct PopupManager {
+    /// Open modals, bottom first
+    open: Vec<Modal>,
+    /// Closed modals still fading out (no longer take input)
+    closing: Vec<Modal>,
+}
+
+impl PopupManager {
+    /// Create a new popup manager
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Push a modal on top of the stack. Returns false if an instance with that name is
+    /// already open. A closing one with the same name stops fading and is replaced.
+    pub fn open(&mut self, instance_name: &str, options: ModalOptions, previous_focus: Option<String>) -> bool {
+        if self.is_open(instance_name) {
+            return false;
+        }
+        self.closing.retain(|modal| modal.instance_name != instance_name);
+        let sort_order = self.open.iter()
+            .chain(&self.closing)
+            .map(|modal| modal.sort_order + SORT_ORDER_STEP)
+            .max()
+            .unwrap_or(MODAL_SORT_ORDER);
+        let opacity = if options.fade_duration > 0.0 { 0.0 } else { 1.0 };
+        self.open.push(Modal {
+            instance_name: instance_name.to_string(),
+            options,
+            previous_focus,
+            sort_order,
+            opacity,
+        });
+        true
+    }
+
+    /// Close the top modal and start its fade out
+    pub fn close_top(&mut self, result: &str) -> Option<ClosedModal> {
+        let name = self.open.last()?.instance_name.clone();
+        self.close(&name, result)
+    }
+
+    /// Close an open modal (not necessarily the top one) and start its fade out
+    pub fn close(&mut self, instance_name: &str, result: &str) -> Option<ClosedModal> {
+        let index = self.open.iter().position(|modal| modal.instance_name == instance_name)?;
+        let modal = self.open.remove(index);
+        let closed = ClosedModal {
+            instance_name: modal.instance_name.clone(),
+            result: result.to_string(),
+            on_closed: modal.options.on_closed.clone(),
+            previous_focus: modal.previous_focus.clone(),
+        };
+        self.closing.push(modal);
+        Some(closed)
+    }
+
+    /// Forget a modal whose instance was deactivated some other way
+    pub fn remove(&mut self, instance_name: &str) {
+        self.open.retain(|modal| modal.instance_name != instance_name);
+        self.closing.retain(|modal| modal.instance_name != instance_name);
+    }
+
+    /// Advance the fades. Returns the closed modals that finished fading out, whose
+    /// instances can now be deactivated.
+    pub fn update(&mut self, dt: f32) -> Vec<String> {
+        for modal in &mut self.open {
+            modal.fade(dt, 1.0);
+        }
+        for modal in &mut self.closing {
+            modal.fade(dt, 0.0);
+        }
+        let (faded, closing): (Vec<Modal>, Vec<Modal>) = self.closing.drain(..).partition(|modal| modal.opacity <= 0.0);
+        self.closing = closing;
+        faded.into_iter().map(|modal| modal.instance_name).collect()
+    }
+
+    /// Top of the stack (the modal that takes input)
+    pub fn top(&self) -> Option<&Modal> {
+        self.open.last()
+    }
+
+    pub fn is_open(&self, instance_name: &str) -> bool {
+        self.open.iter().any(|modal| modal.instance_name == instance_name)
+    }
+
+    pub fn is_closing(&self, instance_name: &str) -> bool {
+        self.closing.iter().any(|modal| modal.instance_name == instance_name)
+    }
+
+    /// Any modal open (input below the top one is blocked)
+    pub fn has_modal(&self) -> bool {
+        !self.open.is_empty()
+    }
+
+    /// A modal is still fading in or out (keep redrawing)
+    pub fn is_fading(&self) -> bool {
+        !self.closing.is_empty() || self.open.iter().any(|modal| modal.opacity < 1.0)
+    }
+
+    /// Open and closing modals
+    pub fn modals(&self) -> impl Iterator<Item = &Modal> {
+        self.open.iter().chain(&self.closing)
+    }
+
+    /// Modal (open or closing) drawn for an instance
+    pub fn modal(&self, instance_name: &str) -> Option<&Modal> {
+        self.modals().find(|modal| modal.instance_name == instance_name)
+    }
+
+    /// Draw order of an instance: modals above everything else, other UIs at 0
+    pub fn sort_order(&self, instance_name: &str) -> i32 {
+        self.modal(instance_name).map_or(0, |modal| modal.sort_order)
+    }
+
+    /// Whether an instance's elements take pointer/keyboard input: only the top modal's
+    /// while a modal is open, and never a closing one's
+    pub fn accepts_input(&self, instance_name: &str) -> bool {
+        match self.top() {
+            Some(top) => top.instance_name == instance_name,
+            None => !self.is_closing(instance_name),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.open.clear();
+        self.closing.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(on_closed: &str) -> ModalOptions {
+        ModalOptions { on_closed: Some(on_closed.to_string()), ..Default::default() }
+    }
+
+    #[test]
+    fn test_stack_layers_and_restores_focus() {
+        let mut popups = PopupManager::new();
+        assert!(popups.accepts_input("hud"));
+
+        assert!(popups.open("pause", options("on_pause_closed"), Some("hud/Menu".to_string())));
+        assert!(popups.open("confirm", options("on_confirm_closed"), Some("pause/Quit".to_string())));
+        assert!(!popups.open("confirm", ModalOptions::default(), None), "already open");
+        popups.update(1.0);
+        assert!(popups.sort_order("confirm") > popups.sort_order("pause"));
+        assert!(popups.sort_order("pause") > popups.sort_order("hud"));
+        assert!(popups.accepts_input("confirm"));
+        assert!(!popups.accepts_input("pause") && !popups.accepts_input("hud"));
+
+        let closed = popups.close_top("confirm").unwrap();
+        assert_eq!(closed.instance_name, "confirm");
+        assert_eq!(closed.on_closed.as_deref(), Some("on_confirm_closed"));
+        assert_eq!(closed.previous_focus.as_deref(), Some("pause/Quit"));
+        assert!(popups.accepts_input("pause"));
+        assert!(!popups.accepts_input("confirm"), "fading out");
+
+        assert!(popups.update(0.1).is_empty());
+        assert_eq!(popups.update(0.1), ["confirm"]);
+        assert_eq!(popups.close_top(CANCEL_RESULT).unwrap().previous_focus.as_deref(), Some("hud/Menu"));
+        assert!(popups.close_top("ok").is_none());
+    }
+
+    #[test]
+    fn test_open_during_close_fade() {
+        let mut popups = PopupManager::new();
+        popups.open("pause", ModalOptions::default(), None);
+        popups.update(1.0);
+        popups.close_top("resume");
+
+        // Another modal opens while the first one fades out: it draws above the fading
+        // one and is the only one on the stack
+        popups.open("settings", ModalOptions::default(), None);
+        assert!(popups.sort_order("settings") > popups.sort_order("pause"));
+        assert_eq!(popups.update(1.0), ["pause"]);
+        assert_eq!(popups.top().unwrap().instance_name, "settings");
+        assert_eq!(popups.top().unwrap().opacity, 1.0);
+
+        // The same prefab opened again mid-fade replaces the fading copy
+        popups.close_top("back");
+        assert!(popups.open("settings", ModalOptions::default(), None));
+        assert!(popups.update(1.0).is_empty());
+        assert_eq!(popups.modals().count(), 1);
+    }
+}
@@ -3,8 +3,8 @@
 use serde::{Deserialize, Serialize};
 use crate::{
     RectTransform, UIElement, UIImage, UIText, UIButton, UIPanel,
-    UISlider, UIToggle, UIDropdown, UIInputField, UIScrollView,
-    UIMask, HorizontalLayoutGroup, VerticalLayoutGroup, GridLayoutGroup,
+    UISlider, UIToggle, UIToggleGroup, UIDropdown, UIInputField, UIScrollView,
+    UIMask, HorizontalLayoutGroup, VerticalLayoutGroup, GridLayoutGroup, UIVirtualInput, UITooltip, UISafeArea, UICanvasGroup, CanvasScaler,
 };
 
 /// UI Prefab for reusable UI templates
@@ -15,6 +15,10 @@ pub struct UIPrefab {
     
     /// Root element data
     pub root: UIPrefabElement,
+
+    /// How the prefab scales with the window (None: one unit per point)
+    #[serde(default)]
+    pub canvas_scaler: Option<CanvasScaler>,
 }
 
 /// UI Prefab element (recursive structure)
@@ -32,6 +36,11 @@ pub struct UIPrefabElement {
     pub panel: Option<UIPanel>,
     pub slider: Option<UISlider>,
     pub toggle: Option<UIToggle>,
+
+    /// Makes the toggles that reference it (by id) mutually exclusive
+    #[serde(default)]
+    pub toggle_group: Option<UIToggleGroup>,
+
     pub dropdown: Option<UIDropdown>,
     pub input_field: Option<UIInputField>,
     pub scroll_view: Option<UIScrollView>,
@@ -39,11 +48,57 @@ pub struct UIPrefabElement {
     pub horizontal_layout: Option<HorizontalLayoutGroup>,
     pub vertical_layout: Option<VerticalLayoutGroup>,
     pub grid_layout: Option<GridLayoutGroup>,
+
+    /// On-screen joystick/button for touch input
+    #[serde(default)]
+    pub virtual_input: Option<UIVirtualInput>,
+
+    /// Hover/focus tooltip
+    #[serde(default)]
+    pub tooltip: Option<UITooltip>,
+
+    /// Insets the element's parent rect to the screen's safe area
+    #[serde(default)]
+    pub safe_area: Option<UISafeArea>,
+
+    /// Alpha/interactable/raycast blocking for the element and everything below it
+    #[serde(default)]
+    pub canvas_group: Option<UICanvasGroup>,
     
     /// Children
     pub children: Vec<UIPrefabElement>,
 }
 
+impl UIPrefabElement {
+    /// Element with only a RectTransform and default UIElement (no optional components)
+    pub fn new(name: impl Into<String>, rect_transform: RectTransform) -> Self {
+        Self {
+            name: name.into(),
+            rect_transform,
+            ui_element: UIElement::default(),
+            image: None,
+            text: None,
+            button: None,
+            panel: None,
+            slider: None,
+            toggle: None,
+            toggle_group: None,
+            dropdown: None,
+            input_field: None,
+            scroll_view: None,
+            mask: None,
+            horizontal_layout: None,
+            vertical_layout: None,
+            grid_layout: None,
+            virtual_input: None,
+            tooltip: None,
+            safe_area: None,
+            canvas_group: None,
+            children: Vec::new(),
+        }
+    }
+}
+
 use std::collections::HashMap;
 
 /// Entity ID type (using u64 as a simple entity identifier)
@@ -75,11 +130,11 @@ impl PrefabParameters {
     }
     
     /// Set a color override for an element
-    pub fn set_color(&mut self, element_name: &str, color: [f32; 4]) -> &mut Self {
+    pub fn set_color(&mut self, element_name: &str, color: impl Into<crate::Color>) -> &mut Self {
         self.overrides
             .entry(element_name.to_string())
             .or_insert_with(HashMap::new)
-            .insert("color".to_string(), PrefabValue::Color(color));
+            .insert("color".to_string(), PrefabValue::Color(color.into()));
         self
     }
     
@@ -122,7 +177,7 @@ impl PrefabParameters {
 #[derive(Clone, Debug)]
 pub enum PrefabValue {
     Text(String),
-    Color([f32; 4]),
+    Color(crate::Color),
     Sprite(String),
     Position(crate::Vec2),
     Size(crate::Vec2),
@@ -154,6 +209,7 @@ pub struct PrefabInstantiator {
     pub panels: HashMap<Entity, UIPanel>,
     pub sliders: HashMap<Entity, UISlider>,
     pub toggles: HashMap<Entity, UIToggle>,
+    pub toggle_groups: HashMap<Entity, UIToggleGroup>,
     pub dropdowns: HashMap<Entity, UIDropdown>,
     pub input_fields: HashMap<Entity, UIInputField>,
     pub scroll_views: HashMap<Entity, UIScrollView>,
@@ -161,6 +217,10 @@ pub struct PrefabInstantiator {
     pub horizontal_layouts: HashMap<Entity, HorizontalLayoutGroup>,
     pub vertical_layouts: HashMap<Entity, VerticalLayoutGroup>,
     pub grid_layouts: HashMap<Entity, GridLayoutGroup>,
+    pub virtual_inputs: HashMap<Entity, UIVirtualInput>,
+    pub tooltips: HashMap<Entity, UITooltip>,
+    pub safe_areas: HashMap<Entity, UISafeArea>,
+    pub canvas_groups: HashMap<Entity, UICanvasGroup>,
     
     /// Parent-child relationships
     pub parents: HashMap<Entity, Entity>,
@@ -180,6 +240,7 @@ impl PrefabInstantiator {
             panels: HashMap::new(),
             sliders: HashMap::new(),
             toggles: HashMap::new(),
+            toggle_groups: HashMap::new(),
             dropdowns: HashMap::new(),
             input_fields: HashMap::new(),
             scroll_views: HashMap::new(),
@@ -187,6 +248,10 @@ impl PrefabInstantiator {
             horizontal_layouts: HashMap::new(),
             vertical_layouts: HashMap::new(),
             grid_layouts: HashMap::new(),
+            virtual_inputs: HashMap::new(),
+            tooltips: HashMap::new(),
+            safe_areas: HashMap::new(),
+            canvas_groups: HashMap::new(),
             parents: HashMap::new(),
             children: HashMap::new(),
         }
@@ -283,6 +348,10 @@ impl PrefabInstantiator {
         if let Some(toggle) = element.toggle.clone() {
             self.toggles.insert(entity, toggle);
         }
+
+        if let Some(toggle_group) = element.toggle_group.clone() {
+            self.toggle_groups.insert(entity, toggle_group);
+        }
         
         if let Some(dropdown) = element.dropdown.clone() {
             self.dropdowns.insert(entity, dropdown);
@@ -311,6 +380,22 @@ impl PrefabInstantiator {
         if let Some(grid_layout) = element.grid_layout.clone() {
             self.grid_layouts.insert(entity, grid_layout);
         }
+
+        if let Some(virtual_input) = element.virtual_input.clone() {
+            self.virtual_inputs.insert(entity, virtual_input);
+        }
+
+        if let Some(tooltip) = element.tooltip.clone() {
+            self.tooltips.insert(entity, tooltip);
+        }
+
+        if let Some(safe_area) = element.safe_area {
+            self.safe_areas.insert(entity, safe_area);
+        }
+
+        if let Some(canvas_group) = element.canvas_group {
+            self.canvas_groups.insert(entity, canvas_group);
+        }
         
         // Set up parent-child relationship
         if let Some(parent_entity) = parent {
@@ -357,6 +442,7 @@ impl PrefabInstantiator {
             self.panels.remove(&entity);
             self.sliders.remove(&entity);
             self.toggles.remove(&entity);
+            self.toggle_groups.remove(&entity);
             self.dropdowns.remove(&entity);
             self.input_fields.remove(&entity);
             self.scroll_views.remove(&entity);
@@ -364,6 +450,10 @@ impl PrefabInstantiator {
             self.horizontal_layouts.remove(&entity);
             self.vertical_layouts.remove(&entity);
             self.grid_layouts.remove(&entity);
+            self.virtual_inputs.remove(&entity);
+            self.tooltips.remove(&entity);
+            self.safe_areas.remove(&entity);
+            self.canvas_groups.remove(&entity);
             self.parents.remove(&entity);
             self.children.remove(&entity);
         }
@@ -384,6 +474,7 @@ mod tests {
     fn create_simple_prefab() -> UIPrefab {
         UIPrefab {
             name: "TestPrefab".to_string(),
+            canvas_scaler: None,
             root: UIPrefabElement {
                 name: "Root".to_string(),
                 rect_transform: RectTransform::anchored(
@@ -398,6 +489,7 @@ mod tests {
                 panel: None,
                 slider: None,
                 toggle: None,
+                toggle_group: None,
                 dropdown: None,
                 input_field: None,
                 scroll_view: None,
@@ -405,6 +497,10 @@ mod tests {
                 horizontal_layout: None,
                 vertical_layout: None,
                 grid_layout: None,
+                virtual_input: None,
+                tooltip: None,
+                safe_area: None,
+                canvas_group: None,
                 children: vec![],
             },
         }
@@ -413,6 +509,7 @@ mod tests {
     fn create_hierarchical_prefab() -> UIPrefab {
         UIPrefab {
             name: "HierarchicalPrefab".to_string(),
+            canvas_scaler: None,
             root: UIPrefabElement {
                 name: "Root".to_string(),
                 rect_transform: RectTransform::default(),
@@ -423,6 +520,7 @@ mod tests {
                 panel: Some(UIPanel::default()),
                 slider: None,
                 toggle: None,
+                toggle_group: None,
                 dropdown: None,
                 input_field: None,
                 scroll_view: None,
@@ -430,6 +528,10 @@ mod tests {
                 horizontal_layout: None,
                 vertical_layout: None,
                 grid_layout: None,
+                virtual_input: None,
+                tooltip: None,
+                safe_area: None,
+                canvas_group: None,
                 children: vec![
                     UIPrefabElement {
                         name: "Child1".to_string(),
@@ -444,6 +546,7 @@ mod tests {
                         panel: None,
                         slider: None,
                         toggle: None,
+                        toggle_group: None,
                         dropdown: None,
                         input_field: None,
                         scroll_view: None,
@@ -451,6 +554,10 @@ mod tests {
                         horizontal_layout: None,
                         vertical_layout: None,
                         grid_layout: None,
+                        virtual_input: None,
+                        tooltip: None,
+                        safe_area: None,
+                        canvas_group: None,
                         children: vec![],
                     },
                     UIPrefabElement {
@@ -463,6 +570,7 @@ mod tests {
                         panel: None,
                         slider: None,
                         toggle: None,
+                        toggle_group: None,
                         dropdown: None,
                         input_field: None,
                         scroll_view: None,
@@ -470,6 +578,10 @@ mod tests {
                         horizontal_layout: None,
                         vertical_layout: None,
                         grid_layout: None,
+                        virtual_input: None,
+                        tooltip: None,
+                        safe_area: None,
+                        canvas_group: None,
                         children: vec![],
                     },
                 ],
@@ -687,4 +799,19 @@ mod tests {
         let child2_transform = instantiator.rect_transforms.get(&child2).unwrap();
         assert_eq!(child2_transform.anchored_position, Vec2::new(50.0, 25.0));
     }
+
+    #[test]
+    fn test_virtual_controls_sample_prefab() {
+        let json = include_str!("../../../assets/ui/virtual_controls.uiprefab");
+        let prefab: UIPrefab = serde_json::from_str(json).unwrap();
+
+        let mut instantiator = PrefabInstantiator::new();
+        let result = instantiator.instantiate(&prefab);
+        let stick = result.named_entities["move_stick"];
+        let jump = result.named_entities["jump_button"];
+
+        assert!(matches!(instantiator.virtual_inputs.get(&stick), Some(UIVirtualInput::Joystick { floating: true, .. })));
+        assert!(matches!(instantiator.virtual_inputs.get(&jump), Some(UIVirtualInput::Button { action }) if action == "jump"));
+        assert!(!instantiator.virtual_inputs.contains_key(&result.root_entity));
+    }
 }
@@ -22,10 +22,10 @@ pub struct RectTransform {
     /// Size delta (additional size beyond anchors)
     pub size_delta: Vec2,
     
-    /// Local rotation (Z-axis rotation in degrees)
+    /// Local rotation (Z-axis rotation in degrees, counter-clockwise, around the pivot)
     pub rotation: f32,
     
-    /// Local scale
+    /// Local scale (around the pivot)
     pub scale: Vec2,
     
     /// Cached world corners (updated by layout system)
@@ -33,10 +33,16 @@ pub struct RectTransform {
     #[serde(skip)]
     pub world_corners: [Vec2; 4],
     
-    /// Cached rect (updated by layout system)
+    /// Cached rect (updated by layout system), in the parent's space before
+    /// this element's rotation and scale
     #[serde(skip)]
     pub rect: Rect,
     
+    /// Cached transform from `rect` space to world space: this element's rotation/scale
+    /// around its pivot, inside every ancestor's (identity when nothing is rotated or scaled)
+    #[serde(skip)]
+    pub world_matrix: glam::Affine2,
+    
     /// Dirty flag
     #[serde(skip)]
     pub dirty: bool,
@@ -53,6 +59,7 @@ impl Default for RectTransform {
             rotation: 0.0,
             scale: Vec2::ONE,
             world_corners: [Vec2::ZERO; 4],
+            world_matrix: glam::Affine2::IDENTITY,
             rect: Rect::default(),
             dirty: true,
         }
@@ -85,6 +92,7 @@ impl RectTransform {
             rotation: 0.0,
             scale: Vec2::ONE,
             world_corners: [Vec2::ZERO; 4],
+            world_matrix: glam::Affine2::IDENTITY,
             rect: Rect::default(),
             dirty: true,
         }
@@ -114,6 +122,7 @@ impl RectTransform {
             rotation: 0.0,
             scale: Vec2::ONE,
             world_corners: [Vec2::ZERO; 4],
+            world_matrix: glam::Affine2::IDENTITY,
             rect: Rect::default(),
             dirty: true,
         }
@@ -156,9 +165,22 @@ impl RectTransform {
         self.rect.center()
     }
     
-    /// Check if point is inside rect (for raycasting)
+    /// Check if point is inside rect (for raycasting). The point is in world space and is
+    /// mapped into the element's local space, so rotated and scaled elements hit correctly.
     pub fn contains_point(&self, point: Vec2) -> bool {
-        self.rect.contains(point)
+        self.rect.contains(self.world_matrix.inverse().transform_point2(point))
+    }
+
+    /// Transform from the element's parent space to its rotated/scaled space, around the
+    /// pivot of `rect`
+    pub fn local_matrix(&self) -> glam::Affine2 {
+        let pivot = Vec2::new(
+            self.rect.x + self.rect.width * self.pivot.x,
+            self.rect.y + self.rect.height * self.pivot.y,
+        );
+        glam::Affine2::from_translation(pivot)
+            * glam::Affine2::from_scale_angle_translation(self.scale, self.rotation.to_radians(), Vec2::ZERO)
+            * glam::Affine2::from_translation(-pivot)
     }
 }
 
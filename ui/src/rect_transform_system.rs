@@ -3,7 +3,7 @@
 //! This module provides systems for calculating world-space positions and sizes
 //! of UI elements based on their RectTransform components and parent-child relationships.
 
-use glam::Vec2;
+use glam::{Affine2, Vec2};
 use crate::{RectTransform, Rect};
 use std::collections::HashMap;
 
@@ -14,19 +14,36 @@ pub type Entity = u64;
 ///
 /// This system calculates world-space corners and rects for all RectTransforms
 /// in the hierarchy, handling parent-child relationships and dirty flagging.
+///
+/// Positions and sizes are in reference units (see CanvasScaler); the resulting
+/// rects and corners are in screen pixels, `scale_factor` pixels per unit.
 pub struct RectTransformSystem {
-    /// Cached parent size for each entity
-    parent_sizes: HashMap<Entity, Vec2>,
+    /// Screen pixels per reference unit (from the canvas scaler)
+    scale_factor: f32,
+
+    /// Screen size and scale factor of the last update (a change relayouts everything)
+    last_screen: Option<(Vec2, f32)>,
 }
 
 impl RectTransformSystem {
     /// Create a new RectTransform system
     pub fn new() -> Self {
         Self {
-            parent_sizes: HashMap::new(),
+            scale_factor: 1.0,
+            last_screen: None,
         }
     }
 
+    /// Set the canvas scale factor (e.g. `CanvasScaler::get_scale_factor()`)
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.scale_factor = scale_factor.max(f32::EPSILON);
+    }
+
+    /// Screen pixels per reference unit
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
     /// Update all RectTransforms in the hierarchy
     ///
     /// This should be called once per frame to recalculate transforms that are dirty.
@@ -34,15 +51,19 @@ impl RectTransformSystem {
     /// # Arguments
     /// * `rect_transforms` - Mutable map of entity to RectTransform
     /// * `parents` - Map of child entity to parent entity
-    /// * `screen_size` - Current screen size (for root elements)
+    /// * `screen_size` - Current screen size in pixels (for root elements)
     pub fn update(
         &mut self,
         rect_transforms: &mut HashMap<Entity, RectTransform>,
         parents: &HashMap<Entity, Entity>,
         screen_size: Vec2,
     ) {
-        // Clear cached parent sizes
-        self.parent_sizes.clear();
+        // Window resized or DPI changed: every anchor resolves differently
+        if self.last_screen != Some((screen_size, self.scale_factor)) {
+            self.last_screen = Some((screen_size, self.scale_factor));
+            Self::mark_all_dirty(rect_transforms);
+        }
+        let canvas_size = screen_size / self.scale_factor;
 
         // Find all root entities (entities without parents)
         let mut roots: Vec<Entity> = rect_transforms
@@ -56,15 +77,19 @@ impl RectTransformSystem {
 
         // Update each root and its children recursively
         for root in roots {
-            self.update_recursive(root, screen_size, rect_transforms, parents);
+            self.update_recursive(root, canvas_size, Affine2::IDENTITY, rect_transforms, parents);
         }
     }
 
     /// Recursively update a RectTransform and its children
+    ///
+    /// `parent_frame` maps the parent's local space (pixels from its bottom-left corner)
+    /// to world space, so children inherit the parent's rotation and scale.
     fn update_recursive(
         &mut self,
         entity: Entity,
         parent_size: Vec2,
+        parent_frame: Affine2,
         rect_transforms: &mut HashMap<Entity, RectTransform>,
         parents: &HashMap<Entity, Entity>,
     ) {
@@ -74,49 +99,42 @@ impl RectTransformSystem {
             None => return,
         };
 
-        // Only recalculate if dirty
-        if !rect_transform.dirty {
-            // Still need to update children with cached parent size
-            if let Some(cached_size) = self.parent_sizes.get(&entity).copied() {
-                let children: Vec<Entity> = parents
-                    .iter()
-                    .filter(|(_, &p)| p == entity)
-                    .map(|(&c, _)| c)
-                    .collect();
-
-                for child in children {
-                    self.update_recursive(child, cached_size, rect_transforms, parents);
-                }
-            }
-            return;
-        }
+        // Only recalculate the rect if dirty
+        if rect_transform.dirty {
+            // Calculate the element's size based on anchors
+            let size = Self::calculate_size(rect_transform, parent_size);
 
-        // Calculate the element's size based on anchors
-        let size = Self::calculate_size(rect_transform, parent_size);
+            // The pivot sits on the anchor reference point (between the anchors, at the
+            // pivot's fraction) plus the anchored position
+            let anchor_span = rect_transform.anchor_max - rect_transform.anchor_min;
+            let anchor_pos = parent_size * (rect_transform.anchor_min + anchor_span * rect_transform.pivot);
+            let rect_pos = anchor_pos + rect_transform.anchored_position - size * rect_transform.pivot;
 
-        // Calculate anchor points in parent space
-        let anchor_min_pos = parent_size * rect_transform.anchor_min;
+            // Update the rect (in screen pixels)
+            let (pixel_pos, pixel_size) = (rect_pos * self.scale_factor, size * self.scale_factor);
+            rect_transform.rect = Rect::new(pixel_pos.x, pixel_pos.y, pixel_size.x, pixel_size.y);
 
-        // Calculate the rect position and size
-        let rect_pos = anchor_min_pos + rect_transform.anchored_position - size * rect_transform.pivot;
-        let rect_size = size * rect_transform.scale;
+            // Mark as clean
+            rect_transform.dirty = false;
+        }
 
-        // Update the rect
-        rect_transform.rect = Rect::new(rect_pos.x, rect_pos.y, rect_size.x, rect_size.y);
+        // Rotation/scale around the pivot, inside the parent's space (ancestors may have
+        // moved even when this rect didn't, so this is always refreshed)
+        let rect = rect_transform.rect;
+        rect_transform.world_matrix = parent_frame * rect_transform.local_matrix();
 
         // Calculate world corners (bottom-left, top-left, top-right, bottom-right)
+        let world = rect_transform.world_matrix;
         rect_transform.world_corners = [
-            Vec2::new(rect_pos.x, rect_pos.y),                           // Bottom-left
-            Vec2::new(rect_pos.x, rect_pos.y + rect_size.y),            // Top-left
-            Vec2::new(rect_pos.x + rect_size.x, rect_pos.y + rect_size.y), // Top-right
-            Vec2::new(rect_pos.x + rect_size.x, rect_pos.y),            // Bottom-right
+            world.transform_point2(Vec2::new(rect.x, rect.y)),                            // Bottom-left
+            world.transform_point2(Vec2::new(rect.x, rect.y + rect.height)),              // Top-left
+            world.transform_point2(Vec2::new(rect.x + rect.width, rect.y + rect.height)), // Top-right
+            world.transform_point2(Vec2::new(rect.x + rect.width, rect.y)),               // Bottom-right
         ];
 
-        // Mark as clean
-        rect_transform.dirty = false;
-
-        // Cache this element's size for its children
-        self.parent_sizes.insert(entity, rect_size);
+        // Children are laid out in this element's unscaled local space
+        let size = Vec2::new(rect.width, rect.height) / self.scale_factor;
+        let frame = world * Affine2::from_translation(Vec2::new(rect.x, rect.y));
 
         // Update children recursively
         let children: Vec<Entity> = parents
@@ -126,7 +144,7 @@ impl RectTransformSystem {
             .collect();
 
         for child in children {
-            self.update_recursive(child, rect_size, rect_transforms, parents);
+            self.update_recursive(child, size, frame, rect_transforms, parents);
         }
     }
 
@@ -277,6 +295,77 @@ mod tests {
         assert_eq!(child_rt.rect.y, 275.0);
     }
 
+    #[test]
+    fn test_scale_with_screen_size_keeps_button_proportional() {
+        let mut scaler = crate::CanvasScaler::scale_with_screen_size(1280.0, 720.0);
+        scaler.set_match_width_or_height(0.5);
+
+        let mut system = RectTransformSystem::new();
+        let mut rect_transforms = HashMap::new();
+        let parents = HashMap::new();
+        let button = 1;
+        rect_transforms.insert(button, RectTransform::anchored(Vec2::new(0.5, 0.5), Vec2::ZERO, Vec2::new(200.0, 100.0)));
+
+        // Same system across resolutions: resizing must relayout without marking anything dirty
+        for (width, height) in [(1280.0, 720.0), (1920.0, 1080.0), (800.0, 600.0)] {
+            let scale = scaler.calculate_scale_factor(width, height, crate::BASE_DPI);
+            system.set_scale_factor(scale);
+            system.update(&mut rect_transforms, &parents, Vec2::new(width, height));
+
+            let rect = rect_transforms[&button].rect;
+            assert!((rect.width / rect.height - 2.0).abs() < 1e-4);
+            assert!((rect.x + rect.width * 0.5 - width * 0.5).abs() < 1e-3);
+            assert!((rect.y + rect.height * 0.5 - height * 0.5).abs() < 1e-3);
+            assert!((rect.width - 200.0 * scale).abs() < 1e-3);
+
+            if width / height == 16.0 / 9.0 {
+                // Same aspect as the reference: same fraction of the screen
+                assert!((rect.width / width - 200.0 / 1280.0).abs() < 1e-4);
+            } else {
+                // 4:3 blends width (0.625) and height (0.833) scales in log space
+                assert!((scale - (0.625f32 * (600.0 / 720.0)).sqrt()).abs() < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotation_and_scale_around_pivot_are_inherited() {
+        let mut system = RectTransformSystem::new();
+        let mut rect_transforms = HashMap::new();
+        let mut parents = HashMap::new();
+        let (panel, button) = (1, 2);
+
+        // Panel 200x100 centered on an 800x600 screen, rotated 90 degrees
+        let mut panel_rt = RectTransform::anchored(Vec2::new(0.5, 0.5), Vec2::ZERO, Vec2::new(200.0, 100.0));
+        panel_rt.rotation = 90.0;
+        rect_transforms.insert(panel, panel_rt);
+
+        // Button 40x20 at the panel's right edge, scaled 2x around its own center
+        let mut button_rt = RectTransform::anchored(Vec2::new(1.0, 0.5), Vec2::new(-20.0, 0.0), Vec2::new(40.0, 20.0));
+        button_rt.scale = Vec2::splat(2.0);
+        rect_transforms.insert(button, button_rt);
+        parents.insert(button, panel);
+
+        system.update(&mut rect_transforms, &parents, Vec2::new(800.0, 600.0));
+
+        // Rotating around the pivot keeps the panel centered; its corners swap extents
+        let panel_rt = &rect_transforms[&panel];
+        let center = panel_rt.world_corners.iter().copied().sum::<Vec2>() / 4.0;
+        assert!((center - Vec2::new(400.0, 300.0)).length() < 1e-3);
+        assert!((panel_rt.world_corners[0] - Vec2::new(450.0, 200.0)).length() < 1e-3);
+
+        // The button follows the panel's rotation: the right edge now points up
+        let button_rt = &rect_transforms[&button];
+        let center = button_rt.world_corners.iter().copied().sum::<Vec2>() / 4.0;
+        assert!((center - Vec2::new(400.0, 380.0)).length() < 1e-3);
+        let width = (button_rt.world_corners[3] - button_rt.world_corners[0]).length();
+        assert!((width - 80.0).abs() < 1e-3);
+
+        // Raycasts hit the rotated quad, not the unrotated rect
+        assert!(button_rt.contains_point(Vec2::new(400.0, 415.0)));
+        assert!(!button_rt.contains_point(Vec2::new(435.0, 380.0)));
+    }
+
     #[test]
     fn test_mark_dirty() {
         let mut rect_transforms = HashMap::new();
@@ -199,7 +199,7 @@ impl UIBatchBuilder {
         }
 
         // Calculate color with alpha
-        let mut color = ui_element.color;
+        let mut color = ui_element.color.to_array();
         color[3] *= ui_element.alpha;
 
         // Generate mesh based on component type
@@ -254,29 +254,53 @@ impl UIBatchBuilder {
     }
     
     /// Generate a mesh for text rendering
-    fn generate_text_mesh(&self, text: &UIText, bounds: Rect, tint_color: [f32; 4]) -> (Option<String>, UIMesh) {
+    fn generate_text_mesh(&mut self, text: &UIText, bounds: Rect, tint_color: [f32; 4]) -> (Option<String>, UIMesh) {
         // Layout the text
         let layout = self.text_renderer.layout_text(text, bounds);
+        let mut texture_id = layout.texture_id.clone();
+
+        // Fonts loaded from files draw from the glyph atlas for this size
+        let atlases = self.text_renderer.font_cache_mut().atlases_mut();
+        let mut atlas = if atlases.has_font(&layout.font_name) {
+            atlases.atlas(&layout.font_name, text.font_size)
+        } else {
+            None
+        };
+        if let Some(atlas) = atlas.as_ref() {
+            texture_id = atlas.texture_id().to_string();
+        }
         
         // Generate mesh from positioned glyphs
         let mut vertices = Vec::new();
         let mut indices = Vec::new();
         
         // Use text color, but apply tint alpha
-        let mut final_color = text.color;
+        let mut final_color = text.color.to_array();
         final_color[3] *= tint_color[3];
         
         for glyph in &layout.glyphs {
             let base_index = vertices.len() as u32;
             
             // Calculate glyph quad corners
-            let x0 = glyph.position.x;
-            let y0 = glyph.position.y;
-            let x1 = x0 + glyph.glyph.size.x * glyph.scale;
-            let y1 = y0 + glyph.glyph.size.y * glyph.scale;
+            let (mut position, mut size, mut uv) = (glyph.position, glyph.glyph.size * glyph.scale, glyph.glyph.uv_rect);
+            if let Some(atlas) = atlas.as_mut() {
+                let Some(packed) = atlas.glyph(glyph.glyph.character) else { continue };
+                // Atlas quads can be padded (SDF) and drawn at another size than rasterized
+                let atlas_scale = text.font_size / atlas.px_size();
+                position += packed.bearing * atlas_scale - glyph.glyph.bearing * glyph.scale;
+                size = packed.size * atlas_scale;
+                uv = packed.uv_rect;
+            }
+            if size.x <= 0.0 || size.y <= 0.0 {
+                continue;
+            }
+            let x0 = position.x;
+            let y0 = position.y;
+            let x1 = x0 + size.x;
+            let y1 = y0 + size.y;
             
             // Get UV coordinates from glyph
-            let uv = &glyph.glyph.uv_rect;
+            let uv = &uv;
             let u0 = uv.x;
             let v0 = uv.y;
             let u1 = uv.x + uv.width;
@@ -312,7 +336,7 @@ impl UIBatchBuilder {
         }
         
         let mesh = UIMesh { vertices, indices };
-        let texture_id = Some(layout.texture_id);
+        let texture_id = Some(texture_id);
         
         (texture_id, mesh)
     }
@@ -533,7 +557,7 @@ impl Default for UIRenderSystem {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Canvas, CanvasRenderMode, CanvasScaler, ScaleMode};
+    use crate::{Canvas, Color, CanvasRenderMode, CanvasScaler, ScaleMode};
 
     fn create_test_canvas(sort_order: i32) -> Canvas {
         Canvas {
@@ -567,10 +591,12 @@ mod tests {
             raycast_target: true,
             blocks_raycasts: true,
             z_order,
-            color: [1.0, 1.0, 1.0, 1.0],
+            color: Color::rgba(1.0, 1.0, 1.0, 1.0),
             alpha: 1.0,
             interactable: true,
             ignore_layout: false,
+            draggable: false,
+            pass_through: false,
             canvas_entity: None,
         }
     }
@@ -699,4 +725,37 @@ mod tests {
         assert_eq!(builder.elements.len(), 1);
         assert_eq!(builder.elements[0].entity, 1);
     }
+
+    #[test]
+    fn test_loaded_font_text_draws_from_size_atlas() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Hack-Regular.ttf");
+        std::fs::write(&path, epaint_default_fonts::HACK_REGULAR).unwrap();
+
+        let mut builder = UIBatchBuilder::new();
+        builder.text_renderer_mut().font_cache_mut().load_font("hack", path.to_str().unwrap()).unwrap();
+
+        let canvas = create_test_canvas(0);
+        let rect = create_test_rect_transform(0.0, 0.0, 400.0, 100.0);
+        let element = create_test_ui_element(0);
+        let text = |font: &str, font_size: f32| UIText {
+            text: "Hi there".to_string(),
+            font: font.to_string(),
+            font_size,
+            ..Default::default()
+        };
+        builder.collect_element(1, &canvas, &rect, &element, None, Some(&text("hack", 16.0)), None);
+        builder.collect_element(2, &canvas, &rect, &element, None, Some(&text("hack", 32.0)), None);
+        builder.collect_element(3, &canvas, &rect, &element, None, Some(&text("default", 16.0)), None);
+
+        assert_eq!(builder.elements[0].texture_id.as_deref(), Some("font_atlas:hack@16"));
+        assert_eq!(builder.elements[1].texture_id.as_deref(), Some("font_atlas:hack@32"));
+        assert_eq!(builder.elements[2].texture_id.as_deref(), Some("default_font"));
+
+        // The space has no quad; the rest are glyph-sized, twice as big at 32px
+        assert_eq!(builder.elements[0].mesh.vertices.len(), 7 * 4);
+        let height = |mesh: &UIMesh| mesh.vertices[1].position.y - mesh.vertices[0].position.y;
+        let ratio = height(&builder.elements[1].mesh) / height(&builder.elements[0].mesh);
+        assert!((ratio - 2.0).abs() < 0.2, "ratio {}", ratio);
+    }
 }
@@ -0,0 +1,542 @@
+//! Glyph atlas cache
+//!
+//! Rasterizes TrueType/OpenType glyphs (through ab_glyph) into atlas textures the
+//! first time they're drawn. Bitmap atlases are keyed by (font, pixel size) and live in
+//! a least-recently-used cache, so a UI using many sizes doesn't keep every atlas
+//! around. In SDF mode each font gets a single signed-distance-field atlas rendered at
+//! `SDF_BASE_SIZE` that stays crisp at any scale.
+
+use crate::{Rect, Vec2};
+use ab_glyph::{Font as _, FontArc, PxScale, ScaleFont as _};
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+/// Atlases kept alive before the least recently used one is dropped
+pub const DEFAULT_ATLAS_CAPACITY: usize = 16;
+
+/// Pixel size SDF atlases are rendered at
+pub const SDF_BASE_SIZE: f32 = 48.0;
+
+/// Distance (in atlas pixels) the SDF encodes on each side of a glyph edge
+pub const SDF_SPREAD: f32 = 4.0;
+
+const ATLAS_WIDTH: u32 = 512;
+const MAX_ATLAS_HEIGHT: u32 = 4096;
+
+/// Empty pixels between packed glyphs (stops bilinear filtering bleeding)
+const GLYPH_PADDING: u32 = 1;
+
+/// How glyphs are stored in the atlas
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GlyphRenderMode {
+    /// Coverage bitmaps, one atlas per pixel size
+    #[default]
+    Bitmap,
+    /// Signed distance fields (128 = edge), one atlas per font for every size
+    Sdf,
+}
+
+/// Identifies one atlas in the cache
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AtlasKey {
+    pub font: String,
+    pub size: u32,
+}
+
+impl AtlasKey {
+    /// Texture ID batches use for this atlas
+    pub fn texture_id(&self) -> String {
+        format!("font_atlas:{}@{}", self.font, self.size)
+    }
+}
+
+/// A glyph packed in an atlas. Sizes are in atlas pixels: multiply by
+/// `font_size / atlas.px_size()` to draw at another size.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtlasGlyph {
+    /// UV coordinates in the atlas (normalized 0-1, zero-sized for whitespace)
+    pub uv_rect: Rect,
+
+    /// Size of the glyph's quad
+    pub size: Vec2,
+
+    /// Offset of the quad from the pen position at the top of the line
+    pub bearing: Vec2,
+
+    /// How far the pen moves after this glyph
+    pub advance: f32,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PackedGlyph {
+    /// x, y, width, height in atlas pixels
+    pixels: [u32; 4],
+    size: Vec2,
+    bearing: Vec2,
+    advance: f32,
+}
+
+/// Single-channel atlas texture for one font at one size (or one SDF font)
+pub struct GlyphAtlas {
+    font: FontArc,
+    px_size: f32,
+    mode: GlyphRenderMode,
+    texture_id: String,
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    glyphs: HashMap<char, Option<PackedGlyph>>,
+
+    // Shelf packer
+    cursor_x: u32,
+    cursor_y: u32,
+    shelf_height: u32,
+
+    /// Bumped whenever pixels change (the texture needs uploading again)
+    revision: u64,
+}
+
+impl GlyphAtlas {
+    fn new(font: FontArc, px_size: f32, mode: GlyphRenderMode, texture_id: String) -> Self {
+        let height = 64;
+        Self {
+            font,
+            px_size,
+            mode,
+            texture_id,
+            width: ATLAS_WIDTH,
+            height,
+            pixels: vec![0; (ATLAS_WIDTH * height) as usize],
+            glyphs: HashMap::new(),
+            cursor_x: 0,
+            cursor_y: 0,
+            shelf_height: 0,
+            revision: 0,
+        }
+    }
+
+    /// Pixel size the glyphs were rasterized at
+    pub fn px_size(&self) -> f32 {
+        self.px_size
+    }
+
+    pub fn mode(&self) -> GlyphRenderMode {
+        self.mode
+    }
+
+    pub fn texture_id(&self) -> &str {
+        &self.texture_id
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Row-major single-channel pixels (coverage, or distance in SDF mode)
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// Changes whenever the pixels do
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Glyph for `c`, rasterized on first use. None if the font has no glyph for it or
+    /// the atlas is full.
+    pub fn glyph(&mut self, c: char) -> Option<AtlasGlyph> {
+        if !self.glyphs.contains_key(&c) {
+            let packed = self.rasterize(c);
+            self.glyphs.insert(c, packed);
+        }
+        let packed = (*self.glyphs.get(&c)?)?;
+        let [x, y, width, height] = packed.pixels;
+        Some(AtlasGlyph {
+            // Computed on lookup: the atlas may have grown since the glyph was packed
+            uv_rect: Rect::new(
+                x as f32 / self.width as f32,
+                y as f32 / self.height as f32,
+                width as f32 / self.width as f32,
+                height as f32 / self.height as f32,
+            ),
+            size: packed.size,
+            bearing: packed.bearing,
+            advance: packed.advance,
+        })
+    }
+
+    fn rasterize(&mut self, c: char) -> Option<PackedGlyph> {
+        let id = self.font.glyph_id(c);
+        if id.0 == 0 {
+            return None;
+        }
+        let scale = PxScale::from(self.px_size);
+        let scaled = self.font.as_scaled(scale);
+        let advance = scaled.h_advance(id);
+        let glyph = id.with_scale_and_position(scale, ab_glyph::point(0.0, scaled.ascent()));
+
+        // Whitespace: nothing to draw, only an advance
+        let Some(outlined) = self.font.outline_glyph(glyph) else {
+            return Some(PackedGlyph { pixels: [0; 4], size: Vec2::ZERO, bearing: Vec2::ZERO, advance });
+        };
+        let bounds = outlined.px_bounds();
+        let (glyph_width, glyph_height) = (bounds.width().ceil() as u32, bounds.height().ceil() as u32);
+        let mut coverage = vec![0u8; (glyph_width * glyph_height) as usize];
+        outlined.draw(|x, y, value| {
+            if x < glyph_width && y < glyph_height {
+                coverage[(y * glyph_width + x) as usize] = (value.clamp(0.0, 1.0) * 255.0) as u8;
+            }
+        });
+
+        let mut bearing = Vec2::new(bounds.min.x, bounds.min.y);
+        let (bitmap, width, height) = match self.mode {
+            GlyphRenderMode::Bitmap => (coverage, glyph_width, glyph_height),
+            GlyphRenderMode::Sdf => {
+                let pad = SDF_SPREAD.ceil() as u32;
+                bearing -= Vec2::splat(pad as f32);
+                let (width, height) = (glyph_width + pad * 2, glyph_height + pad * 2);
+                let mut padded = vec![0u8; (width * height) as usize];
+                for y in 0..glyph_height {
+                    let src = (y * glyph_width) as usize;
+                    let dst = ((y + pad) * width + pad) as usize;
+                    padded[dst..dst + glyph_width as usize].copy_from_slice(&coverage[src..src + glyph_width as usize]);
+                }
+                (signed_distance_field(&padded, width, height, SDF_SPREAD), width, height)
+            }
+        };
+
+        let (x, y) = self.allocate(width, height)?;
+        for row in 0..height {
+            let src = (row * width) as usize;
+            let dst = ((y + row) * self.width + x) as usize;
+            self.pixels[dst..dst + width as usize].copy_from_slice(&bitmap[src..src + width as usize]);
+        }
+        self.revision += 1;
+
+        Some(PackedGlyph {
+            pixels: [x, y, width, height],
+            size: Vec2::new(width as f32, height as f32),
+            bearing,
+            advance,
+        })
+    }
+
+    /// Find room for a `width` x `height` glyph, growing the atlas downwards if needed
+    fn allocate(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        let (padded_width, padded_height) = (width + GLYPH_PADDING, height + GLYPH_PADDING);
+        if padded_width > self.width {
+            return None;
+        }
+        if self.cursor_x + padded_width > self.width {
+            self.cursor_y += self.shelf_height;
+            self.cursor_x = 0;
+            self.shelf_height = 0;
+        }
+        while self.cursor_y + padded_height > self.height {
+            if self.height >= MAX_ATLAS_HEIGHT {
+                return None;
+            }
+            // Rows keep their offsets when the atlas gets taller
+            self.height = (self.height * 2).min(MAX_ATLAS_HEIGHT);
+            self.pixels.resize((self.width * self.height) as usize, 0);
+        }
+        let position = (self.cursor_x, self.cursor_y);
+        self.cursor_x += padded_width;
+        self.shelf_height = self.shelf_height.max(padded_height);
+        Some(position)
+    }
+}
+
+/// Distance field from coverage: 128 on the edge, higher inside, lower outside,
+/// reaching 0/255 at `spread` pixels away
+fn signed_distance_field(coverage: &[u8], width: u32, height: u32, spread: f32) -> Vec<u8> {
+    let radius = spread.ceil() as i32;
+    let inside = |x: i32, y: i32| {
+        x >= 0 && y >= 0 && x < width as i32 && y < height as i32 && coverage[(y as u32 * width + x as u32) as usize] >= 128
+    };
+    let mut field = vec![0u8; coverage.len()];
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            let here = inside(x, y);
+            let mut nearest = spread;
+            for dy in -radius..=radius {
+                for dx in -radius..=radius {
+                    if inside(x + dx, y + dy) != here {
+                        // The edge lies between the two pixel centres
+                        let distance = ((dx * dx + dy * dy) as f32).sqrt() - 0.5;
+                        nearest = nearest.min(distance);
+                    }
+                }
+            }
+            let signed = if here { nearest } else { -nearest };
+            field[(y as u32 * width + x as u32) as usize] = (128.0 + signed / spread * 127.0).clamp(0.0, 255.0) as u8;
+        }
+    }
+    field
+}
+
+/// Loaded fonts and their atlases
+pub struct GlyphAtlasCache {
+    fonts: HashMap<String, FontArc>,
+    default_font: Option<String>,
+    mode: GlyphRenderMode,
+
+    /// Atlases with the clock tick they were last used on
+    atlases: HashMap<AtlasKey, (GlyphAtlas, u64)>,
+    clock: u64,
+    capacity: usize,
+
+    /// Fonts already reported missing
+    reported: BTreeSet<String>,
+    unreported: BTreeSet<String>,
+}
+
+impl GlyphAtlasCache {
+    /// Create a cache keeping up to `DEFAULT_ATLAS_CAPACITY` atlases
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_ATLAS_CAPACITY)
+    }
+
+    /// Create a cache keeping up to `capacity` atlases
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            fonts: HashMap::new(),
+            default_font: None,
+            mode: GlyphRenderMode::default(),
+            atlases: HashMap::new(),
+            clock: 0,
+            capacity: capacity.max(1),
+            reported: BTreeSet::new(),
+            unreported: BTreeSet::new(),
+        }
+    }
+
+    /// Load a .ttf/.otf file under `name`
+    pub fn load_font(&mut self, name: &str, path: impl AsRef<Path>) -> Result<(), String> {
+        let path = path.as_ref();
+        let data = std::fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        self.add_font_data(name, data).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Add a font from .ttf/.otf bytes (replacing any font with the same name). The
+    /// first font added becomes the default.
+    pub fn add_font_data(&mut self, name: &str, data: Vec<u8>) -> Result<(), String> {
+        let font = FontArc::try_from_vec(data).map_err(|e| e.to_string())?;
+        self.fonts.insert(name.to_string(), font);
+        self.atlases.retain(|key, _| key.font != name);
+        if self.default_font.is_none() {
+            self.default_font = Some(name.to_string());
+        }
+        Ok(())
+    }
+
+    pub fn has_font(&self, name: &str) -> bool {
+        self.fonts.contains_key(name)
+    }
+
+    /// Font used in place of fonts that aren't loaded
+    pub fn set_default_font(&mut self, name: &str) {
+        self.default_font = Some(name.to_string());
+    }
+
+    pub fn render_mode(&self) -> GlyphRenderMode {
+        self.mode
+    }
+
+    /// Switch between bitmap and SDF atlases (existing atlases are dropped)
+    pub fn set_render_mode(&mut self, mode: GlyphRenderMode) {
+        if mode != self.mode {
+            self.mode = mode;
+            self.atlases.clear();
+        }
+    }
+
+    /// Name of the loaded font drawing `name`: itself, or the default font (reported
+    /// once by `take_missing_warning`)
+    pub fn resolve_font(&mut self, name: &str) -> Option<String> {
+        if self.fonts.contains_key(name) {
+            return Some(name.to_string());
+        }
+        let fallback = self.default_font.clone().filter(|font| self.fonts.contains_key(font))?;
+        if !self.reported.contains(name) {
+            self.unreported.insert(name.to_string());
+        }
+        Some(fallback)
+    }
+
+    /// Fonts that were missing since the last call, as one warning
+    pub fn take_missing_warning(&mut self) -> Option<String> {
+        if self.unreported.is_empty() {
+            return None;
+        }
+        let fonts: Vec<String> = std::mem::take(&mut self.unreported).into_iter().collect();
+        self.reported.extend(fonts.iter().cloned());
+        Some(format!(
+            "Font(s) not loaded, using '{}': {}",
+            self.default_font.as_deref().unwrap_or_default(),
+            fonts.join(", ")
+        ))
+    }
+
+    /// Key of the atlas that draws `font` at `size`
+    pub fn atlas_key(&self, font: &str, size: f32) -> AtlasKey {
+        let size = match self.mode {
+            GlyphRenderMode::Bitmap => size.round().max(1.0) as u32,
+            GlyphRenderMode::Sdf => SDF_BASE_SIZE as u32,
+        };
+        AtlasKey { font: font.to_string(), size }
+    }
+
+    /// Atlas drawing `font` at `size` (created on first use, evicting the least recently
+    /// used atlas when the cache is full). Missing fonts use the default font.
+    pub fn atlas(&mut self, font: &str, size: f32) -> Option<&mut GlyphAtlas> {
+        let font = self.resolve_font(font)?;
+        let key = self.atlas_key(&font, size);
+        self.clock += 1;
+        let clock = self.clock;
+
+        if !self.atlases.contains_key(&key) {
+            if self.atlases.len() >= self.capacity {
+                let oldest = self.atlases.iter().min_by_key(|(_, (_, used))| *used).map(|(key, _)| key.clone());
+                if let Some(oldest) = oldest {
+                    self.atlases.remove(&oldest);
+                }
+            }
+            let atlas = GlyphAtlas::new(self.fonts[&font].clone(), key.size as f32, self.mode, key.texture_id());
+            self.atlases.insert(key.clone(), (atlas, clock));
+        }
+        let (atlas, used) = self.atlases.get_mut(&key)?;
+        *used = clock;
+        Some(atlas)
+    }
+
+    /// Atlases currently cached
+    pub fn atlases(&self) -> impl Iterator<Item = (&AtlasKey, &GlyphAtlas)> {
+        self.atlases.iter().map(|(key, (atlas, _))| (key, atlas))
+    }
+
+    pub fn atlas_count(&self) -> usize {
+        self.atlases.len()
+    }
+
+    /// Ascent-to-descent height plus line gap of `font` at `size`
+    pub fn line_height(&mut self, font: &str, size: f32) -> Option<f32> {
+        let font = self.resolve_font(font)?;
+        let scaled = self.fonts[&font].as_scaled(PxScale::from(size));
+        Some(scaled.height() + scaled.line_gap())
+    }
+
+    /// Size of `text` (one line per `\n`) in `font` at `size`, with kerning
+    pub fn measure_text(&mut self, font: &str, size: f32, text: &str) -> Option<Vec2> {
+        let font = self.resolve_font(font)?;
+        let scaled = self.fonts[&font].as_scaled(PxScale::from(size));
+        let mut width = 0.0f32;
+        let mut lines = 0;
+        for line in text.split('\n') {
+            let mut line_width = 0.0;
+            let mut previous = None;
+            for c in line.chars() {
+                let id = scaled.glyph_id(c);
+                if let Some(previous) = previous {
+                    line_width += scaled.kern(previous, id);
+                }
+                line_width += scaled.h_advance(id);
+                previous = Some(id);
+            }
+            width = width.max(line_width);
+            lines += 1;
+        }
+        Some(Vec2::new(width, lines as f32 * (scaled.height() + scaled.line_gap())))
+    }
+
+    /// The loaded font behind `name` (no fallback)
+    pub fn font(&self, name: &str) -> Option<&FontArc> {
+        self.fonts.get(name)
+    }
+}
+
+impl Default for GlyphAtlasCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache() -> GlyphAtlasCache {
+        let mut cache = GlyphAtlasCache::new();
+        cache.add_font_data("ubuntu", epaint_default_fonts::UBUNTU_LIGHT.to_vec()).unwrap();
+        cache.add_font_data("hack", epaint_default_fonts::HACK_REGULAR.to_vec()).unwrap();
+        cache
+    }
+
+    #[test]
+    fn test_glyphs_pack_into_atlas_once() {
+        let mut cache = cache();
+        let atlas = cache.atlas("ubuntu", 24.0).unwrap();
+        let a = atlas.glyph('A').unwrap();
+        let revision = atlas.revision();
+        assert!(a.size.x > 0.0 && a.size.y > 0.0 && a.advance > 0.0);
+        assert!(a.uv_rect.width > 0.0 && a.uv_rect.x + a.uv_rect.width <= 1.0);
+        assert!(atlas.pixels().iter().any(|&p| p > 0));
+
+        // Cached: no new rasterization
+        assert_eq!(atlas.glyph('A'), Some(a));
+        assert_eq!(atlas.revision(), revision);
+
+        // Whitespace has an advance but nothing to draw
+        let space = atlas.glyph(' ').unwrap();
+        assert!(space.advance > 0.0 && space.size == Vec2::ZERO);
+    }
+
+    #[test]
+    fn test_missing_font_falls_back_with_one_warning() {
+        let mut cache = cache();
+        assert_eq!(cache.resolve_font("Title.ttf").as_deref(), Some("ubuntu"));
+        cache.resolve_font("Title.ttf");
+        let warning = cache.take_missing_warning().unwrap();
+        assert!(warning.contains("Title.ttf") && warning.contains("'ubuntu'"));
+        cache.resolve_font("Title.ttf");
+        assert_eq!(cache.take_missing_warning(), None);
+
+        // Layout measures with the font actually drawn
+        let monospace = cache.measure_text("hack", 20.0, "iiii").unwrap();
+        let proportional = cache.measure_text("ubuntu", 20.0, "iiii").unwrap();
+        assert!(monospace.x > proportional.x);
+        assert_eq!(cache.measure_text("hack", 20.0, "a\nb").unwrap().y, cache.line_height("hack", 20.0).unwrap() * 2.0);
+    }
+
+    #[test]
+    fn test_least_recently_used_atlas_is_evicted() {
+        let mut cache = GlyphAtlasCache::with_capacity(2);
+        cache.add_font_data("ubuntu", epaint_default_fonts::UBUNTU_LIGHT.to_vec()).unwrap();
+        cache.atlas("ubuntu", 12.0);
+        cache.atlas("ubuntu", 16.0);
+        cache.atlas("ubuntu", 12.0);
+        cache.atlas("ubuntu", 32.0);
+        let mut sizes: Vec<u32> = cache.atlases().map(|(key, _)| key.size).collect();
+        sizes.sort();
+        assert_eq!(sizes, [12, 32]);
+    }
+
+    #[test]
+    fn test_sdf_atlas_is_shared_across_sizes() {
+        let mut cache = cache();
+        cache.set_render_mode(GlyphRenderMode::Sdf);
+        assert_eq!(cache.atlas_key("ubuntu", 12.0), cache.atlas_key("ubuntu", 96.0));
+
+        let atlas = cache.atlas("ubuntu", 96.0).unwrap();
+        assert_eq!(atlas.px_size(), SDF_BASE_SIZE);
+        let glyph = atlas.glyph('O').unwrap();
+        // Padded by the spread, with the edge encoded around 128
+        assert!(glyph.bearing.x < 0.0);
+        let pixels = atlas.pixels();
+        assert!(pixels.iter().any(|&p| p > 140) && pixels.iter().any(|&p| (100..156).contains(&p)));
+    }
+}
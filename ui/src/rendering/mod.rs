@@ -7,6 +7,7 @@ pub mod mask_system;
 pub mod nine_slice;
 pub mod batch_builder;
 pub mod text_renderer;
+pub mod glyph_atlas;
 
 #[cfg(feature = "rendering")]
 pub mod ui_renderer;
@@ -20,6 +21,9 @@ pub use batch_builder::{
 pub use text_renderer::{
     TextRenderer, TextLayout, PositionedGlyph, Font, FontCache, Glyph,
 };
+pub use glyph_atlas::{
+    GlyphAtlasCache, GlyphAtlas, AtlasGlyph, AtlasKey, GlyphRenderMode,
+};
 
 #[cfg(feature = "rendering")]
 pub use ui_renderer::UIRenderPass;
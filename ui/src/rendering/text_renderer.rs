@@ -3,8 +3,14 @@
 //! This module provides text rendering capabilities for the UI system,
 //! including font loading, text layout, and mesh generation.
 
+use super::glyph_atlas::GlyphAtlasCache;
 use crate::{UIText, TextAlignment, OverflowMode, Rect, Vec2};
-use std::collections::HashMap;
+use ab_glyph::{Font as _, PxScale, ScaleFont as _};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, HashMap};
+
+/// Size loaded fonts' layout metrics are stored at
+const LOADED_FONT_BASE_SIZE: f32 = 32.0;
 
 /// A simple glyph representation for bitmap fonts
 #[derive(Clone, Debug)]
@@ -71,6 +77,13 @@ impl Font {
 pub struct FontCache {
     fonts: HashMap<String, Font>,
     default_font: String,
+
+    /// Glyph atlases for fonts loaded from files
+    atlases: GlyphAtlasCache,
+
+    /// Fonts asked for but not loaded, not yet reported
+    missing: RefCell<BTreeSet<String>>,
+    reported: RefCell<BTreeSet<String>>,
 }
 
 impl FontCache {
@@ -79,6 +92,9 @@ impl FontCache {
         let mut cache = Self {
             fonts: HashMap::new(),
             default_font: String::from("default"),
+            atlases: GlyphAtlasCache::new(),
+            missing: RefCell::new(BTreeSet::new()),
+            reported: RefCell::new(BTreeSet::new()),
         };
         
         // Create a default font with basic ASCII characters
@@ -123,21 +139,80 @@ impl FontCache {
         self.fonts.insert("default".to_string(), font);
     }
     
-    /// Load a font (placeholder for future implementation)
-    pub fn load_font(&mut self, _name: &str, _path: &str) -> Result<(), String> {
-        // TODO: Implement actual font loading from file
+    /// Load a .ttf/.otf file. Its metrics drive layout and its glyphs are rasterized
+    /// into the atlas cache as they're drawn.
+    pub fn load_font(&mut self, name: &str, path: &str) -> Result<(), String> {
+        self.atlases.load_font(name, path)?;
+        let ttf = self.atlases.font(name).expect("font was just loaded");
+        let scaled = ttf.as_scaled(PxScale::from(LOADED_FONT_BASE_SIZE));
+
+        let mut font = Font::new(
+            name.to_string(),
+            self.atlases.atlas_key(name, LOADED_FONT_BASE_SIZE).texture_id(),
+            LOADED_FONT_BASE_SIZE,
+            scaled.height() + scaled.line_gap(),
+        );
+        for (id, character) in ttf.codepoint_ids() {
+            let outline = ttf.outline_glyph(id.with_scale_and_position(
+                PxScale::from(LOADED_FONT_BASE_SIZE),
+                ab_glyph::point(0.0, scaled.ascent()),
+            ));
+            let (bearing, size) = outline.map_or((Vec2::ZERO, Vec2::ZERO), |outline| {
+                let bounds = outline.px_bounds();
+                (Vec2::new(bounds.min.x, bounds.min.y), Vec2::new(bounds.width(), bounds.height()))
+            });
+            font.add_glyph(Glyph {
+                character,
+                uv_rect: Rect::new(0.0, 0.0, 0.0, 0.0),
+                advance: scaled.h_advance(id),
+                bearing,
+                size,
+            });
+        }
+        self.fonts.insert(name.to_string(), font);
         Ok(())
     }
     
-    /// Get a font by name
+    /// Get a font by name. Fonts that aren't loaded use the default font and are
+    /// reported once by `take_missing_warning`.
     pub fn get_font(&self, name: &str) -> Option<&Font> {
-        self.fonts.get(name).or_else(|| self.fonts.get(&self.default_font))
+        self.fonts.get(name).or_else(|| {
+            if name != self.default_font && !self.reported.borrow().contains(name) {
+                self.missing.borrow_mut().insert(name.to_string());
+            }
+            self.fonts.get(&self.default_font)
+        })
     }
     
     /// Add a font to the cache
     pub fn add_font(&mut self, font: Font) {
         self.fonts.insert(font.name.clone(), font);
     }
+
+    /// Font used in place of fonts that aren't loaded
+    pub fn set_default_font(&mut self, name: &str) {
+        self.default_font = name.to_string();
+        self.atlases.set_default_font(name);
+    }
+
+    /// Fonts that were missing since the last call, as one warning
+    pub fn take_missing_warning(&mut self) -> Option<String> {
+        let missing: Vec<String> = std::mem::take(self.missing.get_mut()).into_iter().collect();
+        if missing.is_empty() {
+            return None;
+        }
+        self.reported.get_mut().extend(missing.iter().cloned());
+        Some(format!("Font(s) not loaded, using '{}': {}", self.default_font, missing.join(", ")))
+    }
+
+    /// Glyph atlases of the loaded fonts
+    pub fn atlases(&self) -> &GlyphAtlasCache {
+        &self.atlases
+    }
+
+    pub fn atlases_mut(&mut self) -> &mut GlyphAtlasCache {
+        &mut self.atlases
+    }
 }
 
 impl Default for FontCache {
@@ -381,6 +456,7 @@ impl Default for TextRenderer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Color;
     
     #[test]
     fn test_font_cache_default() {
@@ -395,7 +471,7 @@ mod tests {
             text: "Hello".to_string(),
             font: "default".to_string(),
             font_size: 16.0,
-            color: [1.0, 1.0, 1.0, 1.0],
+            color: Color::rgba(1.0, 1.0, 1.0, 1.0),
             alignment: TextAlignment::TopLeft,
             horizontal_overflow: OverflowMode::Overflow,
             vertical_overflow: OverflowMode::Overflow,
@@ -404,6 +480,7 @@ mod tests {
             best_fit: false,
             best_fit_min_size: 10.0,
             best_fit_max_size: 40.0,
+            localization_key: None,
         };
         
         let bounds = Rect {
@@ -441,4 +518,29 @@ mod tests {
         assert!(truncated.ends_with("..."));
         assert!(truncated.len() < text.len());
     }
+    
+    #[test]
+    fn test_loaded_font_metrics_drive_wrapping() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("Hack-Regular.ttf");
+        std::fs::write(&path, epaint_default_fonts::HACK_REGULAR).unwrap();
+        
+        let mut renderer = TextRenderer::new();
+        renderer.font_cache_mut().load_font("hack", path.to_str().unwrap()).unwrap();
+        assert!(renderer.font_cache_mut().load_font("broken", "missing.ttf").is_err());
+        
+        // Hack at 32px is ~19px per character: "Hello World" needs more than 150px
+        let hack = renderer.font_cache.get_font("hack").unwrap();
+        assert!((hack.get_glyph('W').unwrap().advance - hack.get_glyph('i').unwrap().advance).abs() < 0.01);
+        assert!(renderer.wrap_text("Hello World", hack, 1.0, 150.0).contains('\n'));
+        let default = renderer.font_cache.get_font("default").unwrap();
+        assert!(!renderer.wrap_text("Hello World", default, 1.0, 150.0).contains('\n'));
+        
+        // Unknown fonts draw with the default, reported once
+        assert_eq!(renderer.font_cache.get_font("Title.ttf").unwrap().name, "default");
+        let warning = renderer.font_cache_mut().take_missing_warning().unwrap();
+        assert!(warning.contains("Title.ttf"));
+        renderer.font_cache.get_font("Title.ttf");
+        assert_eq!(renderer.font_cache_mut().take_missing_warning(), None);
+    }
 }
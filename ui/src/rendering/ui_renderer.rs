@@ -295,6 +295,7 @@ impl UIRenderPass {
             let index_count = batch.indices.len() as u32;
             if index_count > 0 {
                 render_pass.draw_indexed(index_offset..index_offset + index_count, 0, 0..1);
+                profiler::gpu::add_draw_call(index_count as usize / 3);
                 index_offset += index_count;
             }
         }
@@ -349,6 +349,7 @@ mod tests {
             rotation: 0.0,
             scale: Vec2::ONE,
             world_corners: [Vec2::ZERO; 4],
+            world_matrix: glam::Affine2::IDENTITY,
             rect: Rect::default(),
             dirty: false,
         }
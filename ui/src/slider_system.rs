@@ -207,6 +207,33 @@ impl SliderSystem {
         }
     }
     
+    /// Anchor a slider's fill child (see UISlider::fill_element) from the start of the
+    /// track to `normalized` along it
+    pub fn place_fill(fill: &mut RectTransform, direction: &SliderDirection, normalized: f32) {
+        let normalized = normalized.clamp(0.0, 1.0);
+        match direction {
+            SliderDirection::LeftToRight => (fill.anchor_min.x, fill.anchor_max.x) = (0.0, normalized),
+            SliderDirection::RightToLeft => (fill.anchor_min.x, fill.anchor_max.x) = (1.0 - normalized, 1.0),
+            // Anchors are y-up
+            SliderDirection::BottomToTop => (fill.anchor_min.y, fill.anchor_max.y) = (0.0, normalized),
+            SliderDirection::TopToBottom => (fill.anchor_min.y, fill.anchor_max.y) = (1.0 - normalized, 1.0),
+        }
+        fill.dirty = true;
+    }
+
+    /// Anchor a slider's handle child (see UISlider::handle_element) at `normalized`
+    /// along the track
+    pub fn place_handle(handle: &mut RectTransform, direction: &SliderDirection, normalized: f32) {
+        let normalized = normalized.clamp(0.0, 1.0);
+        match direction {
+            SliderDirection::LeftToRight => (handle.anchor_min.x, handle.anchor_max.x) = (normalized, normalized),
+            SliderDirection::RightToLeft => (handle.anchor_min.x, handle.anchor_max.x) = (1.0 - normalized, 1.0 - normalized),
+            SliderDirection::BottomToTop => (handle.anchor_min.y, handle.anchor_max.y) = (normalized, normalized),
+            SliderDirection::TopToBottom => (handle.anchor_min.y, handle.anchor_max.y) = (1.0 - normalized, 1.0 - normalized),
+        }
+        handle.dirty = true;
+    }
+
     /// Clamp slider value to min/max range
     pub fn clamp_slider_value(slider: &mut UISlider) {
         slider.value = slider.value.clamp(slider.min_value, slider.max_value);
@@ -262,6 +289,10 @@ mod tests {
             max_value: 100.0,
             value: 50.0,
             whole_numbers: false,
+            fill_element: None,
+            handle_element: None,
+            smoothing_time: 0.0,
+            transition: None,
             on_value_changed: None,
         }
     }
@@ -410,4 +441,30 @@ mod tests {
         
         assert!(!system.dragging_sliders.contains_key(&1));
     }
+
+    #[test]
+    fn test_place_fill_and_handle() {
+        let mut fill = RectTransform::default();
+        let mut handle = RectTransform::default();
+        SliderSystem::place_fill(&mut fill, &SliderDirection::LeftToRight, 0.25);
+        SliderSystem::place_handle(&mut handle, &SliderDirection::LeftToRight, 0.25);
+        assert_eq!((fill.anchor_min.x, fill.anchor_max.x), (0.0, 0.25));
+        assert_eq!((handle.anchor_min.x, handle.anchor_max.x), (0.25, 0.25));
+
+        SliderSystem::place_fill(&mut fill, &SliderDirection::TopToBottom, 2.0);
+        SliderSystem::place_handle(&mut handle, &SliderDirection::TopToBottom, 0.25);
+        assert_eq!((fill.anchor_min.y, fill.anchor_max.y), (0.0, 1.0));
+        assert_eq!((handle.anchor_min.y, handle.anchor_max.y), (0.75, 0.75));
+    }
+
+    #[test]
+    fn test_slider_without_visual_fields_keeps_defaults() {
+        let json = r#"{"fill_rect":null,"handle_rect":null,"direction":"LeftToRight","min_value":0.0,
+            "max_value":10.0,"value":5.0,"whole_numbers":false,"on_value_changed":null}"#;
+        let slider: UISlider = serde_json::from_str(json).unwrap();
+        assert_eq!(slider.fill_element, None);
+        assert_eq!(slider.smoothing_time, 0.0);
+        assert!(slider.transition.is_none());
+        assert_eq!(slider.normalized_value(), 0.5);
+    }
 }
@@ -3,7 +3,7 @@
 use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use glam::Vec4;
-use crate::Color;
+use crate::{Color, StateTransition};
 
 /// UI Style definition
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -17,6 +17,18 @@ pub struct UIStyle {
     pub background_color: Color,
     pub text_color: Color,
     pub disabled_color: Color,
+
+    /// Tints of toggles, sliders and dropdowns by interactable state (buttons use
+    /// their own); disabled ones use disabled_color
+    #[serde(default = "default_state_color")]
+    pub hover_color: Color,
+    #[serde(default = "default_state_color")]
+    pub pressed_color: Color,
+    #[serde(default = "default_state_color")]
+    pub selected_color: Color,
+    /// Seconds those tints take to fade from one state to the next
+    #[serde(default)]
+    pub state_fade_duration: f32,
     
     /// Fonts
     pub default_font: String,
@@ -43,11 +55,15 @@ impl Default for UIStyle {
     fn default() -> Self {
         Self {
             name: String::from("default"),
-            primary_color: [0.2, 0.4, 0.8, 1.0],
-            secondary_color: [0.6, 0.6, 0.6, 1.0],
-            background_color: [0.9, 0.9, 0.9, 1.0],
-            text_color: [0.0, 0.0, 0.0, 1.0],
-            disabled_color: [0.5, 0.5, 0.5, 0.5],
+            primary_color: Color::rgba(0.2, 0.4, 0.8, 1.0),
+            secondary_color: Color::rgba(0.6, 0.6, 0.6, 1.0),
+            background_color: Color::rgba(0.9, 0.9, 0.9, 1.0),
+            text_color: Color::rgba(0.0, 0.0, 0.0, 1.0),
+            disabled_color: Color::rgba(0.5, 0.5, 0.5, 0.5),
+            hover_color: default_state_color(),
+            pressed_color: default_state_color(),
+            selected_color: default_state_color(),
+            state_fade_duration: 0.0,
             default_font: String::from("default"),
             default_font_size: 14.0,
             button_sprite: None,
@@ -66,6 +82,25 @@ impl Default for UIStyle {
     }
 }
 
+fn default_state_color() -> Color {
+    Color::WHITE
+}
+
+impl UIStyle {
+    /// The style's state tints as a transition, for controls that don't set their own
+    pub fn state_transition(&self) -> StateTransition {
+        StateTransition {
+            normal_color: Color::WHITE,
+            hover_color: self.hover_color,
+            pressed_color: self.pressed_color,
+            selected_color: self.selected_color,
+            disabled_color: self.disabled_color,
+            fade_duration: self.state_fade_duration,
+            ..Default::default()
+        }
+    }
+}
+
 /// UI Theme (collection of styles)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct UITheme {
@@ -209,14 +244,14 @@ impl Default for StyleTransition {
             duration: 0.3,
             elapsed: 0.0,
             active: false,
-            from_primary: [0.0, 0.0, 0.0, 1.0],
-            from_secondary: [0.0, 0.0, 0.0, 1.0],
-            from_background: [0.0, 0.0, 0.0, 1.0],
-            from_text: [0.0, 0.0, 0.0, 1.0],
-            to_primary: [0.0, 0.0, 0.0, 1.0],
-            to_secondary: [0.0, 0.0, 0.0, 1.0],
-            to_background: [0.0, 0.0, 0.0, 1.0],
-            to_text: [0.0, 0.0, 0.0, 1.0],
+            from_primary: Color::rgba(0.0, 0.0, 0.0, 1.0),
+            from_secondary: Color::rgba(0.0, 0.0, 0.0, 1.0),
+            from_background: Color::rgba(0.0, 0.0, 0.0, 1.0),
+            from_text: Color::rgba(0.0, 0.0, 0.0, 1.0),
+            to_primary: Color::rgba(0.0, 0.0, 0.0, 1.0),
+            to_secondary: Color::rgba(0.0, 0.0, 0.0, 1.0),
+            to_background: Color::rgba(0.0, 0.0, 0.0, 1.0),
+            to_text: Color::rgba(0.0, 0.0, 0.0, 1.0),
         }
     }
 }
@@ -264,12 +299,7 @@ impl StyleTransition {
     
     /// Interpolate a color
     pub fn lerp_color(from: Color, to: Color, t: f32) -> Color {
-        [
-            from[0] + (to[0] - from[0]) * t,
-            from[1] + (to[1] - from[1]) * t,
-            from[2] + (to[2] - from[2]) * t,
-            from[3] + (to[3] - from[3]) * t,
-        ]
+        from.lerp(to, t)
     }
     
     /// Get the current interpolated colors
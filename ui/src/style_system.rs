@@ -254,22 +254,12 @@ impl StyleSystem {
     
     /// Helper: Darken a color by a factor
     fn darken_color(color: Color, factor: f32) -> Color {
-        [
-            color[0] * (1.0 - factor),
-            color[1] * (1.0 - factor),
-            color[2] * (1.0 - factor),
-            color[3],
-        ]
+        color.darken(factor)
     }
     
     /// Helper: Lighten a color by a factor
     fn lighten_color(color: Color, factor: f32) -> Color {
-        [
-            color[0] + (1.0 - color[0]) * factor,
-            color[1] + (1.0 - color[1]) * factor,
-            color[2] + (1.0 - color[2]) * factor,
-            color[3],
-        ]
+        color.lighten(factor)
     }
 }
 
@@ -371,8 +361,7 @@ mod tests {
         let mut transition = StyleTransition::default();
         
         let style1 = UIStyle::default();
-        let mut style2 = UIStyle::default();
-        style2.primary_color = [1.0, 0.0, 0.0, 1.0];
+        let style2 = UIStyle { primary_color: Color::rgba(1.0, 0.0, 0.0, 1.0), ..Default::default() };
         
         transition.start(&style1, &style2);
         assert!(transition.active);
@@ -392,8 +381,8 @@ mod tests {
     
     #[test]
     fn test_color_interpolation() {
-        let from = [0.0, 0.0, 0.0, 1.0];
-        let to = [1.0, 1.0, 1.0, 1.0];
+        let from = Color::rgba(0.0, 0.0, 0.0, 1.0);
+        let to = Color::rgba(1.0, 1.0, 1.0, 1.0);
         
         let mid = StyleTransition::lerp_color(from, to, 0.5);
         assert_eq!(mid, [0.5, 0.5, 0.5, 1.0]);
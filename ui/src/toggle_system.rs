@@ -2,7 +2,7 @@
 //!
 //! Handles toggle state changes and visual updates.
 
-use crate::{UIToggle, ToggleTransition, UIElement};
+use crate::{UIToggle, UIToggleGroup, ToggleGroupRef, ToggleTransition, UIElement};
 use crate::events::{UIEvent, UIEventType, UIEventDispatcher};
 use std::collections::HashMap;
 
@@ -29,14 +29,16 @@ impl ToggleSystem {
         }
     }
     
-    /// Update toggle states based on events
+    /// Update toggle states based on events. Change events (one per toggle outside a
+    /// group, one per group naming the newly active toggle) are dispatched and returned.
     pub fn update_from_events(
         &mut self,
         events: &[UIEvent],
         toggles: &mut HashMap<Entity, UIToggle>,
+        groups: &HashMap<Entity, UIToggleGroup>,
         elements: &HashMap<Entity, UIElement>,
         event_dispatcher: &UIEventDispatcher,
-    ) {
+    ) -> Vec<UIEvent> {
         for event in events {
             match event {
                 UIEvent::PointerClick(entity, _pos) => {
@@ -53,11 +55,83 @@ impl ToggleSystem {
             }
         }
         
-        // Apply pending changes
-        for (entity, new_state) in self.pending_changes.drain() {
-            if let Some(toggle) = toggles.get_mut(&entity) {
-                toggle.is_on = new_state;
-                // Note: In a real implementation, we would trigger the on_value_changed callback here
+        // Apply pending changes (sorted so several clicks in one frame resolve the same way)
+        let mut pending: Vec<_> = self.pending_changes.drain().collect();
+        pending.sort_by_key(|(entity, _)| *entity);
+        let changes: Vec<UIEvent> = pending
+            .into_iter()
+            .filter_map(|(entity, new_state)| Self::apply_state(entity, new_state, toggles, groups))
+            .collect();
+        event_dispatcher.dispatch_events(&changes);
+        changes
+    }
+
+    /// Set one toggle's state, enforcing its group: turning a member on turns the others
+    /// off, and turning the active member off is refused unless the group allows it
+    fn apply_state(
+        entity: Entity,
+        is_on: bool,
+        toggles: &mut HashMap<Entity, UIToggle>,
+        groups: &HashMap<Entity, UIToggleGroup>,
+    ) -> Option<UIEvent> {
+        let toggle = toggles.get_mut(&entity)?;
+        if toggle.is_on == is_on {
+            return None;
+        }
+        let Some(group) = toggle.group.as_ref().and_then(|group| Self::resolve_group(group, groups)) else {
+            toggle.is_on = is_on;
+            return Some(UIEvent::ToggleChanged(entity, is_on));
+        };
+
+        if !is_on {
+            if !groups[&group].allow_switch_off {
+                return None;
+            }
+            toggle.is_on = false;
+            return Some(UIEvent::ToggleGroupChanged(group, None));
+        }
+        for (member, toggle) in toggles.iter_mut() {
+            if toggle.group.as_ref().and_then(|g| Self::resolve_group(g, groups)) == Some(group) {
+                toggle.is_on = *member == entity;
+            }
+        }
+        Some(UIEvent::ToggleGroupChanged(group, Some(entity)))
+    }
+
+    /// Group entity a toggle refers to (None if the group doesn't exist)
+    pub fn resolve_group(group: &ToggleGroupRef, groups: &HashMap<Entity, UIToggleGroup>) -> Option<Entity> {
+        match group {
+            ToggleGroupRef::Entity(entity) => groups.contains_key(entity).then_some(*entity),
+            ToggleGroupRef::Id(id) => groups.iter().find(|(_, group)| group.id == *id).map(|(entity, _)| *entity),
+        }
+    }
+
+    /// Active member of a group
+    pub fn active_toggle(group: Entity, toggles: &HashMap<Entity, UIToggle>, groups: &HashMap<Entity, UIToggleGroup>) -> Option<Entity> {
+        toggles
+            .iter()
+            .filter(|(_, toggle)| toggle.is_on)
+            .find(|(_, toggle)| toggle.group.as_ref().and_then(|g| Self::resolve_group(g, groups)) == Some(group))
+            .map(|(entity, _)| *entity)
+    }
+
+    /// Fix up groups loaded from data: at most one member on, and exactly one when
+    /// switching off isn't allowed (the lowest entity wins)
+    pub fn ensure_valid_groups(toggles: &mut HashMap<Entity, UIToggle>, groups: &HashMap<Entity, UIToggleGroup>) {
+        for (&group_entity, group) in groups {
+            let mut members: Vec<Entity> = toggles
+                .iter()
+                .filter(|(_, toggle)| toggle.group.as_ref().and_then(|g| Self::resolve_group(g, groups)) == Some(group_entity))
+                .map(|(entity, _)| *entity)
+                .collect();
+            members.sort_unstable();
+
+            let active = members.iter().copied().find(|member| toggles[member].is_on);
+            let active = active.or_else(|| if group.allow_switch_off { None } else { members.first().copied() });
+            for member in members {
+                if let Some(toggle) = toggles.get_mut(&member) {
+                    toggle.is_on = Some(member) == active;
+                }
             }
         }
     }
@@ -77,7 +151,7 @@ impl ToggleSystem {
                             // Simply show/hide the graphic
                             graphic_element.alpha = if toggle.is_on { 1.0 } else { 0.0 };
                         }
-                        ToggleTransition::Fade => {
+                        ToggleTransition::Fade | ToggleTransition::Scale => {
                             // Set target alpha (actual fading would be handled by animation system)
                             graphic_element.alpha = if toggle.is_on { 1.0 } else { 0.0 };
                         }
@@ -87,32 +161,32 @@ impl ToggleSystem {
         }
     }
     
-    /// Set toggle state programmatically
+    /// Set toggle state programmatically (with the same group rules as clicks).
+    /// Returns the change event, if anything changed.
     pub fn set_toggle_state(
         entity: Entity,
         is_on: bool,
         toggles: &mut HashMap<Entity, UIToggle>,
+        groups: &HashMap<Entity, UIToggleGroup>,
         elements: &mut HashMap<Entity, UIElement>,
-    ) {
-        if let Some(toggle) = toggles.get_mut(&entity) {
-            toggle.is_on = is_on;
-        }
+    ) -> Option<UIEvent> {
+        let change = Self::apply_state(entity, is_on, toggles, groups);
         
         // Update visual state
         let system = ToggleSystem::new();
         system.update_visual_states(toggles, elements);
+        change
     }
     
     /// Toggle the state programmatically
     pub fn toggle_state(
         entity: Entity,
         toggles: &mut HashMap<Entity, UIToggle>,
+        groups: &HashMap<Entity, UIToggleGroup>,
         elements: &mut HashMap<Entity, UIElement>,
-    ) {
-        if let Some(toggle) = toggles.get(&entity) {
-            let new_state = !toggle.is_on;
-            Self::set_toggle_state(entity, new_state, toggles, elements);
-        }
+    ) -> Option<UIEvent> {
+        let new_state = !toggles.get(&entity)?.is_on;
+        Self::set_toggle_state(entity, new_state, toggles, groups, elements)
     }
     
     /// Check if a toggle is on
@@ -129,13 +203,18 @@ impl ToggleSystem {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Color;
 
     fn create_test_toggle() -> UIToggle {
         UIToggle {
             graphic: Some(2),
             is_on: false,
             toggle_transition: ToggleTransition::None,
+            checkmark: None,
+            checkmark_duration: 0.1,
+            transition: None,
             on_value_changed: None,
+            group: None,
         }
     }
 
@@ -144,10 +223,12 @@ mod tests {
             raycast_target: true,
             blocks_raycasts: true,
             z_order: 0,
-            color: [1.0, 1.0, 1.0, 1.0],
+            color: Color::rgba(1.0, 1.0, 1.0, 1.0),
             alpha: 1.0,
             interactable,
             ignore_layout: false,
+            draggable: false,
+            pass_through: false,
             canvas_entity: None,
         }
     }
@@ -165,7 +246,7 @@ mod tests {
         assert!(!toggles.get(&1).unwrap().is_on);
         
         let events = vec![UIEvent::PointerClick(1, glam::Vec2::ZERO)];
-        system.update_from_events(&events, &mut toggles, &elements, &dispatcher);
+        system.update_from_events(&events, &mut toggles, &HashMap::new(), &elements, &dispatcher);
         
         assert!(toggles.get(&1).unwrap().is_on);
     }
@@ -182,12 +263,12 @@ mod tests {
         
         // First click - turn on
         let events = vec![UIEvent::PointerClick(1, glam::Vec2::ZERO)];
-        system.update_from_events(&events, &mut toggles, &elements, &dispatcher);
+        system.update_from_events(&events, &mut toggles, &HashMap::new(), &elements, &dispatcher);
         assert!(toggles.get(&1).unwrap().is_on);
         
         // Second click - turn off
         let events = vec![UIEvent::PointerClick(1, glam::Vec2::ZERO)];
-        system.update_from_events(&events, &mut toggles, &elements, &dispatcher);
+        system.update_from_events(&events, &mut toggles, &HashMap::new(), &elements, &dispatcher);
         assert!(!toggles.get(&1).unwrap().is_on);
     }
 
@@ -202,7 +283,7 @@ mod tests {
         elements.insert(1, create_test_element(false)); // Not interactable
         
         let events = vec![UIEvent::PointerClick(1, glam::Vec2::ZERO)];
-        system.update_from_events(&events, &mut toggles, &elements, &dispatcher);
+        system.update_from_events(&events, &mut toggles, &HashMap::new(), &elements, &dispatcher);
         
         // State should not change
         assert!(!toggles.get(&1).unwrap().is_on);
@@ -216,7 +297,7 @@ mod tests {
         toggles.insert(1, create_test_toggle());
         elements.insert(2, create_test_element(true)); // Graphic element
         
-        ToggleSystem::set_toggle_state(1, true, &mut toggles, &mut elements);
+        ToggleSystem::set_toggle_state(1, true, &mut toggles, &HashMap::new(), &mut elements);
         
         assert!(toggles.get(&1).unwrap().is_on);
     }
@@ -230,11 +311,11 @@ mod tests {
         elements.insert(2, create_test_element(true));
         
         // Toggle on
-        ToggleSystem::toggle_state(1, &mut toggles, &mut elements);
+        ToggleSystem::toggle_state(1, &mut toggles, &HashMap::new(), &mut elements);
         assert!(toggles.get(&1).unwrap().is_on);
         
         // Toggle off
-        ToggleSystem::toggle_state(1, &mut toggles, &mut elements);
+        ToggleSystem::toggle_state(1, &mut toggles, &HashMap::new(), &mut elements);
         assert!(!toggles.get(&1).unwrap().is_on);
     }
 
@@ -298,4 +379,57 @@ mod tests {
         
         assert!(system.pending_changes.is_empty());
     }
+
+    #[test]
+    fn test_toggle_group_keeps_one_on() {
+        let mut system = ToggleSystem::new();
+        let mut toggles = HashMap::new();
+        let mut elements = HashMap::new();
+        let mut groups = HashMap::new();
+        let dispatcher = UIEventDispatcher::new();
+
+        groups.insert(10, UIToggleGroup { id: "quality".to_string(), ..Default::default() });
+        for entity in 1..=3 {
+            let group = if entity == 3 { ToggleGroupRef::Entity(10) } else { ToggleGroupRef::Id("quality".to_string()) };
+            toggles.insert(entity, UIToggle { group: Some(group), ..create_test_toggle() });
+            elements.insert(entity, create_test_element(true));
+        }
+
+        // Nothing on and switching off not allowed: the first member turns on
+        ToggleSystem::ensure_valid_groups(&mut toggles, &groups);
+        assert_eq!(ToggleSystem::active_toggle(10, &toggles, &groups), Some(1));
+
+        // Clicking another member moves the selection with a single group event
+        let events = vec![UIEvent::PointerClick(3, glam::Vec2::ZERO)];
+        let changes = system.update_from_events(&events, &mut toggles, &groups, &elements, &dispatcher);
+        assert!(matches!(changes.as_slice(), [UIEvent::ToggleGroupChanged(10, Some(3))]));
+        assert!(!toggles[&1].is_on && !toggles[&2].is_on && toggles[&3].is_on);
+
+        // Clicking the active member does nothing
+        let events = vec![UIEvent::PointerClick(3, glam::Vec2::ZERO)];
+        assert!(system.update_from_events(&events, &mut toggles, &groups, &elements, &dispatcher).is_empty());
+        assert!(toggles[&3].is_on);
+
+        // Programmatic changes follow the same rules
+        let change = ToggleSystem::set_toggle_state(2, true, &mut toggles, &groups, &mut elements);
+        assert!(matches!(change, Some(UIEvent::ToggleGroupChanged(10, Some(2)))));
+        assert!(ToggleSystem::set_toggle_state(2, false, &mut toggles, &groups, &mut elements).is_none());
+        assert_eq!(ToggleSystem::active_toggle(10, &toggles, &groups), Some(2));
+
+        groups.get_mut(&10).unwrap().allow_switch_off = true;
+        let change = ToggleSystem::set_toggle_state(2, false, &mut toggles, &groups, &mut elements);
+        assert!(matches!(change, Some(UIEvent::ToggleGroupChanged(10, None))));
+        assert_eq!(ToggleSystem::active_toggle(10, &toggles, &groups), None);
+    }
+
+    #[test]
+    fn test_toggle_group_ref_serialization() {
+        let by_id: UIToggle = serde_json::from_str(r#"{"graphic":null,"is_on":true,"toggle_transition":"None","on_value_changed":null,"group":"quality"}"#).unwrap();
+        assert_eq!(by_id.group, Some(ToggleGroupRef::Id("quality".to_string())));
+        let by_entity: UIToggle = serde_json::from_str(r#"{"graphic":null,"is_on":true,"toggle_transition":"None","on_value_changed":null,"group":10}"#).unwrap();
+        assert_eq!(by_entity.group, Some(ToggleGroupRef::Entity(10)));
+        // Older files without the field
+        let legacy: UIToggle = serde_json::from_str(r#"{"graphic":null,"is_on":true,"toggle_transition":"None","on_value_changed":null}"#).unwrap();
+        assert_eq!(legacy.group, None);
+    }
 }
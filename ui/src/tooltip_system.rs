@@ -0,0 +1,204 @@
+//! Tooltip system
+//!
+//! Tracks how long the pointer (or gamepad focus) has rested on elements with a
+//! UITooltip and decides when the tooltip shows and where it goes on screen.
+
+use crate::events::UIEvent;
+use crate::{TooltipPlacement, UITooltip};
+use glam::Vec2;
+use std::collections::HashMap;
+
+/// Entity type alias
+pub type Entity = u64;
+
+/// Element the pointer/focus is resting on
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ActiveTooltip {
+    pub entity: Entity,
+
+    /// Time of the HoverEnter event
+    pub hover_start: f32,
+}
+
+/// Tooltip dwell tracking. Needs HoverEnter/HoverExit events
+/// (`UIInputHandler::set_track_hover(true)`).
+#[derive(Default)]
+pub struct TooltipSystem {
+    /// Current dwell target (only elements with a tooltip)
+    hovered: Option<ActiveTooltip>,
+
+    /// Pressed since the hover started: stays hidden until the pointer leaves
+    dismissed: bool,
+}
+
+impl TooltipSystem {
+    /// Create a new tooltip system
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Update the dwell target from this frame's events
+    pub fn update_from_events(&mut self, events: &[UIEvent], tooltips: &HashMap<Entity, UITooltip>) {
+        for event in events {
+            match event {
+                UIEvent::HoverEnter(entity, time) if tooltips.contains_key(entity) => {
+                    self.hovered = Some(ActiveTooltip { entity: *entity, hover_start: *time });
+                    self.dismissed = false;
+                }
+                UIEvent::HoverExit(entity, _) if self.hovered.is_some_and(|hovered| hovered.entity == *entity) => {
+                    self.hovered = None;
+                    self.dismissed = false;
+                }
+                UIEvent::PointerDown(_, _) => {
+                    self.dismissed = self.hovered.is_some();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Element whose tooltip should be visible at `time`
+    pub fn visible_tooltip(&self, time: f32, tooltips: &HashMap<Entity, UITooltip>) -> Option<Entity> {
+        let hovered = self.hovered?;
+        let tooltip = tooltips.get(&hovered.entity)?;
+        (!self.dismissed && time - hovered.hover_start >= tooltip.show_delay).then_some(hovered.entity)
+    }
+
+    /// Current dwell target, shown or not
+    pub fn hovered(&self) -> Option<ActiveTooltip> {
+        self.hovered
+    }
+
+    /// Hide the tooltip until the pointer/focus moves to another element
+    pub fn dismiss(&mut self) {
+        self.dismissed = self.hovered.is_some();
+    }
+
+    /// Forget the dwell target (e.g. when the element is destroyed)
+    pub fn clear(&mut self) {
+        self.hovered = None;
+        self.dismissed = false;
+    }
+}
+
+/// Top-left corner for a tooltip of `size` next to `target` (`min`/`max` corners) or the
+/// cursor, flipped to the other side when it would leave `screen`, then clamped inside it.
+/// All coordinates are y-down screen space (same as pointer positions).
+pub fn place_tooltip(
+    size: Vec2,
+    target: (Vec2, Vec2),
+    cursor: Option<Vec2>,
+    screen: (Vec2, Vec2),
+    placement: TooltipPlacement,
+    offset: Vec2,
+) -> Vec2 {
+    let (target_min, target_max) = target;
+    let (screen_min, screen_max) = screen;
+    let target_center = (target_min + target_max) * 0.5;
+
+    // Preferred position, then the mirrored one on the same axis
+    let (preferred, flipped, horizontal) = match (placement, cursor) {
+        (TooltipPlacement::FollowCursor, Some(cursor)) => {
+            // Flip each axis independently (below-right -> above/left)
+            let mut position = cursor + offset;
+            if position.x + size.x > screen_max.x {
+                position.x = cursor.x - offset.x - size.x;
+            }
+            if position.y + size.y > screen_max.y {
+                position.y = cursor.y - offset.y - size.y;
+            }
+            return clamp_to_screen(position, size, screen_min, screen_max);
+        }
+        // Focus has no cursor: anchor below the element
+        (TooltipPlacement::FollowCursor, None) | (TooltipPlacement::Below, _) => (
+            Vec2::new(target_center.x - size.x * 0.5, target_max.y + offset.y),
+            Vec2::new(target_center.x - size.x * 0.5, target_min.y - offset.y - size.y),
+            false,
+        ),
+        (TooltipPlacement::Above, _) => (
+            Vec2::new(target_center.x - size.x * 0.5, target_min.y - offset.y - size.y),
+            Vec2::new(target_center.x - size.x * 0.5, target_max.y + offset.y),
+            false,
+        ),
+        (TooltipPlacement::Left, _) => (
+            Vec2::new(target_min.x - offset.x - size.x, target_center.y - size.y * 0.5),
+            Vec2::new(target_max.x + offset.x, target_center.y - size.y * 0.5),
+            true,
+        ),
+        (TooltipPlacement::Right, _) => (
+            Vec2::new(target_max.x + offset.x, target_center.y - size.y * 0.5),
+            Vec2::new(target_min.x - offset.x - size.x, target_center.y - size.y * 0.5),
+            true,
+        ),
+    };
+
+    let fits = |position: Vec2| {
+        if horizontal {
+            position.x >= screen_min.x && position.x + size.x <= screen_max.x
+        } else {
+            position.y >= screen_min.y && position.y + size.y <= screen_max.y
+        }
+    };
+    let position = if !fits(preferred) && fits(flipped) { flipped } else { preferred };
+    clamp_to_screen(position, size, screen_min, screen_max)
+}
+
+fn clamp_to_screen(position: Vec2, size: Vec2, screen_min: Vec2, screen_max: Vec2) -> Vec2 {
+    // Larger than the screen: keep the top-left corner visible
+    Vec2::new(
+        position.x.min(screen_max.x - size.x).max(screen_min.x),
+        position.y.min(screen_max.y - size.y).max(screen_min.y),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tooltip_shows_after_delay_and_hides_on_press() {
+        let mut system = TooltipSystem::new();
+        let mut tooltips = HashMap::new();
+        tooltips.insert(1, UITooltip { show_delay: 0.5, ..UITooltip::with_text("Heal") });
+
+        system.update_from_events(&[UIEvent::HoverEnter(2, 0.0)], &tooltips);
+        assert_eq!(system.hovered(), None);
+
+        system.update_from_events(&[UIEvent::HoverEnter(1, 1.0)], &tooltips);
+        assert_eq!(system.visible_tooltip(1.4, &tooltips), None);
+        assert_eq!(system.visible_tooltip(1.5, &tooltips), Some(1));
+
+        system.update_from_events(&[UIEvent::PointerDown(1, Vec2::ZERO)], &tooltips);
+        assert_eq!(system.visible_tooltip(3.0, &tooltips), None);
+
+        // Leaving and coming back restarts the dwell
+        system.update_from_events(&[UIEvent::HoverExit(1, 3.0), UIEvent::HoverEnter(1, 3.0)], &tooltips);
+        assert_eq!(system.visible_tooltip(3.2, &tooltips), None);
+        assert_eq!(system.visible_tooltip(3.6, &tooltips), Some(1));
+    }
+
+    #[test]
+    fn test_place_tooltip_flips_near_screen_edges() {
+        let screen = (Vec2::ZERO, Vec2::new(800.0, 600.0));
+        let size = Vec2::new(100.0, 40.0);
+        let offset = Vec2::new(10.0, 10.0);
+
+        // Room below-right of the cursor
+        let position = place_tooltip(size, (Vec2::ZERO, Vec2::ZERO), Some(Vec2::new(100.0, 100.0)), screen, TooltipPlacement::FollowCursor, offset);
+        assert_eq!(position, Vec2::new(110.0, 110.0));
+
+        // Bottom-right corner: flips above-left
+        let position = place_tooltip(size, (Vec2::ZERO, Vec2::ZERO), Some(Vec2::new(780.0, 590.0)), screen, TooltipPlacement::FollowCursor, offset);
+        assert_eq!(position, Vec2::new(670.0, 540.0));
+
+        // Element at the top: "Above" flips below it
+        let target = (Vec2::new(300.0, 0.0), Vec2::new(400.0, 30.0));
+        let position = place_tooltip(size, target, None, screen, TooltipPlacement::Above, offset);
+        assert_eq!(position, Vec2::new(300.0, 40.0));
+
+        // Element at the right edge: "Right" flips left, then stays inside vertically
+        let target = (Vec2::new(750.0, 580.0), Vec2::new(800.0, 600.0));
+        let position = place_tooltip(size, target, None, screen, TooltipPlacement::Right, offset);
+        assert_eq!(position, Vec2::new(640.0, 560.0));
+    }
+}
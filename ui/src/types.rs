@@ -3,8 +3,8 @@
 use serde::{Deserialize, Serialize};
 use glam::Vec2;
 
-/// RGBA color represented as [r, g, b, a] with values in range [0.0, 1.0]
-pub type Color = [f32; 4];
+/// RGBA color with values in range [0.0, 1.0] (shared with sprites, cameras and debug lines)
+pub use engine_core::Color;
 
 /// A rectangle defined by position and size
 #[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
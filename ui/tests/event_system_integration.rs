@@ -23,12 +23,14 @@ fn create_element(
     RaycastElement {
         entity,
         rect: Rect::new(x, y, width, height),
+        transform: glam::Affine2::IDENTITY,
         raycast_target: true,
         blocks_raycasts: true,
         z_order,
         canvas_sort_order: 0,
         visible: true,
         interactable: true,
+        draggable: false,
     }
 }
 
@@ -4,7 +4,7 @@ use ui::{
     Canvas, CanvasRenderMode, CanvasScaler, ScaleMode,
     RectTransform, UIElement, UIText, TextAlignment, OverflowMode,
     rendering::UIBatchBuilder,
-    Rect, Vec2,
+    Rect, Vec2, Color,
 };
 
 #[test]
@@ -52,6 +52,7 @@ fn test_text_rendering_basic() {
             width: 200.0,
             height: 100.0,
         },
+        world_matrix: glam::Affine2::IDENTITY,
         dirty: false,
     };
     
@@ -60,10 +61,12 @@ fn test_text_rendering_basic() {
         raycast_target: true,
         blocks_raycasts: true,
         z_order: 0,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: Color::rgba(1.0, 1.0, 1.0, 1.0),
         alpha: 1.0,
         interactable: true,
         ignore_layout: false,
+        draggable: false,
+        pass_through: false,
         canvas_entity: None,
     };
     
@@ -72,7 +75,7 @@ fn test_text_rendering_basic() {
         text: "Hello World".to_string(),
         font: "default".to_string(),
         font_size: 16.0,
-        color: [0.0, 0.0, 0.0, 1.0],
+        color: Color::rgba(0.0, 0.0, 0.0, 1.0),
         alignment: TextAlignment::MiddleCenter,
         horizontal_overflow: OverflowMode::Overflow,
         vertical_overflow: OverflowMode::Overflow,
@@ -81,6 +84,7 @@ fn test_text_rendering_basic() {
         best_fit: false,
         best_fit_min_size: 10.0,
         best_fit_max_size: 40.0,
+        localization_key: None,
     };
     
     // Collect the text element
@@ -148,6 +152,7 @@ fn test_text_with_wrapping() {
             width: 100.0,
             height: 100.0,
         },
+        world_matrix: glam::Affine2::IDENTITY,
         dirty: false,
     };
     
@@ -155,10 +160,12 @@ fn test_text_with_wrapping() {
         raycast_target: true,
         blocks_raycasts: true,
         z_order: 0,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: Color::rgba(1.0, 1.0, 1.0, 1.0),
         alpha: 1.0,
         interactable: true,
         ignore_layout: false,
+        draggable: false,
+        pass_through: false,
         canvas_entity: None,
     };
     
@@ -167,7 +174,7 @@ fn test_text_with_wrapping() {
         text: "This is a long text that should wrap to multiple lines".to_string(),
         font: "default".to_string(),
         font_size: 16.0,
-        color: [0.0, 0.0, 0.0, 1.0],
+        color: Color::rgba(0.0, 0.0, 0.0, 1.0),
         alignment: TextAlignment::TopLeft,
         horizontal_overflow: OverflowMode::Wrap,
         vertical_overflow: OverflowMode::Overflow,
@@ -176,6 +183,7 @@ fn test_text_with_wrapping() {
         best_fit: false,
         best_fit_min_size: 10.0,
         best_fit_max_size: 40.0,
+        localization_key: None,
     };
     
     builder.collect_element(1, &canvas, &rect, &element, None, Some(&text), None);
@@ -232,6 +240,7 @@ fn test_text_color_tint() {
             width: 200.0,
             height: 100.0,
         },
+        world_matrix: glam::Affine2::IDENTITY,
         dirty: false,
     };
     
@@ -240,10 +249,12 @@ fn test_text_color_tint() {
         raycast_target: true,
         blocks_raycasts: true,
         z_order: 0,
-        color: [1.0, 1.0, 1.0, 1.0],
+        color: Color::rgba(1.0, 1.0, 1.0, 1.0),
         alpha: 0.5,
         interactable: true,
         ignore_layout: false,
+        draggable: false,
+        pass_through: false,
         canvas_entity: None,
     };
     
@@ -251,7 +262,7 @@ fn test_text_color_tint() {
         text: "A".to_string(),
         font: "default".to_string(),
         font_size: 16.0,
-        color: [1.0, 0.0, 0.0, 1.0], // Red with full alpha
+        color: Color::rgba(1.0, 0.0, 0.0, 1.0), // Red with full alpha
         alignment: TextAlignment::MiddleCenter,
         horizontal_overflow: OverflowMode::Overflow,
         vertical_overflow: OverflowMode::Overflow,
@@ -260,6 +271,7 @@ fn test_text_color_tint() {
         best_fit: false,
         best_fit_min_size: 10.0,
         best_fit_max_size: 40.0,
+        localization_key: None,
     };
     
     builder.collect_element(1, &canvas, &rect, &element, None, Some(&text), None);